@@ -4,11 +4,14 @@ mod section;
 mod session;
 
 use file::DocFile;
+use rayexec_bigquery::BigQueryDataSource;
 use rayexec_csv::CsvDataSource;
 use rayexec_delta::DeltaDataSource;
 use rayexec_error::Result;
 use rayexec_execution::datasource::{DataSourceBuilder, DataSourceRegistry, MemoryDataSource};
 use rayexec_iceberg::IcebergDataSource;
+use rayexec_lance::LanceDataSource;
+use rayexec_orc::OrcDataSource;
 use rayexec_parquet::ParquetDataSource;
 use rayexec_postgres::PostgresDataSource;
 use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
@@ -38,9 +41,12 @@ fn main() -> Result<()> {
     let registry = DataSourceRegistry::default()
         .with_datasource("memory", Box::new(MemoryDataSource))?
         .with_datasource("postgres", PostgresDataSource::initialize(runtime.clone()))?
+        .with_datasource("bigquery", BigQueryDataSource::initialize(runtime.clone()))?
         .with_datasource("delta", DeltaDataSource::initialize(runtime.clone()))?
         .with_datasource("unity", UnityCatalogDataSource::initialize(runtime.clone()))?
         .with_datasource("parquet", ParquetDataSource::initialize(runtime.clone()))?
+        .with_datasource("orc", OrcDataSource::initialize(runtime.clone()))?
+        .with_datasource("lance", LanceDataSource::initialize(runtime.clone()))?
         .with_datasource("csv", CsvDataSource::initialize(runtime.clone()))?
         .with_datasource("iceberg", IcebergDataSource::initialize(runtime.clone()))?;
     let engine = SingleUserEngine::try_new(executor, runtime, registry)?;
@@ -8,6 +8,7 @@ use rayexec_execution::arrays::format::{FormatOptions, Formatter};
 const FORMATTER: Formatter = Formatter::new(FormatOptions {
     null: "",
     empty_string: "",
+    ..FormatOptions::new()
 });
 
 pub fn write_markdown_table<'a>(
@@ -91,9 +91,9 @@ impl<T: DataType> BasicColumnValueDecoder<T> {
         }
 
         let decoder = if encoding == Encoding::RLE_DICTIONARY {
-            self.decoders
-                .get_mut(&encoding)
-                .expect("Decoder for dict should have been set")
+            self.decoders.get_mut(&encoding).ok_or_else(|| {
+                general_err!("Missing dictionary page for dictionary encoded data")
+            })?
         } else {
             // Search cache for data page decoder
             match self.decoders.entry(encoding) {
@@ -198,9 +198,9 @@ impl<T: DataType> ColumnValueDecoder for BasicColumnValueDecoder<T> {
         }
 
         let decoder = if encoding == Encoding::RLE_DICTIONARY {
-            self.decoders
-                .get_mut(&encoding)
-                .expect("Decoder for dict should have been set")
+            self.decoders.get_mut(&encoding).ok_or_else(|| {
+                general_err!("Missing dictionary page for dictionary encoded data")
+            })?
         } else {
             // Search cache for data page decoder
             match self.decoders.entry(encoding) {
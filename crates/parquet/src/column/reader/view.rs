@@ -1,8 +1,10 @@
+use std::sync::Arc;
+
 use bytes::Bytes;
 
 use super::decoder::ColumnValueDecoder;
 use super::{ConvertedType, Encoding};
-use crate::decoding::view::{PlainViewDecoder, ViewBuffer, ViewDecoder};
+use crate::decoding::view::{ViewBuffer, ViewDecoder, ViewDictionary};
 use crate::errors::Result;
 use crate::schema::types::ColumnDescPtr;
 
@@ -13,8 +15,8 @@ use crate::schema::types::ColumnDescPtr;
 /// buffers).
 #[derive(Debug)]
 pub struct ViewColumnValueDecoder {
-    /// Optional deictionary.
-    dict: Option<ViewBuffer>,
+    /// Optional dictionary, shared with buffers holding keys into it.
+    dict: Option<Arc<ViewDictionary>>,
     /// Current decoder.
     decoder: Option<ViewDecoder>,
     /// If we should validate utf8.
@@ -55,16 +57,11 @@ impl ColumnValueDecoder for ViewColumnValueDecoder {
             ));
         }
 
-        let mut dict = ViewBuffer::new(num_values as usize);
-        PlainViewDecoder::new(
+        self.dict = Some(Arc::new(ViewDictionary::try_new(
             buf,
             num_values as usize,
-            Some(num_values as usize),
             self.validate_utf8,
-        )
-        .read(&mut dict, num_values as usize)?;
-
-        self.dict = Some(dict);
+        )?));
 
         Ok(())
     }
@@ -64,6 +64,16 @@ impl Int96 {
         seconds * 1_000 + nanoseconds / 1_000_000
     }
 
+    /// Converts this INT96 into an i64 representing the number of MICROSECONDS since EPOCH
+    ///
+    /// Will wrap around on overflow
+    pub fn to_micros(&self) -> i64 {
+        let (seconds, nanoseconds) = self.to_seconds_and_nanos();
+        seconds
+            .wrapping_mul(1_000_000)
+            .wrapping_add(nanoseconds / 1_000)
+    }
+
     /// Converts this INT96 into an i64 representing the number of NANOSECONDS since EPOCH
     ///
     /// Will wrap around on overflow
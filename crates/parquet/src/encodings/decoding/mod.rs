@@ -91,6 +91,9 @@ pub(crate) mod private {
         ) -> Result<Box<dyn Decoder<T>>> {
             match encoding {
                 Encoding::DELTA_BINARY_PACKED => Ok(Box::new(DeltaBitPackDecoder::new())),
+                Encoding::BYTE_STREAM_SPLIT => Ok(Box::new(
+                    byte_stream_split_decoder::ByteStreamSplitDecoder::new(),
+                )),
                 _ => get_decoder_default(descr, encoding),
             }
         }
@@ -103,6 +106,9 @@ pub(crate) mod private {
         ) -> Result<Box<dyn Decoder<T>>> {
             match encoding {
                 Encoding::DELTA_BINARY_PACKED => Ok(Box::new(DeltaBitPackDecoder::new())),
+                Encoding::BYTE_STREAM_SPLIT => Ok(Box::new(
+                    byte_stream_split_decoder::ByteStreamSplitDecoder::new(),
+                )),
                 _ => get_decoder_default(descr, encoding),
             }
         }
@@ -1797,6 +1803,18 @@ mod tests {
         test_byte_stream_split_decode::<f64>(data);
     }
 
+    #[test]
+    fn test_byte_stream_split_int32() {
+        let data = vec![vec![1, -2, i32::MAX], vec![i32::MIN, 0]];
+        test_byte_stream_split_decode::<i32>(data);
+    }
+
+    #[test]
+    fn test_byte_stream_split_int64() {
+        let data = vec![vec![1, -2, i64::MAX], vec![i64::MIN]];
+        test_byte_stream_split_decode::<i64>(data);
+    }
+
     #[test]
     fn test_skip_byte_stream_split() {
         let block_data = vec![0.3, 0.4, 0.1, 4.10];
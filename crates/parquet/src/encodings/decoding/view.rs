@@ -4,10 +4,14 @@
 //! for byte arrays. Note also that none of these implement the `Decoder` trait
 //! since that's hard to work with.
 
+use std::sync::Arc;
+
 use bytes::Bytes;
+use rayexec_execution::arrays::array::{ArrayData, BinaryData};
 use rayexec_execution::arrays::executor::builder::{ArrayDataBuffer, GermanVarlenBuffer};
+use rayexec_execution::arrays::storage::{GermanVarlenStorage, PrimitiveStorage, RawDeallocate};
 
-use super::Encoding;
+use super::{Decoder, DeltaBitPackDecoder, Encoding};
 use crate::encodings::rle::RleDecoder;
 use crate::errors::{ParquetError, Result};
 
@@ -20,6 +24,30 @@ pub struct ViewBuffer {
     /// The actual buffer, should be initialized to the max length we expect to
     /// read.
     buffer: GermanVarlenBuffer<[u8]>,
+    /// Keys into a dictionary, set if every value read so far came from the
+    /// same dictionary.
+    ///
+    /// Values are only copied into `buffer` once we read a value that's not
+    /// from this dictionary.
+    dict_keys: Option<DictionaryKeys>,
+}
+
+#[derive(Debug)]
+struct DictionaryKeys {
+    dict: Arc<ViewDictionary>,
+    keys: Vec<usize>,
+}
+
+/// Values read into a view buffer.
+#[derive(Debug)]
+pub enum ViewValues {
+    /// Values copied into a buffer.
+    Buffer(GermanVarlenBuffer<[u8]>),
+    /// Keys into a dictionary for every value.
+    Dictionary {
+        dict: Arc<ViewDictionary>,
+        keys: Vec<usize>,
+    },
 }
 
 impl ViewBuffer {
@@ -27,6 +55,7 @@ impl ViewBuffer {
         ViewBuffer {
             current_idx: 0,
             buffer: GermanVarlenBuffer::with_len(len),
+            dict_keys: None,
         }
     }
 
@@ -37,30 +66,176 @@ impl ViewBuffer {
             let _ = std::str::from_utf8(data)?;
         }
 
+        self.materialize_dict_keys();
+
         self.buffer.put(self.current_idx, data);
         self.current_idx += 1;
 
         Ok(())
     }
 
+    /// Push values from a dictionary.
+    ///
+    /// If every value in the buffer came from this dictionary, only the keys
+    /// are stored.
+    pub fn try_push_dict_keys(&mut self, dict: &Arc<ViewDictionary>, keys: &[i32]) -> Result<()> {
+        if let Some(&key) = keys
+            .iter()
+            .find(|&&key| key < 0 || key as usize >= dict.len())
+        {
+            return Err(general_err!("Missing dictionary value at index {}", key));
+        }
+
+        if self.current_idx == 0 && self.dict_keys.is_none() {
+            self.dict_keys = Some(DictionaryKeys {
+                dict: dict.clone(),
+                keys: Vec::with_capacity(self.buffer.len()),
+            });
+        }
+
+        match &mut self.dict_keys {
+            Some(existing) if Arc::ptr_eq(&existing.dict, dict) => {
+                existing.keys.extend(keys.iter().map(|&key| key as usize));
+                self.current_idx += keys.len();
+            }
+            _ => {
+                // Values from a different dictionary (or plain values) already
+                // in the buffer, copy the values.
+                self.materialize_dict_keys();
+                for &key in keys {
+                    self.buffer
+                        .put(self.current_idx, dict.get(key as usize).unwrap());
+                    self.current_idx += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn get(&self, idx: usize) -> Option<&[u8]> {
         if idx >= self.current_idx {
             return None;
         }
 
-        self.buffer.get(idx)
+        match &self.dict_keys {
+            Some(dict_keys) => dict_keys.dict.get(dict_keys.keys[idx]),
+            None => self.buffer.get(idx),
+        }
+    }
+
+    /// Get the values in the buffer, keeping them as keys into a dictionary if
+    /// possible.
+    pub fn into_values(mut self) -> ViewValues {
+        match self.dict_keys.take() {
+            Some(DictionaryKeys { dict, keys }) => ViewValues::Dictionary { dict, keys },
+            None => ViewValues::Buffer(self.into_buffer()),
+        }
     }
 
     pub fn into_buffer(mut self) -> GermanVarlenBuffer<[u8]> {
+        self.materialize_dict_keys();
         self.buffer.truncate(self.current_idx);
         self.buffer
     }
+
+    /// Reference values that point into `page` instead of copying them.
+    ///
+    /// Returns false if values already in the buffer prevent sharing, in which
+    /// case values are copied.
+    fn share_page(&mut self, page: &Arc<PrimitiveStorage<u8>>) -> bool {
+        if self.dict_keys.is_some() {
+            return false;
+        }
+        self.buffer.share_data(page.clone())
+    }
+
+    /// Copy dictionary values into the buffer if we're currently only storing
+    /// keys.
+    fn materialize_dict_keys(&mut self) {
+        if let Some(DictionaryKeys { dict, keys }) = self.dict_keys.take() {
+            for (idx, key) in keys.into_iter().enumerate() {
+                // Keys checked when pushed.
+                self.buffer.put(idx, dict.get(key).unwrap());
+            }
+        }
+    }
+}
+
+/// Keeps page data alive while view buffers reference it.
+#[allow(dead_code)]
+#[derive(Debug)]
+struct PageData(Bytes);
+
+impl RawDeallocate for PageData {}
+
+/// Wrap page data as storage that view buffers can reference without copying.
+fn page_storage(buf: &Bytes) -> Arc<PrimitiveStorage<u8>> {
+    Arc::new(PrimitiveStorage::Raw {
+        ptr: buf.as_ptr(),
+        len: buf.len(),
+        deallocate: Arc::new(PageData(buf.clone())),
+    })
+}
+
+/// A decoded dictionary page for a column chunk.
+///
+/// The dictionary is stored as array data so that arrays can be created by
+/// selecting from the dictionary instead of copying values.
+#[derive(Debug)]
+pub struct ViewDictionary {
+    /// Number of values in the dictionary.
+    len: usize,
+    /// Dictionary values, followed by an extra empty value that null rows can
+    /// point to.
+    storage: Arc<GermanVarlenStorage>,
+}
+
+impl ViewDictionary {
+    /// Decode a PLAIN encoded dictionary page.
+    pub fn try_new(buf: Bytes, num_values: usize, validate_utf8: bool) -> Result<Self> {
+        let mut values = ViewBuffer::new(num_values + 1);
+        PlainViewDecoder::new(buf, num_values, Some(num_values), validate_utf8)
+            .read(&mut values, num_values)?;
+
+        let len = values.current_idx;
+        values.try_push(&[], false)?;
+
+        Ok(ViewDictionary {
+            len,
+            storage: Arc::new(values.into_buffer().into_storage()),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Key to use for null values.
+    pub fn null_key(&self) -> usize {
+        self.len
+    }
+
+    pub fn get(&self, key: usize) -> Option<&[u8]> {
+        self.storage.get(key)
+    }
+
+    /// Get the array data for this dictionary, including the value for nulls.
+    pub fn array_data(&self) -> ArrayData {
+        ArrayData::Binary(BinaryData::German(self.storage.clone()))
+    }
 }
 
 #[derive(Debug)]
 pub enum ViewDecoder {
     Plain(PlainViewDecoder),
     Dictionary(DictionaryViewDecoder),
+    DeltaLength(DeltaLengthViewDecoder),
+    DeltaByteArray(DeltaByteArrayViewDecoder),
 }
 
 impl ViewDecoder {
@@ -71,29 +246,33 @@ impl ViewDecoder {
         num_values: Option<usize>,
         validate_utf8: bool,
     ) -> Result<Self> {
-        let decoder = match encoding {
-            Encoding::PLAIN => Self::Plain(PlainViewDecoder::new(
-                data,
-                num_levels,
-                num_values,
-                validate_utf8,
-            )),
-            Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY => {
-                Self::Dictionary(DictionaryViewDecoder::new(data, num_levels, num_values))
-            }
-            // Encoding::DELTA_LENGTH_BYTE_ARRAY => ByteArrayDecoder::DeltaLength(
-            //     ByteArrayDecoderDeltaLength::new(data, validate_utf8)?,
-            // ),
-            // Encoding::DELTA_BYTE_ARRAY => {
-            //     ByteArrayDecoder::DeltaByteArray(ByteArrayDecoderDelta::new(data, validate_utf8)?)
-            // }
-            _ => {
-                return Err(general_err!(
-                    "unsupported encoding for byte array: {}",
-                    encoding
-                ))
-            }
-        };
+        let decoder =
+            match encoding {
+                Encoding::PLAIN => Self::Plain(PlainViewDecoder::new(
+                    data,
+                    num_levels,
+                    num_values,
+                    validate_utf8,
+                )),
+                Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY => {
+                    Self::Dictionary(DictionaryViewDecoder::new(data, num_levels, num_values))
+                }
+                Encoding::DELTA_LENGTH_BYTE_ARRAY => Self::DeltaLength(
+                    DeltaLengthViewDecoder::new(data, num_levels, num_values, validate_utf8)?,
+                ),
+                Encoding::DELTA_BYTE_ARRAY => Self::DeltaByteArray(DeltaByteArrayViewDecoder::new(
+                    data,
+                    num_levels,
+                    num_values,
+                    validate_utf8,
+                )?),
+                _ => {
+                    return Err(general_err!(
+                        "unsupported encoding for byte array: {}",
+                        encoding
+                    ))
+                }
+            };
 
         Ok(decoder)
     }
@@ -102,7 +281,7 @@ impl ViewDecoder {
         &mut self,
         buffer: &mut ViewBuffer,
         num_values: usize,
-        dict: Option<&ViewBuffer>,
+        dict: Option<&Arc<ViewDictionary>>,
     ) -> Result<usize> {
         match self {
             Self::Plain(d) => d.read(buffer, num_values),
@@ -112,10 +291,12 @@ impl ViewDecoder {
 
                 d.read(buffer, dict, num_values)
             }
+            Self::DeltaLength(d) => d.read(buffer, num_values),
+            Self::DeltaByteArray(d) => d.read(buffer, num_values),
         }
     }
 
-    pub fn skip(&mut self, num_values: usize, dict: Option<&ViewBuffer>) -> Result<usize> {
+    pub fn skip(&mut self, num_values: usize, dict: Option<&Arc<ViewDictionary>>) -> Result<usize> {
         match self {
             Self::Plain(d) => d.skip(num_values),
             Self::Dictionary(d) => {
@@ -124,6 +305,8 @@ impl ViewDecoder {
 
                 d.skip(dict, num_values)
             }
+            Self::DeltaLength(d) => d.skip(num_values),
+            Self::DeltaByteArray(d) => d.skip(num_values),
         }
     }
 }
@@ -133,6 +316,8 @@ impl ViewDecoder {
 pub struct PlainViewDecoder {
     /// Currently set page data.
     buf: Bytes,
+    /// Page data shared with the buffers we read into.
+    page: Arc<PrimitiveStorage<u8>>,
     /// Current offset into data.
     offset: usize,
     /// This is a maximum as the null count is not always known, e.g. value data
@@ -150,6 +335,7 @@ impl PlainViewDecoder {
         validate_utf8: bool,
     ) -> Self {
         PlainViewDecoder {
+            page: page_storage(&buf),
             buf,
             offset: 0,
             max_remaining_values: num_values.unwrap_or(num_levels),
@@ -170,7 +356,9 @@ impl PlainViewDecoder {
             .map(|x| x / self.max_remaining_values)
             .unwrap_or_default();
 
-        buffer.buffer.reserve_data(estimated_bytes);
+        if !buffer.share_page(&self.page) {
+            buffer.buffer.reserve_data(estimated_bytes);
+        }
 
         let mut num_read = 0;
         let buf = &self.buf;
@@ -217,6 +405,218 @@ impl PlainViewDecoder {
     }
 }
 
+/// Decode all values from DELTA_BINARY_PACKED encoded lengths at the start of
+/// `data`, returning the lengths and the offset to the data following them.
+fn decode_lengths(
+    data: &Bytes,
+    num_levels: usize,
+    num_values: Option<usize>,
+) -> Result<(Vec<i32>, usize)> {
+    let mut decoder = DeltaBitPackDecoder::<i32>::new();
+    decoder.set_data(data.clone(), num_values.unwrap_or(num_levels))?;
+
+    let mut lengths = vec![0; decoder.values_left()];
+    decoder.read(&mut lengths)?;
+
+    if let Some(len) = lengths.iter().find(|&&len| len < 0) {
+        return Err(general_err!("invalid negative length: {}", len));
+    }
+
+    Ok((lengths, decoder.get_offset()))
+}
+
+/// Decoder for DELTA_LENGTH_BYTE_ARRAY.
+///
+/// Lengths for all values in the page are decoded up front, followed by the
+/// concatenated value bytes.
+#[derive(Debug)]
+pub struct DeltaLengthViewDecoder {
+    /// Lengths for every value in the page.
+    lengths: Vec<i32>,
+    /// Index of the next length to use.
+    length_idx: usize,
+    /// Concatenated value bytes.
+    buf: Bytes,
+    /// Value bytes shared with the buffers we read into.
+    page: Arc<PrimitiveStorage<u8>>,
+    /// Current offset into the value bytes.
+    offset: usize,
+    /// If we should validate utf8.
+    validate_utf8: bool,
+}
+
+impl DeltaLengthViewDecoder {
+    pub fn new(
+        data: Bytes,
+        num_levels: usize,
+        num_values: Option<usize>,
+        validate_utf8: bool,
+    ) -> Result<Self> {
+        let (lengths, offset) = decode_lengths(&data, num_levels, num_values)?;
+        let buf = data.slice(offset..);
+
+        let total: usize = lengths.iter().map(|&len| len as usize).sum();
+        if total > buf.len() {
+            return Err(ParquetError::EOF(format!(
+                "eof decoding delta length byte array, expected {total} bytes, got {}",
+                buf.len()
+            )));
+        }
+
+        Ok(DeltaLengthViewDecoder {
+            lengths,
+            length_idx: 0,
+            page: page_storage(&buf),
+            buf,
+            offset: 0,
+            validate_utf8,
+        })
+    }
+
+    fn values_left(&self) -> usize {
+        self.lengths.len() - self.length_idx
+    }
+
+    /// Get the next value, returning None if there are no values left.
+    fn next_value(&mut self) -> Option<&[u8]> {
+        let len = *self.lengths.get(self.length_idx)? as usize;
+        let start = self.offset;
+
+        self.length_idx += 1;
+        self.offset += len;
+
+        Some(&self.buf[start..self.offset])
+    }
+
+    pub fn read(&mut self, buffer: &mut ViewBuffer, num_vals: usize) -> Result<usize> {
+        let to_read = usize::min(num_vals, self.values_left());
+        let validate_utf8 = self.validate_utf8;
+        buffer.share_page(&self.page);
+
+        for _ in 0..to_read {
+            let data = self.next_value().expect("value to exist");
+            buffer.try_push(data, validate_utf8)?;
+        }
+
+        Ok(to_read)
+    }
+
+    pub fn skip(&mut self, num_vals: usize) -> Result<usize> {
+        let to_skip = usize::min(num_vals, self.values_left());
+
+        let end = self.length_idx + to_skip;
+        let skip_bytes: usize = self.lengths[self.length_idx..end]
+            .iter()
+            .map(|&len| len as usize)
+            .sum();
+
+        self.length_idx = end;
+        self.offset += skip_bytes;
+
+        Ok(to_skip)
+    }
+}
+
+/// Decoder for DELTA_BYTE_ARRAY.
+///
+/// Each value is stored as the length of the prefix it shares with the
+/// previous value, followed by a DELTA_LENGTH_BYTE_ARRAY encoded suffix.
+#[derive(Debug)]
+pub struct DeltaByteArrayViewDecoder {
+    /// Prefix lengths for every value in the page.
+    prefix_lengths: Vec<i32>,
+    /// Index of the next prefix length to use.
+    prefix_idx: usize,
+    /// Decoder for the suffixes.
+    suffixes: DeltaLengthViewDecoder,
+    /// The last decoded value.
+    ///
+    /// Values depend on the previous value, so skipping still requires
+    /// reconstructing each value.
+    previous: Vec<u8>,
+    /// If we should validate utf8.
+    validate_utf8: bool,
+}
+
+impl DeltaByteArrayViewDecoder {
+    pub fn new(
+        data: Bytes,
+        num_levels: usize,
+        num_values: Option<usize>,
+        validate_utf8: bool,
+    ) -> Result<Self> {
+        let (prefix_lengths, offset) = decode_lengths(&data, num_levels, num_values)?;
+
+        // Suffixes validated after reconstructing the full value since a
+        // suffix may begin in the middle of a multi-byte character.
+        let suffixes =
+            DeltaLengthViewDecoder::new(data.slice(offset..), num_levels, num_values, false)?;
+
+        if suffixes.values_left() != prefix_lengths.len() {
+            return Err(general_err!(
+                "mismatched number of prefixes ({}) and suffixes ({}) in delta byte array",
+                prefix_lengths.len(),
+                suffixes.values_left()
+            ));
+        }
+
+        Ok(DeltaByteArrayViewDecoder {
+            prefix_lengths,
+            prefix_idx: 0,
+            suffixes,
+            previous: Vec::new(),
+            validate_utf8,
+        })
+    }
+
+    fn values_left(&self) -> usize {
+        self.prefix_lengths.len() - self.prefix_idx
+    }
+
+    /// Decode the next value into `previous`.
+    fn decode_next(&mut self) -> Result<()> {
+        let prefix_len = self.prefix_lengths[self.prefix_idx] as usize;
+        if prefix_len > self.previous.len() {
+            return Err(general_err!(
+                "prefix length {prefix_len} longer than previous value of length {}",
+                self.previous.len()
+            ));
+        }
+        self.prefix_idx += 1;
+
+        let suffix = self
+            .suffixes
+            .next_value()
+            .ok_or_else(|| general_err!("missing suffix in delta byte array"))?;
+
+        self.previous.truncate(prefix_len);
+        self.previous.extend_from_slice(suffix);
+
+        Ok(())
+    }
+
+    pub fn read(&mut self, buffer: &mut ViewBuffer, num_vals: usize) -> Result<usize> {
+        let to_read = usize::min(num_vals, self.values_left());
+
+        for _ in 0..to_read {
+            self.decode_next()?;
+            buffer.try_push(&self.previous, self.validate_utf8)?;
+        }
+
+        Ok(to_read)
+    }
+
+    pub fn skip(&mut self, num_vals: usize) -> Result<usize> {
+        let to_skip = usize::min(num_vals, self.values_left());
+
+        for _ in 0..to_skip {
+            self.decode_next()?;
+        }
+
+        Ok(to_skip)
+    }
+}
+
 /// Decoder for PLAIN_DICTIONARY/RLE_DICTIONARY.
 #[derive(Debug)]
 pub struct DictionaryViewDecoder {
@@ -233,28 +633,16 @@ impl DictionaryViewDecoder {
     pub fn read(
         &mut self,
         buffer: &mut ViewBuffer,
-        dict: &ViewBuffer,
+        dict: &Arc<ViewDictionary>,
         num_vals: usize,
     ) -> Result<usize> {
-        // TODO: What would be _real_ cool is if `dict` was an array and we just
-        // created selection vectors on top of the dictionary (data is behind an
-        // arc).
-
-        self.decoder.read(num_vals, |keys| {
-            for &key in keys {
-                let val = dict
-                    .get(key as usize)
-                    .ok_or_else(|| general_err!("Missing dictionary value at index {key}"))?;
-                // If we're pushing from a dictionary, we don't need to validate
-                // that the bytes are utf8 since that happens when we create the
-                // values in the dictionary.
-                buffer.try_push(val, false)?;
-            }
-            Ok(())
-        })
+        // Values don't need to be validated as utf8 since that happens when
+        // decoding the dictionary.
+        self.decoder
+            .read(num_vals, |keys| buffer.try_push_dict_keys(dict, keys))
     }
 
-    pub fn skip(&mut self, _dict: &ViewBuffer, num_vals: usize) -> Result<usize> {
+    pub fn skip(&mut self, _dict: &ViewDictionary, num_vals: usize) -> Result<usize> {
         self.decoder.skip(num_vals)
     }
 }
@@ -354,3 +742,214 @@ impl DictIndexDecoder {
         Ok(values_skip)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::ByteArray;
+    use crate::encoding::get_encoder;
+
+    fn encode(encoding: Encoding, values: &[&str]) -> Bytes {
+        let values: Vec<ByteArray> = values
+            .iter()
+            .map(|v| ByteArray::from(v.as_bytes().to_vec()))
+            .collect();
+
+        let mut encoder = get_encoder::<ByteArray>(encoding).unwrap();
+        encoder.put(&values).unwrap();
+        encoder.flush_buffer().unwrap()
+    }
+
+    fn decode_all(
+        encoding: Encoding,
+        values: &[&str],
+        skip: usize,
+        chunk_size: usize,
+    ) -> Vec<String> {
+        let data = encode(encoding, values);
+        let mut decoder = ViewDecoder::new(encoding, data, values.len(), None, true).unwrap();
+        let mut buffer = ViewBuffer::new(values.len());
+
+        assert_eq!(skip, decoder.skip(skip, None).unwrap());
+        while decoder.read(&mut buffer, chunk_size, None).unwrap() > 0 {}
+
+        (0..values.len() - skip)
+            .map(|idx| String::from_utf8(buffer.get(idx).unwrap().to_vec()).unwrap())
+            .collect()
+    }
+
+    const VALUES: &[&str] = &[
+        "apple",
+        "applesauce",
+        "apply",
+        "",
+        "banana",
+        "band",
+        "bandana",
+        "\u{1f600} smile",
+        "\u{1f600} smiley",
+    ];
+
+    #[test]
+    fn delta_length_byte_array() {
+        let encoding = Encoding::DELTA_LENGTH_BYTE_ARRAY;
+        assert_eq!(VALUES, decode_all(encoding, VALUES, 0, 2));
+        assert_eq!(VALUES[3..], decode_all(encoding, VALUES, 3, 4));
+        assert_eq!(Vec::<String>::new(), decode_all(encoding, VALUES, 9, 4));
+    }
+
+    #[test]
+    fn delta_byte_array() {
+        let encoding = Encoding::DELTA_BYTE_ARRAY;
+        assert_eq!(VALUES, decode_all(encoding, VALUES, 0, 2));
+        assert_eq!(VALUES[3..], decode_all(encoding, VALUES, 3, 4));
+        assert_eq!(VALUES[8..], decode_all(encoding, VALUES, 8, 1));
+    }
+
+    #[test]
+    fn delta_length_byte_array_truncated() {
+        let data = encode(Encoding::DELTA_LENGTH_BYTE_ARRAY, VALUES);
+        let truncated = data.slice(..data.len() - 1);
+        DeltaLengthViewDecoder::new(truncated, VALUES.len(), None, false).unwrap_err();
+    }
+
+    fn dictionary() -> Arc<ViewDictionary> {
+        let data = encode(Encoding::PLAIN, VALUES);
+        Arc::new(ViewDictionary::try_new(data, VALUES.len(), true).unwrap())
+    }
+
+    fn buffer_values(buffer: &ViewBuffer, len: usize) -> Vec<&str> {
+        (0..len)
+            .map(|idx| std::str::from_utf8(buffer.get(idx).unwrap()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn dictionary_keys_not_copied() {
+        let dict = dictionary();
+        let mut buffer = ViewBuffer::new(4);
+        buffer.try_push_dict_keys(&dict, &[4, 0]).unwrap();
+        buffer.try_push_dict_keys(&dict, &[4]).unwrap();
+
+        assert_eq!(vec!["banana", "apple", "banana"], buffer_values(&buffer, 3));
+        match buffer.into_values() {
+            ViewValues::Dictionary { dict: got, keys } => {
+                assert!(Arc::ptr_eq(&dict, &got));
+                assert_eq!(vec![4, 0, 4], keys);
+            }
+            other => panic!("unexpected values: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn dictionary_keys_copied_on_plain_value() {
+        let dict = dictionary();
+        let mut buffer = ViewBuffer::new(4);
+        buffer.try_push_dict_keys(&dict, &[1, 2]).unwrap();
+        buffer.try_push(b"plain", true).unwrap();
+        buffer.try_push_dict_keys(&dict, &[5]).unwrap();
+
+        assert_eq!(
+            vec!["applesauce", "apply", "plain", "band"],
+            buffer_values(&buffer, 4)
+        );
+        assert!(matches!(buffer.into_values(), ViewValues::Buffer(_)));
+    }
+
+    #[test]
+    fn dictionary_keys_copied_on_new_dictionary() {
+        let mut buffer = ViewBuffer::new(2);
+        buffer.try_push_dict_keys(&dictionary(), &[0]).unwrap();
+        buffer.try_push_dict_keys(&dictionary(), &[4]).unwrap();
+
+        assert_eq!(vec!["apple", "banana"], buffer_values(&buffer, 2));
+        assert!(matches!(buffer.into_values(), ViewValues::Buffer(_)));
+    }
+
+    #[test]
+    fn dictionary_key_out_of_bounds() {
+        let mut buffer = ViewBuffer::new(1);
+        buffer
+            .try_push_dict_keys(&dictionary(), &[VALUES.len() as i32])
+            .unwrap_err();
+    }
+
+    fn page_values() -> Vec<String> {
+        (0..4)
+            .map(|idx| format!("a value that's too long to inline {idx}"))
+            .collect()
+    }
+
+    #[test]
+    fn plain_values_share_page() {
+        let values = page_values();
+        let values: Vec<_> = values.iter().map(|v| v.as_str()).collect();
+        let data = encode(Encoding::PLAIN, &values);
+        let mut decoder =
+            ViewDecoder::new(Encoding::PLAIN, data, values.len(), None, true).unwrap();
+
+        let mut first = ViewBuffer::new(2);
+        let mut second = ViewBuffer::new(2);
+        assert_eq!(2, decoder.read(&mut first, 2, None).unwrap());
+        assert_eq!(2, decoder.read(&mut second, 2, None).unwrap());
+
+        assert_eq!(values[..2], buffer_values(&first, 2));
+        assert_eq!(values[2..], buffer_values(&second, 2));
+
+        let first = first.into_buffer().into_storage();
+        let second = second.into_buffer().into_storage();
+        assert!(Arc::ptr_eq(first.data(), second.data()));
+    }
+
+    #[test]
+    fn delta_length_values_share_page() {
+        let values = page_values();
+        let values: Vec<_> = values.iter().map(|v| v.as_str()).collect();
+        let data = encode(Encoding::DELTA_LENGTH_BYTE_ARRAY, &values);
+        let mut decoder = ViewDecoder::new(
+            Encoding::DELTA_LENGTH_BYTE_ARRAY,
+            data,
+            values.len(),
+            None,
+            true,
+        )
+        .unwrap();
+
+        let mut first = ViewBuffer::new(2);
+        let mut second = ViewBuffer::new(2);
+        decoder.read(&mut first, 2, None).unwrap();
+        decoder.read(&mut second, 2, None).unwrap();
+
+        let first = first.into_buffer().into_storage();
+        let second = second.into_buffer().into_storage();
+        assert!(Arc::ptr_eq(first.data(), second.data()));
+    }
+
+    #[test]
+    fn values_from_multiple_pages_copied() {
+        let values = page_values();
+        let values: Vec<_> = values.iter().map(|v| v.as_str()).collect();
+        let mut first_page = ViewDecoder::new(
+            Encoding::PLAIN,
+            encode(Encoding::PLAIN, &values[..2]),
+            2,
+            None,
+            true,
+        )
+        .unwrap();
+        let mut second_page = ViewDecoder::new(
+            Encoding::PLAIN,
+            encode(Encoding::PLAIN, &values[2..]),
+            2,
+            None,
+            true,
+        )
+        .unwrap();
+
+        let mut buffer = ViewBuffer::new(4);
+        first_page.read(&mut buffer, 2, None).unwrap();
+        second_page.read(&mut buffer, 2, None).unwrap();
+
+        assert_eq!(values, buffer_values(&buffer, 4));
+    }
+}
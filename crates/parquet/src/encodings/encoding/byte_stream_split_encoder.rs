@@ -56,8 +56,8 @@ impl<T: DataType> Encoder<T> for ByteStreamSplitEncoder<T> {
         self.buffer
             .extend(<T as DataType>::T::slice_as_bytes(values));
         ensure_phys_ty!(
-            Type::FLOAT | Type::DOUBLE,
-            "ByteStreamSplitEncoder only supports f32 or f64"
+            Type::FLOAT | Type::DOUBLE | Type::INT32 | Type::INT64,
+            "ByteStreamSplitEncoder only supports f32, f64, i32, or i64"
         );
 
         Ok(())
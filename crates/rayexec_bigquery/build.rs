@@ -0,0 +1,12 @@
+fn main() {
+    if let Err(e) = tonic_build::configure()
+        .build_server(false)
+        .compile_protos(&["proto/storage.proto"], &["proto"])
+    {
+        // Printing out the error here instead of returning it so that we print
+        // out the Display impl of the error which is easier to read (properly
+        // formatted newlines).
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,317 @@
+//! Decoding arrow record batches from the Storage Read API.
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type,
+    Decimal128Type,
+    Float64Type,
+    Int64Type,
+    TimestampMicrosecondType,
+};
+use arrow_array::{Array as ArrowArray, ArrowPrimitiveType};
+use arrow_buffer::Buffer;
+use arrow_ipc::reader::StreamDecoder;
+use rayexec_error::{not_implemented, RayexecError, Result, ResultExt};
+use rayexec_execution::arrays::array::{Array, ArrayData};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::bitmap::Bitmap;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::field::Field;
+use rayexec_execution::arrays::storage::{BooleanStorage, GermanVarlenStorage, PrimitiveStorage};
+
+/// Decodes serialized record batches for a single read stream.
+///
+/// The session's schema is sent once, and every record batch is an IPC
+/// message without the schema.
+pub struct ArrowBatchDecoder {
+    decoder: StreamDecoder,
+    /// Fields to read out of the record batches, in output order.
+    fields: Vec<Field>,
+}
+
+impl std::fmt::Debug for ArrowBatchDecoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArrowBatchDecoder")
+            .field("fields", &self.fields)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ArrowBatchDecoder {
+    pub fn try_new(serialized_schema: &[u8], fields: Vec<Field>) -> Result<Self> {
+        let mut decoder = StreamDecoder::new();
+        let mut buf = Buffer::from_slice_ref(serialized_schema);
+        if decoder
+            .decode(&mut buf)
+            .context("Failed to decode arrow schema")?
+            .is_some()
+        {
+            return Err(RayexecError::new("Expected only a schema message"));
+        }
+
+        Ok(ArrowBatchDecoder { decoder, fields })
+    }
+
+    pub fn decode(&mut self, serialized_batch: Vec<u8>) -> Result<Batch> {
+        let mut buf = Buffer::from_vec(serialized_batch);
+        let record_batch = self
+            .decoder
+            .decode(&mut buf)
+            .context("Failed to decode arrow record batch")?
+            .ok_or_else(|| RayexecError::new("Incomplete arrow record batch"))?;
+
+        if self.fields.is_empty() {
+            return Ok(Batch::empty_with_num_rows(record_batch.num_rows()));
+        }
+
+        let arrays = self
+            .fields
+            .iter()
+            .map(|field| {
+                let column = record_batch.column_by_name(&field.name).ok_or_else(|| {
+                    RayexecError::new(format!("Missing column '{}' in record batch", field.name))
+                })?;
+                convert_array(column.as_ref(), &field.datatype)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Batch::try_new(arrays)
+    }
+}
+
+/// Convert an arrow array to an array with the given data type.
+fn convert_array(array: &dyn ArrowArray, datatype: &DataType) -> Result<Array> {
+    let validity = array
+        .logical_nulls()
+        .map(|nulls| Bitmap::from_iter(nulls.iter()));
+
+    let data: ArrayData = match datatype {
+        DataType::Boolean => {
+            let values = try_as_boolean(array)?.values();
+            BooleanStorage::from(Bitmap::from_iter(values.iter())).into()
+        }
+        DataType::Int64 => convert_primitive::<Int64Type>(array)?.into(),
+        DataType::Float64 => convert_primitive::<Float64Type>(array)?.into(),
+        DataType::Decimal128(_) => convert_primitive::<Decimal128Type>(array)?.into(),
+        DataType::Date32 => convert_primitive::<Date32Type>(array)?.into(),
+        DataType::Timestamp(_) => convert_primitive::<TimestampMicrosecondType>(array)?.into(),
+        DataType::Utf8 | DataType::Json => {
+            let strings = array
+                .as_string_opt::<i32>()
+                .ok_or_else(|| unexpected_type_err(array, datatype))?;
+            convert_varlen(strings.len(), |idx| strings.value(idx).as_bytes())?.into()
+        }
+        DataType::Binary => {
+            let binary = array
+                .as_binary_opt::<i32>()
+                .ok_or_else(|| unexpected_type_err(array, datatype))?;
+            convert_varlen(binary.len(), |idx| binary.value(idx))?.into()
+        }
+        other => not_implemented!("Convert arrow array to {other}"),
+    };
+
+    Ok(match validity {
+        Some(validity) => Array::new_with_validity_and_array_data(datatype.clone(), validity, data),
+        None => Array::new_with_array_data(datatype.clone(), data),
+    })
+}
+
+fn try_as_boolean(array: &dyn ArrowArray) -> Result<&arrow_array::BooleanArray> {
+    array
+        .as_boolean_opt()
+        .ok_or_else(|| unexpected_type_err(array, &DataType::Boolean))
+}
+
+fn convert_primitive<T: ArrowPrimitiveType>(
+    array: &dyn ArrowArray,
+) -> Result<PrimitiveStorage<T::Native>> {
+    let primitives = array.as_primitive_opt::<T>().ok_or_else(|| {
+        RayexecError::new(format!(
+            "Unexpected arrow type {}, expected {}",
+            array.data_type(),
+            T::DATA_TYPE
+        ))
+    })?;
+    Ok(primitives.values().to_vec().into())
+}
+
+fn convert_varlen<'a>(
+    len: usize,
+    value: impl Fn(usize) -> &'a [u8],
+) -> Result<GermanVarlenStorage> {
+    let mut storage = GermanVarlenStorage::with_metadata_capacity(len);
+    for idx in 0..len {
+        storage.try_push(value(idx))?;
+    }
+    Ok(storage)
+}
+
+fn unexpected_type_err(array: &dyn ArrowArray, datatype: &DataType) -> RayexecError {
+    RayexecError::new(format!(
+        "Unexpected arrow type {} for column of type {datatype}",
+        array.data_type()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::{
+        BooleanArray,
+        Decimal128Array,
+        Int64Array,
+        RecordBatch,
+        StringArray,
+        TimestampMicrosecondArray,
+    };
+    use arrow_ipc::writer::{write_message, DictionaryTracker, IpcDataGenerator, IpcWriteOptions};
+    use arrow_schema::{DataType as ArrowDataType, Field as ArrowField, Schema as ArrowSchema};
+    use rayexec_execution::arrays::datatype::{DecimalTypeMeta, TimeUnit, TimestampTypeMeta};
+    use rayexec_execution::arrays::scalar::ScalarValue;
+    use rayexec_execution::arrays::testutil::assert_batches_eq;
+
+    use super::*;
+
+    /// Serialize a record batch the same way the Storage Read API does, with
+    /// the schema and batch as separate messages.
+    fn serialize(batch: &RecordBatch) -> (Vec<u8>, Vec<u8>) {
+        let options = IpcWriteOptions::default();
+        let generator = IpcDataGenerator::default();
+        let mut tracker = DictionaryTracker::new(false);
+
+        let mut schema_buf = Vec::new();
+        let encoded = generator.schema_to_bytes_with_dictionary_tracker(
+            &batch.schema(),
+            &mut tracker,
+            &options,
+        );
+        write_message(&mut schema_buf, encoded, &options).unwrap();
+
+        let mut batch_buf = Vec::new();
+        let (_, encoded) = generator
+            .encoded_batch(batch, &mut tracker, &options)
+            .unwrap();
+        write_message(&mut batch_buf, encoded, &options).unwrap();
+
+        (schema_buf, batch_buf)
+    }
+
+    fn test_record_batch() -> RecordBatch {
+        let schema = ArrowSchema::new(vec![
+            ArrowField::new("id", ArrowDataType::Int64, false),
+            ArrowField::new("name", ArrowDataType::Utf8, true),
+            ArrowField::new("active", ArrowDataType::Boolean, true),
+            ArrowField::new("amount", ArrowDataType::Decimal128(38, 9), true),
+            ArrowField::new(
+                "created",
+                ArrowDataType::Timestamp(arrow_schema::TimeUnit::Microsecond, Some("UTC".into())),
+                true,
+            ),
+        ]);
+
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    None,
+                    Some("a string longer than twelve bytes"),
+                ])),
+                Arc::new(BooleanArray::from(vec![Some(true), Some(false), None])),
+                Arc::new(
+                    Decimal128Array::from(vec![Some(1_500_000_000), None, Some(-2)])
+                        .with_precision_and_scale(38, 9)
+                        .unwrap(),
+                ),
+                Arc::new(
+                    TimestampMicrosecondArray::from(vec![Some(0), Some(1), None])
+                        .with_timezone("UTC"),
+                ),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn decode_all_columns() {
+        let (schema, batch) = serialize(&test_record_batch());
+
+        let timestamp = DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond));
+        let decimal = DataType::Decimal128(DecimalTypeMeta::new(38, 9));
+        let fields = vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("active", DataType::Boolean, true),
+            Field::new("amount", decimal.clone(), true),
+            Field::new("created", timestamp.clone(), true),
+        ];
+
+        let mut decoder = ArrowBatchDecoder::try_new(&schema, fields).unwrap();
+        let got = decoder.decode(batch).unwrap();
+
+        let expected = Batch::try_new([
+            Array::from_iter([1_i64, 2, 3]),
+            Array::from_iter([Some("a"), None, Some("a string longer than twelve bytes")]),
+            Array::from_iter([Some(true), Some(false), None]),
+            Array::from_iter([Some(1_500_000_000_i128), None, Some(-2)])
+                .try_with_datatype(decimal)
+                .unwrap(),
+            Array::from_iter([Some(0_i64), Some(1), None])
+                .try_with_datatype(timestamp)
+                .unwrap(),
+        ])
+        .unwrap();
+
+        assert_batches_eq(&expected, &got);
+    }
+
+    #[test]
+    fn decode_projected_columns() {
+        let record_batch = test_record_batch();
+        let (schema, batch) = serialize(&record_batch);
+
+        // Output order follows the requested fields, not the record batch.
+        let fields = vec![
+            Field::new("active", DataType::Boolean, true),
+            Field::new("id", DataType::Int64, false),
+        ];
+
+        let mut decoder = ArrowBatchDecoder::try_new(&schema, fields).unwrap();
+        let got = decoder.decode(batch.clone()).unwrap();
+
+        assert_eq!(3, got.num_rows());
+        assert_eq!(
+            ScalarValue::Boolean(false),
+            got.column(0).unwrap().logical_value(1).unwrap()
+        );
+        assert_eq!(
+            ScalarValue::Int64(2),
+            got.column(1).unwrap().logical_value(1).unwrap()
+        );
+
+        // Multiple batches can be decoded with the same decoder.
+        let got = decoder.decode(batch).unwrap();
+        assert_eq!(3, got.num_rows());
+    }
+
+    #[test]
+    fn decode_no_columns() {
+        let (schema, batch) = serialize(&test_record_batch());
+
+        let mut decoder = ArrowBatchDecoder::try_new(&schema, Vec::new()).unwrap();
+        let got = decoder.decode(batch).unwrap();
+
+        assert_eq!(3, got.num_rows());
+        assert_eq!(0, got.num_columns());
+    }
+
+    #[test]
+    fn decode_type_mismatch() {
+        let (schema, batch) = serialize(&test_record_batch());
+
+        let fields = vec![Field::new("name", DataType::Int64, true)];
+        let mut decoder = ArrowBatchDecoder::try_new(&schema, fields).unwrap();
+        decoder.decode(batch).unwrap_err();
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use futures::lock::Mutex;
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::field::Field;
+use rayexec_execution::runtime::stall::maybe_with_stall_timeout;
+use rayexec_execution::storage::table_storage::{
+    DataTable,
+    DataTableScan,
+    Projections,
+    TableStatistics,
+};
+use rayexec_io::http::HttpClient;
+use tokio::runtime::Handle;
+
+use crate::arrow::ArrowBatchDecoder;
+use crate::rest::BigQueryRestClient;
+use crate::storage::{ReadSessionInfo, StorageReadClient};
+
+/// Fully qualified reference to a BigQuery table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableReference {
+    pub project: String,
+    pub dataset: String,
+    pub table: String,
+}
+
+impl TableReference {
+    /// Resource name used by the Storage Read API.
+    pub fn resource_name(&self) -> String {
+        format!(
+            "projects/{}/datasets/{}/tables/{}",
+            self.project, self.dataset, self.table
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct BigQueryDataTable<C: HttpClient> {
+    pub(crate) rest: BigQueryRestClient<C>,
+    pub(crate) storage: StorageReadClient<C>,
+    pub(crate) handle: Handle,
+    pub(crate) stall_timeout: Option<Duration>,
+    /// Project billed for reads.
+    pub(crate) billing_project: String,
+    pub(crate) table: TableReference,
+    pub(crate) columns: Vec<Field>,
+}
+
+impl<C: HttpClient + 'static> DataTable for BigQueryDataTable<C> {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        // Only request the columns we need, BigQuery bills by bytes read.
+        let fields: Vec<_> = match &projections.column_indices {
+            Some(indices) => indices
+                .iter()
+                .map(|&idx| self.columns[idx].clone())
+                .collect(),
+            None => self.columns.clone(),
+        };
+
+        // Selecting no fields reads all of them, so read the first column if
+        // we only need row counts.
+        let selected_fields = match (fields.is_empty(), self.columns.first()) {
+            (true, Some(first)) => vec![first.name.clone()],
+            _ => fields.iter().map(|f| f.name.clone()).collect(),
+        };
+
+        let session = Arc::new(SharedReadSession {
+            storage: self.storage.clone(),
+            billing_project: self.billing_project.clone(),
+            table: self.table.resource_name(),
+            selected_fields,
+            fields,
+            num_partitions,
+            session: Mutex::new(None),
+        });
+
+        let scans = (0..num_partitions)
+            .map(|partition_idx| {
+                let stream = partition_stream(session.clone(), partition_idx, batch_size);
+                Box::new(BigQueryTableScan {
+                    stream: maybe_with_stall_timeout(
+                        stream,
+                        self.stall_timeout,
+                        Some(self.handle.clone()),
+                    ),
+                }) as _
+            })
+            .collect();
+
+        Ok(scans)
+    }
+
+    fn statistics(&self) -> BoxFuture<'_, Result<TableStatistics>> {
+        Box::pin(async {
+            let table = self
+                .rest
+                .get_table(&self.table.project, &self.table.dataset, &self.table.table)
+                .await?
+                .ok_or_else(|| RayexecError::new("Missing table"))?;
+            Ok(table.statistics())
+        })
+    }
+}
+
+/// Read session shared by all partitions of a scan.
+///
+/// The session is created by whichever partition is pulled first, with one
+/// stream requested per partition.
+#[derive(Debug)]
+struct SharedReadSession<C: HttpClient> {
+    storage: StorageReadClient<C>,
+    billing_project: String,
+    table: String,
+    /// Column names requested from BigQuery.
+    selected_fields: Vec<String>,
+    /// Fields being output, in output order.
+    fields: Vec<Field>,
+    num_partitions: usize,
+    /// Created session, or the error from trying to create it.
+    session: Mutex<Option<Result<Arc<ReadSessionInfo>, String>>>,
+}
+
+impl<C: HttpClient + 'static> SharedReadSession<C> {
+    async fn get_or_create(&self) -> Result<Arc<ReadSessionInfo>> {
+        let mut session = self.session.lock().await;
+        if session.is_none() {
+            let created = self
+                .storage
+                .create_read_session(
+                    &self.billing_project,
+                    &self.table,
+                    self.selected_fields.clone(),
+                    self.num_partitions,
+                )
+                .await;
+            *session = Some(created.map(Arc::new).map_err(|e| e.to_string()));
+        }
+
+        match session.as_ref().unwrap() {
+            Ok(session) => Ok(session.clone()),
+            Err(e) => Err(RayexecError::new(e.clone())),
+        }
+    }
+}
+
+/// Create the stream of batches for a single partition.
+///
+/// Partitions read every `num_partitions`-th stream in the session.
+fn partition_stream<C: HttpClient + 'static>(
+    session: Arc<SharedReadSession<C>>,
+    partition_idx: usize,
+    batch_size: usize,
+) -> BoxStream<'static, Result<Batch>> {
+    let open = async move {
+        let info = session.get_or_create().await?;
+
+        let streams: Vec<_> = info
+            .streams
+            .iter()
+            .skip(partition_idx)
+            .step_by(session.num_partitions)
+            .cloned()
+            .collect();
+
+        let mut decoder =
+            ArrowBatchDecoder::try_new(&info.serialized_schema, session.fields.clone())?;
+        let storage = session.storage.clone();
+
+        let batches = stream::iter(streams)
+            .flat_map(move |name| storage.read_rows(name))
+            .map(move |serialized| decoder.decode(serialized?))
+            .flat_map(move |batch| stream::iter(split_batch(batch, batch_size)));
+
+        Ok::<_, RayexecError>(batches)
+    };
+
+    stream::once(open)
+        .map(|result| match result {
+            Ok(batches) => batches.boxed(),
+            Err(e) => stream::once(async { Err(e) }).boxed(),
+        })
+        .flatten()
+        .boxed()
+}
+
+/// Split a batch into batches of at most `batch_size` rows.
+fn split_batch(batch: Result<Batch>, batch_size: usize) -> Vec<Result<Batch>> {
+    let batch = match batch {
+        Ok(batch) => batch,
+        Err(e) => return vec![Err(e)],
+    };
+
+    let num_rows = batch.num_rows();
+    if num_rows <= batch_size || batch_size == 0 {
+        return vec![Ok(batch)];
+    }
+
+    (0..num_rows)
+        .step_by(batch_size)
+        .map(|offset| Ok(batch.slice(offset, batch_size.min(num_rows - offset))))
+        .collect()
+}
+
+pub struct BigQueryTableScan {
+    stream: BoxStream<'static, Result<Batch>>,
+}
+
+impl DataTableScan for BigQueryTableScan {
+    fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
+        Box::pin(async { self.stream.next().await.transpose() })
+    }
+}
+
+impl fmt::Debug for BigQueryTableScan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BigQueryTableScan").finish()
+    }
+}
@@ -0,0 +1,215 @@
+pub mod arrow;
+pub mod auth;
+pub mod datatable;
+pub mod read_bigquery;
+pub mod rest;
+pub mod storage;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use auth::{ServiceAccountKey, TokenProvider};
+use datatable::{BigQueryDataTable, TableReference};
+use futures::future::BoxFuture;
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::field::Field;
+use rayexec_execution::arrays::scalar::OwnedScalarValue;
+use rayexec_execution::database::catalog::CatalogTx;
+use rayexec_execution::database::catalog_entry::{CatalogEntry, TableEntry};
+use rayexec_execution::database::memory_catalog::MemoryCatalog;
+use rayexec_execution::datasource::{
+    check_options_empty,
+    take_option,
+    DataSource,
+    DataSourceBuilder,
+    DataSourceConnection,
+};
+use rayexec_execution::functions::table::TableFunction;
+use rayexec_execution::runtime::{Runtime, TokioHandlerProvider};
+use rayexec_execution::storage::catalog_storage::CatalogStorage;
+use rayexec_execution::storage::table_storage::{DataTable, TableStorage};
+use read_bigquery::ReadBigQuery;
+use rest::BigQueryRestClient;
+use storage::StorageReadClient;
+use tokio::runtime::Handle;
+
+/// Option containing the JSON key for a service account.
+pub const SERVICE_ACCOUNT_KEY_OPTION_KEY: &str = "service_account_key";
+
+/// Option for the project to read from. Defaults to the project of the
+/// service account.
+pub const PROJECT_ID_OPTION_KEY: &str = "project_id";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigQueryDataSource<R: Runtime> {
+    runtime: R,
+}
+
+impl<R: Runtime> DataSourceBuilder<R> for BigQueryDataSource<R> {
+    fn initialize(runtime: R) -> Box<dyn DataSource> {
+        Box::new(BigQueryDataSource { runtime })
+    }
+}
+
+impl<R: Runtime> DataSource for BigQueryDataSource<R> {
+    fn connect(
+        &self,
+        mut options: HashMap<String, OwnedScalarValue>,
+    ) -> BoxFuture<'_, Result<DataSourceConnection>> {
+        let runtime = self.runtime.clone();
+        Box::pin(async move {
+            let key =
+                take_option(SERVICE_ACCOUNT_KEY_OPTION_KEY, &mut options)?.try_into_string()?;
+            let project = match options.remove(PROJECT_ID_OPTION_KEY) {
+                Some(project) => Some(project.try_into_string()?),
+                None => None,
+            };
+            check_options_empty(&options)?;
+
+            let connection =
+                Arc::new(BigQueryConnection::connect(runtime, project.as_deref(), &key).await?);
+
+            Ok(DataSourceConnection {
+                catalog_storage: Some(connection.clone()),
+                table_storage: connection,
+            })
+        })
+    }
+
+    fn initialize_table_functions(&self) -> Vec<Box<dyn TableFunction>> {
+        vec![Box::new(ReadBigQuery {
+            runtime: self.runtime.clone(),
+        })]
+    }
+}
+
+/// Connection to a single BigQuery project.
+///
+/// Datasets in the project are exposed as schemas.
+#[derive(Debug)]
+pub struct BigQueryConnection<R: Runtime> {
+    project: String,
+    rest: BigQueryRestClient<R::HttpClient>,
+    storage: StorageReadClient<R::HttpClient>,
+    handle: Handle,
+    stall_timeout: Option<Duration>,
+}
+
+impl<R: Runtime> BigQueryConnection<R> {
+    async fn connect(runtime: R, project: Option<&str>, key: &str) -> Result<Self> {
+        let key = ServiceAccountKey::from_json(key)?;
+        let project = match project {
+            Some(project) => project.to_string(),
+            None => key.project_id.clone().ok_or_else(|| {
+                RayexecError::new(format!(
+                    "Missing '{PROJECT_ID_OPTION_KEY}', and service account key has no project"
+                ))
+            })?,
+        };
+
+        let tokens = TokenProvider::new(runtime.http_client(), key);
+        // Check the credentials are valid.
+        let _ = tokens.access_token().await?;
+
+        let handle = runtime.tokio_handle().handle()?;
+        let storage = StorageReadClient::try_new(tokens.clone(), handle.clone())?;
+
+        Ok(BigQueryConnection {
+            project,
+            rest: BigQueryRestClient::new(runtime.http_client(), tokens),
+            storage,
+            handle,
+            stall_timeout: runtime.scan_stall().timeout,
+        })
+    }
+
+    fn bigquery_table(
+        &self,
+        dataset: &str,
+        table: &str,
+        columns: Vec<Field>,
+    ) -> BigQueryDataTable<R::HttpClient> {
+        BigQueryDataTable {
+            rest: self.rest.clone(),
+            storage: self.storage.clone(),
+            handle: self.handle.clone(),
+            stall_timeout: self.stall_timeout,
+            billing_project: self.project.clone(),
+            table: TableReference {
+                project: self.project.clone(),
+                dataset: dataset.to_string(),
+                table: table.to_string(),
+            },
+            columns,
+        }
+    }
+}
+
+impl<R: Runtime> CatalogStorage for BigQueryConnection<R> {
+    fn initial_load(&self, _catalog: &MemoryCatalog) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn persist(&self, _catalog: &MemoryCatalog) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn load_table(&self, schema: &str, name: &str) -> BoxFuture<'_, Result<Option<TableEntry>>> {
+        let schema = schema.to_string();
+        let name = name.to_string();
+
+        Box::pin(async move {
+            let table = match self.rest.get_table(&self.project, &schema, &name).await? {
+                Some(table) => table,
+                None => return Ok(None),
+            };
+
+            Ok(Some(TableEntry::new(table.fields()?)))
+        })
+    }
+
+    fn list_tables(&self) -> BoxFuture<'_, Result<Vec<(String, String)>>> {
+        Box::pin(async move {
+            let mut tables = Vec::new();
+            for dataset in self.rest.list_datasets(&self.project).await? {
+                for table in self.rest.list_tables(&self.project, &dataset).await? {
+                    tables.push((dataset.clone(), table));
+                }
+            }
+            Ok(tables)
+        })
+    }
+}
+
+impl<R: Runtime> TableStorage for BigQueryConnection<R> {
+    fn data_table(
+        &self,
+        _tx: &CatalogTx,
+        schema: &str,
+        ent: &CatalogEntry,
+    ) -> Result<Box<dyn DataTable>> {
+        let columns = ent.try_as_table_entry()?.columns.clone();
+        Ok(Box::new(self.bigquery_table(schema, &ent.name, columns)))
+    }
+
+    fn create_physical_table(
+        &self,
+        _schema: &str,
+        _ent: &CatalogEntry,
+    ) -> BoxFuture<'_, Result<Box<dyn DataTable>>> {
+        Box::pin(async {
+            Err(RayexecError::new(
+                "Create physical table unsupported (bigquery)",
+            ))
+        })
+    }
+
+    fn drop_physical_table(&self, _schema: &str, _ent: &CatalogEntry) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async {
+            Err(RayexecError::new(
+                "Drop physical table unsupported (bigquery)",
+            ))
+        })
+    }
+}
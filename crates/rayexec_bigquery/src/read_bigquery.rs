@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::datatype::DataTypeId;
+use rayexec_execution::arrays::field::Schema;
+use rayexec_execution::arrays::scalar::OwnedScalarValue;
+use rayexec_execution::database::DatabaseContext;
+use rayexec_execution::datasource::{check_options_empty, take_option};
+use rayexec_execution::expr;
+use rayexec_execution::functions::documentation::{Category, Documentation};
+use rayexec_execution::functions::table::{
+    PlannedTableFunction,
+    ScanPlanner,
+    TableFunction,
+    TableFunctionImpl,
+    TableFunctionPlanner,
+};
+use rayexec_execution::functions::{FunctionInfo, Signature};
+use rayexec_execution::runtime::Runtime;
+use rayexec_execution::storage::table_storage::DataTable;
+
+use crate::{BigQueryConnection, SERVICE_ACCOUNT_KEY_OPTION_KEY};
+
+/// Read a single BigQuery table using the Storage Read API.
+///
+/// Requires a `service_account_key` argument, usually provided through a
+/// `gcp` secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadBigQuery<R: Runtime> {
+    pub(crate) runtime: R,
+}
+
+impl<R: Runtime> FunctionInfo for ReadBigQuery<R> {
+    fn name(&self) -> &'static str {
+        "read_bigquery"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        const DOC: &Documentation = &Documentation {
+            category: Category::Table,
+            description: "Read a table from BigQuery using a service account key.",
+            arguments: &["project", "dataset", "table"],
+            example: None,
+        };
+
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(DOC),
+        }]
+    }
+}
+
+impl<R: Runtime> TableFunction for ReadBigQuery<R> {
+    fn planner(&self) -> TableFunctionPlanner {
+        TableFunctionPlanner::Scan(self)
+    }
+}
+
+impl<R: Runtime> ScanPlanner for ReadBigQuery<R> {
+    fn plan<'a>(
+        &self,
+        context: &'a DatabaseContext,
+        positional_inputs: Vec<OwnedScalarValue>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> BoxFuture<'a, Result<PlannedTableFunction>> {
+        Self::plan_inner(self.clone(), context, positional_inputs, named_inputs).boxed()
+    }
+}
+
+impl<R: Runtime> ReadBigQuery<R> {
+    async fn plan_inner(
+        self,
+        _context: &DatabaseContext,
+        positional_inputs: Vec<OwnedScalarValue>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> Result<PlannedTableFunction> {
+        if positional_inputs.len() != 3 {
+            return Err(RayexecError::new("read_bigquery requires 3 arguments"));
+        }
+
+        let project = positional_inputs.first().unwrap().try_as_str()?;
+        let dataset = positional_inputs.get(1).unwrap().try_as_str()?;
+        let table = positional_inputs.get(2).unwrap().try_as_str()?;
+
+        let mut options = named_inputs.clone();
+        let key = take_option(SERVICE_ACCOUNT_KEY_OPTION_KEY, &mut options)?.try_into_string()?;
+        check_options_empty(&options)?;
+
+        let conn = BigQueryConnection::connect(self.runtime.clone(), Some(project), &key).await?;
+
+        let fields = match conn.rest.get_table(project, dataset, table).await? {
+            Some(bq_table) => bq_table.fields()?,
+            None => return Err(RayexecError::new("Table not found")),
+        };
+
+        let datatable = conn.bigquery_table(dataset, table, fields.clone());
+        let statistics = datatable.statistics().await?;
+
+        Ok(PlannedTableFunction {
+            function: Box::new(self),
+            positional_inputs: positional_inputs.into_iter().map(expr::lit).collect(),
+            named_inputs,
+            function_impl: TableFunctionImpl::Scan(Arc::new(datatable)),
+            cardinality: statistics.num_rows,
+            schema: Schema::new(fields),
+        })
+    }
+}
@@ -0,0 +1,337 @@
+//! BigQuery REST API for table metadata.
+//!
+//! See <https://cloud.google.com/bigquery/docs/reference/rest>
+use rayexec_error::{not_implemented, RayexecError, Result, ResultExt};
+use rayexec_execution::arrays::datatype::{DataType, DecimalTypeMeta, TimeUnit, TimestampTypeMeta};
+use rayexec_execution::arrays::field::Field;
+use rayexec_execution::logical::statistics::StatisticsValue;
+use rayexec_execution::storage::table_storage::TableStatistics;
+use rayexec_io::http::reqwest::header::AUTHORIZATION;
+use rayexec_io::http::reqwest::{Method, Request, StatusCode};
+use rayexec_io::http::{read_json, read_text, HttpClient, HttpResponse};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use url::Url;
+
+use crate::auth::TokenProvider;
+
+const ENDPOINT: &str = "https://bigquery.googleapis.com/bigquery/v2/";
+
+/// Client for the REST API.
+#[derive(Debug, Clone)]
+pub struct BigQueryRestClient<C: HttpClient> {
+    client: C,
+    tokens: TokenProvider<C>,
+}
+
+impl<C: HttpClient> BigQueryRestClient<C> {
+    pub fn new(client: C, tokens: TokenProvider<C>) -> Self {
+        BigQueryRestClient { client, tokens }
+    }
+
+    /// Get a table, returning None if it doesn't exist.
+    pub async fn get_table(
+        &self,
+        project: &str,
+        dataset: &str,
+        table: &str,
+    ) -> Result<Option<BigQueryTable>> {
+        let url = endpoint_url(&["projects", project, "datasets", dataset, "tables", table])?;
+        self.get_json(url).await
+    }
+
+    /// List dataset ids in a project.
+    pub async fn list_datasets(&self, project: &str) -> Result<Vec<String>> {
+        let url = endpoint_url(&["projects", project, "datasets"])?;
+
+        let mut datasets = Vec::new();
+        self.list_pages(url, |page: DatasetList| {
+            datasets.extend(
+                page.datasets
+                    .into_iter()
+                    .map(|d| d.dataset_reference.dataset_id),
+            );
+            page.next_page_token
+        })
+        .await?;
+
+        Ok(datasets)
+    }
+
+    /// List table ids in a dataset.
+    pub async fn list_tables(&self, project: &str, dataset: &str) -> Result<Vec<String>> {
+        let url = endpoint_url(&["projects", project, "datasets", dataset, "tables"])?;
+
+        let mut tables = Vec::new();
+        self.list_pages(url, |page: TableList| {
+            tables.extend(page.tables.into_iter().map(|t| t.table_reference.table_id));
+            page.next_page_token
+        })
+        .await?;
+
+        Ok(tables)
+    }
+
+    /// Request every page of a list endpoint.
+    ///
+    /// `f` is called for each page, and returns the token for the next page.
+    async fn list_pages<T, F>(&self, url: Url, mut f: F) -> Result<()>
+    where
+        T: DeserializeOwned,
+        F: FnMut(T) -> Option<String>,
+    {
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut url = url.clone();
+            if let Some(token) = &page_token {
+                url.query_pairs_mut().append_pair("pageToken", token);
+            }
+
+            let page = match self.get_json(url).await? {
+                Some(page) => page,
+                None => return Err(RayexecError::new("BigQuery list endpoint not found")),
+            };
+
+            page_token = f(page);
+            if page_token.is_none() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// GET a url, deserializing the response. Returns None if the response
+    /// was 404.
+    async fn get_json<T: DeserializeOwned>(&self, url: Url) -> Result<Option<T>> {
+        let token = self.tokens.access_token().await?;
+
+        let mut request = Request::new(Method::GET, url);
+        request.headers_mut().insert(
+            AUTHORIZATION,
+            format!("Bearer {token}")
+                .try_into()
+                .context("Invalid access token")?,
+        );
+
+        let resp = self.client.do_request(request).await?;
+        match resp.status() {
+            StatusCode::OK => Ok(Some(read_json(resp).await?)),
+            StatusCode::NOT_FOUND => Ok(None),
+            status => {
+                let text = read_text(resp).await.unwrap_or_default();
+                Err(RayexecError::new(format!(
+                    "BigQuery request failed, got {status}: {text}"
+                )))
+            }
+        }
+    }
+}
+
+fn endpoint_url(segments: &[&str]) -> Result<Url> {
+    let mut url = Url::parse(ENDPOINT).context("Failed to parse endpoint")?;
+    url.path_segments_mut()
+        .map_err(|_| RayexecError::new("Endpoint cannot be a base"))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BigQueryTable {
+    /// TABLE, VIEW, MATERIALIZED_VIEW, EXTERNAL, or SNAPSHOT.
+    #[serde(rename = "type")]
+    pub table_type: String,
+    pub schema: Option<TableSchema>,
+    /// Integers are encoded as strings in responses.
+    pub num_rows: Option<String>,
+    pub num_bytes: Option<String>,
+}
+
+impl BigQueryTable {
+    pub fn fields(&self) -> Result<Vec<Field>> {
+        if self.table_type == "VIEW" {
+            return Err(RayexecError::new(
+                "Reading BigQuery views is not supported by the Storage Read API",
+            ));
+        }
+
+        match &self.schema {
+            Some(schema) => schema.fields.iter().map(|f| f.to_field()).collect(),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub fn statistics(&self) -> TableStatistics {
+        let estimated = |v: &Option<String>| match v.as_deref().map(str::parse::<usize>) {
+            Some(Ok(v)) => StatisticsValue::Estimated(v),
+            _ => StatisticsValue::Unknown,
+        };
+
+        TableStatistics {
+            num_rows: estimated(&self.num_rows),
+            num_bytes: estimated(&self.num_bytes),
+            columns: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TableSchema {
+    #[serde(default)]
+    pub fields: Vec<TableFieldSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TableFieldSchema {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// NULLABLE, REQUIRED, or REPEATED. Defaults to NULLABLE.
+    pub mode: Option<String>,
+}
+
+impl TableFieldSchema {
+    pub fn to_field(&self) -> Result<Field> {
+        if self.mode.as_deref() == Some("REPEATED") {
+            not_implemented!("BigQuery REPEATED column '{}'", self.name);
+        }
+
+        let datatype = match self.field_type.as_str() {
+            "BOOLEAN" | "BOOL" => DataType::Boolean,
+            "INTEGER" | "INT64" => DataType::Int64,
+            "FLOAT" | "FLOAT64" => DataType::Float64,
+            // Storage API returns NUMERIC as decimal128(38, 9).
+            "NUMERIC" => DataType::Decimal128(DecimalTypeMeta::new(38, 9)),
+            "STRING" | "GEOGRAPHY" => DataType::Utf8,
+            "JSON" => DataType::Json,
+            "BYTES" => DataType::Binary,
+            "DATE" => DataType::Date32,
+            "TIMESTAMP" | "DATETIME" => {
+                DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond))
+            }
+            other => not_implemented!("BigQuery type {other} for column '{}'", self.name),
+        };
+
+        let nullable = self.mode.as_deref() != Some("REQUIRED");
+
+        Ok(Field::new(&self.name, datatype, nullable))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DatasetList {
+    #[serde(default)]
+    datasets: Vec<DatasetListItem>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DatasetListItem {
+    dataset_reference: DatasetReference,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DatasetReference {
+    dataset_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TableList {
+    #[serde(default)]
+    tables: Vec<TableListItem>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TableListItem {
+    table_reference: TableReference,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TableReference {
+    table_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_fields() {
+        let table: BigQueryTable = serde_json::from_str(
+            r#"{
+                "type": "TABLE",
+                "schema": {
+                    "fields": [
+                        {"name": "id", "type": "INTEGER", "mode": "REQUIRED"},
+                        {"name": "name", "type": "STRING"},
+                        {"name": "amount", "type": "NUMERIC", "mode": "NULLABLE"},
+                        {"name": "created", "type": "TIMESTAMP"}
+                    ]
+                },
+                "numRows": "42",
+                "numBytes": "1024"
+            }"#,
+        )
+        .unwrap();
+
+        let expected = vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new(
+                "amount",
+                DataType::Decimal128(DecimalTypeMeta::new(38, 9)),
+                true,
+            ),
+            Field::new(
+                "created",
+                DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond)),
+                true,
+            ),
+        ];
+        assert_eq!(expected, table.fields().unwrap());
+
+        let stats = table.statistics();
+        assert_eq!(StatisticsValue::Estimated(42), stats.num_rows);
+        assert_eq!(StatisticsValue::Estimated(1024), stats.num_bytes);
+    }
+
+    #[test]
+    fn unsupported_fields() {
+        let repeated = TableFieldSchema {
+            name: "tags".to_string(),
+            field_type: "STRING".to_string(),
+            mode: Some("REPEATED".to_string()),
+        };
+        repeated.to_field().unwrap_err();
+
+        let record = TableFieldSchema {
+            name: "address".to_string(),
+            field_type: "RECORD".to_string(),
+            mode: None,
+        };
+        record.to_field().unwrap_err();
+    }
+
+    #[test]
+    fn view_not_supported() {
+        let table: BigQueryTable =
+            serde_json::from_str(r#"{"type": "VIEW", "schema": {"fields": []}}"#).unwrap();
+        table.fields().unwrap_err();
+    }
+
+    #[test]
+    fn urls_escape_segments() {
+        let url = endpoint_url(&["projects", "my-project", "datasets", "a/b"]).unwrap();
+        assert_eq!(
+            "https://bigquery.googleapis.com/bigquery/v2/projects/my-project/datasets/a%2Fb",
+            url.as_str()
+        );
+    }
+}
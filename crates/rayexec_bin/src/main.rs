@@ -1,20 +1,26 @@
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use clap::Parser;
 use crossterm::event::{self, Event, KeyModifiers};
+use rayexec_bigquery::BigQueryDataSource;
 use rayexec_csv::CsvDataSource;
 use rayexec_delta::DeltaDataSource;
 use rayexec_error::Result;
 use rayexec_execution::datasource::{DataSourceBuilder, DataSourceRegistry, MemoryDataSource};
 use rayexec_execution::runtime::{PipelineExecutor, Runtime, TokioHandlerProvider};
 use rayexec_iceberg::IcebergDataSource;
+use rayexec_io::limit::RequestLimits;
+use rayexec_lance::LanceDataSource;
+use rayexec_orc::OrcDataSource;
 use rayexec_parquet::ParquetDataSource;
 use rayexec_postgres::PostgresDataSource;
 use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
 use rayexec_shell::lineedit::KeyEvent;
 use rayexec_shell::session::SingleUserEngine;
 use rayexec_shell::shell::{Shell, ShellSignal};
+use rayexec_spatial::SpatialDataSource;
 use rayexec_unity_catalog::UnityCatalogDataSource;
 
 #[derive(Parser)]
@@ -25,6 +31,26 @@ struct Arguments {
     files: Vec<PathBuf>,
     #[clap(long)]
     dump_profile: bool,
+    /// Fail remote scans that go this many seconds without receiving any data.
+    #[clap(long)]
+    scan_stall_timeout_secs: Option<u64>,
+    /// Max number of requests to S3 in flight at once.
+    #[clap(long)]
+    s3_max_concurrent_requests: Option<usize>,
+    /// Max number of requests to send to S3 per second.
+    #[clap(long)]
+    s3_max_requests_per_second: Option<f64>,
+    /// Max number of requests to http file sources in flight at once.
+    #[clap(long)]
+    http_max_concurrent_requests: Option<usize>,
+    /// Max number of requests to send to http file sources per second.
+    #[clap(long)]
+    http_max_requests_per_second: Option<f64>,
+    /// Directory to persist tables to.
+    ///
+    /// If omitted, only temporary tables can be created.
+    #[clap(long)]
+    database: Option<PathBuf>,
     /// Queries to execute.
     ///
     /// If omitted, and no files were given via the `files` argument, then an
@@ -39,7 +65,25 @@ fn main() {
     logutil::configure_global_logger(tracing::Level::ERROR, logutil::LogFormat::HumanReadable);
 
     let executor = ThreadedNativeExecutor::try_new().unwrap();
-    let runtime = NativeRuntime::with_default_tokio().unwrap();
+    let mut runtime = NativeRuntime::with_default_tokio().unwrap();
+    if let Some(secs) = args.scan_stall_timeout_secs {
+        runtime = runtime.with_scan_stall_timeout(Duration::from_secs(secs));
+    }
+    runtime = runtime
+        .with_s3_request_limits(RequestLimits {
+            max_concurrent_requests: args.s3_max_concurrent_requests,
+            max_requests_per_second: args.s3_max_requests_per_second,
+        })
+        .and_then(|runtime| {
+            runtime.with_http_request_limits(RequestLimits {
+                max_concurrent_requests: args.http_max_concurrent_requests,
+                max_requests_per_second: args.http_max_requests_per_second,
+            })
+        })
+        .unwrap_or_else(|e| {
+            println!("ERROR: {e}");
+            std::process::exit(1);
+        });
     let tokio_handle = runtime
         .tokio_handle()
         .handle()
@@ -83,12 +127,26 @@ async fn inner(
     let registry = DataSourceRegistry::default()
         .with_datasource("memory", Box::new(MemoryDataSource))?
         .with_datasource("postgres", PostgresDataSource::initialize(runtime.clone()))?
+        .with_datasource("bigquery", BigQueryDataSource::initialize(runtime.clone()))?
         .with_datasource("delta", DeltaDataSource::initialize(runtime.clone()))?
         .with_datasource("unity", UnityCatalogDataSource::initialize(runtime.clone()))?
         .with_datasource("parquet", ParquetDataSource::initialize(runtime.clone()))?
+        .with_datasource("orc", OrcDataSource::initialize(runtime.clone()))?
+        .with_datasource("lance", LanceDataSource::initialize(runtime.clone()))?
         .with_datasource("csv", CsvDataSource::initialize(runtime.clone()))?
-        .with_datasource("iceberg", IcebergDataSource::initialize(runtime.clone()))?;
-    let engine = SingleUserEngine::try_new(executor, runtime, registry)?;
+        .with_datasource("iceberg", IcebergDataSource::initialize(runtime.clone()))?
+        .with_datasource("spatial", SpatialDataSource::initialize(runtime.clone()))?;
+    #[cfg(feature = "odbc")]
+    let registry = registry.with_datasource(
+        "odbc",
+        rayexec_odbc::OdbcDataSource::initialize(runtime.clone()),
+    )?;
+    let engine = match &args.database {
+        Some(path) => {
+            SingleUserEngine::try_new_with_database_path(executor, runtime, registry, path)?
+        }
+        None => SingleUserEngine::try_new(executor, runtime, registry)?,
+    };
 
     let (cols, _rows) = crossterm::terminal::size()?;
     let mut stdout = BufWriter::new(std::io::stdout());
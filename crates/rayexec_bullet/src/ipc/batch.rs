@@ -1,5 +1,6 @@
 //! Conversion to/from ipc for batches.
 use std::collections::VecDeque;
+use std::sync::Arc;
 
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
 use half::f16;
@@ -135,7 +136,11 @@ fn decode_varlen_values(buffers: [&[u8]; 2]) -> Result<ArrayData> {
     let metadata = PrimitiveStorage::<UnionedGermanMetadata>::copy_from_bytes(buffers[0])?;
     let data = PrimitiveStorage::<u8>::copy_from_bytes(buffers[1])?;
 
-    Ok(GermanVarlenStorage { metadata, data }.into())
+    Ok(GermanVarlenStorage {
+        metadata,
+        data: Arc::new(data),
+    }
+    .into())
 }
 
 // fn decode_primitive
@@ -271,7 +276,7 @@ fn encode_array(
                 encode_primitive_values(&d.metadata, data, buffers);
                 // Currently only hold 1 data array for these.
                 variadic_counts.push(1);
-                encode_primitive_values(&d.data, data, buffers);
+                encode_primitive_values(&*d.data, data, buffers);
             }
         },
         ArrayData::List(_) => not_implemented!("IPC-encode list"),
@@ -0,0 +1,357 @@
+//! Parallel scans over byte ranges of a single uncompressed csv file.
+//!
+//! The file is split into fixed size chunks distributed across partitions. A
+//! record belongs to the chunk it starts in, so each chunk skips ahead to the
+//! first record start, and reads past its end to finish its last record.
+//!
+//! Finding where records start requires knowing if a chunk starts inside a
+//! quoted field (which may contain newlines). Each chunk publishes the parity
+//! of its quote count, and waits for the parities of all preceding chunks to
+//! determine its starting state.
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::task::{Poll, Waker};
+
+use futures::future::poll_fn;
+use parking_lot::Mutex;
+use rayexec_error::Result;
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::field::Schema;
+use rayexec_io::FileSource;
+
+use crate::reader::{CsvSchema, DialectOptions};
+use crate::records::RecordIndex;
+use crate::scanner::StructuralScanner;
+
+/// Smallest chunk we'll split a file into.
+///
+/// Files that fit in a single chunk are read with a single stream instead.
+pub const MIN_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Largest chunk we'll split a file into, bounding memory use per partition.
+pub const MAX_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Number of bytes to read at a time past the end of a chunk when finishing
+/// its last record.
+const TAIL_READ_SIZE: usize = 64 * 1024;
+
+/// Get the chunk size to use when splitting a file across partitions.
+pub fn chunk_size_for_file(file_size: usize, num_partitions: usize) -> usize {
+    file_size
+        .div_ceil(num_partitions.max(1))
+        .clamp(MIN_CHUNK_SIZE, MAX_CHUNK_SIZE)
+}
+
+/// Quote parities for each chunk of a file, shared by all partitions scanning
+/// the file.
+#[derive(Debug)]
+pub struct ChunkQuoteStates {
+    state: Mutex<QuoteStatesInner>,
+}
+
+#[derive(Debug)]
+struct QuoteStatesInner {
+    /// If each chunk has an odd number of quotes, None if not yet known.
+    odd_quotes: Vec<Option<bool>>,
+    wakers: Vec<Waker>,
+}
+
+impl ChunkQuoteStates {
+    pub fn new(num_chunks: usize) -> Self {
+        ChunkQuoteStates {
+            state: Mutex::new(QuoteStatesInner {
+                odd_quotes: vec![None; num_chunks],
+                wakers: Vec::new(),
+            }),
+        }
+    }
+
+    fn set_odd_quotes(&self, chunk: usize, odd: bool) {
+        let wakers = {
+            let mut state = self.state.lock();
+            state.odd_quotes[chunk] = Some(odd);
+            std::mem::take(&mut state.wakers)
+        };
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Wait until we know if a chunk starts inside of a quoted field.
+    async fn starts_in_quotes(&self, chunk: usize) -> bool {
+        poll_fn(|cx| {
+            let mut state = self.state.lock();
+            let mut in_quotes = false;
+            for odd in &state.odd_quotes[..chunk] {
+                match odd {
+                    Some(odd) => in_quotes ^= odd,
+                    None => {
+                        state.wakers.push(cx.waker().clone());
+                        return Poll::Pending;
+                    }
+                }
+            }
+            Poll::Ready(in_quotes)
+        })
+        .await
+    }
+}
+
+/// Reads records from some number of chunks in a file.
+pub struct ChunkedCsvReader {
+    source: Box<dyn FileSource>,
+    file_size: usize,
+    chunk_size: usize,
+    /// Chunks left to read.
+    chunks: VecDeque<usize>,
+    quote_states: Arc<ChunkQuoteStates>,
+    schema: Schema,
+    /// If the first record in the file is a header.
+    has_header: bool,
+    scanner: StructuralScanner,
+    batch_size: usize,
+    /// Complete records for the current chunk.
+    buf: Vec<u8>,
+    index: RecordIndex,
+    /// Next record in the index to produce.
+    next_record: usize,
+}
+
+impl ChunkedCsvReader {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        source: Box<dyn FileSource>,
+        file_size: usize,
+        chunk_size: usize,
+        chunks: impl IntoIterator<Item = usize>,
+        quote_states: Arc<ChunkQuoteStates>,
+        csv_schema: CsvSchema,
+        dialect: DialectOptions,
+        batch_size: usize,
+    ) -> Self {
+        let scanner = StructuralScanner::new(dialect);
+        let num_fields = csv_schema.schema.fields.len();
+
+        ChunkedCsvReader {
+            source,
+            file_size,
+            chunk_size,
+            chunks: chunks.into_iter().collect(),
+            quote_states,
+            schema: csv_schema.schema,
+            has_header: csv_schema.has_header,
+            scanner,
+            batch_size,
+            buf: Vec::new(),
+            index: RecordIndex::new(scanner, dialect.quote, num_fields),
+            next_record: 0,
+        }
+    }
+
+    pub async fn read_next(&mut self) -> Result<Option<Batch>> {
+        loop {
+            let num_records = self.index.num_records();
+            if self.next_record < num_records {
+                let end = usize::min(self.next_record + self.batch_size, num_records);
+                let batch =
+                    self.index
+                        .build_batch(&self.buf, &self.schema, self.next_record..end)?;
+                self.next_record = end;
+                return Ok(Some(batch));
+            }
+
+            match self.chunks.pop_front() {
+                Some(chunk) => self.load_chunk(chunk).await?,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Read and index all records starting in a chunk.
+    async fn load_chunk(&mut self, chunk: usize) -> Result<()> {
+        self.index.clear();
+        self.buf.clear();
+        self.next_record = 0;
+
+        let start = chunk * self.chunk_size;
+        let end = usize::min(start + self.chunk_size, self.file_size);
+
+        // Include the last byte of the previous chunk to check if a record
+        // starts right at the beginning of this chunk.
+        let read_start = start.saturating_sub(1);
+        let bytes = self.source.read_range(read_start, end - read_start).await?;
+        let (prev, data) = if chunk == 0 {
+            (None, &bytes[..])
+        } else {
+            (Some(bytes[0]), &bytes[1..])
+        };
+
+        let odd_quotes = self.scanner.count_quotes(data) % 2 == 1;
+        self.quote_states.set_odd_quotes(chunk, odd_quotes);
+        let in_quotes = self.quote_states.starts_in_quotes(chunk).await;
+
+        let record_start = match prev {
+            None => 0,
+            Some(b'\n') if !in_quotes => 0,
+            Some(_) => match self.scanner.find_record_end(data, in_quotes) {
+                Some(pos) => pos + 1,
+                None => return Ok(()), // No records start in this chunk.
+            },
+        };
+        if record_start == data.len() {
+            return Ok(());
+        }
+
+        self.buf.extend_from_slice(&data[record_start..]);
+
+        // Finish the last record using bytes past the end of the chunk.
+        let end_in_quotes = in_quotes ^ odd_quotes;
+        if end_in_quotes || !self.buf.ends_with(b"\n") {
+            self.read_tail(end, end_in_quotes).await?;
+        }
+
+        self.index.index(&self.buf)?;
+        if chunk == 0 && self.has_header {
+            self.next_record = 1;
+        }
+
+        Ok(())
+    }
+
+    /// Append bytes starting at `offset` up to the first record end.
+    async fn read_tail(&mut self, mut offset: usize, mut in_quotes: bool) -> Result<()> {
+        while offset < self.file_size {
+            let len = usize::min(TAIL_READ_SIZE, self.file_size - offset);
+            let tail = self.source.read_range(offset, len).await?;
+
+            if let Some(pos) = self.scanner.find_record_end(&tail, in_quotes) {
+                self.buf.extend_from_slice(&tail[..=pos]);
+                return Ok(());
+            }
+
+            self.buf.extend_from_slice(&tail);
+            in_quotes ^= self.scanner.count_quotes(&tail) % 2 == 1;
+            offset += len;
+        }
+
+        // End of file, last record may be missing its newline.
+        if !self.buf.ends_with(b"\n") {
+            self.buf.push(b'\n');
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ChunkedCsvReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkedCsvReader")
+            .field("chunks", &self.chunks)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures::executor::block_on;
+    use futures::future::{join_all, BoxFuture};
+    use futures::stream::{self, BoxStream};
+    use futures::{FutureExt, StreamExt};
+    use rayexec_execution::arrays::datatype::DataType;
+    use rayexec_execution::arrays::field::Field;
+    use rayexec_execution::arrays::scalar::ScalarValue;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct BytesSource(Bytes);
+
+    impl FileSource for BytesSource {
+        fn read_range(&mut self, start: usize, len: usize) -> BoxFuture<Result<Bytes>> {
+            let bs = self.0.slice(start..start + len);
+            async move { Ok(bs) }.boxed()
+        }
+
+        fn read_stream(&mut self) -> BoxStream<'static, Result<Bytes>> {
+            let bs = self.0.clone();
+            stream::once(async move { Ok(bs) }).boxed()
+        }
+
+        fn size(&mut self) -> BoxFuture<Result<usize>> {
+            let size = self.0.len();
+            async move { Ok(size) }.boxed()
+        }
+    }
+
+    /// Read all chunks with some number of partitions, returning the values
+    /// from the first column.
+    fn read_all(content: &str, chunk_size: usize, num_partitions: usize) -> Vec<String> {
+        let content = Bytes::from(content.to_string());
+        let num_chunks = content.len().div_ceil(chunk_size);
+        let quote_states = Arc::new(ChunkQuoteStates::new(num_chunks));
+
+        let csv_schema = CsvSchema {
+            schema: Schema::new([
+                Field::new("a", DataType::Utf8, true),
+                Field::new("b", DataType::Int64, true),
+            ]),
+            has_header: true,
+        };
+
+        let readers = (0..num_partitions).map(|partition| {
+            let chunks = (partition..num_chunks).step_by(num_partitions);
+            let mut reader = ChunkedCsvReader::new(
+                Box::new(BytesSource(content.clone())),
+                content.len(),
+                chunk_size,
+                chunks,
+                quote_states.clone(),
+                csv_schema.clone(),
+                DialectOptions::default(),
+                2,
+            );
+
+            async move {
+                let mut values = Vec::new();
+                while let Some(batch) = reader.read_next().await.unwrap() {
+                    let col = batch.column(0).unwrap();
+                    for idx in 0..batch.num_rows() {
+                        match col.logical_value(idx).unwrap() {
+                            ScalarValue::Utf8(v) => values.push(v.to_string()),
+                            other => panic!("unexpected value: {other}"),
+                        }
+                    }
+                }
+                values
+            }
+        });
+
+        let mut values: Vec<_> = block_on(join_all(readers)).into_iter().flatten().collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn chunks_match_single_read() {
+        let content = "a,b\n\"x,\ny\",1\nz,2\n\"q\"\"\nr\",3\ns,4\nt,5";
+        let expected = vec!["q\"\nr", "s", "t", "x,\ny", "z"];
+
+        // Single chunk.
+        assert_eq!(expected, read_all(content, content.len(), 1));
+
+        // Every chunk size, so that chunks start at every possible position
+        // including inside of quoted fields.
+        for chunk_size in 1..content.len() {
+            for num_partitions in [1, 3] {
+                assert_eq!(
+                    expected,
+                    read_all(content, chunk_size, num_partitions),
+                    "chunk_size: {chunk_size}, partitions: {num_partitions}"
+                );
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::{self, Debug};
+use std::sync::Arc;
 
 use futures::future::BoxFuture;
 use rayexec_error::Result;
@@ -14,12 +15,14 @@ use rayexec_execution::storage::table_storage::{
 use rayexec_io::location::{AccessConfig, FileLocation};
 use rayexec_io::FileProvider;
 
+use crate::chunked::{chunk_size_for_file, ChunkQuoteStates, ChunkedCsvReader};
 use crate::reader::{AsyncCsvReader, CsvSchema, DialectOptions};
 
 /// Data table implementation that reads from a single file.
 ///
-/// This will produce a single scan that reads the actual file, with the
-/// remaining scans being empty.
+/// If the file can be read by byte ranges, it's split into chunks scanned in
+/// parallel. Otherwise this will produce a single scan that streams the file,
+/// with the remaining scans being empty.
 ///
 /// This should be extended to support multiple files once we add in glob
 /// support.
@@ -29,20 +32,75 @@ pub struct SingleFileCsvDataTable<R: Runtime> {
     pub csv_schema: CsvSchema,
     pub location: FileLocation,
     pub conf: AccessConfig,
+    /// Size of the file if it's uncompressed and supports range reads.
+    pub file_size: Option<usize>,
     pub runtime: R,
 }
 
+impl<R: Runtime> SingleFileCsvDataTable<R> {
+    fn scan_chunked(
+        &self,
+        projections: Projections,
+        file_size: usize,
+        chunk_size: usize,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let num_chunks = file_size.div_ceil(chunk_size);
+        let quote_states = Arc::new(ChunkQuoteStates::new(num_chunks));
+
+        (0..num_partitions)
+            .map(|partition| {
+                let source = self
+                    .runtime
+                    .file_provider()
+                    .file_source(self.location.clone(), &self.conf)?;
+                let reader = ChunkedCsvReader::new(
+                    source,
+                    file_size,
+                    chunk_size,
+                    (partition..num_chunks).step_by(num_partitions),
+                    quote_states.clone(),
+                    self.csv_schema.clone(),
+                    self.options,
+                    batch_size,
+                );
+
+                Ok(Box::new(ProjectedScan::new(
+                    ChunkedCsvScan { reader },
+                    projections.clone(),
+                )) as _)
+            })
+            .collect()
+    }
+}
+
 impl<R: Runtime> DataTable for SingleFileCsvDataTable<R> {
     fn scan(
         &self,
         projections: Projections,
         num_partitions: usize,
+        batch_size: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        if let Some(file_size) = self.file_size {
+            let chunk_size = chunk_size_for_file(file_size, num_partitions);
+            if num_partitions > 1 && file_size > chunk_size {
+                return self.scan_chunked(
+                    projections,
+                    file_size,
+                    chunk_size,
+                    num_partitions,
+                    batch_size,
+                );
+            }
+        }
+
         let reader = self
             .runtime
             .file_provider()
             .file_source(self.location.clone(), &self.conf)?;
-        let csv_reader = AsyncCsvReader::new(reader, self.csv_schema.clone(), self.options);
+        let csv_reader =
+            AsyncCsvReader::new(reader, self.csv_schema.clone(), self.options, batch_size);
 
         let mut scans: Vec<Box<dyn DataTableScan>> = vec![Box::new(ProjectedScan::new(
             CsvFileScan { reader: csv_reader },
@@ -70,3 +128,14 @@ impl fmt::Debug for CsvFileScan {
         f.debug_struct("CsvFileScan").finish_non_exhaustive()
     }
 }
+
+#[derive(Debug)]
+pub struct ChunkedCsvScan {
+    reader: ChunkedCsvReader,
+}
+
+impl DataTableScan for ChunkedCsvScan {
+    fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
+        Box::pin(async { self.reader.read_next().await })
+    }
+}
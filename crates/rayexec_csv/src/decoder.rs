@@ -71,6 +71,8 @@ impl DecoderState {
         self.ends[num_completed * num_fields - 1]
     }
 
+    // Only used in tests since reading records moved to the record index.
+    #[allow(dead_code)]
     pub fn clear_completed(&mut self) {
         let num_completed = self.num_records();
         let num_fields = match self.num_fields {
@@ -3,8 +3,11 @@ pub mod datatable;
 pub mod reader;
 pub mod writer;
 
+mod chunked;
 mod decoder;
 mod read_csv;
+mod records;
+mod scanner;
 
 use copy_to::CsvCopyToFunction;
 use rayexec_execution::datasource::{DataSource, DataSourceBuilder, DataSourceCopyTo, FileHandler};
@@ -41,7 +44,7 @@ impl<R: Runtime> DataSource for CsvDataSource<R> {
     }
 
     fn file_handlers(&self) -> Vec<FileHandler> {
-        let regex = RegexBuilder::new(r"^.*\.(csv)$")
+        let regex = RegexBuilder::new(r"^.*\.(csv)(\.(gz|gzip|zst|zstd|bz2))?$")
             .case_insensitive(true)
             .build()
             .expect("regex to build");
@@ -19,6 +19,8 @@ use rayexec_execution::functions::table::{
 use rayexec_execution::functions::{FunctionInfo, Signature};
 use rayexec_execution::logical::statistics::StatisticsValue;
 use rayexec_execution::runtime::Runtime;
+use rayexec_io::compression::CompressionType;
+use rayexec_io::location::FileLocation;
 use rayexec_io::{FileProvider, FileSource};
 
 use crate::datatable::SingleFileCsvDataTable;
@@ -69,15 +71,15 @@ impl<R: Runtime> ScanPlanner for ReadCsv<R> {
 impl<R: Runtime> ReadCsv<R> {
     async fn plan_inner(
         self,
-        _context: &DatabaseContext,
+        context: &DatabaseContext,
         positional_inputs: Vec<OwnedScalarValue>,
         named_inputs: HashMap<String, OwnedScalarValue>,
     ) -> Result<PlannedTableFunction> {
         let (location, conf) =
             try_location_and_access_config_from_args(&self, &positional_inputs, &named_inputs)?;
+        let runtime = context.scan_runtime(&self.runtime);
 
-        let mut source = self
-            .runtime
+        let mut source = runtime
             .file_provider()
             .file_source(location.clone(), &conf)?;
 
@@ -105,13 +107,15 @@ impl<R: Runtime> ReadCsv<R> {
         let csv_schema = CsvSchema::infer_from_records(completed)?;
 
         let schema = csv_schema.schema.clone();
+        let file_size = range_readable_size(&location, &mut source).await;
 
         let datatable = SingleFileCsvDataTable {
             options: dialect,
             csv_schema,
             location,
             conf,
-            runtime: self.runtime.clone(),
+            file_size,
+            runtime,
         };
 
         Ok(PlannedTableFunction {
@@ -124,3 +128,26 @@ impl<R: Runtime> ReadCsv<R> {
         })
     }
 }
+
+/// Get the size of the file if it can be split into byte ranges for parallel
+/// scanning.
+///
+/// Compressed files need to be read from the start, as do sources that don't
+/// support range reads.
+async fn range_readable_size(
+    location: &FileLocation,
+    source: &mut impl FileSource,
+) -> Option<usize> {
+    if CompressionType::from_location(location).is_some() {
+        return None;
+    }
+
+    let size = source.size().await.ok()?;
+    // Read raw bytes to check for compression not indicated by the extension.
+    let head = source.read_range(0, usize::min(size, 16)).await.ok()?;
+    if CompressionType::from_magic_bytes(&head).is_some() {
+        return None;
+    }
+
+    Some(size)
+}
@@ -23,9 +23,7 @@ use bytes::Bytes;
 use futures::stream::BoxStream;
 use futures::StreamExt;
 use rayexec_error::{RayexecError, Result};
-use rayexec_execution::arrays::array::{Array, ArrayData};
 use rayexec_execution::arrays::batch::Batch;
-use rayexec_execution::arrays::bitmap::Bitmap;
 use rayexec_execution::arrays::compute::cast::parse::{
     BoolParser,
     Float64Parser,
@@ -33,13 +31,13 @@ use rayexec_execution::arrays::compute::cast::parse::{
     Parser,
 };
 use rayexec_execution::arrays::datatype::{DataType, TimeUnit, TimestampTypeMeta};
-use rayexec_execution::arrays::executor::builder::{ArrayDataBuffer, GermanVarlenBuffer};
 use rayexec_execution::arrays::field::{Field, Schema};
-use rayexec_execution::arrays::storage::{BooleanStorage, PrimitiveStorage};
 use rayexec_io::FileSource;
 use serde::{Deserialize, Serialize};
 
 use crate::decoder::{CompletedRecords, CsvDecoder, DecoderResult, DecoderState};
+use crate::records::RecordIndex;
+use crate::scanner::StructuralScanner;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DialectOptions {
@@ -327,16 +325,22 @@ impl AsyncCsvReader {
         mut reader: impl FileSource,
         csv_schema: CsvSchema,
         dialect: DialectOptions,
+        batch_size: usize,
     ) -> Self {
+        let scanner = StructuralScanner::new(dialect);
+        let num_fields = csv_schema.schema.fields.len();
+
         let stream = AsyncCsvStream {
             schema: csv_schema.schema,
             skip_header: csv_schema.has_header,
             stream: reader.read_stream(),
-            decoder_state: DecoderState::default(),
-            decoder: CsvDecoder::new(dialect),
-            buf: None,
-            buf_offset: 0,
-            decoding_finished: false,
+            scanner,
+            batch_size,
+            buf: Vec::new(),
+            records_buf: Vec::new(),
+            index: RecordIndex::new(scanner, dialect.quote, num_fields),
+            next_record: 0,
+            stream_finished: false,
         };
 
         AsyncCsvReader { stream }
@@ -353,6 +357,12 @@ impl fmt::Debug for AsyncCsvReader {
     }
 }
 
+/// Min number of bytes to buffer from the stream before indexing records.
+///
+/// Indexing larger buffers at once amortizes the cost of finding the last
+/// complete record.
+const MIN_INDEX_SIZE: usize = 1024 * 1024;
+
 struct AsyncCsvStream {
     /// Schema we've inferred or otherwise been provided.
     schema: Schema,
@@ -363,216 +373,164 @@ struct AsyncCsvStream {
     /// Inner stream for getting bytes.
     stream: BoxStream<'static, Result<Bytes>>,
 
-    /// Decoder that we push bytes to.
-    decoder: CsvDecoder,
+    scanner: StructuralScanner,
 
-    /// Decoder state updated on every push to the decoder.
-    decoder_state: DecoderState,
+    batch_size: usize,
 
-    /// If we read from the stream and push to the decoder, but the decoder's
-    /// buffer is full, we store the stream's buffer here so the next iteration
-    /// can continue where it left off.
-    buf: Option<Bytes>,
+    /// Bytes read from the stream that haven't been indexed yet.
+    ///
+    /// Always begins at the start of a record.
+    buf: Vec<u8>,
 
-    /// Offset into the above buffer where we should resume reading.
-    buf_offset: usize,
+    /// Complete records that have been indexed.
+    records_buf: Vec<u8>,
 
-    /// If the decoder said we're finished.
-    ///
-    /// This is used to allow us to determine if we should push an empty buffer
-    /// to the decoder as csv_core expects an empty buffer when reading is
-    /// complete. One we've pushed that buffer, this gets set to true and we
-    /// build the remaining batch.
-    decoding_finished: bool,
+    /// Index for records in `records_buf`.
+    index: RecordIndex,
+
+    /// Next record in the index to produce.
+    next_record: usize,
+
+    /// If the stream has been exhausted.
+    stream_finished: bool,
 }
 
 impl AsyncCsvStream {
     async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        let mut min_len = MIN_INDEX_SIZE;
         loop {
-            let (buf, offset) = match self.buf.take() {
-                Some(buf) => (buf, self.buf_offset),
-                None => match self.stream.next().await {
-                    Some(buf) => (buf?, 0),
-                    None => {
-                        if self.decoding_finished {
-                            // We're done, we've already passed an empty buffer
-                            // to the decoder.
-                            return Ok(None);
-                        }
-                        // Provide an empty buffer. csv_core expects and empty
-                        // buffer to signal end of reading.
-                        (Bytes::new(), 0)
-                    }
-                },
-            };
+            let num_records = self.index.num_records();
+            if self.next_record < num_records {
+                let end = usize::min(self.next_record + self.batch_size, num_records);
+                let batch = self.index.build_batch(
+                    &self.records_buf,
+                    &self.schema,
+                    self.next_record..end,
+                )?;
+                self.next_record = end;
+                return Ok(Some(batch));
+            }
 
-            match self
-                .decoder
-                .decode(&buf[offset..], &mut self.decoder_state)?
-            {
-                DecoderResult::Finished => {
-                    self.decoding_finished = true;
-                    // Continue on with using the decoded results.
-                }
-                DecoderResult::InputExhuasted => continue, // To next iteration of outer loop.
-                DecoderResult::BufferFull { input_offset } => {
-                    // Need to flush out buffer. Store for later use.
-                    self.buf = Some(buf);
-                    self.buf_offset = input_offset;
-                }
+            if self.stream_finished && self.buf.is_empty() {
+                return Ok(None);
             }
 
-            let num_fields = match self.decoder_state.num_fields() {
-                Some(num) => num,
-                None => return Err(RayexecError::new("First record exceeded buffer size")),
-            };
+            self.fill_buf(min_len).await?;
 
-            if num_fields != self.schema.fields.len() {
-                return Err(RayexecError::new(format!(
-                    "Number of fields read does not match schema, got {}, expected {}",
-                    num_fields,
-                    self.schema.fields.len()
-                )));
-            }
+            let record_end = match self.scanner.find_last_record_end(&self.buf) {
+                Some(end) => end,
+                None if self.stream_finished => {
+                    return Err(RayexecError::new("CSV input ends inside a quoted field"))
+                }
+                None => {
+                    // Record is larger than what we've buffered so far.
+                    min_len = self.buf.len() + 1;
+                    continue;
+                }
+            };
 
-            let completed = self.decoder_state.completed_records();
-            if completed.num_completed() == 0 {
-                return Err(RayexecError::new(
-                    "CSV record too large, exceeds buffer size",
-                ));
-            }
+            // Move complete records out of the buffer, keeping the partial
+            // record for the next read.
+            self.records_buf.clear();
+            self.records_buf.extend_from_slice(&self.buf[..=record_end]);
+            self.buf.drain(..=record_end);
 
-            let batch = Self::build_batch(completed, &self.schema, self.skip_header)?;
-            self.skip_header = false;
+            self.index.index(&self.records_buf)?;
+            self.next_record = 0;
 
-            self.decoder_state.clear_completed();
+            if self.skip_header && self.index.num_records() > 0 {
+                self.next_record = 1;
+                self.skip_header = false;
+            }
+        }
+    }
 
-            return Ok(Some(batch));
+    /// Read from the stream until we've buffered at least `min_len` bytes, or
+    /// the stream is exhausted.
+    async fn fill_buf(&mut self, min_len: usize) -> Result<()> {
+        while !self.stream_finished && self.buf.len() < min_len {
+            match self.stream.next().await {
+                Some(result) => self.buf.extend_from_slice(result?.as_ref()),
+                None => {
+                    self.stream_finished = true;
+                    // Last record may be missing its newline.
+                    if !self.buf.is_empty() && !self.buf.ends_with(b"\n") {
+                        self.buf.push(b'\n');
+                    }
+                }
+            }
         }
+        Ok(())
     }
+}
 
-    fn build_batch(
-        completed: CompletedRecords,
-        schema: &Schema,
-        skip_header: bool,
-    ) -> Result<Batch> {
-        let skip_records = if skip_header { 1 } else { 0 };
-
-        let mut arrs = Vec::with_capacity(schema.fields.len());
-        for (idx, field) in schema.fields.iter().enumerate() {
-            let arr = match &field.datatype {
-                DataType::Boolean => Self::build_boolean(&completed, idx, skip_records)?,
-                DataType::Int64 => Self::build_primitive(
-                    &field.datatype,
-                    &completed,
-                    idx,
-                    skip_records,
-                    Int64Parser::new(),
-                )?,
-                DataType::Float64 => Self::build_primitive(
-                    &field.datatype,
-                    &completed,
-                    idx,
-                    skip_records,
-                    Float64Parser::new(),
-                )?,
-                DataType::Utf8 => Self::build_utf8(&completed, idx, skip_records)?,
-                other => return Err(RayexecError::new(format!("Unhandled data type: {other}"))),
-            };
+#[cfg(test)]
+mod tests {
+    use futures::future::BoxFuture;
+    use futures::{stream, FutureExt};
+    use rayexec_execution::arrays::scalar::ScalarValue;
 
-            arrs.push(arr);
-        }
+    use super::*;
 
-        Batch::try_new(arrs)
-    }
+    /// Source that streams its content a few bytes at a time.
+    #[derive(Debug)]
+    struct TrickleSource(Bytes);
 
-    fn build_boolean(
-        completed: &CompletedRecords,
-        field_idx: usize,
-        skip_records: usize,
-    ) -> Result<Array> {
-        let mut values = Bitmap::with_capacity(completed.num_completed());
-        let mut validity = Bitmap::with_capacity(completed.num_completed());
-
-        for record in completed.iter().skip(skip_records) {
-            let field = record.get_field(field_idx)?;
-            if field.is_empty() {
-                values.push(false);
-                validity.push(false);
-            } else {
-                values.push(BoolParser.parse(field).ok_or_else(|| {
-                    RayexecError::new(format!("Failed to parse '{field}' into a boolean"))
-                })?);
-                validity.push(true);
-            }
+    impl FileSource for TrickleSource {
+        fn read_range(&mut self, start: usize, len: usize) -> BoxFuture<Result<Bytes>> {
+            let bs = self.0.slice(start..start + len);
+            async move { Ok(bs) }.boxed()
         }
 
-        Ok(Array::new_with_validity_and_array_data(
-            DataType::Boolean,
-            validity,
-            BooleanStorage::from(values),
-        ))
-    }
-
-    fn build_primitive<T, P>(
-        datatype: &DataType,
-        completed: &CompletedRecords,
-        field_idx: usize,
-        skip_records: usize,
-        mut parser: P,
-    ) -> Result<Array>
-    where
-        T: Default,
-        P: Parser<Type = T>,
-        PrimitiveStorage<T>: Into<ArrayData>,
-    {
-        let mut values = Vec::with_capacity(completed.num_completed());
-        let mut validity = Bitmap::with_capacity(completed.num_completed());
-
-        for record in completed.iter().skip(skip_records) {
-            let field = record.get_field(field_idx)?;
-            if field.is_empty() {
-                values.push(T::default());
-                validity.push(false);
-            } else {
-                values.push(
-                    parser
-                        .parse(field)
-                        .ok_or_else(|| RayexecError::new(format!("Failed to parse '{field}'")))?,
-                );
-                validity.push(true);
-            }
+        fn read_stream(&mut self) -> BoxStream<'static, Result<Bytes>> {
+            let content = self.0.clone();
+            let chunks: Vec<_> = (0..content.len())
+                .step_by(3)
+                .map(|start| Ok(content.slice(start..usize::min(start + 3, content.len()))))
+                .collect();
+            stream::iter(chunks).boxed()
         }
 
-        Ok(Array::new_with_validity_and_array_data(
-            datatype.clone(),
-            validity,
-            PrimitiveStorage::from(values),
-        ))
+        fn size(&mut self) -> BoxFuture<Result<usize>> {
+            let size = self.0.len();
+            async move { Ok(size) }.boxed()
+        }
     }
 
-    fn build_utf8(
-        completed: &CompletedRecords,
-        field_idx: usize,
-        skip_records: usize,
-    ) -> Result<Array> {
-        let mut values = GermanVarlenBuffer::with_len(completed.num_completed() - skip_records);
-        let mut validity = Bitmap::with_capacity(completed.num_completed());
-
-        for (idx, record) in completed.iter().skip(skip_records).enumerate() {
-            let field = record.get_field(field_idx)?;
-            if field.is_empty() {
-                validity.push(false);
-            } else {
-                values.put(idx, field);
-                validity.push(true);
+    #[test]
+    fn stream_quoted_records() {
+        let content = "id,name\r\n1,\"a,\r\nb\"\r\n2,\r\n3,\"c\"\"\"";
+
+        let csv_schema = CsvSchema {
+            schema: Schema::new([
+                Field::new("id", DataType::Int64, true),
+                Field::new("name", DataType::Utf8, true),
+            ]),
+            has_header: true,
+        };
+        let mut reader = AsyncCsvReader::new(
+            TrickleSource(Bytes::from(content)),
+            csv_schema,
+            DialectOptions::default(),
+            2,
+        );
+
+        let mut rows = Vec::new();
+        while let Some(batch) = reader.read_next().now_or_never().unwrap().unwrap() {
+            assert!(batch.num_rows() <= 2);
+            for idx in 0..batch.num_rows() {
+                let id = batch.column(0).unwrap().logical_value(idx).unwrap();
+                let name = batch.column(1).unwrap().logical_value(idx).unwrap();
+                let (id, name) = (id.into_owned(), name.into_owned());
+                rows.push((id, name));
             }
         }
 
-        Ok(Array::new_with_validity_and_array_data(
-            DataType::Utf8,
-            validity,
-            values.into_data(),
-        ))
+        let expected = vec![
+            (ScalarValue::Int64(1), ScalarValue::from("a,\r\nb")),
+            (ScalarValue::Int64(2), ScalarValue::Null),
+            (ScalarValue::Int64(3), ScalarValue::from("c\"")),
+        ];
+        assert_eq!(expected, rows);
     }
 }
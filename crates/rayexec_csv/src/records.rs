@@ -0,0 +1,349 @@
+//! Building batches from complete records in a buffer.
+//!
+//! Records are decoded in two passes. The first pass finds the boundaries of
+//! every field using the structural scanner. The second pass builds each
+//! column directly from the field slices in the input, only copying data when
+//! a field needs its quotes removed.
+use std::ops::Range;
+
+use rayexec_error::{RayexecError, Result, ResultExt};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::batch_builder::{BatchBuilder, ColumnBuilder};
+use rayexec_execution::arrays::compute::cast::parse::{
+    BoolParser,
+    Float64Parser,
+    Int64Parser,
+    Parser,
+};
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::executor::builder::{
+    ArrayDataBuffer,
+    BooleanBuffer,
+    GermanVarlenBuffer,
+    PrimitiveBuffer,
+};
+use rayexec_execution::arrays::field::Schema;
+use rayexec_execution::arrays::storage::PrimitiveStorage;
+
+use crate::scanner::StructuralScanner;
+
+/// Field boundaries for complete records in some input.
+#[derive(Debug)]
+pub struct RecordIndex {
+    scanner: StructuralScanner,
+    quote: u8,
+    num_fields: usize,
+    /// Start and end offsets for every field, record by record.
+    spans: Vec<(usize, usize)>,
+    /// Reused buffer for boundaries found by the scanner.
+    boundaries: Vec<usize>,
+}
+
+impl RecordIndex {
+    pub fn new(scanner: StructuralScanner, quote: u8, num_fields: usize) -> Self {
+        RecordIndex {
+            scanner,
+            quote,
+            num_fields,
+            spans: Vec::new(),
+            boundaries: Vec::new(),
+        }
+    }
+
+    pub fn num_records(&self) -> usize {
+        self.spans.len() / self.num_fields
+    }
+
+    pub fn clear(&mut self) {
+        self.spans.clear();
+    }
+
+    /// Index the records in the input, replacing any existing records.
+    ///
+    /// The input should start at the beginning of a record and end with the
+    /// newline of the last record. Blank lines are skipped.
+    pub fn index(&mut self, input: &[u8]) -> Result<()> {
+        debug_assert!(input.is_empty() || input.ends_with(b"\n"));
+
+        self.spans.clear();
+        self.boundaries.clear();
+
+        let in_quotes = self
+            .scanner
+            .find_boundaries(input, false, &mut self.boundaries);
+        if in_quotes {
+            return Err(RayexecError::new("CSV input ends inside a quoted field"));
+        }
+
+        let mut start = 0;
+        let mut record_fields = 0;
+        for &pos in &self.boundaries {
+            let is_newline = input[pos] == b'\n';
+
+            let mut end = pos;
+            if is_newline && end > start && input[end - 1] == b'\r' {
+                end -= 1;
+            }
+
+            self.spans.push((start, end));
+            record_fields += 1;
+            start = pos + 1;
+
+            if is_newline {
+                if record_fields == 1 && self.spans.last() == Some(&(end, end)) {
+                    // Blank line.
+                    self.spans.pop();
+                } else if record_fields != self.num_fields {
+                    return Err(RayexecError::new(format!(
+                        "Invalid number of fields in record. Got {}, expected {}",
+                        record_fields, self.num_fields
+                    )));
+                }
+                record_fields = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the raw bytes for a field, including any quotes.
+    fn raw_field<'a>(&self, input: &'a [u8], record: usize, field: usize) -> &'a [u8] {
+        let (start, end) = self.spans[record * self.num_fields + field];
+        &input[start..end]
+    }
+
+    /// Get the value of a field, removing quotes if it's quoted.
+    ///
+    /// `scratch` is used to hold the value if quotes were removed.
+    fn field_value<'a>(
+        &self,
+        input: &'a [u8],
+        record: usize,
+        field: usize,
+        scratch: &'a mut Vec<u8>,
+    ) -> Result<&'a str> {
+        let raw = self.raw_field(input, record, field);
+
+        let value = if raw.first() == Some(&self.quote) {
+            unquote(raw, self.quote, scratch);
+            scratch.as_slice()
+        } else {
+            raw
+        };
+
+        std::str::from_utf8(value)
+            .context_fn(|| format!("Field '{field}' contains invalid UTF-8 data"))
+    }
+
+    /// Build a batch from a range of records.
+    pub fn build_batch(
+        &self,
+        input: &[u8],
+        schema: &Schema,
+        records: Range<usize>,
+    ) -> Result<Batch> {
+        let num_rows = records.len();
+
+        let mut batch = BatchBuilder::new(num_rows, schema.fields.len());
+        for (idx, field) in schema.fields.iter().enumerate() {
+            match &field.datatype {
+                DataType::Boolean => {
+                    batch.push_column(self.build_boolean(input, idx, records.clone())?)?
+                }
+                DataType::Int64 => batch.push_column(self.build_primitive(
+                    input,
+                    &field.datatype,
+                    idx,
+                    records.clone(),
+                    Int64Parser::new(),
+                )?)?,
+                DataType::Float64 => batch.push_column(self.build_primitive(
+                    input,
+                    &field.datatype,
+                    idx,
+                    records.clone(),
+                    Float64Parser::new(),
+                )?)?,
+                DataType::Utf8 => {
+                    batch.push_column(self.build_utf8(input, idx, records.clone())?)?
+                }
+                other => return Err(RayexecError::new(format!("Unhandled data type: {other}"))),
+            }
+        }
+
+        batch.finish()
+    }
+
+    fn build_boolean(
+        &self,
+        input: &[u8],
+        field_idx: usize,
+        records: Range<usize>,
+    ) -> Result<ColumnBuilder<BooleanBuffer>> {
+        let mut column =
+            ColumnBuilder::new(DataType::Boolean, BooleanBuffer::with_len(records.len()));
+        let mut scratch = Vec::new();
+
+        for (idx, record) in records.enumerate() {
+            let field = self.field_value(input, record, field_idx, &mut scratch)?;
+            if field.is_empty() {
+                column.put_null(idx);
+            } else {
+                let val = BoolParser.parse(field).ok_or_else(|| {
+                    RayexecError::new(format!("Failed to parse '{field}' into a boolean"))
+                })?;
+                column.put(idx, &val);
+            }
+        }
+
+        Ok(column)
+    }
+
+    fn build_primitive<T, P>(
+        &self,
+        input: &[u8],
+        datatype: &DataType,
+        field_idx: usize,
+        records: Range<usize>,
+        mut parser: P,
+    ) -> Result<ColumnBuilder<PrimitiveBuffer<T>>>
+    where
+        T: Default + Copy,
+        P: Parser<Type = T>,
+        PrimitiveBuffer<T>: ArrayDataBuffer<Type = T>,
+        Vec<T>: Into<PrimitiveStorage<T>>,
+    {
+        let mut column =
+            ColumnBuilder::new(datatype.clone(), PrimitiveBuffer::with_len(records.len()));
+        let mut scratch = Vec::new();
+
+        for (idx, record) in records.enumerate() {
+            let field = self.field_value(input, record, field_idx, &mut scratch)?;
+            if field.is_empty() {
+                column.put_null(idx);
+            } else {
+                let val = parser
+                    .parse(field)
+                    .ok_or_else(|| RayexecError::new(format!("Failed to parse '{field}'")))?;
+                column.put(idx, &val);
+            }
+        }
+
+        Ok(column)
+    }
+
+    fn build_utf8(
+        &self,
+        input: &[u8],
+        field_idx: usize,
+        records: Range<usize>,
+    ) -> Result<ColumnBuilder<GermanVarlenBuffer<str>>> {
+        let mut column =
+            ColumnBuilder::new(DataType::Utf8, GermanVarlenBuffer::with_len(records.len()));
+        let mut scratch = Vec::new();
+
+        for (idx, record) in records.enumerate() {
+            let field = self.field_value(input, record, field_idx, &mut scratch)?;
+            if field.is_empty() {
+                column.put_null(idx);
+            } else {
+                column.put(idx, field);
+            }
+        }
+
+        Ok(column)
+    }
+}
+
+/// Remove quotes from a quoted field, writing the result to `out`.
+///
+/// Doubled quotes inside the field are unescaped, and any data after the
+/// closing quote is kept as is.
+fn unquote(raw: &[u8], quote: u8, out: &mut Vec<u8>) {
+    out.clear();
+
+    let mut in_quotes = false;
+    let mut iter = raw.iter().copied().peekable();
+    while let Some(b) = iter.next() {
+        if b != quote {
+            out.push(b);
+        } else if in_quotes && iter.peek() == Some(&quote) {
+            // Escaped quote.
+            out.push(quote);
+            iter.next();
+        } else {
+            in_quotes = !in_quotes;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::DialectOptions;
+
+    fn index(input: &str, num_fields: usize) -> Result<RecordIndex> {
+        let dialect = DialectOptions::default();
+        let mut index =
+            RecordIndex::new(StructuralScanner::new(dialect), dialect.quote, num_fields);
+        index.index(input.as_bytes())?;
+        Ok(index)
+    }
+
+    fn values(index: &RecordIndex, input: &str) -> Vec<Vec<String>> {
+        let mut scratch = Vec::new();
+        (0..index.num_records())
+            .map(|record| {
+                (0..index.num_fields)
+                    .map(|field| {
+                        index
+                            .field_value(input.as_bytes(), record, field, &mut scratch)
+                            .unwrap()
+                            .to_string()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn index_simple() {
+        let input = "a,bb,ccc\nd,,f\n";
+        let index = index(input, 3).unwrap();
+
+        assert_eq!(2, index.num_records());
+        assert_eq!(
+            vec![vec!["a", "bb", "ccc"], vec!["d", "", "f"]],
+            values(&index, input)
+        );
+    }
+
+    #[test]
+    fn index_quoted() {
+        let input = "\"a,b\",\"say \"\"hi\"\"\"\n\"line\nbreak\",\"\"\n";
+        let index = index(input, 2).unwrap();
+
+        assert_eq!(
+            vec![vec!["a,b", "say \"hi\""], vec!["line\nbreak", ""]],
+            values(&index, input)
+        );
+    }
+
+    #[test]
+    fn index_crlf_and_blank_lines() {
+        let input = "a,b\r\n\r\n\nc,\"d\"\r\n";
+        let index = index(input, 2).unwrap();
+
+        assert_eq!(vec![vec!["a", "b"], vec!["c", "d"]], values(&index, input));
+    }
+
+    #[test]
+    fn index_wrong_number_of_fields() {
+        index("a,b\nc\n", 2).unwrap_err();
+    }
+
+    #[test]
+    fn index_unterminated_quote() {
+        index("a,\"b\n", 2).unwrap_err();
+    }
+}
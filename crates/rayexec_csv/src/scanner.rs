@@ -0,0 +1,291 @@
+//! Scanning for structural characters in csv input.
+//!
+//! Input is processed in 64 byte blocks, producing bitmasks for the quote,
+//! delimiter, and newline characters in each block. Quoted regions are found
+//! by taking the prefix xor of the quote mask, letting us find delimiters and
+//! newlines outside of quoted fields without branching on every byte.
+//!
+//! Escaped quotes (two quotes in a row) toggle the quoted state twice, so need
+//! no special handling.
+
+use crate::reader::DialectOptions;
+
+const BLOCK_SIZE: usize = 64;
+
+/// Bitmasks for the positions of characters in a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct BlockMasks {
+    quotes: u64,
+    delimiters: u64,
+    newlines: u64,
+}
+
+impl BlockMasks {
+    /// Get masks for delimiters and newlines that aren't in a quoted field,
+    /// along with if the block ends inside of quotes.
+    ///
+    /// `in_quotes` indicates if the block starts inside of quotes.
+    fn unquoted(&self, in_quotes: bool) -> (u64, u64, bool) {
+        let carry = if in_quotes { u64::MAX } else { 0 };
+        let inside = prefix_xor(self.quotes) ^ carry;
+        (
+            self.delimiters & !inside,
+            self.newlines & !inside,
+            inside >> 63 == 1,
+        )
+    }
+}
+
+/// Compute the prefix xor for a mask, with each bit set to the xor of itself
+/// and all bits below it.
+///
+/// For a quote mask, this sets the bits from an opening quote up to (but not
+/// including) its closing quote.
+const fn prefix_xor(mut mask: u64) -> u64 {
+    mask ^= mask << 1;
+    mask ^= mask << 2;
+    mask ^= mask << 4;
+    mask ^= mask << 8;
+    mask ^= mask << 16;
+    mask ^= mask << 32;
+    mask
+}
+
+/// Finds field and record boundaries in csv input.
+///
+/// Newlines are always '\n', a preceding '\r' is left for the caller to
+/// strip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructuralScanner {
+    delimiter: u8,
+    quote: u8,
+}
+
+impl StructuralScanner {
+    pub const fn new(dialect: DialectOptions) -> Self {
+        StructuralScanner {
+            delimiter: dialect.delimiter,
+            quote: dialect.quote,
+        }
+    }
+
+    /// Count the number of quote characters in the input.
+    pub fn count_quotes(&self, input: &[u8]) -> usize {
+        (0..input.len())
+            .step_by(BLOCK_SIZE)
+            .map(|offset| self.masks_at(input, offset).quotes.count_ones() as usize)
+            .sum()
+    }
+
+    /// Push the positions of all delimiters and newlines not in quoted fields
+    /// to `out`.
+    ///
+    /// `in_quotes` indicates if the input starts inside of a quoted field.
+    /// Returns if the input ends inside of a quoted field.
+    pub fn find_boundaries(&self, input: &[u8], mut in_quotes: bool, out: &mut Vec<usize>) -> bool {
+        for offset in (0..input.len()).step_by(BLOCK_SIZE) {
+            let (delimiters, newlines, end_in_quotes) =
+                self.masks_at(input, offset).unquoted(in_quotes);
+            in_quotes = end_in_quotes;
+
+            let mut mask = delimiters | newlines;
+            while mask != 0 {
+                out.push(offset + mask.trailing_zeros() as usize);
+                mask &= mask - 1;
+            }
+        }
+        in_quotes
+    }
+
+    /// Find the position of the first newline not in a quoted field.
+    ///
+    /// `in_quotes` indicates if the input starts inside of a quoted field.
+    pub fn find_record_end(&self, input: &[u8], mut in_quotes: bool) -> Option<usize> {
+        for offset in (0..input.len()).step_by(BLOCK_SIZE) {
+            let (_, newlines, end_in_quotes) = self.masks_at(input, offset).unquoted(in_quotes);
+            if newlines != 0 {
+                return Some(offset + newlines.trailing_zeros() as usize);
+            }
+            in_quotes = end_in_quotes;
+        }
+        None
+    }
+
+    /// Find the position of the last newline not in a quoted field, assuming
+    /// the input starts outside of quotes.
+    pub fn find_last_record_end(&self, input: &[u8]) -> Option<usize> {
+        let mut in_quotes = false;
+        let mut last = None;
+        for offset in (0..input.len()).step_by(BLOCK_SIZE) {
+            let (_, newlines, end_in_quotes) = self.masks_at(input, offset).unquoted(in_quotes);
+            if newlines != 0 {
+                last = Some(offset + (63 - newlines.leading_zeros() as usize));
+            }
+            in_quotes = end_in_quotes;
+        }
+        last
+    }
+
+    /// Get the masks for the block starting at `offset`.
+    ///
+    /// Blocks at the end of the input that are shorter than the block size
+    /// are padded, with the masks only including bits for the actual input.
+    fn masks_at(&self, input: &[u8], offset: usize) -> BlockMasks {
+        let rem = &input[offset..];
+        if rem.len() >= BLOCK_SIZE {
+            return self.block_masks(rem[..BLOCK_SIZE].try_into().unwrap());
+        }
+
+        let mut block = [0; BLOCK_SIZE];
+        block[..rem.len()].copy_from_slice(rem);
+        let masks = self.block_masks(&block);
+
+        let valid = (1 << rem.len()) - 1;
+        BlockMasks {
+            quotes: masks.quotes & valid,
+            delimiters: masks.delimiters & valid,
+            newlines: masks.newlines & valid,
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn block_masks(&self, block: &[u8; BLOCK_SIZE]) -> BlockMasks {
+        use std::arch::x86_64::{
+            __m128i,
+            _mm_cmpeq_epi8,
+            _mm_loadu_si128,
+            _mm_movemask_epi8,
+            _mm_set1_epi8,
+        };
+
+        // SAFETY: SSE2 is part of the x86_64 baseline, and every load reads 16
+        // bytes within the block.
+        unsafe {
+            let quote = _mm_set1_epi8(self.quote as i8);
+            let delimiter = _mm_set1_epi8(self.delimiter as i8);
+            let newline = _mm_set1_epi8(b'\n' as i8);
+
+            let mut masks = BlockMasks::default();
+            for lane in 0..(BLOCK_SIZE / 16) {
+                let bytes = _mm_loadu_si128(block.as_ptr().add(lane * 16) as *const __m128i);
+                let shift = lane * 16;
+
+                masks.quotes |=
+                    (_mm_movemask_epi8(_mm_cmpeq_epi8(bytes, quote)) as u32 as u64) << shift;
+                masks.delimiters |=
+                    (_mm_movemask_epi8(_mm_cmpeq_epi8(bytes, delimiter)) as u32 as u64) << shift;
+                masks.newlines |=
+                    (_mm_movemask_epi8(_mm_cmpeq_epi8(bytes, newline)) as u32 as u64) << shift;
+            }
+
+            masks
+        }
+    }
+
+    // TODO: NEON and wasm simd128
+    #[cfg(not(target_arch = "x86_64"))]
+    fn block_masks(&self, block: &[u8; BLOCK_SIZE]) -> BlockMasks {
+        self.block_masks_scalar(block)
+    }
+
+    #[allow(dead_code)]
+    fn block_masks_scalar(&self, block: &[u8; BLOCK_SIZE]) -> BlockMasks {
+        let mut masks = BlockMasks::default();
+        for (idx, &b) in block.iter().enumerate() {
+            masks.quotes |= ((b == self.quote) as u64) << idx;
+            masks.delimiters |= ((b == self.delimiter) as u64) << idx;
+            masks.newlines |= ((b == b'\n') as u64) << idx;
+        }
+        masks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scanner() -> StructuralScanner {
+        StructuralScanner::new(DialectOptions::default())
+    }
+
+    fn boundaries(input: &str, in_quotes: bool) -> (Vec<usize>, bool) {
+        let mut out = Vec::new();
+        let end_in_quotes = scanner().find_boundaries(input.as_bytes(), in_quotes, &mut out);
+        (out, end_in_quotes)
+    }
+
+    #[test]
+    fn prefix_xor_quoted_regions() {
+        // Quotes at 1 and 4.
+        assert_eq!(0b01110, prefix_xor(0b10010));
+    }
+
+    #[test]
+    fn simd_masks_match_scalar() {
+        let scanner = scanner();
+        let input: Vec<u8> = (0..BLOCK_SIZE)
+            .map(|idx| [b'a', b',', b'"', b'\n', b'\r'][idx * 7 % 5])
+            .collect();
+        let block: &[u8; BLOCK_SIZE] = input.as_slice().try_into().unwrap();
+
+        assert_eq!(
+            scanner.block_masks_scalar(block),
+            scanner.block_masks(block)
+        );
+    }
+
+    #[test]
+    fn unquoted_boundaries() {
+        let (out, in_quotes) = boundaries("a,bb,ccc\nd,e,f\n", false);
+        assert_eq!(vec![1, 4, 8, 10, 12, 14], out);
+        assert!(!in_quotes);
+    }
+
+    #[test]
+    fn quoted_boundaries() {
+        // Delimiter and newline inside quotes, plus an escaped quote.
+        let (out, in_quotes) = boundaries("\"a,\nb\",\"c\"\"d\"\n", false);
+        assert_eq!(vec![6, 13], out);
+        assert!(!in_quotes);
+    }
+
+    #[test]
+    fn boundaries_across_blocks() {
+        // Quoted field spanning the first block boundary.
+        let mut input = "a".repeat(60);
+        input.push_str(",\"bc,\nde\",f\n");
+
+        let (out, in_quotes) = boundaries(&input, false);
+        assert_eq!(vec![60, 69, 71], out);
+        assert!(!in_quotes);
+    }
+
+    #[test]
+    fn boundaries_starting_in_quotes() {
+        let (out, in_quotes) = boundaries("a,b\",c\n", true);
+        assert_eq!(vec![4, 6], out);
+        assert!(!in_quotes);
+
+        let (out, in_quotes) = boundaries("a,\"b\n", false);
+        assert_eq!(vec![1], out);
+        assert!(in_quotes);
+    }
+
+    #[test]
+    fn record_ends() {
+        let scanner = scanner();
+        let input = "\"a\nb\",c\nd,e\nf";
+
+        assert_eq!(Some(7), scanner.find_record_end(input.as_bytes(), false));
+        // Starting inside quotes means the first newline is a record end.
+        assert_eq!(Some(2), scanner.find_record_end(input.as_bytes(), true));
+        assert_eq!(Some(11), scanner.find_last_record_end(input.as_bytes()));
+        assert_eq!(None, scanner.find_record_end(b"abc", false));
+    }
+
+    #[test]
+    fn count_quotes() {
+        let input = "\"a\"".repeat(30);
+        assert_eq!(60, scanner().count_quotes(input.as_bytes()));
+    }
+}
@@ -3,6 +3,7 @@ use std::io::Write as _;
 use csv::ByteRecord;
 use rayexec_error::{Result, ResultExt};
 use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::compute::cast::format::FloatNotation;
 use rayexec_execution::arrays::field::Schema;
 use rayexec_execution::arrays::format::{FormatOptions, Formatter};
 
@@ -19,6 +20,9 @@ pub struct CsvEncoder {
     /// Dialect of csv we're writing.
     dialect: DialectOptions,
 
+    /// Options for formatting values.
+    format_options: FormatOptions<'static>,
+
     /// Buffer used for formatting the batch.
     format_buf: Vec<u8>,
 
@@ -32,14 +36,21 @@ impl CsvEncoder {
         CsvEncoder {
             schema,
             dialect,
+            format_options: FormatOptions::new(),
             did_write_header: false,
             format_buf: Vec::with_capacity(1024),
             record,
         }
     }
 
+    /// Set the notation to use when writing floats.
+    pub fn with_float_notation(mut self, notation: FloatNotation) -> Self {
+        self.format_options.float_notation = notation;
+        self
+    }
+
     pub fn encode(&mut self, batch: &Batch, output_buf: &mut Vec<u8>) -> Result<()> {
-        const FORMATTER: Formatter = Formatter::new(FormatOptions::new());
+        let formatter = Formatter::new(self.format_options.clone());
 
         let mut csv_writer = csv::WriterBuilder::new()
             .delimiter(self.dialect.delimiter)
@@ -61,7 +72,7 @@ impl CsvEncoder {
             self.record.clear();
 
             for col in batch.columns() {
-                let scalar = FORMATTER
+                let scalar = formatter
                     .format_array_value(col, row)
                     .expect("row to exist");
                 self.format_buf.clear();
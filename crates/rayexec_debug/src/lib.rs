@@ -47,9 +47,7 @@ impl DebugDataSource {
         for table in &opts.preloads {
             catalog_storage.tables.insert(
                 [table.schema.clone(), table.name.clone()],
-                TableEntry {
-                    columns: table.columns.clone(),
-                },
+                TableEntry::new(table.columns.clone()),
             );
         }
 
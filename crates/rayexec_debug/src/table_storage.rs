@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use futures::future::BoxFuture;
@@ -5,11 +6,13 @@ use parking_lot::Mutex;
 use rayexec_error::{RayexecError, Result};
 use rayexec_execution::arrays::batch::Batch;
 use rayexec_execution::arrays::field::Field;
+use rayexec_execution::database::catalog::CatalogTx;
 use rayexec_execution::database::catalog_entry::CatalogEntry;
 use rayexec_execution::execution::operators::sink::PartitionSink;
 use rayexec_execution::storage::table_storage::{
     DataTable,
     DataTableScan,
+    DataVersion,
     ProjectedScan,
     Projections,
     TableStorage,
@@ -55,6 +58,7 @@ impl DebugTableStorage {
                     },
                     DebugDataTable {
                         data: Arc::new(Mutex::new(vec![table.data.clone()])),
+                        version: Arc::new(AtomicU64::new(0)),
                     },
                 )
                 .expect("table to not already exist");
@@ -65,7 +69,12 @@ impl DebugTableStorage {
 }
 
 impl TableStorage for DebugTableStorage {
-    fn data_table(&self, schema: &str, ent: &CatalogEntry) -> Result<Box<dyn DataTable>> {
+    fn data_table(
+        &self,
+        _tx: &CatalogTx,
+        schema: &str,
+        ent: &CatalogEntry,
+    ) -> Result<Box<dyn DataTable>> {
         let key = TableKey {
             schema: schema.to_string(),
             name: ent.name.clone(),
@@ -125,6 +134,11 @@ impl TableStorage for DebugTableStorage {
 #[derive(Debug, Clone, Default)]
 pub struct DebugDataTable {
     data: Arc<Mutex<Vec<Batch>>>,
+    /// Number of inserts into this table.
+    ///
+    /// Only unique within a single table, tables in different debug sources
+    /// will report the same versions.
+    version: Arc<AtomicU64>,
 }
 
 impl DataTable for DebugDataTable {
@@ -132,6 +146,7 @@ impl DataTable for DebugDataTable {
         &self,
         projections: Projections,
         num_partitions: usize,
+        _batch_size: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>> {
         let mut scans: Vec<_> = (0..num_partitions)
             .map(|_| DebugDataTableScan { data: Vec::new() })
@@ -158,12 +173,17 @@ impl DataTable for DebugDataTable {
                 Box::new(DebugDataTableInsert {
                     collected: Vec::new(),
                     data: self.data.clone(),
+                    version: self.version.clone(),
                 }) as _
             })
             .collect();
 
         Ok(inserts)
     }
+
+    fn data_version(&self) -> DataVersion {
+        DataVersion::Version(self.version.load(Ordering::Relaxed))
+    }
 }
 
 #[derive(Debug)]
@@ -181,6 +201,7 @@ impl DataTableScan for DebugDataTableScan {
 pub struct DebugDataTableInsert {
     collected: Vec<Batch>,
     data: Arc<Mutex<Vec<Batch>>>,
+    version: Arc<AtomicU64>,
 }
 
 impl PartitionSink for DebugDataTableInsert {
@@ -195,6 +216,7 @@ impl PartitionSink for DebugDataTableInsert {
         Box::pin(async {
             let mut data = self.data.lock();
             data.append(&mut self.collected);
+            self.version.fetch_add(1, Ordering::Relaxed);
             Ok(())
         })
     }
@@ -17,8 +17,9 @@ impl DataTable for DeltaDataTable {
         &self,
         projections: Projections,
         num_partitions: usize,
+        batch_size: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>> {
-        let table_scans = self.table.scan(projections, num_partitions)?;
+        let table_scans = self.table.scan(projections, num_partitions, batch_size)?;
         let scans: Vec<_> = table_scans
             .into_iter()
             .map(|scan| Box::new(DeltaTableScan { scan }) as _)
@@ -11,6 +11,7 @@ use rayexec_execution::storage::table_storage::Projections;
 use rayexec_io::location::{AccessConfig, FileLocation};
 use rayexec_io::{FileProvider, FileSource};
 use rayexec_parquet::metadata::Metadata;
+use rayexec_parquet::metadata_cache::prefetch_metadata;
 use rayexec_parquet::reader::AsyncBatchReader;
 use serde_json::Deserializer;
 
@@ -131,10 +132,15 @@ impl Table {
         schema_from_struct_type(schema)
     }
 
-    // TODO: batch size, projection
+    // TODO: projection
     // TODO: Reference partition values.
     // TODO: Properly filter based on deletion vector.
-    pub fn scan(&self, projections: Projections, num_partitions: usize) -> Result<Vec<TableScan>> {
+    pub fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<TableScan>> {
         // Each partitions gets some subset of files.
         let mut paths: Vec<_> = (0..num_partitions).map(|_| VecDeque::new()).collect();
 
@@ -154,6 +160,8 @@ impl Table {
                 paths: partition_paths,
                 provider: self.provider.clone(),
                 conf: self.conf.clone(),
+                batch_size,
+                metadata: None,
                 current: None,
             })
             .collect();
@@ -174,6 +182,13 @@ pub struct TableScan {
     /// File provider for getting the actual file sources.
     provider: Arc<dyn FileProvider>,
     conf: AccessConfig,
+    /// Target number of rows per batch read from the data files.
+    batch_size: usize,
+    /// Metadata for each remaining path.
+    ///
+    /// Prefetched for all paths on the first read so that footers are read
+    /// concurrently instead of one file at a time.
+    metadata: Option<VecDeque<Arc<Metadata>>>,
     /// Current reader, initially empty and populated on first stream.
     ///
     /// Once a reader runs out, the next file is loaded, and gets placed here.
@@ -183,24 +198,30 @@ pub struct TableScan {
 impl TableScan {
     /// Read the next batch.
     pub async fn read_next(&mut self) -> Result<Option<Batch>> {
+        if self.metadata.is_none() {
+            // TODO: Need to split path into segments.
+            let locations = self
+                .paths
+                .iter()
+                .map(|path| self.root.join([path]))
+                .collect::<Result<Vec<_>>>()?;
+            let metadata = prefetch_metadata(self.provider.as_ref(), locations, &self.conf).await?;
+            self.metadata = Some(metadata.into());
+        }
+
         loop {
             if self.current.is_none() {
                 let path = match self.paths.pop_front() {
                     Some(path) => path,
                     None => return Ok(None), // We're done.
                 };
+                let metadata = self
+                    .metadata
+                    .as_mut()
+                    .and_then(|metadata| metadata.pop_front())
+                    .ok_or_else(|| RayexecError::new("Missing prefetched metadata for file"))?;
 
-                self.current = Some(
-                    Self::load_reader(
-                        &self.root,
-                        &self.conf,
-                        path,
-                        self.provider.as_ref(),
-                        &self.schema,
-                        self.projections.clone(),
-                    )
-                    .await?,
-                )
+                self.current = Some(self.load_reader(path, metadata)?)
             }
 
             match self.current.as_mut().unwrap().read_next().await {
@@ -215,30 +236,24 @@ impl TableScan {
         }
     }
 
-    async fn load_reader(
-        root: &FileLocation,
-        conf: &AccessConfig,
+    fn load_reader(
+        &self,
         path: String,
-        provider: &dyn FileProvider,
-        schema: &Schema,
-        projections: Projections,
+        metadata: Arc<Metadata>,
     ) -> Result<AsyncBatchReader<Box<dyn FileSource>>> {
         // TODO: Need to split path into segments.
-        let location = root.join([path])?;
-        let mut source = provider.file_source(location, conf)?;
+        let location = self.root.join([path])?;
+        let source = self.provider.file_source(location, &self.conf)?;
 
-        let size = source.size().await?;
-        let metadata = Arc::new(Metadata::new_from_source(source.as_mut(), size).await?);
         let row_groups: VecDeque<_> = (0..metadata.decoded_metadata.row_groups().len()).collect();
 
-        const BATCH_SIZE: usize = 4096; // TODO
         let reader = AsyncBatchReader::try_new(
             source,
             row_groups,
             metadata,
-            schema,
-            BATCH_SIZE,
-            projections,
+            &self.schema,
+            self.batch_size,
+            self.projections.clone(),
         )?;
 
         Ok(reader)
@@ -67,14 +67,15 @@ impl<R: Runtime> ScanPlanner for ReadDelta<R> {
 impl<R: Runtime> ReadDelta<R> {
     async fn plan_inner(
         self,
-        _context: &DatabaseContext,
+        context: &DatabaseContext,
         positional_inputs: Vec<OwnedScalarValue>,
         named_inputs: HashMap<String, OwnedScalarValue>,
     ) -> Result<PlannedTableFunction> {
         let (location, conf) =
             try_location_and_access_config_from_args(&self, &positional_inputs, &named_inputs)?;
+        let runtime = context.scan_runtime(&self.runtime);
 
-        let provider = self.runtime.file_provider();
+        let provider = runtime.file_provider();
 
         let table = Table::load(location.clone(), provider, conf.clone()).await?;
         let schema = table.table_schema()?;
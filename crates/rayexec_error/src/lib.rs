@@ -11,10 +11,185 @@ pub type Result<T, E = RayexecError> = std::result::Result<T, E>;
 macro_rules! not_implemented {
     ($($arg:tt)+) => {{
         let msg = format!($($arg)+);
-        return Err($crate::RayexecError::new(format!("Not yet implemented: {msg}")));
+        return Err(
+            $crate::RayexecError::new(format!("Not yet implemented: {msg}"))
+                .with_kind($crate::ErrorKind::NotImplemented),
+        );
     }};
 }
 
+/// Kind of an error.
+///
+/// Allows for matching on errors programmatically, and for mapping errors to
+/// SQLSTATE codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ErrorKind {
+    /// Error without a more specific kind.
+    #[default]
+    Other,
+    /// Feature isn't supported yet.
+    NotImplemented,
+    /// Error parsing SQL.
+    SyntaxError,
+    /// An argument or option had an invalid value.
+    InvalidArgument,
+    /// Referenced catalog doesn't exist.
+    CatalogNotFound,
+    /// Referenced schema doesn't exist.
+    SchemaNotFound,
+    /// Referenced table or view doesn't exist.
+    TableNotFound,
+    /// Referenced column doesn't exist.
+    ColumnNotFound,
+    /// Referenced function doesn't exist.
+    FunctionNotFound,
+    /// Object being created already exists.
+    AlreadyExists,
+    /// Types provided don't match the types expected.
+    TypeMismatch,
+    /// Not allowed to access a resource.
+    PermissionDenied,
+    /// Error reading or writing data.
+    IoError,
+    /// Remote source didn't respond or send data in time.
+    Timeout,
+    /// Query was canceled.
+    Cancelled,
+    /// Ran out of some resource, e.g. memory.
+    ResourceExhausted,
+    /// Transaction couldn't be committed due to concurrent changes.
+    SerializationFailure,
+    /// Statement isn't valid for the current transaction state, e.g. COMMIT
+    /// without an active transaction.
+    InvalidTransactionState,
+    /// A NULL value was written to a NOT NULL column.
+    NotNullViolation,
+    /// A written value duplicates an existing PRIMARY KEY value.
+    UniqueViolation,
+    /// A written value failed a CHECK constraint.
+    CheckViolation,
+}
+
+impl ErrorKind {
+    const ALL: [ErrorKind; 21] = [
+        ErrorKind::Other,
+        ErrorKind::NotImplemented,
+        ErrorKind::SyntaxError,
+        ErrorKind::InvalidArgument,
+        ErrorKind::CatalogNotFound,
+        ErrorKind::SchemaNotFound,
+        ErrorKind::TableNotFound,
+        ErrorKind::ColumnNotFound,
+        ErrorKind::FunctionNotFound,
+        ErrorKind::AlreadyExists,
+        ErrorKind::TypeMismatch,
+        ErrorKind::PermissionDenied,
+        ErrorKind::IoError,
+        ErrorKind::Timeout,
+        ErrorKind::Cancelled,
+        ErrorKind::ResourceExhausted,
+        ErrorKind::SerializationFailure,
+        ErrorKind::InvalidTransactionState,
+        ErrorKind::NotNullViolation,
+        ErrorKind::UniqueViolation,
+        ErrorKind::CheckViolation,
+    ];
+
+    /// Get the SQLSTATE code for this kind of error.
+    pub const fn sqlstate(&self) -> &'static str {
+        match self {
+            Self::Other => "XX000",
+            Self::NotImplemented => "0A000",
+            Self::SyntaxError => "42601",
+            Self::InvalidArgument => "22023",
+            Self::CatalogNotFound => "3D000",
+            Self::SchemaNotFound => "3F000",
+            Self::TableNotFound => "42P01",
+            Self::ColumnNotFound => "42703",
+            Self::FunctionNotFound => "42883",
+            Self::AlreadyExists => "42710",
+            Self::TypeMismatch => "42804",
+            Self::PermissionDenied => "42501",
+            Self::IoError => "58030",
+            Self::Timeout => "HYT00",
+            Self::Cancelled => "57014",
+            Self::ResourceExhausted => "53000",
+            Self::SerializationFailure => "40001",
+            Self::InvalidTransactionState => "25000",
+            Self::NotNullViolation => "23502",
+            Self::UniqueViolation => "23505",
+            Self::CheckViolation => "23514",
+        }
+    }
+
+    /// Get the kind of error for a SQLSTATE code.
+    ///
+    /// Unknown codes map to `Other`.
+    pub fn from_sqlstate(sqlstate: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.sqlstate() == sqlstate)
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Byte offsets into a SQL string that an error originated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Inclusive start offset.
+    pub start: usize,
+    /// Exclusive end offset.
+    pub end: usize,
+}
+
+impl Span {
+    /// Create a span covering both spans.
+    pub fn merge(self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// Render the line of the SQL string containing the span, with carets
+    /// underneath the spanned text.
+    ///
+    /// Returns None if the span is out of bounds for the string.
+    pub fn render_snippet(&self, sql: &str) -> Option<String> {
+        if self.start > self.end || self.end > sql.len() {
+            return None;
+        }
+
+        let line_start = sql[..self.start]
+            .rfind('\n')
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+        let line_end = sql[self.start..]
+            .find('\n')
+            .map(|idx| idx + self.start)
+            .unwrap_or(sql.len());
+        let line_num = sql[..line_start].matches('\n').count() + 1;
+
+        let prefix = format!("LINE {line_num}: ");
+        let line = sql.get(line_start..line_end)?;
+        let offset = sql.get(line_start..self.start)?.chars().count();
+        // Only underline up to the end of the first line.
+        let width = sql.get(self.start..self.end.min(line_end))?.chars().count();
+
+        Some(format!(
+            "{prefix}{line}\n{}{}",
+            " ".repeat(prefix.len() + offset),
+            "^".repeat(width.max(1)),
+        ))
+    }
+}
+
 // TODO: Implement partial eq on msg
 #[derive(Debug)]
 pub struct RayexecError {
@@ -23,8 +198,12 @@ pub struct RayexecError {
 
 #[derive(Debug)]
 struct RayexecErrorInner {
+    /// Kind of the error.
+    pub kind: ErrorKind,
     /// Message for the error.
     pub msg: String,
+    /// Location in the SQL string that caused the error.
+    pub span: Option<Span>,
     /// Source of the error.
     pub source: Option<Box<dyn Error + Send + Sync>>,
     /// Captured backtrace for the error.
@@ -49,7 +228,9 @@ impl RayexecError {
     pub fn new(msg: impl Into<String>) -> Self {
         RayexecError {
             inner: Box::new(RayexecErrorInner {
+                kind: ErrorKind::Other,
                 msg: msg.into(),
+                span: None,
                 source: None,
                 backtrace: Backtrace::capture(),
                 extra_fields: Vec::new(),
@@ -57,10 +238,17 @@ impl RayexecError {
         }
     }
 
+    /// Create a new error wrapping a source error.
+    ///
+    /// The kind and span are inherited from the source if they're known.
     pub fn with_source(msg: impl Into<String>, source: Box<dyn Error + Send + Sync>) -> Self {
         RayexecError {
             inner: Box::new(RayexecErrorInner {
+                kind: source_error_kind(source.as_ref()),
                 msg: msg.into(),
+                span: source
+                    .downcast_ref::<RayexecError>()
+                    .and_then(|err| err.span()),
                 source: Some(source),
                 backtrace: Backtrace::capture(),
                 extra_fields: Vec::new(),
@@ -68,6 +256,22 @@ impl RayexecError {
         }
     }
 
+    /// Set the kind of this error.
+    pub fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.inner.kind = kind;
+        self
+    }
+
+    /// Set the location in the SQL string that caused this error.
+    ///
+    /// Does nothing if `span` is None, keeping any existing span.
+    pub fn with_span(mut self, span: Option<Span>) -> Self {
+        if span.is_some() {
+            self.inner.span = span;
+        }
+        self
+    }
+
     pub fn with_field<K, V>(mut self, key: K, value: V) -> Self
     where
         K: Into<String>,
@@ -96,6 +300,14 @@ impl RayexecError {
         self
     }
 
+    pub fn kind(&self) -> ErrorKind {
+        self.inner.kind
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.inner.span
+    }
+
     pub fn get_msg(&self) -> &str {
         self.inner.msg.as_str()
     }
@@ -117,6 +329,25 @@ impl From<std::io::Error> for RayexecError {
     }
 }
 
+fn io_error_kind(err: &std::io::Error) -> ErrorKind {
+    match err.kind() {
+        std::io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+        std::io::ErrorKind::OutOfMemory => ErrorKind::ResourceExhausted,
+        _ => ErrorKind::IoError,
+    }
+}
+
+/// Get the kind for an error being wrapped as the source of another error.
+fn source_error_kind(err: &(dyn Error + 'static)) -> ErrorKind {
+    if let Some(err) = err.downcast_ref::<RayexecError>() {
+        return err.kind();
+    }
+    if let Some(err) = err.downcast_ref::<std::io::Error>() {
+        return io_error_kind(err);
+    }
+    ErrorKind::Other
+}
+
 impl From<erased_serde::Error> for RayexecError {
     fn from(value: erased_serde::Error) -> Self {
         Self::with_source("Serialization error", Box::new(value))
@@ -197,3 +428,48 @@ impl<T> OptionExt<T> for Option<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqlstate_roundtrip() {
+        for kind in ErrorKind::ALL {
+            assert_eq!(kind, ErrorKind::from_sqlstate(kind.sqlstate()));
+        }
+        assert_eq!(ErrorKind::Other, ErrorKind::from_sqlstate("99999"));
+    }
+
+    #[test]
+    fn render_snippet() {
+        let sql = "select a,\n  nope\nfrom t";
+        let span = Span { start: 12, end: 16 };
+        assert_eq!(
+            "LINE 2:   nope\n          ^^^^",
+            span.render_snippet(sql).unwrap()
+        );
+
+        // End of input.
+        let span = Span { start: 23, end: 23 };
+        assert_eq!(
+            "LINE 3: from t\n              ^",
+            span.render_snippet(sql).unwrap()
+        );
+
+        let span = Span { start: 20, end: 40 };
+        assert_eq!(None, span.render_snippet(sql));
+    }
+
+    #[test]
+    fn context_retains_kind() {
+        let res: Result<()> = Err(RayexecError::new("missing").with_kind(ErrorKind::TableNotFound));
+        let err = res.context("failed to plan").unwrap_err();
+        assert_eq!(ErrorKind::TableNotFound, err.kind());
+
+        let res: std::result::Result<(), _> =
+            Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+        let err = res.context("failed to open").unwrap_err();
+        assert_eq!(ErrorKind::PermissionDenied, err.kind());
+    }
+}
@@ -151,6 +151,22 @@ impl Array {
         &self.datatype
     }
 
+    /// Replace the data type of the array without modifying the underlying
+    /// data.
+    ///
+    /// Errors if the new data type doesn't share the same physical type as the
+    /// current data type.
+    pub fn try_with_datatype(mut self, datatype: DataType) -> Result<Self> {
+        if self.datatype.physical_type()? != datatype.physical_type()? {
+            return Err(RayexecError::new(format!(
+                "Cannot reinterpret array of type {} as {datatype}",
+                self.datatype
+            )));
+        }
+        self.datatype = datatype;
+        Ok(self)
+    }
+
     pub fn has_selection(&self) -> bool {
         self.selection.is_some()
     }
@@ -254,7 +270,7 @@ impl Array {
     pub fn physical_type(&self) -> PhysicalType {
         match self.data.physical_type() {
             PhysicalType::Binary => match self.datatype {
-                DataType::Utf8 => PhysicalType::Utf8,
+                DataType::Utf8 | DataType::Json => PhysicalType::Utf8,
                 _ => PhysicalType::Binary,
             },
             other => other,
@@ -423,13 +439,29 @@ impl Array {
                 },
                 |v, buf| buf.put(&v),
             ),
+            ArrayData::Binary(BinaryData::German(storage)) => {
+                // Only select the metadata, the data buffer is shared with
+                // this array.
+                let selection = self.selection_vector().expect("selection to exist");
+                let data = storage.select(selection.iter_locations())?;
+                let validity = self.validity().map(|validity| {
+                    selection
+                        .iter_locations()
+                        .map(|idx| validity.value(idx))
+                        .collect::<Bitmap>()
+                        .into()
+                });
+
+                Ok(Array {
+                    datatype: self.datatype.clone(),
+                    selection: None,
+                    validity,
+                    data: data.into(),
+                })
+            }
             ArrayData::Binary(_) => {
                 // Use the german varlen storage for all output varlen arrays,
                 // even if the input use using some other variant.
-                //
-                // TODO: We could special case german varlen input and clone the
-                // data while just selecting the appropriate metadata. Instead
-                // this will just copy everything.
                 if self.datatype().is_utf8() {
                     UnaryExecutor::execute::<PhysicalUtf8, _, _>(
                         self,
@@ -450,7 +482,33 @@ impl Array {
                     )
                 }
             }
-            ArrayData::List(_) => Err(RayexecError::new("Cannot yet unselect list arrays")),
+            ArrayData::List(list) => {
+                // Only select the metadata, the child array is shared with
+                // this array.
+                let selection = self.selection_vector().expect("selection to exist");
+                let metadata: Vec<_> = selection
+                    .iter_locations()
+                    .map(|idx| list.metadata.as_slice()[idx])
+                    .collect();
+                let validity = self.validity().map(|validity| {
+                    selection
+                        .iter_locations()
+                        .map(|idx| validity.value(idx))
+                        .collect::<Bitmap>()
+                        .into()
+                });
+
+                Ok(Array {
+                    datatype: self.datatype.clone(),
+                    selection: None,
+                    validity,
+                    data: ListStorage {
+                        metadata: metadata.into(),
+                        array: list.array.clone(),
+                    }
+                    .into(),
+                })
+            }
         }
     }
 
@@ -554,7 +612,7 @@ impl Array {
                 ArrayData::Interval(arr) => arr.as_ref().as_ref()[idx].into(),
                 _other => return Err(array_not_valid_for_type_err(&self.datatype)),
             },
-            DataType::Utf8 => {
+            DataType::Utf8 | DataType::Json => {
                 let v = match &self.data {
                     ArrayData::Binary(BinaryData::Binary(arr)) => arr
                         .get(idx)
@@ -568,9 +626,13 @@ impl Array {
                     _other => return Err(array_not_valid_for_type_err(&self.datatype)),
                 };
                 let s = std::str::from_utf8(v).context("binary data not valid utf8")?;
-                s.into()
+                if self.datatype.is_json() {
+                    ScalarValue::Json(s.into())
+                } else {
+                    s.into()
+                }
             }
-            DataType::Binary => {
+            DataType::Binary | DataType::Geometry => {
                 let v = match &self.data {
                     ArrayData::Binary(BinaryData::Binary(arr)) => arr
                         .get(idx)
@@ -583,7 +645,11 @@ impl Array {
                         .ok_or_else(|| RayexecError::new("missing data"))?,
                     _other => return Err(array_not_valid_for_type_err(&self.datatype)),
                 };
-                v.into()
+                if self.datatype.is_geometry() {
+                    ScalarValue::Geometry(v.into())
+                } else {
+                    v.into()
+                }
             }
             DataType::Struct(_) => not_implemented!("get value: struct"),
             DataType::List(_) => match &self.data {
@@ -595,7 +661,13 @@ impl Array {
                         .ok_or_else(|| RayexecError::new("Out of bounds"))?;
 
                     let vals = (meta.offset..meta.offset + meta.len)
-                        .map(|idx| list.array.physical_scalar(idx as usize))
+                        .map(|idx| {
+                            let idx = idx as usize;
+                            match list.array.validity() {
+                                Some(validity) if !validity.value(idx) => Ok(ScalarValue::Null),
+                                _ => list.array.physical_scalar(idx),
+                            }
+                        })
                         .collect::<Result<Vec<_>>>()?;
 
                     ScalarValue::List(vals)
@@ -711,13 +783,13 @@ impl Array {
                     None => false,
                 },
             ),
-            ScalarValue::Utf8(v) => {
+            ScalarValue::Utf8(v) | ScalarValue::Json(v) => {
                 UnaryExecutor::value_at::<PhysicalUtf8>(self, row).map(|arr_val| match arr_val {
                     Some(arr_val) => arr_val == v.as_ref(),
                     None => false,
                 })
             }
-            ScalarValue::Binary(v) => {
+            ScalarValue::Binary(v) | ScalarValue::Geometry(v) => {
                 UnaryExecutor::value_at::<PhysicalBinary>(self, row).map(|arr_val| match arr_val {
                     Some(arr_val) => arr_val == v.as_ref(),
                     None => false,
@@ -966,6 +1038,35 @@ impl ArrayData {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Get the size in bytes of the data for the array.
+    ///
+    /// This will not include validity or metadata size in the calculation, and
+    /// doesn't account for storage shared with other arrays.
+    pub fn data_size_bytes(&self) -> usize {
+        match self {
+            Self::UntypedNull(_) => 0,
+            Self::Boolean(s) => s.len().div_ceil(8),
+            Self::Float16(s) => s.data_size_bytes(),
+            Self::Float32(s) => s.data_size_bytes(),
+            Self::Float64(s) => s.data_size_bytes(),
+            Self::Int8(s) => s.data_size_bytes(),
+            Self::Int16(s) => s.data_size_bytes(),
+            Self::Int32(s) => s.data_size_bytes(),
+            Self::Int64(s) => s.data_size_bytes(),
+            Self::Int128(s) => s.data_size_bytes(),
+            Self::UInt8(s) => s.data_size_bytes(),
+            Self::UInt16(s) => s.data_size_bytes(),
+            Self::UInt32(s) => s.data_size_bytes(),
+            Self::UInt64(s) => s.data_size_bytes(),
+            Self::UInt128(s) => s.data_size_bytes(),
+            Self::Interval(s) => s.data_size_bytes(),
+            Self::Binary(bin) => bin.binary_data_size_bytes(),
+            Self::List(s) => {
+                s.metadata.data_size_bytes() + s.inner_array().array_data().data_size_bytes()
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1100,6 +1201,7 @@ impl From<ListStorage> for ArrayData {
 mod tests {
 
     use super::*;
+    use crate::arrays::executor::scalar::concat;
 
     #[test]
     fn select_mut_no_change() {
@@ -1173,4 +1275,57 @@ mod tests {
         assert!(!arr.scalar_value_logically_eq(&scalar, 0).unwrap());
         assert!(arr.scalar_value_logically_eq(&scalar, 1).unwrap());
     }
+
+    #[test]
+    fn unselect_german_shares_data() {
+        let mut arr = Array::from_iter([
+            Some("a_long_string_that_is_not_inlined"),
+            None,
+            Some("short"),
+            Some("another_long_string_not_inlined"),
+        ]);
+        arr.select_mut(SelectionVector::from_iter([3, 1, 0]));
+
+        let unselected = arr.unselect().unwrap();
+        assert!(unselected.selection_vector().is_none());
+
+        assert_eq!(
+            ScalarValue::from("another_long_string_not_inlined"),
+            unselected.logical_value(0).unwrap()
+        );
+        assert_eq!(ScalarValue::Null, unselected.logical_value(1).unwrap());
+        assert_eq!(
+            ScalarValue::from("a_long_string_that_is_not_inlined"),
+            unselected.logical_value(2).unwrap()
+        );
+
+        match (arr.array_data(), unselected.array_data()) {
+            (
+                ArrayData::Binary(BinaryData::German(a)),
+                ArrayData::Binary(BinaryData::German(b)),
+            ) => assert!(Arc::ptr_eq(&a.data, &b.data)),
+            other => panic!("unexpected array data: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unselect_list() {
+        let arr1 = ScalarValue::List(vec![1.into(), 2.into()])
+            .as_array(1)
+            .unwrap();
+        let arr2 = ScalarValue::List(vec![3.into()]).as_array(1).unwrap();
+        let mut arr = concat(&[&arr1, &arr2, &arr1]).unwrap();
+        arr.set_physical_validity(2, false);
+        arr.select_mut(SelectionVector::from_iter([2, 1]));
+
+        let unselected = arr.unselect().unwrap();
+        assert!(unselected.selection_vector().is_none());
+        assert_eq!(2, unselected.logical_len());
+
+        assert_eq!(ScalarValue::Null, unselected.logical_value(0).unwrap());
+        assert_eq!(
+            ScalarValue::List(vec![3.into()]),
+            unselected.logical_value(1).unwrap()
+        );
+    }
 }
@@ -178,6 +178,17 @@ impl Batch {
         self.num_rows
     }
 
+    /// Estimate the size in bytes of the data in this batch.
+    ///
+    /// Doesn't account for validity, metadata, or storage shared between
+    /// columns or batches.
+    pub fn data_size_bytes(&self) -> usize {
+        self.cols
+            .iter()
+            .map(|col| col.array_data().data_size_bytes())
+            .sum()
+    }
+
     pub fn into_arrays(self) -> Vec<Array> {
         self.cols
     }
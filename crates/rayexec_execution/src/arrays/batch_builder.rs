@@ -0,0 +1,220 @@
+//! Builders for producing output batches from pooled buffers.
+//!
+//! Scans and operators that produce batches value by value write each column
+//! into a `ColumnBuilder`. Value and validity buffers are allocated through the
+//! buffer pool and handed back to it when the resulting arrays are dropped, so
+//! producing a stream of similarly sized batches ends up reusing the
+//! allocations of previous batches instead of going through the allocator.
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::array::Array;
+use crate::arrays::batch::Batch;
+use crate::arrays::bitmap::Bitmap;
+use crate::arrays::datatype::DataType;
+use crate::arrays::executor::builder::ArrayDataBuffer;
+
+/// Builds a single array of a fixed length.
+#[derive(Debug)]
+pub struct ColumnBuilder<B> {
+    datatype: DataType,
+    buffer: B,
+    /// Validity for the column, only allocated once a NULL is written.
+    validity: Option<Bitmap>,
+}
+
+impl<B> ColumnBuilder<B>
+where
+    B: ArrayDataBuffer,
+{
+    /// Create a new column builder writing to `buffer`.
+    ///
+    /// The length of the resulting array is the length of the buffer.
+    pub fn new(datatype: DataType, buffer: B) -> Self {
+        ColumnBuilder {
+            datatype,
+            buffer,
+            validity: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Put a value at `idx`.
+    pub fn put(&mut self, idx: usize, val: &B::Type) {
+        self.buffer.put(idx, val)
+    }
+
+    /// Mark the value at `idx` as NULL.
+    pub fn put_null(&mut self, idx: usize) {
+        let len = self.buffer.len();
+        self.validity
+            .get_or_insert_with(|| Bitmap::new_with_all_true(len))
+            .set_unchecked(idx, false);
+    }
+
+    /// Finish building the column.
+    pub fn finish(self) -> Array {
+        match self.validity {
+            Some(validity) => Array::new_with_validity_and_array_data(
+                self.datatype,
+                validity,
+                self.buffer.into_data(),
+            ),
+            None => Array::new_with_array_data(self.datatype, self.buffer.into_data()),
+        }
+    }
+}
+
+/// Collects finished columns into batches of a fixed number of rows.
+///
+/// The builder can be reused across batches, the vec holding the columns is
+/// kept between calls to `finish`.
+#[derive(Debug)]
+pub struct BatchBuilder {
+    num_rows: usize,
+    columns: Vec<Array>,
+}
+
+impl BatchBuilder {
+    pub fn new(num_rows: usize, num_columns: usize) -> Self {
+        BatchBuilder {
+            num_rows,
+            columns: Vec::with_capacity(num_columns),
+        }
+    }
+
+    /// Number of rows the next batch will contain.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Set the number of rows for the next batch.
+    ///
+    /// Errors if columns for the current batch have already been pushed.
+    pub fn set_num_rows(&mut self, num_rows: usize) -> Result<()> {
+        if !self.columns.is_empty() {
+            return Err(RayexecError::new(
+                "Cannot change number of rows with pending columns",
+            ));
+        }
+        self.num_rows = num_rows;
+        Ok(())
+    }
+
+    /// Finish a column and add it to the batch being built.
+    pub fn push_column<B>(&mut self, column: ColumnBuilder<B>) -> Result<()>
+    where
+        B: ArrayDataBuffer,
+    {
+        if column.len() != self.num_rows {
+            return Err(RayexecError::new(format!(
+                "Column length {} does not match expected number of rows {}",
+                column.len(),
+                self.num_rows
+            )));
+        }
+        self.columns.push(column.finish());
+        Ok(())
+    }
+
+    /// Produce a batch from all pushed columns, resetting the builder for the
+    /// next batch.
+    pub fn finish(&mut self) -> Result<Batch> {
+        if self.columns.is_empty() {
+            return Ok(Batch::empty_with_num_rows(self.num_rows));
+        }
+        Batch::try_new(self.columns.drain(..))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::buffer_pool;
+    use crate::arrays::executor::builder::{GermanVarlenBuffer, PrimitiveBuffer};
+    use crate::arrays::scalar::ScalarValue;
+
+    #[test]
+    fn build_column_with_nulls() {
+        let mut builder = ColumnBuilder::new(DataType::Int32, PrimitiveBuffer::<i32>::with_len(3));
+        builder.put(0, &4);
+        builder.put_null(1);
+        builder.put(2, &6);
+        let arr = builder.finish();
+
+        assert_eq!(ScalarValue::Int32(4), arr.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Null, arr.logical_value(1).unwrap());
+        assert_eq!(ScalarValue::Int32(6), arr.logical_value(2).unwrap());
+    }
+
+    #[test]
+    fn build_column_no_nulls_skips_validity() {
+        let mut builder =
+            ColumnBuilder::new(DataType::Utf8, GermanVarlenBuffer::<str>::with_len(2));
+        builder.put(0, "a");
+        builder.put(1, "a string that is longer than the inline threshold");
+        let arr = builder.finish();
+
+        assert!(arr.validity().is_none());
+        assert_eq!(
+            ScalarValue::from("a string that is longer than the inline threshold"),
+            arr.logical_value(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn batch_builder_checks_num_rows() {
+        let mut builder = BatchBuilder::new(4, 1);
+        let column = ColumnBuilder::new(DataType::Int64, PrimitiveBuffer::<i64>::with_len(3));
+        builder.push_column(column).unwrap_err();
+    }
+
+    #[test]
+    fn reuse_buffers_across_batches() {
+        // Large enough for the validity bitmap to be pooled, and an odd size
+        // so that other tests are unlikely to share the bucket.
+        const NUM_ROWS: usize = 9001;
+
+        let build = |builder: &mut BatchBuilder| {
+            let mut col =
+                ColumnBuilder::new(DataType::Int64, PrimitiveBuffer::<i64>::with_len(NUM_ROWS));
+            for idx in 0..NUM_ROWS {
+                if idx % 2 == 0 {
+                    col.put(idx, &(idx as i64));
+                } else {
+                    col.put_null(idx);
+                }
+            }
+            builder.push_column(col).unwrap();
+            builder.finish().unwrap()
+        };
+
+        let mut builder = BatchBuilder::new(NUM_ROWS, 1);
+        let first = build(&mut builder);
+        assert_eq!(NUM_ROWS, first.num_rows());
+        drop(first);
+
+        let before = buffer_pool::stats();
+        let second = build(&mut builder);
+        let after = buffer_pool::stats();
+
+        // Values and validity both served from the pool.
+        if buffer_pool::is_enabled() {
+            assert!(after.reuses >= before.reuses + 2);
+        }
+        assert_eq!(
+            ScalarValue::Int64(9000),
+            second.column(0).unwrap().logical_value(9000).unwrap()
+        );
+        assert_eq!(
+            ScalarValue::Null,
+            second.column(0).unwrap().logical_value(8999).unwrap()
+        );
+    }
+}
@@ -2,28 +2,46 @@ pub mod zip;
 
 use std::borrow::BorrowMut;
 use std::fmt;
+use std::sync::atomic::{self, AtomicUsize};
 
 use rayexec_error::{RayexecError, Result};
 
+use crate::arrays::buffer_pool;
 use crate::arrays::compute::util::IntoExtactSizeIterator;
 
+/// Sentinel for a true count that hasn't been computed yet.
+const UNKNOWN_COUNT: usize = usize::MAX;
+
+/// Number of bytes in a word when operating on multiple bits at once.
+const WORD_BYTES: usize = 8;
+
 /// An LSB ordered bitmap.
-#[derive(Clone, Default, PartialEq, Eq)]
+///
+/// The number of set bits is cached after it's first computed, and reset on
+/// any modification. Validity bitmaps are checked for nulls (`is_all_true`)
+/// far more often than they're modified.
 pub struct Bitmap {
     len: usize,
     data: Vec<u8>,
+    /// Cached number of true bits, `UNKNOWN_COUNT` if not yet computed.
+    true_count: AtomicUsize,
 }
 
 impl Bitmap {
     pub fn try_new(data: Vec<u8>, len: usize) -> Result<Self> {
         // TODO: Validite
-        Ok(Bitmap { len, data })
+        Ok(Self::new_unchecked(data, len, UNKNOWN_COUNT))
     }
 
     pub fn with_capacity(cap: usize) -> Self {
+        Self::new_unchecked(Vec::with_capacity(cap + 1), 0, 0)
+    }
+
+    const fn new_unchecked(data: Vec<u8>, len: usize, true_count: usize) -> Self {
         Bitmap {
-            len: 0,
-            data: Vec::with_capacity(cap + 1),
+            len,
+            data,
+            true_count: AtomicUsize::new(true_count),
         }
     }
 
@@ -39,18 +57,14 @@ impl Bitmap {
 
     pub fn new_with_all_true(len: usize) -> Self {
         let cap = (len + 7) / 8;
-        Bitmap {
-            len,
-            data: vec![u8::MAX; cap],
-        }
+        let mut data = buffer_pool::alloc_vec_with_capacity(cap);
+        data.resize(cap, u8::MAX);
+        Self::new_unchecked(data, len, len)
     }
 
     pub fn new_with_all_false(len: usize) -> Self {
         let cap = (len + 7) / 8;
-        Bitmap {
-            len,
-            data: vec![0; cap],
-        }
+        Self::new_unchecked(buffer_pool::alloc_vec(cap), len, 0)
     }
 
     /// Get the number of bits being tracked by this bitmap.
@@ -68,21 +82,55 @@ impl Bitmap {
         self.data.len()
     }
 
+    /// Get the underlying bytes for the bitmap.
+    ///
+    /// Bits past `len` in the last byte are unspecified.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get the number of bits set to '1'.
+    ///
+    /// The count is cached, so repeated calls without modifying the bitmap
+    /// are cheap.
     pub fn count_trues(&self) -> usize {
-        let mut count = self
-            .data
+        let cached = self.true_count.load(atomic::Ordering::Relaxed);
+        if cached != UNKNOWN_COUNT {
+            return cached;
+        }
+
+        let count = self.compute_count_trues();
+        self.true_count.store(count, atomic::Ordering::Relaxed);
+
+        count
+    }
+
+    /// Get the number of bits set to '0'.
+    pub fn count_falses(&self) -> usize {
+        self.len - self.count_trues()
+    }
+
+    fn compute_count_trues(&self) -> usize {
+        // Only count the bytes that make up the "logical" portion of the
+        // bitmap.
+        let full_bytes = self.len / 8;
+        let (bytes, _) = self.data.split_at(full_bytes);
+
+        let mut words = bytes.chunks_exact(WORD_BYTES);
+        let mut count: usize = words
+            .by_ref()
+            .map(|word| u64::from_le_bytes(word.try_into().unwrap()).count_ones() as usize)
+            .sum();
+        count += words
+            .remainder()
             .iter()
-            .map(|&b| b.count_ones())
-            .fold(0, |acc, v| acc + (v as usize));
+            .map(|b| b.count_ones() as usize)
+            .sum::<usize>();
 
-        // Make sure we're only counting the bits that make up the "logical"
-        // portion of the bitmap.
         let rem = self.len % 8;
         if rem != 0 {
-            let last = self.data.last().unwrap();
-            count -= last.count_ones() as usize;
-            let mask = (255 << (8 - rem)) >> (8 - rem);
-            count += (mask & last).count_ones() as usize;
+            let mask = (1u8 << rem) - 1;
+            count += (self.data[full_bytes] & mask).count_ones() as usize;
         }
 
         count
@@ -92,6 +140,12 @@ impl Bitmap {
         self.count_trues() == self.len()
     }
 
+    /// Reset the cached count after modifying bits.
+    #[inline]
+    fn invalidate_count(&mut self) {
+        *self.true_count.get_mut() = UNKNOWN_COUNT;
+    }
+
     /// Push a value onto the end of the bitmap.
     pub fn push(&mut self, val: bool) {
         if self.len == self.data.len() * 8 {
@@ -99,7 +153,13 @@ impl Bitmap {
         }
         let idx = self.len;
         self.len += 1;
+
+        // Pushing doesn't change existing bits, keep the cached count valid.
+        let count = *self.true_count.get_mut();
         self.set_unchecked(idx, val);
+        if count != UNKNOWN_COUNT {
+            *self.true_count.get_mut() = count + val as usize;
+        }
     }
 
     /// Get the value at index.
@@ -116,6 +176,7 @@ impl Bitmap {
     /// Panics if index is out of bounds.
     #[inline]
     pub fn set_unchecked(&mut self, idx: usize, val: bool) {
+        self.invalidate_count();
         if val {
             // Set bit.
             self.data[idx / 8] |= 1 << (idx % 8)
@@ -140,60 +201,123 @@ impl Bitmap {
         }
     }
 
-    /// Bit OR this bitmap with some other bitmap.
-    pub fn bit_or_mut(&mut self, other: &Bitmap) -> Result<()> {
-        if self.len() != other.len() {
+    /// Copy all bits from `src` into this bitmap, starting at `offset`.
+    ///
+    /// Errors if `src` doesn't fit.
+    pub fn copy_from(&mut self, offset: usize, src: &Bitmap) -> Result<()> {
+        if offset + src.len() > self.len() {
             return Err(RayexecError::new(format!(
-                "Bitmap lengths do not match (or), got {} and {}",
-                self.len(),
-                other.len()
+                "Cannot copy bitmap of length {} to offset {} in bitmap of length {}",
+                src.len(),
+                offset,
+                self.len()
             )));
         }
-
-        for (byte, other) in self.data.iter_mut().zip(other.data.iter()) {
-            *byte |= *other;
+        if src.is_empty() {
+            return Ok(());
+        }
+        self.invalidate_count();
+
+        let shift = offset % 8;
+        let dest_start = offset / 8;
+        let src_bytes = &src.data[..src.len().div_ceil(8)];
+        // Bits in the last source byte that are part of the source bitmap.
+        let last_mask = match src.len() % 8 {
+            0 => u8::MAX,
+            rem => (1u8 << rem) - 1,
+        };
+
+        for (idx, &byte) in src_bytes.iter().enumerate() {
+            let mask = if idx == src_bytes.len() - 1 {
+                last_mask
+            } else {
+                u8::MAX
+            };
+            let byte = byte & mask;
+
+            // Low bits of the source byte go into the current destination byte,
+            // high bits into the next.
+            let dest = &mut self.data[dest_start + idx];
+            *dest = (*dest & !(mask << shift)) | (byte << shift);
+
+            if shift != 0 {
+                let high_mask = ((mask as u16) << shift >> 8) as u8;
+                if high_mask != 0 {
+                    let dest = &mut self.data[dest_start + idx + 1];
+                    *dest = (*dest & !high_mask) | (byte >> (8 - shift));
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Bit OR this bitmap with some other bitmap.
+    pub fn bit_or_mut(&mut self, other: &Bitmap) -> Result<()> {
+        self.check_same_len(other, "or")?;
+        self.apply_words(other, |a, b| a | b);
+        Ok(())
+    }
+
     /// Bit AND this bitmap with some other bitmap.
     pub fn bit_and_mut(&mut self, other: &Bitmap) -> Result<()> {
-        if self.len() != other.len() {
-            return Err(RayexecError::new(format!(
-                "Bitmap lengths do not match (and), got {} and {}",
-                self.len(),
-                other.len()
-            )));
-        }
-
-        for (byte, other) in self.data.iter_mut().zip(other.data.iter()) {
-            *byte &= *other;
-        }
-
+        self.check_same_len(other, "and")?;
+        self.apply_words(other, |a, b| a & b);
         Ok(())
     }
 
     /// Bit AND NOT this bitmap with some other bitmap.
     pub fn bit_and_not_mut(&mut self, other: &Bitmap) -> Result<()> {
+        self.check_same_len(other, "and not")?;
+        self.apply_words(other, |a, b| a & !b);
+        Ok(())
+    }
+
+    pub fn bit_negate(&mut self) {
+        self.invalidate_count();
+
+        let mut words = self.data.chunks_exact_mut(WORD_BYTES);
+        for word in words.by_ref() {
+            let negated = !u64::from_le_bytes((&*word).try_into().unwrap());
+            word.copy_from_slice(&negated.to_le_bytes());
+        }
+        for b in words.into_remainder() {
+            *b = !*b;
+        }
+    }
+
+    fn check_same_len(&self, other: &Bitmap, op: &str) -> Result<()> {
         if self.len() != other.len() {
             return Err(RayexecError::new(format!(
-                "Bitmap lengths do not match (and not), got {} and {}",
+                "Bitmap lengths do not match ({op}), got {} and {}",
                 self.len(),
                 other.len()
             )));
         }
-
-        for (byte, other) in self.data.iter_mut().zip(other.data.iter()) {
-            *byte &= !*other;
-        }
-
         Ok(())
     }
 
-    pub fn bit_negate(&mut self) {
-        for b in self.data.iter_mut() {
-            *b = !*b;
+    /// Apply a bitwise operation with another bitmap a word at a time.
+    ///
+    /// Bitmaps must be the same length.
+    #[inline]
+    fn apply_words(&mut self, other: &Bitmap, op: impl Fn(u64, u64) -> u64) {
+        self.invalidate_count();
+
+        let mut words = self.data.chunks_exact_mut(WORD_BYTES);
+        let mut other_words = other.data.chunks_exact(WORD_BYTES);
+        for (word, other) in words.by_ref().zip(other_words.by_ref()) {
+            let a = u64::from_le_bytes((&*word).try_into().unwrap());
+            let b = u64::from_le_bytes(other.try_into().unwrap());
+            word.copy_from_slice(&op(a, b).to_le_bytes());
+        }
+
+        for (byte, other) in words
+            .into_remainder()
+            .iter_mut()
+            .zip(other_words.remainder())
+        {
+            *byte = op(*byte as u64, *other as u64) as u8;
         }
     }
 
@@ -217,6 +341,37 @@ impl Bitmap {
     }
 }
 
+impl Clone for Bitmap {
+    fn clone(&self) -> Self {
+        Self::new_unchecked(
+            self.data.clone(),
+            self.len,
+            self.true_count.load(atomic::Ordering::Relaxed),
+        )
+    }
+}
+
+/// Hands the allocation back to the buffer pool.
+impl Drop for Bitmap {
+    fn drop(&mut self) {
+        buffer_pool::recycle_vec(std::mem::take(&mut self.data));
+    }
+}
+
+impl Default for Bitmap {
+    fn default() -> Self {
+        Self::new_unchecked(Vec::new(), 0, 0)
+    }
+}
+
+impl PartialEq for Bitmap {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.data == other.data
+    }
+}
+
+impl Eq for Bitmap {}
+
 impl fmt::Debug for Bitmap {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let values: Vec<_> = self.iter().collect();
@@ -252,7 +407,7 @@ impl FromIterator<bool> for Bitmap {
             len += bit_len;
         }
 
-        Bitmap { len, data }
+        Self::new_unchecked(data, len, UNKNOWN_COUNT)
     }
 }
 
@@ -285,7 +440,7 @@ where
             idx += 1;
         }
 
-        Bitmap { len, data }
+        Self::new_unchecked(data, len, UNKNOWN_COUNT)
     }
 }
 
@@ -380,6 +535,22 @@ impl Iterator for BitmapIndexIter<'_> {
                 return None;
             }
 
+            // Skip over entire words or bytes with no set bits.
+            if self.front % 8 == 0 {
+                let byte_idx = self.front / 8;
+                let word_end = byte_idx + WORD_BYTES;
+                if self.front + WORD_BYTES * 8 <= self.back
+                    && self.bitmap.data[byte_idx..word_end].iter().all(|b| *b == 0)
+                {
+                    self.front += WORD_BYTES * 8;
+                    continue;
+                }
+                if self.front + 8 <= self.back && self.bitmap.data[byte_idx] == 0 {
+                    self.front += 8;
+                    continue;
+                }
+            }
+
             if self.bitmap.value(self.front) {
                 let idx = self.front;
                 self.front += 1;
@@ -562,6 +733,102 @@ mod tests {
         assert_eq!(3, bm.count_trues());
     }
 
+    #[test]
+    fn popcnt_multiple_words() {
+        let bits: Vec<_> = (0..150).map(|idx| idx % 3 == 0).collect();
+        let mut bm = Bitmap::from_iter(bits.iter().copied());
+        assert_eq!(50, bm.count_trues());
+        assert_eq!(100, bm.count_falses());
+
+        // Cached count is reset on modification.
+        bm.set_unchecked(1, true);
+        assert_eq!(51, bm.count_trues());
+
+        bm.bit_negate();
+        assert_eq!(99, bm.count_trues());
+
+        bm.push(true);
+        assert_eq!(100, bm.count_trues());
+        assert_eq!(151, bm.len());
+    }
+
+    #[test]
+    fn bit_ops_multiple_words() {
+        let left: Vec<_> = (0..100).map(|idx| idx % 2 == 0).collect();
+        let right: Vec<_> = (0..100).map(|idx| idx % 3 == 0).collect();
+
+        let mut and_bm = Bitmap::from_iter(left.iter().copied());
+        and_bm
+            .bit_and_mut(&Bitmap::from_iter(right.iter().copied()))
+            .unwrap();
+        let mut or_bm = Bitmap::from_iter(left.iter().copied());
+        or_bm
+            .bit_or_mut(&Bitmap::from_iter(right.iter().copied()))
+            .unwrap();
+        let mut and_not_bm = Bitmap::from_iter(left.iter().copied());
+        and_not_bm
+            .bit_and_not_mut(&Bitmap::from_iter(right.iter().copied()))
+            .unwrap();
+
+        for idx in 0..100 {
+            assert_eq!(left[idx] && right[idx], and_bm.value(idx), "idx: {idx}");
+            assert_eq!(left[idx] || right[idx], or_bm.value(idx), "idx: {idx}");
+            assert_eq!(
+                left[idx] && !right[idx],
+                and_not_bm.value(idx),
+                "idx: {idx}"
+            );
+        }
+    }
+
+    #[test]
+    fn copy_from_aligned() {
+        let mut bm = Bitmap::new_with_all_true(16);
+        let src = Bitmap::from_iter([false, true, false, false, true]);
+        bm.copy_from(8, &src).unwrap();
+
+        let expected: Vec<_> = [true; 8]
+            .into_iter()
+            .chain([false, true, false, false, true, true, true, true])
+            .collect();
+        assert_eq!(expected, bm.iter().collect::<Vec<_>>());
+        assert_eq!(13, bm.count_trues());
+    }
+
+    #[test]
+    fn copy_from_unaligned() {
+        for offset in 0..12 {
+            let src_bits: Vec<_> = (0..19).map(|idx| idx % 4 != 1).collect();
+            let src = Bitmap::from_iter(src_bits.iter().copied());
+
+            let mut bm = Bitmap::new_with_all_false(40);
+            bm.copy_from(offset, &src).unwrap();
+
+            for idx in 0..40 {
+                let expected = if idx >= offset && idx < offset + src_bits.len() {
+                    src_bits[idx - offset]
+                } else {
+                    false
+                };
+                assert_eq!(expected, bm.value(idx), "offset: {offset}, idx: {idx}");
+            }
+        }
+    }
+
+    #[test]
+    fn copy_from_out_of_bounds() {
+        let mut bm = Bitmap::new_with_all_true(8);
+        let src = Bitmap::new_with_all_false(4);
+        bm.copy_from(5, &src).unwrap_err();
+    }
+
+    #[test]
+    fn index_iter_sparse() {
+        let bm = Bitmap::from_iter((0..300).map(|idx| idx == 3 || idx == 130 || idx == 299));
+        let indexes: Vec<_> = bm.index_iter().collect();
+        assert_eq!(vec![3, 130, 299], indexes);
+    }
+
     #[test]
     fn index_iter_simple() {
         let bm = Bitmap::from_iter([true, false, false, true, false]);
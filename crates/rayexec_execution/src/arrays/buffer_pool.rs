@@ -0,0 +1,384 @@
+//! Process-wide pool for recycling primitive array buffers.
+//!
+//! Operators allocate output buffers through `alloc_vec` (see
+//! `PrimitiveBuffer`, `GermanVarlenBuffer`, and `Bitmap`), and primitive
+//! storage and bitmaps hand their allocation back to the pool when dropped.
+//! Long pipelines end up allocating and freeing the same sized buffers over and
+//! over, and this lets us skip the allocator for most of them.
+//!
+//! Buffers are bucketed by alignment and byte capacity. Capacities are rounded
+//! up to a power of two so that buckets can be shared between primitive types
+//! of the same alignment. Only types with a power of two size are pooled, so a
+//! bucket's byte size is always an exact multiple of the type's size.
+//!
+//! The pool is configured by the engine (`Engine::with_buffer_pool`), and
+//! never holds more than `max_bytes` worth of buffers.
+use std::alloc::{dealloc, Layout};
+use std::collections::HashMap;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::LazyLock;
+
+use parking_lot::Mutex;
+
+/// Buffers smaller than this (in bytes) aren't worth pooling.
+const MIN_POOLED_BYTES: usize = 1024;
+
+/// Max number of buffers we keep around per bucket.
+const MAX_BUFFERS_PER_BUCKET: usize = 64;
+
+/// Default max bytes held by the pool.
+pub const DEFAULT_BUFFER_POOL_BYTES: usize = 256 * 1024 * 1024;
+
+static BUFFER_POOL: LazyLock<BufferPool> =
+    LazyLock::new(|| BufferPool::new(DEFAULT_BUFFER_POOL_BYTES));
+
+/// Statistics for the buffer pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BufferPoolStats {
+    /// Number of buffers allocated because nothing suitable was pooled.
+    pub allocations: usize,
+    /// Number of allocations served from the pool.
+    pub reuses: usize,
+    /// Number of buffers returned to the pool.
+    pub returns: usize,
+    /// Number of buffers freed instead of being returned (pool full or
+    /// disabled).
+    pub discards: usize,
+    /// Number of buffers currently held by the pool.
+    pub pooled_buffers: usize,
+    /// Bytes currently held by the pool.
+    pub pooled_bytes: usize,
+}
+
+/// Enable or disable buffer pooling.
+///
+/// Disabling drops all currently pooled buffers. Useful when debugging memory
+/// issues since every allocation will go through the allocator.
+pub fn set_enabled(enabled: bool) {
+    BUFFER_POOL.set_enabled(enabled)
+}
+
+/// Returns if buffer pooling is enabled.
+pub fn is_enabled() -> bool {
+    BUFFER_POOL.is_enabled()
+}
+
+/// Set the max bytes held by the pool, freeing pooled buffers if needed.
+pub fn set_max_bytes(max_bytes: usize) {
+    BUFFER_POOL.set_max_bytes(max_bytes)
+}
+
+/// Get the max bytes held by the pool.
+pub fn max_bytes() -> usize {
+    BUFFER_POOL.max_bytes.load(Ordering::Relaxed)
+}
+
+/// Get the current stats for the pool.
+pub fn stats() -> BufferPoolStats {
+    BUFFER_POOL.stats()
+}
+
+/// Allocate a vec of length `len` filled with `T::default()`, reusing a pooled
+/// buffer if possible.
+pub fn alloc_vec<T>(len: usize) -> Vec<T>
+where
+    T: Default + Copy,
+{
+    BUFFER_POOL.alloc_vec(len)
+}
+
+/// Allocate an empty vec with at least `cap` capacity, reusing a pooled buffer
+/// if possible.
+pub fn alloc_vec_with_capacity<T>(cap: usize) -> Vec<T> {
+    BUFFER_POOL.alloc_vec_with_capacity(cap)
+}
+
+/// Return a vec's allocation to the pool.
+///
+/// Values in the vec are dropped. The allocation is only kept if it matches
+/// one of the pool's buckets, otherwise it's freed normally.
+pub fn recycle_vec<T>(vec: Vec<T>) {
+    BUFFER_POOL.recycle_vec(vec)
+}
+
+/// Returns if vecs of `T` can be pooled.
+///
+/// The size must be a power of two so that every bucket size is a multiple of
+/// it. Otherwise a vec created from a pooled buffer would have a capacity
+/// covering fewer bytes than the allocation, and would free it with the wrong
+/// layout.
+const fn is_poolable<T>() -> bool {
+    let size = std::mem::size_of::<T>();
+    size != 0 && size.is_power_of_two()
+}
+
+/// An allocation held by the pool.
+#[derive(Debug)]
+struct RawBuffer {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+// SAFETY: The pool has exclusive ownership of the allocation.
+unsafe impl Send for RawBuffer {}
+
+impl Drop for RawBuffer {
+    fn drop(&mut self) {
+        // SAFETY: Allocation was originally created through the global
+        // allocator with this layout.
+        unsafe { dealloc(self.ptr.as_ptr(), self.layout) }
+    }
+}
+
+#[derive(Debug)]
+struct BufferPool {
+    enabled: AtomicBool,
+    /// Max bytes held across all buckets.
+    max_bytes: AtomicUsize,
+    /// Buffers keyed by (align, byte size).
+    buckets: Mutex<Buckets>,
+    allocations: AtomicUsize,
+    reuses: AtomicUsize,
+    returns: AtomicUsize,
+    discards: AtomicUsize,
+}
+
+#[derive(Debug, Default)]
+struct Buckets {
+    buffers: HashMap<(usize, usize), Vec<RawBuffer>>,
+    /// Total bytes of all buffers in the buckets.
+    bytes: usize,
+}
+
+impl BufferPool {
+    fn new(max_bytes: usize) -> Self {
+        BufferPool {
+            enabled: AtomicBool::new(true),
+            max_bytes: AtomicUsize::new(max_bytes),
+            buckets: Mutex::new(Buckets::default()),
+            allocations: AtomicUsize::new(0),
+            reuses: AtomicUsize::new(0),
+            returns: AtomicUsize::new(0),
+            discards: AtomicUsize::new(0),
+        }
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    fn set_max_bytes(&self, max_bytes: usize) {
+        self.max_bytes.store(max_bytes, Ordering::Relaxed);
+
+        // Free buffers until we're under the new limit. Which buffers get
+        // freed doesn't matter much, they'll all be reallocated as needed.
+        let mut buckets = self.buckets.lock();
+        let Buckets { buffers, bytes } = &mut *buckets;
+        for bucket in buffers.values_mut() {
+            while *bytes > max_bytes {
+                match bucket.pop() {
+                    Some(buf) => *bytes -= buf.layout.size(),
+                    None => break,
+                }
+            }
+        }
+    }
+
+    fn alloc_vec<T>(&self, len: usize) -> Vec<T>
+    where
+        T: Default + Copy,
+    {
+        let mut vec = self.alloc_vec_with_capacity(len);
+        vec.resize(len, T::default());
+        vec
+    }
+
+    fn alloc_vec_with_capacity<T>(&self, cap: usize) -> Vec<T> {
+        let size = std::mem::size_of::<T>();
+        let bytes = cap.saturating_mul(size);
+        if !is_poolable::<T>() || bytes < MIN_POOLED_BYTES || !self.is_enabled() {
+            return Vec::with_capacity(cap);
+        }
+
+        let bucket_bytes = bytes.next_power_of_two();
+        let align = std::mem::align_of::<T>();
+
+        if let Some(ptr) = self.take(align, bucket_bytes) {
+            // SAFETY: Allocation was created by a `Vec` with the same
+            // alignment and total byte size, and `size` divides `bucket_bytes`
+            // exactly since both are powers of two. Length is zero so no
+            // values are read.
+            return unsafe { Vec::from_raw_parts(ptr.as_ptr().cast(), 0, bucket_bytes / size) };
+        }
+
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        Vec::with_capacity(bucket_bytes / size)
+    }
+
+    fn recycle_vec<T>(&self, mut vec: Vec<T>) {
+        let bytes = vec.capacity() * std::mem::size_of::<T>();
+        if !is_poolable::<T>() || bytes < MIN_POOLED_BYTES || !bytes.is_power_of_two() {
+            return;
+        }
+
+        if !self.is_enabled() {
+            self.discards.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        vec.clear();
+        let mut vec = std::mem::ManuallyDrop::new(vec);
+        // SAFETY: Vec pointers are never null.
+        let ptr = unsafe { NonNull::new_unchecked(vec.as_mut_ptr().cast::<u8>()) };
+
+        self.put(RawBuffer {
+            ptr,
+            // Same layout the vec was allocated with.
+            layout: Layout::array::<T>(vec.capacity()).expect("layout from vec to be valid"),
+        });
+    }
+
+    fn take(&self, align: usize, bytes: usize) -> Option<NonNull<u8>> {
+        let mut buckets = self.buckets.lock();
+        let buf = buckets.buffers.get_mut(&(align, bytes))?.pop()?;
+        buckets.bytes -= buf.layout.size();
+        self.reuses.fetch_add(1, Ordering::Relaxed);
+
+        let buf = std::mem::ManuallyDrop::new(buf);
+        Some(buf.ptr)
+    }
+
+    fn put(&self, buf: RawBuffer) {
+        let max_bytes = self.max_bytes.load(Ordering::Relaxed);
+        let mut buckets = self.buckets.lock();
+        let size = buf.layout.size();
+        if buckets.bytes + size > max_bytes {
+            self.discards.fetch_add(1, Ordering::Relaxed);
+            return; // Dropped, deallocates.
+        }
+
+        let bucket = buckets
+            .buffers
+            .entry((buf.layout.align(), size))
+            .or_default();
+        if bucket.len() >= MAX_BUFFERS_PER_BUCKET {
+            self.discards.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        bucket.push(buf);
+        buckets.bytes += size;
+        self.returns.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn clear(&self) {
+        let mut buckets = self.buckets.lock();
+        buckets.buffers.clear();
+        buckets.bytes = 0;
+    }
+
+    fn stats(&self) -> BufferPoolStats {
+        let buckets = self.buckets.lock();
+        let pooled_buffers = buckets.buffers.values().map(|bucket| bucket.len()).sum();
+        let pooled_bytes = buckets.bytes;
+
+        BufferPoolStats {
+            allocations: self.allocations.load(Ordering::Relaxed),
+            reuses: self.reuses.load(Ordering::Relaxed),
+            returns: self.returns.load(Ordering::Relaxed),
+            discards: self.discards.load(Ordering::Relaxed),
+            pooled_buffers,
+            pooled_bytes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recycled_buffer_is_reused() {
+        let pool = BufferPool::new(DEFAULT_BUFFER_POOL_BYTES);
+
+        let vec = pool.alloc_vec::<u64>(3000);
+        let ptr = vec.as_ptr();
+        assert_eq!(3000, vec.len());
+        pool.recycle_vec(vec);
+        assert_eq!(1, pool.stats().pooled_buffers);
+
+        let vec = pool.alloc_vec::<i64>(3001);
+        assert_eq!(ptr as usize, vec.as_ptr() as usize);
+        assert_eq!(3001, vec.len());
+        assert!(vec.iter().all(|&v| v == 0));
+
+        let stats = pool.stats();
+        assert_eq!(1, stats.reuses);
+        assert_eq!(0, stats.pooled_buffers);
+        assert_eq!(0, stats.pooled_bytes);
+    }
+
+    #[test]
+    fn small_buffers_not_pooled() {
+        let pool = BufferPool::new(DEFAULT_BUFFER_POOL_BYTES);
+
+        let vec = pool.alloc_vec::<u8>(16);
+        assert_eq!(16, vec.capacity());
+        pool.recycle_vec(vec);
+        assert_eq!(0, pool.stats().pooled_buffers);
+    }
+
+    #[test]
+    fn non_power_of_two_types_not_pooled() {
+        let pool = BufferPool::new(DEFAULT_BUFFER_POOL_BYTES);
+
+        // 12 bytes doesn't divide any bucket size.
+        let vec = pool.alloc_vec::<[u32; 3]>(1000);
+        assert_eq!(1000, vec.capacity());
+        pool.recycle_vec(vec);
+        assert_eq!(0, pool.stats().pooled_buffers);
+    }
+
+    #[test]
+    fn pooled_bytes_capped() {
+        let pool = BufferPool::new(8192);
+
+        // 4096 bytes each.
+        let vecs: Vec<_> = (0..3).map(|_| pool.alloc_vec::<u64>(512)).collect();
+        for vec in vecs {
+            pool.recycle_vec(vec);
+        }
+
+        let stats = pool.stats();
+        assert_eq!(2, stats.pooled_buffers);
+        assert_eq!(8192, stats.pooled_bytes);
+        assert_eq!(1, stats.discards);
+
+        pool.set_max_bytes(4096);
+        let stats = pool.stats();
+        assert_eq!(1, stats.pooled_buffers);
+        assert_eq!(4096, stats.pooled_bytes);
+    }
+
+    #[test]
+    fn disabled_pool_frees_buffers() {
+        let pool = BufferPool::new(DEFAULT_BUFFER_POOL_BYTES);
+
+        pool.recycle_vec(pool.alloc_vec::<u64>(512));
+        assert_eq!(1, pool.stats().pooled_buffers);
+
+        pool.set_enabled(false);
+        assert_eq!(0, pool.stats().pooled_buffers);
+
+        pool.recycle_vec(pool.alloc_vec::<u64>(512));
+        assert_eq!(0, pool.stats().pooled_buffers);
+    }
+}
@@ -0,0 +1,299 @@
+//! Boolean kernels using three-valued (Kleene) logic.
+//!
+//! NULL is treated as "unknown", so a NULL input only produces a NULL output
+//! if the result actually depends on it:
+//!
+//! - `false AND NULL` is false, `true AND NULL` is NULL.
+//! - `true OR NULL` is true, `false OR NULL` is NULL.
+//! - `NOT NULL` is NULL.
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::array::{Array, ArrayData};
+use crate::arrays::bitmap::Bitmap;
+use crate::arrays::datatype::DataType;
+use crate::arrays::executor::physical_type::PhysicalBool;
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::arrays::storage::BooleanStorage;
+
+/// Kleene AND of all inputs.
+///
+/// All inputs must be boolean arrays of the same logical length.
+pub fn and(inputs: &[&Array]) -> Result<Array> {
+    kleene_fold(inputs, KleeneOp::And)
+}
+
+/// Kleene OR of all inputs.
+///
+/// All inputs must be boolean arrays of the same logical length.
+pub fn or(inputs: &[&Array]) -> Result<Array> {
+    kleene_fold(inputs, KleeneOp::Or)
+}
+
+/// Negate a boolean array, NULLs remain NULL.
+pub fn not(input: &Array) -> Result<Array> {
+    match input.selection_vector() {
+        Some(_) => {
+            let mut values = Bitmap::new_with_all_false(input.logical_len());
+            let mut validity = Bitmap::new_with_all_true(input.logical_len());
+            UnaryExecutor::for_each::<PhysicalBool, _>(input, |idx, val| match val {
+                Some(val) => values.set_unchecked(idx, !val),
+                None => validity.set_unchecked(idx, false),
+            })?;
+
+            if validity.is_all_true() {
+                Ok(Array::new_with_array_data(
+                    DataType::Boolean,
+                    BooleanStorage::from(values),
+                ))
+            } else {
+                Ok(Array::new_with_validity_and_array_data(
+                    DataType::Boolean,
+                    validity,
+                    BooleanStorage::from(values),
+                ))
+            }
+        }
+        None => {
+            // No selection, negate the bitmap directly and keep the existing
+            // validity.
+            let mut values = boolean_bitmap(input)?.clone();
+            values.bit_negate();
+
+            Ok(Array {
+                datatype: DataType::Boolean,
+                selection: None,
+                validity: input.validity.clone(),
+                data: BooleanStorage::from(values).into(),
+            })
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KleeneOp {
+    And,
+    Or,
+}
+
+impl KleeneOp {
+    /// The value that determines the output no matter what the other inputs
+    /// are.
+    const fn dominant(&self) -> bool {
+        match self {
+            Self::And => false,
+            Self::Or => true,
+        }
+    }
+}
+
+fn kleene_fold(inputs: &[&Array], op: KleeneOp) -> Result<Array> {
+    let len = match inputs.first() {
+        Some(input) => input.logical_len(),
+        None => {
+            return Err(RayexecError::new(
+                "Boolean kernel requires at least one input",
+            ))
+        }
+    };
+
+    let dominant = op.dominant();
+
+    // Output values with NULLs treated as the identity value (true for AND,
+    // false for OR) such that they never change the output.
+    let mut values = Bitmap::new_with_val(!dominant, len);
+    // Rows where at least one input was NULL.
+    let mut nulls = Bitmap::new_with_all_false(len);
+    let mut has_nulls = false;
+
+    for input in inputs {
+        if input.logical_len() != len {
+            return Err(RayexecError::new(format!(
+                "Boolean kernel inputs have different lengths, got {} and {}",
+                len,
+                input.logical_len()
+            )));
+        }
+
+        match (input.selection_vector(), input.validity()) {
+            (None, None) => {
+                // Fast path, operate directly on the bitmaps.
+                let bitmap = boolean_bitmap(input)?;
+                match op {
+                    KleeneOp::And => values.bit_and_mut(bitmap)?,
+                    KleeneOp::Or => values.bit_or_mut(bitmap)?,
+                }
+            }
+            _ => {
+                UnaryExecutor::for_each::<PhysicalBool, _>(input, |idx, val| match val {
+                    Some(val) => {
+                        if val == dominant {
+                            values.set_unchecked(idx, dominant);
+                        }
+                    }
+                    None => {
+                        nulls.set_unchecked(idx, true);
+                        has_nulls = true;
+                    }
+                })?;
+            }
+        }
+    }
+
+    if !has_nulls {
+        return Ok(Array::new_with_array_data(
+            DataType::Boolean,
+            BooleanStorage::from(values),
+        ));
+    }
+
+    // A row is NULL only if it saw a NULL and wasn't decided by a dominant
+    // value.
+    let mut undecided = nulls;
+    match op {
+        KleeneOp::And => undecided.bit_and_mut(&values)?,
+        KleeneOp::Or => undecided.bit_and_not_mut(&values)?,
+    }
+    let mut validity = undecided;
+    validity.bit_negate();
+
+    Ok(Array::new_with_validity_and_array_data(
+        DataType::Boolean,
+        validity,
+        BooleanStorage::from(values),
+    ))
+}
+
+fn boolean_bitmap(array: &Array) -> Result<&Bitmap> {
+    match array.array_data() {
+        ArrayData::Boolean(storage) => Ok(storage.as_ref().as_ref()),
+        other => Err(RayexecError::new(format!(
+            "Expected boolean array, got {:?}",
+            other.physical_type()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::arrays::selection::SelectionVector;
+
+    fn values(array: &Array) -> Vec<ScalarValue<'static>> {
+        (0..array.logical_len())
+            .map(|idx| array.logical_value(idx).unwrap().into_owned())
+            .collect()
+    }
+
+    fn bools(vals: impl IntoIterator<Item = Option<bool>>) -> Vec<ScalarValue<'static>> {
+        vals.into_iter()
+            .map(|v| match v {
+                Some(v) => ScalarValue::Boolean(v),
+                None => ScalarValue::Null,
+            })
+            .collect()
+    }
+
+    // All combinations of true, false, and NULL.
+    fn truth_table_inputs() -> (Array, Array) {
+        let a = Array::from_iter([
+            Some(true),
+            Some(true),
+            Some(true),
+            Some(false),
+            Some(false),
+            Some(false),
+            None,
+            None,
+            None,
+        ]);
+        let b = Array::from_iter([
+            Some(true),
+            Some(false),
+            None,
+            Some(true),
+            Some(false),
+            None,
+            Some(true),
+            Some(false),
+            None,
+        ]);
+        (a, b)
+    }
+
+    #[test]
+    fn and_truth_table() {
+        let (a, b) = truth_table_inputs();
+        let out = and(&[&a, &b]).unwrap();
+
+        let expected = bools([
+            Some(true),
+            Some(false),
+            None,
+            Some(false),
+            Some(false),
+            Some(false),
+            None,
+            Some(false),
+            None,
+        ]);
+        assert_eq!(expected, values(&out));
+    }
+
+    #[test]
+    fn or_truth_table() {
+        let (a, b) = truth_table_inputs();
+        let out = or(&[&a, &b]).unwrap();
+
+        let expected = bools([
+            Some(true),
+            Some(true),
+            Some(true),
+            Some(true),
+            Some(false),
+            None,
+            Some(true),
+            None,
+            None,
+        ]);
+        assert_eq!(expected, values(&out));
+    }
+
+    #[test]
+    fn and_no_nulls_fast_path() {
+        let a = Array::from_iter([true, true, false, false]);
+        let b = Array::from_iter([true, false, true, false]);
+        let c = Array::from_iter([true, true, true, true]);
+        let out = and(&[&a, &b, &c]).unwrap();
+
+        assert!(out.validity().is_none());
+        let expected = bools([Some(true), Some(false), Some(false), Some(false)]);
+        assert_eq!(expected, values(&out));
+    }
+
+    #[test]
+    fn or_with_selection() {
+        let mut a = Array::from_iter([Some(false), None, Some(true)]);
+        // Logical values: NULL, false, true, false
+        a.select_mut(Arc::new(SelectionVector::from_iter([1, 0, 2, 0])));
+        let b = Array::from_iter([true, false, false, false]);
+        let out = or(&[&a, &b]).unwrap();
+
+        let expected = bools([Some(true), Some(false), Some(true), Some(false)]);
+        assert_eq!(expected, values(&out));
+    }
+
+    #[test]
+    fn not_keeps_nulls() {
+        let a = Array::from_iter([Some(true), None, Some(false)]);
+        let out = not(&a).unwrap();
+        assert_eq!(bools([Some(false), None, Some(true)]), values(&out));
+
+        let mut a = Array::from_iter([Some(true), None, Some(false)]);
+        a.select_mut(Arc::new(SelectionVector::from_iter([2, 1, 0])));
+        let out = not(&a).unwrap();
+        assert_eq!(bools([Some(true), None, Some(false)]), values(&out));
+    }
+}
@@ -3,6 +3,7 @@ use std::ops::Mul;
 use half::f16;
 use num::{CheckedDiv, CheckedMul, Float, NumCast, PrimInt, ToPrimitive};
 use rayexec_error::{RayexecError, Result};
+use serde::de::IgnoredAny;
 
 use super::behavior::CastFailBehavior;
 use super::format::{
@@ -42,6 +43,7 @@ use super::parse::{
     Int8Parser,
     IntervalParser,
     Parser,
+    TimestampParser,
     UInt128Parser,
     UInt16Parser,
     UInt32Parser,
@@ -77,7 +79,7 @@ use crate::arrays::executor::physical_type::{
 };
 use crate::arrays::executor::scalar::UnaryExecutor;
 use crate::arrays::scalar::decimal::{Decimal128Type, Decimal64Type, DecimalType};
-use crate::arrays::storage::{AddressableStorage, PrimitiveStorage};
+use crate::arrays::storage::{AddressableStorage, ListStorage, PrimitiveStorage};
 
 pub fn cast_array(arr: &Array, to: DataType, behavior: CastFailBehavior) -> Result<Array> {
     if arr.datatype() == &to {
@@ -93,9 +95,19 @@ pub fn cast_array(arr: &Array, to: DataType, behavior: CastFailBehavior) -> Resu
             Array::new_with_validity_and_array_data(to, validity, data)
         }
 
+        // JSON is stored as text, nothing to convert.
+        DataType::Json if to.is_utf8() => arr.clone().try_with_datatype(to)?,
+
+        // Geometries are WKB, reinterpret to and from binary as is.
+        DataType::Geometry if to == DataType::Binary => arr.clone().try_with_datatype(to)?,
+        DataType::Binary if to.is_geometry() => arr.clone().try_with_datatype(to)?,
+
         // String to anything else.
         DataType::Utf8 => cast_from_utf8(arr, to, behavior)?,
 
+        // Lists to lists with a different element type or size.
+        DataType::List(_) if to.is_list() => cast_list(arr, to, behavior)?,
+
         // Primitive numerics to other primitive numerics.
         DataType::Int8 if to.is_primitive_numeric() => {
             cast_primitive_numeric_helper::<PhysicalI8>(arr, to, behavior)?
@@ -203,17 +215,22 @@ pub fn cast_array(arr: &Array, to: DataType, behavior: CastFailBehavior) -> Resu
         }
 
         // Decimal to float.
-        DataType::Decimal64(_) => match to {
+        DataType::Decimal64(_) if !to.is_utf8() => match to {
             DataType::Float32 => cast_decimal_to_float::<PhysicalI64, f32>(arr, to, behavior)?,
             DataType::Float64 => cast_decimal_to_float::<PhysicalI64, f64>(arr, to, behavior)?,
             other => return Err(RayexecError::new(format!("Unhandled data type: {other}"))),
         },
-        DataType::Decimal128(_) => match to {
+        DataType::Decimal128(_) if !to.is_utf8() => match to {
             DataType::Float32 => cast_decimal_to_float::<PhysicalI128, f32>(arr, to, behavior)?,
             DataType::Float64 => cast_decimal_to_float::<PhysicalI128, f64>(arr, to, behavior)?,
             other => return Err(RayexecError::new(format!("Unhandled data type: {other}"))),
         },
 
+        // Timestamp to timestamp with a different unit.
+        DataType::Timestamp(_) if matches!(to, DataType::Timestamp(_)) => {
+            cast_timestamp_unit(arr, to, behavior)?
+        }
+
         // Anything to string.
         _ if to.is_utf8() => cast_to_utf8(arr, behavior)?,
 
@@ -227,6 +244,89 @@ pub fn cast_array(arr: &Array, to: DataType, behavior: CastFailBehavior) -> Resu
     Ok(arr)
 }
 
+/// Cast a list by casting its elements, checking that each list has the right
+/// number of elements when casting to a fixed-size list.
+fn cast_list(arr: &Array, to: DataType, behavior: CastFailBehavior) -> Result<Array> {
+    let (element_type, size) = match &to {
+        DataType::List(meta) => (meta.datatype.as_ref().clone(), meta.size),
+        other => {
+            return Err(RayexecError::new(format!(
+                "Expected list type, got {other}"
+            )))
+        }
+    };
+
+    // Lengths are checked per physical row below.
+    let arr = arr.unselect()?;
+    let list = match arr.array_data() {
+        ArrayData::List(list) => list,
+        _ => return Err(RayexecError::new("Expected list array data")),
+    };
+
+    let mut fail_state = behavior.new_state_for_array(&arr);
+    if let Some(size) = size {
+        for (idx, meta) in list.metadata.as_slice().iter().enumerate() {
+            if arr.is_valid(idx) == Some(true) && meta.len as usize != size {
+                fail_state.set_did_fail_with_error(
+                    idx,
+                    RayexecError::new(format!(
+                        "Expected list with {size} elements, got {}",
+                        meta.len
+                    )),
+                );
+            }
+        }
+    }
+
+    let elements = cast_array(&list.array, element_type, behavior)?;
+    let data = ListStorage::try_new(list.metadata.clone(), elements)?;
+    let output = Array {
+        datatype: to,
+        selection: None,
+        validity: arr.validity.clone(),
+        data: data.into(),
+    };
+
+    fail_state.check_and_apply(&arr, output)
+}
+
+/// Cast timestamps to a timestamp with a different unit.
+///
+/// Converting to a coarser unit rounds towards negative infinity so that
+/// timestamps before the epoch stay in the correct second.
+fn cast_timestamp_unit(arr: &Array, to: DataType, behavior: CastFailBehavior) -> Result<Array> {
+    let unit = |datatype: &DataType| match datatype {
+        DataType::Timestamp(m) => Ok(m.unit),
+        other => Err(RayexecError::new(format!(
+            "Expected timestamp type, got {other}"
+        ))),
+    };
+    let from_scale = unit(arr.datatype())?.units_per_second();
+    let to_scale = unit(&to)?.units_per_second();
+
+    let mut fail_state = behavior.new_state_for_array(arr);
+    let output = UnaryExecutor::execute::<PhysicalI64, _, _>(
+        arr,
+        ArrayBuilder {
+            datatype: to,
+            buffer: PrimitiveBuffer::with_len(arr.logical_len()),
+        },
+        |v, buf| {
+            let converted = if to_scale >= from_scale {
+                v.checked_mul(to_scale / from_scale)
+            } else {
+                Some(v.div_euclid(from_scale / to_scale))
+            };
+            match converted {
+                Some(v) => buf.put(&v),
+                None => fail_state.set_did_fail(buf.idx),
+            }
+        },
+    )?;
+
+    fail_state.check_and_apply(arr, output)
+}
+
 fn decimal_rescale_helper<'a, S>(
     arr: &'a Array,
     to: DataType,
@@ -575,9 +675,14 @@ pub fn cast_from_utf8(
             Decimal128Parser::new(m.precision, m.scale),
         ),
         DataType::Date32 => cast_parse_primitive(arr, datatype, behavior, Date32Parser),
+        DataType::Timestamp(ref m) => {
+            let unit = m.unit;
+            cast_parse_primitive(arr, datatype, behavior, TimestampParser { unit })
+        }
         DataType::Interval => {
             cast_parse_primitive(arr, datatype, behavior, IntervalParser::default())
         }
+        DataType::Json => cast_parse_json(arr, behavior),
         other => Err(RayexecError::new(format!(
             "Unable to cast utf8 array to {other}"
         ))),
@@ -698,6 +803,27 @@ fn cast_parse_bool(arr: &Array, behavior: CastFailBehavior) -> Result<Array> {
     fail_state.check_and_apply(arr, output)
 }
 
+/// Cast strings to JSON, ensuring each string is valid JSON.
+fn cast_parse_json(arr: &Array, behavior: CastFailBehavior) -> Result<Array> {
+    let mut fail_state = behavior.new_state_for_array(arr);
+    let output = UnaryExecutor::execute::<PhysicalUtf8, _, _>(
+        arr,
+        ArrayBuilder {
+            datatype: DataType::Json,
+            buffer: GermanVarlenBuffer::with_len(arr.logical_len()),
+        },
+        |v, buf| match serde_json::from_str::<IgnoredAny>(v) {
+            Ok(_) => buf.put(v),
+            Err(e) => fail_state.set_did_fail_with_error(
+                buf.idx,
+                RayexecError::with_source("Invalid JSON", Box::new(e)),
+            ),
+        },
+    )?;
+
+    fail_state.check_and_apply(arr, output)
+}
+
 fn cast_parse_primitive<P, T>(
     arr: &Array,
     datatype: DataType,
@@ -728,7 +854,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::arrays::datatype::DecimalTypeMeta;
+    use crate::arrays::datatype::{DecimalTypeMeta, TimestampTypeMeta};
+    use crate::arrays::scalar::timestamp::TimestampScalar;
     use crate::arrays::scalar::ScalarValue;
 
     #[test]
@@ -759,6 +886,30 @@ mod tests {
         assert_eq!(ScalarValue::Null, got.logical_value(2).unwrap());
     }
 
+    #[test]
+    fn array_cast_utf8_to_json() {
+        let arr = Array::from_iter([r#"{"a": 1}"#, "[1, 2]"]);
+
+        let got = cast_array(&arr, DataType::Json, CastFailBehavior::Error).unwrap();
+        assert_eq!(&DataType::Json, got.datatype());
+        assert_eq!(
+            ScalarValue::Json(r#"{"a": 1}"#.into()),
+            got.logical_value(0).unwrap()
+        );
+
+        let back = cast_array(&got, DataType::Utf8, CastFailBehavior::Error).unwrap();
+        assert_eq!(ScalarValue::from("[1, 2]"), back.logical_value(1).unwrap());
+    }
+
+    #[test]
+    fn array_cast_utf8_to_json_invalid() {
+        let arr = Array::from_iter([r#"{"a": 1}"#, "{a: 1}"]);
+        cast_array(&arr, DataType::Json, CastFailBehavior::Error).unwrap_err();
+
+        let got = cast_array(&arr, DataType::Json, CastFailBehavior::Null).unwrap();
+        assert_eq!(ScalarValue::Null, got.logical_value(1).unwrap());
+    }
+
     #[test]
     fn array_cast_null_to_f32() {
         let arr = Array::new_untyped_null_array(3);
@@ -788,4 +939,68 @@ mod tests {
         assert_eq!(ScalarValue::Float64(2.0), got.logical_value(1).unwrap());
         assert_eq!(ScalarValue::Float64(2.5), got.logical_value(2).unwrap());
     }
+
+    #[test]
+    fn array_cast_timestamp_units() {
+        let timestamp = |unit| DataType::Timestamp(TimestampTypeMeta::new(unit));
+        let arr = Array::new_with_array_data(
+            timestamp(TimeUnit::Nanosecond),
+            PrimitiveStorage::from(vec![1_500_000_000_i64, -1_500_000_000_i64]),
+        );
+
+        let got = cast_array(
+            &arr,
+            timestamp(TimeUnit::Millisecond),
+            CastFailBehavior::Error,
+        )
+        .unwrap();
+        let expected = |value| {
+            ScalarValue::Timestamp(TimestampScalar {
+                unit: TimeUnit::Millisecond,
+                value,
+            })
+        };
+        assert_eq!(expected(1500), got.logical_value(0).unwrap());
+        assert_eq!(expected(-1500), got.logical_value(1).unwrap());
+
+        // Truncated towards negative infinity.
+        let got = cast_array(&arr, timestamp(TimeUnit::Second), CastFailBehavior::Error).unwrap();
+        let expected = |value| {
+            ScalarValue::Timestamp(TimestampScalar {
+                unit: TimeUnit::Second,
+                value,
+            })
+        };
+        assert_eq!(expected(1), got.logical_value(0).unwrap());
+        assert_eq!(expected(-2), got.logical_value(1).unwrap());
+
+        // Back to nanoseconds.
+        let back = cast_array(
+            &got,
+            timestamp(TimeUnit::Nanosecond),
+            CastFailBehavior::Error,
+        )
+        .unwrap();
+        assert_eq!(
+            ScalarValue::Timestamp(TimestampScalar {
+                unit: TimeUnit::Nanosecond,
+                value: -2_000_000_000,
+            }),
+            back.logical_value(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn array_cast_timestamp_units_overflow() {
+        let arr = Array::new_with_array_data(
+            DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Second)),
+            // Year 2286, past the max nanosecond timestamp.
+            PrimitiveStorage::from(vec![10_000_000_000_i64]),
+        );
+        let to = DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Nanosecond));
+
+        cast_array(&arr, to.clone(), CastFailBehavior::Error).unwrap_err();
+        let got = cast_array(&arr, to, CastFailBehavior::Null).unwrap();
+        assert_eq!(ScalarValue::Null, got.logical_value(0).unwrap());
+    }
 }
@@ -1,5 +1,5 @@
 //! Utilities for writing values into strings (and other buffers).
-use std::fmt::{self, Display, Write as _};
+use std::fmt::{self, Display};
 use std::marker::PhantomData;
 
 use chrono::{DateTime, Utc};
@@ -41,53 +41,339 @@ pub type UInt16Formatter = DisplayFormatter<u16>;
 pub type UInt32Formatter = DisplayFormatter<u32>;
 pub type UInt64Formatter = DisplayFormatter<u64>;
 pub type UInt128Formatter = DisplayFormatter<u128>;
-pub type Float16Formatter = DisplayFormatter<f16>;
-pub type Float32Formatter = DisplayFormatter<f32>;
-pub type Float64Formatter = DisplayFormatter<f64>;
+/// Notation to use when writing floats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FloatNotation {
+    /// Positional notation using the shortest digits that round trip, e.g.
+    /// '1000000000000000' or '0.00001'.
+    ///
+    /// Infinities are written as 'inf' and '-inf'.
+    #[default]
+    Fixed,
+    /// Scientific notation using Postgres' exponent format, e.g. '1e+15' or
+    /// '1.5e-05'.
+    ///
+    /// Infinities are written as 'Infinity' and '-Infinity'.
+    Scientific,
+    /// Matches Postgres' float output.
+    ///
+    /// Scientific notation is used if the exponent is less than -4 or at least
+    /// the number of decimal digits the type can represent (15 for doubles, 6
+    /// for reals), positional notation otherwise.
+    ///
+    /// Infinities are written as 'Infinity' and '-Infinity'.
+    Postgres,
+}
+
+/// Float types that can be written by `FloatFormatter`.
+pub trait FormatFloat: Copy {
+    /// Number of decimal digits that can be represented without loss (DBL_DIG
+    /// and friends).
+    const DIGITS: i32;
+
+    fn is_nan(self) -> bool;
+    fn is_infinite(self) -> bool;
+    fn is_sign_negative(self) -> bool;
+
+    /// Write the shortest round trip representation of a finite value into
+    /// the ryu buffer.
+    fn format_finite(self, buf: &mut ryu::Buffer) -> &str;
+}
+
+impl FormatFloat for f16 {
+    const DIGITS: i32 = 3;
+
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    fn is_infinite(self) -> bool {
+        self.is_infinite()
+    }
+
+    fn is_sign_negative(self) -> bool {
+        self.is_sign_negative()
+    }
+
+    fn format_finite(self, buf: &mut ryu::Buffer) -> &str {
+        // Every f16 is exactly representable as an f32, and the shortest f32
+        // digits round trip back to the same f16.
+        buf.format_finite(self.to_f32())
+    }
+}
+
+impl FormatFloat for f32 {
+    const DIGITS: i32 = 6;
+
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    fn is_infinite(self) -> bool {
+        self.is_infinite()
+    }
+
+    fn is_sign_negative(self) -> bool {
+        self.is_sign_negative()
+    }
+
+    fn format_finite(self, buf: &mut ryu::Buffer) -> &str {
+        buf.format_finite(self)
+    }
+}
+
+impl FormatFloat for f64 {
+    const DIGITS: i32 = 15;
+
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    fn is_infinite(self) -> bool {
+        self.is_infinite()
+    }
+
+    fn is_sign_negative(self) -> bool {
+        self.is_sign_negative()
+    }
+
+    fn format_finite(self, buf: &mut ryu::Buffer) -> &str {
+        buf.format_finite(self)
+    }
+}
+
+/// Formatter for floats using ryu to generate the shortest digits that round
+/// trip.
+///
+/// The default notation produces the same output as the type's `Display`
+/// implementation, just faster. The one exception is a real exactly halfway
+/// between two shortest candidates, where ryu (like Postgres) rounds to even
+/// and `Display` rounds up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FloatFormatter<T: FormatFloat> {
+    notation: FloatNotation,
+    _type: PhantomData<T>,
+}
+
+pub type Float16Formatter = FloatFormatter<f16>;
+pub type Float32Formatter = FloatFormatter<f32>;
+pub type Float64Formatter = FloatFormatter<f64>;
+
+impl<T: FormatFloat> FloatFormatter<T> {
+    pub const fn new(notation: FloatNotation) -> Self {
+        FloatFormatter {
+            notation,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T: FormatFloat> Formatter for FloatFormatter<T> {
+    type Type = T;
+    fn write<W: fmt::Write>(&mut self, val: &Self::Type, buf: &mut W) -> fmt::Result {
+        let val = *val;
+        if val.is_nan() {
+            return buf.write_str("NaN");
+        }
+        if val.is_infinite() {
+            let s = match (self.notation, val.is_sign_negative()) {
+                (FloatNotation::Fixed, false) => "inf",
+                (FloatNotation::Fixed, true) => "-inf",
+                (_, false) => "Infinity",
+                (_, true) => "-Infinity",
+            };
+            return buf.write_str(s);
+        }
+
+        let mut ryu_buf = ryu::Buffer::new();
+        let decimal = ShortestDecimal::from_ryu(val.format_finite(&mut ryu_buf));
+
+        let scientific = match self.notation {
+            FloatNotation::Fixed => false,
+            FloatNotation::Scientific => true,
+            FloatNotation::Postgres => decimal.exp < -4 || decimal.exp >= T::DIGITS,
+        };
+
+        if decimal.negative {
+            buf.write_char('-')?;
+        }
+        if scientific {
+            decimal.write_scientific(buf)
+        } else {
+            decimal.write_fixed(buf)
+        }
+    }
+}
+
+/// Shortest decimal digits for a float in the form `d.ddd * 10^exp`.
+#[derive(Debug)]
+struct ShortestDecimal {
+    negative: bool,
+    /// Significant digits with no leading or trailing zeros. Floats need at
+    /// most 17.
+    digits: [u8; 24],
+    len: usize,
+    exp: i32,
+}
+
+impl ShortestDecimal {
+    /// Extract the digits and exponent from ryu's output.
+    ///
+    /// Ryu writes either positional ('1.0', '0.001', '1234.5') or scientific
+    /// ('1e16', '1.5e-7') notation depending on the exponent.
+    fn from_ryu(s: &str) -> Self {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (mantissa, exp_offset) = match s.split_once('e') {
+            Some((mantissa, exp)) => (mantissa, exp.parse::<i32>().unwrap_or(0)),
+            None => (s, 0),
+        };
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+        let mut decimal = ShortestDecimal {
+            negative,
+            digits: [0; 24],
+            len: 0,
+            exp: 0,
+        };
+
+        // Position of the first significant digit relative to the decimal
+        // point.
+        let mut first_digit_exp = None;
+        for (idx, b) in int.bytes().chain(frac.bytes()).enumerate() {
+            if first_digit_exp.is_none() {
+                if b == b'0' {
+                    continue;
+                }
+                first_digit_exp = Some(int.len() as i32 - 1 - idx as i32);
+            }
+            if decimal.len < decimal.digits.len() {
+                decimal.digits[decimal.len] = b;
+                decimal.len += 1;
+            }
+        }
+
+        match first_digit_exp {
+            Some(exp) => {
+                decimal.exp = exp + exp_offset;
+                while decimal.len > 1 && decimal.digits[decimal.len - 1] == b'0' {
+                    decimal.len -= 1;
+                }
+            }
+            None => {
+                // Zero.
+                decimal.digits[0] = b'0';
+                decimal.len = 1;
+            }
+        }
+
+        decimal
+    }
+
+    fn digits(&self) -> &str {
+        // Digits are only ever ascii.
+        std::str::from_utf8(&self.digits[..self.len]).unwrap_or("0")
+    }
+
+    /// Write as 'ddd', 'dd.dd', or '0.000ddd'.
+    fn write_fixed<W: fmt::Write>(&self, buf: &mut W) -> fmt::Result {
+        let digits = self.digits();
+        if self.exp < 0 {
+            buf.write_str("0.")?;
+            write_zeros(buf, (-self.exp - 1) as usize)?;
+            return buf.write_str(digits);
+        }
+
+        let int_len = self.exp as usize + 1;
+        if digits.len() <= int_len {
+            buf.write_str(digits)?;
+            write_zeros(buf, int_len - digits.len())
+        } else {
+            let (int, frac) = digits.split_at(int_len);
+            buf.write_str(int)?;
+            buf.write_char('.')?;
+            buf.write_str(frac)
+        }
+    }
+
+    /// Write as 'd.ddde+XX', matching the output of C's '%e'.
+    fn write_scientific<W: fmt::Write>(&self, buf: &mut W) -> fmt::Result {
+        let digits = self.digits();
+        buf.write_str(&digits[..1])?;
+        if digits.len() > 1 {
+            buf.write_char('.')?;
+            buf.write_str(&digits[1..])?;
+        }
+        let sign = if self.exp < 0 { '-' } else { '+' };
+        write!(buf, "e{sign}{:02}", self.exp.unsigned_abs())
+    }
+}
+
+/// Write some number of '0's to the buffer.
+fn write_zeros<W: fmt::Write>(buf: &mut W, mut n: usize) -> fmt::Result {
+    const ZEROS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+    while n > 0 {
+        let count = n.min(ZEROS.len());
+        buf.write_str(&ZEROS[..count])?;
+        n -= count;
+    }
+    Ok(())
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct DecimalFormatter<T: Display> {
+pub struct DecimalFormatter<T: itoa::Integer> {
     precision: u8,
     scale: i8,
-    buf: String,
     _type: PhantomData<T>,
 }
 
 pub type Decimal64Formatter = DecimalFormatter<i64>;
 pub type Decimal128Formatter = DecimalFormatter<i128>;
 
-impl<T: Display> DecimalFormatter<T> {
+impl<T: itoa::Integer> DecimalFormatter<T> {
     pub fn new(precision: u8, scale: i8) -> Self {
         DecimalFormatter {
             precision,
             scale,
-            buf: String::new(),
             _type: PhantomData,
         }
     }
 }
 
-impl<T: Display> Formatter for DecimalFormatter<T> {
+impl<T: itoa::Integer + Copy> Formatter for DecimalFormatter<T> {
     type Type = T;
     fn write<W: fmt::Write>(&mut self, val: &Self::Type, buf: &mut W) -> fmt::Result {
-        self.buf.clear();
+        let mut itoa_buf = itoa::Buffer::new();
+        let s = itoa_buf.format(*val);
+
         match self.scale {
             scale if scale > 0 => {
-                write!(&mut self.buf, "{val}").expect("string write to not fail");
-                if self.buf.len() <= self.scale as usize {
-                    let pad = self.scale.unsigned_abs() as usize;
-                    write!(buf, "0.{val:0>pad$}")
+                let scale = scale as usize;
+                let digits = match s.strip_prefix('-') {
+                    Some(digits) => {
+                        buf.write_char('-')?;
+                        digits
+                    }
+                    None => s,
+                };
+                if digits.len() <= scale {
+                    buf.write_str("0.")?;
+                    write_zeros(buf, scale - digits.len())?;
+                    buf.write_str(digits)
                 } else {
-                    self.buf.insert(self.buf.len() - self.scale as usize, '.');
-                    write!(buf, "{}", self.buf)
+                    let (int, frac) = digits.split_at(digits.len() - scale);
+                    buf.write_str(int)?;
+                    buf.write_char('.')?;
+                    buf.write_str(frac)
                 }
             }
             scale if scale < 0 => {
-                write!(&mut self.buf, "{val}").expect("string write to not fail");
-                let pad = self.buf.len() + self.scale.unsigned_abs() as usize;
-                write!(buf, "{val:0<pad$}")
+                buf.write_str(s)?;
+                write_zeros(buf, scale.unsigned_abs() as usize)
             }
-            _ => write!(buf, "{val}"),
+            _ => buf.write_str(s),
         }
     }
 }
@@ -178,62 +464,47 @@ impl Formatter for IntervalFormatter {
     fn write<W: fmt::Write>(&mut self, val: &Self::Type, buf: &mut W) -> fmt::Result {
         let years = val.months / 12;
         let months = val.months % 12;
-
         let days = val.days;
 
-        let mut nanos = val.nanos;
-        let hours = nanos / Interval::NANOSECONDS_IN_HOUR;
-        nanos %= Interval::NANOSECONDS_IN_HOUR;
-        let minutes = nanos / Interval::NANOSECONDS_IN_MINUTE;
-        nanos %= Interval::NANOSECONDS_IN_MINUTE;
-        let seconds = nanos / Interval::NANOSECONDS_IN_SECOND;
-        nanos %= Interval::NANOSECONDS_IN_SECOND;
-        let millis = nanos / Interval::NANOSECONDS_IN_MILLISECOND;
-
         let mut pad = false;
-
-        if years > 0 {
-            write!(buf, "{years} year")?;
-            if years > 1 {
-                write!(buf, "s")?;
+        for (v, unit) in [(years, "year"), (months, "mon"), (days, "day")] {
+            if v == 0 {
+                continue;
             }
-            pad = true;
-        }
-
-        if months > 0 {
             if pad {
                 write!(buf, " ")?;
             }
-
-            write!(buf, "{months} mon")?;
-            if months > 1 {
+            // Matches Postgres, singular only for exactly 1 ('-1 days').
+            write!(buf, "{v} {unit}")?;
+            if v != 1 {
                 write!(buf, "s")?;
             }
             pad = true;
         }
 
-        if days > 0 {
+        // Only write the "time" portion if it's non-zero, or if everything
+        // else is zero.
+        if val.nanos != 0 || !pad {
             if pad {
                 write!(buf, " ")?;
             }
-
-            write!(buf, "{days} day")?;
-            if days > 1 {
-                write!(buf, "s")?;
+            if val.nanos < 0 {
+                write!(buf, "-")?;
             }
-            pad = true;
-        }
 
-        // Only write the "time" portion if anything is non-zero.
-        if (hours + minutes + seconds + millis) != 0 {
-            if pad {
-                write!(buf, " ")?;
-            }
+            let mut nanos = val.nanos.unsigned_abs();
+            let hours = nanos / Interval::NANOSECONDS_IN_HOUR as u64;
+            nanos %= Interval::NANOSECONDS_IN_HOUR as u64;
+            let minutes = nanos / Interval::NANOSECONDS_IN_MINUTE as u64;
+            nanos %= Interval::NANOSECONDS_IN_MINUTE as u64;
+            let seconds = nanos / Interval::NANOSECONDS_IN_SECOND as u64;
+            nanos %= Interval::NANOSECONDS_IN_SECOND as u64;
 
             write!(buf, "{:02}:{:02}:{:02}", hours, minutes, seconds)?;
 
-            if millis > 0 {
-                write!(buf, ".{:.6}", millis)?;
+            if nanos > 0 {
+                let frac = format!("{:09}", nanos);
+                write!(buf, ".{}", frac.trim_end_matches('0'))?;
             }
         }
 
@@ -245,6 +516,86 @@ impl Formatter for IntervalFormatter {
 mod tests {
     use super::*;
 
+    fn format_float<T: FormatFloat>(val: T, notation: FloatNotation) -> String {
+        let mut buf = String::new();
+        FloatFormatter::<T>::new(notation)
+            .write(&val, &mut buf)
+            .unwrap();
+        buf
+    }
+
+    #[test]
+    fn float_fixed_matches_display() {
+        let vals = [
+            0.0,
+            -0.0,
+            1.0,
+            -2.5,
+            0.1,
+            1.0 / 3.0,
+            123456789.125,
+            1e15,
+            1e16,
+            1.5e22,
+            1e-5,
+            2.5e-7,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+        ];
+        for val in vals {
+            assert_eq!(val.to_string(), format_float(val, FloatNotation::Fixed));
+            let val = val as f32;
+            assert_eq!(val.to_string(), format_float(val, FloatNotation::Fixed));
+        }
+
+        // Exactly halfway between '1555387.2' and '1555387.3'.
+        assert_eq!(
+            "1555387.2",
+            format_float(1555387.0_f32 + 0.25, FloatNotation::Fixed)
+        );
+    }
+
+    #[test]
+    fn float_scientific() {
+        assert_eq!("1e+15", format_float(1e15, FloatNotation::Scientific));
+        assert_eq!("1.5e-05", format_float(1.5e-5, FloatNotation::Scientific));
+        assert_eq!("-1.25e+00", format_float(-1.25, FloatNotation::Scientific));
+        assert_eq!("1e+300", format_float(1e300, FloatNotation::Scientific));
+        assert_eq!("0e+00", format_float(0.0, FloatNotation::Scientific));
+        assert_eq!(
+            "Infinity",
+            format_float(f64::INFINITY, FloatNotation::Scientific)
+        );
+    }
+
+    #[test]
+    fn float_postgres() {
+        // (input, expected)
+        let test_cases = [
+            (1.0, "1"),
+            (-0.0, "-0"),
+            (0.1, "0.1"),
+            (1e-4, "0.0001"),
+            (1e-5, "1e-05"),
+            (123456789012345.0, "123456789012345"),
+            (1e15, "1e+15"),
+            (1.2345e20, "1.2345e+20"),
+            (f64::NEG_INFINITY, "-Infinity"),
+            (f64::NAN, "NaN"),
+        ];
+        for (val, expected) in test_cases {
+            assert_eq!(expected, format_float(val, FloatNotation::Postgres));
+        }
+
+        assert_eq!("100000", format_float(1e5_f32, FloatNotation::Postgres));
+        assert_eq!("1e+06", format_float(1e6_f32, FloatNotation::Postgres));
+        assert_eq!("1.1", format_float(1.1_f32, FloatNotation::Postgres));
+    }
+
     #[test]
     fn decimal_positive_scale() {
         let mut formatter = Decimal64Formatter::new(6, 3);
@@ -259,6 +610,14 @@ mod tests {
         let mut buf = String::new();
         formatter.write(&12, &mut buf).unwrap();
         assert_eq!("0.012", buf);
+
+        let mut buf = String::new();
+        formatter.write(&-12, &mut buf).unwrap();
+        assert_eq!("-0.012", buf);
+
+        let mut buf = String::new();
+        formatter.write(&-123450, &mut buf).unwrap();
+        assert_eq!("-123.450", buf);
     }
 
     #[test]
@@ -352,5 +711,23 @@ mod tests {
         let mut buf = String::new();
         IntervalFormatter.write(&interval, &mut buf).unwrap();
         assert_eq!("1 year 2 mons 11 days 03:00:24.982", buf);
+
+        let interval = Interval {
+            months: 0,
+            days: 0,
+            nanos: 0,
+        };
+        let mut buf = String::new();
+        IntervalFormatter.write(&interval, &mut buf).unwrap();
+        assert_eq!("00:00:00", buf);
+
+        let interval = Interval {
+            months: -1,
+            days: -1,
+            nanos: -(3 * Interval::NANOSECONDS_IN_HOUR + 5 * Interval::NANOSECONDS_IN_MILLISECOND),
+        };
+        let mut buf = String::new();
+        IntervalFormatter.write(&interval, &mut buf).unwrap();
+        assert_eq!("-1 mons -1 days -03:00:00.005", buf);
     }
 }
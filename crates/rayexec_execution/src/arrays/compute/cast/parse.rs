@@ -3,11 +3,12 @@ use std::fmt::Write;
 use std::marker::PhantomData;
 use std::str::FromStr;
 
-use chrono::{Datelike, NaiveDate};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime};
 use half::f16;
 use num::PrimInt;
 
 use crate::arrays::compute::date::EPOCH_DAYS_FROM_CE;
+use crate::arrays::datatype::TimeUnit;
 use crate::arrays::scalar::interval::Interval;
 
 /// Logic for parsing a string into some type.
@@ -82,6 +83,35 @@ impl Parser for Date32Parser {
     }
 }
 
+/// Parses timestamps like '1992-10-11 12:30:00', '1992-10-11T12:30:00.123', or
+/// '1992-10-11'. Timestamps with an offset (e.g. '1992-10-11T12:30:00Z') are
+/// converted to UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampParser {
+    pub unit: TimeUnit,
+}
+
+impl Parser for TimestampParser {
+    type Type = i64;
+    fn parse(&mut self, s: &str) -> Option<Self::Type> {
+        let s = s.trim();
+
+        let datetime = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f"))
+            .ok()
+            .or_else(|| DateTime::parse_from_rfc3339(s).ok().map(|d| d.naive_utc()))
+            .or_else(|| NaiveDate::from_str(s).ok()?.and_hms_opt(0, 0, 0))?;
+
+        let datetime = datetime.and_utc();
+        match self.unit {
+            TimeUnit::Second => Some(datetime.timestamp()),
+            TimeUnit::Millisecond => Some(datetime.timestamp_millis()),
+            TimeUnit::Microsecond => Some(datetime.timestamp_micros()),
+            TimeUnit::Nanosecond => datetime.timestamp_nanos_opt(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DecimalParser<T: PrimInt> {
     precision: u8,
@@ -365,6 +395,26 @@ mod tests {
         assert_eq!(-1, Date32Parser.parse("1969-12-31").unwrap());
     }
 
+    #[test]
+    fn test_parse_timestamp() {
+        let mut parser = TimestampParser {
+            unit: TimeUnit::Second,
+        };
+        assert_eq!(718806600, parser.parse("1992-10-11 12:30:00").unwrap());
+        assert_eq!(718806600, parser.parse("1992-10-11T12:30:00").unwrap());
+        assert_eq!(718806600, parser.parse("1992-10-11T12:30:00Z").unwrap());
+        assert_eq!(718761600, parser.parse("1992-10-11").unwrap());
+        assert_eq!(None, parser.parse("1992-10-11 12:30"));
+
+        let mut parser = TimestampParser {
+            unit: TimeUnit::Microsecond,
+        };
+        assert_eq!(
+            718_806_600_123_000,
+            parser.parse("1992-10-11 12:30:00.123").unwrap()
+        );
+    }
+
     #[test]
     fn parse_decimal() {
         // Can parse
@@ -0,0 +1,107 @@
+//! Computing on arrays with fewer distinct physical values than rows.
+//!
+//! Dictionary encoded data (e.g. from parquet) is represented as a selection
+//! on top of the dictionary values. Computing something once per referenced
+//! value and then selecting the results avoids repeating work for every row.
+use crate::arrays::array::Array;
+use crate::arrays::selection::SelectionVector;
+
+/// Max number of physical values per row for an array to be considered
+/// dictionary encoded.
+///
+/// Finding the referenced values requires a mapping for every physical value,
+/// which isn't worth it when the dictionary is much larger than the number of
+/// rows.
+const MAX_VALUES_PER_ROW: usize = 4;
+
+/// An array split into the distinct values it references and a code for each
+/// row.
+#[derive(Debug)]
+pub struct DictionaryCodes {
+    /// Physical values referenced by the array, each referenced once.
+    pub values: Array,
+    /// Index into `values` for each row in the array.
+    pub codes: SelectionVector,
+}
+
+impl DictionaryCodes {
+    /// Try to split an array into its referenced values and codes.
+    ///
+    /// Returns None if the array doesn't have a selection, or if there's no
+    /// fewer referenced values than rows.
+    ///
+    /// Physical values not referenced by the array aren't included, so
+    /// computing on `values` never touches values that were filtered out.
+    pub fn try_from_array(array: &Array) -> Option<Self> {
+        let selection = array.selection_vector()?;
+        let num_rows = selection.num_rows();
+        let num_physical = array.array_data().len();
+        if num_physical > num_rows.saturating_mul(MAX_VALUES_PER_ROW) {
+            return None;
+        }
+
+        const UNSET: usize = usize::MAX;
+        let mut mapping = vec![UNSET; num_physical];
+        let mut referenced = Vec::new();
+        let mut codes = SelectionVector::with_capacity(num_rows);
+
+        for location in selection.iter_locations() {
+            let code = &mut mapping[location];
+            if *code == UNSET {
+                *code = referenced.len();
+                referenced.push(location);
+            }
+            codes.push_location(*code);
+        }
+
+        if referenced.len() >= num_rows {
+            return None;
+        }
+
+        let values = Array {
+            datatype: array.datatype.clone(),
+            selection: Some(SelectionVector::from(referenced).into()),
+            validity: array.validity.clone(),
+            data: array.data.clone(),
+        };
+
+        Some(DictionaryCodes { values, codes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+
+    #[test]
+    fn codes_for_referenced_values() {
+        let mut array = Array::from_iter(["a", "b", "c", "d"]);
+        array.select_mut(SelectionVector::from(vec![2, 0, 2, 2, 0]));
+
+        let dict = DictionaryCodes::try_from_array(&array).unwrap();
+
+        assert_eq!(2, dict.values.logical_len());
+        assert_eq!(
+            ScalarValue::from("c"),
+            dict.values.logical_value(0).unwrap()
+        );
+        assert_eq!(
+            ScalarValue::from("a"),
+            dict.values.logical_value(1).unwrap()
+        );
+        assert_eq!(
+            vec![0, 1, 0, 0, 1],
+            dict.codes.iter_locations().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn no_codes_for_distinct_values() {
+        let mut array = Array::from_iter(["a", "b", "c", "d"]);
+        assert!(DictionaryCodes::try_from_array(&array).is_none());
+
+        array.select_mut(SelectionVector::from(vec![3, 1]));
+        assert!(DictionaryCodes::try_from_array(&array).is_none());
+    }
+}
@@ -0,0 +1,91 @@
+//! Kernels for building outputs by picking rows from multiple inputs.
+//!
+//! Indices are (input_idx, row_idx) pairs where `input_idx` selects the array
+//! (or batch) and `row_idx` is the logical row within it. The length of the
+//! indices determines the length of the output, and indices may be repeated.
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::array::Array;
+use crate::arrays::batch::Batch;
+pub use crate::arrays::executor::scalar::interleave;
+
+/// Interleave multiple batches into one.
+///
+/// All batches must have the same number of columns with matching types.
+pub fn interleave_batches(batches: &[&Batch], indices: &[(usize, usize)]) -> Result<Batch> {
+    let num_cols = match batches.first() {
+        Some(batch) => batch.num_columns(),
+        None => return Err(RayexecError::new("Cannot interleave zero batches")),
+    };
+
+    if let Some(batch) = batches.iter().find(|b| b.num_columns() != num_cols) {
+        return Err(RayexecError::new(format!(
+            "Cannot interleave batches with different number of columns, got {} and {}",
+            num_cols,
+            batch.num_columns()
+        )));
+    }
+
+    if num_cols == 0 {
+        return Ok(Batch::empty_with_num_rows(indices.len()));
+    }
+
+    let mut cols: Vec<&Array> = Vec::with_capacity(batches.len());
+    let interleaved = (0..num_cols)
+        .map(|col_idx| {
+            cols.clear();
+            cols.extend(
+                batches
+                    .iter()
+                    .map(|batch| batch.column(col_idx).expect("column to exist")),
+            );
+            interleave(&cols, indices)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Batch::try_new(interleaved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+
+    #[test]
+    fn interleave_two_batches() {
+        let a = Batch::try_new([
+            Array::from_iter([1, 2, 3]),
+            Array::from_iter(["a", "b", "c"]),
+        ])
+        .unwrap();
+        let b = Batch::try_new([
+            Array::from_iter([4, 5]),
+            Array::from_iter([Some("d"), None]),
+        ])
+        .unwrap();
+
+        let out = interleave_batches(&[&a, &b], &[(1, 1), (0, 0), (1, 0), (0, 0)]).unwrap();
+        assert_eq!(4, out.num_rows());
+
+        let ints = out.column(0).unwrap();
+        let strs = out.column(1).unwrap();
+        let expected = [
+            (ScalarValue::from(5), ScalarValue::Null),
+            (ScalarValue::from(1), ScalarValue::from("a")),
+            (ScalarValue::from(4), ScalarValue::from("d")),
+            (ScalarValue::from(1), ScalarValue::from("a")),
+        ];
+        for (idx, (int, s)) in expected.into_iter().enumerate() {
+            assert_eq!(int, ints.logical_value(idx).unwrap());
+            assert_eq!(s, strs.logical_value(idx).unwrap());
+        }
+    }
+
+    #[test]
+    fn interleave_mismatched_columns() {
+        let a = Batch::try_new([Array::from_iter([1, 2])]).unwrap();
+        let b = Batch::try_new([Array::from_iter([1]), Array::from_iter([2])]).unwrap();
+
+        interleave_batches(&[&a, &b], &[(0, 0)]).unwrap_err();
+    }
+}
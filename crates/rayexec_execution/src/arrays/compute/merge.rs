@@ -0,0 +1,166 @@
+//! Kernels for merging already sorted inputs.
+//!
+//! Inputs are ordered according to their comparable row encodings (see
+//! `ComparableRowEncoder`), with the keys for each input already in sorted
+//! order. Merging produces interleave indices which can then be used to build
+//! the output with the interleave kernels.
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use rayexec_error::{RayexecError, Result};
+
+use super::interleave::interleave_batches;
+use crate::arrays::batch::Batch;
+use crate::arrays::row::encoding::{ComparableRow, ComparableRows};
+
+/// Compute the (input_idx, row_idx) indices that merge sorted inputs into a
+/// single sorted output.
+///
+/// The merge is stable, rows that compare equal are emitted in input order.
+pub fn merge_indices(keys: &[&ComparableRows]) -> Vec<(usize, usize)> {
+    let total: usize = keys.iter().map(|k| k.num_rows()).sum();
+    let mut indices = Vec::with_capacity(total);
+
+    // Heap containing the current head of each non-exhausted input. Ties on
+    // the row are broken by input index to keep the merge stable.
+    let mut heap: BinaryHeap<Reverse<(ComparableRow, usize, usize)>> = keys
+        .iter()
+        .enumerate()
+        .filter_map(|(input_idx, k)| k.first().map(|row| Reverse((row, input_idx, 0))))
+        .collect();
+
+    while let Some(Reverse((_, input_idx, row_idx))) = heap.pop() {
+        if heap.is_empty() {
+            // Only one input remaining, the rest of it is already sorted.
+            indices.extend((row_idx..keys[input_idx].num_rows()).map(|row| (input_idx, row)));
+            break;
+        }
+
+        indices.push((input_idx, row_idx));
+
+        if let Some(row) = keys[input_idx].row(row_idx + 1) {
+            heap.push(Reverse((row, input_idx, row_idx + 1)));
+        }
+    }
+
+    indices
+}
+
+/// Merge sorted batches into a single sorted batch.
+///
+/// `keys` contains the sort keys for each batch.
+pub fn merge_sorted_batches(batches: &[&Batch], keys: &[&ComparableRows]) -> Result<Batch> {
+    if batches.len() != keys.len() {
+        return Err(RayexecError::new(format!(
+            "Number of batches ({}) does not match number of keys ({})",
+            batches.len(),
+            keys.len()
+        )));
+    }
+
+    for (batch, keys) in batches.iter().zip(keys) {
+        if batch.num_rows() != keys.num_rows() {
+            return Err(RayexecError::new(format!(
+                "Batch has {} rows, but keys has {} rows",
+                batch.num_rows(),
+                keys.num_rows()
+            )));
+        }
+    }
+
+    interleave_batches(batches, &merge_indices(keys))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::array::Array;
+    use crate::arrays::row::encoding::{ComparableColumn, ComparableRowEncoder};
+    use crate::arrays::scalar::ScalarValue;
+
+    fn encode(arr: &Array, desc: bool) -> ComparableRows {
+        let encoder = ComparableRowEncoder {
+            columns: vec![ComparableColumn {
+                desc,
+                nulls_first: false,
+            }],
+        };
+        encoder.encode(&[arr]).unwrap()
+    }
+
+    #[test]
+    fn merge_three_inputs() {
+        let a = Array::from_iter([1, 4, 7]);
+        let b = Array::from_iter([2, 5, 8, 9]);
+        let c = Array::from_iter([3, 6]);
+        let keys = [encode(&a, false), encode(&b, false), encode(&c, false)];
+
+        let indices = merge_indices(&[&keys[0], &keys[1], &keys[2]]);
+        let expected = vec![
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (0, 1),
+            (1, 1),
+            (2, 1),
+            (0, 2),
+            (1, 2),
+            (1, 3),
+        ];
+        assert_eq!(expected, indices);
+    }
+
+    #[test]
+    fn merge_is_stable() {
+        let a = Array::from_iter([1, 2, 2]);
+        let b = Array::from_iter([2, 3]);
+        let keys = [encode(&a, false), encode(&b, false)];
+
+        let indices = merge_indices(&[&keys[0], &keys[1]]);
+        assert_eq!(vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1)], indices);
+    }
+
+    #[test]
+    fn merge_with_empty_input() {
+        let a = Array::from_iter([3, 1]);
+        let b = Array::from_iter(Vec::<i32>::new());
+        let keys = [encode(&a, true), encode(&b, true)];
+
+        let indices = merge_indices(&[&keys[0], &keys[1]]);
+        assert_eq!(vec![(0, 0), (0, 1)], indices);
+    }
+
+    #[test]
+    fn merge_batches_desc() {
+        let a = Batch::try_new([
+            Array::from_iter([9, 5, 1]),
+            Array::from_iter(["a", "b", "c"]),
+        ])
+        .unwrap();
+        let b = Batch::try_new([Array::from_iter([6, 2]), Array::from_iter(["d", "e"])]).unwrap();
+        let keys_a = encode(a.column(0).unwrap(), true);
+        let keys_b = encode(b.column(0).unwrap(), true);
+
+        let out = merge_sorted_batches(&[&a, &b], &[&keys_a, &keys_b]).unwrap();
+
+        let expected = [(9, "a"), (6, "d"), (5, "b"), (2, "e"), (1, "c")];
+        for (idx, (int, s)) in expected.into_iter().enumerate() {
+            assert_eq!(
+                ScalarValue::from(int),
+                out.column(0).unwrap().logical_value(idx).unwrap()
+            );
+            assert_eq!(
+                ScalarValue::from(s),
+                out.column(1).unwrap().logical_value(idx).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn merge_batches_mismatched_keys() {
+        let a = Batch::try_new([Array::from_iter([1, 2])]).unwrap();
+        let keys = encode(&Array::from_iter([1]), false);
+
+        merge_sorted_batches(&[&a], &[&keys]).unwrap_err();
+    }
+}
@@ -1,5 +1,9 @@
 //! Compute kernels.
+pub mod boolean;
 pub mod cast;
 pub mod date;
+pub mod dictionary;
+pub mod interleave;
+pub mod merge;
 
 pub mod util;
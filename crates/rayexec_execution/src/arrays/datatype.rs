@@ -48,6 +48,8 @@ pub enum DataTypeId {
     Binary,
     Struct,
     List,
+    Json,
+    Geometry,
 }
 
 impl DataTypeId {
@@ -79,6 +81,8 @@ impl DataTypeId {
             Self::Binary => "Binary",
             Self::Struct => "Struct",
             Self::List => "List",
+            Self::Json => "Json",
+            Self::Geometry => "Geometry",
         }
     }
 }
@@ -114,6 +118,8 @@ impl ProtoConv for DataTypeId {
             Self::Binary => Self::ProtoType::Binary,
             Self::Struct => Self::ProtoType::Struct,
             Self::List => Self::ProtoType::List,
+            Self::Json => Self::ProtoType::Json,
+            Self::Geometry => Self::ProtoType::Geometry,
         })
     }
 
@@ -146,6 +152,8 @@ impl ProtoConv for DataTypeId {
             Self::ProtoType::Binary => Self::Binary,
             Self::ProtoType::Struct => Self::Struct,
             Self::ProtoType::List => Self::List,
+            Self::ProtoType::Json => Self::Json,
+            Self::ProtoType::Geometry => Self::Geometry,
         })
     }
 }
@@ -246,6 +254,18 @@ impl ProtoConv for TimeUnit {
     }
 }
 
+impl TimeUnit {
+    /// Number of units in a second.
+    pub const fn units_per_second(&self) -> i64 {
+        match self {
+            Self::Second => 1,
+            Self::Millisecond => 1_000,
+            Self::Microsecond => 1_000_000,
+            Self::Nanosecond => 1_000_000_000,
+        }
+    }
+}
+
 impl fmt::Display for TimeUnit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -293,12 +313,23 @@ impl ProtoConv for StructTypeMeta {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ListTypeMeta {
     pub datatype: Box<DataType>,
+    /// Number of elements in every list, only set for fixed-size lists (e.g.
+    /// `FLOAT[3]`).
+    pub size: Option<usize>,
 }
 
 impl ListTypeMeta {
     pub fn new(element_type: DataType) -> Self {
         ListTypeMeta {
             datatype: Box::new(element_type),
+            size: None,
+        }
+    }
+
+    pub fn new_fixed_size(element_type: DataType, size: usize) -> Self {
+        ListTypeMeta {
+            datatype: Box::new(element_type),
+            size: Some(size),
         }
     }
 }
@@ -309,12 +340,14 @@ impl ProtoConv for ListTypeMeta {
     fn to_proto(&self) -> Result<Self::ProtoType> {
         Ok(Self::ProtoType {
             datatype: Some(Box::new(self.datatype.to_proto()?)),
+            size: self.size.map(|size| size as u64),
         })
     }
 
     fn from_proto(proto: Self::ProtoType) -> Result<Self> {
         Ok(Self {
             datatype: Box::new(DataType::from_proto(*proto.datatype.required("datatype")?)?),
+            size: proto.size.map(|size| size as usize),
         })
     }
 }
@@ -361,6 +394,15 @@ pub enum DataType {
     Struct(StructTypeMeta),
     /// A list of values all of the same type.
     List(ListTypeMeta),
+    /// JSON text.
+    ///
+    /// Physically stored the same as Utf8. Values are validated when casting
+    /// to this type.
+    Json,
+    /// A geometry encoded as well-known binary (WKB).
+    ///
+    /// Physically stored the same as Binary.
+    Geometry,
 }
 
 impl DataType {
@@ -411,6 +453,8 @@ impl DataType {
             DataTypeId::List => {
                 return Err(RayexecError::new("Cannot create a default List datatype"))
             }
+            DataTypeId::Json => DataType::Json,
+            DataTypeId::Geometry => DataType::Geometry,
         })
     }
 
@@ -442,6 +486,8 @@ impl DataType {
             DataType::Binary => DataTypeId::Binary,
             DataType::Struct(_) => DataTypeId::Struct,
             DataType::List(_) => DataTypeId::List,
+            DataType::Json => DataTypeId::Json,
+            DataType::Geometry => DataTypeId::Geometry,
         }
     }
 
@@ -472,6 +518,8 @@ impl DataType {
             DataType::Binary => PhysicalType::Binary,
             DataType::Struct(_) => not_implemented!("struct data type to physical type"),
             DataType::List(_) => PhysicalType::List,
+            DataType::Json => PhysicalType::Utf8,
+            DataType::Geometry => PhysicalType::Binary,
         })
     }
 
@@ -489,6 +537,14 @@ impl DataType {
         matches!(self, DataType::Utf8)
     }
 
+    pub const fn is_json(&self) -> bool {
+        matches!(self, DataType::Json)
+    }
+
+    pub const fn is_geometry(&self) -> bool {
+        matches!(self, DataType::Geometry)
+    }
+
     pub const fn is_primitive_numeric(&self) -> bool {
         matches!(
             self,
@@ -535,6 +591,22 @@ impl DataType {
         )
     }
 
+    pub const fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            DataType::Int8
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Int128
+                | DataType::UInt8
+                | DataType::UInt16
+                | DataType::UInt32
+                | DataType::UInt64
+                | DataType::UInt128
+        )
+    }
+
     pub const fn is_decimal(&self) -> bool {
         matches!(self, DataType::Decimal64(_) | DataType::Decimal128(_))
     }
@@ -583,6 +655,8 @@ impl ProtoConv for DataType {
             DataType::Binary => Value::TypeBinary(EmptyMeta {}),
             DataType::Struct(m) => Value::TypeStruct(m.to_proto()?),
             DataType::List(m) => Value::TypeList(Box::new(m.to_proto()?)),
+            DataType::Json => Value::TypeJson(EmptyMeta {}),
+            DataType::Geometry => Value::TypeGeometry(EmptyMeta {}),
         };
         Ok(Self::ProtoType { value: Some(value) })
     }
@@ -616,6 +690,8 @@ impl ProtoConv for DataType {
             Value::TypeBinary(_) => DataType::Binary,
             Value::TypeStruct(m) => DataType::Struct(StructTypeMeta::from_proto(m)?),
             Value::TypeList(m) => DataType::List(ListTypeMeta::from_proto(*m)?),
+            Value::TypeJson(_) => DataType::Json,
+            Value::TypeGeometry(_) => DataType::Geometry,
         })
     }
 }
@@ -657,7 +733,12 @@ impl fmt::Display for DataType {
                         .join(", ")
                 )
             }
-            DataType::List(meta) => write!(f, "List[{}]", meta.datatype),
+            DataType::List(meta) => match meta.size {
+                Some(size) => write!(f, "List[{}; {size}]", meta.datatype),
+                None => write!(f, "List[{}]", meta.datatype),
+            },
+            DataType::Json => write!(f, "Json"),
+            DataType::Geometry => write!(f, "Geometry"),
         }
     }
 }
@@ -2,8 +2,9 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 
 use super::physical_type::{AsBytes, VarlenType};
-use crate::arrays::array::{ArrayData, BinaryData};
+use crate::arrays::array::{Array, ArrayData, BinaryData};
 use crate::arrays::bitmap::Bitmap;
+use crate::arrays::buffer_pool;
 use crate::arrays::datatype::DataType;
 use crate::arrays::storage::{
     BooleanStorage,
@@ -116,7 +117,7 @@ where
 {
     pub fn with_len(len: usize) -> Self {
         PrimitiveBuffer {
-            values: vec![T::default(); len],
+            values: buffer_pool::alloc_vec(len),
         }
     }
 }
@@ -146,6 +147,11 @@ where
 pub struct GermanVarlenBuffer<T: ?Sized> {
     pub(crate) metadata: Vec<UnionedGermanMetadata>,
     pub(crate) data: Vec<u8>,
+    /// Out-of-line data shared with some other storage.
+    ///
+    /// When set, large values point into this instead of `data`, and `data` is
+    /// empty.
+    pub(crate) shared: Option<Arc<PrimitiveStorage<u8>>>,
     pub(crate) _type: PhantomData<T>,
 }
 
@@ -158,13 +164,57 @@ where
     }
 
     pub fn with_len_and_data_capacity(len: usize, data_cap: usize) -> Self {
+        let mut metadata = buffer_pool::alloc_vec_with_capacity(len);
+        metadata.resize(len, UnionedGermanMetadata::zero());
+
         GermanVarlenBuffer {
-            metadata: vec![UnionedGermanMetadata::zero(); len],
-            data: Vec::with_capacity(data_cap),
+            metadata,
+            data: buffer_pool::alloc_vec_with_capacity(data_cap),
+            shared: None,
             _type: PhantomData,
         }
     }
 
+    /// Create a buffer for values that are slices of values in `array`.
+    ///
+    /// Values pointing into the array's out-of-line data are stored as
+    /// references to that data instead of being copied. This lets kernels that
+    /// return part of their input (e.g. `substring`) share the input's data
+    /// buffer.
+    pub fn with_len_sharing(len: usize, array: &Array) -> Self {
+        let mut buffer = Self::with_len(len);
+        if let ArrayData::Binary(BinaryData::German(storage)) = array.array_data() {
+            buffer.share_data(storage.data.clone());
+        }
+        buffer
+    }
+
+    /// Share out-of-line data with some other storage.
+    ///
+    /// Values put after this that point into `data` are stored as references
+    /// to it. Values that don't will cause everything to be copied into this
+    /// buffer's own data.
+    ///
+    /// Values referencing previously shared data are copied first. Returns
+    /// false without sharing if this buffer holds copied out-of-line values.
+    pub fn share_data(&mut self, data: Arc<PrimitiveStorage<u8>>) -> bool {
+        if self
+            .shared
+            .as_ref()
+            .is_some_and(|shared| Arc::ptr_eq(shared, &data))
+        {
+            return true;
+        }
+
+        self.unshare();
+        if !self.data.is_empty() {
+            return false;
+        }
+
+        self.shared = Some(data);
+        true
+    }
+
     pub fn get(&self, idx: usize) -> Option<&[u8]> {
         let metadata = self.metadata.get(idx)?;
 
@@ -173,7 +223,7 @@ where
                 Some(&inline[..(*len as usize)])
             }
             GermanMetadata::Large(GermanLargeMetadata { len, offset, .. }) => {
-                Some(&self.data[(*offset as usize)..((offset + len) as usize)])
+                Some(&self.out_of_line_data()[(*offset as usize)..((offset + len) as usize)])
             }
         }
     }
@@ -187,7 +237,7 @@ where
     }
 
     pub fn reserve_data(&mut self, additional: usize) {
-        self.data.reserve(additional)
+        reserve_pooled(&mut self.data, additional)
     }
 
     pub fn truncate(&mut self, len: usize) {
@@ -198,7 +248,60 @@ where
         GermanVarlenBufferIter {
             idx: 0,
             metadata: &self.metadata,
-            data: &self.data,
+            data: self.out_of_line_data(),
+        }
+    }
+}
+
+impl<T: ?Sized> GermanVarlenBuffer<T> {
+    /// Convert the buffer into storage without wrapping it in array data.
+    pub fn into_storage(self) -> GermanVarlenStorage {
+        let data = match self.shared {
+            Some(shared) => shared,
+            None => Arc::new(self.data.into()),
+        };
+
+        GermanVarlenStorage {
+            metadata: self.metadata.into(),
+            data,
+        }
+    }
+
+    /// Copy values pointing into shared data into this buffer's own data.
+    fn unshare(&mut self) {
+        let shared = match self.shared.take() {
+            Some(shared) => shared,
+            None => return,
+        };
+
+        for meta in self.metadata.iter_mut() {
+            if let GermanMetadata::Large(large) = meta.as_metadata() {
+                let (offset, len) = (large.offset as usize, large.len as usize);
+                reserve_pooled(&mut self.data, len);
+                let new_offset = self.data.len();
+                self.data
+                    .extend_from_slice(&shared.as_slice()[offset..(offset + len)]);
+                meta.as_large_mut().offset = new_offset as i32;
+            }
+        }
+    }
+
+    /// Get the offset of `val` in the shared data if it points into it.
+    fn shared_offset(&self, val: &[u8]) -> Option<usize> {
+        let shared = self.shared.as_ref()?.as_slice();
+        let start = (val.as_ptr() as usize).checked_sub(shared.as_ptr() as usize)?;
+        if start + val.len() <= shared.len() {
+            Some(start)
+        } else {
+            None
+        }
+    }
+
+    /// Out-of-line data that large values point into.
+    fn out_of_line_data(&self) -> &[u8] {
+        match &self.shared {
+            Some(shared) => shared.as_slice(),
+            None => &self.data,
         }
     }
 }
@@ -223,7 +326,18 @@ where
             meta.inline[0..val.len()].copy_from_slice(val);
         } else {
             // Store prefix, buf index, and offset in line. Store complete copy
-            // in buffer.
+            // in buffer unless the value is already in the shared data.
+            let offset = match self.shared_offset(val) {
+                Some(offset) => offset,
+                None => {
+                    self.unshare();
+                    reserve_pooled(&mut self.data, val.len());
+                    let offset = self.data.len();
+                    self.data.extend_from_slice(val);
+                    offset
+                }
+            };
+
             let meta = self.metadata[idx].as_large_mut();
             meta.len = val.len() as i32;
 
@@ -234,23 +348,30 @@ where
             meta.buffer_idx = 0;
 
             // Offset, 4 bytes
-            let offset = self.data.len();
             meta.offset = offset as i32;
-
-            self.data.extend_from_slice(val);
         }
     }
 
     fn into_data(self) -> ArrayData {
-        let storage = GermanVarlenStorage {
-            metadata: self.metadata.into(),
-            data: self.data.into(),
-        };
-
+        let storage = self.into_storage();
         ArrayData::Binary(BinaryData::German(Arc::new(storage)))
     }
 }
 
+/// Reserve space for at least `additional` more bytes, growing through the
+/// buffer pool so that the data buffer can be recycled once the resulting array
+/// is dropped.
+fn reserve_pooled(data: &mut Vec<u8>, additional: usize) {
+    if data.capacity() - data.len() >= additional {
+        return;
+    }
+
+    let cap = usize::max(data.capacity() * 2, data.len() + additional);
+    let mut grown = buffer_pool::alloc_vec_with_capacity(cap);
+    grown.extend_from_slice(data);
+    buffer_pool::recycle_vec(std::mem::replace(data, grown));
+}
+
 #[derive(Debug)]
 pub struct GermanVarlenBufferIter<'a> {
     idx: usize,
@@ -275,3 +396,47 @@ impl<'a> Iterator for GermanVarlenBufferIter<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn german_buffer_shares_data() {
+        let storage = GermanVarlenStorage::with_value("a value that's too long to inline");
+        let data = storage.data().clone();
+        let val = storage.get(0).unwrap();
+
+        let mut buffer = GermanVarlenBuffer::<[u8]>::with_len(2);
+        assert!(buffer.share_data(data.clone()));
+        buffer.put(0, &val[2..]);
+        buffer.put(1, b"short");
+
+        assert_eq!(b"value that's too long to inline", buffer.get(0).unwrap());
+        let out = buffer.into_storage();
+        assert!(Arc::ptr_eq(&data, out.data()));
+        assert_eq!(b"short", out.get(1).unwrap());
+    }
+
+    #[test]
+    fn german_buffer_copies_on_unshared_value() {
+        let storage = GermanVarlenStorage::with_value("a value that's too long to inline");
+        let data = storage.data().clone();
+
+        let mut buffer = GermanVarlenBuffer::<[u8]>::with_len(2);
+        buffer.share_data(data.clone());
+        buffer.put(0, storage.get(0).unwrap());
+        buffer.put(1, b"another value that's too long to inline");
+
+        // Can't share again once values have been copied.
+        assert!(!buffer.share_data(data.clone()));
+
+        let out = buffer.into_storage();
+        assert!(!Arc::ptr_eq(&data, out.data()));
+        assert_eq!(b"a value that's too long to inline", out.get(0).unwrap());
+        assert_eq!(
+            b"another value that's too long to inline",
+            out.get(1).unwrap()
+        );
+    }
+}
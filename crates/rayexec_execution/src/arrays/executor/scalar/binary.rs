@@ -1,6 +1,6 @@
 use rayexec_error::Result;
 
-use super::check_validity;
+use super::{check_validity, combine_validities};
 use crate::arrays::array::Array;
 use crate::arrays::bitmap::Bitmap;
 use crate::arrays::executor::builder::{ArrayBuilder, ArrayDataBuffer, OutputBuffer};
@@ -41,7 +41,28 @@ impl BinaryExecutor {
             buffer: builder.buffer,
         };
 
-        if validity1.is_some() || validity2.is_some() {
+        // Without selections, validities map directly to output rows and can be
+        // combined up front instead of checking each one per row.
+        let combined_validity = if selection1.is_none() && selection2.is_none() {
+            combine_validities([validity1, validity2])?
+        } else {
+            None
+        };
+
+        if let Some(validity) = combined_validity {
+            let values1 = S1::get_storage(&array1.data)?;
+            let values2 = S2::get_storage(&array2.data)?;
+
+            for idx in validity.index_iter() {
+                let val1 = unsafe { values1.get_unchecked(idx) };
+                let val2 = unsafe { values2.get_unchecked(idx) };
+
+                output_buffer.idx = idx;
+                op(val1, val2, &mut output_buffer);
+            }
+
+            out_validity = Some(validity.into())
+        } else if validity1.is_some() || validity2.is_some() {
             let values1 = S1::get_storage(&array1.data)?;
             let values2 = S2::get_storage(&array2.data)?;
 
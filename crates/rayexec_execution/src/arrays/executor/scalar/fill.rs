@@ -1,4 +1,4 @@
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 
 use rayexec_error::{RayexecError, Result};
 
@@ -118,6 +118,37 @@ where
         Ok(())
     }
 
+    /// Fill the buffer starting at `offset` with all rows from `array`.
+    ///
+    /// If `array` has no selection, its validity is copied over in bulk
+    /// instead of a bit at a time.
+    pub fn fill_contiguous<'a, S>(&mut self, array: &'a Array, offset: usize) -> Result<()>
+    where
+        S: PhysicalStorage,
+        S::Type<'a>: Borrow<<B as ArrayDataBuffer>::Type>,
+    {
+        match (array.selection_vector(), array.validity()) {
+            (None, Some(validity)) => {
+                self.validity.copy_from(offset, validity)?;
+
+                let values = S::get_storage(&array.data)?;
+                for idx in validity.index_iter() {
+                    let val = unsafe { values.get_unchecked(idx) };
+                    self.builder.buffer.put(idx + offset, val.borrow());
+                }
+
+                Ok(())
+            }
+            _ => {
+                let iter = (0..array.logical_len()).map(|idx| FillMapping {
+                    from: idx,
+                    to: idx + offset,
+                });
+                self.fill::<S, _>(array, iter)
+            }
+        }
+    }
+
     pub fn finish(self) -> Array {
         let validity = if self.validity.is_all_true() {
             None
@@ -282,24 +313,71 @@ pub(crate) fn concat_with_exact_total_len(arrays: &[&Array], total_len: usize) -
     }
 }
 
-fn concat_lists(datatype: DataType, arrays: &[&Array], total_len: usize) -> Result<Array> {
-    let inner_arrays = arrays
+/// List storages and child arrays for a set of list arrays.
+#[derive(Debug)]
+struct ListChildren<'a> {
+    lists: Vec<&'a ListStorage>,
+    /// Child array per list.
+    ///
+    /// List metadata references child rows by location, so children with a
+    /// selection are materialized. Children without any rows can't be
+    /// referenced by the metadata and are `None` since they may not match the
+    /// list's element type (e.g. the untyped NULL child of a NULL list).
+    children: Vec<Option<Cow<'a, Array>>>,
+}
+
+fn list_children<'a>(arrays: &[&'a Array]) -> Result<ListChildren<'a>> {
+    let lists = arrays
         .iter()
         .map(|arr| match arr.array_data() {
-            ArrayData::List(list) => {
-                if list.array.has_selection() {
-                    return Err(RayexecError::new("List child array has selection"));
-                }
-                Ok(&list.array)
-            }
+            ArrayData::List(list) => Ok(list.as_ref()),
             other => Err(RayexecError::new(format!(
-                "Invalid inner array data for concatenating lists, got {:?}",
+                "Invalid inner array data for list, got {:?}",
                 other.physical_type()
             ))),
         })
         .collect::<Result<Vec<_>>>()?;
 
-    let concatenated = concat(&inner_arrays)?;
+    let children = lists
+        .iter()
+        .map(|list| {
+            if list.array.logical_len() == 0 {
+                Ok(None)
+            } else if list.array.has_selection() {
+                Ok(Some(Cow::Owned(list.array.unselect()?)))
+            } else {
+                Ok(Some(Cow::Borrowed(&list.array)))
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ListChildren { lists, children })
+}
+
+/// Create an empty child array for a list data type.
+fn empty_list_child(datatype: &DataType) -> Result<Array> {
+    let element_type = match datatype {
+        DataType::List(meta) => meta.datatype.as_ref().clone(),
+        other => {
+            return Err(RayexecError::new(format!(
+                "Expected list data type, got {other}"
+            )))
+        }
+    };
+    let data = element_type.physical_type()?.zeroed_array_data(0);
+
+    Ok(Array::new_with_array_data(element_type, data))
+}
+
+fn concat_lists(datatype: DataType, arrays: &[&Array], total_len: usize) -> Result<Array> {
+    let ListChildren { children, .. } = list_children(arrays)?;
+
+    let inner_arrays: Vec<_> = children.iter().flatten().map(|c| c.as_ref()).collect();
+    let concatenated = if inner_arrays.is_empty() {
+        empty_list_child(&datatype)?
+    } else {
+        concat(&inner_arrays)?
+    };
 
     // Update metadata objects.
     let mut metadatas = Vec::with_capacity(total_len);
@@ -307,7 +385,7 @@ fn concat_lists(datatype: DataType, arrays: &[&Array], total_len: usize) -> Resu
 
     let mut acc_rows = 0;
 
-    for (array, child_array) in arrays.iter().zip(inner_arrays) {
+    for (array, child_array) in arrays.iter().zip(&children) {
         UnaryExecutor::for_each::<PhysicalList, _>(array, |_row_num, metadata| match metadata {
             Some(metadata) => {
                 metadatas.push(ListItemMetadata {
@@ -321,7 +399,9 @@ fn concat_lists(datatype: DataType, arrays: &[&Array], total_len: usize) -> Resu
             }
         })?;
 
-        acc_rows += child_array.logical_len() as i32;
+        if let Some(child_array) = child_array {
+            acc_rows += child_array.logical_len() as i32;
+        }
     }
 
     let data = ListStorage {
@@ -349,15 +429,8 @@ where
     let mut offset = 0;
 
     for array in arrays {
-        let len = array.logical_len();
-        let iter = (0..len).map(|idx| FillMapping {
-            from: idx,
-            to: idx + offset,
-        });
-
-        fill_state.fill::<S, _>(array, iter)?;
-
-        offset += len;
+        fill_state.fill_contiguous::<S>(array, offset)?;
+        offset += array.logical_len();
     }
 
     Ok(fill_state.finish())
@@ -502,13 +575,83 @@ pub fn interleave(arrays: &[&Array], indices: &[(usize, usize)]) -> Result<Array
             });
             interleave_with_fill_state::<PhysicalBinary, _>(arrays, indices, state)
         }
-        PhysicalType::List => {
-            // TODO: Also doable
-            Err(RayexecError::new(
-                "interleaving list arrays not yet supported",
-            ))
+        PhysicalType::List => interleave_lists(datatype.clone(), arrays, indices),
+    }
+}
+
+fn interleave_lists(
+    datatype: DataType,
+    arrays: &[&Array],
+    indices: &[(usize, usize)],
+) -> Result<Array> {
+    let ListChildren { lists, children } = list_children(arrays)?;
+
+    // Map input array indices to indices into the non-empty child arrays.
+    let mut inner_arrays = Vec::with_capacity(children.len());
+    let child_idx_map: Vec<_> = children
+        .iter()
+        .map(|child| {
+            child.as_ref().map(|child| {
+                inner_arrays.push(child.as_ref());
+                inner_arrays.len() - 1
+            })
+        })
+        .collect();
+
+    let mut metadatas = Vec::with_capacity(indices.len());
+    let mut validity = Bitmap::new_with_all_true(indices.len());
+    // Interleave indices for the child arrays.
+    let mut inner_indices = Vec::new();
+
+    for (out_idx, &(array_idx, row_idx)) in indices.iter().enumerate() {
+        let array = arrays[array_idx];
+        let location = selection::get(array.selection_vector(), row_idx);
+
+        if let Some(array_validity) = array.validity() {
+            if !array_validity.value(location) {
+                metadatas.push(ListItemMetadata::default());
+                validity.set_unchecked(out_idx, false);
+                continue;
+            }
+        }
+
+        let metadata = lists[array_idx].metadata.as_slice()[location];
+        metadatas.push(ListItemMetadata {
+            offset: inner_indices.len() as i32,
+            len: metadata.len,
+        });
+
+        if metadata.len > 0 {
+            let child_idx = child_idx_map[array_idx]
+                .ok_or_else(|| RayexecError::new("List references rows in empty child array"))?;
+            inner_indices.extend(
+                (metadata.offset..metadata.offset + metadata.len)
+                    .map(|inner_row| (child_idx, inner_row as usize)),
+            );
         }
     }
+
+    let inner = if inner_arrays.is_empty() {
+        empty_list_child(&datatype)?
+    } else {
+        interleave(&inner_arrays, &inner_indices)?
+    };
+
+    let data = ListStorage {
+        metadata: PrimitiveStorage::from(metadatas),
+        array: inner,
+    };
+
+    Ok(Array {
+        datatype,
+        selection: None,
+        validity: if validity.is_all_true() {
+            None
+        } else {
+            Some(validity.into())
+        },
+        data: data.into(),
+    })
 }
 
 fn interleave_with_fill_state<'a, S, B>(
@@ -548,10 +691,11 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::arrays::datatype::DataType;
+    use crate::arrays::datatype::{DataType, ListTypeMeta};
     use crate::arrays::executor::builder::PrimitiveBuffer;
     use crate::arrays::executor::physical_type::PhysicalI32;
     use crate::arrays::scalar::ScalarValue;
+    use crate::arrays::selection::SelectionVector;
 
     #[test]
     fn fill_simple_linear() {
@@ -701,6 +845,30 @@ mod tests {
         assert_eq!(ScalarValue::from(8), got.logical_value(4).unwrap());
     }
 
+    #[test]
+    fn concat_with_nulls_unaligned() {
+        // Lengths not a multiple of 8 so validities are copied at unaligned
+        // offsets.
+        let arr1 = Array::from_iter([Some(1), None, Some(3)]);
+        let arr2 = Array::from_iter([4, 5]);
+        let arr3 = Array::from_iter((0..20).map(|v| if v % 3 == 0 { None } else { Some(v) }));
+
+        let got = concat(&[&arr1, &arr2, &arr3]).unwrap();
+        assert_eq!(25, got.logical_len());
+
+        let expected: Vec<_> = [Some(1), None, Some(3), Some(4), Some(5)]
+            .into_iter()
+            .chain((0..20).map(|v| if v % 3 == 0 { None } else { Some(v) }))
+            .collect();
+        for (idx, expected) in expected.into_iter().enumerate() {
+            let expected = match expected {
+                Some(v) => ScalarValue::from(v),
+                None => ScalarValue::Null,
+            };
+            assert_eq!(expected, got.logical_value(idx).unwrap(), "idx: {idx}");
+        }
+    }
+
     #[test]
     fn concat_lists() {
         let arr1 = ScalarValue::List(vec![1.into(), 2.into()])
@@ -721,4 +889,101 @@ mod tests {
             got.logical_value(1).unwrap()
         );
     }
+
+    #[test]
+    fn concat_lists_with_null_list() {
+        let arr1 =
+            Array::new_typed_null_array(DataType::List(ListTypeMeta::new(DataType::Int32)), 2)
+                .unwrap();
+        let arr2 = ScalarValue::List(vec![1.into(), 2.into()])
+            .as_array(1)
+            .unwrap();
+
+        let got = concat(&[&arr1, &arr2]).unwrap();
+
+        assert_eq!(ScalarValue::Null, got.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Null, got.logical_value(1).unwrap());
+        assert_eq!(
+            ScalarValue::List(vec![1.into(), 2.into()]),
+            got.logical_value(2).unwrap()
+        );
+    }
+
+    fn list_array(lists: &[Option<&[i32]>]) -> Array {
+        let mut metadata = Vec::new();
+        let mut values = Vec::new();
+        let mut validity = Bitmap::new_with_all_true(lists.len());
+        for (idx, list) in lists.iter().enumerate() {
+            match list {
+                Some(list) => {
+                    metadata.push(ListItemMetadata {
+                        offset: values.len() as i32,
+                        len: list.len() as i32,
+                    });
+                    values.extend_from_slice(list);
+                }
+                None => {
+                    metadata.push(ListItemMetadata::default());
+                    validity.set_unchecked(idx, false);
+                }
+            }
+        }
+
+        Array::new_with_validity_and_array_data(
+            DataType::List(ListTypeMeta::new(DataType::Int32)),
+            validity,
+            ListStorage::try_new(metadata, Array::from_iter(values)).unwrap(),
+        )
+    }
+
+    fn list_value(list: &[i32]) -> ScalarValue<'static> {
+        ScalarValue::List(list.iter().map(|v| (*v).into()).collect())
+    }
+
+    #[test]
+    fn interleave_lists() {
+        let arr1 = list_array(&[Some(&[1, 2]), None, Some(&[3])]);
+        let mut arr2 = list_array(&[Some(&[4, 5, 6]), Some(&[])]);
+        // Logical rows: [], [4, 5, 6]
+        arr2.select_mut(SelectionVector::from_iter([1, 0]));
+
+        let got = interleave(&[&arr1, &arr2], &[(1, 1), (0, 1), (0, 2), (1, 0), (0, 0)]).unwrap();
+
+        let expected = [
+            list_value(&[4, 5, 6]),
+            ScalarValue::Null,
+            list_value(&[3]),
+            list_value(&[]),
+            list_value(&[1, 2]),
+        ];
+        for (idx, expected) in expected.into_iter().enumerate() {
+            assert_eq!(expected, got.logical_value(idx).unwrap(), "idx: {idx}");
+        }
+    }
+
+    #[test]
+    fn interleave_nested_lists() {
+        let inner1 = list_array(&[Some(&[1]), Some(&[2, 3])]);
+        let inner2 = list_array(&[Some(&[4, 5])]);
+        let outer_type = DataType::List(ListTypeMeta::new(inner1.datatype().clone()));
+        let arr1 = Array::new_with_array_data(
+            outer_type.clone(),
+            ListStorage::try_new(vec![ListItemMetadata { offset: 0, len: 2 }], inner1).unwrap(),
+        );
+        let arr2 = Array::new_with_array_data(
+            outer_type,
+            ListStorage::try_new(vec![ListItemMetadata { offset: 0, len: 1 }], inner2).unwrap(),
+        );
+
+        let got = interleave(&[&arr1, &arr2], &[(1, 0), (0, 0)]).unwrap();
+
+        assert_eq!(
+            ScalarValue::List(vec![list_value(&[4, 5])]),
+            got.logical_value(0).unwrap()
+        );
+        assert_eq!(
+            ScalarValue::List(vec![list_value(&[1]), list_value(&[2, 3])]),
+            got.logical_value(1).unwrap()
+        );
+    }
 }
@@ -3,6 +3,7 @@ use half::f16;
 use rayexec_error::{RayexecError, Result};
 
 use crate::arrays::array::{Array, ArrayData};
+use crate::arrays::compute::dictionary::DictionaryCodes;
 use crate::arrays::executor::physical_type::{
     PhysicalBinary,
     PhysicalBool,
@@ -27,7 +28,7 @@ use crate::arrays::executor::physical_type::{
 };
 use crate::arrays::scalar::interval::Interval;
 use crate::arrays::selection;
-use crate::arrays::storage::{AddressableStorage, UntypedNull};
+use crate::arrays::storage::{AddressableStorage, ListItemMetadata, UntypedNull};
 
 /// State used for all hashing operations during physical execution.
 pub const HASH_RANDOM_STATE: RandomState = RandomState::with_seeds(0, 0, 0, 0);
@@ -39,6 +40,10 @@ impl HashExecutor {
     /// Hashes the given array values, combining them with the existing hashes
     /// in `hashes`.
     pub fn hash_combine(array: &Array, hashes: &mut [u64]) -> Result<()> {
+        if Self::try_hash_dictionary::<CombineSetHash>(array, hashes)? {
+            return Ok(());
+        }
+
         match array.physical_type() {
             PhysicalType::UntypedNull => {
                 Self::hash_one_inner::<PhysicalUntypedNull, CombineSetHash>(array, hashes)?
@@ -103,6 +108,10 @@ impl HashExecutor {
     /// Hash the given array and write the values into `hashes`, overwriting any
     /// existing values.
     pub fn hash_no_combine(array: &Array, hashes: &mut [u64]) -> Result<()> {
+        if Self::try_hash_dictionary::<OverwriteSetHash>(array, hashes)? {
+            return Ok(());
+        }
+
         match array.physical_type() {
             PhysicalType::UntypedNull => {
                 Self::hash_one_inner::<PhysicalUntypedNull, OverwriteSetHash>(array, hashes)?
@@ -178,6 +187,37 @@ impl HashExecutor {
         Ok(hashes)
     }
 
+    /// Hash each distinct value once for dictionary encoded strings, setting
+    /// hashes for rows from the value hashes.
+    ///
+    /// Returns false if the array isn't dictionary encoded.
+    fn try_hash_dictionary<H>(array: &Array, hashes: &mut [u64]) -> Result<bool>
+    where
+        H: SetHash,
+    {
+        // Only worth it for values that are expensive to hash.
+        if !matches!(
+            array.physical_type(),
+            PhysicalType::Utf8 | PhysicalType::Binary
+        ) {
+            return Ok(false);
+        }
+
+        let dict = match DictionaryCodes::try_from_array(array) {
+            Some(dict) => dict,
+            None => return Ok(false),
+        };
+
+        let mut value_hashes = vec![0; dict.values.logical_len()];
+        Self::hash_no_combine(&dict.values, &mut value_hashes)?;
+
+        for (hash, code) in hashes.iter_mut().zip(dict.codes.iter_locations()) {
+            H::set_hash(value_hashes[code], hash);
+        }
+
+        Ok(true)
+    }
+
     fn hash_one_inner<'a, 'b, S, H>(array: &'a Array, hashes: &'b mut [u64]) -> Result<()>
     where
         S: PhysicalStorage,
@@ -243,17 +283,7 @@ impl HashExecutor {
 
                     if validity.value(sel) {
                         let val = unsafe { metadata.get_unchecked(sel) };
-
-                        // Set first hash.
-                        H::set_hash(list_hashes_buf[val.offset as usize], hash);
-
-                        // Combine all the rest.
-                        for hash_idx in 1..val.len {
-                            CombineSetHash::set_hash(
-                                list_hashes_buf[(val.offset + hash_idx) as usize],
-                                hash,
-                            );
-                        }
+                        Self::set_list_hash::<H>(&val, &list_hashes_buf, hash);
                     } else {
                         H::set_hash(null_hash_value(), hash);
                     }
@@ -263,23 +293,35 @@ impl HashExecutor {
                 for (idx, hash) in hashes.iter_mut().enumerate() {
                     let sel = unsafe { selection::get_unchecked(selection, idx) };
                     let val = unsafe { metadata.get_unchecked(sel) };
-
-                    // Set first hash.
-                    H::set_hash(list_hashes_buf[val.offset as usize], hash);
-
-                    // Combine all the rest.
-                    for hash_idx in 1..val.len {
-                        CombineSetHash::set_hash(
-                            list_hashes_buf[(val.offset + hash_idx) as usize],
-                            hash,
-                        );
-                    }
+                    Self::set_list_hash::<H>(&val, &list_hashes_buf, hash);
                 }
             }
         }
 
         Ok(())
     }
+
+    /// Set the hash for a single list using the hashes of all child values.
+    fn set_list_hash<H>(metadata: &ListItemMetadata, child_hashes: &[u64], hash: &mut u64)
+    where
+        H: SetHash,
+    {
+        if metadata.len == 0 {
+            H::set_hash(empty_list_hash_value(), hash);
+            return;
+        }
+
+        let start = metadata.offset as usize;
+        let end = start + metadata.len as usize;
+
+        // Set first hash.
+        H::set_hash(child_hashes[start], hash);
+
+        // Combine all the rest.
+        for &child_hash in &child_hashes[(start + 1)..end] {
+            CombineSetHash::set_hash(child_hash, hash);
+        }
+    }
 }
 
 trait SetHash {
@@ -311,6 +353,11 @@ fn null_hash_value() -> u64 {
     HASH_RANDOM_STATE.hash_one(1)
 }
 
+/// All empty lists should hash to the same value, distinct from NULL.
+fn empty_list_hash_value() -> u64 {
+    HASH_RANDOM_STATE.hash_one(2)
+}
+
 /// Combines two hashes into one hash
 ///
 /// This implementation came from datafusion.
@@ -374,3 +421,66 @@ impl HashValue for UntypedNull {
         null_hash_value()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::datatype::{DataType, ListTypeMeta};
+    use crate::arrays::selection::SelectionVector;
+    use crate::arrays::storage::ListStorage;
+
+    #[test]
+    fn hash_dictionary_matches_materialized() {
+        let mut dict = Array::from_iter(["a", "b", "c", ""]);
+        dict.set_physical_validity(3, false);
+        dict.select_mut(SelectionVector::from_iter([2, 0, 3, 2, 2, 3]));
+
+        let mut materialized = Array::from_iter(["c", "a", "", "c", "c", ""]);
+        materialized.set_physical_validity(2, false);
+        materialized.set_physical_validity(5, false);
+
+        let mut dict_hashes = vec![0; 6];
+        HashExecutor::hash_many(&[dict.clone(), dict], &mut dict_hashes).unwrap();
+        let mut materialized_hashes = vec![0; 6];
+        HashExecutor::hash_many(
+            &[materialized.clone(), materialized],
+            &mut materialized_hashes,
+        )
+        .unwrap();
+
+        assert_eq!(materialized_hashes, dict_hashes);
+    }
+
+    #[test]
+    fn hash_empty_lists() {
+        let list_type = DataType::List(ListTypeMeta::new(DataType::Int32));
+
+        // All lists empty, child array has no rows.
+        let metadata = vec![ListItemMetadata { offset: 0, len: 0 }; 2];
+        let mut arr = Array::new_with_array_data(
+            list_type.clone(),
+            ListStorage::try_new(metadata, Array::from_iter(Vec::<i32>::new())).unwrap(),
+        );
+        arr.set_physical_validity(1, false);
+
+        let mut hashes = vec![0; 2];
+        HashExecutor::hash_no_combine(&arr, &mut hashes).unwrap();
+        assert_eq!(empty_list_hash_value(), hashes[0]);
+        assert_eq!(null_hash_value(), hashes[1]);
+
+        // Empty list followed by a non-empty list.
+        let metadata = vec![
+            ListItemMetadata { offset: 0, len: 0 },
+            ListItemMetadata { offset: 0, len: 2 },
+        ];
+        let arr = Array::new_with_array_data(
+            list_type,
+            ListStorage::try_new(metadata, Array::from_iter([1, 2])).unwrap(),
+        );
+
+        let mut hashes = vec![0; 2];
+        HashExecutor::hash_no_combine(&arr, &mut hashes).unwrap();
+        assert_eq!(empty_list_hash_value(), hashes[0]);
+        assert_ne!(empty_list_hash_value(), hashes[1]);
+    }
+}
@@ -1,4 +1,4 @@
-use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_error::{RayexecError, Result};
 
 use crate::arrays::array::{Array, ArrayData};
 use crate::arrays::bitmap::Bitmap;
@@ -147,8 +147,64 @@ impl<const ALLOW_DIFFERENT_LENS: bool, const ALLOW_NULLS: bool>
                 })
             }
         } else {
-            // let mut out_validity = None;
-            not_implemented!("list validity execute")
+            // Null lists produce null outputs.
+            let metadata1 = PhysicalList::get_storage(array1.array_data())?;
+            let metadata2 = PhysicalList::get_storage(array2.array_data())?;
+
+            let (values1, inner_validity1) = get_inner_array_storage::<S>(array1)?;
+            let (values2, inner_validity2) = get_inner_array_storage::<S>(array2)?;
+
+            let inner_sel1 = get_inner_array_selection(array1)?;
+            let inner_sel2 = get_inner_array_selection(array2)?;
+
+            let mut out_validity = Bitmap::new_with_all_true(len);
+
+            for idx in 0..len {
+                let sel1 = unsafe { selection::get_unchecked(selection1, idx) };
+                let sel2 = unsafe { selection::get_unchecked(selection2, idx) };
+
+                if !check_validity(sel1, validity1) || !check_validity(sel2, validity2) {
+                    out_validity.set_unchecked(idx, false);
+                    continue;
+                }
+
+                let m1 = unsafe { metadata1.get_unchecked(sel1) };
+                let m2 = unsafe { metadata2.get_unchecked(sel2) };
+
+                let len = Self::item_iter_len(m1, m2)?;
+
+                let mut reducer = R::new(m1.len, m2.len);
+
+                for inner_idx in 0..len {
+                    let idx1 = m1.offset + inner_idx;
+                    let idx2 = m2.offset + inner_idx;
+
+                    let sel1 = unsafe { selection::get_unchecked(inner_sel1, idx1 as usize) };
+                    let sel2 = unsafe { selection::get_unchecked(inner_sel2, idx2 as usize) };
+
+                    if check_validity(sel1, inner_validity1)
+                        && check_validity(sel2, inner_validity2)
+                    {
+                        let v1 = unsafe { values1.get_unchecked(sel1) };
+                        let v2 = unsafe { values2.get_unchecked(sel2) };
+
+                        reducer.put_values(v1, v2);
+                    } else if !ALLOW_NULLS {
+                        return Err(RayexecError::new("Cannot reduce list containing NULLs"));
+                    }
+                }
+
+                let out = reducer.finish();
+
+                builder.buffer.put(idx, &out);
+            }
+
+            Ok(Array {
+                datatype: builder.datatype,
+                selection: None,
+                validity: Some(out_validity.into()),
+                data: builder.buffer.into_data(),
+            })
         }
     }
 
@@ -46,6 +46,25 @@ pub fn check_validity(idx: usize, validity: Option<&Bitmap>) -> bool {
     }
 }
 
+/// AND multiple validities into a single validity.
+///
+/// Validities must all be the same length and map directly to output rows
+/// (no selection). Returns None if none of the inputs have a validity.
+pub fn combine_validities<'a, I>(validities: I) -> Result<Option<Bitmap>>
+where
+    I: IntoIterator<Item = Option<&'a Bitmap>>,
+{
+    let mut combined: Option<Bitmap> = None;
+    for validity in validities.into_iter().flatten() {
+        match combined.as_mut() {
+            Some(combined) => combined.bit_and_mut(validity)?,
+            None => combined = Some(validity.clone()),
+        }
+    }
+
+    Ok(combined)
+}
+
 pub fn can_skip_validity_check<'a, I>(validities: I) -> bool
 where
     I: IntoIterator<Item = Option<&'a Bitmap>>,
@@ -6,6 +6,13 @@ use std::fmt;
 use rayexec_error::Result;
 
 use crate::arrays::array::Array;
+use crate::arrays::compute::cast::format::{
+    Float16Formatter,
+    Float32Formatter,
+    Float64Formatter,
+    FloatNotation,
+    Formatter as _,
+};
 use crate::arrays::scalar::ScalarValue;
 
 /// Formatting options for arrays and scalars.
@@ -16,6 +23,9 @@ pub struct FormatOptions<'a> {
 
     /// String to use when a string value is empty.
     pub empty_string: &'a str,
+
+    /// Notation to use for floats.
+    pub float_notation: FloatNotation,
 }
 
 impl FormatOptions<'_> {
@@ -23,6 +33,7 @@ impl FormatOptions<'_> {
         FormatOptions {
             null: "NULL",
             empty_string: "",
+            float_notation: FloatNotation::Fixed,
         }
     }
 }
@@ -82,6 +93,15 @@ impl fmt::Display for FormattedScalarValue<'_, '_> {
                     write!(f, "{v}")
                 }
             }
+            ScalarValue::Float16(v) => {
+                Float16Formatter::new(self.options.float_notation).write(v, f)
+            }
+            ScalarValue::Float32(v) => {
+                Float32Formatter::new(self.options.float_notation).write(v, f)
+            }
+            ScalarValue::Float64(v) => {
+                Float64Formatter::new(self.options.float_notation).write(v, f)
+            }
             other => write!(f, "{other}"), // Use the scalar value's default display impl.
         }
     }
@@ -142,4 +162,23 @@ mod tests {
             .to_string();
         assert_eq!("(empty)", out);
     }
+
+    #[test]
+    fn float_notation() {
+        let opts = FormatOptions {
+            float_notation: FloatNotation::Postgres,
+            ..FormatOptions::new()
+        };
+        let formatter = Formatter::new(opts);
+
+        let out = formatter
+            .format_scalar_value(ScalarValue::Float64(1e15))
+            .to_string();
+        assert_eq!("1e+15", out);
+
+        let out = formatter
+            .format_scalar_value(ScalarValue::Float64(f64::NEG_INFINITY))
+            .to_string();
+        assert_eq!("-Infinity", out);
+    }
 }
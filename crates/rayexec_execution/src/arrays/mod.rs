@@ -1,6 +1,8 @@
 pub mod array;
 pub mod batch;
+pub mod batch_builder;
 pub mod bitmap;
+pub mod buffer_pool;
 pub mod compute;
 pub mod datatype;
 pub mod executor;
@@ -51,7 +51,7 @@ impl ComparableRows {
     }
 
     pub fn row(&self, idx: usize) -> Option<ComparableRow<'_>> {
-        if idx > self.num_rows() {
+        if idx >= self.num_rows() {
             return None;
         }
 
@@ -275,11 +275,16 @@ impl ComparableRowEncoder {
                 ArrayData::Float32(d) => d.data_size_bytes(),
                 ArrayData::Float64(d) => d.data_size_bytes(),
                 ArrayData::Interval(d) => d.data_size_bytes(),
-                ArrayData::Binary(d) => match d {
-                    BinaryData::Binary(d) => d.data_size_bytes(),
-                    BinaryData::LargeBinary(d) => d.data_size_bytes(),
-                    BinaryData::German(d) => d.data_size_bytes(),
-                },
+                // Worst case every byte is escaped, plus a terminator per
+                // value. See `encode_varlen`.
+                ArrayData::Binary(d) => {
+                    let data_size = match d {
+                        BinaryData::Binary(d) => d.data_size_bytes(),
+                        BinaryData::LargeBinary(d) => d.data_size_bytes(),
+                        BinaryData::German(d) => d.data_size_bytes(),
+                    };
+                    data_size * 2 + arr.logical_len()
+                }
                 ArrayData::List(_) => not_implemented!("Row encode list"),
             };
 
@@ -298,6 +303,11 @@ impl ComparableRowEncoder {
 
     /// Encodes a variable length array into `buf` starting at `start`.
     ///
+    /// Zero bytes in the value are escaped as `0x00 0xFF`, and the value is
+    /// terminated with a single `0x00`. This ensures a value sorts before any
+    /// value it's a prefix of, regardless of what's encoded after it in the
+    /// row.
+    ///
     /// This should return the new offset to write to for the next value.
     fn encode_varlen<'a, S>(
         col: &ComparableColumn,
@@ -308,7 +318,7 @@ impl ComparableRowEncoder {
     ) -> Result<usize>
     where
         S: PhysicalStorage,
-        S::Type<'a>: ComparableEncode + AsBytes,
+        S::Type<'a>: AsBytes,
     {
         let null_byte = col.null_byte();
         let valid_byte = col.valid_byte();
@@ -316,12 +326,22 @@ impl ComparableRowEncoder {
         match UnaryExecutor::value_at::<S>(arr, row)? {
             Some(val) => {
                 buf[start] = valid_byte;
-                let end = start + 1 + val.as_bytes().len();
-                let write_buf = &mut buf[start + 1..end];
-                val.encode(write_buf);
-                col.invert_if_desc(write_buf);
 
-                Ok(start + 1 + write_buf.len())
+                let mut end = start + 1;
+                for &b in val.as_bytes() {
+                    buf[end] = b;
+                    end += 1;
+                    if b == 0 {
+                        buf[end] = 0xFF;
+                        end += 1;
+                    }
+                }
+                buf[end] = 0;
+                end += 1;
+
+                col.invert_if_desc(&mut buf[start + 1..end]);
+
+                Ok(end)
             }
             None => {
                 buf[start] = null_byte;
@@ -444,33 +464,17 @@ impl ComparableEncode for f64 {
 
 impl ComparableEncode for Interval {
     fn encode(&self, buf: &mut [u8]) {
-        // TODO: We'll probably need to ensure intervals are normalized.
-        self.months.encode(buf);
-        self.days.encode(buf);
-        self.nanos.encode(buf);
+        // Encode the total span to match the ordering of intervals. Spans
+        // always fit in 16 bytes, the same size as the interval itself.
+        self.span_nanos().encode(buf);
     }
 }
 
 // FALSE < TRUE
 impl ComparableEncode for bool {
     fn encode(&self, buf: &mut [u8]) {
-        if *self {
-            buf[0] = 0;
-        } else {
-            buf[0] = 255;
-        }
-    }
-}
-
-impl ComparableEncode for &str {
-    fn encode(&self, buf: &mut [u8]) {
-        buf.copy_from_slice(self.as_bytes())
-    }
-}
-
-impl ComparableEncode for &[u8] {
-    fn encode(&self, buf: &mut [u8]) {
-        buf.copy_from_slice(self)
+        // False sorts before true.
+        buf[0] = *self as u8;
     }
 }
 
@@ -533,6 +537,29 @@ mod tests {
         assert_eq!(expected, cmps);
     }
 
+    #[test]
+    fn bool_cmp_between_cols_asc() {
+        let col1 = Array::from_iter([false, true, true]);
+        let col2 = Array::from_iter([true, false, true]);
+
+        let encoder = ComparableRowEncoder {
+            columns: vec![ComparableColumn {
+                desc: false,
+                nulls_first: false,
+            }],
+        };
+
+        let rows1 = encoder.encode(&[&col1]).unwrap();
+        let rows2 = encoder.encode(&[&col2]).unwrap();
+
+        let cmps: Vec<_> = (rows1.iter().zip(rows2.iter()))
+            .map(|(left, right)| left.cmp(&right))
+            .collect();
+
+        let expected = vec![Ordering::Less, Ordering::Greater, Ordering::Equal];
+        assert_eq!(expected, cmps);
+    }
+
     #[test]
     fn simple_varlen_cmp_between_cols_asc() {
         let col1 = Array::from_iter(["a", "aa", "bb"]);
@@ -559,6 +586,55 @@ mod tests {
         assert_eq!(expected, cmps);
     }
 
+    #[test]
+    fn varlen_prefix_cmp_with_trailing_col() {
+        // Prefixes should compare less than the longer value no matter what
+        // comes after in the row.
+        let str1 = Array::from_iter(["", "AA", "a\0", "b"]);
+        let int1 = Array::from_iter([1, 1, 1, 1]);
+        let str2 = Array::from_iter(["a", "AAa", "a\0b", "b"]);
+        let int2 = Array::from_iter([0, 0, 0, 0]);
+
+        for desc in [false, true] {
+            let encoder = ComparableRowEncoder {
+                columns: vec![
+                    ComparableColumn {
+                        desc,
+                        nulls_first: true,
+                    },
+                    ComparableColumn {
+                        desc: false,
+                        nulls_first: true,
+                    },
+                ],
+            };
+
+            let rows1 = encoder.encode(&[&str1, &int1]).unwrap();
+            let rows2 = encoder.encode(&[&str2, &int2]).unwrap();
+
+            let cmps: Vec<_> = (rows1.iter().zip(rows2.iter()))
+                .map(|(left, right)| left.cmp(&right))
+                .collect();
+
+            let expected = if desc {
+                vec![
+                    Ordering::Greater,
+                    Ordering::Greater,
+                    Ordering::Greater,
+                    Ordering::Greater,
+                ]
+            } else {
+                vec![
+                    Ordering::Less,
+                    Ordering::Less,
+                    Ordering::Less,
+                    Ordering::Greater,
+                ]
+            };
+            assert_eq!(expected, cmps, "desc: {desc}");
+        }
+    }
+
     #[test]
     fn primitive_nulls_last_asc() {
         let col1 = Array::from_iter([Some(-1), None, Some(1), Some(2)]);
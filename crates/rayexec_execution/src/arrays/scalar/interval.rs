@@ -1,15 +1,20 @@
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Neg, Sub};
 
-use rayexec_error::Result;
+use rayexec_error::{RayexecError, Result};
 use rayexec_proto::ProtoConv;
 use serde::{Deserialize, Serialize};
 
 use crate::arrays::compute::cast::format::{Formatter, IntervalFormatter};
 
 /// A representation of an interval with nanosecond resolution.
-#[derive(
-    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
-)]
+///
+/// Comparisons and hashing use the total span of the interval assuming 30 day
+/// months and 24 hour days, so '1 month' and '30 days' are considered equal
+/// (matches Postgres).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct Interval {
     pub months: i32,
     pub days: i32,
@@ -25,6 +30,8 @@ impl Interval {
     pub const NANOSECONDS_IN_SECOND: i64 = 1_000_000_000;
     pub const NANOSECONDS_IN_MINUTE: i64 = 60 * Self::NANOSECONDS_IN_SECOND;
     pub const NANOSECONDS_IN_HOUR: i64 = 60 * Self::NANOSECONDS_IN_MINUTE;
+    pub const NANOSECONDS_IN_DAY: i64 =
+        Self::ASSUMED_HOURS_IN_DAY as i64 * Self::NANOSECONDS_IN_HOUR;
 
     pub const fn new(months: i32, days: i32, nanos: i64) -> Self {
         Interval {
@@ -34,6 +41,144 @@ impl Interval {
         }
     }
 
+    /// Get the total span of this interval in nanoseconds, assuming 30 day
+    /// months and 24 hour days.
+    pub const fn span_nanos(&self) -> i128 {
+        let days = self.months as i128 * Self::ASSUMED_DAYS_IN_MONTH as i128 + self.days as i128;
+        days * Self::NANOSECONDS_IN_DAY as i128 + self.nanos as i128
+    }
+
+    /// Multiply the interval by a float.
+    ///
+    /// Fractional months and days cascade down into days and nanoseconds
+    /// respectively (matches Postgres' `interval_mul`).
+    pub fn checked_mul_f64(&self, factor: f64) -> Result<Self> {
+        let months = self.months as f64 * factor;
+        let days = self.days as f64 * factor;
+        if !months.is_finite() || !days.is_finite() {
+            return Err(RayexecError::new(format!(
+                "Interval out of range: {self} * {factor}"
+            )));
+        }
+
+        let whole_months = months.trunc();
+        let month_remainder_days = (months - whole_months) * Self::ASSUMED_DAYS_IN_MONTH as f64;
+        let mut whole_days = days.trunc();
+
+        // Remaining fractional days, rounded to the microsecond to avoid
+        // precision errors producing odd looking intervals.
+        let mut remainder_nanos = ((days - whole_days + month_remainder_days.fract())
+            * Self::NANOSECONDS_IN_DAY as f64
+            / Self::NANOSECONDS_IN_MICROSECOND as f64)
+            .round()
+            * Self::NANOSECONDS_IN_MICROSECOND as f64;
+        if remainder_nanos.abs() >= Self::NANOSECONDS_IN_DAY as f64 {
+            let extra_days = (remainder_nanos / Self::NANOSECONDS_IN_DAY as f64).trunc();
+            whole_days += extra_days;
+            remainder_nanos -= extra_days * Self::NANOSECONDS_IN_DAY as f64;
+        }
+        whole_days += month_remainder_days.trunc();
+
+        let nanos = (self.nanos as f64 * factor + remainder_nanos).round();
+
+        let in_range = |v: f64, min: f64, max: f64| v >= min && v <= max;
+        if !in_range(whole_months, i32::MIN as f64, i32::MAX as f64)
+            || !in_range(whole_days, i32::MIN as f64, i32::MAX as f64)
+            || !in_range(nanos, i64::MIN as f64, i64::MAX as f64)
+        {
+            return Err(RayexecError::new(format!(
+                "Interval out of range: {self} * {factor}"
+            )));
+        }
+
+        Ok(Interval {
+            months: whole_months as i32,
+            days: whole_days as i32,
+            nanos: nanos as i64,
+        })
+    }
+
+    /// Divide the interval by a float.
+    pub fn checked_div_f64(&self, divisor: f64) -> Result<Self> {
+        if divisor == 0.0 {
+            return Err(RayexecError::new("Division by zero"));
+        }
+        self.checked_mul_f64(1.0 / divisor)
+    }
+
+    /// Adjust the interval so 30 day periods are represented as months.
+    pub fn justify_days(&self) -> Self {
+        let mut months = self.months;
+        let mut days = self.days;
+
+        months += days / Self::ASSUMED_DAYS_IN_MONTH;
+        days %= Self::ASSUMED_DAYS_IN_MONTH;
+
+        if months > 0 && days < 0 {
+            days += Self::ASSUMED_DAYS_IN_MONTH;
+            months -= 1;
+        } else if months < 0 && days > 0 {
+            days -= Self::ASSUMED_DAYS_IN_MONTH;
+            months += 1;
+        }
+
+        Interval::new(months, days, self.nanos)
+    }
+
+    /// Adjust the interval so 24 hour periods are represented as days.
+    pub fn justify_hours(&self) -> Self {
+        let mut days = self.days;
+        let mut nanos = self.nanos;
+
+        days += (nanos / Self::NANOSECONDS_IN_DAY) as i32;
+        nanos %= Self::NANOSECONDS_IN_DAY;
+
+        if days > 0 && nanos < 0 {
+            nanos += Self::NANOSECONDS_IN_DAY;
+            days -= 1;
+        } else if days < 0 && nanos > 0 {
+            nanos -= Self::NANOSECONDS_IN_DAY;
+            days += 1;
+        }
+
+        Interval::new(self.months, days, nanos)
+    }
+
+    /// Adjust the interval using both `justify_days` and `justify_hours`, with
+    /// additional sign adjustments so all fields share the same sign.
+    pub fn justify_interval(&self) -> Self {
+        let mut months = self.months;
+        let mut days = self.days;
+        let mut nanos = self.nanos;
+
+        months += days / Self::ASSUMED_DAYS_IN_MONTH;
+        days %= Self::ASSUMED_DAYS_IN_MONTH;
+
+        days += (nanos / Self::NANOSECONDS_IN_DAY) as i32;
+        nanos %= Self::NANOSECONDS_IN_DAY;
+
+        months += days / Self::ASSUMED_DAYS_IN_MONTH;
+        days %= Self::ASSUMED_DAYS_IN_MONTH;
+
+        if months > 0 && (days < 0 || (days == 0 && nanos < 0)) {
+            days += Self::ASSUMED_DAYS_IN_MONTH;
+            months -= 1;
+        } else if months < 0 && (days > 0 || (days == 0 && nanos > 0)) {
+            days -= Self::ASSUMED_DAYS_IN_MONTH;
+            months += 1;
+        }
+
+        if days > 0 && nanos < 0 {
+            nanos += Self::NANOSECONDS_IN_DAY;
+            days -= 1;
+        } else if days < 0 && nanos > 0 {
+            nanos -= Self::NANOSECONDS_IN_DAY;
+            days += 1;
+        }
+
+        Interval::new(months, days, nanos)
+    }
+
     pub fn add_microseconds(&mut self, microseconds: i64) {
         self.nanos += microseconds * Self::NANOSECONDS_IN_MICROSECOND
     }
@@ -83,6 +228,69 @@ impl Interval {
     }
 }
 
+impl PartialEq for Interval {
+    fn eq(&self, other: &Self) -> bool {
+        self.span_nanos() == other.span_nanos()
+    }
+}
+
+impl Eq for Interval {}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.span_nanos().cmp(&other.span_nanos())
+    }
+}
+
+impl Hash for Interval {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Must be consistent with `Eq`.
+        self.span_nanos().hash(state)
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Interval {
+            months: self.months + rhs.months,
+            days: self.days + rhs.days,
+            nanos: self.nanos + rhs.nanos,
+        }
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Interval {
+            months: self.months - rhs.months,
+            days: self.days - rhs.days,
+            nanos: self.nanos - rhs.nanos,
+        }
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+
+    fn neg(self) -> Self::Output {
+        Interval {
+            months: -self.months,
+            days: -self.days,
+            nanos: -self.nanos,
+        }
+    }
+}
+
 impl fmt::Display for Interval {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         IntervalFormatter.write(self, f)
@@ -108,3 +316,59 @@ impl ProtoConv for Interval {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hours(h: i64) -> Interval {
+        Interval::new(0, 0, h * Interval::NANOSECONDS_IN_HOUR)
+    }
+
+    #[test]
+    fn compare_by_span() {
+        assert_eq!(Interval::new(1, 0, 0), Interval::new(0, 30, 0));
+        assert_eq!(Interval::new(0, 1, 0), hours(24));
+        assert!(Interval::new(0, 1, 0) < hours(25));
+        assert!(Interval::new(1, 0, 0) > Interval::new(0, 29, 0));
+        assert!(-Interval::new(0, 1, 0) < hours(1));
+    }
+
+    #[test]
+    fn mul_f64_cascades() {
+        let out = Interval::new(0, 1, 0).checked_mul_f64(2.5).unwrap();
+        assert_eq!(
+            (0, 2, 12 * Interval::NANOSECONDS_IN_HOUR),
+            (out.months, out.days, out.nanos)
+        );
+
+        let out = Interval::new(1, 0, 0).checked_mul_f64(0.5).unwrap();
+        assert_eq!((0, 15, 0), (out.months, out.days, out.nanos));
+
+        let out = hours(1).checked_div_f64(3.0).unwrap();
+        assert_eq!(20 * Interval::NANOSECONDS_IN_MINUTE, out.nanos);
+
+        Interval::new(0, 1, 0).checked_div_f64(0.0).unwrap_err();
+        Interval::new(i32::MAX, 0, 0)
+            .checked_mul_f64(2.0)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn justify() {
+        let out = Interval::new(0, 35, 0).justify_days();
+        assert_eq!((1, 5, 0), (out.months, out.days, out.nanos));
+
+        let out = hours(27).justify_hours();
+        assert_eq!(
+            (0, 1, 3 * Interval::NANOSECONDS_IN_HOUR),
+            (out.months, out.days, out.nanos)
+        );
+
+        let out = Interval::new(1, 0, -Interval::NANOSECONDS_IN_HOUR).justify_interval();
+        assert_eq!(
+            (0, 29, 23 * Interval::NANOSECONDS_IN_HOUR),
+            (out.months, out.days, out.nanos)
+        );
+    }
+}
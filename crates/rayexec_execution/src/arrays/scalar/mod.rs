@@ -87,6 +87,9 @@ pub enum ScalarValue<'a> {
     Binary(Cow<'a, [u8]>),
     Struct(Vec<ScalarValue<'a>>),
     List(Vec<ScalarValue<'a>>),
+    Json(Cow<'a, str>),
+    /// Geometry encoded as well-known binary.
+    Geometry(Cow<'a, [u8]>),
 }
 
 // TODO: TBD if we want this. We may need to implement PartialEq to exact
@@ -121,6 +124,8 @@ impl Hash for ScalarValue<'_> {
             Self::Binary(v) => v.hash(state),
             Self::Struct(v) => v.hash(state),
             Self::List(v) => v.hash(state),
+            Self::Json(v) => v.hash(state),
+            Self::Geometry(v) => v.hash(state),
         }
     }
 }
@@ -159,13 +164,11 @@ impl ScalarValue<'_> {
             ScalarValue::Binary(_) => DataType::Binary,
             ScalarValue::Struct(_fields) => unimplemented!(), // TODO: Fill out the meta
             ScalarValue::List(list) => match list.first() {
-                Some(first) => DataType::List(ListTypeMeta {
-                    datatype: Box::new(first.datatype()),
-                }),
-                None => DataType::List(ListTypeMeta {
-                    datatype: Box::new(DataType::Null),
-                }),
+                Some(first) => DataType::List(ListTypeMeta::new(first.datatype())),
+                None => DataType::List(ListTypeMeta::new(DataType::Null)),
             },
+            ScalarValue::Json(_) => DataType::Json,
+            ScalarValue::Geometry(_) => DataType::Geometry,
         }
     }
 
@@ -200,6 +203,8 @@ impl ScalarValue<'_> {
             Self::List(v) => {
                 OwnedScalarValue::List(v.into_iter().map(|v| v.into_owned()).collect())
             }
+            Self::Json(v) => OwnedScalarValue::Json(v.into_owned().into()),
+            Self::Geometry(v) => OwnedScalarValue::Geometry(v.into_owned().into()),
         }
     }
 
@@ -227,8 +232,10 @@ impl ScalarValue<'_> {
             Self::Date64(v) => PrimitiveStorage::from(vec![*v]).into(),
             Self::Timestamp(v) => PrimitiveStorage::from(vec![v.value]).into(),
             Self::Interval(v) => PrimitiveStorage::from(vec![*v]).into(),
-            Self::Utf8(v) => GermanVarlenStorage::with_value(v.as_ref()).into(),
-            Self::Binary(v) => GermanVarlenStorage::with_value(v.as_ref()).into(),
+            Self::Utf8(v) | Self::Json(v) => GermanVarlenStorage::with_value(v.as_ref()).into(),
+            Self::Binary(v) | Self::Geometry(v) => {
+                GermanVarlenStorage::with_value(v.as_ref()).into()
+            }
             Self::List(v) => {
                 if v.is_empty() {
                     let metadata = ListItemMetadata { offset: 0, len: 0 };
@@ -390,8 +397,15 @@ impl fmt::Display for ScalarValue<'_> {
                 TimeUnit::Nanosecond => TimestampNanosecondsFormatter::default().write(&v.value, f),
             },
             Self::Interval(v) => IntervalFormatter.write(v, f),
-            Self::Utf8(v) => write!(f, "{}", v),
+            Self::Utf8(v) | Self::Json(v) => write!(f, "{}", v),
             Self::Binary(v) => write!(f, "{:X?}", v),
+            Self::Geometry(v) => {
+                // Hex encoded WKB, matching how Postgres displays geometries.
+                for b in v.iter() {
+                    write!(f, "{b:02X}")?;
+                }
+                Ok(())
+            }
             Self::Struct(fields) => write!(
                 f,
                 "{{{}}}",
@@ -548,6 +562,8 @@ impl ProtoConv for OwnedScalarValue {
             Self::Date64(v) => Value::ScalarDate64(*v),
             Self::Interval(v) => Value::ScalarInterval(v.to_proto()?),
             Self::Utf8(v) => Value::ScalarUtf8(v.clone().into()),
+            Self::Json(v) => Value::ScalarJson(v.clone().into()),
+            Self::Geometry(v) => Value::ScalarGeometry(v.clone().into()),
             Self::Binary(v) => Value::ScalarBinary(v.clone().into()),
             Self::Struct(v) => {
                 let values = v.iter().map(|v| v.to_proto()).collect::<Result<Vec<_>>>()?;
@@ -593,6 +609,8 @@ impl ProtoConv for OwnedScalarValue {
             Value::ScalarDate64(v) => Self::Date64(v),
             Value::ScalarInterval(v) => Self::Interval(Interval::from_proto(v)?),
             Value::ScalarUtf8(v) => Self::Utf8(v.into()),
+            Value::ScalarJson(v) => Self::Json(v.into()),
+            Value::ScalarGeometry(v) => Self::Geometry(v.into()),
             Value::ScalarBinary(v) => Self::Binary(v.into()),
             Value::ScalarStruct(v) => {
                 let values = v
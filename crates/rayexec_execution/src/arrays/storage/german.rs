@@ -1,6 +1,7 @@
 use std::fmt;
+use std::sync::Arc;
 
-use rayexec_error::Result;
+use rayexec_error::{RayexecError, Result};
 
 use super::{AddressableStorage, PrimitiveStorage};
 use crate::arrays::executor::physical_type::AsBytes;
@@ -109,12 +110,26 @@ impl From<GermanLargeMetadata> for UnionedGermanMetadata {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct GermanVarlenStorage {
     pub(crate) metadata: PrimitiveStorage<UnionedGermanMetadata>,
-    pub(crate) data: PrimitiveStorage<u8>,
+    /// Out-of-line data for values that don't fit inline.
+    ///
+    /// Shared so that selecting from the storage only requires copying the
+    /// metadata.
+    pub(crate) data: Arc<PrimitiveStorage<u8>>,
 }
 
+/// Compares the logical values since the data buffer may contain data not
+/// referenced by any metadata (e.g. after a select).
+impl PartialEq for GermanVarlenStorage {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for GermanVarlenStorage {}
+
 impl GermanVarlenStorage {
     pub fn with_value<V>(val: &V) -> Self
     where
@@ -130,10 +145,37 @@ impl GermanVarlenStorage {
     pub fn with_metadata_capacity(meta_cap: usize) -> Self {
         GermanVarlenStorage {
             metadata: Vec::with_capacity(meta_cap).into(),
-            data: Vec::new().into(),
+            data: Arc::new(Vec::new().into()),
         }
     }
 
+    /// Create a new storage containing only the values at the given indices.
+    ///
+    /// Only the metadata is copied, the out-of-line data buffer is shared with
+    /// this storage. This makes filters and takes on wide string columns cheap
+    /// since we never need to rewrite the string data.
+    pub fn select(&self, indices: impl IntoIterator<Item = usize>) -> Result<Self> {
+        let meta = self.metadata.as_ref();
+        let metadata = indices
+            .into_iter()
+            .map(|idx| {
+                meta.get(idx).copied().ok_or_else(|| {
+                    RayexecError::new(format!("Index {idx} out of bounds for german storage"))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(GermanVarlenStorage {
+            metadata: metadata.into(),
+            data: self.data.clone(),
+        })
+    }
+
+    /// Get the buffer holding out-of-line data.
+    pub fn data(&self) -> &Arc<PrimitiveStorage<u8>> {
+        &self.data
+    }
+
     pub fn len(&self) -> usize {
         self.metadata.as_ref().len()
     }
@@ -143,8 +185,15 @@ impl GermanVarlenStorage {
     }
 
     pub fn try_push(&mut self, value: &[u8]) -> Result<()> {
+        if let PrimitiveStorage::Raw { .. } = self.data.as_ref() {
+            // Externally managed data (e.g. a parquet page) can't be appended
+            // to, copy it.
+            self.data = Arc::new(self.data.as_slice().to_vec().into());
+        }
+
         let metadata = self.metadata.try_as_vec_mut()?;
-        let data = self.data.try_as_vec_mut()?;
+        // Copies the data if it's currently shared with another storage.
+        let data = Arc::make_mut(&mut self.data).try_as_vec_mut()?;
 
         if value.len() as i32 <= INLINE_THRESHOLD {
             // Store completely inline.
@@ -191,7 +240,7 @@ impl GermanVarlenStorage {
                 Some(&inline[..(*len as usize)])
             }
             GermanMetadata::Large(GermanLargeMetadata { len, offset, .. }) => {
-                Some(&self.data.as_ref()[(*offset as usize)..((offset + len) as usize)])
+                Some(&self.data.as_slice()[(*offset as usize)..((offset + len) as usize)])
             }
         }
     }
@@ -215,7 +264,7 @@ impl GermanVarlenStorage {
     pub fn as_german_storage_slice(&self) -> GermanVarlenStorageSlice {
         GermanVarlenStorageSlice {
             metadata: self.metadata.as_ref(),
-            data: self.data.as_ref(),
+            data: self.data.as_slice(),
         }
     }
 }
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use rayexec_error::{RayexecError, Result};
 
 use super::AddressableStorage;
+use crate::arrays::buffer_pool;
 
 /// Marker trait for a deallocation mechanism for the `PrimitiveStorage::Raw`
 /// variant.
@@ -39,7 +40,10 @@ pub enum PrimitiveStorage<T> {
 
     /// Pointer to a raw slice of data that's potentially been externally
     /// allocated.
-    // TODO: Don't use, just thinking about ffi.
+    ///
+    /// Used to reference buffers owned by something else without copying
+    /// them (e.g. decompressed parquet pages). `deallocate` keeps the data
+    /// alive.
     Raw {
         ptr: *const T,
         len: usize,
@@ -81,7 +85,7 @@ impl<T> PrimitiveStorage<T> {
 
         let cap = bytes.len() / std::mem::size_of::<T>();
 
-        let vec: Vec<T> = vec![T::default(); cap];
+        let vec: Vec<T> = buffer_pool::alloc_vec(cap);
         let mut manual = ManuallyDrop::new(vec);
         let ptr = manual.as_mut_ptr();
         let len = manual.len();
@@ -174,6 +178,15 @@ impl<T> From<Vec<T>> for PrimitiveStorage<T> {
     }
 }
 
+/// Hands vec allocations back to the buffer pool.
+impl<T> Drop for PrimitiveStorage<T> {
+    fn drop(&mut self) {
+        if let Self::Vec(v) = self {
+            buffer_pool::recycle_vec(std::mem::take(v));
+        }
+    }
+}
+
 impl<T> AsRef<[T]> for PrimitiveStorage<T> {
     #[inline]
     fn as_ref(&self) -> &[T] {
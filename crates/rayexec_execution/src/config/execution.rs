@@ -1,16 +1,48 @@
 use rayexec_error::{RayexecError, Result};
 
+use crate::execution::operators::hash_join::DEFAULT_NESTED_LOOP_THRESHOLD;
+use crate::functions::scalar::builtin::random::RandomState;
+
 /// Configuration for intermediate pipeline planning.
 #[derive(Debug, Clone)]
 pub struct IntermediatePlanConfig {
     /// If we should allow nested loop join.
     pub allow_nested_loop_join: bool,
+    /// Target number of partitions pipelines will be executed with.
+    ///
+    /// Used for annotating EXPLAIN output with expected parallelism, and for
+    /// determining if an operator's input will be repartitioned.
+    pub target_partitions: Option<usize>,
+    /// Random state for the session planning the pipelines.
+    ///
+    /// Functions producing random values use thread local randomness if not
+    /// set.
+    pub random_state: Option<RandomState>,
+    /// Time the query started, in microseconds since the epoch.
+    ///
+    /// Functions returning the current time use the time they were planned
+    /// if not set.
+    pub query_start_micros: Option<i64>,
+    /// If hash joins should build runtime filters to push into probe side
+    /// scans.
+    ///
+    /// Filters are shared in memory between operators, so this should only be
+    /// enabled when planning for local execution.
+    pub enable_runtime_filters: bool,
+    /// Max number of rows on the build side of a hash join for the join to
+    /// switch to probing with a nested loop at runtime.
+    pub hash_join_nested_loop_threshold: usize,
 }
 
 impl Default for IntermediatePlanConfig {
     fn default() -> Self {
         IntermediatePlanConfig {
             allow_nested_loop_join: true,
+            target_partitions: None,
+            random_state: None,
+            query_start_micros: None,
+            enable_runtime_filters: false,
+            hash_join_nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
         }
     }
 }
@@ -30,4 +62,28 @@ pub struct ExecutablePlanConfig {
     ///
     /// Partitionining determines parallelism for a single pipeline.
     pub partitions: usize,
+    /// Target number of rows in batches produced by scans and operators.
+    pub batch_size: usize,
+    /// Optional target size in bytes for batches produced by scans.
+    ///
+    /// When set, scans derive their batch size from the estimated width of the
+    /// rows they produce instead of using `batch_size`. Wide rows get fewer
+    /// rows per batch, narrow rows get more.
+    pub batch_size_bytes: Option<usize>,
+}
+
+/// Largest batch size that can be configured.
+///
+/// Operators may allocate buffers up front based on the batch size, so we
+/// don't want to allow arbitrarily large sizes.
+pub const MAX_BATCH_SIZE: usize = 1024 * 1024;
+
+/// Check that a batch size is usable for execution.
+pub fn validate_batch_size(batch_size: usize) -> Result<()> {
+    if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
+        return Err(RayexecError::new(format!(
+            "Batch size must be between 1 and {MAX_BATCH_SIZE}, got {batch_size}"
+        )));
+    }
+    Ok(())
 }
@@ -1,3 +1,4 @@
 //! Runtime configuration.
 pub mod execution;
 pub mod session;
+pub mod statements;
@@ -1,11 +1,23 @@
 use std::collections::HashMap;
 use std::sync::LazyLock;
+use std::time::Duration;
 
 use rayexec_error::{RayexecError, Result};
+use rayexec_io::read_cache;
 
 use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::config::execution::validate_batch_size;
+use crate::engine::{plan_cache, result_cache};
+use crate::execution::operators::hash_join::DEFAULT_NESTED_LOOP_THRESHOLD;
+use crate::execution::operators::util::resizer::DEFAULT_TARGET_BATCH_SIZE;
+use crate::optimizer::OPTIMIZER_RULE_NAMES;
+use crate::runtime::stall::{ScanStall, StallAction};
 use crate::runtime::{PipelineExecutor, Runtime};
 
+/// Default max estimated rows for a join input to be shipped to another
+/// catalog.
+pub const DEFAULT_JOIN_SHIP_THRESHOLD: u64 = 1000;
+
 /// Configuration for the session.
 #[derive(Debug)]
 pub struct SessionConfig {
@@ -14,12 +26,28 @@ pub struct SessionConfig {
     pub allow_nested_loop_join: bool,
     pub partitions: u64,
     pub batch_size: u64,
+    pub batch_size_bytes: u64,
     pub verify_optimized_plan: bool,
     pub enable_function_chaining: bool,
+    pub preview_rows: u64,
+    pub memory_limit: u64,
+    pub timezone: String,
+    pub enable_result_cache: bool,
+    pub result_cache_size: u64,
+    pub remote_read_cache_size: u64,
+    pub role: String,
+    pub join_ship_threshold: u64,
+    pub disable_optimizer_rules: String,
+    pub enable_plan_cache: bool,
+    pub plan_cache_size: u64,
+    pub enable_runtime_filters: bool,
+    pub hash_join_nested_loop_threshold: u64,
+    pub scan_stall_timeout: u64,
+    pub scan_stall_action: String,
 }
 
 impl SessionConfig {
-    pub fn new<P, R>(executor: &P, _runtime: &R) -> Self
+    pub fn new<P, R>(executor: &P, runtime: &R) -> Self
     where
         P: PipelineExecutor,
         R: Runtime,
@@ -29,12 +57,53 @@ impl SessionConfig {
             application_name: String::new(),
             allow_nested_loop_join: true,
             partitions: executor.default_partitions() as u64,
-            batch_size: 4096,
+            batch_size: DEFAULT_TARGET_BATCH_SIZE as u64,
+            batch_size_bytes: 0,
             verify_optimized_plan: false,
             enable_function_chaining: true,
+            preview_rows: 0,
+            memory_limit: 0,
+            timezone: "UTC".to_string(),
+            enable_result_cache: false,
+            result_cache_size: result_cache::DEFAULT_RESULT_CACHE_BYTES as u64,
+            remote_read_cache_size: read_cache::DEFAULT_READ_CACHE_BYTES as u64,
+            role: String::new(),
+            join_ship_threshold: DEFAULT_JOIN_SHIP_THRESHOLD,
+            disable_optimizer_rules: String::new(),
+            enable_plan_cache: false,
+            plan_cache_size: plan_cache::DEFAULT_PLAN_CACHE_ENTRIES as u64,
+            enable_runtime_filters: true,
+            hash_join_nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD as u64,
+            scan_stall_timeout: runtime
+                .scan_stall()
+                .timeout
+                .map(|timeout| timeout.as_secs())
+                .unwrap_or(0),
+            scan_stall_action: "fail".to_string(),
+        }
+    }
+
+    /// Stall handling for remote scans planned in this session.
+    pub fn scan_stall(&self) -> ScanStall {
+        ScanStall {
+            timeout: match self.scan_stall_timeout {
+                0 => None,
+                secs => Some(Duration::from_secs(secs)),
+            },
+            action: match self.scan_stall_action.as_str() {
+                "retry" => StallAction::Retry,
+                _ => StallAction::Fail,
+            },
         }
     }
 
+    /// Names of optimizer rules that have been disabled.
+    pub fn disabled_optimizer_rules(&self) -> impl Iterator<Item = &str> {
+        self.disable_optimizer_rules
+            .split(',')
+            .filter(|rule| !rule.is_empty())
+    }
+
     pub fn set_from_scalar(&mut self, name: &str, value: ScalarValue) -> Result<()> {
         let func = GET_SET_FUNCTIONS
             .get(name)
@@ -75,11 +144,34 @@ impl SessionConfig {
     {
         *self = Self::new(executor, runtime);
     }
+
+    /// Get the current values for all settings, ordered by name.
+    pub fn all_settings(&self) -> Vec<SettingValue> {
+        let mut settings: Vec<_> = GET_SET_FUNCTIONS
+            .iter()
+            .map(|(name, func)| SettingValue {
+                name,
+                value: (func.get)(self),
+                description: func.description,
+            })
+            .collect();
+        settings.sort_unstable_by_key(|setting| setting.name);
+        settings
+    }
+}
+
+/// Current value of a setting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingValue {
+    pub name: &'static str,
+    pub value: OwnedScalarValue,
+    pub description: &'static str,
 }
 
 struct SettingFunctions {
     set: fn(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()>,
     get: fn(conf: &SessionConfig) -> OwnedScalarValue,
+    description: &'static str,
 }
 
 impl SettingFunctions {
@@ -87,6 +179,7 @@ impl SettingFunctions {
         SettingFunctions {
             set: S::set_from_scalar as _,
             get: S::get_as_scalar as _,
+            description: S::DESCRIPTION,
         }
     }
 }
@@ -105,7 +198,22 @@ static GET_SET_FUNCTIONS: LazyLock<HashMap<&'static str, SettingFunctions>> = La
     insert_setting::<AllowNestedLoopJoin>(&mut map);
     insert_setting::<Partitions>(&mut map);
     insert_setting::<BatchSize>(&mut map);
+    insert_setting::<BatchSizeBytes>(&mut map);
     insert_setting::<EnableFunctionChaining>(&mut map);
+    insert_setting::<PreviewRows>(&mut map);
+    insert_setting::<MemoryLimit>(&mut map);
+    insert_setting::<Timezone>(&mut map);
+    insert_setting::<EnableResultCache>(&mut map);
+    insert_setting::<ResultCacheSize>(&mut map);
+    insert_setting::<RemoteReadCacheSize>(&mut map);
+    insert_setting::<JoinShipThreshold>(&mut map);
+    insert_setting::<DisableOptimizerRules>(&mut map);
+    insert_setting::<EnablePlanCache>(&mut map);
+    insert_setting::<PlanCacheSize>(&mut map);
+    insert_setting::<EnableRuntimeFilters>(&mut map);
+    insert_setting::<HashJoinNestedLoopThreshold>(&mut map);
+    insert_setting::<ScanStallTimeout>(&mut map);
+    insert_setting::<ScanStallAction>(&mut map);
 
     map
 });
@@ -194,6 +302,10 @@ impl SessionSetting for BatchSize {
 
     fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
         let val = scalar.try_as_i64()?;
+        let val = usize::try_from(val).map_err(|_| {
+            RayexecError::new(format!("batch_size must not be negative, got {val}"))
+        })?;
+        validate_batch_size(val)?;
         conf.batch_size = val as u64;
         Ok(())
     }
@@ -203,6 +315,35 @@ impl SessionSetting for BatchSize {
     }
 }
 
+pub struct BatchSizeBytes;
+
+impl SessionSetting for BatchSizeBytes {
+    const NAME: &'static str = "batch_size_bytes";
+    const DESCRIPTION: &'static str =
+        "Target bytes per batch for scans, sizing batches by row width. Accepts sizes like '1MiB'. Zero means use batch_size.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = match &scalar {
+            ScalarValue::Utf8(s) => parse_byte_size(s)?,
+            other => {
+                let val = other.try_as_i64()?;
+                if val < 0 {
+                    return Err(RayexecError::new(format!(
+                        "batch_size_bytes must not be negative, got {val}"
+                    )));
+                }
+                val as u64
+            }
+        };
+        conf.batch_size_bytes = val;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.batch_size_bytes.into()
+    }
+}
+
 pub struct VerifyOptimizedPlan;
 
 impl SessionSetting for VerifyOptimizedPlan {
@@ -238,6 +379,380 @@ impl SessionSetting for EnableFunctionChaining {
     }
 }
 
+pub struct PreviewRows;
+
+impl SessionSetting for PreviewRows {
+    const NAME: &'static str = "preview_rows";
+    const DESCRIPTION: &'static str =
+        "Limit each source to this many rows, returning partial results. Zero disables previews.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_as_i64()?;
+        if val < 0 {
+            return Err(RayexecError::new(format!(
+                "preview_rows must not be negative, got {val}"
+            )));
+        }
+        conf.preview_rows = val as u64;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.preview_rows.into()
+    }
+}
+
+pub struct MemoryLimit;
+
+impl SessionSetting for MemoryLimit {
+    const NAME: &'static str = "memory_limit";
+    const DESCRIPTION: &'static str =
+        "Max memory in bytes each query may reserve during execution. Accepts sizes like '4GB'. Zero means no limit.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = match &scalar {
+            ScalarValue::Utf8(s) => parse_byte_size(s)?,
+            other => {
+                let val = other.try_as_i64()?;
+                if val < 0 {
+                    return Err(RayexecError::new(format!(
+                        "memory_limit must not be negative, got {val}"
+                    )));
+                }
+                val as u64
+            }
+        };
+        conf.memory_limit = val;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.memory_limit.into()
+    }
+}
+
+pub struct Timezone;
+
+impl SessionSetting for Timezone {
+    const NAME: &'static str = "timezone";
+    const DESCRIPTION: &'static str =
+        "Timezone to use for dates and times. Only 'UTC' is currently supported.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_into_string()?;
+        // Timestamps are always computed and displayed in UTC. Reject anything
+        // else instead of silently ignoring it.
+        if !val.eq_ignore_ascii_case("utc") {
+            return Err(RayexecError::new(format!(
+                "Unsupported timezone: '{val}', only 'UTC' is supported"
+            )));
+        }
+        conf.timezone = "UTC".to_string();
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.timezone.clone().into()
+    }
+}
+
+pub struct EnableResultCache;
+
+impl SessionSetting for EnableResultCache {
+    const NAME: &'static str = "enable_result_cache";
+    const DESCRIPTION: &'static str =
+        "If results for repeated identical queries should be served from the result cache";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_as_bool()?;
+        conf.enable_result_cache = val;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.enable_result_cache.into()
+    }
+}
+
+pub struct ResultCacheSize;
+
+impl SessionSetting for ResultCacheSize {
+    const NAME: &'static str = "result_cache_size";
+    const DESCRIPTION: &'static str =
+        "Max bytes of query results to keep in the result cache. Applies to the whole process.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = match &scalar {
+            ScalarValue::Utf8(s) => parse_byte_size(s)?,
+            other => {
+                let val = other.try_as_i64()?;
+                if val < 0 {
+                    return Err(RayexecError::new(format!(
+                        "result_cache_size must not be negative, got {val}"
+                    )));
+                }
+                val as u64
+            }
+        };
+        // Cache is shared across all sessions, same as the buffer pool.
+        result_cache::set_max_bytes(val as usize);
+        conf.result_cache_size = val;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.result_cache_size.into()
+    }
+}
+
+pub struct RemoteReadCacheSize;
+
+impl SessionSetting for RemoteReadCacheSize {
+    const NAME: &'static str = "remote_read_cache_size";
+    const DESCRIPTION: &'static str =
+        "Max bytes of remote file reads (s3, http) to keep cached. Zero disables caching. Applies to the whole process.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = match &scalar {
+            ScalarValue::Utf8(s) => parse_byte_size(s)?,
+            other => {
+                let val = other.try_as_i64()?;
+                if val < 0 {
+                    return Err(RayexecError::new(format!(
+                        "remote_read_cache_size must not be negative, got {val}"
+                    )));
+                }
+                val as u64
+            }
+        };
+        read_cache::set_max_bytes(val as usize);
+        conf.remote_read_cache_size = val;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.remote_read_cache_size.into()
+    }
+}
+
+pub struct EnablePlanCache;
+
+impl SessionSetting for EnablePlanCache {
+    const NAME: &'static str = "enable_plan_cache";
+    const DESCRIPTION: &'static str =
+        "If plans for repeated identical queries should be reused instead of binding the query again";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_as_bool()?;
+        conf.enable_plan_cache = val;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.enable_plan_cache.into()
+    }
+}
+
+pub struct PlanCacheSize;
+
+impl SessionSetting for PlanCacheSize {
+    const NAME: &'static str = "plan_cache_size";
+    const DESCRIPTION: &'static str =
+        "Max number of plans to keep in the session's plan cache. Zero disables caching.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_as_i64()?;
+        if val < 0 {
+            return Err(RayexecError::new(format!(
+                "plan_cache_size must not be negative, got {val}"
+            )));
+        }
+        conf.plan_cache_size = val as u64;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.plan_cache_size.into()
+    }
+}
+
+pub struct EnableRuntimeFilters;
+
+impl SessionSetting for EnableRuntimeFilters {
+    const NAME: &'static str = "enable_runtime_filters";
+    const DESCRIPTION: &'static str =
+        "If hash joins should filter probe side scans using the keys read from the build side";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_as_bool()?;
+        conf.enable_runtime_filters = val;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.enable_runtime_filters.into()
+    }
+}
+
+pub struct HashJoinNestedLoopThreshold;
+
+impl SessionSetting for HashJoinNestedLoopThreshold {
+    const NAME: &'static str = "hash_join_nested_loop_threshold";
+    const DESCRIPTION: &'static str =
+        "Max rows read from the build side of a hash join for the join to probe using a nested loop instead of the hash table. Zero always uses the hash table.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_as_i64()?;
+        if val < 0 {
+            return Err(RayexecError::new(format!(
+                "hash_join_nested_loop_threshold must not be negative, got {val}"
+            )));
+        }
+        conf.hash_join_nested_loop_threshold = val as u64;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.hash_join_nested_loop_threshold.into()
+    }
+}
+
+pub struct JoinShipThreshold;
+
+impl SessionSetting for JoinShipThreshold {
+    const NAME: &'static str = "join_ship_threshold";
+    const DESCRIPTION: &'static str =
+        "Max estimated rows for a join input to be sent to a remote catalog so the join runs there. Zero disables shipping.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_as_i64()?;
+        if val < 0 {
+            return Err(RayexecError::new(format!(
+                "join_ship_threshold must not be negative, got {val}"
+            )));
+        }
+        conf.join_ship_threshold = val as u64;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.join_ship_threshold.into()
+    }
+}
+
+pub struct ScanStallTimeout;
+
+impl SessionSetting for ScanStallTimeout {
+    const NAME: &'static str = "scan_stall_timeout";
+    const DESCRIPTION: &'static str =
+        "Max seconds a remote scan waits for a response or more data before the read stalls. Zero disables the timeout.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_as_i64()?;
+        if val < 0 {
+            return Err(RayexecError::new(format!(
+                "scan_stall_timeout must not be negative, got {val}"
+            )));
+        }
+        conf.scan_stall_timeout = val as u64;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.scan_stall_timeout.into()
+    }
+}
+
+pub struct ScanStallAction;
+
+impl SessionSetting for ScanStallAction {
+    const NAME: &'static str = "scan_stall_action";
+    const DESCRIPTION: &'static str =
+        "What to do when a remote read stalls, either 'fail' the query or 'retry' the read.";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_into_string()?.to_ascii_lowercase();
+        if val != "fail" && val != "retry" {
+            return Err(RayexecError::new(format!(
+                "Unknown scan_stall_action: '{val}'. Expected 'fail' or 'retry'"
+            )));
+        }
+        conf.scan_stall_action = val;
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.scan_stall_action.clone().into()
+    }
+}
+
+pub struct DisableOptimizerRules;
+
+impl SessionSetting for DisableOptimizerRules {
+    const NAME: &'static str = "disable_optimizer_rules";
+    const DESCRIPTION: &'static str =
+        "Comma separated list of optimizer rules to skip, e.g. 'join_reorder,filter_pushdown'";
+
+    fn set_from_scalar(scalar: ScalarValue, conf: &mut SessionConfig) -> Result<()> {
+        let val = scalar.try_into_string()?;
+
+        let mut rules = Vec::new();
+        for rule in val.split(',') {
+            let rule = rule.trim().to_ascii_lowercase();
+            if rule.is_empty() {
+                continue;
+            }
+            if !OPTIMIZER_RULE_NAMES.contains(&rule.as_str()) {
+                return Err(RayexecError::new(format!(
+                    "Unknown optimizer rule: '{rule}'. Expected one of: {}",
+                    OPTIMIZER_RULE_NAMES.join(", ")
+                )));
+            }
+            if !rules.contains(&rule) {
+                rules.push(rule);
+            }
+        }
+
+        conf.disable_optimizer_rules = rules.join(",");
+        Ok(())
+    }
+
+    fn get_as_scalar(conf: &SessionConfig) -> OwnedScalarValue {
+        conf.disable_optimizer_rules.clone().into()
+    }
+}
+
+/// Parse a human readable byte size (e.g. '512MB', '4 GiB', '1024').
+///
+/// Decimal units (KB, MB, ...) are powers of 1000, binary units (KiB, MiB,
+/// ...) are powers of 1024. A number without a unit is interpreted as bytes.
+fn parse_byte_size(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+
+    let num: f64 = num
+        .parse()
+        .map_err(|_| RayexecError::new(format!("Invalid size: '{s}'")))?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1000,
+        "mb" => 1000 * 1000,
+        "gb" => 1000 * 1000 * 1000,
+        "tb" => 1000 * 1000 * 1000 * 1000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        other => return Err(RayexecError::new(format!("Unknown size unit: '{other}'"))),
+    };
+
+    Ok((num * multiplier as f64) as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -249,8 +764,24 @@ mod tests {
             allow_nested_loop_join: true,
             partitions: 8,
             batch_size: 4096,
+            batch_size_bytes: 0,
             verify_optimized_plan: false,
             enable_function_chaining: true,
+            preview_rows: 0,
+            memory_limit: 0,
+            timezone: "UTC".to_string(),
+            enable_result_cache: false,
+            result_cache_size: result_cache::DEFAULT_RESULT_CACHE_BYTES as u64,
+            remote_read_cache_size: read_cache::DEFAULT_READ_CACHE_BYTES as u64,
+            role: String::new(),
+            join_ship_threshold: DEFAULT_JOIN_SHIP_THRESHOLD,
+            disable_optimizer_rules: String::new(),
+            enable_plan_cache: false,
+            plan_cache_size: plan_cache::DEFAULT_PLAN_CACHE_ENTRIES as u64,
+            enable_runtime_filters: true,
+            hash_join_nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD as u64,
+            scan_stall_timeout: 0,
+            scan_stall_action: "fail".to_string(),
         }
     }
 
@@ -279,4 +810,124 @@ mod tests {
         let val = conf.get_as_scalar("partitions").unwrap();
         assert_eq!(ScalarValue::UInt64(13), val);
     }
+
+    #[test]
+    fn set_memory_limit_with_unit() {
+        let mut conf = new_test_config();
+        conf.set_from_scalar("memory_limit", "2GB".into()).unwrap();
+        assert_eq!(2_000_000_000, conf.memory_limit);
+
+        conf.set_from_scalar("memory_limit", "512 MiB".into())
+            .unwrap();
+        assert_eq!(512 * 1024 * 1024, conf.memory_limit);
+
+        conf.set_from_scalar("memory_limit", 4096.into()).unwrap();
+        assert_eq!(4096, conf.memory_limit);
+
+        conf.set_from_scalar("memory_limit", "4 apples".into())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn set_timezone() {
+        let mut conf = new_test_config();
+        conf.set_from_scalar("timezone", "utc".into()).unwrap();
+        assert_eq!("UTC", conf.timezone);
+
+        conf.set_from_scalar("timezone", "+05:30".into())
+            .unwrap_err();
+        conf.set_from_scalar("timezone", "Mars/Olympus".into())
+            .unwrap_err();
+        assert_eq!("UTC", conf.timezone);
+    }
+
+    #[test]
+    fn set_scan_stall() {
+        let mut conf = new_test_config();
+        assert_eq!(ScanStall::default(), conf.scan_stall());
+
+        conf.set_from_scalar("scan_stall_timeout", 30.into())
+            .unwrap();
+        conf.set_from_scalar("scan_stall_action", "RETRY".into())
+            .unwrap();
+        assert_eq!(
+            ScanStall {
+                timeout: Some(Duration::from_secs(30)),
+                action: StallAction::Retry,
+            },
+            conf.scan_stall()
+        );
+
+        conf.set_from_scalar("scan_stall_timeout", (-1).into())
+            .unwrap_err();
+        conf.set_from_scalar("scan_stall_action", "ignore".into())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn set_batch_size() {
+        let mut conf = new_test_config();
+        conf.set_from_scalar("batch_size", ScalarValue::Int64(1024))
+            .unwrap();
+        assert_eq!(1024, conf.batch_size);
+
+        conf.set_from_scalar("batch_size", ScalarValue::Int64(0))
+            .unwrap_err();
+        conf.set_from_scalar("batch_size", ScalarValue::Int64(-1))
+            .unwrap_err();
+        conf.set_from_scalar("batch_size", ScalarValue::Int64(1 << 30))
+            .unwrap_err();
+        assert_eq!(1024, conf.batch_size);
+    }
+
+    #[test]
+    fn set_batch_size_bytes() {
+        let mut conf = new_test_config();
+        conf.set_from_scalar("batch_size_bytes", "1MiB".into())
+            .unwrap();
+        assert_eq!(1024 * 1024, conf.batch_size_bytes);
+
+        conf.set_from_scalar("batch_size_bytes", ScalarValue::Int64(0))
+            .unwrap();
+        assert_eq!(0, conf.batch_size_bytes);
+
+        conf.set_from_scalar("batch_size_bytes", ScalarValue::Int64(-1))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn set_disable_optimizer_rules() {
+        let mut conf = new_test_config();
+        conf.set_from_scalar(
+            "disable_optimizer_rules",
+            " Join_Reorder, filter_pushdown,join_reorder ".into(),
+        )
+        .unwrap();
+        assert_eq!("join_reorder,filter_pushdown", conf.disable_optimizer_rules);
+        assert_eq!(
+            vec!["join_reorder", "filter_pushdown"],
+            conf.disabled_optimizer_rules().collect::<Vec<_>>()
+        );
+
+        conf.set_from_scalar("disable_optimizer_rules", "make_fast".into())
+            .unwrap_err();
+        assert_eq!("join_reorder,filter_pushdown", conf.disable_optimizer_rules);
+
+        conf.set_from_scalar("disable_optimizer_rules", "".into())
+            .unwrap();
+        assert_eq!(0, conf.disabled_optimizer_rules().count());
+    }
+
+    #[test]
+    fn all_settings_sorted() {
+        let conf = new_test_config();
+        let settings = conf.all_settings();
+
+        let names: Vec<_> = settings.iter().map(|s| s.name).collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, names);
+
+        assert!(names.contains(&"batch_size"));
+    }
 }
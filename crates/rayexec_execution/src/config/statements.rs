@@ -0,0 +1,246 @@
+use std::fmt;
+
+use rayexec_error::{RayexecError, Result};
+use rayexec_parser::meta::AstMeta;
+use rayexec_parser::statement::Statement;
+
+/// Broad classes of statements used to restrict what a session can execute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatementClass {
+    /// SELECT, VALUES, EXPLAIN, DESCRIBE, and SUMMARIZE.
+    Query,
+    /// INSERT.
+    Dml,
+    /// CREATE, DROP, and ALTER for any kind of object, including secrets and
+    /// policies.
+    Ddl,
+    /// ATTACH and DETACH.
+    Attach,
+    /// COPY TO.
+    Copy,
+    /// SET, RESET, and SHOW.
+    Variable,
+    /// BEGIN, COMMIT, and ROLLBACK.
+    Transaction,
+}
+
+impl StatementClass {
+    pub const ALL: [StatementClass; 7] = [
+        StatementClass::Query,
+        StatementClass::Dml,
+        StatementClass::Ddl,
+        StatementClass::Attach,
+        StatementClass::Copy,
+        StatementClass::Variable,
+        StatementClass::Transaction,
+    ];
+
+    pub fn for_statement<T: AstMeta>(statement: &Statement<T>) -> Self {
+        match statement {
+            Statement::Query(_)
+            | Statement::Explain(_)
+            | Statement::Describe(_)
+            | Statement::Summarize(_) => StatementClass::Query,
+            Statement::Insert(_) => StatementClass::Dml,
+            Statement::CreateTable(_)
+            | Statement::CreateSchema(_)
+            | Statement::CreateView(_)
+            | Statement::CreateSecret(_)
+            | Statement::CreateFunction(_)
+            | Statement::CreateMacro(_)
+            | Statement::CreateIndex(_)
+            | Statement::CreatePolicy(_)
+            | Statement::Drop(_)
+            | Statement::AlterTable(_) => StatementClass::Ddl,
+            Statement::Attach(_) | Statement::Detach(_) => StatementClass::Attach,
+            Statement::CopyTo(_) => StatementClass::Copy,
+            Statement::SetVariable(_) | Statement::ResetVariable(_) | Statement::Show(_) => {
+                StatementClass::Variable
+            }
+            Statement::Transaction(_) => StatementClass::Transaction,
+        }
+    }
+
+    fn bit(&self) -> u8 {
+        1 << (*self as u8)
+    }
+}
+
+impl fmt::Display for StatementClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Query => write!(f, "Query"),
+            Self::Dml => write!(f, "DML"),
+            Self::Ddl => write!(f, "DDL"),
+            Self::Attach => write!(f, "ATTACH/DETACH"),
+            Self::Copy => write!(f, "COPY"),
+            Self::Variable => write!(f, "SET/RESET/SHOW"),
+            Self::Transaction => write!(f, "Transaction"),
+        }
+    }
+}
+
+/// Set of statement classes a session is allowed to execute.
+///
+/// Checked during binding, so disallowed statements never touch the catalog
+/// or any data source. Unlike session variables, this can't be changed from
+/// SQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowedStatements {
+    bits: u8,
+}
+
+impl Default for AllowedStatements {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl AllowedStatements {
+    /// Allow every statement.
+    pub const fn all() -> Self {
+        AllowedStatements { bits: u8::MAX }
+    }
+
+    /// Allow only statements that can't modify data, the catalog, or what's
+    /// attached to the session.
+    ///
+    /// Session variables can still be set, and transactions can still be
+    /// started.
+    pub fn read_only() -> Self {
+        Self::only([
+            StatementClass::Query,
+            StatementClass::Variable,
+            StatementClass::Transaction,
+        ])
+    }
+
+    /// Allow only the given statement classes.
+    pub fn only(classes: impl IntoIterator<Item = StatementClass>) -> Self {
+        let bits = classes
+            .into_iter()
+            .fold(0, |bits, class| bits | class.bit());
+        AllowedStatements { bits }
+    }
+
+    /// Also allow the given statement class.
+    pub fn allow(mut self, class: StatementClass) -> Self {
+        self.bits |= class.bit();
+        self
+    }
+
+    /// Disallow the given statement class.
+    pub fn deny(mut self, class: StatementClass) -> Self {
+        self.bits &= !class.bit();
+        self
+    }
+
+    pub fn is_allowed(&self, class: StatementClass) -> bool {
+        self.bits & class.bit() != 0
+    }
+
+    /// If passthrough table functions (e.g. `pg_execute`) are allowed.
+    ///
+    /// These can run anything in an attached database, so they require both
+    /// DML and ATTACH.
+    pub fn allows_passthrough(&self) -> bool {
+        self.is_allowed(StatementClass::Dml) && self.is_allowed(StatementClass::Attach)
+    }
+
+    /// Check that a statement is allowed, returning an error if it's not.
+    pub fn check_statement<T: AstMeta>(&self, statement: &Statement<T>) -> Result<()> {
+        let class = StatementClass::for_statement(statement);
+        if !self.is_allowed(class) {
+            return Err(RayexecError::new(format!(
+                "{class} statements are not allowed in this session"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayexec_parser::parser;
+
+    use super::*;
+
+    fn parse_one(sql: &str) -> Statement<rayexec_parser::meta::Raw> {
+        let mut stmts = parser::parse(sql).unwrap();
+        assert_eq!(1, stmts.len());
+        stmts.pop().unwrap()
+    }
+
+    #[test]
+    fn classify_statements() {
+        let cases = [
+            ("SELECT 1", StatementClass::Query),
+            ("EXPLAIN SELECT 1", StatementClass::Query),
+            ("DESCRIBE t1", StatementClass::Query),
+            ("INSERT INTO t1 VALUES (1)", StatementClass::Dml),
+            ("CREATE TABLE t1 (a INT)", StatementClass::Ddl),
+            ("CREATE TABLE t2 AS SELECT 1", StatementClass::Ddl),
+            ("DROP TABLE t1", StatementClass::Ddl),
+            ("ATTACH memory DATABASE AS m", StatementClass::Attach),
+            ("DETACH DATABASE m", StatementClass::Attach),
+            ("COPY t1 TO 'out.csv'", StatementClass::Copy),
+            ("SET batch_size = 1024", StatementClass::Variable),
+            ("SHOW batch_size", StatementClass::Variable),
+            ("BEGIN", StatementClass::Transaction),
+        ];
+
+        for (sql, expected) in cases {
+            assert_eq!(
+                expected,
+                StatementClass::for_statement(&parse_one(sql)),
+                "sql: {sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn read_only() {
+        let allowed = AllowedStatements::read_only();
+
+        allowed.check_statement(&parse_one("SELECT 1")).unwrap();
+        allowed
+            .check_statement(&parse_one("SET batch_size = 1024"))
+            .unwrap();
+        allowed.check_statement(&parse_one("COMMIT")).unwrap();
+
+        allowed
+            .check_statement(&parse_one("CREATE TABLE t1 (a INT)"))
+            .unwrap_err();
+        allowed
+            .check_statement(&parse_one("INSERT INTO t1 VALUES (1)"))
+            .unwrap_err();
+        allowed
+            .check_statement(&parse_one("ATTACH memory DATABASE AS m"))
+            .unwrap_err();
+        allowed
+            .check_statement(&parse_one("COPY t1 TO 'out.csv'"))
+            .unwrap_err();
+
+        assert!(!allowed.allows_passthrough());
+    }
+
+    #[test]
+    fn allow_and_deny() {
+        let allowed = AllowedStatements::all().deny(StatementClass::Attach);
+        assert!(!allowed.is_allowed(StatementClass::Attach));
+        assert!(allowed.is_allowed(StatementClass::Ddl));
+
+        let allowed = AllowedStatements::only([StatementClass::Query]).allow(StatementClass::Dml);
+        assert!(allowed.is_allowed(StatementClass::Dml));
+        assert!(!allowed.is_allowed(StatementClass::Variable));
+
+        for class in StatementClass::ALL {
+            assert!(AllowedStatements::default().is_allowed(class));
+        }
+
+        assert!(AllowedStatements::default().allows_passthrough());
+        assert!(!AllowedStatements::all()
+            .deny(StatementClass::Attach)
+            .allows_passthrough());
+    }
+}
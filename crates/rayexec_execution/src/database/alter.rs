@@ -0,0 +1,421 @@
+//! Alter messages/structs.
+use rayexec_error::{ErrorKind, RayexecError, Result};
+
+use super::catalog_entry::{IndexMethod, TableEntry, TableIndex, TablePolicy};
+use crate::arrays::datatype::DataType;
+use crate::arrays::field::Field;
+use crate::arrays::scalar::OwnedScalarValue;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlterTableInfo {
+    pub name: String,
+    pub operation: AlterTableOperation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlterTableOperation {
+    AddColumn {
+        field: Field,
+        /// Value of the column for rows that existed before the column was
+        /// added.
+        default: OwnedScalarValue,
+        if_not_exists: bool,
+    },
+    DropColumn {
+        name: String,
+        if_exists: bool,
+    },
+    RenameColumn {
+        from: String,
+        to: String,
+    },
+    CreateIndex {
+        name: String,
+        /// Names of the indexed columns, in key order.
+        columns: Vec<String>,
+        method: IndexMethod,
+        if_not_exists: bool,
+    },
+    CreatePolicy {
+        policy: TablePolicy,
+    },
+}
+
+impl AlterTableOperation {
+    /// Apply the alter to a table entry, returning the new entry.
+    ///
+    /// Returns None if the alter doesn't change the table, e.g. when adding a
+    /// column that already exists with IF NOT EXISTS.
+    ///
+    /// Column ids are kept for existing columns, and a dropped column's id is
+    /// never reused. Data already stored for the table stays valid for the
+    /// new entry.
+    pub fn apply(&self, table: &TableEntry) -> Result<Option<TableEntry>> {
+        let position = |name: &str| table.columns.iter().position(|c| c.name == name);
+
+        let mut table = table.clone();
+        match self {
+            Self::AddColumn {
+                field,
+                default,
+                if_not_exists,
+            } => {
+                if position(&field.name).is_some() {
+                    if *if_not_exists {
+                        return Ok(None);
+                    }
+                    return Err(RayexecError::new(format!(
+                        "Column '{}' already exists",
+                        field.name
+                    ))
+                    .with_kind(ErrorKind::AlreadyExists));
+                }
+
+                table.columns.push(field.clone());
+                table.column_ids.push(table.next_column_id);
+                table.column_defaults.push(default.clone());
+                table.next_column_id += 1;
+            }
+            Self::DropColumn { name, if_exists } => {
+                let idx = match position(name) {
+                    Some(idx) => idx,
+                    None if *if_exists => return Ok(None),
+                    None => return Err(missing_column(name)),
+                };
+                if table.columns.len() == 1 {
+                    return Err(RayexecError::new(format!(
+                        "Cannot drop column '{name}', it's the only column in the table"
+                    )));
+                }
+                let id = table.column_ids[idx];
+                if table.primary_key.contains(&id) {
+                    return Err(RayexecError::new(format!(
+                        "Cannot drop column '{name}', it's part of the primary key"
+                    )));
+                }
+                if let Some(index) = table.indexes.iter().find(|i| i.column_ids.contains(&id)) {
+                    return Err(RayexecError::new(format!(
+                        "Cannot drop column '{name}', it's used by index '{}'",
+                        index.name
+                    )));
+                }
+
+                // Data for the column is left in storage, but is no longer
+                // reachable through the entry.
+                table.columns.remove(idx);
+                table.column_ids.remove(idx);
+                table.column_defaults.remove(idx);
+                table.checks.retain(|check| check.column_id != id);
+            }
+            Self::RenameColumn { from, to } => {
+                let idx = position(from).ok_or_else(|| missing_column(from))?;
+                if from == to {
+                    return Ok(None);
+                }
+                if position(to).is_some() {
+                    return Err(RayexecError::new(format!("Column '{to}' already exists"))
+                        .with_kind(ErrorKind::AlreadyExists));
+                }
+
+                table.columns[idx].name = to.clone();
+            }
+            Self::CreateIndex {
+                name,
+                columns,
+                method,
+                if_not_exists,
+            } => {
+                if table.indexes.iter().any(|index| &index.name == name) {
+                    if *if_not_exists {
+                        return Ok(None);
+                    }
+                    return Err(RayexecError::new(format!("Index '{name}' already exists"))
+                        .with_kind(ErrorKind::AlreadyExists));
+                }
+
+                if *method == IndexMethod::FullText && columns.len() != 1 {
+                    return Err(RayexecError::new(format!(
+                        "Full-text index '{name}' must be on exactly one column"
+                    )));
+                }
+                if matches!(method, IndexMethod::Hnsw(_)) && columns.len() != 1 {
+                    return Err(RayexecError::new(format!(
+                        "HNSW index '{name}' must be on exactly one column"
+                    )));
+                }
+
+                let mut column_ids = Vec::with_capacity(columns.len());
+                for column in columns {
+                    let idx = position(column).ok_or_else(|| missing_column(column))?;
+                    let id = table.column_ids[idx];
+                    if column_ids.contains(&id) {
+                        return Err(RayexecError::new(format!(
+                            "Column '{column}' appears more than once in index '{name}'"
+                        )));
+                    }
+                    if let IndexMethod::Hnsw(_) = method {
+                        if !is_float_vector(&table.columns[idx].datatype) {
+                            return Err(RayexecError::new(format!(
+                                "HNSW index requires a fixed-size float array column, '{column}' has type {}",
+                                table.columns[idx].datatype
+                            )));
+                        }
+                    } else if matches!(
+                        table.columns[idx].datatype,
+                        DataType::List(_) | DataType::Struct(_)
+                    ) {
+                        return Err(RayexecError::new(format!(
+                            "Cannot index column '{column}' with type {}",
+                            table.columns[idx].datatype
+                        )));
+                    }
+                    if *method == IndexMethod::FullText
+                        && table.columns[idx].datatype != DataType::Utf8
+                    {
+                        return Err(RayexecError::new(format!(
+                            "Full-text index requires a text column, '{column}' has type {}",
+                            table.columns[idx].datatype
+                        )));
+                    }
+                    column_ids.push(id);
+                }
+
+                table.indexes.push(TableIndex {
+                    name: name.clone(),
+                    column_ids,
+                    method: *method,
+                });
+            }
+            Self::CreatePolicy { policy } => {
+                if table.policies.iter().any(|p| p.name == policy.name) {
+                    return Err(RayexecError::new(format!(
+                        "Policy '{}' already exists",
+                        policy.name
+                    ))
+                    .with_kind(ErrorKind::AlreadyExists));
+                }
+                table.policies.push(policy.clone());
+            }
+        }
+
+        Ok(Some(table))
+    }
+}
+
+/// If the type is a fixed-size list of floats.
+fn is_float_vector(datatype: &DataType) -> bool {
+    match datatype {
+        DataType::List(meta) => {
+            meta.size.is_some()
+                && matches!(
+                    meta.datatype.as_ref(),
+                    DataType::Float16 | DataType::Float32 | DataType::Float64
+                )
+        }
+        _ => false,
+    }
+}
+
+fn missing_column(name: &str) -> RayexecError {
+    RayexecError::new(format!("Column '{name}' does not exist"))
+        .with_kind(ErrorKind::ColumnNotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::datatype::{DataType, ListTypeMeta};
+    use crate::database::catalog_entry::CheckConstraint;
+    use crate::functions::scalar::builtin::similarity::VectorMetric;
+
+    fn table() -> TableEntry {
+        TableEntry::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ])
+    }
+
+    fn add(name: &str, if_not_exists: bool) -> AlterTableOperation {
+        AlterTableOperation::AddColumn {
+            field: Field::new(name, DataType::Int64, true),
+            default: OwnedScalarValue::Int64(4),
+            if_not_exists,
+        }
+    }
+
+    fn drop(name: &str, if_exists: bool) -> AlterTableOperation {
+        AlterTableOperation::DropColumn {
+            name: name.to_string(),
+            if_exists,
+        }
+    }
+
+    #[test]
+    fn column_ids_not_reused() {
+        let t = add("c", false).apply(&table()).unwrap().unwrap();
+        assert_eq!(vec![0, 1, 2], t.column_ids);
+        assert_eq!(OwnedScalarValue::Int64(4), t.column_defaults[2]);
+
+        let t = drop("c", false).apply(&t).unwrap().unwrap();
+        let t = add("c", false).apply(&t).unwrap().unwrap();
+        assert_eq!(vec![0, 1, 3], t.column_ids);
+        assert_eq!(4, t.next_column_id);
+
+        let t = drop("a", false).apply(&t).unwrap().unwrap();
+        assert_eq!(vec![1, 3], t.column_ids);
+        assert_eq!("b", t.columns[0].name);
+    }
+
+    #[test]
+    fn rename_keeps_id() {
+        let op = AlterTableOperation::RenameColumn {
+            from: "a".to_string(),
+            to: "z".to_string(),
+        };
+        let t = op.apply(&table()).unwrap().unwrap();
+        assert_eq!("z", t.columns[0].name);
+        assert_eq!(vec![0, 1], t.column_ids);
+
+        let op = AlterTableOperation::RenameColumn {
+            from: "a".to_string(),
+            to: "b".to_string(),
+        };
+        let err = op.apply(&table()).unwrap_err();
+        assert_eq!(ErrorKind::AlreadyExists, err.kind());
+    }
+
+    #[test]
+    fn if_exists_noops() {
+        assert_eq!(None, add("a", true).apply(&table()).unwrap());
+        assert_eq!(None, drop("c", true).apply(&table()).unwrap());
+
+        let err = add("a", false).apply(&table()).unwrap_err();
+        assert_eq!(ErrorKind::AlreadyExists, err.kind());
+        let err = drop("c", false).apply(&table()).unwrap_err();
+        assert_eq!(ErrorKind::ColumnNotFound, err.kind());
+    }
+
+    #[test]
+    fn drop_column_constraints() {
+        let mut t = table();
+        t.primary_key = vec![0];
+        t.checks = vec![CheckConstraint {
+            column_id: 1,
+            column_name: "b".to_string(),
+            expression: "b <> ''".to_string(),
+        }];
+
+        drop("a", false).apply(&t).unwrap_err();
+        let t = drop("b", false).apply(&t).unwrap().unwrap();
+        assert!(t.checks.is_empty());
+        assert_eq!(vec![0], t.primary_key);
+    }
+
+    #[test]
+    fn create_index() {
+        let create =
+            |name: &str, columns: &[&str], if_not_exists| AlterTableOperation::CreateIndex {
+                name: name.to_string(),
+                columns: columns.iter().map(|c| c.to_string()).collect(),
+                method: IndexMethod::BTree,
+                if_not_exists,
+            };
+
+        let t = create("idx", &["b", "a"], false)
+            .apply(&table())
+            .unwrap()
+            .unwrap();
+        assert_eq!(vec![1, 0], t.indexes[0].column_ids);
+
+        assert_eq!(None, create("idx", &["a"], true).apply(&t).unwrap());
+        let err = create("idx", &["a"], false).apply(&t).unwrap_err();
+        assert_eq!(ErrorKind::AlreadyExists, err.kind());
+        let err = create("idx2", &["c"], false).apply(&t).unwrap_err();
+        assert_eq!(ErrorKind::ColumnNotFound, err.kind());
+        create("idx2", &["a", "a"], false).apply(&t).unwrap_err();
+
+        // Indexed columns can be renamed, but not dropped.
+        let rename = AlterTableOperation::RenameColumn {
+            from: "a".to_string(),
+            to: "z".to_string(),
+        };
+        let t = rename.apply(&t).unwrap().unwrap();
+        assert_eq!(vec![1, 0], t.indexes[0].column_ids);
+        drop("z", false).apply(&t).unwrap_err();
+    }
+
+    #[test]
+    fn create_full_text_index() {
+        let create = |columns: &[&str]| AlterTableOperation::CreateIndex {
+            name: "fts".to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            method: IndexMethod::FullText,
+            if_not_exists: false,
+        };
+
+        let t = create(&["b"]).apply(&table()).unwrap().unwrap();
+        assert_eq!(IndexMethod::FullText, t.indexes[0].method);
+
+        // Only a single text column can be indexed.
+        create(&["a"]).apply(&table()).unwrap_err();
+        create(&["a", "b"]).apply(&table()).unwrap_err();
+    }
+
+    #[test]
+    fn create_hnsw_index() {
+        let t = TableEntry::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new(
+                "v",
+                DataType::List(ListTypeMeta::new_fixed_size(DataType::Float32, 3)),
+                true,
+            ),
+            Field::new(
+                "l",
+                DataType::List(ListTypeMeta::new(DataType::Float32)),
+                true,
+            ),
+        ]);
+
+        let create = |columns: &[&str]| AlterTableOperation::CreateIndex {
+            name: "hnsw".to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            method: IndexMethod::Hnsw(VectorMetric::Cosine),
+            if_not_exists: false,
+        };
+
+        let t2 = create(&["v"]).apply(&t).unwrap().unwrap();
+        assert_eq!(vec![1], t2.indexes[0].column_ids);
+
+        // Only a single fixed-size float list can be indexed.
+        create(&["l"]).apply(&t).unwrap_err();
+        create(&["a"]).apply(&t).unwrap_err();
+        create(&["v", "a"]).apply(&t).unwrap_err();
+    }
+
+    #[test]
+    fn create_policy() {
+        let create = |name: &str| AlterTableOperation::CreatePolicy {
+            policy: TablePolicy {
+                name: name.to_string(),
+                roles: vec!["analyst".to_string()],
+                expression: "a > 4".to_string(),
+            },
+        };
+
+        let t = create("p1").apply(&table()).unwrap().unwrap();
+        let t = create("p2").apply(&t).unwrap().unwrap();
+        assert_eq!(2, t.policies.len());
+        assert!(t.policies[0].applies_to("analyst"));
+        assert!(!t.policies[0].applies_to("admin"));
+
+        let err = create("p1").apply(&t).unwrap_err();
+        assert_eq!(ErrorKind::AlreadyExists, err.kind());
+    }
+
+    #[test]
+    fn cannot_drop_only_column() {
+        let t = drop("a", false).apply(&table()).unwrap().unwrap();
+        drop("b", false).apply(&t).unwrap_err();
+    }
+}
@@ -1,15 +1,28 @@
-/// All builtin views placed in the 'system.glare_catalog' schema.
-pub const BUILTIN_VIEWS: &[BuiltinView] =
-    &[SHOW_DATABASES_VIEW, SHOW_SCHEMAS_VIEW, SHOW_TABLES_VIEW];
+/// All builtin views placed in the 'system' catalog.
+pub const BUILTIN_VIEWS: &[BuiltinView] = &[
+    SHOW_DATABASES_VIEW,
+    SHOW_SCHEMAS_VIEW,
+    SHOW_TABLES_VIEW,
+    INFORMATION_SCHEMA_SCHEMATA_VIEW,
+    INFORMATION_SCHEMA_TABLES_VIEW,
+    INFORMATION_SCHEMA_COLUMNS_VIEW,
+    INFORMATION_SCHEMA_VIEWS_VIEW,
+    SYSTEM_QUERIES_VIEW,
+    SYSTEM_FUNCTIONS_VIEW,
+    SYSTEM_MEMORY_VIEW,
+];
 
 /// Describes a builtin view.
 #[derive(Debug)]
 pub struct BuiltinView {
+    /// Schema in the system catalog the view is placed in.
+    pub schema: &'static str,
     pub name: &'static str,
     pub view: &'static str,
 }
 
 pub const SHOW_DATABASES_VIEW: BuiltinView = BuiltinView {
+    schema: "glare_catalog",
     name: "show_databases",
     view: "
 SELECT database_name
@@ -19,6 +32,7 @@ ORDER BY database_name;
 };
 
 pub const SHOW_SCHEMAS_VIEW: BuiltinView = BuiltinView {
+    schema: "glare_catalog",
     name: "show_schemas",
     view: "
 SELECT schema_name
@@ -28,6 +42,7 @@ ORDER BY schema_name;
 };
 
 pub const SHOW_TABLES_VIEW: BuiltinView = BuiltinView {
+    schema: "glare_catalog",
     name: "show_tables",
     view: "
 SELECT table_name as name
@@ -35,3 +50,89 @@ FROM list_tables()
 ORDER BY name;
 ",
 };
+
+pub const INFORMATION_SCHEMA_SCHEMATA_VIEW: BuiltinView = BuiltinView {
+    schema: "information_schema",
+    name: "schemata",
+    view: "
+SELECT database_name AS catalog_name, schema_name
+FROM list_schemas();
+",
+};
+
+pub const INFORMATION_SCHEMA_TABLES_VIEW: BuiltinView = BuiltinView {
+    schema: "information_schema",
+    name: "tables",
+    view: "
+SELECT database_name AS table_catalog,
+       schema_name AS table_schema,
+       table_name,
+       'BASE TABLE' AS table_type
+FROM list_tables()
+UNION ALL
+SELECT database_name AS table_catalog,
+       schema_name AS table_schema,
+       view_name AS table_name,
+       'VIEW' AS table_type
+FROM list_views();
+",
+};
+
+pub const INFORMATION_SCHEMA_COLUMNS_VIEW: BuiltinView = BuiltinView {
+    schema: "information_schema",
+    name: "columns",
+    view: "
+SELECT database_name AS table_catalog,
+       schema_name AS table_schema,
+       table_name,
+       column_name,
+       ordinal_position,
+       data_type,
+       CASE WHEN is_nullable THEN 'YES' ELSE 'NO' END AS is_nullable
+FROM list_columns();
+",
+};
+
+pub const INFORMATION_SCHEMA_VIEWS_VIEW: BuiltinView = BuiltinView {
+    schema: "information_schema",
+    name: "views",
+    view: "
+SELECT database_name AS table_catalog,
+       schema_name AS table_schema,
+       view_name AS table_name,
+       sql AS view_definition
+FROM list_views();
+",
+};
+
+pub const SYSTEM_QUERIES_VIEW: BuiltinView = BuiltinView {
+    schema: "system",
+    name: "queries",
+    view: "
+SELECT query_id, query, state, elapsed_ms, error
+FROM list_queries();
+",
+};
+
+pub const SYSTEM_FUNCTIONS_VIEW: BuiltinView = BuiltinView {
+    schema: "system",
+    name: "functions",
+    view: "
+SELECT schema_name,
+       function_name,
+       function_type,
+       argument_types,
+       return_type,
+       description
+FROM list_functions();
+",
+};
+
+pub const SYSTEM_MEMORY_VIEW: BuiltinView = BuiltinView {
+    schema: "system",
+    name: "memory",
+    view: "
+SELECT name, value, description
+FROM list_memory();
+",
+};
@@ -1,10 +1,353 @@
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
 
-#[derive(Debug, Default)]
-pub struct CatalogTx {}
+use parking_lot::Mutex;
+use rayexec_error::{ErrorKind, RayexecError, Result};
+
+/// Timestamp of the most recent commit.
+///
+/// Shared across all databases so that a single transaction can write to
+/// tables in multiple databases.
+static LAST_COMMIT: AtomicU64 = AtomicU64::new(0);
+
+/// Source for transaction ids.
+static NEXT_TX_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Held while committing so that checking for conflicts and applying writes
+/// happens atomically with respect to other commits.
+///
+/// Also held when taking a snapshot to ensure all writes for commits up to the
+/// snapshot have been applied.
+static COMMIT_LOCK: Mutex<()> = parking_lot::const_mutex(());
+
+/// Snapshots of explicit transactions that are still around, along with the
+/// number of transactions reading at each snapshot.
+static ACTIVE_SNAPSHOTS: Mutex<BTreeMap<u64, usize>> = parking_lot::const_mutex(BTreeMap::new());
+
+/// Something with changes buffered in a transaction, e.g. a table that's been
+/// inserted into.
+pub trait TransactionParticipant: Debug + Sync + Send {
+    /// Check if the changes can be committed.
+    ///
+    /// `snapshot` is the commit timestamp the transaction has been reading at.
+    /// Called for all participants before any changes are applied.
+    fn check_commit(&self, snapshot: u64) -> Result<()>;
+
+    /// Apply the buffered changes, making them visible to transactions reading
+    /// at or after `commit_ts`.
+    fn commit(&self, commit_ts: u64);
+
+    /// Discard the buffered changes.
+    fn rollback(&self);
+}
+
+/// A transaction for reading from and writing to catalogs and their tables.
+///
+/// By default transactions are auto-commit, reading the latest committed data
+/// and committing writes as soon as they're complete. Explicit transactions
+/// created with `begin` read from a snapshot taken when the transaction began,
+/// and buffer writes until the transaction is committed.
+///
+/// Conflicts are detected on commit at the table level. A transaction that
+/// wrote to a table will fail to commit if another transaction committed
+/// writes to the same table after this transaction began.
+///
+/// Catalog changes (creating or dropping tables, etc) are not transactional,
+/// and are applied immediately even in an explicit transaction.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogTx {
+    /// State for explicit transactions, None if auto-commit.
+    state: Option<Arc<TxState>>,
+}
+
+#[derive(Debug)]
+struct TxState {
+    id: u64,
+    /// Commit timestamp this transaction reads at.
+    snapshot: u64,
+    /// Participants with changes buffered in this transaction.
+    participants: Mutex<Vec<Arc<dyn TransactionParticipant>>>,
+    /// Set once the transaction has been committed or rolled back.
+    finished: AtomicBool,
+}
+
+impl Drop for TxState {
+    fn drop(&mut self) {
+        // Don't leave around buffered writes for a transaction that was never
+        // committed.
+        if !self.finished.load(Ordering::Acquire) {
+            for participant in self.participants.get_mut().drain(..) {
+                participant.rollback();
+            }
+        }
+
+        let mut active = ACTIVE_SNAPSHOTS.lock();
+        if let Some(count) = active.get_mut(&self.snapshot) {
+            *count -= 1;
+            if *count == 0 {
+                active.remove(&self.snapshot);
+            }
+        }
+    }
+}
 
 impl CatalogTx {
+    /// Create a new auto-commit transaction.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Begin an explicit transaction reading from a snapshot of the currently
+    /// committed data.
+    pub fn begin() -> Self {
+        let _guard = COMMIT_LOCK.lock();
+        let snapshot = LAST_COMMIT.load(Ordering::Acquire);
+        *ACTIVE_SNAPSHOTS.lock().entry(snapshot).or_default() += 1;
+
+        CatalogTx {
+            state: Some(Arc::new(TxState {
+                id: NEXT_TX_ID.fetch_add(1, Ordering::Relaxed),
+                snapshot,
+                participants: Mutex::new(Vec::new()),
+                finished: AtomicBool::new(false),
+            })),
+        }
+    }
+
+    /// If this is an explicit transaction started with `begin`.
+    pub fn is_explicit(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Id of the transaction, None if auto-commit.
+    pub fn id(&self) -> Option<u64> {
+        self.state.as_ref().map(|s| s.id)
+    }
+
+    /// Commit timestamp this transaction reads at, None if auto-commit
+    /// (always reads the latest committed data).
+    pub fn snapshot(&self) -> Option<u64> {
+        self.state.as_ref().map(|s| s.snapshot)
+    }
+
+    /// Snapshots explicit transactions may still be reading at, in ascending
+    /// order.
+    ///
+    /// Transactions that begin later read at a snapshot that includes every
+    /// commit so far.
+    pub fn active_snapshots() -> Vec<u64> {
+        ACTIVE_SNAPSHOTS.lock().keys().copied().collect()
+    }
+
+    /// Check if data committed at `commit_ts` is visible to this transaction.
+    pub fn is_visible(&self, commit_ts: u64) -> bool {
+        match self.snapshot() {
+            Some(snapshot) => commit_ts <= snapshot,
+            None => true,
+        }
+    }
+
+    /// Register a participant with changes buffered in this transaction.
+    ///
+    /// Errors if this transaction is auto-commit or has already finished.
+    pub fn register(&self, participant: Arc<dyn TransactionParticipant>) -> Result<()> {
+        let state = self.state.as_ref().ok_or_else(|| {
+            RayexecError::new("Cannot buffer changes in an auto-commit transaction")
+        })?;
+
+        let mut participants = state.participants.lock();
+        // Checked with the lock held to avoid racing with commit/rollback
+        // taking the participants.
+        if state.finished.load(Ordering::Acquire) {
+            return Err(RayexecError::new("Transaction has already finished")
+                .with_kind(ErrorKind::InvalidTransactionState));
+        }
+        participants.push(participant);
+
+        Ok(())
+    }
+
+    /// Commit the transaction.
+    ///
+    /// If any participant fails to commit, all changes are rolled back. This
+    /// is a no-op for auto-commit transactions.
+    pub fn commit(&self) -> Result<()> {
+        let state = match &self.state {
+            Some(state) => state,
+            None => return Ok(()),
+        };
+
+        let participants = state.take_participants()?;
+
+        let _guard = COMMIT_LOCK.lock();
+        for participant in &participants {
+            if let Err(e) = participant.check_commit(state.snapshot) {
+                for participant in &participants {
+                    participant.rollback();
+                }
+                return Err(e);
+            }
+        }
+
+        if participants.is_empty() {
+            // Read-only, don't need to bump the timestamp.
+            return Ok(());
+        }
+
+        let commit_ts = LAST_COMMIT.load(Ordering::Acquire) + 1;
+        for participant in &participants {
+            participant.commit(commit_ts);
+        }
+        LAST_COMMIT.store(commit_ts, Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Roll back the transaction, discarding all buffered changes.
+    ///
+    /// This is a no-op for auto-commit transactions.
+    pub fn rollback(&self) -> Result<()> {
+        let state = match &self.state {
+            Some(state) => state,
+            None => return Ok(()),
+        };
+
+        for participant in state.take_participants()? {
+            participant.rollback();
+        }
+
+        Ok(())
+    }
+
+    /// Apply a write outside of an explicit transaction.
+    ///
+    /// `apply` is called with the commit timestamp the written data should be
+    /// visible at.
+    pub fn auto_commit<T>(apply: impl FnOnce(u64) -> T) -> T {
+        let _guard = COMMIT_LOCK.lock();
+        let commit_ts = LAST_COMMIT.load(Ordering::Acquire) + 1;
+        let out = apply(commit_ts);
+        LAST_COMMIT.store(commit_ts, Ordering::Release);
+        out
+    }
+}
+
+impl TxState {
+    /// Mark the transaction as finished, taking all registered participants.
+    fn take_participants(&self) -> Result<Vec<Arc<dyn TransactionParticipant>>> {
+        let mut participants = self.participants.lock();
+        if self.finished.swap(true, Ordering::AcqRel) {
+            return Err(RayexecError::new("Transaction has already finished")
+                .with_kind(ErrorKind::InvalidTransactionState));
+        }
+        Ok(std::mem::take(&mut participants))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct TestParticipant {
+        conflict: bool,
+        committed_at: Mutex<Option<u64>>,
+        rolled_back: AtomicBool,
+    }
+
+    impl TransactionParticipant for TestParticipant {
+        fn check_commit(&self, _snapshot: u64) -> Result<()> {
+            if self.conflict {
+                return Err(RayexecError::new("conflict"));
+            }
+            Ok(())
+        }
+
+        fn commit(&self, commit_ts: u64) {
+            *self.committed_at.lock() = Some(commit_ts);
+        }
+
+        fn rollback(&self) {
+            self.rolled_back.store(true, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn auto_commit_sees_everything() {
+        let tx = CatalogTx::new();
+        assert!(!tx.is_explicit());
+        assert!(tx.is_visible(u64::MAX));
+        tx.register(Arc::new(TestParticipant::default()))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn snapshot_visibility() {
+        let tx = CatalogTx::begin();
+        let ts = CatalogTx::auto_commit(|ts| ts);
+
+        assert!(ts > tx.snapshot().unwrap());
+        assert!(!tx.is_visible(ts));
+        assert!(CatalogTx::begin().is_visible(ts));
+    }
+
+    #[test]
+    fn active_snapshots_tracked_until_drop() {
+        let tx = CatalogTx::begin();
+        let snapshot = tx.snapshot().unwrap();
+        // Other tests may have transactions open too.
+        assert!(CatalogTx::active_snapshots().contains(&snapshot));
+
+        std::mem::drop(tx);
+        let ts = CatalogTx::auto_commit(|ts| ts);
+        let later = CatalogTx::begin();
+        let active = CatalogTx::active_snapshots();
+        assert!(active.contains(&later.snapshot().unwrap()));
+        assert!(later.snapshot().unwrap() >= ts);
+    }
+
+    #[test]
+    fn commit_applies_changes() {
+        let tx = CatalogTx::begin();
+        let participant = Arc::new(TestParticipant::default());
+        tx.register(participant.clone()).unwrap();
+
+        tx.commit().unwrap();
+        let committed_at = participant.committed_at.lock().unwrap();
+        assert!(committed_at > tx.snapshot().unwrap());
+
+        // Can't commit twice.
+        tx.commit().unwrap_err();
+        tx.register(Arc::new(TestParticipant::default()))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn conflict_rolls_back_all() {
+        let tx = CatalogTx::begin();
+        let ok = Arc::new(TestParticipant::default());
+        let conflict = Arc::new(TestParticipant {
+            conflict: true,
+            ..Default::default()
+        });
+        tx.register(ok.clone()).unwrap();
+        tx.register(conflict.clone()).unwrap();
+
+        tx.commit().unwrap_err();
+        assert!(ok.committed_at.lock().is_none());
+        assert!(ok.rolled_back.load(Ordering::Relaxed));
+        assert!(conflict.rolled_back.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn rollback_on_drop() {
+        let participant = Arc::new(TestParticipant::default());
+        let tx = CatalogTx::begin();
+        tx.register(participant.clone()).unwrap();
+
+        std::mem::drop(tx);
+        assert!(participant.rolled_back.load(Ordering::Relaxed));
+    }
 }
@@ -1,13 +1,15 @@
 use std::fmt;
 use std::sync::Arc;
 
-use rayexec_error::{OptionExt, RayexecError, Result};
+use rayexec_error::{not_implemented, ErrorKind, OptionExt, RayexecError, Result};
 use rayexec_proto::ProtoConv;
 
 use super::DatabaseContext;
 use crate::arrays::field::Field;
+use crate::arrays::scalar::OwnedScalarValue;
 use crate::functions::aggregate::AggregateFunction;
 use crate::functions::copy::CopyToFunction;
+use crate::functions::scalar::builtin::similarity::VectorMetric;
 use crate::functions::scalar::ScalarFunction;
 use crate::functions::table::TableFunction;
 use crate::proto::DatabaseProtoConv;
@@ -21,6 +23,8 @@ pub enum CatalogEntryType {
     AggregateFunction,
     TableFunction,
     CopyToFunction,
+    ScalarMacro,
+    TableMacro,
 }
 
 impl fmt::Display for CatalogEntryType {
@@ -33,6 +37,8 @@ impl fmt::Display for CatalogEntryType {
             Self::AggregateFunction => write!(f, "aggregate function"),
             Self::TableFunction => write!(f, "table function"),
             Self::CopyToFunction => write!(f, "copy to function"),
+            Self::ScalarMacro => write!(f, "scalar macro"),
+            Self::TableMacro => write!(f, "table macro"),
         }
     }
 }
@@ -49,6 +55,9 @@ impl ProtoConv for CatalogEntryType {
             Self::AggregateFunction => Self::ProtoType::AggregateFunction,
             Self::TableFunction => Self::ProtoType::TableFunction,
             Self::CopyToFunction => Self::ProtoType::CopyToFunction,
+            Self::ScalarMacro | Self::TableMacro => {
+                not_implemented!("macro catalog entry type to proto")
+            }
         })
     }
 
@@ -115,6 +124,8 @@ pub enum CatalogEntryInner {
     AggregateFunction(AggregateFunctionEntry),
     TableFunction(TableFunctionEntry),
     CopyToFunction(CopyToFunctionEntry),
+    ScalarMacro(MacroEntry),
+    TableMacro(MacroEntry),
 }
 
 impl DatabaseProtoConv for CatalogEntryInner {
@@ -131,6 +142,9 @@ impl DatabaseProtoConv for CatalogEntryInner {
             Self::AggregateFunction(ent) => Value::AggregateFunction(ent.to_proto_ctx(context)?),
             Self::TableFunction(ent) => Value::TableFunction(ent.to_proto_ctx(context)?),
             Self::CopyToFunction(ent) => Value::CopyToFunction(ent.to_proto_ctx(context)?),
+            Self::ScalarMacro(_ent) | Self::TableMacro(_ent) => {
+                not_implemented!("macro catalog entry to proto")
+            }
         };
 
         Ok(Self::ProtoType { value: Some(value) })
@@ -266,6 +280,233 @@ impl DatabaseProtoConv for CopyToFunctionEntry {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TableEntry {
     pub columns: Vec<Field>,
+    /// Storage ids for each column.
+    ///
+    /// Ids stay the same when columns are renamed, and aren't reused once a
+    /// column is dropped. This lets storage keep data written with previous
+    /// versions of the table, and readers still using a previous version of
+    /// the entry see the columns as they were.
+    pub column_ids: Vec<usize>,
+    /// Value of each column for rows written before the column was added.
+    pub column_defaults: Vec<OwnedScalarValue>,
+    /// Id to use for the next added column.
+    pub next_column_id: usize,
+    /// CHECK constraints on the table's columns.
+    pub checks: Vec<CheckConstraint>,
+    /// Ids of the columns making up the primary key.
+    ///
+    /// Empty if the table doesn't have a primary key.
+    pub primary_key: Vec<usize>,
+    /// Secondary indexes on the table.
+    pub indexes: Vec<TableIndex>,
+    /// Row-level security policies on the table.
+    ///
+    /// Once a table has a policy, only rows matching at least one policy
+    /// that applies to the session's role are visible when reading it.
+    pub policies: Vec<TablePolicy>,
+}
+
+impl TableEntry {
+    /// Create a new table entry with columns laid out in order.
+    pub fn new(columns: Vec<Field>) -> Self {
+        TableEntry {
+            column_ids: (0..columns.len()).collect(),
+            column_defaults: vec![OwnedScalarValue::Null; columns.len()],
+            next_column_id: columns.len(),
+            columns,
+            checks: Vec::new(),
+            primary_key: Vec::new(),
+            indexes: Vec::new(),
+            policies: Vec::new(),
+        }
+    }
+
+    /// Get the constraints for a column as they'd be written in a column
+    /// definition, e.g. 'NOT NULL'.
+    pub fn column_constraints(&self, idx: usize) -> Vec<String> {
+        let id = self.column_ids[idx];
+        let mut constraints = Vec::new();
+
+        if self.primary_key.contains(&id) {
+            constraints.push("PRIMARY KEY".to_string());
+        } else if !self.columns[idx].nullable {
+            constraints.push("NOT NULL".to_string());
+        }
+        constraints.extend(
+            self.checks
+                .iter()
+                .filter(|check| check.column_id == id)
+                .map(|check| format!("CHECK ({})", check.expression)),
+        );
+
+        constraints
+    }
+}
+
+/// A CHECK constraint on a table column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckConstraint {
+    /// Id of the column the constraint is on.
+    pub column_id: usize,
+    /// Name of the column when the constraint was created.
+    ///
+    /// The expression is bound against a single column with this name, which
+    /// keeps the constraint valid if the column is renamed.
+    pub column_name: String,
+    /// SQL for the boolean expression.
+    pub expression: String,
+}
+
+impl ProtoConv for CheckConstraint {
+    type ProtoType = rayexec_proto::generated::catalog::CheckConstraint;
+
+    fn to_proto(&self) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            column_id: self.column_id as u64,
+            column_name: self.column_name.clone(),
+            expression: self.expression.clone(),
+        })
+    }
+
+    fn from_proto(proto: Self::ProtoType) -> Result<Self> {
+        Ok(Self {
+            column_id: proto.column_id as usize,
+            column_name: proto.column_name,
+            expression: proto.expression,
+        })
+    }
+}
+
+/// How a secondary index is structured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IndexMethod {
+    /// Ordered index over the column values.
+    #[default]
+    BTree,
+    /// Inverted index from terms to the rows containing them. Indexes a
+    /// single string column.
+    FullText,
+    /// HNSW graph over vectors for approximate nearest neighbor search,
+    /// using the given distance metric. Indexes a single fixed-size list
+    /// column of floats.
+    Hnsw(VectorMetric),
+}
+
+impl IndexMethod {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "btree" => Ok(Self::BTree),
+            "fts" => Ok(Self::FullText),
+            "hnsw" => Ok(Self::Hnsw(VectorMetric::L2)),
+            "hnsw_cosine" => Ok(Self::Hnsw(VectorMetric::Cosine)),
+            "hnsw_ip" => Ok(Self::Hnsw(VectorMetric::InnerProduct)),
+            other => Err(RayexecError::new(format!(
+                "Unsupported index method '{other}', only 'btree', 'fts', 'hnsw', 'hnsw_cosine', and 'hnsw_ip' are supported"
+            ))
+            .with_kind(ErrorKind::NotImplemented)),
+        }
+    }
+}
+
+impl fmt::Display for IndexMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BTree => write!(f, "btree"),
+            Self::FullText => write!(f, "fts"),
+            Self::Hnsw(VectorMetric::L2) => write!(f, "hnsw"),
+            Self::Hnsw(VectorMetric::Cosine) => write!(f, "hnsw_cosine"),
+            Self::Hnsw(VectorMetric::InnerProduct) => write!(f, "hnsw_ip"),
+        }
+    }
+}
+
+/// A secondary index on a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableIndex {
+    /// Name of the index, unique within the table.
+    pub name: String,
+    /// Ids of the indexed columns, in key order.
+    pub column_ids: Vec<usize>,
+    /// How the index is structured.
+    pub method: IndexMethod,
+}
+
+impl ProtoConv for TableIndex {
+    type ProtoType = rayexec_proto::generated::catalog::TableIndex;
+
+    fn to_proto(&self) -> Result<Self::ProtoType> {
+        use rayexec_proto::generated::catalog::IndexMethod as ProtoIndexMethod;
+
+        let method = match self.method {
+            IndexMethod::BTree => ProtoIndexMethod::Btree,
+            IndexMethod::FullText => ProtoIndexMethod::FullText,
+            IndexMethod::Hnsw(VectorMetric::L2) => ProtoIndexMethod::HnswL2,
+            IndexMethod::Hnsw(VectorMetric::Cosine) => ProtoIndexMethod::HnswCosine,
+            IndexMethod::Hnsw(VectorMetric::InnerProduct) => ProtoIndexMethod::HnswInnerProduct,
+        };
+
+        Ok(Self::ProtoType {
+            name: self.name.clone(),
+            column_ids: self.column_ids.iter().map(|&id| id as u64).collect(),
+            method: method as i32,
+        })
+    }
+
+    fn from_proto(proto: Self::ProtoType) -> Result<Self> {
+        use rayexec_proto::generated::catalog::IndexMethod as ProtoIndexMethod;
+
+        let method = match proto.method() {
+            ProtoIndexMethod::Btree => IndexMethod::BTree,
+            ProtoIndexMethod::FullText => IndexMethod::FullText,
+            ProtoIndexMethod::HnswL2 => IndexMethod::Hnsw(VectorMetric::L2),
+            ProtoIndexMethod::HnswCosine => IndexMethod::Hnsw(VectorMetric::Cosine),
+            ProtoIndexMethod::HnswInnerProduct => IndexMethod::Hnsw(VectorMetric::InnerProduct),
+        };
+
+        Ok(Self {
+            name: proto.name,
+            column_ids: proto.column_ids.into_iter().map(|id| id as usize).collect(),
+            method,
+        })
+    }
+}
+
+/// A row-level security policy on a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TablePolicy {
+    /// Name of the policy, unique within the table.
+    pub name: String,
+    /// Roles the policy applies to. Empty if the policy applies to everyone.
+    pub roles: Vec<String>,
+    /// SQL for the boolean expression rows must satisfy to be visible.
+    pub expression: String,
+}
+
+impl TablePolicy {
+    /// If the policy applies to sessions using the given role.
+    pub fn applies_to(&self, role: &str) -> bool {
+        self.roles.is_empty() || self.roles.iter().any(|r| r == role)
+    }
+}
+
+impl ProtoConv for TablePolicy {
+    type ProtoType = rayexec_proto::generated::catalog::TablePolicy;
+
+    fn to_proto(&self) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            name: self.name.clone(),
+            roles: self.roles.clone(),
+            expression: self.expression.clone(),
+        })
+    }
+
+    fn from_proto(proto: Self::ProtoType) -> Result<Self> {
+        Ok(Self {
+            name: proto.name,
+            roles: proto.roles,
+            expression: proto.expression,
+        })
+    }
 }
 
 impl ProtoConv for TableEntry {
@@ -278,17 +519,79 @@ impl ProtoConv for TableEntry {
                 .iter()
                 .map(|c| c.to_proto())
                 .collect::<Result<_>>()?,
+            column_ids: self.column_ids.iter().map(|&id| id as u64).collect(),
+            column_defaults: self
+                .column_defaults
+                .iter()
+                .map(|v| v.to_proto())
+                .collect::<Result<_>>()?,
+            next_column_id: self.next_column_id as u64,
+            checks: self
+                .checks
+                .iter()
+                .map(|c| c.to_proto())
+                .collect::<Result<_>>()?,
+            primary_key: self.primary_key.iter().map(|&id| id as u64).collect(),
+            indexes: self
+                .indexes
+                .iter()
+                .map(|idx| idx.to_proto())
+                .collect::<Result<_>>()?,
+            policies: self
+                .policies
+                .iter()
+                .map(|p| p.to_proto())
+                .collect::<Result<_>>()?,
         })
     }
 
     fn from_proto(proto: Self::ProtoType) -> Result<Self> {
-        Ok(Self {
-            columns: proto
-                .columns
-                .into_iter()
-                .map(ProtoConv::from_proto)
-                .collect::<Result<_>>()?,
-        })
+        let columns = proto
+            .columns
+            .into_iter()
+            .map(ProtoConv::from_proto)
+            .collect::<Result<_>>()?;
+
+        let mut ent = if proto.column_ids.is_empty() {
+            Self::new(columns)
+        } else {
+            Self {
+                columns,
+                column_ids: proto.column_ids.into_iter().map(|id| id as usize).collect(),
+                column_defaults: proto
+                    .column_defaults
+                    .into_iter()
+                    .map(ProtoConv::from_proto)
+                    .collect::<Result<_>>()?,
+                next_column_id: proto.next_column_id as usize,
+                checks: Vec::new(),
+                primary_key: Vec::new(),
+                indexes: Vec::new(),
+                policies: Vec::new(),
+            }
+        };
+        ent.checks = proto
+            .checks
+            .into_iter()
+            .map(ProtoConv::from_proto)
+            .collect::<Result<_>>()?;
+        ent.primary_key = proto
+            .primary_key
+            .into_iter()
+            .map(|id| id as usize)
+            .collect();
+        ent.indexes = proto
+            .indexes
+            .into_iter()
+            .map(ProtoConv::from_proto)
+            .collect::<Result<_>>()?;
+        ent.policies = proto
+            .policies
+            .into_iter()
+            .map(ProtoConv::from_proto)
+            .collect::<Result<_>>()?;
+
+        Ok(ent)
     }
 }
 
@@ -310,6 +613,17 @@ impl ProtoConv for ViewEntry {
     }
 }
 
+/// A macro defined in SQL.
+///
+/// The body is stored as a string and parsed each time the macro is called,
+/// with parameters substituted for the call's arguments during resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroEntry {
+    pub parameters: Vec<String>,
+    /// An expression for scalar macros, or a query for table macros.
+    pub body_sql: String,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct SchemaEntry {}
 
@@ -335,6 +649,8 @@ impl CatalogEntry {
             CatalogEntryInner::AggregateFunction(_) => CatalogEntryType::AggregateFunction,
             CatalogEntryInner::TableFunction(_) => CatalogEntryType::TableFunction,
             CatalogEntryInner::CopyToFunction(_) => CatalogEntryType::CopyToFunction,
+            CatalogEntryInner::ScalarMacro(_) => CatalogEntryType::ScalarMacro,
+            CatalogEntryInner::TableMacro(_) => CatalogEntryType::TableMacro,
         }
     }
 
@@ -379,4 +695,11 @@ impl CatalogEntry {
             _ => Err(RayexecError::new("Entry not a copy to function")),
         }
     }
+
+    pub fn try_as_macro_entry(&self) -> Result<&MacroEntry> {
+        match &self.entry {
+            CatalogEntryInner::ScalarMacro(ent) | CatalogEntryInner::TableMacro(ent) => Ok(ent),
+            _ => Err(RayexecError::new("Entry not a macro")),
+        }
+    }
 }
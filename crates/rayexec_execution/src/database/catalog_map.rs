@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{ErrorKind, RayexecError, Result};
 use scc::ebr::Guard;
 
 use super::catalog::CatalogTx;
@@ -33,6 +33,28 @@ impl CatalogMap {
         Ok(())
     }
 
+    /// Replace an entry with a new version of it.
+    ///
+    /// Errors if the current entry isn't `old`, e.g. if it was concurrently
+    /// altered or dropped. Existing references to the old entry remain valid.
+    pub fn replace_entry(
+        &self,
+        _tx: &CatalogTx,
+        old: &Arc<CatalogEntry>,
+        new: Arc<CatalogEntry>,
+    ) -> Result<()> {
+        match self.entries.entry(old.name.clone()) {
+            scc::hash_index::Entry::Occupied(ent) if Arc::ptr_eq(ent.get(), old) => {
+                ent.update(new);
+                Ok(())
+            }
+            _ => Err(
+                RayexecError::new(format!("Entry '{}' was concurrently modified", old.name))
+                    .with_kind(ErrorKind::SerializationFailure),
+            ),
+        }
+    }
+
     pub fn get_entry(&self, _tx: &CatalogTx, name: &str) -> Result<Option<Arc<CatalogEntry>>> {
         let guard = Guard::new();
         let ent = self.entries.peek(name, &guard).cloned();
@@ -3,6 +3,7 @@ use rayexec_error::{RayexecError, Result};
 use rayexec_proto::ProtoConv;
 
 use crate::arrays::field::Field;
+use crate::database::catalog_entry::CheckConstraint;
 use crate::functions::aggregate::AggregateFunction;
 use crate::functions::copy::CopyToFunction;
 use crate::functions::scalar::ScalarFunction;
@@ -51,6 +52,9 @@ impl ProtoConv for OnConflict {
 pub struct CreateTableInfo {
     pub name: String,
     pub columns: Vec<Field>,
+    pub checks: Vec<CheckConstraint>,
+    /// Indices of the columns making up the primary key.
+    pub primary_key: Vec<usize>,
     pub on_conflict: OnConflict,
 }
 
@@ -65,6 +69,12 @@ impl ProtoConv for CreateTableInfo {
                 .iter()
                 .map(|f| f.to_proto())
                 .collect::<Result<Vec<_>>>()?,
+            checks: self
+                .checks
+                .iter()
+                .map(|c| c.to_proto())
+                .collect::<Result<Vec<_>>>()?,
+            primary_key: self.primary_key.iter().map(|&idx| idx as u64).collect(),
             on_conflict: self.on_conflict.to_proto()? as i32,
         })
     }
@@ -78,6 +88,16 @@ impl ProtoConv for CreateTableInfo {
                 .into_iter()
                 .map(Field::from_proto)
                 .collect::<Result<Vec<_>>>()?,
+            checks: proto
+                .checks
+                .into_iter()
+                .map(CheckConstraint::from_proto)
+                .collect::<Result<Vec<_>>>()?,
+            primary_key: proto
+                .primary_key
+                .into_iter()
+                .map(|idx| idx as usize)
+                .collect(),
         })
     }
 }
@@ -146,3 +166,11 @@ pub struct CreateCopyToFunctionInfo {
     pub implementation: Box<dyn CopyToFunction>,
     pub on_conflict: OnConflict,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateMacroInfo {
+    pub name: String,
+    pub parameters: Vec<String>,
+    pub body_sql: String,
+    pub on_conflict: OnConflict,
+}
@@ -1,8 +1,10 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{ErrorKind, RayexecError, Result};
 use scc::ebr::Guard;
 
+use super::alter::AlterTableInfo;
 use super::catalog::CatalogTx;
 use super::catalog_entry::{
     AggregateFunctionEntry,
@@ -10,6 +12,7 @@ use super::catalog_entry::{
     CatalogEntryInner,
     CatalogEntryType,
     CopyToFunctionEntry,
+    MacroEntry,
     ScalarFunctionEntry,
     SchemaEntry,
     TableEntry,
@@ -20,6 +23,7 @@ use super::catalog_map::CatalogMap;
 use super::create::{
     CreateAggregateFunctionInfo,
     CreateCopyToFunctionInfo,
+    CreateMacroInfo,
     CreateScalarFunctionInfo,
     CreateSchemaInfo,
     CreateTableFunctionInfo,
@@ -27,6 +31,7 @@ use super::create::{
     CreateViewInfo,
 };
 use super::drop::{DropInfo, DropObject};
+use super::suggest::similar_names;
 use crate::database::create::OnConflict;
 
 // Using `scc` package for concurrent datastructures.
@@ -64,9 +69,21 @@ use crate::database::create::OnConflict;
 #[derive(Debug, Default)]
 pub struct MemoryCatalog {
     schemas: scc::HashIndex<String, Arc<MemorySchema>>,
+    /// Incremented on every change to the catalog, shared with all schemas in
+    /// the catalog.
+    version: Arc<AtomicU64>,
 }
 
 impl MemoryCatalog {
+    /// Get the current version of the catalog.
+    ///
+    /// The version changes whenever a schema or entry in the catalog is
+    /// created, altered, or dropped. Plans bound against one version may not
+    /// be valid against another.
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
     pub fn get_schema(&self, _tx: &CatalogTx, name: &str) -> Result<Option<Arc<MemorySchema>>> {
         let guard = Guard::new();
         Ok(self.schemas.peek(name, &guard).cloned())
@@ -88,6 +105,7 @@ impl MemoryCatalog {
             table_functions: CatalogMap::default(),
             functions: CatalogMap::default(),
             copy_to_functions: CatalogMap::default(),
+            version: self.version.clone(),
         });
 
         use scc::hash_index::Entry;
@@ -95,6 +113,7 @@ impl MemoryCatalog {
         match (self.schemas.entry(create.name.clone()), create.on_conflict) {
             (Entry::Vacant(ent), _) => {
                 ent.insert_entry(schema.clone());
+                self.version.fetch_add(1, Ordering::AcqRel);
                 Ok(schema)
             }
             (Entry::Occupied(ent), OnConflict::Ignore) => {
@@ -104,12 +123,14 @@ impl MemoryCatalog {
             (Entry::Occupied(ent), OnConflict::Replace) => {
                 // TODO: Drop then replace.
                 ent.update(schema.clone());
+                self.version.fetch_add(1, Ordering::AcqRel);
                 Ok(schema)
             }
             (Entry::Occupied(_), OnConflict::Error) => Err(RayexecError::new(format!(
                 "Duplicate schema name: '{}'",
                 create.name,
-            ))),
+            ))
+            .with_kind(ErrorKind::AlreadyExists)),
         }
     }
 
@@ -121,19 +142,20 @@ impl MemoryCatalog {
 
             // TODO: Schemas should be implemented as a CatalogMap.
             if !self.schemas.remove(&drop.schema) && !drop.if_exists {
-                return Err(RayexecError::new(format!(
-                    "Missing schema: {}",
-                    drop.schema
-                )));
+                return Err(
+                    RayexecError::new(format!("Missing schema: {}", drop.schema))
+                        .with_kind(ErrorKind::SchemaNotFound),
+                );
             }
+            self.version.fetch_add(1, Ordering::AcqRel);
 
             return Ok(());
         }
 
-        let schema = self
-            .schemas
-            .get(&drop.schema)
-            .ok_or_else(|| RayexecError::new(format!("Missing schema: {}", drop.schema)))?;
+        let schema = self.schemas.get(&drop.schema).ok_or_else(|| {
+            RayexecError::new(format!("Missing schema: {}", drop.schema))
+                .with_kind(ErrorKind::SchemaNotFound)
+        })?;
 
         schema.drop_entry(tx, drop)?;
 
@@ -164,6 +186,8 @@ pub struct MemorySchema {
     functions: CatalogMap,
     /// All functions implementing COPY TO for a fomat in the schema.
     copy_to_functions: CatalogMap,
+    /// Version of the parent catalog.
+    version: Arc<AtomicU64>,
 }
 
 impl MemorySchema {
@@ -171,6 +195,10 @@ impl MemorySchema {
         &self.schema
     }
 
+    fn bump_version(&self) {
+        self.version.fetch_add(1, Ordering::AcqRel);
+    }
+
     pub fn create_table(
         &self,
         tx: &CatalogTx,
@@ -180,12 +208,91 @@ impl MemorySchema {
             oid: 0,
             name: create.name.clone(),
             entry: CatalogEntryInner::Table(TableEntry {
-                columns: create.columns.clone(),
+                checks: create.checks.clone(),
+                primary_key: create.primary_key.clone(),
+                ..TableEntry::new(create.columns.clone())
             }),
             child: None,
         };
 
-        Self::create_entry(tx, &self.tables, table, create.on_conflict)
+        self.create_entry(tx, &self.tables, table, create.on_conflict)
+    }
+
+    /// Create a table from an existing table entry, keeping its column
+    /// layout.
+    pub(crate) fn create_table_entry(
+        &self,
+        tx: &CatalogTx,
+        name: &str,
+        table: TableEntry,
+    ) -> Result<Arc<CatalogEntry>> {
+        let table = CatalogEntry {
+            oid: 0,
+            name: name.to_string(),
+            entry: CatalogEntryInner::Table(table),
+            child: None,
+        };
+
+        self.create_entry(tx, &self.tables, table, OnConflict::Error)
+    }
+
+    /// Alter a table, replacing its entry.
+    ///
+    /// Returns the old and new entries, or None if the table doesn't exist
+    /// with IF EXISTS, or if the alter doesn't change anything. Queries
+    /// already holding the old entry continue to see the table as it was.
+    pub fn alter_table(
+        &self,
+        tx: &CatalogTx,
+        alter: &AlterTableInfo,
+        if_exists: bool,
+    ) -> Result<Option<(Arc<CatalogEntry>, Arc<CatalogEntry>)>> {
+        let old = match self.tables.get_entry(tx, &alter.name)? {
+            Some(ent) => ent,
+            None if if_exists => return Ok(None),
+            None => {
+                return Err(RayexecError::new(format!("Missing table '{}'", alter.name))
+                    .with_kind(ErrorKind::TableNotFound))
+            }
+        };
+
+        let table = match &old.entry {
+            CatalogEntryInner::Table(table) => table,
+            _ => {
+                return Err(RayexecError::new(format!(
+                    "'{}' is not a table",
+                    alter.name
+                )))
+            }
+        };
+
+        let table = match alter.operation.apply(table)? {
+            Some(table) => table,
+            None => return Ok(None),
+        };
+
+        let new = Arc::new(CatalogEntry {
+            oid: old.oid,
+            name: old.name.clone(),
+            entry: CatalogEntryInner::Table(table),
+            child: old.child.clone(),
+        });
+        self.tables.replace_entry(tx, &old, new.clone())?;
+        self.bump_version();
+
+        Ok(Some((old, new)))
+    }
+
+    /// Restore a previous version of an entry after a failed alter.
+    pub fn revert_alter(
+        &self,
+        tx: &CatalogTx,
+        old: &Arc<CatalogEntry>,
+        new: &Arc<CatalogEntry>,
+    ) -> Result<()> {
+        self.tables.replace_entry(tx, new, old.clone())?;
+        self.bump_version();
+        Ok(())
     }
 
     pub fn create_view(
@@ -203,7 +310,7 @@ impl MemorySchema {
             child: None,
         };
 
-        Self::create_entry(tx, &self.tables, view, create.on_conflict)
+        self.create_entry(tx, &self.tables, view, create.on_conflict)
     }
 
     pub fn create_scalar_function(
@@ -220,7 +327,7 @@ impl MemorySchema {
             child: None,
         };
 
-        Self::create_entry(tx, &self.functions, ent, create.on_conflict)
+        self.create_entry(tx, &self.functions, ent, create.on_conflict)
     }
 
     pub fn create_aggregate_function(
@@ -237,7 +344,7 @@ impl MemorySchema {
             child: None,
         };
 
-        Self::create_entry(tx, &self.functions, ent, create.on_conflict)
+        self.create_entry(tx, &self.functions, ent, create.on_conflict)
     }
 
     pub fn create_table_function(
@@ -254,7 +361,7 @@ impl MemorySchema {
             child: None,
         };
 
-        Self::create_entry(tx, &self.table_functions, ent, create.on_conflict)
+        self.create_entry(tx, &self.table_functions, ent, create.on_conflict)
     }
 
     pub fn create_copy_to_function(
@@ -272,12 +379,55 @@ impl MemorySchema {
             child: None,
         };
 
-        Self::create_entry(tx, &self.copy_to_functions, ent, create.on_conflict)
+        self.create_entry(tx, &self.copy_to_functions, ent, create.on_conflict)
+    }
+
+    /// Create a scalar macro.
+    ///
+    /// Scalar macros share a namespace with scalar and aggregate functions.
+    pub fn create_scalar_macro(
+        &self,
+        tx: &CatalogTx,
+        create: &CreateMacroInfo,
+    ) -> Result<Arc<CatalogEntry>> {
+        let ent = CatalogEntry {
+            oid: 0,
+            name: create.name.clone(),
+            entry: CatalogEntryInner::ScalarMacro(MacroEntry {
+                parameters: create.parameters.clone(),
+                body_sql: create.body_sql.clone(),
+            }),
+            child: None,
+        };
+
+        self.create_entry(tx, &self.functions, ent, create.on_conflict)
+    }
+
+    /// Create a table macro.
+    ///
+    /// Table macros share a namespace with table functions.
+    pub fn create_table_macro(
+        &self,
+        tx: &CatalogTx,
+        create: &CreateMacroInfo,
+    ) -> Result<Arc<CatalogEntry>> {
+        let ent = CatalogEntry {
+            oid: 0,
+            name: create.name.clone(),
+            entry: CatalogEntryInner::TableMacro(MacroEntry {
+                parameters: create.parameters.clone(),
+                body_sql: create.body_sql.clone(),
+            }),
+            child: None,
+        };
+
+        self.create_entry(tx, &self.table_functions, ent, create.on_conflict)
     }
 
     /// Internal helper for inserting entries into the schema while obeying
     /// conflict rules.
     fn create_entry(
+        &self,
         tx: &CatalogTx,
         map: &CatalogMap,
         entry: CatalogEntry,
@@ -290,20 +440,23 @@ impl MemorySchema {
                 // Return existing entry.
                 return Ok(ent.clone());
             }
-            (OnConflict::Replace, _) => {
-                // TODO: Drop
+            (OnConflict::Replace, existing) => {
+                if let Some(existing) = existing {
+                    map.drop_entry(tx, &existing)?;
+                }
                 map.create_entry(tx, entry)?;
             }
             (OnConflict::Error, Some(_)) => {
-                return Err(RayexecError::new(format!(
-                    "Duplicate entry: {}",
-                    entry.name
-                )))
+                return Err(
+                    RayexecError::new(format!("Duplicate entry: {}", entry.name))
+                        .with_kind(ErrorKind::AlreadyExists),
+                )
             }
             (OnConflict::Error, None) | (OnConflict::Ignore, None) => {
                 map.create_entry(tx, entry)?;
             }
         }
+        self.bump_version();
 
         let ent = map
             .get_entry(tx, &name)?
@@ -361,6 +514,30 @@ impl MemorySchema {
         Ok(ent)
     }
 
+    pub fn get_scalar_macro(
+        &self,
+        tx: &CatalogTx,
+        name: &str,
+    ) -> Result<Option<Arc<CatalogEntry>>> {
+        let ent = self.functions.get_entry(tx, name)?;
+        let ent = ent.and_then(|ent| match &ent.entry {
+            CatalogEntryInner::ScalarMacro(_) => Some(ent),
+            _ => None,
+        });
+
+        Ok(ent)
+    }
+
+    pub fn get_table_macro(&self, tx: &CatalogTx, name: &str) -> Result<Option<Arc<CatalogEntry>>> {
+        let ent = self.table_functions.get_entry(tx, name)?;
+        let ent = ent.and_then(|ent| match &ent.entry {
+            CatalogEntryInner::TableMacro(_) => Some(ent),
+            _ => None,
+        });
+
+        Ok(ent)
+    }
+
     pub fn get_copy_to_function(
         &self,
         tx: &CatalogTx,
@@ -402,16 +579,17 @@ impl MemorySchema {
                 Err(RayexecError::new("Dropping functions not yet supported"))
             }
             DropObject::Table(name) => {
-                Self::drop_entry_inner(tx, &self.tables, name, drop.if_exists, drop.cascade)
+                self.drop_entry_inner(tx, &self.tables, name, drop.if_exists, drop.cascade)
             }
             DropObject::View(name) => {
-                Self::drop_entry_inner(tx, &self.tables, name, drop.if_exists, drop.cascade)
+                self.drop_entry_inner(tx, &self.tables, name, drop.if_exists, drop.cascade)
             }
             DropObject::Schema => Err(RayexecError::new("Cannot drop schema from inside schema")),
         }
     }
 
     fn drop_entry_inner(
+        &self,
         tx: &CatalogTx,
         map: &CatalogMap,
         name: &str,
@@ -427,6 +605,7 @@ impl MemorySchema {
         match (ent, if_exists) {
             (Some(ent), _) => {
                 map.drop_entry(tx, ent.as_ref())?;
+                self.bump_version();
                 Ok(())
             }
             (None, true) => Ok(()),
@@ -444,77 +623,40 @@ impl MemorySchema {
         Ok(())
     }
 
-    pub fn find_similar_entry(
+    /// Find names of entries similar to `name`, most similar first.
+    pub fn find_similar_entries(
         &self,
         tx: &CatalogTx,
         typs: &[CatalogEntryType],
         name: &str,
-    ) -> Result<Option<SimilarEntry>> {
-        let mut similar: Option<SimilarEntry> = None;
+    ) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        let mut push_name = |_: &String, ent: &Arc<CatalogEntry>| {
+            names.push(ent.name.clone());
+            Ok(())
+        };
 
         for typ in typs {
             match typ {
-                CatalogEntryType::Table => self.tables.for_each_entry(tx, &mut |_, ent| {
-                    SimilarEntry::maybe_update(&mut similar, ent, name);
-                    Ok(())
-                })?,
-                CatalogEntryType::ScalarFunction => {
-                    self.functions.for_each_entry(tx, &mut |_, ent| {
-                        SimilarEntry::maybe_update(&mut similar, ent, name);
-                        Ok(())
-                    })?
+                CatalogEntryType::Table | CatalogEntryType::View => {
+                    self.tables.for_each_entry(tx, &mut push_name)?
                 }
-                CatalogEntryType::AggregateFunction => {
-                    self.functions.for_each_entry(tx, &mut |_, ent| {
-                        SimilarEntry::maybe_update(&mut similar, ent, name);
-                        Ok(())
-                    })?
+                CatalogEntryType::ScalarFunction
+                | CatalogEntryType::AggregateFunction
+                | CatalogEntryType::ScalarMacro => {
+                    self.functions.for_each_entry(tx, &mut push_name)?
                 }
-                CatalogEntryType::TableFunction => {
-                    self.table_functions.for_each_entry(tx, &mut |_, ent| {
-                        SimilarEntry::maybe_update(&mut similar, ent, name);
-                        Ok(())
-                    })?
+                CatalogEntryType::TableFunction | CatalogEntryType::TableMacro => {
+                    self.table_functions.for_each_entry(tx, &mut push_name)?
                 }
                 _ => (),
             }
         }
 
-        Ok(similar)
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct SimilarEntry {
-    pub score: f64,
-    pub entry: Arc<CatalogEntry>,
-}
-
-impl SimilarEntry {
-    /// Maybe updates `current` with a new entry if the new entry scores higher
-    /// in similarity with `name`.
-    fn maybe_update(current: &mut Option<Self>, entry: &Arc<CatalogEntry>, name: &str) {
-        const SIMILARITY_THRESHOLD: f64 = 0.7;
-
-        let score = strsim::jaro(&entry.name, name);
-        if score > SIMILARITY_THRESHOLD {
-            match current {
-                Some(existing) => {
-                    if score > existing.score {
-                        *current = Some(SimilarEntry {
-                            score,
-                            entry: entry.clone(),
-                        })
-                    }
-                }
-                None => {
-                    *current = Some(SimilarEntry {
-                        score,
-                        entry: entry.clone(),
-                    })
-                }
-            }
-        }
+        Ok(similar_names(name, names.iter().map(|name| name.as_str()))
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect())
     }
 }
 
@@ -528,7 +670,7 @@ mod tests {
         let catalog = MemoryCatalog::default();
         let _schema = catalog
             .create_schema(
-                &CatalogTx {},
+                &CatalogTx::new(),
                 &CreateSchemaInfo {
                     name: "test".to_string(),
                     on_conflict: OnConflict::Error,
@@ -542,11 +684,14 @@ mod tests {
     #[test]
     fn similarity_function_name() {
         let catalog = create_test_catalog();
-        let schema = catalog.get_schema(&CatalogTx {}, "test").unwrap().unwrap();
+        let schema = catalog
+            .get_schema(&CatalogTx::new(), "test")
+            .unwrap()
+            .unwrap();
 
         schema
             .create_aggregate_function(
-                &CatalogTx {},
+                &CatalogTx::new(),
                 &CreateAggregateFunctionInfo {
                     name: "sum".to_string(),
                     implementation: Box::new(Sum),
@@ -556,24 +701,107 @@ mod tests {
             .unwrap();
 
         let similar = schema
-            .find_similar_entry(
-                &CatalogTx {},
+            .find_similar_entries(
+                &CatalogTx::new(),
                 &[CatalogEntryType::AggregateFunction],
                 "summ",
             )
-            .unwrap()
             .unwrap();
-        assert_eq!("sum", similar.entry.name);
+        assert_eq!(vec!["sum".to_string()], similar);
 
         let similar = schema
-            .find_similar_entry(&CatalogTx {}, &[CatalogEntryType::AggregateFunction], "sim")
-            .unwrap()
+            .find_similar_entries(
+                &CatalogTx::new(),
+                &[CatalogEntryType::AggregateFunction],
+                "sim",
+            )
             .unwrap();
-        assert_eq!("sum", similar.entry.name);
+        assert_eq!(vec!["sum".to_string()], similar);
 
         let similar = schema
-            .find_similar_entry(&CatalogTx {}, &[CatalogEntryType::AggregateFunction], "ham")
+            .find_similar_entries(
+                &CatalogTx::new(),
+                &[CatalogEntryType::AggregateFunction],
+                "ham",
+            )
+            .unwrap();
+        assert!(similar.is_empty());
+    }
+
+    #[test]
+    fn replace_existing_function() {
+        let catalog = create_test_catalog();
+        let schema = catalog
+            .get_schema(&CatalogTx::new(), "test")
+            .unwrap()
             .unwrap();
-        assert_eq!(None, similar);
+
+        let create = |on_conflict| {
+            schema.create_aggregate_function(
+                &CatalogTx::new(),
+                &CreateAggregateFunctionInfo {
+                    name: "sum".to_string(),
+                    implementation: Box::new(Sum),
+                    on_conflict,
+                },
+            )
+        };
+
+        create(OnConflict::Error).unwrap();
+        create(OnConflict::Error).unwrap_err();
+        create(OnConflict::Replace).unwrap();
+        create(OnConflict::Ignore).unwrap();
+    }
+
+    #[test]
+    fn macros_share_function_namespace() {
+        let catalog = create_test_catalog();
+        let schema = catalog
+            .get_schema(&CatalogTx::new(), "test")
+            .unwrap()
+            .unwrap();
+
+        schema
+            .create_aggregate_function(
+                &CatalogTx::new(),
+                &CreateAggregateFunctionInfo {
+                    name: "sum".to_string(),
+                    implementation: Box::new(Sum),
+                    on_conflict: OnConflict::Error,
+                },
+            )
+            .unwrap();
+
+        let create = |name: &str| CreateMacroInfo {
+            name: name.to_string(),
+            parameters: vec!["a".to_string()],
+            body_sql: "a + 1".to_string(),
+            on_conflict: OnConflict::Error,
+        };
+
+        schema
+            .create_scalar_macro(&CatalogTx::new(), &create("sum"))
+            .unwrap_err();
+        schema
+            .create_scalar_macro(&CatalogTx::new(), &create("add_one"))
+            .unwrap();
+        // Table macros live alongside table functions.
+        schema
+            .create_table_macro(&CatalogTx::new(), &create("add_one"))
+            .unwrap();
+
+        let ent = schema
+            .get_scalar_macro(&CatalogTx::new(), "add_one")
+            .unwrap()
+            .unwrap();
+        assert_eq!(CatalogEntryType::ScalarMacro, ent.entry_type());
+        assert!(schema
+            .get_scalar_function(&CatalogTx::new(), "add_one")
+            .unwrap()
+            .is_none());
+        assert!(schema
+            .get_scalar_macro(&CatalogTx::new(), "sum")
+            .unwrap()
+            .is_none());
     }
 }
@@ -1,9 +1,12 @@
+pub mod alter;
 pub mod builtin_views;
 pub mod catalog;
 pub mod catalog_entry;
 pub mod create;
 pub mod drop;
 pub mod memory_catalog;
+pub mod secrets;
+pub mod suggest;
 pub mod system;
 
 mod catalog_map;
@@ -15,10 +18,16 @@ use std::sync::Arc;
 use catalog::CatalogTx;
 use create::{CreateSchemaInfo, OnConflict};
 use memory_catalog::MemoryCatalog;
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{ErrorKind, RayexecError, Result};
 use rayexec_proto::ProtoConv;
+use secrets::SecretStore;
+use uuid::Uuid;
 
 use crate::arrays::scalar::OwnedScalarValue;
+use crate::engine::query_log::QueryLogVisibility;
+use crate::runtime::memory::MemoryTracker;
+use crate::runtime::stall::ScanStall;
+use crate::runtime::Runtime;
 use crate::storage::catalog_storage::CatalogStorage;
 use crate::storage::memory::MemoryTableStorage;
 use crate::storage::table_storage::TableStorage;
@@ -63,6 +72,11 @@ impl ProtoConv for AttachInfo {
 /// An attached database.
 #[derive(Debug, Clone)]
 pub struct Database {
+    /// Unique id for this instance of the database.
+    ///
+    /// Distinguishes databases attached under the same name in different
+    /// sessions (e.g. when looking up cached results).
+    pub id: Uuid,
     /// In-memory catalog for the database.
     pub catalog: Arc<MemoryCatalog>,
     /// Storage for the catalog.
@@ -84,6 +98,23 @@ pub struct Database {
 #[derive(Debug)]
 pub struct DatabaseContext {
     databases: HashMap<String, Database>,
+    /// Secrets created in this session.
+    secrets: SecretStore,
+    /// Transaction statements are currently executing in.
+    ///
+    /// Auto-commit unless an explicit transaction has been started.
+    transaction: CatalogTx,
+    /// Tracker that operators reserve memory from during execution.
+    ///
+    /// Shared with the session's resource group unless the session sets a
+    /// per-query memory limit.
+    memory: Arc<MemoryTracker>,
+    /// Queries in the query log this context can see.
+    query_log_visibility: QueryLogVisibility,
+    /// Stall handling for remote scans planned with this context.
+    ///
+    /// None uses the runtime's defaults.
+    scan_stall: Option<ScanStall>,
 }
 
 impl DatabaseContext {
@@ -99,6 +130,7 @@ impl DatabaseContext {
         databases.insert(
             "system".to_string(),
             Database {
+                id: Uuid::new_v4(),
                 catalog: system_catalog,
                 catalog_storage: None,
                 table_storage: None,
@@ -108,7 +140,7 @@ impl DatabaseContext {
 
         let temp = MemoryCatalog::default();
         temp.create_schema(
-            &CatalogTx {},
+            &CatalogTx::new(),
             &CreateSchemaInfo {
                 name: "temp".to_string(),
                 on_conflict: OnConflict::Error,
@@ -118,6 +150,7 @@ impl DatabaseContext {
         databases.insert(
             "temp".to_string(),
             Database {
+                id: Uuid::new_v4(),
                 catalog: Arc::new(temp),
                 catalog_storage: None,
                 table_storage: Some(Arc::new(MemoryTableStorage::default())),
@@ -125,7 +158,17 @@ impl DatabaseContext {
             },
         );
 
-        Ok(DatabaseContext { databases })
+        Ok(DatabaseContext {
+            databases,
+            secrets: SecretStore::default(),
+            transaction: CatalogTx::new(),
+            memory: Arc::new(MemoryTracker::new("query", None)),
+            query_log_visibility: QueryLogVisibility {
+                session_id: Uuid::new_v4(),
+                all_sessions: false,
+            },
+            scan_stall: None,
+        })
     }
 
     pub fn system_catalog(&self) -> Result<&MemoryCatalog> {
@@ -161,12 +204,62 @@ impl DatabaseContext {
     }
 
     pub fn get_database(&self, name: &str) -> Result<&Database> {
-        self.databases
-            .get(name)
-            .ok_or_else(|| RayexecError::new(format!("Missing catalog '{name}'")))
+        self.databases.get(name).ok_or_else(|| {
+            RayexecError::new(format!("Missing catalog '{name}'"))
+                .with_kind(ErrorKind::CatalogNotFound)
+        })
     }
 
     pub fn iter_databases(&self) -> impl Iterator<Item = (&String, &Database)> {
         self.databases.iter()
     }
+
+    pub fn secrets(&self) -> &SecretStore {
+        &self.secrets
+    }
+
+    pub fn secrets_mut(&mut self) -> &mut SecretStore {
+        &mut self.secrets
+    }
+
+    pub fn transaction(&self) -> &CatalogTx {
+        &self.transaction
+    }
+
+    /// Set the current transaction, returning the previous one.
+    pub fn set_transaction(&mut self, tx: CatalogTx) -> CatalogTx {
+        std::mem::replace(&mut self.transaction, tx)
+    }
+
+    pub fn memory_tracker(&self) -> &Arc<MemoryTracker> {
+        &self.memory
+    }
+
+    pub fn set_memory_tracker(&mut self, memory: Arc<MemoryTracker>) {
+        self.memory = memory;
+    }
+
+    /// Queries in the query log visible to this context.
+    ///
+    /// Only queries executed with this context are visible by default.
+    pub fn query_log_visibility(&self) -> &QueryLogVisibility {
+        &self.query_log_visibility
+    }
+
+    pub fn set_view_all_queries(&mut self, all_sessions: bool) {
+        self.query_log_visibility.all_sessions = all_sessions;
+    }
+
+    pub fn set_scan_stall(&mut self, stall: ScanStall) {
+        self.scan_stall = Some(stall);
+    }
+
+    /// Get the runtime that scans planned with this context should read
+    /// through, applying this context's stall handling.
+    pub fn scan_runtime<R: Runtime>(&self, runtime: &R) -> R {
+        match self.scan_stall {
+            Some(stall) => runtime.with_scan_stall(stall),
+            None => runtime.clone(),
+        }
+    }
 }
@@ -0,0 +1,288 @@
+//! Named credentials for accessing external data sources.
+//!
+//! Secrets are created with `CREATE SECRET`, and referenced by name from
+//! table function arguments, COPY TO options, and ATTACH options using the
+//! `secret_name` option. The secret's values get merged into the options
+//! before they reach the data source, so data sources don't need to know about
+//! secrets at all.
+use std::collections::HashMap;
+use std::fmt;
+
+use rayexec_error::{ErrorKind, RayexecError, Result};
+
+use super::create::OnConflict;
+use crate::arrays::scalar::OwnedScalarValue;
+
+/// Option used to reference a secret by name.
+pub const SECRET_NAME_OPTION: &str = "secret_name";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretType {
+    /// Credentials for S3 (or S3 compatible) object stores.
+    S3,
+    /// Credentials for connecting to Postgres.
+    Postgres,
+    /// Service account credentials for Google Cloud (BigQuery).
+    Gcp,
+}
+
+impl SecretType {
+    pub fn from_name(name: &str) -> Result<Self> {
+        Ok(match name {
+            "s3" => Self::S3,
+            "postgres" => Self::Postgres,
+            "gcp" => Self::Gcp,
+            other => return Err(RayexecError::new(format!("Unknown secret type: {other}"))),
+        })
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::S3 => "s3",
+            Self::Postgres => "postgres",
+            Self::Gcp => "gcp",
+        }
+    }
+
+    /// Options that must be provided when creating a secret of this type.
+    ///
+    /// These are also the only options accepted.
+    const fn options(&self) -> &'static [&'static str] {
+        match self {
+            Self::S3 => &["key_id", "secret", "region"],
+            Self::Postgres => &["connection_string"],
+            Self::Gcp => &["service_account_key"],
+        }
+    }
+}
+
+impl fmt::Display for SecretType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A named set of credentials.
+#[derive(Clone, PartialEq)]
+pub struct Secret {
+    pub secret_type: SecretType,
+    pub options: HashMap<String, OwnedScalarValue>,
+}
+
+impl Secret {
+    /// Create a new secret, checking that the options are valid for the
+    /// secret type.
+    pub fn try_new(
+        secret_type: SecretType,
+        options: HashMap<String, OwnedScalarValue>,
+    ) -> Result<Self> {
+        let expected = secret_type.options();
+
+        for key in options.keys() {
+            if !expected.contains(&key.as_str()) {
+                return Err(RayexecError::new(format!(
+                    "Unexpected option '{key}' for secret of type {secret_type}"
+                )));
+            }
+        }
+
+        for key in expected {
+            if !options.contains_key(*key) {
+                return Err(RayexecError::new(format!(
+                    "Missing option '{key}' for secret of type {secret_type}"
+                )));
+            }
+        }
+
+        Ok(Secret {
+            secret_type,
+            options,
+        })
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the actual values.
+        let mut keys: Vec<_> = self.options.keys().collect();
+        keys.sort();
+
+        f.debug_struct("Secret")
+            .field("secret_type", &self.secret_type)
+            .field("options", &keys)
+            .finish()
+    }
+}
+
+/// Secrets visible to a session.
+#[derive(Debug, Default)]
+pub struct SecretStore {
+    secrets: HashMap<String, Secret>,
+}
+
+impl SecretStore {
+    pub fn create_secret(
+        &mut self,
+        name: impl Into<String>,
+        secret: Secret,
+        on_conflict: OnConflict,
+    ) -> Result<()> {
+        let name = name.into();
+        match (self.secrets.contains_key(&name), on_conflict) {
+            (true, OnConflict::Error) => Err(RayexecError::new(format!(
+                "Secret with name '{name}' already exists"
+            ))
+            .with_kind(ErrorKind::AlreadyExists)),
+            (true, OnConflict::Ignore) => Ok(()),
+            _ => {
+                self.secrets.insert(name, secret);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn drop_secret(&mut self, name: &str, if_exists: bool) -> Result<()> {
+        if self.secrets.remove(name).is_none() && !if_exists {
+            return Err(RayexecError::new(format!(
+                "Secret with name '{name}' doesn't exist"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn get_secret(&self, name: &str) -> Option<&Secret> {
+        self.secrets.get(name)
+    }
+
+    /// Replace a `secret_name` option with the values from the referenced
+    /// secret.
+    ///
+    /// Options provided explicitly take precedence over values from the
+    /// secret. Does nothing if `secret_name` isn't in the options.
+    pub fn apply_secret(&self, options: &mut HashMap<String, OwnedScalarValue>) -> Result<()> {
+        let name = match options.remove(SECRET_NAME_OPTION) {
+            Some(name) => name.try_into_string()?,
+            None => return Ok(()),
+        };
+
+        let secret = self
+            .get_secret(&name)
+            .ok_or_else(|| RayexecError::new(format!("Missing secret '{name}'")))?;
+
+        for (key, val) in &secret.options {
+            if !options.contains_key(key) {
+                options.insert(key.clone(), val.clone());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s3_secret(region: &str) -> Secret {
+        Secret::try_new(
+            SecretType::S3,
+            [
+                ("key_id".to_string(), "key".into()),
+                ("secret".to_string(), "shh".into()),
+                ("region".to_string(), region.to_string().into()),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn missing_option() {
+        Secret::try_new(
+            SecretType::S3,
+            [("key_id".to_string(), "key".into())].into_iter().collect(),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn unexpected_option() {
+        Secret::try_new(
+            SecretType::Postgres,
+            [
+                ("connection_string".to_string(), "postgres://".into()),
+                ("region".to_string(), "us-east-1".into()),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn secret_type_roundtrip() {
+        for typ in [SecretType::S3, SecretType::Postgres, SecretType::Gcp] {
+            assert_eq!(typ, SecretType::from_name(typ.as_str()).unwrap());
+        }
+    }
+
+    #[test]
+    fn apply_explicit_options_take_precedence() {
+        let mut store = SecretStore::default();
+        store
+            .create_secret("my_s3", s3_secret("us-east-1"), OnConflict::Error)
+            .unwrap();
+
+        let mut options: HashMap<String, OwnedScalarValue> = [
+            (SECRET_NAME_OPTION.to_string(), "my_s3".into()),
+            ("region".to_string(), "eu-west-1".into()),
+        ]
+        .into_iter()
+        .collect();
+
+        store.apply_secret(&mut options).unwrap();
+
+        assert!(!options.contains_key(SECRET_NAME_OPTION));
+        assert_eq!(OwnedScalarValue::from("key"), options["key_id"]);
+        assert_eq!(OwnedScalarValue::from("eu-west-1"), options["region"]);
+    }
+
+    #[test]
+    fn apply_missing_secret() {
+        let store = SecretStore::default();
+        let mut options: HashMap<String, OwnedScalarValue> =
+            [(SECRET_NAME_OPTION.to_string(), "missing".into())]
+                .into_iter()
+                .collect();
+
+        store.apply_secret(&mut options).unwrap_err();
+    }
+
+    #[test]
+    fn create_conflict() {
+        let mut store = SecretStore::default();
+        store
+            .create_secret("my_s3", s3_secret("us-east-1"), OnConflict::Error)
+            .unwrap();
+        store
+            .create_secret("my_s3", s3_secret("eu-west-1"), OnConflict::Error)
+            .unwrap_err();
+
+        store
+            .create_secret("my_s3", s3_secret("eu-west-1"), OnConflict::Ignore)
+            .unwrap();
+        assert_eq!(
+            OwnedScalarValue::from("us-east-1"),
+            store.get_secret("my_s3").unwrap().options["region"]
+        );
+
+        store
+            .create_secret("my_s3", s3_secret("eu-west-1"), OnConflict::Replace)
+            .unwrap();
+        assert_eq!(
+            OwnedScalarValue::from("eu-west-1"),
+            store.get_secret("my_s3").unwrap().options["region"]
+        );
+    }
+}
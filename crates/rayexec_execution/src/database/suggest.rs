@@ -0,0 +1,84 @@
+//! Suggestions for names that failed to resolve.
+
+/// Max number of suggestions to include in an error message.
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// Find candidates that are within a small edit distance of `name`.
+///
+/// The allowed distance scales with the length of `name`. Comparisons are
+/// case insensitive. Returns at most `MAX_SUGGESTIONS` candidates, closest
+/// first.
+pub fn similar_names<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let name = name.to_lowercase();
+    let max_distance = name.chars().count().div_ceil(3);
+
+    let mut similar: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let distance = strsim::levenshtein(&name, &candidate.to_lowercase());
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        .collect();
+
+    similar.sort_unstable();
+    similar.dedup_by(|a, b| a.1 == b.1);
+
+    similar
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Append a "did you mean" hint to an error message.
+///
+/// Returns the message unchanged if there are no suggestions.
+pub fn with_suggestions(msg: impl Into<String>, suggestions: &[impl AsRef<str>]) -> String {
+    let mut msg = msg.into();
+
+    let quoted: Vec<_> = suggestions
+        .iter()
+        .map(|s| format!("'{}'", s.as_ref()))
+        .collect();
+
+    match quoted.as_slice() {
+        [] => (),
+        [only] => msg.push_str(&format!(", did you mean {only}?")),
+        [rest @ .., last] => {
+            msg.push_str(&format!(", did you mean {} or {last}?", rest.join(", ")))
+        }
+    }
+
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn similar_names_closest_first() {
+        let candidates = ["sum", "sin", "summary", "cos", "sum"];
+
+        assert_eq!(vec!["sum"], similar_names("summ", candidates));
+        assert_eq!(vec!["sum"], similar_names("SUMM", candidates));
+        assert_eq!(vec!["sin", "sum"], similar_names("sim", candidates));
+        assert_eq!(Vec::<&str>::new(), similar_names("coalesce", candidates));
+    }
+
+    #[test]
+    fn suggestion_messages() {
+        assert_eq!("missing", with_suggestions("missing", &[] as &[&str]));
+        assert_eq!(
+            "missing, did you mean 'a'?",
+            with_suggestions("missing", &["a"])
+        );
+        assert_eq!(
+            "missing, did you mean 'a', 'b' or 'c'?",
+            with_suggestions("missing", &["a", "b", "c"])
+        );
+    }
+}
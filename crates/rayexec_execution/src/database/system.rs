@@ -1,4 +1,4 @@
-use rayexec_error::Result;
+use rayexec_error::{OptionExt, Result};
 
 use super::builtin_views::BUILTIN_VIEWS;
 use super::create::{CreateCopyToFunctionInfo, CreateViewInfo};
@@ -23,7 +23,7 @@ use crate::functions::table::builtin::BUILTIN_TABLE_FUNCTIONS;
 pub fn new_system_catalog(registry: &DataSourceRegistry) -> Result<MemoryCatalog> {
     let catalog = MemoryCatalog::default();
 
-    let tx = &CatalogTx {};
+    let tx = &CatalogTx::new();
 
     let builtin = catalog.create_schema(
         tx,
@@ -41,7 +41,7 @@ pub fn new_system_catalog(registry: &DataSourceRegistry) -> Result<MemoryCatalog
         },
     )?;
 
-    let _pg_catalog = catalog.create_schema(
+    let _information_schema = catalog.create_schema(
         tx,
         &CreateSchemaInfo {
             name: "information_schema".to_string(),
@@ -49,6 +49,14 @@ pub fn new_system_catalog(registry: &DataSourceRegistry) -> Result<MemoryCatalog
         },
     )?;
 
+    let _system = catalog.create_schema(
+        tx,
+        &CreateSchemaInfo {
+            name: "system".to_string(),
+            on_conflict: OnConflict::Error,
+        },
+    )?;
+
     // Add builtin scalars.
     for func in BUILTIN_SCALAR_FUNCTIONS.iter() {
         builtin.create_scalar_function(
@@ -120,7 +128,11 @@ pub fn new_system_catalog(registry: &DataSourceRegistry) -> Result<MemoryCatalog
 
     // Add builtin views.
     for view in BUILTIN_VIEWS {
-        builtin.create_view(
+        let schema = catalog
+            .get_schema(tx, view.schema)?
+            .required("schema for builtin view")?;
+
+        schema.create_view(
             tx,
             &CreateViewInfo {
                 name: view.name.to_string(),
@@ -157,6 +169,30 @@ pub fn new_system_catalog(registry: &DataSourceRegistry) -> Result<MemoryCatalog
             }
         }
 
+        let scalar_funcs = datasource.initialize_scalar_functions();
+
+        for func in scalar_funcs {
+            builtin.create_scalar_function(
+                tx,
+                &CreateScalarFunctionInfo {
+                    name: func.name().to_string(),
+                    implementation: func.clone(),
+                    on_conflict: OnConflict::Error,
+                },
+            )?;
+
+            for alias in func.aliases() {
+                builtin.create_scalar_function(
+                    tx,
+                    &CreateScalarFunctionInfo {
+                        name: alias.to_string(),
+                        implementation: func.clone(),
+                        on_conflict: OnConflict::Error,
+                    },
+                )?;
+            }
+        }
+
         let copy_to_funcs = datasource.initialize_copy_to_functions();
 
         for func in copy_to_funcs {
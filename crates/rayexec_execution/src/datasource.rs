@@ -3,11 +3,12 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use futures::future::BoxFuture;
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{ErrorKind, RayexecError, Result};
 use regex::Regex;
 
 use crate::arrays::scalar::OwnedScalarValue;
 use crate::functions::copy::CopyToFunction;
+use crate::functions::scalar::ScalarFunction;
 use crate::functions::table::TableFunction;
 use crate::runtime::Runtime;
 use crate::storage::catalog_storage::CatalogStorage;
@@ -90,6 +91,15 @@ pub trait DataSource: Sync + Send + Debug {
         Vec::new()
     }
 
+    /// Initialize a list of scalar functions that this data source provides.
+    ///
+    /// This allows for extending the function system with functions that
+    /// aren't builtin, e.g. for optional types. These functions are registered
+    /// into the system catalog alongside the builtin scalar functions.
+    fn initialize_scalar_functions(&self) -> Vec<Box<dyn ScalarFunction>> {
+        Vec::new()
+    }
+
     /// Return file handlers that this data souce can handle.
     ///
     /// During binding, these file handlers will be used to determine if there's
@@ -210,9 +220,10 @@ pub fn take_option(
     name: &str,
     options: &mut HashMap<String, OwnedScalarValue>,
 ) -> Result<OwnedScalarValue> {
-    options
-        .remove(name)
-        .ok_or_else(|| RayexecError::new(format!("Missing required option '{name}'")))
+    options.remove(name).ok_or_else(|| {
+        RayexecError::new(format!("Missing required option '{name}'"))
+            .with_kind(ErrorKind::InvalidArgument)
+    })
 }
 
 /// Check that options is empty, erroring if it isn't.
@@ -226,9 +237,10 @@ pub fn check_options_empty(options: &HashMap<String, OwnedScalarValue>) -> Resul
         .collect::<Vec<_>>()
         .join(", ");
 
-    Err(RayexecError::new(format!(
-        "Unexpected extra arguments: {extras}"
-    )))
+    Err(
+        RayexecError::new(format!("Unexpected extra arguments: {extras}"))
+            .with_kind(ErrorKind::InvalidArgument),
+    )
 }
 
 #[derive(Debug)]
@@ -0,0 +1,320 @@
+//! Admission control for queries executing on an engine.
+//!
+//! Sessions acquire a permit before spawning a query's pipelines, and the
+//! permit is held until the query completes. Once the max number of concurrent
+//! queries is reached, queries wait in a queue and are admitted in the order
+//! they arrived.
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+
+use parking_lot::Mutex;
+use rayexec_error::{ErrorKind, RayexecError, Result};
+
+/// Limits the number of queries executing concurrently on an engine.
+///
+/// Shared between all sessions of an engine.
+#[derive(Debug, Default)]
+pub struct AdmissionControl {
+    state: Mutex<AdmissionState>,
+}
+
+#[derive(Debug, Default)]
+struct AdmissionState {
+    /// Max number of queries that can execute at once. No limit if None.
+    max_concurrent: Option<usize>,
+    /// Max number of queries that can wait to be admitted. No limit if None.
+    max_queued: Option<usize>,
+    /// Number of queries currently holding a permit.
+    running: usize,
+    /// Queries waiting to be admitted, in arrival order.
+    queue: VecDeque<QueuedQuery>,
+    /// Id to assign to the next queued query.
+    next_id: u64,
+}
+
+#[derive(Debug)]
+struct QueuedQuery {
+    id: u64,
+    waker: Option<Waker>,
+}
+
+impl AdmissionState {
+    fn has_capacity(&self) -> bool {
+        match self.max_concurrent {
+            Some(max) => self.running < max,
+            None => true,
+        }
+    }
+
+    /// Wake the query at the front of the queue if it can be admitted.
+    fn wake_next(&mut self) {
+        if !self.has_capacity() {
+            return;
+        }
+        if let Some(waker) = self.queue.front_mut().and_then(|q| q.waker.take()) {
+            waker.wake();
+        }
+    }
+}
+
+impl AdmissionControl {
+    /// Set the max number of queries that can execute at once.
+    ///
+    /// Zero removes the limit.
+    pub fn set_max_concurrent_queries(&self, max: usize) {
+        let mut state = self.state.lock();
+        state.max_concurrent = (max != 0).then_some(max);
+        state.wake_next();
+    }
+
+    /// Set the max number of queries that can be waiting for admission.
+    ///
+    /// Queries that arrive when the queue is full error instead of waiting.
+    /// Zero removes the limit.
+    pub fn set_max_queued_queries(&self, max: usize) {
+        self.state.lock().max_queued = (max != 0).then_some(max);
+    }
+
+    /// Number of queries currently admitted.
+    pub fn num_running(&self) -> usize {
+        self.state.lock().running
+    }
+
+    /// Number of queries waiting to be admitted.
+    pub fn num_queued(&self) -> usize {
+        self.state.lock().queue.len()
+    }
+
+    /// Wait for a query to be admitted.
+    ///
+    /// Errors immediately if the query would need to wait, but the queue is
+    /// full. Dropping the future before it completes removes the query from
+    /// the queue.
+    pub fn admit(self: &Arc<Self>) -> Result<AdmitFuture> {
+        let mut state = self.state.lock();
+
+        if state.queue.is_empty() && state.has_capacity() {
+            state.running += 1;
+            return Ok(AdmitFuture {
+                control: self.clone(),
+                queued: None,
+                permit: Some(AdmissionPermit {
+                    control: self.clone(),
+                }),
+            });
+        }
+
+        if let Some(max) = state.max_queued {
+            if state.queue.len() >= max {
+                return Err(RayexecError::new(format!(
+                    "Too many queries waiting to execute, max queued queries: {max}"
+                ))
+                .with_kind(ErrorKind::ResourceExhausted));
+            }
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+        state.queue.push_back(QueuedQuery { id, waker: None });
+
+        Ok(AdmitFuture {
+            control: self.clone(),
+            queued: Some(id),
+            permit: None,
+        })
+    }
+}
+
+/// Future resolving to a permit once the query has been admitted.
+#[derive(Debug)]
+pub struct AdmitFuture {
+    control: Arc<AdmissionControl>,
+    /// Id of the query in the queue if waiting.
+    queued: Option<u64>,
+    /// Permit if admitted without waiting.
+    permit: Option<AdmissionPermit>,
+}
+
+impl Future for AdmitFuture {
+    type Output = AdmissionPermit;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(permit) = self.permit.take() {
+            return Poll::Ready(permit);
+        }
+
+        let id = self.queued.expect("admit future to be queued if no permit");
+        let mut state = self.control.state.lock();
+
+        let at_front = state.queue.front().map(|q| q.id) == Some(id);
+        if at_front && state.has_capacity() {
+            state.queue.pop_front();
+            state.running += 1;
+            // Multiple slots may have opened up at once.
+            state.wake_next();
+            std::mem::drop(state);
+
+            self.queued = None;
+            return Poll::Ready(AdmissionPermit {
+                control: self.control.clone(),
+            });
+        }
+
+        if let Some(queued) = state.queue.iter_mut().find(|q| q.id == id) {
+            queued.waker = Some(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for AdmitFuture {
+    fn drop(&mut self) {
+        let id = match self.queued {
+            Some(id) => id,
+            None => return,
+        };
+
+        let mut state = self.control.state.lock();
+        if let Some(pos) = state.queue.iter().position(|q| q.id == id) {
+            state.queue.remove(pos);
+            // If we were at the front, the next query may be able to go.
+            state.wake_next();
+        }
+    }
+}
+
+/// Permit for an admitted query. The slot is released on drop.
+#[derive(Debug)]
+pub struct AdmissionPermit {
+    control: Arc<AdmissionControl>,
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        let mut state = self.control.state.lock();
+        state.running -= 1;
+        state.wake_next();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+
+    use super::*;
+    use crate::execution::operators::test_util::TestWakerContext;
+
+    fn poll_admit(fut: Pin<&mut AdmitFuture>, cx: &TestWakerContext) -> Option<AdmissionPermit> {
+        match fut.poll(&mut cx.context()) {
+            Poll::Ready(permit) => Some(permit),
+            Poll::Pending => None,
+        }
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let control = Arc::new(AdmissionControl::default());
+        let cx = TestWakerContext::new();
+
+        let permits: Vec<_> = (0..16)
+            .map(|_| poll_admit(pin!(control.admit().unwrap()), &cx).unwrap())
+            .collect();
+        assert_eq!(16, control.num_running());
+
+        std::mem::drop(permits);
+        assert_eq!(0, control.num_running());
+    }
+
+    #[test]
+    fn queue_in_arrival_order() {
+        let control = Arc::new(AdmissionControl::default());
+        control.set_max_concurrent_queries(1);
+
+        let cx1 = TestWakerContext::new();
+        let permit1 = poll_admit(pin!(control.admit().unwrap()), &cx1).unwrap();
+
+        let cx2 = TestWakerContext::new();
+        let mut fut2 = pin!(control.admit().unwrap());
+        assert!(poll_admit(fut2.as_mut(), &cx2).is_none());
+
+        let cx3 = TestWakerContext::new();
+        let mut fut3 = pin!(control.admit().unwrap());
+        assert!(poll_admit(fut3.as_mut(), &cx3).is_none());
+        assert_eq!(2, control.num_queued());
+
+        // Releasing the permit only wakes the first queued query.
+        std::mem::drop(permit1);
+        assert_eq!(1, cx2.wake_count());
+        assert_eq!(0, cx3.wake_count());
+        assert!(poll_admit(fut3.as_mut(), &cx3).is_none());
+
+        let permit2 = poll_admit(fut2.as_mut(), &cx2).unwrap();
+        assert_eq!(1, control.num_running());
+
+        std::mem::drop(permit2);
+        assert_eq!(1, cx3.wake_count());
+        let _permit3 = poll_admit(fut3.as_mut(), &cx3).unwrap();
+        assert_eq!(0, control.num_queued());
+    }
+
+    #[test]
+    fn dropped_queued_query_wakes_next() {
+        let control = Arc::new(AdmissionControl::default());
+        control.set_max_concurrent_queries(1);
+
+        let cx = TestWakerContext::new();
+        let permit1 = poll_admit(pin!(control.admit().unwrap()), &cx).unwrap();
+
+        let cx2 = TestWakerContext::new();
+        let mut fut2 = Box::pin(control.admit().unwrap());
+        assert!(poll_admit(fut2.as_mut(), &cx2).is_none());
+
+        let cx3 = TestWakerContext::new();
+        let mut fut3 = pin!(control.admit().unwrap());
+        assert!(poll_admit(fut3.as_mut(), &cx3).is_none());
+
+        std::mem::drop(permit1);
+        assert_eq!(1, cx2.wake_count());
+
+        // Woken query goes away without being admitted, next should be woken
+        // instead.
+        std::mem::drop(fut2);
+        assert_eq!(1, cx3.wake_count());
+        assert!(poll_admit(fut3.as_mut(), &cx3).is_some());
+    }
+
+    #[test]
+    fn full_queue_errors() {
+        let control = Arc::new(AdmissionControl::default());
+        control.set_max_concurrent_queries(1);
+        control.set_max_queued_queries(1);
+
+        let cx = TestWakerContext::new();
+        let _permit = poll_admit(pin!(control.admit().unwrap()), &cx).unwrap();
+        let _queued = control.admit().unwrap();
+
+        let err = control.admit().unwrap_err();
+        assert_eq!(ErrorKind::ResourceExhausted, err.kind());
+    }
+
+    #[test]
+    fn raising_limit_admits_queued() {
+        let control = Arc::new(AdmissionControl::default());
+        control.set_max_concurrent_queries(1);
+
+        let cx = TestWakerContext::new();
+        let _permit1 = poll_admit(pin!(control.admit().unwrap()), &cx).unwrap();
+
+        let cx2 = TestWakerContext::new();
+        let mut fut2 = pin!(control.admit().unwrap());
+        assert!(poll_admit(fut2.as_mut(), &cx2).is_none());
+
+        control.set_max_concurrent_queries(0);
+        assert_eq!(1, cx2.wake_count());
+        assert!(poll_admit(fut2.as_mut(), &cx2).is_some());
+    }
+}
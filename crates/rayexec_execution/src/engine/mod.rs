@@ -1,28 +1,60 @@
+pub mod admission;
+#[cfg(feature = "otlp")]
+pub mod otlp;
+pub mod plan_cache;
 pub mod profiler;
+pub mod query_log;
 pub mod result;
+pub mod result_cache;
 pub mod server_state;
 pub mod session;
 
 mod verifier;
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use admission::AdmissionControl;
 use rayexec_error::Result;
 use server_state::ServerState;
 use session::Session;
+use tracing::Dispatch;
 
+use crate::arrays::buffer_pool;
+use crate::config::statements::AllowedStatements;
 use crate::database::memory_catalog::MemoryCatalog;
 use crate::database::system::new_system_catalog;
-use crate::database::DatabaseContext;
+use crate::database::{Database, DatabaseContext};
 use crate::datasource::{DataSourceRegistry, MemoryDataSource};
+use crate::runtime::resource_group::{ResourceGroupConfig, ResourceGroups};
 use crate::runtime::{PipelineExecutor, Runtime};
+use crate::storage::disk::DiskTableStorage;
+
+/// Name of the catalog for tables persisted to the engine's database path.
+pub const PERSISTENT_CATALOG: &str = "main";
 
 #[derive(Debug)]
 pub struct Engine<P: PipelineExecutor, R: Runtime> {
     registry: Arc<DataSourceRegistry>,
     system_catalog: Arc<MemoryCatalog>,
+    /// Database persisted to disk, attached to every session.
+    persistent: Option<Database>,
     executor: P,
     runtime: R,
+    /// Limits the number of queries executing concurrently across sessions.
+    admission: Arc<AdmissionControl>,
+    /// Resource groups sessions can be assigned to.
+    resource_groups: Arc<ResourceGroups>,
+    /// Statements new sessions are allowed to execute.
+    allowed_statements: AllowedStatements,
+    /// Subscriber to create query spans with.
+    ///
+    /// Spans are created with the global default subscriber if not set.
+    span_subscriber: Option<Dispatch>,
+    /// Exporter backing the span subscriber, flushed once the engine is
+    /// dropped.
+    #[cfg(feature = "otlp")]
+    otlp_exporter: Option<otlp::OtlpExporter>,
 }
 
 impl<P, R> Engine<P, R>
@@ -46,17 +78,140 @@ where
         Ok(Engine {
             registry: Arc::new(registry),
             system_catalog,
+            persistent: None,
             executor,
             runtime,
+            admission: Arc::new(AdmissionControl::default()),
+            resource_groups: Arc::new(ResourceGroups::default()),
+            allowed_statements: AllowedStatements::all(),
+            span_subscriber: None,
+            #[cfg(feature = "otlp")]
+            otlp_exporter: None,
         })
     }
 
-    /// Creates a new database context that contains only the system catalog and
-    /// a temporary catalog.
+    /// Persist tables to a database in the given directory.
+    ///
+    /// The database is attached to every session as the "main" catalog, and
+    /// is where tables created with `CREATE TABLE` (without TEMP) are placed.
+    /// Tables previously persisted to the directory are available immediately,
+    /// their data is read from disk as it's scanned.
+    pub fn with_database_path(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        self.persistent = Some(DiskTableStorage::open_database(path)?);
+        Ok(self)
+    }
+
+    /// Limit the number of queries that can execute at once across all
+    /// sessions.
+    ///
+    /// Queries past the limit wait to be admitted in the order they were
+    /// executed. Zero (the default) means no limit.
+    pub fn with_max_concurrent_queries(self, max: usize) -> Self {
+        self.admission.set_max_concurrent_queries(max);
+        self
+    }
+
+    /// Limit the number of queries that can wait to be admitted.
+    ///
+    /// Queries that would exceed this limit error instead of waiting. Zero
+    /// (the default) means no limit.
+    pub fn with_max_queued_queries(self, max: usize) -> Self {
+        self.admission.set_max_queued_queries(max);
+        self
+    }
+
+    /// Admission control shared by all sessions for this engine.
+    pub fn admission_control(&self) -> &Arc<AdmissionControl> {
+        &self.admission
+    }
+
+    /// Create a resource group that sessions can be assigned to with
+    /// `Session::set_resource_group`.
+    ///
+    /// Configures the existing group if one with the same name exists, this
+    /// includes the "default" group that sessions start in.
+    pub fn with_resource_group(self, name: impl Into<String>, config: ResourceGroupConfig) -> Self {
+        self.resource_groups.create_or_update(name, config);
+        self.limit_buffer_pool();
+        self
+    }
+
+    /// Resource groups for this engine.
+    ///
+    /// Groups can be created or reconfigured while the engine is running.
+    pub fn resource_groups(&self) -> &Arc<ResourceGroups> {
+        &self.resource_groups
+    }
+
+    /// Enable or disable recycling array buffers through the buffer pool.
+    ///
+    /// Enabled by default. The pool is shared by the whole process, so this
+    /// affects every engine. Disabling is mostly useful when debugging memory
+    /// issues since every allocation then goes through the allocator.
+    pub fn with_buffer_pool(self, enabled: bool) -> Self {
+        buffer_pool::set_enabled(enabled);
+        self.limit_buffer_pool();
+        self
+    }
+
+    /// Cap the bytes held by the buffer pool to the default resource group's
+    /// memory limit.
+    ///
+    /// Pooled buffers aren't reserved against any tracker, so without this a
+    /// pool could hold on to more memory than queries are allowed to use.
+    fn limit_buffer_pool(&self) {
+        let max_bytes = match self.resource_groups.default_group().memory().limit() {
+            Some(limit) => limit.min(buffer_pool::DEFAULT_BUFFER_POOL_BYTES),
+            None => buffer_pool::DEFAULT_BUFFER_POOL_BYTES,
+        };
+        buffer_pool::set_max_bytes(max_bytes);
+    }
+
+    /// Restrict the statements sessions created by this engine can execute.
+    ///
+    /// Also applies to statements planned on behalf of hybrid clients. Sessions
+    /// can be further configured with `Session::set_allowed_statements`.
+    pub fn with_allowed_statements(mut self, allowed: AllowedStatements) -> Self {
+        self.allowed_statements = allowed;
+        self
+    }
+
+    /// Create spans for queries executed by this engine's sessions with the
+    /// given subscriber instead of the global default.
+    ///
+    /// This covers spans for parsing, planning, and pipeline execution, as
+    /// well as events emitted while planning.
+    pub fn with_span_subscriber(mut self, subscriber: impl Into<Dispatch>) -> Self {
+        self.span_subscriber = Some(subscriber.into());
+        self
+    }
+
+    /// Export query spans to an OTLP collector.
+    ///
+    /// Spans are exported in batches by a background task on the provided
+    /// tokio runtime. Remaining spans are flushed when the engine is dropped.
+    #[cfg(feature = "otlp")]
+    pub fn with_otlp_export(
+        mut self,
+        config: &otlp::OtlpConfig,
+        tokio_handle: &tokio::runtime::Handle,
+    ) -> Result<Self> {
+        let (exporter, dispatch) = otlp::OtlpExporter::try_new(config, tokio_handle)?;
+        self.otlp_exporter = Some(exporter);
+        Ok(self.with_span_subscriber(dispatch))
+    }
+
+    /// Creates a new database context that contains only the system catalog, a
+    /// temporary catalog, and the persistent catalog if the engine has a
+    /// database path.
     ///
     /// This should be the base of all session catalogs.
     pub fn new_base_database_context(&self) -> Result<DatabaseContext> {
-        DatabaseContext::new(self.system_catalog.clone())
+        let mut context = DatabaseContext::new(self.system_catalog.clone())?;
+        if let Some(database) = &self.persistent {
+            context.attach_database(PERSISTENT_CATALOG, database.clone())?;
+        }
+        Ok(context)
     }
 
     pub fn new_session(&self) -> Result<Session<P, R>> {
@@ -66,7 +221,11 @@ where
             self.executor.clone(),
             self.runtime.clone(),
             self.registry.clone(),
-        ))
+            self.admission.clone(),
+            self.resource_groups.clone(),
+            self.allowed_statements,
+        )
+        .with_span_subscriber(self.span_subscriber.clone()))
     }
 
     pub fn new_server_state(&self) -> Result<ServerState<P, R>> {
@@ -74,6 +233,8 @@ where
             self.executor.clone(),
             self.runtime.clone(),
             self.registry.clone(),
+            self.resource_groups.default_group(),
+            self.allowed_statements,
         ))
     }
 }
@@ -0,0 +1,74 @@
+//! Export query spans to an OTLP collector.
+//!
+//! Spans are exported from the engine directly, letting embedders see engine
+//! behavior (query planning, pipeline execution) in their existing tracing
+//! backend without having to route everything through the global subscriber.
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
+use rayexec_error::{RayexecError, Result};
+use tracing::{warn, Dispatch};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Registry;
+
+/// Configuration for exporting spans to an OTLP collector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtlpConfig {
+    /// Endpoint of the collector, e.g. "http://localhost:4317".
+    pub endpoint: String,
+    /// Service name to attach to exported spans.
+    pub service_name: String,
+}
+
+/// Exports spans to a collector in the background.
+///
+/// Dropping the exporter flushes any remaining spans and shuts it down.
+#[derive(Debug)]
+pub(crate) struct OtlpExporter {
+    provider: TracerProvider,
+}
+
+impl OtlpExporter {
+    /// Create a new exporter, returning the dispatch that spans should be
+    /// created with.
+    ///
+    /// Spans are exported using a background task on the provided tokio
+    /// runtime.
+    pub(crate) fn try_new(
+        config: &OtlpConfig,
+        tokio_handle: &tokio::runtime::Handle,
+    ) -> Result<(Self, Dispatch)> {
+        // Batch exporting spawns onto the current tokio runtime.
+        let _enter = tokio_handle.enter();
+
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(config.endpoint.clone())
+            .build()
+            .map_err(|e| RayexecError::with_source("Failed to build OTLP exporter", Box::new(e)))?;
+
+        let provider = TracerProvider::builder()
+            .with_batch_exporter(exporter, runtime::Tokio)
+            .with_resource(Resource::new([KeyValue::new(
+                "service.name",
+                config.service_name.clone(),
+            )]))
+            .build();
+
+        let tracer = provider.tracer("rayexec");
+        let subscriber =
+            Registry::default().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+        Ok((OtlpExporter { provider }, Dispatch::new(subscriber)))
+    }
+}
+
+impl Drop for OtlpExporter {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            warn!(%e, "failed to shut down OTLP exporter");
+        }
+    }
+}
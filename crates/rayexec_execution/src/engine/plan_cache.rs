@@ -0,0 +1,321 @@
+//! Per-session cache of planned queries.
+//!
+//! Sessions with `enable_plan_cache` set keep the logical plans for recently
+//! executed queries, letting repeated statements (e.g. a client re-executing
+//! the same query over the wire protocol) skip resolving, binding, and the
+//! normal optimizer passes. Parsed statements are cached by their sql text as
+//! well, so repeated sql skips parsing.
+//!
+//! Plans are keyed by a normalized fingerprint of the statement along with the
+//! versions of every catalog attached to the session. Creating, altering, or
+//! dropping anything in a catalog bumps its version, so plans bound against
+//! the old catalog are never reused. Settings can change how a query gets
+//! bound, so the session clears cached plans whenever a setting changes.
+//!
+//! Optimizer rules that depend on the current state of tables (e.g. pushing
+//! aggregates into a table) aren't part of the cached plan, and are applied
+//! every time the plan is used.
+//!
+//! Only queries are cached. Statements don't accept parameters yet, once they
+//! do, parameter types will need to be part of the key.
+//!
+//! The least recently used entries are evicted once the cache holds more than
+//! `plan_cache_size` entries.
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+
+use rayexec_error::{Result, ResultExt};
+use rayexec_parser::statement::{RawStatement, Statement};
+
+use crate::database::DatabaseContext;
+use crate::logical::binder::bind_context::BindContext;
+use crate::logical::operator::LogicalOperator;
+
+/// Default max number of plans to keep in a session's cache.
+pub const DEFAULT_PLAN_CACHE_ENTRIES: usize = 128;
+
+/// Statistics for a plan cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlanCacheStats {
+    /// Number of cached plans.
+    pub entries: usize,
+    /// Number of lookups that found a cached plan.
+    pub hits: usize,
+    /// Number of lookups that didn't find a cached plan.
+    pub misses: usize,
+}
+
+/// Normalized fingerprint of a statement.
+///
+/// Statements that only differ in whitespace or the case of keywords have the
+/// same fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatementFingerprint(String);
+
+impl StatementFingerprint {
+    pub fn new(statement: &RawStatement) -> Result<Self> {
+        // Locations of identifiers in the sql string aren't serialized, leaving
+        // just the structure of the statement.
+        let normalized =
+            serde_json::to_string(statement).context("failed to fingerprint statement")?;
+        Ok(StatementFingerprint(normalized))
+    }
+}
+
+/// Key for a cached plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanCacheKey {
+    /// Fingerprint of the statement that was planned.
+    statement: StatementFingerprint,
+    /// Versions of every catalog attached to the session, ordered by catalog
+    /// name.
+    catalog_versions: Vec<(String, u64)>,
+}
+
+impl PlanCacheKey {
+    /// Try to create a key for caching the plan of a statement.
+    ///
+    /// Returns None if the plan for the statement can't be cached. Only
+    /// queries are cacheable, everything else either modifies state during
+    /// planning or is cheap to plan.
+    pub fn try_new(statement: &RawStatement, context: &DatabaseContext) -> Result<Option<Self>> {
+        if !matches!(statement, Statement::Query(_)) {
+            return Ok(None);
+        }
+
+        let mut catalog_versions: Vec<_> = context
+            .iter_databases()
+            .map(|(name, database)| (name.clone(), database.catalog.version()))
+            .collect();
+        catalog_versions.sort_unstable();
+
+        Ok(Some(PlanCacheKey {
+            statement: StatementFingerprint::new(statement)?,
+            catalog_versions,
+        }))
+    }
+}
+
+/// A bound plan that's gone through the normal optimizer passes.
+#[derive(Debug, Clone)]
+pub struct CachedPlan {
+    pub plan: LogicalOperator,
+    pub bind_context: BindContext,
+}
+
+/// Cache of plans and parsed statements for a single session.
+#[derive(Debug, Default)]
+pub struct PlanCache {
+    plans: LruEntries<PlanCacheKey, CachedPlan>,
+    /// Parsed statements keyed by the sql text they were parsed from, along
+    /// with the sql text for each individual statement.
+    statements: LruEntries<String, Vec<(RawStatement, String)>>,
+    hits: usize,
+    misses: usize,
+}
+
+impl PlanCache {
+    pub fn stats(&self) -> PlanCacheStats {
+        PlanCacheStats {
+            entries: self.plans.entries.len(),
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+
+    /// Get a copy of the cached plan for a key.
+    pub fn get(&mut self, key: &PlanCacheKey) -> Option<CachedPlan> {
+        match self.plans.get(key) {
+            Some(plan) => {
+                self.hits += 1;
+                Some(plan.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: PlanCacheKey, plan: CachedPlan, max_entries: usize) {
+        self.plans.insert(key, plan, max_entries);
+    }
+
+    /// Get the statements previously parsed from a sql string.
+    pub fn get_statements(&mut self, sql: &str) -> Option<Vec<(RawStatement, String)>> {
+        self.statements.get(sql).cloned()
+    }
+
+    pub fn insert_statements(
+        &mut self,
+        sql: String,
+        statements: Vec<(RawStatement, String)>,
+        max_entries: usize,
+    ) {
+        self.statements.insert(sql, statements, max_entries);
+    }
+
+    /// Remove all cached plans.
+    ///
+    /// Parsed statements are kept since parsing doesn't depend on any session
+    /// state.
+    pub fn clear_plans(&mut self) {
+        self.plans.entries.clear();
+    }
+}
+
+/// Entries ordered from least to most recently used.
+#[derive(Debug)]
+struct LruEntries<K, V> {
+    entries: VecDeque<(K, V)>,
+}
+
+impl<K, V> Default for LruEntries<K, V> {
+    fn default() -> Self {
+        LruEntries {
+            entries: VecDeque::new(),
+        }
+    }
+}
+
+impl<K: PartialEq, V> LruEntries<K, V> {
+    fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        let pos = self.entries.iter().position(|(k, _)| k.borrow() == key)?;
+        // Move to the back to mark as most recently used.
+        let entry = self.entries.remove(pos).expect("entry to exist");
+        self.entries.push_back(entry);
+        self.entries.back().map(|(_, v)| v)
+    }
+
+    fn insert(&mut self, key: K, value: V, max_entries: usize) {
+        if let Some(pos) = self.entries.iter().position(|(k, _)| k == &key) {
+            self.entries.remove(pos);
+        }
+        if max_entries == 0 {
+            return;
+        }
+
+        self.entries.push_back((key, value));
+        while self.entries.len() > max_entries {
+            self.entries.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayexec_parser::parser;
+
+    use super::*;
+
+    fn parse_one(sql: &str) -> RawStatement {
+        parser::parse(sql).unwrap().pop().unwrap()
+    }
+
+    fn test_key(sql: &str, version: u64) -> PlanCacheKey {
+        PlanCacheKey {
+            statement: StatementFingerprint::new(&parse_one(sql)).unwrap(),
+            catalog_versions: vec![("temp".to_string(), version)],
+        }
+    }
+
+    fn test_plan() -> CachedPlan {
+        CachedPlan {
+            plan: LogicalOperator::EMPTY,
+            bind_context: BindContext::new(),
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_formatting() {
+        let a = StatementFingerprint::new(&parse_one("select a from t1 where a > 1")).unwrap();
+        let b = StatementFingerprint::new(&parse_one("SELECT  a\nFROM t1\n WHERE a>1")).unwrap();
+        assert_eq!(a, b);
+
+        let c = StatementFingerprint::new(&parse_one("select a from t1 where a > 2")).unwrap();
+        assert_ne!(a, c);
+
+        let d = StatementFingerprint::new(&parse_one("select \"A\" from t1 where a > 1")).unwrap();
+        assert_ne!(a, d);
+    }
+
+    #[test]
+    fn hit_requires_same_catalog_versions() {
+        let mut cache = PlanCache::default();
+        cache.insert(test_key("select 1", 1), test_plan(), 4);
+
+        assert!(cache.get(&test_key("select 1", 1)).is_some());
+        assert!(cache.get(&test_key("select 1", 2)).is_none());
+        assert!(cache.get(&test_key("select 2", 1)).is_none());
+
+        let stats = cache.stats();
+        assert_eq!(1, stats.entries);
+        assert_eq!(1, stats.hits);
+        assert_eq!(2, stats.misses);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = PlanCache::default();
+        cache.insert(test_key("select 1", 1), test_plan(), 2);
+        cache.insert(test_key("select 2", 1), test_plan(), 2);
+
+        // Touch the first entry so the second one gets evicted instead.
+        cache.get(&test_key("select 1", 1)).unwrap();
+        cache.insert(test_key("select 3", 1), test_plan(), 2);
+
+        assert!(cache.get(&test_key("select 1", 1)).is_some());
+        assert!(cache.get(&test_key("select 2", 1)).is_none());
+        assert!(cache.get(&test_key("select 3", 1)).is_some());
+        assert_eq!(2, cache.stats().entries);
+    }
+
+    #[test]
+    fn zero_size_disables_caching() {
+        let mut cache = PlanCache::default();
+        cache.insert(test_key("select 1", 1), test_plan(), 0);
+        cache.insert_statements(
+            "select 1".to_string(),
+            vec![(parse_one("select 1"), "select 1".to_string())],
+            0,
+        );
+
+        assert_eq!(0, cache.stats().entries);
+        assert!(cache.get_statements("select 1").is_none());
+    }
+
+    #[test]
+    fn clear_plans_keeps_statements() {
+        let mut cache = PlanCache::default();
+        cache.insert(test_key("select 1", 1), test_plan(), 4);
+        cache.insert_statements(
+            "select 1".to_string(),
+            vec![(parse_one("select 1"), "select 1".to_string())],
+            4,
+        );
+
+        cache.clear_plans();
+
+        assert_eq!(0, cache.stats().entries);
+        assert_eq!(
+            Some(vec![(parse_one("select 1"), "select 1".to_string())]),
+            cache.get_statements("select 1")
+        );
+    }
+
+    #[test]
+    fn only_queries_are_cacheable() {
+        let context = DatabaseContext::new(Default::default()).unwrap();
+
+        let key = PlanCacheKey::try_new(&parse_one("select 1"), &context).unwrap();
+        assert!(key.is_some());
+
+        let key =
+            PlanCacheKey::try_new(&parse_one("create temp table t1 (a int)"), &context).unwrap();
+        assert!(key.is_none());
+    }
+}
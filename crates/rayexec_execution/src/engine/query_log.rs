@@ -0,0 +1,438 @@
+//! Process-wide log of queries executed by sessions.
+//!
+//! Sessions register a query once it's been bound, and the result sinks mark
+//! the query as finished (or failed) once execution completes. The log backs
+//! the `list_queries` system function (and the `system.queries` view), and
+//! holds on to execution profile data for the `query_profile` function.
+//!
+//! Only a bounded number of completed queries are kept around. Running queries
+//! are never evicted.
+//!
+//! Sessions only see their own queries unless the embedder grants them access
+//! to every session's queries. Statements that may contain credentials (e.g.
+//! CREATE SECRET) only record the kind of statement instead of the sql text.
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rayexec_error::RayexecError;
+use rayexec_parser::statement::{RawStatement, Statement};
+use uuid::Uuid;
+
+use super::admission::AdmissionPermit;
+use crate::execution::executable::profiler::{ExecutionProfileCollector, ExecutionProfileData};
+
+/// Max number of completed queries to keep in the log.
+const MAX_COMPLETED_QUERIES: usize = 1000;
+
+static QUERY_LOG: LazyLock<QueryLog> = LazyLock::new(QueryLog::new);
+
+/// Function returning the time elapsed since a query started.
+///
+/// Boxed since getting the current time is runtime-specific.
+pub type ElapsedFn = Box<dyn Fn() -> Duration + Sync + Send>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryState {
+    Running,
+    Finished,
+    Failed,
+    Canceled,
+}
+
+impl QueryState {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Finished => "finished",
+            Self::Failed => "failed",
+            Self::Canceled => "canceled",
+        }
+    }
+}
+
+impl fmt::Display for QueryState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Snapshot of a single query in the log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryLogEntry {
+    pub query_id: Uuid,
+    /// Session that executed the query.
+    pub session_id: Uuid,
+    /// Sql text of the query if known.
+    pub query: Option<String>,
+    pub state: QueryState,
+    /// Time spent so far if the query is running, otherwise the total time
+    /// from binding to completion.
+    pub elapsed: Duration,
+    /// Error message if the query failed.
+    pub error: Option<String>,
+}
+
+/// Which queries in the log are visible to a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryLogVisibility {
+    /// Session looking at the log.
+    pub session_id: Uuid,
+    /// If queries from every session are visible, not just the session's own.
+    pub all_sessions: bool,
+}
+
+impl QueryLogVisibility {
+    pub fn can_view(&self, entry: &QueryLogEntry) -> bool {
+        self.all_sessions || entry.session_id == self.session_id
+    }
+}
+
+/// Register a new running query in the log.
+///
+/// `query` should be the sql text for a single statement, see `loggable_sql`.
+pub fn register(
+    query_id: Uuid,
+    session_id: Uuid,
+    query: Option<String>,
+    elapsed_fn: ElapsedFn,
+) -> QueryTracker {
+    QUERY_LOG.register(query_id, session_id, query, elapsed_fn)
+}
+
+/// Get a snapshot of all queries in the log, oldest first.
+pub fn snapshot() -> Vec<QueryLogEntry> {
+    QUERY_LOG.snapshot()
+}
+
+/// Get a snapshot of the queries in the log visible to a session, oldest
+/// first.
+pub fn visible_snapshot(visibility: &QueryLogVisibility) -> Vec<QueryLogEntry> {
+    let mut queries = QUERY_LOG.snapshot();
+    queries.retain(|entry| visibility.can_view(entry));
+    queries
+}
+
+/// If a statement may contain credentials that shouldn't be kept around
+/// outside of the statement's execution.
+pub fn may_contain_secrets(statement: &RawStatement) -> bool {
+    matches!(statement, Statement::CreateSecret(_) | Statement::Attach(_))
+}
+
+/// Get the sql text to record in the log for a statement.
+///
+/// Statements that may contain credentials only record the kind of statement.
+pub fn loggable_sql(statement: &RawStatement, sql: &str) -> String {
+    match statement {
+        Statement::CreateSecret(_) => "CREATE SECRET <redacted>".to_string(),
+        Statement::Attach(_) => "ATTACH <redacted>".to_string(),
+        _ => sql.to_string(),
+    }
+}
+
+/// Get the execution profile data for a query in the log.
+///
+/// Returns None if the query isn't in the log. Profile data only includes
+/// partition pipelines that have completed, and will be empty for queries
+/// that never executed (e.g. queries served from the result cache).
+pub fn execution_profile(query_id: Uuid) -> Option<ExecutionProfileData> {
+    QUERY_LOG.execution_profile(query_id)
+}
+
+/// Handle for updating the state of a query in the log.
+///
+/// State transitions only happen from running, so the first call to `finish`,
+/// `fail`, or `cancel` wins.
+#[derive(Debug, Clone)]
+pub struct QueryTracker {
+    entry: Arc<Mutex<TrackedQuery>>,
+}
+
+impl QueryTracker {
+    pub fn finish(&self) {
+        self.complete(QueryState::Finished, None);
+    }
+
+    pub fn fail(&self, error: &RayexecError) {
+        self.complete(QueryState::Failed, Some(error.get_msg().to_string()));
+    }
+
+    pub fn cancel(&self) {
+        self.complete(QueryState::Canceled, None);
+    }
+
+    pub fn state(&self) -> QueryState {
+        self.entry.lock().state
+    }
+
+    /// Hold on to the query's admission permit until the query completes.
+    ///
+    /// The permit is dropped immediately if the query already completed.
+    pub fn set_admission_permit(&self, permit: AdmissionPermit) {
+        let mut entry = self.entry.lock();
+        if entry.state == QueryState::Running {
+            entry.permit = Some(permit);
+        }
+    }
+
+    /// Set the collector that pipelines for this query write profile data to.
+    pub fn set_profile_collector(&self, collector: Arc<ExecutionProfileCollector>) {
+        self.entry.lock().profile = Some(collector);
+    }
+
+    fn complete(&self, state: QueryState, error: Option<String>) {
+        let did_complete = self.entry.lock().complete(state, error);
+        // Entry lock needs to be released before evicting, the log locks
+        // entries while holding its own lock.
+        if did_complete {
+            QUERY_LOG.evict_completed();
+        }
+    }
+}
+
+struct TrackedQuery {
+    query_id: Uuid,
+    session_id: Uuid,
+    query: Option<String>,
+    state: QueryState,
+    error: Option<String>,
+    /// Set once the query completes.
+    elapsed: Option<Duration>,
+    /// Dropped once the query completes.
+    elapsed_fn: Option<ElapsedFn>,
+    /// Profile data for the query's pipelines.
+    profile: Option<Arc<ExecutionProfileCollector>>,
+    /// Dropped once the query completes, letting the next query be admitted.
+    permit: Option<AdmissionPermit>,
+}
+
+impl TrackedQuery {
+    /// Move the query out of running, returning false if the query was
+    /// already completed.
+    fn complete(&mut self, state: QueryState, error: Option<String>) -> bool {
+        if self.state != QueryState::Running {
+            return false;
+        }
+
+        self.state = state;
+        self.error = error;
+        self.elapsed = Some(self.current_elapsed());
+        self.elapsed_fn = None;
+        self.permit = None;
+
+        true
+    }
+
+    fn current_elapsed(&self) -> Duration {
+        match (&self.elapsed, &self.elapsed_fn) {
+            (Some(elapsed), _) => *elapsed,
+            (None, Some(f)) => f(),
+            (None, None) => Duration::ZERO,
+        }
+    }
+
+    fn to_entry(&self) -> QueryLogEntry {
+        QueryLogEntry {
+            query_id: self.query_id,
+            session_id: self.session_id,
+            query: self.query.clone(),
+            state: self.state,
+            elapsed: self.current_elapsed(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for TrackedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedQuery")
+            .field("query_id", &self.query_id)
+            .field("session_id", &self.session_id)
+            .field("query", &self.query)
+            .field("state", &self.state)
+            .field("error", &self.error)
+            .field("elapsed", &self.elapsed)
+            .field("profile", &self.profile)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct QueryLog {
+    queries: Mutex<VecDeque<Arc<Mutex<TrackedQuery>>>>,
+}
+
+impl QueryLog {
+    fn new() -> Self {
+        QueryLog {
+            queries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn register(
+        &self,
+        query_id: Uuid,
+        session_id: Uuid,
+        query: Option<String>,
+        elapsed_fn: ElapsedFn,
+    ) -> QueryTracker {
+        let entry = Arc::new(Mutex::new(TrackedQuery {
+            query_id,
+            session_id,
+            query,
+            state: QueryState::Running,
+            error: None,
+            elapsed: None,
+            elapsed_fn: Some(elapsed_fn),
+            profile: None,
+            permit: None,
+        }));
+
+        self.queries.lock().push_back(entry.clone());
+
+        QueryTracker { entry }
+    }
+
+    fn snapshot(&self) -> Vec<QueryLogEntry> {
+        self.queries
+            .lock()
+            .iter()
+            .map(|entry| entry.lock().to_entry())
+            .collect()
+    }
+
+    fn execution_profile(&self, query_id: Uuid) -> Option<ExecutionProfileData> {
+        let queries = self.queries.lock();
+        let entry = queries
+            .iter()
+            .rev()
+            .find(|entry| entry.lock().query_id == query_id)?
+            .lock();
+
+        Some(
+            entry
+                .profile
+                .as_ref()
+                .map(|profile| profile.snapshot())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Remove the oldest completed queries if we're over the limit.
+    fn evict_completed(&self) {
+        let mut queries = self.queries.lock();
+
+        let is_completed =
+            |entry: &Arc<Mutex<TrackedQuery>>| entry.lock().state != QueryState::Running;
+
+        let mut completed = queries.iter().filter(|entry| is_completed(entry)).count();
+        while completed > MAX_COMPLETED_QUERIES {
+            match queries.iter().position(is_completed) {
+                Some(pos) => {
+                    queries.remove(pos);
+                    completed -= 1;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(query_id: Uuid) -> QueryLogEntry {
+        snapshot()
+            .into_iter()
+            .find(|entry| entry.query_id == query_id)
+            .unwrap()
+    }
+
+    #[test]
+    fn finish_records_elapsed() {
+        let query_id = Uuid::new_v4();
+        let tracker = register(
+            query_id,
+            Uuid::new_v4(),
+            Some("select 1".to_string()),
+            Box::new(|| Duration::from_millis(5)),
+        );
+
+        assert_eq!(QueryState::Running, find(query_id).state);
+
+        tracker.finish();
+        let entry = find(query_id);
+        assert_eq!(QueryState::Finished, entry.state);
+        assert_eq!(Duration::from_millis(5), entry.elapsed);
+    }
+
+    #[test]
+    fn first_completion_wins() {
+        let query_id = Uuid::new_v4();
+        let tracker = register(query_id, Uuid::new_v4(), None, Box::new(|| Duration::ZERO));
+
+        tracker.fail(&RayexecError::new("oops"));
+        tracker.finish();
+
+        let entry = find(query_id);
+        assert_eq!(QueryState::Failed, entry.state);
+        assert_eq!(Some("oops".to_string()), entry.error);
+    }
+
+    #[test]
+    fn execution_profile_for_query() {
+        let query_id = Uuid::new_v4();
+        let tracker = register(query_id, Uuid::new_v4(), None, Box::new(|| Duration::ZERO));
+
+        // Registered queries without a collector have empty profiles.
+        assert_eq!(
+            Some(ExecutionProfileData::default()),
+            execution_profile(query_id)
+        );
+
+        tracker.set_profile_collector(Arc::new(ExecutionProfileCollector::default()));
+        assert!(execution_profile(query_id).is_some());
+
+        assert_eq!(None, execution_profile(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn visible_snapshot_only_includes_session_queries() {
+        let session_id = Uuid::new_v4();
+        let own = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        register(own, session_id, None, Box::new(|| Duration::ZERO));
+        register(other, Uuid::new_v4(), None, Box::new(|| Duration::ZERO));
+
+        let visible = |all_sessions| {
+            let visibility = QueryLogVisibility {
+                session_id,
+                all_sessions,
+            };
+            let ids: Vec<_> = visible_snapshot(&visibility)
+                .into_iter()
+                .map(|entry| entry.query_id)
+                .collect();
+            (ids.contains(&own), ids.contains(&other))
+        };
+
+        assert_eq!((true, false), visible(false));
+        assert_eq!((true, true), visible(true));
+    }
+
+    #[test]
+    fn loggable_sql_redacts_secrets() {
+        let sql = "CREATE SECRET s (TYPE s3, KEY_ID 'key', SECRET 'hunter2')";
+        let stmt = rayexec_parser::parser::parse(sql).unwrap().pop().unwrap();
+        assert!(may_contain_secrets(&stmt));
+        assert_eq!("CREATE SECRET <redacted>", loggable_sql(&stmt, sql));
+
+        let sql = "SELECT 'hunter2'";
+        let stmt = rayexec_parser::parser::parse(sql).unwrap().pop().unwrap();
+        assert!(!may_contain_secrets(&stmt));
+        assert_eq!(sql, loggable_sql(&stmt, sql));
+    }
+}
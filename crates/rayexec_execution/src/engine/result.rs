@@ -1,14 +1,17 @@
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll, Waker};
 
 use futures::future::BoxFuture;
-use futures::{Future, Stream};
+use futures::{Future, Stream, StreamExt};
 use parking_lot::Mutex;
 use rayexec_error::{RayexecError, Result};
 use tracing::warn;
 
 use super::profiler::PlanningProfileData;
+use super::query_log::QueryTracker;
+use super::result_cache::ResultCacheWriter;
 use crate::arrays::batch::Batch;
 use crate::arrays::field::Schema;
 use crate::database::DatabaseContext;
@@ -18,13 +21,23 @@ use crate::runtime::handle::QueryHandle;
 use crate::runtime::ErrorSink;
 
 /// Create sinks and streams for sending query output to a client.
-pub fn new_results_sinks() -> (ResultStream, ResultSink, ResultErrorSink) {
+///
+/// The tracker is updated once the query finishes, errors, or the stream is
+/// dropped before completion. If a cache writer is provided, the results will
+/// be inserted into the result cache once the query finishes successfully.
+pub fn new_results_sinks(
+    tracker: QueryTracker,
+    cache_writer: Option<ResultCacheWriter>,
+) -> (ResultStream, ResultSink, ResultErrorSink) {
     let inner = Arc::new(Mutex::new(InnerState {
         batch: None,
+        pending: VecDeque::new(),
         error: None,
         finished: false,
         push_waker: None,
         pull_waker: None,
+        tracker,
+        cache_writer,
     }));
 
     (
@@ -38,12 +51,95 @@ pub fn new_results_sinks() -> (ResultStream, ResultSink, ResultErrorSink) {
     )
 }
 
+/// Create a stream for returning results that have already been computed
+/// (e.g. from the result cache).
+///
+/// The query is marked as finished immediately.
+pub fn new_cached_results(
+    batches: Vec<Batch>,
+    tracker: QueryTracker,
+) -> (ResultStream, ResultErrorSink) {
+    tracker.finish();
+
+    let inner = Arc::new(Mutex::new(InnerState {
+        batch: None,
+        pending: batches.into(),
+        error: None,
+        finished: true,
+        push_waker: None,
+        pull_waker: None,
+        tracker,
+        cache_writer: None,
+    }));
+
+    (
+        ResultStream {
+            inner: inner.clone(),
+        },
+        ResultErrorSink { inner },
+    )
+}
+
 #[derive(Debug)]
 pub struct ExecutionResult {
     pub planning_profile: PlanningProfileData,
     pub output_schema: Schema,
     pub stream: ResultStream,
     pub handle: Arc<dyn QueryHandle>,
+    /// If the results are partial due to the query being executed in preview
+    /// mode (`preview_rows`).
+    pub partial: bool,
+}
+
+/// Output of a single statement, streamed as batches are produced.
+///
+/// Returned from `Session::execute_streaming`. Batches are only held in memory
+/// until they're pulled from the stream, so arbitrarily large results can be
+/// processed incrementally. Dropping the stream before it's exhausted cancels
+/// the query.
+#[derive(Debug)]
+pub struct StreamingResult {
+    /// Schema of the batches produced by the stream.
+    pub schema: Schema,
+    pub handle: Arc<dyn QueryHandle>,
+    stream: ResultStream,
+    /// If the stream's been exhausted.
+    exhausted: bool,
+}
+
+impl StreamingResult {
+    pub fn new(result: ExecutionResult) -> Self {
+        StreamingResult {
+            schema: result.output_schema,
+            handle: result.handle,
+            stream: result.stream,
+            exhausted: false,
+        }
+    }
+}
+
+impl Stream for StreamingResult {
+    type Item = Result<Batch>;
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = self.stream.poll_next_unpin(cx);
+        if let Poll::Ready(None) = poll {
+            self.exhausted = true;
+        }
+        poll
+    }
+}
+
+impl Drop for StreamingResult {
+    fn drop(&mut self) {
+        if self.exhausted {
+            return;
+        }
+
+        // Mark canceled before canceling execution, canceled pipelines report
+        // an error which would otherwise mark the query as failed.
+        self.stream.inner.lock().tracker.cancel();
+        self.handle.cancel();
+    }
 }
 
 #[derive(Debug)]
@@ -64,6 +160,10 @@ impl Stream for ResultStream {
             return Poll::Ready(Some(Ok(batch)));
         }
 
+        if let Some(batch) = inner.pending.pop_front() {
+            return Poll::Ready(Some(Ok(batch)));
+        }
+
         if inner.finished {
             return Poll::Ready(None);
         }
@@ -78,6 +178,34 @@ impl Stream for ResultStream {
     }
 }
 
+impl ResultStream {
+    /// Wait for the query to finish, buffering all of its output.
+    ///
+    /// Afterwards the stream returns the buffered batches without waiting on
+    /// execution. Errors if the query failed.
+    pub async fn buffer(&mut self) -> Result<()> {
+        let mut batches = VecDeque::new();
+        while let Some(batch) = self.next().await {
+            batches.push_back(batch?);
+        }
+        self.inner.lock().pending = batches;
+        Ok(())
+    }
+}
+
+impl Drop for ResultStream {
+    fn drop(&mut self) {
+        let mut inner = self.inner.lock();
+        // No-op if the query already completed.
+        inner.tracker.cancel();
+
+        // Pipelines waiting to push hold on to the sink, drop their waker so
+        // they're not kept alive by the sink's state.
+        inner.push_waker = None;
+        inner.pull_waker = None;
+    }
+}
+
 #[derive(Debug)]
 pub struct ResultSink {
     inner: Arc<Mutex<InnerState>>,
@@ -143,7 +271,10 @@ impl ErrorSink for ResultErrorSink {
         // First error wins.
         let mut inner = self.inner.lock();
         if inner.error.is_none() {
+            inner.tracker.fail(&error);
             inner.error = Some(error);
+            // Don't cache partial results.
+            inner.cache_writer = None;
         }
 
         if let Some(waker) = inner.pull_waker.take() {
@@ -158,10 +289,16 @@ impl ErrorSink for ResultErrorSink {
 #[derive(Debug)]
 struct InnerState {
     batch: Option<Batch>,
+    /// Batches ready to be returned without waiting on execution.
+    pending: VecDeque<Batch>,
     error: Option<RayexecError>,
     finished: bool,
     push_waker: Option<Waker>,
     pull_waker: Option<Waker>,
+    /// Entry for this query in the query log.
+    tracker: QueryTracker,
+    /// Writer for caching the results of this query.
+    cache_writer: Option<ResultCacheWriter>,
 }
 
 struct PushFuture {
@@ -179,7 +316,11 @@ impl Future for PushFuture {
             return Poll::Pending;
         }
 
-        inner.batch = this.batch.take();
+        let batch = this.batch.take();
+        if let (Some(writer), Some(batch)) = (inner.cache_writer.as_mut(), batch.as_ref()) {
+            writer.push(batch);
+        }
+        inner.batch = batch;
 
         if let Some(pull_waker) = inner.pull_waker.take() {
             pull_waker.wake();
@@ -198,6 +339,13 @@ impl Future for FinalizeFuture {
     fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut inner = self.inner.lock();
         inner.finished = true;
+        inner.tracker.finish();
+
+        if inner.error.is_none() {
+            if let Some(writer) = inner.cache_writer.take() {
+                writer.finish();
+            }
+        }
 
         if let Some(pull_waker) = inner.pull_waker.take() {
             pull_waker.wake();
@@ -0,0 +1,401 @@
+//! Process-wide cache of query results.
+//!
+//! Sessions with `enable_result_cache` set look up queries in the cache after
+//! optimizing, and skip execution entirely on a hit. Results are keyed by the
+//! optimized logical plan along with the data versions of every table the plan
+//! scans, so a change to a versioned source (e.g. an insert into a memory
+//! table) results in a miss.
+//!
+//! Plans only name catalogs by the alias they're attached under in a session,
+//! so keys also include the id of every database scanned. Two sessions only
+//! share results when they're scanning the same database (e.g. the persistent
+//! catalog).
+//!
+//! Sources that can't report a version (e.g. files without an etag or last
+//! modified time) are treated as volatile. Plans scanning volatile sources or
+//! calling stable or volatile functions are never cached.
+//!
+//! The least recently used results are evicted once the cache grows past its
+//! max size (`result_cache_size`).
+use std::collections::VecDeque;
+use std::sync::LazyLock;
+
+use parking_lot::Mutex;
+use rayexec_error::Result;
+use uuid::Uuid;
+
+use crate::arrays::batch::Batch;
+use crate::database::DatabaseContext;
+use crate::functions::table::TableFunctionImpl;
+use crate::functions::FunctionVolatility;
+use crate::logical::logical_scan::ScanSource;
+use crate::logical::operator::{LogicalNode, LogicalOperator};
+use crate::storage::table_storage::DataVersion;
+
+/// Default max size of the cache in bytes.
+pub const DEFAULT_RESULT_CACHE_BYTES: usize = 256 * 1024 * 1024;
+
+static RESULT_CACHE: LazyLock<ResultCache> =
+    LazyLock::new(|| ResultCache::new(DEFAULT_RESULT_CACHE_BYTES));
+
+/// Statistics for the result cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResultCacheStats {
+    /// Number of cached results.
+    pub entries: usize,
+    /// Bytes currently held by the cache.
+    pub bytes: usize,
+    /// Number of lookups that found cached results.
+    pub hits: usize,
+    /// Number of lookups that didn't find cached results.
+    pub misses: usize,
+}
+
+/// Set the max size of the cache in bytes, evicting results if needed.
+pub fn set_max_bytes(max_bytes: usize) {
+    RESULT_CACHE.set_max_bytes(max_bytes)
+}
+
+/// Get the max size of the cache in bytes.
+pub fn max_bytes() -> usize {
+    RESULT_CACHE.state.lock().max_bytes
+}
+
+/// Get the current stats for the cache.
+pub fn stats() -> ResultCacheStats {
+    RESULT_CACHE.stats()
+}
+
+/// Get the cached results for a query.
+pub fn get(key: &ResultCacheKey) -> Option<Vec<Batch>> {
+    RESULT_CACHE.get(key)
+}
+
+/// Key for cached results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultCacheKey {
+    /// The optimized plan for the query.
+    plan: LogicalOperator,
+    /// Data versions for every scan in the plan, in the order they appear in
+    /// the plan.
+    versions: Vec<DataVersion>,
+    /// Ids of the databases for every table scan in the plan, in the order
+    /// they appear in the plan.
+    databases: Vec<Uuid>,
+}
+
+impl ResultCacheKey {
+    /// Try to create a key for caching the results of a plan.
+    ///
+    /// Returns None if results for the plan can't be cached. Only read-only
+    /// queries are cacheable. Anything that modifies state, depends on session
+    /// state, or references materializations (which live outside of the plan)
+    /// won't produce a key.
+    pub fn try_new(plan: &LogicalOperator, context: &DatabaseContext) -> Result<Option<Self>> {
+        let mut key = ResultCacheKey {
+            plan: plan.clone(),
+            versions: Vec::new(),
+            databases: Vec::new(),
+        };
+        if !collect_data_versions(plan, context, &mut key)? {
+            return Ok(None);
+        }
+
+        Ok(Some(key))
+    }
+}
+
+/// Walk the plan collecting the data versions and databases for all scans.
+///
+/// Returns false if the plan isn't cacheable.
+fn collect_data_versions(
+    plan: &LogicalOperator,
+    context: &DatabaseContext,
+    key: &mut ResultCacheKey,
+) -> Result<bool> {
+    match plan {
+        LogicalOperator::Project(_)
+        | LogicalOperator::Filter(_)
+        | LogicalOperator::Limit(_)
+        | LogicalOperator::Order(_)
+        | LogicalOperator::Distinct(_)
+        | LogicalOperator::Aggregate(_)
+        | LogicalOperator::SetOp(_)
+        | LogicalOperator::Empty(_)
+        | LogicalOperator::CrossJoin(_)
+        | LogicalOperator::ComparisonJoin(_)
+        | LogicalOperator::ArbitraryJoin(_)
+        | LogicalOperator::Unnest(_)
+        | LogicalOperator::Window(_)
+        | LogicalOperator::InOut(_) => (),
+        LogicalOperator::Scan(scan) => {
+            // Samples may differ between runs.
+            if scan.node.sample.is_some() {
+                return Ok(false);
+            }
+
+            let version = match &scan.node.source {
+                ScanSource::Table {
+                    catalog,
+                    schema,
+                    source,
+                } => {
+                    let database = context.get_database(catalog)?;
+                    key.databases.push(database.id);
+                    match &database.table_storage {
+                        Some(storage) => storage
+                            .data_table(context.transaction(), schema, source)?
+                            .data_version(),
+                        None => DataVersion::Volatile,
+                    }
+                }
+                ScanSource::TableFunction { function } => match &function.function_impl {
+                    TableFunctionImpl::Scan(table) => table.data_version(),
+                    // Output depends only on the inputs.
+                    TableFunctionImpl::InOut(_) => DataVersion::Untracked,
+                },
+                ScanSource::ExpressionList { .. } => DataVersion::Untracked,
+                // Views should have been inlined during binding.
+                ScanSource::View { .. } => DataVersion::Volatile,
+                // Remote data can change without us knowing.
+                ScanSource::Query { .. } => DataVersion::Volatile,
+            };
+
+            if version == DataVersion::Volatile {
+                return Ok(false);
+            }
+            key.versions.push(version);
+        }
+        _ => return Ok(false),
+    }
+
+    // Stable functions may return something different in the next query.
+    let mut has_mutable = false;
+    plan.for_each_expr(&mut |expr| {
+        has_mutable = has_mutable || expr.volatility() != FunctionVolatility::Immutable;
+        Ok(())
+    })?;
+    if has_mutable {
+        return Ok(false);
+    }
+
+    for child in plan.children() {
+        if !collect_data_versions(child, context, key)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Collects the output batches for a query, inserting them into the cache
+/// once the query completes.
+#[derive(Debug)]
+pub struct ResultCacheWriter {
+    key: ResultCacheKey,
+    batches: Vec<Batch>,
+    bytes: usize,
+    /// Set if the results grew too large to cache.
+    overflowed: bool,
+}
+
+impl ResultCacheWriter {
+    pub fn new(key: ResultCacheKey) -> Self {
+        ResultCacheWriter {
+            key,
+            batches: Vec::new(),
+            bytes: 0,
+            overflowed: false,
+        }
+    }
+
+    pub fn push(&mut self, batch: &Batch) {
+        if self.overflowed {
+            return;
+        }
+
+        self.bytes += batch.data_size_bytes();
+        if self.bytes > max_bytes() {
+            // No sense in holding on to batches that we'll never be able to
+            // cache.
+            self.overflowed = true;
+            self.batches = Vec::new();
+            return;
+        }
+
+        self.batches.push(batch.clone());
+    }
+
+    /// Insert the collected results into the cache.
+    ///
+    /// Should only be called once the query completes successfully.
+    pub fn finish(self) {
+        if self.overflowed {
+            return;
+        }
+        RESULT_CACHE.insert(self.key, self.batches, self.bytes);
+    }
+}
+
+#[derive(Debug)]
+struct CachedResult {
+    key: ResultCacheKey,
+    batches: Vec<Batch>,
+    bytes: usize,
+}
+
+#[derive(Debug)]
+struct CacheState {
+    /// Cached results, ordered from least to most recently used.
+    entries: VecDeque<CachedResult>,
+    bytes: usize,
+    max_bytes: usize,
+    hits: usize,
+    misses: usize,
+}
+
+impl CacheState {
+    /// Evict the least recently used results until we're under the max size.
+    fn evict(&mut self) {
+        while self.bytes > self.max_bytes {
+            match self.entries.pop_front() {
+                Some(entry) => self.bytes -= entry.bytes,
+                None => break,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ResultCache {
+    state: Mutex<CacheState>,
+}
+
+impl ResultCache {
+    fn new(max_bytes: usize) -> Self {
+        ResultCache {
+            state: Mutex::new(CacheState {
+                entries: VecDeque::new(),
+                bytes: 0,
+                max_bytes,
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    fn set_max_bytes(&self, max_bytes: usize) {
+        let mut state = self.state.lock();
+        state.max_bytes = max_bytes;
+        state.evict();
+    }
+
+    fn stats(&self) -> ResultCacheStats {
+        let state = self.state.lock();
+        ResultCacheStats {
+            entries: state.entries.len(),
+            bytes: state.bytes,
+            hits: state.hits,
+            misses: state.misses,
+        }
+    }
+
+    fn get(&self, key: &ResultCacheKey) -> Option<Vec<Batch>> {
+        let mut state = self.state.lock();
+        match state.entries.iter().position(|entry| &entry.key == key) {
+            Some(pos) => {
+                state.hits += 1;
+                // Move to the back to mark as most recently used.
+                let entry = state.entries.remove(pos).expect("entry to exist");
+                let batches = entry.batches.clone();
+                state.entries.push_back(entry);
+                Some(batches)
+            }
+            None => {
+                state.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&self, key: ResultCacheKey, batches: Vec<Batch>, bytes: usize) {
+        let mut state = self.state.lock();
+        if bytes > state.max_bytes {
+            return;
+        }
+
+        // Another session may have run the same query concurrently.
+        if let Some(pos) = state.entries.iter().position(|entry| entry.key == key) {
+            let existing = state.entries.remove(pos).expect("entry to exist");
+            state.bytes -= existing.bytes;
+        }
+
+        state.bytes += bytes;
+        state.entries.push_back(CachedResult {
+            key,
+            batches,
+            bytes,
+        });
+        state.evict();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::array::Array;
+
+    fn test_key(version: u64) -> ResultCacheKey {
+        ResultCacheKey {
+            plan: LogicalOperator::EMPTY,
+            versions: vec![DataVersion::Version(version)],
+            databases: Vec::new(),
+        }
+    }
+
+    fn test_batch(num_rows: i64) -> Batch {
+        Batch::try_new([Array::from_iter(0..num_rows)]).unwrap()
+    }
+
+    #[test]
+    fn hit_requires_same_versions() {
+        let cache = ResultCache::new(1024);
+        cache.insert(test_key(1), vec![test_batch(4)], 32);
+
+        assert_eq!(Some(vec![test_batch(4)]), cache.get(&test_key(1)));
+        assert_eq!(None, cache.get(&test_key(2)));
+
+        let stats = cache.stats();
+        assert_eq!(1, stats.hits);
+        assert_eq!(1, stats.misses);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = ResultCache::new(64);
+        cache.insert(test_key(1), vec![test_batch(4)], 32);
+        cache.insert(test_key(2), vec![test_batch(4)], 32);
+
+        // Touch the first entry so the second one gets evicted instead.
+        cache.get(&test_key(1)).unwrap();
+        cache.insert(test_key(3), vec![test_batch(4)], 32);
+
+        assert!(cache.get(&test_key(1)).is_some());
+        assert!(cache.get(&test_key(2)).is_none());
+        assert!(cache.get(&test_key(3)).is_some());
+        assert_eq!(64, cache.stats().bytes);
+    }
+
+    #[test]
+    fn skip_results_larger_than_cache() {
+        let cache = ResultCache::new(16);
+        cache.insert(test_key(1), vec![test_batch(4)], 32);
+
+        assert_eq!(0, cache.stats().entries);
+    }
+
+    #[test]
+    fn batch_size() {
+        assert_eq!(32, test_batch(4).data_size_bytes());
+    }
+}
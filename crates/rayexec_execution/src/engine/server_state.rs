@@ -8,6 +8,7 @@ use crate::arrays::batch::Batch;
 use crate::arrays::field::{Field, Schema};
 use crate::config::execution::{ExecutablePlanConfig, IntermediatePlanConfig};
 use crate::config::session::SessionConfig;
+use crate::config::statements::AllowedStatements;
 use crate::database::catalog::CatalogTx;
 use crate::database::DatabaseContext;
 use crate::datasource::DataSourceRegistry;
@@ -18,6 +19,7 @@ use crate::execution::intermediate::pipeline::{
     StreamId,
 };
 use crate::execution::intermediate::planner::IntermediatePipelinePlanner;
+use crate::execution::operators::util::resizer::DEFAULT_TARGET_BATCH_SIZE;
 use crate::hybrid::buffer::ServerStreamBuffers;
 use crate::hybrid::client::{HybridPlanResponse, PullStatus};
 use crate::logical::binder::bind_statement::StatementBinder;
@@ -28,6 +30,7 @@ use crate::logical::resolver::resolve_hybrid::{HybridContextExtender, HybridReso
 use crate::logical::resolver::ResolvedStatement;
 use crate::optimizer::Optimizer;
 use crate::runtime::handle::QueryHandle;
+use crate::runtime::resource_group::ResourceGroup;
 use crate::runtime::{PipelineExecutor, Runtime};
 
 /// Server state for planning and executing user queries on a remote server.
@@ -47,6 +50,12 @@ pub struct ServerState<P: PipelineExecutor, R: Runtime> {
 
     executor: P,
     runtime: R,
+
+    /// Resource group remote pipelines execute in.
+    resource_group: Arc<ResourceGroup>,
+
+    /// Statements clients are allowed to have planned.
+    allowed_statements: AllowedStatements,
 }
 
 #[derive(Debug)]
@@ -64,7 +73,13 @@ where
     P: PipelineExecutor,
     R: Runtime,
 {
-    pub fn new(executor: P, runtime: R, registry: Arc<DataSourceRegistry>) -> Self {
+    pub fn new(
+        executor: P,
+        runtime: R,
+        registry: Arc<DataSourceRegistry>,
+        resource_group: Arc<ResourceGroup>,
+        allowed_statements: AllowedStatements,
+    ) -> Self {
         ServerState {
             registry,
             buffers: ServerStreamBuffers::default(),
@@ -72,6 +87,8 @@ where
             executing_pipelines: DashMap::new(),
             executor,
             runtime,
+            resource_group,
+            allowed_statements,
         }
     }
 
@@ -89,13 +106,15 @@ where
         stmt: ResolvedStatement,
         bind_data: ResolveContext,
     ) -> Result<HybridPlanResponse> {
+        context.set_memory_tracker(self.resource_group.memory().clone());
+
         // Extend context with what we need in the query.
         let mut extender = HybridContextExtender::new(&mut context, &self.registry);
         extender.attach_unknown_databases(&bind_data).await?;
 
         // Now resolve with the extended context.
         let tx = CatalogTx::new();
-        let resolver = HybridResolver::new(&tx, &context);
+        let resolver = HybridResolver::new(&tx, &context, &self.allowed_statements);
         let resolve_context = resolver.resolve_remaining(bind_data).await?;
 
         // TODO: Remove session config requirement.
@@ -104,6 +123,7 @@ where
         let binder = StatementBinder {
             session_config: &session_config,
             resolve_context: &resolve_context,
+            allowed_statements: &self.allowed_statements,
         };
         let (bound_stmt, mut bind_context) = binder.bind(stmt)?;
         let mut logical = StatementPlanner.plan(&mut bind_context, bound_stmt)?;
@@ -166,6 +186,8 @@ where
             &state.context,
             ExecutablePlanConfig {
                 partitions: num_cpus::get(),
+                batch_size: DEFAULT_TARGET_BATCH_SIZE,
+                batch_size_bytes: None,
             },
             PlanLocationState::Server {
                 stream_buffers: &self.buffers,
@@ -177,19 +199,30 @@ where
         let error_sink = self.buffers.create_error_sink(query_id)?;
 
         let pipelines = planner.plan_from_intermediate(state.group, state.materializations)?;
-        let handle = self.executor.spawn_pipelines(pipelines, error_sink);
+        let handle =
+            self.executor
+                .spawn_pipelines(pipelines, error_sink, self.resource_group.clone());
 
         self.executing_pipelines.insert(query_id, handle);
 
         Ok(())
     }
 
-    pub fn push_batch_for_stream(&self, stream_id: StreamId, batch: Batch) -> Result<()> {
-        self.buffers.push_batch_for_stream(&stream_id, batch)
+    /// Push a batch to a partition of an incoming stream, waiting until the
+    /// partition has room for it.
+    pub async fn push_batch_for_stream(
+        &self,
+        stream_id: StreamId,
+        partition: usize,
+        batch: Batch,
+    ) -> Result<()> {
+        self.buffers
+            .push_batch_for_stream(&stream_id, partition, batch)
+            .await
     }
 
-    pub fn finalize_stream(&self, stream_id: StreamId) -> Result<()> {
-        self.buffers.finalize_stream(&stream_id)
+    pub fn finalize_stream(&self, stream_id: StreamId, partition: usize) -> Result<()> {
+        self.buffers.finalize_stream(&stream_id, partition)
     }
 
     pub fn pull_batch_for_stream(&self, stream_id: StreamId) -> Result<PullStatus> {
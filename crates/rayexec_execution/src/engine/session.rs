@@ -1,38 +1,69 @@
 use std::sync::Arc;
 
+use chrono::Utc;
 use hashbrown::HashMap;
-use rayexec_error::{OptionExt, RayexecError, Result};
+use rayexec_error::{ErrorKind, OptionExt, RayexecError, Result};
 use rayexec_parser::parser;
 use rayexec_parser::statement::RawStatement;
+use tracing::instrument::WithSubscriber;
+use tracing::{dispatcher, field, info_span, Dispatch, Instrument, Span};
 use uuid::Uuid;
 
+use super::admission::AdmissionControl;
+use super::plan_cache::{CachedPlan, PlanCache, PlanCacheKey, PlanCacheStats};
 use super::profiler::PlanningProfileData;
-use super::result::{new_results_sinks, ExecutionResult, ResultErrorSink, ResultStream};
+use super::query_log::{self, ElapsedFn, QueryTracker};
+use super::result::{
+    new_cached_results,
+    new_results_sinks,
+    ExecutionResult,
+    ResultErrorSink,
+    ResultStream,
+    StreamingResult,
+};
+use super::result_cache::{self, ResultCacheKey, ResultCacheWriter};
 use super::verifier::QueryVerifier;
 use super::DataSourceRegistry;
+use crate::arrays::batch::Batch;
 use crate::arrays::field::{Field, Schema};
+use crate::arrays::scalar::OwnedScalarValue;
 use crate::config::execution::{ExecutablePlanConfig, IntermediatePlanConfig};
 use crate::config::session::SessionConfig;
+use crate::config::statements::AllowedStatements;
 use crate::database::catalog::CatalogTx;
+use crate::database::create::{CreateScalarFunctionInfo, CreateTableInfo, OnConflict};
 use crate::database::memory_catalog::MemoryCatalog;
 use crate::database::{AttachInfo, Database, DatabaseContext};
 use crate::execution::executable::pipeline::ExecutablePipeline;
 use crate::execution::executable::planner::{ExecutablePipelinePlanner, PlanLocationState};
+use crate::execution::executable::profiler::ExecutionProfileCollector;
 use crate::execution::intermediate::pipeline::{
     IntermediateMaterializationGroup,
     IntermediatePipelineGroup,
 };
 use crate::execution::intermediate::planner::IntermediatePipelinePlanner;
+use crate::functions::scalar::builtin::random::RandomState;
+use crate::functions::scalar::ScalarFunction;
 use crate::hybrid::client::HybridClient;
+use crate::logical::binder::bind_context::BindContext;
 use crate::logical::binder::bind_statement::StatementBinder;
+use crate::logical::logical_alter::LogicalAlterTable;
 use crate::logical::logical_attach::LogicalAttachDatabase;
 use crate::logical::logical_set::VariableOrAll;
+use crate::logical::logical_transaction::LogicalTransaction;
 use crate::logical::operator::{LogicalOperator, Node};
 use crate::logical::planner::plan_statement::StatementPlanner;
 use crate::logical::resolver::resolve_context::ResolveContext;
 use crate::logical::resolver::{ResolveConfig, ResolveMode, ResolvedStatement, Resolver};
-use crate::optimizer::Optimizer;
-use crate::runtime::time::Timer;
+use crate::optimizer::aggregate_pushdown::AggregatePushdown;
+use crate::optimizer::index_scan::IndexScanRule;
+use crate::optimizer::merge_join::MergeJoinRule;
+use crate::optimizer::preview::PreviewSample;
+use crate::optimizer::query_pushdown::QueryPushdown;
+use crate::optimizer::{OptimizeRule, Optimizer};
+use crate::runtime::memory::MemoryTracker;
+use crate::runtime::resource_group::{ResourceGroup, ResourceGroups};
+use crate::runtime::time::{RuntimeInstant, Timer};
 use crate::runtime::{PipelineExecutor, Runtime};
 
 /// A "client" session capable of executing queries from arbitrary sql
@@ -79,20 +110,57 @@ pub struct Session<P: PipelineExecutor, R: Runtime> {
     /// Pipeline executor.
     executor: P,
 
+    /// Limits the number of queries executing concurrently across all
+    /// sessions for the engine.
+    admission: Arc<AdmissionControl>,
+
+    /// Resource groups registered with the engine.
+    resource_groups: Arc<ResourceGroups>,
+
+    /// Resource group queries from this session execute in.
+    resource_group: Arc<ResourceGroup>,
+
+    /// Statements this session is allowed to execute.
+    allowed_statements: AllowedStatements,
+
     /// Prepared statements.
     prepared: HashMap<String, PreparedStatement>,
 
     /// Portals for statements ready to be executed.
     portals: HashMap<String, ExecutablePortal>,
 
+    /// Recently planned queries and parsed sql.
+    plan_cache: PlanCache,
+
+    /// Random state shared by all random functions in the session.
+    random_state: RandomState,
+
     /// Client for hybrid execution if enabled.
     hybrid_client: Option<Arc<HybridClient<R::HttpClient>>>,
+
+    /// Subscriber to create query spans with, uses the global default if not
+    /// set.
+    span_subscriber: Option<Dispatch>,
+
+    /// Original values for settings changed with SET LOCAL.
+    ///
+    /// These get restored at the end of the current (implicit) transaction.
+    local_settings: HashMap<String, OwnedScalarValue>,
+
+    /// If a batch of statements is currently executing as a single implicit
+    /// transaction.
+    ///
+    /// Outside of a batch, each `execute` is its own implicit transaction.
+    in_implicit_batch: bool,
 }
 
 #[derive(Debug)]
 struct PreparedStatement {
     verifier: Option<QueryVerifier>,
     statement: RawStatement,
+    /// Sql text of the statement, if known. Used for the query log, and
+    /// redacted if the statement may contain credentials.
+    sql: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -104,6 +172,16 @@ enum ExecutionMode {
     Hybrid,
 }
 
+/// Where a logical plan that's being planned locally came from.
+#[derive(Debug)]
+enum LogicalPlanSource {
+    /// Plan was just bound. It'll be put in the plan cache after optimizing if
+    /// there's a key for it.
+    Bound(Option<PlanCacheKey>),
+    /// Plan came from the plan cache and has already been optimized.
+    Cached,
+}
+
 /// Intermediate struct hold on to pipelines that still need to be planned for
 /// execution.
 #[derive(Debug)]
@@ -113,6 +191,12 @@ struct IntermediatePortal {
     intermediate_pipelines: IntermediatePipelineGroup,
     intermediate_materializations: IntermediateMaterializationGroup,
     output_schema: Schema,
+    /// If sources were sampled for a preview.
+    partial: bool,
+    /// Key for caching the results of this query if cacheable.
+    cache_key: Option<ResultCacheKey>,
+    /// Results from the result cache. If set, there's nothing to execute.
+    cached_results: Option<Vec<Batch>>,
 }
 
 /// Portal containing executable pipelines.
@@ -135,10 +219,14 @@ struct ExecutablePortal {
     result_stream: ResultStream,
     /// Where errors will be sent do.
     error_sink: ResultErrorSink,
+    /// Entry for the query in the query log.
+    tracker: QueryTracker,
     /// Profile data we've collected during resolving/binding/planning.
     profile: PlanningProfileData,
     /// Optional verifier that we're carrying through planning.
     verifier: Option<QueryVerifier>,
+    /// If results will be partial due to preview mode.
+    partial: bool,
 }
 
 impl<P, R> Session<P, R>
@@ -147,55 +235,286 @@ where
     R: Runtime,
 {
     pub fn new(
-        context: DatabaseContext,
+        mut context: DatabaseContext,
         executor: P,
         runtime: R,
         registry: Arc<DataSourceRegistry>,
+        admission: Arc<AdmissionControl>,
+        resource_groups: Arc<ResourceGroups>,
+        allowed_statements: AllowedStatements,
     ) -> Self {
         let config = SessionConfig::new(&executor, &runtime);
 
+        let resource_group = resource_groups.default_group();
+        context.set_memory_tracker(resource_group.memory().clone());
+
         Session {
             context,
             runtime,
             executor,
+            admission,
+            resource_groups,
+            resource_group,
+            allowed_statements,
             registry,
             config,
             prepared: HashMap::new(),
             portals: HashMap::new(),
+            plan_cache: PlanCache::default(),
+            random_state: RandomState::default(),
             hybrid_client: None,
+            span_subscriber: None,
+            local_settings: HashMap::new(),
+            in_implicit_batch: false,
         }
     }
 
+    /// Create query spans with the given subscriber instead of the global
+    /// default.
+    pub(crate) fn with_span_subscriber(mut self, subscriber: Option<Dispatch>) -> Self {
+        self.span_subscriber = subscriber;
+        self
+    }
+
     pub(crate) fn config_mut(&mut self) -> &mut SessionConfig {
+        // Settings may change how queries get bound.
+        self.plan_cache.clear_plans();
         &mut self.config
     }
 
-    /// Get execution results from one or more sql queries.
+    /// Get stats for this session's plan cache.
+    pub fn plan_cache_stats(&self) -> PlanCacheStats {
+        self.plan_cache.stats()
+    }
+
+    /// Move this session into a different resource group.
+    ///
+    /// Queries executed afterwards are scheduled using the group's CPU shares
+    /// and reserve memory against the group's memory limit. Queries that are
+    /// already executing stay in their original group.
+    pub fn set_resource_group(&mut self, name: &str) -> Result<()> {
+        let group = self.resource_groups.get(name)?;
+        self.context.set_memory_tracker(group.memory().clone());
+        self.resource_group = group;
+        Ok(())
+    }
+
+    /// Resource group this session is executing queries in.
+    pub fn resource_group(&self) -> &Arc<ResourceGroup> {
+        &self.resource_group
+    }
+
+    /// Restrict the statements this session can execute.
+    ///
+    /// Statements outside of the allowed set error during binding. This can't
+    /// be changed from SQL, so can be used to e.g. make a session read-only
+    /// before handing it to untrusted users.
+    pub fn set_allowed_statements(&mut self, allowed: AllowedStatements) {
+        // Statements are checked during binding, cached plans skip that.
+        self.plan_cache.clear_plans();
+        self.allowed_statements = allowed;
+    }
+
+    /// Set the role used to pick which row-level security policies apply.
+    ///
+    /// `None` clears the role. Like allowed statements, this can't be changed
+    /// from SQL, and sessions with a role can't create policies.
+    pub fn set_role(&mut self, role: Option<&str>) {
+        // Policies are applied during resolving, cached plans skip that.
+        self.plan_cache.clear_plans();
+        self.config.role = role.unwrap_or_default().to_string();
+    }
+
+    /// Let this session see queries from every session in the query log.
     ///
-    /// Analogous to postgres' simple query protocol. Goes through all prepatory
-    /// steps for query parsing and planning, returning execution results.
+    /// Sessions only see their own queries by default. Like allowed
+    /// statements, this can't be changed from SQL.
+    pub fn set_view_all_queries(&mut self, all_sessions: bool) {
+        self.context.set_view_all_queries(all_sessions);
+    }
+
+    /// Statements this session is allowed to execute.
+    pub fn allowed_statements(&self) -> &AllowedStatements {
+        &self.allowed_statements
+    }
+
+    /// Execute a script of one or more semicolon separated sql statements,
+    /// returning the results for each statement.
     ///
-    /// Execution result streams should be read in order.
+    /// Analogous to postgres' simple query protocol. Statements are executed in
+    /// order, with each statement running to completion before the next one is
+    /// planned. This lets later statements depend on the effects of earlier
+    /// ones, e.g. inserting into a table created earlier in the script. The
+    /// output of every statement but the last is buffered in its result, the
+    /// last statement's output is streamed as it executes.
+    ///
+    /// Execution stops at the first statement that fails. If the script began
+    /// an explicit transaction that's still open at that point, the
+    /// transaction is rolled back since the script can't continue to commit
+    /// it. Errors from the last statement that happen while streaming its
+    /// output are left to the caller.
     ///
     /// Uses the unnamed ("") keys for prepared statements and portals.
     pub async fn simple(&mut self, sql: &str) -> Result<Vec<ExecutionResult>> {
-        let stmts = parser::parse(sql)?;
-        let mut results = Vec::with_capacity(stmts.len());
+        let stmts = self.parse(sql)?;
+        let num_stmts = stmts.len();
+        let mut results = Vec::with_capacity(num_stmts);
 
         const UNNAMED: &str = "";
 
-        for stmt in stmts {
-            self.prepare(UNNAMED, stmt)?;
-            self.bind(UNNAMED, UNNAMED).await?;
-            let result = self.execute(UNNAMED).await?;
-            results.push(result);
+        let began_in_tx = self.context.transaction().is_explicit();
+
+        // All statements run as part of a single implicit transaction, SET
+        // LOCAL will apply to the remaining statements.
+        self.begin_implicit_transaction()?;
+        let result = async {
+            for (idx, (stmt, stmt_sql)) in stmts.into_iter().enumerate() {
+                self.prepare_with_sql(UNNAMED, stmt, Some(stmt_sql))?;
+                self.bind(UNNAMED, UNNAMED).await?;
+                let mut result = self.execute(UNNAMED).await?;
+                // Following statements may depend on this one's effects.
+                if idx < num_stmts - 1 {
+                    result.stream.buffer().await?;
+                }
+                results.push(result);
+            }
+            Ok::<_, RayexecError>(())
         }
+        .await;
+
+        if result.is_err() && !began_in_tx && self.context.transaction().is_explicit() {
+            let tx = self.context.set_transaction(CatalogTx::new());
+            tx.rollback()?;
+        }
+
+        self.end_implicit_transaction()?;
+        result?;
 
         Ok(results)
     }
 
+    /// Execute a single sql statement, returning its output schema along with
+    /// a stream of result batches.
+    ///
+    /// Unlike `simple`, the output isn't buffered. Batches are produced as
+    /// execution progresses, letting callers process results that wouldn't
+    /// fit in memory. The statement runs in its own implicit transaction.
+    ///
+    /// Uses the unnamed ("") keys for prepared statements and portals.
+    pub async fn execute_streaming(&mut self, sql: &str) -> Result<StreamingResult> {
+        let mut stmts = self.parse(sql)?;
+        if stmts.len() != 1 {
+            return Err(RayexecError::new(format!(
+                "Expected exactly 1 statement, got {}",
+                stmts.len()
+            )));
+        }
+        let (stmt, stmt_sql) = stmts.pop().unwrap();
+
+        const UNNAMED: &str = "";
+
+        self.end_implicit_transaction()?;
+
+        let result = async {
+            self.prepare_with_sql(UNNAMED, stmt, Some(stmt_sql))?;
+            self.bind(UNNAMED, UNNAMED).await?;
+            self.execute(UNNAMED).await
+        }
+        .await;
+
+        self.end_implicit_transaction()?;
+
+        Ok(StreamingResult::new(result?))
+    }
+
+    /// Begin an implicit transaction spanning multiple calls to `execute`.
+    ///
+    /// Any state left over from a previous implicit transaction is cleared
+    /// first. Settings changed with SET LOCAL stay in effect until
+    /// `end_implicit_transaction` is called.
+    pub fn begin_implicit_transaction(&mut self) -> Result<()> {
+        self.end_implicit_transaction()?;
+        self.in_implicit_batch = true;
+        Ok(())
+    }
+
+    /// End the current implicit transaction, reverting any settings that were
+    /// changed with SET LOCAL.
+    ///
+    /// Outside of an explicit transaction, a batch of statements submitted
+    /// together is treated as a single transaction. This is a no-op while an
+    /// explicit transaction is in progress, settings are instead reverted on
+    /// COMMIT or ROLLBACK.
+    pub fn end_implicit_transaction(&mut self) -> Result<()> {
+        self.in_implicit_batch = false;
+        if self.context.transaction().is_explicit() {
+            return Ok(());
+        }
+        if !self.local_settings.is_empty() {
+            self.plan_cache.clear_plans();
+        }
+        for (name, value) in self.local_settings.drain() {
+            self.config.set_from_scalar(&name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Parse a sql string into statements, along with the sql text for each
+    /// statement.
+    ///
+    /// Statements parsed from the same sql are reused if the plan cache is
+    /// enabled. Sql containing statements that may include credentials is
+    /// never cached.
+    fn parse(&mut self, sql: &str) -> Result<Vec<(RawStatement, String)>> {
+        if self.config.enable_plan_cache {
+            if let Some(stmts) = self.plan_cache.get_statements(sql) {
+                return Ok(stmts);
+            }
+        }
+
+        let stmts: Vec<_> = self.in_span_scope(|| {
+            info_span!("parse").in_scope(|| {
+                let stmts = parser::parse_with_sql(sql)?;
+                Ok::<_, RayexecError>(
+                    stmts
+                        .into_iter()
+                        .map(|(stmt, sql)| (stmt, sql.to_string()))
+                        .collect(),
+                )
+            })
+        })?;
+
+        let cacheable = !stmts
+            .iter()
+            .any(|(stmt, _)| query_log::may_contain_secrets(stmt));
+        if self.config.enable_plan_cache && cacheable {
+            self.plan_cache.insert_statements(
+                sql.to_string(),
+                stmts.clone(),
+                self.config.plan_cache_size as usize,
+            );
+        }
+
+        Ok(stmts)
+    }
+
     // TODO: Typed parameters at some point.
     pub fn prepare(&mut self, prepared_name: impl Into<String>, stmt: RawStatement) -> Result<()> {
+        self.prepare_with_sql(prepared_name, stmt, None)
+    }
+
+    /// Prepare a statement, keeping the sql text it was parsed from for the
+    /// query log.
+    ///
+    /// `sql` should be the text of this statement only. Text for statements
+    /// that may contain credentials is redacted.
+    pub fn prepare_with_sql(
+        &mut self,
+        prepared_name: impl Into<String>,
+        stmt: RawStatement,
+        sql: Option<String>,
+    ) -> Result<()> {
+        let sql = sql.map(|sql| query_log::loggable_sql(&stmt, &sql));
         let verifier = if self.config.verify_optimized_plan {
             Some(QueryVerifier::new(stmt.clone()))
         } else {
@@ -207,6 +526,7 @@ where
             PreparedStatement {
                 statement: stmt,
                 verifier,
+                sql,
             },
         );
         Ok(())
@@ -214,69 +534,134 @@ where
 
     /// Gets a prepared statement by name and generates intermedidate executable
     /// pipelines that get placed into a portal.
+    ///
+    /// Planning happens inside of a `query` span. Spans for pipeline execution
+    /// are children of this span, so the span stays open until the query
+    /// completes.
     pub async fn bind(
         &mut self,
         prepared_name: &str,
         portal_name: impl Into<String>,
     ) -> Result<()> {
+        let span = self.in_span_scope(|| info_span!("query", query_id = field::Empty));
+        match self.span_subscriber.clone() {
+            Some(subscriber) => {
+                self.bind_inner(prepared_name, portal_name.into())
+                    .instrument(span)
+                    .with_subscriber(subscriber)
+                    .await
+            }
+            None => {
+                self.bind_inner(prepared_name, portal_name.into())
+                    .instrument(span)
+                    .await
+            }
+        }
+    }
+
+    /// Run a function with this session's span subscriber set as the default.
+    fn in_span_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        match &self.span_subscriber {
+            Some(subscriber) => dispatcher::with_default(subscriber, f),
+            None => f(),
+        }
+    }
+
+    async fn bind_inner(&mut self, prepared_name: &str, portal_name: String) -> Result<()> {
         let stmt = self.prepared.get(prepared_name).ok_or_else(|| {
             RayexecError::new(format!(
                 "Missing named prepared statement: '{prepared_name}'"
             ))
         })?;
         let verifier = stmt.verifier.clone();
+        let statement = stmt.statement.clone();
+        let sql = stmt.sql.clone();
+        let session_id = self.context.query_log_visibility().session_id;
+
+        // Query log tracks time from the start of binding.
+        let start = R::Instant::now();
+        let elapsed_fn: ElapsedFn =
+            Box::new(move || R::Instant::now().duration_since(start.clone()));
 
         let mut profile = PlanningProfileData::default();
 
-        // TODO: Store tx state on session.
-        let tx = CatalogTx::new();
+        // Scans pick up the stall settings when they're planned.
+        self.context.set_scan_stall(self.config.scan_stall());
 
-        let resolve_mode = if self.hybrid_client.is_some() {
-            ResolveMode::Hybrid
-        } else {
-            ResolveMode::Normal
+        let intermediate_portal = match self.plan_statement(statement, &mut profile).await {
+            Ok(portal) => portal,
+            Err(e) => {
+                query_log::register(Uuid::new_v4(), session_id, sql, elapsed_fn).fail(&e);
+                return Err(e);
+            }
         };
 
-        let timer = Timer::<R::Instant>::start();
-        let (resolved_stmt, resolve_context) = Resolver::new(
-            resolve_mode,
-            &tx,
-            &self.context,
-            self.registry.get_file_handlers(),
-            ResolveConfig {
-                enable_function_chaining: self.config.enable_function_chaining,
-            },
-        )
-        .resolve_statement(stmt.statement.clone())
-        .await?;
-        profile.resolve_step = Some(timer.stop());
+        Span::current().record("query_id", field::display(intermediate_portal.query_id));
+        let tracker =
+            query_log::register(intermediate_portal.query_id, session_id, sql, elapsed_fn);
 
-        let intermediate_portal = self
-            .plan_intermediate(resolved_stmt, resolve_context, resolve_mode, &mut profile)
-            .await?;
+        let (pipelines, stream, errors) = match intermediate_portal.cached_results {
+            Some(batches) => {
+                // Results came from the cache, nothing to execute.
+                let (stream, errors) = new_cached_results(batches, tracker.clone());
+                (Vec::new(), stream, errors)
+            }
+            None => {
+                let cache_writer = intermediate_portal.cache_key.map(ResultCacheWriter::new);
+                let (stream, sink, errors) = new_results_sinks(tracker.clone(), cache_writer);
 
-        let (stream, sink, errors) = new_results_sinks();
+                // Operators reserve memory when their states are created
+                // below, so the query's tracker needs to be set first.
+                let group_memory = self.resource_group.memory().clone();
+                let memory = match self.config.memory_limit {
+                    0 => group_memory,
+                    limit => Arc::new(MemoryTracker::new_with_parent(
+                        "query",
+                        Some(limit as usize),
+                        group_memory,
+                    )),
+                };
+                self.context.set_memory_tracker(memory);
 
-        let mut planner = ExecutablePipelinePlanner::<R>::new(
-            &self.context,
-            ExecutablePlanConfig {
-                partitions: self.config.partitions as usize,
-            },
-            PlanLocationState::Client {
-                output_sink: Some(sink),
-                hybrid_client: self.hybrid_client.as_ref(),
-            },
-        );
+                let mut planner = ExecutablePipelinePlanner::<R>::new(
+                    &self.context,
+                    ExecutablePlanConfig {
+                        partitions: self.config.partitions as usize,
+                        batch_size: self.config.batch_size as usize,
+                        batch_size_bytes: match self.config.batch_size_bytes {
+                            0 => None,
+                            n => Some(n as usize),
+                        },
+                    },
+                    PlanLocationState::Client {
+                        output_sink: Some(sink),
+                        hybrid_client: self.hybrid_client.as_ref(),
+                    },
+                );
 
-        let timer = Timer::<R::Instant>::start();
-        let pipelines = planner.plan_from_intermediate(
-            intermediate_portal.intermediate_pipelines,
-            intermediate_portal.intermediate_materializations,
-        )?;
-        profile.plan_executable_step = Some(timer.stop());
+                let timer = Timer::<R::Instant>::start();
+                // Not wrapped in a span, spans for executing the pipelines
+                // are created here and should be children of the query span.
+                let mut pipelines = planner
+                    .plan_from_intermediate(
+                        intermediate_portal.intermediate_pipelines,
+                        intermediate_portal.intermediate_materializations,
+                    )
+                    .inspect_err(|e| tracker.fail(e))?;
+                profile.plan_executable_step = Some(timer.stop());
+
+                let collector = Arc::new(ExecutionProfileCollector::default());
+                for pipeline in &mut pipelines {
+                    pipeline.set_profile_collector(&collector);
+                }
+                tracker.set_profile_collector(collector);
+
+                (pipelines, stream, errors)
+            }
+        };
 
         self.portals.insert(
-            portal_name.into(),
+            portal_name,
             ExecutablePortal {
                 query_id: intermediate_portal.query_id,
                 execution_mode: intermediate_portal.execution_mode,
@@ -284,13 +669,79 @@ where
                 output_schema: intermediate_portal.output_schema,
                 result_stream: stream,
                 error_sink: errors,
+                tracker,
                 profile,
                 verifier,
+                partial: intermediate_portal.partial,
             },
         );
         Ok(())
     }
 
+    /// Resolves a statement and plans the intermediate pipelines for it.
+    async fn plan_statement(
+        &mut self,
+        statement: RawStatement,
+        profile: &mut PlanningProfileData,
+    ) -> Result<IntermediatePortal> {
+        let tx = self.context.transaction().clone();
+
+        let resolve_mode = if self.hybrid_client.is_some() {
+            ResolveMode::Hybrid
+        } else {
+            ResolveMode::Normal
+        };
+
+        // Hybrid planning may complete planning remotely, only local plans are
+        // cached.
+        let plan_cache_key = if self.config.enable_plan_cache && resolve_mode == ResolveMode::Normal
+        {
+            PlanCacheKey::try_new(&statement, &self.context)?
+        } else {
+            None
+        };
+
+        if let Some(cached) = plan_cache_key
+            .as_ref()
+            .and_then(|key| self.plan_cache.get(key))
+        {
+            return self
+                .plan_local(
+                    cached.plan,
+                    cached.bind_context,
+                    LogicalPlanSource::Cached,
+                    profile,
+                )
+                .await;
+        }
+
+        let timer = Timer::<R::Instant>::start();
+        let (resolved_stmt, resolve_context) = Resolver::new(
+            resolve_mode,
+            &tx,
+            &self.context,
+            self.registry.get_file_handlers(),
+            ResolveConfig {
+                enable_function_chaining: self.config.enable_function_chaining,
+                role: self.config.role.clone(),
+                allow_passthrough: self.allowed_statements.allows_passthrough(),
+            },
+        )
+        .resolve_statement(statement)
+        .instrument(info_span!("resolve"))
+        .await?;
+        profile.resolve_step = Some(timer.stop());
+
+        self.plan_intermediate(
+            resolved_stmt,
+            resolve_context,
+            resolve_mode,
+            plan_cache_key,
+            profile,
+        )
+        .await
+    }
+
     /// Plans the intermediate pipelines from a resolved statement.
     ///
     /// If the resolve context indicates that not all objects were resolved,
@@ -300,11 +751,14 @@ where
         stmt: ResolvedStatement,
         resolve_context: ResolveContext,
         resolve_mode: ResolveMode,
+        plan_cache_key: Option<PlanCacheKey>,
         profile: &mut PlanningProfileData,
     ) -> Result<IntermediatePortal> {
         match resolve_mode {
             ResolveMode::Hybrid if resolve_context.any_unresolved() => {
-                // Hybrid planning, send to remote to complete planning.
+                // Hybrid planning, send to remote to complete planning. Check
+                // the statement first since binding happens remotely.
+                self.allowed_statements.check_statement(&stmt)?;
                 let hybrid_client = self.hybrid_client.clone().required("hybrid_client")?;
                 let resp = hybrid_client
                     .remote_plan(stmt, resolve_context, &self.context)
@@ -316,6 +770,9 @@ where
                     intermediate_pipelines: resp.pipelines,
                     intermediate_materializations: IntermediateMaterializationGroup::default(), // TODO: Need to get these somehow.
                     output_schema: resp.schema,
+                    partial: false,
+                    cache_key: None,
+                    cached_results: None,
                 })
             }
             _ => {
@@ -324,137 +781,392 @@ where
                 let binder = StatementBinder {
                     session_config: &self.config,
                     resolve_context: &resolve_context,
+                    allowed_statements: &self.allowed_statements,
                 };
                 let timer = Timer::<R::Instant>::start();
-                let (bound_stmt, mut bind_context) = binder.bind(stmt)?;
+                let (bound_stmt, mut bind_context) =
+                    info_span!("bind").in_scope(|| binder.bind(stmt))?;
                 profile.bind_step = Some(timer.stop());
 
                 let timer = Timer::<R::Instant>::start();
-                let mut logical = StatementPlanner.plan(&mut bind_context, bound_stmt)?;
+                let logical = info_span!("plan_logical")
+                    .in_scope(|| StatementPlanner.plan(&mut bind_context, bound_stmt))?;
                 profile.plan_logical_step = Some(timer.stop());
 
-                if self.config.enable_optimizer {
-                    let mut optimizer = Optimizer::new();
-                    logical = optimizer.optimize::<R::Instant>(&mut bind_context, logical)?;
-                    profile.optimizer_step = Some(optimizer.profile_data);
-                }
+                self.plan_local(
+                    logical,
+                    bind_context,
+                    LogicalPlanSource::Bound(plan_cache_key),
+                    profile,
+                )
+                .await
+            }
+        }
+    }
 
-                // If we're an explain, put a copy of the optimized plan on the
-                // node.
-                if let LogicalOperator::Explain(explain) = &mut logical {
-                    let child = explain
-                        .children
-                        .first()
-                        .ok_or_else(|| RayexecError::new("Missing explain child"))?;
-                    explain.node.logical_optimized = Some(Box::new(child.clone()));
-                }
+    /// Optimizes a logical plan and plans the intermediate pipelines for it.
+    ///
+    /// Freshly bound plans are put in the plan cache after going through the
+    /// normal optimizer passes if a cache key is provided. Plans from the cache
+    /// skip those passes, only the rules that depend on the current state of
+    /// tables are applied.
+    async fn plan_local(
+        &mut self,
+        mut logical: LogicalOperator,
+        mut bind_context: BindContext,
+        source: LogicalPlanSource,
+        profile: &mut PlanningProfileData,
+    ) -> Result<IntermediatePortal> {
+        let mut optimizer = if self.config.enable_optimizer {
+            let mut optimizer =
+                Optimizer::new().with_disabled_rules(self.config.disabled_optimizer_rules());
+            let capture_steps = matches!(
+                &logical,
+                LogicalOperator::Explain(explain) if explain.node.optimized_steps.is_some()
+            );
+            if capture_steps {
+                optimizer = optimizer.with_captured_steps();
+            }
+            Some(optimizer)
+        } else {
+            None
+        };
 
-                let schema = Schema::new(
-                    bind_context
-                        .iter_tables_in_scope(bind_context.root_scope_ref())?
-                        .flat_map(|t| {
-                            t.column_names
-                                .iter()
-                                .zip(&t.column_types)
-                                .map(|(name, datatype)| Field::new(name, datatype.clone(), true))
-                        }),
-                );
+        if let LogicalPlanSource::Bound(plan_cache_key) = source {
+            if let Some(optimizer) = &mut optimizer {
+                logical = info_span!("optimize")
+                    .in_scope(|| optimizer.optimize::<R::Instant>(&mut bind_context, logical))?;
+            }
 
-                let query_id = Uuid::new_v4();
-                let planner = IntermediatePipelinePlanner::new(
-                    IntermediatePlanConfig {
-                        allow_nested_loop_join: self.config.allow_nested_loop_join,
+            if let Some(key) = plan_cache_key {
+                self.plan_cache.insert(
+                    key,
+                    CachedPlan {
+                        plan: logical.clone(),
+                        bind_context: bind_context.clone(),
                     },
-                    query_id,
+                    self.config.plan_cache_size as usize,
                 );
+            }
+        }
 
-                let pipelines = match logical {
-                    LogicalOperator::AttachDatabase(attach) => {
-                        self.handle_attach_database(attach).await?;
-                        planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?
-                    }
-                    LogicalOperator::DetachDatabase(detach) => {
-                        let empty = planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?; // Here to avoid lifetime issues.
-                        self.context.detach_database(&detach.as_ref().name)?;
-                        empty
-                    }
-                    LogicalOperator::SetVar(set_var) => {
-                        // TODO: Do we want this logic to exist here?
-                        //
-                        // SET seems fine, but what happens with things like wanting to
-                        // update the catalog? Possibly an "external resources context"
-                        // that has clients/etc for everything that the session can look
-                        // at to update its local state?
-                        //
-                        // We could have an implementation for the local session, and a
-                        // separate implementation used for nodes taking part in
-                        // distributed execution.
-                        self.config
-                            .set_from_scalar(&set_var.node.name, set_var.node.value)?;
-                        planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?
-                    }
-                    LogicalOperator::ResetVar(reset) => {
-                        // Same TODO as above.
-                        match &reset.as_ref().var {
-                            VariableOrAll::Variable(v) => {
-                                self.config.reset(v, &self.executor, &self.runtime)?
-                            }
-                            VariableOrAll::All => {
-                                self.config.reset_all(&self.executor, &self.runtime)
-                            }
-                        }
-                        planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?
+        if let Some(mut optimizer) = optimizer {
+            // Needs the database context to check what tables support,
+            // so can't be part of the normal optimizer passes.
+            let rule = QueryPushdown::new(&self.context)
+                .with_ship_threshold(self.config.join_ship_threshold as usize);
+            logical = optimizer.apply_rule::<R::Instant>(
+                "query_pushdown",
+                rule,
+                &mut bind_context,
+                logical,
+            )?;
+            logical = optimizer.apply_rule::<R::Instant>(
+                "aggregate_pushdown",
+                AggregatePushdown::new(&self.context),
+                &mut bind_context,
+                logical,
+            )?;
+            logical = optimizer.apply_rule::<R::Instant>(
+                "index_scan",
+                IndexScanRule::new(&self.context),
+                &mut bind_context,
+                logical,
+            )?;
+            // Last since the join inputs need to be final to know what order
+            // they produce.
+            logical = optimizer.apply_rule::<R::Instant>(
+                "merge_join",
+                MergeJoinRule,
+                &mut bind_context,
+                logical,
+            )?;
+
+            // Steps are captured with the explain as the root, only
+            // keep the plans being explained.
+            if let (LogicalOperator::Explain(explain), Some(steps)) =
+                (&mut logical, optimizer.steps.take())
+            {
+                explain.node.optimized_steps = Some(
+                    steps
+                        .into_iter()
+                        .filter_map(|(name, mut plan)| {
+                            plan.children_mut().pop().map(|child| (name, child))
+                        })
+                        .collect(),
+                );
+            }
+
+            profile.optimizer_step = Some(optimizer.profile_data);
+        }
+
+        // Bound sources if we're in preview mode. Done after
+        // optimizing so that the limits don't get in the way of
+        // pushdowns.
+        let mut partial = false;
+        if self.config.preview_rows > 0 {
+            let mut preview = PreviewSample::new(self.config.preview_rows as usize);
+            logical = preview.optimize(&mut bind_context, logical)?;
+            partial = preview.applied;
+        }
+
+        // If we're an explain, put a copy of the optimized plan on the
+        // node.
+        if let LogicalOperator::Explain(explain) = &mut logical {
+            let child = explain
+                .children
+                .first()
+                .ok_or_else(|| RayexecError::new("Missing explain child"))?;
+            explain.node.logical_optimized = Some(Box::new(child.clone()));
+        }
+
+        let schema = Schema::new(
+            bind_context
+                .iter_tables_in_scope(bind_context.root_scope_ref())?
+                .flat_map(|t| {
+                    t.column_names
+                        .iter()
+                        .zip(&t.column_types)
+                        .map(|(name, datatype)| Field::new(name, datatype.clone(), true))
+                }),
+        );
+
+        let query_id = Uuid::new_v4();
+
+        // Preview results are partial, and shouldn't be cached.
+        let cache_key = if self.config.enable_result_cache && !partial {
+            ResultCacheKey::try_new(&logical, &self.context)?
+        } else {
+            None
+        };
+
+        if let Some(batches) = cache_key.as_ref().and_then(result_cache::get) {
+            return Ok(IntermediatePortal {
+                query_id,
+                execution_mode: ExecutionMode::LocalOnly,
+                intermediate_pipelines: IntermediatePipelineGroup::default(),
+                intermediate_materializations: IntermediateMaterializationGroup::default(),
+                output_schema: schema,
+                partial: false,
+                cache_key: None,
+                cached_results: Some(batches),
+            });
+        }
+
+        let planner = IntermediatePipelinePlanner::new(
+            IntermediatePlanConfig {
+                allow_nested_loop_join: self.config.allow_nested_loop_join,
+                target_partitions: Some(self.config.partitions as usize),
+                random_state: Some(self.random_state.clone()),
+                query_start_micros: Some(Utc::now().timestamp_micros()),
+                enable_runtime_filters: self.config.enable_runtime_filters,
+                hash_join_nested_loop_threshold: self.config.hash_join_nested_loop_threshold
+                    as usize,
+            },
+            query_id,
+        );
+
+        let pipelines = match logical {
+            LogicalOperator::AttachDatabase(attach) => {
+                self.handle_attach_database(attach).await?;
+                planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?
+            }
+            LogicalOperator::DetachDatabase(detach) => {
+                let empty = planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?; // Here to avoid lifetime issues.
+                self.context.detach_database(&detach.as_ref().name)?;
+                empty
+            }
+            LogicalOperator::CreateSecret(create) => {
+                let empty = planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?;
+                let create = create.into_inner();
+                self.context.secrets_mut().create_secret(
+                    create.name,
+                    create.secret,
+                    create.on_conflict,
+                )?;
+                empty
+            }
+            LogicalOperator::CreateFunction(create) => {
+                let empty = planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?;
+                let create = create.into_inner();
+                let tx = CatalogTx::new();
+                self.context
+                    .get_database(&create.catalog)?
+                    .catalog
+                    .get_schema(&tx, &create.schema)?
+                    .required("schema")?
+                    .create_scalar_function(
+                        &tx,
+                        &CreateScalarFunctionInfo {
+                            name: create.function.name().to_string(),
+                            implementation: create.function,
+                            on_conflict: create.on_conflict,
+                        },
+                    )?;
+                empty
+            }
+            LogicalOperator::CreateMacro(create) => {
+                let empty = planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?;
+                let create = create.into_inner();
+                let tx = CatalogTx::new();
+                let schema = self
+                    .context
+                    .get_database(&create.catalog)?
+                    .catalog
+                    .get_schema(&tx, &create.schema)?
+                    .required("schema")?;
+                if create.table_macro {
+                    schema.create_table_macro(&tx, &create.info)?;
+                } else {
+                    schema.create_scalar_macro(&tx, &create.info)?;
+                }
+                empty
+            }
+            LogicalOperator::AlterTable(alter) => {
+                let empty = planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?;
+                self.handle_alter_table(alter).await?;
+                empty
+            }
+            LogicalOperator::DropSecret(drop) => {
+                let empty = planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?;
+                let drop = drop.into_inner();
+                self.context
+                    .secrets_mut()
+                    .drop_secret(&drop.name, drop.if_exists)?;
+                empty
+            }
+            LogicalOperator::SetVar(set_var) => {
+                // TODO: Do we want this logic to exist here?
+                //
+                // SET seems fine, but what happens with things like wanting to
+                // update the catalog? Possibly an "external resources context"
+                // that has clients/etc for everything that the session can look
+                // at to update its local state?
+                //
+                // We could have an implementation for the local session, and a
+                // separate implementation used for nodes taking part in
+                // distributed execution.
+                let set_var = set_var.into_inner();
+                if set_var.local {
+                    // Keep the value from before the transaction so
+                    // we can restore it.
+                    if !self.local_settings.contains_key(&set_var.name) {
+                        let orig = self.config.get_as_scalar(&set_var.name)?;
+                        self.local_settings.insert(set_var.name.clone(), orig);
                     }
-                    root => {
-                        let timer = Timer::<R::Instant>::start();
-                        let pipelines = planner.plan_pipelines(root, bind_context)?;
-                        profile.plan_intermediate_step = Some(timer.stop());
-                        pipelines
+                } else {
+                    // Non-local SET persists beyond the transaction.
+                    self.local_settings.remove(&set_var.name);
+                }
+                self.config.set_from_scalar(&set_var.name, set_var.value)?;
+                self.plan_cache.clear_plans();
+                planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?
+            }
+            LogicalOperator::Transaction(tx) => {
+                self.handle_transaction(*tx.as_ref())?;
+                planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?
+            }
+            LogicalOperator::ResetVar(reset) => {
+                // Same TODO as above.
+                match &reset.as_ref().var {
+                    VariableOrAll::Variable(v) => {
+                        self.config.reset(v, &self.executor, &self.runtime)?
                     }
-                };
-
-                if !pipelines.remote.is_empty() {
-                    return Err(RayexecError::new(
-                        "Remote pipelines should not have been planned",
-                    ));
+                    VariableOrAll::All => self.config.reset_all(&self.executor, &self.runtime),
                 }
-
-                Ok(IntermediatePortal {
-                    query_id,
-                    execution_mode: ExecutionMode::LocalOnly,
-                    intermediate_pipelines: pipelines.local,
-                    intermediate_materializations: pipelines.materializations,
-                    output_schema: schema,
-                })
+                self.plan_cache.clear_plans();
+                planner.plan_pipelines(LogicalOperator::EMPTY, bind_context)?
+            }
+            root => {
+                let timer = Timer::<R::Instant>::start();
+                let pipelines = info_span!("plan_intermediate")
+                    .in_scope(|| planner.plan_pipelines(root, bind_context))?;
+                profile.plan_intermediate_step = Some(timer.stop());
+                pipelines
             }
+        };
+
+        if !pipelines.remote.is_empty() {
+            return Err(RayexecError::new(
+                "Remote pipelines should not have been planned",
+            ));
         }
+
+        Ok(IntermediatePortal {
+            query_id,
+            execution_mode: ExecutionMode::LocalOnly,
+            intermediate_pipelines: pipelines.local,
+            intermediate_materializations: pipelines.materializations,
+            output_schema: schema,
+            partial,
+            cache_key,
+            cached_results: None,
+        })
     }
 
     /// Executes the pipelines in the given portal.
     ///
     /// This will go through the final phase of planning (producing executable
     /// pipelines) then spawn those on the executor.
+    ///
+    /// If the engine is at its max number of concurrent queries, this waits
+    /// until the query is admitted before spawning anything.
+    ///
+    /// Unless called as part of a batch started with
+    /// `begin_implicit_transaction`, the statement is its own implicit
+    /// transaction and SET LOCAL settings are reverted once it's executed.
     pub async fn execute(&mut self, portal_name: &str) -> Result<ExecutionResult> {
+        let result = self.execute_inner(portal_name, true).await;
+        if !self.in_implicit_batch {
+            self.end_implicit_transaction()?;
+        }
+        result
+    }
+
+    /// Executes the pipelines in the given portal, optionally skipping
+    /// admission control.
+    ///
+    /// Admission is skipped when verifying a query, the verification query
+    /// runs under the original query's permit.
+    pub(super) async fn execute_inner(
+        &mut self,
+        portal_name: &str,
+        admit: bool,
+    ) -> Result<ExecutionResult> {
         let portal = self
             .portals
             .remove(portal_name)
             .ok_or_else(|| RayexecError::new(format!("Missing portal: '{portal_name}'")))?;
 
+        // Queries served from the cache don't have anything to execute.
+        if admit && !portal.executable_pipelines.is_empty() {
+            let permit = self
+                .admission
+                .admit()
+                .inspect_err(|e| portal.tracker.fail(e))?
+                .await;
+            // Released once the query completes.
+            portal.tracker.set_admission_permit(permit);
+        }
+
         if portal.execution_mode == ExecutionMode::Hybrid {
             // Need to begin execution on the remote side.
             let hybrid_client = self.hybrid_client.clone().required("hybrid_client")?;
             hybrid_client.remote_execute(portal.query_id).await?;
         }
 
-        let handle = self
-            .executor
-            .spawn_pipelines(portal.executable_pipelines, Arc::new(portal.error_sink));
+        let handle = self.executor.spawn_pipelines(
+            portal.executable_pipelines,
+            Arc::new(portal.error_sink),
+            self.resource_group.clone(),
+        );
 
         let exec_result = ExecutionResult {
             planning_profile: portal.profile,
             output_schema: portal.output_schema,
             stream: portal.result_stream,
             handle: handle.into(),
+            partial: portal.partial,
         };
 
         match portal.verifier {
@@ -470,10 +1182,66 @@ where
         }
     }
 
+    /// Begin, commit, or roll back an explicit transaction.
+    fn handle_transaction(&mut self, tx: LogicalTransaction) -> Result<()> {
+        match tx {
+            LogicalTransaction::Begin => {
+                if self.context.transaction().is_explicit() {
+                    return Err(RayexecError::new("Transaction already in progress")
+                        .with_kind(ErrorKind::InvalidTransactionState));
+                }
+                self.context.set_transaction(CatalogTx::begin());
+                Ok(())
+            }
+            LogicalTransaction::Commit | LogicalTransaction::Rollback => {
+                if !self.context.transaction().is_explicit() {
+                    return Err(RayexecError::new("No transaction in progress")
+                        .with_kind(ErrorKind::InvalidTransactionState));
+                }
+                let current = self.context.set_transaction(CatalogTx::new());
+                // SET LOCAL settings are reverted even if the commit fails.
+                self.end_implicit_transaction()?;
+                match tx {
+                    LogicalTransaction::Commit => current.commit(),
+                    _ => current.rollback(),
+                }
+            }
+        }
+    }
+
+    async fn handle_alter_table(&mut self, alter: Node<LogicalAlterTable>) -> Result<()> {
+        let alter = alter.into_inner();
+        let tx = CatalogTx::new();
+        let database = self.context.get_database(&alter.catalog)?;
+        let schema = database
+            .catalog
+            .get_schema(&tx, &alter.schema)?
+            .ok_or_else(|| {
+                RayexecError::new(format!("Missing schema: {}", alter.schema))
+                    .with_kind(ErrorKind::SchemaNotFound)
+            })?;
+        let storage = database.table_storage.as_ref().required("table_storage")?;
+
+        let (old, new) = match schema.alter_table(&tx, &alter.info, alter.if_exists)? {
+            Some(entries) => entries,
+            None => return Ok(()),
+        };
+
+        // Queries already bound to the old entry keep reading the table as it
+        // was. Storage only needs to make the new entry readable.
+        if let Err(e) = storage.alter_physical_table(&alter.schema, &new).await {
+            schema.revert_alter(&tx, &old, &new)?;
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
     async fn handle_attach_database(&mut self, attach: Node<LogicalAttachDatabase>) -> Result<()> {
         // TODO: This should always be client local. Is there a case where we
         // want to have that not be the cases? What would the behavior be.
-        let attach = attach.into_inner();
+        let mut attach = attach.into_inner();
+        self.context.secrets().apply_secret(&mut attach.options)?;
 
         let database = match self.registry.get_datasource(&attach.datasource) {
             Some(datasource) => {
@@ -486,6 +1254,7 @@ where
                 }
 
                 Database {
+                    id: Uuid::new_v4(),
                     catalog,
                     catalog_storage: connection.catalog_storage,
                     table_storage: Some(connection.table_storage),
@@ -512,6 +1281,7 @@ where
                 // Having no catalog storage will result in resolving always
                 // kicking out to hybrid execution.
                 Database {
+                    id: Uuid::new_v4(),
                     catalog: Arc::new(MemoryCatalog::default()),
                     catalog_storage: None,
                     table_storage: None,
@@ -528,6 +1298,82 @@ where
         Ok(())
     }
 
+    /// Registers a scalar function in this session's temp schema.
+    ///
+    /// Unqualified function references that can't be found in the system
+    /// catalog will be resolved against the temp schema, allowing embedders
+    /// (e.g. python) to provide their own UDFs.
+    ///
+    /// Replaces any existing function with the same name.
+    pub fn register_scalar_function(&mut self, function: Box<dyn ScalarFunction>) -> Result<()> {
+        let tx = CatalogTx::new();
+        let schema = self
+            .context
+            .get_database("temp")?
+            .catalog
+            .get_schema(&tx, "temp")?
+            .required("temp schema")?;
+
+        schema.create_scalar_function(
+            &tx,
+            &CreateScalarFunctionInfo {
+                name: function.name().to_string(),
+                implementation: function,
+                on_conflict: OnConflict::Replace,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Creates a table in this session's temp schema containing the provided
+    /// batches.
+    ///
+    /// Batches are expected to match the types of the provided columns.
+    pub async fn register_table(
+        &mut self,
+        name: &str,
+        columns: Vec<Field>,
+        batches: Vec<Batch>,
+    ) -> Result<()> {
+        let tx = CatalogTx::new();
+        let database = self.context.get_database("temp")?;
+        let schema = database
+            .catalog
+            .get_schema(&tx, "temp")?
+            .required("temp schema")?;
+
+        let ent = schema.create_table(
+            &tx,
+            &CreateTableInfo {
+                name: name.to_string(),
+                columns,
+                checks: Vec::new(),
+                primary_key: Vec::new(),
+                on_conflict: OnConflict::Error,
+            },
+        )?;
+
+        let table = database
+            .table_storage
+            .as_ref()
+            .required("temp table storage")?
+            .create_physical_table("temp", &ent)
+            .await?;
+
+        let mut inserts = table.insert(1)?;
+        let insert = inserts
+            .first_mut()
+            .ok_or_else(|| RayexecError::new("Missing insert sink for temp table"))?;
+
+        for batch in batches {
+            insert.push(batch).await?;
+        }
+        insert.finalize().await?;
+
+        Ok(())
+    }
+
     pub fn set_hybrid(&mut self, client: HybridClient<R::HttpClient>) {
         self.hybrid_client = Some(Arc::new(client));
     }
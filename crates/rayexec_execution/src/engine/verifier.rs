@@ -41,7 +41,7 @@ impl QueryVerifier {
         session.prepare(VERIFIED_NAME, self.statement.clone())?;
         session.bind(VERIFIED_NAME, VERIFIED_NAME).await?;
 
-        let result = session.execute(VERIFIED_NAME).await;
+        let result = session.execute_inner(VERIFIED_NAME, false).await;
 
         // Reset variables before returning any results.
         let config = session.config_mut();
@@ -1,11 +1,12 @@
 use std::fmt;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use rayexec_error::{RayexecError, Result};
-use tracing::trace;
+use tracing::{info_span, trace, Span};
 
-use super::profiler::OperatorProfileData;
+use super::profiler::{ExecutionProfileCollector, OperatorProfileData};
 use crate::arrays::batch::Batch;
 use crate::execution::computed_batch::ComputedBatches;
 use crate::execution::operators::{
@@ -107,6 +108,14 @@ impl ExecutablePipeline {
 
         Ok(())
     }
+
+    /// Set the collector that partition pipelines will write their profile
+    /// data to once they complete.
+    pub(crate) fn set_profile_collector(&mut self, collector: &Arc<ExecutionProfileCollector>) {
+        for partition in &mut self.partitions {
+            partition.profile_collector = Some(collector.clone());
+        }
+    }
 }
 
 impl Explainable for ExecutablePipeline {
@@ -139,6 +148,23 @@ pub struct ExecutablePartitionPipeline {
 
     /// Where to begin pulling from.
     pull_start: PullStart,
+
+    /// Number of times this pipeline has been executed.
+    executions: usize,
+
+    /// The operator we're waiting on if the last execution returned pending.
+    pending: Option<PendingOperator>,
+
+    /// Where to write profile data once this pipeline completes.
+    ///
+    /// Taken once the data has been written.
+    profile_collector: Option<Arc<ExecutionProfileCollector>>,
+
+    /// Span covering execution of this pipeline, entered on every execution.
+    ///
+    /// Created as a child of the current span (typically the span for the
+    /// query), and closed once the pipeline completes.
+    span: Span,
 }
 
 impl ExecutablePartitionPipeline {
@@ -154,6 +180,10 @@ impl ExecutablePartitionPipeline {
                 pull_start: 0,
                 pull_stack: Vec::new(),
             },
+            executions: 0,
+            pending: None,
+            profile_collector: None,
+            span: info_span!("execute_pipeline", pipeline_id = pipeline.0, partition),
         }
     }
 
@@ -175,6 +205,32 @@ impl ExecutablePartitionPipeline {
     pub fn operators(&self) -> &[OperatorWithState] {
         &self.operators
     }
+
+    /// Get the number of times this pipeline has been executed.
+    pub fn executions(&self) -> usize {
+        self.executions
+    }
+}
+
+/// Timer for tracking how long we've been waiting on a pending operator.
+trait PendingTimer: fmt::Debug + Sync + Send {
+    fn stop(self: Box<Self>) -> Duration;
+}
+
+impl<I> PendingTimer for Timer<I>
+where
+    I: RuntimeInstant + fmt::Debug + Sync + Send,
+{
+    fn stop(self: Box<Self>) -> Duration {
+        Timer::stop(*self)
+    }
+}
+
+/// An operator that returned pending.
+#[derive(Debug)]
+struct PendingOperator {
+    operator_idx: usize,
+    timer: Box<dyn PendingTimer>,
 }
 
 /// Where to begin pulling from after pushing a batch through the pipeline.
@@ -281,6 +337,18 @@ pub enum PipelinePartitionState {
     Completed,
 }
 
+impl PipelinePartitionState {
+    /// Get the index of the operator this state is operating on.
+    fn operator_idx(&self) -> Option<usize> {
+        match self {
+            Self::PullFromOperator { operator_idx }
+            | Self::PushTo { operator_idx, .. }
+            | Self::FinalizePush { operator_idx } => Some(*operator_idx),
+            Self::Completed => None,
+        }
+    }
+}
+
 impl fmt::Debug for PipelinePartitionState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -315,6 +383,11 @@ impl ExecutablePartitionPipeline {
     /// the pull state gets reset such that this will begin pulling from the
     /// first non-exhausted operator.
     ///
+    /// `Poll::Ready(Some(Ok(())))` is returned each time a batch has been
+    /// fully pushed through the pipeline (or consumed by an operator needing
+    /// more input). The caller should call this again, but gets a chance to
+    /// yield to other pipelines first.
+    ///
     /// When an operator is exhausted (no more batches to pull), `finalize_push`
     /// is called on the _next_ operator, and we begin pulling from the _next_
     /// operator until it's exhausted.
@@ -324,16 +397,61 @@ impl ExecutablePartitionPipeline {
     /// We set the state to skip pulling from all previous operators even if
     /// they've not been exhausted. An example operator that would emit a Break
     /// is LIMIT.
+    ///
+    /// Profile data for the pipeline is written to the profile collector (if
+    /// set) once the pipeline completes or errors.
     pub fn poll_execute<I>(&mut self, cx: &mut Context) -> Poll<Option<Result<()>>>
     where
-        I: RuntimeInstant,
+        I: RuntimeInstant + fmt::Debug + Sync + Send + 'static,
     {
+        let span = self.span.clone();
+        let _entered = span.enter();
+
         trace!(
             pipeline_id = %self.info.pipeline.0,
             partition = %self.info.partition,
             "executing partition pipeline",
         );
 
+        self.executions += 1;
+
+        // Time since we last returned pending gets attributed to the operator
+        // that returned pending.
+        if let Some(pending) = self.pending.take() {
+            if let Some(operator) = self.operators.get_mut(pending.operator_idx) {
+                operator.profile_data.wait_elapsed += pending.timer.stop();
+            }
+        }
+
+        let poll = self.poll_execute_inner::<I>(cx);
+
+        match &poll {
+            Poll::Pending => {
+                if let Some(operator_idx) = self.state.operator_idx() {
+                    self.operators[operator_idx].profile_data.pending += 1;
+                    self.pending = Some(PendingOperator {
+                        operator_idx,
+                        timer: Box::new(Timer::<I>::start()),
+                    });
+                }
+            }
+            Poll::Ready(None) | Poll::Ready(Some(Err(_))) => {
+                if let Some(collector) = self.profile_collector.take() {
+                    collector.add_partition_data(self);
+                }
+                // Nothing left to execute, close the span.
+                self.span = Span::none();
+            }
+            Poll::Ready(Some(Ok(_))) => (),
+        }
+
+        poll
+    }
+
+    fn poll_execute_inner<I>(&mut self, cx: &mut Context) -> Poll<Option<Result<()>>>
+    where
+        I: RuntimeInstant,
+    {
         let state = &mut self.state;
 
         loop {
@@ -345,6 +463,7 @@ impl ExecutablePartitionPipeline {
                         .expect("operator to exist");
 
                     // Otherwise do a normal pull.
+                    operator.profile_data.polls += 1;
                     let timer = Timer::<I>::start();
                     let poll_pull = operator.physical.poll_pull(
                         cx,
@@ -415,6 +534,7 @@ impl ExecutablePartitionPipeline {
                         .get_mut(*operator_idx)
                         .expect("next operator to exist");
 
+                    next_operator.profile_data.polls += 1;
                     let timer = Timer::<I>::start();
                     let poll_finalize = next_operator.physical.poll_finalize_push(
                         cx,
@@ -458,6 +578,7 @@ impl ExecutablePartitionPipeline {
                         .expect("operator to exist");
 
                     operator.profile_data.rows_read += batch.num_rows();
+                    operator.profile_data.polls += 1;
 
                     let timer = Timer::<I>::start();
                     let poll_push = operator.physical.poll_push(
@@ -480,6 +601,7 @@ impl ExecutablePartitionPipeline {
                                 // Next iteration will pull from the first
                                 // non-exhausted operator.
                                 *state = self.pull_start.next_start_state()?;
+                                return Poll::Ready(Some(Ok(())));
                             } else {
                                 // Otherwise we should just pull from the
                                 // operator we just pushed to.
@@ -528,7 +650,7 @@ impl ExecutablePartitionPipeline {
                             // pipline to produce more batches.
                             assert_ne!(0, *operator_idx);
                             *state = self.pull_start.next_start_state()?;
-                            continue;
+                            return Poll::Ready(Some(Ok(())));
                         }
                         Err(e) => {
                             // Errors currently unrecoverable.
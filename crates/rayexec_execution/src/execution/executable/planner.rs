@@ -20,6 +20,7 @@ use crate::execution::operators::materialize::MaterializeOperation;
 use crate::execution::operators::round_robin::PhysicalRoundRobinRepartition;
 use crate::execution::operators::sink::{SinkOperation, SinkOperator};
 use crate::execution::operators::source::{SourceOperation, SourceOperator};
+use crate::execution::operators::util::batch_width::batch_size_for_row_width;
 use crate::execution::operators::{
     ExecutableOperator,
     InputOutputStates,
@@ -29,7 +30,8 @@ use crate::execution::operators::{
 };
 use crate::hybrid::buffer::ServerStreamBuffers;
 use crate::hybrid::client::HybridClient;
-use crate::hybrid::stream::{ClientToServerStream, ServerToClientStream};
+use crate::hybrid::exchange::ExchangeSink;
+use crate::hybrid::stream::ServerToClientStream;
 use crate::logical::binder::bind_context::MaterializationRef;
 use crate::runtime::Runtime;
 
@@ -124,6 +126,8 @@ struct PendingQuery {
     pipelines: HashMap<IntermediatePipelineId, PendingPipeline>,
     /// Pending materializations in the query.
     materializations: HashMap<MaterializationRef, PendingMaterialization>,
+    /// Target batch size for operators created during executable planning.
+    batch_size: usize,
 }
 
 impl PendingQuery {
@@ -253,6 +257,7 @@ impl PendingQuery {
             operators,
             pipelines: pending_pipelines,
             materializations: pending_materializations,
+            batch_size: config.batch_size,
         })
     }
 
@@ -297,6 +302,8 @@ impl PendingQuery {
             operator_indices.next();
         }
 
+        let batch_size = self.batch_size;
+
         // Wire up the rest.
         for operator_idx in operator_indices {
             let operator = self.get_operator_mut(*operator_idx)?;
@@ -307,6 +314,7 @@ impl PendingQuery {
             if partition_states.len() != pipeline.num_partitions() {
                 pipeline = Self::push_repartition(
                     context,
+                    batch_size,
                     id_gen,
                     pipeline,
                     partition_states.len(),
@@ -358,12 +366,18 @@ impl PendingQuery {
                 };
 
                 if partitions != pipeline.num_partitions() {
-                    pipeline =
-                        Self::push_repartition(context, id_gen, pipeline, partitions, executables)?;
+                    pipeline = Self::push_repartition(
+                        context,
+                        self.batch_size,
+                        id_gen,
+                        pipeline,
+                        partitions,
+                        executables,
+                    )?;
                 }
 
                 let operator = Arc::new(PhysicalOperator::ResultSink(SinkOperator::new(sink)));
-                let states = operator.create_states(context, vec![partitions])?;
+                let states = operator.create_states(context, self.batch_size, vec![partitions])?;
                 let partition_states = match states.partition_states {
                     InputOutputStates::OneToOne { partition_states } => partition_states,
                     _ => return Err(RayexecError::new("invalid partition states for query sink")),
@@ -385,6 +399,7 @@ impl PendingQuery {
             } => {
                 // We have the sink pipeline with us, wire up directly.
 
+                let batch_size = self.batch_size;
                 let pending = self.pipelines.get(&pipeline_id).unwrap();
                 let operator = self.get_operator_mut(pending.operators[operator_idx])?;
                 let partition_states = operator.take_input_states(input_idx)?;
@@ -392,6 +407,7 @@ impl PendingQuery {
                 if partition_states.len() != pipeline.num_partitions() {
                     pipeline = Self::push_repartition(
                         context,
+                        batch_size,
                         id_gen,
                         pipeline,
                         partition_states.len(),
@@ -410,10 +426,16 @@ impl PendingQuery {
             PipelineSink::OtherGroup {
                 stream_id,
                 partitions,
+                ref hash_columns,
             } => {
                 // Sink is pipeline executing somewhere else.
                 let operator: SinkOperator<Box<dyn SinkOperation>> = match loc_state {
                     PlanLocationState::Server { stream_buffers } => {
+                        if partitions != 1 || !hash_columns.is_empty() {
+                            return Err(RayexecError::new(
+                                "Outgoing streams only support a single partition",
+                            ));
+                        }
                         let sink = stream_buffers.create_outgoing_stream(stream_id)?;
                         SinkOperator::new(Box::new(sink))
                     }
@@ -424,12 +446,25 @@ impl PendingQuery {
                         let hybrid_client = hybrid_client.ok_or_else(|| {
                             RayexecError::new("Hybrid client missing, cannot create sink pipeline")
                         })?;
-                        let sink = ClientToServerStream::new(stream_id, hybrid_client.clone());
+                        let sink = ExchangeSink::new(
+                            stream_id,
+                            hybrid_client.clone(),
+                            partitions,
+                            hash_columns.clone(),
+                        );
                         SinkOperator::new(Box::new(sink))
                     }
                 };
 
-                let states = operator.create_states(context, vec![partitions])?;
+                // Exchanges accept any number of local partitions, they're
+                // distributed to the remote partitions when sending.
+                let local_partitions = match operator.sink.partition_requirement() {
+                    Some(n) => n,
+                    None => pipeline.num_partitions(),
+                };
+
+                let states =
+                    operator.create_states(context, self.batch_size, vec![local_partitions])?;
                 let partition_states = match states.partition_states {
                     InputOutputStates::OneToOne { partition_states } => partition_states,
                     _ => return Err(RayexecError::new("invalid partition states")),
@@ -438,6 +473,7 @@ impl PendingQuery {
                 if partition_states.len() != pipeline.num_partitions() {
                     pipeline = Self::push_repartition(
                         context,
+                        self.batch_size,
                         id_gen,
                         pipeline,
                         partition_states.len(),
@@ -536,7 +572,9 @@ impl PendingQuery {
                 // Set up hybrid operator.
                 let operator = match &loc_state {
                     PlanLocationState::Server { stream_buffers } => {
-                        let source = stream_buffers.create_incoming_stream(stream_id)?;
+                        // Only the client sends to the server.
+                        let source =
+                            stream_buffers.create_incoming_stream(stream_id, partitions, 1)?;
                         SourceOperator::new(Box::new(source) as Box<dyn SourceOperation>)
                     }
                     PlanLocationState::Client { hybrid_client, .. } => {
@@ -549,7 +587,7 @@ impl PendingQuery {
                     }
                 };
 
-                let states = operator.create_states(context, vec![partitions])?;
+                let states = operator.create_states(context, self.batch_size, vec![partitions])?;
                 let partition_states = match states.partition_states {
                     InputOutputStates::OneToOne { partition_states } => partition_states,
                     _ => {
@@ -603,14 +641,18 @@ impl PendingQuery {
     /// with the repartition as the source.
     fn push_repartition(
         context: &DatabaseContext,
+        batch_size: usize,
         id_gen: &mut PipelineIdGen,
         mut pipeline: ExecutablePipeline,
         output_partitions: usize,
         pipelines: &mut Vec<ExecutablePipeline>,
     ) -> Result<ExecutablePipeline> {
         let rr_operator = Arc::new(PhysicalOperator::RoundRobin(PhysicalRoundRobinRepartition));
-        let states = rr_operator
-            .create_states(context, vec![pipeline.num_partitions(), output_partitions])?;
+        let states = rr_operator.create_states(
+            context,
+            batch_size,
+            vec![pipeline.num_partitions(), output_partitions],
+        )?;
 
         let (push_states, pull_states) = match states.partition_states {
             InputOutputStates::SeparateInputOutput {
@@ -695,8 +737,20 @@ impl PendingOperatorWithState {
             .partitioning_requirement
             .unwrap_or(config.partitions);
 
+        // Scans may size their batches by estimated row width if we have a
+        // byte budget. Everything else uses the configured row count.
+        let batch_size = match config.batch_size_bytes {
+            Some(target) => match operator.operator.estimated_scan_row_width()? {
+                Some(width) => batch_size_for_row_width(target, width),
+                None => config.batch_size,
+            },
+            None => config.batch_size,
+        };
+
         // TODO: How to get other input partitions.
-        let states = operator.operator.create_states(context, vec![partitions])?;
+        let states = operator
+            .operator
+            .create_states(context, batch_size, vec![partitions])?;
 
         Ok(match states.partition_states {
             InputOutputStates::OneToOne { partition_states } => PendingOperatorWithState {
@@ -2,6 +2,8 @@ use std::collections::BTreeMap;
 use std::fmt;
 use std::time::Duration;
 
+use parking_lot::Mutex;
+
 use super::pipeline::{ExecutablePartitionPipeline, PipelineId};
 use crate::explain::context_display::ContextDisplayMode;
 use crate::explain::explainable::ExplainConfig;
@@ -17,6 +19,7 @@ impl ExecutionProfileData {
         let pipeline_data = self.pipelines.entry(partition.pipeline_id()).or_default();
 
         let partition_data = PartitionPipelineProfileData {
+            executions: partition.executions(),
             operators: partition
                 .operators()
                 .iter()
@@ -42,19 +45,47 @@ impl ExecutionProfileData {
     }
 }
 
+/// Collects profile data from partition pipelines as they complete.
+///
+/// Shared across all partition pipelines for a query so that profile data
+/// outlives the query handle.
+#[derive(Debug, Default)]
+pub struct ExecutionProfileCollector {
+    data: Mutex<ExecutionProfileData>,
+}
+
+impl ExecutionProfileCollector {
+    pub fn add_partition_data(&self, partition: &ExecutablePartitionPipeline) {
+        self.data.lock().add_partition_data(partition)
+    }
+
+    /// Get the profile data for all partition pipelines that have completed so
+    /// far.
+    pub fn snapshot(&self) -> ExecutionProfileData {
+        self.data.lock().clone()
+    }
+}
+
 impl fmt::Display for ExecutionProfileData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for (id, pipeline) in &self.pipelines {
             writeln!(f, "Pipeline {id:?}")?;
 
             for (id, partition) in &pipeline.partitions {
-                writeln!(f, "  Partition {id}")?;
+                writeln!(f, "  Partition {id} (executions: {})", partition.executions)?;
 
                 #[allow(clippy::write_literal)]
                 writeln!(
                     f,
-                    "    [{:>2}]  {:>8}  {:>8}  {:>16}  {}",
-                    "Op", "Read", "Emitted", "Elapsed (micro)", "Explain",
+                    "    [{:>2}]  {:>8}  {:>8}  {:>8}  {:>8}  {:>16}  {:>16}  {}",
+                    "Op",
+                    "Read",
+                    "Emitted",
+                    "Polls",
+                    "Pending",
+                    "Elapsed (micro)",
+                    "Wait (micro)",
+                    "Explain",
                 )?;
 
                 for (idx, (operator, explain)) in partition
@@ -65,11 +96,14 @@ impl fmt::Display for ExecutionProfileData {
                 {
                     writeln!(
                         f,
-                        "    [{:>2}]  {:>8}  {:>8}  {:>16}  {}",
+                        "    [{:>2}]  {:>8}  {:>8}  {:>8}  {:>8}  {:>16}  {:>16}  {}",
                         idx,
                         operator.rows_read,
                         operator.rows_emitted,
+                        operator.polls,
+                        operator.pending,
                         operator.elapsed.as_micros(),
+                        operator.wait_elapsed.as_micros(),
                         explain,
                     )?;
                 }
@@ -90,6 +124,8 @@ pub struct PipelineProfileData {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PartitionPipelineProfileData {
+    /// Number of times the partition pipeline was executed by the scheduler.
+    pub executions: usize,
     /// Profile data for all operators in this partition pipeline.
     pub operators: Vec<OperatorProfileData>,
     // TODO: This is here just to help debug. Evetually I want to just be able
@@ -106,4 +142,14 @@ pub struct OperatorProfileData {
     pub rows_emitted: usize,
     /// Elapsed time while activley executing this operator.
     pub elapsed: Duration,
+    /// Number of times this operator was polled.
+    pub polls: usize,
+    /// Number of times this operator returned pending.
+    pub pending: usize,
+    /// Time spent waiting for the pipeline to be executed again after this
+    /// operator returned pending.
+    ///
+    /// Includes both the time waiting on the operator to wake the pipeline,
+    /// and the time waiting on the scheduler to pick the pipeline back up.
+    pub wait_elapsed: Duration,
 }
@@ -92,6 +92,11 @@ pub enum PipelineSink {
         stream_id: StreamId,
         /// Number of partitions the receiving pipeline expects.
         partitions: usize,
+        /// Columns to hash when picking the receiving partition for a row.
+        ///
+        /// If empty, batches are distributed round robin across the receiving
+        /// partitions.
+        hash_columns: Vec<usize>,
     },
     /// Sink is into a materialization operator.
     Materialization { mat_ref: MaterializationRef },
@@ -123,9 +128,11 @@ impl ProtoConv for PipelineSink {
             Self::OtherGroup {
                 partitions,
                 stream_id,
+                hash_columns,
             } => Value::OtherGroup(PipelineSinkOtherGroup {
                 stream_id: Some(stream_id.to_proto()?),
                 partitions: *partitions as u32,
+                hash_columns: hash_columns.iter().map(|&c| c as u32).collect(),
             }),
             Self::Materialization { mat_ref } => {
                 Value::Materialization(PipelineSinkMaterialization {
@@ -160,9 +167,11 @@ impl ProtoConv for PipelineSink {
             Value::OtherGroup(PipelineSinkOtherGroup {
                 stream_id,
                 partitions,
+                hash_columns,
             }) => Self::OtherGroup {
                 stream_id: StreamId::from_proto(stream_id.required("stream_id")?)?,
                 partitions: partitions as usize,
+                hash_columns: hash_columns.into_iter().map(|c| c as usize).collect(),
             },
             Value::Materialization(PipelineSinkMaterialization {
                 materialization_ref,
@@ -355,6 +364,52 @@ pub struct IntermediatePipeline {
     pub(crate) operators: Vec<IntermediateOperator>,
 }
 
+impl IntermediatePipeline {
+    /// Returns the number of partitions each operator in this pipeline will
+    /// execute with.
+    ///
+    /// This mirrors how the executable planner resolves partitioning.
+    /// Operators without a partitioning requirement use the target number of
+    /// partitions, and a repartition is inserted between operators with
+    /// differing partitions.
+    pub fn operator_partitions(&self, target_partitions: usize) -> Vec<usize> {
+        self.operators
+            .iter()
+            .enumerate()
+            .map(|(idx, operator)| {
+                let requirement = match (&self.source, operator.partitioning_requirement) {
+                    (
+                        PipelineSource::OtherPipeline {
+                            partitioning_requirement,
+                            ..
+                        },
+                        None,
+                    ) if idx == 0 => *partitioning_requirement,
+                    (_, requirement) => requirement,
+                };
+                requirement.unwrap_or(target_partitions)
+            })
+            .collect()
+    }
+
+    /// Returns the number of partitions this pipeline's source produces.
+    pub fn source_partitions(&self, target_partitions: usize) -> usize {
+        match &self.source {
+            PipelineSource::InPipeline => self
+                .operator_partitions(target_partitions)
+                .first()
+                .copied()
+                .unwrap_or(target_partitions),
+            PipelineSource::OtherPipeline {
+                partitioning_requirement,
+                ..
+            } => partitioning_requirement.unwrap_or(target_partitions),
+            PipelineSource::OtherGroup { partitions, .. } => *partitions,
+            PipelineSource::Materialization { .. } => target_partitions,
+        }
+    }
+}
+
 impl DatabaseProtoConv for IntermediatePipeline {
     type ProtoType = rayexec_proto::generated::execution::IntermediatePipeline;
 
@@ -421,9 +476,18 @@ impl DatabaseProtoConv for IntermediateOperator {
 
 impl Explainable for IntermediateOperator {
     fn explain_entry(&self, conf: ExplainConfig) -> ExplainEntry {
-        self.operator.explain_entry(conf).with_value(
-            "partitioning_requirement",
-            format!("{:?}", self.partitioning_requirement),
-        )
+        let execution = if self.operator.is_blocking() {
+            "blocking"
+        } else {
+            "streaming"
+        };
+
+        self.operator
+            .explain_entry(conf)
+            .with_value("execution", execution)
+            .with_value(
+                "partitioning_requirement",
+                format!("{:?}", self.partitioning_requirement),
+            )
     }
 }
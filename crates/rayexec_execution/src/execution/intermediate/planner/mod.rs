@@ -16,6 +16,7 @@ mod plan_limit;
 mod plan_magic_scan;
 mod plan_materialize_scan;
 mod plan_project;
+mod plan_sample;
 mod plan_scan;
 mod plan_set_operation;
 mod plan_show_var;
@@ -40,9 +41,11 @@ use super::pipeline::{
 };
 use crate::config::execution::IntermediatePlanConfig;
 use crate::execution::operators::batch_resizer::PhysicalBatchResizer;
+use crate::execution::operators::runtime_filter::ScanRuntimeFilter;
 use crate::execution::operators::PhysicalOperator;
 use crate::expr::physical::planner::PhysicalExpressionPlanner;
 use crate::logical::binder::bind_context::BindContext;
+use crate::logical::binder::table_list::TableRef;
 use crate::logical::operator::{self, LocationRequirement, LogicalOperator};
 
 /// Planned pipelines grouped into locations for where they should be executed.
@@ -141,6 +144,30 @@ struct InProgressPipeline {
     source: PipelineSource,
 }
 
+impl InProgressPipeline {
+    /// Returns the number of partitions the last operator in the pipeline will
+    /// execute with, or None if it'll execute with the target number of
+    /// partitions.
+    ///
+    /// If there are no operators yet, returns the partitioning the next pushed
+    /// operator will inherit from the source.
+    fn output_partitioning(&self) -> Option<usize> {
+        let inherited = match &self.source {
+            PipelineSource::OtherPipeline {
+                partitioning_requirement,
+                ..
+            } => *partitioning_requirement,
+            _ => None,
+        };
+
+        match self.operators.as_slice() {
+            [] => inherited,
+            [operator] => operator.partitioning_requirement.or(inherited),
+            [.., operator] => operator.partitioning_requirement,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct IntermediatePipelineBuildState<'a> {
     config: &'a IntermediatePlanConfig,
@@ -160,11 +187,24 @@ struct IntermediatePipelineBuildState<'a> {
     bind_context: &'a BindContext,
     /// Expression planner for converting logical to physical expressions.
     expr_planner: PhysicalExpressionPlanner<'a>,
+    /// Runtime filters created by joins waiting for their probe side scans to
+    /// be planned.
+    runtime_filters: Vec<PendingRuntimeFilter>,
+}
+
+/// A runtime filter to push into a scan.
+#[derive(Debug)]
+struct PendingRuntimeFilter {
+    /// Table ref for the scan the filter applies to.
+    table_ref: TableRef,
+    filter: ScanRuntimeFilter,
 }
 
 impl<'a> IntermediatePipelineBuildState<'a> {
     fn new(config: &'a IntermediatePlanConfig, bind_context: &'a BindContext) -> Self {
-        let expr_planner = PhysicalExpressionPlanner::new(bind_context.get_table_list());
+        let expr_planner = PhysicalExpressionPlanner::new(bind_context.get_table_list())
+            .with_random_state(config.random_state.as_ref())
+            .with_query_start_micros(config.query_start_micros);
 
         IntermediatePipelineBuildState {
             config,
@@ -173,9 +213,20 @@ impl<'a> IntermediatePipelineBuildState<'a> {
             remote_group: IntermediatePipelineGroup::default(),
             bind_context,
             expr_planner,
+            runtime_filters: Vec::new(),
         }
     }
 
+    /// Take all pending runtime filters for a scan.
+    fn take_runtime_filters(&mut self, table_ref: TableRef) -> Vec<ScanRuntimeFilter> {
+        let (filters, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.runtime_filters)
+            .into_iter()
+            .partition(|pending| pending.table_ref == table_ref);
+        self.runtime_filters = pending;
+
+        filters.into_iter().map(|pending| pending.filter).collect()
+    }
+
     /// Plan materializations from the bind context.
     fn plan_materializations(&mut self, id_gen: &mut PipelineIdGen) -> Result<Materializations> {
         // TODO: The way this and the materialization ref is implemented allows
@@ -239,6 +290,7 @@ impl<'a> IntermediatePipelineBuildState<'a> {
             LogicalOperator::Empty(empty) => self.plan_empty(id_gen, empty),
             LogicalOperator::Aggregate(agg) => self.plan_aggregate(id_gen, materializations, agg),
             LogicalOperator::Limit(limit) => self.plan_limit(id_gen, materializations, limit),
+            LogicalOperator::Sample(sample) => self.plan_sample(id_gen, materializations, sample),
             LogicalOperator::Order(order) => self.plan_sort(id_gen, materializations, order),
             LogicalOperator::ShowVar(show_var) => self.plan_show_var(id_gen, show_var),
             LogicalOperator::Explain(explain) => {
@@ -261,7 +313,7 @@ impl<'a> IntermediatePipelineBuildState<'a> {
             LogicalOperator::MagicMaterializationScan(scan) => {
                 self.plan_magic_materialize_scan(id_gen, materializations, scan)
             }
-            LogicalOperator::Scan(scan) => self.plan_scan(id_gen, scan),
+            LogicalOperator::Scan(scan) => self.plan_scan(id_gen, materializations, scan),
             LogicalOperator::SetOp(setop) => {
                 self.plan_set_operation(id_gen, materializations, setop)
             }
@@ -272,9 +324,24 @@ impl<'a> IntermediatePipelineBuildState<'a> {
             LogicalOperator::ResetVar(_) => {
                 Err(RayexecError::new("RESET should be handled in the session"))
             }
+            LogicalOperator::Transaction(_) => Err(RayexecError::new(
+                "BEGIN/COMMIT/ROLLBACK should be handled in the session",
+            )),
             LogicalOperator::DetachDatabase(_) | LogicalOperator::AttachDatabase(_) => Err(
                 RayexecError::new("ATTACH/DETACH should be handled in the session"),
             ),
+            LogicalOperator::CreateSecret(_) | LogicalOperator::DropSecret(_) => Err(
+                RayexecError::new("CREATE/DROP SECRET should be handled in the session"),
+            ),
+            LogicalOperator::CreateFunction(_) => Err(RayexecError::new(
+                "CREATE FUNCTION should be handled in the session",
+            )),
+            LogicalOperator::CreateMacro(_) => Err(RayexecError::new(
+                "CREATE MACRO should be handled in the session",
+            )),
+            LogicalOperator::AlterTable(_) => Err(RayexecError::new(
+                "ALTER TABLE should be handled in the session",
+            )),
             other => not_implemented!("logical plan to pipeline: {other:?}"),
         }
     }
@@ -396,6 +463,7 @@ impl<'a> IntermediatePipelineBuildState<'a> {
                 sink: PipelineSink::OtherGroup {
                     stream_id,
                     partitions: 1,
+                    hash_columns: Vec::new(),
                 },
                 source: in_progress.source,
                 operators: in_progress.operators,
@@ -419,6 +487,20 @@ impl<'a> IntermediatePipelineBuildState<'a> {
         Ok(())
     }
 
+    /// Require every operator on the in-progress pipeline to run with a
+    /// single partition.
+    ///
+    /// Used to keep the order of a sorted input, which is only produced as a
+    /// single partition. Operators without a requirement would otherwise be
+    /// repartitioned.
+    fn keep_single_partition(&mut self) -> Result<()> {
+        let in_progress = self.in_progress_pipeline_mut()?;
+        for operator in &mut in_progress.operators {
+            operator.partitioning_requirement = Some(1);
+        }
+        Ok(())
+    }
+
     /// Pushes a batch resizer onto the current pipline.
     ///
     /// If the latest operator is already a batch resizer operator, we skip
@@ -474,6 +556,7 @@ impl<'a> IntermediatePipelineBuildState<'a> {
                 sink: PipelineSink::OtherGroup {
                     stream_id,
                     partitions: 1,
+                    hash_columns: Vec::new(),
                 },
                 source: in_progress.source,
                 operators: in_progress.operators,
@@ -1,18 +1,21 @@
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use rayexec_error::{RayexecError, Result, ResultExt};
 
 use super::{IntermediatePipelineBuildState, Materializations, PipelineIdGen};
+use crate::arrays::datatype::DataType;
 use crate::execution::intermediate::pipeline::IntermediateOperator;
 use crate::execution::operators::hash_aggregate::PhysicalHashAggregate;
 use crate::execution::operators::project::{PhysicalProject, ProjectOperation};
 use crate::execution::operators::ungrouped_aggregate::PhysicalUngroupedAggregate;
 use crate::execution::operators::PhysicalOperator;
 use crate::expr::physical::column_expr::PhysicalColumnExpr;
-use crate::expr::physical::PhysicalAggregateExpression;
-use crate::expr::Expression;
-use crate::logical::logical_aggregate::LogicalAggregate;
-use crate::logical::operator::{LogicalNode, Node};
+use crate::expr::physical::{PhysicalAggregateExpression, PhysicalSortExpression};
+use crate::expr::{self, Expression};
+use crate::logical::binder::table_list::TableList;
+use crate::logical::logical_aggregate::{GroupingFunction, LogicalAggregate};
+use crate::logical::operator::{LocationRequirement, LogicalNode, Node};
 
 impl IntermediatePipelineBuildState<'_> {
     pub fn plan_aggregate(
@@ -51,18 +54,40 @@ impl IntermediatePipelineBuildState<'_> {
             }
             let end_col_index = preproject_exprs.len();
 
+            // Ordering keys follow the inputs.
+            let mut order_by = Vec::with_capacity(agg.order_by.len());
+            for expr in &agg.order_by {
+                let scalar = self
+                    .expr_planner
+                    .plan_scalar(&input_refs, &expr.expr)
+                    .context("Failed to plan ordering for aggregate pre-projection")?;
+                order_by.push(PhysicalSortExpression {
+                    column: PhysicalColumnExpr {
+                        idx: preproject_exprs.len(),
+                    },
+                    desc: expr.desc,
+                    nulls_first: expr.nulls_first,
+                });
+                preproject_exprs.push(scalar);
+            }
+
+            let input_types = self.expr_planner.input_types(&agg.agg.inputs)?;
+
             let phys_agg = PhysicalAggregateExpression {
                 function: agg.agg,
                 columns: (start_col_index..end_col_index)
                     .map(|idx| PhysicalColumnExpr { idx })
                     .collect(),
+                input_types,
                 is_distinct: agg.distinct,
+                order_by,
             };
 
             phys_aggs.push(phys_agg);
         }
 
         // Place group by expressions in pre-projection as well.
+        let group_types = self.expr_planner.input_types(&agg.node.group_exprs)?;
         for group_expr in agg.node.group_exprs {
             let scalar = self
                 .expr_planner
@@ -72,6 +97,39 @@ impl IntermediatePipelineBuildState<'_> {
             preproject_exprs.push(scalar);
         }
 
+        // Rows are sent to the aggregate across an exchange if the input is
+        // executing somewhere else, and repartitioned if the input executes
+        // with a different number of partitions than the aggregate. In either
+        // case, try to aggregate with the input first so that only partial
+        // results are exchanged or repartitioned.
+        let input = self.in_progress_pipeline_mut()?;
+        let input_location = input.location;
+        let input_partitions = input.output_partitioning();
+
+        let exchanged = input_location != LocationRequirement::Any
+            && location != LocationRequirement::Any
+            && input_location != location;
+        let repartitioned = input_partitions
+            .is_some_and(|partitions| self.config.target_partitions != Some(partitions));
+
+        let final_aggs = if exchanged || repartitioned {
+            plan_final_aggregates(
+                &phys_aggs,
+                &group_types,
+                agg.node.grouping_sets.as_deref(),
+                &agg.node.grouping_functions,
+            )?
+        } else {
+            None
+        };
+
+        // Pre-projection and partial aggregate run with the input.
+        let (partial_location, partial_partitions) = match final_aggs {
+            Some(_) if exchanged => (input_location, None),
+            Some(_) => (location, input_partitions),
+            None => (location, None),
+        };
+
         // // Resize batches prior to pre-projection.
         // self.push_batch_resizer(id_gen)?;
 
@@ -80,24 +138,63 @@ impl IntermediatePipelineBuildState<'_> {
                 operator: Arc::new(PhysicalOperator::Project(PhysicalProject {
                     operation: ProjectOperation::new(preproject_exprs),
                 })),
-                partitioning_requirement: None,
+                partitioning_requirement: partial_partitions,
             },
-            location,
+            partial_location,
             id_gen,
         )?;
 
-        match agg.node.grouping_sets {
+        match final_aggs {
+            Some(final_aggs) => {
+                self.push_aggregate_operator(
+                    phys_aggs,
+                    agg.node.grouping_sets.clone(),
+                    Vec::new(),
+                    partial_location,
+                    partial_partitions,
+                    id_gen,
+                )?;
+                self.push_aggregate_operator(
+                    final_aggs,
+                    agg.node.grouping_sets,
+                    agg.node.grouping_functions,
+                    location,
+                    None,
+                    id_gen,
+                )?;
+            }
+            None => {
+                self.push_aggregate_operator(
+                    phys_aggs,
+                    agg.node.grouping_sets,
+                    agg.node.grouping_functions,
+                    location,
+                    None,
+                    id_gen,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push_aggregate_operator(
+        &mut self,
+        phys_aggs: Vec<PhysicalAggregateExpression>,
+        grouping_sets: Option<Vec<BTreeSet<usize>>>,
+        grouping_functions: Vec<GroupingFunction>,
+        location: LocationRequirement,
+        partitioning_requirement: Option<usize>,
+        id_gen: &mut PipelineIdGen,
+    ) -> Result<()> {
+        match grouping_sets {
             Some(grouping_sets) => {
                 // If we're working with groups, push a hash aggregate operator.
                 let operator = IntermediateOperator {
                     operator: Arc::new(PhysicalOperator::HashAggregate(
-                        PhysicalHashAggregate::new(
-                            phys_aggs,
-                            grouping_sets,
-                            agg.node.grouping_functions,
-                        ),
+                        PhysicalHashAggregate::new(phys_aggs, grouping_sets, grouping_functions),
                     )),
-                    partitioning_requirement: None,
+                    partitioning_requirement,
                 };
                 self.push_intermediate_operator(operator, location, id_gen)?;
             }
@@ -108,7 +205,7 @@ impl IntermediatePipelineBuildState<'_> {
                     operator: Arc::new(PhysicalOperator::UngroupedAggregate(
                         PhysicalUngroupedAggregate::new(phys_aggs),
                     )),
-                    partitioning_requirement: None,
+                    partitioning_requirement,
                 };
                 self.push_intermediate_operator(operator, location, id_gen)?;
             }
@@ -117,3 +214,69 @@ impl IntermediatePipelineBuildState<'_> {
         Ok(())
     }
 }
+
+/// Plan the aggregates for combining the output of a partial aggregate into
+/// the final aggregate results.
+///
+/// The partial aggregate outputs a column per aggregate followed by the group
+/// columns, which the final aggregate groups on again. The final aggregate
+/// produces the same output as if the aggregate was computed in one phase.
+///
+/// Returns None if the aggregate can't be split into two phases.
+fn plan_final_aggregates(
+    phys_aggs: &[PhysicalAggregateExpression],
+    group_types: &[DataType],
+    grouping_sets: Option<&[BTreeSet<usize>]>,
+    grouping_functions: &[GroupingFunction],
+) -> Result<Option<Vec<PhysicalAggregateExpression>>> {
+    // Grouping sets would need the group id to be carried to the final
+    // aggregate, only a single set containing all group exprs is supported.
+    if let Some(grouping_sets) = grouping_sets {
+        match grouping_sets {
+            [set] if set.iter().copied().eq(0..group_types.len()) => (),
+            _ => return Ok(None),
+        }
+    }
+    if !grouping_functions.is_empty() {
+        return Ok(None);
+    }
+
+    let mut partial_types: Vec<_> = phys_aggs
+        .iter()
+        .map(|agg| agg.function.return_type.clone())
+        .collect();
+    partial_types.extend(group_types.iter().cloned());
+
+    let mut table_list = TableList::empty();
+    let names = (0..partial_types.len())
+        .map(|idx| format!("__partial_{idx}"))
+        .collect();
+    let table_ref = table_list.push_table(None, partial_types.clone(), names)?;
+
+    let mut final_aggs = Vec::with_capacity(phys_aggs.len());
+    for (idx, agg) in phys_aggs.iter().enumerate() {
+        if agg.is_distinct || !agg.order_by.is_empty() {
+            return Ok(None);
+        }
+
+        let combine = match agg.function.function.partial_combine() {
+            Some(combine) => combine,
+            None => return Ok(None),
+        };
+
+        let function = combine.plan(&table_list, vec![expr::col_ref(table_ref, idx)])?;
+        if function.return_type != agg.function.return_type {
+            return Ok(None);
+        }
+
+        final_aggs.push(PhysicalAggregateExpression {
+            function,
+            columns: vec![PhysicalColumnExpr { idx }],
+            input_types: vec![partial_types[idx].clone()],
+            is_distinct: false,
+            order_by: Vec::new(),
+        });
+    }
+
+    Ok(Some(final_aggs))
+}
@@ -64,6 +64,8 @@ impl IntermediatePipelineBuildState<'_> {
                     info: CreateTableInfo {
                         name: create.node.name,
                         columns: create.node.columns,
+                        checks: create.node.checks,
+                        primary_key: create.node.primary_key,
                         on_conflict: create.node.on_conflict,
                     },
                     is_ctas,
@@ -24,8 +24,17 @@ impl IntermediatePipelineBuildState<'_> {
         }
 
         let names = Array::from_iter(describe.node.schema.iter().map(|f| f.name.as_str()));
-        let datatypes =
-            Array::from_iter(describe.node.schema.iter().map(|f| f.datatype.to_string()));
+        // Constraints are shown after the type, the same as in a column
+        // definition.
+        let datatypes = describe.node.schema.iter().enumerate().map(|(idx, f)| {
+            match describe.node.constraints.get(idx) {
+                Some(constraints) if !constraints.is_empty() => {
+                    format!("{} {}", f.datatype, constraints.join(" "))
+                }
+                _ => f.datatype.to_string(),
+            }
+        });
+        let datatypes = Array::from_iter(datatypes);
         let batch = Batch::try_new(vec![names, datatypes])?;
 
         let operator = IntermediateOperator {
@@ -62,18 +62,31 @@ impl IntermediatePipelineBuildState<'_> {
         type_strings.push("unoptimized".to_string());
         plan_strings.push(formatter.format_logical_plan(&explain.node.logical_unoptimized)?);
 
+        for (name, plan) in explain.node.optimized_steps.iter().flatten() {
+            type_strings.push(format!("after {name}"));
+            plan_strings.push(formatter.format_logical_plan(plan)?);
+        }
+
         if let Some(optimized) = explain.node.logical_optimized {
             type_strings.push("optimized".to_string());
             plan_strings.push(formatter.format_logical_plan(&optimized)?);
         }
 
+        if let Some(resolved) = &explain.node.bindings {
+            type_strings.push("bindings".to_string());
+            plan_strings.push(formatter.format_bindings(resolved)?);
+        }
+
         match plan_result {
             Ok(_) => {
                 type_strings.push("physical".to_string());
-                plan_strings.push(formatter.format_intermediate_groups(&[
-                    ("local", &planner.local_group),
-                    ("remote", &planner.remote_group),
-                ])?);
+                plan_strings.push(formatter.format_intermediate_groups(
+                    &[
+                        ("local", &planner.local_group),
+                        ("remote", &planner.remote_group),
+                    ],
+                    self.config.target_partitions,
+                )?);
             }
             Err(e) => {
                 error!(%e, "error planning explain input")
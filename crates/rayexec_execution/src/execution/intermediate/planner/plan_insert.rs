@@ -1,11 +1,11 @@
 use std::sync::Arc;
 
-use rayexec_error::Result;
+use rayexec_error::{Result, ResultExt};
 
 use super::{IntermediatePipelineBuildState, Materializations, PipelineIdGen};
 use crate::execution::intermediate::pipeline::IntermediateOperator;
-use crate::execution::operators::insert::InsertOperation;
-use crate::execution::operators::sink::SinkOperator;
+use crate::execution::operators::insert::{InsertCheck, InsertOperation};
+use crate::execution::operators::sink::{SinkOperation, SinkOperator};
 use crate::execution::operators::PhysicalOperator;
 use crate::logical::logical_insert::LogicalInsert;
 use crate::logical::operator::Node;
@@ -22,15 +22,34 @@ impl IntermediatePipelineBuildState<'_> {
 
         self.walk(materializations, id_gen, input)?;
 
+        let checks = insert
+            .node
+            .checks
+            .iter()
+            .map(|check| {
+                let expr = self
+                    .expr_planner
+                    .plan_scalar(&[check.table_ref], &check.expr)
+                    .context("Failed to plan CHECK constraint for insert")?;
+                Ok(InsertCheck {
+                    column_idx: check.column_idx,
+                    expr,
+                    sql: check.sql.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let sink = InsertOperation {
+            catalog: insert.node.catalog,
+            schema: insert.node.schema,
+            table: insert.node.table,
+            checks,
+        };
+        let partitioning_requirement = sink.partition_requirement();
+
         let operator = IntermediateOperator {
-            operator: Arc::new(PhysicalOperator::Insert(SinkOperator::new(
-                InsertOperation {
-                    catalog: insert.node.catalog,
-                    schema: insert.node.schema,
-                    table: insert.node.table,
-                },
-            ))),
-            partitioning_requirement: None,
+            operator: Arc::new(PhysicalOperator::Insert(SinkOperator::new(sink))),
+            partitioning_requirement,
         };
 
         self.push_intermediate_operator(operator, location, id_gen)?;
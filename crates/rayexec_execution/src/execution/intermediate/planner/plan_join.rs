@@ -1,15 +1,24 @@
 use std::sync::Arc;
 
-use rayexec_error::{not_implemented, RayexecError, Result, ResultExt};
+use rayexec_error::{RayexecError, Result, ResultExt};
 
-use super::{IntermediatePipelineBuildState, Materializations, PipelineIdGen};
+use super::{
+    IntermediatePipelineBuildState,
+    Materializations,
+    PendingRuntimeFilter,
+    PipelineIdGen,
+};
+use crate::arrays::datatype::DataType;
 use crate::execution::intermediate::pipeline::IntermediateOperator;
 use crate::execution::operators::hash_join::PhysicalHashJoin;
+use crate::execution::operators::merge_join::PhysicalMergeJoin;
 use crate::execution::operators::nl_join::PhysicalNestedLoopJoin;
+use crate::execution::operators::runtime_filter::{RuntimeFilter, ScanRuntimeFilter};
 use crate::execution::operators::PhysicalOperator;
-use crate::expr;
 use crate::expr::comparison_expr::ComparisonOperator;
 use crate::expr::physical::PhysicalScalarExpression;
+use crate::expr::{self, Expression};
+use crate::logical::binder::table_list::TableRef;
 use crate::logical::logical_join::{
     JoinType,
     LogicalArbitraryJoin,
@@ -17,7 +26,8 @@ use crate::logical::logical_join::{
     LogicalCrossJoin,
     LogicalMagicJoin,
 };
-use crate::logical::operator::{self, LocationRequirement, LogicalNode, Node};
+use crate::logical::logical_scan::{LogicalScan, ScanSource};
+use crate::logical::operator::{self, LocationRequirement, LogicalNode, LogicalOperator, Node};
 
 impl IntermediatePipelineBuildState<'_> {
     pub fn plan_magic_join(
@@ -35,6 +45,7 @@ impl IntermediatePipelineBuildState<'_> {
                 node: LogicalComparisonJoin {
                     join_type: join.node.join_type,
                     conditions: join.node.conditions,
+                    merge_keys: Vec::new(),
                 },
                 location: join.location,
                 children: join.children,
@@ -49,6 +60,10 @@ impl IntermediatePipelineBuildState<'_> {
         materializations: &mut Materializations,
         mut join: Node<LogicalComparisonJoin>,
     ) -> Result<()> {
+        if !join.node.merge_keys.is_empty() {
+            return self.plan_merge_join(id_gen, materializations, join);
+        }
+
         let location = join.location;
 
         let equality_indices: Vec<_> = join
@@ -72,17 +87,12 @@ impl IntermediatePipelineBuildState<'_> {
             let left_refs = left.get_output_table_refs(self.bind_context);
             let right_refs = right.get_output_table_refs(self.bind_context);
 
-            let mut left_types = Vec::new();
-            for &table_ref in &left_refs {
-                let table = self.bind_context.get_table(table_ref)?;
-                left_types.extend(table.column_types.iter().cloned());
-            }
+            let left_types = self.column_types(&left_refs)?;
+            let right_types = self.column_types(&right_refs)?;
 
-            let mut right_types = Vec::new();
-            for &table_ref in &right_refs {
-                let table = self.bind_context.get_table(table_ref)?;
-                right_types.extend(table.column_types.iter().cloned());
-            }
+            // Register runtime filters before planning the probe side so the
+            // scan picks them up.
+            let runtime_filters = self.plan_runtime_filters(&join, &equality_indices, &right)?;
 
             // Build up all inputs on the right (probe) side. This is going to
             // continue with the the current pipeline.
@@ -131,13 +141,17 @@ impl IntermediatePipelineBuildState<'_> {
                 .collect::<Result<Vec<_>>>()?;
 
             let operator = IntermediateOperator {
-                operator: Arc::new(PhysicalOperator::HashJoin(PhysicalHashJoin::new(
-                    join.node.join_type,
-                    &equality_indices,
-                    conditions,
-                    left_types,
-                    right_types,
-                ))),
+                operator: Arc::new(PhysicalOperator::HashJoin(
+                    PhysicalHashJoin::new(
+                        join.node.join_type,
+                        &equality_indices,
+                        conditions,
+                        left_types,
+                        right_types,
+                    )
+                    .with_runtime_filters(runtime_filters)
+                    .with_nested_loop_threshold(self.config.hash_join_nested_loop_threshold),
+                )),
                 partitioning_requirement: None,
             };
             self.push_intermediate_operator(operator, location, id_gen)?;
@@ -153,11 +167,7 @@ impl IntermediatePipelineBuildState<'_> {
         } else {
             // Need to fall back to nested loop join.
 
-            if join.node.join_type != JoinType::Inner {
-                not_implemented!("join type with nl join: {}", join.node.join_type);
-            }
-
-            let table_refs = join.get_output_table_refs(self.bind_context);
+            let table_refs = join.get_children_table_refs(self.bind_context);
             let [left, right] = join.take_two_children_exact()?;
 
             let condition = expr::and(
@@ -189,6 +199,151 @@ impl IntermediatePipelineBuildState<'_> {
         }
     }
 
+    /// Plan a merge join for a join with both inputs sorted on the merge keys.
+    ///
+    /// Both inputs are kept as a single partition from their sorts up to the
+    /// join so that batches arrive in order.
+    fn plan_merge_join(
+        &mut self,
+        id_gen: &mut PipelineIdGen,
+        materializations: &mut Materializations,
+        mut join: Node<LogicalComparisonJoin>,
+    ) -> Result<()> {
+        let location = join.location;
+
+        let [left, right] = join.take_two_children_exact()?;
+        let left_refs = left.get_output_table_refs(self.bind_context);
+        let right_refs = right.get_output_table_refs(self.bind_context);
+
+        let left_types = self.column_types(&left_refs)?;
+        let right_types = self.column_types(&right_refs)?;
+
+        // Right side continues with the current pipeline.
+        //
+        // No batch resizers on either side, they'd only be able to run with a
+        // single partition.
+        self.walk(materializations, id_gen, right)?;
+        self.keep_single_partition()?;
+
+        let mut left_state = IntermediatePipelineBuildState::new(self.config, self.bind_context);
+        left_state.walk(materializations, id_gen, left)?;
+        left_state.keep_single_partition()?;
+
+        self.local_group
+            .merge_from_other(&mut left_state.local_group);
+        self.remote_group
+            .merge_from_other(&mut left_state.remote_group);
+
+        let left_pipeline = left_state.in_progress.take().ok_or_else(|| {
+            RayexecError::new("expected in-progress pipeline from left side of join")
+        })?;
+
+        let conditions = join
+            .node
+            .conditions
+            .iter()
+            .map(|condition| {
+                self.expr_planner
+                    .plan_join_condition_as_hash_join_condition(&left_refs, &right_refs, condition)
+                    .context_fn(|| format!("Failed to plan condition: {condition}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let operator = IntermediateOperator {
+            operator: Arc::new(PhysicalOperator::MergeJoin(PhysicalMergeJoin::new(
+                join.node.join_type,
+                conditions,
+                join.node.merge_keys,
+                left_types,
+                right_types,
+            ))),
+            partitioning_requirement: Some(1),
+        };
+        self.push_intermediate_operator(operator, location, id_gen)?;
+
+        self.push_as_child_pipeline(left_pipeline, PhysicalMergeJoin::BUILD_SIDE_INPUT_INDEX)?;
+
+        // Resize output of join.
+        self.push_batch_resizer(id_gen)?;
+
+        Ok(())
+    }
+
+    /// Get the column types for all columns in the given tables.
+    fn column_types(&self, table_refs: &[TableRef]) -> Result<Vec<DataType>> {
+        let mut types = Vec::new();
+        for &table_ref in table_refs {
+            let table = self.bind_context.get_table(table_ref)?;
+            types.extend(table.column_types.iter().cloned());
+        }
+        Ok(types)
+    }
+
+    /// Create runtime filters for a hash join.
+    ///
+    /// A filter is created for each equality where the probe side is a column
+    /// from a table scan, with the filter being registered to be pushed into
+    /// the scan. Returns the filters paired with the index of their equality.
+    fn plan_runtime_filters(
+        &mut self,
+        join: &Node<LogicalComparisonJoin>,
+        equality_indices: &[usize],
+        right: &LogicalOperator,
+    ) -> Result<Vec<(usize, Arc<RuntimeFilter>)>> {
+        // Only joins that discard probe rows without a match can filter the
+        // probe side.
+        //
+        // Filters are shared in memory between the join and the scan, so both
+        // need to be executing locally.
+        if !self.config.enable_runtime_filters
+            || join.location == LocationRequirement::Remote
+            || !matches!(
+                join.node.join_type,
+                JoinType::Inner | JoinType::Left | JoinType::Semi | JoinType::Anti
+            )
+        {
+            return Ok(Vec::new());
+        }
+
+        let scan = match find_probe_scan(right) {
+            Some(scan) if scan.location == join.location => scan,
+            _ => return Ok(Vec::new()),
+        };
+
+        let table_list = self.bind_context.get_table_list();
+        let mut filters = Vec::new();
+
+        for (idx, &cond_idx) in equality_indices.iter().enumerate() {
+            let condition = &join.node.conditions[cond_idx];
+            let column = match &condition.right {
+                Expression::Column(col) if col.table_scope == scan.node.table_ref => col,
+                _ => continue,
+            };
+            let table_column = match scan.node.projection.get(column.column) {
+                Some(&table_column) => table_column,
+                None => continue,
+            };
+
+            // Keys are compared by hash, the build side needs to produce the
+            // same type.
+            if condition.left.datatype(table_list)? != condition.right.datatype(table_list)? {
+                continue;
+            }
+
+            let filter = Arc::new(RuntimeFilter::new());
+            self.runtime_filters.push(PendingRuntimeFilter {
+                table_ref: scan.node.table_ref,
+                filter: ScanRuntimeFilter {
+                    column: table_column,
+                    filter: filter.clone(),
+                },
+            });
+            filters.push((idx, filter));
+        }
+
+        Ok(filters)
+    }
+
     pub fn plan_arbitrary_join(
         &mut self,
         id_gen: &mut PipelineIdGen,
@@ -204,17 +359,6 @@ impl IntermediatePipelineBuildState<'_> {
             )
             .context("Failed to plan expressions arbitrary join filter")?;
 
-        // Modify the filter as to match the join type.
-        let filter = match join.node.join_type {
-            JoinType::Inner => filter,
-            other => {
-                // TODO: Other join types.
-                return Err(RayexecError::new(format!(
-                    "Unhandled join type for arbitrary join: {other:?}"
-                )));
-            }
-        };
-
         let [left, right] = join.take_two_children_exact()?;
 
         self.push_nl_join(
@@ -266,6 +410,9 @@ impl IntermediatePipelineBuildState<'_> {
     ) -> Result<()> {
         self.config.check_nested_loop_join_allowed()?;
 
+        let left_types = self.column_types(&left.get_output_table_refs(self.bind_context))?;
+        let right_types = self.column_types(&right.get_output_table_refs(self.bind_context))?;
+
         // Continue to build up all the inputs into the right side.
         self.walk(materializations, id_gen, right)?;
 
@@ -289,7 +436,7 @@ impl IntermediatePipelineBuildState<'_> {
 
         let operator = IntermediateOperator {
             operator: Arc::new(PhysicalOperator::NestedLoopJoin(
-                PhysicalNestedLoopJoin::new(filter, join_type),
+                PhysicalNestedLoopJoin::new(filter, join_type, left_types, right_types),
             )),
             partitioning_requirement: None,
         };
@@ -304,3 +451,16 @@ impl IntermediatePipelineBuildState<'_> {
         Ok(())
     }
 }
+
+/// Find the table scan feeding directly into the probe side of a join, looking
+/// through filters.
+fn find_probe_scan(plan: &LogicalOperator) -> Option<&Node<LogicalScan>> {
+    match plan {
+        LogicalOperator::Scan(scan) => match &scan.node.source {
+            ScanSource::Table { .. } if scan.node.aggregate.is_none() => Some(scan),
+            _ => None,
+        },
+        LogicalOperator::Filter(filter) => find_probe_scan(filter.children.first()?),
+        _ => None,
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use rayexec_error::Result;
+
+use super::{IntermediatePipelineBuildState, Materializations, PipelineIdGen};
+use crate::execution::intermediate::pipeline::IntermediateOperator;
+use crate::execution::operators::sample::{
+    PhysicalReservoirSample,
+    PhysicalSample,
+    SampleOperation,
+};
+use crate::execution::operators::PhysicalOperator;
+use crate::logical::logical_sample::LogicalSample;
+use crate::logical::operator::Node;
+
+impl IntermediatePipelineBuildState<'_> {
+    pub fn plan_sample(
+        &mut self,
+        id_gen: &mut PipelineIdGen,
+        materializations: &mut Materializations,
+        mut sample: Node<LogicalSample>,
+    ) -> Result<()> {
+        let location = sample.location;
+        let input = sample.take_one_child_exact()?;
+
+        self.walk(materializations, id_gen, input)?;
+
+        let operator = match sample.node {
+            LogicalSample::Table(sample) => IntermediateOperator {
+                operator: Arc::new(PhysicalOperator::Sample(PhysicalSample::new(
+                    SampleOperation::new(sample),
+                ))),
+                partitioning_requirement: None,
+            },
+            LogicalSample::Reservoir { rows } => {
+                // Sample needs to be global, ensure this operator is only
+                // receiving a single input partition.
+                IntermediateOperator {
+                    operator: Arc::new(PhysicalOperator::ReservoirSample(
+                        PhysicalReservoirSample::new(rows),
+                    )),
+                    partitioning_requirement: Some(1),
+                }
+            }
+        };
+
+        self.push_intermediate_operator(operator, location, id_gen)?;
+
+        Ok(())
+    }
+}
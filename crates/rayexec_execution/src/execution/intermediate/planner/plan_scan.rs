@@ -2,10 +2,11 @@ use std::sync::Arc;
 
 use rayexec_error::{not_implemented, RayexecError, Result, ResultExt};
 
-use super::{InProgressPipeline, IntermediatePipelineBuildState, PipelineIdGen};
+use super::{InProgressPipeline, IntermediatePipelineBuildState, Materializations, PipelineIdGen};
 use crate::arrays::array::Array;
 use crate::arrays::batch::Batch;
 use crate::execution::intermediate::pipeline::{IntermediateOperator, PipelineSource};
+use crate::execution::operators::query_scan::{PhysicalQueryScan, PhysicalShippedQueryScan};
 use crate::execution::operators::scan::PhysicalScan;
 use crate::execution::operators::table_function::PhysicalTableFunction;
 use crate::execution::operators::values::PhysicalValues;
@@ -16,13 +17,27 @@ use crate::logical::operator::Node;
 use crate::storage::table_storage::Projections;
 
 impl IntermediatePipelineBuildState<'_> {
-    pub fn plan_scan(&mut self, id_gen: &mut PipelineIdGen, scan: Node<LogicalScan>) -> Result<()> {
+    pub fn plan_scan(
+        &mut self,
+        id_gen: &mut PipelineIdGen,
+        materializations: &mut Materializations,
+        scan: Node<LogicalScan>,
+    ) -> Result<()> {
         let location = scan.location;
 
+        if let ScanSource::Query {
+            shipped: Some(_), ..
+        } = &scan.node.source
+        {
+            return self.plan_shipped_query_scan(id_gen, materializations, scan);
+        }
+
         if self.in_progress.is_some() {
             return Err(RayexecError::new("Expected in progress to be None"));
         }
 
+        let runtime_filters = self.take_runtime_filters(scan.node.table_ref);
+
         // TODO: Split up scan source.
         let projections = if scan.node.did_prune_columns {
             Projections {
@@ -38,28 +53,48 @@ impl IntermediatePipelineBuildState<'_> {
                 schema,
                 source,
             } => IntermediateOperator {
-                operator: Arc::new(PhysicalOperator::Scan(PhysicalScan::new(
-                    catalog,
-                    schema,
-                    source,
-                    projections,
-                ))),
+                operator: Arc::new(PhysicalOperator::Scan(
+                    PhysicalScan::new(catalog, schema, source, projections)
+                        .with_sample(scan.node.sample)
+                        .with_limit(scan.node.limit)
+                        .with_aggregate(scan.node.aggregate)
+                        .with_index_scan(scan.node.index_scan.map(|s| *s))
+                        .with_filters(scan.node.scan_filters)
+                        .with_runtime_filters(runtime_filters),
+                )),
                 partitioning_requirement: None,
             },
             ScanSource::TableFunction { function } => IntermediateOperator {
-                operator: Arc::new(PhysicalOperator::TableFunction(PhysicalTableFunction::new(
-                    function,
-                    projections,
-                ))),
+                operator: Arc::new(PhysicalOperator::TableFunction(
+                    PhysicalTableFunction::new(function, projections)
+                        .with_sample(scan.node.sample)
+                        .with_limit(scan.node.limit)
+                        .with_aggregate(scan.node.aggregate)
+                        .with_filters(scan.node.scan_filters),
+                )),
                 partitioning_requirement: None,
             },
             ScanSource::ExpressionList { rows } => {
+                if scan.node.sample.is_some() {
+                    return Err(RayexecError::new("Cannot push sample into a VALUES list"));
+                }
+                if scan.node.aggregate.is_some() {
+                    return Err(RayexecError::new(
+                        "Cannot push aggregate into a VALUES list",
+                    ));
+                }
                 let batches = self.create_batches_for_row_values(projections, rows)?;
                 IntermediateOperator {
                     operator: Arc::new(PhysicalOperator::Values(PhysicalValues::new(batches))),
                     partitioning_requirement: None,
                 }
             }
+            ScanSource::Query { catalog, query, .. } => IntermediateOperator {
+                operator: Arc::new(PhysicalOperator::QueryScan(PhysicalQueryScan::new(
+                    catalog, query,
+                ))),
+                partitioning_requirement: None,
+            },
             ScanSource::View { .. } => not_implemented!("view physical planning"),
         };
 
@@ -73,6 +108,45 @@ impl IntermediatePipelineBuildState<'_> {
         Ok(())
     }
 
+    /// Plan a query scan with rows shipped from its child.
+    ///
+    /// The child is executed locally, and its output is collected by the scan
+    /// before sending the query.
+    fn plan_shipped_query_scan(
+        &mut self,
+        id_gen: &mut PipelineIdGen,
+        materializations: &mut Materializations,
+        mut scan: Node<LogicalScan>,
+    ) -> Result<()> {
+        let location = scan.location;
+
+        let input = scan.take_one_child_exact()?;
+        self.walk(materializations, id_gen, input)?;
+
+        let (catalog, query, shipped) = match scan.node.source {
+            ScanSource::Query {
+                catalog,
+                query,
+                shipped: Some(shipped),
+            } => (catalog, query, shipped),
+            other => {
+                return Err(RayexecError::new(format!(
+                    "Expected query scan with shipped rows, got {other:?}"
+                )))
+            }
+        };
+
+        let operator = IntermediateOperator {
+            operator: Arc::new(PhysicalOperator::ShippedQueryScan(
+                PhysicalShippedQueryScan::new(catalog, query, shipped),
+            )),
+            partitioning_requirement: None,
+        };
+        self.push_intermediate_operator(operator, location, id_gen)?;
+
+        Ok(())
+    }
+
     fn create_batches_for_row_values(
         &self,
         projections: Projections,
@@ -24,10 +24,25 @@ impl IntermediatePipelineBuildState<'_> {
             return Err(RayexecError::new("Expected in progress to be None"));
         }
 
+        let batch = match show {
+            LogicalShowVar::Variable { value, .. } => {
+                Batch::try_new([Array::from_iter([value.to_string().as_str()])])?
+            }
+            LogicalShowVar::All { settings } => {
+                let names: Vec<_> = settings.iter().map(|s| s.name).collect();
+                let values: Vec<_> = settings.iter().map(|s| s.value.to_string()).collect();
+                let descriptions: Vec<_> = settings.iter().map(|s| s.description).collect();
+
+                Batch::try_new([
+                    Array::from_iter(names),
+                    Array::from_iter(values),
+                    Array::from_iter(descriptions),
+                ])?
+            }
+        };
+
         let operator = IntermediateOperator {
-            operator: Arc::new(PhysicalOperator::Values(PhysicalValues::new(vec![
-                Batch::try_new([Array::from_iter([show.value.to_string().as_str()])])?,
-            ]))),
+            operator: Arc::new(PhysicalOperator::Values(PhysicalValues::new(vec![batch]))),
             partitioning_requirement: Some(1),
         };
 
@@ -9,7 +9,8 @@ use crate::execution::intermediate::pipeline::{
     PipelineSink,
     PipelineSource,
 };
-use crate::execution::operators::sort::gather_sort::PhysicalGatherSort;
+use crate::execution::operators::round_robin::PhysicalRoundRobinRepartition;
+use crate::execution::operators::sort::parallel_merge::PhysicalParallelMerge;
 use crate::execution::operators::sort::scatter_sort::PhysicalScatterSort;
 use crate::execution::operators::PhysicalOperator;
 use crate::logical::logical_order::LogicalOrder;
@@ -32,6 +33,19 @@ impl IntermediatePipelineBuildState<'_> {
             .expr_planner
             .plan_sorts(&input_refs, &order.node.exprs)?;
 
+        // Spread input batches across all partitions.
+        //
+        // The input may have all of its rows in a single partition (e.g. a
+        // table function called once), which would leave a single partition
+        // doing all the sorting. Distributing the batches lets each
+        // partition sort a share of the input before the final merge.
+        let operator = IntermediateOperator {
+            operator: Arc::new(PhysicalOperator::RoundRobin(PhysicalRoundRobinRepartition)),
+            partitioning_requirement: None,
+        };
+        self.push_intermediate_operator(operator, location, id_gen)?;
+        self.split_pipeline(id_gen, location, None)?;
+
         // Resize input batches.
         //
         // The local sort is going to be converting things into a row
@@ -48,8 +62,12 @@ impl IntermediatePipelineBuildState<'_> {
         self.push_intermediate_operator(operator, location, id_gen)?;
 
         // Global sorting.
+        //
+        // Once all partitions are sorted, each partition merges a range of
+        // keys from every sorted partition. The merged ranges are then
+        // produced in order.
         let operator = IntermediateOperator {
-            operator: Arc::new(PhysicalOperator::MergeSorted(PhysicalGatherSort::new(
+            operator: Arc::new(PhysicalOperator::ParallelMerge(PhysicalParallelMerge::new(
                 exprs,
             ))),
             partitioning_requirement: None,
@@ -58,7 +76,22 @@ impl IntermediatePipelineBuildState<'_> {
 
         // Global sorting accepts n-partitions, but produces only a single
         // partition. We finish the current pipeline
+        self.split_pipeline(id_gen, location, Some(1))?;
 
+        Ok(())
+    }
+
+    /// Finish the in-progress pipeline, and start a new pipeline that's fed by
+    /// the output of the last operator in the finished pipeline.
+    ///
+    /// The last operator is expected to have separate input and output
+    /// partition states.
+    fn split_pipeline(
+        &mut self,
+        id_gen: &mut PipelineIdGen,
+        location: LocationRequirement,
+        partitioning_requirement: Option<usize>,
+    ) -> Result<()> {
         let in_progress = self.take_in_progress_pipeline()?;
         self.in_progress = Some(InProgressPipeline {
             id: id_gen.next_pipeline_id(),
@@ -66,7 +99,7 @@ impl IntermediatePipelineBuildState<'_> {
             location,
             source: PipelineSource::OtherPipeline {
                 pipeline: in_progress.id,
-                partitioning_requirement: Some(1),
+                partitioning_requirement,
             },
         });
 
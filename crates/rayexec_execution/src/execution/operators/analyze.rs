@@ -23,6 +23,7 @@ impl ExecutableOperator for PhysicalAnalyze {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         _partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         unimplemented!()
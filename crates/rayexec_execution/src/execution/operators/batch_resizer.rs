@@ -3,7 +3,7 @@ use std::task::{Context, Waker};
 
 use rayexec_error::Result;
 
-use super::util::resizer::{BatchResizer, DEFAULT_TARGET_BATCH_SIZE};
+use super::util::resizer::BatchResizer;
 use super::{
     ExecutableOperator,
     ExecutionStates,
@@ -40,6 +40,7 @@ impl ExecutableOperator for PhysicalBatchResizer {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         Ok(ExecutionStates {
@@ -49,7 +50,7 @@ impl ExecutableOperator for PhysicalBatchResizer {
                     .map(|_| {
                         PartitionState::BatchResizer(BatchResizerPartitionState {
                             buffered: ComputedBatches::None,
-                            resizer: BatchResizer::new(DEFAULT_TARGET_BATCH_SIZE),
+                            resizer: BatchResizer::new(batch_size),
                             pull_waker: None,
                             push_waker: None,
                             exhausted: false,
@@ -53,6 +53,7 @@ impl ExecutableOperator for PhysicalCreateSchema {
     fn create_states(
         &self,
         context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         if partitions[0] != 1 {
@@ -64,12 +65,15 @@ impl ExecutableOperator for PhysicalCreateSchema {
         // TODO: Placeholder.
         let tx = CatalogTx::new();
 
-        let catalog = context.get_database(&self.catalog)?.catalog.clone();
+        let database = context.get_database(&self.catalog)?;
+        let catalog = database.catalog.clone();
+        let storage = database.table_storage.clone();
         let info = self.info.clone();
         let create = Box::pin(async move {
             catalog.create_schema(&tx, &info)?;
-            // TODO: And persist some how (write to log, flush on commit)
-            // TODO: Probably doesn't even need to be async...
+            if let Some(storage) = storage {
+                storage.create_physical_schema(&info.name).await?;
+            }
             Ok(())
         });
 
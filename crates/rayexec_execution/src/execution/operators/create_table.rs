@@ -1,7 +1,7 @@
 use std::fmt;
 
 use futures::future::BoxFuture;
-use rayexec_error::{OptionExt, RayexecError, Result};
+use rayexec_error::{ErrorKind, OptionExt, RayexecError, Result};
 use rayexec_proto::ProtoConv;
 
 use super::sink::{PartitionSink, SinkOperation, SinkOperator};
@@ -49,6 +49,7 @@ impl SinkOperation for CreateTableSinkOperation {
             .get_schema(&tx, &self.schema)?
             .ok_or_else(|| {
                 RayexecError::new(format!("Missing schema for table create: {}", self.schema))
+                    .with_kind(ErrorKind::SchemaNotFound)
             })?;
 
         let info = self.info.clone();
@@ -4,7 +4,7 @@ use std::task::{Context, Poll};
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{ErrorKind, RayexecError, Result};
 
 use super::{
     ExecutableOperator,
@@ -44,6 +44,7 @@ impl ExecutableOperator for PhysicalCreateView {
     fn create_states(
         &self,
         context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         if partitions[0] != 1 {
@@ -61,6 +62,7 @@ impl ExecutableOperator for PhysicalCreateView {
             .get_schema(&tx, &self.schema)?
             .ok_or_else(|| {
                 RayexecError::new(format!("Missing schema for view create: {}", self.schema))
+                    .with_kind(ErrorKind::SchemaNotFound)
             })?;
 
         let info = self.info.clone();
@@ -19,7 +19,7 @@ use super::{
 };
 use crate::arrays::batch::Batch;
 use crate::database::catalog::CatalogTx;
-use crate::database::drop::DropInfo;
+use crate::database::drop::{DropInfo, DropObject};
 use crate::database::DatabaseContext;
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 use crate::proto::DatabaseProtoConv;
@@ -50,6 +50,7 @@ impl ExecutableOperator for PhysicalDrop {
     fn create_states(
         &self,
         context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         if partitions[0] != 1 {
@@ -59,11 +60,16 @@ impl ExecutableOperator for PhysicalDrop {
         // TODO: Placeholder.
         let tx = CatalogTx::new();
 
-        let catalog = context.get_database(&self.catalog)?.catalog.clone();
+        let database = context.get_database(&self.catalog)?;
+        let catalog = database.catalog.clone();
+        let storage = database.table_storage.clone();
         let info = self.info.clone();
         let drop = Box::pin(async move {
             catalog.drop_entry(&tx, &info)?;
-            // TODO: Log drop, enqueue physical table drop.
+            if let (DropObject::Schema, Some(storage)) = (&info.object, storage) {
+                storage.drop_physical_schema(&info.schema).await?;
+            }
+            // TODO: Enqueue physical table drop.
             // TODO: Probably doesn't even need to be async...
             Ok(())
         });
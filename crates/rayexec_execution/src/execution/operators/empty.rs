@@ -32,6 +32,7 @@ impl ExecutableOperator for PhysicalEmpty {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         Ok(ExecutionStates {
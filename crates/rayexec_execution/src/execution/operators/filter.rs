@@ -24,10 +24,32 @@ impl FilterOperation {
 
 impl StatelessOperation for FilterOperation {
     fn execute(&self, batch: Batch) -> Result<Batch> {
-        let selection = self.predicate.select(&batch)?;
-        let batch = batch.select(Arc::new(selection)); // TODO: Select mut
+        match &self.predicate {
+            PhysicalScalarExpression::ScalarFunction(func)
+                if func.function.function.name() == "and" =>
+            {
+                // Only rows where every conjunct is true pass the filter, so
+                // each conjunct only needs to be evaluated on rows that passed
+                // the previous ones. Rows that evaluate to NULL are dropped,
+                // which matches 'NULL AND x' never being true.
+                let mut batch = batch;
+                for conjunct in &func.inputs {
+                    if batch.num_rows() == 0 {
+                        break;
+                    }
+                    let selection = conjunct.select(&batch)?;
+                    batch = batch.select(Arc::new(selection));
+                }
 
-        Ok(batch)
+                Ok(batch)
+            }
+            _ => {
+                let selection = self.predicate.select(&batch)?;
+                let batch = batch.select(Arc::new(selection)); // TODO: Select mut
+
+                Ok(batch)
+            }
+        }
     }
 }
 
@@ -1,10 +1,10 @@
 use std::collections::BTreeSet;
 
-use rayexec_error::{not_implemented, Result};
+use rayexec_error::{RayexecError, Result};
 
 use super::chunk::GroupChunk;
 use super::hash_table::GroupAddress;
-use crate::arrays::array::Array;
+use crate::arrays::array::{Array, ArrayData};
 use crate::arrays::executor::physical_type::{
     PhysicalBinary,
     PhysicalBool,
@@ -17,6 +17,7 @@ use crate::arrays::executor::physical_type::{
     PhysicalI64,
     PhysicalI8,
     PhysicalInterval,
+    PhysicalList,
     PhysicalStorage,
     PhysicalType,
     PhysicalU128,
@@ -79,87 +80,164 @@ where
     I1: Iterator<Item = usize> + Clone,
     I2: Iterator<Item = usize> + Clone,
 {
-    for col_idx in 0..arrays1.len() {
-        let rows1 = rows1.clone();
-        let rows2 = rows2.clone();
-
-        let array1 = &arrays1[col_idx];
-        let array2 = &arrays2[col_idx];
-
-        // We need to handle trying to compare against untyped nulls in case
-        // there's a hash collision with groups from different grouping sets
-        // (e.g. group may have no masked columns but we're comparing against a
-        // group with masked columns).
-        if array1.physical_type() != array2.physical_type() {
-            not_eq_rows.extend(rows1);
-            return Ok(());
-        }
-
-        match array1.physical_type() {
-            PhysicalType::UntypedNull => compare_rows_eq::<PhysicalUntypedNull, _, _>(
-                array1,
-                array2,
-                rows1,
-                rows2,
-                not_eq_rows,
-            )?,
-            PhysicalType::Boolean => {
-                compare_rows_eq::<PhysicalBool, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Int8 => {
-                compare_rows_eq::<PhysicalI8, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Int16 => {
-                compare_rows_eq::<PhysicalI16, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Int32 => {
-                compare_rows_eq::<PhysicalI32, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Int64 => {
-                compare_rows_eq::<PhysicalI64, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Int128 => {
-                compare_rows_eq::<PhysicalI128, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::UInt8 => {
-                compare_rows_eq::<PhysicalU8, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::UInt16 => {
-                compare_rows_eq::<PhysicalU16, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::UInt32 => {
-                compare_rows_eq::<PhysicalU32, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::UInt64 => {
-                compare_rows_eq::<PhysicalU64, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::UInt128 => {
-                compare_rows_eq::<PhysicalU128, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Float16 => {
-                compare_rows_eq::<PhysicalF16, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Float32 => {
-                compare_rows_eq::<PhysicalF32, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Float64 => {
-                compare_rows_eq::<PhysicalF64, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Interval => compare_rows_eq::<PhysicalInterval, _, _>(
-                array1,
-                array2,
-                rows1,
-                rows2,
-                not_eq_rows,
-            )?,
-            PhysicalType::Binary => {
-                compare_rows_eq::<PhysicalBinary, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
-            }
-            PhysicalType::Utf8 => {
-                compare_rows_eq::<PhysicalUtf8, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+    for (array1, array2) in arrays1.iter().zip(arrays2) {
+        compare_array_rows_eq(array1, array2, rows1.clone(), rows2.clone(), not_eq_rows)?;
+    }
+
+    Ok(())
+}
+
+fn compare_array_rows_eq<I1, I2>(
+    array1: &Array,
+    array2: &Array,
+    rows1: I1,
+    rows2: I2,
+    not_eq_rows: &mut BTreeSet<usize>,
+) -> Result<()>
+where
+    I1: Iterator<Item = usize> + Clone,
+    I2: Iterator<Item = usize> + Clone,
+{
+    // We need to handle trying to compare against untyped nulls in case
+    // there's a hash collision with groups from different grouping sets
+    // (e.g. group may have no masked columns but we're comparing against a
+    // group with masked columns).
+    if array1.physical_type() != array2.physical_type() {
+        not_eq_rows.extend(rows1);
+        return Ok(());
+    }
+
+    match array1.physical_type() {
+        PhysicalType::UntypedNull => {
+            compare_rows_eq::<PhysicalUntypedNull, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Boolean => {
+            compare_rows_eq::<PhysicalBool, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Int8 => {
+            compare_rows_eq::<PhysicalI8, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Int16 => {
+            compare_rows_eq::<PhysicalI16, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Int32 => {
+            compare_rows_eq::<PhysicalI32, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Int64 => {
+            compare_rows_eq::<PhysicalI64, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Int128 => {
+            compare_rows_eq::<PhysicalI128, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::UInt8 => {
+            compare_rows_eq::<PhysicalU8, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::UInt16 => {
+            compare_rows_eq::<PhysicalU16, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::UInt32 => {
+            compare_rows_eq::<PhysicalU32, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::UInt64 => {
+            compare_rows_eq::<PhysicalU64, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::UInt128 => {
+            compare_rows_eq::<PhysicalU128, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Float16 => {
+            compare_rows_eq::<PhysicalF16, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Float32 => {
+            compare_rows_eq::<PhysicalF32, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Float64 => {
+            compare_rows_eq::<PhysicalF64, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Interval => {
+            compare_rows_eq::<PhysicalInterval, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Binary => {
+            compare_rows_eq::<PhysicalBinary, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::Utf8 => {
+            compare_rows_eq::<PhysicalUtf8, _, _>(array1, array2, rows1, rows2, not_eq_rows)?
+        }
+        PhysicalType::List => compare_list_rows_eq(array1, array2, rows1, rows2, not_eq_rows)?,
+    }
+
+    Ok(())
+}
+
+/// Compares list rows from two arrays.
+///
+/// Lists are equal if they're the same length and all child values are equal,
+/// with NULL child values being considered equal to each other.
+fn compare_list_rows_eq<I1, I2>(
+    array1: &Array,
+    array2: &Array,
+    rows1: I1,
+    rows2: I2,
+    not_eq_rows: &mut BTreeSet<usize>,
+) -> Result<()>
+where
+    I1: Iterator<Item = usize>,
+    I2: Iterator<Item = usize>,
+{
+    let (list1, list2) = match (array1.array_data(), array2.array_data()) {
+        (ArrayData::List(list1), ArrayData::List(list2)) => (list1, list2),
+        _ => return Err(RayexecError::new("Expected list arrays for list compare")),
+    };
+
+    let selection1 = array1.selection_vector();
+    let selection2 = array2.selection_vector();
+
+    let validity1 = array1.validity();
+    let validity2 = array2.validity();
+
+    let metadata1 = PhysicalList::get_storage(array1.array_data())?;
+    let metadata2 = PhysicalList::get_storage(array2.array_data())?;
+
+    // Child rows that weren't equal, reused across list rows.
+    let mut child_not_eq = BTreeSet::new();
+
+    for (row1, row2) in rows1.zip(rows2) {
+        let sel1 = unsafe { selection::get_unchecked(selection1, row1) };
+        let sel2 = unsafe { selection::get_unchecked(selection2, row2) };
+
+        match (
+            check_validity(sel1, validity1),
+            check_validity(sel2, validity2),
+        ) {
+            (true, true) => {
+                let meta1 = unsafe { metadata1.get_unchecked(sel1) };
+                let meta2 = unsafe { metadata2.get_unchecked(sel2) };
+
+                if meta1.len != meta2.len {
+                    not_eq_rows.insert(row1);
+                    continue;
+                }
+
+                // Ranges keep the iterator types the same when recursing into
+                // nested lists.
+                let child_rows1 = (meta1.offset as usize)..((meta1.offset + meta1.len) as usize);
+                let child_rows2 = (meta2.offset as usize)..((meta2.offset + meta2.len) as usize);
+
+                child_not_eq.clear();
+                compare_array_rows_eq(
+                    &list1.array,
+                    &list2.array,
+                    child_rows1,
+                    child_rows2,
+                    &mut child_not_eq,
+                )?;
+
+                if !child_not_eq.is_empty() {
+                    not_eq_rows.insert(row1);
+                }
             }
-            PhysicalType::List => {
-                not_implemented!("Row compare list")
+            (false, false) => (), // NULLs are equal when grouping.
+            _ => {
+                not_eq_rows.insert(row1);
             }
         }
     }
@@ -3,20 +3,33 @@ use std::sync::Arc;
 use rayexec_error::Result;
 
 use super::hash_table::HashTable;
+use super::spill::{buffered_size, spill_partition_for_hash, SpillFile, SPILL_PARTITIONS};
 use crate::arrays::array::Array;
-use crate::arrays::executor::scalar::HashExecutor;
+use crate::arrays::executor::physical_type::PhysicalU64;
+use crate::arrays::executor::scalar::{HashExecutor, UnaryExecutor};
 use crate::arrays::selection::SelectionVector;
 use crate::execution::operators::hash_aggregate::hash_table::GroupAddress;
 use crate::functions::aggregate::states::{AggregateGroupStates, OpaqueStatesMut};
 use crate::functions::aggregate::ChunkGroupAddressIter;
+use crate::runtime::memory::MemoryReservation;
 
 /// And implementation of GroupedStates that buffers inputs to an aggregate in a
 /// hash table to ensure the aggregate is computed with distinct values.
+///
+/// Inputs for all groups are kept in a single hash table keyed on the group
+/// index followed by the aggregate inputs. A value seen in multiple groups is
+/// stored once per group.
+///
+/// If the table grows past the memory limit, its rows are spilled to files
+/// partitioned by hash and the table is cleared. Each partition is
+/// deduplicated separately when finalizing.
 // TODO: Move this to aggregates function module.
 #[derive(Debug)]
 pub struct DistinctGroupedStates {
-    /// Distinct inputs per group.
-    distinct_inputs: Vec<Option<HashTable>>,
+    /// Number of groups we're tracking.
+    num_states: usize,
+    /// Distinct (group, inputs) pairs.
+    distinct_inputs: DistinctInputs,
     /// The underlying states.
     ///
     /// These won't be initialized until we've received all distinct input.
@@ -25,14 +38,155 @@ pub struct DistinctGroupedStates {
     hash_buf: Vec<u64>,
 }
 
+/// Hash table with the group index as the first column.
+///
+/// Wrapped to allow downcasting when combining states.
+#[derive(Debug)]
+struct DistinctInputs {
+    table: HashTable,
+    /// Memory reserved for the rows in the table.
+    reservation: MemoryReservation,
+    /// Rows spilled from the table, partitioned by hash.
+    ///
+    /// Empty if nothing has been spilled.
+    spilled: Vec<SpillFile>,
+}
+
+impl DistinctInputs {
+    fn new(reservation: MemoryReservation) -> Self {
+        DistinctInputs {
+            table: HashTable::new(16, Vec::new()),
+            reservation,
+            spilled: Vec::new(),
+        }
+    }
+
+    /// Insert rows into the table.
+    ///
+    /// `columns` should begin with the group index column.
+    fn insert(&mut self, columns: &[Array], hash_buf: &mut Vec<u64>) -> Result<()> {
+        let len = match columns.first() {
+            Some(arr) => arr.logical_len(),
+            None => return Ok(()),
+        };
+        if len == 0 {
+            return Ok(());
+        }
+
+        hash_buf.clear();
+        hash_buf.resize(len, 0);
+        HashExecutor::hash_many(columns, hash_buf)?;
+
+        // Insert into hash map with empty inputs.
+        self.table.insert(columns, hash_buf, &[])?;
+
+        if self.reservation.try_grow(buffered_size(columns)).is_err() {
+            self.spill(hash_buf)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write all rows in the table to the spill files, and clear the table.
+    fn spill(&mut self, hash_buf: &mut Vec<u64>) -> Result<()> {
+        if self.spilled.is_empty() {
+            self.spilled = (0..SPILL_PARTITIONS)
+                .map(|_| SpillFile::try_new())
+                .collect::<Result<_>>()?;
+        }
+
+        let table = std::mem::replace(&mut self.table, HashTable::new(16, Vec::new()));
+        for result in table.into_drain() {
+            let columns = result?.into_arrays();
+            let len = columns[0].logical_len();
+
+            hash_buf.clear();
+            hash_buf.resize(len, 0);
+            HashExecutor::hash_many(&columns, hash_buf)?;
+
+            let mut partition_rows: Vec<_> = (0..SPILL_PARTITIONS)
+                .map(|_| SelectionVector::with_capacity(0))
+                .collect();
+            for (row, hash) in hash_buf.iter().enumerate() {
+                partition_rows[spill_partition_for_hash(*hash)].push_location(row);
+            }
+
+            for (file, rows) in self.spilled.iter_mut().zip(partition_rows) {
+                if rows.is_empty() {
+                    continue;
+                }
+                let rows = Arc::new(rows);
+                let selected: Vec<_> = columns
+                    .iter()
+                    .map(|arr| {
+                        let mut arr = arr.clone();
+                        arr.select_mut(rows.clone());
+                        arr
+                    })
+                    .collect();
+                file.write(&selected)?;
+            }
+        }
+
+        std::mem::drop(self.reservation.take());
+
+        Ok(())
+    }
+
+    /// Take all rows, both in memory and spilled, leaving this empty.
+    fn take_all(&mut self) -> Box<dyn Iterator<Item = Result<Vec<Array>>>> {
+        let table = std::mem::replace(&mut self.table, HashTable::new(16, Vec::new()));
+        let spilled = std::mem::take(&mut self.spilled);
+        std::mem::drop(self.reservation.take());
+
+        let in_memory = table
+            .into_drain()
+            .map(|result| result.map(|batch| batch.into_arrays()));
+        let spilled = spilled.into_iter().flat_map(|file| spill_reader(file));
+
+        Box::new(in_memory.chain(spilled))
+    }
+}
+
+/// Read back all batches from a spill file.
+fn spill_reader(file: SpillFile) -> Box<dyn Iterator<Item = Result<Vec<Array>>>> {
+    match file.into_reader() {
+        Ok(reader) => Box::new(reader),
+        Err(e) => Box::new(std::iter::once(Err(e))),
+    }
+}
+
 impl DistinctGroupedStates {
-    pub fn new(states: Box<dyn AggregateGroupStates>) -> Self {
+    pub fn new(states: Box<dyn AggregateGroupStates>, reservation: MemoryReservation) -> Self {
         DistinctGroupedStates {
-            distinct_inputs: Vec::new(),
+            num_states: 0,
+            distinct_inputs: DistinctInputs::new(reservation),
             states,
             hash_buf: Vec::new(),
         }
     }
+
+    /// Update the underlying states with distinct (group, inputs) rows.
+    fn update_underlying(&mut self, arrays: &[Array]) -> Result<()> {
+        // TODO: Bit jank, but works. We just assume we're working with
+        // chunk 0 always.
+        //
+        // I would like to have `GroupStates` be able to accept any iterator
+        // that produce row mappings, but can't really do that with dynamic
+        // dispatch.
+        let mut addresses = Vec::with_capacity(arrays[0].logical_len());
+        UnaryExecutor::for_each::<PhysicalU64, _>(&arrays[0], |_, idx| {
+            addresses.push(GroupAddress {
+                chunk_idx: 0,
+                row_idx: idx.unwrap_or_default() as u16,
+            });
+        })?;
+
+        let chunk_iter = ChunkGroupAddressIter::new(0, &addresses);
+
+        let inputs: Vec<_> = arrays[1..].iter().collect();
+        self.states.update_states(&inputs, chunk_iter)
+    }
 }
 
 impl AggregateGroupStates for DistinctGroupedStates {
@@ -41,59 +195,34 @@ impl AggregateGroupStates for DistinctGroupedStates {
     }
 
     fn new_states(&mut self, count: usize) {
-        // Hash tables created with empty aggregates.
-        self.distinct_inputs
-            .extend((0..count).map(|_| Some(HashTable::new(16, Vec::new()))));
+        self.num_states += count;
     }
 
     fn num_states(&self) -> usize {
-        self.distinct_inputs.len()
+        self.num_states
     }
 
     fn update_states(&mut self, inputs: &[&Array], mapping: ChunkGroupAddressIter) -> Result<()> {
-        // TODO: Would be cool not needing to do this.
-        let mappings: Vec<_> = mapping.collect();
-
-        // For each group we're tracking, select the rows from the input and
-        // insert into the group specific hash table.
-        for state_idx in 0..self.distinct_inputs.len() {
-            let row_sel = Arc::new(SelectionVector::from_iter(mappings.iter().filter_map(
-                |row_mapping| {
-                    if row_mapping.to_state == state_idx {
-                        Some(row_mapping.from_row)
-                    } else {
-                        None
-                    }
-                },
-            )));
-
-            let inputs: Vec<_> = inputs
-                .iter()
-                .map(|&arr| {
-                    let mut arr = arr.clone();
-                    arr.select_mut(row_sel.clone());
-                    arr
-                })
-                .collect();
+        let len = inputs.first().map(|arr| arr.logical_len()).unwrap_or(0);
+        let mut row_sel = SelectionVector::with_capacity(len);
+        let mut group_indices = Vec::with_capacity(len);
 
-            let len = match inputs.first() {
-                Some(arr) => arr.logical_len(),
-                None => return Ok(()),
-            };
+        for row_mapping in mapping {
+            row_sel.push_location(row_mapping.from_row);
+            group_indices.push(row_mapping.to_state as u64);
+        }
 
-            self.hash_buf.clear();
-            self.hash_buf.resize(len, 0);
+        let row_sel = Arc::new(row_sel);
 
-            HashExecutor::hash_many(&inputs, &mut self.hash_buf)?;
+        let mut columns = Vec::with_capacity(inputs.len() + 1);
+        columns.push(Array::from_iter(group_indices));
+        columns.extend(inputs.iter().map(|&arr| {
+            let mut arr = arr.clone();
+            arr.select_mut(row_sel.clone());
+            arr
+        }));
 
-            // Insert into hash map with empty inputs.
-            self.distinct_inputs[state_idx]
-                .as_mut()
-                .expect("hash table to exist")
-                .insert(&inputs, &self.hash_buf, &[])?;
-        }
-
-        Ok(())
+        self.distinct_inputs.insert(&columns, &mut self.hash_buf)
     }
 
     fn combine(
@@ -101,14 +230,64 @@ impl AggregateGroupStates for DistinctGroupedStates {
         consume: &mut Box<dyn AggregateGroupStates>,
         mapping: ChunkGroupAddressIter,
     ) -> Result<()> {
-        let other_distinct_inputs = consume
-            .opaque_states_mut()
-            .downcast::<Vec<Option<HashTable>>>()?;
-
-        for mapping in mapping {
-            let target = self.distinct_inputs[mapping.to_state].as_mut().unwrap();
-            let consume = other_distinct_inputs[mapping.from_row].as_mut().unwrap();
-            target.merge(consume)?;
+        let other = consume.opaque_states_mut().downcast::<DistinctInputs>()?;
+
+        // Map group indices in the other table to our group indices.
+        //
+        // The mapping only contains groups that belong to this chunk. Inputs
+        // for other groups are kept in the other table for the next combine.
+        const UNMAPPED: usize = usize::MAX;
+        let mut group_mapping = Vec::new();
+        for row_mapping in mapping {
+            if group_mapping.len() <= row_mapping.from_row {
+                group_mapping.resize(row_mapping.from_row + 1, UNMAPPED);
+            }
+            group_mapping[row_mapping.from_row] = row_mapping.to_state;
+        }
+
+        for result in other.take_all() {
+            let columns = result?;
+
+            let mut mapped_rows = SelectionVector::with_capacity(columns[0].logical_len());
+            let mut unmapped_rows = SelectionVector::with_capacity(0);
+            let mut group_indices = Vec::with_capacity(columns[0].logical_len());
+
+            UnaryExecutor::for_each::<PhysicalU64, _>(&columns[0], |row, idx| {
+                let target = idx
+                    .and_then(|idx| group_mapping.get(idx as usize).copied())
+                    .unwrap_or(UNMAPPED);
+                if target == UNMAPPED {
+                    unmapped_rows.push_location(row);
+                } else {
+                    mapped_rows.push_location(row);
+                    group_indices.push(target as u64);
+                }
+            })?;
+
+            if !unmapped_rows.is_empty() {
+                let unmapped_rows = Arc::new(unmapped_rows);
+                let unmapped: Vec<_> = columns
+                    .iter()
+                    .map(|arr| {
+                        let mut arr = arr.clone();
+                        arr.select_mut(unmapped_rows.clone());
+                        arr
+                    })
+                    .collect();
+                other.insert(&unmapped, &mut self.hash_buf)?;
+            }
+
+            let mapped_rows = Arc::new(mapped_rows);
+            let mut mapped: Vec<_> = columns
+                .into_iter()
+                .map(|mut arr| {
+                    arr.select_mut(mapped_rows.clone());
+                    arr
+                })
+                .collect();
+            mapped[0] = Array::from_iter(group_indices);
+
+            self.distinct_inputs.insert(&mapped, &mut self.hash_buf)?;
         }
 
         Ok(())
@@ -116,37 +295,32 @@ impl AggregateGroupStates for DistinctGroupedStates {
 
     fn finalize(&mut self) -> Result<Array> {
         // And now we actually create the states we need.
-        self.states.new_states(self.distinct_inputs.len());
-
-        let mut addresses_buf = Vec::new();
-
-        for (group_idx, hash_table) in self.distinct_inputs.iter_mut().enumerate() {
-            // Drain the hash table and inserting them into the newly created
-            // states.
-            let drain = hash_table.take().unwrap().into_drain();
-
-            for result in drain {
-                let batch = result?;
-                let len = batch.num_rows();
-                // TODO: Prune group id column?
-                let arrays = batch.into_arrays();
-
-                // TODO: Bit jank, but works. We just assume we're working with
-                // chunk 0 always.
-                //
-                // I would like to have `GroupStates` be able to accept any
-                // iterator that produce row mappings, but can't really do that
-                // with dynamic dispatch.
-                addresses_buf.clear();
-                addresses_buf.extend((0..len).map(|_| GroupAddress {
-                    chunk_idx: 0,
-                    row_idx: group_idx as u16,
-                }));
-
-                let chunk_iter = ChunkGroupAddressIter::new(0, &addresses_buf);
-
-                let inputs: Vec<_> = arrays.iter().collect(); // TODO
-                self.states.update_states(&inputs, chunk_iter)?;
+        self.states.new_states(self.num_states);
+
+        if self.distinct_inputs.spilled.is_empty() {
+            // Drain the hash table and insert the distinct inputs into the
+            // newly created states.
+            for result in self.distinct_inputs.take_all() {
+                self.update_underlying(&result?)?;
+            }
+        } else {
+            // Rows with the same (group, inputs) are always written to the
+            // same partition, so each partition can be deduplicated on its
+            // own.
+            self.distinct_inputs.spill(&mut self.hash_buf)?;
+
+            for file in std::mem::take(&mut self.distinct_inputs.spilled) {
+                let mut table = HashTable::new(16, Vec::new());
+                for result in spill_reader(file) {
+                    let columns = result?;
+                    self.hash_buf.clear();
+                    self.hash_buf.resize(columns[0].logical_len(), 0);
+                    HashExecutor::hash_many(&columns, &mut self.hash_buf)?;
+                    table.insert(&columns, &self.hash_buf, &[])?;
+                }
+                for result in table.into_drain() {
+                    self.update_underlying(&result?.into_arrays())?;
+                }
             }
         }
 
@@ -154,3 +328,162 @@ impl AggregateGroupStates for DistinctGroupedStates {
         self.states.finalize()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::datatype::DataType;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::expr;
+    use crate::functions::aggregate::builtin::sum::Sum;
+    use crate::functions::aggregate::AggregateFunction;
+    use crate::logical::binder::table_list::TableList;
+    use crate::runtime::memory::MemoryTracker;
+
+    fn distinct_sum_states() -> DistinctGroupedStates {
+        distinct_sum_states_with_limit(None)
+    }
+
+    fn distinct_sum_states_with_limit(limit: Option<usize>) -> DistinctGroupedStates {
+        let mut table_list = TableList::empty();
+        let table_ref = table_list
+            .push_table(None, vec![DataType::Int64], vec!["i".to_string()])
+            .unwrap();
+        let planned = Sum
+            .plan(&table_list, vec![expr::col_ref(table_ref, 0)])
+            .unwrap();
+
+        let tracker = Arc::new(MemoryTracker::new("test", limit));
+        DistinctGroupedStates::new(
+            planned.function_impl.new_states(),
+            tracker.new_reservation(),
+        )
+    }
+
+    fn addresses(states: &[u16]) -> Vec<GroupAddress> {
+        states
+            .iter()
+            .map(|&row_idx| GroupAddress {
+                chunk_idx: 0,
+                row_idx,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn distinct_per_group() {
+        let mut states = distinct_sum_states();
+        states.new_states(3);
+
+        let input = Array::from_iter::<[i64; 6]>([1, 1, 2, 2, 2, 4]);
+        let addrs = addresses(&[0, 0, 0, 1, 1, 0]);
+        states
+            .update_states(&[&input], ChunkGroupAddressIter::new(0, &addrs))
+            .unwrap();
+
+        let out = states.finalize().unwrap();
+        assert_eq!(ScalarValue::Int64(7), out.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Int64(2), out.logical_value(1).unwrap());
+        assert_eq!(ScalarValue::Null, out.logical_value(2).unwrap());
+    }
+
+    #[test]
+    fn combine_remaps_groups() {
+        let mut states = distinct_sum_states();
+        states.new_states(2);
+        let input = Array::from_iter::<[i64; 3]>([1, 2, 8]);
+        let addrs = addresses(&[0, 0, 1]);
+        states
+            .update_states(&[&input], ChunkGroupAddressIter::new(0, &addrs))
+            .unwrap();
+
+        let mut other: Box<dyn AggregateGroupStates> = Box::new(distinct_sum_states());
+        other.new_states(2);
+        let input = Array::from_iter::<[i64; 4]>([8, 2, 3, 3]);
+        let addrs = addresses(&[0, 1, 1, 1]);
+        other
+            .update_states(&[&input], ChunkGroupAddressIter::new(0, &addrs))
+            .unwrap();
+
+        // Other's group 1 maps to our group 0. Other's group 0 belongs to a
+        // different chunk, and is combined separately.
+        let combine_addrs = [
+            GroupAddress {
+                chunk_idx: 1,
+                row_idx: 1,
+            },
+            GroupAddress {
+                chunk_idx: 0,
+                row_idx: 0,
+            },
+        ];
+        states
+            .combine(&mut other, ChunkGroupAddressIter::new(0, &combine_addrs))
+            .unwrap();
+
+        let combine_addrs = addresses(&[1]);
+        states
+            .combine(&mut other, ChunkGroupAddressIter::new(0, &combine_addrs))
+            .unwrap();
+
+        let out = states.finalize().unwrap();
+        assert_eq!(ScalarValue::Int64(6), out.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Int64(8), out.logical_value(1).unwrap());
+    }
+
+    #[test]
+    fn distinct_with_spilling() {
+        // Every insert spills.
+        let mut states = distinct_sum_states_with_limit(Some(1));
+        states.new_states(3);
+
+        let input = Array::from_iter::<[i64; 6]>([1, 1, 2, 2, 2, 4]);
+        let addrs = addresses(&[0, 0, 0, 1, 1, 0]);
+        states
+            .update_states(&[&input], ChunkGroupAddressIter::new(0, &addrs))
+            .unwrap();
+        assert!(!states.distinct_inputs.spilled.is_empty());
+
+        // Duplicates of previously spilled rows.
+        let input = Array::from_iter::<[i64; 3]>([4, 2, 8]);
+        let addrs = addresses(&[0, 1, 2]);
+        states
+            .update_states(&[&input], ChunkGroupAddressIter::new(0, &addrs))
+            .unwrap();
+
+        let out = states.finalize().unwrap();
+        assert_eq!(ScalarValue::Int64(7), out.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Int64(2), out.logical_value(1).unwrap());
+        assert_eq!(ScalarValue::Int64(8), out.logical_value(2).unwrap());
+    }
+
+    #[test]
+    fn combine_spilled() {
+        let mut states = distinct_sum_states_with_limit(Some(1));
+        states.new_states(2);
+        let input = Array::from_iter::<[i64; 3]>([1, 2, 8]);
+        let addrs = addresses(&[0, 0, 1]);
+        states
+            .update_states(&[&input], ChunkGroupAddressIter::new(0, &addrs))
+            .unwrap();
+
+        let mut other: Box<dyn AggregateGroupStates> =
+            Box::new(distinct_sum_states_with_limit(Some(1)));
+        other.new_states(2);
+        let input = Array::from_iter::<[i64; 4]>([8, 2, 3, 3]);
+        let addrs = addresses(&[0, 1, 1, 1]);
+        other
+            .update_states(&[&input], ChunkGroupAddressIter::new(0, &addrs))
+            .unwrap();
+
+        // Other's group 1 maps to our group 0, and group 0 to our group 1.
+        let combine_addrs = addresses(&[1, 0]);
+        states
+            .combine(&mut other, ChunkGroupAddressIter::new(0, &combine_addrs))
+            .unwrap();
+
+        let out = states.finalize().unwrap();
+        assert_eq!(ScalarValue::Int64(6), out.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Int64(8), out.logical_value(1).unwrap());
+    }
+}
@@ -486,12 +486,15 @@ mod tests {
     use crate::functions::aggregate::builtin::sum::Sum;
     use crate::functions::aggregate::{AggregateFunction, PlannedAggregateFunction};
     use crate::logical::binder::table_list::TableList;
+    use crate::runtime::memory::MemoryTracker;
 
     fn make_hash_table(function: PlannedAggregateFunction) -> HashTable {
         let aggregate = Aggregate {
             function: function.function_impl,
             col_selection: Bitmap::from_iter([true]),
             is_distinct: false,
+            order_by: Vec::new(),
+            memory: Arc::new(MemoryTracker::new("test", None)),
         };
 
         HashTable::new(16, vec![aggregate])
@@ -4,6 +4,8 @@ pub mod distinct;
 pub mod drain;
 pub mod entry;
 pub mod hash_table;
+pub mod ordered;
+pub mod spill;
 
 use std::collections::BTreeSet;
 use std::sync::Arc;
@@ -12,6 +14,7 @@ use std::task::{Context, Waker};
 use distinct::DistinctGroupedStates;
 use drain::HashTableDrain;
 use hash_table::HashTable;
+use ordered::OrderedGroupedStates;
 use parking_lot::Mutex;
 use rayexec_error::{RayexecError, Result};
 
@@ -23,6 +26,7 @@ use crate::arrays::datatype::DataType;
 use crate::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
 use crate::arrays::executor::physical_type::PhysicalU64;
 use crate::arrays::executor::scalar::{HashExecutor, UnaryExecutor};
+use crate::arrays::row::encoding::ComparableColumn;
 use crate::arrays::scalar::ScalarValue;
 use crate::arrays::selection::SelectionVector;
 use crate::database::DatabaseContext;
@@ -40,6 +44,8 @@ use crate::expr::physical::PhysicalAggregateExpression;
 use crate::functions::aggregate::states::AggregateGroupStates;
 use crate::functions::aggregate::AggregateFunctionImpl;
 use crate::logical::logical_aggregate::GroupingFunction;
+use crate::proto::DatabaseProtoConv;
+use crate::runtime::memory::MemoryTracker;
 
 #[derive(Debug)]
 pub struct Aggregate {
@@ -49,12 +55,34 @@ pub struct Aggregate {
     pub col_selection: Bitmap,
     /// If inputs are distinct.
     pub is_distinct: bool,
+    /// Ordering for inputs to the aggregate.
+    ///
+    /// If not empty, the last columns in the selection are the columns to
+    /// order by.
+    pub order_by: Vec<ComparableColumn>,
+    /// Tracker for memory used buffering DISTINCT and ordered inputs.
+    pub memory: Arc<MemoryTracker>,
 }
 
 impl Aggregate {
     pub fn new_states(&self) -> Result<AggregateStates> {
         if self.is_distinct {
-            let states = Box::new(DistinctGroupedStates::new(self.function.new_states()));
+            let states = Box::new(DistinctGroupedStates::new(
+                self.function.new_states(),
+                self.memory.new_reservation(),
+            ));
+            Ok(AggregateStates {
+                states,
+                col_selection: self.col_selection.clone(),
+            })
+        } else if !self.order_by.is_empty() {
+            let num_inputs = self.col_selection.count_trues() - self.order_by.len();
+            let states = Box::new(OrderedGroupedStates::new(
+                self.function.new_states(),
+                num_inputs,
+                self.order_by.clone(),
+                self.memory.new_reservation(),
+            ));
             Ok(AggregateStates {
                 states,
                 col_selection: self.col_selection.clone(),
@@ -185,6 +213,7 @@ impl PhysicalHashAggregate {
         let mut agg_input_cols = BTreeSet::new();
         for expr in &exprs {
             agg_input_cols.extend(expr.columns.iter().map(|expr| expr.idx));
+            agg_input_cols.extend(expr.order_by.iter().map(|expr| expr.column.idx));
         }
 
         // Used to generate intial null masks. This doesn't take into account
@@ -225,7 +254,8 @@ impl PhysicalHashAggregate {
 impl ExecutableOperator for PhysicalHashAggregate {
     fn create_states(
         &self,
-        _context: &DatabaseContext,
+        context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let num_partitions = partitions[0];
@@ -266,6 +296,15 @@ impl ExecutableOperator for PhysicalHashAggregate {
                             function: expr.function.function_impl.clone(),
                             col_selection: col_selection.clone(),
                             is_distinct: expr.is_distinct,
+                            order_by: expr
+                                .order_by
+                                .iter()
+                                .map(|order_by| ComparableColumn {
+                                    desc: order_by.desc,
+                                    nulls_first: order_by.nulls_first,
+                                })
+                                .collect(),
+                            memory: context.memory_tracker().clone(),
                         })
                         .collect();
                     HashTable::new(16, aggregates)
@@ -619,3 +658,75 @@ impl Explainable for PhysicalHashAggregate {
         ExplainEntry::new("HashAggregate").with_values("aggregate_columns", &self.aggregate_columns)
     }
 }
+
+impl DatabaseProtoConv for PhysicalHashAggregate {
+    type ProtoType = rayexec_proto::generated::execution::PhysicalHashAggregate;
+
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        use rayexec_proto::generated::execution::{
+            GroupingFunction as ProtoGroupingFunction,
+            NullMask,
+        };
+
+        Ok(Self::ProtoType {
+            grouping_functions: self
+                .grouping_functions
+                .iter()
+                .map(|f| ProtoGroupingFunction {
+                    group_exprs: f.group_exprs.iter().map(|&idx| idx as u64).collect(),
+                })
+                .collect(),
+            null_masks: self
+                .null_masks
+                .iter()
+                .map(|mask| NullMask {
+                    data: mask.as_bytes().to_vec(),
+                    len: mask.len() as u64,
+                })
+                .collect(),
+            group_columns: self.group_columns.iter().map(|&idx| idx as u64).collect(),
+            aggregate_columns: self
+                .aggregate_columns
+                .iter()
+                .map(|&idx| idx as u64)
+                .collect(),
+            exprs: self
+                .exprs
+                .iter()
+                .map(|expr| expr.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        Ok(Self {
+            grouping_functions: proto
+                .grouping_functions
+                .into_iter()
+                .map(|f| GroupingFunction {
+                    group_exprs: f.group_exprs.into_iter().map(|idx| idx as usize).collect(),
+                })
+                .collect(),
+            null_masks: proto
+                .null_masks
+                .into_iter()
+                .map(|mask| Bitmap::try_new(mask.data, mask.len as usize))
+                .collect::<Result<Vec<_>>>()?,
+            group_columns: proto
+                .group_columns
+                .into_iter()
+                .map(|idx| idx as usize)
+                .collect(),
+            aggregate_columns: proto
+                .aggregate_columns
+                .into_iter()
+                .map(|idx| idx as usize)
+                .collect(),
+            exprs: proto
+                .exprs
+                .into_iter()
+                .map(|expr| DatabaseProtoConv::from_proto_ctx(expr, context))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+}
@@ -0,0 +1,555 @@
+use std::sync::Arc;
+
+use rayexec_error::Result;
+
+use super::spill::{buffered_size, SpillFile, SpillReader};
+use crate::arrays::array::Array;
+use crate::arrays::batch::Batch;
+use crate::arrays::executor::physical_type::PhysicalU64;
+use crate::arrays::executor::scalar::{concat, UnaryExecutor};
+use crate::arrays::row::encoding::{ComparableColumn, ComparableRowEncoder};
+use crate::arrays::selection::SelectionVector;
+use crate::execution::operators::hash_aggregate::hash_table::GroupAddress;
+use crate::execution::operators::sort::util::merger::{IterState, KWayMerger, MergeResult};
+use crate::execution::operators::sort::util::sorted_batch::{
+    PhysicallySortedBatch,
+    SortedKeysIter,
+};
+use crate::functions::aggregate::states::{AggregateGroupStates, OpaqueStatesMut};
+use crate::functions::aggregate::ChunkGroupAddressIter;
+use crate::runtime::memory::MemoryReservation;
+
+/// Number of rows per block when writing sorted runs, and per batch when
+/// merging them.
+const RUN_BATCH_SIZE: usize = 4096;
+
+/// An implementation of GroupedStates that buffers inputs to an aggregate so
+/// that they can be provided to the aggregate in a specific order.
+///
+/// Inputs are expected to be the aggregate inputs followed by the columns to
+/// order by. Only the aggregate inputs are passed to the underlying states.
+///
+/// If the buffered inputs grow past the memory limit, they're sorted and
+/// written out as a run to a spill file. Runs are merged when finalizing.
+#[derive(Debug)]
+pub struct OrderedGroupedStates {
+    /// Number of groups we're tracking.
+    num_states: usize,
+    /// Buffered (group, inputs, order by) rows.
+    buffered: OrderedInputs,
+    /// The underlying states.
+    ///
+    /// These won't be initialized until we've received all input.
+    states: Box<dyn AggregateGroupStates>,
+}
+
+/// Buffered batches with the group index as the first column.
+///
+/// Wrapped to allow downcasting when combining states.
+#[derive(Debug)]
+struct OrderedInputs {
+    /// Number of inputs to the underlying aggregate. Remaining inputs are the
+    /// order by columns.
+    num_inputs: usize,
+    /// How each order by column should be ordered.
+    order_by: Vec<ComparableColumn>,
+    batches: Vec<Vec<Array>>,
+    /// Memory reserved for the buffered batches.
+    reservation: MemoryReservation,
+    /// Sorted runs of spilled rows.
+    runs: Vec<SpillFile>,
+}
+
+impl OrderedInputs {
+    /// Buffer a batch, spilling if we're over the memory limit.
+    fn push(&mut self, columns: Vec<Array>) -> Result<()> {
+        let size = buffered_size(&columns);
+        self.batches.push(columns);
+
+        if self.reservation.try_grow(size).is_err() {
+            self.spill()?;
+        }
+
+        Ok(())
+    }
+
+    /// Encode the order by columns.
+    fn sorted_batch(&self, columns: Vec<Array>) -> Result<PhysicallySortedBatch> {
+        let keys: Vec<_> = columns[(1 + self.num_inputs)..].iter().collect();
+        let keys = ComparableRowEncoder {
+            columns: self.order_by.clone(),
+        }
+        .encode(&keys)?;
+
+        Ok(PhysicallySortedBatch {
+            batch: Batch::try_new(columns)?,
+            keys,
+        })
+    }
+
+    /// Take the buffered batches, returning all columns sorted by the order by
+    /// columns.
+    ///
+    /// Rows for different groups are interleaved, but each group sees its own
+    /// rows in order.
+    fn take_sorted(&mut self) -> Result<Option<Vec<Array>>> {
+        let batches = std::mem::take(&mut self.batches);
+        std::mem::drop(self.reservation.take());
+
+        if batches.is_empty() {
+            return Ok(None);
+        }
+
+        let num_columns = batches[0].len();
+        let columns = (0..num_columns)
+            .map(|col_idx| {
+                let arrays: Vec<_> = batches.iter().map(|batch| &batch[col_idx]).collect();
+                concat(&arrays)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let keys: Vec<_> = columns[(1 + self.num_inputs)..].iter().collect();
+        let rows = ComparableRowEncoder {
+            columns: self.order_by.clone(),
+        }
+        .encode(&keys)?;
+
+        let mut indices: Vec<_> = (0..rows.num_rows()).collect();
+        indices.sort_by_key(|&idx| rows.row(idx).expect("row to exist"));
+
+        Ok(Some(select_columns(
+            &columns,
+            &Arc::new(SelectionVector::from(indices)),
+        )))
+    }
+
+    /// Sort the buffered batches and write them out as a new run.
+    fn spill(&mut self) -> Result<()> {
+        let sorted = match self.take_sorted()? {
+            Some(sorted) => sorted,
+            None => return Ok(()),
+        };
+
+        let mut run = SpillFile::try_new()?;
+        let len = sorted[0].logical_len();
+        for start in (0..len).step_by(RUN_BATCH_SIZE) {
+            let end = usize::min(start + RUN_BATCH_SIZE, len);
+            run.write(&select_columns(
+                &sorted,
+                &Arc::new(SelectionVector::with_range(start..end)),
+            ))?;
+        }
+        self.runs.push(run);
+
+        Ok(())
+    }
+
+    /// Take all rows, both in memory and spilled, leaving this empty.
+    fn take_all(&mut self) -> Box<dyn Iterator<Item = Result<Vec<Array>>>> {
+        let batches = std::mem::take(&mut self.batches);
+        let runs = std::mem::take(&mut self.runs);
+        std::mem::drop(self.reservation.take());
+
+        let runs =
+            runs.into_iter()
+                .flat_map(|run| -> Box<dyn Iterator<Item = Result<Vec<Array>>>> {
+                    match run.into_reader() {
+                        Ok(reader) => Box::new(reader),
+                        Err(e) => Box::new(std::iter::once(Err(e))),
+                    }
+                });
+
+        Box::new(batches.into_iter().map(Ok).chain(runs))
+    }
+
+    /// Read the next block from a run as a sorted batch.
+    fn next_sorted_batch(
+        &self,
+        reader: &mut SpillReader,
+    ) -> Result<Option<(Batch, SortedKeysIter)>> {
+        match reader.next() {
+            Some(columns) => Ok(Some(self.sorted_batch(columns?)?.into_batch_and_iter())),
+            None => Ok(None),
+        }
+    }
+}
+
+impl OrderedGroupedStates {
+    pub fn new(
+        states: Box<dyn AggregateGroupStates>,
+        num_inputs: usize,
+        order_by: Vec<ComparableColumn>,
+        reservation: MemoryReservation,
+    ) -> Self {
+        OrderedGroupedStates {
+            num_states: 0,
+            buffered: OrderedInputs {
+                num_inputs,
+                order_by,
+                batches: Vec::new(),
+                reservation,
+                runs: Vec::new(),
+            },
+            states,
+        }
+    }
+
+    /// Update the underlying states with sorted (group, inputs, order by)
+    /// rows.
+    fn update_underlying(&mut self, sorted: &[Array]) -> Result<()> {
+        // TODO: Same as distinct, assume we're always working with chunk 0.
+        let mut addresses = Vec::with_capacity(sorted[0].logical_len());
+        UnaryExecutor::for_each::<PhysicalU64, _>(&sorted[0], |_, idx| {
+            addresses.push(GroupAddress {
+                chunk_idx: 0,
+                row_idx: idx.unwrap_or_default() as u16,
+            });
+        })?;
+
+        let inputs: Vec<_> = sorted[1..(1 + self.buffered.num_inputs)].iter().collect();
+        self.states
+            .update_states(&inputs, ChunkGroupAddressIter::new(0, &addresses))
+    }
+
+    /// Merge all spilled runs, updating the underlying states in order.
+    fn merge_runs(&mut self) -> Result<()> {
+        let mut readers = std::mem::take(&mut self.buffered.runs)
+            .into_iter()
+            .map(SpillFile::into_reader)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut inputs = Vec::with_capacity(readers.len());
+        for reader in &mut readers {
+            match self.buffered.next_sorted_batch(reader)? {
+                Some((batch, iter)) => inputs.push((Some(batch), IterState::Iterator(iter))),
+                None => inputs.push((None, IterState::Finished)),
+            }
+        }
+
+        let mut merger = KWayMerger::try_new(inputs)?;
+        loop {
+            match merger.try_merge(RUN_BATCH_SIZE)? {
+                MergeResult::Batch(batch) => self.update_underlying(batch.columns())?,
+                MergeResult::NeedsInput(idx) => {
+                    match self.buffered.next_sorted_batch(&mut readers[idx])? {
+                        Some((batch, iter)) => merger.push_batch_for_input(idx, batch, iter)?,
+                        None => merger.input_finished(idx),
+                    }
+                }
+                MergeResult::Exhausted => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Select rows from all columns.
+fn select_columns<'a>(
+    columns: impl IntoIterator<Item = &'a Array>,
+    selection: &Arc<SelectionVector>,
+) -> Vec<Array> {
+    columns
+        .into_iter()
+        .map(|arr| {
+            let mut arr = arr.clone();
+            arr.select_mut(selection.clone());
+            arr
+        })
+        .collect()
+}
+
+impl AggregateGroupStates for OrderedGroupedStates {
+    fn opaque_states_mut(&mut self) -> OpaqueStatesMut<'_> {
+        OpaqueStatesMut(&mut self.buffered)
+    }
+
+    fn new_states(&mut self, count: usize) {
+        self.num_states += count;
+    }
+
+    fn num_states(&self) -> usize {
+        self.num_states
+    }
+
+    fn update_states(&mut self, inputs: &[&Array], mapping: ChunkGroupAddressIter) -> Result<()> {
+        let len = inputs.first().map(|arr| arr.logical_len()).unwrap_or(0);
+        let mut row_sel = SelectionVector::with_capacity(len);
+        let mut group_indices = Vec::with_capacity(len);
+
+        for row_mapping in mapping {
+            row_sel.push_location(row_mapping.from_row);
+            group_indices.push(row_mapping.to_state as u64);
+        }
+
+        if group_indices.is_empty() {
+            return Ok(());
+        }
+
+        let mut columns = Vec::with_capacity(inputs.len() + 1);
+        columns.push(Array::from_iter(group_indices));
+        columns.extend(select_columns(inputs.iter().copied(), &Arc::new(row_sel)));
+
+        self.buffered.push(columns)
+    }
+
+    fn combine(
+        &mut self,
+        consume: &mut Box<dyn AggregateGroupStates>,
+        mapping: ChunkGroupAddressIter,
+    ) -> Result<()> {
+        let other = consume.opaque_states_mut().downcast::<OrderedInputs>()?;
+
+        // Map group indices in the other states to our group indices.
+        //
+        // The mapping only contains groups that belong to this chunk. Inputs
+        // for other groups are kept in the other states for the next combine.
+        const UNMAPPED: usize = usize::MAX;
+        let mut group_mapping = Vec::new();
+        for row_mapping in mapping {
+            if group_mapping.len() <= row_mapping.from_row {
+                group_mapping.resize(row_mapping.from_row + 1, UNMAPPED);
+            }
+            group_mapping[row_mapping.from_row] = row_mapping.to_state;
+        }
+
+        for result in other.take_all() {
+            let columns = result?;
+            let len = columns[0].logical_len();
+            let mut mapped_rows = SelectionVector::with_capacity(len);
+            let mut unmapped_rows = SelectionVector::with_capacity(0);
+            let mut group_indices = Vec::with_capacity(len);
+
+            UnaryExecutor::for_each::<PhysicalU64, _>(&columns[0], |row, idx| {
+                let target = idx
+                    .and_then(|idx| group_mapping.get(idx as usize).copied())
+                    .unwrap_or(UNMAPPED);
+                if target == UNMAPPED {
+                    unmapped_rows.push_location(row);
+                } else {
+                    mapped_rows.push_location(row);
+                    group_indices.push(target as u64);
+                }
+            })?;
+
+            if !unmapped_rows.is_empty() {
+                other.push(select_columns(&columns, &Arc::new(unmapped_rows)))?;
+            }
+
+            if !group_indices.is_empty() {
+                let mut mapped = select_columns(&columns, &Arc::new(mapped_rows));
+                mapped[0] = Array::from_iter(group_indices);
+                self.buffered.push(mapped)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<Array> {
+        // And now we actually create the states we need.
+        self.states.new_states(self.num_states);
+
+        if self.buffered.runs.is_empty() {
+            if let Some(sorted) = self.buffered.take_sorted()? {
+                self.update_underlying(&sorted)?;
+            }
+        } else {
+            // Write out whatever's still buffered so that everything can be
+            // merged from the sorted runs.
+            self.buffered.spill()?;
+            self.merge_runs()?;
+        }
+
+        // Now we can actually drain the states.
+        self.states.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::datatype::DataType;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::expr;
+    use crate::functions::aggregate::builtin::string_agg::StringAgg;
+    use crate::functions::aggregate::AggregateFunction;
+    use crate::logical::binder::table_list::TableList;
+    use crate::runtime::memory::MemoryTracker;
+
+    /// STRING_AGG with inputs ordered by a single int column.
+    fn ordered_string_agg_states(desc: bool) -> OrderedGroupedStates {
+        ordered_string_agg_states_with_limit(desc, None)
+    }
+
+    fn ordered_string_agg_states_with_limit(
+        desc: bool,
+        limit: Option<usize>,
+    ) -> OrderedGroupedStates {
+        let mut table_list = TableList::empty();
+        let table_ref = table_list
+            .push_table(
+                None,
+                vec![DataType::Utf8, DataType::Utf8],
+                vec!["s".to_string(), "sep".to_string()],
+            )
+            .unwrap();
+        let planned = StringAgg
+            .plan(
+                &table_list,
+                vec![expr::col_ref(table_ref, 0), expr::lit(",")],
+            )
+            .unwrap();
+
+        OrderedGroupedStates::new(
+            planned.function_impl.new_states(),
+            2,
+            vec![ComparableColumn {
+                desc,
+                nulls_first: false,
+            }],
+            Arc::new(MemoryTracker::new("test", limit)).new_reservation(),
+        )
+    }
+
+    fn addresses(states: &[u16]) -> Vec<GroupAddress> {
+        states
+            .iter()
+            .map(|&row_idx| GroupAddress {
+                chunk_idx: 0,
+                row_idx,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn inputs_ordered_per_group() {
+        let mut states = ordered_string_agg_states(false);
+        states.new_states(2);
+
+        let strings = Array::from_iter(["c", "a", "y", "b", "x"]);
+        let seps = Array::from_iter([",", ",", ",", ",", ","]);
+        let keys = Array::from_iter::<[i32; 5]>([3, 1, 2, 2, 1]);
+        let addrs = addresses(&[0, 0, 1, 0, 1]);
+        states
+            .update_states(
+                &[&strings, &seps, &keys],
+                ChunkGroupAddressIter::new(0, &addrs),
+            )
+            .unwrap();
+
+        let out = states.finalize().unwrap();
+        assert_eq!(ScalarValue::from("a,b,c"), out.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::from("x,y"), out.logical_value(1).unwrap());
+    }
+
+    #[test]
+    fn combine_keeps_order() {
+        let mut states = ordered_string_agg_states(true);
+        states.new_states(1);
+        let strings = Array::from_iter(["a", "c"]);
+        let seps = Array::from_iter([",", ","]);
+        let keys = Array::from_iter::<[i32; 2]>([1, 3]);
+        let addrs = addresses(&[0, 0]);
+        states
+            .update_states(
+                &[&strings, &seps, &keys],
+                ChunkGroupAddressIter::new(0, &addrs),
+            )
+            .unwrap();
+
+        let mut other: Box<dyn AggregateGroupStates> = Box::new(ordered_string_agg_states(true));
+        other.new_states(2);
+        let strings = Array::from_iter(["b", "z"]);
+        let seps = Array::from_iter([",", ","]);
+        let keys = Array::from_iter::<[i32; 2]>([2, 4]);
+        let addrs = addresses(&[1, 0]);
+        other
+            .update_states(
+                &[&strings, &seps, &keys],
+                ChunkGroupAddressIter::new(0, &addrs),
+            )
+            .unwrap();
+
+        // Only other's group 1 belongs to this chunk.
+        let combine_addrs = [
+            GroupAddress {
+                chunk_idx: 1,
+                row_idx: 0,
+            },
+            GroupAddress {
+                chunk_idx: 0,
+                row_idx: 0,
+            },
+        ];
+        states
+            .combine(&mut other, ChunkGroupAddressIter::new(0, &combine_addrs))
+            .unwrap();
+
+        let out = states.finalize().unwrap();
+        assert_eq!(ScalarValue::from("c,b,a"), out.logical_value(0).unwrap());
+    }
+
+    #[test]
+    fn ordered_with_spilling() {
+        // Limit small enough that every update spills a run.
+        let mut states = ordered_string_agg_states_with_limit(false, Some(1));
+        states.new_states(2);
+
+        for (strings, keys, addrs) in [
+            (["c", "a", "y"], [3, 1, 2], [0, 0, 1]),
+            (["b", "x", "d"], [2, 1, 4], [0, 1, 0]),
+        ] {
+            let strings = Array::from_iter(strings);
+            let seps = Array::from_iter([",", ",", ","]);
+            let keys = Array::from_iter::<[i32; 3]>(keys);
+            let addrs = addresses(&addrs);
+            states
+                .update_states(
+                    &[&strings, &seps, &keys],
+                    ChunkGroupAddressIter::new(0, &addrs),
+                )
+                .unwrap();
+        }
+        assert_eq!(2, states.buffered.runs.len());
+
+        let out = states.finalize().unwrap();
+        assert_eq!(ScalarValue::from("a,b,c,d"), out.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::from("x,y"), out.logical_value(1).unwrap());
+    }
+
+    #[test]
+    fn combine_spilled_runs() {
+        let mut states = ordered_string_agg_states(false);
+        states.new_states(1);
+        let strings = Array::from_iter(["b"]);
+        let seps = Array::from_iter([","]);
+        let keys = Array::from_iter::<[i32; 1]>([2]);
+        let addrs = addresses(&[0]);
+        states
+            .update_states(
+                &[&strings, &seps, &keys],
+                ChunkGroupAddressIter::new(0, &addrs),
+            )
+            .unwrap();
+
+        let mut other: Box<dyn AggregateGroupStates> =
+            Box::new(ordered_string_agg_states_with_limit(false, Some(1)));
+        other.new_states(1);
+        let strings = Array::from_iter(["c", "a"]);
+        let seps = Array::from_iter([",", ","]);
+        let keys = Array::from_iter::<[i32; 2]>([3, 1]);
+        let addrs = addresses(&[0, 0]);
+        other
+            .update_states(
+                &[&strings, &seps, &keys],
+                ChunkGroupAddressIter::new(0, &addrs),
+            )
+            .unwrap();
+
+        states
+            .combine(&mut other, ChunkGroupAddressIter::new(0, &addrs[..1]))
+            .unwrap();
+
+        let out = states.finalize().unwrap();
+        assert_eq!(ScalarValue::from("a,b,c"), out.logical_value(0).unwrap());
+    }
+}
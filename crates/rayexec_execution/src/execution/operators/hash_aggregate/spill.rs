@@ -0,0 +1,234 @@
+//! Spilling buffered aggregate inputs to temporary files.
+//!
+//! DISTINCT and ordered aggregates need to see all of their inputs before
+//! updating the underlying states. Inputs are buffered in memory until the
+//! query's memory limit is reached, at which point they're written out to
+//! spill files and read back when finalizing.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::PathBuf;
+
+use rayexec_error::{RayexecError, Result, ResultExt};
+use rayexec_proto::generated::storage::TableBlock;
+use rayexec_proto::prost::Message;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::DataType;
+use crate::storage::disk::{decode_column, encode_column};
+
+/// Number of files distinct inputs are partitioned into when spilled.
+pub const SPILL_PARTITIONS: usize = 16;
+
+/// Get the spill partition for a hash.
+///
+/// Uses the high bits of the hash since the low bits are used for picking
+/// slots in the hash table rows are read back into.
+pub const fn spill_partition_for_hash(hash: u64) -> usize {
+    (hash >> 60) as usize % SPILL_PARTITIONS
+}
+
+/// Estimated number of bytes needed to buffer the rows of some columns.
+///
+/// Columns may be selections over larger arrays, only the selected rows are
+/// counted.
+pub fn buffered_size(columns: &[Array]) -> usize {
+    columns
+        .iter()
+        .map(|arr| {
+            let data = arr.array_data();
+            match data.len() {
+                0 => 0,
+                len => data.data_size_bytes() / len * arr.logical_len(),
+            }
+        })
+        .sum()
+}
+
+/// A temporary file containing batches of spilled rows.
+///
+/// Each batch is written as a length prefixed block of encoded columns. The
+/// file is deleted on drop.
+#[derive(Debug)]
+pub struct SpillFile {
+    path: PathBuf,
+    writer: Option<BufWriter<File>>,
+    /// Types of the spilled columns, set on the first write.
+    datatypes: Vec<DataType>,
+    num_rows: usize,
+}
+
+impl SpillFile {
+    pub fn try_new() -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("rayexec_spill_{}", uuid::Uuid::new_v4()));
+        let file = File::create(&path)
+            .context_fn(|| format!("Failed to create spill file: {}", path.display()))?;
+
+        Ok(SpillFile {
+            path,
+            writer: Some(BufWriter::new(file)),
+            datatypes: Vec::new(),
+            num_rows: 0,
+        })
+    }
+
+    /// Number of rows written to the file.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Append rows to the file.
+    ///
+    /// All writes to a file must have the same column types.
+    pub fn write(&mut self, columns: &[Array]) -> Result<()> {
+        let len = match columns.first() {
+            Some(arr) => arr.logical_len(),
+            None => return Ok(()),
+        };
+        if len == 0 {
+            return Ok(());
+        }
+
+        if self.datatypes.is_empty() {
+            self.datatypes = columns.iter().map(|arr| arr.datatype().clone()).collect();
+        }
+
+        let block = TableBlock {
+            columns: columns
+                .iter()
+                .map(encode_column)
+                .collect::<Result<Vec<_>>>()?,
+            column_ids: Vec::new(),
+        };
+        let buf = block.encode_to_vec();
+
+        let writer = self
+            .writer
+            .as_mut()
+            .ok_or_else(|| RayexecError::new("Spill file already read"))?;
+        writer
+            .write_all(&(buf.len() as u64).to_le_bytes())
+            .and_then(|_| writer.write_all(&buf))
+            .context("Failed to write to spill file")?;
+        self.num_rows += len;
+
+        Ok(())
+    }
+
+    /// Finish writing, and return a reader producing the written batches in
+    /// the order they were written.
+    pub fn into_reader(mut self) -> Result<SpillReader> {
+        if let Some(writer) = self.writer.take() {
+            writer
+                .into_inner()
+                .map_err(|e| e.into_error())
+                .context("Failed to flush spill file")?;
+        }
+
+        let file = File::open(&self.path).context("Failed to open spill file")?;
+
+        Ok(SpillReader {
+            reader: BufReader::new(file),
+            file: self,
+        })
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        // Close before removing.
+        self.writer.take();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Reads batches back from a spill file.
+#[derive(Debug)]
+pub struct SpillReader {
+    reader: BufReader<File>,
+    /// File being read, deleted once the reader is dropped.
+    file: SpillFile,
+}
+
+impl SpillReader {
+    fn read_next(&mut self) -> Result<Option<Vec<Array>>> {
+        let mut len_buf = [0; 8];
+        match self.reader.read_exact(&mut len_buf) {
+            Ok(()) => (),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e).context("Failed to read spill file"),
+        }
+
+        let mut buf = vec![0; u64::from_le_bytes(len_buf) as usize];
+        self.reader
+            .read_exact(&mut buf)
+            .context("Failed to read spill file")?;
+        let block = TableBlock::decode(buf.as_slice()).context("Failed to decode spill block")?;
+
+        if block.columns.len() != self.file.datatypes.len() {
+            return Err(RayexecError::new(format!(
+                "Spill block has {} columns, expected {}",
+                block.columns.len(),
+                self.file.datatypes.len()
+            )));
+        }
+
+        let columns = block
+            .columns
+            .into_iter()
+            .zip(&self.file.datatypes)
+            .map(|(column, datatype)| decode_column(column, datatype))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(columns))
+    }
+}
+
+impl Iterator for SpillReader {
+    type Item = Result<Vec<Array>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+
+    #[test]
+    fn write_then_read() {
+        let mut file = SpillFile::try_new().unwrap();
+        file.write(&[
+            Array::from_iter::<[i64; 2]>([1, 2]),
+            Array::from_iter([Some("a"), None]),
+        ])
+        .unwrap();
+        file.write(&[Array::from_iter::<[i64; 1]>([3]), Array::from_iter(["c"])])
+            .unwrap();
+        assert_eq!(3, file.num_rows());
+
+        let path = file.path.clone();
+        let mut reader = file.into_reader().unwrap();
+
+        let first = reader.next().unwrap().unwrap();
+        assert_eq!(ScalarValue::Int64(2), first[0].logical_value(1).unwrap());
+        assert_eq!(ScalarValue::Null, first[1].logical_value(1).unwrap());
+        let second = reader.next().unwrap().unwrap();
+        assert_eq!(ScalarValue::from("c"), second[1].logical_value(0).unwrap());
+        assert!(reader.next().is_none());
+
+        std::mem::drop(reader);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn buffered_size_counts_selected_rows() {
+        let mut arr = Array::from_iter::<[i64; 4]>([1, 2, 3, 4]);
+        assert_eq!(32, buffered_size(&[arr.clone()]));
+
+        arr.select_mut(crate::arrays::selection::SelectionVector::from(vec![0, 2]));
+        assert_eq!(16, buffered_size(&[arr]));
+    }
+}
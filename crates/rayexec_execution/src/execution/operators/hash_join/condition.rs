@@ -1,15 +1,19 @@
 use std::fmt;
 use std::sync::Arc;
 
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{OptionExt, RayexecError, Result};
 
 use crate::arrays::array::Array;
 use crate::arrays::batch::Batch;
+use crate::arrays::datatype::DataType;
 use crate::arrays::executor::scalar::SelectExecutor;
 use crate::arrays::selection::SelectionVector;
+use crate::database::DatabaseContext;
 use crate::expr::physical::PhysicalScalarExpression;
+use crate::functions::proto::{decode_planned_scalar_function, encode_planned_scalar_function};
 use crate::functions::scalar::builtin::boolean::AndImpl;
 use crate::functions::scalar::{PlannedScalarFunction, ScalarFunctionImpl};
+use crate::proto::DatabaseProtoConv;
 
 #[derive(Debug, Clone)]
 pub struct HashJoinCondition {
@@ -23,6 +27,10 @@ pub struct HashJoinCondition {
     /// condition was created for. Assumed to take exactly two inputs (left and
     /// right).
     pub function: PlannedScalarFunction,
+    /// Datatypes of the inputs to the comparison function.
+    ///
+    /// Needed for planning the function again when deserializing.
+    pub input_types: Vec<DataType>,
 }
 
 impl fmt::Display for HashJoinCondition {
@@ -37,6 +45,33 @@ impl fmt::Display for HashJoinCondition {
     }
 }
 
+impl DatabaseProtoConv for HashJoinCondition {
+    type ProtoType = rayexec_proto::generated::execution::HashJoinCondition;
+
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            left: Some(self.left.to_proto_ctx(context)?),
+            right: Some(self.right.to_proto_ctx(context)?),
+            function: Some(encode_planned_scalar_function(
+                &self.function,
+                &self.input_types,
+            )?),
+        })
+    }
+
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        let (function, input_types) =
+            decode_planned_scalar_function(proto.function.required("function")?, context)?;
+
+        Ok(Self {
+            left: DatabaseProtoConv::from_proto_ctx(proto.left.required("left")?, context)?,
+            right: DatabaseProtoConv::from_proto_ctx(proto.right.required("right")?, context)?,
+            function,
+            input_types,
+        })
+    }
+}
+
 /// Join condition with the left side precomputed.
 ///
 /// When inserting into the hash table for the left side, the left scalar
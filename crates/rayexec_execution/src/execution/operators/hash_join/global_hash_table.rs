@@ -19,6 +19,24 @@ use crate::execution::operators::util::outer_join_tracker::{
     RightOuterJoinTracker,
 };
 
+/// How the global table is probed.
+///
+/// Picked once all build partitions have completed and the actual number of
+/// rows on the build side is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinStrategy {
+    /// Look up candidate rows in the hash table using hashes of the probe
+    /// side keys.
+    Hash,
+    /// Build side is small enough that every build row is a candidate for
+    /// every probe row.
+    ///
+    /// Skips merging the partition hash tables and hashing the probe side.
+    /// Join conditions are evaluated against the cross product of each probe
+    /// batch with each build batch.
+    NestedLoop,
+}
+
 /// Global hash table shared across all partitions for a single instance of a
 /// hash join operator.
 ///
@@ -30,7 +48,11 @@ pub struct GlobalHashTable {
     /// Conditions we're joining on.
     conditions: LeftPrecomputedJoinConditions,
     /// Hash table pointing to a row.
+    ///
+    /// Empty when probing with a nested loop.
     hash_table: RawTable<(u64, RowKey)>,
+    /// Strategy to use when probing.
+    strategy: JoinStrategy,
     /// Column types for left side of join.
     ///
     /// Used when generating the left columns for a RIGHT OUTER join.
@@ -46,17 +68,34 @@ pub struct GlobalHashTable {
 
 impl GlobalHashTable {
     /// Merge many partition hash tables into a new global hash table.
+    ///
+    /// If the partition tables hold `nested_loop_threshold` rows or fewer, the
+    /// hash tables are discarded and the table is probed using a nested loop.
+    /// A threshold of zero always probes using the hash table.
     pub fn new(
         left_types: Vec<DataType>,
         right_join: bool,
         is_mark: bool,
         partition_tables: Vec<PartitionHashTable>,
         conditions: &[HashJoinCondition],
+        nested_loop_threshold: usize,
     ) -> Self {
         // Merge all partition tables left to right.
 
         let batches_cap: usize = partition_tables.iter().map(|t| t.batches.len()).sum();
         let hash_table_cap: usize = partition_tables.iter().map(|t| t.hash_table.len()).sum();
+
+        // Each row on the build side has exactly one entry in the partition
+        // hash tables.
+        let strategy = if nested_loop_threshold > 0 && hash_table_cap <= nested_loop_threshold {
+            JoinStrategy::NestedLoop
+        } else {
+            JoinStrategy::Hash
+        };
+        let hash_table_cap = match strategy {
+            JoinStrategy::Hash => hash_table_cap,
+            JoinStrategy::NestedLoop => 0,
+        };
         let precomputed_cap: usize = partition_tables
             .iter()
             .map(|t| {
@@ -91,9 +130,11 @@ impl GlobalHashTable {
 
             // Merge hash tables, updating row key to point to the correct batch
             // in the merged batch vec.
-            for (hash, mut row_key) in table.hash_table.drain() {
-                row_key.batch_idx += batch_offset as u32;
-                hash_table.insert(hash, (hash, row_key), |(hash, _)| *hash);
+            if strategy == JoinStrategy::Hash {
+                for (hash, mut row_key) in table.hash_table.drain() {
+                    row_key.batch_idx += batch_offset as u32;
+                    hash_table.insert(hash, (hash, row_key), |(hash, _)| *hash);
+                }
             }
 
             // Append all precompute left results.
@@ -113,6 +154,7 @@ impl GlobalHashTable {
             batches,
             conditions,
             hash_table,
+            strategy,
             left_types,
             right_join,
             is_mark,
@@ -123,7 +165,14 @@ impl GlobalHashTable {
         &self.batches
     }
 
+    pub fn strategy(&self) -> JoinStrategy {
+        self.strategy
+    }
+
     /// Probe the table.
+    ///
+    /// `hashes` should contain the hashes of the right side keys, and is
+    /// ignored when probing with a nested loop.
     pub fn probe(
         &self,
         right: &Batch,
@@ -134,41 +183,10 @@ impl GlobalHashTable {
         //
         // The value is a vec of (left_idx, right_idx) pairs pointing to rows in
         // the left (build) and right (probe) batches respectively
-        let mut row_indices: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
-
-        for (right_idx, hash) in hashes.iter().enumerate() {
-            // Get all matching row keys from hash table.
-            //
-            // SAFETY: Iterator only lives for this method call.
-            // See: https://docs.rs/hashbrown/latest/hashbrown/raw/struct.RawTable.html#method.iter_hash
-            unsafe {
-                self.hash_table.iter_hash(*hash).for_each(|bucket| {
-                    let val = bucket.as_ref(); // Unsafe
-                    let row_key = val.1;
-
-                    // Hashbrown only stores first seven bits of hash. We check
-                    // here to further prune items we pull out of the table.
-                    //
-                    // Note this still doesn't guarantee row equality. That is
-                    // checked when we actually execute the conditions, this
-                    // just gets us the candidates.
-                    if &val.0 != hash {
-                        return;
-                    }
-
-                    // This is all safe, just adding to the row_indices vec.
-                    use std::collections::hash_map::Entry;
-                    match row_indices.entry(row_key.batch_idx as usize) {
-                        Entry::Occupied(mut ent) => {
-                            ent.get_mut().push((row_key.row_idx as usize, right_idx))
-                        }
-                        Entry::Vacant(ent) => {
-                            ent.insert(vec![(row_key.row_idx as usize, right_idx)]);
-                        }
-                    }
-                })
-            }
-        }
+        let row_indices = match self.strategy {
+            JoinStrategy::Hash => self.hash_candidates(hashes),
+            JoinStrategy::NestedLoop => self.nested_loop_candidates(right.num_rows()),
+        };
 
         let mut right_tracker = if self.right_join {
             Some(RightOuterJoinTracker::new_for_batch(right))
@@ -241,6 +259,68 @@ impl GlobalHashTable {
 
         Ok(batches)
     }
+
+    /// Get candidate (left, right) row pairs for each build batch using the
+    /// hash table.
+    fn hash_candidates(&self, hashes: &[u64]) -> HashMap<usize, Vec<(usize, usize)>> {
+        let mut row_indices: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+
+        for (right_idx, hash) in hashes.iter().enumerate() {
+            // Get all matching row keys from hash table.
+            //
+            // SAFETY: Iterator only lives for this method call.
+            // See: https://docs.rs/hashbrown/latest/hashbrown/raw/struct.RawTable.html#method.iter_hash
+            unsafe {
+                self.hash_table.iter_hash(*hash).for_each(|bucket| {
+                    let val = bucket.as_ref(); // Unsafe
+                    let row_key = val.1;
+
+                    // Hashbrown only stores first seven bits of hash. We check
+                    // here to further prune items we pull out of the table.
+                    //
+                    // Note this still doesn't guarantee row equality. That is
+                    // checked when we actually execute the conditions, this
+                    // just gets us the candidates.
+                    if &val.0 != hash {
+                        return;
+                    }
+
+                    // This is all safe, just adding to the row_indices vec.
+                    use std::collections::hash_map::Entry;
+                    match row_indices.entry(row_key.batch_idx as usize) {
+                        Entry::Occupied(mut ent) => {
+                            ent.get_mut().push((row_key.row_idx as usize, right_idx))
+                        }
+                        Entry::Vacant(ent) => {
+                            ent.insert(vec![(row_key.row_idx as usize, right_idx)]);
+                        }
+                    }
+                })
+            }
+        }
+
+        row_indices
+    }
+
+    /// Get every (left, right) row pair for each build batch.
+    fn nested_loop_candidates(&self, num_right_rows: usize) -> HashMap<usize, Vec<(usize, usize)>> {
+        let mut row_indices = HashMap::with_capacity(self.batches.len());
+
+        for (batch_idx, batch) in self.batches.iter().enumerate() {
+            let num_left_rows = batch.num_rows();
+            if num_left_rows == 0 || num_right_rows == 0 {
+                continue;
+            }
+
+            let mut pairs = Vec::with_capacity(num_left_rows * num_right_rows);
+            for left_idx in 0..num_left_rows {
+                pairs.extend((0..num_right_rows).map(|right_idx| (left_idx, right_idx)));
+            }
+            row_indices.insert(batch_idx, pairs);
+        }
+
+        row_indices
+    }
 }
 
 impl fmt::Debug for GlobalHashTable {
@@ -7,11 +7,13 @@ use std::sync::Arc;
 use std::task::{Context, Waker};
 
 use condition::HashJoinCondition;
-use global_hash_table::GlobalHashTable;
+use global_hash_table::{GlobalHashTable, JoinStrategy};
 use parking_lot::Mutex;
 use partition_hash_table::PartitionHashTable;
 use rayexec_error::{OptionExt, RayexecError, Result};
+use rayexec_proto::ProtoConv;
 
+use super::runtime_filter::{RuntimeFilter, RuntimeFilterBuilder};
 use super::util::outer_join_tracker::{LeftOuterJoinDrainState, LeftOuterJoinTracker};
 use super::{
     ComputedBatches,
@@ -30,6 +32,12 @@ use crate::arrays::executor::scalar::HashExecutor;
 use crate::database::DatabaseContext;
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 use crate::logical::logical_join::JoinType;
+use crate::proto::DatabaseProtoConv;
+use crate::runtime::memory::MemoryReservation;
+
+/// Default max number of rows on the build side for a hash join to switch to
+/// probing with a nested loop.
+pub const DEFAULT_NESTED_LOOP_THRESHOLD: usize = 16;
 
 #[derive(Debug)]
 pub struct HashJoinBuildPartitionState {
@@ -40,6 +48,13 @@ pub struct HashJoinBuildPartitionState {
     local_hashtable: Option<PartitionHashTable>,
     /// Reusable hashes buffer.
     hash_buf: Vec<u64>,
+    /// Memory reserved for batches inserted into the local table.
+    ///
+    /// Moved to the global state once this partition finishes building.
+    reservation: MemoryReservation,
+    /// Builders for each runtime filter, merged into the global state once
+    /// this partition finishes building.
+    runtime_filter_builders: Vec<RuntimeFilterBuilder>,
 }
 
 #[derive(Debug)]
@@ -83,6 +98,13 @@ struct SharedState {
     completed_hash_tables: Vec<PartitionHashTable>,
     /// Global hash table once it's been built.
     global_hash_table: Option<Arc<GlobalHashTable>>,
+    /// Runtime filter builders merged from completed build partitions.
+    runtime_filter_builders: Option<Vec<RuntimeFilterBuilder>>,
+    /// Memory reserved for batches collected from all build partitions.
+    ///
+    /// Held for as long as the operator since probers reference the batches
+    /// until the end.
+    reservation: MemoryReservation,
     /// Number of partitions that are probiding the table.
     ///
     /// This is used to initialize the drain states such that each partition
@@ -132,6 +154,17 @@ pub struct PhysicalHashJoin {
     /// Types for the batches we'll be receiving from the right side. Used
     /// during LEFT joins to produce null columns on the right side.
     right_types: Vec<DataType>,
+    /// Runtime filters to complete once the build side has been read, paired
+    /// with the index of the equality providing the keys.
+    ///
+    /// Scans on the probe side hold the same filters.
+    runtime_filters: Vec<(usize, Arc<RuntimeFilter>)>,
+    /// Max number of rows on the build side to probe with a nested loop
+    /// instead of the hash table.
+    ///
+    /// Checked against the actual number of rows once the build side
+    /// completes. Zero always uses the hash table.
+    nested_loop_threshold: usize,
 }
 
 impl PhysicalHashJoin {
@@ -161,9 +194,27 @@ impl PhysicalHashJoin {
             conditions,
             left_types,
             right_types,
+            runtime_filters: Vec::new(),
+            nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
         }
     }
 
+    /// Complete runtime filters with the keys from the build side.
+    ///
+    /// `equality_idx` should point to an equality in `equalities`. Filters are
+    /// only valid for joins that discard probe rows without a match.
+    pub fn with_runtime_filters(mut self, filters: Vec<(usize, Arc<RuntimeFilter>)>) -> Self {
+        debug_assert!(filters.iter().all(|(idx, _)| *idx < self.equalities.len()));
+        self.runtime_filters = filters;
+        self
+    }
+
+    /// Set the max number of build side rows to probe using a nested loop.
+    pub fn with_nested_loop_threshold(mut self, threshold: usize) -> Self {
+        self.nested_loop_threshold = threshold;
+        self
+    }
+
     const fn join_requires_drain(&self) -> bool {
         // Note that while a SEMI join is pretty much an inner join just with
         // the right chopped off, we need to be able to handle duplicate rows on
@@ -190,7 +241,8 @@ impl PhysicalHashJoin {
 impl ExecutableOperator for PhysicalHashJoin {
     fn create_states(
         &self,
-        _context: &DatabaseContext,
+        context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         // TODO: Determine if this is what we want.
@@ -200,6 +252,8 @@ impl ExecutableOperator for PhysicalHashJoin {
         let shared = SharedState {
             completed_hash_tables: Vec::with_capacity(build_partitions),
             global_hash_table: None,
+            runtime_filter_builders: None,
+            reservation: context.memory_tracker().new_reservation(),
             probe_partition_count: probe_partitions,
             build_inputs_remaining: build_partitions,
             probe_inputs_remaining: probe_partitions,
@@ -217,6 +271,12 @@ impl ExecutableOperator for PhysicalHashJoin {
                 PartitionState::HashJoinBuild(HashJoinBuildPartitionState {
                     local_hashtable: Some(PartitionHashTable::new(&self.conditions)),
                     hash_buf: Vec::new(),
+                    reservation: context.memory_tracker().new_reservation(),
+                    runtime_filter_builders: self
+                        .runtime_filters
+                        .iter()
+                        .map(|_| RuntimeFilterBuilder::default())
+                        .collect(),
                 })
             })
             .collect();
@@ -309,22 +369,27 @@ impl ExecutableOperator for PhysicalHashJoin {
                     // Continue on.
                 }
 
+                let hashtable = state.global.as_ref().expect("hash table to exist");
+
                 // Compute right hashes on equality condition.
+                //
+                // Not needed if the build side was small enough to probe with
+                // a nested loop.
                 state.hash_buf.clear();
-                state.hash_buf.resize(batch.num_rows(), 0);
+                if hashtable.strategy() == JoinStrategy::Hash {
+                    state.hash_buf.resize(batch.num_rows(), 0);
 
-                for (idx, equality) in self.equalities.iter().enumerate() {
-                    let result = equality.right.eval(&batch)?;
+                    for (idx, equality) in self.equalities.iter().enumerate() {
+                        let result = equality.right.eval(&batch)?;
 
-                    if idx == 0 {
-                        HashExecutor::hash_no_combine(&result, &mut state.hash_buf)?;
-                    } else {
-                        HashExecutor::hash_combine(&result, &mut state.hash_buf)?;
+                        if idx == 0 {
+                            HashExecutor::hash_no_combine(&result, &mut state.hash_buf)?;
+                        } else {
+                            HashExecutor::hash_combine(&result, &mut state.hash_buf)?;
+                        }
                     }
                 }
 
-                let hashtable = state.global.as_ref().expect("hash table to exist");
-
                 let batches = hashtable.probe(
                     &batch,
                     &state.hash_buf,
@@ -365,6 +430,17 @@ impl ExecutableOperator for PhysicalHashJoin {
                     Some(table) => shared.completed_hash_tables.push(table),
                     None => return Err(RayexecError::new("Missing partition table")), // Shouldn't happen.
                 }
+                shared.reservation.merge(state.reservation.take());
+
+                let builders = std::mem::take(&mut state.runtime_filter_builders);
+                match shared.runtime_filter_builders.as_mut() {
+                    Some(merged) => {
+                        for (merged, builder) in merged.iter_mut().zip(builders) {
+                            merged.merge(builder);
+                        }
+                    }
+                    None => shared.runtime_filter_builders = Some(builders),
+                }
 
                 shared.build_inputs_remaining -= 1;
 
@@ -376,18 +452,26 @@ impl ExecutableOperator for PhysicalHashJoin {
                 // into their local states to avoid needing to synchronize.
                 if shared.build_inputs_remaining == 0 {
                     let completed = std::mem::take(&mut shared.completed_hash_tables);
+                    let builders = shared.runtime_filter_builders.take().unwrap_or_default();
 
                     // Release the lock. Building the table can be
                     // computationally expensive. Other threads still need
                     // access to the global state to register wakers.
                     std::mem::drop(shared);
 
+                    // Complete runtime filters first so probe side scans can
+                    // start while we build the table.
+                    for ((_, filter), builder) in self.runtime_filters.iter().zip(builders) {
+                        filter.complete(builder.finish());
+                    }
+
                     let global = GlobalHashTable::new(
                         self.left_types.clone(),
                         self.is_right_join(),
                         self.is_mark_join(),
                         completed,
                         &self.conditions,
+                        self.nested_loop_threshold,
                     );
 
                     // Reacquire, and place in global state.
@@ -607,6 +691,8 @@ impl PhysicalHashJoin {
         state: &mut HashJoinBuildPartitionState,
         batch: Batch,
     ) -> Result<()> {
+        state.reservation.try_grow_for_batch(&batch)?;
+
         // Compute left hashes on equality conditions.
 
         state.hash_buf.clear();
@@ -620,6 +706,16 @@ impl PhysicalHashJoin {
             } else {
                 HashExecutor::hash_combine(&result, &mut state.hash_buf)?;
             }
+
+            for ((equality_idx, _), builder) in self
+                .runtime_filters
+                .iter()
+                .zip(state.runtime_filter_builders.iter_mut())
+            {
+                if *equality_idx == idx {
+                    builder.insert(&result)?;
+                }
+            }
         }
 
         state
@@ -634,9 +730,80 @@ impl PhysicalHashJoin {
 
 impl Explainable for PhysicalHashJoin {
     fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
-        ExplainEntry::new("HashJoin")
+        let mut ent = ExplainEntry::new("HashJoin")
             .with_values("conditions", &self.conditions)
             .with_values("equalities", &self.equalities)
             .with_value("join_type", self.join_type)
+            .with_value("nested_loop_threshold", self.nested_loop_threshold);
+        if !self.runtime_filters.is_empty() {
+            ent = ent.with_values(
+                "runtime_filters",
+                self.runtime_filters
+                    .iter()
+                    .map(|(idx, _)| &self.equalities[*idx].right),
+            );
+        }
+        ent
+    }
+}
+
+impl DatabaseProtoConv for PhysicalHashJoin {
+    type ProtoType = rayexec_proto::generated::execution::PhysicalHashJoin;
+
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            join_type: Some(self.join_type.to_proto()?),
+            equalities: self
+                .equalities
+                .iter()
+                .map(|c| c.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+            conditions: self
+                .conditions
+                .iter()
+                .map(|c| c.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+            left_types: self
+                .left_types
+                .iter()
+                .map(|t| t.to_proto())
+                .collect::<Result<Vec<_>>>()?,
+            right_types: self
+                .right_types
+                .iter()
+                .map(|t| t.to_proto())
+                .collect::<Result<Vec<_>>>()?,
+            nested_loop_threshold: self.nested_loop_threshold as u64,
+        })
+    }
+
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        Ok(Self {
+            join_type: ProtoConv::from_proto(proto.join_type.required("join_type")?)?,
+            equalities: proto
+                .equalities
+                .into_iter()
+                .map(|c| DatabaseProtoConv::from_proto_ctx(c, context))
+                .collect::<Result<Vec<_>>>()?,
+            conditions: proto
+                .conditions
+                .into_iter()
+                .map(|c| DatabaseProtoConv::from_proto_ctx(c, context))
+                .collect::<Result<Vec<_>>>()?,
+            left_types: proto
+                .left_types
+                .into_iter()
+                .map(ProtoConv::from_proto)
+                .collect::<Result<Vec<_>>>()?,
+            right_types: proto
+                .right_types
+                .into_iter()
+                .map(ProtoConv::from_proto)
+                .collect::<Result<Vec<_>>>()?,
+            // Runtime filters are shared with scans in the same plan, and
+            // aren't sent with the operator.
+            runtime_filters: Vec::new(),
+            nested_loop_threshold: proto.nested_loop_threshold as usize,
+        })
     }
 }
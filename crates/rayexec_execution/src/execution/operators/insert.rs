@@ -1,12 +1,17 @@
 use std::sync::Arc;
 
-use rayexec_error::{OptionExt, RayexecError, Result};
+use futures::future::BoxFuture;
+use rayexec_error::{ErrorKind, OptionExt, RayexecError, Result};
 
 use super::sink::{PartitionSink, SinkOperation, SinkOperator};
-use crate::database::catalog::CatalogTx;
+use crate::arrays::batch::Batch;
+use crate::arrays::executor::physical_type::PhysicalBool;
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::arrays::field::Field;
 use crate::database::catalog_entry::CatalogEntry;
 use crate::database::DatabaseContext;
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::expr::physical::PhysicalScalarExpression;
 use crate::proto::DatabaseProtoConv;
 
 pub type PhysicalInsert = SinkOperator<InsertOperation>;
@@ -16,6 +21,28 @@ pub struct InsertOperation {
     pub catalog: String,
     pub schema: String,
     pub table: Arc<CatalogEntry>,
+    pub checks: Vec<InsertCheck>,
+}
+
+/// A CHECK constraint to validate inserted rows against.
+#[derive(Debug, Clone)]
+pub struct InsertCheck {
+    /// Index of the checked column in the table.
+    pub column_idx: usize,
+    /// Expression evaluated on a batch containing only the checked column.
+    pub expr: PhysicalScalarExpression,
+    /// SQL for the constraint, used in error messages.
+    pub sql: String,
+}
+
+impl InsertOperation {
+    /// If inserted rows need to be validated against any constraints.
+    fn has_constraints(&self) -> Result<bool> {
+        let table = self.table.try_as_table_entry()?;
+        Ok(!self.checks.is_empty()
+            || !table.primary_key.is_empty()
+            || table.columns.iter().any(|c| !c.nullable))
+    }
 }
 
 impl SinkOperation for InsertOperation {
@@ -24,30 +51,118 @@ impl SinkOperation for InsertOperation {
         context: &DatabaseContext,
         num_sinks: usize,
     ) -> Result<Vec<Box<dyn PartitionSink>>> {
-        // TODO: Placeholder.
-        let _tx = CatalogTx::new();
-
         let database = context.get_database(&self.catalog)?;
         let data_table = database
             .table_storage
             .as_ref()
             .ok_or_else(|| RayexecError::new("Missing table storage for insert"))?
-            .data_table(&self.schema, &self.table)?;
+            .data_table(context.transaction(), &self.schema, &self.table)?;
 
-        // TODO: Pass constraints, on conflict
+        // TODO: On conflict
         let inserts = data_table.insert(num_sinks)?;
 
-        Ok(inserts)
+        let table = self.table.try_as_table_entry()?;
+        if self.checks.is_empty() && table.columns.iter().all(|c| c.nullable) {
+            return Ok(inserts);
+        }
+
+        let columns: Arc<[Field]> = table.columns.clone().into();
+        let checks: Arc<[InsertCheck]> = self.checks.clone().into();
+
+        Ok(inserts
+            .into_iter()
+            .map(|inner| {
+                Box::new(ValidatingInsert {
+                    columns: columns.clone(),
+                    checks: checks.clone(),
+                    inner,
+                }) as _
+            })
+            .collect())
     }
 
     fn partition_requirement(&self) -> Option<usize> {
-        None
+        // Constraint violations should abort the whole insert. Using a single
+        // partition means a violation is found before any partition
+        // finalizes.
+        match self.has_constraints() {
+            Ok(true) => Some(1),
+            _ => None,
+        }
+    }
+}
+
+/// Sink validating NOT NULL and CHECK constraints before pushing batches to
+/// the table.
+#[derive(Debug)]
+struct ValidatingInsert {
+    /// Columns of the table, with nullability.
+    columns: Arc<[Field]>,
+    checks: Arc<[InsertCheck]>,
+    inner: Box<dyn PartitionSink>,
+}
+
+impl ValidatingInsert {
+    fn validate(&self, batch: &Batch) -> Result<()> {
+        for (field, array) in self.columns.iter().zip(batch.columns()) {
+            // Arrays without a validity mask can't contain NULLs.
+            if field.nullable || array.validity().is_none() {
+                continue;
+            }
+            if (0..array.logical_len()).any(|row| array.is_valid(row) == Some(false)) {
+                return Err(RayexecError::new(format!(
+                    "NULL value in column '{}' violates NOT NULL constraint",
+                    field.name
+                ))
+                .with_kind(ErrorKind::NotNullViolation));
+            }
+        }
+
+        for check in self.checks.iter() {
+            let input = batch.project(&[check.column_idx]);
+            let result = check.expr.eval(&input)?;
+
+            // Only false fails the check, NULL is treated as passing.
+            let mut violated = false;
+            UnaryExecutor::for_each::<PhysicalBool, _>(&result, |_, v| {
+                if v == Some(false) {
+                    violated = true;
+                }
+            })?;
+
+            if violated {
+                return Err(RayexecError::new(format!(
+                    "Value in column '{}' violates CHECK constraint: {}",
+                    self.columns[check.column_idx].name, check.sql
+                ))
+                .with_kind(ErrorKind::CheckViolation));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PartitionSink for ValidatingInsert {
+    fn push(&mut self, batch: Batch) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async {
+            self.validate(&batch)?;
+            self.inner.push(batch).await
+        })
+    }
+
+    fn finalize(&mut self) -> BoxFuture<'_, Result<()>> {
+        self.inner.finalize()
     }
 }
 
 impl Explainable for InsertOperation {
     fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
-        ExplainEntry::new("Insert").with_value("table", &self.table.name)
+        let ent = ExplainEntry::new("Insert").with_value("table", &self.table.name);
+        if self.checks.is_empty() {
+            return ent;
+        }
+        ent.with_values("checks", self.checks.iter().map(|c| &c.expr))
     }
 }
 
@@ -55,10 +170,24 @@ impl DatabaseProtoConv for PhysicalInsert {
     type ProtoType = rayexec_proto::generated::execution::PhysicalInsert;
 
     fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        use rayexec_proto::generated::execution::InsertCheck as ProtoInsertCheck;
+
         Ok(Self::ProtoType {
             catalog: self.sink.catalog.clone(),
             schema: self.sink.schema.clone(),
             table: Some(self.sink.table.to_proto_ctx(context)?),
+            checks: self
+                .sink
+                .checks
+                .iter()
+                .map(|check| {
+                    Ok(ProtoInsertCheck {
+                        column_idx: check.column_idx as u64,
+                        expr: Some(check.expr.to_proto_ctx(context)?),
+                        sql: check.sql.clone(),
+                    })
+                })
+                .collect::<Result<_>>()?,
         })
     }
 
@@ -70,6 +199,20 @@ impl DatabaseProtoConv for PhysicalInsert {
                 proto.table.required("table")?,
                 context,
             )?),
+            checks: proto
+                .checks
+                .into_iter()
+                .map(|check| {
+                    Ok(InsertCheck {
+                        column_idx: check.column_idx as usize,
+                        expr: DatabaseProtoConv::from_proto_ctx(
+                            check.expr.required("expr")?,
+                            context,
+                        )?,
+                        sql: check.sql,
+                    })
+                })
+                .collect::<Result<_>>()?,
         }))
     }
 }
@@ -65,6 +65,7 @@ impl ExecutableOperator for PhysicalLimit {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let partitions = partitions[0];
@@ -241,10 +242,13 @@ mod tests {
         unwrap_poll_pull_batch,
         TestWakerContext,
     };
+    use crate::execution::operators::util::resizer::DEFAULT_TARGET_BATCH_SIZE;
 
     fn create_states(operator: &PhysicalLimit, partitions: usize) -> Vec<PartitionState> {
         let context = test_database_context();
-        let states = operator.create_states(&context, vec![partitions]).unwrap();
+        let states = operator
+            .create_states(&context, DEFAULT_TARGET_BATCH_SIZE, vec![partitions])
+            .unwrap();
 
         match states.partition_states {
             InputOutputStates::OneToOne { partition_states } => partition_states,
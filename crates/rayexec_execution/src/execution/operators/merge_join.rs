@@ -0,0 +1,817 @@
+use std::fmt;
+use std::sync::Arc;
+use std::task::{Context, Waker};
+
+use parking_lot::Mutex;
+use rayexec_error::{OptionExt, RayexecError, Result};
+use rayexec_proto::ProtoConv;
+
+use super::hash_join::condition::{
+    HashJoinCondition,
+    LeftPrecomputedJoinCondition,
+    LeftPrecomputedJoinConditions,
+};
+use super::util::outer_join_tracker::{
+    LeftOuterJoinDrainState,
+    LeftOuterJoinTracker,
+    RightOuterJoinTracker,
+};
+use super::{
+    ComputedBatches,
+    ExecutableOperator,
+    ExecutionStates,
+    InputOutputStates,
+    OperatorState,
+    PartitionState,
+    PollFinalize,
+    PollPull,
+    PollPush,
+};
+use crate::arrays::batch::Batch;
+use crate::arrays::datatype::DataType;
+use crate::arrays::row::encoding::{ComparableColumn, ComparableRowEncoder, ComparableRows};
+use crate::arrays::selection::SelectionVector;
+use crate::database::DatabaseContext;
+use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::logical::logical_join::{JoinType, MergeJoinKey};
+use crate::proto::DatabaseProtoConv;
+use crate::runtime::memory::MemoryReservation;
+
+#[derive(Debug)]
+pub struct MergeJoinBuildPartitionState {
+    /// Batches collected from the left side, in sorted order.
+    batches: Vec<Batch>,
+    /// Encoded merge keys for each batch.
+    keys: Vec<ComparableRows>,
+    /// Conditions with the left side precomputed for each batch.
+    conditions: LeftPrecomputedJoinConditions,
+    /// Memory reserved for the collected batches.
+    ///
+    /// Moved to the operator state once the build completes.
+    reservation: MemoryReservation,
+}
+
+#[derive(Debug)]
+pub struct MergeJoinProbePartitionState {
+    /// The sorted left side. If None, the operator state should be checked to
+    /// see if the build has completed.
+    left: Option<Arc<SortedLeft>>,
+    /// Position of the first left row that may still join with the right
+    /// side.
+    ///
+    /// Only moves forward since the right side is sorted too.
+    cursor: LeftCursor,
+    /// Buffered output batches.
+    buffered_output: ComputedBatches,
+    /// Waker that's stored from a push if there's already a buffered batch.
+    push_waker: Option<Waker>,
+    /// Waker that's stored from a pull if there's no batch available.
+    pull_waker: Option<Waker>,
+    /// If the input for this partition is complete.
+    input_finished: bool,
+    /// Track rows visited on the left side.
+    ///
+    /// Initialized once the build completes for LEFT and FULL joins.
+    outer_join_tracker: Option<LeftOuterJoinTracker>,
+    /// State for emitting unvisited left rows once probing is done.
+    outer_join_drain_state: Option<LeftOuterJoinDrainState>,
+}
+
+#[derive(Debug)]
+pub struct MergeJoinOperatorState {
+    inner: Mutex<SharedState>,
+}
+
+#[derive(Debug)]
+struct SharedState {
+    /// Left side once the build completes.
+    left: Option<Arc<SortedLeft>>,
+    /// Memory reserved for the left batches.
+    ///
+    /// Held for as long as the operator since the prober references the
+    /// batches until the end.
+    reservation: MemoryReservation,
+    /// Waker for the prober if it tried to probe before the build completed.
+    probe_push_waker: Option<Waker>,
+}
+
+/// All batches from the left side along with their encoded keys.
+#[derive(Debug)]
+struct SortedLeft {
+    batches: Vec<Batch>,
+    keys: Vec<ComparableRows>,
+    conditions: LeftPrecomputedJoinConditions,
+}
+
+impl SortedLeft {
+    fn key(&self, cursor: LeftCursor) -> Option<&[u8]> {
+        self.keys
+            .get(cursor.batch_idx)
+            .and_then(|keys| keys.row(cursor.row_idx))
+            .map(|row| row.data())
+    }
+
+    fn next(&self, cursor: LeftCursor) -> LeftCursor {
+        let num_rows = self.keys[cursor.batch_idx].num_rows();
+        if cursor.row_idx + 1 < num_rows {
+            LeftCursor {
+                batch_idx: cursor.batch_idx,
+                row_idx: cursor.row_idx + 1,
+            }
+        } else {
+            LeftCursor {
+                batch_idx: cursor.batch_idx + 1,
+                row_idx: 0,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct LeftCursor {
+    batch_idx: usize,
+    row_idx: usize,
+}
+
+/// Join for inputs that are both sorted on the join keys.
+///
+/// Rows on the left and right are matched by walking both sides in order,
+/// comparing the row encoded keys. Left rows are collected, but no hash table
+/// is built. Right rows are streamed through, with each right row only
+/// checked against the run of left rows with the same key.
+///
+/// Both sides must be a single partition to keep their order.
+#[derive(Debug)]
+pub struct PhysicalMergeJoin {
+    join_type: JoinType,
+    /// All left/right conditions, including the equalities used as merge
+    /// keys.
+    ///
+    /// Checked for every pair of rows with equal keys.
+    conditions: Vec<HashJoinCondition>,
+    /// Equalities in `conditions` the inputs are sorted on.
+    keys: Vec<MergeJoinKey>,
+    /// Encoder for the key columns, matching the sort order of the inputs.
+    encoder: ComparableRowEncoder,
+    /// Types for the batches from the left side. Used during RIGHT joins to
+    /// produce null columns on the left side.
+    left_types: Vec<DataType>,
+    /// Types for the batches from the right side. Used during LEFT joins to
+    /// produce null columns on the right side.
+    right_types: Vec<DataType>,
+}
+
+impl PhysicalMergeJoin {
+    pub const BUILD_SIDE_INPUT_INDEX: usize = 0;
+    pub const PROBE_SIDE_INPUT_INDEX: usize = 1;
+
+    pub fn new(
+        join_type: JoinType,
+        conditions: Vec<HashJoinCondition>,
+        keys: Vec<MergeJoinKey>,
+        left_types: Vec<DataType>,
+        right_types: Vec<DataType>,
+    ) -> Self {
+        assert!(!keys.is_empty());
+
+        let encoder = ComparableRowEncoder {
+            columns: keys
+                .iter()
+                .map(|key| ComparableColumn {
+                    desc: key.desc,
+                    nulls_first: key.nulls_first,
+                })
+                .collect(),
+        };
+
+        PhysicalMergeJoin {
+            join_type,
+            conditions,
+            keys,
+            encoder,
+            left_types,
+            right_types,
+        }
+    }
+
+    const fn is_left_join(&self) -> bool {
+        matches!(self.join_type, JoinType::Left | JoinType::Full)
+    }
+
+    const fn is_right_join(&self) -> bool {
+        matches!(self.join_type, JoinType::Right | JoinType::Full)
+    }
+
+    /// Encode the keys for one side of the join.
+    fn encode_keys(&self, batch: &Batch, left: bool) -> Result<ComparableRows> {
+        let arrays = self
+            .keys
+            .iter()
+            .map(|key| {
+                let condition = &self.conditions[key.condition];
+                let expr = if left {
+                    &condition.left
+                } else {
+                    &condition.right
+                };
+                expr.eval(batch)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let arrays: Vec<_> = arrays.iter().map(|arr| arr.as_ref()).collect();
+
+        self.encoder.encode(&arrays)
+    }
+
+    /// Join a batch from the right with the left side.
+    fn probe(
+        &self,
+        left: &SortedLeft,
+        cursor: &mut LeftCursor,
+        right: &Batch,
+        mut left_outer_tracker: Option<&mut LeftOuterJoinTracker>,
+    ) -> Result<Vec<Batch>> {
+        let right_keys = self.encode_keys(right, false)?;
+
+        // Candidate (left, right) row pairs with equal keys, grouped by left
+        // batch.
+        let mut selections: Vec<(SelectionVector, SelectionVector)> =
+            vec![Default::default(); left.batches.len()];
+
+        for (right_idx, right_key) in right_keys.iter().enumerate() {
+            let right_key = right_key.data();
+
+            // Skip left rows that sort before this key. No later right row can
+            // match them either.
+            while let Some(left_key) = left.key(*cursor) {
+                if left_key >= right_key {
+                    break;
+                }
+                *cursor = left.next(*cursor);
+            }
+
+            // Every left row in the run of equal keys is a candidate. The
+            // cursor stays at the start of the run since the next right row
+            // may have the same key.
+            let mut pos = *cursor;
+            while let Some(left_key) = left.key(pos) {
+                if left_key != right_key {
+                    break;
+                }
+                let (left_sel, right_sel) = &mut selections[pos.batch_idx];
+                left_sel.push_location(pos.row_idx);
+                right_sel.push_location(right_idx);
+                pos = left.next(pos);
+            }
+        }
+
+        let mut right_tracker = if self.is_right_join() {
+            Some(RightOuterJoinTracker::new_for_batch(right))
+        } else {
+            None
+        };
+
+        let mut batches = Vec::new();
+        for (batch_idx, (left_sel, right_sel)) in selections.into_iter().enumerate() {
+            if left_sel.is_empty() {
+                continue;
+            }
+
+            // Equal encoded keys don't mean the rows join, nulls encode the
+            // same as each other. Run all conditions to prune the candidates.
+            let (left_sel, right_sel) = left
+                .conditions
+                .compute_selection_for_probe(batch_idx, left_sel, right_sel, right)?;
+
+            if let Some(right_tracker) = right_tracker.as_mut() {
+                right_tracker.mark_rows_visited(right_sel.iter_locations());
+            }
+            if let Some(left_tracker) = left_outer_tracker.as_mut() {
+                left_tracker.mark_rows_visited_for_batch(batch_idx, left_sel.iter_locations());
+            }
+
+            let left_cols = left.batches[batch_idx]
+                .select(Arc::new(left_sel))
+                .into_arrays();
+            let right_cols = right.select(Arc::new(right_sel)).into_arrays();
+
+            batches.push(Batch::try_new(left_cols.into_iter().chain(right_cols))?);
+        }
+
+        if let Some(right_tracker) = right_tracker {
+            if let Some(extra) = right_tracker.into_unvisited(&self.left_types, right)? {
+                batches.push(extra);
+            }
+        }
+
+        Ok(batches)
+    }
+}
+
+impl ExecutableOperator for PhysicalMergeJoin {
+    fn create_states(
+        &self,
+        context: &DatabaseContext,
+        _batch_size: usize,
+        partitions: Vec<usize>,
+    ) -> Result<ExecutionStates> {
+        // Multiple partitions would each see only part of the sorted input.
+        if partitions[0] != 1 {
+            return Err(RayexecError::new(format!(
+                "Merge join requires a single partition, got {}",
+                partitions[0]
+            )));
+        }
+
+        let operator_state = MergeJoinOperatorState {
+            inner: Mutex::new(SharedState {
+                left: None,
+                reservation: context.memory_tracker().new_reservation(),
+                probe_push_waker: None,
+            }),
+        };
+
+        let build_state = PartitionState::MergeJoinBuild(MergeJoinBuildPartitionState {
+            batches: Vec::new(),
+            keys: Vec::new(),
+            conditions: LeftPrecomputedJoinConditions {
+                conditions: self
+                    .conditions
+                    .iter()
+                    .map(|c| LeftPrecomputedJoinCondition::from(c.clone()))
+                    .collect(),
+            },
+            reservation: context.memory_tracker().new_reservation(),
+        });
+
+        let probe_state = PartitionState::MergeJoinProbe(MergeJoinProbePartitionState {
+            left: None,
+            cursor: LeftCursor::default(),
+            buffered_output: ComputedBatches::None,
+            push_waker: None,
+            pull_waker: None,
+            input_finished: false,
+            outer_join_tracker: None,
+            outer_join_drain_state: None,
+        });
+
+        Ok(ExecutionStates {
+            operator_state: Arc::new(OperatorState::MergeJoin(operator_state)),
+            partition_states: InputOutputStates::NaryInputSingleOutput {
+                partition_states: vec![vec![build_state], vec![probe_state]],
+                pull_states: Self::PROBE_SIDE_INPUT_INDEX,
+            },
+        })
+    }
+
+    fn poll_push(
+        &self,
+        cx: &mut Context,
+        partition_state: &mut PartitionState,
+        operator_state: &OperatorState,
+        batch: Batch,
+    ) -> Result<PollPush> {
+        match partition_state {
+            PartitionState::MergeJoinBuild(state) => {
+                // Empty batches would only get in the way of moving the
+                // cursor.
+                if batch.num_rows() == 0 {
+                    return Ok(PollPush::NeedsMore);
+                }
+
+                state.reservation.try_grow_for_batch(&batch)?;
+                state.keys.push(self.encode_keys(&batch, true)?);
+                state.conditions.precompute_for_left_batch(&batch)?;
+                state.batches.push(batch);
+
+                Ok(PollPush::NeedsMore)
+            }
+            PartitionState::MergeJoinProbe(state) => {
+                // Wait for pending output to get pulled before computing more.
+                if !state.buffered_output.is_empty() {
+                    state.push_waker = Some(cx.waker().clone());
+                    return Ok(PollPush::Pending(batch));
+                }
+
+                if state.left.is_none() {
+                    let mut shared = match operator_state {
+                        OperatorState::MergeJoin(state) => state.inner.lock(),
+                        other => panic!("invalid operator state: {other:?}"),
+                    };
+
+                    match shared.left.as_ref() {
+                        Some(left) => {
+                            if self.is_left_join() {
+                                state.outer_join_tracker =
+                                    Some(LeftOuterJoinTracker::new_for_batches(&left.batches));
+                            }
+                            state.left = Some(left.clone());
+                        }
+                        None => {
+                            // Come back once the build completes.
+                            shared.probe_push_waker = Some(cx.waker().clone());
+                            return Ok(PollPush::Pending(batch));
+                        }
+                    }
+                }
+
+                let left = state.left.as_ref().expect("left side to exist");
+                let batches = self.probe(
+                    left,
+                    &mut state.cursor,
+                    &batch,
+                    state.outer_join_tracker.as_mut(),
+                )?;
+
+                state.buffered_output = ComputedBatches::new(batches);
+                if state.buffered_output.is_empty() {
+                    return Ok(PollPush::NeedsMore);
+                }
+
+                if let Some(waker) = state.pull_waker.take() {
+                    waker.wake();
+                }
+
+                Ok(PollPush::Pushed)
+            }
+            other => panic!("invalid partition state: {other:?}"),
+        }
+    }
+
+    fn poll_finalize_push(
+        &self,
+        cx: &mut Context,
+        partition_state: &mut PartitionState,
+        operator_state: &OperatorState,
+    ) -> Result<PollFinalize> {
+        let mut shared = match operator_state {
+            OperatorState::MergeJoin(state) => state.inner.lock(),
+            other => panic!("invalid operator state: {other:?}"),
+        };
+
+        match partition_state {
+            PartitionState::MergeJoinBuild(state) => {
+                let conditions = std::mem::replace(
+                    &mut state.conditions,
+                    LeftPrecomputedJoinConditions {
+                        conditions: Vec::new(),
+                    },
+                );
+
+                shared.left = Some(Arc::new(SortedLeft {
+                    batches: std::mem::take(&mut state.batches),
+                    keys: std::mem::take(&mut state.keys),
+                    conditions,
+                }));
+                shared.reservation.merge(state.reservation.take());
+
+                if let Some(waker) = shared.probe_push_waker.take() {
+                    waker.wake();
+                }
+
+                Ok(PollFinalize::Finalized)
+            }
+            PartitionState::MergeJoinProbe(state) => {
+                // Unvisited left rows can only be emitted once we have the
+                // complete left side. We might not have it yet if we never
+                // received any batches to probe with.
+                if state.left.is_none() {
+                    match shared.left.as_ref() {
+                        Some(left) => {
+                            if self.is_left_join() {
+                                state.outer_join_tracker =
+                                    Some(LeftOuterJoinTracker::new_for_batches(&left.batches));
+                            }
+                            state.left = Some(left.clone());
+                        }
+                        None => {
+                            shared.probe_push_waker = Some(cx.waker().clone());
+                            return Ok(PollFinalize::Pending);
+                        }
+                    }
+                }
+
+                state.input_finished = true;
+                if let Some(waker) = state.pull_waker.take() {
+                    waker.wake();
+                }
+
+                Ok(PollFinalize::Finalized)
+            }
+            other => panic!("invalid partition state: {other:?}"),
+        }
+    }
+
+    fn poll_pull(
+        &self,
+        cx: &mut Context,
+        partition_state: &mut PartitionState,
+        _operator_state: &OperatorState,
+    ) -> Result<PollPull> {
+        let state = match partition_state {
+            PartitionState::MergeJoinProbe(state) => state,
+            PartitionState::MergeJoinBuild(_) => panic!("should not pull with a build state"),
+            other => panic!("invalid partition state: {other:?}"),
+        };
+
+        let computed = state.buffered_output.take();
+        if computed.has_batches() {
+            if let Some(waker) = state.push_waker.take() {
+                waker.wake();
+            }
+            return Ok(PollPull::Computed(computed));
+        }
+
+        if !state.input_finished {
+            state.pull_waker = Some(cx.waker().clone());
+            if let Some(waker) = state.push_waker.take() {
+                waker.wake();
+            }
+            return Ok(PollPull::Pending);
+        }
+
+        // Input finished, emit left rows that never found a match.
+        if let Some(tracker) = state.outer_join_tracker.take() {
+            let left = state.left.as_ref().expect("left side to exist");
+            state.outer_join_drain_state = Some(LeftOuterJoinDrainState::new(
+                0,
+                1,
+                tracker,
+                left.batches.clone(),
+                self.right_types.clone(),
+            ));
+        }
+
+        if let Some(drain_state) = state.outer_join_drain_state.as_mut() {
+            if let Some(batch) = drain_state.drain_next()? {
+                return Ok(PollPull::Computed(batch.into()));
+            }
+        }
+
+        Ok(PollPull::Exhausted)
+    }
+}
+
+impl Explainable for PhysicalMergeJoin {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("MergeJoin")
+            .with_values("conditions", &self.conditions)
+            .with_values(
+                "keys",
+                self.keys.iter().map(|key| MergeKeyDisplay(self, key)),
+            )
+            .with_value("join_type", self.join_type)
+    }
+}
+
+/// Display a merge key using the expressions being compared.
+struct MergeKeyDisplay<'a>(&'a PhysicalMergeJoin, &'a MergeJoinKey);
+
+impl fmt::Display for MergeKeyDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let condition = &self.0.conditions[self.1.condition];
+        write!(
+            f,
+            "(LEFT {}) = (RIGHT {}) {} {}",
+            condition.left,
+            condition.right,
+            if self.1.desc { "DESC" } else { "ASC" },
+            if self.1.nulls_first {
+                "NULLS FIRST"
+            } else {
+                "NULLS LAST"
+            }
+        )
+    }
+}
+
+impl DatabaseProtoConv for PhysicalMergeJoin {
+    type ProtoType = rayexec_proto::generated::execution::PhysicalMergeJoin;
+
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            join_type: Some(self.join_type.to_proto()?),
+            conditions: self
+                .conditions
+                .iter()
+                .map(|c| c.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+            keys: self
+                .keys
+                .iter()
+                .map(|k| k.to_proto())
+                .collect::<Result<Vec<_>>>()?,
+            left_types: self
+                .left_types
+                .iter()
+                .map(|t| t.to_proto())
+                .collect::<Result<Vec<_>>>()?,
+            right_types: self
+                .right_types
+                .iter()
+                .map(|t| t.to_proto())
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        let keys = proto
+            .keys
+            .into_iter()
+            .map(ProtoConv::from_proto)
+            .collect::<Result<Vec<_>>>()?;
+        if keys.is_empty() {
+            return Err(RayexecError::new("Merge join missing keys"));
+        }
+
+        Ok(Self::new(
+            ProtoConv::from_proto(proto.join_type.required("join_type")?)?,
+            proto
+                .conditions
+                .into_iter()
+                .map(|c| DatabaseProtoConv::from_proto_ctx(c, context))
+                .collect::<Result<Vec<_>>>()?,
+            keys,
+            proto
+                .left_types
+                .into_iter()
+                .map(ProtoConv::from_proto)
+                .collect::<Result<Vec<_>>>()?,
+            proto
+                .right_types
+                .into_iter()
+                .map(ProtoConv::from_proto)
+                .collect::<Result<Vec<_>>>()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::execution::operators::test_util::{
+        logical_value,
+        make_i32_batch,
+        test_database_context,
+        TestWakerContext,
+    };
+    use crate::execution::operators::util::resizer::DEFAULT_TARGET_BATCH_SIZE;
+    use crate::expr;
+    use crate::expr::physical::column_expr::PhysicalColumnExpr;
+    use crate::expr::physical::PhysicalScalarExpression;
+    use crate::functions::scalar::builtin::comparison::Eq;
+    use crate::functions::scalar::ScalarFunction;
+    use crate::logical::binder::table_list::TableList;
+
+    /// Create an operator joining two single column i32 inputs on equality.
+    fn eq_join(join_type: JoinType) -> Arc<PhysicalMergeJoin> {
+        let function = Eq
+            .plan(&TableList::empty(), vec![expr::lit(0), expr::lit(0)])
+            .unwrap();
+        let condition = HashJoinCondition {
+            left: PhysicalScalarExpression::Column(PhysicalColumnExpr { idx: 0 }),
+            right: PhysicalScalarExpression::Column(PhysicalColumnExpr { idx: 0 }),
+            function,
+            input_types: vec![DataType::Int32, DataType::Int32],
+        };
+
+        Arc::new(PhysicalMergeJoin::new(
+            join_type,
+            vec![condition],
+            vec![MergeJoinKey {
+                condition: 0,
+                desc: false,
+                nulls_first: false,
+            }],
+            vec![DataType::Int32],
+            vec![DataType::Int32],
+        ))
+    }
+
+    /// Run the join to completion, returning (left, right) values for each
+    /// output row.
+    fn run_join(
+        operator: &Arc<PhysicalMergeJoin>,
+        left: Vec<Batch>,
+        right: Vec<Batch>,
+    ) -> Vec<(ScalarValue<'static>, ScalarValue<'static>)> {
+        let states = operator
+            .create_states(&test_database_context(), DEFAULT_TARGET_BATCH_SIZE, vec![1])
+            .unwrap();
+        let operator_state = states.operator_state;
+        let mut partition_states = match states.partition_states {
+            InputOutputStates::NaryInputSingleOutput {
+                partition_states, ..
+            } => partition_states,
+            other => panic!("invalid states: {other:?}"),
+        };
+        let mut probe_state = partition_states.pop().unwrap().pop().unwrap();
+        let mut build_state = partition_states.pop().unwrap().pop().unwrap();
+
+        let cx = TestWakerContext::new();
+        for batch in left {
+            let poll = cx
+                .poll_push(operator, &mut build_state, &operator_state, batch)
+                .unwrap();
+            assert_eq!(PollPush::NeedsMore, poll);
+        }
+        operator
+            .poll_finalize_push(&mut cx.context(), &mut build_state, &operator_state)
+            .unwrap();
+
+        let mut output = Vec::new();
+        let mut collect = |computed: ComputedBatches| {
+            let mut computed = computed;
+            while let Some(batch) = computed.try_pop_front().unwrap() {
+                for row in 0..batch.num_rows() {
+                    output.push((
+                        logical_value(&batch, 0, row).into_owned(),
+                        logical_value(&batch, 1, row).into_owned(),
+                    ));
+                }
+            }
+        };
+
+        for batch in right {
+            cx.poll_push(operator, &mut probe_state, &operator_state, batch)
+                .unwrap();
+            if let PollPull::Computed(computed) = cx
+                .poll_pull(operator, &mut probe_state, &operator_state)
+                .unwrap()
+            {
+                collect(computed);
+            }
+        }
+        operator
+            .poll_finalize_push(&mut cx.context(), &mut probe_state, &operator_state)
+            .unwrap();
+
+        loop {
+            match cx
+                .poll_pull(operator, &mut probe_state, &operator_state)
+                .unwrap()
+            {
+                PollPull::Computed(computed) => collect(computed),
+                PollPull::Exhausted => break,
+                other => panic!("unexpected poll pull: {other:?}"),
+            }
+        }
+
+        output
+    }
+
+    fn pairs(
+        vals: impl IntoIterator<Item = (Option<i32>, Option<i32>)>,
+    ) -> Vec<(ScalarValue<'static>, ScalarValue<'static>)> {
+        let to_scalar = |v: Option<i32>| v.map(ScalarValue::Int32).unwrap_or(ScalarValue::Null);
+        vals.into_iter()
+            .map(|(l, r)| (to_scalar(l), to_scalar(r)))
+            .collect()
+    }
+
+    #[test]
+    fn inner_join_runs_across_batches() {
+        let operator = eq_join(JoinType::Inner);
+        let out = run_join(
+            &operator,
+            vec![make_i32_batch([1, 2, 2]), make_i32_batch([2, 4])],
+            vec![make_i32_batch([2, 3]), make_i32_batch([4, 4, 5])],
+        );
+
+        let expected = pairs([
+            (Some(2), Some(2)),
+            (Some(2), Some(2)),
+            (Some(2), Some(2)),
+            (Some(4), Some(4)),
+            (Some(4), Some(4)),
+        ]);
+        assert_eq!(expected, out);
+    }
+
+    #[test]
+    fn full_join_emits_unmatched() {
+        let operator = eq_join(JoinType::Full);
+        let out = run_join(
+            &operator,
+            vec![make_i32_batch([1, 3])],
+            vec![make_i32_batch([2, 3]), make_i32_batch([4])],
+        );
+
+        let expected = pairs([
+            (Some(3), Some(3)),
+            (None, Some(2)),
+            (None, Some(4)),
+            (Some(1), None),
+        ]);
+        assert_eq!(expected, out);
+    }
+
+    #[test]
+    fn left_join_empty_right() {
+        let operator = eq_join(JoinType::Left);
+        let out = run_join(&operator, vec![make_i32_batch([1, 2])], Vec::new());
+
+        let expected = pairs([(Some(1), None), (Some(2), None)]);
+        assert_eq!(expected, out);
+    }
+}
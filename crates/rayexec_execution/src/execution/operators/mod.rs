@@ -14,9 +14,13 @@ pub mod hash_join;
 pub mod insert;
 pub mod limit;
 pub mod materialize;
+pub mod merge_join;
 pub mod nl_join;
 pub mod project;
+pub mod query_scan;
 pub mod round_robin;
+pub mod runtime_filter;
+pub mod sample;
 pub mod scan;
 pub mod simple;
 pub mod sink;
@@ -33,7 +37,7 @@ pub mod window;
 pub(crate) mod util;
 
 #[cfg(test)]
-mod test_util;
+pub(crate) mod test_util;
 
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -57,14 +61,28 @@ use hash_join::{
 use insert::PhysicalInsert;
 use limit::PhysicalLimit;
 use materialize::{MaterializeSourceOperation, MaterializedSinkOperation};
+use merge_join::{
+    MergeJoinBuildPartitionState,
+    MergeJoinOperatorState,
+    MergeJoinProbePartitionState,
+    PhysicalMergeJoin,
+};
 use nl_join::PhysicalNestedLoopJoin;
 use project::{PhysicalProject, ProjectOperation};
+use query_scan::{
+    PhysicalQueryScan,
+    PhysicalShippedQueryScan,
+    ShippedQueryScanOperatorState,
+    ShippedQueryScanPartitionState,
+};
 use rayexec_error::{not_implemented, OptionExt, Result};
 use round_robin::PhysicalRoundRobinRepartition;
+use sample::{PhysicalReservoirSample, PhysicalSample, ReservoirSamplePartitionState};
 use scan::{PhysicalScan, ScanPartitionState};
 use simple::SimpleOperator;
 use sink::{SinkOperation, SinkOperator, SinkOperatorState, SinkPartitionState};
 use sort::gather_sort::PhysicalGatherSort;
+use sort::parallel_merge::PhysicalParallelMerge;
 use sort::scatter_sort::PhysicalScatterSort;
 use source::{SourceOperation, SourceOperator, SourcePartitionState};
 use table_function::{PhysicalTableFunction, TableFunctionPartitionState};
@@ -98,6 +116,11 @@ use self::sort::gather_sort::{
     GatherSortPullPartitionState,
     GatherSortPushPartitionState,
 };
+use self::sort::parallel_merge::{
+    ParallelMergeOperatorState,
+    ParallelMergePullPartitionState,
+    ParallelMergePushPartitionState,
+};
 use self::sort::scatter_sort::ScatterSortPartitionState;
 use self::values::ValuesPartitionState;
 use super::computed_batch::ComputedBatches;
@@ -117,6 +140,8 @@ pub enum PartitionState {
     NestedLoopJoinProbe(NestedLoopJoinProbePartitionState),
     HashJoinBuild(HashJoinBuildPartitionState),
     HashJoinProbe(HashJoinProbePartitionState),
+    MergeJoinBuild(MergeJoinBuildPartitionState),
+    MergeJoinProbe(MergeJoinProbePartitionState),
     Values(ValuesPartitionState),
     Sink(SinkPartitionState),
     Source(SourcePartitionState),
@@ -124,6 +149,8 @@ pub enum PartitionState {
     RoundRobinPull(RoundRobinPullPartitionState),
     GatherSortPush(GatherSortPushPartitionState),
     GatherSortPull(GatherSortPullPartitionState),
+    ParallelMergePush(ParallelMergePushPartitionState),
+    ParallelMergePull(ParallelMergePullPartitionState),
     ScatterSort(ScatterSortPartitionState),
     Limit(LimitPartitionState),
     Unnest(UnnestPartitionState),
@@ -131,6 +158,7 @@ pub enum PartitionState {
     UnionBottom(UnionBottomPartitionState),
     Simple(SimplePartitionState),
     Scan(ScanPartitionState),
+    ShippedQueryScan(ShippedQueryScanPartitionState),
     TableFunction(TableFunctionPartitionState),
     TableInOut(TableInOutPartitionState),
     CreateSchema(CreateSchemaPartitionState),
@@ -138,6 +166,7 @@ pub enum PartitionState {
     Drop(DropPartitionState),
     Empty(EmptyPartitionState),
     BatchResizer(BatchResizerPartitionState),
+    ReservoirSample(ReservoirSamplePartitionState),
     None,
 }
 
@@ -149,10 +178,13 @@ pub enum OperatorState {
     UngroupedAggregate(UngroupedAggregateOperatorState),
     NestedLoopJoin(NestedLoopJoinOperatorState),
     HashJoin(HashJoinOperatorState),
+    MergeJoin(MergeJoinOperatorState),
     RoundRobin(RoundRobinOperatorState),
     GatherSort(GatherSortOperatorState),
+    ParallelMerge(ParallelMergeOperatorState),
     Union(UnionOperatorState),
     Sink(SinkOperatorState),
+    ShippedQueryScan(ShippedQueryScanOperatorState),
     None,
 }
 
@@ -276,10 +308,15 @@ pub trait ExecutableOperator: Sync + Send + Debug + Explainable {
     /// `input_partitions` is the partitioning for each input that will be
     /// pushing batches through this operator.
     ///
+    /// `batch_size` is the target number of rows for batches produced by this
+    /// operator. Operators that produce batches of their own (scans, sorts,
+    /// etc) should try to stay close to this size.
+    ///
     /// Joins are assumed to have two inputs.
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         _partitions: Vec<usize>,
     ) -> Result<ExecutionStates>;
 
@@ -320,6 +357,7 @@ pub enum PhysicalOperator {
     Window(PhysicalWindow),
     NestedLoopJoin(PhysicalNestedLoopJoin),
     HashJoin(PhysicalHashJoin),
+    MergeJoin(PhysicalMergeJoin),
     Values(PhysicalValues),
     ResultSink(SinkOperator<ResultSink>),
     DynSink(SinkOperator<Box<dyn SinkOperation>>),
@@ -328,6 +366,7 @@ pub enum PhysicalOperator {
     MaterializedSource(SourceOperator<MaterializeSourceOperation>),
     RoundRobin(PhysicalRoundRobinRepartition),
     MergeSorted(PhysicalGatherSort),
+    ParallelMerge(PhysicalParallelMerge),
     LocalSort(PhysicalScatterSort),
     Limit(PhysicalLimit),
     Union(PhysicalUnion),
@@ -335,6 +374,8 @@ pub enum PhysicalOperator {
     Project(SimpleOperator<ProjectOperation>),
     Unnest(PhysicalUnnest),
     Scan(PhysicalScan),
+    QueryScan(PhysicalQueryScan),
+    ShippedQueryScan(PhysicalShippedQueryScan),
     TableFunction(PhysicalTableFunction),
     TableInOut(PhysicalTableInOut),
     Insert(PhysicalInsert),
@@ -345,45 +386,93 @@ pub enum PhysicalOperator {
     Drop(PhysicalDrop),
     Empty(PhysicalEmpty),
     BatchResizer(PhysicalBatchResizer),
+    Sample(PhysicalSample),
+    ReservoirSample(PhysicalReservoirSample),
+}
+
+impl PhysicalOperator {
+    /// Estimated width in bytes of rows produced by this operator if it's a
+    /// scan.
+    ///
+    /// Used to pick batch sizes for scans when a byte budget for batches is
+    /// configured. Returns None for all other operators.
+    pub fn estimated_scan_row_width(&self) -> Result<Option<usize>> {
+        Ok(match self {
+            Self::Scan(op) => Some(op.estimated_row_width()?),
+            Self::QueryScan(op) => Some(op.estimated_row_width()),
+            Self::ShippedQueryScan(op) => Some(op.estimated_row_width()),
+            Self::TableFunction(op) => Some(op.estimated_row_width()),
+            _ => None,
+        })
+    }
+
+    /// If this operator needs to consume all of its input before producing
+    /// any output.
+    ///
+    /// Blocking operators break pipelines, rows won't flow past them until
+    /// every input partition has finished. Joins are considered streaming
+    /// since only their build side blocks, and the build side is always fed by
+    /// a separate pipeline.
+    pub fn is_blocking(&self) -> bool {
+        matches!(
+            self,
+            Self::HashAggregate(_)
+                | Self::UngroupedAggregate(_)
+                | Self::Window(_)
+                | Self::LocalSort(_)
+                | Self::ParallelMerge(_)
+                | Self::ReservoirSample(_)
+                | Self::Insert(_)
+                | Self::CopyTo(_)
+                | Self::CreateTable(_)
+        )
+    }
 }
 
 impl ExecutableOperator for PhysicalOperator {
     fn create_states(
         &self,
         context: &DatabaseContext,
+        batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         match self {
-            Self::HashAggregate(op) => op.create_states(context, partitions),
-            Self::UngroupedAggregate(op) => op.create_states(context, partitions),
-            Self::Window(op) => op.create_states(context, partitions),
-            Self::NestedLoopJoin(op) => op.create_states(context, partitions),
-            Self::HashJoin(op) => op.create_states(context, partitions),
-            Self::Values(op) => op.create_states(context, partitions),
-            Self::ResultSink(op) => op.create_states(context, partitions),
-            Self::DynSink(op) => op.create_states(context, partitions),
-            Self::DynSource(op) => op.create_states(context, partitions),
-            Self::MaterializedSink(op) => op.create_states(context, partitions),
-            Self::MaterializedSource(op) => op.create_states(context, partitions),
-            Self::RoundRobin(op) => op.create_states(context, partitions),
-            Self::MergeSorted(op) => op.create_states(context, partitions),
-            Self::LocalSort(op) => op.create_states(context, partitions),
-            Self::Limit(op) => op.create_states(context, partitions),
-            Self::Union(op) => op.create_states(context, partitions),
-            Self::Filter(op) => op.create_states(context, partitions),
-            Self::Project(op) => op.create_states(context, partitions),
-            Self::Unnest(op) => op.create_states(context, partitions),
-            Self::Scan(op) => op.create_states(context, partitions),
-            Self::TableFunction(op) => op.create_states(context, partitions),
-            Self::TableInOut(op) => op.create_states(context, partitions),
-            Self::Insert(op) => op.create_states(context, partitions),
-            Self::CopyTo(op) => op.create_states(context, partitions),
-            Self::CreateTable(op) => op.create_states(context, partitions),
-            Self::CreateSchema(op) => op.create_states(context, partitions),
-            Self::CreateView(op) => op.create_states(context, partitions),
-            Self::Drop(op) => op.create_states(context, partitions),
-            Self::Empty(op) => op.create_states(context, partitions),
-            Self::BatchResizer(op) => op.create_states(context, partitions),
+            Self::HashAggregate(op) => op.create_states(context, batch_size, partitions),
+            Self::UngroupedAggregate(op) => op.create_states(context, batch_size, partitions),
+            Self::Window(op) => op.create_states(context, batch_size, partitions),
+            Self::NestedLoopJoin(op) => op.create_states(context, batch_size, partitions),
+            Self::HashJoin(op) => op.create_states(context, batch_size, partitions),
+            Self::MergeJoin(op) => op.create_states(context, batch_size, partitions),
+            Self::Values(op) => op.create_states(context, batch_size, partitions),
+            Self::ResultSink(op) => op.create_states(context, batch_size, partitions),
+            Self::DynSink(op) => op.create_states(context, batch_size, partitions),
+            Self::DynSource(op) => op.create_states(context, batch_size, partitions),
+            Self::MaterializedSink(op) => op.create_states(context, batch_size, partitions),
+            Self::MaterializedSource(op) => op.create_states(context, batch_size, partitions),
+            Self::RoundRobin(op) => op.create_states(context, batch_size, partitions),
+            Self::MergeSorted(op) => op.create_states(context, batch_size, partitions),
+            Self::ParallelMerge(op) => op.create_states(context, batch_size, partitions),
+            Self::LocalSort(op) => op.create_states(context, batch_size, partitions),
+            Self::Limit(op) => op.create_states(context, batch_size, partitions),
+            Self::Union(op) => op.create_states(context, batch_size, partitions),
+            Self::Filter(op) => op.create_states(context, batch_size, partitions),
+            Self::Project(op) => op.create_states(context, batch_size, partitions),
+            Self::Unnest(op) => op.create_states(context, batch_size, partitions),
+            Self::Scan(op) => op.create_states(context, batch_size, partitions),
+            Self::QueryScan(op) => op.create_states(context, batch_size, partitions),
+            Self::ShippedQueryScan(op) => op.create_states(context, batch_size, partitions),
+            Self::TableFunction(op) => op.create_states(context, batch_size, partitions),
+            Self::TableInOut(op) => op.create_states(context, batch_size, partitions),
+            Self::Insert(op) => op.create_states(context, batch_size, partitions),
+            Self::CopyTo(op) => op.create_states(context, batch_size, partitions),
+            Self::CreateTable(op) => op.create_states(context, batch_size, partitions),
+            Self::CreateSchema(op) => op.create_states(context, batch_size, partitions),
+            Self::CreateView(op) => op.create_states(context, batch_size, partitions),
+            Self::Drop(op) => op.create_states(context, batch_size, partitions),
+            Self::Empty(op) => op.create_states(context, batch_size, partitions),
+            Self::BatchResizer(op) => op.create_states(context, batch_size, partitions),
+            Self::Sample(op) => op.create_states(context, batch_size, partitions),
+            Self::ReservoirSample(op) => op.create_states(context, batch_size, partitions),
         }
     }
 
@@ -402,6 +491,7 @@ impl ExecutableOperator for PhysicalOperator {
             Self::Window(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::NestedLoopJoin(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::HashJoin(op) => op.poll_push(cx, partition_state, operator_state, batch),
+            Self::MergeJoin(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::Values(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::ResultSink(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::DynSink(op) => op.poll_push(cx, partition_state, operator_state, batch),
@@ -412,6 +502,7 @@ impl ExecutableOperator for PhysicalOperator {
             }
             Self::RoundRobin(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::MergeSorted(op) => op.poll_push(cx, partition_state, operator_state, batch),
+            Self::ParallelMerge(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::LocalSort(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::Limit(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::Union(op) => op.poll_push(cx, partition_state, operator_state, batch),
@@ -419,6 +510,8 @@ impl ExecutableOperator for PhysicalOperator {
             Self::Project(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::Unnest(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::Scan(op) => op.poll_push(cx, partition_state, operator_state, batch),
+            Self::QueryScan(op) => op.poll_push(cx, partition_state, operator_state, batch),
+            Self::ShippedQueryScan(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::TableFunction(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::TableInOut(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::Insert(op) => op.poll_push(cx, partition_state, operator_state, batch),
@@ -429,6 +522,8 @@ impl ExecutableOperator for PhysicalOperator {
             Self::Drop(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::Empty(op) => op.poll_push(cx, partition_state, operator_state, batch),
             Self::BatchResizer(op) => op.poll_push(cx, partition_state, operator_state, batch),
+            Self::Sample(op) => op.poll_push(cx, partition_state, operator_state, batch),
+            Self::ReservoirSample(op) => op.poll_push(cx, partition_state, operator_state, batch),
         }
     }
 
@@ -446,6 +541,7 @@ impl ExecutableOperator for PhysicalOperator {
             Self::Window(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::NestedLoopJoin(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::HashJoin(op) => op.poll_finalize_push(cx, partition_state, operator_state),
+            Self::MergeJoin(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::Values(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::ResultSink(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::DynSink(op) => op.poll_finalize_push(cx, partition_state, operator_state),
@@ -458,6 +554,7 @@ impl ExecutableOperator for PhysicalOperator {
             }
             Self::RoundRobin(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::MergeSorted(op) => op.poll_finalize_push(cx, partition_state, operator_state),
+            Self::ParallelMerge(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::LocalSort(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::Limit(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::Union(op) => op.poll_finalize_push(cx, partition_state, operator_state),
@@ -465,6 +562,10 @@ impl ExecutableOperator for PhysicalOperator {
             Self::Project(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::Unnest(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::Scan(op) => op.poll_finalize_push(cx, partition_state, operator_state),
+            Self::QueryScan(op) => op.poll_finalize_push(cx, partition_state, operator_state),
+            Self::ShippedQueryScan(op) => {
+                op.poll_finalize_push(cx, partition_state, operator_state)
+            }
             Self::TableFunction(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::TableInOut(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::Insert(op) => op.poll_finalize_push(cx, partition_state, operator_state),
@@ -475,6 +576,8 @@ impl ExecutableOperator for PhysicalOperator {
             Self::Drop(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::Empty(op) => op.poll_finalize_push(cx, partition_state, operator_state),
             Self::BatchResizer(op) => op.poll_finalize_push(cx, partition_state, operator_state),
+            Self::Sample(op) => op.poll_finalize_push(cx, partition_state, operator_state),
+            Self::ReservoirSample(op) => op.poll_finalize_push(cx, partition_state, operator_state),
         }
     }
 
@@ -490,6 +593,7 @@ impl ExecutableOperator for PhysicalOperator {
             Self::Window(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::NestedLoopJoin(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::HashJoin(op) => op.poll_pull(cx, partition_state, operator_state),
+            Self::MergeJoin(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::Values(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::ResultSink(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::DynSink(op) => op.poll_pull(cx, partition_state, operator_state),
@@ -498,6 +602,7 @@ impl ExecutableOperator for PhysicalOperator {
             Self::MaterializedSource(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::RoundRobin(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::MergeSorted(op) => op.poll_pull(cx, partition_state, operator_state),
+            Self::ParallelMerge(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::LocalSort(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::Limit(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::Union(op) => op.poll_pull(cx, partition_state, operator_state),
@@ -505,6 +610,8 @@ impl ExecutableOperator for PhysicalOperator {
             Self::Project(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::Unnest(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::Scan(op) => op.poll_pull(cx, partition_state, operator_state),
+            Self::QueryScan(op) => op.poll_pull(cx, partition_state, operator_state),
+            Self::ShippedQueryScan(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::TableFunction(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::TableInOut(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::Insert(op) => op.poll_pull(cx, partition_state, operator_state),
@@ -515,6 +622,8 @@ impl ExecutableOperator for PhysicalOperator {
             Self::Drop(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::Empty(op) => op.poll_pull(cx, partition_state, operator_state),
             Self::BatchResizer(op) => op.poll_pull(cx, partition_state, operator_state),
+            Self::Sample(op) => op.poll_pull(cx, partition_state, operator_state),
+            Self::ReservoirSample(op) => op.poll_pull(cx, partition_state, operator_state),
         }
     }
 }
@@ -527,6 +636,7 @@ impl Explainable for PhysicalOperator {
             Self::Window(op) => op.explain_entry(conf),
             Self::NestedLoopJoin(op) => op.explain_entry(conf),
             Self::HashJoin(op) => op.explain_entry(conf),
+            Self::MergeJoin(op) => op.explain_entry(conf),
             Self::Values(op) => op.explain_entry(conf),
             Self::ResultSink(op) => op.explain_entry(conf),
             Self::DynSink(op) => op.explain_entry(conf),
@@ -535,6 +645,7 @@ impl Explainable for PhysicalOperator {
             Self::MaterializedSource(op) => op.explain_entry(conf),
             Self::RoundRobin(op) => op.explain_entry(conf),
             Self::MergeSorted(op) => op.explain_entry(conf),
+            Self::ParallelMerge(op) => op.explain_entry(conf),
             Self::LocalSort(op) => op.explain_entry(conf),
             Self::Limit(op) => op.explain_entry(conf),
             Self::Union(op) => op.explain_entry(conf),
@@ -542,6 +653,8 @@ impl Explainable for PhysicalOperator {
             Self::Project(op) => op.explain_entry(conf),
             Self::Unnest(op) => op.explain_entry(conf),
             Self::Scan(op) => op.explain_entry(conf),
+            Self::QueryScan(op) => op.explain_entry(conf),
+            Self::ShippedQueryScan(op) => op.explain_entry(conf),
             Self::TableFunction(op) => op.explain_entry(conf),
             Self::TableInOut(op) => op.explain_entry(conf),
             Self::Insert(op) => op.explain_entry(conf),
@@ -552,6 +665,8 @@ impl Explainable for PhysicalOperator {
             Self::Drop(op) => op.explain_entry(conf),
             Self::Empty(op) => op.explain_entry(conf),
             Self::BatchResizer(op) => op.explain_entry(conf),
+            Self::Sample(op) => op.explain_entry(conf),
+            Self::ReservoirSample(op) => op.explain_entry(conf),
         }
     }
 }
@@ -580,6 +695,11 @@ impl DatabaseProtoConv for PhysicalOperator {
             Self::CopyTo(op) => Value::CopyTo(op.to_proto_ctx(context)?),
             Self::LocalSort(op) => Value::LocalSort(op.to_proto_ctx(context)?),
             Self::MergeSorted(op) => Value::MergeSorted(op.to_proto_ctx(context)?),
+            Self::ParallelMerge(op) => Value::ParallelMerge(op.to_proto_ctx(context)?),
+            Self::HashJoin(op) => Value::HashJoin(op.to_proto_ctx(context)?),
+            Self::MergeJoin(op) => Value::MergeJoin(op.to_proto_ctx(context)?),
+            Self::HashAggregate(op) => Value::HashAggregate(op.to_proto_ctx(context)?),
+            Self::RoundRobin(op) => Value::RoundRobin(op.to_proto_ctx(context)?),
             other => not_implemented!("to proto: {other:?}"),
         };
 
@@ -637,6 +757,21 @@ impl DatabaseProtoConv for PhysicalOperator {
             Value::MergeSorted(op) => {
                 PhysicalOperator::MergeSorted(PhysicalGatherSort::from_proto_ctx(op, context)?)
             }
+            Value::ParallelMerge(op) => {
+                PhysicalOperator::ParallelMerge(PhysicalParallelMerge::from_proto_ctx(op, context)?)
+            }
+            Value::HashJoin(op) => {
+                PhysicalOperator::HashJoin(PhysicalHashJoin::from_proto_ctx(op, context)?)
+            }
+            Value::MergeJoin(op) => {
+                PhysicalOperator::MergeJoin(PhysicalMergeJoin::from_proto_ctx(op, context)?)
+            }
+            Value::HashAggregate(op) => {
+                PhysicalOperator::HashAggregate(PhysicalHashAggregate::from_proto_ctx(op, context)?)
+            }
+            Value::RoundRobin(op) => PhysicalOperator::RoundRobin(
+                PhysicalRoundRobinRepartition::from_proto_ctx(op, context)?,
+            ),
         })
     }
 }
@@ -2,11 +2,17 @@ use std::sync::Arc;
 use std::task::{Context, Waker};
 
 use parking_lot::Mutex;
-use rayexec_error::Result;
+use rayexec_error::{OptionExt, Result};
+use rayexec_proto::ProtoConv;
 
-use super::util::outer_join_tracker::LeftOuterJoinTracker;
+use super::util::outer_join_tracker::{
+    LeftOuterJoinDrainState,
+    LeftOuterJoinTracker,
+    RightOuterJoinTracker,
+};
 use super::ComputedBatches;
 use crate::arrays::batch::Batch;
+use crate::arrays::datatype::DataType;
 use crate::arrays::selection::SelectionVector;
 use crate::database::DatabaseContext;
 use crate::execution::operators::{
@@ -21,6 +27,7 @@ use crate::execution::operators::{
 };
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 use crate::expr::physical::PhysicalScalarExpression;
+use crate::functions::scalar::JoinIndex;
 use crate::logical::logical_join::JoinType;
 use crate::proto::DatabaseProtoConv;
 
@@ -44,6 +51,10 @@ pub struct NestedLoopJoinProbePartitionState {
     /// from any of the probe partitions.
     partition_idx: usize,
 
+    /// Max number of (left, right) row pairs to evaluate the join filter on at
+    /// once.
+    block_size: usize,
+
     /// All batches from all partitions received on the build side.
     ///
     /// Store in the probe side local state to avoid needing to lock.
@@ -59,6 +70,9 @@ pub struct NestedLoopJoinProbePartitionState {
     /// from the build side.
     is_populated: bool,
 
+    /// Index over the build side batches if the join condition supports one.
+    join_index: Option<Arc<NestedLoopJoinIndex>>,
+
     /// Buffered batch that's ready to be pulled.
     buffered: ComputedBatches,
 
@@ -80,19 +94,26 @@ pub struct NestedLoopJoinProbePartitionState {
 
     /// Track rows visited on the left side for this partition.
     partition_outer_join_tracker: Option<LeftOuterJoinTracker>,
+
+    /// State for draining unvisited (or visited) rows from the left once all
+    /// probe partitions have finished.
+    outer_join_drain_state: Option<LeftOuterJoinDrainState>,
 }
 
 impl NestedLoopJoinProbePartitionState {
-    pub fn new_for_partition(partition: usize) -> Self {
+    pub fn new_for_partition(partition: usize, block_size: usize) -> Self {
         NestedLoopJoinProbePartitionState {
             partition_idx: partition,
+            block_size,
             all_batches: Arc::new(Vec::new()),
             is_populated: false,
+            join_index: None,
             buffered: ComputedBatches::None,
             push_waker: None,
             pull_waker: None,
             input_finished: false,
             partition_outer_join_tracker: None,
+            outer_join_drain_state: None,
         }
     }
 }
@@ -145,10 +166,28 @@ enum SharedOperatorState {
         /// All batches from all partitions.
         batches: Arc<Vec<Batch>>,
 
+        /// Index built over all batches for the join condition.
+        join_index: Option<Arc<NestedLoopJoinIndex>>,
+
         /// Union of all bitmaps across all partitions.
         ///
         /// Referenced with draining unvisited rows in the case of a LEFT join.
         global_outer_join_tracker: Option<LeftOuterJoinTracker>,
+
+        /// Number of partitions on the probe side.
+        ///
+        /// Used to initialize the drain states such that each partition drains
+        /// a different set of batches.
+        probe_partition_count: usize,
+
+        /// Number of partitions on the probe side that haven't finished yet.
+        probe_partitions_remaining: usize,
+
+        /// Wakers for partitions on the probe side waiting for all other
+        /// partitions to merge their left visit bitmaps before draining.
+        ///
+        /// Indexed by partition index on the probe side.
+        probe_drain_wakers: Vec<Option<Waker>>,
     },
 }
 
@@ -157,7 +196,11 @@ impl SharedOperatorState {
     ///
     /// Must be called when number of partitions remaining on the build side is
     /// zero.
-    fn transition_into_probing(&mut self, join_type: JoinType) {
+    fn transition_into_probing(
+        &mut self,
+        requires_drain: bool,
+        filter: Option<&PhysicalScalarExpression>,
+    ) -> Result<()> {
         match self {
             Self::Building {
                 batches,
@@ -167,24 +210,32 @@ impl SharedOperatorState {
                 assert_eq!(0, *build_partitions_remaining);
 
                 // Wake any pending probers.
-                for waker in probe_side_wakers {
+                for waker in probe_side_wakers.iter_mut() {
                     if let Some(waker) = waker.take() {
                         waker.wake();
                     }
                 }
 
-                let global_outer_join_tracker = match join_type {
-                    JoinType::Left | JoinType::Full => {
-                        Some(LeftOuterJoinTracker::new_for_batches(batches))
-                    }
-                    _ => None,
+                let global_outer_join_tracker = if requires_drain {
+                    Some(LeftOuterJoinTracker::new_for_batches(batches))
+                } else {
+                    None
                 };
 
+                let probe_partition_count = probe_side_wakers.len();
                 let batches = std::mem::take(batches);
+                let join_index = NestedLoopJoinIndex::try_build(filter, &batches)?.map(Arc::new);
+
                 *self = Self::Probing {
                     batches: Arc::new(batches),
+                    join_index,
                     global_outer_join_tracker,
-                }
+                    probe_partition_count,
+                    probe_partitions_remaining: probe_partition_count,
+                    probe_drain_wakers: vec![None; probe_partition_count],
+                };
+
+                Ok(())
             }
             Self::Probing { .. } => panic!("inner state is already probing"),
         }
@@ -192,19 +243,130 @@ impl SharedOperatorState {
 }
 
 /// Nested loop join.
+///
+/// Used for joins that have no equalities to hash on. Every row on the left is
+/// paired with every row on the right, with the filter evaluated on blocks of
+/// pairs at a time.
 #[derive(Debug)]
 pub struct PhysicalNestedLoopJoin {
     /// Filter to apply after cross joining batches.
     filter: Option<PhysicalScalarExpression>,
     join_type: JoinType,
+    /// Types for the batches on the left side. Used to create the null columns
+    /// for unvisited rows on the right in RIGHT joins.
+    left_types: Vec<DataType>,
+    /// Types for the batches on the right side. Used to create the null
+    /// columns for unvisited rows on the left in LEFT joins.
+    right_types: Vec<DataType>,
 }
 
 impl PhysicalNestedLoopJoin {
     pub const BUILD_SIDE_INPUT_INDEX: usize = 0;
     pub const PROBE_SIDE_INPUT_INDEX: usize = 1;
 
-    pub fn new(filter: Option<PhysicalScalarExpression>, join_type: JoinType) -> Self {
-        PhysicalNestedLoopJoin { filter, join_type }
+    pub fn new(
+        filter: Option<PhysicalScalarExpression>,
+        join_type: JoinType,
+        left_types: Vec<DataType>,
+        right_types: Vec<DataType>,
+    ) -> Self {
+        PhysicalNestedLoopJoin {
+            filter,
+            join_type,
+            left_types,
+            right_types,
+        }
+    }
+
+    const fn join_requires_drain(&self) -> bool {
+        matches!(
+            self.join_type,
+            JoinType::Left
+                | JoinType::Full
+                | JoinType::Semi
+                | JoinType::Anti
+                | JoinType::LeftMark { .. }
+        )
+    }
+
+    const fn is_right_join(&self) -> bool {
+        matches!(self.join_type, JoinType::Full | JoinType::Right)
+    }
+
+    /// If the join only tracks which rows on the left have a match, with the
+    /// output produced when draining.
+    const fn is_mark_join(&self) -> bool {
+        matches!(
+            self.join_type,
+            JoinType::Semi | JoinType::Anti | JoinType::LeftMark { .. }
+        )
+    }
+
+    /// Join a batch from the right side with all batches on the left.
+    fn probe(
+        &self,
+        state: &mut NestedLoopJoinProbePartitionState,
+        right: &Batch,
+    ) -> Result<Vec<Batch>> {
+        let mut output = ProbeOutput {
+            left_tracker: state.partition_outer_join_tracker.as_mut(),
+            right_tracker: if self.is_right_join() {
+                Some(RightOuterJoinTracker::new_for_batch(right))
+            } else {
+                None
+            },
+            mark_only: self.is_mark_join(),
+            batches: Vec::new(),
+        };
+
+        match (&state.join_index, self.filter.as_ref()) {
+            (Some(join_index), Some(filter)) => {
+                let probe = right
+                    .column(join_index.probe_column)
+                    .required("probe column")?;
+
+                // Selections for (left, right) rows, grouped by left batch.
+                let mut selections: Vec<(SelectionVector, SelectionVector)> =
+                    vec![Default::default(); state.all_batches.len()];
+                for candidate in join_index.index.probe(probe)? {
+                    let (left_sel, right_sel) = &mut selections[candidate.build_batch];
+                    left_sel.push_location(candidate.build_row);
+                    right_sel.push_location(candidate.probe_row);
+                }
+
+                for (left_batch_idx, (left_sel, right_sel)) in selections.into_iter().enumerate() {
+                    if left_sel.is_empty() {
+                        continue;
+                    }
+                    output.push_candidates(
+                        Some(filter),
+                        left_batch_idx,
+                        &state.all_batches[left_batch_idx],
+                        left_sel,
+                        right,
+                        right_sel,
+                    )?;
+                }
+            }
+            _ => {
+                for (left_batch_idx, left) in state.all_batches.iter().enumerate() {
+                    let blocks =
+                        cross_product_blocks(left.num_rows(), right.num_rows(), state.block_size);
+                    for (left_sel, right_sel) in blocks {
+                        output.push_candidates(
+                            self.filter.as_ref(),
+                            left_batch_idx,
+                            left,
+                            left_sel,
+                            right,
+                            right_sel,
+                        )?;
+                    }
+                }
+            }
+        }
+
+        output.finish(&self.left_types, right)
     }
 }
 
@@ -212,6 +374,7 @@ impl ExecutableOperator for PhysicalNestedLoopJoin {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         // TODO: Allow different number of partitions on left & right?
@@ -226,7 +389,7 @@ impl ExecutableOperator for PhysicalNestedLoopJoin {
         let right_states = (0..num_partitions)
             .map(|partition| {
                 PartitionState::NestedLoopJoinProbe(
-                    NestedLoopJoinProbePartitionState::new_for_partition(partition),
+                    NestedLoopJoinProbePartitionState::new_for_partition(partition, batch_size),
                 )
             })
             .collect();
@@ -277,11 +440,14 @@ impl ExecutableOperator for PhysicalNestedLoopJoin {
                         }
                         SharedOperatorState::Probing {
                             batches,
+                            join_index,
                             global_outer_join_tracker,
+                            ..
                         } => {
                             // Otherwise the batches are ready for us. Clone the
                             // reference into our local state.
                             state.all_batches = batches.clone();
+                            state.join_index = join_index.clone();
                             state.is_populated = true;
 
                             if global_outer_join_tracker.is_some() {
@@ -302,18 +468,7 @@ impl ExecutableOperator for PhysicalNestedLoopJoin {
                 }
 
                 // Do the join.
-                let mut batches = Vec::new();
-                for (left_idx, left) in state.all_batches.iter().enumerate() {
-                    let mut out = cross_join(
-                        left_idx,
-                        left,
-                        &batch,
-                        self.filter.as_ref(),
-                        state.partition_outer_join_tracker.as_mut(),
-                        false,
-                    )?;
-                    batches.append(&mut out);
-                }
+                let batches = self.probe(state, &batch)?;
 
                 state.buffered = ComputedBatches::new(batches);
                 if state.buffered.is_empty() {
@@ -334,17 +489,17 @@ impl ExecutableOperator for PhysicalNestedLoopJoin {
 
     fn poll_finalize_push(
         &self,
-        _cx: &mut Context,
+        cx: &mut Context,
         partition_state: &mut PartitionState,
         operator_state: &OperatorState,
     ) -> Result<PollFinalize> {
+        let operator_state = match operator_state {
+            OperatorState::NestedLoopJoin(operater_state) => operater_state,
+            other => panic!("invalid operator state: {other:?}"),
+        };
+
         match partition_state {
             PartitionState::NestedLoopJoinBuild(state) => {
-                let operator_state = match operator_state {
-                    OperatorState::NestedLoopJoin(operater_state) => operater_state,
-                    other => panic!("invalid operator state: {other:?}"),
-                };
-
                 let mut inner = operator_state.inner.lock();
                 match &mut *inner {
                     SharedOperatorState::Building {
@@ -361,7 +516,10 @@ impl ExecutableOperator for PhysicalNestedLoopJoin {
                         // If we're the last build partition, go ahead and
                         // transition the global state to begin probing.
                         if *build_partitions_remaining == 0 {
-                            inner.transition_into_probing(self.join_type);
+                            inner.transition_into_probing(
+                                self.join_requires_drain(),
+                                self.filter.as_ref(),
+                            )?;
                         }
 
                         // And we're done.
@@ -371,6 +529,43 @@ impl ExecutableOperator for PhysicalNestedLoopJoin {
                 }
             }
             PartitionState::NestedLoopJoinProbe(state) => {
+                let mut inner = operator_state.inner.lock();
+                match &mut *inner {
+                    SharedOperatorState::Building {
+                        probe_side_wakers, ..
+                    } => {
+                        // Need the complete left side before we can finish,
+                        // unvisited left rows are drained from it.
+                        probe_side_wakers[state.partition_idx] = Some(cx.waker().clone());
+                        return Ok(PollFinalize::Pending);
+                    }
+                    SharedOperatorState::Probing {
+                        global_outer_join_tracker,
+                        probe_partitions_remaining,
+                        probe_drain_wakers,
+                        ..
+                    } => {
+                        *probe_partitions_remaining -= 1;
+
+                        if let Some(global) = global_outer_join_tracker {
+                            // Local may be None if this partition didn't
+                            // receive any batches for probing.
+                            if let Some(local) = &state.partition_outer_join_tracker {
+                                global.merge_from(local);
+                            }
+
+                            if *probe_partitions_remaining == 0 {
+                                // Wake up partitions waiting to drain.
+                                for waker in probe_drain_wakers.iter_mut() {
+                                    if let Some(waker) = waker.take() {
+                                        waker.wake();
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
                 state.input_finished = true;
                 if let Some(waker) = state.pull_waker.take() {
                     waker.wake();
@@ -385,22 +580,85 @@ impl ExecutableOperator for PhysicalNestedLoopJoin {
         &self,
         cx: &mut Context,
         partition_state: &mut PartitionState,
-        _operator_state: &OperatorState,
+        operator_state: &OperatorState,
     ) -> Result<PollPull> {
         match partition_state {
             PartitionState::NestedLoopJoinProbe(state) => {
                 let computed = state.buffered.take();
                 if computed.has_batches() {
-                    Ok(PollPull::Computed(computed))
-                } else if state.input_finished {
-                    Ok(PollPull::Exhausted)
-                } else {
+                    // Space available, wake up a pending pusher.
+                    if let Some(waker) = state.push_waker.take() {
+                        waker.wake();
+                    }
+                    return Ok(PollPull::Computed(computed));
+                }
+
+                if !state.input_finished {
                     // We just gotta wait for more input.
                     if let Some(waker) = state.push_waker.take() {
                         waker.wake();
                     }
                     state.pull_waker = Some(cx.waker().clone());
-                    Ok(PollPull::Pending)
+                    return Ok(PollPull::Pending);
+                }
+
+                if !self.join_requires_drain() {
+                    return Ok(PollPull::Exhausted);
+                }
+
+                if state.outer_join_drain_state.is_none() {
+                    let operator_state = match operator_state {
+                        OperatorState::NestedLoopJoin(operater_state) => operater_state,
+                        other => panic!("invalid operator state: {other:?}"),
+                    };
+
+                    let mut inner = operator_state.inner.lock();
+                    match &mut *inner {
+                        SharedOperatorState::Probing {
+                            batches,
+                            global_outer_join_tracker,
+                            probe_partition_count,
+                            probe_partitions_remaining,
+                            probe_drain_wakers,
+                            ..
+                        } => {
+                            if *probe_partitions_remaining != 0 {
+                                // Other partitions still probing, wait for
+                                // their visit bitmaps.
+                                probe_drain_wakers[state.partition_idx] = Some(cx.waker().clone());
+                                return Ok(PollPull::Pending);
+                            }
+
+                            let global = global_outer_join_tracker
+                                .as_ref()
+                                .required("global left outer join tracker")?;
+
+                            state.outer_join_drain_state = Some(LeftOuterJoinDrainState::new(
+                                state.partition_idx,
+                                *probe_partition_count,
+                                global.clone(),
+                                batches.to_vec(),
+                                self.right_types.clone(),
+                            ));
+                        }
+                        other => panic!("inner join state is not probing: {other:?}"),
+                    }
+                }
+
+                let drain_state = state
+                    .outer_join_drain_state
+                    .as_mut()
+                    .required("outer join drain state")?;
+
+                let batch = match self.join_type {
+                    JoinType::LeftMark { .. } => drain_state.drain_mark_next()?,
+                    JoinType::Semi => drain_state.drain_semi_next()?,
+                    _ => drain_state.drain_next()?,
+                };
+
+                match batch {
+                    Some(batch) => Ok(PollPull::Computed(batch.into())),
+                    None => Ok(PollPull::Exhausted),
                 }
             }
             PartitionState::NestedLoopJoinBuild(_) => {
@@ -417,47 +675,169 @@ impl ExecutableOperator for PhysicalNestedLoopJoin {
     }
 }
 
-/// Generate a cross product of two batches, applying an optional filter to the
-/// result.
-fn cross_join(
-    left_batch_idx: usize,
-    left: &Batch,
-    right: &Batch,
-    filter_expr: Option<&PhysicalScalarExpression>,
-    mut left_outer_tracker: Option<&mut LeftOuterJoinTracker>,
-    _right_join: bool,
-) -> Result<Vec<Batch>> {
-    let mut batches = Vec::with_capacity(left.num_rows() * right.num_rows());
-
-    // For each row in the left batch, join the entirety of right.
-    for left_idx in 0..left.num_rows() {
-        // Create constant selection vector pointing to the one row on the left.
-        let selection = SelectionVector::repeated(right.num_rows(), left_idx);
-
-        // Columns from the left, one row repeated.
-        let left_columns = left.select(Arc::new(selection)).into_arrays();
-        // Columns from the right, all rows.
-        let right_columns = right.clone().into_arrays();
+/// Output from joining a single batch on the right side.
+#[derive(Debug)]
+struct ProbeOutput<'a> {
+    /// Visited rows on the left for LEFT, SEMI, ANTI, and mark joins.
+    left_tracker: Option<&'a mut LeftOuterJoinTracker>,
+    /// Visited rows in the right batch for RIGHT joins.
+    right_tracker: Option<RightOuterJoinTracker>,
+    /// Only track visited rows on the left, don't produce joined rows.
+    mark_only: bool,
+    batches: Vec<Batch>,
+}
+
+impl ProbeOutput<'_> {
+    /// Join (left, right) row pairs, keeping the pairs that pass the filter.
+    fn push_candidates(
+        &mut self,
+        filter: Option<&PhysicalScalarExpression>,
+        left_batch_idx: usize,
+        left: &Batch,
+        left_sel: SelectionVector,
+        right: &Batch,
+        right_sel: SelectionVector,
+    ) -> Result<()> {
+        let left_columns = left.select(Arc::new(left_sel.clone())).into_arrays();
+        let right_columns = right.select(Arc::new(right_sel.clone())).into_arrays();
 
         let mut output = Batch::try_new(left_columns.into_iter().chain(right_columns))?;
 
-        // If we have a filter, apply it to the output batch.
-        if let Some(filter_expr) = &filter_expr {
-            let selection = Arc::new(filter_expr.select(&output)?);
-            output = output.select(selection.clone());
+        // Prune pairs not passing the filter, keeping track of which rows on
+        // both sides remain.
+        let (left_sel, right_sel) = match filter {
+            Some(filter) => {
+                let selection = filter.select(&output)?;
+                let remaining = (left_sel.select(&selection), right_sel.select(&selection));
+                output = output.select(Arc::new(selection));
+                remaining
+            }
+            None => (left_sel, right_sel),
+        };
+
+        if let Some(left_tracker) = self.left_tracker.as_mut() {
+            left_tracker.mark_rows_visited_for_batch(left_batch_idx, left_sel.iter_locations());
+        }
+
+        if let Some(right_tracker) = self.right_tracker.as_mut() {
+            right_tracker.mark_rows_visited(right_sel.iter_locations());
+        }
+
+        if !self.mark_only && output.num_rows() > 0 {
+            self.batches.push(output);
+        }
+
+        Ok(())
+    }
 
-            // If we're left joining, compute indices in the left batch that we
-            // visited.
-            if let Some(left_outer_tracker) = &mut left_outer_tracker {
-                left_outer_tracker
-                    .mark_rows_visited_for_batch(left_batch_idx, selection.iter_locations());
+    /// Get the output batches, including the unvisited rows from the right
+    /// batch for RIGHT joins.
+    fn finish(mut self, left_types: &[DataType], right: &Batch) -> Result<Vec<Batch>> {
+        if let Some(right_tracker) = self.right_tracker {
+            if let Some(extra) = right_tracker.into_unvisited(left_types, right)? {
+                self.batches.push(extra);
             }
         }
 
-        batches.push(output);
+        Ok(self.batches)
     }
+}
+
+/// Pair every row on the left with every row on the right, producing selection
+/// vectors for (left, right) rows in blocks of at most `block_size` pairs.
+///
+/// The right rows are split into blocks of `block_size` rows, with each block
+/// paired with as many left rows as will fit.
+fn cross_product_blocks(
+    num_left: usize,
+    num_right: usize,
+    block_size: usize,
+) -> impl Iterator<Item = (SelectionVector, SelectionVector)> {
+    let right_block = usize::min(num_right, block_size).max(1);
+
+    (0..num_right)
+        .step_by(right_block)
+        .flat_map(move |right_start| {
+            let right_len = usize::min(right_block, num_right - right_start);
+            let left_block = (block_size / right_len).max(1);
+
+            (0..num_left).step_by(left_block).map(move |left_start| {
+                let left_len = usize::min(left_block, num_left - left_start);
 
-    Ok(batches)
+                let mut left_sel = SelectionVector::with_capacity(left_len * right_len);
+                let mut right_sel = SelectionVector::with_capacity(left_len * right_len);
+                for left_idx in left_start..(left_start + left_len) {
+                    for right_idx in right_start..(right_start + right_len) {
+                        left_sel.push_location(left_idx);
+                        right_sel.push_location(right_idx);
+                    }
+                }
+
+                (left_sel, right_sel)
+            })
+        })
+}
+
+/// Index over the build side of the join provided by the function used in the
+/// join condition.
+#[derive(Debug)]
+struct NestedLoopJoinIndex {
+    index: Box<dyn JoinIndex>,
+    /// Column in the probe side batches to probe the index with.
+    probe_column: usize,
+}
+
+impl NestedLoopJoinIndex {
+    /// Try to build an index for the join filter.
+    ///
+    /// An index can only be built if the filter is a function call with two
+    /// column arguments, one from each side of the join, and the function
+    /// supports join indexes.
+    fn try_build(
+        filter: Option<&PhysicalScalarExpression>,
+        batches: &[Batch],
+    ) -> Result<Option<Self>> {
+        let func = match filter {
+            Some(PhysicalScalarExpression::ScalarFunction(func)) => func,
+            _ => return Ok(None),
+        };
+
+        // Output batches contain the columns from the build side followed by
+        // the columns from the probe side.
+        let build_width = match batches.first() {
+            Some(batch) => batch.num_columns(),
+            None => return Ok(None),
+        };
+
+        let (left, right) = match func.inputs.as_slice() {
+            [PhysicalScalarExpression::Column(left), PhysicalScalarExpression::Column(right)] => {
+                (left.idx, right.idx)
+            }
+            _ => return Ok(None),
+        };
+
+        let (build_arg, build_column, probe_column) =
+            match (left < build_width, right < build_width) {
+                (true, false) => (0, left, right - build_width),
+                (false, true) => (1, right, left - build_width),
+                _ => return Ok(None),
+            };
+
+        let build_arrays = batches
+            .iter()
+            .map(|batch| batch.column(build_column).required("build column"))
+            .collect::<Result<Vec<_>>>()?;
+
+        let index = func
+            .function
+            .function_impl
+            .build_join_index(build_arg, &build_arrays)?;
+
+        Ok(index.map(|index| NestedLoopJoinIndex {
+            index,
+            probe_column,
+        }))
+    }
 }
 
 impl Explainable for PhysicalNestedLoopJoin {
@@ -475,24 +855,241 @@ impl Explainable for PhysicalNestedLoopJoin {
 impl DatabaseProtoConv for PhysicalNestedLoopJoin {
     type ProtoType = rayexec_proto::generated::execution::PhysicalNestedLoopJoin;
 
-    fn to_proto_ctx(&self, _context: &DatabaseContext) -> Result<Self::ProtoType> {
-        unimplemented!()
-        // Ok(Self::ProtoType {
-        //     filter: self
-        //         .filter
-        //         .as_ref()
-        //         .map(|f| f.to_proto_ctx(context))
-        //         .transpose()?,
-        // })
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            filter: self
+                .filter
+                .as_ref()
+                .map(|f| f.to_proto_ctx(context))
+                .transpose()?,
+            join_type: Some(self.join_type.to_proto()?),
+            left_types: self
+                .left_types
+                .iter()
+                .map(|t| t.to_proto())
+                .collect::<Result<Vec<_>>>()?,
+            right_types: self
+                .right_types
+                .iter()
+                .map(|t| t.to_proto())
+                .collect::<Result<Vec<_>>>()?,
+        })
     }
 
-    fn from_proto_ctx(_proto: Self::ProtoType, _context: &DatabaseContext) -> Result<Self> {
-        unimplemented!()
-        // Ok(Self {
-        //     filter: proto
-        //         .filter
-        //         .map(|f| PhysicalScalarExpression::from_proto_ctx(f, context))
-        //         .transpose()?,
-        // })
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        Ok(Self {
+            filter: proto
+                .filter
+                .map(|f| PhysicalScalarExpression::from_proto_ctx(f, context))
+                .transpose()?,
+            join_type: ProtoConv::from_proto(proto.join_type.required("join_type")?)?,
+            left_types: proto
+                .left_types
+                .into_iter()
+                .map(ProtoConv::from_proto)
+                .collect::<Result<Vec<_>>>()?,
+            right_types: proto
+                .right_types
+                .into_iter()
+                .map(ProtoConv::from_proto)
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::execution::operators::test_util::{
+        logical_value,
+        make_i32_batch,
+        test_database_context,
+        TestWakerContext,
+    };
+    use crate::expr;
+    use crate::expr::physical::column_expr::PhysicalColumnExpr;
+    use crate::expr::physical::scalar_function_expr::PhysicalScalarFunctionExpr;
+    use crate::functions::scalar::builtin::comparison::Lt;
+    use crate::functions::scalar::ScalarFunction;
+    use crate::logical::binder::table_list::TableList;
+
+    /// Create an operator joining two single column i32 inputs on `left <
+    /// right`.
+    fn lt_join(join_type: JoinType) -> Arc<PhysicalNestedLoopJoin> {
+        let function = Lt
+            .plan(&TableList::empty(), vec![expr::lit(0), expr::lit(0)])
+            .unwrap();
+        let filter = PhysicalScalarExpression::ScalarFunction(PhysicalScalarFunctionExpr {
+            function,
+            inputs: vec![
+                PhysicalScalarExpression::Column(PhysicalColumnExpr { idx: 0 }),
+                PhysicalScalarExpression::Column(PhysicalColumnExpr { idx: 1 }),
+            ],
+            input_types: vec![DataType::Int32, DataType::Int32],
+        });
+
+        Arc::new(PhysicalNestedLoopJoin::new(
+            Some(filter),
+            join_type,
+            vec![DataType::Int32],
+            vec![DataType::Int32],
+        ))
+    }
+
+    /// Run the join to completion with a single partition on each side,
+    /// returning (left, right) values for each output row.
+    fn run_join(
+        operator: &Arc<PhysicalNestedLoopJoin>,
+        block_size: usize,
+        left: Vec<Batch>,
+        right: Vec<Batch>,
+    ) -> Vec<(ScalarValue<'static>, ScalarValue<'static>)> {
+        let states = operator
+            .create_states(&test_database_context(), block_size, vec![1])
+            .unwrap();
+        let operator_state = states.operator_state;
+        let mut partition_states = match states.partition_states {
+            InputOutputStates::NaryInputSingleOutput {
+                partition_states, ..
+            } => partition_states,
+            other => panic!("invalid states: {other:?}"),
+        };
+        let mut probe_state = partition_states.pop().unwrap().pop().unwrap();
+        let mut build_state = partition_states.pop().unwrap().pop().unwrap();
+
+        let cx = TestWakerContext::new();
+        for batch in left {
+            cx.poll_push(operator, &mut build_state, &operator_state, batch)
+                .unwrap();
+        }
+        operator
+            .poll_finalize_push(&mut cx.context(), &mut build_state, &operator_state)
+            .unwrap();
+
+        let mut output = Vec::new();
+        let mut collect = |computed: ComputedBatches| {
+            let mut computed = computed;
+            while let Some(batch) = computed.try_pop_front().unwrap() {
+                for row in 0..batch.num_rows() {
+                    output.push((
+                        logical_value(&batch, 0, row).into_owned(),
+                        logical_value(&batch, 1, row).into_owned(),
+                    ));
+                }
+            }
+        };
+
+        for batch in right {
+            cx.poll_push(operator, &mut probe_state, &operator_state, batch)
+                .unwrap();
+            if let PollPull::Computed(computed) = cx
+                .poll_pull(operator, &mut probe_state, &operator_state)
+                .unwrap()
+            {
+                collect(computed);
+            }
+        }
+        operator
+            .poll_finalize_push(&mut cx.context(), &mut probe_state, &operator_state)
+            .unwrap();
+
+        loop {
+            match cx
+                .poll_pull(operator, &mut probe_state, &operator_state)
+                .unwrap()
+            {
+                PollPull::Computed(computed) => collect(computed),
+                PollPull::Exhausted => break,
+                other => panic!("unexpected poll pull: {other:?}"),
+            }
+        }
+
+        output
+    }
+
+    fn pairs(
+        vals: impl IntoIterator<Item = (Option<i32>, Option<i32>)>,
+    ) -> Vec<(ScalarValue<'static>, ScalarValue<'static>)> {
+        let to_scalar = |v: Option<i32>| v.map(ScalarValue::Int32).unwrap_or(ScalarValue::Null);
+        vals.into_iter()
+            .map(|(l, r)| (to_scalar(l), to_scalar(r)))
+            .collect()
+    }
+
+    #[test]
+    fn cross_product_blocks_cover_all_pairs() {
+        for (num_left, num_right, block_size) in [
+            (3, 4, 5),
+            (3, 4, 100),
+            (10, 3, 1),
+            (2, 7, 3),
+            (0, 4, 2),
+            (4, 0, 2),
+        ] {
+            let mut seen = Vec::new();
+            for (left_sel, right_sel) in cross_product_blocks(num_left, num_right, block_size) {
+                assert!(left_sel.num_rows() <= block_size);
+                assert_eq!(left_sel.num_rows(), right_sel.num_rows());
+                seen.extend(left_sel.iter_locations().zip(right_sel.iter_locations()));
+            }
+            seen.sort();
+
+            let expected: Vec<_> = (0..num_left)
+                .flat_map(|l| (0..num_right).map(move |r| (l, r)))
+                .collect();
+            assert_eq!(expected, seen, "{num_left}x{num_right} ({block_size})");
+        }
+    }
+
+    #[test]
+    fn inner_join_across_blocks() {
+        let operator = lt_join(JoinType::Inner);
+        let out = run_join(
+            &operator,
+            2,
+            vec![make_i32_batch([1, 3]), make_i32_batch([5])],
+            vec![make_i32_batch([2, 4, 6])],
+        );
+
+        let expected = pairs([
+            (Some(1), Some(2)),
+            (Some(1), Some(4)),
+            (Some(3), Some(4)),
+            (Some(1), Some(6)),
+            (Some(3), Some(6)),
+            (Some(5), Some(6)),
+        ]);
+        assert_eq!(expected, out);
+    }
+
+    #[test]
+    fn full_join_emits_unmatched() {
+        let operator = lt_join(JoinType::Full);
+        let out = run_join(
+            &operator,
+            4,
+            vec![make_i32_batch([1, 7])],
+            vec![make_i32_batch([0, 3])],
+        );
+
+        // Unmatched rows on the right are emitted with each probe batch, rows
+        // on the left once probing is done.
+        let expected = pairs([(Some(1), Some(3)), (None, Some(0)), (Some(7), None)]);
+        assert_eq!(expected, out);
+    }
+
+    #[test]
+    fn anti_join_emits_left_without_match() {
+        let operator = lt_join(JoinType::Anti);
+        let out = run_join(
+            &operator,
+            4,
+            vec![make_i32_batch([1, 7, 2])],
+            vec![make_i32_batch([3])],
+        );
+
+        let expected = pairs([(Some(7), None)]);
+        assert_eq!(expected, out);
     }
 }
@@ -0,0 +1,335 @@
+use std::sync::Arc;
+use std::task::{Context, Waker};
+
+use parking_lot::Mutex;
+use rayexec_error::{RayexecError, Result};
+
+use super::scan::ScanPartitionState;
+use super::util::batch_width::estimated_row_width;
+use super::{
+    ExecutableOperator,
+    ExecutionStates,
+    InputOutputStates,
+    OperatorState,
+    PartitionState,
+    PollFinalize,
+    PollPull,
+    PollPush,
+};
+use crate::arrays::batch::Batch;
+use crate::database::DatabaseContext;
+use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::optimizer::query_pushdown::ShippedRows;
+use crate::storage::table_storage::{DataTableScan, RemoteQuery, TableStorage};
+
+/// Scan the output of a query executed by a catalog's table storage.
+#[derive(Debug)]
+pub struct PhysicalQueryScan {
+    catalog: String,
+    query: RemoteQuery,
+}
+
+impl PhysicalQueryScan {
+    pub fn new(catalog: impl Into<String>, query: RemoteQuery) -> Self {
+        PhysicalQueryScan {
+            catalog: catalog.into(),
+            query,
+        }
+    }
+
+    /// Estimated width in bytes of the rows produced by this scan.
+    pub fn estimated_row_width(&self) -> usize {
+        estimated_row_width(self.query.types.iter())
+    }
+}
+
+impl ExecutableOperator for PhysicalQueryScan {
+    fn create_states(
+        &self,
+        context: &DatabaseContext,
+        batch_size: usize,
+        partitions: Vec<usize>,
+    ) -> Result<ExecutionStates> {
+        let database = context.get_database(&self.catalog)?;
+        let scans = database
+            .table_storage
+            .as_ref()
+            .ok_or_else(|| RayexecError::new("Missing table storage for query scan"))?
+            .scan_query(&self.query, partitions[0], batch_size)?;
+
+        let states = scans
+            .into_iter()
+            .map(|scan| PartitionState::Scan(ScanPartitionState::new(scan)))
+            .collect();
+
+        Ok(ExecutionStates {
+            operator_state: Arc::new(OperatorState::None),
+            partition_states: InputOutputStates::OneToOne {
+                partition_states: states,
+            },
+        })
+    }
+
+    fn poll_push(
+        &self,
+        _cx: &mut Context,
+        _partition_state: &mut PartitionState,
+        _operator_state: &OperatorState,
+        _batch: Batch,
+    ) -> Result<PollPush> {
+        Err(RayexecError::new("Cannot push to physical query scan"))
+    }
+
+    fn poll_finalize_push(
+        &self,
+        _cx: &mut Context,
+        _partition_state: &mut PartitionState,
+        _operator_state: &OperatorState,
+    ) -> Result<PollFinalize> {
+        Err(RayexecError::new("Cannot push to physical query scan"))
+    }
+
+    fn poll_pull(
+        &self,
+        cx: &mut Context,
+        partition_state: &mut PartitionState,
+        _operator_state: &OperatorState,
+    ) -> Result<PollPull> {
+        match partition_state {
+            PartitionState::Scan(state) => state.poll_pull(cx),
+            other => panic!("invalid partition state: {other:?}"),
+        }
+    }
+}
+
+impl Explainable for PhysicalQueryScan {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("QueryScan")
+            .with_value("catalog", &self.catalog)
+            .with_value("query", &self.query.sql)
+    }
+}
+
+#[derive(Debug)]
+pub enum ShippedQueryScanPartitionState {
+    /// Partition is collecting rows to ship.
+    Collecting {
+        /// Index of this partition.
+        partition_idx: usize,
+        /// Rows collected by this partition.
+        batches: Vec<Batch>,
+    },
+    /// Partition is reading the query output.
+    Scanning(Box<ScanPartitionState>),
+}
+
+#[derive(Debug)]
+pub struct ShippedQueryScanOperatorState {
+    inner: Mutex<ShippedQueryScanOperatorStateInner>,
+}
+
+#[derive(Debug)]
+struct ShippedQueryScanOperatorStateInner {
+    /// Number of partitions still collecting rows.
+    remaining: usize,
+    /// Rows collected from all finished partitions.
+    batches: Vec<Batch>,
+    storage: Arc<dyn TableStorage>,
+    batch_size: usize,
+    /// Scans for each partition, set once all rows are collected.
+    scans: Option<Vec<Option<Box<dyn DataTableScan>>>>,
+    /// Wakers for partitions waiting for the scans.
+    ///
+    /// Indexed by partition_idx.
+    pull_wakers: Vec<Option<Waker>>,
+}
+
+/// Scan the output of a query executed by a catalog's table storage, with
+/// rows from the input sent along with the query.
+///
+/// All input rows are collected before the query is sent.
+#[derive(Debug)]
+pub struct PhysicalShippedQueryScan {
+    catalog: String,
+    query: RemoteQuery,
+    shipped: ShippedRows,
+}
+
+impl PhysicalShippedQueryScan {
+    pub fn new(catalog: impl Into<String>, query: RemoteQuery, shipped: ShippedRows) -> Self {
+        PhysicalShippedQueryScan {
+            catalog: catalog.into(),
+            query,
+            shipped,
+        }
+    }
+
+    /// Estimated width in bytes of the rows produced by this scan.
+    pub fn estimated_row_width(&self) -> usize {
+        estimated_row_width(self.query.types.iter())
+    }
+}
+
+impl ExecutableOperator for PhysicalShippedQueryScan {
+    fn create_states(
+        &self,
+        context: &DatabaseContext,
+        batch_size: usize,
+        partitions: Vec<usize>,
+    ) -> Result<ExecutionStates> {
+        let num_partitions = partitions[0];
+
+        let database = context.get_database(&self.catalog)?;
+        let storage = database
+            .table_storage
+            .clone()
+            .ok_or_else(|| RayexecError::new("Missing table storage for query scan"))?;
+
+        let operator_state = ShippedQueryScanOperatorState {
+            inner: Mutex::new(ShippedQueryScanOperatorStateInner {
+                remaining: num_partitions,
+                batches: Vec::new(),
+                storage,
+                batch_size,
+                scans: None,
+                pull_wakers: (0..num_partitions).map(|_| None).collect(),
+            }),
+        };
+
+        let partition_states = (0..num_partitions)
+            .map(|idx| {
+                PartitionState::ShippedQueryScan(ShippedQueryScanPartitionState::Collecting {
+                    partition_idx: idx,
+                    batches: Vec::new(),
+                })
+            })
+            .collect();
+
+        Ok(ExecutionStates {
+            operator_state: Arc::new(OperatorState::ShippedQueryScan(operator_state)),
+            partition_states: InputOutputStates::OneToOne { partition_states },
+        })
+    }
+
+    fn poll_push(
+        &self,
+        _cx: &mut Context,
+        partition_state: &mut PartitionState,
+        _operator_state: &OperatorState,
+        batch: Batch,
+    ) -> Result<PollPush> {
+        match partition_state {
+            PartitionState::ShippedQueryScan(ShippedQueryScanPartitionState::Collecting {
+                batches,
+                ..
+            }) => {
+                batches.push(batch);
+                Ok(PollPush::NeedsMore)
+            }
+            PartitionState::ShippedQueryScan(ShippedQueryScanPartitionState::Scanning(_)) => Err(
+                RayexecError::new("Attempted to push to partition that's scanning"),
+            ),
+            other => panic!("invalid partition state: {other:?}"),
+        }
+    }
+
+    fn poll_finalize_push(
+        &self,
+        _cx: &mut Context,
+        partition_state: &mut PartitionState,
+        operator_state: &OperatorState,
+    ) -> Result<PollFinalize> {
+        let batches = match partition_state {
+            PartitionState::ShippedQueryScan(ShippedQueryScanPartitionState::Collecting {
+                batches,
+                ..
+            }) => std::mem::take(batches),
+            PartitionState::ShippedQueryScan(ShippedQueryScanPartitionState::Scanning(_)) => {
+                return Err(RayexecError::new(
+                    "Attempted to finalize push partition that's scanning",
+                ))
+            }
+            other => panic!("invalid partition state: {other:?}"),
+        };
+
+        let mut shared = match operator_state {
+            OperatorState::ShippedQueryScan(state) => state.inner.lock(),
+            other => panic!("invalid operator state: {other:?}"),
+        };
+
+        shared.batches.extend(batches);
+        shared.remaining -= 1;
+
+        if shared.remaining == 0 {
+            // All rows collected, send the query.
+            let query = self.shipped.query_with_rows(&self.query, &shared.batches)?;
+            shared.batches.clear();
+
+            let num_partitions = shared.pull_wakers.len();
+            let scans = shared
+                .storage
+                .scan_query(&query, num_partitions, shared.batch_size)?;
+            shared.scans = Some(scans.into_iter().map(Some).collect());
+
+            for waker in shared.pull_wakers.iter_mut() {
+                if let Some(waker) = waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+
+        Ok(PollFinalize::Finalized)
+    }
+
+    fn poll_pull(
+        &self,
+        cx: &mut Context,
+        partition_state: &mut PartitionState,
+        operator_state: &OperatorState,
+    ) -> Result<PollPull> {
+        let state = match partition_state {
+            PartitionState::ShippedQueryScan(state) => state,
+            other => panic!("invalid partition state: {other:?}"),
+        };
+
+        if let ShippedQueryScanPartitionState::Collecting { partition_idx, .. } = state {
+            let mut shared = match operator_state {
+                OperatorState::ShippedQueryScan(state) => state.inner.lock(),
+                other => panic!("invalid operator state: {other:?}"),
+            };
+
+            let scan = match shared.scans.as_mut() {
+                Some(scans) => scans.get_mut(*partition_idx).and_then(|scan| scan.take()),
+                None => {
+                    // Still collecting rows in other partitions.
+                    shared.pull_wakers[*partition_idx] = Some(cx.waker().clone());
+                    return Ok(PollPull::Pending);
+                }
+            };
+
+            match scan {
+                Some(scan) => {
+                    *state = ShippedQueryScanPartitionState::Scanning(Box::new(
+                        ScanPartitionState::new(scan),
+                    ))
+                }
+                // Storage produced fewer scans than partitions.
+                None => return Ok(PollPull::Exhausted),
+            }
+        }
+
+        match state {
+            ShippedQueryScanPartitionState::Scanning(scan) => scan.poll_pull(cx),
+            ShippedQueryScanPartitionState::Collecting { .. } => unreachable!(),
+        }
+    }
+}
+
+impl Explainable for PhysicalShippedQueryScan {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("ShippedQueryScan")
+            .with_value("catalog", &self.catalog)
+            .with_value("query", &self.query.sql)
+            .with_values("shipped_columns", &self.shipped.columns)
+    }
+}
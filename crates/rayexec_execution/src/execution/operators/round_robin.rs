@@ -18,6 +18,7 @@ use crate::execution::operators::{
     PollPush,
 };
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::proto::DatabaseProtoConv;
 
 /// Partition state on the pull side.
 #[derive(Debug)]
@@ -61,17 +62,20 @@ impl ExecutableOperator for PhysicalRoundRobinRepartition {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
-        if partitions.len() != 2 {
-            return Err(RayexecError::new(
-                "Round robin expects to values (input, output) in partition vec",
-            ));
-        }
-
-        // TODO: Unsure if I like this.
-        let input_partitions = partitions[0];
-        let output_partitions = partitions[1];
+        // A single value is used when planned as an intermediate operator,
+        // with the output partitions matching the input partitions.
+        let (input_partitions, output_partitions) = match partitions.as_slice() {
+            [partitions] => (*partitions, *partitions),
+            [input, output] => (*input, *output),
+            _ => {
+                return Err(RayexecError::new(
+                    "Round robin expects one or two values (input, output) in partition vec",
+                ))
+            }
+        };
 
         let operator_state = RoundRobinOperatorState {
             num_inputs_remaining: AtomicUsize::new(input_partitions),
@@ -295,3 +299,15 @@ impl Explainable for PhysicalRoundRobinRepartition {
         ExplainEntry::new("RoundRobinRepartition")
     }
 }
+
+impl DatabaseProtoConv for PhysicalRoundRobinRepartition {
+    type ProtoType = rayexec_proto::generated::execution::PhysicalRoundRobinRepartition;
+
+    fn to_proto_ctx(&self, _context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {})
+    }
+
+    fn from_proto_ctx(_proto: Self::ProtoType, _context: &DatabaseContext) -> Result<Self> {
+        Ok(Self)
+    }
+}
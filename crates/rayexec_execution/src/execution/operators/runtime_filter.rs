@@ -0,0 +1,412 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Poll, Waker};
+
+use parking_lot::Mutex;
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::executor::scalar::HashExecutor;
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::arrays::selection::SelectionVector;
+
+/// Max number of distinct keys to keep in a runtime filter.
+///
+/// Filters with more keys only keep the bloom filter and range.
+pub const MAX_RUNTIME_FILTER_DISTINCT: usize = 256;
+
+/// Upper bound on the number of bits in a bloom filter (8MB).
+const MAX_BLOOM_BITS: usize = 1 << 26;
+
+/// Bits to use per key in the bloom filter.
+const BLOOM_BITS_PER_KEY: usize = 8;
+
+/// Number of bits set per key in the bloom filter.
+const BLOOM_NUM_HASHES: u64 = 3;
+
+/// A filter on a single join key produced by the build side of a hash join.
+///
+/// The filter is completed once all build side inputs have been read, and can
+/// be used by scans on the probe side to skip rows that can't possibly have a
+/// join partner.
+#[derive(Debug, Default)]
+pub struct RuntimeFilter {
+    state: Mutex<RuntimeFilterState>,
+}
+
+#[derive(Debug, Default)]
+struct RuntimeFilterState {
+    values: Option<Arc<RuntimeFilterValues>>,
+    wakers: Vec<Waker>,
+}
+
+impl RuntimeFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Complete the filter, waking up anything waiting on it.
+    pub fn complete(&self, values: RuntimeFilterValues) {
+        let mut state = self.state.lock();
+        state.values = Some(Arc::new(values));
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Get the filter values if the filter has been completed.
+    pub fn get(&self) -> Option<Arc<RuntimeFilterValues>> {
+        self.state.lock().values.clone()
+    }
+
+    /// Wait for the filter to be completed.
+    pub fn wait(&self) -> impl Future<Output = Arc<RuntimeFilterValues>> + '_ {
+        futures::future::poll_fn(|cx| {
+            let mut state = self.state.lock();
+            match &state.values {
+                Some(values) => Poll::Ready(values.clone()),
+                None => {
+                    state.wakers.push(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+    }
+}
+
+/// A runtime filter pushed into a scan.
+#[derive(Debug, Clone)]
+pub struct ScanRuntimeFilter {
+    /// The column index this filter applies to.
+    ///
+    /// This is referencing a column prior to any projections being performed.
+    pub column: usize,
+    /// The filter, may not yet be complete when the scan starts.
+    pub filter: Arc<RuntimeFilter>,
+}
+
+impl fmt::Display for ScanRuntimeFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.column)
+    }
+}
+
+/// Join keys collected from the build side of a join.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeFilterValues {
+    /// Number of non-null keys on the build side, including duplicates.
+    pub num_keys: usize,
+    /// Min and max keys, only tracked for integer keys.
+    pub range: Option<(i128, i128)>,
+    /// All distinct keys, only kept if there's at most
+    /// `MAX_RUNTIME_FILTER_DISTINCT` of them.
+    pub distinct: Option<Vec<OwnedScalarValue>>,
+    /// Bloom filter over key hashes.
+    bloom: Vec<u64>,
+}
+
+impl RuntimeFilterValues {
+    /// Returns true if the build side had no non-null keys, meaning nothing can
+    /// match.
+    pub fn is_empty(&self) -> bool {
+        self.num_keys == 0
+    }
+
+    /// Select rows in the array that may match a key on the build side.
+    ///
+    /// Nulls are never selected. The array must be the same type as the keys
+    /// on the build side.
+    pub fn select(&self, array: &Array, hash_buf: &mut Vec<u64>) -> Result<SelectionVector> {
+        let num_rows = array.logical_len();
+        if self.is_empty() {
+            return Ok(SelectionVector::with_capacity(0));
+        }
+
+        hash_buf.clear();
+        hash_buf.resize(num_rows, 0);
+        HashExecutor::hash_no_combine(array, hash_buf)?;
+
+        let selection = hash_buf
+            .iter()
+            .enumerate()
+            .filter(|(idx, hash)| {
+                array.is_valid(*idx).unwrap_or(false) && bloom_contains(&self.bloom, **hash)
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        Ok(selection)
+    }
+}
+
+/// Collects keys for a runtime filter.
+///
+/// Each build partition collects into its own builder, with all builders being
+/// merged once the build side is complete.
+#[derive(Debug)]
+pub struct RuntimeFilterBuilder {
+    /// Hashes for all non-null keys.
+    hashes: Vec<u64>,
+    /// If we're still tracking the key range, unset on the first non-integer
+    /// key.
+    track_range: bool,
+    range: Option<(i128, i128)>,
+    /// Distinct keys, unset once we go over the limit.
+    distinct: Option<HashSet<OwnedScalarValue>>,
+    /// Reusable hash buffer.
+    hash_buf: Vec<u64>,
+}
+
+impl Default for RuntimeFilterBuilder {
+    fn default() -> Self {
+        RuntimeFilterBuilder {
+            hashes: Vec::new(),
+            track_range: true,
+            range: None,
+            distinct: Some(HashSet::new()),
+            hash_buf: Vec::new(),
+        }
+    }
+}
+
+impl RuntimeFilterBuilder {
+    /// Insert all keys from an array of join keys.
+    pub fn insert(&mut self, array: &Array) -> Result<()> {
+        let num_rows = array.logical_len();
+
+        let mut hash_buf = std::mem::take(&mut self.hash_buf);
+        hash_buf.clear();
+        hash_buf.resize(num_rows, 0);
+        HashExecutor::hash_no_combine(array, &mut hash_buf)?;
+
+        self.track_range &= array.datatype().is_integer();
+
+        for (idx, hash) in hash_buf.iter().enumerate() {
+            if !array.is_valid(idx).unwrap_or(false) {
+                continue;
+            }
+            self.hashes.push(*hash);
+
+            if !self.track_range && self.distinct.is_none() {
+                continue;
+            }
+
+            let value = array.logical_value(idx)?;
+            if self.track_range {
+                match scalar_as_i128(&value) {
+                    Some(v) => self.extend_range(v, v),
+                    None => {
+                        self.track_range = false;
+                        self.range = None;
+                    }
+                }
+            }
+            if let Some(distinct) = &mut self.distinct {
+                distinct.insert(value.into_owned());
+                if distinct.len() > MAX_RUNTIME_FILTER_DISTINCT {
+                    self.distinct = None;
+                }
+            }
+        }
+
+        self.hash_buf = hash_buf;
+
+        Ok(())
+    }
+
+    /// Merge keys from another builder into this one.
+    pub fn merge(&mut self, other: RuntimeFilterBuilder) {
+        self.hashes.extend(other.hashes);
+
+        self.track_range &= other.track_range;
+        match (self.track_range, other.range) {
+            (true, Some((min, max))) => self.extend_range(min, max),
+            (false, _) => self.range = None,
+            _ => (),
+        }
+
+        self.distinct = match (self.distinct.take(), other.distinct) {
+            (Some(mut distinct), Some(other)) => {
+                distinct.extend(other);
+                (distinct.len() <= MAX_RUNTIME_FILTER_DISTINCT).then_some(distinct)
+            }
+            _ => None,
+        };
+    }
+
+    pub fn finish(self) -> RuntimeFilterValues {
+        let num_bits = (self.hashes.len() * BLOOM_BITS_PER_KEY)
+            .next_power_of_two()
+            .clamp(64, MAX_BLOOM_BITS);
+
+        let mut bloom = vec![0; num_bits / 64];
+        for hash in &self.hashes {
+            bloom_insert(&mut bloom, *hash);
+        }
+
+        RuntimeFilterValues {
+            num_keys: self.hashes.len(),
+            range: self.range,
+            distinct: self.distinct.map(|distinct| distinct.into_iter().collect()),
+            bloom,
+        }
+    }
+
+    fn extend_range(&mut self, min: i128, max: i128) {
+        self.range = match self.range {
+            Some((curr_min, curr_max)) => Some((curr_min.min(min), curr_max.max(max))),
+            None => Some((min, max)),
+        };
+    }
+}
+
+fn scalar_as_i128(value: &ScalarValue) -> Option<i128> {
+    Some(match value {
+        ScalarValue::Int8(v) => *v as i128,
+        ScalarValue::Int16(v) => *v as i128,
+        ScalarValue::Int32(v) => *v as i128,
+        ScalarValue::Int64(v) => *v as i128,
+        ScalarValue::Int128(v) => *v,
+        ScalarValue::UInt8(v) => *v as i128,
+        ScalarValue::UInt16(v) => *v as i128,
+        ScalarValue::UInt32(v) => *v as i128,
+        ScalarValue::UInt64(v) => *v as i128,
+        _ => return None,
+    })
+}
+
+/// Bit positions for a hash using double hashing.
+fn bloom_positions(num_bits: usize, hash: u64) -> impl Iterator<Item = usize> {
+    let mask = num_bits as u64 - 1;
+    let h2 = hash.rotate_left(32) | 1;
+    (0..BLOOM_NUM_HASHES).map(move |i| (hash.wrapping_add(i.wrapping_mul(h2)) & mask) as usize)
+}
+
+fn bloom_insert(bloom: &mut [u64], hash: u64) {
+    for pos in bloom_positions(bloom.len() * 64, hash) {
+        bloom[pos / 64] |= 1 << (pos % 64);
+    }
+}
+
+fn bloom_contains(bloom: &[u64], hash: u64) -> bool {
+    bloom_positions(bloom.len() * 64, hash).all(|pos| bloom[pos / 64] & (1 << (pos % 64)) != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Context;
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    fn build(arrays: &[Array]) -> RuntimeFilterValues {
+        let mut builder = RuntimeFilterBuilder::default();
+        for array in arrays {
+            builder.insert(array).unwrap();
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn select_matching_keys() {
+        let values = build(&[Array::from_iter([1, 5, 9])]);
+        assert_eq!(Some((1, 9)), values.range);
+
+        let probe = Array::from_iter([Some(5), None, Some(1), Some(9)]);
+        let selection = values.select(&probe, &mut Vec::new()).unwrap();
+
+        // Bloom filters can have false positives, but every key on the build
+        // side must be selected, and nulls never.
+        let selected: Vec<_> = selection.iter_locations().collect();
+        assert!(selected.starts_with(&[0]));
+        assert!(selected.contains(&2));
+        assert!(selected.contains(&3));
+        assert!(!selected.contains(&1));
+    }
+
+    #[test]
+    fn select_filters_most_non_matching() {
+        let values = build(&[Array::from_iter(0..100_i64)]);
+
+        let probe = Array::from_iter(1000..11_000_i64);
+        let selection = values.select(&probe, &mut Vec::new()).unwrap();
+        assert!(
+            selection.num_rows() < 1000,
+            "selected: {}",
+            selection.num_rows()
+        );
+    }
+
+    #[test]
+    fn empty_build_selects_nothing() {
+        let values = build(&[Array::from_iter([None as Option<i32>])]);
+        assert!(values.is_empty());
+
+        let probe = Array::from_iter([1, 2, 3]);
+        let selection = values.select(&probe, &mut Vec::new()).unwrap();
+        assert_eq!(0, selection.num_rows());
+    }
+
+    #[test]
+    fn merge_builders() {
+        let mut b1 = RuntimeFilterBuilder::default();
+        b1.insert(&Array::from_iter([4, 8])).unwrap();
+        let mut b2 = RuntimeFilterBuilder::default();
+        b2.insert(&Array::from_iter([-2, 8])).unwrap();
+        b1.merge(b2);
+
+        let values = b1.finish();
+        assert_eq!(4, values.num_keys);
+        assert_eq!(Some((-2, 8)), values.range);
+
+        let mut distinct = values.distinct.unwrap();
+        distinct.sort_by_key(|v| v.try_as_i64().unwrap());
+        assert_eq!(
+            vec![
+                ScalarValue::Int32(-2),
+                ScalarValue::Int32(4),
+                ScalarValue::Int32(8)
+            ],
+            distinct
+        );
+    }
+
+    #[test]
+    fn string_keys_no_range() {
+        let values = build(&[Array::from_iter(["a", "b"])]);
+        assert_eq!(None, values.range);
+        assert_eq!(2, values.distinct.as_ref().unwrap().len());
+
+        let probe = Array::from_iter(["b"]);
+        let selection = values.select(&probe, &mut Vec::new()).unwrap();
+        assert_eq!(vec![0], selection.iter_locations().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn too_many_distinct() {
+        let values = build(&[Array::from_iter(
+            0..(MAX_RUNTIME_FILTER_DISTINCT as i32 + 1),
+        )]);
+        assert_eq!(None, values.distinct);
+    }
+
+    #[test]
+    fn wait_for_complete() {
+        let filter = RuntimeFilter::new();
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut fut = Box::pin(filter.wait());
+        assert!(fut.poll_unpin(&mut cx).is_pending());
+        assert!(filter.get().is_none());
+
+        filter.complete(RuntimeFilterBuilder::default().finish());
+        match fut.poll_unpin(&mut cx) {
+            Poll::Ready(values) => assert!(values.is_empty()),
+            Poll::Pending => panic!("expected filter to be complete"),
+        }
+    }
+}
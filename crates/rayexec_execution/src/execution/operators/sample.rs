@@ -0,0 +1,300 @@
+use std::sync::Arc;
+use std::task::{Context, Waker};
+
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rayexec_error::Result;
+
+use super::simple::{SimpleOperator, StatelessOperation};
+use super::{
+    ExecutableOperator,
+    ExecutionStates,
+    InputOutputStates,
+    OperatorState,
+    PartitionState,
+    PollFinalize,
+    PollPull,
+    PollPush,
+};
+use crate::arrays::batch::Batch;
+use crate::arrays::selection::SelectionVector;
+use crate::database::DatabaseContext;
+use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::storage::table_storage::TableSample;
+
+pub type PhysicalSample = SimpleOperator<SampleOperation>;
+
+/// Streaming sample of batches flowing through the pipeline.
+///
+/// Used for TABLESAMPLE on inputs that aren't scans.
+#[derive(Debug)]
+pub struct SampleOperation {
+    sample: TableSample,
+    rng: Mutex<StdRng>,
+}
+
+impl SampleOperation {
+    pub fn new(sample: TableSample) -> Self {
+        SampleOperation {
+            rng: Mutex::new(sample.rng(0)),
+            sample,
+        }
+    }
+}
+
+impl StatelessOperation for SampleOperation {
+    fn execute(&self, batch: Batch) -> Result<Batch> {
+        let mut rng = self.rng.lock();
+        Ok(self.sample.sample_batch(&mut rng, batch))
+    }
+}
+
+impl Explainable for SampleOperation {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("Sample").with_value("sample", &self.sample)
+    }
+}
+
+/// Fixed size uniform sample of rows using reservoir sampling.
+#[derive(Debug)]
+pub struct Reservoir {
+    /// Max number of rows to keep.
+    capacity: usize,
+    /// Total number of rows seen so far.
+    seen: usize,
+    /// Rows currently in the reservoir.
+    sample: Option<Batch>,
+    rng: StdRng,
+}
+
+impl Reservoir {
+    pub fn new(capacity: usize, rng: StdRng) -> Self {
+        Reservoir {
+            capacity,
+            seen: 0,
+            sample: None,
+            rng,
+        }
+    }
+
+    /// Push a batch through the reservoir, possibly replacing rows already in
+    /// the sample.
+    pub fn push(&mut self, batch: Batch) -> Result<()> {
+        let num_rows = batch.num_rows();
+        if num_rows == 0 || self.capacity == 0 {
+            self.seen += num_rows;
+            return Ok(());
+        }
+
+        // Row indices into the concatenation of the current sample and the
+        // new batch.
+        let sample_len = self.sample.as_ref().map(|b| b.num_rows()).unwrap_or(0);
+        let mut slots: Vec<usize> = (0..sample_len).collect();
+        let mut changed = false;
+
+        for row in 0..num_rows {
+            if slots.len() < self.capacity {
+                slots.push(sample_len + row);
+                changed = true;
+                continue;
+            }
+
+            let idx = self.rng.gen_range(0..=(self.seen + row));
+            if idx < self.capacity {
+                slots[idx] = sample_len + row;
+                changed = true;
+            }
+        }
+        self.seen += num_rows;
+
+        if !changed {
+            return Ok(());
+        }
+
+        let combined = match self.sample.take() {
+            Some(sample) => Batch::concat(&[sample, batch])?,
+            None => batch,
+        };
+        self.sample = Some(combined.select(Arc::new(SelectionVector::from(slots))));
+
+        Ok(())
+    }
+
+    /// Return the sampled rows in random order.
+    pub fn finish(&mut self) -> Option<Batch> {
+        let sample = self.sample.take()?;
+        let mut indices: Vec<usize> = (0..sample.num_rows()).collect();
+        indices.shuffle(&mut self.rng);
+
+        Some(sample.select(Arc::new(SelectionVector::from(indices))))
+    }
+}
+
+#[derive(Debug)]
+pub struct ReservoirSamplePartitionState {
+    reservoir: Reservoir,
+    /// Final sample once all input has been pushed.
+    output: Option<Batch>,
+    /// Waker on the pull side, woken once the sample is complete.
+    pull_waker: Option<Waker>,
+    finished: bool,
+}
+
+/// Produce a uniform random sample of some fixed number of rows.
+///
+/// Used in place of `ORDER BY random() LIMIT n` to avoid sorting the entire
+/// input. Rows are returned in random order.
+///
+/// Sampling is done per partition, a global sample should be done by using a
+/// single partition.
+#[derive(Debug)]
+pub struct PhysicalReservoirSample {
+    rows: usize,
+}
+
+impl PhysicalReservoirSample {
+    pub fn new(rows: usize) -> Self {
+        PhysicalReservoirSample { rows }
+    }
+}
+
+impl ExecutableOperator for PhysicalReservoirSample {
+    fn create_states(
+        &self,
+        _context: &DatabaseContext,
+        _batch_size: usize,
+        partitions: Vec<usize>,
+    ) -> Result<ExecutionStates> {
+        Ok(ExecutionStates {
+            operator_state: Arc::new(OperatorState::None),
+            partition_states: InputOutputStates::OneToOne {
+                partition_states: (0..partitions[0])
+                    .map(|_| {
+                        PartitionState::ReservoirSample(ReservoirSamplePartitionState {
+                            reservoir: Reservoir::new(self.rows, StdRng::from_entropy()),
+                            output: None,
+                            pull_waker: None,
+                            finished: false,
+                        })
+                    })
+                    .collect(),
+            },
+        })
+    }
+
+    fn poll_push(
+        &self,
+        _cx: &mut Context,
+        partition_state: &mut PartitionState,
+        _operator_state: &OperatorState,
+        batch: Batch,
+    ) -> Result<PollPush> {
+        let state = match partition_state {
+            PartitionState::ReservoirSample(state) => state,
+            other => panic!("invalid partition state: {other:?}"),
+        };
+
+        state.reservoir.push(batch)?;
+
+        Ok(PollPush::NeedsMore)
+    }
+
+    fn poll_finalize_push(
+        &self,
+        _cx: &mut Context,
+        partition_state: &mut PartitionState,
+        _operator_state: &OperatorState,
+    ) -> Result<PollFinalize> {
+        let state = match partition_state {
+            PartitionState::ReservoirSample(state) => state,
+            other => panic!("invalid partition state: {other:?}"),
+        };
+
+        state.output = state.reservoir.finish();
+        state.finished = true;
+        if let Some(waker) = state.pull_waker.take() {
+            waker.wake();
+        }
+
+        Ok(PollFinalize::Finalized)
+    }
+
+    fn poll_pull(
+        &self,
+        cx: &mut Context,
+        partition_state: &mut PartitionState,
+        _operator_state: &OperatorState,
+    ) -> Result<PollPull> {
+        let state = match partition_state {
+            PartitionState::ReservoirSample(state) => state,
+            other => panic!("invalid partition state: {other:?}"),
+        };
+
+        if !state.finished {
+            state.pull_waker = Some(cx.waker().clone());
+            return Ok(PollPull::Pending);
+        }
+
+        match state.output.take() {
+            Some(batch) => Ok(PollPull::Computed(batch.into())),
+            None => Ok(PollPull::Exhausted),
+        }
+    }
+}
+
+impl Explainable for PhysicalReservoirSample {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("ReservoirSample").with_value("rows", self.rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::execution::operators::test_util::{logical_value, make_i32_batch};
+
+    fn sampled_values(batch: &Batch) -> HashSet<i32> {
+        (0..batch.num_rows())
+            .map(|row| match logical_value(batch, 0, row) {
+                ScalarValue::Int32(v) => v,
+                other => panic!("unexpected value: {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reservoir_fewer_rows_than_capacity() {
+        let mut reservoir = Reservoir::new(10, StdRng::seed_from_u64(0));
+        reservoir.push(make_i32_batch([1, 2, 3])).unwrap();
+        reservoir.push(make_i32_batch([4, 5])).unwrap();
+
+        let out = reservoir.finish().unwrap();
+        assert_eq!(HashSet::from([1, 2, 3, 4, 5]), sampled_values(&out));
+    }
+
+    #[test]
+    fn reservoir_caps_rows() {
+        let mut reservoir = Reservoir::new(4, StdRng::seed_from_u64(0));
+        for start in (0..100).step_by(10) {
+            reservoir.push(make_i32_batch(start..(start + 10))).unwrap();
+        }
+
+        let out = reservoir.finish().unwrap();
+        let values = sampled_values(&out);
+        assert_eq!(4, out.num_rows());
+        // No duplicates.
+        assert_eq!(4, values.len());
+        assert!(values.iter().all(|v| (0..100).contains(v)));
+    }
+
+    #[test]
+    fn reservoir_no_input() {
+        let mut reservoir = Reservoir::new(4, StdRng::seed_from_u64(0));
+        assert!(reservoir.finish().is_none());
+    }
+}
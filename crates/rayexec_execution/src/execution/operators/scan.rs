@@ -6,6 +6,8 @@ use futures::future::BoxFuture;
 use futures::FutureExt;
 use rayexec_error::{RayexecError, Result};
 
+use super::runtime_filter::{RuntimeFilter, ScanRuntimeFilter};
+use super::util::batch_width::estimated_row_width;
 use super::util::futures::make_static;
 use super::{
     ExecutableOperator,
@@ -18,17 +20,100 @@ use super::{
     PollPush,
 };
 use crate::arrays::batch::Batch;
-use crate::database::catalog::CatalogTx;
 use crate::database::catalog_entry::CatalogEntry;
 use crate::database::DatabaseContext;
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::logical::scan_filter::ScanFilter;
 use crate::proto::DatabaseProtoConv;
-use crate::storage::table_storage::{DataTableScan, Projections};
+use crate::storage::table_storage::{
+    DataTableScan,
+    IndexScan,
+    Projections,
+    TableAggregate,
+    TableSample,
+};
 
 pub struct ScanPartitionState {
     scan: Box<dyn DataTableScan>,
     /// In progress pull we're working on.
     future: Option<BoxFuture<'static, Result<Option<Batch>>>>,
+    /// Runtime filters to apply to batches once they're complete, paired with
+    /// the index of the column in the output batch.
+    runtime_filters: Vec<(usize, Arc<RuntimeFilter>)>,
+    /// Reusable hash buffer for checking runtime filters.
+    hash_buf: Vec<u64>,
+}
+
+impl ScanPartitionState {
+    pub(crate) fn new(scan: Box<dyn DataTableScan>) -> Self {
+        ScanPartitionState {
+            scan,
+            future: None,
+            runtime_filters: Vec::new(),
+            hash_buf: Vec::new(),
+        }
+    }
+
+    fn with_runtime_filters(mut self, filters: Vec<(usize, Arc<RuntimeFilter>)>) -> Self {
+        self.runtime_filters = filters;
+        self
+    }
+
+    /// Pull the next batch from the scan.
+    pub(crate) fn poll_pull(&mut self, cx: &mut Context) -> Result<PollPull> {
+        if let Some(future) = &mut self.future {
+            match future.poll_unpin(cx) {
+                Poll::Ready(Ok(Some(batch))) => {
+                    self.future = None; // Future complete, next pull with create a new one.
+                    let batch = self.apply_runtime_filters(batch)?;
+                    return Ok(PollPull::Computed(batch.into()));
+                }
+                Poll::Ready(Ok(None)) => return Ok(PollPull::Exhausted),
+                Poll::Ready(Err(e)) => return Err(e),
+                Poll::Pending => return Ok(PollPull::Pending),
+            }
+        }
+
+        let mut future = self.scan.pull();
+        match future.poll_unpin(cx) {
+            Poll::Ready(Ok(Some(batch))) => {
+                std::mem::drop(future);
+                let batch = self.apply_runtime_filters(batch)?;
+                Ok(PollPull::Computed(batch.into()))
+            }
+            Poll::Ready(Ok(None)) => Ok(PollPull::Exhausted),
+            Poll::Ready(Err(e)) => Err(e),
+            Poll::Pending => {
+                // SAFETY: Scan lives on the partition state and outlives this
+                // future.
+                self.future = Some(unsafe { make_static(future) });
+                Ok(PollPull::Pending)
+            }
+        }
+    }
+
+    /// Remove rows from the batch that can't match any completed runtime
+    /// filter.
+    ///
+    /// Filters that haven't been completed yet are skipped.
+    fn apply_runtime_filters(&mut self, mut batch: Batch) -> Result<Batch> {
+        for (idx, filter) in &self.runtime_filters {
+            let values = match filter.get() {
+                Some(values) => values,
+                None => continue,
+            };
+
+            let col = batch.column(*idx).ok_or_else(|| {
+                RayexecError::new(format!("Missing column {idx} for runtime filter"))
+            })?;
+            let selection = values.select(col, &mut self.hash_buf)?;
+            if selection.num_rows() != batch.num_rows() {
+                batch = batch.select(Arc::new(selection));
+            }
+        }
+
+        Ok(batch)
+    }
 }
 
 impl fmt::Debug for ScanPartitionState {
@@ -43,6 +128,12 @@ pub struct PhysicalScan {
     schema: String,
     table: Arc<CatalogEntry>,
     projections: Projections,
+    sample: Option<TableSample>,
+    limit: Option<usize>,
+    aggregate: Option<TableAggregate>,
+    index_scan: Option<IndexScan>,
+    filters: Vec<ScanFilter>,
+    runtime_filters: Vec<ScanRuntimeFilter>,
 }
 
 impl PhysicalScan {
@@ -57,33 +148,143 @@ impl PhysicalScan {
             schema: schema.into(),
             table,
             projections,
+            sample: None,
+            limit: None,
+            aggregate: None,
+            index_scan: None,
+            filters: Vec::new(),
+            runtime_filters: Vec::new(),
         }
     }
+
+    /// Sample the table instead of reading all rows.
+    pub fn with_sample(mut self, sample: Option<TableSample>) -> Self {
+        self.sample = sample;
+        self
+    }
+
+    /// Hint to the table that each partition only needs to produce up to
+    /// `limit` rows.
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Have the table compute an aggregate instead of returning its rows.
+    pub fn with_aggregate(mut self, aggregate: Option<TableAggregate>) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// Read rows through an index instead of scanning the whole table.
+    pub fn with_index_scan(mut self, index_scan: Option<IndexScan>) -> Self {
+        self.index_scan = index_scan;
+        self
+    }
+
+    /// Filters the table may use to skip reading data.
+    pub fn with_filters(mut self, filters: Vec<ScanFilter>) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    /// Filters produced by joins while executing.
+    ///
+    /// Rows not matching a filter are removed once the filter is complete,
+    /// and the table may use the filters to skip reading data.
+    pub fn with_runtime_filters(mut self, filters: Vec<ScanRuntimeFilter>) -> Self {
+        self.runtime_filters = filters;
+        self
+    }
+
+    /// Estimated width in bytes of the rows produced by this scan.
+    pub fn estimated_row_width(&self) -> Result<usize> {
+        let columns = &self.table.try_as_table_entry()?.columns;
+        let width = match &self.projections.column_indices {
+            Some(indices) => estimated_row_width(
+                indices
+                    .iter()
+                    .filter_map(|&idx| columns.get(idx).map(|c| &c.datatype)),
+            ),
+            None => estimated_row_width(columns.iter().map(|c| &c.datatype)),
+        };
+        Ok(width)
+    }
 }
 
 impl ExecutableOperator for PhysicalScan {
     fn create_states(
         &self,
         context: &DatabaseContext,
+        batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
-        // TODO: Placeholder for now. Transaction info should probably go on the
-        // operator.
-        let _tx = CatalogTx::new();
-
         let database = context.get_database(&self.catalog)?;
         let data_table = database
             .table_storage
             .as_ref()
             .ok_or_else(|| RayexecError::new("Missing table storage for scan"))?
-            .data_table(&self.schema, &self.table)?;
+            .data_table(context.transaction(), &self.schema, &self.table)?;
+
+        // TODO: Pushdown projections
+        let scans = match (&self.aggregate, &self.index_scan, &self.sample, self.limit) {
+            (Some(aggregate), _, _, _) => {
+                data_table.scan_aggregate(aggregate, partitions[0], batch_size)?
+            }
+            (None, Some(index_scan), _, _) => data_table.scan_index(
+                self.projections.clone(),
+                index_scan,
+                partitions[0],
+                batch_size,
+            )?,
+            (None, None, Some(sample), _) => data_table.scan_sample(
+                self.projections.clone(),
+                sample,
+                partitions[0],
+                batch_size,
+            )?,
+            (None, None, None, Some(limit)) => {
+                data_table.scan_limit(self.projections.clone(), limit, partitions[0], batch_size)?
+            }
+            (None, None, None, None) if !self.runtime_filters.is_empty() => data_table
+                .scan_runtime_filtered(
+                    self.projections.clone(),
+                    &self.filters,
+                    &self.runtime_filters,
+                    partitions[0],
+                    batch_size,
+                )?,
+            (None, None, None, None) if !self.filters.is_empty() => data_table.scan_filtered(
+                self.projections.clone(),
+                &self.filters,
+                partitions[0],
+                batch_size,
+            )?,
+            (None, None, None, None) => {
+                data_table.scan(self.projections.clone(), partitions[0], batch_size)?
+            }
+        };
 
-        // TODO: Pushdown projections, filters
-        let scans = data_table.scan(self.projections.clone(), partitions[0])?;
+        // Map runtime filters to columns in the projected output.
+        let runtime_filters: Vec<_> = self
+            .runtime_filters
+            .iter()
+            .filter_map(|filter| {
+                let idx = match &self.projections.column_indices {
+                    Some(indices) => indices.iter().position(|&idx| idx == filter.column)?,
+                    None => filter.column,
+                };
+                Some((idx, filter.filter.clone()))
+            })
+            .collect();
 
         let states = scans
             .into_iter()
-            .map(|scan| PartitionState::Scan(ScanPartitionState { scan, future: None }))
+            .map(|scan| {
+                PartitionState::Scan(
+                    ScanPartitionState::new(scan).with_runtime_filters(runtime_filters.clone()),
+                )
+            })
             .collect();
 
         Ok(ExecutionStates {
@@ -120,33 +321,7 @@ impl ExecutableOperator for PhysicalScan {
         _operator_state: &OperatorState,
     ) -> Result<PollPull> {
         match partition_state {
-            PartitionState::Scan(state) => {
-                if let Some(future) = &mut state.future {
-                    match future.poll_unpin(cx) {
-                        Poll::Ready(Ok(Some(batch))) => {
-                            state.future = None; // Future complete, next pull with create a new one.
-                            return Ok(PollPull::Computed(batch.into()));
-                        }
-                        Poll::Ready(Ok(None)) => return Ok(PollPull::Exhausted),
-                        Poll::Ready(Err(e)) => return Err(e),
-                        Poll::Pending => return Ok(PollPull::Pending),
-                    }
-                }
-
-                let mut future = state.scan.pull();
-                match future.poll_unpin(cx) {
-                    Poll::Ready(Ok(Some(batch))) => Ok(PollPull::Computed(batch.into())),
-                    Poll::Ready(Ok(None)) => Ok(PollPull::Exhausted),
-                    Poll::Ready(Err(e)) => Err(e),
-                    Poll::Pending => {
-                        // SAFETY: Scan lives on the partition state and
-                        // outlives this future.
-                        state.future = Some(unsafe { make_static(future) });
-                        Ok(PollPull::Pending)
-                    }
-                }
-            }
-
+            PartitionState::Scan(state) => state.poll_pull(cx),
             other => panic!("invalid partition state: {other:?}"),
         }
     }
@@ -154,7 +329,26 @@ impl ExecutableOperator for PhysicalScan {
 
 impl Explainable for PhysicalScan {
     fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
-        ExplainEntry::new("Scan").with_value("table", &self.table.name)
+        let mut ent = ExplainEntry::new("Scan").with_value("table", &self.table.name);
+        if !self.filters.is_empty() {
+            ent = ent.with_values("filters", &self.filters);
+        }
+        if !self.runtime_filters.is_empty() {
+            ent = ent.with_values("runtime_filters", &self.runtime_filters);
+        }
+        if let Some(sample) = &self.sample {
+            ent = ent.with_value("sample", sample);
+        }
+        if let Some(limit) = self.limit {
+            ent = ent.with_value("limit", limit);
+        }
+        if let Some(index_scan) = &self.index_scan {
+            ent = ent.with_value("index_scan", index_scan);
+        }
+        if let Some(aggregate) = &self.aggregate {
+            ent = ent.with_values("pushed_aggregates", &aggregate.aggregates);
+        }
+        ent
     }
 }
 
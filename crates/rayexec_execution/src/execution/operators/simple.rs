@@ -80,6 +80,7 @@ impl<S: StatelessOperation> ExecutableOperator for SimpleOperator<S> {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         Ok(ExecutionStates {
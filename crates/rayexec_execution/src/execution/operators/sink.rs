@@ -144,6 +144,7 @@ impl<S: SinkOperation> ExecutableOperator for SinkOperator<S> {
     fn create_states(
         &self,
         context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let partitions = partitions[0];
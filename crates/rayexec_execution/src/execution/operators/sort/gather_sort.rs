@@ -10,6 +10,7 @@ use super::util::sorted_batch::{PhysicallySortedBatch, SortedKeysIter};
 use crate::arrays::batch::Batch;
 use crate::database::DatabaseContext;
 use crate::execution::operators::sort::util::merger::IterState;
+use crate::execution::operators::util::resizer::DEFAULT_TARGET_BATCH_SIZE;
 use crate::execution::operators::{
     ExecutableOperator,
     ExecutionStates,
@@ -54,6 +55,9 @@ pub struct GatherSortPullPartitionState {
     input_buffers: InputBuffers,
 
     merge_state: PullMergeState,
+
+    /// Target size for output batches.
+    batch_size: usize,
 }
 
 #[derive(Debug)]
@@ -185,6 +189,7 @@ impl PhysicalGatherSort {
                 finished: (0..input_partitions).map(|_| false).collect(),
             },
             merge_state: PullMergeState::Initializing,
+            batch_size: DEFAULT_TARGET_BATCH_SIZE,
         }];
 
         (operator_state, push_states, pull_states)
@@ -195,6 +200,7 @@ impl ExecutableOperator for PhysicalGatherSort {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let input_partitions = partitions[0];
@@ -227,6 +233,7 @@ impl ExecutableOperator for PhysicalGatherSort {
                     finished: (0..input_partitions).map(|_| false).collect(),
                 },
                 merge_state: PullMergeState::Initializing,
+                batch_size,
             },
         )];
 
@@ -384,8 +391,7 @@ impl ExecutableOperator for PhysicalGatherSort {
                 // We loop to try to make as much progress with the merger using
                 // our local buffered batches as much as possible.
                 loop {
-                    // TODO: Configurable batch size.
-                    match merger.try_merge(1024)? {
+                    match merger.try_merge(state.batch_size)? {
                         MergeResult::Batch(batch) => return Ok(PollPull::Computed(batch.into())),
                         MergeResult::NeedsInput(input_idx) => {
                             let pushed = Self::try_push_input_batch_to_merger(
@@ -542,7 +548,14 @@ impl PhysicalGatherSort {
                     // global input_buffers.
                     for (idx, local_buf) in input_buffers.buffered.iter_mut().enumerate() {
                         if local_buf.is_none() {
-                            *local_buf = shared.batches[idx].take();
+                            if let Some(batch) = shared.batches[idx].take() {
+                                *local_buf = Some(batch);
+                                // Global state has room for another batch,
+                                // wake a pending waker to try to get more.
+                                if let Some(waker) = shared.push_wakers[idx].take() {
+                                    waker.wake();
+                                }
+                            }
                         }
                         input_buffers.finished[idx] = shared.finished[idx];
                     }
@@ -835,4 +848,70 @@ mod tests {
             .unwrap();
         assert_eq!(PollPull::Exhausted, poll_pull);
     }
+
+    #[test]
+    fn pull_wakes_pending_push() {
+        let operator = Arc::new(PhysicalGatherSort::new(vec![PhysicalSortExpression {
+            column: PhysicalColumnExpr { idx: 0 },
+            desc: true,
+            nulls_first: true,
+        }]));
+        let (operator_state, push_states, pull_states) = operator.create_states_orig(1);
+        let operator_state = Arc::new(OperatorState::GatherSort(operator_state));
+        let mut push_states: Vec<_> = push_states
+            .into_iter()
+            .map(PartitionState::GatherSortPush)
+            .collect();
+        let mut pull_states: Vec<_> = pull_states
+            .into_iter()
+            .map(PartitionState::GatherSortPull)
+            .collect();
+
+        let pull_cx = TestWakerContext::new();
+        let push_cx = TestWakerContext::new();
+
+        // Initialize the merger with the first batch.
+        let poll_push = push_cx
+            .poll_push(
+                &operator,
+                &mut push_states[0],
+                &operator_state,
+                make_i32_batch([8, 6]),
+            )
+            .unwrap();
+        assert_eq!(PollPush::Pushed, poll_push);
+        let poll_pull = pull_cx
+            .poll_pull(&operator, &mut pull_states[0], &operator_state)
+            .unwrap();
+        assert_eq!(PollPull::Pending, poll_pull);
+
+        // Fill up the global state, the next push has to wait.
+        let poll_push = push_cx
+            .poll_push(
+                &operator,
+                &mut push_states[0],
+                &operator_state,
+                make_i32_batch([5, 4]),
+            )
+            .unwrap();
+        assert_eq!(PollPush::Pushed, poll_push);
+        let poll_push = push_cx
+            .poll_push(
+                &operator,
+                &mut push_states[0],
+                &operator_state,
+                make_i32_batch([3, 2]),
+            )
+            .unwrap();
+        assert!(matches!(poll_push, PollPush::Pending(_)));
+        assert_eq!(0, push_cx.wake_count());
+
+        // Pulling moves the batch out of the global state, the push side
+        // should be woken up.
+        let poll_pull = pull_cx
+            .poll_pull(&operator, &mut pull_states[0], &operator_state)
+            .unwrap();
+        assert_eq!(PollPull::Pending, poll_pull);
+        assert_eq!(1, push_cx.wake_count());
+    }
 }
@@ -1,5 +1,6 @@
 pub mod gather_sort;
+pub mod parallel_merge;
 pub mod scatter_sort;
 pub mod top_k;
 
-mod util;
+pub(crate) mod util;
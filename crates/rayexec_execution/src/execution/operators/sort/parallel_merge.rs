@@ -0,0 +1,718 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::task::{Context, Waker};
+
+use parking_lot::Mutex;
+use rayexec_error::{RayexecError, Result};
+
+use super::util::merger::{IterState, KWayMerger, MergeResult};
+use super::util::sort_keys::SortKeysExtractor;
+use super::util::sorted_batch::{RangeKeysIter, RowReference};
+use crate::arrays::batch::Batch;
+use crate::arrays::row::encoding::{ComparableRow, ComparableRows};
+use crate::database::DatabaseContext;
+use crate::execution::operators::{
+    ExecutableOperator,
+    ExecutionStates,
+    InputOutputStates,
+    OperatorState,
+    PartitionState,
+    PollFinalize,
+    PollPull,
+    PollPush,
+};
+use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::expr::physical::PhysicalSortExpression;
+use crate::proto::DatabaseProtoConv;
+use crate::runtime::memory::MemoryReservation;
+
+/// Number of rows sampled from each sorted run when picking the keys that
+/// split the runs into ranges.
+const SAMPLES_PER_RUN: usize = 64;
+
+/// Partition state on the push side.
+#[derive(Debug)]
+pub struct ParallelMergePushPartitionState {
+    /// Index of this partition. Also the index of the range this partition
+    /// merges once all inputs are finished.
+    partition_idx: usize,
+    /// Extract the sort keys from a batch.
+    extractor: SortKeysExtractor,
+    /// Sorted batches pushed to this partition.
+    ///
+    /// Taken once this partition's input is finished.
+    run: Option<Vec<RunBatch>>,
+    /// Target size for merged batches.
+    batch_size: usize,
+    /// Memory reserved for the buffered batches.
+    reservation: MemoryReservation,
+}
+
+/// Partition state on the pull side.
+#[derive(Debug)]
+pub struct ParallelMergePullPartitionState {
+    /// Index of the next range to produce output for.
+    range_idx: usize,
+    /// Total number of ranges.
+    num_ranges: usize,
+    /// Merged batches for the current range.
+    batches: VecDeque<Batch>,
+}
+
+#[derive(Debug)]
+pub struct ParallelMergeOperatorState {
+    shared: Mutex<SharedState>,
+}
+
+#[derive(Debug)]
+struct SharedState {
+    /// Sorted runs from each input partition.
+    ///
+    /// Indexed by input partition idx.
+    runs: Vec<Vec<RunBatch>>,
+    /// Number of input partitions that haven't finished yet.
+    remaining: usize,
+    /// Runs split into ranges, set once all inputs are finished.
+    ranges: Option<Arc<MergeRanges>>,
+    /// Wakers for input partitions waiting on all other inputs to finish.
+    ///
+    /// Indexed by input partition idx.
+    finalize_wakers: Vec<Option<Waker>>,
+    /// Merged output for each range.
+    ///
+    /// Indexed by range idx.
+    merged: Vec<Option<Vec<Batch>>>,
+    /// Number of ranges that haven't been merged yet.
+    merging: usize,
+    /// Waker for the pull side waiting on a range to be merged.
+    pull_waker: Option<Waker>,
+}
+
+/// A batch in a sorted run along with its sort keys.
+#[derive(Debug)]
+struct RunBatch {
+    batch: Batch,
+    keys: Arc<ComparableRows>,
+}
+
+/// Position of a row in a sorted run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RunPosition {
+    batch_idx: usize,
+    row_idx: usize,
+}
+
+/// Sorted runs from every input, split into ranges of keys.
+///
+/// Every row in a range sorts before all rows in the next range, so ranges
+/// can be merged independently and concatenated.
+#[derive(Debug)]
+struct MergeRanges {
+    runs: Vec<Vec<RunBatch>>,
+    /// Start position in each run for each range, followed by the end
+    /// positions.
+    ///
+    /// Indexed by range idx, then run idx.
+    bounds: Vec<Vec<RunPosition>>,
+}
+
+impl MergeRanges {
+    /// Split the runs into `num_ranges` ranges.
+    ///
+    /// Keys are sampled from every run, and the ranges are split on evenly
+    /// spaced keys from the sorted samples. Rows with equal keys always end up
+    /// in the same range.
+    fn new(runs: Vec<Vec<RunBatch>>, num_ranges: usize) -> Self {
+        let mut samples = Vec::new();
+        for run in &runs {
+            let num_rows: usize = run.iter().map(|batch| batch.keys.num_rows()).sum();
+            if num_rows == 0 {
+                continue;
+            }
+            let step = num_rows.div_ceil(SAMPLES_PER_RUN);
+
+            let mut offset = 0;
+            for batch in run {
+                let mut row_idx = (step - offset % step) % step;
+                while row_idx < batch.keys.num_rows() {
+                    samples.push(RowReference {
+                        rows: batch.keys.clone(),
+                        row_idx,
+                    });
+                    row_idx += step;
+                }
+                offset += batch.keys.num_rows();
+            }
+        }
+        samples.sort();
+
+        let starts = vec![
+            RunPosition {
+                batch_idx: 0,
+                row_idx: 0,
+            };
+            runs.len()
+        ];
+        let ends: Vec<_> = runs
+            .iter()
+            .map(|run| RunPosition {
+                batch_idx: run.len(),
+                row_idx: 0,
+            })
+            .collect();
+
+        let mut bounds = Vec::with_capacity(num_ranges + 1);
+        bounds.push(starts);
+        for range_idx in 1..num_ranges {
+            match samples.get(range_idx * samples.len() / num_ranges) {
+                Some(splitter) => {
+                    let key = splitter.rows.row(splitter.row_idx).expect("row to exist");
+                    bounds.push(runs.iter().map(|run| lower_bound(run, key)).collect());
+                }
+                None => bounds.push(ends.clone()),
+            }
+        }
+        bounds.push(ends);
+
+        MergeRanges { runs, bounds }
+    }
+
+    /// Merge all rows in a range.
+    fn merge_range(&self, range_idx: usize, batch_size: usize) -> Result<Vec<Batch>> {
+        let starts = &self.bounds[range_idx];
+        let ends = &self.bounds[range_idx + 1];
+
+        let mut segments: Vec<_> = self
+            .runs
+            .iter()
+            .zip(starts.iter().zip(ends))
+            .map(|(run, (start, end))| run_segments(run, *start, *end))
+            .collect();
+
+        let inputs = segments
+            .iter_mut()
+            .map(|segments| match segments.pop_front() {
+                Some((batch, iter)) => (Some(batch), IterState::Iterator(iter)),
+                None => (None, IterState::Finished),
+            })
+            .collect();
+
+        let mut merger = KWayMerger::try_new(inputs)?;
+        let mut merged = Vec::new();
+        loop {
+            match merger.try_merge(batch_size)? {
+                MergeResult::Batch(batch) => merged.push(batch),
+                MergeResult::NeedsInput(input_idx) => match segments[input_idx].pop_front() {
+                    Some((batch, iter)) => merger.push_batch_for_input(input_idx, batch, iter)?,
+                    None => merger.input_finished(input_idx),
+                },
+                MergeResult::Exhausted => return Ok(merged),
+            }
+        }
+    }
+}
+
+/// Find the position of the first row in a run that's not less than `key`.
+fn lower_bound(run: &[RunBatch], key: ComparableRow<'_>) -> RunPosition {
+    let batch_idx =
+        run.partition_point(|batch| batch.keys.last().expect("batch to not be empty") < key);
+    if batch_idx == run.len() {
+        return RunPosition {
+            batch_idx,
+            row_idx: 0,
+        };
+    }
+
+    let keys = &run[batch_idx].keys;
+    let (mut low, mut high) = (0, keys.num_rows());
+    while low < high {
+        let mid = (low + high) / 2;
+        if keys.row(mid).expect("row to exist") < key {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    RunPosition {
+        batch_idx,
+        row_idx: low,
+    }
+}
+
+/// Get the non-empty batch segments of a run between two positions.
+fn run_segments(
+    run: &[RunBatch],
+    start: RunPosition,
+    end: RunPosition,
+) -> VecDeque<(Batch, RangeKeysIter)> {
+    let mut segments = VecDeque::new();
+    let end_batch = usize::min(end.batch_idx + 1, run.len());
+    for (batch_idx, batch) in run.iter().enumerate().take(end_batch).skip(start.batch_idx) {
+        let first = if batch_idx == start.batch_idx {
+            start.row_idx
+        } else {
+            0
+        };
+        let last = if batch_idx == end.batch_idx {
+            end.row_idx
+        } else {
+            batch.keys.num_rows()
+        };
+
+        if first < last {
+            segments.push_back((
+                batch.batch.clone(),
+                RangeKeysIter::new(batch.keys.clone(), first..last),
+            ));
+        }
+    }
+    segments
+}
+
+/// Merge sorted partitions into a single output partition, with each input
+/// partition merging a share of the rows.
+///
+/// Once all inputs are finished, the sorted runs from every input are split
+/// into one range of keys per input partition. Each input partition then
+/// merges its range in parallel with the others, and the pull side produces
+/// the merged ranges in order.
+#[derive(Debug)]
+pub struct PhysicalParallelMerge {
+    exprs: Vec<PhysicalSortExpression>,
+}
+
+impl PhysicalParallelMerge {
+    pub fn new(exprs: Vec<PhysicalSortExpression>) -> Self {
+        PhysicalParallelMerge { exprs }
+    }
+}
+
+impl ExecutableOperator for PhysicalParallelMerge {
+    fn create_states(
+        &self,
+        context: &DatabaseContext,
+        batch_size: usize,
+        partitions: Vec<usize>,
+    ) -> Result<ExecutionStates> {
+        let input_partitions = partitions[0];
+
+        let operator_state = OperatorState::ParallelMerge(ParallelMergeOperatorState {
+            shared: Mutex::new(SharedState {
+                runs: (0..input_partitions).map(|_| Vec::new()).collect(),
+                remaining: input_partitions,
+                ranges: None,
+                finalize_wakers: (0..input_partitions).map(|_| None).collect(),
+                merged: (0..input_partitions).map(|_| None).collect(),
+                merging: input_partitions,
+                pull_waker: None,
+            }),
+        });
+
+        let extractor = SortKeysExtractor::new(&self.exprs);
+        let push_states = (0..input_partitions)
+            .map(|idx| {
+                PartitionState::ParallelMergePush(ParallelMergePushPartitionState {
+                    partition_idx: idx,
+                    extractor: extractor.clone(),
+                    run: Some(Vec::new()),
+                    batch_size,
+                    reservation: context.memory_tracker().new_reservation(),
+                })
+            })
+            .collect();
+
+        let pull_states = vec![PartitionState::ParallelMergePull(
+            ParallelMergePullPartitionState {
+                range_idx: 0,
+                num_ranges: input_partitions,
+                batches: VecDeque::new(),
+            },
+        )];
+
+        Ok(ExecutionStates {
+            operator_state: Arc::new(operator_state),
+            partition_states: InputOutputStates::SeparateInputOutput {
+                push_states,
+                pull_states,
+            },
+        })
+    }
+
+    fn poll_push(
+        &self,
+        _cx: &mut Context,
+        partition_state: &mut PartitionState,
+        _operator_state: &OperatorState,
+        batch: Batch,
+    ) -> Result<PollPush> {
+        let state = match partition_state {
+            PartitionState::ParallelMergePush(state) => state,
+            other => panic!("invalid partition state: {other:?}"),
+        };
+
+        let run = state
+            .run
+            .as_mut()
+            .ok_or_else(|| RayexecError::new("Attempted to push to finished partition"))?;
+
+        if batch.num_rows() == 0 {
+            return Ok(PollPush::NeedsMore);
+        }
+
+        state.reservation.try_grow_for_batch(&batch)?;
+        let keys = state.extractor.sort_keys(&batch)?;
+        run.push(RunBatch {
+            batch,
+            keys: Arc::new(keys),
+        });
+
+        Ok(PollPush::NeedsMore)
+    }
+
+    fn poll_finalize_push(
+        &self,
+        cx: &mut Context,
+        partition_state: &mut PartitionState,
+        operator_state: &OperatorState,
+    ) -> Result<PollFinalize> {
+        let state = match partition_state {
+            PartitionState::ParallelMergePush(state) => state,
+            other => panic!("invalid partition state: {other:?}"),
+        };
+
+        let operator_state = match operator_state {
+            OperatorState::ParallelMerge(state) => state,
+            other => panic!("invalid operator state: {other:?}"),
+        };
+
+        let mut shared = operator_state.shared.lock();
+
+        if let Some(run) = state.run.take() {
+            shared.runs[state.partition_idx] = run;
+            shared.remaining -= 1;
+
+            // Last input to finish splits the runs for everyone.
+            if shared.remaining == 0 {
+                let runs = std::mem::take(&mut shared.runs);
+                let num_ranges = shared.merged.len();
+                shared.ranges = Some(Arc::new(MergeRanges::new(runs, num_ranges)));
+
+                for waker in shared.finalize_wakers.iter_mut() {
+                    if let Some(waker) = waker.take() {
+                        waker.wake();
+                    }
+                }
+            }
+        }
+
+        let ranges = match &shared.ranges {
+            Some(ranges) => ranges.clone(),
+            None => {
+                // Still waiting on other inputs.
+                shared.finalize_wakers[state.partition_idx] = Some(cx.waker().clone());
+                return Ok(PollFinalize::Pending);
+            }
+        };
+        std::mem::drop(shared);
+
+        let merged = ranges.merge_range(state.partition_idx, state.batch_size)?;
+        std::mem::drop(ranges);
+
+        let mut shared = operator_state.shared.lock();
+        shared.merged[state.partition_idx] = Some(merged);
+        shared.merging -= 1;
+        if shared.merging == 0 {
+            // Release the runs.
+            shared.ranges = None;
+        }
+
+        if let Some(waker) = shared.pull_waker.take() {
+            waker.wake();
+        }
+
+        Ok(PollFinalize::Finalized)
+    }
+
+    fn poll_pull(
+        &self,
+        cx: &mut Context,
+        partition_state: &mut PartitionState,
+        operator_state: &OperatorState,
+    ) -> Result<PollPull> {
+        let state = match partition_state {
+            PartitionState::ParallelMergePull(state) => state,
+            other => panic!("invalid partition state: {other:?}"),
+        };
+
+        let operator_state = match operator_state {
+            OperatorState::ParallelMerge(state) => state,
+            other => panic!("invalid operator state: {other:?}"),
+        };
+
+        loop {
+            if let Some(batch) = state.batches.pop_front() {
+                return Ok(PollPull::Computed(batch.into()));
+            }
+
+            if state.range_idx >= state.num_ranges {
+                return Ok(PollPull::Exhausted);
+            }
+
+            let mut shared = operator_state.shared.lock();
+            match shared.merged[state.range_idx].take() {
+                Some(batches) => {
+                    state.batches = batches.into();
+                    state.range_idx += 1;
+                }
+                None => {
+                    // Range still being merged.
+                    shared.pull_waker = Some(cx.waker().clone());
+                    return Ok(PollPull::Pending);
+                }
+            }
+        }
+    }
+}
+
+impl Explainable for PhysicalParallelMerge {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("ParallelMerge")
+    }
+}
+
+impl DatabaseProtoConv for PhysicalParallelMerge {
+    type ProtoType = rayexec_proto::generated::execution::PhysicalMergeSortedInputs;
+
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            exprs: self
+                .exprs
+                .iter()
+                .map(|expr| expr.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        Ok(Self {
+            exprs: proto
+                .exprs
+                .into_iter()
+                .map(|expr| DatabaseProtoConv::from_proto_ctx(expr, context))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::execution::operators::test_util::{
+        logical_value,
+        make_i32_batch,
+        test_database_context,
+        unwrap_poll_pull_batch,
+        TestWakerContext,
+    };
+    use crate::expr::physical::column_expr::PhysicalColumnExpr;
+
+    fn create_operator(
+        partitions: usize,
+    ) -> (
+        Arc<PhysicalParallelMerge>,
+        Arc<OperatorState>,
+        Vec<PartitionState>,
+        Vec<PartitionState>,
+    ) {
+        let operator = Arc::new(PhysicalParallelMerge::new(vec![PhysicalSortExpression {
+            column: PhysicalColumnExpr { idx: 0 },
+            desc: false,
+            nulls_first: false,
+        }]));
+        let states = operator
+            .create_states(&test_database_context(), 4, vec![partitions])
+            .unwrap();
+        match states.partition_states {
+            InputOutputStates::SeparateInputOutput {
+                push_states,
+                pull_states,
+            } => (operator, states.operator_state, push_states, pull_states),
+            other => panic!("unexpected states: {other:?}"),
+        }
+    }
+
+    fn pull_all(
+        operator: &Arc<PhysicalParallelMerge>,
+        operator_state: &OperatorState,
+        pull_state: &mut PartitionState,
+    ) -> Vec<Batch> {
+        let pull_cx = TestWakerContext::new();
+        let mut batches = Vec::new();
+        loop {
+            let poll = pull_cx
+                .poll_pull(operator, pull_state, operator_state)
+                .unwrap();
+            match poll {
+                PollPull::Exhausted => return batches,
+                poll => batches.push(unwrap_poll_pull_batch(poll)),
+            }
+        }
+    }
+
+    fn values(batches: &[Batch]) -> Vec<i32> {
+        batches
+            .iter()
+            .flat_map(|batch| {
+                (0..batch.num_rows()).map(|row| match logical_value(batch, 0, row) {
+                    ScalarValue::Int32(v) => v,
+                    other => panic!("unexpected value: {other:?}"),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_waits_for_all_inputs() {
+        let (operator, operator_state, mut push_states, mut pull_states) = create_operator(2);
+
+        let p0_cx = TestWakerContext::new();
+        let p1_cx = TestWakerContext::new();
+        let pull_cx = TestWakerContext::new();
+
+        for batch in [make_i32_batch([1, 3, 5]), make_i32_batch([7, 9])] {
+            p0_cx
+                .poll_push(&operator, &mut push_states[0], &operator_state, batch)
+                .unwrap();
+        }
+        p1_cx
+            .poll_push(
+                &operator,
+                &mut push_states[1],
+                &operator_state,
+                make_i32_batch([2, 4, 6, 8, 10]),
+            )
+            .unwrap();
+
+        // Nothing to pull yet.
+        let poll = pull_cx
+            .poll_pull(&operator, &mut pull_states[0], &operator_state)
+            .unwrap();
+        assert_eq!(PollPull::Pending, poll);
+
+        // First input waits for the second.
+        let poll = operator
+            .poll_finalize_push(&mut p0_cx.context(), &mut push_states[0], &operator_state)
+            .unwrap();
+        assert_eq!(PollFinalize::Pending, poll);
+
+        // Second input finishes, and merges its range.
+        let poll = operator
+            .poll_finalize_push(&mut p1_cx.context(), &mut push_states[1], &operator_state)
+            .unwrap();
+        assert_eq!(PollFinalize::Finalized, poll);
+        assert_eq!(1, p0_cx.wake_count());
+        assert_eq!(1, pull_cx.wake_count());
+
+        // First range still needs to be merged.
+        let poll = pull_cx
+            .poll_pull(&operator, &mut pull_states[0], &operator_state)
+            .unwrap();
+        assert_eq!(PollPull::Pending, poll);
+
+        let poll = operator
+            .poll_finalize_push(&mut p0_cx.context(), &mut push_states[0], &operator_state)
+            .unwrap();
+        assert_eq!(PollFinalize::Finalized, poll);
+
+        let batches = pull_all(&operator, &operator_state, &mut pull_states[0]);
+        assert_eq!((1..=10).collect::<Vec<_>>(), values(&batches));
+    }
+
+    #[test]
+    fn merge_many_partitions_with_duplicates() {
+        let (operator, operator_state, mut push_states, mut pull_states) = create_operator(4);
+
+        // Each partition gets a sorted run with lots of duplicate keys.
+        let mut expected = Vec::new();
+        for (idx, state) in push_states.iter_mut().enumerate() {
+            let cx = TestWakerContext::new();
+            let mut run: Vec<i32> = (0..200).map(|v| (v * (idx as i32 + 1)) % 37).collect();
+            run.sort();
+            expected.extend(run.iter().copied());
+
+            for chunk in run.chunks(30) {
+                cx.poll_push(
+                    &operator,
+                    state,
+                    &operator_state,
+                    make_i32_batch(chunk.iter().copied()),
+                )
+                .unwrap();
+            }
+        }
+        expected.sort();
+
+        // Finish in reverse, everyone but the last input has to wait.
+        let cxs: Vec<_> = (0..4).map(|_| TestWakerContext::new()).collect();
+        for idx in (0..4).rev() {
+            let poll = operator
+                .poll_finalize_push(
+                    &mut cxs[idx].context(),
+                    &mut push_states[idx],
+                    &operator_state,
+                )
+                .unwrap();
+            if idx == 0 {
+                assert_eq!(PollFinalize::Finalized, poll);
+            } else {
+                assert_eq!(PollFinalize::Pending, poll);
+            }
+        }
+        for idx in 1..4 {
+            assert_eq!(1, cxs[idx].wake_count());
+            let poll = operator
+                .poll_finalize_push(
+                    &mut cxs[idx].context(),
+                    &mut push_states[idx],
+                    &operator_state,
+                )
+                .unwrap();
+            assert_eq!(PollFinalize::Finalized, poll);
+        }
+
+        let batches = pull_all(&operator, &operator_state, &mut pull_states[0]);
+        assert!(batches.iter().all(|batch| batch.num_rows() <= 4));
+        assert_eq!(expected, values(&batches));
+    }
+
+    #[test]
+    fn merge_empty_inputs() {
+        let (operator, operator_state, mut push_states, mut pull_states) = create_operator(3);
+
+        let cx = TestWakerContext::new();
+        cx.poll_push(
+            &operator,
+            &mut push_states[1],
+            &operator_state,
+            make_i32_batch([4, 5, 6]),
+        )
+        .unwrap();
+
+        for idx in [0, 2, 1] {
+            operator
+                .poll_finalize_push(&mut cx.context(), &mut push_states[idx], &operator_state)
+                .unwrap();
+        }
+        for idx in [0, 2] {
+            let poll = operator
+                .poll_finalize_push(&mut cx.context(), &mut push_states[idx], &operator_state)
+                .unwrap();
+            assert_eq!(PollFinalize::Finalized, poll);
+        }
+
+        let batches = pull_all(&operator, &operator_state, &mut pull_states[0]);
+        assert_eq!(vec![4, 5, 6], values(&batches));
+    }
+}
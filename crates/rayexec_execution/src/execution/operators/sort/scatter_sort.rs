@@ -8,7 +8,6 @@ use super::util::sort_keys::SortKeysExtractor;
 use super::util::sorted_batch::{IndexSortedBatch, SortedIndicesIter};
 use crate::arrays::batch::Batch;
 use crate::database::DatabaseContext;
-use crate::execution::operators::util::resizer::DEFAULT_TARGET_BATCH_SIZE;
 use crate::execution::operators::{
     ExecutableOperator,
     ExecutionStates,
@@ -22,6 +21,7 @@ use crate::execution::operators::{
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 use crate::expr::physical::PhysicalSortExpression;
 use crate::proto::DatabaseProtoConv;
+use crate::runtime::memory::MemoryReservation;
 
 #[derive(Debug)]
 pub enum ScatterSortPartitionState {
@@ -42,12 +42,21 @@ pub struct ConsumingPartitionState {
     /// Waker on the pull side that tried to get a batch before we were done
     /// sorting this partition.
     pull_waker: Option<Waker>,
+    /// Target size for output batches.
+    batch_size: usize,
+    /// Memory reserved for the buffered batches.
+    reservation: MemoryReservation,
 }
 
 #[derive(Debug)]
 pub struct ProducingPartitionState {
     /// Merger for merging all batches in this partition.
     merger: KWayMerger<SortedIndicesIter>,
+    /// Target size for output batches.
+    batch_size: usize,
+    /// Memory reserved for the batches being merged. Released when the
+    /// partition state is dropped.
+    _reservation: MemoryReservation,
 }
 
 /// Physical operator for sorting batches within a partition stream.
@@ -65,7 +74,8 @@ impl PhysicalScatterSort {
 impl ExecutableOperator for PhysicalScatterSort {
     fn create_states(
         &self,
-        _context: &DatabaseContext,
+        context: &DatabaseContext,
+        batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let partitions = partitions[0];
@@ -78,6 +88,8 @@ impl ExecutableOperator for PhysicalScatterSort {
                         extractor: extractor.clone(),
                         batches: Vec::new(),
                         pull_waker: None,
+                        batch_size,
+                        reservation: context.memory_tracker().new_reservation(),
                     },
                 ))
             })
@@ -152,7 +164,11 @@ impl ExecutableOperator for PhysicalScatterSort {
                 }
 
                 // Update partition state to "producing" using the merger.
-                *state = ScatterSortPartitionState::Producing(ProducingPartitionState { merger });
+                *state = ScatterSortPartitionState::Producing(ProducingPartitionState {
+                    merger,
+                    batch_size: consuming_state.batch_size,
+                    _reservation: consuming_state.reservation.take(),
+                });
 
                 Ok(PollFinalize::Finalized)
             }
@@ -181,8 +197,7 @@ impl ExecutableOperator for PhysicalScatterSort {
             }
             ScatterSortPartitionState::Producing(state) => {
                 loop {
-                    // TODO: Configurable batch size.
-                    match state.merger.try_merge(DEFAULT_TARGET_BATCH_SIZE)? {
+                    match state.merger.try_merge(state.batch_size)? {
                         MergeResult::Batch(batch) => {
                             return Ok(PollPull::Computed(batch.into()));
                         }
@@ -209,6 +224,8 @@ impl PhysicalScatterSort {
         state: &mut ConsumingPartitionState,
         batch: Batch,
     ) -> Result<()> {
+        state.reservation.try_grow_for_batch(&batch)?;
+
         let keys = state.extractor.sort_keys(&batch)?;
 
         // Produce the indices that would result in a sorted batches. We
@@ -262,6 +279,8 @@ impl DatabaseProtoConv for PhysicalScatterSort {
 mod tests {
     use std::sync::Arc;
 
+    use rayexec_error::ErrorKind;
+
     use super::*;
     use crate::execution::operators::test_util::{
         make_i32_batch,
@@ -269,11 +288,15 @@ mod tests {
         unwrap_poll_pull_batch,
         TestWakerContext,
     };
+    use crate::execution::operators::util::resizer::DEFAULT_TARGET_BATCH_SIZE;
     use crate::expr::physical::column_expr::PhysicalColumnExpr;
+    use crate::runtime::memory::MemoryTracker;
 
     fn create_states(operator: &PhysicalScatterSort, partitions: usize) -> Vec<PartitionState> {
         let context = test_database_context();
-        let states = operator.create_states(&context, vec![partitions]).unwrap();
+        let states = operator
+            .create_states(&context, DEFAULT_TARGET_BATCH_SIZE, vec![partitions])
+            .unwrap();
 
         match states.partition_states {
             InputOutputStates::OneToOne { partition_states } => partition_states,
@@ -493,4 +516,51 @@ mod tests {
             .unwrap();
         assert_eq!(PollPull::Exhausted, poll_pull);
     }
+
+    #[test]
+    fn sort_exceeds_memory_limit() {
+        let mut context = test_database_context();
+        let tracker = Arc::new(MemoryTracker::new("test", Some(24)));
+        context.set_memory_tracker(tracker.clone());
+
+        let operator = Arc::new(PhysicalScatterSort::new(vec![PhysicalSortExpression {
+            column: PhysicalColumnExpr { idx: 0 },
+            desc: false,
+            nulls_first: true,
+        }]));
+        let operator_state = Arc::new(OperatorState::None);
+        let mut partition_states = match operator
+            .create_states(&context, DEFAULT_TARGET_BATCH_SIZE, vec![1])
+            .unwrap()
+            .partition_states
+        {
+            InputOutputStates::OneToOne { partition_states } => partition_states,
+            other => panic!("unexpected states: {other:?}"),
+        };
+
+        let push_cx = TestWakerContext::new();
+        push_cx
+            .poll_push(
+                &operator,
+                &mut partition_states[0],
+                &operator_state,
+                make_i32_batch([1, 2, 3, 4]),
+            )
+            .unwrap();
+        assert_eq!(16, tracker.reserved());
+
+        let err = push_cx
+            .poll_push(
+                &operator,
+                &mut partition_states[0],
+                &operator_state,
+                make_i32_batch([5, 6, 7]),
+            )
+            .unwrap_err();
+        assert_eq!(ErrorKind::ResourceExhausted, err.kind());
+
+        // Reservation released once the query's states go away.
+        std::mem::drop(partition_states);
+        assert_eq!(0, tracker.reserved());
+    }
 }
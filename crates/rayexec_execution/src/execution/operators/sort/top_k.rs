@@ -28,6 +28,7 @@ impl ExecutableOperator for PhysicalTopK {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         _partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         unimplemented!()
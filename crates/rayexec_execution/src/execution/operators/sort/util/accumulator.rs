@@ -1,7 +1,7 @@
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::Result;
 
 use crate::arrays::batch::Batch;
-use crate::arrays::executor::scalar::interleave;
+use crate::arrays::compute::interleave::interleave_batches;
 
 /// Tracks the state per input into the merge.
 #[derive(Debug, Clone)]
@@ -62,24 +62,10 @@ impl IndicesAccumulator {
             return Ok(None);
         }
 
-        // If we have indices, we should have at least one batch.
-        let num_cols = self.num_columns()?;
-
-        let merged = (0..num_cols)
-            .map(|col_idx| {
-                let cols: Vec<_> = self
-                    .batches
-                    .iter()
-                    .map(|(_, batch)| batch.column(col_idx).expect("column to exist"))
-                    .collect();
-
-                interleave(&cols, &self.indices)
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let batches: Vec<_> = self.batches.iter().map(|(_, batch)| batch).collect();
+        let batch = interleave_batches(&batches, &self.indices)?;
         self.indices.clear();
 
-        let batch = Batch::try_new(merged)?;
-
         // Drops batches that are no longer reachable (won't be contributing to
         // the output).
         let mut retained = 0;
@@ -102,14 +88,4 @@ impl IndicesAccumulator {
 
         Ok(Some(batch))
     }
-
-    /// Return the number of columns in the ouput.
-    ///
-    /// Errors if there's no buffered batches.
-    fn num_columns(&self) -> Result<usize> {
-        match self.batches.first() {
-            Some((_, b)) => Ok(b.num_columns()),
-            None => Err(RayexecError::new("Cannot get number of columns")),
-        }
-    }
 }
@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::fmt;
+use std::ops::Range;
 use std::sync::Arc;
 
 use crate::arrays::batch::Batch;
@@ -57,6 +58,46 @@ impl Iterator for SortedKeysIter {
     }
 }
 
+/// Iterator over a range of rows in a physically sorted batch.
+#[derive(Debug)]
+pub struct RangeKeysIter {
+    row_idx: usize,
+    end: usize,
+    keys: Arc<ComparableRows>,
+}
+
+impl RangeKeysIter {
+    pub fn new(keys: Arc<ComparableRows>, range: Range<usize>) -> Self {
+        RangeKeysIter {
+            row_idx: range.start,
+            end: range.end,
+            keys,
+        }
+    }
+}
+
+impl Iterator for RangeKeysIter {
+    type Item = RowReference;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row_idx >= self.end {
+            return None;
+        }
+        let row_idx = self.row_idx;
+        self.row_idx += 1;
+
+        Some(RowReference {
+            rows: self.keys.clone(),
+            row_idx,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.row_idx;
+        (len, Some(len))
+    }
+}
+
 /// A logically sorted batch.
 ///
 /// This doens't store a sorted batch itself, but instead stores row indices
@@ -88,6 +88,7 @@ impl<S: SourceOperation> ExecutableOperator for SourceOperator<S> {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let states = self
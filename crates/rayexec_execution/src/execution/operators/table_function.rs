@@ -6,6 +6,7 @@ use futures::future::BoxFuture;
 use futures::FutureExt;
 use rayexec_error::{RayexecError, Result};
 
+use super::util::batch_width::estimated_row_width;
 use super::util::futures::make_static;
 use super::{
     ExecutableOperator,
@@ -21,8 +22,9 @@ use crate::arrays::batch::Batch;
 use crate::database::DatabaseContext;
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 use crate::functions::table::{PlannedTableFunction, TableFunctionImpl};
+use crate::logical::scan_filter::ScanFilter;
 use crate::proto::DatabaseProtoConv;
-use crate::storage::table_storage::{DataTableScan, Projections};
+use crate::storage::table_storage::{DataTableScan, Projections, TableAggregate, TableSample};
 
 pub struct TableFunctionPartitionState {
     scan_state: Box<dyn DataTableScan>,
@@ -41,6 +43,10 @@ impl fmt::Debug for TableFunctionPartitionState {
 pub struct PhysicalTableFunction {
     function: PlannedTableFunction,
     projections: Projections,
+    sample: Option<TableSample>,
+    limit: Option<usize>,
+    aggregate: Option<TableAggregate>,
+    filters: Vec<ScanFilter>,
 }
 
 impl PhysicalTableFunction {
@@ -48,6 +54,50 @@ impl PhysicalTableFunction {
         PhysicalTableFunction {
             function,
             projections,
+            sample: None,
+            limit: None,
+            aggregate: None,
+            filters: Vec::new(),
+        }
+    }
+
+    /// Sample the function output instead of reading all rows.
+    pub fn with_sample(mut self, sample: Option<TableSample>) -> Self {
+        self.sample = sample;
+        self
+    }
+
+    /// Hint to the function that each partition only needs to produce up to
+    /// `limit` rows.
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Have the table compute an aggregate instead of returning its rows.
+    pub fn with_aggregate(mut self, aggregate: Option<TableAggregate>) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// Filters the function may use to skip producing rows.
+    ///
+    /// Filtering still happens above the function, these are only hints.
+    pub fn with_filters(mut self, filters: Vec<ScanFilter>) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    /// Estimated width in bytes of the rows produced by this function.
+    pub fn estimated_row_width(&self) -> usize {
+        let fields = &self.function.schema.fields;
+        match &self.projections.column_indices {
+            Some(indices) => estimated_row_width(
+                indices
+                    .iter()
+                    .filter_map(|&idx| fields.get(idx).map(|f| &f.datatype)),
+            ),
+            None => estimated_row_width(fields.iter().map(|f| &f.datatype)),
         }
     }
 }
@@ -56,6 +106,7 @@ impl ExecutableOperator for PhysicalTableFunction {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let scan_func = match &self.function.function_impl {
@@ -68,8 +119,29 @@ impl ExecutableOperator for PhysicalTableFunction {
             }
         };
 
-        // TODO: Pushdown  filters
-        let scans = scan_func.scan(self.projections.clone(), partitions[0])?;
+        let scans = match (&self.aggregate, &self.sample, self.limit) {
+            (Some(aggregate), _, _) => {
+                scan_func.scan_aggregate(aggregate, partitions[0], batch_size)?
+            }
+            (None, Some(sample), _) => scan_func.scan_sample(
+                self.projections.clone(),
+                sample,
+                partitions[0],
+                batch_size,
+            )?,
+            (None, None, Some(limit)) => {
+                scan_func.scan_limit(self.projections.clone(), limit, partitions[0], batch_size)?
+            }
+            (None, None, None) if !self.filters.is_empty() => scan_func.scan_filtered(
+                self.projections.clone(),
+                &self.filters,
+                partitions[0],
+                batch_size,
+            )?,
+            (None, None, None) => {
+                scan_func.scan(self.projections.clone(), partitions[0], batch_size)?
+            }
+        };
 
         let states = scans
             .into_iter()
@@ -149,7 +221,20 @@ impl ExecutableOperator for PhysicalTableFunction {
 
 impl Explainable for PhysicalTableFunction {
     fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
-        ExplainEntry::new("TableFunction")
+        let mut ent = ExplainEntry::new("TableFunction");
+        if let Some(sample) = &self.sample {
+            ent = ent.with_value("sample", sample);
+        }
+        if let Some(limit) = self.limit {
+            ent = ent.with_value("limit", limit);
+        }
+        if let Some(aggregate) = &self.aggregate {
+            ent = ent.with_values("pushed_aggregates", &aggregate.aggregates);
+        }
+        if !self.filters.is_empty() {
+            ent = ent.with_values("filters", &self.filters);
+        }
+        ent
     }
 }
 
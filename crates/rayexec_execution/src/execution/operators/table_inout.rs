@@ -42,12 +42,13 @@ impl ExecutableOperator for PhysicalTableInOut {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let partitions = partitions[0];
 
         let states = match &self.function.function_impl {
-            TableFunctionImpl::InOut(function) => function.create_states(partitions)?,
+            TableFunctionImpl::InOut(function) => function.create_states(partitions, batch_size)?,
             _ => {
                 return Err(RayexecError::new(format!(
                     "'{}' is not a table in/out function",
@@ -7,6 +7,7 @@ use rayexec_error::{RayexecError, Result};
 
 use super::hash_aggregate::distinct::DistinctGroupedStates;
 use super::hash_aggregate::hash_table::GroupAddress;
+use super::hash_aggregate::ordered::OrderedGroupedStates;
 use super::{
     ExecutableOperator,
     ExecutionStates,
@@ -17,6 +18,7 @@ use super::{
     PollPush,
 };
 use crate::arrays::batch::Batch;
+use crate::arrays::row::encoding::ComparableColumn;
 use crate::database::DatabaseContext;
 use crate::execution::operators::InputOutputStates;
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
@@ -24,6 +26,7 @@ use crate::expr::physical::PhysicalAggregateExpression;
 use crate::functions::aggregate::states::AggregateGroupStates;
 use crate::functions::aggregate::ChunkGroupAddressIter;
 use crate::proto::DatabaseProtoConv;
+use crate::runtime::memory::MemoryTracker;
 
 #[derive(Debug)]
 pub enum UngroupedAggregatePartitionState {
@@ -83,12 +86,29 @@ impl PhysicalUngroupedAggregate {
         PhysicalUngroupedAggregate { aggregates }
     }
 
-    fn create_agg_states_with_single_group(&self) -> Result<Vec<Box<dyn AggregateGroupStates>>> {
+    fn create_agg_states_with_single_group(
+        &self,
+        memory: &Arc<MemoryTracker>,
+    ) -> Result<Vec<Box<dyn AggregateGroupStates>>> {
         let mut states = Vec::with_capacity(self.aggregates.len());
         for agg in &self.aggregates {
-            let mut state = if agg.is_distinct {
+            let mut state: Box<dyn AggregateGroupStates> = if agg.is_distinct {
                 Box::new(DistinctGroupedStates::new(
                     agg.function.function_impl.new_states(),
+                    memory.new_reservation(),
+                ))
+            } else if !agg.order_by.is_empty() {
+                Box::new(OrderedGroupedStates::new(
+                    agg.function.function_impl.new_states(),
+                    agg.columns.len(),
+                    agg.order_by
+                        .iter()
+                        .map(|order_by| ComparableColumn {
+                            desc: order_by.desc,
+                            nulls_first: order_by.nulls_first,
+                        })
+                        .collect(),
+                    memory.new_reservation(),
                 ))
             } else {
                 agg.function.function_impl.new_states()
@@ -104,14 +124,16 @@ impl PhysicalUngroupedAggregate {
 impl ExecutableOperator for PhysicalUngroupedAggregate {
     fn create_states(
         &self,
-        _context: &DatabaseContext,
+        context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let num_partitions = partitions[0];
+        let memory = context.memory_tracker();
 
         let inner = OperatorStateInner {
             remaining: num_partitions,
-            agg_states: self.create_agg_states_with_single_group()?,
+            agg_states: self.create_agg_states_with_single_group(memory)?,
             pull_wakers: (0..num_partitions).map(|_| None).collect(),
         };
         let operator_state = UngroupedAggregateOperatorState {
@@ -123,7 +145,7 @@ impl ExecutableOperator for PhysicalUngroupedAggregate {
                 Ok(PartitionState::UngroupedAggregate(
                     UngroupedAggregatePartitionState::Aggregating {
                         partition_idx: idx,
-                        agg_states: self.create_agg_states_with_single_group()?,
+                        agg_states: self.create_agg_states_with_single_group(memory)?,
                     },
                 ))
             })
@@ -161,6 +183,7 @@ impl ExecutableOperator for PhysicalUngroupedAggregate {
                     let cols: Vec<_> = agg
                         .columns
                         .iter()
+                        .chain(agg.order_by.iter().map(|order_by| &order_by.column))
                         .map(|expr| batch.column(expr.idx).expect("column to exist"))
                         .collect();
 
@@ -237,7 +260,13 @@ impl ExecutableOperator for PhysicalUngroupedAggregate {
                         .map(|s| s.finalize())
                         .collect::<Result<Vec<_>>>()?;
 
-                    let batch = Batch::try_new(arrays)?;
+                    // Always a single output row, even without any
+                    // aggregates (e.g. `SELECT 1 FROM t HAVING true`).
+                    let batch = if arrays.is_empty() {
+                        Batch::empty_with_num_rows(1)
+                    } else {
+                        Batch::try_new(arrays)?
+                    };
 
                     *state = UngroupedAggregatePartitionState::Producing {
                         partition_idx: *partition_idx,
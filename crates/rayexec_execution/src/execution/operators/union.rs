@@ -71,6 +71,7 @@ impl ExecutableOperator for PhysicalUnion {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let num_partitions = partitions[0];
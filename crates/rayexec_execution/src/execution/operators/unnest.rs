@@ -85,6 +85,7 @@ impl ExecutableOperator for PhysicalUnnest {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let partitions = partitions[0];
@@ -0,0 +1,111 @@
+//! Estimate row widths for picking batch sizes from a byte budget.
+//!
+//! Estimates are based only on data types. Variable length values and lists
+//! use fixed guesses for their average size, so the estimates are rough, but
+//! good enough to keep wide scans from producing very large batches.
+
+use crate::arrays::datatype::DataType;
+use crate::config::execution::MAX_BATCH_SIZE;
+
+/// Size of the inline metadata for a single varlen value.
+const VARLEN_METADATA_BYTES: usize = 16;
+
+/// Estimated average number of heap bytes for a single varlen value.
+const ESTIMATED_VARLEN_BYTES: usize = 32;
+
+/// Size of the metadata (offset and length) for a single list value.
+const LIST_METADATA_BYTES: usize = 8;
+
+/// Estimated average number of elements in a list value.
+const ESTIMATED_LIST_LEN: usize = 4;
+
+/// Smallest batch size we'll pick from a byte budget.
+///
+/// Very small batches have a high per-batch overhead, so even extremely wide
+/// rows get at least this many rows per batch.
+pub const MIN_ADAPTIVE_BATCH_SIZE: usize = 64;
+
+/// Estimate the number of bytes a single value of this type takes up in a
+/// batch.
+pub fn estimated_value_width(datatype: &DataType) -> usize {
+    match datatype {
+        DataType::Null => 0,
+        DataType::Boolean | DataType::Int8 | DataType::UInt8 => 1,
+        DataType::Int16 | DataType::UInt16 | DataType::Float16 => 2,
+        DataType::Int32 | DataType::UInt32 | DataType::Float32 | DataType::Date32 => 4,
+        DataType::Int64
+        | DataType::UInt64
+        | DataType::Float64
+        | DataType::Date64
+        | DataType::Decimal64(_)
+        | DataType::Timestamp(_) => 8,
+        DataType::Int128 | DataType::UInt128 | DataType::Decimal128(_) | DataType::Interval => 16,
+        DataType::Utf8 | DataType::Binary | DataType::Json | DataType::Geometry => {
+            VARLEN_METADATA_BYTES + ESTIMATED_VARLEN_BYTES
+        }
+        DataType::List(meta) => {
+            LIST_METADATA_BYTES + ESTIMATED_LIST_LEN * estimated_value_width(&meta.datatype)
+        }
+        DataType::Struct(meta) => meta
+            .fields
+            .iter()
+            .map(|f| estimated_value_width(&f.datatype))
+            .sum(),
+    }
+}
+
+/// Estimate the number of bytes a single row with the given types takes up in
+/// a batch.
+///
+/// Always at least 1.
+pub fn estimated_row_width<'a>(types: impl IntoIterator<Item = &'a DataType>) -> usize {
+    types
+        .into_iter()
+        .map(estimated_value_width)
+        .sum::<usize>()
+        .max(1)
+}
+
+/// Compute the number of rows per batch that keeps batches with rows of
+/// `row_width` bytes close to `target_bytes`.
+pub fn batch_size_for_row_width(target_bytes: usize, row_width: usize) -> usize {
+    (target_bytes / row_width.max(1)).clamp(MIN_ADAPTIVE_BATCH_SIZE, MAX_BATCH_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::datatype::ListTypeMeta;
+
+    #[test]
+    fn wide_rows_get_fewer_rows() {
+        let narrow = estimated_row_width(&[DataType::Int32]);
+        let wide = estimated_row_width(&[
+            DataType::Int64,
+            DataType::Utf8,
+            DataType::Utf8,
+            DataType::List(ListTypeMeta::new(DataType::Utf8)),
+        ]);
+        assert_eq!(4, narrow);
+        assert!(wide > narrow);
+
+        let target = 1024 * 1024;
+        let narrow_rows = batch_size_for_row_width(target, narrow);
+        let wide_rows = batch_size_for_row_width(target, wide);
+        assert_eq!(262144, narrow_rows);
+        assert!(wide_rows < narrow_rows);
+    }
+
+    #[test]
+    fn batch_size_clamped() {
+        assert_eq!(MIN_ADAPTIVE_BATCH_SIZE, batch_size_for_row_width(16, 1024));
+        assert_eq!(MAX_BATCH_SIZE, batch_size_for_row_width(usize::MAX, 1));
+        assert_eq!(MAX_BATCH_SIZE, batch_size_for_row_width(usize::MAX, 0));
+    }
+
+    #[test]
+    fn empty_row_width() {
+        assert_eq!(1, estimated_row_width(&[]));
+        assert_eq!(1, estimated_row_width(&[DataType::Null]));
+    }
+}
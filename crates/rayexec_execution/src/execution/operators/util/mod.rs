@@ -1,4 +1,5 @@
 pub mod barrier;
+pub mod batch_width;
 pub mod broadcast;
 pub mod futures;
 pub mod hash;
@@ -6,8 +6,10 @@ use crate::arrays::batch::Batch;
 use crate::arrays::selection::SelectionVector;
 use crate::execution::computed_batch::ComputedBatches;
 
-// TODO: Shouldn't be a const, should be determined when we create the
-// executable plans.
+/// Default target batch size.
+///
+/// Operators receive the actual target size (from the `batch_size` setting)
+/// when creating their states.
 pub const DEFAULT_TARGET_BATCH_SIZE: usize = 4096;
 
 /// Resize input batches to produce output batches of a target size.
@@ -38,6 +38,7 @@ impl ExecutableOperator for PhysicalValues {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         let num_partitions = partitions[0];
@@ -22,6 +22,7 @@ impl ExecutableOperator for PhysicalWindow {
     fn create_states(
         &self,
         _context: &DatabaseContext,
+        _batch_size: usize,
         _partitions: Vec<usize>,
     ) -> Result<ExecutionStates> {
         unimplemented!()
@@ -54,8 +54,21 @@ impl<'a> ExplainFormatter<'a> {
     pub fn format_intermediate_groups(
         &self,
         groups: &[(&str, &IntermediatePipelineGroup)],
+        target_partitions: Option<usize>,
     ) -> Result<String> {
-        let node = ExplainNode::from_intermediate_groups(self.bind_context, groups, self.config);
+        let node = ExplainNode::from_intermediate_groups(
+            self.bind_context,
+            groups,
+            target_partitions,
+            self.config,
+        );
+        self.format(&node)
+    }
+
+    /// Format the tables, scopes, and correlations in the bind context along
+    /// with the database objects that were resolved for the query.
+    pub fn format_bindings(&self, resolved: &[ExplainEntry]) -> Result<String> {
+        let node = ExplainNode::from_bind_context(self.bind_context, resolved)?;
         self.format(&node)
     }
 
@@ -113,12 +126,15 @@ impl ExplainNode {
     fn from_intermediate_groups(
         bind_context: &BindContext,
         groups: &[(&str, &IntermediatePipelineGroup)],
+        target_partitions: Option<usize>,
         config: ExplainConfig,
     ) -> ExplainNode {
         let entry = ExplainEntry::new("IntermediatePipelineGroups");
         let children = groups
             .iter()
-            .map(|(label, group)| Self::from_intermediate_group(bind_context, group, label, config))
+            .map(|(label, group)| {
+                Self::from_intermediate_group(bind_context, group, label, target_partitions, config)
+            })
             .collect();
 
         ExplainNode { entry, children }
@@ -128,6 +144,7 @@ impl ExplainNode {
         bind_context: &BindContext,
         group: &IntermediatePipelineGroup,
         label: &str,
+        target_partitions: Option<usize>,
         config: ExplainConfig,
     ) -> ExplainNode {
         let entry = ExplainEntry::new(format!("IntermediatePipelineGroup {label}"));
@@ -135,7 +152,9 @@ impl ExplainNode {
         let children = group
             .pipelines
             .values()
-            .map(|pipeline| Self::from_intermedate_pipeline(bind_context, pipeline, config))
+            .map(|pipeline| {
+                Self::from_intermedate_pipeline(bind_context, pipeline, target_partitions, config)
+            })
             .collect();
 
         ExplainNode { entry, children }
@@ -144,6 +163,7 @@ impl ExplainNode {
     fn from_intermedate_pipeline(
         bind_context: &BindContext,
         pipeline: &IntermediatePipeline,
+        target_partitions: Option<usize>,
         config: ExplainConfig,
     ) -> ExplainNode {
         let _ = bind_context;
@@ -168,6 +188,7 @@ impl ExplainNode {
             PipelineSink::OtherGroup {
                 stream_id,
                 partitions,
+                ref hash_columns,
             } => entry.with_named_map(
                 "Sink",
                 "OtherGroup",
@@ -175,6 +196,7 @@ impl ExplainNode {
                     ("query_id", stream_id.query_id.to_string()),
                     ("stream_id", stream_id.stream_id.to_string()),
                     ("partitions", partitions.to_string()),
+                    ("hash_columns", format!("{hash_columns:?}")),
                 ],
             ),
             PipelineSink::Materialization { mat_ref } => entry.with_named_map(
@@ -208,6 +230,40 @@ impl ExplainNode {
             ),
         };
 
+        // Annotate with how parallel this pipeline will actually be compared to
+        // the configured target. Pipelines end up less parallel than the
+        // target when an operator requires a specific number of partitions
+        // (e.g. a gather sort requiring a single partition).
+        if let Some(target) = target_partitions {
+            let source = pipeline.source_partitions(target);
+            let operators = pipeline.operator_partitions(target);
+            let actual = operators
+                .iter()
+                .copied()
+                .min()
+                .unwrap_or(source)
+                .min(source);
+
+            let mut repartitions = 0;
+            let mut prev = source;
+            for &partitions in &operators {
+                if partitions != prev {
+                    repartitions += 1;
+                }
+                prev = partitions;
+            }
+
+            entry = entry.with_named_map(
+                "Parallelism",
+                if actual < target { "Reduced" } else { "Full" },
+                [
+                    ("estimated", target),
+                    ("actual", actual),
+                    ("repartitions", repartitions),
+                ],
+            );
+        }
+
         let children = pipeline
             .operators
             .iter()
@@ -220,6 +276,96 @@ impl ExplainNode {
         ExplainNode { entry, children }
     }
 
+    fn from_bind_context(
+        bind_context: &BindContext,
+        resolved: &[ExplainEntry],
+    ) -> Result<ExplainNode> {
+        let resolved = ExplainNode {
+            entry: ExplainEntry::new("ResolvedObjects"),
+            children: resolved.iter().cloned().map(ExplainNode::leaf).collect(),
+        };
+
+        let tables = ExplainNode {
+            entry: ExplainEntry::new("Tables"),
+            children: bind_context
+                .get_table_list()
+                .iter()
+                .map(|table| {
+                    let mut entry = ExplainEntry::new(format!("Table {}", table.reference));
+                    if let Some(alias) = &table.alias {
+                        entry = entry.with_value("alias", alias);
+                    }
+                    entry = entry.with_values(
+                        "columns",
+                        table
+                            .column_names
+                            .iter()
+                            .zip(&table.column_types)
+                            .map(|(name, datatype)| format!("{name} {datatype}")),
+                    );
+                    ExplainNode::leaf(entry)
+                })
+                .collect(),
+        };
+
+        let mut scopes = Vec::new();
+        for scope in bind_context.iter_scope_refs() {
+            let mut entry = ExplainEntry::new(format!("Scope {}", scope.context_idx));
+            if let Some(parent) = bind_context.get_parent_ref(scope)? {
+                entry = entry.with_value("parent", parent.context_idx);
+            }
+            entry = entry.with_values(
+                "tables",
+                bind_context
+                    .iter_tables_in_scope(scope)?
+                    .map(|table| table.reference),
+            );
+
+            let correlated = bind_context.correlated_columns(scope)?;
+            if !correlated.is_empty() {
+                let correlated = correlated
+                    .iter()
+                    .map(|col| {
+                        let (name, datatype) = bind_context.get_column(col.table, col.col_idx)?;
+                        Ok(format!(
+                            "{name} {datatype} ({}.{}, outer scope {})",
+                            col.table, col.col_idx, col.outer.context_idx
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                entry = entry.with_values("correlated_columns", correlated);
+            }
+
+            let using = bind_context.get_using_columns(scope)?;
+            if !using.is_empty() {
+                entry = entry.with_values(
+                    "using_columns",
+                    using
+                        .iter()
+                        .map(|col| format!("{} ({}.{})", col.column, col.table_ref, col.col_idx)),
+                );
+            }
+
+            scopes.push(ExplainNode::leaf(entry));
+        }
+        let scopes = ExplainNode {
+            entry: ExplainEntry::new("Scopes"),
+            children: scopes,
+        };
+
+        Ok(ExplainNode {
+            entry: ExplainEntry::new("Bindings"),
+            children: vec![resolved, tables, scopes],
+        })
+    }
+
+    fn leaf(entry: ExplainEntry) -> ExplainNode {
+        ExplainNode {
+            entry,
+            children: Vec::new(),
+        }
+    }
+
     fn walk_logical_plan(
         bind_context: &BindContext,
         plan: &LogicalOperator,
@@ -235,17 +381,24 @@ impl ExplainNode {
             LogicalOperator::SetOp(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::Empty(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::Limit(n) => (n.explain_entry(config), &n.children),
+            LogicalOperator::Sample(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::Order(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::SetVar(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::ResetVar(n) => (n.explain_entry(config), &n.children),
+            LogicalOperator::Transaction(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::ShowVar(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::AttachDatabase(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::DetachDatabase(n) => (n.explain_entry(config), &n.children),
+            LogicalOperator::CreateSecret(n) => (n.explain_entry(config), &n.children),
+            LogicalOperator::DropSecret(n) => (n.explain_entry(config), &n.children),
+            LogicalOperator::AlterTable(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::Drop(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::Insert(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::CreateSchema(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::CreateTable(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::CreateView(n) => (n.explain_entry(config), &n.children),
+            LogicalOperator::CreateFunction(n) => (n.explain_entry(config), &n.children),
+            LogicalOperator::CreateMacro(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::Describe(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::Explain(n) => (n.explain_entry(config), &n.children),
             LogicalOperator::CopyTo(n) => (n.explain_entry(config), &n.children),
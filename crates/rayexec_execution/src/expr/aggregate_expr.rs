@@ -7,6 +7,7 @@ use crate::arrays::datatype::DataType;
 use crate::explain::context_display::{ContextDisplay, ContextDisplayMode, ContextDisplayWrapper};
 use crate::functions::aggregate::PlannedAggregateFunction;
 use crate::logical::binder::bind_context::BindContext;
+use crate::logical::binder::bind_query::bind_modifier::BoundOrderByExpr;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AggregateExpr {
@@ -16,6 +17,10 @@ pub struct AggregateExpr {
     pub filter: Option<Box<Expression>>,
     /// If the inputs should be deduplicated.
     pub distinct: bool,
+    /// Order in which inputs should be fed to the aggregate.
+    ///
+    /// Empty if the aggregate doesn't depend on input order.
+    pub order_by: Vec<BoundOrderByExpr>,
 }
 
 impl AggregateExpr {
@@ -38,10 +43,28 @@ impl ContextDisplay for AggregateExpr {
             .map(|e| ContextDisplayWrapper::with_mode(e, mode).to_string())
             .collect::<Vec<_>>()
             .join(", ");
-        if self.distinct {
-            write!(f, "(DISTINCT {})", inputs)?;
+        let distinct = if self.distinct { "DISTINCT " } else { "" };
+        if self.order_by.is_empty() {
+            write!(f, "({distinct}{inputs})")?;
         } else {
-            write!(f, "({})", inputs)?;
+            let order_by = self
+                .order_by
+                .iter()
+                .map(|order_by| {
+                    format!(
+                        "{} {} {}",
+                        ContextDisplayWrapper::with_mode(&order_by.expr, mode),
+                        if order_by.desc { "DESC" } else { "ASC" },
+                        if order_by.nulls_first {
+                            "NULLS FIRST"
+                        } else {
+                            "NULLS LAST"
+                        },
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(f, "({distinct}{inputs} ORDER BY {order_by})")?;
         }
 
         if let Some(filter) = self.filter.as_ref() {
@@ -0,0 +1,76 @@
+use std::fmt;
+
+use rayexec_error::{RayexecError, Result};
+
+use super::Expression;
+use crate::arrays::datatype::DataType;
+use crate::explain::context_display::{ContextDisplay, ContextDisplayMode, ContextDisplayWrapper};
+use crate::logical::binder::table_list::TableList;
+
+/// Conditional expressions that only evaluate inputs when needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConditionalOperator {
+    /// Returns the first non-null input.
+    Coalesce,
+    /// Returns NULL if both inputs are equal, otherwise the first input.
+    NullIf,
+    /// Returns the largest non-null input.
+    Greatest,
+    /// Returns the smallest non-null input.
+    Least,
+}
+
+impl ConditionalOperator {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Coalesce => "COALESCE",
+            Self::NullIf => "NULLIF",
+            Self::Greatest => "GREATEST",
+            Self::Least => "LEAST",
+        }
+    }
+}
+
+impl fmt::Display for ConditionalOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A conditional expression.
+///
+/// All inputs have already been cast to the same type by the binder.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConditionalExpr {
+    pub op: ConditionalOperator,
+    pub inputs: Vec<Expression>,
+}
+
+impl ConditionalExpr {
+    pub fn datatype(&self, table_list: &TableList) -> Result<DataType> {
+        match self.inputs.first() {
+            Some(input) => input.datatype(table_list),
+            None => Err(RayexecError::new(format!(
+                "{} requires at least one input",
+                self.op
+            ))),
+        }
+    }
+}
+
+impl ContextDisplay for ConditionalExpr {
+    fn fmt_using_context(
+        &self,
+        mode: ContextDisplayMode,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}(", self.op)?;
+        for (idx, input) in self.inputs.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", ContextDisplayWrapper::with_mode(input, mode))?;
+        }
+        write!(f, ")")
+    }
+}
@@ -5,6 +5,7 @@ pub mod case_expr;
 pub mod cast_expr;
 pub mod column_expr;
 pub mod comparison_expr;
+pub mod conditional_expr;
 pub mod conjunction_expr;
 pub mod grouping_set_expr;
 pub mod is_expr;
@@ -27,6 +28,7 @@ use case_expr::CaseExpr;
 use cast_expr::CastExpr;
 use column_expr::ColumnExpr;
 use comparison_expr::{ComparisonExpr, ComparisonOperator};
+use conditional_expr::ConditionalExpr;
 use conjunction_expr::{ConjunctionExpr, ConjunctionOperator};
 use grouping_set_expr::GroupingSetExpr;
 use is_expr::IsExpr;
@@ -41,8 +43,10 @@ use window_expr::WindowExpr;
 use crate::arrays::datatype::DataType;
 use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
 use crate::explain::context_display::{ContextDisplay, ContextDisplayMode};
-use crate::functions::scalar::{FunctionVolatility, ScalarFunction};
+use crate::functions::scalar::ScalarFunction;
+use crate::functions::FunctionVolatility;
 use crate::logical::binder::table_list::{TableList, TableRef};
+use crate::logical::walk::Recursion;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Expression {
@@ -53,6 +57,7 @@ pub enum Expression {
     Cast(CastExpr),
     Column(ColumnExpr),
     Comparison(ComparisonExpr),
+    Conditional(ConditionalExpr),
     Conjunction(ConjunctionExpr),
     Is(IsExpr),
     Literal(LiteralExpr),
@@ -78,6 +83,7 @@ impl Expression {
             Self::Cast(expr) => expr.to.clone(),
             Self::Column(expr) => expr.datatype(table_list)?,
             Self::Comparison(_) => DataType::Boolean,
+            Self::Conditional(expr) => expr.datatype(table_list)?,
             Self::Conjunction(_) => DataType::Boolean,
             Self::Is(_) => DataType::Boolean,
             Self::Literal(expr) => expr.literal.datatype(),
@@ -102,6 +108,9 @@ impl Expression {
                 if let Some(filter) = agg.filter.as_mut() {
                     func(filter)?;
                 }
+                for order_by in &mut agg.order_by {
+                    func(&mut order_by.expr)?;
+                }
             }
             Self::Arith(arith) => {
                 func(&mut arith.left)?;
@@ -129,6 +138,11 @@ impl Expression {
                 func(&mut comp.left)?;
                 func(&mut comp.right)?;
             }
+            Self::Conditional(cond) => {
+                for input in &mut cond.inputs {
+                    func(input)?;
+                }
+            }
             Self::Conjunction(conj) => {
                 for child in &mut conj.expressions {
                     func(child)?;
@@ -176,6 +190,9 @@ impl Expression {
                 if let Some(filter) = agg.filter.as_ref() {
                     func(filter)?;
                 }
+                for order_by in &agg.order_by {
+                    func(&order_by.expr)?;
+                }
             }
             Self::Arith(arith) => {
                 func(&arith.left)?;
@@ -203,6 +220,11 @@ impl Expression {
                 func(&comp.left)?;
                 func(&comp.right)?;
             }
+            Self::Conditional(cond) => {
+                for input in &cond.inputs {
+                    func(input)?;
+                }
+            }
             Self::Conjunction(conj) => {
                 for child in &conj.expressions {
                     func(child)?;
@@ -238,6 +260,85 @@ impl Expression {
         Ok(())
     }
 
+    /// Walk this expression depth first.
+    ///
+    /// `pre` is called on each expression on the way down, and `post` on the
+    /// way up. See [`Recursion`] for how `pre` controls the walk.
+    pub fn walk<F1, F2>(&self, pre: &mut F1, post: &mut F2) -> Result<()>
+    where
+        F1: FnMut(&Expression) -> Result<Recursion>,
+        F2: FnMut(&Expression) -> Result<()>,
+    {
+        self.walk_inner(pre, post)?;
+        Ok(())
+    }
+
+    fn walk_inner<F1, F2>(&self, pre: &mut F1, post: &mut F2) -> Result<Recursion>
+    where
+        F1: FnMut(&Expression) -> Result<Recursion>,
+        F2: FnMut(&Expression) -> Result<()>,
+    {
+        match pre(self)? {
+            Recursion::Stop => return Ok(Recursion::Stop),
+            Recursion::SkipChildren => (),
+            Recursion::Continue => {
+                let mut recursion = Recursion::Continue;
+                self.for_each_child(&mut |child| {
+                    if recursion != Recursion::Stop {
+                        recursion = child.walk_inner(pre, post)?;
+                    }
+                    Ok(())
+                })?;
+                if recursion == Recursion::Stop {
+                    return Ok(Recursion::Stop);
+                }
+            }
+        }
+
+        post(self)?;
+        Ok(Recursion::Continue)
+    }
+
+    /// Walk this expression depth first, allowing expressions to be rewritten
+    /// in place.
+    ///
+    /// If `pre` replaces an expression, the walk continues with the children
+    /// of the replacement.
+    pub fn walk_mut<F1, F2>(&mut self, pre: &mut F1, post: &mut F2) -> Result<()>
+    where
+        F1: FnMut(&mut Expression) -> Result<Recursion>,
+        F2: FnMut(&mut Expression) -> Result<()>,
+    {
+        self.walk_mut_inner(pre, post)?;
+        Ok(())
+    }
+
+    fn walk_mut_inner<F1, F2>(&mut self, pre: &mut F1, post: &mut F2) -> Result<Recursion>
+    where
+        F1: FnMut(&mut Expression) -> Result<Recursion>,
+        F2: FnMut(&mut Expression) -> Result<()>,
+    {
+        match pre(self)? {
+            Recursion::Stop => return Ok(Recursion::Stop),
+            Recursion::SkipChildren => (),
+            Recursion::Continue => {
+                let mut recursion = Recursion::Continue;
+                self.for_each_child_mut(&mut |child| {
+                    if recursion != Recursion::Stop {
+                        recursion = child.walk_mut_inner(pre, post)?;
+                    }
+                    Ok(())
+                })?;
+                if recursion == Recursion::Stop {
+                    return Ok(Recursion::Stop);
+                }
+            }
+        }
+
+        post(self)?;
+        Ok(Recursion::Continue)
+    }
+
     /// Replace this expression using a replacement function.
     pub fn replace_with<F>(&mut self, replace_fn: F)
     where
@@ -290,6 +391,24 @@ impl Expression {
         }
     }
 
+    pub fn contains_aggregate(&self) -> bool {
+        match self {
+            Self::Aggregate(_) => true,
+            _ => {
+                let mut has_aggregate = false;
+                self.for_each_child(&mut |expr| {
+                    if has_aggregate {
+                        return Ok(());
+                    }
+                    has_aggregate = has_aggregate || expr.contains_aggregate();
+                    Ok(())
+                })
+                .expect("aggregate check to not fail");
+                has_aggregate
+            }
+        }
+    }
+
     pub fn contains_window(&self) -> bool {
         match self {
             Self::Window(_) => true,
@@ -308,6 +427,30 @@ impl Expression {
         }
     }
 
+    /// Checks if this expression calls a volatile function.
+    pub fn contains_volatile(&self) -> bool {
+        self.volatility() == FunctionVolatility::Volatile
+    }
+
+    /// Get the volatility of the most volatile function called in this
+    /// expression.
+    ///
+    /// Expressions not calling any functions are immutable.
+    pub fn volatility(&self) -> FunctionVolatility {
+        let mut volatility = match self {
+            Self::ScalarFunction(f) => f.function.function.volatility(),
+            _ => FunctionVolatility::Immutable,
+        };
+        self.for_each_child(&mut |expr| {
+            if volatility != FunctionVolatility::Volatile {
+                volatility = volatility.max(expr.volatility());
+            }
+            Ok(())
+        })
+        .expect("volatility check to not fail");
+        volatility
+    }
+
     /// Checks if this expression can be folded into a constant.
     pub fn is_const_foldable(&self) -> bool {
         // Encountering any column means we can't fold.
@@ -350,8 +493,12 @@ impl Expression {
             Self::Aggregate(_) => false,
             Self::Window(_) => false,
             Self::Subquery(_) => false, // Subquery shouldn't be in the plan anyways once this gets called.
+            // Generators produce a variable number of rows, and grouping sets
+            // depend on the grouping being executed.
+            Self::Unnest(_) => false,
+            Self::GroupingSet(_) => false,
             Self::ScalarFunction(f)
-                if f.function.function.volatility() == FunctionVolatility::Volatile =>
+                if f.function.function.volatility() != FunctionVolatility::Immutable =>
             {
                 false
             }
@@ -570,6 +717,7 @@ impl ContextDisplay for Expression {
             Self::Cast(expr) => expr.fmt_using_context(mode, f),
             Self::Column(expr) => expr.fmt_using_context(mode, f),
             Self::Comparison(expr) => expr.fmt_using_context(mode, f),
+            Self::Conditional(expr) => expr.fmt_using_context(mode, f),
             Self::Conjunction(expr) => expr.fmt_using_context(mode, f),
             Self::Is(expr) => expr.fmt_using_context(mode, f),
             Self::Literal(expr) => expr.fmt_using_context(mode, f),
@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::fmt;
 use std::sync::Arc;
 
-use rayexec_error::Result;
+use rayexec_error::{OptionExt, Result};
 
 use super::PhysicalScalarExpression;
 use crate::arrays::array::Array;
@@ -10,6 +10,8 @@ use crate::arrays::batch::Batch;
 use crate::arrays::bitmap::Bitmap;
 use crate::arrays::executor::scalar::{interleave, SelectExecutor};
 use crate::arrays::selection::SelectionVector;
+use crate::database::DatabaseContext;
+use crate::proto::DatabaseProtoConv;
 
 #[derive(Debug, Clone)]
 pub struct PhysicalWhenThen {
@@ -113,6 +115,53 @@ impl fmt::Display for PhysicalCaseExpr {
     }
 }
 
+impl DatabaseProtoConv for PhysicalWhenThen {
+    type ProtoType = rayexec_proto::generated::physical_expr::PhysicalWhenThen;
+
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            when: Some(self.when.to_proto_ctx(context)?),
+            then: Some(self.then.to_proto_ctx(context)?),
+        })
+    }
+
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        Ok(Self {
+            when: DatabaseProtoConv::from_proto_ctx(proto.when.required("when")?, context)?,
+            then: DatabaseProtoConv::from_proto_ctx(proto.then.required("then")?, context)?,
+        })
+    }
+}
+
+impl DatabaseProtoConv for PhysicalCaseExpr {
+    type ProtoType = rayexec_proto::generated::physical_expr::PhysicalCaseExpr;
+
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            cases: self
+                .cases
+                .iter()
+                .map(|case| case.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+            else_expr: Some(Box::new(self.else_expr.to_proto_ctx(context)?)),
+        })
+    }
+
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        Ok(Self {
+            cases: proto
+                .cases
+                .into_iter()
+                .map(|case| DatabaseProtoConv::from_proto_ctx(case, context))
+                .collect::<Result<Vec<_>>>()?,
+            else_expr: Box::new(DatabaseProtoConv::from_proto_ctx(
+                *proto.else_expr.required("else_expr")?,
+                context,
+            )?),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
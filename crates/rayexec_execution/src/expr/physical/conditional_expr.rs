@@ -0,0 +1,354 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use fmtutil::IntoDisplayableSlice;
+use rayexec_error::{OptionExt, RayexecError, Result};
+use rayexec_proto::ProtoConv;
+
+use super::PhysicalScalarExpression;
+use crate::arrays::array::Array;
+use crate::arrays::batch::Batch;
+use crate::arrays::bitmap::Bitmap;
+use crate::arrays::datatype::DataType;
+use crate::arrays::executor::physical_type::PhysicalBool;
+use crate::arrays::executor::scalar::{interleave, UnaryExecutor};
+use crate::arrays::selection::SelectionVector;
+use crate::database::DatabaseContext;
+use crate::expr::conditional_expr::ConditionalOperator;
+use crate::functions::proto::{decode_planned_scalar_function, encode_planned_scalar_function};
+use crate::functions::scalar::PlannedScalarFunction;
+use crate::proto::DatabaseProtoConv;
+
+#[derive(Debug, Clone)]
+pub struct PhysicalConditionalExpr {
+    pub op: ConditionalOperator,
+    pub inputs: Vec<PhysicalScalarExpression>,
+    /// Output type, all inputs produce arrays of this type.
+    pub datatype: DataType,
+    /// Comparison used to pick between inputs.
+    ///
+    /// '=' for NULLIF, '>' for GREATEST, '<' for LEAST. Called with two arrays
+    /// of `datatype`.
+    ///
+    /// None for COALESCE, or if all inputs are untyped NULLs.
+    pub compare: Option<PlannedScalarFunction>,
+}
+
+impl PhysicalConditionalExpr {
+    pub fn eval<'a>(&self, batch: &'a Batch) -> Result<Cow<'a, Array>> {
+        match self.op {
+            ConditionalOperator::Coalesce => self.eval_coalesce(batch),
+            ConditionalOperator::NullIf => self.eval_nullif(batch),
+            ConditionalOperator::Greatest | ConditionalOperator::Least => {
+                self.eval_greatest_least(batch)
+            }
+        }
+    }
+
+    /// Evaluate each input only for the rows that are still NULL after
+    /// evaluating the previous inputs.
+    fn eval_coalesce<'a>(&self, batch: &'a Batch) -> Result<Cow<'a, Array>> {
+        let mut arrays = Vec::new();
+        // All rows start out pointing to a null value that's pushed to the end
+        // of `arrays` if needed.
+        let mut indices: Vec<(usize, usize)> = vec![(usize::MAX, 0); batch.num_rows()];
+
+        // True bits are rows that haven't produced a non-null value yet.
+        let mut remaining = Bitmap::new_with_all_true(batch.num_rows());
+
+        for (input_idx, input) in self.inputs.iter().enumerate() {
+            if input_idx == 0 {
+                let output = input.eval(batch)?;
+                if output.validity().is_none() {
+                    // Every row is non-null, nothing else to evaluate.
+                    return Ok(output);
+                }
+                let output = output.into_owned();
+                for (row_idx, index) in indices.iter_mut().enumerate() {
+                    if output.is_valid(row_idx).unwrap_or(false) {
+                        *index = (0, row_idx);
+                        remaining.set_unchecked(row_idx, false);
+                    }
+                }
+                arrays.push(output);
+                continue;
+            }
+
+            if remaining.count_trues() == 0 {
+                break;
+            }
+
+            let selection = Arc::new(SelectionVector::from_iter(remaining.index_iter()));
+            let selected_batch = batch.select(selection.clone());
+            let output = input.eval(&selected_batch)?.into_owned();
+
+            let array_idx = arrays.len();
+            for (array_row_idx, output_row_idx) in selection.iter_locations().enumerate() {
+                if output.is_valid(array_row_idx).unwrap_or(false) {
+                    indices[output_row_idx] = (array_idx, array_row_idx);
+                    remaining.set_unchecked(output_row_idx, false);
+                }
+            }
+            arrays.push(output);
+        }
+
+        if remaining.count_trues() != 0 {
+            let null_idx = arrays.len();
+            arrays.push(Array::new_typed_null_array(self.datatype.clone(), 1)?);
+            for (array_idx, _) in indices.iter_mut() {
+                if *array_idx == usize::MAX {
+                    *array_idx = null_idx;
+                }
+            }
+        }
+
+        let refs: Vec<_> = arrays.iter().collect();
+        Ok(Cow::Owned(interleave(&refs, &indices)?))
+    }
+
+    /// Evaluate both inputs, nulling out rows where they're equal.
+    fn eval_nullif<'a>(&self, batch: &'a Batch) -> Result<Cow<'a, Array>> {
+        let (left, right) = match self.inputs.as_slice() {
+            [left, right] => (left.eval(batch)?, right.eval(batch)?),
+            _ => return Err(RayexecError::new("NULLIF requires exactly two inputs")),
+        };
+        let compare = match self.compare.as_ref() {
+            Some(compare) => compare,
+            None => return Ok(left),
+        };
+        let equal = compare
+            .function_impl
+            .execute(&[left.as_ref(), right.as_ref()])?;
+
+        let mut indices: Vec<(usize, usize)> = (0..batch.num_rows()).map(|idx| (0, idx)).collect();
+        let mut any_equal = false;
+        UnaryExecutor::for_each::<PhysicalBool, _>(&equal, |idx, equal| {
+            if equal == Some(true) {
+                indices[idx] = (1, 0);
+                any_equal = true;
+            }
+        })?;
+
+        if !any_equal {
+            return Ok(left);
+        }
+
+        let nulls = Array::new_typed_null_array(self.datatype.clone(), 1)?;
+        Ok(Cow::Owned(interleave(&[left.as_ref(), &nulls], &indices)?))
+    }
+
+    /// Evaluate all inputs, picking the largest (or smallest) non-null value
+    /// for each row.
+    fn eval_greatest_least<'a>(&self, batch: &'a Batch) -> Result<Cow<'a, Array>> {
+        let mut inputs = self.inputs.iter();
+        let mut current = match inputs.next() {
+            Some(input) => input.eval(batch)?,
+            None => return Err(RayexecError::new(format!("{} requires inputs", self.op))),
+        };
+        let compare = match self.compare.as_ref() {
+            Some(compare) => compare,
+            None => return Ok(current),
+        };
+
+        for input in inputs {
+            let next = input.eval(batch)?;
+            // True if 'next' should replace the current value.
+            let replace = compare
+                .function_impl
+                .execute(&[next.as_ref(), current.as_ref()])?;
+
+            let mut indices: Vec<(usize, usize)> =
+                (0..batch.num_rows()).map(|idx| (0, idx)).collect();
+            let mut any_replaced = false;
+            UnaryExecutor::for_each::<PhysicalBool, _>(&replace, |idx, replace| {
+                let replace = match replace {
+                    Some(replace) => replace,
+                    // One side is null, only replace if the current value is
+                    // the null one.
+                    None => {
+                        !current.is_valid(idx).unwrap_or(false)
+                            && next.is_valid(idx).unwrap_or(false)
+                    }
+                };
+                if replace {
+                    indices[idx] = (1, idx);
+                    any_replaced = true;
+                }
+            })?;
+
+            if any_replaced {
+                current = Cow::Owned(interleave(&[current.as_ref(), next.as_ref()], &indices)?);
+            }
+        }
+
+        Ok(current)
+    }
+}
+
+impl fmt::Display for PhysicalConditionalExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.op, self.inputs.display_as_list())
+    }
+}
+
+impl ProtoConv for ConditionalOperator {
+    type ProtoType = rayexec_proto::generated::physical_expr::ConditionalOperator;
+
+    fn to_proto(&self) -> Result<Self::ProtoType> {
+        Ok(match self {
+            Self::Coalesce => Self::ProtoType::Coalesce,
+            Self::NullIf => Self::ProtoType::Nullif,
+            Self::Greatest => Self::ProtoType::Greatest,
+            Self::Least => Self::ProtoType::Least,
+        })
+    }
+
+    fn from_proto(proto: Self::ProtoType) -> Result<Self> {
+        Ok(match proto {
+            Self::ProtoType::InvalidConditionalOperator => {
+                return Err(RayexecError::new("invalid conditional operator"))
+            }
+            Self::ProtoType::Coalesce => Self::Coalesce,
+            Self::ProtoType::Nullif => Self::NullIf,
+            Self::ProtoType::Greatest => Self::Greatest,
+            Self::ProtoType::Least => Self::Least,
+        })
+    }
+}
+
+impl DatabaseProtoConv for PhysicalConditionalExpr {
+    type ProtoType = rayexec_proto::generated::physical_expr::PhysicalConditionalExpr;
+
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        let compare = self
+            .compare
+            .as_ref()
+            .map(|compare| {
+                encode_planned_scalar_function(
+                    compare,
+                    &[self.datatype.clone(), self.datatype.clone()],
+                )
+            })
+            .transpose()?;
+
+        Ok(Self::ProtoType {
+            op: self.op.to_proto()? as i32,
+            inputs: self
+                .inputs
+                .iter()
+                .map(|input| input.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+            datatype: Some(self.datatype.to_proto()?),
+            compare,
+        })
+    }
+
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        let op = ConditionalOperator::from_proto(proto.op())?;
+        let compare = proto
+            .compare
+            .map(|compare| decode_planned_scalar_function(compare, context))
+            .transpose()?
+            .map(|(compare, _)| compare);
+
+        Ok(Self {
+            op,
+            inputs: proto
+                .inputs
+                .into_iter()
+                .map(|input| DatabaseProtoConv::from_proto_ctx(input, context))
+                .collect::<Result<Vec<_>>>()?,
+            datatype: DataType::from_proto(proto.datatype.required("datatype")?)?,
+            compare,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::expr::conditional_expr::ConditionalExpr;
+    use crate::expr::physical::planner::PhysicalExpressionPlanner;
+    use crate::expr::{self, Expression};
+    use crate::logical::binder::table_list::TableList;
+
+    fn plan_conditional(op: ConditionalOperator, num_cols: usize) -> PhysicalScalarExpression {
+        let mut table_list = TableList::empty();
+        let table_ref = table_list
+            .push_table(
+                None,
+                vec![DataType::Int32; num_cols],
+                (0..num_cols).map(|idx| format!("c{idx}")).collect(),
+            )
+            .unwrap();
+
+        let expr = Expression::Conditional(ConditionalExpr {
+            op,
+            inputs: (0..num_cols)
+                .map(|idx| expr::col_ref(table_ref, idx))
+                .collect(),
+        });
+
+        let planner = PhysicalExpressionPlanner::new(&table_list);
+        planner.plan_scalar(&[table_ref], &expr).unwrap()
+    }
+
+    #[test]
+    fn coalesce_picks_first_non_null() {
+        let batch = Batch::try_new([
+            Array::from_iter([Some(1), None, None, None]),
+            Array::from_iter([Some(10), Some(20), None, None]),
+            Array::from_iter([Some(100), Some(200), Some(300), None]),
+        ])
+        .unwrap();
+
+        let physical = plan_conditional(ConditionalOperator::Coalesce, 3);
+        let got = physical.eval(&batch).unwrap();
+
+        assert_eq!(ScalarValue::Int32(1), got.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Int32(20), got.logical_value(1).unwrap());
+        assert_eq!(ScalarValue::Int32(300), got.logical_value(2).unwrap());
+        assert_eq!(ScalarValue::Null, got.logical_value(3).unwrap());
+    }
+
+    #[test]
+    fn nullif_nulls_equal_rows() {
+        let batch = Batch::try_new([
+            Array::from_iter([Some(1), Some(2), None]),
+            Array::from_iter([Some(1), Some(3), Some(4)]),
+        ])
+        .unwrap();
+
+        let physical = plan_conditional(ConditionalOperator::NullIf, 2);
+        let got = physical.eval(&batch).unwrap();
+
+        assert_eq!(ScalarValue::Null, got.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Int32(2), got.logical_value(1).unwrap());
+        assert_eq!(ScalarValue::Null, got.logical_value(2).unwrap());
+    }
+
+    #[test]
+    fn greatest_least_ignore_nulls() {
+        let batch = Batch::try_new([
+            Array::from_iter([Some(1), None, Some(5), None]),
+            Array::from_iter([Some(3), Some(2), None, None]),
+        ])
+        .unwrap();
+
+        let greatest = plan_conditional(ConditionalOperator::Greatest, 2);
+        let got = greatest.eval(&batch).unwrap();
+        assert_eq!(ScalarValue::Int32(3), got.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Int32(2), got.logical_value(1).unwrap());
+        assert_eq!(ScalarValue::Int32(5), got.logical_value(2).unwrap());
+        assert_eq!(ScalarValue::Null, got.logical_value(3).unwrap());
+
+        let least = plan_conditional(ConditionalOperator::Least, 2);
+        let got = least.eval(&batch).unwrap();
+        assert_eq!(ScalarValue::Int32(1), got.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Int32(2), got.logical_value(1).unwrap());
+        assert_eq!(ScalarValue::Int32(5), got.logical_value(2).unwrap());
+        assert_eq!(ScalarValue::Null, got.logical_value(3).unwrap());
+    }
+}
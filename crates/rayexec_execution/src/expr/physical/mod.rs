@@ -3,6 +3,7 @@ pub mod planner;
 pub mod case_expr;
 pub mod cast_expr;
 pub mod column_expr;
+pub mod conditional_expr;
 pub mod literal_expr;
 pub mod scalar_function_expr;
 
@@ -12,16 +13,22 @@ use std::fmt;
 use case_expr::PhysicalCaseExpr;
 use cast_expr::PhysicalCastExpr;
 use column_expr::PhysicalColumnExpr;
+use conditional_expr::PhysicalConditionalExpr;
 use literal_expr::PhysicalLiteralExpr;
-use rayexec_error::{not_implemented, OptionExt, Result};
+use rayexec_error::{OptionExt, Result};
 use scalar_function_expr::PhysicalScalarFunctionExpr;
 
 use crate::arrays::array::Array;
 use crate::arrays::batch::Batch;
+use crate::arrays::datatype::DataType;
 use crate::arrays::executor::scalar::SelectExecutor;
 use crate::arrays::selection::SelectionVector;
 use crate::database::DatabaseContext;
 use crate::functions::aggregate::PlannedAggregateFunction;
+use crate::functions::proto::{
+    decode_planned_aggregate_function,
+    encode_planned_aggregate_function,
+};
 use crate::proto::DatabaseProtoConv;
 
 #[derive(Debug, Clone)]
@@ -29,6 +36,7 @@ pub enum PhysicalScalarExpression {
     Case(PhysicalCaseExpr),
     Cast(PhysicalCastExpr),
     Column(PhysicalColumnExpr),
+    Conditional(PhysicalConditionalExpr),
     Literal(PhysicalLiteralExpr),
     ScalarFunction(PhysicalScalarFunctionExpr),
 }
@@ -39,6 +47,7 @@ impl PhysicalScalarExpression {
             Self::Case(e) => e.eval(batch),
             Self::Cast(e) => e.eval(batch),
             Self::Column(e) => e.eval(batch),
+            Self::Conditional(e) => e.eval(batch),
             Self::Literal(e) => e.eval(batch),
             Self::ScalarFunction(e) => e.eval(batch),
         }
@@ -64,6 +73,7 @@ impl fmt::Display for PhysicalScalarExpression {
             Self::Case(expr) => expr.fmt(f),
             Self::Cast(expr) => expr.fmt(f),
             Self::Column(expr) => expr.fmt(f),
+            Self::Conditional(expr) => expr.fmt(f),
             Self::Literal(expr) => expr.fmt(f),
             Self::ScalarFunction(expr) => expr.fmt(f),
         }
@@ -77,9 +87,10 @@ impl DatabaseProtoConv for PhysicalScalarExpression {
         use rayexec_proto::generated::physical_expr::physical_scalar_expression::Value;
 
         let value = match self {
-            Self::Case(_) => not_implemented!("proto encode CASE"),
+            Self::Case(case) => Value::Case(Box::new(case.to_proto_ctx(context)?)),
             Self::Cast(cast) => Value::Cast(Box::new(cast.to_proto_ctx(context)?)),
             Self::Column(cast) => Value::Column(cast.to_proto_ctx(context)?),
+            Self::Conditional(cond) => Value::Conditional(cond.to_proto_ctx(context)?),
             Self::Literal(cast) => Value::Literal(cast.to_proto_ctx(context)?),
             Self::ScalarFunction(cast) => Value::Function(cast.to_proto_ctx(context)?),
         };
@@ -101,6 +112,10 @@ impl DatabaseProtoConv for PhysicalScalarExpression {
             Value::Function(proto) => {
                 Self::ScalarFunction(DatabaseProtoConv::from_proto_ctx(proto, context)?)
             }
+            Value::Case(proto) => Self::Case(DatabaseProtoConv::from_proto_ctx(*proto, context)?),
+            Value::Conditional(proto) => {
+                Self::Conditional(DatabaseProtoConv::from_proto_ctx(proto, context)?)
+            }
         })
     }
 }
@@ -111,49 +126,68 @@ pub struct PhysicalAggregateExpression {
     pub function: PlannedAggregateFunction,
     /// Column expressions we're aggregating on.
     pub columns: Vec<PhysicalColumnExpr>,
+    /// Datatypes of the inputs to the function.
+    ///
+    /// Needed for planning the function again when deserializing.
+    pub input_types: Vec<DataType>,
     /// If inputs are distinct.
     pub is_distinct: bool,
+    /// Order in which inputs should be provided to the aggregate.
+    ///
+    /// Sort columns are separate from the input columns.
+    pub order_by: Vec<PhysicalSortExpression>,
     // TODO: Filter
 }
 
 impl PhysicalAggregateExpression {
     pub fn contains_column_idx(&self, column: usize) -> bool {
         self.columns.iter().any(|expr| expr.idx == column)
+            || self.order_by.iter().any(|expr| expr.column.idx == column)
     }
 }
 
 impl DatabaseProtoConv for PhysicalAggregateExpression {
     type ProtoType = rayexec_proto::generated::physical_expr::PhysicalAggregateExpression;
 
-    fn to_proto_ctx(&self, _context: &DatabaseContext) -> Result<Self::ProtoType> {
-        unimplemented!()
-        // Ok(Self::ProtoType {
-        //     function: Some(self.function.to_proto_ctx(context)?),
-        //     columns: self
-        //         .columns
-        //         .iter()
-        //         .map(|c| c.to_proto_ctx(context))
-        //         .collect::<Result<Vec<_>>>()?,
-        //     output_type: Some(self.output_type.to_proto()?),
-        //     is_distinct: self.is_distinct,
-        // })
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            function: Some(encode_planned_aggregate_function(
+                &self.function,
+                &self.input_types,
+            )?),
+            columns: self
+                .columns
+                .iter()
+                .map(|c| c.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+            is_distinct: self.is_distinct,
+            order_by: self
+                .order_by
+                .iter()
+                .map(|o| o.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+        })
     }
 
-    fn from_proto_ctx(_proto: Self::ProtoType, _context: &DatabaseContext) -> Result<Self> {
-        unimplemented!()
-        // Ok(Self {
-        //     function: DatabaseProtoConv::from_proto_ctx(
-        //         proto.function.required("function")?,
-        //         context,
-        //     )?,
-        //     columns: proto
-        //         .columns
-        //         .into_iter()
-        //         .map(|c| DatabaseProtoConv::from_proto_ctx(c, context))
-        //         .collect::<Result<Vec<_>>>()?,
-        //     output_type: ProtoConv::from_proto(proto.output_type.required("output_type")?)?,
-        //     is_distinct: proto.is_distinct,
-        // })
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        let (function, input_types) =
+            decode_planned_aggregate_function(proto.function.required("function")?, context)?;
+
+        Ok(Self {
+            function,
+            columns: proto
+                .columns
+                .into_iter()
+                .map(|c| DatabaseProtoConv::from_proto_ctx(c, context))
+                .collect::<Result<Vec<_>>>()?,
+            input_types,
+            is_distinct: proto.is_distinct,
+            order_by: proto
+                .order_by
+                .into_iter()
+                .map(|o| DatabaseProtoConv::from_proto_ctx(o, context))
+                .collect::<Result<Vec<_>>>()?,
+        })
     }
 }
 
@@ -249,4 +283,27 @@ mod tests {
 
         assert_eq!(expected, selection)
     }
+
+    #[test]
+    fn select_dictionary() {
+        // Dictionary with a null value, only some values referenced.
+        let mut dict = Array::from_iter(["a", "b", "c", ""]);
+        dict.set_physical_validity(3, false);
+        dict.select_mut(SelectionVector::from_iter([0, 2, 0, 3, 2, 0]));
+        let batch = Batch::try_new([dict]).unwrap();
+
+        let mut table_list = TableList::empty();
+        let table_ref = table_list
+            .push_table(None, vec![DataType::Utf8], vec!["a".to_string()])
+            .unwrap();
+
+        let expr = expr::eq(expr::col_ref(table_ref, 0), expr::lit("a"));
+        let planner = PhysicalExpressionPlanner::new(&table_list);
+        let physical = planner.plan_scalar(&[table_ref], &expr).unwrap();
+
+        let selection = physical.select(&batch).unwrap();
+        let expected = SelectionVector::from_iter([0, 2, 5]);
+
+        assert_eq!(expected, selection)
+    }
 }
@@ -4,14 +4,20 @@ use rayexec_error::{RayexecError, Result, ResultExt};
 use super::case_expr::PhysicalCaseExpr;
 use super::cast_expr::PhysicalCastExpr;
 use super::column_expr::PhysicalColumnExpr;
+use super::conditional_expr::PhysicalConditionalExpr;
 use super::literal_expr::PhysicalLiteralExpr;
 use super::scalar_function_expr::PhysicalScalarFunctionExpr;
 use super::PhysicalSortExpression;
+use crate::arrays::datatype::DataType;
 use crate::arrays::scalar::ScalarValue;
 use crate::execution::operators::hash_join::condition::HashJoinCondition;
+use crate::expr::comparison_expr::ComparisonOperator;
+use crate::expr::conditional_expr::ConditionalOperator;
 use crate::expr::physical::case_expr::PhysicalWhenThen;
 use crate::expr::physical::PhysicalScalarExpression;
 use crate::expr::{AsScalarFunction, Expression};
+use crate::functions::scalar::builtin::random::RandomState;
+use crate::functions::scalar::PlannedScalarFunction;
 use crate::logical::binder::bind_query::bind_modifier::BoundOrderByExpr;
 use crate::logical::binder::table_list::{TableList, TableRef};
 use crate::logical::logical_join::ComparisonCondition;
@@ -20,11 +26,29 @@ use crate::logical::logical_join::ComparisonCondition;
 #[derive(Debug)]
 pub struct PhysicalExpressionPlanner<'a> {
     pub table_list: &'a TableList,
+    /// Session random state used by functions producing random values.
+    pub random_state: Option<&'a RandomState>,
+    /// Time the query started, used by functions returning the current time.
+    pub query_start_micros: Option<i64>,
 }
 
 impl<'a> PhysicalExpressionPlanner<'a> {
     pub fn new(table_list: &'a TableList) -> Self {
-        PhysicalExpressionPlanner { table_list }
+        PhysicalExpressionPlanner {
+            table_list,
+            random_state: None,
+            query_start_micros: None,
+        }
+    }
+
+    pub fn with_random_state(mut self, random_state: Option<&'a RandomState>) -> Self {
+        self.random_state = random_state;
+        self
+    }
+
+    pub fn with_query_start_micros(mut self, query_start_micros: Option<i64>) -> Self {
+        self.query_start_micros = query_start_micros;
+        self
     }
 
     /// Plan more than one scalar expression.
@@ -89,14 +113,7 @@ impl<'a> PhysicalExpressionPlanner<'a> {
                 }))
             }
             Expression::ScalarFunction(expr) => {
-                let physical_inputs = self.plan_scalars(table_refs, &expr.function.inputs)?;
-
-                Ok(PhysicalScalarExpression::ScalarFunction(
-                    PhysicalScalarFunctionExpr {
-                        function: expr.function.clone(),
-                        inputs: physical_inputs,
-                    },
-                ))
+                self.plan_scalar_function(table_refs, expr.function.clone())
             }
             Expression::Cast(expr) => Ok(PhysicalScalarExpression::Cast(PhysicalCastExpr {
                 to: expr.to.clone(),
@@ -109,27 +126,13 @@ impl<'a> PhysicalExpressionPlanner<'a> {
                     vec![expr.left.as_ref().clone(), expr.right.as_ref().clone()],
                 )?;
 
-                let physical_inputs = self.plan_scalars(table_refs, &function.inputs)?;
-
-                Ok(PhysicalScalarExpression::ScalarFunction(
-                    PhysicalScalarFunctionExpr {
-                        function,
-                        inputs: physical_inputs,
-                    },
-                ))
+                self.plan_scalar_function(table_refs, function)
             }
             Expression::Conjunction(expr) => {
                 let scalar = expr.op.as_scalar_function();
                 let function = scalar.plan(self.table_list, expr.expressions.clone())?;
 
-                let physical_inputs = self.plan_scalars(table_refs, &function.inputs)?;
-
-                Ok(PhysicalScalarExpression::ScalarFunction(
-                    PhysicalScalarFunctionExpr {
-                        function,
-                        inputs: physical_inputs,
-                    },
-                ))
+                self.plan_scalar_function(table_refs, function)
             }
             Expression::Arith(expr) => {
                 let scalar = expr.op.as_scalar_function();
@@ -138,27 +141,13 @@ impl<'a> PhysicalExpressionPlanner<'a> {
                     vec![expr.left.as_ref().clone(), expr.right.as_ref().clone()],
                 )?;
 
-                let physical_inputs = self.plan_scalars(table_refs, &function.inputs)?;
-
-                Ok(PhysicalScalarExpression::ScalarFunction(
-                    PhysicalScalarFunctionExpr {
-                        function,
-                        inputs: physical_inputs,
-                    },
-                ))
+                self.plan_scalar_function(table_refs, function)
             }
             Expression::Negate(expr) => {
                 let scalar = expr.op.as_scalar_function();
                 let function = scalar.plan(self.table_list, vec![expr.expr.as_ref().clone()])?;
 
-                let physical_inputs = self.plan_scalars(table_refs, &function.inputs)?;
-
-                Ok(PhysicalScalarExpression::ScalarFunction(
-                    PhysicalScalarFunctionExpr {
-                        function,
-                        inputs: physical_inputs,
-                    },
-                ))
+                self.plan_scalar_function(table_refs, function)
             }
             Expression::Case(expr) => {
                 let datatype = expr.datatype(self.table_list)?;
@@ -188,6 +177,38 @@ impl<'a> PhysicalExpressionPlanner<'a> {
                     else_expr: Box::new(else_expr),
                 }))
             }
+            Expression::Conditional(expr) => {
+                let datatype = expr.datatype(self.table_list)?;
+
+                let compare_op = match expr.op {
+                    ConditionalOperator::Coalesce => None,
+                    ConditionalOperator::NullIf => Some(ComparisonOperator::Eq),
+                    ConditionalOperator::Greatest => Some(ComparisonOperator::Gt),
+                    ConditionalOperator::Least => Some(ComparisonOperator::Lt),
+                };
+
+                // All inputs have the same type, so the comparison can be
+                // planned using just the first input.
+                let compare = match compare_op {
+                    Some(op) if datatype != DataType::Null => {
+                        let input = expr.inputs[0].clone();
+                        Some(
+                            op.as_scalar_function()
+                                .plan(self.table_list, vec![input.clone(), input])?,
+                        )
+                    }
+                    _ => None,
+                };
+
+                Ok(PhysicalScalarExpression::Conditional(
+                    PhysicalConditionalExpr {
+                        op: expr.op,
+                        inputs: self.plan_scalars(table_refs, &expr.inputs)?,
+                        datatype,
+                        compare,
+                    },
+                ))
+            }
             other => Err(RayexecError::new(format!(
                 "Unsupported scalar expression: {other}"
             ))),
@@ -206,6 +227,8 @@ impl<'a> PhysicalExpressionPlanner<'a> {
             vec![condition.left.clone(), condition.right.clone()],
         )?;
 
+        let input_types = self.input_types(&function.inputs)?;
+
         Ok(HashJoinCondition {
             left: self
                 .plan_scalar(left_refs, &condition.left)
@@ -214,6 +237,7 @@ impl<'a> PhysicalExpressionPlanner<'a> {
                 .plan_scalar(right_refs, &condition.right)
                 .context("Failed to plan for right side of condition")?,
             function,
+            input_types,
         })
     }
 
@@ -228,6 +252,43 @@ impl<'a> PhysicalExpressionPlanner<'a> {
             .collect::<Result<Vec<_>>>()
     }
 
+    /// Plan a scalar function along with its inputs.
+    fn plan_scalar_function(
+        &self,
+        table_refs: &[TableRef],
+        mut function: PlannedScalarFunction,
+    ) -> Result<PhysicalScalarExpression> {
+        if let Some(random_state) = self.random_state {
+            if let Some(function_impl) = function.function_impl.with_random_state(random_state) {
+                function.function_impl = function_impl;
+            }
+        }
+        if let Some(micros) = self.query_start_micros {
+            if let Some(function_impl) = function.function_impl.with_query_start(micros) {
+                function.function_impl = function_impl;
+            }
+        }
+
+        let inputs = self.plan_scalars(table_refs, &function.inputs)?;
+        let input_types = self.input_types(&function.inputs)?;
+
+        Ok(PhysicalScalarExpression::ScalarFunction(
+            PhysicalScalarFunctionExpr {
+                function,
+                inputs,
+                input_types,
+            },
+        ))
+    }
+
+    /// Get the datatypes for a function's logical inputs.
+    pub fn input_types(&self, inputs: &[Expression]) -> Result<Vec<DataType>> {
+        inputs
+            .iter()
+            .map(|input| input.datatype(self.table_list))
+            .collect()
+    }
+
     /// Plan a sort expression.
     ///
     /// Sort expressions should be column expressions pointing to some
@@ -2,19 +2,28 @@ use std::borrow::Cow;
 use std::fmt;
 
 use fmtutil::IntoDisplayableSlice;
-use rayexec_error::Result;
+use rayexec_error::{OptionExt, Result};
 
 use super::PhysicalScalarExpression;
 use crate::arrays::array::Array;
 use crate::arrays::batch::Batch;
+use crate::arrays::compute::dictionary::DictionaryCodes;
+use crate::arrays::datatype::DataType;
+use crate::arrays::selection::SelectionVector;
 use crate::database::DatabaseContext;
+use crate::functions::proto::{decode_planned_scalar_function, encode_planned_scalar_function};
 use crate::functions::scalar::PlannedScalarFunction;
+use crate::functions::FunctionVolatility;
 use crate::proto::DatabaseProtoConv;
 
 #[derive(Debug, Clone)]
 pub struct PhysicalScalarFunctionExpr {
     pub function: PlannedScalarFunction,
     pub inputs: Vec<PhysicalScalarExpression>,
+    /// Datatypes of the inputs.
+    ///
+    /// Needed for planning the function again when deserializing.
+    pub input_types: Vec<DataType>,
 }
 
 impl PhysicalScalarFunctionExpr {
@@ -26,20 +35,73 @@ impl PhysicalScalarFunctionExpr {
             .collect::<Result<Vec<_>>>()?;
 
         let refs: Vec<_> = inputs.iter().map(|a| a.as_ref()).collect(); // Can I not?
-        let mut out = self.function.function_impl.execute(&refs)?;
-
-        // If function is provided no input, it's expected to return an
-        // array of length 1. We extend the array here so that it's the
-        // same size as the rest.
-        //
-        // TODO: Could just extend the selection vector too.
-        if refs.is_empty() {
-            let scalar = out.logical_value(0)?;
-            out = scalar.as_array(batch.num_rows())?;
+        if let Some(out) = self.eval_dictionary(&refs)? {
+            return Ok(Cow::Owned(out));
         }
 
+        // Functions without inputs don't know how many rows to produce.
+        let out = if refs.is_empty() {
+            self.function
+                .function_impl
+                .execute_without_inputs(batch.num_rows())?
+        } else {
+            self.function.function_impl.execute(&refs)?
+        };
+
         Ok(Cow::Owned(out))
     }
+
+    /// Evaluate the function once per distinct value when the only
+    /// non-constant input is dictionary encoded.
+    ///
+    /// Returns None if the inputs can't be evaluated this way.
+    fn eval_dictionary(&self, inputs: &[&Array]) -> Result<Option<Array>> {
+        if self.function.function.volatility() == FunctionVolatility::Volatile {
+            return Ok(None);
+        }
+
+        let mut dict_idx = None;
+        for (idx, input) in inputs.iter().enumerate() {
+            if input.array_data().len() == 1 {
+                // Constant.
+                continue;
+            }
+            if dict_idx.is_some() {
+                return Ok(None);
+            }
+            dict_idx = Some(idx);
+        }
+
+        let dict_idx = match dict_idx {
+            Some(idx) => idx,
+            None => return Ok(None),
+        };
+        let dict = match DictionaryCodes::try_from_array(inputs[dict_idx]) {
+            Some(dict) => dict,
+            None => return Ok(None),
+        };
+
+        let num_values = dict.values.logical_len();
+        let value_inputs: Vec<_> = inputs
+            .iter()
+            .enumerate()
+            .map(|(idx, input)| {
+                if idx == dict_idx {
+                    dict.values.clone()
+                } else {
+                    let mut constant = (*input).clone();
+                    constant.put_selection(SelectionVector::repeated(num_values, 0));
+                    constant
+                }
+            })
+            .collect();
+
+        let refs: Vec<_> = value_inputs.iter().collect();
+        let mut out = self.function.function_impl.execute(&refs)?;
+        out.select_mut(dict.codes);
+
+        Ok(Some(out))
+    }
 }
 
 impl fmt::Display for PhysicalScalarFunctionExpr {
@@ -56,30 +118,32 @@ impl fmt::Display for PhysicalScalarFunctionExpr {
 impl DatabaseProtoConv for PhysicalScalarFunctionExpr {
     type ProtoType = rayexec_proto::generated::physical_expr::PhysicalScalarFunctionExpr;
 
-    fn to_proto_ctx(&self, _context: &DatabaseContext) -> Result<Self::ProtoType> {
-        unimplemented!()
-        // Ok(Self::ProtoType {
-        //     function: Some(self.function.to_proto_ctx(context)?),
-        //     inputs: self
-        //         .inputs
-        //         .iter()
-        //         .map(|input| input.to_proto_ctx(context))
-        //         .collect::<Result<Vec<_>>>()?,
-        // })
+    fn to_proto_ctx(&self, context: &DatabaseContext) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            function: Some(encode_planned_scalar_function(
+                &self.function,
+                &self.input_types,
+            )?),
+            inputs: self
+                .inputs
+                .iter()
+                .map(|input| input.to_proto_ctx(context))
+                .collect::<Result<Vec<_>>>()?,
+        })
     }
 
-    fn from_proto_ctx(_proto: Self::ProtoType, _context: &DatabaseContext) -> Result<Self> {
-        unimplemented!()
-        // Ok(Self {
-        //     function: DatabaseProtoConv::from_proto_ctx(
-        //         proto.function.required("function")?,
-        //         context,
-        //     )?,
-        //     inputs: proto
-        //         .inputs
-        //         .into_iter()
-        //         .map(|input| DatabaseProtoConv::from_proto_ctx(input, context))
-        //         .collect::<Result<Vec<_>>>()?,
-        // })
+    fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
+        let (function, input_types) =
+            decode_planned_scalar_function(proto.function.required("function")?, context)?;
+
+        Ok(Self {
+            function,
+            inputs: proto
+                .inputs
+                .into_iter()
+                .map(|input| DatabaseProtoConv::from_proto_ctx(input, context))
+                .collect::<Result<Vec<_>>>()?,
+            input_types,
+        })
     }
 }
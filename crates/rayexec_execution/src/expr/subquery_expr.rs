@@ -23,6 +23,10 @@ pub enum SubqueryType {
         expr: Box<Expression>,
         /// The comparison operator to use.
         op: ComparisonOperator,
+        /// Type to cast the subquery output to before comparing.
+        ///
+        /// None if the output type already matches the type of `expr`.
+        cast_output: Option<DataType>,
     },
 }
 
@@ -51,7 +55,7 @@ impl ContextDisplay for SubqueryExpr {
             SubqueryType::Scalar => (),
             SubqueryType::Exists { negated: false } => write!(f, "EXISTS ")?,
             SubqueryType::Exists { negated: true } => write!(f, "NOT EXISTS ")?,
-            SubqueryType::Any { expr, op } => write!(
+            SubqueryType::Any { expr, op, .. } => write!(
                 f,
                 "{} {} ANY ",
                 ContextDisplayWrapper::with_mode(expr.as_ref(), mode),
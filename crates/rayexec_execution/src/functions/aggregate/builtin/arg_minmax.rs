@@ -0,0 +1,429 @@
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::aggregate::AggregateState;
+use crate::arrays::executor::scalar::concat;
+use crate::arrays::row::encoding::{ComparableColumn, ComparableRowEncoder};
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::expr::Expression;
+use crate::functions::aggregate::states::{AggregateGroupStates, TypedAggregateGroupStates};
+use crate::functions::aggregate::{
+    AggregateFunction,
+    AggregateFunctionImpl,
+    ChunkGroupAddressIter,
+    PlannedAggregateFunction,
+};
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+/// Signatures accepting an argument of any type, and a value of any type that
+/// can be ordered.
+const fn generate_arg_minmax_sigs(doc: &'static Documentation) -> [Signature; 22] {
+    [
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Boolean],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Int8],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Int16],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Int32],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Int64],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Int128],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::UInt8],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::UInt16],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::UInt32],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::UInt64],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::UInt128],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Float16],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Float32],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Float64],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Decimal64],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Decimal128],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Timestamp],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Date32],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Date64],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Interval],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Binary],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(doc),
+        },
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgMax;
+
+impl FunctionInfo for ArgMax {
+    fn name(&self) -> &'static str {
+        "arg_max"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["argmax", "max_by"]
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        const DOC: Documentation = Documentation {
+            category: Category::Aggregate,
+            description: "Return the value of 'arg' for the row with the largest non-NULL 'val'.",
+            arguments: &["arg", "val"],
+            example: Some(Example {
+                example: "arg_max(name, updated_at)",
+                output: "Most recently updated name",
+            }),
+        };
+
+        const SIGS: &[Signature] = &generate_arg_minmax_sigs(&DOC);
+
+        SIGS
+    }
+}
+
+impl AggregateFunction for ArgMax {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_arg_minmax(self, true, table_list, inputs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgMin;
+
+impl FunctionInfo for ArgMin {
+    fn name(&self) -> &'static str {
+        "arg_min"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["argmin", "min_by"]
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        const DOC: Documentation = Documentation {
+            category: Category::Aggregate,
+            description: "Return the value of 'arg' for the row with the smallest non-NULL 'val'.",
+            arguments: &["arg", "val"],
+            example: Some(Example {
+                example: "arg_min(name, created_at)",
+                output: "Earliest created name",
+            }),
+        };
+
+        const SIGS: &[Signature] = &generate_arg_minmax_sigs(&DOC);
+
+        SIGS
+    }
+}
+
+impl AggregateFunction for ArgMin {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_arg_minmax(self, false, table_list, inputs)
+    }
+}
+
+fn plan_arg_minmax(
+    func: &(impl AggregateFunction + Clone + 'static),
+    max: bool,
+    table_list: &TableList,
+    inputs: Vec<Expression>,
+) -> Result<PlannedAggregateFunction> {
+    plan_check_num_args(func, &inputs, 2)?;
+
+    let datatype = inputs[0].datatype(table_list)?;
+    let val = inputs[1].datatype(table_list)?;
+    if matches!(
+        val,
+        DataType::Null | DataType::Struct(_) | DataType::List(_)
+    ) {
+        return Err(invalid_input_types_error(func, &[datatype, val]));
+    }
+
+    Ok(PlannedAggregateFunction {
+        function: Box::new(func.clone()),
+        return_type: datatype.clone(),
+        inputs,
+        function_impl: Box::new(ArgMinMaxImpl { datatype, max }),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgMinMaxImpl {
+    /// Data type of the returned argument.
+    pub datatype: DataType,
+    /// If we're looking for the max value instead of the min.
+    pub max: bool,
+}
+
+impl AggregateFunctionImpl for ArgMinMaxImpl {
+    fn new_states(&self) -> Box<dyn AggregateGroupStates> {
+        let datatype = self.datatype.clone();
+        let max = self.max;
+
+        Box::new(TypedAggregateGroupStates::new(
+            ArgMinMaxState::default,
+            move |inputs: &[&Array],
+                  mapping: ChunkGroupAddressIter,
+                  states: &mut [ArgMinMaxState]| {
+                arg_minmax_update(max, inputs, mapping, states)
+            },
+            move |states: &mut [ArgMinMaxState]| arg_minmax_finalize(datatype.clone(), states),
+        ))
+    }
+}
+
+fn arg_minmax_update(
+    max: bool,
+    inputs: &[&Array],
+    mapping: ChunkGroupAddressIter,
+    states: &mut [ArgMinMaxState],
+) -> Result<()> {
+    let args = inputs[0];
+    let vals = inputs[1];
+
+    // Encode values such that the value we're looking for always compares
+    // smallest.
+    let keys = ComparableRowEncoder {
+        columns: vec![ComparableColumn {
+            desc: max,
+            nulls_first: false,
+        }],
+    }
+    .encode(&[vals])?;
+
+    for mapping in mapping {
+        if vals.is_valid(mapping.from_row) != Some(true) {
+            continue;
+        }
+
+        // Only materialize args that will replace the current one.
+        let key = keys.row(mapping.from_row).expect("row to exist").data();
+        let state = &mut states[mapping.to_state];
+        if state.accepts(key) {
+            let arg = args.logical_value(mapping.from_row)?.into_owned();
+            state.update((key.to_vec(), arg))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn arg_minmax_finalize(datatype: DataType, states: &mut [ArgMinMaxState]) -> Result<Array> {
+    let values = states
+        .iter_mut()
+        .map(|state| match state.finalize()? {
+            (ScalarValue::Null, _) | (_, false) => Array::new_typed_null_array(datatype.clone(), 1),
+            (value, true) => value.as_array(1),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if values.is_empty() {
+        return Array::new_typed_null_array(datatype, 0);
+    }
+
+    let refs: Vec<_> = values.iter().collect();
+    concat(&refs)?.try_with_datatype(datatype)
+}
+
+/// State for ARG_MIN and ARG_MAX.
+///
+/// Keys are row encoded values, with the encoding chosen such that the best
+/// value always has the smallest key. The first row seen wins ties.
+#[derive(Debug, Default)]
+pub struct ArgMinMaxState {
+    best: Option<(Vec<u8>, OwnedScalarValue)>,
+}
+
+impl ArgMinMaxState {
+    /// Returns if a value with the given key would replace the current arg.
+    fn accepts(&self, key: &[u8]) -> bool {
+        match &self.best {
+            Some((best, _)) => key < best.as_slice(),
+            None => true,
+        }
+    }
+}
+
+impl AggregateState<(Vec<u8>, OwnedScalarValue), OwnedScalarValue> for ArgMinMaxState {
+    fn merge(&mut self, other: &mut Self) -> Result<()> {
+        if let Some(best) = other.best.take() {
+            if self.accepts(&best.0) {
+                self.best = Some(best);
+            }
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, input: (Vec<u8>, OwnedScalarValue)) -> Result<()> {
+        self.best = Some(input);
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(OwnedScalarValue, bool)> {
+        match self.best.take() {
+            Some((_, arg)) => Ok((arg, true)),
+            None => Ok((ScalarValue::Null, false)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::operators::hash_aggregate::hash_table::GroupAddress;
+
+    #[test]
+    fn arg_max_per_group() {
+        let mut states = ArgMinMaxImpl {
+            datatype: DataType::Utf8,
+            max: true,
+        }
+        .new_states();
+        states.new_states(2);
+
+        let args = Array::from_iter(["a", "b", "c", "d", "e"]);
+        let vals = Array::from_iter([Some(1), Some(3), None, Some(3), Some(2)]);
+        let addrs: Vec<_> = [0, 0, 0, 1, 1]
+            .into_iter()
+            .map(|row_idx| GroupAddress {
+                chunk_idx: 0,
+                row_idx,
+            })
+            .collect();
+        states
+            .update_states(&[&args, &vals], ChunkGroupAddressIter::new(0, &addrs))
+            .unwrap();
+
+        let out = states.finalize().unwrap();
+        assert_eq!(ScalarValue::from("b"), out.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::from("d"), out.logical_value(1).unwrap());
+    }
+
+    #[test]
+    fn merge_keeps_smallest_key() {
+        let mut a = ArgMinMaxState::default();
+        a.update((vec![5], ScalarValue::Int32(5))).unwrap();
+        let mut b = ArgMinMaxState::default();
+        b.update((vec![2], ScalarValue::Int32(2))).unwrap();
+        a.merge(&mut b).unwrap();
+        assert_eq!((ScalarValue::Int32(2), true), a.finalize().unwrap());
+
+        let mut empty = ArgMinMaxState::default();
+        let mut c = ArgMinMaxState::default();
+        empty.merge(&mut c).unwrap();
+        assert_eq!((ScalarValue::Null, false), empty.finalize().unwrap());
+    }
+}
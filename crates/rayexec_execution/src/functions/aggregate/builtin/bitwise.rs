@@ -0,0 +1,415 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use num::PrimInt;
+use rayexec_error::Result;
+
+use crate::arrays::array::ArrayData;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::aggregate::AggregateState;
+use crate::arrays::executor::physical_type::{
+    PhysicalI128,
+    PhysicalI16,
+    PhysicalI32,
+    PhysicalI64,
+    PhysicalI8,
+    PhysicalStorage,
+    PhysicalU128,
+    PhysicalU16,
+    PhysicalU32,
+    PhysicalU64,
+    PhysicalU8,
+};
+use crate::arrays::storage::PrimitiveStorage;
+use crate::expr::Expression;
+use crate::functions::aggregate::states::{
+    new_unary_aggregate_states,
+    primitive_finalize,
+    AggregateGroupStates,
+};
+use crate::functions::aggregate::{
+    AggregateFunction,
+    AggregateFunctionImpl,
+    PlannedAggregateFunction,
+};
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+const fn generate_bitwise_sigs(doc: &'static Documentation) -> [Signature; 10] {
+    [
+        Signature {
+            positional_args: &[DataTypeId::Int8],
+            variadic_arg: None,
+            return_type: DataTypeId::Int8,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Int16],
+            variadic_arg: None,
+            return_type: DataTypeId::Int16,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Int32],
+            variadic_arg: None,
+            return_type: DataTypeId::Int32,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Int64],
+            variadic_arg: None,
+            return_type: DataTypeId::Int64,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::Int128],
+            variadic_arg: None,
+            return_type: DataTypeId::Int128,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::UInt8],
+            variadic_arg: None,
+            return_type: DataTypeId::UInt8,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::UInt16],
+            variadic_arg: None,
+            return_type: DataTypeId::UInt16,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::UInt32],
+            variadic_arg: None,
+            return_type: DataTypeId::UInt32,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::UInt64],
+            variadic_arg: None,
+            return_type: DataTypeId::UInt64,
+            doc: Some(doc),
+        },
+        Signature {
+            positional_args: &[DataTypeId::UInt128],
+            variadic_arg: None,
+            return_type: DataTypeId::UInt128,
+            doc: Some(doc),
+        },
+    ]
+}
+
+/// Plan a bitwise aggregate for any of the integer types.
+fn plan_bitwise<O: BitwiseOperation>(
+    func: &(impl AggregateFunction + Clone + 'static),
+    table_list: &TableList,
+    inputs: Vec<Expression>,
+) -> Result<PlannedAggregateFunction> {
+    plan_check_num_args(func, &inputs, 1)?;
+
+    let datatype = inputs[0].datatype(table_list)?;
+
+    let function_impl: Box<dyn AggregateFunctionImpl> = match &datatype {
+        DataType::Int8 => Box::new(BitwiseImpl::<O, PhysicalI8, i8>::new(datatype.clone())),
+        DataType::Int16 => Box::new(BitwiseImpl::<O, PhysicalI16, i16>::new(datatype.clone())),
+        DataType::Int32 => Box::new(BitwiseImpl::<O, PhysicalI32, i32>::new(datatype.clone())),
+        DataType::Int64 => Box::new(BitwiseImpl::<O, PhysicalI64, i64>::new(datatype.clone())),
+        DataType::Int128 => Box::new(BitwiseImpl::<O, PhysicalI128, i128>::new(datatype.clone())),
+        DataType::UInt8 => Box::new(BitwiseImpl::<O, PhysicalU8, u8>::new(datatype.clone())),
+        DataType::UInt16 => Box::new(BitwiseImpl::<O, PhysicalU16, u16>::new(datatype.clone())),
+        DataType::UInt32 => Box::new(BitwiseImpl::<O, PhysicalU32, u32>::new(datatype.clone())),
+        DataType::UInt64 => Box::new(BitwiseImpl::<O, PhysicalU64, u64>::new(datatype.clone())),
+        DataType::UInt128 => Box::new(BitwiseImpl::<O, PhysicalU128, u128>::new(datatype.clone())),
+        other => return Err(invalid_input_types_error(func, &[other])),
+    };
+
+    Ok(PlannedAggregateFunction {
+        function: Box::new(func.clone()),
+        return_type: datatype,
+        inputs,
+        function_impl,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitAnd;
+
+impl FunctionInfo for BitAnd {
+    fn name(&self) -> &'static str {
+        "bit_and"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        const DOC: Documentation = Documentation {
+            category: Category::Aggregate,
+            description: "Compute the bitwise AND of all non-NULL inputs.",
+            arguments: &["inputs"],
+            example: Some(Example {
+                example: "bit_and(a)",
+                output: "Bits set in every value of 'a'",
+            }),
+        };
+
+        const SIGS: &[Signature] = &generate_bitwise_sigs(&DOC);
+
+        SIGS
+    }
+}
+
+impl AggregateFunction for BitAnd {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_bitwise::<BitAndOperation>(self, table_list, inputs)
+    }
+
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        Some(Box::new(*self))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitOr;
+
+impl FunctionInfo for BitOr {
+    fn name(&self) -> &'static str {
+        "bit_or"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        const DOC: Documentation = Documentation {
+            category: Category::Aggregate,
+            description: "Compute the bitwise OR of all non-NULL inputs.",
+            arguments: &["inputs"],
+            example: Some(Example {
+                example: "bit_or(a)",
+                output: "Bits set in any value of 'a'",
+            }),
+        };
+
+        const SIGS: &[Signature] = &generate_bitwise_sigs(&DOC);
+
+        SIGS
+    }
+}
+
+impl AggregateFunction for BitOr {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_bitwise::<BitOrOperation>(self, table_list, inputs)
+    }
+
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        Some(Box::new(*self))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitXor;
+
+impl FunctionInfo for BitXor {
+    fn name(&self) -> &'static str {
+        "bit_xor"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        const DOC: Documentation = Documentation {
+            category: Category::Aggregate,
+            description: "Compute the bitwise XOR of all non-NULL inputs.",
+            arguments: &["inputs"],
+            example: Some(Example {
+                example: "bit_xor(a)",
+                output: "Bits set in an odd number of values of 'a'",
+            }),
+        };
+
+        const SIGS: &[Signature] = &generate_bitwise_sigs(&DOC);
+
+        SIGS
+    }
+}
+
+impl AggregateFunction for BitXor {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_bitwise::<BitXorOperation>(self, table_list, inputs)
+    }
+
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        Some(Box::new(*self))
+    }
+}
+
+/// Operation for combining values in a bitwise aggregate.
+pub trait BitwiseOperation: Debug + Sync + Send + 'static {
+    fn apply<T: PrimInt>(a: T, b: T) -> T;
+}
+
+#[derive(Debug)]
+pub struct BitAndOperation;
+
+impl BitwiseOperation for BitAndOperation {
+    fn apply<T: PrimInt>(a: T, b: T) -> T {
+        a & b
+    }
+}
+
+#[derive(Debug)]
+pub struct BitOrOperation;
+
+impl BitwiseOperation for BitOrOperation {
+    fn apply<T: PrimInt>(a: T, b: T) -> T {
+        a | b
+    }
+}
+
+#[derive(Debug)]
+pub struct BitXorOperation;
+
+impl BitwiseOperation for BitXorOperation {
+    fn apply<T: PrimInt>(a: T, b: T) -> T {
+        a ^ b
+    }
+}
+
+// TODO: Remove T
+#[derive(Debug)]
+pub struct BitwiseImpl<O, S, T> {
+    datatype: DataType,
+    _o: PhantomData<O>,
+    _s: PhantomData<S>,
+    _t: PhantomData<T>,
+}
+
+impl<O, S, T> BitwiseImpl<O, S, T> {
+    fn new(datatype: DataType) -> Self {
+        BitwiseImpl {
+            datatype,
+            _o: PhantomData,
+            _s: PhantomData,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<O, S, T> AggregateFunctionImpl for BitwiseImpl<O, S, T>
+where
+    O: BitwiseOperation,
+    for<'a> S: PhysicalStorage<Type<'a> = T>,
+    T: PrimInt + Debug + Default + Sync + Send + 'static,
+    ArrayData: From<PrimitiveStorage<T>>,
+{
+    fn new_states(&self) -> Box<dyn AggregateGroupStates> {
+        let datatype = self.datatype.clone();
+
+        new_unary_aggregate_states::<S, _, _, _, _>(BitwiseState::<O, T>::default, move |states| {
+            primitive_finalize(datatype.clone(), states)
+        })
+    }
+}
+
+impl<O, S, T> Clone for BitwiseImpl<O, S, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.datatype.clone())
+    }
+}
+
+#[derive(Debug)]
+pub struct BitwiseState<O, T> {
+    value: T,
+    valid: bool,
+    _o: PhantomData<O>,
+}
+
+impl<O, T: Default> Default for BitwiseState<O, T> {
+    fn default() -> Self {
+        BitwiseState {
+            value: T::default(),
+            valid: false,
+            _o: PhantomData,
+        }
+    }
+}
+
+impl<O, T> AggregateState<T, T> for BitwiseState<O, T>
+where
+    O: BitwiseOperation,
+    T: PrimInt + Debug + Default,
+{
+    fn merge(&mut self, other: &mut Self) -> Result<()> {
+        if other.valid {
+            self.update(other.value)?;
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, input: T) -> Result<()> {
+        if self.valid {
+            self.value = O::apply(self.value, input);
+        } else {
+            self.valid = true;
+            self.value = input;
+        }
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(T, bool)> {
+        if self.valid {
+            Ok((self.value, true))
+        } else {
+            Ok((T::default(), false))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compute<O: BitwiseOperation>(inputs: &[&[i32]]) -> Option<i32> {
+        let mut states = inputs.iter().map(|values| {
+            let mut state = BitwiseState::<O, i32>::default();
+            for &v in values.iter() {
+                state.update(v).unwrap();
+            }
+            state
+        });
+
+        let mut state = states.next().unwrap();
+        for mut other in states {
+            state.merge(&mut other).unwrap();
+        }
+
+        match state.finalize().unwrap() {
+            (v, true) => Some(v),
+            (_, false) => None,
+        }
+    }
+
+    #[test]
+    fn bitwise_merge() {
+        assert_eq!(
+            Some(0b0100),
+            compute::<BitAndOperation>(&[&[0b1100, 0b0110], &[], &[0b0101]])
+        );
+        assert_eq!(
+            Some(0b1111),
+            compute::<BitOrOperation>(&[&[0b1100, 0b0010], &[], &[0b0001]])
+        );
+        assert_eq!(
+            Some(0b0011),
+            compute::<BitXorOperation>(&[&[0b1100, 0b0110], &[], &[0b1001]])
+        );
+        assert_eq!(None, compute::<BitAndOperation>(&[&[], &[]]));
+    }
+}
@@ -0,0 +1,205 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use rayexec_error::Result;
+
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::aggregate::AggregateState;
+use crate::arrays::executor::physical_type::PhysicalBool;
+use crate::expr::Expression;
+use crate::functions::aggregate::states::{
+    boolean_finalize,
+    new_unary_aggregate_states,
+    AggregateGroupStates,
+};
+use crate::functions::aggregate::{
+    AggregateFunction,
+    AggregateFunctionImpl,
+    PlannedAggregateFunction,
+};
+use crate::functions::documentation::{Category, Documentation};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoolAnd;
+
+impl FunctionInfo for BoolAnd {
+    fn name(&self) -> &'static str {
+        "bool_and"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["every"]
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Boolean],
+            variadic_arg: None,
+            return_type: DataTypeId::Boolean,
+            doc: Some(&Documentation {
+                category: Category::Aggregate,
+                description: "Return true if all non-NULL inputs are true.",
+                arguments: &["inputs"],
+                example: None,
+            }),
+        }]
+    }
+}
+
+impl AggregateFunction for BoolAnd {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+
+        match inputs[0].datatype(table_list)? {
+            DataType::Boolean => Ok(PlannedAggregateFunction {
+                function: Box::new(*self),
+                return_type: DataType::Boolean,
+                inputs,
+                function_impl: Box::new(BoolAndImpl::new()),
+            }),
+            other => Err(invalid_input_types_error(self, &[other])),
+        }
+    }
+
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        Some(Box::new(*self))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoolOr;
+
+impl FunctionInfo for BoolOr {
+    fn name(&self) -> &'static str {
+        "bool_or"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Boolean],
+            variadic_arg: None,
+            return_type: DataTypeId::Boolean,
+            doc: Some(&Documentation {
+                category: Category::Aggregate,
+                description: "Return true if any non-NULL input is true.",
+                arguments: &["inputs"],
+                example: None,
+            }),
+        }]
+    }
+}
+
+impl AggregateFunction for BoolOr {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+
+        match inputs[0].datatype(table_list)? {
+            DataType::Boolean => Ok(PlannedAggregateFunction {
+                function: Box::new(*self),
+                return_type: DataType::Boolean,
+                inputs,
+                function_impl: Box::new(BoolOrImpl::new()),
+            }),
+            other => Err(invalid_input_types_error(self, &[other])),
+        }
+    }
+
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        Some(Box::new(*self))
+    }
+}
+
+pub type BoolAndImpl = BoolImpl<BoolAndState>;
+pub type BoolOrImpl = BoolImpl<BoolOrState>;
+
+#[derive(Debug)]
+pub struct BoolImpl<B> {
+    _b: PhantomData<B>,
+}
+
+impl<B> BoolImpl<B> {
+    fn new() -> Self {
+        BoolImpl { _b: PhantomData }
+    }
+}
+
+impl<B> AggregateFunctionImpl for BoolImpl<B>
+where
+    B: AggregateState<bool, bool> + Default + Debug + Sync + Send + 'static,
+{
+    fn new_states(&self) -> Box<dyn AggregateGroupStates> {
+        new_unary_aggregate_states::<PhysicalBool, _, _, _, _>(B::default, move |states| {
+            boolean_finalize(DataType::Boolean, states)
+        })
+    }
+}
+
+impl<B> Clone for BoolImpl<B> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BoolAndState {
+    result: bool,
+    valid: bool,
+}
+
+impl AggregateState<bool, bool> for BoolAndState {
+    fn merge(&mut self, other: &mut Self) -> Result<()> {
+        if other.valid {
+            self.update(other.result)?;
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, input: bool) -> Result<()> {
+        self.result = if self.valid {
+            self.result && input
+        } else {
+            input
+        };
+        self.valid = true;
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(bool, bool)> {
+        Ok((self.result, self.valid))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BoolOrState {
+    result: bool,
+    valid: bool,
+}
+
+impl AggregateState<bool, bool> for BoolOrState {
+    fn merge(&mut self, other: &mut Self) -> Result<()> {
+        if other.valid {
+            self.update(other.result)?;
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, input: bool) -> Result<()> {
+        self.result |= input;
+        self.valid = true;
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(bool, bool)> {
+        Ok((self.result, self.valid))
+    }
+}
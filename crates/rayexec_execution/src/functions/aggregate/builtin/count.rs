@@ -4,6 +4,7 @@ use crate::arrays::datatype::{DataType, DataTypeId};
 use crate::arrays::executor::aggregate::AggregateState;
 use crate::arrays::executor::physical_type::PhysicalAny;
 use crate::expr::{self, Expression};
+use crate::functions::aggregate::builtin::sum::Sum;
 use crate::functions::aggregate::states::{
     new_unary_aggregate_states,
     primitive_finalize,
@@ -68,6 +69,10 @@ impl AggregateFunction for Count {
             function_impl: Box::new(CountNonNullImpl),
         })
     }
+
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        Some(Box::new(Sum))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -44,7 +44,7 @@ use crate::functions::aggregate::{
     AggregateFunctionImpl,
     PlannedAggregateFunction,
 };
-use crate::functions::documentation::{Category, Documentation};
+use crate::functions::documentation::{Category, Documentation, Example};
 use crate::functions::{plan_check_num_args, FunctionInfo, Signature};
 use crate::logical::binder::table_list::TableList;
 
@@ -56,6 +56,10 @@ impl FunctionInfo for First {
         "first"
     }
 
+    fn aliases(&self) -> &'static [&'static str] {
+        &["arbitrary", "any_value"]
+    }
+
     fn signatures(&self) -> &[Signature] {
         &[Signature {
             positional_args: &[DataTypeId::Any],
@@ -65,7 +69,10 @@ impl FunctionInfo for First {
                 category: Category::Aggregate,
                 description: "Return the first non-NULL value.",
                 arguments: &["input"],
-                example: None,
+                example: Some(Example {
+                    example: "first(a ORDER BY b)",
+                    output: "Value of 'a' for the smallest 'b'",
+                }),
             }),
         }]
     }
@@ -77,88 +84,155 @@ impl AggregateFunction for First {
         table_list: &TableList,
         inputs: Vec<Expression>,
     ) -> Result<PlannedAggregateFunction> {
-        plan_check_num_args(self, &inputs, 1)?;
-
-        let datatype = inputs[0].datatype(table_list)?;
-
-        let function_impl: Box<dyn AggregateFunctionImpl> = match datatype.physical_type()? {
-            PhysicalType::UntypedNull => Box::new(FirstUntypedNullImpl),
-            PhysicalType::Boolean => Box::new(FirstBoolImpl),
-            PhysicalType::Float16 => Box::new(FirstPrimitiveImpl::<PhysicalF16, f16>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::Float32 => Box::new(FirstPrimitiveImpl::<PhysicalF32, f32>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::Float64 => Box::new(FirstPrimitiveImpl::<PhysicalF64, f64>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::Int8 => {
-                Box::new(FirstPrimitiveImpl::<PhysicalI8, i8>::new(datatype.clone()))
-            }
-            PhysicalType::Int16 => Box::new(FirstPrimitiveImpl::<PhysicalI16, i16>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::Int32 => Box::new(FirstPrimitiveImpl::<PhysicalI32, i32>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::Int64 => Box::new(FirstPrimitiveImpl::<PhysicalI64, i64>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::Int128 => Box::new(FirstPrimitiveImpl::<PhysicalI128, i128>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::UInt8 => {
-                Box::new(FirstPrimitiveImpl::<PhysicalU8, u8>::new(datatype.clone()))
-            }
-            PhysicalType::UInt16 => Box::new(FirstPrimitiveImpl::<PhysicalU16, u16>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::UInt32 => Box::new(FirstPrimitiveImpl::<PhysicalU32, u32>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::UInt64 => Box::new(FirstPrimitiveImpl::<PhysicalU64, u64>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::UInt128 => Box::new(FirstPrimitiveImpl::<PhysicalU128, u128>::new(
-                datatype.clone(),
-            )),
-            PhysicalType::Interval => Box::new(
-                FirstPrimitiveImpl::<PhysicalInterval, Interval>::new(datatype.clone()),
-            ),
-            PhysicalType::Binary => Box::new(FirstBinaryImpl {
-                datatype: datatype.clone(),
-            }),
-            PhysicalType::Utf8 => Box::new(FirstBinaryImpl {
-                datatype: datatype.clone(),
+        plan_first_last(self, false, table_list, inputs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Last;
+
+impl FunctionInfo for Last {
+    fn name(&self) -> &'static str {
+        "last"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Any],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(&Documentation {
+                category: Category::Aggregate,
+                description: "Return the last non-NULL value.",
+                arguments: &["input"],
+                example: Some(Example {
+                    example: "last(a ORDER BY b)",
+                    output: "Value of 'a' for the largest 'b'",
+                }),
             }),
-            PhysicalType::List => {
-                // TODO: Easy, clone underlying array and select.
-                not_implemented!("FIRST for list arrays")
-            }
-        };
-
-        Ok(PlannedAggregateFunction {
-            function: Box::new(*self),
-            return_type: datatype,
-            inputs,
-            function_impl,
-        })
+        }]
+    }
+}
+
+impl AggregateFunction for Last {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_first_last(self, true, table_list, inputs)
     }
 }
 
-/// FIRST aggregate impl for utf8 and binary.
+/// Plan FIRST or LAST.
+///
+/// Both keep a single value per group, LAST replaces the value on every
+/// update.
+fn plan_first_last(
+    func: &(impl AggregateFunction + Clone + 'static),
+    last: bool,
+    table_list: &TableList,
+    inputs: Vec<Expression>,
+) -> Result<PlannedAggregateFunction> {
+    plan_check_num_args(func, &inputs, 1)?;
+
+    let datatype = inputs[0].datatype(table_list)?;
+
+    let function_impl: Box<dyn AggregateFunctionImpl> = match datatype.physical_type()? {
+        PhysicalType::UntypedNull => Box::new(FirstUntypedNullImpl),
+        PhysicalType::Boolean => Box::new(FirstBoolImpl { last }),
+        PhysicalType::Float16 => Box::new(FirstPrimitiveImpl::<PhysicalF16, f16>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::Float32 => Box::new(FirstPrimitiveImpl::<PhysicalF32, f32>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::Float64 => Box::new(FirstPrimitiveImpl::<PhysicalF64, f64>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::Int8 => Box::new(FirstPrimitiveImpl::<PhysicalI8, i8>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::Int16 => Box::new(FirstPrimitiveImpl::<PhysicalI16, i16>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::Int32 => Box::new(FirstPrimitiveImpl::<PhysicalI32, i32>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::Int64 => Box::new(FirstPrimitiveImpl::<PhysicalI64, i64>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::Int128 => Box::new(FirstPrimitiveImpl::<PhysicalI128, i128>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::UInt8 => Box::new(FirstPrimitiveImpl::<PhysicalU8, u8>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::UInt16 => Box::new(FirstPrimitiveImpl::<PhysicalU16, u16>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::UInt32 => Box::new(FirstPrimitiveImpl::<PhysicalU32, u32>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::UInt64 => Box::new(FirstPrimitiveImpl::<PhysicalU64, u64>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::UInt128 => Box::new(FirstPrimitiveImpl::<PhysicalU128, u128>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::Interval => Box::new(FirstPrimitiveImpl::<PhysicalInterval, Interval>::new(
+            datatype.clone(),
+            last,
+        )),
+        PhysicalType::Binary => Box::new(FirstBinaryImpl {
+            datatype: datatype.clone(),
+            last,
+        }),
+        PhysicalType::Utf8 => Box::new(FirstBinaryImpl {
+            datatype: datatype.clone(),
+            last,
+        }),
+        PhysicalType::List => {
+            // TODO: Easy, clone underlying array and select.
+            not_implemented!("{} for list arrays", func.name().to_uppercase())
+        }
+    };
+
+    Ok(PlannedAggregateFunction {
+        function: Box::new(func.clone()),
+        return_type: datatype,
+        inputs,
+        function_impl,
+    })
+}
+
+/// FIRST/LAST aggregate impl for utf8 and binary.
 #[derive(Debug, Clone)]
 pub struct FirstBinaryImpl {
     datatype: DataType,
+    last: bool,
 }
 
 impl AggregateFunctionImpl for FirstBinaryImpl {
     fn new_states(&self) -> Box<dyn AggregateGroupStates> {
         let datatype = self.datatype.clone();
+        let last = self.last;
 
         new_unary_aggregate_states::<PhysicalBinary, _, _, _, _>(
-            FirstStateBinary::default,
+            move || FirstStateBinary::new(last),
             move |states| {
                 let builder = ArrayBuilder {
                     datatype: datatype.clone(),
@@ -176,19 +250,22 @@ pub struct FirstUntypedNullImpl;
 impl AggregateFunctionImpl for FirstUntypedNullImpl {
     fn new_states(&self) -> Box<dyn AggregateGroupStates> {
         new_unary_aggregate_states::<PhysicalUntypedNull, _, _, _, _>(
-            FirstState::<UntypedNull>::default,
+            || FirstState::<UntypedNull>::new(false),
             untyped_null_finalize,
         )
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct FirstBoolImpl;
+pub struct FirstBoolImpl {
+    last: bool,
+}
 
 impl AggregateFunctionImpl for FirstBoolImpl {
     fn new_states(&self) -> Box<dyn AggregateGroupStates> {
+        let last = self.last;
         new_unary_aggregate_states::<PhysicalBool, _, _, _, _>(
-            FirstState::<bool>::default,
+            move || FirstState::<bool>::new(last),
             move |states| boolean_finalize(DataType::Boolean, states),
         )
     }
@@ -198,14 +275,16 @@ impl AggregateFunctionImpl for FirstBoolImpl {
 #[derive(Debug, Clone)]
 pub struct FirstPrimitiveImpl<S, T> {
     datatype: DataType,
+    last: bool,
     _s: PhantomData<S>,
     _t: PhantomData<T>,
 }
 
 impl<S, T> FirstPrimitiveImpl<S, T> {
-    fn new(datatype: DataType) -> Self {
+    fn new(datatype: DataType, last: bool) -> Self {
         FirstPrimitiveImpl {
             datatype,
+            last,
             _s: PhantomData,
             _t: PhantomData,
         }
@@ -220,29 +299,41 @@ where
 {
     fn new_states(&self) -> Box<dyn AggregateGroupStates> {
         let datatype = self.datatype.clone();
+        let last = self.last;
 
-        new_unary_aggregate_states::<S, _, _, _, _>(FirstState::<T>::default, move |states| {
-            primitive_finalize(datatype.clone(), states)
-        })
+        new_unary_aggregate_states::<S, _, _, _, _>(
+            move || FirstState::<T>::new(last),
+            move |states| primitive_finalize(datatype.clone(), states),
+        )
     }
 }
 
-#[derive(Debug, Default)]
+/// State for FIRST and LAST.
+///
+/// When `last` is set, the most recent non-NULL value is kept instead of the
+/// first one. Merging treats `other` as coming after `self`.
+#[derive(Debug)]
 pub struct FirstState<T> {
     value: Option<T>,
+    last: bool,
+}
+
+impl<T> FirstState<T> {
+    fn new(last: bool) -> Self {
+        FirstState { value: None, last }
+    }
 }
 
 impl<T: Default + Debug + Copy> AggregateState<T, T> for FirstState<T> {
     fn merge(&mut self, other: &mut Self) -> Result<()> {
-        if self.value.is_none() {
+        if self.value.is_none() || (self.last && other.value.is_some()) {
             self.value = other.value;
-            return Ok(());
         }
         Ok(())
     }
 
     fn update(&mut self, input: T) -> Result<()> {
-        if self.value.is_none() {
+        if self.value.is_none() || self.last {
             self.value = Some(input);
         }
         Ok(())
@@ -256,22 +347,28 @@ impl<T: Default + Debug + Copy> AggregateState<T, T> for FirstState<T> {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct FirstStateBinary {
     value: Option<Vec<u8>>,
+    last: bool,
+}
+
+impl FirstStateBinary {
+    fn new(last: bool) -> Self {
+        FirstStateBinary { value: None, last }
+    }
 }
 
 impl AggregateState<&[u8], Vec<u8>> for FirstStateBinary {
     fn merge(&mut self, other: &mut Self) -> Result<()> {
-        if self.value.is_none() {
+        if self.value.is_none() || (self.last && other.value.is_some()) {
             std::mem::swap(&mut self.value, &mut other.value);
-            return Ok(());
         }
         Ok(())
     }
 
     fn update(&mut self, input: &[u8]) -> Result<()> {
-        if self.value.is_none() {
+        if self.value.is_none() || self.last {
             self.value = Some(input.to_owned());
         }
         Ok(())
@@ -284,3 +381,31 @@ impl AggregateState<&[u8], Vec<u8>> for FirstStateBinary {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_and_last_merge() {
+        let mut first = FirstState::<i32>::new(false);
+        first.update(1).unwrap();
+        first.update(2).unwrap();
+        let mut other = FirstState::<i32>::new(false);
+        other.update(3).unwrap();
+        first.merge(&mut other).unwrap();
+        assert_eq!((1, true), first.finalize().unwrap());
+
+        let mut last = FirstState::<i32>::new(true);
+        last.update(1).unwrap();
+        last.update(2).unwrap();
+        let mut other = FirstState::<i32>::new(true);
+        other.update(3).unwrap();
+        last.merge(&mut other).unwrap();
+        assert_eq!((3, true), last.finalize().unwrap());
+
+        let mut empty = FirstState::<i32>::new(true);
+        last.merge(&mut empty).unwrap();
+        assert_eq!((3, true), last.finalize().unwrap());
+    }
+}
@@ -140,6 +140,10 @@ impl AggregateFunction for Min {
             function_impl,
         })
     }
+
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        Some(Box::new(*self))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -234,6 +238,10 @@ impl AggregateFunction for Max {
             function_impl,
         })
     }
+
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        Some(Box::new(*self))
+    }
 }
 
 #[derive(Debug, Clone)]
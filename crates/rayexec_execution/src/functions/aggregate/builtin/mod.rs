@@ -1,13 +1,19 @@
+pub mod arg_minmax;
 pub mod avg;
+pub mod bitwise;
+pub mod boolean;
 pub mod corr;
 pub mod count;
 pub mod covar;
 pub mod first;
 pub mod minmax;
+pub mod percentile;
 pub mod regr_avg;
 pub mod regr_count;
 pub mod regr_r2;
 pub mod regr_slope;
+pub mod reservoir_sample;
+pub mod sketch;
 pub mod stddev;
 pub mod string_agg;
 pub mod sum;
@@ -25,6 +31,14 @@ pub static BUILTIN_AGGREGATE_FUNCTIONS: LazyLock<Vec<Box<dyn AggregateFunction>>
             Box::new(minmax::Min),
             Box::new(minmax::Max),
             Box::new(first::First),
+            Box::new(first::Last),
+            Box::new(arg_minmax::ArgMax),
+            Box::new(arg_minmax::ArgMin),
+            Box::new(bitwise::BitAnd),
+            Box::new(bitwise::BitOr),
+            Box::new(bitwise::BitXor),
+            Box::new(boolean::BoolAnd),
+            Box::new(boolean::BoolOr),
             Box::new(stddev::StddevPop),
             Box::new(stddev::StddevSamp),
             Box::new(stddev::VarPop),
@@ -38,5 +52,10 @@ pub static BUILTIN_AGGREGATE_FUNCTIONS: LazyLock<Vec<Box<dyn AggregateFunction>>
             Box::new(regr_r2::RegrR2),
             Box::new(regr_slope::RegrSlope),
             Box::new(string_agg::StringAgg),
+            Box::new(percentile::PercentileCont),
+            Box::new(percentile::PercentileDisc),
+            Box::new(reservoir_sample::ReservoirSample),
+            Box::new(sketch::Minhash),
+            Box::new(sketch::ApproxCountDistinct),
         ]
     });
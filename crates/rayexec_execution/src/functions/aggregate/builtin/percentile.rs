@@ -0,0 +1,364 @@
+use std::fmt::Debug;
+
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::aggregate::AggregateState;
+use crate::arrays::executor::physical_type::PhysicalF64;
+use crate::arrays::executor::scalar::concat;
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::expr::Expression;
+use crate::functions::aggregate::states::{
+    new_unary_aggregate_states,
+    primitive_finalize,
+    AggregateGroupStates,
+    TypedAggregateGroupStates,
+};
+use crate::functions::aggregate::{
+    AggregateFunction,
+    AggregateFunctionImpl,
+    ChunkGroupAddressIter,
+    PlannedAggregateFunction,
+};
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+use crate::optimizer::expr_rewrite::const_fold::ConstFold;
+use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
+
+/// Get the constant fraction provided as the second input to a percentile
+/// function.
+fn plan_fraction(
+    func: &impl FunctionInfo,
+    table_list: &TableList,
+    input: &Expression,
+) -> Result<f64> {
+    if !input.is_const_foldable() {
+        return Err(RayexecError::new(format!(
+            "Fraction for {} must be constant",
+            func.name().to_uppercase()
+        )));
+    }
+
+    match ConstFold::rewrite(table_list, input.clone())?.try_into_scalar()? {
+        ScalarValue::Float64(f) if (0.0..=1.0).contains(&f) => Ok(f),
+        other => Err(RayexecError::new(format!(
+            "Fraction for {} must be between 0 and 1, got {other}",
+            func.name().to_uppercase()
+        ))),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PercentileCont;
+
+impl FunctionInfo for PercentileCont {
+    fn name(&self) -> &'static str {
+        "percentile_cont"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Float64, DataTypeId::Float64],
+            variadic_arg: None,
+            return_type: DataTypeId::Float64,
+            doc: Some(&Documentation {
+                category: Category::Aggregate,
+                description: "Compute the value at the given fraction of the ordered input, interpolating between adjacent values if needed.",
+                arguments: &["input", "fraction"],
+                example: Some(Example {
+                    example: "percentile_cont(0.5) WITHIN GROUP (ORDER BY a)",
+                    output: "Median of 'a'",
+                }),
+            }),
+        }]
+    }
+}
+
+impl AggregateFunction for PercentileCont {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+
+        let datatypes = inputs
+            .iter()
+            .map(|expr| expr.datatype(table_list))
+            .collect::<Result<Vec<_>>>()?;
+
+        match (&datatypes[0], &datatypes[1]) {
+            (DataType::Float64, DataType::Float64) => (),
+            _ => return Err(invalid_input_types_error(self, &datatypes)),
+        }
+
+        let fraction = plan_fraction(self, table_list, &inputs[1])?;
+
+        Ok(PlannedAggregateFunction {
+            function: Box::new(*self),
+            return_type: DataType::Float64,
+            inputs,
+            function_impl: Box::new(PercentileContImpl { fraction }),
+        })
+    }
+
+    fn is_ordered_set(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentileContImpl {
+    /// Fraction to compute.
+    pub fraction: f64,
+}
+
+impl AggregateFunctionImpl for PercentileContImpl {
+    fn new_states(&self) -> Box<dyn AggregateGroupStates> {
+        let fraction = self.fraction;
+        new_unary_aggregate_states::<PhysicalF64, _, _, _, _>(
+            move || PercentileContState {
+                fraction,
+                values: Vec::new(),
+            },
+            move |states| primitive_finalize(DataType::Float64, states),
+        )
+    }
+}
+
+/// State for PERCENTILE_CONT.
+///
+/// Expects values to be provided in order.
+#[derive(Debug, Default)]
+pub struct PercentileContState {
+    fraction: f64,
+    values: Vec<f64>,
+}
+
+impl AggregateState<f64, f64> for PercentileContState {
+    fn merge(&mut self, other: &mut Self) -> Result<()> {
+        self.values.append(&mut other.values);
+        Ok(())
+    }
+
+    fn update(&mut self, input: f64) -> Result<()> {
+        self.values.push(input);
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(f64, bool)> {
+        if self.values.is_empty() {
+            return Ok((0.0, false));
+        }
+
+        let pos = self.fraction * (self.values.len() - 1) as f64;
+        let lower = pos.floor();
+        let lower_val = self.values[lower as usize];
+        let upper_val = self.values[pos.ceil() as usize];
+
+        Ok((lower_val + (upper_val - lower_val) * (pos - lower), true))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PercentileDisc;
+
+impl FunctionInfo for PercentileDisc {
+    fn name(&self) -> &'static str {
+        "percentile_disc"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Float64],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(&Documentation {
+                category: Category::Aggregate,
+                description: "Return the first input value whose position in the ordered input is at or after the given fraction.",
+                arguments: &["input", "fraction"],
+                example: Some(Example {
+                    example: "percentile_disc(0.5) WITHIN GROUP (ORDER BY a)",
+                    output: "Median value of 'a'",
+                }),
+            }),
+        }]
+    }
+}
+
+impl AggregateFunction for PercentileDisc {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+
+        let datatypes = inputs
+            .iter()
+            .map(|expr| expr.datatype(table_list))
+            .collect::<Result<Vec<_>>>()?;
+
+        if datatypes[1] != DataType::Float64 {
+            return Err(invalid_input_types_error(self, &datatypes));
+        }
+
+        let fraction = plan_fraction(self, table_list, &inputs[1])?;
+        let datatype = datatypes[0].clone();
+
+        Ok(PlannedAggregateFunction {
+            function: Box::new(*self),
+            return_type: datatype.clone(),
+            inputs,
+            function_impl: Box::new(PercentileDiscImpl { datatype, fraction }),
+        })
+    }
+
+    fn is_ordered_set(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentileDiscImpl {
+    /// Data type of the input values.
+    pub datatype: DataType,
+    /// Fraction to compute.
+    pub fraction: f64,
+}
+
+impl AggregateFunctionImpl for PercentileDiscImpl {
+    fn new_states(&self) -> Box<dyn AggregateGroupStates> {
+        let fraction = self.fraction;
+        let datatype = self.datatype.clone();
+
+        Box::new(TypedAggregateGroupStates::new(
+            move || PercentileDiscState {
+                fraction,
+                values: Vec::new(),
+            },
+            percentile_disc_update,
+            move |states: &mut [PercentileDiscState]| {
+                percentile_disc_finalize(datatype.clone(), states)
+            },
+        ))
+    }
+}
+
+fn percentile_disc_update(
+    inputs: &[&Array],
+    mapping: ChunkGroupAddressIter,
+    states: &mut [PercentileDiscState],
+) -> Result<()> {
+    let array = inputs[0];
+    for mapping in mapping {
+        if array.is_valid(mapping.from_row) != Some(true) {
+            continue;
+        }
+        let value = array.logical_value(mapping.from_row)?.into_owned();
+        states[mapping.to_state].update(value)?;
+    }
+    Ok(())
+}
+
+fn percentile_disc_finalize(
+    datatype: DataType,
+    states: &mut [PercentileDiscState],
+) -> Result<Array> {
+    let values = states
+        .iter_mut()
+        .map(|state| match state.finalize()? {
+            (value, true) => value.as_array(1),
+            (_, false) => Array::new_typed_null_array(datatype.clone(), 1),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if values.is_empty() {
+        return Array::new_typed_null_array(datatype, 0);
+    }
+
+    let refs: Vec<_> = values.iter().collect();
+    concat(&refs)?.try_with_datatype(datatype)
+}
+
+/// State for PERCENTILE_DISC.
+///
+/// Expects values to be provided in order.
+#[derive(Debug)]
+pub struct PercentileDiscState {
+    fraction: f64,
+    values: Vec<OwnedScalarValue>,
+}
+
+impl AggregateState<OwnedScalarValue, OwnedScalarValue> for PercentileDiscState {
+    fn merge(&mut self, other: &mut Self) -> Result<()> {
+        self.values.append(&mut other.values);
+        Ok(())
+    }
+
+    fn update(&mut self, input: OwnedScalarValue) -> Result<()> {
+        self.values.push(input);
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(OwnedScalarValue, bool)> {
+        if self.values.is_empty() {
+            return Ok((ScalarValue::Null, false));
+        }
+
+        // First value whose cumulative distribution is >= the fraction.
+        let idx = ((self.fraction * self.values.len() as f64).ceil() as usize).saturating_sub(1);
+        let value = self.values.swap_remove(idx);
+        self.values.clear();
+
+        Ok((value, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cont(fraction: f64, values: &[f64]) -> Option<f64> {
+        let mut state = PercentileContState {
+            fraction,
+            values: values.to_vec(),
+        };
+        match state.finalize().unwrap() {
+            (v, true) => Some(v),
+            (_, false) => None,
+        }
+    }
+
+    fn disc(fraction: f64, values: &[i32]) -> Option<OwnedScalarValue> {
+        let mut state = PercentileDiscState {
+            fraction,
+            values: values.iter().map(|&v| ScalarValue::Int32(v)).collect(),
+        };
+        match state.finalize().unwrap() {
+            (v, true) => Some(v),
+            (_, false) => None,
+        }
+    }
+
+    #[test]
+    fn percentile_cont_interpolates() {
+        assert_eq!(Some(2.5), cont(0.5, &[1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(Some(1.0), cont(0.0, &[1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(Some(4.0), cont(1.0, &[1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(Some(1.75), cont(0.25, &[1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(Some(8.0), cont(0.3, &[8.0]));
+        assert_eq!(None, cont(0.5, &[]));
+    }
+
+    #[test]
+    fn percentile_disc_picks_value() {
+        assert_eq!(Some(ScalarValue::Int32(2)), disc(0.5, &[1, 2, 3, 4]));
+        assert_eq!(Some(ScalarValue::Int32(1)), disc(0.0, &[1, 2, 3, 4]));
+        assert_eq!(Some(ScalarValue::Int32(4)), disc(1.0, &[1, 2, 3, 4]));
+        assert_eq!(Some(ScalarValue::Int32(3)), disc(0.51, &[1, 2, 3, 4]));
+        assert_eq!(None, disc(0.5, &[]));
+    }
+}
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use rand::Rng;
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::array::Array;
+use crate::arrays::bitmap::Bitmap;
+use crate::arrays::datatype::{DataType, DataTypeId, ListTypeMeta};
+use crate::arrays::executor::aggregate::AggregateState;
+use crate::arrays::executor::scalar::concat;
+use crate::arrays::scalar::OwnedScalarValue;
+use crate::arrays::storage::{ListItemMetadata, ListStorage};
+use crate::expr::Expression;
+use crate::functions::aggregate::states::{AggregateGroupStates, TypedAggregateGroupStates};
+use crate::functions::aggregate::{
+    AggregateFunction,
+    AggregateFunctionImpl,
+    ChunkGroupAddressIter,
+    PlannedAggregateFunction,
+};
+use crate::functions::documentation::{Category, Documentation};
+use crate::functions::{plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+use crate::optimizer::expr_rewrite::const_fold::ConstFold;
+use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReservoirSample;
+
+impl FunctionInfo for ReservoirSample {
+    fn name(&self) -> &'static str {
+        "reservoir_sample"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Int64],
+            variadic_arg: None,
+            return_type: DataTypeId::List,
+            doc: Some(&Documentation {
+                category: Category::Aggregate,
+                description: "Return a list containing a uniform random sample of up to 'n' non-NULL input values.",
+                arguments: &["input", "n"],
+                example: None,
+            }),
+        }]
+    }
+}
+
+impl AggregateFunction for ReservoirSample {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+
+        if !inputs[1].is_const_foldable() {
+            return Err(RayexecError::new(
+                "Second argument to RESERVOIR_SAMPLE must be constant",
+            ));
+        }
+
+        let n = ConstFold::rewrite(table_list, inputs[1].clone())?
+            .try_into_scalar()?
+            .try_as_usize()?;
+        if n == 0 {
+            return Err(RayexecError::new(
+                "Sample size for RESERVOIR_SAMPLE must be greater than zero",
+            ));
+        }
+
+        let datatype = inputs[0].datatype(table_list)?;
+
+        Ok(PlannedAggregateFunction {
+            function: Box::new(*self),
+            return_type: DataType::List(ListTypeMeta::new(datatype.clone())),
+            inputs,
+            function_impl: Box::new(ReservoirSampleImpl { datatype, n }),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservoirSampleImpl {
+    /// Data type of the values being sampled.
+    pub datatype: DataType,
+    /// Max number of values to keep per group.
+    pub n: usize,
+}
+
+impl AggregateFunctionImpl for ReservoirSampleImpl {
+    fn new_states(&self) -> Box<dyn AggregateGroupStates> {
+        let n = self.n;
+        let datatype = self.datatype.clone();
+
+        Box::new(TypedAggregateGroupStates::new(
+            move || ReservoirSampleState {
+                n,
+                sample: BinaryHeap::new(),
+            },
+            reservoir_sample_update,
+            move |states: &mut [ReservoirSampleState]| {
+                reservoir_sample_finalize(datatype.clone(), states)
+            },
+        ))
+    }
+}
+
+fn reservoir_sample_update(
+    inputs: &[&Array],
+    mapping: ChunkGroupAddressIter,
+    states: &mut [ReservoirSampleState],
+) -> Result<()> {
+    let array = inputs[0];
+    let mut rng = rand::thread_rng();
+
+    for mapping in mapping {
+        if array.is_valid(mapping.from_row) != Some(true) {
+            continue;
+        }
+
+        // Only materialize values that will end up in the sample.
+        let key = rng.gen();
+        let state = &mut states[mapping.to_state];
+        if state.accepts(key) {
+            let value = array.logical_value(mapping.from_row)?.into_owned();
+            state.update((key, value))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn reservoir_sample_finalize(
+    datatype: DataType,
+    states: &mut [ReservoirSampleState],
+) -> Result<Array> {
+    let list_type = DataType::List(ListTypeMeta::new(datatype.clone()));
+
+    let mut values = Vec::new();
+    let mut metadata = Vec::with_capacity(states.len());
+    let mut validity = Bitmap::new_with_all_true(states.len());
+
+    for (idx, state) in states.iter_mut().enumerate() {
+        let (sample, valid) = state.finalize()?;
+        if !valid {
+            validity.set_unchecked(idx, false);
+        }
+        metadata.push(ListItemMetadata {
+            offset: values.len() as i32,
+            len: sample.len() as i32,
+        });
+        for value in sample {
+            values.push(value.as_array(1)?);
+        }
+    }
+
+    if values.is_empty() {
+        return Array::new_typed_null_array(list_type, states.len());
+    }
+
+    let refs: Vec<_> = values.iter().collect();
+    let child = concat(&refs)?.try_with_datatype(datatype)?;
+    let data = ListStorage::try_new(metadata, child)?;
+
+    Ok(Array::new_with_validity_and_array_data(
+        list_type, validity, data,
+    ))
+}
+
+/// A value in the sample along with the random key it was assigned.
+#[derive(Debug)]
+struct SampledValue {
+    key: u64,
+    value: OwnedScalarValue,
+}
+
+impl PartialEq for SampledValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for SampledValue {}
+
+impl PartialOrd for SampledValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SampledValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Sample of values for a single group.
+///
+/// Every value is assigned a random key, and only the values with the `n`
+/// smallest keys are kept. This gives a uniform sample without replacement,
+/// and lets samples from different partitions be merged by keeping the
+/// smallest keys across both.
+#[derive(Debug)]
+pub struct ReservoirSampleState {
+    n: usize,
+    /// Sampled values, largest key first.
+    sample: BinaryHeap<SampledValue>,
+}
+
+impl ReservoirSampleState {
+    /// Returns if a value with the given key would be kept in the sample.
+    fn accepts(&self, key: u64) -> bool {
+        match self.sample.peek() {
+            Some(largest) if self.sample.len() >= self.n => key < largest.key,
+            _ => true,
+        }
+    }
+
+    fn insert(&mut self, value: SampledValue) {
+        self.sample.push(value);
+        if self.sample.len() > self.n {
+            self.sample.pop();
+        }
+    }
+}
+
+impl AggregateState<(u64, OwnedScalarValue), Vec<OwnedScalarValue>> for ReservoirSampleState {
+    fn merge(&mut self, other: &mut Self) -> Result<()> {
+        for value in std::mem::take(&mut other.sample) {
+            if self.accepts(value.key) {
+                self.insert(value);
+            }
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, (key, value): (u64, OwnedScalarValue)) -> Result<()> {
+        self.insert(SampledValue { key, value });
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(Vec<OwnedScalarValue>, bool)> {
+        if self.sample.is_empty() {
+            return Ok((Vec::new(), false));
+        }
+
+        let sample = std::mem::take(&mut self.sample)
+            .into_sorted_vec()
+            .into_iter()
+            .map(|sampled| sampled.value)
+            .collect();
+
+        Ok((sample, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+
+    fn state_with_keys(n: usize, keys: impl IntoIterator<Item = u64>) -> ReservoirSampleState {
+        let mut state = ReservoirSampleState {
+            n,
+            sample: BinaryHeap::new(),
+        };
+        for key in keys {
+            if state.accepts(key) {
+                state.update((key, ScalarValue::UInt64(key))).unwrap();
+            }
+        }
+        state
+    }
+
+    #[test]
+    fn keeps_smallest_keys() {
+        let mut state = state_with_keys(3, [50, 10, 40, 20, 30]);
+        let (sample, valid) = state.finalize().unwrap();
+        assert!(valid);
+        assert_eq!(
+            vec![
+                ScalarValue::UInt64(10),
+                ScalarValue::UInt64(20),
+                ScalarValue::UInt64(30)
+            ],
+            sample
+        );
+    }
+
+    #[test]
+    fn merge_keeps_smallest_keys_across_states() {
+        let mut a = state_with_keys(3, [5, 60, 70]);
+        let mut b = state_with_keys(3, [20, 1, 80]);
+        a.merge(&mut b).unwrap();
+
+        let (sample, _) = a.finalize().unwrap();
+        assert_eq!(
+            vec![
+                ScalarValue::UInt64(1),
+                ScalarValue::UInt64(5),
+                ScalarValue::UInt64(20)
+            ],
+            sample
+        );
+    }
+
+    #[test]
+    fn empty_is_invalid() {
+        let mut state = state_with_keys(3, []);
+        let (_, valid) = state.finalize().unwrap();
+        assert!(!valid);
+    }
+}
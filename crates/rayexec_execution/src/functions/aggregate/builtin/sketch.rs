@@ -0,0 +1,416 @@
+//! Mergeable sketches built from hashes of the input values.
+
+use std::collections::BTreeSet;
+
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::array::Array;
+use crate::arrays::bitmap::Bitmap;
+use crate::arrays::datatype::{DataType, DataTypeId, ListTypeMeta};
+use crate::arrays::executor::aggregate::AggregateState;
+use crate::arrays::executor::scalar::HashExecutor;
+use crate::arrays::storage::{ListItemMetadata, ListStorage, PrimitiveStorage};
+use crate::expr::Expression;
+use crate::functions::aggregate::states::{
+    primitive_finalize,
+    AggregateGroupStates,
+    TypedAggregateGroupStates,
+};
+use crate::functions::aggregate::{
+    AggregateFunction,
+    AggregateFunctionImpl,
+    ChunkGroupAddressIter,
+    PlannedAggregateFunction,
+};
+use crate::functions::documentation::{Category, Documentation};
+use crate::functions::{plan_check_num_args, plan_check_num_args_one_of, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+use crate::optimizer::expr_rewrite::const_fold::ConstFold;
+use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
+
+/// Number of hashes in a minhash signature if not provided.
+const DEFAULT_MINHASH_SIZE: usize = 128;
+
+/// Number of hashes kept in a theta sketch. Gives a relative standard error of
+/// about 1.6%.
+const THETA_SKETCH_SIZE: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Minhash;
+
+impl FunctionInfo for Minhash {
+    fn name(&self) -> &'static str {
+        "minhash"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Any],
+                variadic_arg: None,
+                return_type: DataTypeId::List,
+                doc: Some(&Documentation {
+                    category: Category::Aggregate,
+                    description: "Compute a minhash signature of 128 hashes for the set of non-NULL input values.",
+                    arguments: &["input"],
+                    example: None,
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Any, DataTypeId::Int64],
+                variadic_arg: None,
+                return_type: DataTypeId::List,
+                doc: Some(&Documentation {
+                    category: Category::Aggregate,
+                    description: "Compute a minhash signature of 'k' hashes for the set of non-NULL input values. Signatures can be compared with 'minhash_similarity' to estimate the Jaccard similarity of two sets.",
+                    arguments: &["input", "k"],
+                    example: None,
+                }),
+            },
+        ]
+    }
+}
+
+impl AggregateFunction for Minhash {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_check_num_args_one_of(self, &inputs, [1, 2])?;
+
+        let k = match inputs.get(1) {
+            Some(input) => {
+                if !input.is_const_foldable() {
+                    return Err(RayexecError::new(
+                        "Second argument to MINHASH must be constant",
+                    ));
+                }
+                let k = ConstFold::rewrite(table_list, input.clone())?
+                    .try_into_scalar()?
+                    .try_as_usize()?;
+                if k == 0 {
+                    return Err(RayexecError::new(
+                        "Signature size for MINHASH must be greater than zero",
+                    ));
+                }
+                k
+            }
+            None => DEFAULT_MINHASH_SIZE,
+        };
+
+        Ok(PlannedAggregateFunction {
+            function: Box::new(*self),
+            return_type: DataType::List(ListTypeMeta::new(DataType::UInt64)),
+            inputs,
+            function_impl: Box::new(MinhashImpl { k }),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinhashImpl {
+    /// Number of hashes in the signature.
+    pub k: usize,
+}
+
+impl AggregateFunctionImpl for MinhashImpl {
+    fn new_states(&self) -> Box<dyn AggregateGroupStates> {
+        let k = self.k;
+
+        Box::new(TypedAggregateGroupStates::new(
+            move || MinhashState {
+                k,
+                signature: Vec::new(),
+            },
+            hashed_update::<MinhashState, Vec<u64>>,
+            minhash_finalize,
+        ))
+    }
+}
+
+fn minhash_finalize(states: &mut [MinhashState]) -> Result<Array> {
+    let mut hashes = Vec::new();
+    let mut metadata = Vec::with_capacity(states.len());
+    let mut validity = Bitmap::new_with_all_true(states.len());
+
+    for (idx, state) in states.iter_mut().enumerate() {
+        let (signature, valid) = state.finalize()?;
+        if !valid {
+            validity.set_unchecked(idx, false);
+        }
+        metadata.push(ListItemMetadata {
+            offset: hashes.len() as i32,
+            len: signature.len() as i32,
+        });
+        hashes.extend(signature);
+    }
+
+    let child = Array::new_with_array_data(DataType::UInt64, PrimitiveStorage::from(hashes));
+    let data = ListStorage::try_new(metadata, child)?;
+
+    Ok(Array::new_with_validity_and_array_data(
+        DataType::List(ListTypeMeta::new(DataType::UInt64)),
+        validity,
+        data,
+    ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApproxCountDistinct;
+
+impl FunctionInfo for ApproxCountDistinct {
+    fn name(&self) -> &'static str {
+        "approx_count_distinct"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Any],
+            variadic_arg: None,
+            return_type: DataTypeId::Int64,
+            doc: Some(&Documentation {
+                category: Category::Aggregate,
+                description: "Estimate the number of distinct non-NULL input values using a theta sketch. Counts are exact for small inputs.",
+                arguments: &["input"],
+                example: None,
+            }),
+        }]
+    }
+}
+
+impl AggregateFunction for ApproxCountDistinct {
+    fn plan(
+        &self,
+        _table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedAggregateFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+
+        Ok(PlannedAggregateFunction {
+            function: Box::new(*self),
+            return_type: DataType::Int64,
+            inputs,
+            function_impl: Box::new(ApproxCountDistinctImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApproxCountDistinctImpl;
+
+impl AggregateFunctionImpl for ApproxCountDistinctImpl {
+    fn new_states(&self) -> Box<dyn AggregateGroupStates> {
+        Box::new(TypedAggregateGroupStates::new(
+            || ThetaSketchState {
+                k: THETA_SKETCH_SIZE,
+                hashes: BTreeSet::new(),
+            },
+            hashed_update::<ThetaSketchState, i64>,
+            |states: &mut [ThetaSketchState]| primitive_finalize(DataType::Int64, states),
+        ))
+    }
+}
+
+/// Update states with the hashes of the non-NULL values in the first input.
+fn hashed_update<State, Output>(
+    inputs: &[&Array],
+    mapping: ChunkGroupAddressIter,
+    states: &mut [State],
+) -> Result<()>
+where
+    State: AggregateState<u64, Output>,
+{
+    let array = inputs[0];
+    let mut hashes = vec![0; array.logical_len()];
+    HashExecutor::hash_no_combine(array, &mut hashes)?;
+
+    for mapping in mapping {
+        if array.is_valid(mapping.from_row) != Some(true) {
+            continue;
+        }
+        states[mapping.to_state].update(hashes[mapping.from_row])?;
+    }
+
+    Ok(())
+}
+
+/// Derive the hash for the `idx`th hash function in a minhash signature from
+/// the hash of a value.
+fn minhash_hash(hash: u64, idx: usize) -> u64 {
+    // splitmix64 finalizer, offset by the index.
+    let mut z = hash.wrapping_add((idx as u64 + 1).wrapping_mul(0x9e3779b97f4a7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Minhash signature for a single group.
+///
+/// Each position in the signature holds the minimum hash seen for a different
+/// hash function. Merging takes the minimum of each position.
+#[derive(Debug)]
+pub struct MinhashState {
+    k: usize,
+    /// Signature, empty if we haven't received any input.
+    signature: Vec<u64>,
+}
+
+impl AggregateState<u64, Vec<u64>> for MinhashState {
+    fn merge(&mut self, other: &mut Self) -> Result<()> {
+        if self.signature.is_empty() {
+            std::mem::swap(&mut self.signature, &mut other.signature);
+            return Ok(());
+        }
+
+        for (a, b) in self.signature.iter_mut().zip(&other.signature) {
+            *a = (*a).min(*b);
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self, input: u64) -> Result<()> {
+        if self.signature.is_empty() {
+            self.signature = vec![u64::MAX; self.k];
+        }
+
+        for (idx, min) in self.signature.iter_mut().enumerate() {
+            *min = (*min).min(minhash_hash(input, idx));
+        }
+
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(Vec<u64>, bool)> {
+        let signature = std::mem::take(&mut self.signature);
+        let valid = !signature.is_empty();
+        Ok((signature, valid))
+    }
+}
+
+/// Theta sketch (k minimum values) for a single group.
+///
+/// Keeps the `k` smallest distinct hashes seen. If fewer than `k` hashes have
+/// been seen, the count is exact. Otherwise the number of distinct values is
+/// estimated from how densely the kept hashes cover the hash space.
+#[derive(Debug)]
+pub struct ThetaSketchState {
+    k: usize,
+    hashes: BTreeSet<u64>,
+}
+
+impl ThetaSketchState {
+    fn insert(&mut self, hash: u64) {
+        if self.hashes.len() >= self.k {
+            match self.hashes.last() {
+                Some(&largest) if hash < largest => (),
+                _ => return,
+            }
+        }
+
+        if self.hashes.insert(hash) && self.hashes.len() > self.k {
+            self.hashes.pop_last();
+        }
+    }
+
+    fn estimate(&self) -> i64 {
+        if self.hashes.len() < self.k {
+            return self.hashes.len() as i64;
+        }
+
+        let theta = match self.hashes.last() {
+            Some(&largest) => largest as f64 / u64::MAX as f64,
+            None => return 0,
+        };
+
+        ((self.k - 1) as f64 / theta).round() as i64
+    }
+}
+
+impl AggregateState<u64, i64> for ThetaSketchState {
+    fn merge(&mut self, other: &mut Self) -> Result<()> {
+        for hash in std::mem::take(&mut other.hashes) {
+            self.insert(hash);
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, input: u64) -> Result<()> {
+        self.insert(input);
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(i64, bool)> {
+        Ok((self.estimate(), true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minhash_state(k: usize, values: impl IntoIterator<Item = u64>) -> MinhashState {
+        let mut state = MinhashState {
+            k,
+            signature: Vec::new(),
+        };
+        for value in values {
+            state.update(value).unwrap();
+        }
+        state
+    }
+
+    fn theta_state(k: usize, values: impl IntoIterator<Item = u64>) -> ThetaSketchState {
+        let mut state = ThetaSketchState {
+            k,
+            hashes: BTreeSet::new(),
+        };
+        for value in values {
+            // Values are already spread out, treat them as hashes.
+            state.update(minhash_hash(value, 0)).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn minhash_merge_matches_single_state() {
+        let mut expected = minhash_state(16, 0..100);
+
+        let mut a = minhash_state(16, 0..40);
+        let mut b = minhash_state(16, 40..100);
+        a.merge(&mut b).unwrap();
+
+        assert_eq!(expected.finalize().unwrap(), a.finalize().unwrap());
+    }
+
+    #[test]
+    fn minhash_similarity_of_overlapping_sets() {
+        // Jaccard similarity of 500 / 2000.
+        let (a, _) = minhash_state(1024, 0..1000).finalize().unwrap();
+        let (b, _) = minhash_state(1024, 500..2000).finalize().unwrap();
+
+        let same = a.iter().zip(&b).filter(|(a, b)| a == b).count();
+        let similarity = same as f64 / 1024.0;
+        assert!((similarity - 0.25).abs() < 0.05, "{similarity}");
+    }
+
+    #[test]
+    fn theta_exact_for_small_inputs() {
+        let state = theta_state(64, (0..50).chain(0..50));
+        assert_eq!(50, state.estimate());
+    }
+
+    #[test]
+    fn theta_estimate_with_merge() {
+        let mut a = theta_state(THETA_SKETCH_SIZE, 0..60_000);
+        let mut b = theta_state(THETA_SKETCH_SIZE, 40_000..100_000);
+        a.merge(&mut b).unwrap();
+
+        let estimate = a.estimate() as f64;
+        assert!(
+            (estimate - 100_000.0).abs() / 100_000.0 < 0.05,
+            "{estimate}"
+        );
+    }
+}
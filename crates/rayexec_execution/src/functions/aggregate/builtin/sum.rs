@@ -107,6 +107,10 @@ impl AggregateFunction for Sum {
             function_impl,
         })
     }
+
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        Some(Box::new(*self))
+    }
 }
 
 #[derive(Debug, Clone)]
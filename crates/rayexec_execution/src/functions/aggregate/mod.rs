@@ -23,6 +23,28 @@ pub trait AggregateFunction: FunctionInfo + Debug + Sync + Send + DynClone {
         table_list: &TableList,
         inputs: Vec<Expression>,
     ) -> Result<PlannedAggregateFunction>;
+
+    /// If this is an ordered-set aggregate requiring a `WITHIN GROUP (ORDER BY
+    /// ...)` clause.
+    ///
+    /// Ordered-set aggregates are planned with the ordering expressions as the
+    /// leading inputs, and expect inputs to be provided in that order.
+    fn is_ordered_set(&self) -> bool {
+        false
+    }
+
+    /// Returns the aggregate used to combine partial results of this
+    /// aggregate.
+    ///
+    /// If Some, this aggregate may be computed in two phases, with the
+    /// returned aggregate computing the final result from the partial results
+    /// of each group. E.g. partial `COUNT`s are combined with `SUM`.
+    ///
+    /// Planning the returned aggregate with the partial results must produce
+    /// the same return type as this aggregate.
+    fn partial_combine(&self) -> Option<Box<dyn AggregateFunction>> {
+        None
+    }
 }
 
 impl Clone for Box<dyn AggregateFunction> {
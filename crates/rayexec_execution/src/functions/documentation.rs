@@ -11,6 +11,9 @@ pub enum Category {
     String,
     Regexp,
     Binary,
+    Json,
+    TextSearch,
+    Spatial,
     Table,
 }
 
@@ -1,3 +1,9 @@
+//! Implicit cast rules used during binding.
+//!
+//! These rules are used anywhere the binder needs to reconcile types without
+//! the user explicitly casting: function and operator arguments (including
+//! comparisons), UNION branches, and INSERT targets.
+
 use crate::arrays::datatype::{DataType, DataTypeId};
 
 /// Score that should be used if no cast is needed.
@@ -33,9 +39,14 @@ pub const fn implicit_cast_score(have: &DataType, want: DataTypeId) -> Option<u3
         DataType::Float32 => return float32_cast_score(want),
         DataType::Float64 => return float64_cast_score(want),
 
+        // Decimal casts
+        DataType::Decimal64(_) => return decimal64_cast_score(want),
+        DataType::Decimal128(_) => return decimal128_cast_score(want),
+
         // String casts
         DataType::Utf8 => match want {
-            DataTypeId::Int8
+            DataTypeId::Boolean
+            | DataTypeId::Int8
             | DataTypeId::Int16
             | DataTypeId::Int32
             | DataTypeId::Int64
@@ -46,7 +57,9 @@ pub const fn implicit_cast_score(have: &DataType, want: DataTypeId) -> Option<u3
             | DataTypeId::Decimal64
             | DataTypeId::Decimal128
             | DataTypeId::Interval
-            | DataTypeId::Timestamp => return Some(target_score(want)),
+            | DataTypeId::Date32
+            | DataTypeId::Timestamp
+            | DataTypeId::Json => return Some(target_score(want)),
 
             // Non-zero since it's a valid cast, just we would prefer something
             // else.
@@ -222,16 +235,112 @@ const fn float64_cast_score(want: DataTypeId) -> Option<u32> {
     })
 }
 
+/// Score for casting a decimal to a float.
+///
+/// Lower than the score for casting a float to a decimal so that mixing
+/// decimals and floats (e.g. `sum(a) * 0.5`) keeps the decimal type. Floats
+/// are only used when nothing accepts a decimal.
+const DECIMAL_TO_FLOAT_SCORE: u32 = 110;
+
+const fn decimal64_cast_score(want: DataTypeId) -> Option<u32> {
+    Some(match want {
+        DataTypeId::Decimal128 => target_score(want),
+        DataTypeId::Float32 | DataTypeId::Float64 => DECIMAL_TO_FLOAT_SCORE,
+        _ => return None,
+    })
+}
+
+const fn decimal128_cast_score(want: DataTypeId) -> Option<u32> {
+    Some(match want {
+        DataTypeId::Float32 | DataTypeId::Float64 => DECIMAL_TO_FLOAT_SCORE,
+        _ => return None,
+    })
+}
+
+/// Get the best common type that all inputs can be implicitly cast to. Returns
+/// None if there isn't a common type.
+///
+/// The input types themselves are tried first. If none of them work, we'll try
+/// some wider types that inputs may be able to be cast to (e.g. Int8 and UInt8
+/// can both be cast to Int16).
+pub fn common_supertype(inputs: &[&DataType]) -> Option<DataTypeId> {
+    /// Types to try if none of the input types are valid for all inputs.
+    const SUPERTYPE_FALLBACKS: &[DataTypeId] = &[
+        DataTypeId::Int16,
+        DataTypeId::Int32,
+        DataTypeId::Int64,
+        DataTypeId::Float64,
+    ];
+
+    // Total score for casting all inputs to the test type. None if there's an
+    // input that can't be cast to the test type.
+    let total_score = |test_type: DataTypeId| -> Option<u32> {
+        let mut total = 0;
+        for input in inputs {
+            if input.datatype_id() == test_type {
+                // Arbitrary.
+                total += 200;
+                continue;
+            }
+            total += implicit_cast_score(input, test_type)?;
+        }
+        Some(total)
+    };
+
+    let best_of = |test_types: &mut dyn Iterator<Item = DataTypeId>| {
+        let mut best_type = None;
+        let mut best_total_score = 0;
+
+        for test_type in test_types {
+            if let Some(score) = total_score(test_type) {
+                if score > best_total_score {
+                    best_type = Some(test_type);
+                    best_total_score = score;
+                }
+            }
+        }
+
+        best_type
+    };
+
+    best_of(&mut inputs.iter().map(|input| input.datatype_id()))
+        .or_else(|| best_of(&mut SUPERTYPE_FALLBACKS.iter().copied()))
+}
+
+/// Check if a value of type `have` can be assigned to a column of type `want`,
+/// e.g. when inserting into a table.
+///
+/// This is more permissive than implicit casting. Narrowing numeric casts are
+/// allowed (and will error at execution time if a value doesn't fit), strings
+/// can be assigned to any column (and will error at execution time if a value
+/// can't be parsed), and anything can be assigned to a string column.
+pub fn assignment_cast_allowed(have: &DataType, want: &DataType) -> bool {
+    if have.datatype_id() == want.datatype_id() {
+        return true;
+    }
+
+    if implicit_cast_score(have, want.datatype_id()).is_some() {
+        return true;
+    }
+
+    if have.is_numeric() && want.is_numeric() {
+        return true;
+    }
+
+    have.is_utf8() || want.is_utf8()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::arrays::datatype::{TimeUnit, TimestampTypeMeta};
+    use crate::arrays::datatype::{DecimalTypeMeta, TimeUnit, TimestampTypeMeta};
 
     #[test]
     fn implicit_cast_from_utf8() {
         assert!(implicit_cast_score(&DataType::Utf8, DataTypeId::Int32).is_some());
         assert!(implicit_cast_score(&DataType::Utf8, DataTypeId::Timestamp).is_some());
         assert!(implicit_cast_score(&DataType::Utf8, DataTypeId::Interval).is_some());
+        assert!(implicit_cast_score(&DataType::Utf8, DataTypeId::Json).is_some());
     }
 
     #[test]
@@ -277,4 +386,99 @@ mod tests {
             "int64: {to_int64_score}, float32: {to_float32_score}"
         );
     }
+
+    #[test]
+    fn best_datatype_for_ints_and_floats() {
+        let inputs = &[&DataType::Int64, &DataType::Float64, &DataType::Int64];
+        let best = common_supertype(inputs);
+        assert_eq!(Some(DataTypeId::Float64), best);
+    }
+
+    #[test]
+    fn best_datatype_for_floats() {
+        let inputs = &[&DataType::Float64, &DataType::Float64, &DataType::Float64];
+        let best = common_supertype(inputs);
+        assert_eq!(Some(DataTypeId::Float64), best);
+    }
+
+    #[test]
+    fn best_datatype_fallback_to_wider_type() {
+        let inputs = &[&DataType::Int8, &DataType::UInt8];
+        let best = common_supertype(inputs);
+        assert_eq!(Some(DataTypeId::Int16), best);
+    }
+
+    #[test]
+    fn best_datatype_no_common_type() {
+        let inputs = &[&DataType::Int64, &DataType::Boolean];
+        let best = common_supertype(inputs);
+        assert_eq!(None, best);
+    }
+
+    #[test]
+    fn decimal_casts() {
+        // Valid
+        assert!(implicit_cast_score(
+            &DataType::Decimal64(DecimalTypeMeta::new(10, 2)),
+            DataTypeId::Decimal128
+        )
+        .is_some());
+        assert!(implicit_cast_score(
+            &DataType::Decimal128(DecimalTypeMeta::new(20, 2)),
+            DataTypeId::Float64
+        )
+        .is_some());
+
+        // Not valid
+        assert!(implicit_cast_score(
+            &DataType::Decimal128(DecimalTypeMeta::new(20, 2)),
+            DataTypeId::Decimal64
+        )
+        .is_none());
+        assert!(implicit_cast_score(
+            &DataType::Decimal64(DecimalTypeMeta::new(10, 2)),
+            DataTypeId::Int64
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn prefer_float_to_decimal() {
+        let to_decimal = implicit_cast_score(&DataType::Float64, DataTypeId::Decimal64).unwrap();
+        let to_float = implicit_cast_score(
+            &DataType::Decimal64(DecimalTypeMeta::new(10, 2)),
+            DataTypeId::Float64,
+        )
+        .unwrap();
+
+        assert!(
+            to_decimal > to_float,
+            "to decimal: {to_decimal}, to float: {to_float}"
+        );
+    }
+
+    #[test]
+    fn assignment_casts() {
+        // Narrowing numerics
+        assert!(assignment_cast_allowed(&DataType::Int64, &DataType::Int8));
+        assert!(assignment_cast_allowed(
+            &DataType::Float64,
+            &DataType::Int32
+        ));
+        // Anything to string
+        assert!(assignment_cast_allowed(&DataType::Boolean, &DataType::Utf8));
+        assert!(assignment_cast_allowed(&DataType::Null, &DataType::Date32));
+        // Strings to anything
+        assert!(assignment_cast_allowed(&DataType::Utf8, &DataType::Float32));
+
+        // Not valid
+        assert!(!assignment_cast_allowed(
+            &DataType::Boolean,
+            &DataType::Date32
+        ));
+        assert!(!assignment_cast_allowed(
+            &DataType::Int32,
+            &DataType::Boolean
+        ));
+    }
 }
@@ -5,18 +5,32 @@ pub mod implicit;
 pub mod proto;
 pub mod scalar;
 pub mod table;
+#[cfg(feature = "wasm_udf")]
+pub mod wasm;
 
 use std::borrow::Borrow;
-use std::fmt::Display;
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+use std::sync::LazyLock;
 
 use documentation::Documentation;
 use fmtutil::IntoDisplayableSlice;
-use implicit::{implicit_cast_score, NO_CAST_SCORE};
-use rayexec_error::{RayexecError, Result};
+use implicit::{common_supertype, implicit_cast_score, NO_CAST_SCORE};
+use parking_lot::Mutex;
+use rayexec_error::{ErrorKind, RayexecError, Result};
 
 use crate::arrays::datatype::{DataType, DataTypeId};
 
 /// Function signature.
+///
+/// Arguments (both positional and variadic) declared as `DataTypeId::Any` are
+/// generic over their input type. All arguments declared as `Any` in a single
+/// signature are bound to the same type. If the inputs for those arguments
+/// don't all share the same type, the signature will not be considered an
+/// exact match, and instead a candidate signature search will be triggered to
+/// determine a common type that all of them can be cast to. This simplifies
+/// planning and function implementations since only a single type needs to be
+/// handled for something like `greatest(a, b, ...)`.
 // TODO: Include named args. Also make sure to update PartialEq too.
 #[derive(Debug, Clone, Copy)]
 pub struct Signature {
@@ -27,11 +41,8 @@ pub struct Signature {
     ///
     /// If None, the function is not considered variadic.
     ///
-    /// If the variadic type is `DataTypeId::Any`, and the user provides 1 or
-    /// more variadic arguments, the signature will never be considered an exact
-    /// match, and instead a candidate signature search will be triggered. This
-    /// allows us to determine a single data type that all variadic args can be
-    /// cast to, which simplifies planning and function implementation.
+    /// Variadic args may be `DataTypeId::Any`, see below for how that gets
+    /// bound.
     pub variadic_arg: Option<DataTypeId>,
 
     /// The expected return type.
@@ -68,38 +79,87 @@ impl Signature {
         self.variadic_arg.is_some()
     }
 
+    /// Get the expected type for the argument at the given index.
+    ///
+    /// Returns None if the index is out of bounds for a non-variadic
+    /// signature.
+    fn arg_type(&self, idx: usize) -> Option<DataTypeId> {
+        self.positional_args.get(idx).copied().or(self.variadic_arg)
+    }
+
+    /// Check if the number of inputs is valid for this signature.
+    fn num_args_valid(&self, num: usize) -> bool {
+        if self.is_variadic() {
+            num >= self.positional_args.len()
+        } else {
+            num == self.positional_args.len()
+        }
+    }
+
     /// Return if inputs given data types exactly satisfy the signature.
+    ///
+    /// Inputs for arguments declared as `Any` must all have the same type to
+    /// be an exact match.
     fn exact_match(&self, inputs: &[DataType]) -> bool {
-        if self.positional_args.len() != inputs.len() && !self.is_variadic() {
+        if !self.num_args_valid(inputs.len()) {
             return false;
         }
 
-        for (&expected, have) in self.positional_args.iter().zip(inputs.iter()) {
-            if expected == DataTypeId::Any {
-                continue;
+        let mut any_type = None;
+
+        for (idx, have) in inputs.iter().enumerate() {
+            match self.arg_type(idx) {
+                Some(DataTypeId::Any) => match any_type {
+                    Some(bound) if bound != have.datatype_id() => return false,
+                    Some(_) => (),
+                    None => any_type = Some(have.datatype_id()),
+                },
+                Some(expected) if expected == have.datatype_id() => (),
+                _ => return false,
             }
+        }
+
+        true
+    }
+}
+
+/// Argument lists for signatures created at runtime, see
+/// `intern_positional_args`.
+static INTERNED_ARGS: LazyLock<Mutex<HashSet<&'static [DataTypeId]>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Get a static slice of argument types for a signature created at runtime
+/// (e.g. for a UDF).
+///
+/// Signatures only hold static slices. Slices are interned so that each
+/// distinct argument list is allocated once for the life of the process, no
+/// matter how many functions are created with it.
+pub fn intern_positional_args(args: &[DataTypeId]) -> &'static [DataTypeId] {
+    let mut interned = INTERNED_ARGS.lock();
+    if let Some(existing) = interned.get(args) {
+        return existing;
+    }
+    let args: &'static [DataTypeId] = Box::leak(args.to_vec().into_boxed_slice());
+    interned.insert(args);
+    args
+}
 
-            if have.datatype_id() != expected {
-                return false;
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (idx, arg) in self.positional_args.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
             }
+            write!(f, "{arg}")?;
         }
-
-        // Check variadic.
-        if let Some(expected) = self.variadic_arg {
-            let remaining = &inputs[self.positional_args.len()..];
-            for have in remaining {
-                if expected == DataTypeId::Any {
-                    // If we're matching against any, we're never an exact match.
-                    return false;
-                }
-
-                if have.datatype_id() != expected {
-                    return false;
-                }
+        if let Some(variadic) = self.variadic_arg {
+            if !self.positional_args.is_empty() {
+                write!(f, ", ")?;
             }
+            write!(f, "{variadic}...")?;
         }
-
-        true
+        write!(f, ") -> {}", self.return_type)
     }
 }
 
@@ -113,10 +173,34 @@ impl PartialEq for Signature {
 
 impl Eq for Signature {}
 
+/// How consistent a function's output is for the same inputs.
+///
+/// Ordered from least to most volatile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FunctionVolatility {
+    /// The function always returns the same value for the same arguments.
+    ///
+    /// Calls with constant arguments are evaluated during planning.
+    Immutable,
+    /// The function returns the same value for the same arguments within a
+    /// single query, but may return something different in another query
+    /// (e.g. depends on session settings or the current time).
+    ///
+    /// Never evaluated during planning since plans may be reused across
+    /// queries.
+    Stable,
+    /// Every call to this function with the same arguemnts is not guaranteed to
+    /// return the same value.
+    ///
+    /// The optimizer will never constant fold or deduplicate calls to volatile
+    /// functions.
+    Volatile,
+}
+
 /// Trait for defining informating about functions.
 pub trait FunctionInfo {
     /// Name of the function.
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
     /// Aliases for the function.
     ///
@@ -139,6 +223,13 @@ pub trait FunctionInfo {
     /// function given some inputs, and how we should handle implicit casting.
     fn signatures(&self) -> &[Signature];
 
+    /// Volatility of the function.
+    ///
+    /// Defaults to immutable.
+    fn volatility(&self) -> FunctionVolatility {
+        FunctionVolatility::Immutable
+    }
+
     /// Get the signature for a function if it's an exact match for the inputs.
     ///
     /// If there are no exact signatures for these types, None will be retuned.
@@ -194,12 +285,7 @@ impl CandidateSignature {
 
         let mut buf = Vec::new();
         for (idx, sig) in sigs.iter().enumerate() {
-            if !Self::compare_and_fill_types(
-                inputs,
-                sig.positional_args,
-                sig.variadic_arg,
-                &mut buf,
-            ) {
+            if !Self::compare_and_fill_types(inputs, sig, &mut buf) {
                 continue;
             }
 
@@ -223,18 +309,37 @@ impl CandidateSignature {
     /// buffer with the cast type.
     ///
     /// Returns true if everything is able to be implicitly cast, false otherwise.
-    fn compare_and_fill_types(
-        have: &[DataType],
-        want: &[DataTypeId],
-        variadic: Option<DataTypeId>,
-        buf: &mut Vec<CastType>,
-    ) -> bool {
-        if have.len() != want.len() && variadic.is_none() {
+    fn compare_and_fill_types(have: &[DataType], sig: &Signature, buf: &mut Vec<CastType>) -> bool {
+        if !sig.num_args_valid(have.len()) {
             return false;
         }
         buf.clear();
 
-        for (have, &want) in have.iter().zip(want.iter()) {
+        // Find a common data type to use in place of Any for all arguments
+        // declared as Any.
+        let any_inputs: Vec<_> = have
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| sig.arg_type(*idx) == Some(DataTypeId::Any))
+            .map(|(_, have)| have)
+            .collect();
+
+        let any_type = if any_inputs.is_empty() {
+            None
+        } else {
+            match common_supertype(&any_inputs) {
+                Some(typ) => Some(typ),
+                None => return false, // No common data type for all Any args.
+            }
+        };
+
+        for (idx, have) in have.iter().enumerate() {
+            let want = match sig.arg_type(idx) {
+                Some(DataTypeId::Any) => any_type.expect("any type to be bound"),
+                Some(want) => want,
+                None => return false,
+            };
+
             if have.datatype_id() == want {
                 buf.push(CastType::NoCastNeeded);
                 continue;
@@ -249,84 +354,8 @@ impl CandidateSignature {
             return false;
         }
 
-        // Check variadic.
-        let remaining = &have[want.len()..];
-        match variadic {
-            Some(expected) if !remaining.is_empty() => {
-                let expected = if expected == DataTypeId::Any {
-                    // Find a common data type to use in place of Any.
-                    match Self::best_datatype_for_variadic_any(remaining) {
-                        Some(typ) => typ,
-                        None => return false, // No common data type for all remaining args.
-                    }
-                } else {
-                    expected
-                };
-
-                for have in remaining {
-                    if have.datatype_id() == expected {
-                        buf.push(CastType::NoCastNeeded);
-                        continue;
-                    }
-
-                    let score = implicit_cast_score(have, expected);
-                    if let Some(score) = score {
-                        buf.push(CastType::Cast {
-                            to: expected,
-                            score,
-                        });
-                        continue;
-                    }
-
-                    return false;
-                }
-
-                // Everything's valid, casts have been pushed to the buffer.
-                true
-            }
-            _ => {
-                // No variadics to check. If we got this far, everything's valid
-                // for this signature.
-                true
-            }
-        }
-    }
-
-    /// Get the best common data type that we can cast to for the given inputs. Returns None
-    /// if there isn't a common data type.
-    fn best_datatype_for_variadic_any(inputs: &[DataType]) -> Option<DataTypeId> {
-        let mut best_type = None;
-        let mut best_total_score = 0;
-
-        for input in inputs {
-            let test_type = input.datatype_id();
-            let mut total_score = 0;
-            let mut valid = true;
-
-            for input in inputs {
-                if input.datatype_id() == test_type {
-                    // Arbitrary.
-                    total_score += 200;
-                    continue;
-                }
-
-                let score = implicit_cast_score(input, test_type);
-                match score {
-                    Some(score) => total_score += score,
-                    None => {
-                        // Test type is not a valid cast for this input.
-                        valid = false;
-                    }
-                }
-            }
-
-            if total_score > best_total_score && valid {
-                best_type = Some(test_type);
-                best_total_score = total_score;
-            }
-        }
-
-        best_type
+        // Everything's valid, casts have been pushed to the buffer.
+        true
     }
 }
 
@@ -367,24 +396,84 @@ pub fn plan_check_num_args_one_of<T, const N: usize>(
 
 /// Return an error indicating the input types we got are not ones we can
 /// handle.
-// TODO: Include valid signatures in the error
 pub fn invalid_input_types_error<T>(func: &impl FunctionInfo, got: &[T]) -> RayexecError
 where
     T: Borrow<DataType> + Display,
 {
-    // TODO: Include relevant valid signatures. What "relevant" means and how we
-    // determine that is stil tbd.
+    // TODO: Include only relevant valid signatures. What "relevant" means and
+    // how we determine that is stil tbd.
     RayexecError::new(format!(
-        "Got invalid type(s) '{}' for '{}'",
+        "Got invalid type(s) '{}' for '{}'{}",
         got.display_with_brackets(),
-        func.name()
+        func.name(),
+        SignaturesHint(func),
+    ))
+    .with_kind(ErrorKind::TypeMismatch)
+}
+
+/// Return an error indicating that overload resolution failed to find a
+/// signature for the given inputs, even when considering implicit casts.
+pub fn no_matching_signature_error<F>(func: &F, inputs: &[DataType]) -> RayexecError
+where
+    F: FunctionInfo + ?Sized,
+{
+    RayexecError::new(format!(
+        "Invalid inputs to '{}': {}{}",
+        func.name(),
+        inputs.display_with_brackets(),
+        SignaturesHint(func),
     ))
+    .with_kind(ErrorKind::TypeMismatch)
+}
+
+/// Formats the available signatures for a function for use in error messages.
+struct SignaturesHint<'a, F: ?Sized>(&'a F);
+
+impl<F> fmt::Display for SignaturesHint<'_, F>
+where
+    F: FunctionInfo + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sigs = self.0.signatures();
+        if sigs.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, "\nAvailable signatures:")?;
+        for sig in sigs {
+            write!(f, "\n    {}{sig}", self.0.name())?;
+        }
+
+        let has_any = sigs.iter().any(|sig| {
+            sig.positional_args.contains(&DataTypeId::Any)
+                || sig.variadic_arg == Some(DataTypeId::Any)
+        });
+        if has_any {
+            write!(
+                f,
+                "\nArguments of type Any must all be castable to a single common type"
+            )?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn interned_args_shared() {
+        let a = intern_positional_args(&[DataTypeId::Int64, DataTypeId::Utf8]);
+        let b = intern_positional_args(&[DataTypeId::Int64, DataTypeId::Utf8]);
+        assert_eq!(&[DataTypeId::Int64, DataTypeId::Utf8], a);
+        assert!(std::ptr::eq(a, b));
+
+        let c = intern_positional_args(&[DataTypeId::Utf8, DataTypeId::Int64]);
+        assert!(!std::ptr::eq(a, c));
+    }
+
     #[test]
     fn find_candidate_no_match() {
         let inputs = &[DataType::Int64];
@@ -443,16 +532,100 @@ mod tests {
     }
 
     #[test]
-    fn best_datatype_for_ints_and_floats() {
-        let inputs = &[DataType::Int64, DataType::Float64, DataType::Int64];
-        let best = CandidateSignature::best_datatype_for_variadic_any(inputs);
-        assert_eq!(Some(DataTypeId::Float64), best);
+    fn exact_match_any_requires_same_types() {
+        let sig = Signature {
+            positional_args: &[DataTypeId::Any],
+            variadic_arg: Some(DataTypeId::Any),
+            return_type: DataTypeId::Any,
+            doc: None,
+        };
+
+        assert!(sig.exact_match(&[DataType::Int64]));
+        assert!(sig.exact_match(&[DataType::Int64, DataType::Int64]));
+        assert!(!sig.exact_match(&[DataType::Int64, DataType::Int32]));
+        assert!(!sig.exact_match(&[]));
+    }
+
+    #[test]
+    fn find_candidate_positional_any_bound_to_common_type() {
+        let inputs = &[DataType::Int32, DataType::Utf8, DataType::Int64];
+        let sigs = &[Signature {
+            positional_args: &[DataTypeId::Any, DataTypeId::Utf8, DataTypeId::Any],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: None,
+        }];
+
+        let candidates = CandidateSignature::find_candidates(inputs, sigs);
+        let expected = vec![CandidateSignature {
+            signature_idx: 0,
+            casts: vec![
+                CastType::Cast {
+                    to: DataTypeId::Int64,
+                    score: implicit_cast_score(&DataType::Int32, DataTypeId::Int64).unwrap(),
+                },
+                CastType::NoCastNeeded,
+                CastType::NoCastNeeded,
+            ],
+        }];
+
+        assert_eq!(expected, candidates);
+    }
+
+    #[test]
+    fn find_candidate_positional_and_variadic_any_share_type() {
+        let inputs = &[DataType::Int64, DataType::Null, DataType::Float64];
+        let sigs = &[Signature {
+            positional_args: &[DataTypeId::Any],
+            variadic_arg: Some(DataTypeId::Any),
+            return_type: DataTypeId::Any,
+            doc: None,
+        }];
+
+        let candidates = CandidateSignature::find_candidates(inputs, sigs);
+        let expected = vec![CandidateSignature {
+            signature_idx: 0,
+            casts: vec![
+                CastType::Cast {
+                    to: DataTypeId::Float64,
+                    score: implicit_cast_score(&DataType::Int64, DataTypeId::Float64).unwrap(),
+                },
+                CastType::Cast {
+                    to: DataTypeId::Float64,
+                    score: implicit_cast_score(&DataType::Null, DataTypeId::Float64).unwrap(),
+                },
+                CastType::NoCastNeeded,
+            ],
+        }];
+
+        assert_eq!(expected, candidates);
+    }
+
+    #[test]
+    fn find_candidate_variadic_too_few_args() {
+        let inputs = &[];
+        let sigs = &[Signature {
+            positional_args: &[DataTypeId::Utf8],
+            variadic_arg: Some(DataTypeId::Utf8),
+            return_type: DataTypeId::Utf8,
+            doc: None,
+        }];
+
+        let candidates = CandidateSignature::find_candidates(inputs, sigs);
+        assert!(candidates.is_empty());
     }
 
     #[test]
-    fn best_datatype_for_floats() {
-        let inputs = &[DataType::Float64, DataType::Float64, DataType::Float64];
-        let best = CandidateSignature::best_datatype_for_variadic_any(inputs);
-        assert_eq!(Some(DataTypeId::Float64), best);
+    fn display_signature() {
+        let sig = Signature {
+            positional_args: &[DataTypeId::Utf8],
+            variadic_arg: Some(DataTypeId::Any),
+            return_type: DataTypeId::Utf8,
+            doc: None,
+        };
+        assert_eq!("(Utf8, Any...) -> Utf8", sig.to_string());
+
+        let sig = Signature::new_positional(&[], DataTypeId::Int64);
+        assert_eq!("() -> Int64", sig.to_string());
     }
 }
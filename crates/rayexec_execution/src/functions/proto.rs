@@ -1,15 +1,22 @@
 use std::collections::HashMap;
 
-use rayexec_error::{OptionExt, Result};
+use rayexec_error::{OptionExt, RayexecError, Result};
 use rayexec_proto::ProtoConv;
 
 use super::aggregate::{AggregateFunction, PlannedAggregateFunction};
 use super::copy::{CopyToArgs, CopyToFunction};
 use super::scalar::{PlannedScalarFunction, ScalarFunction};
 use super::table::{PlannedTableFunction, TableFunction};
+use crate::arrays::datatype::DataType;
 use crate::arrays::scalar::OwnedScalarValue;
 use crate::database::catalog::CatalogTx;
 use crate::database::DatabaseContext;
+use crate::expr::column_expr::ColumnExpr;
+use crate::expr::literal_expr::LiteralExpr;
+use crate::expr::Expression;
+use crate::logical::binder::table_list::TableList;
+use crate::optimizer::expr_rewrite::const_fold::ConstFold;
+use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
 use crate::proto::DatabaseProtoConv;
 
 pub const FUNCTION_LOOKUP_CATALOG: &str = "glare_catalog";
@@ -24,7 +31,7 @@ impl DatabaseProtoConv for Box<dyn ScalarFunction> {
     }
 
     fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
-        let tx = &CatalogTx {};
+        let tx = &CatalogTx::new();
         let ent = context
             .system_catalog()?
             .get_schema(tx, FUNCTION_LOOKUP_CATALOG)?
@@ -37,37 +44,6 @@ impl DatabaseProtoConv for Box<dyn ScalarFunction> {
     }
 }
 
-impl DatabaseProtoConv for PlannedScalarFunction {
-    type ProtoType = rayexec_proto::generated::functions::PlannedScalarFunction;
-
-    fn to_proto_ctx(&self, _context: &DatabaseContext) -> Result<Self::ProtoType> {
-        unimplemented!()
-        // let mut state = Vec::new();
-        // self.encode_state(&mut state)?;
-
-        // Ok(Self::ProtoType {
-        //     name: self.scalar_function().name().to_string(),
-        //     state,
-        // })
-    }
-
-    fn from_proto_ctx(_proto: Self::ProtoType, _context: &DatabaseContext) -> Result<Self> {
-        unimplemented!()
-        // let tx = &CatalogTx {};
-        // let ent = context
-        //     .system_catalog()?
-        //     .get_schema(tx, FUNCTION_LOOKUP_CATALOG)?
-        //     .required("lookup schema")?
-        //     .get_scalar_function(tx, &proto.name)?
-        //     .required("scalar function")?;
-        // let ent = ent.try_as_scalar_function_entry()?;
-
-        // let planned = ent.function.decode_state(&proto.state)?;
-
-        // Ok(planned)
-    }
-}
-
 impl DatabaseProtoConv for Box<dyn AggregateFunction> {
     type ProtoType = rayexec_proto::generated::functions::AggregateFunction;
 
@@ -78,7 +54,7 @@ impl DatabaseProtoConv for Box<dyn AggregateFunction> {
     }
 
     fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
-        let tx = &CatalogTx {};
+        let tx = &CatalogTx::new();
         let ent = context
             .system_catalog()?
             .get_schema(tx, FUNCTION_LOOKUP_CATALOG)?
@@ -91,35 +67,156 @@ impl DatabaseProtoConv for Box<dyn AggregateFunction> {
     }
 }
 
-impl DatabaseProtoConv for PlannedAggregateFunction {
-    type ProtoType = rayexec_proto::generated::functions::PlannedAggregateFunction;
+/// Encode a planned scalar function.
+///
+/// Planned functions are encoded by name and planned again when decoding.
+/// `input_types` are the datatypes of the function's inputs. Constant inputs
+/// are encoded as is since functions may specialize on them, all other inputs
+/// are encoded as just their datatype.
+pub fn encode_planned_scalar_function(
+    function: &PlannedScalarFunction,
+    input_types: &[DataType],
+) -> Result<rayexec_proto::generated::functions::PlannedScalarFunction> {
+    Ok(rayexec_proto::generated::functions::PlannedScalarFunction {
+        name: function.function.name().to_string(),
+        inputs: encode_function_inputs(&function.inputs, input_types)?,
+    })
+}
 
-    fn to_proto_ctx(&self, _context: &DatabaseContext) -> Result<Self::ProtoType> {
-        unimplemented!()
-        // let mut state = Vec::new();
-        // self.encode_state(&mut state)?;
+/// Decode a planned scalar function, returning the function along with the
+/// datatypes of its inputs.
+pub fn decode_planned_scalar_function(
+    proto: rayexec_proto::generated::functions::PlannedScalarFunction,
+    context: &DatabaseContext,
+) -> Result<(PlannedScalarFunction, Vec<DataType>)> {
+    let function = <Box<dyn ScalarFunction>>::from_proto_ctx(
+        rayexec_proto::generated::functions::ScalarFunction { name: proto.name },
+        context,
+    )?;
+
+    let (table_list, inputs, input_types) = decode_function_inputs(proto.inputs)?;
+    let planned = function.plan(&table_list, inputs)?;
+
+    Ok((planned, input_types))
+}
 
-        // Ok(Self::ProtoType {
-        //     name: self.aggregate_function().name().to_string(),
-        //     state,
-        // })
-    }
+/// Encode a planned aggregate function.
+///
+/// See `encode_planned_scalar_function`.
+pub fn encode_planned_aggregate_function(
+    function: &PlannedAggregateFunction,
+    input_types: &[DataType],
+) -> Result<rayexec_proto::generated::functions::PlannedAggregateFunction> {
+    Ok(
+        rayexec_proto::generated::functions::PlannedAggregateFunction {
+            name: function.function.name().to_string(),
+            inputs: encode_function_inputs(&function.inputs, input_types)?,
+        },
+    )
+}
 
-    fn from_proto_ctx(_proto: Self::ProtoType, _context: &DatabaseContext) -> Result<Self> {
-        unimplemented!()
-        // let tx = &CatalogTx {};
-        // let ent = context
-        //     .system_catalog()?
-        //     .get_schema(tx, FUNCTION_LOOKUP_CATALOG)?
-        //     .required("lookup schema")?
-        //     .get_aggregate_function(tx, &proto.name)?
-        //     .required("agg function")?;
-        // let ent = ent.try_as_aggregate_function_entry()?;
+/// Decode a planned aggregate function, returning the function along with the
+/// datatypes of its inputs.
+pub fn decode_planned_aggregate_function(
+    proto: rayexec_proto::generated::functions::PlannedAggregateFunction,
+    context: &DatabaseContext,
+) -> Result<(PlannedAggregateFunction, Vec<DataType>)> {
+    let function = <Box<dyn AggregateFunction>>::from_proto_ctx(
+        rayexec_proto::generated::functions::AggregateFunction { name: proto.name },
+        context,
+    )?;
+
+    let (table_list, inputs, input_types) = decode_function_inputs(proto.inputs)?;
+    let planned = function.plan(&table_list, inputs)?;
+
+    Ok((planned, input_types))
+}
 
-        // let planned = ent.function.decode_state(&proto.state)?;
+fn encode_function_inputs(
+    inputs: &[Expression],
+    input_types: &[DataType],
+) -> Result<Vec<rayexec_proto::generated::functions::PlannedFunctionInput>> {
+    use rayexec_proto::generated::functions::planned_function_input::Value;
+
+    if inputs.len() != input_types.len() {
+        return Err(RayexecError::new(format!(
+            "Expected {} input types for function inputs, got {}",
+            inputs.len(),
+            input_types.len()
+        )));
+    }
 
-        // Ok(planned)
+    inputs
+        .iter()
+        .zip(input_types)
+        .map(|(input, datatype)| {
+            // Inputs that weren't folded during optimization may still be
+            // constant.
+            let input = if input.is_const_foldable() {
+                ConstFold::rewrite(&TableList::empty(), input.clone())?
+            } else {
+                input.clone()
+            };
+
+            let value = match input {
+                Expression::Literal(lit) => Value::Constant(lit.literal.to_proto()?),
+                _ => Value::Column(datatype.to_proto()?),
+            };
+
+            Ok(rayexec_proto::generated::functions::PlannedFunctionInput { value: Some(value) })
+        })
+        .collect()
+}
+
+/// Decode function inputs into expressions that the function can be planned
+/// with.
+///
+/// Non-constant inputs are turned into columns of a single table in the
+/// returned table list.
+fn decode_function_inputs(
+    protos: Vec<rayexec_proto::generated::functions::PlannedFunctionInput>,
+) -> Result<(TableList, Vec<Expression>, Vec<DataType>)> {
+    use rayexec_proto::generated::functions::planned_function_input::Value;
+
+    let mut constants = Vec::with_capacity(protos.len());
+    let mut input_types = Vec::with_capacity(protos.len());
+    let mut column_types = Vec::new();
+
+    for proto in protos {
+        match proto.value.required("value")? {
+            Value::Constant(constant) => {
+                let constant = OwnedScalarValue::from_proto(constant)?;
+                input_types.push(constant.datatype());
+                constants.push(Some(constant));
+            }
+            Value::Column(datatype) => {
+                let datatype = DataType::from_proto(datatype)?;
+                input_types.push(datatype.clone());
+                column_types.push(datatype);
+                constants.push(None);
+            }
+        }
     }
+
+    let mut table_list = TableList::empty();
+    let column_names = (0..column_types.len())
+        .map(|idx| format!("input{idx}"))
+        .collect();
+    let table_ref = table_list.push_table(None, column_types, column_names)?;
+
+    let mut column = 0;
+    let inputs = constants
+        .into_iter()
+        .map(|constant| match constant {
+            Some(literal) => Expression::Literal(LiteralExpr { literal }),
+            None => {
+                column += 1;
+                Expression::Column(ColumnExpr::new(table_ref, column - 1))
+            }
+        })
+        .collect();
+
+    Ok((table_list, inputs, input_types))
 }
 
 impl DatabaseProtoConv for Box<dyn TableFunction> {
@@ -132,7 +229,7 @@ impl DatabaseProtoConv for Box<dyn TableFunction> {
     }
 
     fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
-        let tx = &CatalogTx {};
+        let tx = &CatalogTx::new();
         let ent = context
             .system_catalog()?
             .get_schema(tx, FUNCTION_LOOKUP_CATALOG)?
@@ -161,7 +258,7 @@ impl DatabaseProtoConv for PlannedTableFunction {
 
     fn from_proto_ctx(_proto: Self::ProtoType, _context: &DatabaseContext) -> Result<Self> {
         unimplemented!()
-        // let tx = &CatalogTx {};
+        // let tx = &CatalogTx::new();
         // let ent = context
         //     .system_catalog()?
         //     .get_schema(tx, FUNCTION_LOOKUP_CATALOG)?
@@ -186,7 +283,7 @@ impl DatabaseProtoConv for Box<dyn CopyToFunction> {
     }
 
     fn from_proto_ctx(proto: Self::ProtoType, context: &DatabaseContext) -> Result<Self> {
-        let tx = &CatalogTx {};
+        let tx = &CatalogTx::new();
         let ent = context
             .system_catalog()?
             .get_schema(tx, FUNCTION_LOOKUP_CATALOG)?
@@ -220,3 +317,65 @@ impl ProtoConv for CopyToArgs {
         Ok(Self { named })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::database::system::new_system_catalog;
+    use crate::datasource::DataSourceRegistry;
+    use crate::expr;
+    use crate::functions::aggregate::builtin::sum::Sum;
+    use crate::functions::scalar::builtin::string::StartsWith;
+
+    fn test_context() -> DatabaseContext {
+        DatabaseContext::new(Arc::new(
+            new_system_catalog(&DataSourceRegistry::default()).unwrap(),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn planned_scalar_function_roundtrip() {
+        let mut table_list = TableList::empty();
+        let table_ref = table_list
+            .push_table(None, vec![DataType::Utf8], vec!["c0".to_string()])
+            .unwrap();
+
+        let planned = StartsWith
+            .plan(
+                &table_list,
+                vec![expr::col_ref(table_ref, 0), expr::lit("ab")],
+            )
+            .unwrap();
+
+        let input_types = [DataType::Utf8, DataType::Utf8];
+        let proto = encode_planned_scalar_function(&planned, &input_types).unwrap();
+        let (decoded, decoded_types) =
+            decode_planned_scalar_function(proto, &test_context()).unwrap();
+
+        assert_eq!(planned, decoded);
+        assert_eq!(input_types.to_vec(), decoded_types);
+    }
+
+    #[test]
+    fn planned_aggregate_function_roundtrip() {
+        let mut table_list = TableList::empty();
+        let table_ref = table_list
+            .push_table(None, vec![DataType::Int64], vec!["c0".to_string()])
+            .unwrap();
+
+        let planned = Sum
+            .plan(&table_list, vec![expr::col_ref(table_ref, 0)])
+            .unwrap();
+
+        let input_types = [DataType::Int64];
+        let proto = encode_planned_aggregate_function(&planned, &input_types).unwrap();
+        let (decoded, decoded_types) =
+            decode_planned_aggregate_function(proto, &test_context()).unwrap();
+
+        assert_eq!(planned, decoded);
+        assert_eq!(input_types.to_vec(), decoded_types);
+    }
+}
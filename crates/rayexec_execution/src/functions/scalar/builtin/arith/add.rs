@@ -15,6 +15,7 @@ use crate::arrays::executor::physical_type::{
     PhysicalI32,
     PhysicalI64,
     PhysicalI8,
+    PhysicalInterval,
     PhysicalStorage,
     PhysicalU128,
     PhysicalU16,
@@ -75,13 +76,17 @@ impl FunctionInfo for Add {
             Signature::new_positional(&[DataTypeId::Date32, DataTypeId::Int32], DataTypeId::Date32),
             Signature::new_positional(&[DataTypeId::Int32, DataTypeId::Date32], DataTypeId::Date32),
             Signature::new_positional(
-                &[DataTypeId::Interval, DataTypeId::Int64],
+                &[DataTypeId::Interval, DataTypeId::Interval],
                 DataTypeId::Interval,
             ),
             Signature::new_positional(
                 &[DataTypeId::Decimal64, DataTypeId::Decimal64],
                 DataTypeId::Decimal64,
             ),
+            Signature::new_positional(
+                &[DataTypeId::Decimal128, DataTypeId::Decimal128],
+                DataTypeId::Decimal128,
+            ),
         ];
         SIGS
     }
@@ -174,7 +179,12 @@ impl ScalarFunction for Add {
                 DataType::Date32,
             ),
 
-            // TODO: Interval
+            // Interval + interval
+            (DataType::Interval, DataType::Interval) => (
+                Box::new(AddImpl::<PhysicalInterval>::new(DataType::Interval)),
+                DataType::Interval,
+            ),
+
             (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
         };
 
@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
+use num_traits::NumCast;
 use rayexec_error::Result;
 
 use crate::arrays::array::{Array, ArrayData};
@@ -17,6 +18,7 @@ use crate::arrays::executor::physical_type::{
     PhysicalI32,
     PhysicalI64,
     PhysicalI8,
+    PhysicalInterval,
     PhysicalStorage,
     PhysicalU128,
     PhysicalU16,
@@ -26,6 +28,7 @@ use crate::arrays::executor::physical_type::{
 };
 use crate::arrays::executor::scalar::BinaryExecutor;
 use crate::arrays::scalar::decimal::{Decimal128Type, Decimal64Type, DecimalType};
+use crate::arrays::scalar::interval::Interval;
 use crate::arrays::storage::PrimitiveStorage;
 use crate::expr::Expression;
 use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
@@ -76,10 +79,18 @@ impl FunctionInfo for Div {
                 DataTypeId::UInt64,
             ),
             Signature::new_positional(&[DataTypeId::Date32, DataTypeId::Int64], DataTypeId::Date32),
+            Signature::new_positional(
+                &[DataTypeId::Interval, DataTypeId::Int32],
+                DataTypeId::Interval,
+            ),
             Signature::new_positional(
                 &[DataTypeId::Interval, DataTypeId::Int64],
                 DataTypeId::Interval,
             ),
+            Signature::new_positional(
+                &[DataTypeId::Interval, DataTypeId::Float64],
+                DataTypeId::Interval,
+            ),
             Signature::new_positional(
                 &[DataTypeId::Decimal64, DataTypeId::Decimal64],
                 DataTypeId::Float64,
@@ -168,7 +179,21 @@ impl ScalarFunction for Div {
                 DataType::Float64,
             ),
 
-            // TODO: Interval, dates
+            // Interval
+            (DataType::Interval, DataType::Int32) => (
+                Box::new(IntervalDivImpl::<PhysicalI32>::new()),
+                DataType::Interval,
+            ),
+            (DataType::Interval, DataType::Int64) => (
+                Box::new(IntervalDivImpl::<PhysicalI64>::new()),
+                DataType::Interval,
+            ),
+            (DataType::Interval, DataType::Float64) => (
+                Box::new(IntervalDivImpl::<PhysicalF64>::new()),
+                DataType::Interval,
+            ),
+
+            // TODO: Dates
             (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
         };
 
@@ -181,6 +206,55 @@ impl ScalarFunction for Div {
     }
 }
 
+/// Divide an interval by a number, cascading fractional months and days into
+/// smaller units.
+#[derive(Debug, Clone)]
+pub struct IntervalDivImpl<Rhs> {
+    _rhs: PhantomData<Rhs>,
+}
+
+impl<Rhs> IntervalDivImpl<Rhs> {
+    fn new() -> Self {
+        IntervalDivImpl { _rhs: PhantomData }
+    }
+}
+
+impl<Rhs> ScalarFunctionImpl for IntervalDivImpl<Rhs>
+where
+    Rhs: PhysicalStorage,
+    for<'a> Rhs::Type<'a>: NumCast,
+{
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let a = inputs[0];
+        let b = inputs[1];
+
+        let builder = ArrayBuilder {
+            datatype: DataType::Interval,
+            buffer: PrimitiveBuffer::<Interval>::with_len(a.logical_len()),
+        };
+
+        // First division error, returned after executing.
+        let mut error = None;
+        let out =
+            BinaryExecutor::execute::<PhysicalInterval, Rhs, _, _>(a, b, builder, |a, b, buf| {
+                let divisor = <f64 as NumCast>::from(b).unwrap_or_default();
+                match a.checked_div_f64(divisor) {
+                    Ok(v) => buf.put(&v),
+                    Err(e) => {
+                        if error.is_none() {
+                            error = Some(e);
+                        }
+                    }
+                }
+            })?;
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(out),
+        }
+    }
+}
+
 // TODO: We could possibly wrap inputs in a cast and avoid the special casing
 // here.
 #[derive(Debug, Clone)]
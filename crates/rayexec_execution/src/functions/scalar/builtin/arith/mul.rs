@@ -94,11 +94,24 @@ impl FunctionInfo for Mul {
                 &[DataTypeId::Int64, DataTypeId::Interval],
                 DataTypeId::Interval,
             ),
+            // Interval * Float (commutative)
+            Signature::new_positional(
+                &[DataTypeId::Interval, DataTypeId::Float64],
+                DataTypeId::Interval,
+            ),
+            Signature::new_positional(
+                &[DataTypeId::Float64, DataTypeId::Interval],
+                DataTypeId::Interval,
+            ),
             // Decimal
             Signature::new_positional(
                 &[DataTypeId::Decimal64, DataTypeId::Decimal64],
                 DataTypeId::Decimal64,
             ),
+            Signature::new_positional(
+                &[DataTypeId::Decimal128, DataTypeId::Decimal128],
+                DataTypeId::Decimal128,
+            ),
         ];
         SIGS
     }
@@ -209,6 +222,12 @@ impl ScalarFunction for Mul {
                 Box::new(IntervalMulImpl::<PhysicalI64, true>::new()),
                 DataType::Interval,
             ),
+            (DataType::Interval, DataType::Float64) => {
+                (Box::new(IntervalMulFloatImpl::<false>), DataType::Interval)
+            }
+            (DataType::Float64, DataType::Interval) => {
+                (Box::new(IntervalMulFloatImpl::<true>), DataType::Interval)
+            }
 
             // TODO: Date
             (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
@@ -262,6 +281,47 @@ where
     }
 }
 
+/// Multiply an interval by a float, cascading fractional months and days into
+/// smaller units.
+#[derive(Debug, Clone)]
+pub struct IntervalMulFloatImpl<const LHS_RHS_FLIPPED: bool>;
+
+impl<const LHS_RHS_FLIPPED: bool> ScalarFunctionImpl for IntervalMulFloatImpl<LHS_RHS_FLIPPED> {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let (lhs, rhs) = if LHS_RHS_FLIPPED {
+            (inputs[1], inputs[0])
+        } else {
+            (inputs[0], inputs[1])
+        };
+
+        let builder = ArrayBuilder {
+            datatype: DataType::Interval,
+            buffer: PrimitiveBuffer::<Interval>::with_len(lhs.logical_len()),
+        };
+
+        // First out of range error, returned after executing.
+        let mut error = None;
+        let out = BinaryExecutor::execute::<PhysicalInterval, PhysicalF64, _, _>(
+            lhs,
+            rhs,
+            builder,
+            |a, b, buf| match a.checked_mul_f64(b) {
+                Ok(v) => buf.put(&v),
+                Err(e) => {
+                    if error.is_none() {
+                        error = Some(e);
+                    }
+                }
+            },
+        )?;
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(out),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DecimalMulImpl<D> {
     datatype: DataType,
@@ -15,6 +15,7 @@ use crate::arrays::executor::physical_type::{
     PhysicalI32,
     PhysicalI64,
     PhysicalI8,
+    PhysicalInterval,
     PhysicalStorage,
     PhysicalU128,
     PhysicalU16,
@@ -81,12 +82,10 @@ impl FunctionInfo for Sub {
                 DataTypeId::UInt128,
             ),
             Signature::new_positional(&[DataTypeId::Date32, DataTypeId::Int32], DataTypeId::Date32),
-            // TODO
-            // Signature {
-            //     input: &[DataTypeId::Interval, DataTypeId::Int64],
-            //     variadic: None,
-            //     return_type: DataTypeId::Interval,
-            // },
+            Signature::new_positional(
+                &[DataTypeId::Interval, DataTypeId::Interval],
+                DataTypeId::Interval,
+            ),
             Signature::new_positional(
                 &[DataTypeId::Decimal64, DataTypeId::Decimal64],
                 DataTypeId::Decimal64,
@@ -181,7 +180,12 @@ impl ScalarFunction for Sub {
                 DataType::Date32,
             ),
 
-            // TODO: Interval
+            // Interval - interval
+            (DataType::Interval, DataType::Interval) => (
+                Box::new(SubImpl::<PhysicalInterval>::new(DataType::Interval)),
+                DataType::Interval,
+            ),
+
             (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
         };
 
@@ -0,0 +1,191 @@
+use md5::Md5 as Md5Hasher;
+use rayexec_error::Result;
+use sha2::{Digest, Sha256 as Sha256Hasher};
+
+use super::hex_encode;
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use crate::arrays::executor::physical_type::PhysicalBinary;
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Md5;
+
+impl FunctionInfo for Md5 {
+    fn name(&self) -> &'static str {
+        "md5"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Binary,
+                    description: "Compute the MD5 hash of a string, returning the result as hex.",
+                    arguments: &["string"],
+                    example: Some(Example {
+                        example: "md5('hello')",
+                        output: "5d41402abc4b2a76b9719d911017c592",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Binary],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Binary,
+                    description:
+                        "Compute the MD5 hash of a binary blob, returning the result as hex.",
+                    arguments: &["blob"],
+                    example: None,
+                }),
+            },
+        ]
+    }
+}
+
+impl ScalarFunction for Md5 {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+        match inputs[0].datatype(table_list)? {
+            DataType::Utf8 | DataType::Binary => Ok(PlannedScalarFunction {
+                function: Box::new(*self),
+                return_type: DataType::Utf8,
+                inputs,
+                function_impl: Box::new(DigestImpl {
+                    algorithm: DigestAlgorithm::Md5,
+                }),
+            }),
+            a => Err(invalid_input_types_error(self, &[a])),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sha256;
+
+impl FunctionInfo for Sha256 {
+    fn name(&self) -> &'static str {
+        "sha256"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Binary,
+                    description:
+                        "Compute the SHA-256 hash of a string, returning the result as hex.",
+                    arguments: &["string"],
+                    example: Some(Example {
+                        example: "sha256('hello')",
+                        output: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Binary],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Binary,
+                    description:
+                        "Compute the SHA-256 hash of a binary blob, returning the result as hex.",
+                    arguments: &["blob"],
+                    example: None,
+                }),
+            },
+        ]
+    }
+}
+
+impl ScalarFunction for Sha256 {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+        match inputs[0].datatype(table_list)? {
+            DataType::Utf8 | DataType::Binary => Ok(PlannedScalarFunction {
+                function: Box::new(*self),
+                return_type: DataType::Utf8,
+                inputs,
+                function_impl: Box::new(DigestImpl {
+                    algorithm: DigestAlgorithm::Sha256,
+                }),
+            }),
+            a => Err(invalid_input_types_error(self, &[a])),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    /// Hash the input, returning the digest as a lowercase hex string.
+    pub fn hex_digest(&self, input: &[u8]) -> String {
+        match self {
+            Self::Md5 => hex_encode(&Md5Hasher::digest(input)),
+            Self::Sha256 => hex_encode(&Sha256Hasher::digest(input)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestImpl {
+    pub algorithm: DigestAlgorithm,
+}
+
+impl ScalarFunctionImpl for DigestImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let input = inputs[0];
+        let builder = ArrayBuilder {
+            datatype: DataType::Utf8,
+            buffer: GermanVarlenBuffer::<str>::with_len(input.logical_len()),
+        };
+
+        // Binary applicable to both str and [u8].
+        UnaryExecutor::execute::<PhysicalBinary, _, _>(input, builder, |v, buf| {
+            buf.put(&self.algorithm.hex_digest(v))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digests() {
+        assert_eq!(
+            "5d41402abc4b2a76b9719d911017c592",
+            DigestAlgorithm::Md5.hex_digest(b"hello")
+        );
+        assert_eq!(
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+            DigestAlgorithm::Sha256.hex_digest(b"hello")
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::str::FromStr;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use crate::arrays::executor::physical_type::{PhysicalBinary, PhysicalUtf8};
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+use crate::optimizer::expr_rewrite::const_fold::ConstFold;
+use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
+
+/// Textual encodings for binary data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryEncoding {
+    Hex,
+    Base64,
+}
+
+impl BinaryEncoding {
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Self::Hex => hex_encode(bytes),
+            Self::Base64 => BASE64_STANDARD.encode(bytes),
+        }
+    }
+
+    pub fn decode(&self, s: &str) -> Result<Vec<u8>> {
+        match self {
+            Self::Hex => hex_decode(s),
+            Self::Base64 => BASE64_STANDARD
+                .decode(s)
+                .map_err(|e| RayexecError::with_source("Invalid base64 string", Box::new(e))),
+        }
+    }
+}
+
+impl FromStr for BinaryEncoding {
+    type Err = RayexecError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "hex" => Self::Hex,
+            "base64" => Self::Base64,
+            other => return Err(RayexecError::new(format!("Unexpected encoding: {other}"))),
+        })
+    }
+}
+
+/// Encode bytes as a lowercase hex string.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        s.push(HEX_CHARS[(b >> 4) as usize] as char);
+        s.push(HEX_CHARS[(b & 0xf) as usize] as char);
+    }
+    s
+}
+
+/// Decode a hex string into bytes. Accepts both upper and lower case digits.
+pub fn hex_decode(s: &str) -> Result<Vec<u8>> {
+    fn digit(c: u8) -> Result<u8> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            other => Err(RayexecError::new(format!(
+                "Invalid hex digit: {}",
+                other as char
+            ))),
+        }
+    }
+
+    let s = s.as_bytes();
+    if s.len() % 2 != 0 {
+        return Err(RayexecError::new(
+            "Hex string must have an even number of digits",
+        ));
+    }
+
+    s.chunks_exact(2)
+        .map(|pair| Ok((digit(pair[0])? << 4) | digit(pair[1])?))
+        .collect()
+}
+
+/// Get the encoding from a constant function argument.
+fn plan_encoding(table_list: &TableList, input: &Expression) -> Result<BinaryEncoding> {
+    ConstFold::rewrite(table_list, input.clone())?
+        .try_into_scalar()?
+        .try_into_string()?
+        .to_lowercase()
+        .parse()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Encode;
+
+impl FunctionInfo for Encode {
+    fn name(&self) -> &'static str {
+        "encode"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Binary, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Binary,
+                    description: "Encode binary data as text using either 'hex' or 'base64'.",
+                    arguments: &["blob", "encoding"],
+                    example: Some(Example {
+                        example: "encode(decode('deadbeef', 'hex'), 'base64')",
+                        output: "3q2+7w==",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Binary,
+                    description: "Encode the bytes of a string using either 'hex' or 'base64'.",
+                    arguments: &["string", "encoding"],
+                    example: Some(Example {
+                        example: "encode('hello', 'hex')",
+                        output: "68656c6c6f",
+                    }),
+                }),
+            },
+        ]
+    }
+}
+
+impl ScalarFunction for Encode {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+
+        match (
+            inputs[0].datatype(table_list)?,
+            inputs[1].datatype(table_list)?,
+        ) {
+            (DataType::Binary | DataType::Utf8, DataType::Utf8) => (),
+            (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
+        }
+
+        let encoding = plan_encoding(table_list, &inputs[1])?;
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl: Box::new(EncodeImpl { encoding }),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeImpl {
+    pub encoding: BinaryEncoding,
+}
+
+impl ScalarFunctionImpl for EncodeImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let input = inputs[0];
+        let builder = ArrayBuilder {
+            datatype: DataType::Utf8,
+            buffer: GermanVarlenBuffer::<str>::with_len(input.logical_len()),
+        };
+
+        // Binary applicable to both str and [u8].
+        UnaryExecutor::execute::<PhysicalBinary, _, _>(input, builder, |v, buf| {
+            buf.put(&self.encoding.encode(v))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decode;
+
+impl FunctionInfo for Decode {
+    fn name(&self) -> &'static str {
+        "decode"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Binary,
+            doc: Some(&Documentation {
+                category: Category::Binary,
+                description: "Decode text encoded using either 'hex' or 'base64' into binary data.",
+                arguments: &["string", "encoding"],
+                example: Some(Example {
+                    example: "decode('3q2+7w==', 'base64')",
+                    output: "[DE, AD, BE, EF]",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Decode {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+
+        match (
+            inputs[0].datatype(table_list)?,
+            inputs[1].datatype(table_list)?,
+        ) {
+            (DataType::Utf8, DataType::Utf8) => (),
+            (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
+        }
+
+        let encoding = plan_encoding(table_list, &inputs[1])?;
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Binary,
+            inputs,
+            function_impl: Box::new(DecodeImpl { encoding }),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeImpl {
+    pub encoding: BinaryEncoding,
+}
+
+impl ScalarFunctionImpl for DecodeImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let input = inputs[0];
+        let builder = ArrayBuilder {
+            datatype: DataType::Binary,
+            buffer: GermanVarlenBuffer::<[u8]>::with_len(input.logical_len()),
+        };
+
+        // First decode error, returned after executing.
+        let mut error = None;
+        let out =
+            UnaryExecutor::execute::<PhysicalUtf8, _, _>(input, builder, |v, buf| {
+                match self.encoding.decode(v) {
+                    Ok(bytes) => buf.put(&bytes),
+                    Err(e) => {
+                        if error.is_none() {
+                            error = Some(e);
+                        }
+                    }
+                }
+            })?;
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_roundtrip() {
+        let bytes = [0x00, 0x0f, 0xde, 0xad, 0xbe, 0xef];
+        let s = hex_encode(&bytes);
+        assert_eq!("000fdeadbeef", s);
+        assert_eq!(bytes.to_vec(), hex_decode(&s).unwrap());
+        assert_eq!(bytes.to_vec(), hex_decode("000FDEADBEEF").unwrap());
+    }
+
+    #[test]
+    fn hex_decode_invalid() {
+        hex_decode("abc").unwrap_err();
+        hex_decode("zz").unwrap_err();
+    }
+
+    #[test]
+    fn base64_roundtrip() {
+        let bytes = b"hello";
+        let s = BinaryEncoding::Base64.encode(bytes);
+        assert_eq!("aGVsbG8=", s);
+        assert_eq!(bytes.to_vec(), BinaryEncoding::Base64.decode(&s).unwrap());
+    }
+}
@@ -0,0 +1,7 @@
+//! Binary functions.
+
+mod encode;
+pub use encode::*;
+
+mod digest;
+pub use digest::*;
@@ -5,10 +5,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::arrays::array::Array;
 use crate::arrays::bitmap::Bitmap;
+use crate::arrays::compute::boolean;
 use crate::arrays::datatype::{DataType, DataTypeId};
-use crate::arrays::executor::builder::{ArrayBuilder, BooleanBuffer};
-use crate::arrays::executor::physical_type::PhysicalBool;
-use crate::arrays::executor::scalar::{BinaryExecutor, TernaryExecutor, UniformExecutor};
 use crate::arrays::storage::BooleanStorage;
 use crate::expr::Expression;
 use crate::functions::documentation::{Category, Documentation, Example};
@@ -81,45 +79,7 @@ impl ScalarFunctionImpl for AndImpl {
                 Ok(array)
             }
             1 => Ok(inputs[0].clone()),
-            2 => {
-                let a = inputs[0];
-                let b = inputs[1];
-                BinaryExecutor::execute::<PhysicalBool, PhysicalBool, _, _>(
-                    a,
-                    b,
-                    ArrayBuilder {
-                        datatype: DataType::Boolean,
-                        buffer: BooleanBuffer::with_len(a.logical_len()),
-                    },
-                    |a, b, buf| buf.put(&(a && b)),
-                )
-            }
-            3 => {
-                let a = inputs[0];
-                let b = inputs[1];
-                let c = inputs[2];
-                TernaryExecutor::execute::<PhysicalBool, PhysicalBool, PhysicalBool, _, _>(
-                    a,
-                    b,
-                    c,
-                    ArrayBuilder {
-                        datatype: DataType::Boolean,
-                        buffer: BooleanBuffer::with_len(a.logical_len()),
-                    },
-                    |a, b, c, buf| buf.put(&(a && b && c)),
-                )
-            }
-            _ => {
-                let len = inputs[0].logical_len();
-                UniformExecutor::execute::<PhysicalBool, _, _>(
-                    inputs,
-                    ArrayBuilder {
-                        datatype: DataType::Boolean,
-                        buffer: BooleanBuffer::with_len(len),
-                    },
-                    |bools, buf| buf.put(&(bools.iter().all(|b| *b))),
-                )
-            }
+            _ => boolean::and(inputs),
         }
     }
 }
@@ -189,30 +149,7 @@ impl ScalarFunctionImpl for OrImpl {
                 Ok(array)
             }
             1 => Ok(inputs[0].clone()),
-            2 => {
-                let a = inputs[0];
-                let b = inputs[1];
-                BinaryExecutor::execute::<PhysicalBool, PhysicalBool, _, _>(
-                    a,
-                    b,
-                    ArrayBuilder {
-                        datatype: DataType::Boolean,
-                        buffer: BooleanBuffer::with_len(a.logical_len()),
-                    },
-                    |a, b, buf| buf.put(&(a || b)),
-                )
-            }
-            _ => {
-                let len = inputs[0].logical_len();
-                UniformExecutor::execute::<PhysicalBool, _, _>(
-                    inputs,
-                    ArrayBuilder {
-                        datatype: DataType::Boolean,
-                        buffer: BooleanBuffer::with_len(len),
-                    },
-                    |bools, buf| buf.put(&(bools.iter().any(|b| *b))),
-                )
-            }
+            _ => boolean::or(inputs),
         }
     }
 }
@@ -311,4 +248,34 @@ mod tests {
         assert_eq!(ScalarValue::from(true), out.logical_value(1).unwrap());
         assert_eq!(ScalarValue::from(false), out.logical_value(2).unwrap());
     }
+
+    #[test]
+    fn and_or_with_nulls() {
+        let a = Array::from_iter([Some(false), Some(true), None]);
+        let b = Array::from_iter([None, None, Some(true)]);
+
+        let mut table_list = TableList::empty();
+        let table_ref = table_list
+            .push_table(
+                None,
+                vec![DataType::Boolean, DataType::Boolean],
+                vec!["a".to_string(), "b".to_string()],
+            )
+            .unwrap();
+        let inputs = vec![expr::col_ref(table_ref, 0), expr::col_ref(table_ref, 1)];
+
+        let planned = And.plan(&table_list, inputs.clone()).unwrap();
+        let out = planned.function_impl.execute(&[&a, &b]).unwrap();
+
+        assert_eq!(ScalarValue::from(false), out.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Null, out.logical_value(1).unwrap());
+        assert_eq!(ScalarValue::Null, out.logical_value(2).unwrap());
+
+        let planned = Or.plan(&table_list, inputs).unwrap();
+        let out = planned.function_impl.execute(&[&a, &b]).unwrap();
+
+        assert_eq!(ScalarValue::Null, out.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::from(true), out.logical_value(1).unwrap());
+        assert_eq!(ScalarValue::from(true), out.logical_value(2).unwrap());
+    }
 }
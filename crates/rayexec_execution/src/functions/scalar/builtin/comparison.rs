@@ -45,7 +45,7 @@ use crate::logical::binder::table_list::TableList;
 // - Normalize scales for decimals for comparisons (will be needed elsewhere too).
 // - Normalize intervals for comparisons
 
-const fn generate_comparison_sigs(doc: &'static Documentation) -> [Signature; 21] {
+const fn generate_comparison_sigs(doc: &'static Documentation) -> [Signature; 23] {
     [
         Signature {
             positional_args: &[DataTypeId::Boolean, DataTypeId::Boolean],
@@ -155,6 +155,12 @@ const fn generate_comparison_sigs(doc: &'static Documentation) -> [Signature; 21
             return_type: DataTypeId::Boolean,
             doc: Some(doc),
         },
+        Signature {
+            positional_args: &[DataTypeId::Interval, DataTypeId::Interval],
+            variadic_arg: None,
+            return_type: DataTypeId::Boolean,
+            doc: Some(doc),
+        },
         Signature {
             positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
             variadic_arg: None,
@@ -167,6 +173,12 @@ const fn generate_comparison_sigs(doc: &'static Documentation) -> [Signature; 21
             return_type: DataTypeId::Boolean,
             doc: Some(doc),
         },
+        Signature {
+            positional_args: &[DataTypeId::Json, DataTypeId::Json],
+            variadic_arg: None,
+            return_type: DataTypeId::Boolean,
+            doc: Some(doc),
+        },
         Signature {
             positional_args: &[DataTypeId::List, DataTypeId::List],
             variadic_arg: None,
@@ -566,7 +578,7 @@ fn new_comparison_impl<O: ComparisonOperation>(
                 RescalingComparisionImpl::<O, Decimal128Type>::new(left, right),
             ),
             (DataType::Timestamp(_), DataType::Timestamp(_)) => {
-                Box::new(BaseComparisonImpl::<O, PhysicalBool>::new())
+                Box::new(BaseComparisonImpl::<O, PhysicalI64>::new())
             }
             (DataType::Interval, DataType::Interval) => {
                 Box::new(BaseComparisonImpl::<O, PhysicalInterval>::new())
@@ -583,7 +595,12 @@ fn new_comparison_impl<O: ComparisonOperation>(
             (DataType::Binary, DataType::Binary) => {
                 Box::new(BaseComparisonImpl::<O, PhysicalBinary>::new())
             }
-            (DataType::List(m1), DataType::List(m2)) if m1 == m2 => {
+            // JSON values compare by their text.
+            (DataType::Json, DataType::Json) => {
+                Box::new(BaseComparisonImpl::<O, PhysicalUtf8>::new())
+            }
+            // Fixed-size lists compare with lists of any size.
+            (DataType::List(m1), DataType::List(m2)) if m1.datatype == m2.datatype => {
                 // TODO: We'll want to figure out casting for lists.
                 Box::new(ListComparisonImpl::<O>::new(m1.datatype.physical_type()?))
             }
@@ -0,0 +1,682 @@
+//! Shared utilities for formatting and parsing dates, timestamps, and
+//! intervals.
+//!
+//! Two kinds of format strings are supported:
+//!
+//! - Postgres style templates (`YYYY-MM-DD HH24:MI:SS`) used by `to_char` and
+//!   `to_timestamp`.
+//! - strftime style formats (`%Y-%m-%d %H:%M:%S`) used by `strftime` and
+//!   `strptime`.
+use std::fmt::Write as _;
+
+use chrono::format::{Item, Parsed, StrftimeItems};
+use chrono::{DateTime, Datelike, NaiveDateTime, NaiveTime, Timelike};
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::compute::date::SECONDS_IN_DAY;
+use crate::arrays::datatype::TimeUnit;
+use crate::arrays::scalar::interval::Interval;
+
+/// Caches the compiled form of the most recently used format string.
+///
+/// The format argument is nearly always a constant, so this lets us compile it
+/// once per batch instead of once per row.
+#[derive(Debug)]
+pub struct FormatCache<T> {
+    format: String,
+    compiled: Option<T>,
+}
+
+impl<T> Default for FormatCache<T> {
+    fn default() -> Self {
+        FormatCache {
+            format: String::new(),
+            compiled: None,
+        }
+    }
+}
+
+impl<T> FormatCache<T> {
+    /// Get the compiled format, compiling it if it differs from the
+    /// previously used format.
+    pub fn get_or_compile(
+        &mut self,
+        format: &str,
+        compile: impl FnOnce(&str) -> Result<T>,
+    ) -> Result<&T> {
+        if self.compiled.is_none() || self.format != format {
+            // Clear first so a failed compile doesn't leave a mismatched
+            // format cached.
+            self.compiled = None;
+            let compiled = compile(format)?;
+            self.format.clear();
+            self.format.push_str(format);
+            self.compiled = Some(compiled);
+        }
+        Ok(self.compiled.as_ref().expect("compiled format to be set"))
+    }
+}
+
+/// Convert a date or timestamp value to a datetime.
+///
+/// A unit of None indicates the value is a Date32 (days since epoch).
+pub fn datetime_from_value(v: i64, unit: Option<TimeUnit>) -> Option<NaiveDateTime> {
+    let datetime = match unit {
+        None => DateTime::from_timestamp(v.checked_mul(SECONDS_IN_DAY)?, 0)?,
+        Some(TimeUnit::Second) => DateTime::from_timestamp(v, 0)?,
+        Some(TimeUnit::Millisecond) => DateTime::from_timestamp_millis(v)?,
+        Some(TimeUnit::Microsecond) => DateTime::from_timestamp_micros(v)?,
+        Some(TimeUnit::Nanosecond) => DateTime::from_timestamp_nanos(v),
+    };
+    Some(datetime.naive_utc())
+}
+
+/// Letter case to use when writing a textual field (month or day names,
+/// AM/PM).
+///
+/// Determined by the case of the pattern in the template, e.g. 'MONTH' writes
+/// 'JANUARY', 'Month' writes 'January', and 'month' writes 'january'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextCase {
+    Upper,
+    Capitalized,
+    Lower,
+}
+
+impl TextCase {
+    fn from_pattern(pattern: &str) -> Self {
+        let mut chars = pattern.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), _) if c.is_ascii_lowercase() => TextCase::Lower,
+            (_, Some(c)) if c.is_ascii_lowercase() => TextCase::Capitalized,
+            _ => TextCase::Upper,
+        }
+    }
+
+    fn write(&self, s: &str, buf: &mut String) {
+        match self {
+            TextCase::Upper => buf.push_str(&s.to_ascii_uppercase()),
+            TextCase::Lower => buf.push_str(&s.to_ascii_lowercase()),
+            TextCase::Capitalized => buf.push_str(s),
+        }
+    }
+}
+
+/// A field in a Postgres style template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateField {
+    /// 'YYYY', year with at least 4 digits.
+    Year,
+    /// 'YY', last 2 digits of the year.
+    YearShort,
+    /// 'MM', month number.
+    Month,
+    /// 'MONTH' or 'MON', full or abbreviated month name.
+    MonthName { abbrev: bool, case: TextCase },
+    /// 'DD', day of month.
+    Day,
+    /// 'DDD', day of year.
+    DayOfYear,
+    /// 'DAY' or 'DY', full or abbreviated day name.
+    DayName { abbrev: bool, case: TextCase },
+    /// 'HH24', hour of day (0-23).
+    Hour24,
+    /// 'HH' or 'HH12', hour of day (1-12).
+    Hour12,
+    /// 'MI', minute.
+    Minute,
+    /// 'SS', second.
+    Second,
+    /// 'MS', millisecond (000-999).
+    Millisecond,
+    /// 'US', microsecond (000000-999999).
+    Microsecond,
+    /// 'AM' or 'PM', meridiem indicator.
+    Meridiem { case: TextCase },
+}
+
+impl TemplateField {
+    /// Name of the pattern for this field, used in error messages.
+    fn pattern(&self) -> &'static str {
+        match self {
+            TemplateField::Year => "YYYY",
+            TemplateField::YearShort => "YY",
+            TemplateField::Month => "MM",
+            TemplateField::MonthName { abbrev: true, .. } => "MON",
+            TemplateField::MonthName { abbrev: false, .. } => "MONTH",
+            TemplateField::Day => "DD",
+            TemplateField::DayOfYear => "DDD",
+            TemplateField::DayName { abbrev: true, .. } => "DY",
+            TemplateField::DayName { abbrev: false, .. } => "DAY",
+            TemplateField::Hour24 => "HH24",
+            TemplateField::Hour12 => "HH12",
+            TemplateField::Minute => "MI",
+            TemplateField::Second => "SS",
+            TemplateField::Millisecond => "MS",
+            TemplateField::Microsecond => "US",
+            TemplateField::Meridiem { .. } => "AM",
+        }
+    }
+}
+
+/// A single piece of a Postgres style template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatToken {
+    /// Text copied to the output as-is.
+    Literal(String),
+    /// A field to write.
+    ///
+    /// `fill_mode` is set when the field is prefixed with 'FM', which
+    /// suppresses zero padding for numbers and blank padding for names.
+    Field {
+        field: TemplateField,
+        fill_mode: bool,
+    },
+}
+
+/// Creates a field given the case of the matched pattern.
+type FieldFn = fn(TextCase) -> TemplateField;
+
+/// Template patterns, longest first so 'HH24' isn't parsed as 'HH' followed
+/// by a literal.
+const PATTERNS: &[(&str, FieldFn)] = &[
+    ("YYYY", |_| TemplateField::Year),
+    ("YY", |_| TemplateField::YearShort),
+    ("HH24", |_| TemplateField::Hour24),
+    ("HH12", |_| TemplateField::Hour12),
+    ("HH", |_| TemplateField::Hour12),
+    ("MI", |_| TemplateField::Minute),
+    ("SS", |_| TemplateField::Second),
+    ("MS", |_| TemplateField::Millisecond),
+    ("US", |_| TemplateField::Microsecond),
+    ("MONTH", |case| TemplateField::MonthName {
+        abbrev: false,
+        case,
+    }),
+    ("MON", |case| TemplateField::MonthName {
+        abbrev: true,
+        case,
+    }),
+    ("MM", |_| TemplateField::Month),
+    ("DDD", |_| TemplateField::DayOfYear),
+    ("DD", |_| TemplateField::Day),
+    ("DAY", |case| TemplateField::DayName {
+        abbrev: false,
+        case,
+    }),
+    ("DY", |case| TemplateField::DayName { abbrev: true, case }),
+    ("AM", |case| TemplateField::Meridiem { case }),
+    ("PM", |case| TemplateField::Meridiem { case }),
+];
+
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.len() >= prefix.len()
+        && s.is_char_boundary(prefix.len())
+        && s[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+/// Parse a Postgres style template.
+///
+/// Patterns are matched case-insensitively, with the case of name patterns
+/// determining the case of the output. Text in double quotes is always
+/// treated as a literal.
+pub fn parse_template(format: &str) -> Result<Vec<FormatToken>> {
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut fill_mode = false;
+    let mut rest = format;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '"' {
+            let end = rest[1..].find('"').ok_or_else(|| {
+                RayexecError::new(format!("Unterminated quoted literal in format: {format}"))
+            })?;
+            literal.push_str(&rest[1..end + 1]);
+            rest = &rest[end + 2..];
+            fill_mode = false;
+            continue;
+        }
+
+        if starts_with_ignore_case(rest, "FM") {
+            fill_mode = true;
+            rest = &rest[2..];
+            continue;
+        }
+
+        let pattern = PATTERNS
+            .iter()
+            .find(|(pattern, _)| starts_with_ignore_case(rest, pattern));
+
+        match pattern {
+            Some((pattern, field)) => {
+                if !literal.is_empty() {
+                    tokens.push(FormatToken::Literal(std::mem::take(&mut literal)));
+                }
+                let case = TextCase::from_pattern(&rest[..pattern.len()]);
+                tokens.push(FormatToken::Field {
+                    field: field(case),
+                    fill_mode,
+                });
+                rest = &rest[pattern.len()..];
+            }
+            None => {
+                literal.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        fill_mode = false;
+    }
+
+    if !literal.is_empty() {
+        tokens.push(FormatToken::Literal(literal));
+    }
+
+    Ok(tokens)
+}
+
+/// Compile a Postgres style template into items for parsing.
+pub fn compile_template_for_parse(format: &str) -> Result<Vec<Item<'static>>> {
+    let mut strftime = String::new();
+    for token in parse_template(format)? {
+        match token {
+            FormatToken::Literal(s) => strftime.push_str(&s.replace('%', "%%")),
+            FormatToken::Field { field, .. } => strftime.push_str(match field {
+                TemplateField::Year => "%Y",
+                TemplateField::YearShort => "%y",
+                TemplateField::Month => "%m",
+                TemplateField::MonthName { abbrev: true, .. } => "%b",
+                TemplateField::MonthName { abbrev: false, .. } => "%B",
+                TemplateField::Day => "%d",
+                TemplateField::DayOfYear => "%j",
+                TemplateField::DayName { abbrev: true, .. } => "%a",
+                TemplateField::DayName { abbrev: false, .. } => "%A",
+                TemplateField::Hour24 => "%H",
+                TemplateField::Hour12 => "%I",
+                TemplateField::Minute => "%M",
+                TemplateField::Second => "%S",
+                TemplateField::Millisecond => "%3f",
+                TemplateField::Microsecond => "%6f",
+                TemplateField::Meridiem { .. } => "%p",
+            }),
+        }
+    }
+    compile_strftime(&strftime)
+}
+
+/// Compile a strftime style format.
+pub fn compile_strftime(format: &str) -> Result<Vec<Item<'static>>> {
+    StrftimeItems::new(format)
+        .parse_to_owned()
+        .map_err(|_| RayexecError::new(format!("Invalid format string: '{format}'")))
+}
+
+/// Parse a string into a datetime using compiled format items.
+///
+/// Missing date fields default to the start of the year or month, and a
+/// missing time defaults to midnight. If the format contains a UTC offset,
+/// the returned datetime is adjusted to UTC.
+pub fn parse_datetime(s: &str, items: &[Item<'static>]) -> Result<NaiveDateTime> {
+    let parse_err =
+        |e: chrono::format::ParseError| RayexecError::new(format!("Failed to parse '{s}': {e}"));
+
+    let mut parsed = Parsed::new();
+    chrono::format::parse(&mut parsed, s, items.iter()).map_err(parse_err)?;
+
+    // Default missing date fields, e.g. 'YYYY-MM' gets the first of the month.
+    let has_week = parsed.isoweek().is_some()
+        || parsed.week_from_sun().is_some()
+        || parsed.week_from_mon().is_some();
+    if parsed.month().is_none() && parsed.ordinal().is_none() && !has_week {
+        parsed.set_month(1).map_err(parse_err)?;
+    }
+    if parsed.month().is_some() && parsed.day().is_none() {
+        parsed.set_day(1).map_err(parse_err)?;
+    }
+
+    // Default missing time fields. 12 hour clocks without AM/PM are assumed
+    // to be AM.
+    let time = if parsed.hour_mod_12().is_none() {
+        NaiveTime::MIN
+    } else {
+        if parsed.hour_div_12().is_none() {
+            parsed.set_ampm(false).map_err(parse_err)?;
+        }
+        if parsed.minute().is_none() {
+            parsed.set_minute(0).map_err(parse_err)?;
+        }
+        parsed.to_naive_time().map_err(parse_err)?
+    };
+
+    let date = parsed.to_naive_date().map_err(parse_err)?;
+    let datetime = date.and_time(time);
+
+    match parsed.offset() {
+        Some(offset) => datetime
+            .checked_sub_signed(chrono::Duration::seconds(offset as i64))
+            .ok_or_else(|| RayexecError::new(format!("Timestamp out of range: '{s}'"))),
+        None => Ok(datetime),
+    }
+}
+
+/// Write a number, zero padded to `width` unless in fill mode.
+///
+/// The sign is written before any padding.
+fn write_number(buf: &mut String, v: i64, width: usize, fill_mode: bool) {
+    if v < 0 {
+        buf.push('-');
+    }
+    let width = if fill_mode { 0 } else { width };
+    // Writing to a string never fails.
+    let _ = write!(buf, "{:0width$}", v.unsigned_abs());
+}
+
+/// Write a name, blank padded to `width` unless in fill mode.
+fn write_name(buf: &mut String, name: &str, case: TextCase, width: usize, fill_mode: bool) {
+    case.write(name, buf);
+    if !fill_mode {
+        for _ in name.len()..width {
+            buf.push(' ');
+        }
+    }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// Write a datetime to `buf` according to the template tokens.
+pub fn write_datetime_template(datetime: &NaiveDateTime, tokens: &[FormatToken], buf: &mut String) {
+    for token in tokens {
+        let (field, fill_mode) = match token {
+            FormatToken::Literal(s) => {
+                buf.push_str(s);
+                continue;
+            }
+            FormatToken::Field { field, fill_mode } => (*field, *fill_mode),
+        };
+
+        match field {
+            TemplateField::Year => write_number(buf, datetime.year() as i64, 4, fill_mode),
+            TemplateField::YearShort => {
+                write_number(buf, datetime.year().rem_euclid(100) as i64, 2, fill_mode)
+            }
+            TemplateField::Month => write_number(buf, datetime.month() as i64, 2, fill_mode),
+            TemplateField::MonthName { abbrev, case } => {
+                let name = MONTH_NAMES[datetime.month0() as usize];
+                if abbrev {
+                    write_name(buf, &name[..3], case, 3, fill_mode)
+                } else {
+                    write_name(buf, name, case, 9, fill_mode)
+                }
+            }
+            TemplateField::Day => write_number(buf, datetime.day() as i64, 2, fill_mode),
+            TemplateField::DayOfYear => write_number(buf, datetime.ordinal() as i64, 3, fill_mode),
+            TemplateField::DayName { abbrev, case } => {
+                let name = DAY_NAMES[datetime.weekday().num_days_from_sunday() as usize];
+                if abbrev {
+                    write_name(buf, &name[..3], case, 3, fill_mode)
+                } else {
+                    write_name(buf, name, case, 9, fill_mode)
+                }
+            }
+            TemplateField::Hour24 => write_number(buf, datetime.hour() as i64, 2, fill_mode),
+            TemplateField::Hour12 => write_number(buf, datetime.hour12().1 as i64, 2, fill_mode),
+            TemplateField::Minute => write_number(buf, datetime.minute() as i64, 2, fill_mode),
+            TemplateField::Second => write_number(buf, datetime.second() as i64, 2, fill_mode),
+            TemplateField::Millisecond => write_number(
+                buf,
+                (datetime.nanosecond() / 1_000_000 % 1000) as i64,
+                3,
+                fill_mode,
+            ),
+            TemplateField::Microsecond => write_number(
+                buf,
+                (datetime.nanosecond() / 1_000 % 1_000_000) as i64,
+                6,
+                fill_mode,
+            ),
+            TemplateField::Meridiem { case } => {
+                let meridiem = if datetime.hour12().0 { "PM" } else { "AM" };
+                case.write(meridiem, buf)
+            }
+        }
+    }
+}
+
+/// Write an interval to `buf` according to the template tokens.
+///
+/// Fields are taken from the interval as-is, without justifying. Hours may
+/// exceed 23 if the interval's time component is longer than a day.
+pub fn write_interval_template(
+    interval: &Interval,
+    tokens: &[FormatToken],
+    buf: &mut String,
+) -> Result<()> {
+    let years = (interval.months / 12) as i64;
+    let months = (interval.months % 12) as i64;
+    let days = interval.days as i64;
+
+    let nanos = interval.nanos;
+    let hours = nanos / Interval::NANOSECONDS_IN_HOUR;
+    let minutes = nanos % Interval::NANOSECONDS_IN_HOUR / Interval::NANOSECONDS_IN_MINUTE;
+    let seconds = nanos % Interval::NANOSECONDS_IN_MINUTE / Interval::NANOSECONDS_IN_SECOND;
+    let subsec = nanos % Interval::NANOSECONDS_IN_SECOND;
+
+    for token in tokens {
+        let (field, fill_mode) = match token {
+            FormatToken::Literal(s) => {
+                buf.push_str(s);
+                continue;
+            }
+            FormatToken::Field { field, fill_mode } => (*field, *fill_mode),
+        };
+
+        match field {
+            TemplateField::Year => write_number(buf, years, 4, fill_mode),
+            TemplateField::YearShort => write_number(buf, years % 100, 2, fill_mode),
+            TemplateField::Month => write_number(buf, months, 2, fill_mode),
+            TemplateField::Day => write_number(buf, days, 2, fill_mode),
+            TemplateField::Hour24 => write_number(buf, hours, 2, fill_mode),
+            TemplateField::Hour12 => {
+                let h = hours % 12;
+                write_number(buf, if h == 0 { 12 } else { h }, 2, fill_mode)
+            }
+            TemplateField::Minute => write_number(buf, minutes, 2, fill_mode),
+            TemplateField::Second => write_number(buf, seconds, 2, fill_mode),
+            TemplateField::Millisecond => write_number(
+                buf,
+                subsec / Interval::NANOSECONDS_IN_MILLISECOND,
+                3,
+                fill_mode,
+            ),
+            TemplateField::Microsecond => write_number(
+                buf,
+                subsec / Interval::NANOSECONDS_IN_MICROSECOND,
+                6,
+                fill_mode,
+            ),
+            other => {
+                return Err(RayexecError::new(format!(
+                    "Format pattern '{}' not supported for intervals",
+                    other.pattern()
+                )))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn datetime(y: i32, m: u32, d: u32, h: u32, mi: u32, s: u32, micros: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_micro_opt(h, mi, s, micros)
+            .unwrap()
+    }
+
+    fn format_datetime(dt: NaiveDateTime, format: &str) -> String {
+        let mut buf = String::new();
+        write_datetime_template(&dt, &parse_template(format).unwrap(), &mut buf);
+        buf
+    }
+
+    fn format_interval(interval: Interval, format: &str) -> String {
+        let mut buf = String::new();
+        write_interval_template(&interval, &parse_template(format).unwrap(), &mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn parse_with_literals() {
+        let tokens = parse_template("HH24:mi \"HH\"x").unwrap();
+        assert_eq!(
+            vec![
+                FormatToken::Field {
+                    field: TemplateField::Hour24,
+                    fill_mode: false
+                },
+                FormatToken::Literal(":".to_string()),
+                FormatToken::Field {
+                    field: TemplateField::Minute,
+                    fill_mode: false
+                },
+                FormatToken::Literal(" HHx".to_string()),
+            ],
+            tokens
+        );
+
+        parse_template("\"unterminated").unwrap_err();
+    }
+
+    #[test]
+    fn format_datetimes() {
+        let dt = datetime(2024, 3, 5, 15, 4, 9, 123_456);
+
+        assert_eq!(
+            "2024-03-05 15:04:09",
+            format_datetime(dt, "YYYY-MM-DD HH24:MI:SS")
+        );
+        assert_eq!("03:04:09 PM", format_datetime(dt, "HH12:MI:SS AM"));
+        assert_eq!("123 123456", format_datetime(dt, "MS US"));
+        assert_eq!(
+            "March     | MAR | mar",
+            format_datetime(dt, "Month | MON | mon")
+        );
+        assert_eq!(
+            "Tuesday, March 5",
+            format_datetime(dt, "FMDay, FMMonth FMDD")
+        );
+        assert_eq!("24 065 Tue", format_datetime(dt, "YY DDD Dy"));
+        assert_eq!("3pm", format_datetime(dt, "FMHH12pm"));
+    }
+
+    #[test]
+    fn format_intervals() {
+        let mut interval = Interval::new(14, 3, 0);
+        interval.add_hours(15);
+        interval.add_minutes(2);
+        interval.add_milliseconds(12_345);
+
+        assert_eq!("15:02:12", format_interval(interval, "HH24:MI:SS"));
+        assert_eq!("03 12.345", format_interval(interval, "HH12 SS.MS"));
+        assert_eq!("0001-02-03", format_interval(interval, "YYYY-MM-DD"));
+        assert_eq!("345000", format_interval(interval, "US"));
+
+        let mut interval = Interval::default();
+        interval.add_hours(-27);
+        assert_eq!("-27:00", format_interval(interval, "HH24:MI"));
+
+        let tokens = parse_template("Mon").unwrap();
+        write_interval_template(&interval, &tokens, &mut String::new()).unwrap_err();
+    }
+
+    #[test]
+    fn parse_with_template() {
+        let items = compile_template_for_parse("YYYY-MM-DD HH24:MI:SS.US").unwrap();
+        assert_eq!(
+            datetime(2024, 3, 5, 15, 4, 9, 123_456),
+            parse_datetime("2024-03-05 15:04:09.123456", &items).unwrap()
+        );
+
+        let items = compile_template_for_parse("DD Mon YYYY HH12:MI AM").unwrap();
+        assert_eq!(
+            datetime(2024, 3, 5, 15, 4, 0, 0),
+            parse_datetime("05 mar 2024 03:04 PM", &items).unwrap()
+        );
+
+        let items = compile_template_for_parse("YYYYMMDD").unwrap();
+        assert_eq!(
+            datetime(2024, 3, 5, 0, 0, 0, 0),
+            parse_datetime("20240305", &items).unwrap()
+        );
+
+        let items = compile_template_for_parse("YYYY-MM").unwrap();
+        assert_eq!(
+            datetime(2024, 3, 1, 0, 0, 0, 0),
+            parse_datetime("2024-03", &items).unwrap()
+        );
+
+        parse_datetime("2024-13", &items).unwrap_err();
+        parse_datetime("not a date", &items).unwrap_err();
+    }
+
+    #[test]
+    fn parse_with_strftime() {
+        let items = compile_strftime("%Y-%m-%dT%H:%M:%S%z").unwrap();
+        assert_eq!(
+            datetime(2024, 3, 5, 13, 4, 9, 0),
+            parse_datetime("2024-03-05T15:04:09+0200", &items).unwrap()
+        );
+
+        compile_strftime("%Q").unwrap_err();
+    }
+
+    #[test]
+    fn format_cache_recompiles_on_change() {
+        let mut cache = FormatCache::default();
+        let mut compiles = 0;
+        let mut compile = |f: &str| {
+            compiles += 1;
+            parse_template(f)
+        };
+
+        cache.get_or_compile("YYYY", &mut compile).unwrap();
+        cache.get_or_compile("YYYY", &mut compile).unwrap();
+        let tokens = cache.get_or_compile("MM", &mut compile).unwrap().clone();
+        assert_eq!(
+            vec![FormatToken::Field {
+                field: TemplateField::Month,
+                fill_mode: false
+            }],
+            tokens
+        );
+        assert_eq!(2, compiles);
+    }
+}
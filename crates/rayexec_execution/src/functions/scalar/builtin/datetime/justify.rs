@@ -0,0 +1,162 @@
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
+use crate::arrays::executor::physical_type::PhysicalInterval;
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::arrays::scalar::interval::Interval;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JustifyDays;
+
+impl FunctionInfo for JustifyDays {
+    fn name(&self) -> &'static str {
+        "justify_days"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Interval],
+            variadic_arg: None,
+            return_type: DataTypeId::Interval,
+            doc: Some(&Documentation {
+                category: Category::Interval,
+                description: "Adjust an interval so 30-day periods are represented as months.",
+                arguments: &["interval"],
+                example: Some(Example {
+                    example: "justify_days(INTERVAL '35 days')",
+                    output: "1 mon 5 days",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for JustifyDays {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_justify(self, table_list, inputs, Interval::justify_days)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JustifyHours;
+
+impl FunctionInfo for JustifyHours {
+    fn name(&self) -> &'static str {
+        "justify_hours"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Interval],
+            variadic_arg: None,
+            return_type: DataTypeId::Interval,
+            doc: Some(&Documentation {
+                category: Category::Interval,
+                description: "Adjust an interval so 24-hour periods are represented as days.",
+                arguments: &["interval"],
+                example: Some(Example {
+                    example: "justify_hours(INTERVAL '27 hours')",
+                    output: "1 day 03:00:00",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for JustifyHours {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_justify(self, table_list, inputs, Interval::justify_hours)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JustifyInterval;
+
+impl FunctionInfo for JustifyInterval {
+    fn name(&self) -> &'static str {
+        "justify_interval"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Interval],
+            variadic_arg: None,
+            return_type: DataTypeId::Interval,
+            doc: Some(&Documentation {
+                category: Category::Interval,
+                description: "Adjust an interval using both justify_days and justify_hours, \
+                              additionally adjusting signs so all fields have the same sign.",
+                arguments: &["interval"],
+                example: Some(Example {
+                    example: "justify_interval(INTERVAL '1 month -1 hour')",
+                    output: "29 days 23:00:00",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for JustifyInterval {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_justify(self, table_list, inputs, Interval::justify_interval)
+    }
+}
+
+fn plan_justify<F>(
+    func: &F,
+    table_list: &TableList,
+    inputs: Vec<Expression>,
+    justify: fn(&Interval) -> Interval,
+) -> Result<PlannedScalarFunction>
+where
+    F: ScalarFunction + Clone + 'static,
+{
+    plan_check_num_args(func, &inputs, 1)?;
+    match inputs[0].datatype(table_list)? {
+        DataType::Interval => Ok(PlannedScalarFunction {
+            function: Box::new(func.clone()),
+            return_type: DataType::Interval,
+            inputs,
+            function_impl: Box::new(JustifyImpl { justify }),
+        }),
+        other => Err(invalid_input_types_error(func, &[other])),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct JustifyImpl {
+    justify: fn(&Interval) -> Interval,
+}
+
+impl ScalarFunctionImpl for JustifyImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let input = inputs[0];
+        let builder = ArrayBuilder {
+            datatype: DataType::Interval,
+            buffer: PrimitiveBuffer::<Interval>::with_len(input.logical_len()),
+        };
+
+        UnaryExecutor::execute::<PhysicalInterval, _, _>(input, builder, |v, buf| {
+            buf.put(&(self.justify)(&v))
+        })
+    }
+}
@@ -6,3 +6,17 @@ pub use epoch::*;
 
 mod date_trunc;
 pub use date_trunc::*;
+
+mod justify;
+pub use justify::*;
+
+mod format;
+
+mod now;
+pub use now::*;
+
+mod to_char;
+pub use to_char::*;
+
+mod to_timestamp;
+pub use to_timestamp::*;
@@ -0,0 +1,154 @@
+use chrono::Utc;
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId, TimeUnit, TimestampTypeMeta};
+use crate::arrays::scalar::timestamp::TimestampScalar;
+use crate::arrays::scalar::ScalarValue;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{plan_check_num_args, FunctionInfo, FunctionVolatility, Signature};
+use crate::logical::binder::table_list::TableList;
+
+const MICROS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Now;
+
+impl FunctionInfo for Now {
+    fn name(&self) -> &'static str {
+        "now"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["current_timestamp"]
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[],
+            variadic_arg: None,
+            return_type: DataTypeId::Timestamp,
+            doc: Some(&Documentation {
+                category: Category::Date,
+                description: "Return the time the current query started.",
+                arguments: &[],
+                example: None,
+            }),
+        }]
+    }
+
+    fn volatility(&self) -> FunctionVolatility {
+        FunctionVolatility::Stable
+    }
+}
+
+impl ScalarFunction for Now {
+    fn plan(
+        &self,
+        _table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 0)?;
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond)),
+            inputs,
+            function_impl: Box::new(NowImpl {
+                micros: Utc::now().timestamp_micros(),
+            }),
+        })
+    }
+}
+
+/// Returns a fixed timestamp.
+///
+/// Planned with the current time, replaced with the query's start time when
+/// planning physical expressions so that every call in a query returns the
+/// same value.
+#[derive(Debug, Clone, Copy)]
+pub struct NowImpl {
+    micros: i64,
+}
+
+impl ScalarFunctionImpl for NowImpl {
+    fn execute(&self, _inputs: &[&Array]) -> Result<Array> {
+        ScalarValue::Timestamp(TimestampScalar {
+            unit: TimeUnit::Microsecond,
+            value: self.micros,
+        })
+        .as_array(1)
+    }
+
+    fn with_query_start(&self, micros: i64) -> Option<Box<dyn ScalarFunctionImpl>> {
+        Some(Box::new(NowImpl { micros }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrentDate;
+
+impl FunctionInfo for CurrentDate {
+    fn name(&self) -> &'static str {
+        "current_date"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[],
+            variadic_arg: None,
+            return_type: DataTypeId::Date32,
+            doc: Some(&Documentation {
+                category: Category::Date,
+                description: "Return the date (UTC) the current query started.",
+                arguments: &[],
+                example: None,
+            }),
+        }]
+    }
+
+    fn volatility(&self) -> FunctionVolatility {
+        FunctionVolatility::Stable
+    }
+}
+
+impl ScalarFunction for CurrentDate {
+    fn plan(
+        &self,
+        _table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 0)?;
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Date32,
+            inputs,
+            function_impl: Box::new(CurrentDateImpl::from_micros(Utc::now().timestamp_micros())),
+        })
+    }
+}
+
+/// Returns the date (UTC) for a timestamp.
+#[derive(Debug, Clone, Copy)]
+pub struct CurrentDateImpl {
+    days: i32,
+}
+
+impl CurrentDateImpl {
+    fn from_micros(micros: i64) -> Self {
+        CurrentDateImpl {
+            days: micros.div_euclid(MICROS_PER_DAY) as i32,
+        }
+    }
+}
+
+impl ScalarFunctionImpl for CurrentDateImpl {
+    fn execute(&self, _inputs: &[&Array]) -> Result<Array> {
+        ScalarValue::Date32(self.days).as_array(1)
+    }
+
+    fn with_query_start(&self, micros: i64) -> Option<Box<dyn ScalarFunctionImpl>> {
+        Some(Box::new(CurrentDateImpl::from_micros(micros)))
+    }
+}
@@ -0,0 +1,348 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use chrono::format::Item;
+use chrono::NaiveDateTime;
+use rayexec_error::{RayexecError, Result};
+
+use super::format::{
+    compile_strftime,
+    datetime_from_value,
+    parse_template,
+    write_datetime_template,
+    write_interval_template,
+    FormatCache,
+    FormatToken,
+};
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId, TimeUnit};
+use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use crate::arrays::executor::physical_type::{
+    PhysicalI32,
+    PhysicalI64,
+    PhysicalInterval,
+    PhysicalStorage,
+    PhysicalUtf8,
+};
+use crate::arrays::executor::scalar::BinaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToChar;
+
+impl FunctionInfo for ToChar {
+    fn name(&self) -> &'static str {
+        "to_char"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Timestamp, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Date,
+                    description: "Format a timestamp as a string using a Postgres style template.",
+                    arguments: &["timestamp", "format"],
+                    example: Some(Example {
+                        example:
+                            "to_char(TIMESTAMP '2024-03-05 15:04:09', 'YYYY-MM-DD HH12:MI AM')",
+                        output: "2024-03-05 03:04 PM",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Date32, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Date,
+                    description: "Format a date as a string using a Postgres style template.",
+                    arguments: &["date", "format"],
+                    example: Some(Example {
+                        example: "to_char(DATE '2024-03-05', 'FMDay, FMMonth FMDD')",
+                        output: "Tuesday, March 5",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Interval, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Interval,
+                    description: "Format an interval as a string using a Postgres style template.",
+                    arguments: &["interval", "format"],
+                    example: Some(Example {
+                        example: "to_char(INTERVAL '15 hours 2 minutes 12 seconds', 'HH24:MI:SS')",
+                        output: "15:02:12",
+                    }),
+                }),
+            },
+        ]
+    }
+}
+
+impl ScalarFunction for ToChar {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+        let function_impl: Box<dyn ScalarFunctionImpl> = match (
+            inputs[0].datatype(table_list)?,
+            inputs[1].datatype(table_list)?,
+        ) {
+            (DataType::Timestamp(meta), DataType::Utf8) => {
+                Box::new(DateTimeFormatImpl::<PhysicalI64, TemplateFormat>::new(
+                    Some(meta.unit),
+                ))
+            }
+            (DataType::Date32, DataType::Utf8) => {
+                Box::new(DateTimeFormatImpl::<PhysicalI32, TemplateFormat>::new(None))
+            }
+            (DataType::Interval, DataType::Utf8) => Box::new(IntervalToCharImpl),
+            (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
+        };
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Strftime;
+
+impl FunctionInfo for Strftime {
+    fn name(&self) -> &'static str {
+        "strftime"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Timestamp, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Date,
+                    description: "Format a timestamp as a string using a strftime style format.",
+                    arguments: &["timestamp", "format"],
+                    example: Some(Example {
+                        example: "strftime(TIMESTAMP '2024-03-05 15:04:09', '%d/%m/%Y %H:%M')",
+                        output: "05/03/2024 15:04",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Date32, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Date,
+                    description: "Format a date as a string using a strftime style format.",
+                    arguments: &["date", "format"],
+                    example: Some(Example {
+                        example: "strftime(DATE '2024-03-05', '%A, %B %-d')",
+                        output: "Tuesday, March 5",
+                    }),
+                }),
+            },
+        ]
+    }
+}
+
+impl ScalarFunction for Strftime {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+        let function_impl: Box<dyn ScalarFunctionImpl> = match (
+            inputs[0].datatype(table_list)?,
+            inputs[1].datatype(table_list)?,
+        ) {
+            (DataType::Timestamp(meta), DataType::Utf8) => {
+                Box::new(DateTimeFormatImpl::<PhysicalI64, StrftimeFormat>::new(
+                    Some(meta.unit),
+                ))
+            }
+            (DataType::Date32, DataType::Utf8) => {
+                Box::new(DateTimeFormatImpl::<PhysicalI32, StrftimeFormat>::new(None))
+            }
+            (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
+        };
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl,
+        })
+    }
+}
+
+/// A compiled format for writing datetimes.
+pub trait DateTimeFormat: Debug + Sync + Send + Clone + Sized + 'static {
+    fn compile(format: &str) -> Result<Self>;
+    fn write(&self, datetime: &NaiveDateTime, buf: &mut String) -> Result<()>;
+}
+
+/// Postgres style template, e.g. 'YYYY-MM-DD'.
+#[derive(Debug, Clone)]
+pub struct TemplateFormat(Vec<FormatToken>);
+
+impl DateTimeFormat for TemplateFormat {
+    fn compile(format: &str) -> Result<Self> {
+        Ok(TemplateFormat(parse_template(format)?))
+    }
+
+    fn write(&self, datetime: &NaiveDateTime, buf: &mut String) -> Result<()> {
+        write_datetime_template(datetime, &self.0, buf);
+        Ok(())
+    }
+}
+
+/// strftime style format, e.g. '%Y-%m-%d'.
+#[derive(Debug, Clone)]
+pub struct StrftimeFormat(Vec<Item<'static>>);
+
+impl DateTimeFormat for StrftimeFormat {
+    fn compile(format: &str) -> Result<Self> {
+        Ok(StrftimeFormat(compile_strftime(format)?))
+    }
+
+    fn write(&self, datetime: &NaiveDateTime, buf: &mut String) -> Result<()> {
+        use std::fmt::Write as _;
+
+        // Errors if the format requires info we don't have, e.g. a timezone.
+        write!(buf, "{}", datetime.format_with_items(self.0.iter()))
+            .map_err(|_| RayexecError::new("Failed to format timestamp"))
+    }
+}
+
+/// Formats dates and timestamps as strings.
+///
+/// A unit of None indicates the input is a Date32.
+#[derive(Debug, Clone)]
+pub struct DateTimeFormatImpl<S, F> {
+    unit: Option<TimeUnit>,
+    _s: PhantomData<S>,
+    _f: PhantomData<F>,
+}
+
+impl<S, F> DateTimeFormatImpl<S, F> {
+    fn new(unit: Option<TimeUnit>) -> Self {
+        DateTimeFormatImpl {
+            unit,
+            _s: PhantomData,
+            _f: PhantomData,
+        }
+    }
+}
+
+impl<S, F> ScalarFunctionImpl for DateTimeFormatImpl<S, F>
+where
+    S: PhysicalStorage,
+    for<'a> S::Type<'a>: Into<i64>,
+    F: DateTimeFormat,
+{
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let values = inputs[0];
+        let formats = inputs[1];
+
+        let mut string_buf = String::new();
+        let mut cache = FormatCache::<F>::default();
+        // First format error, returned after executing.
+        let mut error = None;
+
+        let out = BinaryExecutor::execute::<S, PhysicalUtf8, _, _>(
+            values,
+            formats,
+            ArrayBuilder {
+                datatype: DataType::Utf8,
+                buffer: GermanVarlenBuffer::with_len(values.logical_len()),
+            },
+            |v, format, buf| {
+                let result = cache.get_or_compile(format, F::compile).and_then(|format| {
+                    let v: i64 = v.into();
+                    let datetime = datetime_from_value(v, self.unit)
+                        .ok_or_else(|| RayexecError::new(format!("Timestamp out of range: {v}")))?;
+                    string_buf.clear();
+                    format.write(&datetime, &mut string_buf)
+                });
+
+                match result {
+                    Ok(_) => buf.put(string_buf.as_str()),
+                    Err(e) => {
+                        if error.is_none() {
+                            error = Some(e);
+                        }
+                    }
+                }
+            },
+        )?;
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(out),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IntervalToCharImpl;
+
+impl ScalarFunctionImpl for IntervalToCharImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let intervals = inputs[0];
+        let formats = inputs[1];
+
+        let mut string_buf = String::new();
+        let mut cache = FormatCache::default();
+        // First format error, returned after executing.
+        let mut error = None;
+
+        let out = BinaryExecutor::execute::<PhysicalInterval, PhysicalUtf8, _, _>(
+            intervals,
+            formats,
+            ArrayBuilder {
+                datatype: DataType::Utf8,
+                buffer: GermanVarlenBuffer::with_len(intervals.logical_len()),
+            },
+            |interval, format, buf| {
+                let result = cache
+                    .get_or_compile(format, parse_template)
+                    .and_then(|tokens| {
+                        string_buf.clear();
+                        write_interval_template(&interval, tokens, &mut string_buf)
+                    });
+
+                match result {
+                    Ok(_) => buf.put(string_buf.as_str()),
+                    Err(e) => {
+                        if error.is_none() {
+                            error = Some(e);
+                        }
+                    }
+                }
+            },
+        )?;
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(out),
+        }
+    }
+}
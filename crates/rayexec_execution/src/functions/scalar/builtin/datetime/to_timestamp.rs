@@ -0,0 +1,156 @@
+use chrono::format::Item;
+use rayexec_error::Result;
+
+use super::format::{compile_strftime, compile_template_for_parse, parse_datetime, FormatCache};
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId, TimeUnit, TimestampTypeMeta};
+use crate::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
+use crate::arrays::executor::physical_type::PhysicalUtf8;
+use crate::arrays::executor::scalar::BinaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToTimestamp;
+
+impl FunctionInfo for ToTimestamp {
+    fn name(&self) -> &'static str {
+        "to_timestamp"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Timestamp,
+            doc: Some(&Documentation {
+                category: Category::Date,
+                description: "Parse a string into a timestamp using a Postgres style template.",
+                arguments: &["string", "format"],
+                example: Some(Example {
+                    example: "to_timestamp('05 Mar 2024 03:04 PM', 'DD Mon YYYY HH12:MI AM')",
+                    output: "2024-03-05 15:04:00 UTC",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for ToTimestamp {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_parse_timestamp(self, table_list, inputs, compile_template_for_parse)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Strptime;
+
+impl FunctionInfo for Strptime {
+    fn name(&self) -> &'static str {
+        "strptime"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Timestamp,
+            doc: Some(&Documentation {
+                category: Category::Date,
+                description: "Parse a string into a timestamp using a strftime style format.",
+                arguments: &["string", "format"],
+                example: Some(Example {
+                    example: "strptime('05/03/2024 15:04', '%d/%m/%Y %H:%M')",
+                    output: "2024-03-05 15:04:00 UTC",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Strptime {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_parse_timestamp(self, table_list, inputs, compile_strftime)
+    }
+}
+
+fn plan_parse_timestamp<F>(
+    func: &F,
+    table_list: &TableList,
+    inputs: Vec<Expression>,
+    compile: fn(&str) -> Result<Vec<Item<'static>>>,
+) -> Result<PlannedScalarFunction>
+where
+    F: ScalarFunction + Clone + 'static,
+{
+    plan_check_num_args(func, &inputs, 2)?;
+    match (
+        inputs[0].datatype(table_list)?,
+        inputs[1].datatype(table_list)?,
+    ) {
+        (DataType::Utf8, DataType::Utf8) => Ok(PlannedScalarFunction {
+            function: Box::new(func.clone()),
+            return_type: DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond)),
+            inputs,
+            function_impl: Box::new(ParseTimestampImpl { compile }),
+        }),
+        (a, b) => Err(invalid_input_types_error(func, &[a, b])),
+    }
+}
+
+/// Parses strings into microsecond timestamps.
+#[derive(Debug, Clone, Copy)]
+pub struct ParseTimestampImpl {
+    compile: fn(&str) -> Result<Vec<Item<'static>>>,
+}
+
+impl ScalarFunctionImpl for ParseTimestampImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let strings = inputs[0];
+        let formats = inputs[1];
+
+        let mut cache = FormatCache::default();
+        // First parse error, returned after executing.
+        let mut error = None;
+
+        let out = BinaryExecutor::execute::<PhysicalUtf8, PhysicalUtf8, _, _>(
+            strings,
+            formats,
+            ArrayBuilder {
+                datatype: DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond)),
+                buffer: PrimitiveBuffer::<i64>::with_len(strings.logical_len()),
+            },
+            |s, format, buf| {
+                let result = cache
+                    .get_or_compile(format, self.compile)
+                    .and_then(|items| parse_datetime(s, items))
+                    .map(|datetime| datetime.and_utc().timestamp_micros());
+
+                match result {
+                    Ok(v) => buf.put(&v),
+                    Err(e) => {
+                        if error.is_none() {
+                            error = Some(e);
+                        }
+                    }
+                }
+            },
+        )?;
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(out),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use rayexec_error::Result;
+use serde_json::Value;
+
+use super::{json_get_index, json_get_path, parse_json_path};
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use crate::arrays::executor::physical_type::{PhysicalI64, PhysicalUtf8};
+use crate::arrays::executor::scalar::BinaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonExtract;
+
+impl FunctionInfo for JsonExtract {
+    fn name(&self) -> &'static str {
+        "json_extract"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Json, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Json,
+                doc: Some(&Documentation {
+                    category: Category::Json,
+                    description: "Extract a value from JSON using either an object key or a path starting with '$'. Returns NULL if the value doesn't exist.",
+                    arguments: &["json", "path"],
+                    example: Some(Example {
+                        example: "json_extract('{\"a\": [1, 2]}', '$.a[1]')",
+                        output: "2",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Json, DataTypeId::Int64],
+                variadic_arg: None,
+                return_type: DataTypeId::Json,
+                doc: Some(&Documentation {
+                    category: Category::Json,
+                    description: "Extract an element from a JSON array. The index is 0-based, negative indices count from the end of the array.",
+                    arguments: &["json", "index"],
+                    example: Some(Example {
+                        example: "json_extract('[1, 2, 3]', -1)",
+                        output: "3",
+                    }),
+                }),
+            },
+        ]
+    }
+}
+
+impl ScalarFunction for JsonExtract {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_json_extract(self, table_list, inputs, DataType::Json)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonExtractString;
+
+impl FunctionInfo for JsonExtractString {
+    fn name(&self) -> &'static str {
+        "json_extract_string"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Json, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Json,
+                    description: "Extract a value from JSON as text using either an object key or a path starting with '$'. Returns NULL if the value doesn't exist or is JSON null.",
+                    arguments: &["json", "path"],
+                    example: Some(Example {
+                        example: "json_extract_string('{\"a\": \"b\"}', 'a')",
+                        output: "b",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Json, DataTypeId::Int64],
+                variadic_arg: None,
+                return_type: DataTypeId::Utf8,
+                doc: Some(&Documentation {
+                    category: Category::Json,
+                    description: "Extract an element from a JSON array as text. The index is 0-based, negative indices count from the end of the array.",
+                    arguments: &["json", "index"],
+                    example: Some(Example {
+                        example: "json_extract_string('[\"a\", \"b\"]', 0)",
+                        output: "a",
+                    }),
+                }),
+            },
+        ]
+    }
+}
+
+impl ScalarFunction for JsonExtractString {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_json_extract(self, table_list, inputs, DataType::Utf8)
+    }
+}
+
+fn plan_json_extract<F>(
+    func: &F,
+    table_list: &TableList,
+    inputs: Vec<Expression>,
+    return_type: DataType,
+) -> Result<PlannedScalarFunction>
+where
+    F: ScalarFunction + Clone + 'static,
+{
+    plan_check_num_args(func, &inputs, 2)?;
+
+    let by_index = match (
+        inputs[0].datatype(table_list)?,
+        inputs[1].datatype(table_list)?,
+    ) {
+        (DataType::Json, DataType::Utf8) => false,
+        (DataType::Json, DataType::Int64) => true,
+        (a, b) => return Err(invalid_input_types_error(func, &[a, b])),
+    };
+
+    Ok(PlannedScalarFunction {
+        function: Box::new(func.clone()),
+        return_type: return_type.clone(),
+        inputs,
+        function_impl: Box::new(JsonExtractImpl {
+            return_type,
+            by_index,
+        }),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonExtractImpl {
+    /// Either Json or Utf8. Utf8 returns strings without quotes.
+    pub return_type: DataType,
+    /// If the second argument is an array index instead of a path.
+    pub by_index: bool,
+}
+
+impl ScalarFunctionImpl for JsonExtractImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let as_text = self.return_type.is_utf8();
+        let builder = ArrayBuilder {
+            datatype: self.return_type.clone(),
+            buffer: GermanVarlenBuffer::<str>::with_len(inputs[0].logical_len()),
+        };
+
+        // Rows where the value doesn't exist. These get set to NULL in the
+        // output.
+        let mut missing = Vec::new();
+
+        let mut out = if self.by_index {
+            BinaryExecutor::execute::<PhysicalUtf8, PhysicalI64, _, _>(
+                inputs[0],
+                inputs[1],
+                builder,
+                |json, idx, buf| {
+                    let value = serde_json::from_str::<Value>(json).ok();
+                    let extracted = value.as_ref().and_then(|v| json_get_index(v, idx));
+                    match extracted_to_string(extracted, as_text) {
+                        Some(s) => buf.put(&s),
+                        None => missing.push(buf.idx),
+                    }
+                },
+            )?
+        } else {
+            BinaryExecutor::execute::<PhysicalUtf8, PhysicalUtf8, _, _>(
+                inputs[0],
+                inputs[1],
+                builder,
+                |json, path, buf| {
+                    let value = serde_json::from_str::<Value>(json).ok();
+                    let extracted = match (value.as_ref(), parse_json_path(path)) {
+                        (Some(value), Some(path)) => json_get_path(value, &path),
+                        _ => None,
+                    };
+                    match extracted_to_string(extracted, as_text) {
+                        Some(s) => buf.put(&s),
+                        None => missing.push(buf.idx),
+                    }
+                },
+            )?
+        };
+
+        for idx in missing {
+            out.set_physical_validity(idx, false);
+        }
+
+        Ok(out)
+    }
+}
+
+/// Convert an extracted value to the string we'll put in the output.
+///
+/// When extracting as text, strings are returned without quotes and JSON null
+/// is returned as None.
+fn extracted_to_string(value: Option<&Value>, as_text: bool) -> Option<String> {
+    match value? {
+        Value::String(s) if as_text => Some(s.clone()),
+        Value::Null if as_text => None,
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_to_string_cases() {
+        let value: Value = serde_json::from_str(r#"{"a": "b", "c": null, "d": [1]}"#).unwrap();
+
+        assert_eq!(
+            Some("\"b\"".to_string()),
+            extracted_to_string(value.get("a"), false)
+        );
+        assert_eq!(
+            Some("b".to_string()),
+            extracted_to_string(value.get("a"), true)
+        );
+        assert_eq!(
+            Some("null".to_string()),
+            extracted_to_string(value.get("c"), false)
+        );
+        assert_eq!(None, extracted_to_string(value.get("c"), true));
+        assert_eq!(
+            Some("[1]".to_string()),
+            extracted_to_string(value.get("d"), true)
+        );
+        assert_eq!(None, extracted_to_string(value.get("e"), false));
+    }
+}
@@ -0,0 +1,7 @@
+//! JSON functions.
+
+mod path;
+pub use path::*;
+
+mod extract;
+pub use extract::*;
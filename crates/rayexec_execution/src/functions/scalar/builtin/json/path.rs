@@ -0,0 +1,147 @@
+use serde_json::Value;
+
+/// A single step in a path into a JSON value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonPathElement {
+    /// Get the value for a key in an object.
+    Key(String),
+    /// Get an element from an array. Negative indices index from the end of
+    /// the array.
+    Index(i64),
+}
+
+/// Parse a path used for extracting values from JSON.
+///
+/// Paths starting with '$' are parsed as a (simplified) JSONPath, e.g.
+/// `$.a.b[0]` or `$["a"][1]`. Any other string is treated as a single object
+/// key.
+///
+/// Returns None if the path is malformed.
+pub fn parse_json_path(path: &str) -> Option<Vec<JsonPathElement>> {
+    let rest = match path.strip_prefix('$') {
+        Some(rest) => rest,
+        None => return Some(vec![JsonPathElement::Key(path.to_string())]),
+    };
+
+    let mut elements = Vec::new();
+    let mut chars = rest.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut key = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '.' || c == '[' {
+                        break;
+                    }
+                    key.push(c);
+                    chars.next();
+                }
+                if key.is_empty() {
+                    return None;
+                }
+                elements.push(JsonPathElement::Key(key));
+            }
+            '[' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => inner.push(c),
+                        None => return None,
+                    }
+                }
+                let inner = inner.trim();
+                let quoted = inner
+                    .strip_prefix('"')
+                    .and_then(|s| s.strip_suffix('"'))
+                    .or_else(|| inner.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')));
+                match quoted {
+                    Some(key) => elements.push(JsonPathElement::Key(key.to_string())),
+                    None => elements.push(JsonPathElement::Index(inner.parse().ok()?)),
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(elements)
+}
+
+/// Walk a JSON value following the given path.
+///
+/// Returns None if any step of the path doesn't exist.
+pub fn json_get_path<'a>(value: &'a Value, path: &[JsonPathElement]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, element| match element {
+        JsonPathElement::Key(key) => value.as_object()?.get(key),
+        JsonPathElement::Index(idx) => json_get_index(value, *idx),
+    })
+}
+
+/// Get an element from a JSON array.
+///
+/// Negative indices index from the end of the array. Returns None if the value
+/// isn't an array or if the index is out of bounds.
+pub fn json_get_index(value: &Value, idx: i64) -> Option<&Value> {
+    let arr = value.as_array()?;
+    let idx = if idx < 0 {
+        arr.len().checked_sub(idx.unsigned_abs() as usize)?
+    } else {
+        idx as usize
+    };
+    arr.get(idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_path_cases() {
+        let test_cases = [
+            ("a", Some(vec![JsonPathElement::Key("a".to_string())])),
+            ("$", Some(vec![])),
+            (
+                "$.a.b",
+                Some(vec![
+                    JsonPathElement::Key("a".to_string()),
+                    JsonPathElement::Key("b".to_string()),
+                ]),
+            ),
+            (
+                "$.a[1][-1]",
+                Some(vec![
+                    JsonPathElement::Key("a".to_string()),
+                    JsonPathElement::Index(1),
+                    JsonPathElement::Index(-1),
+                ]),
+            ),
+            (
+                "$[\"a.b\"]",
+                Some(vec![JsonPathElement::Key("a.b".to_string())]),
+            ),
+            ("$.", None),
+            ("$[1", None),
+            ("$[abc]", None),
+            ("$a", None),
+        ];
+
+        for (path, expected) in test_cases {
+            assert_eq!(expected, parse_json_path(path), "path: {path}");
+        }
+    }
+
+    #[test]
+    fn get_path() {
+        let value: Value = serde_json::from_str(r#"{"a": {"b": [1, 2, 3]}}"#).unwrap();
+
+        let path = parse_json_path("$.a.b[-1]").unwrap();
+        assert_eq!(Some(&Value::from(3)), json_get_path(&value, &path));
+
+        let path = parse_json_path("$.a.c").unwrap();
+        assert_eq!(None, json_get_path(&value, &path));
+
+        let path = parse_json_path("$.a.b[3]").unwrap();
+        assert_eq!(None, json_get_path(&value, &path));
+    }
+}
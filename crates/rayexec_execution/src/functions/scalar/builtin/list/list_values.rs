@@ -2,8 +2,8 @@ use rayexec_error::{RayexecError, Result};
 
 use crate::arrays::array::Array;
 use crate::arrays::datatype::{DataType, DataTypeId, ListTypeMeta};
-use crate::arrays::executor::scalar::concat;
-use crate::arrays::storage::ListStorage;
+use crate::arrays::executor::scalar::interleave;
+use crate::arrays::storage::{ListItemMetadata, ListStorage};
 use crate::expr::Expression;
 use crate::functions::documentation::{Category, Documentation, Example};
 use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
@@ -45,9 +45,7 @@ impl ScalarFunction for ListValues {
         let first = match inputs.first() {
             Some(expr) => expr.datatype(table_list)?,
             None => {
-                let return_type = DataType::List(ListTypeMeta {
-                    datatype: Box::new(DataType::Null),
-                });
+                let return_type = DataType::List(ListTypeMeta::new(DataType::Null));
                 return Ok(PlannedScalarFunction {
                     function: Box::new(*self),
                     return_type: return_type.clone(),
@@ -69,9 +67,7 @@ impl ScalarFunction for ListValues {
             }
         }
 
-        let return_type = DataType::List(ListTypeMeta {
-            datatype: Box::new(first.clone()),
-        });
+        let return_type = DataType::List(ListTypeMeta::new(first.clone()));
 
         Ok(PlannedScalarFunction {
             function: Box::new(*self),
@@ -101,8 +97,20 @@ impl ScalarFunctionImpl for ListValuesImpl {
             return Ok(Array::new_with_array_data(self.list_datatype.clone(), data));
         }
 
-        let out = concat(inputs)?;
-        let data = ListStorage::single_list(out);
+        // Each row gets a list containing the values of every input in that
+        // row.
+        let num_rows = inputs[0].logical_len();
+        let indices: Vec<_> = (0..num_rows)
+            .flat_map(|row| (0..inputs.len()).map(move |input| (input, row)))
+            .collect();
+        let metadata: Vec<_> = (0..num_rows)
+            .map(|row| ListItemMetadata {
+                offset: (row * inputs.len()) as i32,
+                len: inputs.len() as i32,
+            })
+            .collect();
+
+        let data = ListStorage::try_new(metadata, interleave(inputs, &indices)?)?;
 
         Ok(Array::new_with_array_data(self.list_datatype.clone(), data))
     }
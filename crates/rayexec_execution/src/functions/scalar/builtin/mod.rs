@@ -1,8 +1,10 @@
 pub mod arith;
+pub mod binary;
 pub mod boolean;
 pub mod comparison;
 pub mod datetime;
 pub mod is;
+pub mod json;
 pub mod list;
 pub mod negate;
 pub mod numeric;
@@ -10,6 +12,7 @@ pub mod random;
 pub mod similarity;
 pub mod string;
 pub mod struct_funcs;
+pub mod text_search;
 
 use std::sync::LazyLock;
 
@@ -73,6 +76,25 @@ pub static BUILTIN_SCALAR_FUNCTIONS: LazyLock<Vec<Box<dyn ScalarFunction>>> = La
         Box::new(string::RightTrim::new()),
         Box::new(string::BTrim::new()),
         Box::new(string::Like),
+        Box::new(string::InitCap),
+        Box::new(string::ConcatWs),
+        Box::new(string::SplitPart),
+        Box::new(string::Translate),
+        Box::new(string::Strpos),
+        Box::new(string::Left),
+        Box::new(string::Right),
+        Box::new(string::Reverse),
+        Box::new(string::Levenshtein),
+        Box::new(string::Collate),
+        // Text search
+        Box::new(text_search::Tokenize),
+        Box::new(text_search::ToTsvector),
+        Box::new(text_search::Matches),
+        // Binary
+        Box::new(binary::Encode),
+        Box::new(binary::Decode),
+        Box::new(binary::Md5),
+        Box::new(binary::Sha256),
         // Struct
         Box::new(struct_funcs::StructPack),
         // Unary
@@ -80,6 +102,8 @@ pub static BUILTIN_SCALAR_FUNCTIONS: LazyLock<Vec<Box<dyn ScalarFunction>>> = La
         Box::new(negate::Not),
         // Random
         Box::new(random::Random),
+        Box::new(random::SetSeed),
+        Box::new(random::GenRandomUuid),
         // List
         Box::new(list::ListExtract),
         Box::new(list::ListValues),
@@ -88,6 +112,15 @@ pub static BUILTIN_SCALAR_FUNCTIONS: LazyLock<Vec<Box<dyn ScalarFunction>>> = La
         Box::new(datetime::DateTrunc),
         Box::new(datetime::EpochMs),
         Box::new(datetime::Epoch),
+        Box::new(datetime::JustifyDays),
+        Box::new(datetime::JustifyHours),
+        Box::new(datetime::JustifyInterval),
+        Box::new(datetime::ToChar),
+        Box::new(datetime::Strftime),
+        Box::new(datetime::ToTimestamp),
+        Box::new(datetime::Strptime),
+        Box::new(datetime::Now),
+        Box::new(datetime::CurrentDate),
         // Is
         Box::new(is::IsNull),
         Box::new(is::IsNotNull),
@@ -97,5 +130,11 @@ pub static BUILTIN_SCALAR_FUNCTIONS: LazyLock<Vec<Box<dyn ScalarFunction>>> = La
         Box::new(is::IsNotFalse),
         // Distance
         Box::new(similarity::L2Distance),
+        Box::new(similarity::CosineDistance),
+        Box::new(similarity::InnerProduct),
+        Box::new(similarity::MinhashSimilarity),
+        // Json
+        Box::new(json::JsonExtract),
+        Box::new(json::JsonExtractString),
     ]
 });
@@ -3,10 +3,10 @@ use std::marker::PhantomData;
 use rayexec_error::Result;
 
 use crate::arrays::array::{Array, ArrayData};
+use crate::arrays::compute::boolean;
 use crate::arrays::datatype::{DataType, DataTypeId};
-use crate::arrays::executor::builder::{ArrayBuilder, BooleanBuffer, PrimitiveBuffer};
+use crate::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
 use crate::arrays::executor::physical_type::{
-    PhysicalBool,
     PhysicalF16,
     PhysicalF32,
     PhysicalF64,
@@ -15,6 +15,7 @@ use crate::arrays::executor::physical_type::{
     PhysicalI32,
     PhysicalI64,
     PhysicalI8,
+    PhysicalInterval,
     PhysicalStorage,
 };
 use crate::arrays::executor::scalar::UnaryExecutor;
@@ -59,7 +60,6 @@ impl ScalarFunction for Negate {
 
         let dt = inputs[0].datatype(table_list)?;
 
-        // TODO: Interval
         let function_impl: Box<dyn ScalarFunctionImpl> = match dt.clone() {
             dt @ DataType::Int8 => Box::new(NegateImpl::<PhysicalI8>::new(dt)),
             dt @ DataType::Int16 => Box::new(NegateImpl::<PhysicalI16>::new(dt)),
@@ -69,6 +69,7 @@ impl ScalarFunction for Negate {
             dt @ DataType::Float16 => Box::new(NegateImpl::<PhysicalF16>::new(dt)),
             dt @ DataType::Float32 => Box::new(NegateImpl::<PhysicalF32>::new(dt)),
             dt @ DataType::Float64 => Box::new(NegateImpl::<PhysicalF64>::new(dt)),
+            dt @ DataType::Interval => Box::new(NegateImpl::<PhysicalInterval>::new(dt)),
             other => return Err(invalid_input_types_error(self, &[other])),
         };
 
@@ -166,13 +167,6 @@ pub struct NotImpl;
 
 impl ScalarFunctionImpl for NotImpl {
     fn execute(&self, inputs: &[&Array]) -> Result<Array> {
-        UnaryExecutor::execute::<PhysicalBool, _, _>(
-            inputs[0],
-            ArrayBuilder {
-                datatype: DataType::Boolean,
-                buffer: BooleanBuffer::with_len(inputs[0].logical_len()),
-            },
-            |b, buf| buf.put(&(!b)),
-        )
+        boolean::not(inputs[0])
     }
 }
@@ -1,20 +1,61 @@
-use rayexec_error::Result;
-use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayexec_error::{RayexecError, Result};
 
 use crate::arrays::array::Array;
 use crate::arrays::datatype::{DataType, DataTypeId};
-use crate::arrays::storage::PrimitiveStorage;
+use crate::arrays::executor::physical_type::PhysicalF64;
+use crate::arrays::executor::scalar::UnaryExecutor;
 use crate::expr::Expression;
-use crate::functions::documentation::{Category, Documentation};
-use crate::functions::scalar::{
-    FunctionVolatility,
-    PlannedScalarFunction,
-    ScalarFunction,
-    ScalarFunctionImpl,
-};
-use crate::functions::{plan_check_num_args, FunctionInfo, Signature};
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{plan_check_num_args, FunctionInfo, FunctionVolatility, Signature};
 use crate::logical::binder::table_list::TableList;
 
+/// Random number generator shared by all random functions in a session.
+///
+/// Seeded from entropy by default. Calling `setseed` reseeds the generator so
+/// that values produced afterwards are reproducible.
+#[derive(Debug, Clone)]
+pub struct RandomState {
+    rng: Arc<Mutex<StdRng>>,
+}
+
+impl Default for RandomState {
+    fn default() -> Self {
+        RandomState {
+            rng: Arc::new(Mutex::new(StdRng::from_entropy())),
+        }
+    }
+}
+
+impl RandomState {
+    /// Reseed the generator.
+    ///
+    /// The seed must be between -1.0 and 1.0 inclusive.
+    pub fn set_seed(&self, seed: f64) -> Result<()> {
+        check_seed(seed)?;
+        *self.rng.lock() = StdRng::seed_from_u64(seed.to_bits());
+        Ok(())
+    }
+
+    fn with_rng<T>(&self, f: impl FnOnce(&mut StdRng) -> T) -> T {
+        f(&mut self.rng.lock())
+    }
+}
+
+fn check_seed(seed: f64) -> Result<()> {
+    if !(-1.0..=1.0).contains(&seed) {
+        return Err(RayexecError::new(format!(
+            "Seed must be between -1.0 and 1.0, got {seed}"
+        )));
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Random;
 
@@ -30,19 +71,19 @@ impl FunctionInfo for Random {
             return_type: DataTypeId::Float64,
             doc: Some(&Documentation {
                 category: Category::Numeric,
-                description: "Return a random float.",
+                description: "Return a random float in the range [0, 1).",
                 arguments: &[],
                 example: None,
             }),
         }]
     }
-}
 
-impl ScalarFunction for Random {
     fn volatility(&self) -> FunctionVolatility {
         FunctionVolatility::Volatile
     }
+}
 
+impl ScalarFunction for Random {
     fn plan(
         &self,
         _table_list: &TableList,
@@ -53,21 +94,244 @@ impl ScalarFunction for Random {
             function: Box::new(*self),
             return_type: DataType::Float64,
             inputs,
-            function_impl: Box::new(RandomImpl),
+            function_impl: Box::new(RandomImpl { state: None }),
         })
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct RandomImpl;
+#[derive(Debug, Clone)]
+pub struct RandomImpl {
+    /// Session random state, thread local rng used if not set.
+    state: Option<RandomState>,
+}
 
 impl ScalarFunctionImpl for RandomImpl {
     fn execute(&self, _inputs: &[&Array]) -> Result<Array> {
-        // TODO: Need to pass in dummy input to produce all unique values.
-        let val = rand::random::<f64>();
-        Ok(Array::new_with_array_data(
-            DataType::Float64,
-            PrimitiveStorage::from(vec![val]),
-        ))
+        self.execute_without_inputs(1)
+    }
+
+    fn execute_without_inputs(&self, num_rows: usize) -> Result<Array> {
+        let vals: Vec<f64> = match &self.state {
+            Some(state) => state.with_rng(|rng| (0..num_rows).map(|_| rng.gen()).collect()),
+            None => {
+                let mut rng = rand::thread_rng();
+                (0..num_rows).map(|_| rng.gen()).collect()
+            }
+        };
+        Ok(Array::from_iter(vals))
+    }
+
+    fn with_random_state(&self, state: &RandomState) -> Option<Box<dyn ScalarFunctionImpl>> {
+        Some(Box::new(RandomImpl {
+            state: Some(state.clone()),
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetSeed;
+
+impl FunctionInfo for SetSeed {
+    fn name(&self) -> &'static str {
+        "setseed"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Float64],
+            variadic_arg: None,
+            return_type: DataTypeId::Null,
+            doc: Some(&Documentation {
+                category: Category::Numeric,
+                description: "Set the seed for subsequent calls to random functions in this session. The seed must be between -1.0 and 1.0.",
+                arguments: &["seed"],
+                example: Some(Example {
+                    example: "setseed(0.5)",
+                    output: "NULL",
+                }),
+            }),
+        }]
+    }
+
+    fn volatility(&self) -> FunctionVolatility {
+        FunctionVolatility::Volatile
+    }
+}
+
+impl ScalarFunction for SetSeed {
+    fn plan(
+        &self,
+        _table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Null,
+            inputs,
+            function_impl: Box::new(SetSeedImpl { state: None }),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetSeedImpl {
+    /// Session random state to reseed, seeds are only checked if not set.
+    state: Option<RandomState>,
+}
+
+impl ScalarFunctionImpl for SetSeedImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let input = inputs[0];
+
+        let mut seeds = Vec::new();
+        UnaryExecutor::for_each::<PhysicalF64, _>(input, |_idx, val| {
+            if let Some(val) = val {
+                seeds.push(val);
+            }
+        })?;
+
+        // Reseeded per row, last seed wins.
+        for seed in seeds {
+            match &self.state {
+                Some(state) => state.set_seed(seed)?,
+                None => check_seed(seed)?,
+            }
+        }
+
+        Ok(Array::new_untyped_null_array(input.logical_len()))
+    }
+
+    fn with_random_state(&self, state: &RandomState) -> Option<Box<dyn ScalarFunctionImpl>> {
+        Some(Box::new(SetSeedImpl {
+            state: Some(state.clone()),
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenRandomUuid;
+
+impl FunctionInfo for GenRandomUuid {
+    fn name(&self) -> &'static str {
+        "gen_random_uuid"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["uuid"]
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[],
+            variadic_arg: None,
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::General,
+                description: "Return a random version 4 UUID as a string.",
+                arguments: &[],
+                example: None,
+            }),
+        }]
+    }
+
+    fn volatility(&self) -> FunctionVolatility {
+        FunctionVolatility::Volatile
+    }
+}
+
+impl ScalarFunction for GenRandomUuid {
+    fn plan(
+        &self,
+        _table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 0)?;
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl: Box::new(GenRandomUuidImpl { state: None }),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GenRandomUuidImpl {
+    /// Session random state, thread local rng used if not set.
+    state: Option<RandomState>,
+}
+
+impl ScalarFunctionImpl for GenRandomUuidImpl {
+    fn execute(&self, _inputs: &[&Array]) -> Result<Array> {
+        self.execute_without_inputs(1)
+    }
+
+    fn execute_without_inputs(&self, num_rows: usize) -> Result<Array> {
+        let generate = |rng: &mut dyn rand::RngCore| -> Vec<String> {
+            (0..num_rows)
+                .map(|_| {
+                    let mut bytes = [0; 16];
+                    rng.fill_bytes(&mut bytes);
+                    uuid::Builder::from_random_bytes(bytes)
+                        .into_uuid()
+                        .to_string()
+                })
+                .collect()
+        };
+
+        let vals = match &self.state {
+            Some(state) => state.with_rng(|rng| generate(rng)),
+            None => generate(&mut rand::thread_rng()),
+        };
+        Ok(Array::from_iter(vals))
+    }
+
+    fn with_random_state(&self, state: &RandomState) -> Option<Box<dyn ScalarFunctionImpl>> {
+        Some(Box::new(GenRandomUuidImpl {
+            state: Some(state.clone()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_values(state: &RandomState, num_rows: usize) -> Array {
+        RandomImpl {
+            state: Some(state.clone()),
+        }
+        .execute_without_inputs(num_rows)
+        .unwrap()
+    }
+
+    #[test]
+    fn random_produces_value_per_row() {
+        let out = random_values(&RandomState::default(), 4);
+        assert_eq!(4, out.logical_len());
+        assert_ne!(out.logical_value(0).unwrap(), out.logical_value(1).unwrap());
+    }
+
+    #[test]
+    fn random_reproducible_with_seed() {
+        let state = RandomState::default();
+
+        state.set_seed(0.5).unwrap();
+        let a = random_values(&state, 3);
+        state.set_seed(0.5).unwrap();
+        let b = random_values(&state, 3);
+        assert_eq!(a, b);
+
+        state.set_seed(-0.5).unwrap();
+        let c = random_values(&state, 3);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn set_seed_out_of_range() {
+        let state = RandomState::default();
+        state.set_seed(1.5).unwrap_err();
+        state.set_seed(-1.0).unwrap();
     }
 }
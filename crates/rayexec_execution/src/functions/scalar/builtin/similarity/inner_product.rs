@@ -0,0 +1,134 @@
+use std::marker::PhantomData;
+use std::ops::AddAssign;
+
+use num_traits::{AsPrimitive, Float};
+use rayexec_error::Result;
+
+use super::plan_float_list_inputs;
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
+use crate::arrays::executor::physical_type::{
+    PhysicalF16,
+    PhysicalF32,
+    PhysicalF64,
+    PhysicalStorage,
+};
+use crate::arrays::executor::scalar::{BinaryListReducer, ListExecutor};
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+/// Inner (dot) product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InnerProduct;
+
+impl FunctionInfo for InnerProduct {
+    fn name(&self) -> &'static str {
+        "inner_product"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["array_inner_product"]
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        // TODO: Ideally return type would depend on the primitive type in the
+        // list.
+        &[Signature {
+            positional_args: &[DataTypeId::List, DataTypeId::List],
+            variadic_arg: None,
+            return_type: DataTypeId::Float64,
+            doc: Some(&Documentation{
+                category: Category::List,
+                description: "Compute the inner product of two lists. Both lists must be the same length and cannot contain NULLs.",
+                arguments: &["list1", "list2"],
+                example: Some(Example{
+                    example: "inner_product([1.0, 1.0], [2.0, 4.0])",
+                    output: "6",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for InnerProduct {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        let (element_type, inputs) = plan_float_list_inputs(self, table_list, inputs)?;
+
+        let function_impl: Box<dyn ScalarFunctionImpl> = match element_type {
+            DataType::Float16 => Box::new(InnerProductImpl::<PhysicalF16>::new()),
+            DataType::Float32 => Box::new(InnerProductImpl::<PhysicalF32>::new()),
+            DataType::Float64 => Box::new(InnerProductImpl::<PhysicalF64>::new()),
+            other => return Err(invalid_input_types_error(self, &[other])),
+        };
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Float64,
+            inputs,
+            function_impl,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InnerProductImpl<S: PhysicalStorage> {
+    _s: PhantomData<S>,
+}
+
+impl<S> InnerProductImpl<S>
+where
+    S: PhysicalStorage,
+{
+    fn new() -> Self {
+        InnerProductImpl { _s: PhantomData }
+    }
+}
+
+impl<S> ScalarFunctionImpl for InnerProductImpl<S>
+where
+    S: PhysicalStorage,
+    for<'a> S::Type<'a>: Float + AddAssign + AsPrimitive<f64> + Default + Copy,
+{
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let a = inputs[0];
+        let b = inputs[1];
+
+        let builder = ArrayBuilder {
+            datatype: DataType::Float64,
+            buffer: PrimitiveBuffer::with_len(a.logical_len()),
+        };
+
+        ListExecutor::<false, false>::binary_reduce::<S, _, InnerProductReducer<_>>(a, b, builder)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct InnerProductReducer<F> {
+    pub product: F,
+}
+
+impl<F> BinaryListReducer<F, f64> for InnerProductReducer<F>
+where
+    F: Float + AddAssign + AsPrimitive<f64> + Default,
+{
+    fn new(left_len: i32, right_len: i32) -> Self {
+        debug_assert_eq!(left_len, right_len);
+        Self::default()
+    }
+
+    fn put_values(&mut self, v1: F, v2: F) {
+        self.product += v1 * v2;
+    }
+
+    fn finish(self) -> f64 {
+        self.product.as_()
+    }
+}
@@ -4,6 +4,7 @@ use std::ops::AddAssign;
 use num_traits::{AsPrimitive, Float};
 use rayexec_error::Result;
 
+use super::plan_float_list_inputs;
 use crate::arrays::array::Array;
 use crate::arrays::datatype::{DataType, DataTypeId};
 use crate::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
@@ -17,7 +18,7 @@ use crate::arrays::executor::scalar::{BinaryListReducer, ListExecutor};
 use crate::expr::Expression;
 use crate::functions::documentation::{Category, Documentation, Example};
 use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
-use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::functions::{invalid_input_types_error, FunctionInfo, Signature};
 use crate::logical::binder::table_list::TableList;
 
 /// Euclidean distance.
@@ -59,27 +60,13 @@ impl ScalarFunction for L2Distance {
         table_list: &TableList,
         inputs: Vec<Expression>,
     ) -> Result<PlannedScalarFunction> {
-        plan_check_num_args(self, &inputs, 2)?;
-
-        let function_impl: Box<dyn ScalarFunctionImpl> = match (
-            inputs[0].datatype(table_list)?,
-            inputs[1].datatype(table_list)?,
-        ) {
-            (DataType::List(a), DataType::List(b)) => {
-                match (a.datatype.as_ref(), b.datatype.as_ref()) {
-                    (DataType::Float16, DataType::Float16) => {
-                        Box::new(L2DistanceImpl::<PhysicalF16>::new())
-                    }
-                    (DataType::Float32, DataType::Float32) => {
-                        Box::new(L2DistanceImpl::<PhysicalF32>::new())
-                    }
-                    (DataType::Float64, DataType::Float64) => {
-                        Box::new(L2DistanceImpl::<PhysicalF64>::new())
-                    }
-                    (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
-                }
-            }
-            (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
+        let (element_type, inputs) = plan_float_list_inputs(self, table_list, inputs)?;
+
+        let function_impl: Box<dyn ScalarFunctionImpl> = match element_type {
+            DataType::Float16 => Box::new(L2DistanceImpl::<PhysicalF16>::new()),
+            DataType::Float32 => Box::new(L2DistanceImpl::<PhysicalF32>::new()),
+            DataType::Float64 => Box::new(L2DistanceImpl::<PhysicalF64>::new()),
+            other => return Err(invalid_input_types_error(self, &[other])),
         };
 
         Ok(PlannedScalarFunction {
@@ -0,0 +1,89 @@
+use super::{CosineDistance, InnerProduct, L2Distance};
+use crate::functions::FunctionInfo;
+
+/// How the distance between two vectors is measured by a vector index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VectorMetric {
+    /// Euclidean distance, `l2_distance`.
+    L2,
+    /// Cosine distance, `cosine_distance`.
+    Cosine,
+    /// Inner product, `inner_product`. Larger values are closer.
+    InnerProduct,
+}
+
+impl VectorMetric {
+    /// Get the metric computed by a scalar function.
+    pub fn from_function_name(name: &str) -> Option<Self> {
+        [Self::L2, Self::Cosine, Self::InnerProduct]
+            .into_iter()
+            .find(|metric| metric.function_name() == name)
+    }
+
+    /// Name of the scalar function computing this metric.
+    pub fn function_name(&self) -> &'static str {
+        match self {
+            Self::L2 => L2Distance.name(),
+            Self::Cosine => CosineDistance.name(),
+            Self::InnerProduct => InnerProduct.name(),
+        }
+    }
+
+    /// If the nearest vectors have the largest function values.
+    pub const fn nearest_is_largest(&self) -> bool {
+        matches!(self, Self::InnerProduct)
+    }
+
+    /// Distance between two vectors of the same length, with smaller values
+    /// always being closer.
+    ///
+    /// This is only used to order vectors, and isn't necessarily the value
+    /// the metric's function would return.
+    pub fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            Self::L2 => a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum(),
+            Self::Cosine => {
+                let (mut dot, mut a_norm, mut b_norm) = (0.0, 0.0, 0.0);
+                for (a, b) in a.iter().zip(b) {
+                    dot += a * b;
+                    a_norm += a * a;
+                    b_norm += b * b;
+                }
+                let norm = (a_norm * b_norm).sqrt();
+                if norm == 0.0 {
+                    // Zero vectors have no direction, treat them as
+                    // unrelated to everything.
+                    1.0
+                } else {
+                    1.0 - dot / norm
+                }
+            }
+            Self::InnerProduct => -a.iter().zip(b).map(|(a, b)| a * b).sum::<f32>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metric_from_function_name() {
+        assert_eq!(
+            Some(VectorMetric::Cosine),
+            VectorMetric::from_function_name("cosine_distance")
+        );
+        assert_eq!(None, VectorMetric::from_function_name("abs"));
+    }
+
+    #[test]
+    fn distances() {
+        let a = [1.0, 0.0];
+        let b = [0.0, 2.0];
+        assert_eq!(5.0, VectorMetric::L2.distance(&a, &b));
+        assert_eq!(1.0, VectorMetric::Cosine.distance(&a, &b));
+        assert_eq!(0.0, VectorMetric::Cosine.distance(&b, &[0.0, 1.0]));
+        assert_eq!(1.0, VectorMetric::Cosine.distance(&a, &[0.0, 0.0]));
+        assert_eq!(-4.0, VectorMetric::InnerProduct.distance(&b, &b));
+    }
+}
@@ -0,0 +1,123 @@
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId, ListTypeMeta};
+use crate::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
+use crate::arrays::executor::physical_type::PhysicalU64;
+use crate::arrays::executor::scalar::{BinaryListReducer, ListExecutor};
+use crate::expr::cast_expr::CastExpr;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+/// Estimated Jaccard similarity between two minhash signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinhashSimilarity;
+
+impl FunctionInfo for MinhashSimilarity {
+    fn name(&self) -> &'static str {
+        "minhash_similarity"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::List, DataTypeId::List],
+            variadic_arg: None,
+            return_type: DataTypeId::Float64,
+            doc: Some(&Documentation{
+                category: Category::List,
+                description: "Estimate the Jaccard similarity of two sets from their minhash signatures. Both signatures must be the same length.",
+                arguments: &["signature1", "signature2"],
+                example: Some(Example{
+                    example: "minhash_similarity([1, 2, 3, 4], [1, 2, 5, 6])",
+                    output: "0.5",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for MinhashSimilarity {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+
+        let datatypes = inputs
+            .iter()
+            .map(|input| input.datatype(table_list))
+            .collect::<Result<Vec<_>>>()?;
+
+        let signature_type = DataType::List(ListTypeMeta::new(DataType::UInt64));
+        let inputs = inputs
+            .into_iter()
+            .zip(&datatypes)
+            .map(|(input, datatype)| match datatype {
+                DataType::List(meta) if meta.datatype.as_ref() == &DataType::UInt64 => Ok(input),
+                DataType::List(meta) if meta.datatype.is_integer() || meta.datatype.is_null() => {
+                    Ok(Expression::Cast(CastExpr {
+                        to: signature_type.clone(),
+                        expr: Box::new(input),
+                    }))
+                }
+                _ => Err(invalid_input_types_error(self, &datatypes)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Float64,
+            inputs,
+            function_impl: Box::new(MinhashSimilarityImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinhashSimilarityImpl;
+
+impl ScalarFunctionImpl for MinhashSimilarityImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let a = inputs[0];
+        let b = inputs[1];
+
+        let builder = ArrayBuilder {
+            datatype: DataType::Float64,
+            buffer: PrimitiveBuffer::with_len(a.logical_len()),
+        };
+
+        ListExecutor::<false, false>::binary_reduce::<PhysicalU64, _, MinhashSimilarityReducer>(
+            a, b, builder,
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MinhashSimilarityReducer {
+    pub matching: usize,
+    pub len: usize,
+}
+
+impl BinaryListReducer<u64, f64> for MinhashSimilarityReducer {
+    fn new(left_len: i32, right_len: i32) -> Self {
+        debug_assert_eq!(left_len, right_len);
+        MinhashSimilarityReducer {
+            matching: 0,
+            len: left_len as usize,
+        }
+    }
+
+    fn put_values(&mut self, v1: u64, v2: u64) {
+        if v1 == v2 {
+            self.matching += 1;
+        }
+    }
+
+    fn finish(self) -> f64 {
+        self.matching as f64 / self.len as f64
+    }
+}
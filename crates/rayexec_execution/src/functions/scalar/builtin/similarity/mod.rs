@@ -1,2 +1,79 @@
+//! Distance and similarity functions between vectors (lists of floats).
+
 mod l2_distance;
 pub use l2_distance::*;
+
+mod cosine_distance;
+pub use cosine_distance::*;
+
+mod inner_product;
+pub use inner_product::*;
+
+mod metric;
+pub use metric::*;
+
+mod minhash_similarity;
+pub use minhash_similarity::*;
+use rayexec_error::Result;
+
+use crate::arrays::datatype::{DataType, ListTypeMeta};
+use crate::expr::cast_expr::CastExpr;
+use crate::expr::Expression;
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo};
+use crate::logical::binder::table_list::TableList;
+
+/// Plan the two list inputs to a vector function, returning the float type
+/// both lists will contain along with the inputs.
+///
+/// Lists with different float types are cast to the wider type, and lists of
+/// other numeric types (e.g. integers) are cast to lists of floats.
+fn plan_float_list_inputs(
+    func: &impl FunctionInfo,
+    table_list: &TableList,
+    inputs: Vec<Expression>,
+) -> Result<(DataType, Vec<Expression>)> {
+    plan_check_num_args(func, &inputs, 2)?;
+
+    let datatypes = inputs
+        .iter()
+        .map(|input| input.datatype(table_list))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut element_type: Option<&DataType> = None;
+    for datatype in &datatypes {
+        let element = match datatype {
+            DataType::List(meta) if meta.datatype.is_numeric() || meta.datatype.is_null() => {
+                meta.datatype.as_ref()
+            }
+            _ => return Err(invalid_input_types_error(func, &datatypes)),
+        };
+        if float_width(element) > element_type.map(float_width).unwrap_or(0) {
+            element_type = Some(element);
+        }
+    }
+    let element_type = element_type.cloned().unwrap_or(DataType::Float64);
+
+    let inputs = inputs
+        .into_iter()
+        .zip(&datatypes)
+        .map(|(input, datatype)| match datatype {
+            DataType::List(meta) if meta.datatype.as_ref() == &element_type => input,
+            _ => Expression::Cast(CastExpr {
+                to: DataType::List(ListTypeMeta::new(element_type.clone())),
+                expr: Box::new(input),
+            }),
+        })
+        .collect();
+
+    Ok((element_type, inputs))
+}
+
+/// Relative width of float types, zero for anything else.
+const fn float_width(datatype: &DataType) -> u8 {
+    match datatype {
+        DataType::Float16 => 1,
+        DataType::Float32 => 2,
+        DataType::Float64 => 3,
+        _ => 0,
+    }
+}
@@ -121,6 +121,82 @@ impl ScalarFunctionImpl for UpperImpl {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitCap;
+
+impl FunctionInfo for InitCap {
+    fn name(&self) -> &'static str {
+        "initcap"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Convert the first letter of each word to uppercase and the rest to lowercase. Words are sequences of alphanumeric characters.",
+                arguments: &["string"],
+                example: Some(Example {
+                    example: "initcap('hello wORLD')",
+                    output: "Hello World",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for InitCap {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+        match inputs[0].datatype(table_list)? {
+            DataType::Utf8 => Ok(PlannedScalarFunction {
+                function: Box::new(*self),
+                return_type: DataType::Utf8,
+                inputs,
+                function_impl: Box::new(InitCapImpl),
+            }),
+            a => Err(invalid_input_types_error(self, &[a])),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitCapImpl;
+
+impl ScalarFunctionImpl for InitCapImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let input = inputs[0];
+        case_convert_execute(input, initcap)
+    }
+}
+
+fn initcap(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_word = false;
+
+    for c in s.chars() {
+        if c.is_alphanumeric() {
+            if in_word {
+                out.extend(c.to_lowercase());
+            } else {
+                out.extend(c.to_uppercase());
+            }
+            in_word = true;
+        } else {
+            out.push(c);
+            in_word = false;
+        }
+    }
+
+    out
+}
+
 fn case_convert_execute<F>(input: &Array, case_fn: F) -> Result<Array>
 where
     F: Fn(&str) -> String,
@@ -140,3 +216,24 @@ where
         buf.put(&case_fn(v))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initcap_cases() {
+        let test_cases = [
+            ("hello world", "Hello World"),
+            ("hELLO wORLD", "Hello World"),
+            ("hello-world_foo", "Hello-World_Foo"),
+            ("123abc def", "123abc Def"),
+            ("élan vital", "Élan Vital"),
+            ("", ""),
+        ];
+
+        for (input, expected) in test_cases {
+            assert_eq!(expected, initcap(input));
+        }
+    }
+}
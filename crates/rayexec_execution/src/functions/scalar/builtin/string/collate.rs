@@ -0,0 +1,194 @@
+use std::borrow::Cow;
+
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use crate::arrays::executor::physical_type::PhysicalUtf8;
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+use crate::optimizer::expr_rewrite::const_fold::ConstFold;
+use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
+
+/// Determines how strings are compared.
+///
+/// Collations are applied by mapping each string to a collation key.
+/// Comparisons, ORDER BY, GROUP BY, and DISTINCT on the keys then use the
+/// default byte-wise ordering of strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Collation {
+    /// Compare strings by their UTF-8 bytes. This is the default.
+    Binary,
+    /// Case-insensitive comparison using the Unicode lowercase mapping.
+    NoCase,
+    /// Case-insensitive comparison using full Unicode case folding, with
+    /// canonically equivalent strings comparing equal.
+    #[cfg(feature = "icu")]
+    Icu,
+}
+
+impl Collation {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "binary" | "c" | "posix" => Ok(Collation::Binary),
+            "nocase" => Ok(Collation::NoCase),
+            #[cfg(feature = "icu")]
+            "icu" => Ok(Collation::Icu),
+            #[cfg(not(feature = "icu"))]
+            "icu" => Err(RayexecError::new(
+                "ICU collation requires building with the 'icu' feature",
+            )),
+            _ => Err(RayexecError::new(format!("Unknown collation: {name}"))),
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Collation::Binary => "binary",
+            Collation::NoCase => "nocase",
+            #[cfg(feature = "icu")]
+            Collation::Icu => "icu",
+        }
+    }
+
+    /// Get the collation key for a string.
+    pub fn key<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match self {
+            Collation::Binary => Cow::Borrowed(s),
+            Collation::NoCase => {
+                if s.is_ascii() && !s.bytes().any(|b| b.is_ascii_uppercase()) {
+                    Cow::Borrowed(s)
+                } else {
+                    Cow::Owned(s.to_lowercase())
+                }
+            }
+            #[cfg(feature = "icu")]
+            Collation::Icu => {
+                // Canonical caseless match, NFD(fold(NFD(s))).
+                let nfd = icu_normalizer::DecomposingNormalizer::new_nfd();
+                let folded = icu_casemap::CaseMapper::new().fold_string(&nfd.normalize(s));
+                Cow::Owned(nfd.normalize(&folded))
+            }
+        }
+    }
+}
+
+/// Produces the collation key for a string.
+///
+/// `<expr> COLLATE <collation>` is bound to this function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collate;
+
+impl FunctionInfo for Collate {
+    fn name(&self) -> &'static str {
+        "collate"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Get the key used to compare the string using the given collation.",
+                arguments: &["string", "collation"],
+                example: Some(Example {
+                    example: "collate('ABC', 'nocase')",
+                    output: "abc",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Collate {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+        let datatypes = inputs
+            .iter()
+            .map(|expr| expr.datatype(table_list))
+            .collect::<Result<Vec<_>>>()?;
+
+        match (&datatypes[0], &datatypes[1]) {
+            (DataType::Utf8, DataType::Utf8) => (),
+            _ => return Err(invalid_input_types_error(self, &datatypes)),
+        }
+
+        if !inputs[1].is_const_foldable() {
+            return Err(RayexecError::new("Collation must be a constant"));
+        }
+        let collation = ConstFold::rewrite(table_list, inputs[1].clone())?
+            .try_into_scalar()?
+            .try_into_string()?;
+        let collation = Collation::from_name(&collation)?;
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl: Box::new(CollateImpl { collation }),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CollateImpl {
+    pub collation: Collation,
+}
+
+impl ScalarFunctionImpl for CollateImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let input = inputs[0];
+        if self.collation == Collation::Binary {
+            return Ok(input.clone());
+        }
+
+        let builder = ArrayBuilder {
+            datatype: DataType::Utf8,
+            buffer: GermanVarlenBuffer::<str>::with_len(input.logical_len()),
+        };
+
+        UnaryExecutor::execute::<PhysicalUtf8, _, _>(input, builder, |v, buf| {
+            buf.put(self.collation.key(v).as_ref())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collation_from_name() {
+        assert_eq!(Collation::NoCase, Collation::from_name("NOCASE").unwrap());
+        assert_eq!(Collation::Binary, Collation::from_name("binary").unwrap());
+        Collation::from_name("klingon").unwrap_err();
+    }
+
+    #[test]
+    fn nocase_keys() {
+        let collation = Collation::NoCase;
+        assert_eq!("hello", collation.key("HeLLo"));
+        assert_eq!("straße", collation.key("STRAßE"));
+        assert!(matches!(collation.key("already lower"), Cow::Borrowed(_)));
+    }
+
+    #[cfg(feature = "icu")]
+    #[test]
+    fn icu_keys() {
+        let collation = Collation::Icu;
+        assert_eq!(collation.key("STRASSE"), collation.key("straße"));
+        // Precomposed and decomposed forms compare equal.
+        assert_eq!(collation.key("CAF\u{00C9}"), collation.key("cafe\u{0301}"));
+    }
+}
@@ -1,10 +1,10 @@
-use rayexec_error::Result;
+use rayexec_error::{RayexecError, Result};
 
 use crate::arrays::array::Array;
 use crate::arrays::datatype::{DataType, DataTypeId};
 use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
 use crate::arrays::executor::physical_type::PhysicalUtf8;
-use crate::arrays::executor::scalar::{BinaryExecutor, UniformExecutor};
+use crate::arrays::executor::scalar::{BinaryExecutor, UnaryExecutor, UniformExecutor};
 use crate::expr::Expression;
 use crate::functions::documentation::{Category, Documentation, Example};
 use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
@@ -120,3 +120,101 @@ impl ScalarFunctionImpl for StringConcatImpl {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcatWs;
+
+impl FunctionInfo for ConcatWs {
+    fn name(&self) -> &'static str {
+        "concat_ws"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8],
+            variadic_arg: Some(DataTypeId::Utf8),
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Concatenate many strings using the first argument as a separator. NULL arguments are skipped.",
+                arguments: &["separator", "var_args"],
+                example: Some(Example {
+                    example: "concat_ws(', ', 'cat', 'dog', 'mouse')",
+                    output: "cat, dog, mouse",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for ConcatWs {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        if inputs.is_empty() {
+            return Err(RayexecError::new(
+                "concat_ws requires at least a separator argument",
+            ));
+        }
+
+        let datatypes = inputs
+            .iter()
+            .map(|input| input.datatype(table_list))
+            .collect::<Result<Vec<_>>>()?;
+
+        if !datatypes.iter().all(|dt| dt == &DataType::Utf8) {
+            return Err(invalid_input_types_error(self, &datatypes));
+        }
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl: Box::new(StringConcatWsImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StringConcatWsImpl;
+
+impl ScalarFunctionImpl for StringConcatWsImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let sep = inputs[0];
+        let strings = &inputs[1..];
+
+        let mut string_buf = String::new();
+        let mut out = Vec::with_capacity(sep.logical_len());
+
+        for idx in 0..sep.logical_len() {
+            // NULL separator produces NULL.
+            let sep = match UnaryExecutor::value_at::<PhysicalUtf8>(sep, idx)? {
+                Some(sep) => sep,
+                None => {
+                    out.push(None);
+                    continue;
+                }
+            };
+
+            string_buf.clear();
+            let mut first = true;
+
+            for array in strings {
+                // NULL arguments are skipped.
+                if let Some(s) = UnaryExecutor::value_at::<PhysicalUtf8>(array, idx)? {
+                    if !first {
+                        string_buf.push_str(sep);
+                    }
+                    string_buf.push_str(s);
+                    first = false;
+                }
+            }
+
+            out.push(Some(string_buf.clone()));
+        }
+
+        Ok(Array::from_iter(out))
+    }
+}
@@ -0,0 +1,191 @@
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use crate::arrays::executor::physical_type::{PhysicalI64, PhysicalUtf8};
+use crate::arrays::executor::scalar::BinaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Left;
+
+impl FunctionInfo for Left {
+    fn name(&self) -> &'static str {
+        "left"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Int64],
+            variadic_arg: None,
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Get the first n characters of a string. If n is negative, returns all but the last |n| characters.",
+                arguments: &["string", "n"],
+                example: Some(Example {
+                    example: "left('abcde', 2)",
+                    output: "ab",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Left {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_left_right(self, table_list, inputs, LeftRightImpl { from_left: true })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Right;
+
+impl FunctionInfo for Right {
+    fn name(&self) -> &'static str {
+        "right"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Int64],
+            variadic_arg: None,
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Get the last n characters of a string. If n is negative, returns all but the first |n| characters.",
+                arguments: &["string", "n"],
+                example: Some(Example {
+                    example: "right('abcde', 2)",
+                    output: "de",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Right {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_left_right(self, table_list, inputs, LeftRightImpl { from_left: false })
+    }
+}
+
+fn plan_left_right<F>(
+    func: &F,
+    table_list: &TableList,
+    inputs: Vec<Expression>,
+    function_impl: LeftRightImpl,
+) -> Result<PlannedScalarFunction>
+where
+    F: ScalarFunction + Clone + 'static,
+{
+    plan_check_num_args(func, &inputs, 2)?;
+    match (
+        inputs[0].datatype(table_list)?,
+        inputs[1].datatype(table_list)?,
+    ) {
+        (DataType::Utf8, DataType::Int64) => Ok(PlannedScalarFunction {
+            function: Box::new(func.clone()),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl: Box::new(function_impl),
+        }),
+        (a, b) => Err(invalid_input_types_error(func, &[a, b])),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeftRightImpl {
+    /// If we're taking characters from the start of the string (left) or the
+    /// end (right).
+    pub from_left: bool,
+}
+
+impl ScalarFunctionImpl for LeftRightImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Utf8,
+            buffer: GermanVarlenBuffer::<str>::with_len_sharing(inputs[0].logical_len(), inputs[0]),
+        };
+
+        BinaryExecutor::execute::<PhysicalUtf8, PhysicalI64, _, _>(
+            inputs[0],
+            inputs[1],
+            builder,
+            |s, n, buf| {
+                if self.from_left {
+                    buf.put(left(s, n))
+                } else {
+                    buf.put(right(s, n))
+                }
+            },
+        )
+    }
+}
+
+/// Byte offset of the nth character in `s`, or the length of the string if
+/// there's fewer than n characters.
+fn char_offset(s: &str, n: usize) -> usize {
+    s.char_indices()
+        .nth(n)
+        .map(|(offset, _)| offset)
+        .unwrap_or(s.len())
+}
+
+fn left(s: &str, n: i64) -> &str {
+    let count = if n >= 0 {
+        n as usize
+    } else {
+        s.chars().count().saturating_sub(n.unsigned_abs() as usize)
+    };
+
+    &s[..char_offset(s, count)]
+}
+
+fn right(s: &str, n: i64) -> &str {
+    let skip = if n >= 0 {
+        s.chars().count().saturating_sub(n as usize)
+    } else {
+        n.unsigned_abs() as usize
+    };
+
+    &s[char_offset(s, skip)..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn left_cases() {
+        assert_eq!("ab", left("abcde", 2));
+        assert_eq!("abcde", left("abcde", 10));
+        assert_eq!("", left("abcde", 0));
+        assert_eq!("abc", left("abcde", -2));
+        assert_eq!("", left("abcde", -10));
+        assert_eq!("tsch", left("tschüß", -2));
+    }
+
+    #[test]
+    fn right_cases() {
+        assert_eq!("de", right("abcde", 2));
+        assert_eq!("abcde", right("abcde", 10));
+        assert_eq!("", right("abcde", 0));
+        assert_eq!("cde", right("abcde", -2));
+        assert_eq!("", right("abcde", -10));
+        assert_eq!("üß", right("tschüß", 2));
+    }
+}
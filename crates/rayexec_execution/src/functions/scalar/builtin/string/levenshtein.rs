@@ -0,0 +1,79 @@
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
+use crate::arrays::executor::physical_type::PhysicalUtf8;
+use crate::arrays::executor::scalar::BinaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Levenshtein;
+
+impl FunctionInfo for Levenshtein {
+    fn name(&self) -> &'static str {
+        "levenshtein"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Int64,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Compute the Levenshtein edit distance between two strings.",
+                arguments: &["string1", "string2"],
+                example: Some(Example {
+                    example: "levenshtein('kitten', 'sitting')",
+                    output: "3",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Levenshtein {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+        match (
+            inputs[0].datatype(table_list)?,
+            inputs[1].datatype(table_list)?,
+        ) {
+            (DataType::Utf8, DataType::Utf8) => Ok(PlannedScalarFunction {
+                function: Box::new(*self),
+                return_type: DataType::Int64,
+                inputs,
+                function_impl: Box::new(LevenshteinImpl),
+            }),
+            (a, b) => Err(invalid_input_types_error(self, &[a, b])),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevenshteinImpl;
+
+impl ScalarFunctionImpl for LevenshteinImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Int64,
+            buffer: PrimitiveBuffer::<i64>::with_len(inputs[0].logical_len()),
+        };
+
+        BinaryExecutor::execute::<PhysicalUtf8, PhysicalUtf8, _, _>(
+            inputs[0],
+            inputs[1],
+            builder,
+            |a, b, buf| buf.put(&(strsim::levenshtein(a, b) as i64)),
+        )
+    }
+}
@@ -135,7 +135,8 @@ fn like_pattern_to_regex(
     escape_char: Option<char>,
 ) -> Result<Regex> {
     buf.clear();
-    buf.push('^');
+    // Wildcards match any character, including newlines.
+    buf.push_str("(?s)^");
 
     let mut chars = pattern.chars().peekable();
     while let Some(c) = chars.next() {
@@ -38,3 +38,24 @@ pub use concat::*;
 
 mod like;
 pub use like::*;
+
+mod split_part;
+pub use split_part::*;
+
+mod translate;
+pub use translate::*;
+
+mod strpos;
+pub use strpos::*;
+
+mod left_right;
+pub use left_right::*;
+
+mod reverse;
+pub use reverse::*;
+
+mod levenshtein;
+pub use levenshtein::*;
+
+mod collate;
+pub use collate::*;
@@ -244,39 +244,21 @@ impl ScalarFunctionImpl for RightPadImpl {
 fn lpad(s: &str, count: i64, pad: &str, buf: &mut String) {
     buf.clear();
 
-    if pad.is_empty() {
-        // Just write the original string and don't pad. Matches postgres.
-        buf.push_str(s);
-        return;
-    }
-
-    let s_char_len = s.chars().count() as i64;
-    if s_char_len > count {
+    let count = count.max(0) as usize;
+    let s_char_len = s.chars().count();
+    if s_char_len >= count {
         // Just write count number of chars to output.
-        buf.push_str(&s.chars().as_str()[..count as usize]);
+        buf.push_str(take_chars(s, count));
         return;
     }
 
-    let pad_char_len = pad.chars().count() as i64;
-    let mut rem = count - s_char_len;
-    while rem > 0 {
-        buf.push_str(pad);
-        rem -= pad_char_len;
+    if pad.is_empty() {
+        // Just write the original string and don't pad. Matches postgres.
+        buf.push_str(s);
+        return;
     }
 
-    if rem < 0 {
-        // Need to trim from right now.
-        let byte_pos = buf
-            .char_indices()
-            .rev()
-            .skip((rem.abs() - 1) as usize)
-            .map(|(pos, _)| pos)
-            .next();
-
-        if let Some(pos) = byte_pos {
-            buf.truncate(pos);
-        }
-    }
+    buf.extend(pad.chars().cycle().take(count - s_char_len));
 
     // Push original string.
     buf.push_str(s);
@@ -284,33 +266,29 @@ fn lpad(s: &str, count: i64, pad: &str, buf: &mut String) {
 
 fn rpad(s: &str, count: i64, pad: &str, buf: &mut String) {
     buf.clear();
-    buf.push_str(s);
 
-    if pad.is_empty() {
+    let count = count.max(0) as usize;
+    let s_char_len = s.chars().count();
+    if s_char_len >= count {
+        // Just write count number of chars to output.
+        buf.push_str(take_chars(s, count));
         return;
     }
 
-    let s_char_len = s.chars().count() as i64;
-    let pad_char_len = pad.chars().count() as i64;
-    let mut rem = count - s_char_len;
+    buf.push_str(s);
 
-    while rem > 0 {
-        buf.push_str(pad);
-        rem -= pad_char_len;
+    if pad.is_empty() {
+        return;
     }
 
-    if rem < 0 {
-        // Need to trim from right now.
-        let byte_pos = buf
-            .char_indices()
-            .rev()
-            .skip((rem.abs() - 1) as usize)
-            .map(|(pos, _)| pos)
-            .next();
-
-        if let Some(pos) = byte_pos {
-            buf.truncate(pos);
-        }
+    buf.extend(pad.chars().cycle().take(count - s_char_len));
+}
+
+/// Get the first `n` chars of a string.
+fn take_chars(s: &str, n: usize) -> &str {
+    match s.char_indices().nth(n) {
+        Some((pos, _)) => &s[..pos],
+        None => s,
     }
 }
 
@@ -352,6 +330,24 @@ mod tests {
                 count: 6,
                 expected: "aaa",
             },
+            TestCase {
+                s: "tschüß",
+                pad: "b",
+                count: 5,
+                expected: "tschü",
+            },
+            TestCase {
+                s: "aaa",
+                pad: "b",
+                count: -1,
+                expected: "",
+            },
+            TestCase {
+                s: "aaa",
+                pad: "ü",
+                count: 5,
+                expected: "üüaaa",
+            },
         ];
 
         let mut buf = String::new();
@@ -388,6 +384,24 @@ mod tests {
                 count: 6,
                 expected: "aaa",
             },
+            TestCase {
+                s: "aaa",
+                pad: "",
+                count: 2,
+                expected: "aa",
+            },
+            TestCase {
+                s: "tschüß",
+                pad: "b",
+                count: 5,
+                expected: "tschü",
+            },
+            TestCase {
+                s: "aaa",
+                pad: "b",
+                count: -1,
+                expected: "",
+            },
         ];
 
         let mut buf = String::new();
@@ -0,0 +1,78 @@
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use crate::arrays::executor::physical_type::PhysicalUtf8;
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reverse;
+
+impl FunctionInfo for Reverse {
+    fn name(&self) -> &'static str {
+        "reverse"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Reverse the characters in a string.",
+                arguments: &["string"],
+                example: Some(Example {
+                    example: "reverse('abc')",
+                    output: "cba",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Reverse {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+        match inputs[0].datatype(table_list)? {
+            DataType::Utf8 => Ok(PlannedScalarFunction {
+                function: Box::new(*self),
+                return_type: DataType::Utf8,
+                inputs,
+                function_impl: Box::new(ReverseImpl),
+            }),
+            a => Err(invalid_input_types_error(self, &[a])),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReverseImpl;
+
+impl ScalarFunctionImpl for ReverseImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let input = inputs[0];
+        let builder = ArrayBuilder {
+            datatype: DataType::Utf8,
+            buffer: GermanVarlenBuffer::<str>::with_len(input.logical_len()),
+        };
+
+        let mut string_buf = String::new();
+
+        UnaryExecutor::execute::<PhysicalUtf8, _, _>(input, builder, |v, buf| {
+            string_buf.clear();
+            string_buf.extend(v.chars().rev());
+            buf.put(string_buf.as_str())
+        })
+    }
+}
@@ -0,0 +1,132 @@
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use crate::arrays::executor::physical_type::{PhysicalI64, PhysicalUtf8};
+use crate::arrays::executor::scalar::TernaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitPart;
+
+impl FunctionInfo for SplitPart {
+    fn name(&self) -> &'static str {
+        "split_part"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8, DataTypeId::Int64],
+            variadic_arg: None,
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Split a string on a delimiter and return the nth part. The index is 1-based, negative indices count from the end.",
+                arguments: &["string", "delimiter", "n"],
+                example: Some(Example {
+                    example: "split_part('a,b,c', ',', 2)",
+                    output: "b",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for SplitPart {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 3)?;
+
+        match (
+            inputs[0].datatype(table_list)?,
+            inputs[1].datatype(table_list)?,
+            inputs[2].datatype(table_list)?,
+        ) {
+            (DataType::Utf8, DataType::Utf8, DataType::Int64) => (),
+            (a, b, c) => return Err(invalid_input_types_error(self, &[a, b, c])),
+        }
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl: Box::new(SplitPartImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitPartImpl;
+
+impl ScalarFunctionImpl for SplitPartImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Utf8,
+            buffer: GermanVarlenBuffer::<str>::with_len_sharing(inputs[0].logical_len(), inputs[0]),
+        };
+
+        TernaryExecutor::execute::<PhysicalUtf8, PhysicalUtf8, PhysicalI64, _, _>(
+            inputs[0],
+            inputs[1],
+            inputs[2],
+            builder,
+            |s, delim, n, buf| buf.put(split_part(s, delim, n)),
+        )
+    }
+}
+
+/// Get the nth part of a string split on a delimiter.
+///
+/// Returns an empty string if the part doesn't exist (including for n = 0). An
+/// empty delimiter treats the entire string as a single part.
+fn split_part<'a>(s: &'a str, delim: &str, n: i64) -> &'a str {
+    if delim.is_empty() {
+        return if n == 1 || n == -1 { s } else { "" };
+    }
+
+    let part = if n > 0 {
+        s.split(delim).nth((n - 1) as usize)
+    } else if n < 0 {
+        s.rsplit(delim).nth((-(n + 1)) as usize)
+    } else {
+        None
+    };
+
+    part.unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_part_cases() {
+        // ((string, delimiter, n), expected)
+        let test_cases = [
+            (("a,b,c", ",", 1), "a"),
+            (("a,b,c", ",", 3), "c"),
+            (("a,b,c", ",", 4), ""),
+            (("a,b,c", ",", -1), "c"),
+            (("a,b,c", ",", -3), "a"),
+            (("a,b,c", ",", -4), ""),
+            (("a,b,c", ",", 0), ""),
+            (("a::b::c", "::", 2), "b"),
+            (("abc", "", 1), "abc"),
+            (("abc", "", 2), ""),
+            (("", ",", 1), ""),
+        ];
+
+        for case in test_cases {
+            let out = split_part(case.0 .0, case.0 .1, case.0 .2);
+            assert_eq!(case.1, out, "case: {case:?}");
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
+use crate::arrays::executor::physical_type::PhysicalUtf8;
+use crate::arrays::executor::scalar::BinaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Strpos;
+
+impl FunctionInfo for Strpos {
+    fn name(&self) -> &'static str {
+        "strpos"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["instr"]
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Int64,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Get the 1-based character position of the first occurrence of a substring within a string. Returns 0 if the substring isn't found.",
+                arguments: &["string", "substring"],
+                example: Some(Example {
+                    example: "strpos('hello', 'll')",
+                    output: "3",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Strpos {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+        match (
+            inputs[0].datatype(table_list)?,
+            inputs[1].datatype(table_list)?,
+        ) {
+            (DataType::Utf8, DataType::Utf8) => Ok(PlannedScalarFunction {
+                function: Box::new(*self),
+                return_type: DataType::Int64,
+                inputs,
+                function_impl: Box::new(StrposImpl),
+            }),
+            (a, b) => Err(invalid_input_types_error(self, &[a, b])),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrposImpl;
+
+impl ScalarFunctionImpl for StrposImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Int64,
+            buffer: PrimitiveBuffer::<i64>::with_len(inputs[0].logical_len()),
+        };
+
+        BinaryExecutor::execute::<PhysicalUtf8, PhysicalUtf8, _, _>(
+            inputs[0],
+            inputs[1],
+            builder,
+            |s, substring, buf| buf.put(&strpos(s, substring)),
+        )
+    }
+}
+
+/// Find the 1-based character position of `substring` in `s`, returning 0 if
+/// it's not found.
+fn strpos(s: &str, substring: &str) -> i64 {
+    match s.find(substring) {
+        Some(byte_idx) => s[..byte_idx].chars().count() as i64 + 1,
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strpos_cases() {
+        assert_eq!(3, strpos("hello", "ll"));
+        assert_eq!(1, strpos("hello", "h"));
+        assert_eq!(0, strpos("hello", "x"));
+        assert_eq!(1, strpos("hello", ""));
+        assert_eq!(0, strpos("", "a"));
+        // Position is in characters, not bytes.
+        assert_eq!(5, strpos("tschüß", "üß"));
+    }
+}
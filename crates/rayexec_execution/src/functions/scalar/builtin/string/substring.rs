@@ -3,7 +3,7 @@ use rayexec_error::Result;
 use crate::arrays::array::Array;
 use crate::arrays::datatype::{DataType, DataTypeId};
 use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
-use crate::arrays::executor::physical_type::{PhysicalI64, PhysicalUtf8};
+use crate::arrays::executor::physical_type::{PhysicalBinary, PhysicalI64, PhysicalUtf8};
 use crate::arrays::executor::scalar::{BinaryExecutor, TernaryExecutor};
 use crate::expr::Expression;
 use crate::functions::documentation::{Category, Documentation, Example};
@@ -60,6 +60,30 @@ impl FunctionInfo for Substring {
                     }),
                 }),
             },
+            // substring(<blob>, <from>, <for>)
+            Signature {
+                positional_args: &[DataTypeId::Binary, DataTypeId::Int64, DataTypeId::Int64],
+                variadic_arg: None,
+                return_type: DataTypeId::Binary,
+                doc: Some(&Documentation{
+                    category: Category::Binary,
+                    description: "Get a slice of a binary blob starting at an index for some number of bytes. The index is 1-based.",
+                    arguments: &["blob", "index", "for"],
+                    example: None,
+                })
+            },
+            // substring(<blob>, <from>)
+            Signature {
+                positional_args: &[DataTypeId::Binary, DataTypeId::Int64],
+                variadic_arg: None,
+                return_type: DataTypeId::Binary,
+                doc: Some(&Documentation{
+                    category: Category::Binary,
+                    description: "Get a slice of a binary blob starting at an index until the end of the blob. The index is 1-based.",
+                    arguments: &["blob", "index"],
+                    example: None,
+                }),
+            },
         ]
     }
 }
@@ -85,6 +109,12 @@ impl ScalarFunction for Substring {
                     inputs,
                     function_impl: Box::new(SubstringFromImpl),
                 }),
+                (DataType::Binary, DataType::Int64) => Ok(PlannedScalarFunction {
+                    function: Box::new(*self),
+                    return_type: DataType::Binary,
+                    inputs,
+                    function_impl: Box::new(BinarySubstringFromImpl),
+                }),
                 (a, b) => Err(invalid_input_types_error(self, &[a, b])),
             },
             3 => match (&datatypes[0], &datatypes[1], &datatypes[2]) {
@@ -94,6 +124,12 @@ impl ScalarFunction for Substring {
                     inputs,
                     function_impl: Box::new(SubstringFromToImpl),
                 }),
+                (DataType::Binary, DataType::Int64, DataType::Int64) => Ok(PlannedScalarFunction {
+                    function: Box::new(*self),
+                    return_type: DataType::Binary,
+                    inputs,
+                    function_impl: Box::new(BinarySubstringFromToImpl),
+                }),
                 (a, b, c) => Err(invalid_input_types_error(self, &[a, b, c])),
             },
             _ => Err(invalid_input_types_error(self, &datatypes)),
@@ -112,7 +148,7 @@ impl ScalarFunctionImpl for SubstringFromImpl {
             inputs[1],
             ArrayBuilder {
                 datatype: DataType::Utf8,
-                buffer: GermanVarlenBuffer::with_len(len),
+                buffer: GermanVarlenBuffer::with_len_sharing(len, inputs[0]),
             },
             |s, from, buf| buf.put(substring_from(s, from)),
         )
@@ -131,13 +167,50 @@ impl ScalarFunctionImpl for SubstringFromToImpl {
             inputs[2],
             ArrayBuilder {
                 datatype: DataType::Utf8,
-                buffer: GermanVarlenBuffer::with_len(len),
+                buffer: GermanVarlenBuffer::with_len_sharing(len, inputs[0]),
             },
             |s, from, count, buf| buf.put(substring_from_count(s, from, count)),
         )
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct BinarySubstringFromImpl;
+
+impl ScalarFunctionImpl for BinarySubstringFromImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let len = inputs[0].logical_len();
+        BinaryExecutor::execute::<PhysicalBinary, PhysicalI64, _, _>(
+            inputs[0],
+            inputs[1],
+            ArrayBuilder {
+                datatype: DataType::Binary,
+                buffer: GermanVarlenBuffer::with_len_sharing(len, inputs[0]),
+            },
+            |b, from, buf| buf.put(binary_substring_from_count(b, from, i64::MAX)),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BinarySubstringFromToImpl;
+
+impl ScalarFunctionImpl for BinarySubstringFromToImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let len = inputs[0].logical_len();
+        TernaryExecutor::execute::<PhysicalBinary, PhysicalI64, PhysicalI64, _, _>(
+            inputs[0],
+            inputs[1],
+            inputs[2],
+            ArrayBuilder {
+                datatype: DataType::Binary,
+                buffer: GermanVarlenBuffer::with_len_sharing(len, inputs[0]),
+            },
+            |b, from, count, buf| buf.put(binary_substring_from_count(b, from, count)),
+        )
+    }
+}
+
 fn substring_from(s: &str, from: i64) -> &str {
     let start = (from - 1) as usize;
     let mut chars = s.chars();
@@ -174,9 +247,29 @@ fn substring_from_count(s: &str, from: i64, count: i64) -> &str {
     }
 }
 
+/// Slice bytes using a 1-based start index and a byte count.
+///
+/// Matches postgres, the range is computed before clamping to the bounds of
+/// the input, so a start before the first byte reduces the number of bytes
+/// returned.
+fn binary_substring_from_count(b: &[u8], from: i64, count: i64) -> &[u8] {
+    let len = b.len() as i64;
+    let start = from.saturating_sub(1);
+    let end = start.saturating_add(count.max(0));
+
+    let start = start.clamp(0, len);
+    let end = end.clamp(start, len);
+    &b[start as usize..end as usize]
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::arrays::array::{ArrayData, BinaryData};
+    use crate::arrays::scalar::ScalarValue;
+    use crate::arrays::storage::PrimitiveStorage;
 
     #[test]
     fn substring_from_cases() {
@@ -215,4 +308,52 @@ mod tests {
             assert_eq!(case.1, out);
         }
     }
+
+    #[test]
+    fn binary_substring_from_count_cases() {
+        // ((bytes, from, count), expected)
+        let test_cases = [
+            ((&b"hello"[..], 1, 10), &b"hello"[..]),
+            ((&b"hello"[..], 2, 2), &b"el"[..]),
+            ((&b"hello"[..], 8, 10), &b""[..]),
+            ((&b"hello"[..], 1, 0), &b""[..]),
+            ((&b"hello"[..], 0, 2), &b"h"[..]),
+            ((&b"hello"[..], 2, i64::MAX), &b"ello"[..]),
+        ];
+
+        for case in test_cases {
+            let out = binary_substring_from_count(case.0 .0, case.0 .1, case.0 .2);
+            assert_eq!(case.1, out);
+        }
+    }
+
+    fn german_data(array: &Array) -> Arc<PrimitiveStorage<u8>> {
+        match array.array_data() {
+            ArrayData::Binary(BinaryData::German(storage)) => storage.data().clone(),
+            other => panic!("unexpected array data: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn substring_shares_input_data() {
+        let strings = Array::from_iter([
+            "a string too long to inline",
+            "short",
+            "another string too long to inline",
+        ]);
+        let from = Array::from_iter([3_i64, 2, 9]);
+
+        let out = SubstringFromImpl.execute(&[&strings, &from]).unwrap();
+
+        assert_eq!(
+            ScalarValue::from("string too long to inline"),
+            out.logical_value(0).unwrap()
+        );
+        assert_eq!(ScalarValue::from("hort"), out.logical_value(1).unwrap());
+        assert_eq!(
+            ScalarValue::from("string too long to inline"),
+            out.logical_value(2).unwrap()
+        );
+        assert!(Arc::ptr_eq(&german_data(&strings), &german_data(&out)));
+    }
 }
@@ -0,0 +1,132 @@
+use rayexec_error::Result;
+
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use crate::arrays::executor::physical_type::PhysicalUtf8;
+use crate::arrays::executor::scalar::TernaryExecutor;
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Translate;
+
+impl FunctionInfo for Translate {
+    fn name(&self) -> &'static str {
+        "translate"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::String,
+                description: "Replace each character in the string that matches a character in 'from' with the corresponding character in 'to'. Characters without a corresponding character in 'to' are removed.",
+                arguments: &["string", "from", "to"],
+                example: Some(Example {
+                    example: "translate('12345', '143', 'ax')",
+                    output: "a2x5",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Translate {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 3)?;
+
+        match (
+            inputs[0].datatype(table_list)?,
+            inputs[1].datatype(table_list)?,
+            inputs[2].datatype(table_list)?,
+        ) {
+            (DataType::Utf8, DataType::Utf8, DataType::Utf8) => (),
+            (a, b, c) => return Err(invalid_input_types_error(self, &[a, b, c])),
+        }
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl: Box::new(TranslateImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranslateImpl;
+
+impl ScalarFunctionImpl for TranslateImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Utf8,
+            buffer: GermanVarlenBuffer::<str>::with_len(inputs[0].logical_len()),
+        };
+
+        let mut string_buf = String::new();
+
+        TernaryExecutor::execute::<PhysicalUtf8, PhysicalUtf8, PhysicalUtf8, _, _>(
+            inputs[0],
+            inputs[1],
+            inputs[2],
+            builder,
+            |s, from, to, buf| {
+                string_buf.clear();
+                translate(s, from, to, &mut string_buf);
+                buf.put(string_buf.as_str());
+            },
+        )
+    }
+}
+
+/// Translate characters in `s` according to the mapping `from` -> `to`,
+/// writing the result to `out`.
+///
+/// Only the first occurrence of a character in `from` is used.
+fn translate(s: &str, from: &str, to: &str, out: &mut String) {
+    for c in s.chars() {
+        match from.chars().position(|f| f == c) {
+            Some(idx) => {
+                if let Some(replacement) = to.chars().nth(idx) {
+                    out.push(replacement);
+                }
+            }
+            None => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translate_cases() {
+        // ((string, from, to), expected)
+        let test_cases = [
+            (("12345", "143", "ax"), "a2x5"),
+            (("hello", "l", "L"), "heLLo"),
+            (("hello", "lo", ""), "he"),
+            (("hello", "", "abc"), "hello"),
+            (("hello", "ll", "ab"), "heaao"),
+            (("straße", "ß", "s"), "strase"),
+            (("", "a", "b"), ""),
+        ];
+
+        for case in test_cases {
+            let mut out = String::new();
+            translate(case.0 .0, case.0 .1, case.0 .2, &mut out);
+            assert_eq!(case.1, out, "case: {case:?}");
+        }
+    }
+}
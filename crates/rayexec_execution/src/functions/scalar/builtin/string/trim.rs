@@ -225,7 +225,7 @@ impl<F: StringTrimOp> ScalarFunctionImpl for TrimWhitespaceImpl<F> {
     fn execute(&self, inputs: &[&Array]) -> Result<Array> {
         let builder = ArrayBuilder {
             datatype: DataType::Utf8,
-            buffer: GermanVarlenBuffer::<str>::with_len(inputs[0].logical_len()),
+            buffer: GermanVarlenBuffer::<str>::with_len_sharing(inputs[0].logical_len(), inputs[0]),
         };
 
         UnaryExecutor::execute::<PhysicalUtf8, _, _>(inputs[0], builder, |s, buf| {
@@ -250,7 +250,7 @@ impl<F: StringTrimOp> ScalarFunctionImpl for TrimPatternImpl<F> {
     fn execute(&self, inputs: &[&Array]) -> Result<Array> {
         let builder = ArrayBuilder {
             datatype: DataType::Utf8,
-            buffer: GermanVarlenBuffer::<str>::with_len(inputs[0].logical_len()),
+            buffer: GermanVarlenBuffer::<str>::with_len_sharing(inputs[0].logical_len(), inputs[0]),
         };
 
         BinaryExecutor::execute::<PhysicalUtf8, PhysicalUtf8, _, _>(
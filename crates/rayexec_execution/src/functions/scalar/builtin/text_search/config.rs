@@ -0,0 +1,126 @@
+#[cfg(feature = "stemming")]
+use std::collections::HashSet;
+#[cfg(feature = "stemming")]
+use std::sync::LazyLock;
+
+use rayexec_error::{RayexecError, Result};
+
+/// Determines how text is broken up into terms for searching.
+///
+/// All configurations split text on non-alphanumeric characters and lowercase
+/// the resulting tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextSearchConfig {
+    /// Use the lowercased tokens as is. This is the default.
+    #[default]
+    Simple,
+    /// Remove English stopwords and reduce the remaining tokens to their stems.
+    #[cfg(feature = "stemming")]
+    English,
+}
+
+impl TextSearchConfig {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "simple" => Ok(TextSearchConfig::Simple),
+            #[cfg(feature = "stemming")]
+            "english" => Ok(TextSearchConfig::English),
+            #[cfg(not(feature = "stemming"))]
+            "english" => Err(RayexecError::new(
+                "Text search config 'english' requires building with the 'stemming' feature",
+            )),
+            _ => Err(RayexecError::new(format!(
+                "Unknown text search config: {name}"
+            ))),
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            TextSearchConfig::Simple => "simple",
+            #[cfg(feature = "stemming")]
+            TextSearchConfig::English => "english",
+        }
+    }
+
+    /// Get the terms for some text in the order they appear.
+    ///
+    /// Terms may be repeated.
+    pub fn terms(&self, text: &str) -> Vec<String> {
+        match self {
+            TextSearchConfig::Simple => tokenize(text).collect(),
+            #[cfg(feature = "stemming")]
+            TextSearchConfig::English => tokenize(text)
+                .filter(|token| !ENGLISH_STOPWORDS.contains(token.as_str()))
+                .map(|token| ENGLISH_STEMMER.stem(&token).into_owned())
+                .collect(),
+        }
+    }
+
+    /// Get the sorted, deduplicated terms for some text.
+    pub fn term_set(&self, text: &str) -> Vec<String> {
+        let mut terms = self.terms(text);
+        terms.sort_unstable();
+        terms.dedup();
+        terms
+    }
+}
+
+/// Split text into lowercased tokens on non-alphanumeric characters.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+}
+
+#[cfg(feature = "stemming")]
+static ENGLISH_STEMMER: LazyLock<rust_stemmers::Stemmer> =
+    LazyLock::new(|| rust_stemmers::Stemmer::create(rust_stemmers::Algorithm::English));
+
+/// Stopwords from the Snowball English stop list.
+#[cfg(feature = "stemming")]
+static ENGLISH_STOPWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    "a about above after again against all am an and any are as at be because been \
+     before being below between both but by can did do does doing down during each \
+     few for from further had has have having he her here hers herself him himself \
+     his how i if in into is it its itself just me more most my myself no nor not \
+     now of off on once only or other our ours ourselves out over own same she \
+     should so some such than that the their theirs them themselves then there \
+     these they this those through to too under until up very was we were what when \
+     where which while who whom why will with you your yours yourself yourselves"
+        .split_whitespace()
+        .collect()
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_splits_and_lowercases() {
+        let tokens: Vec<_> = tokenize("Hello, World! it's  2024").collect();
+        assert_eq!(vec!["hello", "world", "it", "s", "2024"], tokens);
+    }
+
+    #[test]
+    fn simple_term_set() {
+        let terms = TextSearchConfig::Simple.term_set("the cat and THE hat");
+        assert_eq!(vec!["and", "cat", "hat", "the"], terms);
+    }
+
+    #[test]
+    fn config_from_name() {
+        assert_eq!(
+            TextSearchConfig::Simple,
+            TextSearchConfig::from_name("SIMPLE").unwrap()
+        );
+        TextSearchConfig::from_name("klingon").unwrap_err();
+    }
+
+    #[cfg(feature = "stemming")]
+    #[test]
+    fn english_terms() {
+        let terms = TextSearchConfig::English.terms("The cats were running quickly");
+        assert_eq!(vec!["cat", "run", "quick"], terms);
+    }
+}
@@ -0,0 +1,183 @@
+use rayexec_error::Result;
+
+use super::{plan_config, TextSearchConfig};
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::builder::{ArrayBuilder, BooleanBuffer};
+use crate::arrays::executor::physical_type::PhysicalUtf8;
+use crate::arrays::executor::scalar::{BinaryExecutor, UnaryExecutor};
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{
+    invalid_input_types_error,
+    plan_check_num_args_one_of,
+    FunctionInfo,
+    Signature,
+};
+use crate::logical::binder::table_list::TableList;
+use crate::optimizer::expr_rewrite::const_fold::ConstFold;
+use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
+
+/// Checks if a document contains all terms in a query.
+///
+/// `<document> MATCH <query>` is bound to this function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Matches;
+
+impl FunctionInfo for Matches {
+    fn name(&self) -> &'static str {
+        "matches"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Boolean,
+                doc: Some(&Documentation {
+                    category: Category::TextSearch,
+                    description:
+                        "Check if a document contains all query terms using the 'simple' config.",
+                    arguments: &["document", "query"],
+                    example: Some(Example {
+                        example: "matches('The quick brown fox', 'FOX quick')",
+                        output: "true",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::Boolean,
+                doc: Some(&Documentation {
+                    category: Category::TextSearch,
+                    description:
+                        "Check if a document contains all query terms using the given config.",
+                    arguments: &["document", "query", "config"],
+                    example: Some(Example {
+                        example: "matches('The foxes jumped', 'fox jumping', 'english')",
+                        output: "true",
+                    }),
+                }),
+            },
+        ]
+    }
+}
+
+impl ScalarFunction for Matches {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args_one_of(self, &inputs, [2, 3])?;
+        let datatypes = inputs
+            .iter()
+            .map(|expr| expr.datatype(table_list))
+            .collect::<Result<Vec<_>>>()?;
+        if datatypes.iter().any(|dt| dt != &DataType::Utf8) {
+            return Err(invalid_input_types_error(self, &datatypes));
+        }
+
+        let config = match inputs.len() {
+            3 => plan_config(table_list, &inputs[2])?,
+            _ => TextSearchConfig::default(),
+        };
+
+        let function_impl: Box<dyn ScalarFunctionImpl> = if inputs[1].is_const_foldable() {
+            let query = ConstFold::rewrite(table_list, inputs[1].clone())?
+                .try_into_scalar()?
+                .try_into_string()?;
+
+            Box::new(MatchesConstantImpl {
+                config,
+                terms: config.term_set(&query),
+            })
+        } else {
+            Box::new(MatchesImpl { config })
+        };
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Boolean,
+            inputs,
+            function_impl,
+        })
+    }
+}
+
+/// Check if a document contains all of the given terms.
+///
+/// A query without any terms (e.g. only stopwords) matches nothing.
+pub fn document_matches(config: TextSearchConfig, document: &str, terms: &[String]) -> bool {
+    if terms.is_empty() {
+        return false;
+    }
+    let document_terms = config.term_set(document);
+    terms
+        .iter()
+        .all(|term| document_terms.binary_search(term).is_ok())
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchesConstantImpl {
+    pub config: TextSearchConfig,
+    pub terms: Vec<String>,
+}
+
+impl ScalarFunctionImpl for MatchesConstantImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Boolean,
+            buffer: BooleanBuffer::with_len(inputs[0].logical_len()),
+        };
+
+        UnaryExecutor::execute::<PhysicalUtf8, _, _>(inputs[0], builder, |document, buf| {
+            buf.put(&document_matches(self.config, document, &self.terms))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchesImpl {
+    pub config: TextSearchConfig,
+}
+
+impl ScalarFunctionImpl for MatchesImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Boolean,
+            buffer: BooleanBuffer::with_len(inputs[0].logical_len()),
+        };
+
+        BinaryExecutor::execute::<PhysicalUtf8, PhysicalUtf8, _, _>(
+            inputs[0],
+            inputs[1],
+            builder,
+            |document, query, buf| {
+                let terms = self.config.term_set(query);
+                buf.put(&document_matches(self.config, document, &terms))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_all_terms() {
+        let config = TextSearchConfig::Simple;
+        let terms = config.term_set("fox QUICK");
+        assert!(document_matches(config, "The quick brown fox", &terms));
+        assert!(!document_matches(config, "The quick brown dog", &terms));
+    }
+
+    #[test]
+    fn empty_query_matches_nothing() {
+        assert!(!document_matches(TextSearchConfig::Simple, "anything", &[]));
+    }
+}
@@ -0,0 +1,79 @@
+//! Full-text search functions.
+
+mod config;
+pub use config::*;
+
+mod tokenize;
+pub use tokenize::*;
+
+mod to_tsvector;
+pub use to_tsvector::*;
+
+mod matches;
+pub use matches::*;
+use rayexec_error::{RayexecError, Result};
+
+use crate::arrays::array::Array;
+use crate::arrays::bitmap::Bitmap;
+use crate::arrays::datatype::{DataType, ListTypeMeta};
+use crate::arrays::executor::physical_type::PhysicalUtf8;
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::arrays::storage::{GermanVarlenStorage, ListItemMetadata, ListStorage};
+use crate::expr::Expression;
+use crate::logical::binder::table_list::TableList;
+use crate::optimizer::expr_rewrite::const_fold::ConstFold;
+use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
+
+/// Get the text search config from a constant expression.
+fn plan_config(table_list: &TableList, expr: &Expression) -> Result<TextSearchConfig> {
+    if !expr.is_const_foldable() {
+        return Err(RayexecError::new("Text search config must be a constant"));
+    }
+    let name = ConstFold::rewrite(table_list, expr.clone())?
+        .try_into_scalar()?
+        .try_into_string()?;
+    TextSearchConfig::from_name(&name)
+}
+
+/// Build a list array of terms by applying `terms_fn` to every string in the
+/// input.
+fn terms_list_array<F>(input: &Array, mut terms_fn: F) -> Result<Array>
+where
+    F: FnMut(&str) -> Vec<String>,
+{
+    let len = input.logical_len();
+
+    let mut validity = Bitmap::new_with_all_true(len);
+    let mut metadatas = Vec::with_capacity(len);
+    let mut terms = GermanVarlenStorage::with_metadata_capacity(len);
+    let mut result = Ok(());
+
+    UnaryExecutor::for_each::<PhysicalUtf8, _>(input, |idx, text| {
+        let offset = terms.len() as i32;
+        match text {
+            Some(text) => {
+                let row_terms = terms_fn(text);
+                for term in &row_terms {
+                    if let Err(e) = terms.try_push(term.as_bytes()) {
+                        result = Err(e);
+                    }
+                }
+                metadatas.push(ListItemMetadata {
+                    offset,
+                    len: row_terms.len() as i32,
+                });
+            }
+            None => {
+                validity.set_unchecked(idx, false);
+                metadatas.push(ListItemMetadata { offset, len: 0 });
+            }
+        }
+    })?;
+    result?;
+
+    Ok(Array::new_with_validity_and_array_data(
+        DataType::List(ListTypeMeta::new(DataType::Utf8)),
+        validity,
+        ListStorage::try_new(metadatas, Array::new_with_array_data(DataType::Utf8, terms))?,
+    ))
+}
@@ -0,0 +1,102 @@
+use rayexec_error::Result;
+
+use super::{plan_config, terms_list_array, TextSearchConfig};
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId, ListTypeMeta};
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{
+    invalid_input_types_error,
+    plan_check_num_args_one_of,
+    FunctionInfo,
+    Signature,
+};
+use crate::logical::binder::table_list::TableList;
+
+/// Produces the sorted, deduplicated terms for a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToTsvector;
+
+impl FunctionInfo for ToTsvector {
+    fn name(&self) -> &'static str {
+        "to_tsvector"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[
+            Signature {
+                positional_args: &[DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::List,
+                doc: Some(&Documentation {
+                    category: Category::TextSearch,
+                    description:
+                        "Get the sorted, unique terms of a document using the 'simple' config.",
+                    arguments: &["document"],
+                    example: Some(Example {
+                        example: "to_tsvector('the cat and the hat')",
+                        output: "[and, cat, hat, the]",
+                    }),
+                }),
+            },
+            Signature {
+                positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+                variadic_arg: None,
+                return_type: DataTypeId::List,
+                doc: Some(&Documentation {
+                    category: Category::TextSearch,
+                    description:
+                        "Get the sorted, unique terms of a document using the given config.",
+                    arguments: &["config", "document"],
+                    example: Some(Example {
+                        example: "to_tsvector('english', 'the cats and the hats')",
+                        output: "[cat, hat]",
+                    }),
+                }),
+            },
+        ]
+    }
+}
+
+impl ScalarFunction for ToTsvector {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args_one_of(self, &inputs, [1, 2])?;
+        let datatypes = inputs
+            .iter()
+            .map(|expr| expr.datatype(table_list))
+            .collect::<Result<Vec<_>>>()?;
+        if datatypes.iter().any(|dt| dt != &DataType::Utf8) {
+            return Err(invalid_input_types_error(self, &datatypes));
+        }
+
+        let config = match inputs.len() {
+            2 => plan_config(table_list, &inputs[0])?,
+            _ => TextSearchConfig::default(),
+        };
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::List(ListTypeMeta::new(DataType::Utf8)),
+            inputs,
+            function_impl: Box::new(ToTsvectorImpl { config }),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ToTsvectorImpl {
+    pub config: TextSearchConfig,
+}
+
+impl ScalarFunctionImpl for ToTsvectorImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        // Document is always the last argument.
+        let document = inputs[inputs.len() - 1];
+        terms_list_array(document, |text| self.config.term_set(text))
+    }
+}
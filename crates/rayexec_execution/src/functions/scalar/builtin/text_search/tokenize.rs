@@ -0,0 +1,67 @@
+use rayexec_error::Result;
+
+use super::{terms_list_array, tokenize};
+use crate::arrays::array::Array;
+use crate::arrays::datatype::{DataType, DataTypeId, ListTypeMeta};
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tokenize;
+
+impl FunctionInfo for Tokenize {
+    fn name(&self) -> &'static str {
+        "tokenize"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::List,
+            doc: Some(&Documentation {
+                category: Category::TextSearch,
+                description:
+                    "Split a string into lowercased tokens on non-alphanumeric characters.",
+                arguments: &["string"],
+                example: Some(Example {
+                    example: "tokenize('Hello, World!')",
+                    output: "[hello, world]",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for Tokenize {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+        match inputs[0].datatype(table_list)? {
+            DataType::Utf8 => (),
+            other => return Err(invalid_input_types_error(self, &[other])),
+        }
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::List(ListTypeMeta::new(DataType::Utf8)),
+            inputs,
+            function_impl: Box::new(TokenizeImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenizeImpl;
+
+impl ScalarFunctionImpl for TokenizeImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        terms_list_array(inputs[0], |text| tokenize(text).collect())
+    }
+}
@@ -3,6 +3,7 @@ pub mod builtin;
 use std::fmt::Debug;
 use std::hash::Hash;
 
+use builtin::random::RandomState;
 use dyn_clone::DynClone;
 use rayexec_error::Result;
 
@@ -12,24 +13,11 @@ use crate::arrays::datatype::DataType;
 use crate::expr::Expression;
 use crate::logical::binder::table_list::TableList;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum FunctionVolatility {
-    /// Every call to this function with the same arguemnts is not guaranteed to
-    /// return the same value.
-    Volatile,
-    /// This function is consistent within the query.
-    Consistent,
-}
-
 /// A generic scalar function that can specialize into a more specific function
 /// depending on input types.
 ///
 /// Generic scalar functions must be cheaply cloneable.
 pub trait ScalarFunction: FunctionInfo + Debug + Sync + Send + DynClone {
-    fn volatility(&self) -> FunctionVolatility {
-        FunctionVolatility::Consistent
-    }
-
     /// Plan a scalar function based on expression inputs.
     ///
     /// This allows functions to check for constant expressions and generate a
@@ -104,6 +92,71 @@ impl Hash for PlannedScalarFunction {
 
 pub trait ScalarFunctionImpl: Debug + Sync + Send + DynClone {
     fn execute(&self, inputs: &[&Array]) -> Result<Array>;
+
+    /// Execute a function that takes no inputs, producing `num_rows` values.
+    ///
+    /// By default the function is executed once and the value is repeated for
+    /// every row. Volatile functions that produce a different value per row
+    /// should override this.
+    fn execute_without_inputs(&self, num_rows: usize) -> Result<Array> {
+        let out = self.execute(&[])?;
+        out.logical_value(0)?.as_array(num_rows)
+    }
+
+    /// Create a copy of this implementation that uses the session's random
+    /// state.
+    ///
+    /// Returns None if the function doesn't produce random values.
+    fn with_random_state(&self, _state: &RandomState) -> Option<Box<dyn ScalarFunctionImpl>> {
+        None
+    }
+
+    /// Create a copy of this implementation that uses the time the query
+    /// started (microseconds since the epoch).
+    ///
+    /// Returns None if the function doesn't depend on the current time.
+    fn with_query_start(&self, _micros: i64) -> Option<Box<dyn ScalarFunctionImpl>> {
+        None
+    }
+
+    /// Build an index for a join using this function as the join condition.
+    ///
+    /// `arg` is the index of the argument that's coming from the build side of
+    /// the join, and `build_arrays` contains that argument's values for every
+    /// batch on the build side. The other argument's values are used to probe
+    /// the index.
+    ///
+    /// Returns None if this function doesn't support join indexes, in which
+    /// case the join falls back to evaluating the function for every pair of
+    /// rows.
+    fn build_join_index(
+        &self,
+        _arg: usize,
+        _build_arrays: &[&Array],
+    ) -> Result<Option<Box<dyn JoinIndex>>> {
+        Ok(None)
+    }
+}
+
+/// An index over the build side of a join on a two argument predicate.
+pub trait JoinIndex: Debug + Sync + Send {
+    /// Find the build side rows that may satisfy the predicate for each row in
+    /// the probe array.
+    ///
+    /// Rows that aren't returned must never satisfy the predicate. Returned
+    /// rows are still checked by evaluating the predicate.
+    fn probe(&self, probe: &Array) -> Result<Vec<JoinCandidate>>;
+}
+
+/// A pair of rows that may satisfy a join predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JoinCandidate {
+    /// Row in the probe array.
+    pub probe_row: usize,
+    /// Index of the batch on the build side.
+    pub build_batch: usize,
+    /// Row in the build side batch.
+    pub build_row: usize,
 }
 
 impl Clone for Box<dyn ScalarFunctionImpl> {
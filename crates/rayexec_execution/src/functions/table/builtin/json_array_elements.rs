@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::task::{Context, Waker};
+
+use rayexec_error::{RayexecError, Result};
+use serde_json::Value;
+
+use crate::arrays::array::Array;
+use crate::arrays::batch::Batch;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::executor::physical_type::PhysicalUtf8;
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::arrays::field::{Field, Schema};
+use crate::arrays::scalar::OwnedScalarValue;
+use crate::execution::operators::{PollFinalize, PollPush};
+use crate::expr::Expression;
+use crate::functions::documentation::{Category, Documentation, Example};
+use crate::functions::table::inout::{InOutPollPull, TableInOutFunction, TableInOutPartitionState};
+use crate::functions::table::{
+    InOutPlanner,
+    PlannedTableFunction,
+    TableFunction,
+    TableFunctionImpl,
+    TableFunctionPlanner,
+};
+use crate::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo, Signature};
+use crate::logical::binder::table_list::TableList;
+use crate::logical::statistics::StatisticsValue;
+
+#[derive(Debug, Clone, Copy)]
+pub struct JsonArrayElements;
+
+impl FunctionInfo for JsonArrayElements {
+    fn name(&self) -> &'static str {
+        "json_array_elements"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Json],
+            variadic_arg: None,
+            return_type: DataTypeId::Json,
+            doc: Some(&Documentation {
+                category: Category::Table,
+                description: "Expand a JSON array into a table containing a row for each element.",
+                arguments: &["json"],
+                example: Some(Example {
+                    example: "json_array_elements('[1, 2, 3]')",
+                    output: "1\n2\n3",
+                }),
+            }),
+        }]
+    }
+}
+
+impl TableFunction for JsonArrayElements {
+    fn planner(&self) -> TableFunctionPlanner {
+        TableFunctionPlanner::InOut(self)
+    }
+}
+
+impl InOutPlanner for JsonArrayElements {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        positional_inputs: Vec<Expression>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> Result<PlannedTableFunction> {
+        plan_check_num_args(self, &positional_inputs, 1)?;
+        if !named_inputs.is_empty() {
+            return Err(RayexecError::new(
+                "json_array_elements does not accept named arguments",
+            ));
+        }
+
+        match positional_inputs[0].datatype(table_list)? {
+            DataType::Json => (),
+            other => return Err(invalid_input_types_error(self, &[other])),
+        }
+
+        let schema = Schema::new([Field::new("value", DataType::Json, true)]);
+
+        Ok(PlannedTableFunction {
+            function: Box::new(*self),
+            positional_inputs,
+            named_inputs,
+            function_impl: TableFunctionImpl::InOut(Box::new(JsonArrayElementsInOutImpl)),
+            cardinality: StatisticsValue::Unknown,
+            schema,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonArrayElementsInOutImpl;
+
+impl TableInOutFunction for JsonArrayElementsInOutImpl {
+    fn create_states(
+        &self,
+        num_partitions: usize,
+        _batch_size: usize,
+    ) -> Result<Vec<Box<dyn TableInOutPartitionState>>> {
+        let states: Vec<_> = (0..num_partitions)
+            .map(|_| {
+                Box::new(JsonArrayElementsInOutPartitionState {
+                    input: None,
+                    input_num_rows: 0,
+                    current_row: 0,
+                    finished: false,
+                    push_waker: None,
+                    pull_waker: None,
+                }) as _
+            })
+            .collect();
+
+        Ok(states)
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonArrayElementsInOutPartitionState {
+    /// The array containing the JSON arrays we're expanding.
+    input: Option<Array>,
+    /// Number of rows in the input batch.
+    input_num_rows: usize,
+    /// Current row we're processing.
+    current_row: usize,
+    /// If we're finished receiving inputs.
+    finished: bool,
+    /// Push side waker.
+    ///
+    /// Set if we still have rows to process.
+    push_waker: Option<Waker>,
+    /// Pull side waker.
+    ///
+    /// Set if we've processed all rows and need more input.
+    pull_waker: Option<Waker>,
+}
+
+impl TableInOutPartitionState for JsonArrayElementsInOutPartitionState {
+    fn poll_push(&mut self, cx: &mut Context, inputs: Batch) -> Result<PollPush> {
+        if self.current_row < self.input_num_rows {
+            // Still processing inputs, come back later.
+            self.push_waker = Some(cx.waker().clone());
+            if let Some(waker) = self.pull_waker.take() {
+                waker.wake();
+            }
+
+            return Ok(PollPush::Pending(inputs));
+        }
+
+        self.input_num_rows = inputs.num_rows();
+        self.current_row = 0;
+
+        match inputs.columns().len() {
+            1 => self.input = inputs.into_arrays().pop(),
+            other => {
+                return Err(RayexecError::new("Invalid number of arrays").with_field("len", other))
+            }
+        }
+
+        if let Some(waker) = self.pull_waker.take() {
+            waker.wake();
+        }
+
+        Ok(PollPush::Pushed)
+    }
+
+    fn poll_finalize_push(&mut self, _cx: &mut Context) -> Result<PollFinalize> {
+        self.finished = true;
+
+        if let Some(waker) = self.pull_waker.take() {
+            waker.wake();
+        }
+
+        Ok(PollFinalize::Finalized)
+    }
+
+    fn poll_pull(&mut self, cx: &mut Context) -> Result<InOutPollPull> {
+        // Skip over rows that don't produce any elements (NULLs and empty
+        // arrays).
+        if self.current_row < self.input_num_rows {
+            let input = self.input.as_ref().unwrap();
+            let row = self.current_row;
+            let elements = match UnaryExecutor::value_at::<PhysicalUtf8>(input, row)? {
+                Some(json) => json_array_elements(json)?,
+                None => Vec::new(),
+            };
+
+            // Next pull works on the next row.
+            // Next pull works on the next row. Rows without elements still
+            // produce an empty batch so the pipeline keeps making progress.
+            self.current_row += 1;
+
+            // If these inputs are done, go ahead and let the push side know.
+            if self.current_row >= self.input_num_rows {
+                if let Some(waker) = self.push_waker.take() {
+                    waker.wake()
+                }
+            }
+
+            let row_nums = vec![row; elements.len()];
+            let output = Array::from_iter(elements).try_with_datatype(DataType::Json)?;
+            let batch = Batch::try_new([output])?;
+
+            return Ok(InOutPollPull::Batch { batch, row_nums });
+        }
+
+        if self.finished {
+            return Ok(InOutPollPull::Exhausted);
+        }
+
+        // We're done with these inputs. Come back later.
+        self.pull_waker = Some(cx.waker().clone());
+        if let Some(waker) = self.push_waker.take() {
+            waker.wake();
+        }
+
+        Ok(InOutPollPull::Pending)
+    }
+}
+
+/// Get the serialized elements of a JSON array.
+fn json_array_elements(json: &str) -> Result<Vec<String>> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| RayexecError::with_source("Invalid JSON", Box::new(e)))?;
+
+    match value {
+        Value::Array(elements) => Ok(elements.iter().map(|v| v.to_string()).collect()),
+        _ => Err(RayexecError::new(
+            "Cannot extract elements from a JSON value that isn't an array",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_elements() {
+        let elements = json_array_elements(r#"[1, "a", {"b": null}, []]"#).unwrap();
+        assert_eq!(vec!["1", "\"a\"", "{\"b\":null}", "[]"], elements);
+    }
+
+    #[test]
+    fn array_elements_not_array() {
+        json_array_elements(r#"{"a": 1}"#).unwrap_err();
+    }
+}
@@ -1,11 +1,24 @@
+pub mod json_array_elements;
+pub mod query_profile;
 pub mod series;
 pub mod system;
 pub mod unnest;
 
 use std::sync::LazyLock;
 
+use json_array_elements::JsonArrayElements;
+use query_profile::QueryProfile;
 use series::GenerateSeries;
-use system::{ListDatabases, ListFunctions, ListSchemas, ListTables};
+use system::{
+    ListColumns,
+    ListDatabases,
+    ListFunctions,
+    ListMemory,
+    ListQueries,
+    ListSchemas,
+    ListTables,
+    ListViews,
+};
 use unnest::Unnest;
 
 use super::TableFunction;
@@ -14,10 +27,16 @@ pub static BUILTIN_TABLE_FUNCTIONS: LazyLock<Vec<Box<dyn TableFunction>>> = Lazy
     vec![
         Box::new(GenerateSeries),
         Box::new(Unnest),
+        Box::new(JsonArrayElements),
         // Various list system object functions.
         Box::new(ListDatabases::new()),
         Box::new(ListSchemas::new()),
         Box::new(ListTables::new()),
+        Box::new(ListViews::new()),
+        Box::new(ListColumns::new()),
         Box::new(ListFunctions::new()),
+        Box::new(ListQueries::new()),
+        Box::new(ListMemory::new()),
+        Box::new(QueryProfile),
     ]
 });
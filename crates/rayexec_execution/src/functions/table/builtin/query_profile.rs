@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use rayexec_error::{RayexecError, Result};
+use uuid::Uuid;
+
+use crate::arrays::array::Array;
+use crate::arrays::batch::Batch;
+use crate::arrays::datatype::{DataType, DataTypeId};
+use crate::arrays::field::{Field, Schema};
+use crate::arrays::scalar::OwnedScalarValue;
+use crate::database::DatabaseContext;
+use crate::engine::query_log::{self, QueryLogVisibility};
+use crate::execution::executable::profiler::ExecutionProfileData;
+use crate::expr;
+use crate::functions::table::{
+    try_get_positional,
+    PlannedTableFunction,
+    ScanPlanner,
+    TableFunction,
+    TableFunctionImpl,
+    TableFunctionPlanner,
+};
+use crate::functions::{FunctionInfo, Signature};
+use crate::logical::statistics::StatisticsValue;
+use crate::storage::table_storage::{
+    DataTable,
+    DataTableScan,
+    DataVersion,
+    EmptyTableScan,
+    ProjectedScan,
+    Projections,
+};
+
+/// Returns per-operator execution profile data for a query.
+///
+/// Each row is a single operator in a single partition pipeline. The `stack`
+/// column is formatted as a semicolon-separated stack (pipeline, partition,
+/// operator) so that rows can be folded directly into flame graphs using
+/// either `cpu_ms` or `wait_ms` as the sample value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryProfile;
+
+impl FunctionInfo for QueryProfile {
+    fn name(&self) -> &'static str {
+        "query_profile"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: None,
+        }]
+    }
+}
+
+impl TableFunction for QueryProfile {
+    fn planner(&self) -> TableFunctionPlanner {
+        TableFunctionPlanner::Scan(self)
+    }
+}
+
+impl ScanPlanner for QueryProfile {
+    fn plan<'a>(
+        &self,
+        context: &'a DatabaseContext,
+        positional_inputs: Vec<OwnedScalarValue>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> BoxFuture<'a, Result<PlannedTableFunction>> {
+        let planned = plan_query_profile(
+            positional_inputs,
+            named_inputs,
+            context.query_log_visibility(),
+        );
+        Box::pin(async move { planned })
+    }
+}
+
+fn plan_query_profile(
+    positional_inputs: Vec<OwnedScalarValue>,
+    named_inputs: HashMap<String, OwnedScalarValue>,
+    visibility: &QueryLogVisibility,
+) -> Result<PlannedTableFunction> {
+    if !named_inputs.is_empty() {
+        return Err(RayexecError::new(
+            "query_profile does not accept named arguments",
+        ));
+    }
+
+    let query_id = try_get_positional(&QueryProfile, 0, &positional_inputs)?.try_as_str()?;
+    let query_id = Uuid::parse_str(query_id)
+        .map_err(|e| RayexecError::with_source("Invalid query id", Box::new(e)))?;
+
+    // Check that the query exists now so we can error during planning. Queries
+    // from other sessions are treated as unknown unless they're visible.
+    let visible = query_log::visible_snapshot(visibility)
+        .iter()
+        .any(|entry| entry.query_id == query_id);
+    if !visible {
+        return Err(RayexecError::new(format!("Unknown query id: {query_id}")));
+    }
+
+    Ok(PlannedTableFunction {
+        function: Box::new(QueryProfile),
+        positional_inputs: positional_inputs.into_iter().map(expr::lit).collect(),
+        named_inputs,
+        function_impl: TableFunctionImpl::Scan(Arc::new(QueryProfileDataTable { query_id })),
+        cardinality: StatisticsValue::Unknown,
+        schema: query_profile_schema(),
+    })
+}
+
+fn query_profile_schema() -> Schema {
+    Schema::new([
+        Field::new("pipeline_id", DataType::Int64, false),
+        Field::new("partition", DataType::Int64, false),
+        Field::new("operator_idx", DataType::Int64, false),
+        Field::new("operator", DataType::Utf8, false),
+        Field::new("stack", DataType::Utf8, false),
+        Field::new("rows_read", DataType::Int64, false),
+        Field::new("rows_emitted", DataType::Int64, false),
+        Field::new("polls", DataType::Int64, false),
+        Field::new("pending", DataType::Int64, false),
+        Field::new("executions", DataType::Int64, false),
+        Field::new("cpu_ms", DataType::Float64, false),
+        Field::new("wait_ms", DataType::Float64, false),
+    ])
+}
+
+fn profile_to_batch(profile: &ExecutionProfileData) -> Result<Batch> {
+    let mut pipeline_ids = Vec::new();
+    let mut partitions = Vec::new();
+    let mut operator_idxs = Vec::new();
+    let mut operators = Vec::new();
+    let mut stacks = Vec::new();
+    let mut rows_read = Vec::new();
+    let mut rows_emitted = Vec::new();
+    let mut polls = Vec::new();
+    let mut pending = Vec::new();
+    let mut executions = Vec::new();
+    let mut cpu_ms = Vec::new();
+    let mut wait_ms = Vec::new();
+
+    for (pipeline_id, pipeline) in &profile.pipelines {
+        for (partition, data) in &pipeline.partitions {
+            for (idx, (operator, explain)) in
+                data.operators.iter().zip(&data.explain_strings).enumerate()
+            {
+                pipeline_ids.push(pipeline_id.0 as i64);
+                partitions.push(*partition as i64);
+                operator_idxs.push(idx as i64);
+                operators.push(explain.clone());
+                stacks.push(format!(
+                    "pipeline {};partition {partition};{explain}",
+                    pipeline_id.0
+                ));
+                rows_read.push(operator.rows_read as i64);
+                rows_emitted.push(operator.rows_emitted as i64);
+                polls.push(operator.polls as i64);
+                pending.push(operator.pending as i64);
+                executions.push(data.executions as i64);
+                cpu_ms.push(operator.elapsed.as_secs_f64() * 1000.0);
+                wait_ms.push(operator.wait_elapsed.as_secs_f64() * 1000.0);
+            }
+        }
+    }
+
+    Batch::try_new([
+        Array::from_iter(pipeline_ids),
+        Array::from_iter(partitions),
+        Array::from_iter(operator_idxs),
+        Array::from_iter(operators),
+        Array::from_iter(stacks),
+        Array::from_iter(rows_read),
+        Array::from_iter(rows_emitted),
+        Array::from_iter(polls),
+        Array::from_iter(pending),
+        Array::from_iter(executions),
+        Array::from_iter(cpu_ms),
+        Array::from_iter(wait_ms),
+    ])
+}
+
+#[derive(Debug, Clone)]
+struct QueryProfileDataTable {
+    query_id: Uuid,
+}
+
+impl DataTable for QueryProfileDataTable {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        _batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let mut scans: Vec<Box<dyn DataTableScan>> = vec![Box::new(ProjectedScan::new(
+            QueryProfileScan {
+                query_id: Some(self.query_id),
+            },
+            projections,
+        )) as _];
+
+        scans.extend((1..num_partitions).map(|_| Box::new(EmptyTableScan) as _));
+
+        Ok(scans)
+    }
+
+    fn data_version(&self) -> DataVersion {
+        // Profile data changes as the query executes.
+        DataVersion::Volatile
+    }
+}
+
+#[derive(Debug)]
+struct QueryProfileScan {
+    /// Query to produce the profile for, None once the profile's been
+    /// produced.
+    query_id: Option<Uuid>,
+}
+
+impl DataTableScan for QueryProfileScan {
+    fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
+        Box::pin(async {
+            let query_id = match self.query_id.take() {
+                Some(query_id) => query_id,
+                None => return Ok(None),
+            };
+
+            // Query may have been evicted from the log since planning.
+            let profile = query_log::execution_profile(query_id).unwrap_or_default();
+
+            Ok(Some(profile_to_batch(&profile)?))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::execution::executable::pipeline::PipelineId;
+    use crate::execution::executable::profiler::{
+        OperatorProfileData,
+        PartitionPipelineProfileData,
+        PipelineProfileData,
+    };
+
+    #[test]
+    fn profile_rows() {
+        let profile = ExecutionProfileData {
+            pipelines: BTreeMap::from([(
+                PipelineId(2),
+                PipelineProfileData {
+                    partitions: BTreeMap::from([(
+                        1,
+                        PartitionPipelineProfileData {
+                            executions: 3,
+                            operators: vec![
+                                OperatorProfileData {
+                                    rows_read: 0,
+                                    rows_emitted: 4,
+                                    elapsed: Duration::from_millis(2),
+                                    polls: 2,
+                                    pending: 1,
+                                    wait_elapsed: Duration::from_millis(5),
+                                },
+                                OperatorProfileData {
+                                    rows_read: 4,
+                                    rows_emitted: 0,
+                                    elapsed: Duration::from_millis(1),
+                                    polls: 2,
+                                    pending: 0,
+                                    wait_elapsed: Duration::ZERO,
+                                },
+                            ],
+                            explain_strings: vec!["Scan".to_string(), "Sink".to_string()],
+                        },
+                    )]),
+                },
+            )]),
+        };
+
+        let batch = profile_to_batch(&profile).unwrap();
+        assert_eq!(2, batch.num_rows());
+        assert_eq!(
+            ScalarValue::from("pipeline 2;partition 1;Sink"),
+            batch.column(4).unwrap().logical_value(1).unwrap()
+        );
+        assert_eq!(
+            ScalarValue::Int64(3),
+            batch.column(9).unwrap().logical_value(0).unwrap()
+        );
+        assert_eq!(
+            ScalarValue::Float64(5.0),
+            batch.column(11).unwrap().logical_value(0).unwrap()
+        );
+    }
+
+    #[test]
+    fn unknown_query_id() {
+        let visibility = QueryLogVisibility {
+            session_id: Uuid::new_v4(),
+            all_sessions: true,
+        };
+        let err = plan_query_profile(
+            vec![Uuid::new_v4().to_string().into()],
+            HashMap::new(),
+            &visibility,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Unknown query id"));
+    }
+
+    #[test]
+    fn query_from_other_session() {
+        let query_id = Uuid::new_v4();
+        let _tracker = query_log::register(
+            query_id,
+            Uuid::new_v4(),
+            None,
+            Box::new(|| std::time::Duration::ZERO),
+        );
+
+        let visibility = QueryLogVisibility {
+            session_id: Uuid::new_v4(),
+            all_sessions: false,
+        };
+        let err = plan_query_profile(
+            vec![query_id.to_string().into()],
+            HashMap::new(),
+            &visibility,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Unknown query id"));
+    }
+}
@@ -125,11 +125,12 @@ impl TableInOutFunction for GenerateSeriesInOutImpl {
     fn create_states(
         &self,
         num_partitions: usize,
+        batch_size: usize,
     ) -> Result<Vec<Box<dyn TableInOutPartitionState>>> {
         let states: Vec<_> = (0..num_partitions)
             .map(|_| {
                 Box::new(GenerateSeriesInOutPartitionState {
-                    batch_size: 1024, // TODO
+                    batch_size,
                     batch: None,
                     next_row_idx: 0,
                     finished: false,
@@ -168,7 +169,7 @@ impl SeriesParams {
         debug_assert!(!self.exhausted);
 
         let mut series: Vec<i64> = Vec::new();
-        if self.curr < self.stop && self.step > 0 {
+        if self.curr <= self.stop && self.step > 0 {
             // Going up.
             let mut count = 0;
             while self.curr <= self.stop && count < batch_size {
@@ -176,7 +177,7 @@ impl SeriesParams {
                 self.curr += self.step;
                 count += 1;
             }
-        } else if self.curr > self.stop && self.step < 0 {
+        } else if self.curr >= self.stop && self.step < 0 {
             // Going down.
             let mut count = 0;
             while self.curr >= self.stop && count < batch_size {
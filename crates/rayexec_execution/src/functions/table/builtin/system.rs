@@ -6,20 +6,33 @@ use std::sync::Arc;
 use futures::future::BoxFuture;
 use parking_lot::Mutex;
 use rayexec_error::{OptionExt, RayexecError, Result};
+use rayexec_io::read_cache;
+use tracing::debug;
 
 use crate::arrays::array::Array;
 use crate::arrays::batch::Batch;
 use crate::arrays::bitmap::Bitmap;
+use crate::arrays::buffer_pool;
 use crate::arrays::datatype::{DataType, DataTypeId, ListTypeMeta};
 use crate::arrays::executor::builder::{ArrayDataBuffer, GermanVarlenBuffer};
 use crate::arrays::field::{Field, Schema};
-use crate::arrays::scalar::OwnedScalarValue;
-use crate::arrays::storage::{GermanVarlenStorage, ListItemMetadata, ListStorage};
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::arrays::storage::{
+    BooleanStorage,
+    GermanVarlenStorage,
+    ListItemMetadata,
+    ListStorage,
+    PrimitiveStorage,
+};
 use crate::database::catalog::CatalogTx;
 use crate::database::catalog_entry::{CatalogEntryInner, CatalogEntryType};
+use crate::database::create::{CreateSchemaInfo, CreateTableInfo, OnConflict};
 use crate::database::memory_catalog::MemoryCatalog;
 use crate::database::{AttachInfo, DatabaseContext};
+use crate::engine::query_log::{self, QueryLogVisibility, QueryState};
+use crate::engine::result_cache;
 use crate::expr;
+use crate::expr::comparison_expr::ComparisonOperator;
 use crate::functions::table::{
     PlannedTableFunction,
     ScanPlanner,
@@ -28,10 +41,13 @@ use crate::functions::table::{
     TableFunctionPlanner,
 };
 use crate::functions::{FunctionInfo, Signature};
+use crate::logical::scan_filter::{ScanFilter, ScanFilterType};
 use crate::logical::statistics::StatisticsValue;
+use crate::storage::catalog_storage::CatalogStorage;
 use crate::storage::table_storage::{
     DataTable,
     DataTableScan,
+    DataVersion,
     EmptyTableScan,
     ProjectedScan,
     Projections,
@@ -39,9 +55,26 @@ use crate::storage::table_storage::{
 
 pub trait SystemFunctionImpl: Debug + Sync + Send + Copy + 'static {
     const NAME: &'static str;
+    /// If tables from external catalogs should be loaded into the in-memory
+    /// catalogs before listing.
+    ///
+    /// External tables are normally loaded lazily during resolving, so
+    /// without this we'd only see tables that have already been queried.
+    ///
+    /// Listing an external catalog may require round trips to a remote
+    /// database, so tables are only loaded for catalogs the scan is filtered
+    /// to with `database_name = '...'`. The first column of the function's
+    /// schema must be the database name.
+    const LOAD_EXTERNAL_TABLES: bool = false;
+
     fn schema() -> Schema;
+    /// Create the next batch from the remaining databases.
+    ///
+    /// `query_log` restricts which queries in the query log are visible to
+    /// the session doing the scan.
     fn new_batch(
         databases: &mut VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+        query_log: &QueryLogVisibility,
     ) -> Result<Batch>;
 }
 
@@ -62,6 +95,7 @@ impl SystemFunctionImpl for ListDatabasesImpl {
 
     fn new_batch(
         databases: &mut VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+        _query_log: &QueryLogVisibility,
     ) -> Result<Batch> {
         let len = databases.len();
 
@@ -118,6 +152,7 @@ impl SystemFunctionImpl for ListFunctionsImpl {
 
     fn new_batch(
         databases: &mut VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+        _query_log: &QueryLogVisibility,
     ) -> Result<Batch> {
         let database = databases.pop_front().required("database")?;
 
@@ -144,7 +179,7 @@ impl SystemFunctionImpl for ListFunctionsImpl {
         let mut example_outputs_validity = Bitmap::default();
         let mut example_outputs = GermanVarlenStorage::with_metadata_capacity(0);
 
-        let tx = &CatalogTx {};
+        let tx = &CatalogTx::new();
 
         database.1.for_each_schema(tx, &mut |schema_name, schema| {
             schema.for_each_entry(tx, &mut |_, entry| {
@@ -273,6 +308,7 @@ pub struct ListTablesImpl;
 
 impl SystemFunctionImpl for ListTablesImpl {
     const NAME: &'static str = "list_tables";
+    const LOAD_EXTERNAL_TABLES: bool = true;
 
     fn schema() -> Schema {
         Schema::new([
@@ -284,6 +320,7 @@ impl SystemFunctionImpl for ListTablesImpl {
 
     fn new_batch(
         databases: &mut VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+        _query_log: &QueryLogVisibility,
     ) -> Result<Batch> {
         let database = databases.pop_front().required("database")?;
 
@@ -291,7 +328,7 @@ impl SystemFunctionImpl for ListTablesImpl {
         let mut schema_names = GermanVarlenStorage::with_metadata_capacity(0);
         let mut table_names = GermanVarlenStorage::with_metadata_capacity(0);
 
-        let tx = &CatalogTx {};
+        let tx = &CatalogTx::new();
 
         database.1.for_each_schema(tx, &mut |schema_name, schema| {
             schema.for_each_entry(tx, &mut |_, entry| {
@@ -316,6 +353,134 @@ impl SystemFunctionImpl for ListTablesImpl {
     }
 }
 
+pub type ListViews = SystemFunction<ListViewsImpl>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListViewsImpl;
+
+impl SystemFunctionImpl for ListViewsImpl {
+    const NAME: &'static str = "list_views";
+
+    fn schema() -> Schema {
+        Schema::new([
+            Field::new("database_name", DataType::Utf8, false),
+            Field::new("schema_name", DataType::Utf8, false),
+            Field::new("view_name", DataType::Utf8, false),
+            Field::new("sql", DataType::Utf8, false),
+        ])
+    }
+
+    fn new_batch(
+        databases: &mut VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+        _query_log: &QueryLogVisibility,
+    ) -> Result<Batch> {
+        let database = databases.pop_front().required("database")?;
+
+        let mut database_names = GermanVarlenStorage::with_metadata_capacity(0);
+        let mut schema_names = GermanVarlenStorage::with_metadata_capacity(0);
+        let mut view_names = GermanVarlenStorage::with_metadata_capacity(0);
+        let mut sqls = GermanVarlenStorage::with_metadata_capacity(0);
+
+        let tx = &CatalogTx::new();
+
+        database.1.for_each_schema(tx, &mut |schema_name, schema| {
+            schema.for_each_entry(tx, &mut |_, entry| {
+                let view = match &entry.entry {
+                    CatalogEntryInner::View(view) => view,
+                    _ => return Ok(()),
+                };
+
+                database_names.try_push(database.0.as_bytes())?;
+                schema_names.try_push(schema_name.as_bytes())?;
+                view_names.try_push(entry.name.as_bytes())?;
+                sqls.try_push(view.query_sql.trim().as_bytes())?;
+
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+
+        Batch::try_new([
+            Array::new_with_array_data(DataType::Utf8, database_names),
+            Array::new_with_array_data(DataType::Utf8, schema_names),
+            Array::new_with_array_data(DataType::Utf8, view_names),
+            Array::new_with_array_data(DataType::Utf8, sqls),
+        ])
+    }
+}
+
+pub type ListColumns = SystemFunction<ListColumnsImpl>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListColumnsImpl;
+
+impl SystemFunctionImpl for ListColumnsImpl {
+    const NAME: &'static str = "list_columns";
+    const LOAD_EXTERNAL_TABLES: bool = true;
+
+    fn schema() -> Schema {
+        Schema::new([
+            Field::new("database_name", DataType::Utf8, false),
+            Field::new("schema_name", DataType::Utf8, false),
+            Field::new("table_name", DataType::Utf8, false),
+            Field::new("column_name", DataType::Utf8, false),
+            Field::new("ordinal_position", DataType::Int64, false),
+            Field::new("data_type", DataType::Utf8, false),
+            Field::new("is_nullable", DataType::Boolean, false),
+        ])
+    }
+
+    fn new_batch(
+        databases: &mut VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+        _query_log: &QueryLogVisibility,
+    ) -> Result<Batch> {
+        let database = databases.pop_front().required("database")?;
+
+        let mut database_names = GermanVarlenStorage::with_metadata_capacity(0);
+        let mut schema_names = GermanVarlenStorage::with_metadata_capacity(0);
+        let mut table_names = GermanVarlenStorage::with_metadata_capacity(0);
+        let mut column_names = GermanVarlenStorage::with_metadata_capacity(0);
+        let mut ordinals = Vec::new();
+        let mut data_types = GermanVarlenStorage::with_metadata_capacity(0);
+        let mut nullables = Bitmap::default();
+
+        let tx = &CatalogTx::new();
+
+        database.1.for_each_schema(tx, &mut |schema_name, schema| {
+            schema.for_each_entry(tx, &mut |_, entry| {
+                let table = match &entry.entry {
+                    CatalogEntryInner::Table(table) => table,
+                    _ => return Ok(()),
+                };
+
+                for (idx, field) in table.columns.iter().enumerate() {
+                    database_names.try_push(database.0.as_bytes())?;
+                    schema_names.try_push(schema_name.as_bytes())?;
+                    table_names.try_push(entry.name.as_bytes())?;
+                    column_names.try_push(field.name.as_bytes())?;
+                    // Ordinal positions are 1-based.
+                    ordinals.push(idx as i64 + 1);
+                    data_types.try_push(field.datatype.to_string().as_bytes())?;
+                    nullables.push(field.nullable);
+                }
+
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+
+        Batch::try_new([
+            Array::new_with_array_data(DataType::Utf8, database_names),
+            Array::new_with_array_data(DataType::Utf8, schema_names),
+            Array::new_with_array_data(DataType::Utf8, table_names),
+            Array::new_with_array_data(DataType::Utf8, column_names),
+            Array::new_with_array_data(DataType::Int64, PrimitiveStorage::from(ordinals)),
+            Array::new_with_array_data(DataType::Utf8, data_types),
+            Array::new_with_array_data(DataType::Boolean, BooleanStorage::from(nullables)),
+        ])
+    }
+}
+
 pub type ListSchemas = SystemFunction<ListSchemasImpl>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -333,13 +498,14 @@ impl SystemFunctionImpl for ListSchemasImpl {
 
     fn new_batch(
         databases: &mut VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+        _query_log: &QueryLogVisibility,
     ) -> Result<Batch> {
         let database = databases.pop_front().required("database")?;
 
         let mut database_names = GermanVarlenStorage::with_metadata_capacity(0);
         let mut schema_names = GermanVarlenStorage::with_metadata_capacity(0);
 
-        let tx = &CatalogTx {};
+        let tx = &CatalogTx::new();
 
         database.1.for_each_schema(tx, &mut |schema_name, _| {
             database_names.try_push(database.0.as_bytes())?;
@@ -355,6 +521,170 @@ impl SystemFunctionImpl for ListSchemasImpl {
     }
 }
 
+pub type ListQueries = SystemFunction<ListQueriesImpl>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListQueriesImpl;
+
+impl SystemFunctionImpl for ListQueriesImpl {
+    const NAME: &'static str = "list_queries";
+
+    fn schema() -> Schema {
+        Schema::new([
+            Field::new("query_id", DataType::Utf8, false),
+            Field::new("query", DataType::Utf8, true),
+            Field::new("state", DataType::Utf8, false),
+            Field::new("elapsed_ms", DataType::Float64, false),
+            Field::new("error", DataType::Utf8, true),
+        ])
+    }
+
+    fn new_batch(
+        databases: &mut VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+        query_log: &QueryLogVisibility,
+    ) -> Result<Batch> {
+        // Queries aren't tied to a database, produce everything in a single
+        // batch.
+        databases.clear();
+
+        let queries = query_log::visible_snapshot(query_log);
+
+        Batch::try_new([
+            Array::from_iter(queries.iter().map(|q| q.query_id.to_string())),
+            Array::from_iter(queries.iter().map(|q| q.query.as_deref())),
+            Array::from_iter(queries.iter().map(|q| q.state.as_str())),
+            Array::from_iter(queries.iter().map(|q| q.elapsed.as_secs_f64() * 1000.0)),
+            Array::from_iter(queries.iter().map(|q| q.error.as_deref())),
+        ])
+    }
+}
+
+pub type ListMemory = SystemFunction<ListMemoryImpl>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListMemoryImpl;
+
+impl SystemFunctionImpl for ListMemoryImpl {
+    const NAME: &'static str = "list_memory";
+
+    fn schema() -> Schema {
+        Schema::new([
+            Field::new("name", DataType::Utf8, false),
+            Field::new("value", DataType::Int64, false),
+            Field::new("description", DataType::Utf8, false),
+        ])
+    }
+
+    fn new_batch(
+        databases: &mut VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+        _query_log: &QueryLogVisibility,
+    ) -> Result<Batch> {
+        databases.clear();
+
+        let stats = buffer_pool::stats();
+        let cache_stats = result_cache::stats();
+        let read_cache_stats = read_cache::stats();
+        let running_queries = query_log::snapshot()
+            .iter()
+            .filter(|q| q.state == QueryState::Running)
+            .count();
+
+        let rows: [(&str, usize, &str); 17] = [
+            (
+                "buffer_pool_enabled",
+                buffer_pool::is_enabled() as usize,
+                "If primitive buffers are pooled for reuse (1 if enabled)",
+            ),
+            (
+                "buffer_pool_max_bytes",
+                buffer_pool::max_bytes(),
+                "Max bytes the buffer pool holds on to",
+            ),
+            (
+                "buffer_pool_pooled_buffers",
+                stats.pooled_buffers,
+                "Number of buffers currently held by the buffer pool",
+            ),
+            (
+                "buffer_pool_pooled_bytes",
+                stats.pooled_bytes,
+                "Bytes currently held by the buffer pool",
+            ),
+            (
+                "buffer_pool_allocations",
+                stats.allocations,
+                "Buffers allocated because nothing suitable was pooled",
+            ),
+            (
+                "buffer_pool_reuses",
+                stats.reuses,
+                "Allocations served from the buffer pool",
+            ),
+            (
+                "buffer_pool_returns",
+                stats.returns,
+                "Buffers returned to the buffer pool",
+            ),
+            (
+                "buffer_pool_discards",
+                stats.discards,
+                "Buffers freed instead of being returned to the buffer pool",
+            ),
+            (
+                "remote_read_cache_entries",
+                read_cache_stats.entries,
+                "Number of byte ranges held by the remote read cache",
+            ),
+            (
+                "remote_read_cache_bytes",
+                read_cache_stats.bytes,
+                "Bytes currently held by the remote read cache",
+            ),
+            (
+                "remote_read_cache_hits",
+                read_cache_stats.hits,
+                "Remote reads served from the remote read cache",
+            ),
+            (
+                "remote_read_cache_misses",
+                read_cache_stats.misses,
+                "Remote reads that needed to fetch from the source",
+            ),
+            (
+                "result_cache_entries",
+                cache_stats.entries,
+                "Number of query results held by the result cache",
+            ),
+            (
+                "result_cache_bytes",
+                cache_stats.bytes,
+                "Bytes currently held by the result cache",
+            ),
+            (
+                "result_cache_hits",
+                cache_stats.hits,
+                "Queries served from the result cache",
+            ),
+            (
+                "result_cache_misses",
+                cache_stats.misses,
+                "Cacheable queries not found in the result cache",
+            ),
+            (
+                "running_queries",
+                running_queries,
+                "Number of queries currently executing",
+            ),
+        ];
+
+        Batch::try_new([
+            Array::from_iter(rows.iter().map(|(name, _, _)| *name)),
+            Array::from_iter(rows.iter().map(|(_, value, _)| *value as i64)),
+            Array::from_iter(rows.iter().map(|(_, _, desc)| *desc)),
+        ])
+    }
+}
+
 #[derive(Debug, Clone, Default, Copy, PartialEq, Eq)]
 pub struct SystemFunction<F: SystemFunctionImpl> {
     _ty: PhantomData<F>,
@@ -402,37 +732,108 @@ where
         positional_inputs: Vec<OwnedScalarValue>,
         named_inputs: HashMap<String, OwnedScalarValue>,
     ) -> BoxFuture<'a, Result<PlannedTableFunction>> {
-        let databases = context
-            .iter_databases()
-            .map(|(name, database)| {
-                (
-                    name.clone(),
-                    database.catalog.clone(),
-                    database.attach_info.clone(),
-                )
+        Box::pin(async move {
+            let external = if F::LOAD_EXTERNAL_TABLES {
+                context
+                    .iter_databases()
+                    .filter_map(|(name, database)| {
+                        let storage = database.catalog_storage.clone()?;
+                        Some((name.clone(), (database.catalog.clone(), storage)))
+                    })
+                    .collect()
+            } else {
+                HashMap::new()
+            };
+
+            let databases = context
+                .iter_databases()
+                .map(|(name, database)| {
+                    (
+                        name.clone(),
+                        database.catalog.clone(),
+                        database.attach_info.clone(),
+                    )
+                })
+                .collect();
+
+            Ok(PlannedTableFunction {
+                function: Box::new(SystemFunction::<F>::new()),
+                positional_inputs: positional_inputs.into_iter().map(expr::lit).collect(),
+                named_inputs,
+                function_impl: TableFunctionImpl::Scan(Arc::new(SystemDataTable::<F> {
+                    databases: Arc::new(Mutex::new(Some(databases))),
+                    external,
+                    query_log: *context.query_log_visibility(),
+                    _f: PhantomData,
+                })),
+                cardinality: StatisticsValue::Unknown,
+                schema: F::schema(),
             })
-            .collect();
+        })
+    }
+}
 
-        let planned = PlannedTableFunction {
-            function: Box::new(SystemFunction::<F>::new()),
-            positional_inputs: positional_inputs.into_iter().map(expr::lit).collect(),
-            named_inputs,
-            function_impl: TableFunctionImpl::Scan(Arc::new(SystemDataTable::<F> {
-                databases: Arc::new(Mutex::new(Some(databases))),
-                _f: PhantomData,
-            })),
-            cardinality: StatisticsValue::Unknown,
-            schema: F::schema(),
+/// In-memory catalog for a database along with the storage for its external
+/// catalog.
+type ExternalCatalog = (Arc<MemoryCatalog>, Arc<dyn CatalogStorage>);
+
+/// Load all tables from a database's external catalog into its in-memory
+/// catalog.
+///
+/// Tables that fail to load (e.g. because of unsupported column types) are
+/// skipped.
+async fn load_external_tables(catalog: &MemoryCatalog, storage: &dyn CatalogStorage) -> Result<()> {
+    let tx = &CatalogTx::new();
+
+    for (schema_name, table_name) in storage.list_tables().await? {
+        let schema = match catalog.get_schema(tx, &schema_name)? {
+            Some(schema) => schema,
+            None => catalog.create_schema(
+                tx,
+                &CreateSchemaInfo {
+                    name: schema_name.clone(),
+                    on_conflict: OnConflict::Ignore,
+                },
+            )?,
         };
 
-        Box::pin(async move { Ok(planned) })
+        if schema.get_table_or_view(tx, &table_name)?.is_some() {
+            continue;
+        }
+
+        let ent = match storage.load_table(&schema_name, &table_name).await {
+            Ok(Some(ent)) => ent,
+            Ok(None) => continue,
+            Err(e) => {
+                debug!(%e, %schema_name, %table_name, "skipping external table");
+                continue;
+            }
+        };
+
+        schema.create_table(
+            tx,
+            &CreateTableInfo {
+                name: table_name,
+                columns: ent.columns,
+                checks: Vec::new(),
+                primary_key: Vec::new(),
+                on_conflict: OnConflict::Ignore,
+            },
+        )?;
     }
+
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
 struct SystemDataTable<F: SystemFunctionImpl> {
     #[allow(clippy::type_complexity)] // Temp
     databases: Arc<Mutex<Option<VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>>>>,
+    /// Databases with external catalogs, keyed by database name.
+    ///
+    /// Empty if the function doesn't load external tables.
+    external: HashMap<String, ExternalCatalog>,
+    query_log: QueryLogVisibility,
     _f: PhantomData<F>,
 }
 
@@ -441,7 +842,30 @@ impl<F: SystemFunctionImpl> DataTable for SystemDataTable<F> {
         &self,
         projections: Projections,
         num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        self.scan_filtered(projections, &[], num_partitions, batch_size)
+    }
+
+    fn scan_filtered(
+        &self,
+        projections: Projections,
+        filters: &[ScanFilter],
+        num_partitions: usize,
+        _batch_size: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        // Only load external catalogs we're filtered to.
+        let load = filters
+            .iter()
+            .filter_map(|filter| match &filter.filter {
+                ScanFilterType::ConstComparison {
+                    op: ComparisonOperator::Eq,
+                    constant: ScalarValue::Utf8(name),
+                } if filter.column == 0 => self.external.get(name.as_ref()).cloned(),
+                _ => None,
+            })
+            .collect();
+
         let databases = self
             .databases
             .lock()
@@ -451,6 +875,8 @@ impl<F: SystemFunctionImpl> DataTable for SystemDataTable<F> {
         let mut scans: Vec<Box<dyn DataTableScan>> = vec![Box::new(ProjectedScan::new(
             SystemDataTableScan::<F> {
                 databases,
+                load,
+                query_log: self.query_log,
                 _f: PhantomData,
             },
             projections,
@@ -460,22 +886,35 @@ impl<F: SystemFunctionImpl> DataTable for SystemDataTable<F> {
 
         Ok(scans)
     }
+
+    fn data_version(&self) -> DataVersion {
+        // Catalogs and system state can change without us knowing.
+        DataVersion::Volatile
+    }
 }
 
 #[derive(Debug)]
 struct SystemDataTableScan<F: SystemFunctionImpl> {
     databases: VecDeque<(String, Arc<MemoryCatalog>, Option<AttachInfo>)>,
+    /// External catalogs to load tables from before producing the first
+    /// batch.
+    load: Vec<ExternalCatalog>,
+    query_log: QueryLogVisibility,
     _f: PhantomData<F>,
 }
 
 impl<F: SystemFunctionImpl> DataTableScan for SystemDataTableScan<F> {
     fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
         Box::pin(async {
+            for (catalog, storage) in std::mem::take(&mut self.load) {
+                load_external_tables(&catalog, storage.as_ref()).await?;
+            }
+
             if self.databases.is_empty() {
                 return Ok(None);
             }
 
-            let batch = F::new_batch(&mut self.databases)?;
+            let batch = F::new_batch(&mut self.databases, &self.query_log)?;
 
             Ok(Some(batch))
         })
@@ -107,6 +107,7 @@ impl TableInOutFunction for UnnestInOutImpl {
     fn create_states(
         &self,
         num_partitions: usize,
+        _batch_size: usize,
     ) -> Result<Vec<Box<dyn TableInOutPartitionState>>> {
         let states: Vec<_> = (0..num_partitions)
             .map(|_| {
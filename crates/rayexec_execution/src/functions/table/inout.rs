@@ -8,9 +8,14 @@ use crate::arrays::batch::Batch;
 use crate::execution::operators::{PollFinalize, PollPush};
 
 pub trait TableInOutFunction: Debug + Sync + Send + DynClone {
+    /// Create partition states for the function.
+    ///
+    /// `batch_size` is the target number of rows for each batch produced by
+    /// the function.
     fn create_states(
         &self,
         num_partitions: usize,
+        batch_size: usize,
     ) -> Result<Vec<Box<dyn TableInOutPartitionState>>>;
 }
 
@@ -33,6 +33,15 @@ use crate::storage::table_storage::DataTable;
 pub trait TableFunction: FunctionInfo + Debug + Sync + Send + DynClone {
     /// Return a planner that will produce a planned table function.
     fn planner(&self) -> TableFunctionPlanner;
+
+    /// If this function sends arbitrary statements to an external system
+    /// (e.g. `pg_execute`).
+    ///
+    /// We can't know what those statements do, so passthrough functions are
+    /// only allowed in sessions that can run DML and ATTACH.
+    fn is_passthrough(&self) -> bool {
+        false
+    }
 }
 
 impl Clone for Box<dyn TableFunction> {
@@ -128,8 +137,9 @@ pub enum TableFunctionImpl {
 }
 
 /// Try to get a file location and access config from the table args.
-// TODO: Secrets provider that we pass in allowing us to get creds from some
-// secrets store.
+///
+/// Secrets referenced with `secret_name` have already been merged into the
+/// named arguments during resolving.
 pub fn try_location_and_access_config_from_args(
     func: &impl TableFunction,
     positional: &[OwnedScalarValue],
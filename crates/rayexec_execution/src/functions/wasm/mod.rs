@@ -0,0 +1,860 @@
+//! Scalar functions backed by user provided WASM modules.
+//!
+//! Modules are run using the wasmi interpreter. They have no imports, so the
+//! only thing a module can touch is its own linear memory. Execution is
+//! bounded by fuel and memory limits.
+//!
+//! # ABI
+//!
+//! A module for a function named `f` must export:
+//!
+//! - `memory`: The linear memory used to exchange buffers.
+//! - `alloc(size: i32) -> i32`: Allocate `size` bytes, returning a pointer.
+//! - `f(num_rows: i32, arg0_values: i32, arg0_validity: i32, ..., out_values:
+//!   i32, out_validity: i32)`: Compute outputs for a batch of rows. A batch
+//!   may be split across multiple calls, so functions shouldn't depend on
+//!   seeing every row of a batch at once.
+//!
+//! And may optionally export:
+//!
+//! - `dealloc(ptr: i32, size: i32)`: Free a buffer previously returned by
+//!   `alloc`. Called for every buffer once a batch is complete.
+//!
+//! Buffers follow the Arrow C data layout for fixed-width types. Values are
+//! contiguous little-endian, and validity is an LSB ordered bitmap with a 1
+//! indicating the value is valid. The output validity bitmap is initialized to
+//! the intersection of the input validities before the function is called, so
+//! functions only need to touch it if they want to produce additional nulls.
+//!
+//! Only fixed-width integer and float types are supported for arguments and
+//! return values.
+
+#[cfg(test)]
+mod testutil;
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rayexec_error::{ErrorKind, RayexecError, Result};
+use wasmi::core::{TrapCode, ValType};
+use wasmi::{
+    Config,
+    Engine,
+    Func,
+    FuncType,
+    Instance,
+    Linker,
+    Memory,
+    Module,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    TypedFunc,
+    Val,
+};
+
+use super::scalar::{PlannedScalarFunction, ScalarFunction, ScalarFunctionImpl};
+use super::{
+    intern_positional_args,
+    plan_check_num_args,
+    FunctionInfo,
+    FunctionVolatility,
+    Signature,
+};
+use crate::arrays::array::Array;
+use crate::arrays::bitmap::Bitmap;
+use crate::arrays::datatype::DataType;
+use crate::arrays::executor::physical_type::{
+    PhysicalF32,
+    PhysicalF64,
+    PhysicalI16,
+    PhysicalI32,
+    PhysicalI64,
+    PhysicalI8,
+    PhysicalU16,
+    PhysicalU32,
+    PhysicalU64,
+    PhysicalU8,
+};
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::arrays::storage::PrimitiveStorage;
+use crate::expr::Expression;
+use crate::logical::binder::table_list::TableList;
+use crate::runtime::cancel::CancelFlag;
+
+/// Fuel available for instantiating a module and running its start function.
+const INSTANTIATE_FUEL: u64 = 10_000_000;
+
+/// Base fuel available for each call into the module.
+const CALL_FUEL: u64 = 1_000_000;
+
+/// Additional fuel per row passed to a call.
+const ROW_FUEL: u64 = 100_000;
+
+/// Max number of rows passed to a single call.
+///
+/// Cancellation is checked between calls, so this bounds how long a canceled
+/// query may keep running the function.
+const ROWS_PER_CALL: usize = 256;
+
+/// Max size of a module's linear memory (64 MiB).
+const MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Decode a module provided as a hex string.
+///
+/// Whitespace is ignored, and the string may optionally be prefixed with `\x`
+/// or `0x`.
+pub fn decode_hex_module(body: &str) -> Result<Vec<u8>> {
+    let hex: Vec<u8> = body.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    let hex = hex
+        .strip_prefix(b"\\x")
+        .or_else(|| hex.strip_prefix(b"0x"))
+        .unwrap_or(&hex);
+
+    if hex.len() % 2 != 0 {
+        return Err(RayexecError::new(
+            "WASM module hex string has an odd number of digits",
+        ));
+    }
+
+    hex.chunks_exact(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| RayexecError::new("Invalid hex digit in WASM module"))
+        })
+        .collect()
+}
+
+/// Get the size in bytes of values of a data type passed to or returned from
+/// a WASM function.
+fn value_size(datatype: &DataType) -> Result<usize> {
+    Ok(match datatype {
+        DataType::Int8 | DataType::UInt8 => 1,
+        DataType::Int16 | DataType::UInt16 => 2,
+        DataType::Int32 | DataType::UInt32 | DataType::Float32 => 4,
+        DataType::Int64 | DataType::UInt64 | DataType::Float64 => 8,
+        other => {
+            return Err(RayexecError::new(format!(
+                "Unsupported type for WASM function: {other}"
+            )))
+        }
+    })
+}
+
+/// A scalar function backed by a WASM module.
+///
+/// The module is compiled once when the function is created, and instances of
+/// it are shared by every query in the session.
+#[derive(Debug, Clone)]
+pub struct WasmScalarFunction {
+    name: String,
+    signatures: Vec<Signature>,
+    arg_types: Vec<DataType>,
+    return_type: DataType,
+    instances: Arc<InstancePool>,
+}
+
+impl WasmScalarFunction {
+    pub fn try_new(
+        name: String,
+        arg_types: Vec<DataType>,
+        return_type: DataType,
+        module_bytes: &[u8],
+    ) -> Result<Self> {
+        for datatype in arg_types.iter().chain([&return_type]) {
+            value_size(datatype)?;
+        }
+
+        let instances = InstancePool::try_new(module_bytes, &name, arg_types.len())?;
+
+        let positional_args = intern_positional_args(
+            &arg_types
+                .iter()
+                .map(|t| t.datatype_id())
+                .collect::<Vec<_>>(),
+        );
+
+        Ok(WasmScalarFunction {
+            name,
+            signatures: vec![Signature {
+                positional_args,
+                variadic_arg: None,
+                return_type: return_type.datatype_id(),
+                doc: None,
+            }],
+            arg_types,
+            return_type,
+            instances: Arc::new(instances),
+        })
+    }
+}
+
+impl FunctionInfo for WasmScalarFunction {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &self.signatures
+    }
+
+    fn volatility(&self) -> FunctionVolatility {
+        // Instances have mutable state that persists across calls.
+        FunctionVolatility::Volatile
+    }
+}
+
+impl ScalarFunction for WasmScalarFunction {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, self.arg_types.len())?;
+
+        for (input, expected) in inputs.iter().zip(&self.arg_types) {
+            let datatype = input.datatype(table_list)?;
+            if &datatype != expected {
+                return Err(RayexecError::new(format!(
+                    "Expected {expected} for argument to '{}', got {datatype}",
+                    self.name
+                )));
+            }
+        }
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(self.clone()),
+            return_type: self.return_type.clone(),
+            inputs,
+            function_impl: Box::new(WasmScalarFunctionImpl {
+                return_type: self.return_type.clone(),
+                instances: self.instances.clone(),
+            }),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WasmScalarFunctionImpl {
+    return_type: DataType,
+    instances: Arc<InstancePool>,
+}
+
+impl ScalarFunctionImpl for WasmScalarFunctionImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let len = match inputs.first() {
+            Some(input) => input.logical_len(),
+            None => 1,
+        };
+
+        let mut buffers = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let (values, validity) = encode_array(input)?;
+            buffers.push((values, validity, value_size(input.datatype())?));
+        }
+
+        // Output validity starts as the intersection of the input validities.
+        let mut out_validity = vec![u8::MAX; len.div_ceil(8)];
+        for (_, validity, _) in &buffers {
+            for (out, b) in out_validity.iter_mut().zip(validity) {
+                *out &= b;
+            }
+        }
+        let out_size = value_size(&self.return_type)?;
+        let out_values = vec![0; len * out_size];
+
+        let mut instance = self.instances.checkout()?;
+        let (values, validity) =
+            instance.execute_batch(len, &buffers, out_values, out_validity, out_size)?;
+        self.instances.checkin(instance);
+
+        decode_array(&self.return_type, len, values, validity)
+    }
+}
+
+/// Encode an array into value and validity buffers.
+fn encode_array(array: &Array) -> Result<(Vec<u8>, Vec<u8>)> {
+    let len = array.logical_len();
+    let mut validity = vec![0; len.div_ceil(8)];
+
+    macro_rules! encode {
+        ($phys:ty, $size:expr) => {{
+            let mut values = vec![0; len * $size];
+            UnaryExecutor::for_each::<$phys, _>(array, |idx, v| {
+                if let Some(v) = v {
+                    values[idx * $size..(idx + 1) * $size].copy_from_slice(&v.to_le_bytes());
+                    validity[idx / 8] |= 1 << (idx % 8);
+                }
+            })?;
+            values
+        }};
+    }
+
+    let values = match array.datatype() {
+        DataType::Int8 => encode!(PhysicalI8, 1),
+        DataType::Int16 => encode!(PhysicalI16, 2),
+        DataType::Int32 => encode!(PhysicalI32, 4),
+        DataType::Int64 => encode!(PhysicalI64, 8),
+        DataType::UInt8 => encode!(PhysicalU8, 1),
+        DataType::UInt16 => encode!(PhysicalU16, 2),
+        DataType::UInt32 => encode!(PhysicalU32, 4),
+        DataType::UInt64 => encode!(PhysicalU64, 8),
+        DataType::Float32 => encode!(PhysicalF32, 4),
+        DataType::Float64 => encode!(PhysicalF64, 8),
+        other => {
+            return Err(RayexecError::new(format!(
+                "Unsupported type for WASM function: {other}"
+            )))
+        }
+    };
+
+    Ok((values, validity))
+}
+
+/// Decode value and validity buffers into an array.
+fn decode_array(
+    datatype: &DataType,
+    len: usize,
+    values: Vec<u8>,
+    validity: Vec<u8>,
+) -> Result<Array> {
+    let validity = Bitmap::try_new(validity, len)?;
+
+    macro_rules! decode {
+        ($ty:ty) => {{
+            let values: Vec<$ty> = values
+                .chunks_exact(std::mem::size_of::<$ty>())
+                .map(|b| <$ty>::from_le_bytes(b.try_into().unwrap()))
+                .collect();
+            Array::new_with_validity_and_array_data(
+                datatype.clone(),
+                validity,
+                PrimitiveStorage::from(values),
+            )
+        }};
+    }
+
+    Ok(match datatype {
+        DataType::Int8 => decode!(i8),
+        DataType::Int16 => decode!(i16),
+        DataType::Int32 => decode!(i32),
+        DataType::Int64 => decode!(i64),
+        DataType::UInt8 => decode!(u8),
+        DataType::UInt16 => decode!(u16),
+        DataType::UInt32 => decode!(u32),
+        DataType::UInt64 => decode!(u64),
+        DataType::Float32 => decode!(f32),
+        DataType::Float64 => decode!(f64),
+        other => {
+            return Err(RayexecError::new(format!(
+                "Unsupported type for WASM function: {other}"
+            )))
+        }
+    })
+}
+
+/// A compiled module along with instances of it that aren't currently
+/// executing.
+///
+/// Each partition executing the function checks out its own instance for the
+/// duration of a batch, so partitions never wait on each other. Instances are
+/// returned once the batch completes, so there's at most one instance per
+/// partition executing the function at a time.
+#[derive(Debug)]
+struct InstancePool {
+    module: Module,
+    name: String,
+    num_args: usize,
+    idle: Mutex<Vec<WasmInstance>>,
+}
+
+impl InstancePool {
+    fn try_new(module_bytes: &[u8], name: &str, num_args: usize) -> Result<Self> {
+        if !module_bytes.starts_with(b"\0asm") {
+            return Err(RayexecError::new("Not a WASM module"));
+        }
+
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, module_bytes)
+            .map_err(|e| RayexecError::with_source("Invalid WASM module", Box::new(e)))?;
+
+        let pool = InstancePool {
+            module,
+            name: name.to_string(),
+            num_args,
+            idle: Mutex::new(Vec::new()),
+        };
+
+        // Instantiate up front so that a module missing the needed exports
+        // errors on create.
+        let instance = pool.checkout()?;
+        pool.checkin(instance);
+
+        Ok(pool)
+    }
+
+    /// Get an idle instance, or create a new one if all instances are in use.
+    fn checkout(&self) -> Result<WasmInstance> {
+        match self.idle.lock().pop() {
+            Some(instance) => Ok(instance),
+            None => WasmInstance::try_new(&self.module, &self.name, self.num_args),
+        }
+    }
+
+    /// Return an instance to the pool.
+    ///
+    /// Instances that errored shouldn't be returned since their state may be
+    /// left inconsistent.
+    fn checkin(&self, instance: WasmInstance) {
+        self.idle.lock().push(instance);
+    }
+}
+
+/// An instantiated module along with the functions needed for the ABI.
+#[derive(Debug)]
+struct WasmInstance {
+    store: Store<StoreLimits>,
+    memory: Memory,
+    func: Func,
+    alloc: TypedFunc<i32, i32>,
+    dealloc: Option<TypedFunc<(i32, i32), ()>>,
+}
+
+impl WasmInstance {
+    fn try_new(module: &Module, name: &str, num_args: usize) -> Result<Self> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .instances(1)
+            .build();
+        let mut store = Store::new(module.engine(), limits);
+        store.limiter(|limits| limits);
+        set_fuel(&mut store, INSTANTIATE_FUEL)?;
+
+        // No host functions are provided, modules with imports fail to
+        // instantiate.
+        let linker = Linker::new(module.engine());
+        let instance = linker
+            .instantiate(&mut store, module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(wasm_error)?;
+
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| RayexecError::new("WASM module must export 'memory'"))?;
+
+        let func = find_export(
+            &store,
+            &instance,
+            name,
+            &FuncType::new(vec![ValType::I32; 1 + (num_args + 1) * 2], []),
+        )?
+        .ok_or_else(|| RayexecError::new(format!("WASM module must export '{name}'")))?;
+        let alloc = find_export(
+            &store,
+            &instance,
+            "alloc",
+            &FuncType::new([ValType::I32], [ValType::I32]),
+        )?
+        .ok_or_else(|| RayexecError::new("WASM module must export 'alloc'"))?;
+        let dealloc = find_export(
+            &store,
+            &instance,
+            "dealloc",
+            &FuncType::new([ValType::I32, ValType::I32], []),
+        )?;
+
+        Ok(WasmInstance {
+            alloc: alloc.typed(&store).map_err(wasm_error)?,
+            dealloc: dealloc
+                .map(|dealloc| dealloc.typed(&store))
+                .transpose()
+                .map_err(wasm_error)?,
+            store,
+            memory,
+            func,
+        })
+    }
+
+    /// Execute the function for a batch of rows.
+    ///
+    /// `inputs` contains the value and validity buffers for each argument
+    /// along with the size of each value. Returns the output value and
+    /// validity buffers.
+    fn execute_batch(
+        &mut self,
+        num_rows: usize,
+        inputs: &[(Vec<u8>, Vec<u8>, usize)],
+        mut out_values: Vec<u8>,
+        mut out_validity: Vec<u8>,
+        out_size: usize,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let mut allocations = Vec::with_capacity(inputs.len() * 2 + 2);
+        let result = self.call_batch(
+            num_rows,
+            inputs,
+            &mut out_values,
+            &mut out_validity,
+            out_size,
+            &mut allocations,
+        );
+
+        // Free buffers even if the call failed.
+        let freed = match self.dealloc {
+            Some(dealloc) => allocations.into_iter().try_for_each(|(ptr, size)| {
+                set_fuel(&mut self.store, CALL_FUEL)?;
+                dealloc
+                    .call(&mut self.store, (ptr as i32, size as i32))
+                    .map_err(wasm_error)
+            }),
+            None => Ok(()),
+        };
+        result?;
+        freed?;
+
+        Ok((out_values, out_validity))
+    }
+
+    fn call_batch(
+        &mut self,
+        num_rows: usize,
+        inputs: &[(Vec<u8>, Vec<u8>, usize)],
+        out_values: &mut [u8],
+        out_validity: &mut [u8],
+        out_size: usize,
+        allocations: &mut Vec<(usize, usize)>,
+    ) -> Result<()> {
+        // Pointers to each buffer along with the size of the values it holds.
+        // Validity buffers have a size of zero.
+        let mut buffers = Vec::with_capacity(inputs.len() * 2 + 2);
+        for (values, validity, size) in inputs {
+            buffers.push((self.alloc_copy(values, allocations)?, *size));
+            buffers.push((self.alloc_copy(validity, allocations)?, 0));
+        }
+        let values_ptr = self.alloc_copy(out_values, allocations)?;
+        let validity_ptr = self.alloc_copy(out_validity, allocations)?;
+        buffers.extend([(values_ptr, out_size), (validity_ptr, 0)]);
+
+        // Rows are passed in chunks so that cancellation can be checked in
+        // between calls. Chunks are a multiple of 8 rows so that they start on
+        // a byte boundary in the validity bitmaps.
+        let mut args = Vec::with_capacity(1 + buffers.len());
+        for start in (0..num_rows).step_by(ROWS_PER_CALL) {
+            if CancelFlag::current_is_canceled() {
+                return Err(RayexecError::new("Query canceled").with_kind(ErrorKind::Cancelled));
+            }
+
+            let chunk_rows = ROWS_PER_CALL.min(num_rows - start);
+            args.clear();
+            args.push(Val::I32(chunk_rows as i32));
+            for (ptr, size) in &buffers {
+                let offset = match size {
+                    0 => start / 8,
+                    size => start * size,
+                };
+                args.push(Val::I32((ptr + offset) as i32));
+            }
+
+            let fuel = CALL_FUEL.saturating_add(ROW_FUEL.saturating_mul(chunk_rows as u64));
+            set_fuel(&mut self.store, fuel)?;
+            self.func
+                .call(&mut self.store, &args, &mut [])
+                .map_err(wasm_error)?;
+        }
+
+        // Memory may have grown, but never shrinks, so these are still in
+        // bounds.
+        let memory = self.memory.data(&self.store);
+        out_values.copy_from_slice(&memory[values_ptr..values_ptr + out_values.len()]);
+        out_validity.copy_from_slice(&memory[validity_ptr..validity_ptr + out_validity.len()]);
+
+        Ok(())
+    }
+
+    /// Allocate a buffer in the module's memory and copy `buf` into it,
+    /// checking that the returned pointer is in bounds.
+    fn alloc_copy(&mut self, buf: &[u8], allocations: &mut Vec<(usize, usize)>) -> Result<usize> {
+        let size = buf.len();
+        let size32 = i32::try_from(size)
+            .map_err(|_| RayexecError::new("Batch too large for WASM function"))?;
+
+        set_fuel(&mut self.store, CALL_FUEL)?;
+        let ptr = self
+            .alloc
+            .call(&mut self.store, size32)
+            .map_err(wasm_error)? as u32 as usize;
+
+        let memory = self.memory.data_mut(&mut self.store);
+        match ptr.checked_add(size) {
+            Some(end) if end <= memory.len() => {
+                memory[ptr..end].copy_from_slice(buf);
+                allocations.push((ptr, size));
+                Ok(ptr)
+            }
+            _ => Err(RayexecError::new(format!(
+                "WASM alloc returned out of bounds pointer {ptr} for size {size}"
+            ))),
+        }
+    }
+}
+
+fn set_fuel(store: &mut Store<StoreLimits>, fuel: u64) -> Result<()> {
+    store
+        .set_fuel(fuel)
+        .map_err(|e| RayexecError::new(format!("Failed to set WASM fuel: {e}")))
+}
+
+/// Convert an error from executing a module, giving traps a consistent
+/// prefix.
+fn wasm_error(e: wasmi::Error) -> RayexecError {
+    match e.as_trap_code() {
+        Some(TrapCode::OutOfFuel) => RayexecError::new("WASM trap: out of fuel"),
+        Some(code) => RayexecError::new(format!("WASM trap: {code}")),
+        None => RayexecError::new(format!("WASM error: {e}")),
+    }
+}
+
+/// Find an exported function, checking that it has the expected type.
+fn find_export(
+    store: &Store<StoreLimits>,
+    instance: &Instance,
+    name: &str,
+    expected: &FuncType,
+) -> Result<Option<Func>> {
+    let func = match instance.get_func(store, name) {
+        Some(func) => func,
+        None => return Ok(None),
+    };
+
+    if &func.ty(store) != expected {
+        return Err(RayexecError::new(format!(
+            "WASM export '{name}' has the wrong type, expected {} i32 params and {} results",
+            expected.params().len(),
+            expected.results().len(),
+        )));
+    }
+
+    Ok(Some(func))
+}
+
+#[cfg(test)]
+mod tests {
+    use testutil::{assemble, TestFunc, I32};
+
+    use super::*;
+    use crate::arrays::testutil::assert_arrays_eq;
+
+    /// Module for `add_one(BIGINT) -> BIGINT` using a bump allocator that's
+    /// reset on dealloc.
+    fn add_one_module() -> Vec<u8> {
+        assemble(
+            &[
+                TestFunc {
+                    name: "alloc",
+                    params: &[I32],
+                    results: &[I32],
+                    code: &[
+                        0x00, 0x23, 0x00, 0x23, 0x00, 0x20, 0x00, 0x6A, 0x24, 0x00, 0x0B,
+                    ],
+                },
+                TestFunc {
+                    name: "dealloc",
+                    params: &[I32, I32],
+                    results: &[],
+                    code: &[0x00, 0x41, 0x80, 0x08, 0x24, 0x00, 0x0B],
+                },
+                TestFunc {
+                    name: "add_one",
+                    params: &[I32; 5],
+                    results: &[],
+                    code: &[
+                        0x01, 0x01, I32, // One i32 local for the row index.
+                        0x02, 0x40, 0x03, 0x40, // block, loop
+                        0x20, 0x05, 0x20, 0x00, 0x4F, 0x0D, 0x01, // Break if done.
+                        0x20, 0x03, 0x20, 0x05, 0x41, 0x03, 0x74, 0x6A, // Output address.
+                        0x20, 0x01, 0x20, 0x05, 0x41, 0x03, 0x74, 0x6A, // Input address.
+                        0x29, 0x03, 0x00, 0x42, 0x01, 0x7C, 0x37, 0x03, 0x00, // Add one.
+                        0x20, 0x05, 0x41, 0x01, 0x6A, 0x21, 0x05, // Next row.
+                        0x0C, 0x00, 0x0B, 0x0B, 0x0B,
+                    ],
+                },
+            ],
+            true,
+            Some(1024),
+        )
+    }
+
+    #[test]
+    fn decode_hex() {
+        assert_eq!(vec![0x00, 0x61, 0xFF], decode_hex_module("0061ff").unwrap());
+        assert_eq!(vec![0x00, 0x61], decode_hex_module("\\x00 61\n").unwrap());
+        assert_eq!(vec![0xAB], decode_hex_module("0xab").unwrap());
+        decode_hex_module("006").unwrap_err();
+        decode_hex_module("zz").unwrap_err();
+    }
+
+    #[test]
+    fn execute_batches() {
+        let function = WasmScalarFunction::try_new(
+            "add_one".to_string(),
+            vec![DataType::Int64],
+            DataType::Int64,
+            &add_one_module(),
+        )
+        .unwrap();
+        let function_impl = WasmScalarFunctionImpl {
+            return_type: DataType::Int64,
+            instances: function.instances.clone(),
+        };
+
+        // Multiple batches to make sure the allocator gets reset.
+        for _ in 0..3 {
+            let input = Array::from_iter([Some(1_i64), None, Some(41)]);
+            let out = function_impl.execute(&[&input]).unwrap();
+            let expected = Array::from_iter([Some(2_i64), None, Some(42)]);
+            assert_arrays_eq(&expected, &out);
+        }
+
+        // Batch split across multiple calls.
+        let num_rows = ROWS_PER_CALL * 3 + 5;
+        let input = Array::from_iter((0..num_rows as i64).map(|v| (v % 3 != 0).then_some(v)));
+        let out = function_impl.execute(&[&input]).unwrap();
+        let expected =
+            Array::from_iter((0..num_rows as i64).map(|v| (v % 3 != 0).then_some(v + 1)));
+        assert_arrays_eq(&expected, &out);
+    }
+
+    #[test]
+    fn instance_per_concurrent_caller() {
+        let function = WasmScalarFunction::try_new(
+            "add_one".to_string(),
+            vec![DataType::Int64],
+            DataType::Int64,
+            &add_one_module(),
+        )
+        .unwrap();
+        let pool = &function.instances;
+
+        let a = pool.checkout().unwrap();
+        let b = pool.checkout().unwrap();
+        pool.checkin(a);
+        pool.checkin(b);
+        assert_eq!(2, pool.idle.lock().len());
+
+        // Idle instances get reused.
+        let a = pool.checkout().unwrap();
+        pool.checkin(a);
+        assert_eq!(2, pool.idle.lock().len());
+    }
+
+    /// Module for `spin() -> INT` that never returns.
+    fn spin_module() -> Vec<u8> {
+        assemble(
+            &[
+                TestFunc {
+                    name: "alloc",
+                    params: &[I32],
+                    results: &[I32],
+                    code: &[0x00, 0x41, 0x00, 0x0B],
+                },
+                TestFunc {
+                    name: "spin",
+                    params: &[I32; 3],
+                    results: &[],
+                    code: &[0x00, 0x03, 0x40, 0x0C, 0x00, 0x0B, 0x0B],
+                },
+            ],
+            true,
+            None,
+        )
+    }
+
+    #[test]
+    fn out_of_fuel() {
+        let function = WasmScalarFunction::try_new(
+            "spin".to_string(),
+            Vec::new(),
+            DataType::Int32,
+            &spin_module(),
+        )
+        .unwrap();
+        let function_impl = WasmScalarFunctionImpl {
+            return_type: DataType::Int32,
+            instances: function.instances.clone(),
+        };
+
+        let err = function_impl.execute(&[]).unwrap_err();
+        assert_eq!("WASM trap: out of fuel", err.get_msg());
+    }
+
+    #[test]
+    fn canceled() {
+        let function = WasmScalarFunction::try_new(
+            "add_one".to_string(),
+            vec![DataType::Int64],
+            DataType::Int64,
+            &add_one_module(),
+        )
+        .unwrap();
+        let function_impl = WasmScalarFunctionImpl {
+            return_type: DataType::Int64,
+            instances: function.instances.clone(),
+        };
+
+        let flag = CancelFlag::new();
+        flag.cancel();
+
+        let input = Array::from_iter([Some(1_i64)]);
+        let err = flag.enter(|| function_impl.execute(&[&input])).unwrap_err();
+        assert_eq!(ErrorKind::Cancelled, err.kind());
+    }
+
+    #[test]
+    fn not_a_module() {
+        let err = WasmScalarFunction::try_new(
+            "f".to_string(),
+            Vec::new(),
+            DataType::Int64,
+            &[1, 2, 3, 4],
+        )
+        .unwrap_err();
+        assert_eq!("Not a WASM module", err.get_msg());
+    }
+
+    #[test]
+    fn missing_exports() {
+        let err = WasmScalarFunction::try_new(
+            "add_two".to_string(),
+            vec![DataType::Int64],
+            DataType::Int64,
+            &add_one_module(),
+        )
+        .unwrap_err();
+        assert_eq!("WASM module must export 'add_two'", err.get_msg());
+
+        // Export exists, but doesn't match the number of arguments.
+        let err = WasmScalarFunction::try_new(
+            "add_one".to_string(),
+            vec![DataType::Int64, DataType::Int64],
+            DataType::Int64,
+            &add_one_module(),
+        )
+        .unwrap_err();
+        assert!(err.get_msg().contains("wrong type"), "{err}");
+    }
+
+    #[test]
+    fn unsupported_type() {
+        let err = WasmScalarFunction::try_new(
+            "add_one".to_string(),
+            vec![DataType::Utf8],
+            DataType::Int64,
+            &add_one_module(),
+        )
+        .unwrap_err();
+        assert_eq!("Unsupported type for WASM function: Utf8", err.get_msg());
+    }
+}
@@ -0,0 +1,104 @@
+//! Helpers for assembling WASM modules in tests.
+
+pub const I32: u8 = 0x7F;
+
+/// A function to include in a module.
+#[derive(Debug, Clone, Copy)]
+pub struct TestFunc<'a> {
+    pub name: &'a str,
+    pub params: &'a [u8],
+    pub results: &'a [u8],
+    /// Encoded locals followed by the body, including the final `end`.
+    pub code: &'a [u8],
+}
+
+fn leb_u32(mut v: u32, out: &mut Vec<u8>) {
+    loop {
+        let b = (v & 0x7F) as u8;
+        v >>= 7;
+        if v == 0 {
+            out.push(b);
+            return;
+        }
+        out.push(b | 0x80);
+    }
+}
+
+fn leb_i32(mut v: i32, out: &mut Vec<u8>) {
+    loop {
+        let b = (v & 0x7F) as u8;
+        v >>= 7;
+        if (v == 0 && b & 0x40 == 0) || (v == -1 && b & 0x40 != 0) {
+            out.push(b);
+            return;
+        }
+        out.push(b | 0x80);
+    }
+}
+
+fn section(id: u8, count: usize, contents: &[u8], out: &mut Vec<u8>) {
+    let mut body = Vec::new();
+    leb_u32(count as u32, &mut body);
+    body.extend_from_slice(contents);
+
+    out.push(id);
+    leb_u32(body.len() as u32, out);
+    out.extend(body);
+}
+
+/// Assemble a module with one type per function, each function exported
+/// under its name, and optionally a single page of exported memory and a
+/// mutable i32 global initialized to `global`.
+pub fn assemble(funcs: &[TestFunc], memory: bool, global: Option<i32>) -> Vec<u8> {
+    let mut out = b"\0asm\x01\0\0\0".to_vec();
+
+    let mut types = Vec::new();
+    for func in funcs {
+        types.push(0x60);
+        leb_u32(func.params.len() as u32, &mut types);
+        types.extend_from_slice(func.params);
+        leb_u32(func.results.len() as u32, &mut types);
+        types.extend_from_slice(func.results);
+    }
+    section(1, funcs.len(), &types, &mut out);
+
+    let mut func_types = Vec::new();
+    for idx in 0..funcs.len() {
+        leb_u32(idx as u32, &mut func_types);
+    }
+    section(3, funcs.len(), &func_types, &mut out);
+
+    if memory {
+        section(5, 1, &[0x00, 0x01], &mut out);
+    }
+
+    if let Some(init) = global {
+        let mut globals = vec![I32, 0x01, 0x41];
+        leb_i32(init, &mut globals);
+        globals.push(0x0B);
+        section(6, 1, &globals, &mut out);
+    }
+
+    let mut exports = Vec::new();
+    let mut num_exports = funcs.len();
+    for (idx, func) in funcs.iter().enumerate() {
+        leb_u32(func.name.len() as u32, &mut exports);
+        exports.extend_from_slice(func.name.as_bytes());
+        exports.push(0x00);
+        leb_u32(idx as u32, &mut exports);
+    }
+    if memory {
+        exports.extend_from_slice(b"\x06memory\x02\x00");
+        num_exports += 1;
+    }
+    section(7, num_exports, &exports, &mut out);
+
+    let mut code = Vec::new();
+    for func in funcs {
+        leb_u32(func.code.len() as u32, &mut code);
+        code.extend_from_slice(func.code);
+    }
+    section(10, funcs.len(), &code, &mut out);
+
+    out
+}
@@ -11,7 +11,7 @@ use rayexec_error::{RayexecError, Result};
 use tracing::debug;
 use uuid::Uuid;
 
-use super::client::{IpcBatch, PullStatus};
+use super::client::{EncodedBatch, PullStatus};
 use crate::arrays::batch::Batch;
 use crate::database::DatabaseContext;
 use crate::execution::intermediate::pipeline::StreamId;
@@ -33,15 +33,35 @@ pub struct ServerStreamBuffers {
 }
 
 impl ServerStreamBuffers {
-    pub fn create_incoming_stream(&self, stream_id: StreamId) -> Result<IncomingStream> {
-        debug!(?stream_id, "creating incoming stream");
+    /// Create a stream for receiving batches from other processes.
+    ///
+    /// The stream has `partitions` partitions, with each partition finishing
+    /// once all `senders` have finalized it.
+    pub fn create_incoming_stream(
+        &self,
+        stream_id: StreamId,
+        partitions: usize,
+        senders: usize,
+    ) -> Result<IncomingStream> {
+        debug!(?stream_id, %partitions, %senders, "creating incoming stream");
+
+        if partitions == 0 || senders == 0 {
+            return Err(RayexecError::new(format!(
+                "Incoming stream requires at least one partition and sender, got {partitions} partitions and {senders} senders"
+            )));
+        }
 
         let stream = IncomingStream {
-            state: Arc::new(Mutex::new(IncomingStreamState {
-                finished: false,
-                batches: VecDeque::new(),
-                pull_waker: None,
-            })),
+            partitions: (0..partitions)
+                .map(|_| {
+                    Arc::new(Mutex::new(IncomingPartitionState {
+                        batches: VecDeque::new(),
+                        remaining_senders: senders,
+                        pull_waker: None,
+                        push_wakers: Vec::new(),
+                    }))
+                })
+                .collect(),
         };
 
         self.incoming.insert(stream_id, stream.clone());
@@ -95,13 +115,36 @@ impl ServerStreamBuffers {
         Ok(error_sink.value().clone())
     }
 
-    pub fn push_batch_for_stream(&self, stream_id: &StreamId, batch: Batch) -> Result<()> {
-        let incoming = self.incoming.get(stream_id).ok_or_else(|| {
-            RayexecError::new(format!("Missing incoming stream with id: {stream_id:?}"))
-        })?;
+    /// Push a batch to a partition of an incoming stream.
+    ///
+    /// Resolves once the partition has room for the batch. This is what
+    /// provides backpressure to senders, a sender won't have its push
+    /// acknowledged until the receiving pipeline catches up.
+    pub async fn push_batch_for_stream(
+        &self,
+        stream_id: &StreamId,
+        partition: usize,
+        batch: Batch,
+    ) -> Result<()> {
+        let state = self.get_incoming_partition(stream_id, partition)?;
+        IncomingPushFuture {
+            batch: Some(batch),
+            state,
+        }
+        .await
+    }
+
+    /// Mark a partition of an incoming stream as finished for one sender.
+    pub fn finalize_stream(&self, stream_id: &StreamId, partition: usize) -> Result<()> {
+        let state = self.get_incoming_partition(stream_id, partition)?;
+        let mut state = state.lock();
 
-        let mut state = incoming.state.lock();
-        state.batches.push_back(batch);
+        if state.remaining_senders == 0 {
+            return Err(RayexecError::new(format!(
+                "Partition {partition} for stream {stream_id:?} already finalized by all senders"
+            )));
+        }
+        state.remaining_senders -= 1;
 
         if let Some(waker) = state.pull_waker.take() {
             waker.wake();
@@ -110,19 +153,23 @@ impl ServerStreamBuffers {
         Ok(())
     }
 
-    pub fn finalize_stream(&self, stream_id: &StreamId) -> Result<()> {
+    fn get_incoming_partition(
+        &self,
+        stream_id: &StreamId,
+        partition: usize,
+    ) -> Result<Arc<Mutex<IncomingPartitionState>>> {
         let incoming = self.incoming.get(stream_id).ok_or_else(|| {
             RayexecError::new(format!("Missing incoming stream with id: {stream_id:?}"))
         })?;
 
-        let mut state = incoming.state.lock();
-        state.finished = true;
-
-        if let Some(waker) = state.pull_waker.take() {
-            waker.wake();
-        }
+        let state = incoming.partitions.get(partition).ok_or_else(|| {
+            RayexecError::new(format!(
+                "Invalid partition {partition} for incoming stream {stream_id:?}, stream has {} partitions",
+                incoming.partitions.len()
+            ))
+        })?;
 
-        Ok(())
+        Ok(state.clone())
     }
 
     pub fn pull_batch_for_stream(&self, stream_id: &StreamId) -> Result<PullStatus> {
@@ -144,7 +191,7 @@ impl ServerStreamBuffers {
         }
 
         let status = match state.batch.take() {
-            Some(batch) => PullStatus::Batch(IpcBatch(batch)),
+            Some(batch) => PullStatus::Batch(EncodedBatch(batch)),
             None if state.finished => PullStatus::Finished,
             None => PullStatus::Pending,
         };
@@ -251,22 +298,33 @@ impl Future for OutgoingFinalizeFuture {
     }
 }
 
+/// Max number of batches buffered for a single partition of an incoming
+/// stream.
+///
+/// Pushes to a full partition wait until the receiving pipeline pulls a batch.
+pub const INCOMING_PARTITION_CAPACITY: usize = 4;
+
 #[derive(Debug, Clone)]
 pub struct IncomingStream {
-    state: Arc<Mutex<IncomingStreamState>>,
+    partitions: Vec<Arc<Mutex<IncomingPartitionState>>>,
 }
 
 impl SourceOperation for IncomingStream {
     fn create_partition_sources(&self, num_sources: usize) -> Vec<Box<dyn PartitionSource>> {
-        assert_eq!(1, num_sources);
-
-        vec![Box::new(IncomingPartitionStream {
-            state: self.state.clone(),
-        })]
+        assert_eq!(self.partitions.len(), num_sources);
+
+        self.partitions
+            .iter()
+            .map(|state| {
+                Box::new(IncomingPartitionStream {
+                    state: state.clone(),
+                }) as _
+            })
+            .collect()
     }
 
     fn partition_requirement(&self) -> Option<usize> {
-        Some(1)
+        Some(self.partitions.len())
     }
 }
 
@@ -278,7 +336,7 @@ impl Explainable for IncomingStream {
 
 #[derive(Debug)]
 pub struct IncomingPartitionStream {
-    state: Arc<Mutex<IncomingStreamState>>,
+    state: Arc<Mutex<IncomingPartitionState>>,
 }
 
 impl PartitionSource for IncomingPartitionStream {
@@ -290,14 +348,52 @@ impl PartitionSource for IncomingPartitionStream {
 }
 
 #[derive(Debug)]
-struct IncomingStreamState {
-    finished: bool,
+struct IncomingPartitionState {
     batches: VecDeque<Batch>,
+    /// Number of senders that have yet to finalize this partition.
+    remaining_senders: usize,
     pull_waker: Option<Waker>,
+    /// Wakers for pushes waiting on room in the buffer.
+    push_wakers: Vec<Waker>,
+}
+
+struct IncomingPushFuture {
+    batch: Option<Batch>,
+    state: Arc<Mutex<IncomingPartitionState>>,
+}
+
+impl Future for IncomingPushFuture {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.state.lock();
+
+        if state.remaining_senders == 0 {
+            return Poll::Ready(Err(RayexecError::new(
+                "Cannot push to a partition that's already been finalized",
+            )));
+        }
+
+        if state.batches.len() >= INCOMING_PARTITION_CAPACITY {
+            state.push_wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        if let Some(batch) = this.batch.take() {
+            state.batches.push_back(batch);
+        }
+
+        if let Some(waker) = state.pull_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(()))
+    }
 }
 
 struct IncomingPullFuture {
-    state: Arc<Mutex<IncomingStreamState>>,
+    state: Arc<Mutex<IncomingPartitionState>>,
 }
 
 impl Future for IncomingPullFuture {
@@ -307,8 +403,13 @@ impl Future for IncomingPullFuture {
         let mut state = self.state.lock();
 
         match state.batches.pop_front() {
-            Some(batch) => Poll::Ready(Ok(Some(batch))),
-            None if state.finished => Poll::Ready(Ok(None)),
+            Some(batch) => {
+                for waker in state.push_wakers.drain(..) {
+                    waker.wake();
+                }
+                Poll::Ready(Ok(Some(batch)))
+            }
+            None if state.remaining_senders == 0 => Poll::Ready(Ok(None)),
             None => {
                 state.pull_waker = Some(cx.waker().clone());
                 Poll::Pending
@@ -334,3 +435,100 @@ impl ErrorSink for SharedErrorSink {
         *inner = Some(error);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+
+    use super::*;
+    use crate::arrays::array::Array;
+    use crate::execution::operators::test_util::TestWakerContext;
+
+    fn test_stream_id() -> StreamId {
+        StreamId {
+            query_id: Uuid::from_u128(1),
+            stream_id: Uuid::from_u128(2),
+        }
+    }
+
+    fn test_batch() -> Batch {
+        Batch::try_new([Array::from_iter([1, 2, 3])]).unwrap()
+    }
+
+    #[test]
+    fn push_waits_for_full_partition() {
+        let buffers = ServerStreamBuffers::default();
+        let stream_id = test_stream_id();
+        let stream = buffers.create_incoming_stream(stream_id, 2, 1).unwrap();
+        let mut sources = stream.create_partition_sources(2);
+
+        let push_cx = TestWakerContext::new();
+        for _ in 0..INCOMING_PARTITION_CAPACITY {
+            let push = pin!(buffers.push_batch_for_stream(&stream_id, 0, test_batch()));
+            let poll = push.poll(&mut push_cx.context());
+            assert!(matches!(poll, Poll::Ready(Ok(()))));
+        }
+
+        // Other partition still has room.
+        let push = pin!(buffers.push_batch_for_stream(&stream_id, 1, test_batch()));
+        assert!(matches!(
+            push.poll(&mut push_cx.context()),
+            Poll::Ready(Ok(()))
+        ));
+
+        let mut push = pin!(buffers.push_batch_for_stream(&stream_id, 0, test_batch()));
+        assert!(push.as_mut().poll(&mut push_cx.context()).is_pending());
+
+        // Pulling a batch makes room, waking the pending push.
+        let pull_cx = TestWakerContext::new();
+        let pull = pin!(sources[0].pull());
+        assert!(matches!(
+            pull.poll(&mut pull_cx.context()),
+            Poll::Ready(Ok(Some(_)))
+        ));
+        assert_eq!(1, push_cx.wake_count());
+
+        assert!(matches!(
+            push.poll(&mut push_cx.context()),
+            Poll::Ready(Ok(()))
+        ));
+    }
+
+    #[test]
+    fn partition_finishes_after_all_senders() {
+        let buffers = ServerStreamBuffers::default();
+        let stream_id = test_stream_id();
+        let stream = buffers.create_incoming_stream(stream_id, 1, 2).unwrap();
+        let mut sources = stream.create_partition_sources(1);
+
+        let cx = TestWakerContext::new();
+
+        buffers.finalize_stream(&stream_id, 0).unwrap();
+        {
+            let pull = pin!(sources[0].pull());
+            assert!(pull.poll(&mut cx.context()).is_pending());
+        }
+
+        buffers.finalize_stream(&stream_id, 0).unwrap();
+        assert_eq!(1, cx.wake_count());
+        let pull = pin!(sources[0].pull());
+        assert!(matches!(
+            pull.poll(&mut cx.context()),
+            Poll::Ready(Ok(None))
+        ));
+
+        // All senders already finalized.
+        buffers.finalize_stream(&stream_id, 0).unwrap_err();
+    }
+
+    #[test]
+    fn push_invalid_partition() {
+        let buffers = ServerStreamBuffers::default();
+        let stream_id = test_stream_id();
+        buffers.create_incoming_stream(stream_id, 2, 1).unwrap();
+
+        let cx = TestWakerContext::new();
+        let push = pin!(buffers.push_batch_for_stream(&stream_id, 2, test_batch()));
+        assert!(matches!(push.poll(&mut cx.context()), Poll::Ready(Err(_))));
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use rayexec_error::{OptionExt, RayexecError, Result, ResultExt};
+use rayexec_error::{ErrorKind, OptionExt, RayexecError, Result, ResultExt};
 use rayexec_io::http::reqwest::header::{HeaderValue, CONTENT_TYPE};
 use rayexec_io::http::reqwest::{Method, Request, StatusCode};
 use rayexec_io::http::{read_text, HttpClient, HttpResponse};
@@ -10,8 +10,12 @@ use serde::{Deserialize, Serialize};
 use url::{Host, Url};
 use uuid::Uuid;
 
+use crate::arrays::array::Array;
 use crate::arrays::batch::Batch;
+use crate::arrays::datatype::DataType;
+use crate::arrays::executor::scalar::concat;
 use crate::arrays::field::Schema;
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
 use crate::database::DatabaseContext;
 use crate::execution::intermediate::pipeline::{IntermediatePipelineGroup, StreamId};
 use crate::logical::resolver::resolve_context::ResolveContext;
@@ -20,6 +24,9 @@ use crate::proto::DatabaseProtoConv;
 
 pub const API_VERSION: usize = 0;
 
+/// Header containing the SQLSTATE code for an error response.
+pub const SQLSTATE_HEADER: &str = "x-rayexec-sqlstate";
+
 pub const REMOTE_ENDPOINTS: Endpoints = Endpoints {
     healthz: "/healthz",
     rpc_hybrid_plan: "/rpc/v0/hybrid/plan",
@@ -147,7 +154,7 @@ impl ProtoConv for HybridExecuteResponse {
 pub struct HybridPushRequest {
     pub stream_id: StreamId,
     pub partition: usize,
-    pub batch: IpcBatch,
+    pub batch: EncodedBatch,
 }
 
 impl ProtoConv for HybridPushRequest {
@@ -165,7 +172,7 @@ impl ProtoConv for HybridPushRequest {
         Ok(Self {
             stream_id: StreamId::from_proto(proto.stream_id.required("stream_id")?)?,
             partition: proto.partition as usize,
-            batch: IpcBatch::from_proto(proto.batch.required("batch")?)?,
+            batch: EncodedBatch::from_proto(proto.batch.required("batch")?)?,
         })
     }
 }
@@ -271,7 +278,7 @@ impl ProtoConv for HybridPullResponse {
 
 #[derive(Debug)]
 pub enum PullStatus {
-    Batch(IpcBatch),
+    Batch(EncodedBatch),
     Pending,
     Finished,
 }
@@ -299,7 +306,7 @@ impl ProtoConv for PullStatus {
 
         Ok(match proto.value.required("value")? {
             Value::Batch(batch) => {
-                Self::Batch(IpcBatch::from_proto(batch.batch.required("batch")?)?)
+                Self::Batch(EncodedBatch::from_proto(batch.batch.required("batch")?)?)
             }
             Value::Pending(_) => Self::Pending,
             Value::Finished(_) => Self::Finished,
@@ -307,48 +314,91 @@ impl ProtoConv for PullStatus {
     }
 }
 
-/// Wrapper around a batch that implements IPC encoding/decoding when converting
-/// to protobuf.
+/// Wrapper around a batch that implements encoding/decoding when converting to
+/// protobuf.
+///
+/// Columns are encoded as scalar values alongside their datatypes.
 #[derive(Debug)]
-pub struct IpcBatch(pub Batch);
+pub struct EncodedBatch(pub Batch);
 
-// TODO: Don't allocate vectors in this.
-impl ProtoConv for IpcBatch {
-    type ProtoType = rayexec_proto::generated::array::IpcStreamBatch;
+impl ProtoConv for EncodedBatch {
+    type ProtoType = rayexec_proto::generated::array::ScalarBatch;
 
     fn to_proto(&self) -> Result<Self::ProtoType> {
-        unimplemented!()
-        // let buf = Vec::new();
-
-        // // Field names don't matter. A full schema is included just for
-        // // compatability with arrow ipc, but we only care about the data types.
-        // let schema = Schema::new(
-        //     self.0
-        //         .columns()
-        //         .iter()
-        //         .map(|c| Field::new("", c.datatype().clone(), true)),
-        // );
-
-        // let mut writer = StreamWriter::try_new(buf, &schema, IpcConfig {})?;
-        // writer.write_batch(&self.0)?;
+        use rayexec_proto::generated::array::ScalarColumn;
+
+        let types = self
+            .0
+            .columns()
+            .iter()
+            .map(|array| array.datatype().to_proto())
+            .collect::<Result<Vec<_>>>()?;
+
+        let columns = self
+            .0
+            .columns()
+            .iter()
+            .map(|array| {
+                let values = (0..array.logical_len())
+                    .map(|idx| array.logical_value(idx)?.into_owned().to_proto())
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(ScalarColumn { values })
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-        // let buf = writer.into_writer();
-
-        // Ok(Self::ProtoType { ipc: buf })
+        Ok(Self::ProtoType {
+            types,
+            columns,
+            num_rows: self.0.num_rows() as u64,
+        })
     }
 
-    fn from_proto(_proto: Self::ProtoType) -> Result<Self> {
-        unimplemented!()
-        // let mut reader = StreamReader::try_new(Cursor::new(proto.ipc), IpcConfig {})?;
-        // let batch = reader
-        //     .try_next_batch()?
-        //     .ok_or_else(|| RayexecError::new("Missing IPC batch"))?;
+    fn from_proto(proto: Self::ProtoType) -> Result<Self> {
+        if proto.types.len() != proto.columns.len() {
+            return Err(RayexecError::new(format!(
+                "Encoded batch has {} columns, but {} types",
+                proto.columns.len(),
+                proto.types.len()
+            )));
+        }
 
-        // if reader.try_next_batch()?.is_some() {
-        //     return Err(RayexecError::new("Received too many IPC batches"));
-        // }
+        let num_rows = proto.num_rows as usize;
+        if proto.columns.is_empty() {
+            return Ok(Self(Batch::empty_with_num_rows(num_rows)));
+        }
 
-        // Ok(Self(batch))
+        let arrays = proto
+            .types
+            .into_iter()
+            .zip(proto.columns)
+            .map(|(datatype, column)| {
+                let datatype = DataType::from_proto(datatype)?;
+                if column.values.len() != num_rows {
+                    return Err(RayexecError::new(format!(
+                        "Encoded column has {} values, expected {num_rows}",
+                        column.values.len()
+                    )));
+                }
+
+                let values = column
+                    .values
+                    .into_iter()
+                    .map(|value| match OwnedScalarValue::from_proto(value)? {
+                        ScalarValue::Null => Array::new_typed_null_array(datatype.clone(), 1),
+                        value => value.as_array(1),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                if values.is_empty() {
+                    Array::new_typed_null_array(datatype, 0)
+                } else {
+                    let refs: Vec<_> = values.iter().collect();
+                    concat(&refs)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self(Batch::try_new(arrays)?))
     }
 }
 
@@ -450,8 +500,7 @@ impl<C: HttpClient> HybridClient<C> {
             .context("failed to send request")?;
 
         if resp.status() != StatusCode::OK {
-            let text = read_text(resp).await?;
-            return Err(RayexecError::new(text));
+            return Err(read_error(resp).await?);
         }
 
         let resp: ResponseEnvelope = serde_json::from_slice(resp.bytes().await?.as_ref())
@@ -487,7 +536,7 @@ impl<C: HttpClient> HybridClient<C> {
         let msg = HybridPushRequest {
             stream_id,
             partition,
-            batch: IpcBatch(batch),
+            batch: EncodedBatch(batch),
         };
 
         let _resp: HybridPushResponse = self.do_request(msg, url).await?;
@@ -546,9 +595,7 @@ impl<C: HttpClient> HybridClient<C> {
             .context("failed to send request")?;
 
         if resp.status() != StatusCode::OK {
-            // TODO: Structured return error.
-            let text = read_text(resp).await?;
-            return Err(RayexecError::new(text));
+            return Err(read_error(resp).await?);
         }
 
         let resp: ResponseEnvelope = serde_json::from_slice(resp.bytes().await?.as_ref())
@@ -570,3 +617,48 @@ impl<C: HttpClient> HybridClient<C> {
         Ok(())
     }
 }
+
+/// Read an error response from the remote server.
+///
+/// The kind of the error is recovered from the SQLSTATE header if it's set.
+async fn read_error(resp: impl HttpResponse) -> Result<RayexecError> {
+    let kind = resp
+        .headers()
+        .get(SQLSTATE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(ErrorKind::from_sqlstate)
+        .unwrap_or_default();
+    let text = read_text(resp).await?;
+
+    Ok(RayexecError::new(text).with_kind(kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoded_batch_roundtrip() {
+        let batch = Batch::try_new([
+            Array::from_iter([Some(1), None, Some(3)]),
+            Array::from_iter(["a", "b", "c"]),
+        ])
+        .unwrap();
+
+        let proto = EncodedBatch(batch.clone()).to_proto().unwrap();
+        let got = EncodedBatch::from_proto(proto).unwrap().0;
+
+        assert_eq!(batch, got);
+    }
+
+    #[test]
+    fn encoded_batch_roundtrip_no_columns() {
+        let batch = Batch::empty_with_num_rows(4);
+
+        let proto = EncodedBatch(batch).to_proto().unwrap();
+        let got = EncodedBatch::from_proto(proto).unwrap().0;
+
+        assert_eq!(0, got.num_columns());
+        assert_eq!(4, got.num_rows());
+    }
+}
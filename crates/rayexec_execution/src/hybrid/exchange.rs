@@ -0,0 +1,291 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use futures::future::{try_join_all, BoxFuture};
+use rayexec_error::{RayexecError, Result};
+use rayexec_io::http::HttpClient;
+
+use super::client::HybridClient;
+use crate::arrays::batch::Batch;
+use crate::arrays::executor::scalar::HashExecutor;
+use crate::arrays::selection::SelectionVector;
+use crate::database::DatabaseContext;
+use crate::execution::intermediate::pipeline::StreamId;
+use crate::execution::operators::sink::{PartitionSink, SinkOperation};
+use crate::execution::operators::util::hash::partition_for_hash;
+use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+
+/// Sink for sending batches to the partitions of a pipeline executing in some
+/// other process.
+///
+/// Rows are assigned to remote partitions by hashing the exchange's hash
+/// columns, using the same hash to partition mapping as hash joins and hash
+/// aggregates. This lets the remote side run a partitioned join or aggregate
+/// without repartitioning. If there are no hash columns, whole batches are
+/// distributed round robin.
+///
+/// Each push waits for the receiving partition to accept the batch, so a slow
+/// receiver will slow down the senders instead of buffering unbounded batches.
+///
+/// Any number of local partitions can send to the exchange. Remote partitions
+/// are only finalized once every local partition has finished.
+#[derive(Debug)]
+pub struct ExchangeSink<C: HttpClient> {
+    stream_id: StreamId,
+    client: Arc<HybridClient<C>>,
+    remote_partitions: usize,
+    hash_columns: Vec<usize>,
+}
+
+impl<C: HttpClient + 'static> ExchangeSink<C> {
+    pub fn new(
+        stream_id: StreamId,
+        client: Arc<HybridClient<C>>,
+        remote_partitions: usize,
+        hash_columns: Vec<usize>,
+    ) -> Self {
+        ExchangeSink {
+            stream_id,
+            client,
+            remote_partitions,
+            hash_columns,
+        }
+    }
+}
+
+impl<C: HttpClient + 'static> SinkOperation for ExchangeSink<C> {
+    fn create_partition_sinks(
+        &self,
+        _context: &DatabaseContext,
+        num_sinks: usize,
+    ) -> Result<Vec<Box<dyn PartitionSink>>> {
+        if self.remote_partitions == 0 {
+            return Err(RayexecError::new(
+                "Exchange requires at least one remote partition",
+            ));
+        }
+
+        let remaining = Arc::new(AtomicUsize::new(num_sinks));
+
+        let sinks = (0..num_sinks)
+            .map(|idx| {
+                Box::new(ExchangePartitionSink {
+                    stream_id: self.stream_id,
+                    client: self.client.clone(),
+                    partitioner: BatchPartitioner::new(
+                        self.hash_columns.clone(),
+                        self.remote_partitions,
+                        idx,
+                    ),
+                    remaining: remaining.clone(),
+                }) as _
+            })
+            .collect();
+
+        Ok(sinks)
+    }
+
+    fn partition_requirement(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<C: HttpClient> Explainable for ExchangeSink<C> {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("ExchangeSink")
+            .with_value("remote_partitions", self.remote_partitions)
+            .with_values("hash_columns", &self.hash_columns)
+    }
+}
+
+#[derive(Debug)]
+pub struct ExchangePartitionSink<C: HttpClient> {
+    stream_id: StreamId,
+    client: Arc<HybridClient<C>>,
+    partitioner: BatchPartitioner,
+    /// Number of local partitions that have yet to finalize.
+    remaining: Arc<AtomicUsize>,
+}
+
+impl<C: HttpClient> PartitionSink for ExchangePartitionSink<C> {
+    fn push(&mut self, batch: Batch) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            let batches = self.partitioner.partition(batch)?;
+
+            let pushes = batches
+                .into_iter()
+                .enumerate()
+                .filter_map(|(partition, batch)| {
+                    let batch = batch?;
+                    Some(self.client.push(self.stream_id, partition, batch))
+                });
+            try_join_all(pushes).await?;
+
+            Ok(())
+        })
+    }
+
+    fn finalize(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async {
+            // Only the last local partition finalizes the remote partitions.
+            // All other local partitions have had their pushes acknowledged at
+            // this point.
+            if self.remaining.fetch_sub(1, Ordering::SeqCst) != 1 {
+                return Ok(());
+            }
+
+            let finalizes = (0..self.partitioner.partitions)
+                .map(|partition| self.client.finalize(self.stream_id, partition));
+            try_join_all(finalizes).await?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Splits batches into per-partition batches.
+#[derive(Debug)]
+pub struct BatchPartitioner {
+    hash_columns: Vec<usize>,
+    partitions: usize,
+    /// Next partition to send to when distributing round robin.
+    next: usize,
+    hash_buf: Vec<u64>,
+}
+
+impl BatchPartitioner {
+    /// Create a new partitioner.
+    ///
+    /// `offset` is the partition to start with when distributing round robin,
+    /// and should differ between senders to spread out the initial batches.
+    pub fn new(hash_columns: Vec<usize>, partitions: usize, offset: usize) -> Self {
+        BatchPartitioner {
+            hash_columns,
+            partitions,
+            next: offset % partitions.max(1),
+            hash_buf: Vec::new(),
+        }
+    }
+
+    /// Split a batch into one (possibly missing) batch per partition.
+    ///
+    /// Partitions that don't receive any rows from the batch are None.
+    pub fn partition(&mut self, batch: Batch) -> Result<Vec<Option<Batch>>> {
+        let mut out: Vec<Option<Batch>> = (0..self.partitions).map(|_| None).collect();
+
+        if batch.num_rows() == 0 {
+            return Ok(out);
+        }
+
+        if self.hash_columns.is_empty() {
+            out[self.next] = Some(batch);
+            self.next = (self.next + 1) % self.partitions;
+            return Ok(out);
+        }
+
+        let columns = self
+            .hash_columns
+            .iter()
+            .map(|&idx| {
+                batch.column(idx).cloned().ok_or_else(|| {
+                    RayexecError::new(format!(
+                        "Missing hash column {idx} for exchange, batch has {} columns",
+                        batch.num_columns()
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.hash_buf.clear();
+        self.hash_buf.resize(batch.num_rows(), 0);
+        let hashes = HashExecutor::hash_many(&columns, &mut self.hash_buf)?;
+
+        let mut selections: Vec<SelectionVector> = (0..self.partitions)
+            .map(|_| SelectionVector::with_capacity(0))
+            .collect();
+        for (row_idx, hash) in hashes.iter().enumerate() {
+            selections[partition_for_hash(*hash, self.partitions)].push_location(row_idx);
+        }
+
+        for (partition, selection) in selections.into_iter().enumerate() {
+            if selection.is_empty() {
+                continue;
+            }
+            out[partition] = Some(batch.select(Arc::new(selection)));
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::array::Array;
+    use crate::arrays::scalar::ScalarValue;
+
+    fn row_values(batch: &Batch) -> Vec<ScalarValue<'static>> {
+        (0..batch.num_rows())
+            .map(|idx| {
+                batch
+                    .column(0)
+                    .unwrap()
+                    .logical_value(idx)
+                    .unwrap()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn partition_round_robin() {
+        let mut partitioner = BatchPartitioner::new(Vec::new(), 3, 1);
+
+        let batch = Batch::try_new([Array::from_iter([1, 2, 3])]).unwrap();
+        let out = partitioner.partition(batch.clone()).unwrap();
+        assert!(out[0].is_none());
+        assert_eq!(3, out[1].as_ref().unwrap().num_rows());
+        assert!(out[2].is_none());
+
+        let out = partitioner.partition(batch).unwrap();
+        assert!(out[2].is_some());
+    }
+
+    #[test]
+    fn partition_hash_consistent() {
+        let mut partitioner = BatchPartitioner::new(vec![0], 4, 0);
+
+        let batch = Batch::try_new([
+            Array::from_iter([1, 2, 3, 4, 5, 6, 7, 8, 1, 2]),
+            Array::from_iter(["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]),
+        ])
+        .unwrap();
+        let out = partitioner.partition(batch).unwrap();
+
+        let total: usize = out.iter().flatten().map(|b| b.num_rows()).sum();
+        assert_eq!(10, total);
+
+        // Same keys always go to the same partition.
+        let partition_of = |value: i32| {
+            out.iter()
+                .position(|b| {
+                    b.as_ref()
+                        .map(|b| row_values(b).contains(&ScalarValue::Int32(value)))
+                        .unwrap_or(false)
+                })
+                .unwrap()
+        };
+        let first = partition_of(1);
+        let out_again = partitioner
+            .partition(Batch::try_new([Array::from_iter([1]), Array::from_iter(["z"])]).unwrap())
+            .unwrap();
+        assert!(out_again[first].is_some());
+    }
+
+    #[test]
+    fn partition_missing_hash_column() {
+        let mut partitioner = BatchPartitioner::new(vec![2], 2, 0);
+        let batch = Batch::try_new([Array::from_iter([1, 2])]).unwrap();
+        partitioner.partition(batch).unwrap_err();
+    }
+}
@@ -3,4 +3,5 @@
 pub mod client;
 
 pub(crate) mod buffer;
+pub(crate) mod exchange;
 pub(crate) mod stream;
@@ -6,74 +6,10 @@ use rayexec_io::http::HttpClient;
 
 use super::client::{HybridClient, PullStatus};
 use crate::arrays::batch::Batch;
-use crate::database::DatabaseContext;
 use crate::execution::intermediate::pipeline::StreamId;
-use crate::execution::operators::sink::{PartitionSink, SinkOperation};
 use crate::execution::operators::source::{PartitionSource, SourceOperation};
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 
-/// Client-side stream for sending batches from the client to the server (push).
-///
-/// All the ipc client/server streams have a partitioning requirement of one.
-/// However it should be pretty easy to extend to support multiple partition
-/// with very little change. The remote side (server) could just set it to its
-/// target partitioning value, and the for clients, we can just send that
-/// information over along with the plan+bind data. Then the remote side would
-/// have everything it needs for wiring up the streams for correct partitioning.
-#[derive(Debug)]
-pub struct ClientToServerStream<C: HttpClient> {
-    stream_id: StreamId,
-    client: Arc<HybridClient<C>>,
-}
-
-impl<C: HttpClient + 'static> ClientToServerStream<C> {
-    pub fn new(stream_id: StreamId, client: Arc<HybridClient<C>>) -> Self {
-        ClientToServerStream { stream_id, client }
-    }
-}
-
-impl<C: HttpClient + 'static> SinkOperation for ClientToServerStream<C> {
-    fn create_partition_sinks(
-        &self,
-        _context: &DatabaseContext,
-        num_sinks: usize,
-    ) -> Result<Vec<Box<dyn PartitionSink>>> {
-        assert_eq!(1, num_sinks);
-
-        Ok(vec![Box::new(ClientToServerPartitionSink {
-            stream_id: self.stream_id,
-            client: self.client.clone(),
-        })])
-    }
-
-    fn partition_requirement(&self) -> Option<usize> {
-        Some(1)
-    }
-}
-
-impl<C: HttpClient> Explainable for ClientToServerStream<C> {
-    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
-        ExplainEntry::new("ClientToServerStream")
-    }
-}
-
-#[derive(Debug)]
-pub struct ClientToServerPartitionSink<C: HttpClient> {
-    stream_id: StreamId,
-    client: Arc<HybridClient<C>>,
-}
-
-impl<C: HttpClient> PartitionSink for ClientToServerPartitionSink<C> {
-    fn push(&mut self, batch: Batch) -> BoxFuture<'_, Result<()>> {
-        // TODO: Figure out backpressure
-        Box::pin(async { self.client.push(self.stream_id, 0, batch).await })
-    }
-
-    fn finalize(&mut self) -> BoxFuture<'_, Result<()>> {
-        Box::pin(async { self.client.finalize(self.stream_id, 0).await })
-    }
-}
-
 /// Client-side stream for receiving batches from the server to the client
 /// (pull).
 #[derive(Debug)]
@@ -0,0 +1,216 @@
+use rayexec_error::{ErrorKind, RayexecError, Result};
+use rayexec_parser::ast;
+
+use super::bind_context::{BindContext, BindScopeRef};
+use super::bind_policy::PolicyBinder;
+use super::constant_binder::ConstantBinder;
+use crate::arrays::compute::cast::scalar::cast_scalar;
+use crate::arrays::field::Field;
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::database::alter::{AlterTableInfo, AlterTableOperation};
+use crate::database::catalog_entry::{IndexMethod, TablePolicy};
+use crate::logical::logical_alter::LogicalAlterTable;
+use crate::logical::operator::{LocationRequirement, Node};
+use crate::logical::resolver::resolve_context::ResolveContext;
+use crate::logical::resolver::resolved_table::ResolvedTableOrCteReference;
+use crate::logical::resolver::ResolvedMeta;
+use crate::logical::statistics::StatisticsValue;
+
+#[derive(Debug)]
+pub struct AlterTableBinder<'a> {
+    pub current: BindScopeRef,
+    pub resolve_context: &'a ResolveContext,
+}
+
+impl<'a> AlterTableBinder<'a> {
+    pub fn new(current: BindScopeRef, resolve_context: &'a ResolveContext) -> Self {
+        AlterTableBinder {
+            current,
+            resolve_context,
+        }
+    }
+
+    pub fn bind_alter_table(
+        &self,
+        _bind_context: &mut BindContext,
+        mut alter: ast::AlterTable<ResolvedMeta>,
+    ) -> Result<Node<LogicalAlterTable>> {
+        let [catalog, schema, name] = alter.name.pop_3()?;
+
+        let operation = match alter.operation {
+            ast::AlterTableOperation::AddColumn {
+                if_not_exists,
+                column,
+                default,
+            } => {
+                let column_name = column.name.into_normalized_string();
+                let default = match default {
+                    Some(default) => {
+                        let value = ConstantBinder::new(self.resolve_context)
+                            .bind_constant_expression(&default)?;
+                        match value {
+                            ScalarValue::Null => OwnedScalarValue::Null,
+                            value => cast_scalar(value, &column.datatype)?,
+                        }
+                    }
+                    None => OwnedScalarValue::Null,
+                };
+
+                let mut nullable = true;
+                for opt in column.opts {
+                    match opt {
+                        ast::ColumnOption::Null => (),
+                        ast::ColumnOption::NotNull => {
+                            // Existing rows get the default.
+                            if default == ScalarValue::Null {
+                                return Err(RayexecError::new(format!(
+                                    "Column '{column_name}' must have a non-NULL default to be added as NOT NULL"
+                                ))
+                                .with_kind(ErrorKind::NotNullViolation));
+                            }
+                            nullable = false;
+                        }
+                        ast::ColumnOption::PrimaryKey | ast::ColumnOption::Check { .. } => {
+                            return Err(RayexecError::new(
+                                "Adding a column with a PRIMARY KEY or CHECK constraint is not yet supported",
+                            )
+                            .with_kind(ErrorKind::NotImplemented))
+                        }
+                    }
+                }
+
+                AlterTableOperation::AddColumn {
+                    field: Field::new(column_name, column.datatype, nullable),
+                    default,
+                    if_not_exists,
+                }
+            }
+            ast::AlterTableOperation::DropColumn { if_exists, name } => {
+                AlterTableOperation::DropColumn {
+                    name: name.into_normalized_string(),
+                    if_exists,
+                }
+            }
+            ast::AlterTableOperation::RenameColumn { from, to } => {
+                AlterTableOperation::RenameColumn {
+                    from: from.into_normalized_string(),
+                    to: to.into_normalized_string(),
+                }
+            }
+        };
+
+        Ok(Node {
+            node: LogicalAlterTable {
+                catalog,
+                schema,
+                if_exists: alter.if_exists,
+                info: AlterTableInfo { name, operation },
+            },
+            location: LocationRequirement::ClientLocal,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+
+    /// Bind a CREATE INDEX, which is applied as an alter of the indexed table.
+    pub fn bind_create_index(
+        &self,
+        _bind_context: &mut BindContext,
+        mut create: ast::CreateIndex<ResolvedMeta>,
+    ) -> Result<Node<LogicalAlterTable>> {
+        let [catalog, schema, table] = create.table.pop_3()?;
+
+        let method = match create.using {
+            Some(using) => IndexMethod::from_name(&using.into_normalized_string())?,
+            None => IndexMethod::default(),
+        };
+
+        let operation = AlterTableOperation::CreateIndex {
+            name: create.name.into_normalized_string(),
+            columns: create
+                .columns
+                .into_iter()
+                .map(|column| column.into_normalized_string())
+                .collect(),
+            method,
+            if_not_exists: create.if_not_exists,
+        };
+
+        Ok(Node {
+            node: LogicalAlterTable {
+                catalog,
+                schema,
+                if_exists: false,
+                info: AlterTableInfo {
+                    name: table,
+                    operation,
+                },
+            },
+            location: LocationRequirement::ClientLocal,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+
+    /// Bind a CREATE POLICY, which is applied as an alter of the table.
+    ///
+    /// The predicate is bound against the table to check that it's valid,
+    /// but only the original SQL is stored.
+    pub fn bind_create_policy(
+        &self,
+        bind_context: &mut BindContext,
+        create: ast::CreatePolicy<ResolvedMeta>,
+    ) -> Result<Node<LogicalAlterTable>> {
+        let table = match self.resolve_context.tables.try_get_bound(create.table)? {
+            (ResolvedTableOrCteReference::Table(table), _) => table,
+            (ResolvedTableOrCteReference::Cte(name), _) => {
+                return Err(RayexecError::new(format!(
+                    "Cannot create a policy on CTE '{name}'"
+                )))
+            }
+        };
+        let ent = table.entry.try_as_table_entry()?;
+
+        PolicyBinder::new(self.resolve_context).bind_policy(
+            bind_context,
+            &table.entry.name,
+            ent,
+            &create.using,
+        )?;
+
+        let roles: Vec<_> = create
+            .roles
+            .into_iter()
+            .map(|role| role.into_normalized_string())
+            .collect();
+        // PUBLIC means everyone, which is the same as not listing any roles.
+        let roles = if roles.iter().any(|role| role == "public") {
+            Vec::new()
+        } else {
+            roles
+        };
+
+        let operation = AlterTableOperation::CreatePolicy {
+            policy: TablePolicy {
+                name: create.name.into_normalized_string(),
+                roles,
+                expression: create.sql,
+            },
+        };
+
+        Ok(Node {
+            node: LogicalAlterTable {
+                catalog: table.catalog.clone(),
+                schema: table.schema.clone(),
+                if_exists: false,
+                info: AlterTableInfo {
+                    name: table.entry.name.clone(),
+                    operation,
+                },
+            },
+            location: LocationRequirement::ClientLocal,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use rayexec_error::{RayexecError, Result};
+use rayexec_parser::ast;
+
+use super::bind_context::BindContext;
+use super::column_binder::DefaultColumnBinder;
+use super::expr_binder::{BaseExpressionBinder, RecursionContext};
+use super::table_list::TableRef;
+use crate::arrays::datatype::DataType;
+use crate::expr::Expression;
+use crate::logical::resolver::resolve_context::ResolveContext;
+use crate::logical::resolver::ResolvedMeta;
+
+/// A CHECK constraint bound to a single table column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundCheck {
+    /// Index of the column in the table.
+    pub column_idx: usize,
+    /// Table containing only the checked column, referenced by the
+    /// expression.
+    pub table_ref: TableRef,
+    pub expr: Expression,
+    /// SQL for the constraint, used in error messages.
+    pub sql: String,
+}
+
+/// Binds CHECK constraint expressions.
+///
+/// A constraint may only reference the column it's defined on, so each
+/// expression is bound against its own single column table.
+#[derive(Debug)]
+pub struct CheckBinder<'a> {
+    pub resolve_context: &'a ResolveContext,
+}
+
+impl<'a> CheckBinder<'a> {
+    pub fn new(resolve_context: &'a ResolveContext) -> Self {
+        CheckBinder { resolve_context }
+    }
+
+    pub fn bind_check(
+        &self,
+        bind_context: &mut BindContext,
+        column_name: &str,
+        datatype: &DataType,
+        expr: &ast::Expr<ResolvedMeta>,
+    ) -> Result<(TableRef, Expression)> {
+        let scope = bind_context.new_orphan_scope();
+        let table_ref = bind_context.push_table(
+            scope,
+            None,
+            vec![datatype.clone()],
+            vec![column_name.to_string()],
+        )?;
+
+        let expr = BaseExpressionBinder::new(scope, self.resolve_context).bind_expression(
+            bind_context,
+            expr,
+            &mut DefaultColumnBinder,
+            RecursionContext {
+                allow_aggregates: false,
+                allow_windows: false,
+                is_root: true,
+            },
+        )?;
+
+        if expr.contains_subquery()
+            || expr.contains_aggregate()
+            || expr.contains_window()
+            || expr.contains_unnest()
+        {
+            return Err(RayexecError::new(format!(
+                "CHECK constraint on column '{column_name}' may not contain subqueries, aggregates, window functions, or UNNEST"
+            )));
+        }
+
+        let return_type = expr.datatype(bind_context.get_table_list())?;
+        if return_type != DataType::Boolean {
+            return Err(RayexecError::new(format!(
+                "CHECK constraint on column '{column_name}' must be a boolean expression, got {return_type}"
+            )));
+        }
+
+        Ok((table_ref, expr))
+    }
+}
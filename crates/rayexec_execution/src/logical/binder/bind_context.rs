@@ -314,6 +314,11 @@ impl BindContext {
         self.materializations.iter()
     }
 
+    /// Iterate references to all scopes in the context.
+    pub fn iter_scope_refs(&self) -> impl Iterator<Item = BindScopeRef> {
+        (0..self.scopes.len()).map(|context_idx| BindScopeRef { context_idx })
+    }
+
     pub fn get_parent_ref(&self, bind_ref: BindScopeRef) -> Result<Option<BindScopeRef>> {
         let child = self.get_scope(bind_ref)?;
         Ok(child.parent)
@@ -0,0 +1,100 @@
+use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_parser::ast;
+
+use super::bind_context::{BindContext, BindScopeRef};
+use crate::arrays::datatype::DataType;
+use crate::database::create::OnConflict;
+use crate::functions::scalar::ScalarFunction;
+#[cfg(feature = "wasm_udf")]
+use crate::functions::wasm::{decode_hex_module, WasmScalarFunction};
+use crate::logical::logical_create::LogicalCreateFunction;
+use crate::logical::operator::{LocationRequirement, Node};
+use crate::logical::resolver::ResolvedMeta;
+use crate::logical::statistics::StatisticsValue;
+
+#[derive(Debug)]
+pub struct CreateFunctionBinder {
+    pub current: BindScopeRef,
+}
+
+impl CreateFunctionBinder {
+    pub fn new(current: BindScopeRef) -> Self {
+        CreateFunctionBinder { current }
+    }
+
+    pub fn bind_create_function(
+        &self,
+        _bind_context: &mut BindContext,
+        mut create: ast::CreateFunction<ResolvedMeta>,
+    ) -> Result<Node<LogicalCreateFunction>> {
+        let on_conflict = if create.or_replace {
+            OnConflict::Replace
+        } else {
+            OnConflict::Error
+        };
+
+        let [catalog, schema, name] = create.name.pop_3()?;
+        // Unqualified function lookups only fall back to the session's temp
+        // schema.
+        if catalog != "temp" || schema != "temp" {
+            return Err(RayexecError::new(format!(
+                "Functions can only be created in the temp schema, got '{catalog}.{schema}'"
+            )));
+        }
+
+        let language = match create.language {
+            Some(language) => language.into_normalized_string(),
+            None => return Err(RayexecError::new("Missing LANGUAGE for function")),
+        };
+        let return_type = create
+            .return_type
+            .ok_or_else(|| RayexecError::new("Missing RETURNS type for function"))?;
+        let arg_types = create.args.into_iter().map(|arg| arg.datatype).collect();
+
+        let function: Box<dyn ScalarFunction> = match language.as_str() {
+            "wasm" => create_wasm_function(name, arg_types, return_type, &create.body)?,
+            other => not_implemented!("Functions with language '{other}'"),
+        };
+
+        // Modules are instantiated per session, always handled on the client.
+        Ok(Node {
+            node: LogicalCreateFunction {
+                catalog,
+                schema,
+                function,
+                on_conflict,
+            },
+            location: LocationRequirement::ClientLocal,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+}
+
+#[cfg(feature = "wasm_udf")]
+fn create_wasm_function(
+    name: String,
+    arg_types: Vec<DataType>,
+    return_type: DataType,
+    body: &str,
+) -> Result<Box<dyn ScalarFunction>> {
+    let module = decode_hex_module(body)?;
+    Ok(Box::new(WasmScalarFunction::try_new(
+        name,
+        arg_types,
+        return_type,
+        &module,
+    )?))
+}
+
+#[cfg(not(feature = "wasm_udf"))]
+fn create_wasm_function(
+    _name: String,
+    _arg_types: Vec<DataType>,
+    _return_type: DataType,
+    _body: &str,
+) -> Result<Box<dyn ScalarFunction>> {
+    Err(RayexecError::new(
+        "Functions with language 'wasm' require building with the 'wasm_udf' feature",
+    ))
+}
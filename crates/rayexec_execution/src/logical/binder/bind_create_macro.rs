@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use rayexec_error::{RayexecError, Result};
+use rayexec_parser::ast;
+
+use super::bind_context::{BindContext, BindScopeRef};
+use crate::database::create::{CreateMacroInfo, OnConflict};
+use crate::logical::logical_create::LogicalCreateMacro;
+use crate::logical::operator::{LocationRequirement, Node};
+use crate::logical::resolver::ResolvedMeta;
+use crate::logical::statistics::StatisticsValue;
+
+#[derive(Debug)]
+pub struct CreateMacroBinder {
+    pub current: BindScopeRef,
+}
+
+impl CreateMacroBinder {
+    pub fn new(current: BindScopeRef) -> Self {
+        CreateMacroBinder { current }
+    }
+
+    pub fn bind_create_macro(
+        &self,
+        _bind_context: &mut BindContext,
+        mut create: ast::CreateMacro<ResolvedMeta>,
+    ) -> Result<Node<LogicalCreateMacro>> {
+        let on_conflict = if create.or_replace {
+            OnConflict::Replace
+        } else {
+            OnConflict::Error
+        };
+
+        let [catalog, schema, name] = create.name.pop_3()?;
+        // Same as functions, unqualified lookups only fall back to the
+        // session's temp schema.
+        if catalog != "temp" || schema != "temp" {
+            return Err(RayexecError::new(format!(
+                "Macros can only be created in the temp schema, got '{catalog}.{schema}'"
+            )));
+        }
+
+        let mut seen = HashSet::with_capacity(create.params.len());
+        let parameters = create
+            .params
+            .into_iter()
+            .map(|param| {
+                let param = param.into_normalized_string();
+                if !seen.insert(param.clone()) {
+                    return Err(RayexecError::new(format!(
+                        "Duplicate parameter '{param}' for macro '{name}'"
+                    )));
+                }
+                Ok(param)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let (body_sql, table_macro) = match create.body {
+            ast::MacroBody::Expr(sql) => (sql, false),
+            ast::MacroBody::Table(sql) => (sql, true),
+        };
+
+        // Macros only live for the session.
+        Ok(Node {
+            node: LogicalCreateMacro {
+                catalog,
+                schema,
+                info: CreateMacroInfo {
+                    name,
+                    parameters,
+                    body_sql,
+                    on_conflict,
+                },
+                table_macro,
+            },
+            location: LocationRequirement::ClientLocal,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+}
@@ -1,9 +1,11 @@
 use rayexec_error::{RayexecError, Result};
 use rayexec_parser::ast;
 
+use super::bind_check::CheckBinder;
 use super::bind_context::{BindContext, BindScopeRef};
 use super::bind_query::BoundQuery;
 use crate::arrays::field::Field;
+use crate::database::catalog_entry::CheckConstraint;
 use crate::database::create::OnConflict;
 use crate::logical::binder::bind_query::QueryBinder;
 use crate::logical::resolver::resolve_context::ResolveContext;
@@ -15,6 +17,9 @@ pub struct BoundCreateTable {
     pub schema: String,
     pub name: String,
     pub columns: Vec<Field>,
+    pub checks: Vec<CheckConstraint>,
+    /// Indices of the columns making up the primary key.
+    pub primary_key: Vec<usize>,
     pub on_conflict: OnConflict,
     pub source: Option<BoundQuery>,
 }
@@ -49,12 +54,55 @@ impl<'a> CreateTableBinder<'a> {
             }
         };
 
-        // TODO: Verify column constraints.
-        let mut columns: Vec<_> = create
-            .columns
-            .into_iter()
-            .map(|col| Field::new(col.name.into_normalized_string(), col.datatype, true))
-            .collect();
+        let mut columns = Vec::with_capacity(create.columns.len());
+        let mut checks = Vec::new();
+        let mut primary_key = Vec::new();
+
+        for (idx, col) in create.columns.into_iter().enumerate() {
+            let name = col.name.into_normalized_string();
+            let mut not_null = None;
+
+            for opt in col.opts {
+                let opt_not_null = match opt {
+                    ast::ColumnOption::Null => false,
+                    ast::ColumnOption::NotNull => true,
+                    ast::ColumnOption::PrimaryKey => {
+                        if !primary_key.is_empty() {
+                            return Err(RayexecError::new(
+                                "Multiple primary keys for a table are not allowed",
+                            ));
+                        }
+                        primary_key.push(idx);
+                        true
+                    }
+                    ast::ColumnOption::Check { expr, sql } => {
+                        // Bound to catch errors early, the expression gets
+                        // bound again for every insert.
+                        let _ = CheckBinder::new(self.resolve_context).bind_check(
+                            bind_context,
+                            &name,
+                            &col.datatype,
+                            &expr,
+                        )?;
+                        checks.push(CheckConstraint {
+                            column_id: idx,
+                            column_name: name.clone(),
+                            expression: sql,
+                        });
+                        continue;
+                    }
+                };
+
+                if not_null.is_some_and(|not_null| not_null != opt_not_null) {
+                    return Err(RayexecError::new(format!(
+                        "Conflicting NULL/NOT NULL declarations for column '{name}'"
+                    )));
+                }
+                not_null = Some(opt_not_null);
+            }
+
+            columns.push(Field::new(name, col.datatype, !not_null.unwrap_or(false)));
+        }
 
         let input = match create.source {
             Some(source) => {
@@ -98,6 +146,8 @@ impl<'a> CreateTableBinder<'a> {
             schema,
             name,
             columns,
+            checks,
+            primary_key,
             on_conflict,
             source: input,
         })
@@ -4,7 +4,8 @@ use rayexec_parser::ast;
 use super::bind_context::{BindContext, BindScopeRef};
 use crate::arrays::datatype::DataType;
 use crate::arrays::field::{Field, Schema};
-use crate::logical::binder::bind_query::bind_from::FromBinder;
+use crate::database::catalog_entry::CatalogEntryInner;
+use crate::logical::binder::bind_query::bind_from::{BoundFromItem, FromBinder};
 use crate::logical::binder::bind_query::QueryBinder;
 use crate::logical::logical_describe::LogicalDescribe;
 use crate::logical::operator::{LocationRequirement, Node};
@@ -40,16 +41,23 @@ impl<'a> DescribeBinder<'a> {
 
         let query_scope = bind_context.new_orphan_scope();
 
-        // We don't care about the results of the bind, just the changes it
-        // makes to the bind context (columns).
+        // We mostly don't care about the results of the bind, just the
+        // changes it makes to the bind context (columns). Describing a table
+        // directly also shows the table's constraints.
+        let mut table = None;
         match describe {
             ast::Describe::Query(query) => {
                 let _ = QueryBinder::new(query_scope, self.resolve_context)
                     .bind(bind_context, query)?;
             }
             ast::Describe::FromNode(from) => {
-                let _ = FromBinder::new(query_scope, self.resolve_context)
+                let bound = FromBinder::new(query_scope, self.resolve_context)
                     .bind(bind_context, Some(from))?;
+                if let BoundFromItem::BaseTable(base) = bound.item {
+                    if let CatalogEntryInner::Table(ent) = &base.entry.entry {
+                        table = Some(ent.clone());
+                    }
+                }
             }
         }
 
@@ -62,9 +70,17 @@ impl<'a> DescribeBinder<'a> {
                     .map(|(name, datatype)| Field::new(name, datatype.clone(), true))
             });
 
+        let constraints = match table {
+            Some(table) => (0..table.columns.len())
+                .map(|idx| table.column_constraints(idx))
+                .collect(),
+            None => Vec::new(),
+        };
+
         Ok(Node {
             node: LogicalDescribe {
                 schema: Schema::new(fields),
+                constraints,
                 table_ref,
             },
             location: LocationRequirement::Any,
@@ -4,9 +4,11 @@ use rayexec_parser::ast;
 use super::bind_context::{BindContext, BindScopeRef};
 use super::bind_query::BoundQuery;
 use crate::arrays::datatype::DataType;
+use crate::explain::explainable::ExplainEntry;
 use crate::logical::binder::bind_query::QueryBinder;
 use crate::logical::logical_explain::ExplainFormat;
-use crate::logical::resolver::resolve_context::ResolveContext;
+use crate::logical::resolver::resolve_context::{MaybeResolved, ResolveContext};
+use crate::logical::resolver::resolved_table::ResolvedTableOrCteReference;
 use crate::logical::resolver::ResolvedMeta;
 
 #[derive(Debug)]
@@ -16,6 +18,12 @@ pub struct BoundExplain {
     pub format: ExplainFormat,
     pub verbose: bool,
     pub analyze: bool,
+    /// Database objects resolved for the query, only populated if the bind
+    /// context should be included in the output.
+    pub bindings: Option<Vec<ExplainEntry>>,
+    /// If the plan after each optimizer rule should be included in the
+    /// output.
+    pub optimized_steps: bool,
 }
 
 #[derive(Debug)]
@@ -62,11 +70,65 @@ impl<'a> ExplainBinder<'a> {
             vec!["plan_type".to_string(), "plan".to_string()],
         )?;
 
+        let bindings = if explain.bindings {
+            Some(self.explain_resolved_objects())
+        } else {
+            None
+        };
+
         Ok(BoundExplain {
             query,
             format,
             verbose: explain.verbose,
             analyze: explain.analyze,
+            bindings,
+            optimized_steps: explain.optimized_steps,
         })
     }
+
+    /// Create explain entries for the tables and table functions resolved for
+    /// the query.
+    fn explain_resolved_objects(&self) -> Vec<ExplainEntry> {
+        let tables = self
+            .resolve_context
+            .tables
+            .inner
+            .iter()
+            .map(|table| match table {
+                MaybeResolved::Resolved(ResolvedTableOrCteReference::Table(table), location) => {
+                    ExplainEntry::new("ResolvedTable")
+                        .with_value("catalog", &table.catalog)
+                        .with_value("schema", &table.schema)
+                        .with_value("name", &table.entry.name)
+                        .with_value("location", location)
+                }
+                MaybeResolved::Resolved(ResolvedTableOrCteReference::Cte(name), location) => {
+                    ExplainEntry::new("ResolvedCte")
+                        .with_value("name", name)
+                        .with_value("location", location)
+                }
+                MaybeResolved::Unresolved(unresolved) => ExplainEntry::new("UnresolvedTable")
+                    .with_value("reference", &unresolved.reference)
+                    .with_value("catalog", &unresolved.catalog),
+            });
+
+        let functions = self
+            .resolve_context
+            .table_functions
+            .inner
+            .iter()
+            .map(|function| match function {
+                MaybeResolved::Resolved(function, location) => {
+                    ExplainEntry::new("ResolvedTableFunction")
+                        .with_value("name", function.base_table_alias())
+                        .with_value("location", location)
+                }
+                MaybeResolved::Unresolved(unresolved) => {
+                    ExplainEntry::new("UnresolvedTableFunction")
+                        .with_value("reference", &unresolved.reference)
+                }
+            });
+
+        tables.chain(functions).collect()
+    }
 }
@@ -1,6 +1,7 @@
 use rayexec_error::{RayexecError, Result};
 use rayexec_parser::ast;
 
+use super::bind_check::{BoundCheck, CheckBinder};
 use super::bind_context::{BindContext, BindScopeRef};
 use super::bind_query::BoundQuery;
 use super::table_list::TableRef;
@@ -8,6 +9,7 @@ use crate::arrays::datatype::DataType;
 use crate::expr::cast_expr::CastExpr;
 use crate::expr::column_expr::ColumnExpr;
 use crate::expr::Expression;
+use crate::functions::implicit::assignment_cast_allowed;
 use crate::logical::binder::bind_query::QueryBinder;
 use crate::logical::operator::LocationRequirement;
 use crate::logical::resolver::resolve_context::ResolveContext;
@@ -35,6 +37,8 @@ pub struct BoundInsert {
     ///
     /// None if no casts are needed.
     pub projections: Option<InsertProjections>,
+    /// CHECK constraints to validate inserted rows against.
+    pub checks: Vec<BoundCheck>,
 }
 
 #[derive(Debug)]
@@ -82,12 +86,8 @@ impl<'a> InsertBinder<'a> {
         // Currently assumes we're inserting by position.
 
         // Check types, determine appropriate casts.
-        let table_types = reference
-            .entry
-            .try_as_table_entry()?
-            .columns
-            .iter()
-            .map(|c| &c.datatype);
+        let table = reference.entry.try_as_table_entry()?;
+        let table_columns = &table.columns;
 
         // Types from the source plan.
         let source_types: Vec<(TableRef, usize, &DataType)> = bind_context
@@ -101,10 +101,10 @@ impl<'a> InsertBinder<'a> {
             })
             .collect();
 
-        if table_types.len() != source_types.len() {
+        if table_columns.len() != source_types.len() {
             return Err(RayexecError::new(format!(
                 "Invalid number of inputs. Expected {}, got {}",
-                table_types.len(),
+                table_columns.len(),
                 source_types.len(),
             )));
         }
@@ -112,13 +112,21 @@ impl<'a> InsertBinder<'a> {
         let mut has_cast = false;
         let mut projections = Vec::with_capacity(source_types.len());
 
-        for (have, want) in source_types.into_iter().zip(table_types) {
+        for (have, column) in source_types.into_iter().zip(table_columns) {
             let mut expr = Expression::Column(ColumnExpr {
                 table_scope: have.0,
                 column: have.1,
             });
 
+            let want = &column.datatype;
             if have.2 != want {
+                if !assignment_cast_allowed(have.2, want) {
+                    return Err(RayexecError::new(format!(
+                        "Cannot insert value of type {} into column '{}' of type {want}",
+                        have.2, column.name,
+                    )));
+                }
+
                 expr = Expression::Cast(CastExpr {
                     to: want.clone(),
                     expr: Box::new(expr),
@@ -149,11 +157,52 @@ impl<'a> InsertBinder<'a> {
             None
         };
 
+        if table.checks.len() != self.resolve_context.insert_checks.len() {
+            return Err(RayexecError::new(format!(
+                "Expected {} resolved CHECK constraints, got {}",
+                table.checks.len(),
+                self.resolve_context.insert_checks.len(),
+            )));
+        }
+
+        let checks = table
+            .checks
+            .iter()
+            .zip(&self.resolve_context.insert_checks)
+            .map(|(check, expr)| {
+                let column_idx = table
+                    .column_ids
+                    .iter()
+                    .position(|&id| id == check.column_id)
+                    .ok_or_else(|| {
+                        RayexecError::new(format!(
+                            "Missing column for CHECK constraint: {}",
+                            check.expression
+                        ))
+                    })?;
+
+                let (table_ref, expr) = CheckBinder::new(self.resolve_context).bind_check(
+                    bind_context,
+                    &check.column_name,
+                    &table_columns[column_idx].datatype,
+                    expr,
+                )?;
+
+                Ok(BoundCheck {
+                    column_idx,
+                    table_ref,
+                    expr,
+                    sql: check.expression.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
         Ok(BoundInsert {
             source: bound_query,
             table: reference.clone(),
             table_location: location,
             projections,
+            checks,
         })
     }
 }
@@ -0,0 +1,77 @@
+use rayexec_error::{RayexecError, Result};
+use rayexec_parser::ast;
+
+use super::bind_context::BindContext;
+use super::column_binder::DefaultColumnBinder;
+use super::expr_binder::{BaseExpressionBinder, RecursionContext};
+use super::table_list::TableRef;
+use crate::arrays::datatype::DataType;
+use crate::database::catalog_entry::TableEntry;
+use crate::expr::Expression;
+use crate::logical::resolver::resolve_context::ResolveContext;
+use crate::logical::resolver::ResolvedMeta;
+
+/// Binds row-level security policy predicates.
+///
+/// Predicates are bound against the table's own column names, so they're not
+/// affected by how the table is aliased in the query.
+#[derive(Debug)]
+pub struct PolicyBinder<'a> {
+    pub resolve_context: &'a ResolveContext,
+}
+
+impl<'a> PolicyBinder<'a> {
+    pub fn new(resolve_context: &'a ResolveContext) -> Self {
+        PolicyBinder { resolve_context }
+    }
+
+    /// Bind a predicate for a table.
+    ///
+    /// The returned expression references columns in the returned table,
+    /// which contains the table's columns in order.
+    pub fn bind_policy(
+        &self,
+        bind_context: &mut BindContext,
+        table_name: &str,
+        table: &TableEntry,
+        expr: &ast::Expr<ResolvedMeta>,
+    ) -> Result<(TableRef, Expression)> {
+        let scope = bind_context.new_orphan_scope();
+        let policy_ref = bind_context.push_table(
+            scope,
+            None,
+            table.columns.iter().map(|c| c.datatype.clone()).collect(),
+            table.columns.iter().map(|c| c.name.clone()).collect(),
+        )?;
+
+        let expr = BaseExpressionBinder::new(scope, self.resolve_context).bind_expression(
+            bind_context,
+            expr,
+            &mut DefaultColumnBinder,
+            RecursionContext {
+                allow_aggregates: false,
+                allow_windows: false,
+                is_root: true,
+            },
+        )?;
+
+        if expr.contains_subquery()
+            || expr.contains_aggregate()
+            || expr.contains_window()
+            || expr.contains_unnest()
+        {
+            return Err(RayexecError::new(format!(
+                "Policy on table '{table_name}' may not contain subqueries, aggregates, window functions, or UNNEST"
+            )));
+        }
+
+        let return_type = expr.datatype(bind_context.get_table_list())?;
+        if return_type != DataType::Boolean {
+            return Err(RayexecError::new(format!(
+                "Policy on table '{table_name}' must be a boolean expression, got {return_type}"
+            )));
+        }
+
+        Ok((policy_ref, expr))
+    }
+}
@@ -18,8 +18,13 @@ use crate::logical::binder::bind_context::{
     CteRef,
     UsingColumn,
 };
+use crate::logical::binder::bind_policy::PolicyBinder;
 use crate::logical::binder::column_binder::DefaultColumnBinder;
-use crate::logical::binder::expr_binder::{BaseExpressionBinder, RecursionContext};
+use crate::logical::binder::expr_binder::{
+    verify_no_aggregates_or_windows,
+    BaseExpressionBinder,
+    RecursionContext,
+};
 use crate::logical::binder::table_list::{TableAlias, TableRef};
 use crate::logical::logical_join::JoinType;
 use crate::logical::operator::LocationRequirement;
@@ -29,6 +34,7 @@ use crate::logical::resolver::resolved_table_function::ResolvedTableFunctionRefe
 use crate::logical::resolver::{ResolvedMeta, ResolvedSubqueryOptions};
 use crate::optimizer::expr_rewrite::const_fold::ConstFold;
 use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
+use crate::storage::table_storage::{SampleMethod, TableSample};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BoundFrom {
@@ -43,6 +49,7 @@ pub enum BoundFromItem {
     TableFunction(BoundTableFunction),
     Subquery(BoundSubquery),
     MaterializedCte(BoundMaterializedCte),
+    Sample(BoundSample),
     Empty,
 }
 
@@ -53,6 +60,8 @@ pub struct BoundBaseTable {
     pub catalog: String,
     pub schema: String,
     pub entry: Arc<CatalogEntry>,
+    /// Row-level security predicate rows must pass to be visible.
+    pub policy: Option<Expression>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -75,6 +84,13 @@ pub struct BoundMaterializedCte {
     pub cte_name: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundSample {
+    pub sample: TableSample,
+    /// The item being sampled.
+    pub from: Box<BoundFrom>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BoundJoin {
     /// Reference to binder for left side of join.
@@ -122,7 +138,7 @@ impl<'a> FromBinder<'a> {
             }
         };
 
-        match from.body {
+        let bound = match from.body {
             ast::FromNodeBody::BaseTable(table) => self.bind_table(bind_context, table, from.alias),
             ast::FromNodeBody::Join(join) => self.bind_join(bind_context, join), // TODO: What to do with alias?
             ast::FromNodeBody::TableFunction(func) => {
@@ -134,9 +150,40 @@ impl<'a> FromBinder<'a> {
             ast::FromNodeBody::File(_) => Err(RayexecError::new(
                 "Resolver should have replaced file path with a table function",
             )),
+        }?;
+
+        match from.sample {
+            Some(sample) => Ok(BoundFrom {
+                bind_ref: self.current,
+                item: BoundFromItem::Sample(BoundSample {
+                    sample: Self::bind_sample(sample)?,
+                    from: Box::new(bound),
+                }),
+            }),
+            None => Ok(bound),
         }
     }
 
+    fn bind_sample(sample: ast::TableSample) -> Result<TableSample> {
+        if !(0.0..=100.0).contains(&sample.percentage) {
+            return Err(RayexecError::new(format!(
+                "Sample percentage must be between 0 and 100, got {}",
+                sample.percentage
+            )));
+        }
+
+        let method = match sample.method {
+            ast::TableSampleMethod::System => SampleMethod::System,
+            ast::TableSampleMethod::Bernoulli => SampleMethod::Bernoulli,
+        };
+
+        Ok(TableSample {
+            method,
+            percentage: sample.percentage,
+            seed: sample.seed,
+        })
+    }
+
     fn push_table_scope_with_from_alias(
         &self,
         bind_context: &mut BindContext,
@@ -194,7 +241,8 @@ impl<'a> FromBinder<'a> {
         table: ast::FromBaseTable<ResolvedMeta>,
         alias: Option<ast::FromAlias>,
     ) -> Result<BoundFrom> {
-        match self.resolve_context.tables.try_get_bound(table.reference)? {
+        let reference = table.reference;
+        match self.resolve_context.tables.try_get_bound(reference)? {
             (ResolvedTableOrCteReference::Table(table), location) => {
                 let column_types = table
                     .entry
@@ -225,6 +273,23 @@ impl<'a> FromBinder<'a> {
                     alias,
                 )?;
 
+                let policy = match self.resolve_context.find_table_policy(reference) {
+                    Some(expr) => {
+                        let ent = table.entry.try_as_table_entry()?;
+                        let (policy_ref, expr) = PolicyBinder::new(self.resolve_context)
+                            .bind_policy(bind_context, &table.entry.name, ent, expr)?;
+                        // Point the predicate at the scan's columns.
+                        let expr = (0..ent.columns.len()).fold(expr, |expr, col| {
+                            expr.replace_column(
+                                ColumnExpr::new(policy_ref, col),
+                                ColumnExpr::new(table_ref, col),
+                            )
+                        });
+                        Some(expr)
+                    }
+                    None => None,
+                };
+
                 Ok(BoundFrom {
                     bind_ref: self.current,
                     item: BoundFromItem::BaseTable(BoundBaseTable {
@@ -233,6 +298,7 @@ impl<'a> FromBinder<'a> {
                         catalog: table.catalog.clone(),
                         schema: table.schema.clone(),
                         entry: table.entry.clone(),
+                        policy,
                     }),
                 })
             }
@@ -593,6 +659,7 @@ impl<'a> FromBinder<'a> {
             ast::JoinType::Inner => JoinType::Inner,
             ast::JoinType::Left => JoinType::Left,
             ast::JoinType::Right => JoinType::Right,
+            ast::JoinType::Outer => JoinType::Full,
             ast::JoinType::LeftSemi => JoinType::Semi,
             other => not_implemented!("plan join type: {other:?}"),
         };
@@ -612,6 +679,9 @@ impl<'a> FromBinder<'a> {
                 is_root: true,
             },
         )?;
+        for condition in &conditions {
+            verify_no_aggregates_or_windows(condition, "JOIN conditions")?;
+        }
 
         // Handle any USING columns, adding conditions as needed.
         for using in using_cols {
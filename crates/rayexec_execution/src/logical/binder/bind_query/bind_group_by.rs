@@ -7,7 +7,11 @@ use super::select_list::SelectList;
 use crate::expr::Expression;
 use crate::logical::binder::bind_context::{BindContext, BindScopeRef};
 use crate::logical::binder::column_binder::{DefaultColumnBinder, ExpressionColumnBinder};
-use crate::logical::binder::expr_binder::{BaseExpressionBinder, RecursionContext};
+use crate::logical::binder::expr_binder::{
+    verify_no_aggregates_or_windows,
+    BaseExpressionBinder,
+    RecursionContext,
+};
 use crate::logical::binder::table_list::TableRef;
 use crate::logical::resolver::resolve_context::ResolveContext;
 use crate::logical::resolver::ResolvedMeta;
@@ -39,6 +43,11 @@ impl<'a> GroupByBinder<'a> {
         select_list: &mut SelectList,
         group_by: ast::GroupByNode<ResolvedMeta>,
     ) -> Result<BoundGroupBy> {
+        let group_by = match group_by {
+            ast::GroupByNode::All => Self::expand_group_by_all(select_list),
+            other => other,
+        };
+
         let sets = GroupByWithSets::try_from_ast(group_by)?;
         let group_table = bind_context.new_ephemeral_table()?;
 
@@ -60,6 +69,7 @@ impl<'a> GroupByBinder<'a> {
                         is_root: true,
                     },
                 )?;
+                verify_no_aggregates_or_windows(&expr, "GROUP BY")?;
 
                 let datatype = expr.datatype(bind_context.get_table_list())?;
                 bind_context.push_column_for_table(
@@ -78,6 +88,20 @@ impl<'a> GroupByBinder<'a> {
             grouping_sets: sets.grouping_sets,
         })
     }
+
+    /// Rewrite GROUP BY ALL to group by the ordinals of all select
+    /// expressions that aren't aggregates.
+    fn expand_group_by_all(select_list: &SelectList) -> ast::GroupByNode<ResolvedMeta> {
+        let exprs = select_list
+            .non_aggregate_ordinals()
+            .into_iter()
+            .map(|ordinal| ast::Expr::Literal(ast::Literal::Number(ordinal.to_string())))
+            .collect();
+
+        ast::GroupByNode::Exprs {
+            exprs: vec![ast::GroupByExpr::Expr(exprs)],
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -145,7 +169,9 @@ struct GroupByWithSets {
 impl GroupByWithSets {
     fn try_from_ast(group_by: ast::GroupByNode<ResolvedMeta>) -> Result<Self> {
         match group_by {
-            ast::GroupByNode::All => not_implemented!("GROUP BY ALL"),
+            ast::GroupByNode::All => Err(RayexecError::new(
+                "GROUP BY ALL should have been expanded using the select list",
+            )),
             ast::GroupByNode::Exprs { mut exprs } => {
                 let expr = match exprs.len() {
                     1 => exprs.pop().unwrap(),
@@ -3,11 +3,11 @@ use rayexec_parser::ast;
 
 use super::bind_group_by::BoundGroupBy;
 use super::bind_select_list::SelectListBinder;
-use super::select_list::{BoundSelectList, SelectList};
+use super::select_list::{verify_grouped_column_references, BoundSelectList, SelectList};
 use crate::expr::column_expr::ColumnExpr;
 use crate::expr::Expression;
 use crate::logical::binder::bind_context::{BindContext, BindScopeRef};
-use crate::logical::binder::column_binder::DefaultColumnBinder;
+use crate::logical::binder::column_binder::{DefaultColumnBinder, ExpressionColumnBinder};
 use crate::logical::binder::expr_binder::{BaseExpressionBinder, RecursionContext};
 use crate::logical::resolver::resolve_context::ResolveContext;
 use crate::logical::resolver::ResolvedMeta;
@@ -32,11 +32,12 @@ impl<'a> HavingBinder<'a> {
         select_list: &mut SelectList,
         having: ast::Expr<ResolvedMeta>,
     ) -> Result<Expression> {
+        let mut column_binder = HavingColumnBinder { select_list };
         let mut expr = BaseExpressionBinder::new(self.current, self.resolve_context)
             .bind_expression(
                 bind_context,
                 &having,
-                &mut DefaultColumnBinder,
+                &mut column_binder,
                 RecursionContext {
                     allow_windows: false,
                     allow_aggregates: true,
@@ -44,6 +45,12 @@ impl<'a> HavingBinder<'a> {
                 },
             )?;
 
+        if expr.contains_window() {
+            return Err(RayexecError::new(
+                "Window functions are not allowed in HAVING",
+            ));
+        }
+
         // Extract out the aggregates from the expression.
         SelectListBinder::extract_aggregates(
             select_list.aggregates_table,
@@ -65,6 +72,7 @@ impl<'a> HavingBinder<'a> {
     /// only after select list finalizing.
     pub fn update_expression_dependencies(
         &self,
+        bind_context: &BindContext,
         select_list: &BoundSelectList,
         having_expr: &mut Expression,
         group_by: Option<&BoundGroupBy>,
@@ -97,18 +105,63 @@ impl<'a> HavingBinder<'a> {
 
         // Verify that we only reference either GROUP BY columns or contain
         // aggregates.
-        let mut refs = having_expr.get_table_references();
-        refs.remove(&select_list.aggregates_table);
+        let mut allowed = vec![
+            select_list.aggregates_table,
+            select_list.grouping_functions_table,
+        ];
         if let Some(group_by) = group_by {
-            refs.remove(&group_by.group_exprs_table);
+            allowed.push(group_by.group_exprs_table);
         }
 
-        if !refs.is_empty() {
-            return Err(RayexecError::new(
-                "HAVING contains columns not found in the GROUP BY clause",
-            ));
-        }
+        verify_grouped_column_references(bind_context, having_expr, &allowed)?;
 
         Ok(())
     }
 }
+
+/// Column binder for HAVING that falls back to binding to aliases in the
+/// select list.
+#[derive(Debug)]
+pub struct HavingColumnBinder<'a> {
+    select_list: &'a SelectList,
+}
+
+impl ExpressionColumnBinder for HavingColumnBinder<'_> {
+    fn bind_from_root_literal(
+        &mut self,
+        bind_scope: BindScopeRef,
+        bind_context: &mut BindContext,
+        literal: &ast::Literal<ResolvedMeta>,
+    ) -> Result<Option<Expression>> {
+        DefaultColumnBinder.bind_from_root_literal(bind_scope, bind_context, literal)
+    }
+
+    fn bind_from_ident(
+        &mut self,
+        bind_scope: BindScopeRef,
+        bind_context: &mut BindContext,
+        ident: &ast::Ident,
+        recur: RecursionContext,
+    ) -> Result<Option<Expression>> {
+        // Columns take precedence over aliases.
+        if let Some(col) =
+            DefaultColumnBinder.bind_from_ident(bind_scope, bind_context, ident, recur)?
+        {
+            return Ok(Some(col));
+        }
+
+        // Use the aliased expression directly, the GROUP BY dependencies will
+        // be updated once the select list is finalized.
+        self.select_list.expression_by_user_alias(ident)
+    }
+
+    fn bind_from_idents(
+        &mut self,
+        bind_scope: BindScopeRef,
+        bind_context: &mut BindContext,
+        idents: &[ast::Ident],
+        recur: RecursionContext,
+    ) -> Result<Option<Expression>> {
+        DefaultColumnBinder.bind_from_idents(bind_scope, bind_context, idents, recur)
+    }
+}
@@ -226,25 +226,32 @@ impl ExpressionColumnBinder for OrderByColumnBinder<'_> {
         ident: &ast::Ident,
         recur: RecursionContext,
     ) -> Result<Option<Expression>> {
-        // Try to bind normally.
-        if let Some(col) =
-            DefaultColumnBinder.bind_from_ident(bind_scope, bind_context, ident, recur)?
+        // Columns from this query's FROM take precedence.
+        let name = ident.as_normalized_string();
+        if bind_context
+            .find_table_for_column(bind_scope, None, &name)?
+            .is_some()
         {
-            return Ok(Some(col));
+            return DefaultColumnBinder.bind_from_ident(bind_scope, bind_context, ident, recur);
         }
 
-        // Only allow binding alias if we at the root of the expressions.
-        if !recur.is_root {
-            return Ok(None);
-        }
-
-        // Try to bind to a user-provided alias.
-        if let Some(col) = self.select_list.column_by_user_alias(ident) {
-            self.did_bind_to_select = true;
-            return Ok(Some(Expression::Column(col)));
+        // Then aliases in the select list, checked before outer scopes so that
+        // an alias isn't mistaken for a correlated column.
+        if recur.is_root {
+            // Binding to an alias at the root of the expression can just
+            // reference the select list.
+            if let Some(col) = self.select_list.column_by_user_alias(ident) {
+                self.did_bind_to_select = true;
+                return Ok(Some(Expression::Column(col)));
+            }
+        } else if let Some(expr) = self.select_list.expression_by_user_alias(ident)? {
+            // Otherwise the alias is part of a larger expression, use the
+            // aliased expression directly.
+            return Ok(Some(expr));
         }
 
-        Ok(None)
+        // Finally try outer scopes.
+        DefaultColumnBinder.bind_from_ident(bind_scope, bind_context, ident, recur)
     }
 
     fn bind_from_idents(
@@ -11,7 +11,11 @@ use super::select_list::BoundSelectList;
 use crate::expr::Expression;
 use crate::logical::binder::bind_context::{BindContext, BindScopeRef};
 use crate::logical::binder::column_binder::DefaultColumnBinder;
-use crate::logical::binder::expr_binder::{BaseExpressionBinder, RecursionContext};
+use crate::logical::binder::expr_binder::{
+    verify_no_aggregates_or_windows,
+    BaseExpressionBinder,
+    RecursionContext,
+};
 use crate::logical::resolver::resolve_context::ResolveContext;
 use crate::logical::resolver::ResolvedMeta;
 
@@ -74,9 +78,9 @@ impl<'a> SelectBinder<'a> {
         // Handle WHERE
         let where_expr = select
             .where_expr
-            .map(|expr| {
+            .map(|expr| -> Result<_> {
                 let binder = BaseExpressionBinder::new(from_bind_ref, self.resolve_context);
-                binder.bind_expression(
+                let expr = binder.bind_expression(
                     bind_context,
                     &expr,
                     &mut DefaultColumnBinder,
@@ -85,7 +89,9 @@ impl<'a> SelectBinder<'a> {
                         allow_aggregates: false,
                         is_root: true,
                     },
-                )
+                )?;
+                verify_no_aggregates_or_windows(&expr, "WHERE")?;
+                Ok(expr)
             })
             .transpose()?;
 
@@ -99,6 +105,12 @@ impl<'a> SelectBinder<'a> {
         // Handle GROUP BY
         let mut group_by = select
             .group_by
+            .filter(|group_by| {
+                // GROUP BY ALL without any non-aggregate expressions is the
+                // same as not having a GROUP BY.
+                !matches!(group_by, ast::GroupByNode::All)
+                    || !select_list.non_aggregate_ordinals().is_empty()
+            })
             .map(|group_by| {
                 let mut group_by_binder = GroupByBinder::new(from_bind_ref, self.resolve_context);
                 group_by_binder.bind(bind_context, &mut select_list, group_by)
@@ -118,11 +130,13 @@ impl<'a> SelectBinder<'a> {
             .transpose()?;
 
         // Finalize projections.
-        let select_list = select_list.finalize(bind_context, group_by.as_mut())?;
+        let select_list =
+            select_list.finalize(bind_context, group_by.as_mut(), having.is_some())?;
 
         // Update HAVING if needed.
         if let Some(having) = &mut having {
             HavingBinder::new(from_bind_ref, self.resolve_context).update_expression_dependencies(
+                bind_context,
                 &select_list,
                 having,
                 group_by.as_ref(),
@@ -63,6 +63,7 @@ impl<'a> SelectListBinder<'a> {
                         _ => "?column?".to_string(),
                     },
                     ExpandedSelectExpr::Column { name, .. } => name.clone(),
+                    ExpandedSelectExpr::ColumnsExpr { name, .. } => name.clone(),
                 })
             })
             .collect::<Result<Vec<_>>>()?;
@@ -71,8 +72,10 @@ impl<'a> SelectListBinder<'a> {
         //
         // TODO: This should be updated in finalize, GROUP BY may reference an
         // unaliased column.
-        for (alias, idx) in &alias_map {
-            names[*idx] = alias.clone();
+        for (idx, projection) in projections.iter().enumerate() {
+            if let Some(alias) = projection.get_alias() {
+                names[idx] = alias.to_string();
+            }
         }
 
         // Bind the expressions.
@@ -85,6 +88,7 @@ impl<'a> SelectListBinder<'a> {
                         current_idx: idx,
                         alias_map: &alias_map,
                         previous_exprs: &exprs,
+                        columns_replacement: None,
                     };
 
                     let expr = expr_binder.bind_expression(
@@ -102,6 +106,26 @@ impl<'a> SelectListBinder<'a> {
                 ExpandedSelectExpr::Column { expr, .. } => {
                     exprs.push(Expression::Column(expr));
                 }
+                ExpandedSelectExpr::ColumnsExpr { expr, column, .. } => {
+                    let mut col_binder = SelectAliasColumnBinder {
+                        current_idx: idx,
+                        alias_map: &alias_map,
+                        previous_exprs: &exprs,
+                        columns_replacement: Some(column),
+                    };
+
+                    let expr = expr_binder.bind_expression(
+                        bind_context,
+                        &expr,
+                        &mut col_binder,
+                        RecursionContext {
+                            allow_windows: true,
+                            allow_aggregates: true,
+                            is_root: true,
+                        },
+                    )?;
+                    exprs.push(expr);
+                }
             }
         }
 
@@ -266,6 +290,8 @@ struct SelectAliasColumnBinder<'a> {
     alias_map: &'a HashMap<String, usize>,
     /// Previously planned expressions.
     previous_exprs: &'a [Expression],
+    /// Column to use for COLUMNS expressions in the expression.
+    columns_replacement: Option<ColumnExpr>,
 }
 
 impl ExpressionColumnBinder for SelectAliasColumnBinder<'_> {
@@ -321,4 +347,8 @@ impl ExpressionColumnBinder for SelectAliasColumnBinder<'_> {
     ) -> Result<Option<Expression>> {
         DefaultColumnBinder.bind_from_idents(bind_scope, bind_context, idents, recur)
     }
+
+    fn bind_from_columns(&mut self, _columns: &ast::ColumnsExpr) -> Result<Option<Expression>> {
+        Ok(self.columns_replacement.map(Expression::Column))
+    }
 }
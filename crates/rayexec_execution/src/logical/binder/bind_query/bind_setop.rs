@@ -4,7 +4,8 @@ use rayexec_parser::ast;
 use super::bind_modifier::{BoundLimit, BoundOrderBy};
 use super::bind_select_list::SelectListBinder;
 use super::BoundQuery;
-use crate::functions::implicit::implicit_cast_score;
+use crate::arrays::datatype::DataType;
+use crate::functions::implicit::common_supertype;
 use crate::logical::binder::bind_context::{BindContext, BindScopeRef};
 use crate::logical::binder::bind_query::bind_modifier::ModifierBinder;
 use crate::logical::binder::bind_query::QueryBinder;
@@ -32,6 +33,17 @@ pub enum SetOpCastRequirement {
     None,
 }
 
+/// Column mapping for a set operation binding by name (`UNION BY NAME`).
+///
+/// Each entry corresponds to an output column, and points to the column index
+/// on the input side providing the value. `None` indicates the input doesn't
+/// have a column with that name, and NULLs should be produced instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetOpColumnMapping {
+    pub left: Vec<Option<usize>>,
+    pub right: Vec<Option<usize>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BoundSetOp {
     pub left: Box<BoundQuery>,
@@ -46,6 +58,12 @@ pub struct BoundSetOp {
     /// Bound LIMIT.
     pub limit: Option<BoundLimit>,
     pub cast_req: SetOpCastRequirement,
+    /// Column mapping if aligning inputs by name.
+    ///
+    /// If set, the cast requirement will always require casting both sides,
+    /// with the casting projections reordering columns according to the
+    /// mapping.
+    pub column_mapping: Option<SetOpColumnMapping>,
 }
 
 #[derive(Debug)]
@@ -92,17 +110,70 @@ impl<'a> SetOpBinder<'a> {
             },
         )?;
 
-        let mut left_types = Vec::new();
+        let mut left_types: Vec<DataType> = Vec::new();
         let mut left_names = Vec::new();
         for table in bind_context.iter_tables_in_scope(left_scope)? {
             left_types.extend_from_slice(&table.column_types);
             left_names.extend_from_slice(&table.column_names);
         }
 
-        let right_types: Vec<_> = bind_context
-            .iter_tables_in_scope(right_scope)?
-            .flat_map(|t| t.column_types.iter().cloned())
-            .collect();
+        let mut right_types = Vec::new();
+        let mut right_names = Vec::new();
+        for table in bind_context.iter_tables_in_scope(right_scope)? {
+            right_types.extend_from_slice(&table.column_types);
+            right_names.extend_from_slice(&table.column_names);
+        }
+
+        let column_mapping = if setop.by_name {
+            if setop.operation != ast::SetOperation::Union {
+                return Err(RayexecError::new(format!(
+                    "BY NAME is only supported for UNION, got {:?}",
+                    setop.operation
+                )));
+            }
+
+            let mapping = Self::align_by_name(&left_names, &right_names)?;
+
+            // Reorder input types to match the output columns. Missing columns
+            // take the type from the other side, the NULLs we produce for it
+            // will just be casted.
+            let align_types = |types: &[DataType],
+                               other: &[DataType],
+                               this_map: &[Option<usize>],
+                               other_map: &[Option<usize>]| {
+                this_map
+                    .iter()
+                    .zip(other_map)
+                    .map(|(this_idx, other_idx)| match (this_idx, other_idx) {
+                        (Some(idx), _) => types[*idx].clone(),
+                        (None, Some(idx)) => other[*idx].clone(),
+                        (None, None) => unreachable!("output column exists on at least one side"),
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            let aligned_left =
+                align_types(&left_types, &right_types, &mapping.left, &mapping.right);
+            let aligned_right =
+                align_types(&right_types, &left_types, &mapping.right, &mapping.left);
+
+            left_names = mapping
+                .left
+                .iter()
+                .zip(&mapping.right)
+                .map(|(left_idx, right_idx)| match (left_idx, right_idx) {
+                    (Some(idx), _) => left_names[*idx].clone(),
+                    (None, Some(idx)) => right_names[*idx].clone(),
+                    (None, None) => unreachable!("output column exists on at least one side"),
+                })
+                .collect();
+            left_types = aligned_left;
+            right_types = aligned_right;
+
+            Some(mapping)
+        } else {
+            None
+        };
 
         // Determine output types of this node by comparing both sides, and
         // marking which side neds casting.
@@ -117,22 +188,32 @@ impl<'a> SetOpBinder<'a> {
                 continue;
             }
 
-            let left_score = implicit_cast_score(&right, left.datatype_id());
-            let right_score = implicit_cast_score(&left, right.datatype_id());
-
-            if left_score.is_none() && right_score.is_none() {
-                return Err(RayexecError::new(format!(
+            let common = common_supertype(&[&left, &right]).ok_or_else(|| {
+                RayexecError::new(format!(
                     "Cannot find suitable cast type for {left} and {right}"
-                )));
-            }
+                ))
+            })?;
 
-            if left_score >= right_score {
-                output_types.push(left);
-                right_needs_cast = true;
+            // Prefer keeping the full type (e.g. decimal precision/scale) of
+            // one of the sides if possible.
+            let output = if left.datatype_id() == common {
+                left.clone()
+            } else if right.datatype_id() == common {
+                right.clone()
             } else {
-                output_types.push(right);
-                left_needs_cast = true;
-            }
+                DataType::try_default_datatype(common)?
+            };
+
+            left_needs_cast |= output != left;
+            right_needs_cast |= output != right;
+            output_types.push(output);
+        }
+
+        if column_mapping.is_some() {
+            // Both sides always need a projection to reorder columns and fill
+            // in missing ones.
+            left_needs_cast = true;
+            right_needs_cast = true;
         }
 
         let cast_req = match (left_needs_cast, right_needs_cast) {
@@ -191,6 +272,89 @@ impl<'a> SetOpBinder<'a> {
             order_by,
             limit,
             cast_req,
+            column_mapping,
         })
     }
+
+    /// Compute the column mapping for aligning both sides of a set operation by
+    /// column name.
+    ///
+    /// Output columns are ordered by the columns on the left, followed by any
+    /// columns only on the right.
+    ///
+    /// Names are matched case-insensitively, so a quoted `"A"` on one side
+    /// lines up with an unquoted `a` on the other.
+    fn align_by_name(left_names: &[String], right_names: &[String]) -> Result<SetOpColumnMapping> {
+        fn matches(a: &str, b: &str) -> bool {
+            a.to_lowercase() == b.to_lowercase()
+        }
+
+        fn check_unique(names: &[String]) -> Result<()> {
+            for (idx, name) in names.iter().enumerate() {
+                if names[..idx].iter().any(|n| matches(n, name)) {
+                    return Err(RayexecError::new(format!(
+                        "Duplicate column name '{name}' in input to UNION BY NAME"
+                    )));
+                }
+            }
+            Ok(())
+        }
+
+        check_unique(left_names)?;
+        check_unique(right_names)?;
+
+        let mut left = Vec::with_capacity(left_names.len());
+        let mut right = Vec::with_capacity(left_names.len());
+
+        for (left_idx, name) in left_names.iter().enumerate() {
+            left.push(Some(left_idx));
+            right.push(right_names.iter().position(|n| matches(n, name)));
+        }
+
+        for (right_idx, name) in right_names.iter().enumerate() {
+            if !left_names.iter().any(|n| matches(n, name)) {
+                left.push(None);
+                right.push(Some(right_idx));
+            }
+        }
+
+        Ok(SetOpColumnMapping { left, right })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn align_by_name_reorders_and_fills() {
+        let mapping =
+            SetOpBinder::align_by_name(&names(&["a", "b", "c"]), &names(&["d", "c", "a"])).unwrap();
+
+        let expected = SetOpColumnMapping {
+            left: vec![Some(0), Some(1), Some(2), None],
+            right: vec![Some(2), None, Some(1), Some(0)],
+        };
+        assert_eq!(expected, mapping);
+    }
+
+    #[test]
+    fn align_by_name_ignores_case() {
+        let mapping = SetOpBinder::align_by_name(&names(&["a", "B"]), &names(&["b", "A"])).unwrap();
+
+        let expected = SetOpColumnMapping {
+            left: vec![Some(0), Some(1)],
+            right: vec![Some(1), Some(0)],
+        };
+        assert_eq!(expected, mapping);
+    }
+
+    #[test]
+    fn align_by_name_duplicate_names() {
+        SetOpBinder::align_by_name(&names(&["a", "a"]), &names(&["a"])).unwrap_err();
+    }
 }
@@ -30,12 +30,30 @@ pub enum ExpandedSelectExpr {
         /// Name as it existed in the bind scope.
         name: String,
     },
+    /// An expression containing COLUMNS expressions, where each COLUMNS
+    /// expression should be bound to a specific column.
+    ///
+    /// A single select expression will expand to one of these for each column
+    /// matched by the COLUMNS expression.
+    ColumnsExpr {
+        /// The original AST expression containing the COLUMNS expression.
+        expr: ast::Expr<ResolvedMeta>,
+        /// The column the COLUMNS expression should bind to.
+        column: ColumnExpr,
+        /// Output name for the expression, either the user-provided alias or
+        /// the name of the column.
+        name: String,
+        /// Optional user-provided alias.
+        alias: Option<String>,
+    },
 }
 
 impl ExpandedSelectExpr {
     pub fn get_alias(&self) -> Option<&str> {
         match self {
-            Self::Expr { alias, .. } => alias.as_ref().map(|a| a.as_str()),
+            Self::Expr { alias, .. } | Self::ColumnsExpr { alias, .. } => {
+                alias.as_ref().map(|a| a.as_str())
+            }
             Self::Column { .. } => None,
         }
     }
@@ -77,44 +95,7 @@ impl<'a> SelectExprExpander<'a> {
     ) -> Result<Vec<ExpandedSelectExpr>> {
         Ok(match expr {
             ast::SelectExpr::Wildcard(modifier) => {
-                let mut exprs = Vec::new();
-
-                // Handle USING columns. Expanding a SELECT * query that
-                // contains USING in the join, we only want to display the
-                // column once.
-                //
-                // USING columns are listed first, followed by the other table
-                // columns.
-                let mut handled = HashSet::new();
-                for using in self.bind_context.get_using_columns(self.current)? {
-                    exprs.push(ExpandedSelectExpr::Column {
-                        expr: ColumnExpr {
-                            table_scope: using.table_ref,
-                            column: using.col_idx,
-                        },
-                        name: using.column.clone(),
-                    });
-
-                    handled.insert(&using.column);
-                }
-
-                for table in self.bind_context.iter_tables_in_scope(self.current)? {
-                    for (col_idx, name) in table.column_names.iter().enumerate() {
-                        // If column is already added from USING, skip it.
-                        if handled.contains(name) {
-                            continue;
-                        }
-
-                        exprs.push(ExpandedSelectExpr::Column {
-                            expr: ColumnExpr {
-                                table_scope: table.reference,
-                                column: col_idx,
-                            },
-                            name: name.clone(),
-                        })
-                    }
-                }
-
+                let mut exprs = self.scope_columns()?;
                 Self::exclude_and_replace_cols(&mut exprs, modifier)?;
 
                 exprs
@@ -163,47 +144,137 @@ impl<'a> SelectExprExpander<'a> {
                 exprs
             }
             ast::SelectExpr::AliasedExpr(expr, alias) => {
-                vec![ExpandedSelectExpr::Expr {
-                    expr,
-                    alias: Some(alias.into_normalized_string()),
-                }]
+                self.expand_expr(expr, Some(alias.into_normalized_string()))?
+            }
+            ast::SelectExpr::Expr(expr) => match expr {
+                // Top-level COLUMNS expression, just expands to the matched
+                // columns.
+                ast::Expr::Columns(cols_expr) => self.expand_columns_expr(&cols_expr)?,
+                expr => self.expand_expr(expr, None)?,
+            },
+        })
+    }
+
+    /// Expand an expression that may contain COLUMNS expressions.
+    ///
+    /// If there are no COLUMNS expressions, this returns the expression as-is.
+    /// Otherwise the expression is repeated for each column matched by the
+    /// COLUMNS expression.
+    fn expand_expr(
+        &self,
+        expr: ast::Expr<ResolvedMeta>,
+        alias: Option<String>,
+    ) -> Result<Vec<ExpandedSelectExpr>> {
+        let mut found = Vec::new();
+        collect_columns_exprs(&expr, &mut found);
+
+        let cols_expr = match found.split_first() {
+            Some((first, rest)) => {
+                if rest.iter().any(|other| other != first) {
+                    return Err(RayexecError::new(
+                        "Multiple different COLUMNS expressions in the same select expression not supported",
+                    ));
+                }
+                (*first).clone()
             }
-            ast::SelectExpr::Expr(expr) => {
-                // Check if this is a COLUMNS expr, expand if it is.
-                if let ast::Expr::Columns(cols_expr) = expr {
-                    match cols_expr {
-                        ast::ColumnsExpr::Pattern(pattern) => {
-                            let regex = Regex::new(&pattern)
-                                .context("Failed to build column regex from pattern")?;
-
-                            let mut exprs = Vec::new();
-                            // Iter all columns in the context, select the ones
-                            // that match the regex.
-                            for table in self.bind_context.iter_tables_in_scope(self.current)? {
-                                for (col_idx, name) in table.column_names.iter().enumerate() {
-                                    if !regex.is_match(name) {
-                                        continue;
-                                    }
-
-                                    exprs.push(ExpandedSelectExpr::Column {
-                                        expr: ColumnExpr {
-                                            table_scope: table.reference,
-                                            column: col_idx,
-                                        },
-                                        name: name.clone(),
-                                    })
-                                }
-                            }
-
-                            exprs
-                        }
-                    }
-                } else {
-                    // Normal expression.
-                    vec![ExpandedSelectExpr::Expr { expr, alias: None }]
+            None => return Ok(vec![ExpandedSelectExpr::Expr { expr, alias }]),
+        };
+
+        let columns = self.expand_columns_expr(&cols_expr)?;
+        if alias.is_some() && columns.len() != 1 {
+            return Err(RayexecError::new(format!(
+                "Cannot alias COLUMNS expression that matches {} columns",
+                columns.len()
+            )));
+        }
+
+        columns
+            .into_iter()
+            .map(|col| match col {
+                ExpandedSelectExpr::Column { expr: column, name } => {
+                    Ok(ExpandedSelectExpr::ColumnsExpr {
+                        expr: expr.clone(),
+                        column,
+                        name: alias.clone().unwrap_or(name),
+                        alias: alias.clone(),
+                    })
                 }
+                other => Err(RayexecError::new(format!(
+                    "Unexpected expanded COLUMNS expression: {other:?}"
+                ))),
+            })
+            .collect()
+    }
+
+    /// Get the columns matched by a COLUMNS expression.
+    fn expand_columns_expr(&self, cols_expr: &ast::ColumnsExpr) -> Result<Vec<ExpandedSelectExpr>> {
+        let mut exprs = self.scope_columns()?;
+
+        match cols_expr {
+            ast::ColumnsExpr::Pattern(pattern) => {
+                let regex =
+                    Regex::new(pattern).context("Failed to build column regex from pattern")?;
+
+                // Select the columns that match the regex.
+                exprs.retain(|expr| match expr {
+                    ExpandedSelectExpr::Column { name, .. } => regex.is_match(name),
+                    _ => false,
+                });
             }
-        })
+            ast::ColumnsExpr::Wildcard { exclude_cols } => {
+                Self::exclude_and_replace_cols(
+                    &mut exprs,
+                    ast::WildcardModifier {
+                        exclude_cols: exclude_cols.clone(),
+                        replace_cols: Vec::new(),
+                    },
+                )?;
+            }
+        }
+
+        Ok(exprs)
+    }
+
+    /// Get all columns in the current scope in the order they should be
+    /// returned when expanding a wildcard.
+    fn scope_columns(&self) -> Result<Vec<ExpandedSelectExpr>> {
+        let mut exprs = Vec::new();
+
+        // Handle USING columns. Expanding a SELECT * query that contains USING
+        // in the join, we only want to display the column once.
+        //
+        // USING columns are listed first, followed by the other table columns.
+        let mut handled = HashSet::new();
+        for using in self.bind_context.get_using_columns(self.current)? {
+            exprs.push(ExpandedSelectExpr::Column {
+                expr: ColumnExpr {
+                    table_scope: using.table_ref,
+                    column: using.col_idx,
+                },
+                name: using.column.clone(),
+            });
+
+            handled.insert(&using.column);
+        }
+
+        for table in self.bind_context.iter_tables_in_scope(self.current)? {
+            for (col_idx, name) in table.column_names.iter().enumerate() {
+                // If column is already added from USING, skip it.
+                if handled.contains(name) {
+                    continue;
+                }
+
+                exprs.push(ExpandedSelectExpr::Column {
+                    expr: ColumnExpr {
+                        table_scope: table.reference,
+                        column: col_idx,
+                    },
+                    name: name.clone(),
+                })
+            }
+        }
+
+        Ok(exprs)
     }
 
     fn exclude_and_replace_cols(
@@ -289,6 +360,113 @@ impl<'a> SelectExprExpander<'a> {
     }
 }
 
+/// Collect all COLUMNS expressions in an expression.
+///
+/// Subqueries are not descended into since any COLUMNS expression in those
+/// will be expanded using the subquery's scope.
+fn collect_columns_exprs<'a>(
+    expr: &'a ast::Expr<ResolvedMeta>,
+    out: &mut Vec<&'a ast::ColumnsExpr>,
+) {
+    match expr {
+        ast::Expr::Columns(cols_expr) => out.push(cols_expr),
+        ast::Expr::Ident(_)
+        | ast::Expr::CompoundIdent(_)
+        | ast::Expr::QualifiedWildcard(_)
+        | ast::Expr::TypedString { .. }
+        | ast::Expr::Subquery(_)
+        | ast::Expr::Exists { .. } => (),
+        ast::Expr::Literal(lit) => {
+            if let ast::Literal::Struct { values, .. } = lit {
+                values.iter().for_each(|v| collect_columns_exprs(v, out));
+            }
+        }
+        ast::Expr::Array(exprs) | ast::Expr::Tuple(exprs) => {
+            exprs.iter().for_each(|e| collect_columns_exprs(e, out));
+        }
+        ast::Expr::ArraySubscript { expr, subscript } => {
+            collect_columns_exprs(expr, out);
+            match subscript.as_ref() {
+                ast::ArraySubscript::Index(idx) => collect_columns_exprs(idx, out),
+                ast::ArraySubscript::Slice {
+                    lower,
+                    upper,
+                    stride,
+                } => [lower, upper, stride]
+                    .into_iter()
+                    .flatten()
+                    .for_each(|e| collect_columns_exprs(e, out)),
+            }
+        }
+        ast::Expr::UnaryExpr { expr, .. }
+        | ast::Expr::Nested(expr)
+        | ast::Expr::Collate { expr, .. }
+        | ast::Expr::Cast { expr, .. }
+        | ast::Expr::IsNull { expr, .. }
+        | ast::Expr::IsBool { expr, .. }
+        | ast::Expr::Extract { expr, .. }
+        | ast::Expr::AnySubquery { left: expr, .. }
+        | ast::Expr::AllSubquery { left: expr, .. }
+        | ast::Expr::InSubquery { expr, .. } => collect_columns_exprs(expr, out),
+        ast::Expr::Interval(interval) => collect_columns_exprs(&interval.value, out),
+        ast::Expr::BinaryExpr { left, right, .. }
+        | ast::Expr::Like {
+            expr: left,
+            pattern: right,
+            ..
+        } => {
+            collect_columns_exprs(left, out);
+            collect_columns_exprs(right, out);
+        }
+        ast::Expr::Function(func) => {
+            for arg in &func.args {
+                let arg = match arg {
+                    ast::FunctionArg::Named { arg, .. } => arg,
+                    ast::FunctionArg::Unnamed { arg } => arg,
+                };
+                if let ast::FunctionArgExpr::Expr(expr) = arg {
+                    collect_columns_exprs(expr, out);
+                }
+            }
+            if let Some(filter) = &func.filter {
+                collect_columns_exprs(filter, out);
+            }
+        }
+        ast::Expr::InList { expr, list, .. } => {
+            collect_columns_exprs(expr, out);
+            list.iter().for_each(|e| collect_columns_exprs(e, out));
+        }
+        ast::Expr::Between {
+            expr, low, high, ..
+        } => {
+            collect_columns_exprs(expr, out);
+            collect_columns_exprs(low, out);
+            collect_columns_exprs(high, out);
+        }
+        ast::Expr::Case {
+            expr,
+            conditions,
+            results,
+            else_expr,
+        } => {
+            expr.iter()
+                .chain(else_expr.iter())
+                .for_each(|e| collect_columns_exprs(e, out));
+            conditions
+                .iter()
+                .chain(results.iter())
+                .for_each(|e| collect_columns_exprs(e, out));
+        }
+        ast::Expr::Substring { expr, from, count } => {
+            collect_columns_exprs(expr, out);
+            collect_columns_exprs(from, out);
+            if let Some(count) = count {
+                collect_columns_exprs(count, out);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ast::ObjectReference;
@@ -426,4 +604,57 @@ mod tests {
 
         assert_eq!(expected, expanded);
     }
+
+    #[test]
+    fn expand_columns_in_expr() {
+        let mut bind_context = BindContext::new();
+        let table_ref = bind_context
+            .push_table(
+                bind_context.root_scope_ref(),
+                Some(TableAlias {
+                    database: Some("d1".to_string()),
+                    schema: Some("s1".to_string()),
+                    table: "t1".to_string(),
+                }),
+                vec![DataType::Int32, DataType::Int32, DataType::Int32],
+                vec!["a1".to_string(), "a2".to_string(), "b".to_string()],
+            )
+            .unwrap();
+
+        let expander = SelectExprExpander::new(bind_context.root_scope_ref(), &bind_context);
+
+        // COLUMNS('a.*') + 1
+        let expr = ast::Expr::BinaryExpr {
+            left: Box::new(ast::Expr::Columns(ast::ColumnsExpr::Pattern(
+                "a.*".to_string(),
+            ))),
+            op: ast::BinaryOperator::Plus,
+            right: Box::new(ast::Expr::Literal(ast::Literal::Number("1".to_string()))),
+        };
+        let exprs = vec![ast::SelectExpr::Expr(expr.clone())];
+
+        let expected = vec![
+            ExpandedSelectExpr::ColumnsExpr {
+                expr: expr.clone(),
+                column: ColumnExpr {
+                    table_scope: table_ref,
+                    column: 0,
+                },
+                name: "a1".to_string(),
+                alias: None,
+            },
+            ExpandedSelectExpr::ColumnsExpr {
+                expr,
+                column: ColumnExpr {
+                    table_scope: table_ref,
+                    column: 1,
+                },
+                name: "a2".to_string(),
+                alias: None,
+            },
+        ];
+        let expanded = expander.expand_all_select_exprs(exprs).unwrap();
+
+        assert_eq!(expected, expanded);
+    }
 }
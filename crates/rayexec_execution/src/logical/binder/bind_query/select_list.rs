@@ -89,10 +89,14 @@ impl SelectList {
     ///
     /// This will extract aggregates from the list, placing them in their own
     /// table, and add pruning projections if needed.
+    ///
+    /// `has_having` indicates the query has a HAVING clause, making it a
+    /// grouped query even without any aggregates or GROUP BY.
     pub fn finalize(
         mut self,
         bind_context: &mut BindContext,
         mut group_by: Option<&mut BoundGroupBy>,
+        has_having: bool,
     ) -> Result<BoundSelectList> {
         let grouping_functions = self.expressions_to_grouping_functions(&group_by)?;
 
@@ -108,6 +112,7 @@ impl SelectList {
             self.grouping_functions_table,
             &self.aggregates,
             group_by,
+            has_having,
         )?;
 
         // If we had appended column, ensure we have a pruned table that only
@@ -175,24 +180,12 @@ impl SelectList {
         groupings_table: TableRef,
         aggs: &[Expression],
         group_by: Option<&mut BoundGroupBy>,
+        has_having: bool,
     ) -> Result<()> {
-        if aggs.is_empty() && group_by.is_none() {
+        if aggs.is_empty() && group_by.is_none() && !has_having {
             return Ok(());
         }
 
-        fn inner(bind_context: &BindContext, expr: &Expression, refs: &[TableRef]) -> Result<()> {
-            match expr {
-                Expression::Column(col) => {
-                    if !refs.iter().any(|table_ref| &col.table_scope == table_ref) {
-                        let (col_name, _) = bind_context.get_column(col.table_scope, col.column)?;
-                        return Err(RayexecError::new(format!("Column '{col_name}' must appear in the GROUP BY clause or be used in an aggregate function")));
-                    }
-                }
-                other => other.for_each_child(&mut |child| inner(bind_context, child, refs))?,
-            }
-            Ok(())
-        }
-
         match group_by {
             Some(group_by) => {
                 for expr in self.projections.iter().chain(&self.appended) {
@@ -200,7 +193,7 @@ impl SelectList {
                     // - An aggregate
                     // - An expression in the group by
                     // - A GROUPING call
-                    inner(
+                    verify_grouped_column_references(
                         bind_context,
                         expr,
                         &[agg_table, group_by.group_exprs_table, groupings_table],
@@ -209,7 +202,7 @@ impl SelectList {
             }
             None => {
                 for expr in self.projections.iter().chain(&self.appended) {
-                    inner(bind_context, expr, &[agg_table])?
+                    verify_grouped_column_references(bind_context, expr, &[agg_table])?
                 }
             }
         }
@@ -264,6 +257,50 @@ impl SelectList {
         None
     }
 
+    /// Get a copy of the expression for a user-provided alias.
+    ///
+    /// Unlike `column_by_user_alias`, the returned expression can be used as
+    /// part of a larger expression.
+    pub fn expression_by_user_alias(&self, ident: &ast::Ident) -> Result<Option<Expression>> {
+        let idx = match self.alias_map.get(&ident.as_normalized_string()) {
+            Some(idx) => *idx,
+            None => return Ok(None),
+        };
+
+        let expr = self
+            .projections
+            .get(idx)
+            .ok_or_else(|| RayexecError::new(format!("Missing projection for alias '{ident}'")))?;
+
+        // Windows are computed after everything else, we can't use them as
+        // part of other expressions.
+        if expr.get_table_references().contains(&self.windows_table) {
+            return Err(RayexecError::new(format!(
+                "Alias '{ident}' references a window function and cannot be used in an expression"
+            )));
+        }
+
+        Ok(Some(expr.clone()))
+    }
+
+    /// Get the ordinals of projections that don't contain aggregates, windows,
+    /// or GROUPING calls.
+    ///
+    /// Used to expand GROUP BY ALL.
+    pub fn non_aggregate_ordinals(&self) -> Vec<usize> {
+        self.projections
+            .iter()
+            .enumerate()
+            .filter(|(_, expr)| {
+                let refs = expr.get_table_references();
+                !refs.contains(&self.aggregates_table)
+                    && !refs.contains(&self.windows_table)
+                    && !refs.contains(&self.grouping_functions_table)
+            })
+            .map(|(idx, _)| idx + 1)
+            .collect()
+    }
+
     /// Get a column reference by ordinal.
     pub fn column_by_ordinal(
         &self,
@@ -404,3 +441,26 @@ impl SelectList {
         Ok(())
     }
 }
+
+/// Verify that all column references in a grouped expression point to one of
+/// `refs` (aggregates, GROUP BY expressions, or GROUPING calls).
+///
+/// Errors with the name of the first column that doesn't.
+pub fn verify_grouped_column_references(
+    bind_context: &BindContext,
+    expr: &Expression,
+    refs: &[TableRef],
+) -> Result<()> {
+    match expr {
+        Expression::Column(col) => {
+            if !refs.iter().any(|table_ref| &col.table_scope == table_ref) {
+                let (col_name, _) = bind_context.get_column(col.table_scope, col.column)?;
+                return Err(RayexecError::new(format!("Column '{col_name}' must appear in the GROUP BY clause or be used in an aggregate function")));
+            }
+        }
+        other => other.for_each_child(&mut |child| {
+            verify_grouped_column_references(bind_context, child, refs)
+        })?,
+    }
+    Ok(())
+}
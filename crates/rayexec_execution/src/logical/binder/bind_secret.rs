@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_parser::ast;
+
+use super::bind_context::{BindContext, BindScopeRef};
+use super::column_binder::ErroringColumnBinder;
+use super::expr_binder::{BaseExpressionBinder, RecursionContext};
+use crate::database::create::OnConflict;
+use crate::database::secrets::{Secret, SecretType};
+use crate::logical::logical_secret::{LogicalCreateSecret, LogicalDropSecret};
+use crate::logical::operator::{LocationRequirement, Node};
+use crate::logical::resolver::resolve_context::ResolveContext;
+use crate::logical::resolver::ResolvedMeta;
+use crate::logical::statistics::StatisticsValue;
+
+#[derive(Debug)]
+pub struct SecretBinder {
+    pub current: BindScopeRef,
+}
+
+impl SecretBinder {
+    pub fn new(current: BindScopeRef) -> Self {
+        SecretBinder { current }
+    }
+
+    pub fn bind_create_secret(
+        &self,
+        bind_context: &mut BindContext,
+        create: ast::CreateSecret<ResolvedMeta>,
+    ) -> Result<Node<LogicalCreateSecret>> {
+        if create.persistent {
+            not_implemented!("Persistent secrets");
+        }
+
+        let secret_type = SecretType::from_name(&create.secret_type.into_normalized_string())?;
+
+        let mut options = HashMap::new();
+        for (k, v) in create.options {
+            let k = k.into_normalized_string();
+            let expr = BaseExpressionBinder::new(self.current, &ResolveContext::empty())
+                .bind_expression(
+                    bind_context,
+                    &v,
+                    &mut ErroringColumnBinder,
+                    RecursionContext {
+                        allow_windows: false,
+                        allow_aggregates: false,
+                        is_root: true,
+                    },
+                )?;
+            let v = expr.try_into_scalar()?;
+
+            if options.contains_key(&k) {
+                return Err(RayexecError::new(format!(
+                    "Option '{k}' provided more than once"
+                )));
+            }
+            options.insert(k, v);
+        }
+
+        let on_conflict = if create.or_replace {
+            OnConflict::Replace
+        } else if create.if_not_exists {
+            OnConflict::Ignore
+        } else {
+            OnConflict::Error
+        };
+
+        // Secrets are session state, always handled on the client.
+        Ok(Node {
+            node: LogicalCreateSecret {
+                name: create.name.into_normalized_string(),
+                secret: Secret::try_new(secret_type, options)?,
+                on_conflict,
+            },
+            location: LocationRequirement::ClientLocal,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+
+    pub fn bind_drop_secret(
+        &self,
+        _bind_context: &mut BindContext,
+        mut drop: ast::DropStatement<ResolvedMeta>,
+    ) -> Result<Node<LogicalDropSecret>> {
+        if drop.name.0.len() != 1 {
+            return Err(RayexecError::new(format!(
+                "Expected a single identifier for secret name, got '{}'",
+                drop.name
+            )));
+        }
+
+        Ok(Node {
+            node: LogicalDropSecret {
+                name: drop.name.pop()?,
+                if_exists: drop.if_exists,
+            },
+            location: LocationRequirement::ClientLocal,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+}
@@ -47,7 +47,11 @@ impl<'a> SetVarBinder<'a> {
         let _ = self.config.get_as_scalar(&name)?;
 
         Ok(Node {
-            node: LogicalSetVar { name, value },
+            node: LogicalSetVar {
+                name,
+                value,
+                local: set.local,
+            },
             location: LocationRequirement::ClientLocal,
             children: Vec::new(),
             estimated_cardinality: StatisticsValue::Unknown,
@@ -79,15 +83,42 @@ impl<'a> SetVarBinder<'a> {
     pub fn bind_show(
         &self,
         bind_context: &mut BindContext,
-        mut show: ast::Show<ResolvedMeta>,
+        show: ast::Show<ResolvedMeta>,
     ) -> Result<Node<LogicalShowVar>> {
-        let name = show.reference.pop()?; // TODO: Allow compound references?
-        let var = self.config.get_as_scalar(&name)?;
+        let node = match show.reference {
+            ast::VariableOrAll::Variable(mut v) => {
+                let name = v.pop()?; // TODO: Allow compound references?
+                let value = self.config.get_as_scalar(&name)?;
 
-        bind_context.push_table(self.current, None, vec![DataType::Utf8], vec![name.clone()])?;
+                bind_context.push_table(
+                    self.current,
+                    None,
+                    vec![DataType::Utf8],
+                    vec![name.clone()],
+                )?;
+
+                LogicalShowVar::Variable { name, value }
+            }
+            ast::VariableOrAll::All => {
+                bind_context.push_table(
+                    self.current,
+                    None,
+                    vec![DataType::Utf8, DataType::Utf8, DataType::Utf8],
+                    vec![
+                        "name".to_string(),
+                        "value".to_string(),
+                        "description".to_string(),
+                    ],
+                )?;
+
+                LogicalShowVar::All {
+                    settings: self.config.all_settings(),
+                }
+            }
+        };
 
         Ok(Node {
-            node: LogicalShowVar { name, value: var },
+            node,
             location: LocationRequirement::ClientLocal, // Technically could be any since the variable is copied.
             children: Vec::new(),
             estimated_cardinality: StatisticsValue::Unknown,
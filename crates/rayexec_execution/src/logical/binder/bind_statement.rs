@@ -1,9 +1,13 @@
-use rayexec_error::Result;
+use rayexec_error::{RayexecError, Result};
+use rayexec_parser::ast;
 use rayexec_parser::statement::Statement;
 
+use super::bind_alter_table::AlterTableBinder;
 use super::bind_attach::{AttachBinder, BoundAttach, BoundDetach};
 use super::bind_context::BindContext;
 use super::bind_copy::{BoundCopyTo, CopyBinder};
+use super::bind_create_function::CreateFunctionBinder;
+use super::bind_create_macro::CreateMacroBinder;
 use super::bind_create_schema::CreateSchemaBinder;
 use super::bind_create_table::{BoundCreateTable, CreateTableBinder};
 use super::bind_create_view::CreateViewBinder;
@@ -12,16 +16,28 @@ use super::bind_drop::DropBinder;
 use super::bind_explain::{BoundExplain, ExplainBinder};
 use super::bind_insert::{BoundInsert, InsertBinder};
 use super::bind_query::BoundQuery;
+use super::bind_secret::SecretBinder;
 use super::bind_set::SetVarBinder;
+use super::bind_summarize::{BoundSummarize, SummarizeBinder};
 use crate::config::session::SessionConfig;
+use crate::config::statements::AllowedStatements;
 use crate::logical::binder::bind_query::QueryBinder;
-use crate::logical::logical_create::{LogicalCreateSchema, LogicalCreateView};
+use crate::logical::logical_alter::LogicalAlterTable;
+use crate::logical::logical_create::{
+    LogicalCreateFunction,
+    LogicalCreateMacro,
+    LogicalCreateSchema,
+    LogicalCreateView,
+};
 use crate::logical::logical_describe::LogicalDescribe;
 use crate::logical::logical_drop::LogicalDrop;
+use crate::logical::logical_secret::{LogicalCreateSecret, LogicalDropSecret};
 use crate::logical::logical_set::{LogicalResetVar, LogicalSetVar, LogicalShowVar};
-use crate::logical::operator::Node;
+use crate::logical::logical_transaction::LogicalTransaction;
+use crate::logical::operator::{LocationRequirement, Node};
 use crate::logical::resolver::resolve_context::ResolveContext;
 use crate::logical::resolver::ResolvedMeta;
+use crate::logical::statistics::StatisticsValue;
 
 /// "Bound" variants for SQL statements that we support.
 ///
@@ -36,15 +52,22 @@ pub enum BoundStatement {
     Query(BoundQuery),
     SetVar(Node<LogicalSetVar>),
     ResetVar(Node<LogicalResetVar>),
+    Transaction(Node<LogicalTransaction>),
     ShowVar(Node<LogicalShowVar>),
     Attach(BoundAttach),
     Detach(BoundDetach),
     Drop(Node<LogicalDrop>),
+    AlterTable(Node<LogicalAlterTable>),
+    CreateSecret(Node<LogicalCreateSecret>),
+    DropSecret(Node<LogicalDropSecret>),
     Insert(BoundInsert),
     CreateSchema(Node<LogicalCreateSchema>),
     CreateTable(BoundCreateTable),
     CreateView(Node<LogicalCreateView>),
+    CreateFunction(Node<LogicalCreateFunction>),
+    CreateMacro(Node<LogicalCreateMacro>),
     Describe(Node<LogicalDescribe>),
+    Summarize(BoundSummarize),
     Explain(BoundExplain),
     CopyTo(BoundCopyTo),
 }
@@ -53,6 +76,8 @@ pub enum BoundStatement {
 pub struct StatementBinder<'a> {
     pub session_config: &'a SessionConfig,
     pub resolve_context: &'a ResolveContext,
+    /// Statements that are allowed to be bound, anything else errors.
+    pub allowed_statements: &'a AllowedStatements,
 }
 
 impl StatementBinder<'_> {
@@ -60,6 +85,8 @@ impl StatementBinder<'_> {
         &self,
         statement: Statement<ResolvedMeta>,
     ) -> Result<(BoundStatement, BindContext)> {
+        self.allowed_statements.check_statement(&statement)?;
+
         let mut context = BindContext::new();
         let root_scope = context.root_scope_ref();
 
@@ -80,15 +107,51 @@ impl StatementBinder<'_> {
             Statement::ResetVariable(set) => BoundStatement::ResetVar(
                 SetVarBinder::new(root_scope, self.session_config).bind_reset(&mut context, set)?,
             ),
+            Statement::Transaction(tx) => BoundStatement::Transaction(Node {
+                node: match tx {
+                    ast::TransactionStatement::Begin => LogicalTransaction::Begin,
+                    ast::TransactionStatement::Commit => LogicalTransaction::Commit,
+                    ast::TransactionStatement::Rollback => LogicalTransaction::Rollback,
+                },
+                location: LocationRequirement::ClientLocal,
+                children: Vec::new(),
+                estimated_cardinality: StatisticsValue::Unknown,
+            }),
             Statement::Attach(attach) => BoundStatement::Attach(
                 AttachBinder::new(root_scope).bind_attach(&mut context, attach)?,
             ),
             Statement::Detach(detach) => BoundStatement::Detach(
                 AttachBinder::new(root_scope).bind_detach(&mut context, detach)?,
             ),
+            Statement::Drop(drop) if drop.drop_type == ast::DropType::Secret => {
+                BoundStatement::DropSecret(
+                    SecretBinder::new(root_scope).bind_drop_secret(&mut context, drop)?,
+                )
+            }
             Statement::Drop(drop) => {
                 BoundStatement::Drop(DropBinder::new(root_scope).bind_drop(&mut context, drop)?)
             }
+            Statement::AlterTable(alter) => BoundStatement::AlterTable(
+                AlterTableBinder::new(root_scope, self.resolve_context)
+                    .bind_alter_table(&mut context, alter)?,
+            ),
+            Statement::CreateIndex(create) => BoundStatement::AlterTable(
+                AlterTableBinder::new(root_scope, self.resolve_context)
+                    .bind_create_index(&mut context, create)?,
+            ),
+            Statement::CreatePolicy(create) => {
+                // Policies are the thing restricting what a role can see, so
+                // don't let a restricted session change them.
+                if !self.session_config.role.is_empty() {
+                    return Err(RayexecError::new(
+                        "Cannot create policies while the session has a role",
+                    ));
+                }
+                BoundStatement::AlterTable(
+                    AlterTableBinder::new(root_scope, self.resolve_context)
+                        .bind_create_policy(&mut context, create)?,
+                )
+            }
             Statement::Insert(insert) => BoundStatement::Insert(
                 InsertBinder::new(root_scope, self.resolve_context)
                     .bind_insert(&mut context, insert)?,
@@ -104,10 +167,23 @@ impl StatementBinder<'_> {
                 CreateViewBinder::new(root_scope, self.resolve_context)
                     .bind_create_view(&mut context, create)?,
             ),
+            Statement::CreateSecret(create) => BoundStatement::CreateSecret(
+                SecretBinder::new(root_scope).bind_create_secret(&mut context, create)?,
+            ),
+            Statement::CreateFunction(create) => BoundStatement::CreateFunction(
+                CreateFunctionBinder::new(root_scope).bind_create_function(&mut context, create)?,
+            ),
+            Statement::CreateMacro(create) => BoundStatement::CreateMacro(
+                CreateMacroBinder::new(root_scope).bind_create_macro(&mut context, create)?,
+            ),
             Statement::Describe(describe) => BoundStatement::Describe(
                 DescribeBinder::new(root_scope, self.resolve_context)
                     .bind_describe(&mut context, describe)?,
             ),
+            Statement::Summarize(summarize) => BoundStatement::Summarize(
+                SummarizeBinder::new(root_scope, self.resolve_context)
+                    .bind_summarize(&mut context, summarize)?,
+            ),
             Statement::Explain(explain) => BoundStatement::Explain(
                 ExplainBinder::new(root_scope, self.resolve_context)
                     .bind_explain(&mut context, explain)?,
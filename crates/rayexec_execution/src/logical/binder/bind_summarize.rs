@@ -0,0 +1,260 @@
+use rayexec_error::{RayexecError, Result};
+use rayexec_parser::ast;
+
+use super::bind_context::{BindContext, BindScopeRef};
+use super::bind_query::bind_from::{BoundFrom, FromBinder};
+use super::bind_query::{BoundQuery, QueryBinder};
+use super::table_list::TableRef;
+use crate::arrays::datatype::DataType;
+use crate::arrays::scalar::ScalarValue;
+use crate::expr::aggregate_expr::AggregateExpr;
+use crate::expr::case_expr::{CaseExpr, WhenThen};
+use crate::expr::scalar_function_expr::ScalarFunctionExpr;
+use crate::expr::unnest_expr::UnnestExpr;
+use crate::expr::{self, Expression};
+use crate::functions::aggregate::builtin::avg::Avg;
+use crate::functions::aggregate::builtin::count::Count;
+use crate::functions::aggregate::builtin::minmax::{Max, Min};
+use crate::functions::aggregate::builtin::sketch::ApproxCountDistinct;
+use crate::functions::aggregate::{AggregateFunction, PlannedAggregateFunction};
+use crate::functions::scalar::builtin::is::IsNull;
+use crate::functions::scalar::builtin::list::ListValues;
+use crate::functions::scalar::ScalarFunction;
+use crate::logical::resolver::resolve_context::ResolveContext;
+use crate::logical::resolver::ResolvedMeta;
+
+#[derive(Debug)]
+pub enum BoundSummarizeSource {
+    Query(Box<BoundQuery>),
+    Table(Box<BoundFrom>),
+}
+
+/// A bound SUMMARIZE.
+///
+/// All statistics are computed with a single ungrouped aggregate over the
+/// source, with the projections unnesting the aggregate results into one row
+/// per source column.
+#[derive(Debug)]
+pub struct BoundSummarize {
+    pub source: BoundSummarizeSource,
+    /// Table ref for the aggregates output.
+    pub aggregates_table: TableRef,
+    /// Aggregates computed over the source.
+    pub aggregates: Vec<Expression>,
+    /// Table ref for the final output.
+    pub projection_table: TableRef,
+    /// Projections containing UNNEST expressions over lists of aggregate
+    /// results.
+    pub projections: Vec<Expression>,
+}
+
+#[derive(Debug)]
+pub struct SummarizeBinder<'a> {
+    pub current: BindScopeRef,
+    pub resolve_context: &'a ResolveContext,
+}
+
+impl<'a> SummarizeBinder<'a> {
+    pub fn new(current: BindScopeRef, resolve_context: &'a ResolveContext) -> Self {
+        SummarizeBinder {
+            current,
+            resolve_context,
+        }
+    }
+
+    pub fn bind_summarize(
+        &self,
+        bind_context: &mut BindContext,
+        summarize: ast::Summarize<ResolvedMeta>,
+    ) -> Result<BoundSummarize> {
+        let projection_table = bind_context.push_table(
+            self.current,
+            None,
+            vec![
+                DataType::Utf8,
+                DataType::Utf8,
+                DataType::Utf8,
+                DataType::Utf8,
+                DataType::Int64,
+                DataType::Float64,
+                DataType::Int64,
+                DataType::Float64,
+            ],
+            vec![
+                "column_name".to_string(),
+                "column_type".to_string(),
+                "min".to_string(),
+                "max".to_string(),
+                "approx_unique".to_string(),
+                "avg".to_string(),
+                "count".to_string(),
+                "null_percentage".to_string(),
+            ],
+        )?;
+
+        let source_scope = bind_context.new_orphan_scope();
+        let source = match summarize {
+            ast::Summarize::Query(query) => BoundSummarizeSource::Query(Box::new(
+                QueryBinder::new(source_scope, self.resolve_context).bind(bind_context, query)?,
+            )),
+            ast::Summarize::FromNode(from) => BoundSummarizeSource::Table(Box::new(
+                FromBinder::new(source_scope, self.resolve_context)
+                    .bind(bind_context, Some(from))?,
+            )),
+        };
+
+        let columns: Vec<_> = bind_context
+            .iter_tables_in_scope(source_scope)?
+            .flat_map(|t| {
+                t.column_names.iter().zip(&t.column_types).enumerate().map(
+                    |(idx, (name, datatype))| (t.reference, idx, name.clone(), datatype.clone()),
+                )
+            })
+            .collect();
+
+        if columns.is_empty() {
+            return Err(RayexecError::new(
+                "Cannot summarize a source with no columns",
+            ));
+        }
+
+        let mut aggs = SummarizeAggregates {
+            table: bind_context.new_ephemeral_table()?,
+            aggregates: Vec::new(),
+        };
+
+        let count = aggs.push(bind_context, Count.count_star())?;
+
+        let mut names = Vec::with_capacity(columns.len());
+        let mut types = Vec::with_capacity(columns.len());
+        let mut mins = Vec::with_capacity(columns.len());
+        let mut maxes = Vec::with_capacity(columns.len());
+        let mut distincts = Vec::with_capacity(columns.len());
+        let mut avgs = Vec::with_capacity(columns.len());
+        let mut counts = Vec::with_capacity(columns.len());
+        let mut null_percentages = Vec::with_capacity(columns.len());
+
+        for (table_ref, idx, name, datatype) in columns {
+            let column = expr::col_ref(table_ref, idx);
+
+            names.push(expr::lit(name));
+            types.push(expr::lit(datatype.to_string()));
+            counts.push(count.clone());
+
+            if supports_min_max(&datatype) {
+                let min = Min.plan(bind_context.get_table_list(), vec![column.clone()])?;
+                let max = Max.plan(bind_context.get_table_list(), vec![column.clone()])?;
+                mins.push(expr::cast(aggs.push(bind_context, min)?, DataType::Utf8));
+                maxes.push(expr::cast(aggs.push(bind_context, max)?, DataType::Utf8));
+            } else {
+                mins.push(expr::cast(expr::lit(ScalarValue::Null), DataType::Utf8));
+                maxes.push(expr::cast(expr::lit(ScalarValue::Null), DataType::Utf8));
+            }
+
+            let avg_input = if datatype.is_decimal() {
+                Some(column.clone())
+            } else if datatype.is_primitive_numeric() {
+                Some(expr::cast(column.clone(), DataType::Float64))
+            } else {
+                None
+            };
+            match avg_input {
+                Some(input) => {
+                    let avg = Avg.plan(bind_context.get_table_list(), vec![input])?;
+                    avgs.push(aggs.push(bind_context, avg)?);
+                }
+                None => avgs.push(expr::cast(expr::lit(ScalarValue::Null), DataType::Float64)),
+            }
+
+            let distinct =
+                ApproxCountDistinct.plan(bind_context.get_table_list(), vec![column.clone()])?;
+            distincts.push(aggs.push(bind_context, distinct)?);
+
+            // Average of 100 for every NULL and 0 for everything else.
+            let is_null = Expression::Case(CaseExpr {
+                cases: vec![WhenThen {
+                    when: Expression::ScalarFunction(ScalarFunctionExpr {
+                        function: IsNull.plan(bind_context.get_table_list(), vec![column])?,
+                    }),
+                    then: expr::lit(100.0_f64),
+                }],
+                else_expr: Some(Box::new(expr::lit(0.0_f64))),
+            });
+            let null_percentage = Avg.plan(bind_context.get_table_list(), vec![is_null])?;
+            null_percentages.push(aggs.push(bind_context, null_percentage)?);
+        }
+
+        let projections = [
+            names,
+            types,
+            mins,
+            maxes,
+            distincts,
+            avgs,
+            counts,
+            null_percentages,
+        ]
+        .into_iter()
+        .map(|values| {
+            let list = ListValues.plan(bind_context.get_table_list(), values)?;
+            Ok(Expression::Unnest(UnnestExpr {
+                expr: Box::new(Expression::ScalarFunction(ScalarFunctionExpr {
+                    function: list,
+                })),
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+        Ok(BoundSummarize {
+            source,
+            aggregates_table: aggs.table,
+            aggregates: aggs.aggregates,
+            projection_table,
+            projections,
+        })
+    }
+}
+
+/// Aggregates being computed for a SUMMARIZE.
+#[derive(Debug)]
+struct SummarizeAggregates {
+    table: TableRef,
+    aggregates: Vec<Expression>,
+}
+
+impl SummarizeAggregates {
+    /// Add an aggregate, returning a column expression referencing its
+    /// output.
+    fn push(
+        &mut self,
+        bind_context: &mut BindContext,
+        agg: PlannedAggregateFunction,
+    ) -> Result<Expression> {
+        let col_idx = bind_context.push_column_for_table(
+            self.table,
+            "__generated_agg_ref",
+            agg.return_type.clone(),
+        )?;
+        self.aggregates.push(Expression::Aggregate(AggregateExpr {
+            agg,
+            filter: None,
+            distinct: false,
+            order_by: Vec::new(),
+        }));
+
+        Ok(expr::col_ref(self.table, col_idx))
+    }
+}
+
+/// If min and max should be computed for a column of this type.
+///
+/// Min and max are displayed as strings, so this is limited to types with a
+/// meaningful ordering that can be cast to text.
+fn supports_min_max(datatype: &DataType) -> bool {
+    datatype.is_primitive_numeric()
+        || datatype.is_decimal()
+        || matches!(
+            datatype,
+            DataType::Boolean | DataType::Utf8 | DataType::Timestamp(_)
+        )
+}
@@ -43,6 +43,14 @@ pub trait ExpressionColumnBinder {
         idents: &[ast::Ident],
         recur: RecursionContext,
     ) -> Result<Option<Expression>>;
+
+    /// Try to bind a COLUMNS expression.
+    ///
+    /// COLUMNS expressions are expanded in the select list, so only the select
+    /// list binder should be able to bind these.
+    fn bind_from_columns(&mut self, _columns: &ast::ColumnsExpr) -> Result<Option<Expression>> {
+        Ok(None)
+    }
 }
 
 /// Default column binder.
@@ -1,17 +1,20 @@
 use fmtutil::IntoDisplayableSlice;
-use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_error::{not_implemented, ErrorKind, RayexecError, Result};
 use rayexec_parser::ast::{self, QueryNode};
 
 use super::bind_context::{BindContext, BindScopeRef};
 use super::column_binder::ExpressionColumnBinder;
-use crate::arrays::datatype::DataType;
+use crate::arrays::datatype::{DataType, DataTypeId, DecimalTypeMeta};
+use crate::arrays::scalar::decimal::{Decimal128Type, Decimal64Type, DecimalType};
 use crate::arrays::scalar::interval::Interval;
 use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::database::suggest::{similar_names, with_suggestions};
 use crate::expr::aggregate_expr::AggregateExpr;
 use crate::expr::arith_expr::{ArithExpr, ArithOperator};
 use crate::expr::case_expr::{CaseExpr, WhenThen};
 use crate::expr::cast_expr::CastExpr;
 use crate::expr::comparison_expr::{ComparisonExpr, ComparisonOperator};
+use crate::expr::conditional_expr::{ConditionalExpr, ConditionalOperator};
 use crate::expr::conjunction_expr::{ConjunctionExpr, ConjunctionOperator};
 use crate::expr::grouping_set_expr::GroupingSetExpr;
 use crate::expr::literal_expr::LiteralExpr;
@@ -22,18 +25,31 @@ use crate::expr::unnest_expr::UnnestExpr;
 use crate::expr::window_expr::{WindowExpr, WindowFrameBound, WindowFrameExclusion};
 use crate::expr::{AsScalarFunction, Expression};
 use crate::functions::aggregate::AggregateFunction;
+use crate::functions::implicit::common_supertype;
 use crate::functions::scalar::builtin::datetime::DatePart;
 use crate::functions::scalar::builtin::is;
+use crate::functions::scalar::builtin::json::{JsonExtract, JsonExtractString};
 use crate::functions::scalar::builtin::list::{ListExtract, ListValues};
-use crate::functions::scalar::builtin::string::{Concat, Like, StartsWith, Substring};
+use crate::functions::scalar::builtin::string::{
+    Collate,
+    Collation,
+    Concat,
+    Like,
+    StartsWith,
+    Substring,
+};
+use crate::functions::scalar::builtin::text_search::Matches;
 use crate::functions::scalar::ScalarFunction;
 use crate::functions::table::TableFunction;
-use crate::functions::CastType;
+use crate::functions::{no_matching_signature_error, CastType, FunctionInfo};
 use crate::logical::binder::bind_query::bind_modifier::BoundOrderByExpr;
 use crate::logical::binder::bind_query::QueryBinder;
+use crate::logical::binder::table_list::TableList;
 use crate::logical::resolver::resolve_context::ResolveContext;
 use crate::logical::resolver::resolved_function::{ResolvedFunction, SpecialBuiltinFunction};
 use crate::logical::resolver::ResolvedMeta;
+use crate::optimizer::expr_rewrite::const_fold::ConstFold;
+use crate::optimizer::expr_rewrite::ExpressionRewriteRule;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RecursionContext {
@@ -81,6 +97,21 @@ impl<'a> BaseExpressionBinder<'a> {
             .collect::<Result<Vec<_>>>()
     }
 
+    /// Get names of columns in the current scope that are similar to `name`
+    /// for use in error messages.
+    fn similar_columns(&self, bind_context: &BindContext, name: &str) -> Vec<String> {
+        let tables = match bind_context.iter_tables_in_scope(self.current) {
+            Ok(tables) => tables,
+            Err(_) => return Vec::new(),
+        };
+
+        let columns = tables.flat_map(|table| table.column_names.iter().map(|c| c.as_str()));
+        similar_names(name, columns)
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect()
+    }
+
     pub fn bind_expression(
         &self,
         bind_context: &mut BindContext,
@@ -95,9 +126,16 @@ impl<'a> BaseExpressionBinder<'a> {
                 // Use the provided column binder, no fallback.
                 match column_binder.bind_from_ident(self.current, bind_context, ident, recur)? {
                     Some(expr) => Ok(expr),
-                    None => Err(RayexecError::new(format!(
-                        "Missing column for reference: {ident}",
-                    ))),
+                    None => {
+                        let similar =
+                            self.similar_columns(bind_context, &ident.as_normalized_string());
+                        Err(RayexecError::new(with_suggestions(
+                            format!("Missing column for reference: {ident}"),
+                            &similar,
+                        ))
+                        .with_kind(ErrorKind::ColumnNotFound)
+                        .with_span(ident.span()))
+                    }
                 }
             }
             ast::Expr::CompoundIdent(idents) => {
@@ -110,9 +148,25 @@ impl<'a> BaseExpressionBinder<'a> {
                             .map(|i| i.as_normalized_string())
                             .collect::<Vec<_>>()
                             .join(".");
-                        Err(RayexecError::new(format!(
-                            "Missing column for reference: {ident_string}",
-                        )))
+                        let span = match (idents.first(), idents.last()) {
+                            (Some(first), Some(last)) => first
+                                .span()
+                                .zip(last.span())
+                                .map(|(first, last)| first.merge(last)),
+                            _ => None,
+                        };
+                        let similar = match idents.last() {
+                            Some(last) => {
+                                self.similar_columns(bind_context, &last.as_normalized_string())
+                            }
+                            None => Vec::new(),
+                        };
+                        Err(RayexecError::new(with_suggestions(
+                            format!("Missing column for reference: {ident_string}"),
+                            &similar,
+                        ))
+                        .with_kind(ErrorKind::ColumnNotFound)
+                        .with_span(span))
                     }
                 }
             }
@@ -263,6 +317,7 @@ impl<'a> BaseExpressionBinder<'a> {
                 Ok(match op {
                     ast::BinaryOperator::NotEq => {
                         let op = ComparisonOperator::NotEq;
+                        let [left, right] = self.apply_collation(bind_context, [left, right])?;
                         let [left, right] =
                             self.apply_cast_for_operator(bind_context, op, [left, right])?;
                         Expression::Comparison(ComparisonExpr {
@@ -273,6 +328,7 @@ impl<'a> BaseExpressionBinder<'a> {
                     }
                     ast::BinaryOperator::Eq => {
                         let op = ComparisonOperator::Eq;
+                        let [left, right] = self.apply_collation(bind_context, [left, right])?;
                         let [left, right] =
                             self.apply_cast_for_operator(bind_context, op, [left, right])?;
                         Expression::Comparison(ComparisonExpr {
@@ -283,6 +339,7 @@ impl<'a> BaseExpressionBinder<'a> {
                     }
                     ast::BinaryOperator::Lt => {
                         let op = ComparisonOperator::Lt;
+                        let [left, right] = self.apply_collation(bind_context, [left, right])?;
                         let [left, right] =
                             self.apply_cast_for_operator(bind_context, op, [left, right])?;
                         Expression::Comparison(ComparisonExpr {
@@ -293,6 +350,7 @@ impl<'a> BaseExpressionBinder<'a> {
                     }
                     ast::BinaryOperator::LtEq => {
                         let op = ComparisonOperator::LtEq;
+                        let [left, right] = self.apply_collation(bind_context, [left, right])?;
                         let [left, right] =
                             self.apply_cast_for_operator(bind_context, op, [left, right])?;
                         Expression::Comparison(ComparisonExpr {
@@ -303,6 +361,7 @@ impl<'a> BaseExpressionBinder<'a> {
                     }
                     ast::BinaryOperator::Gt => {
                         let op = ComparisonOperator::Gt;
+                        let [left, right] = self.apply_collation(bind_context, [left, right])?;
                         let [left, right] =
                             self.apply_cast_for_operator(bind_context, op, [left, right])?;
                         Expression::Comparison(ComparisonExpr {
@@ -313,6 +372,7 @@ impl<'a> BaseExpressionBinder<'a> {
                     }
                     ast::BinaryOperator::GtEq => {
                         let op = ComparisonOperator::GtEq;
+                        let [left, right] = self.apply_collation(bind_context, [left, right])?;
                         let [left, right] =
                             self.apply_cast_for_operator(bind_context, op, [left, right])?;
                         Expression::Comparison(ComparisonExpr {
@@ -403,6 +463,30 @@ impl<'a> BaseExpressionBinder<'a> {
                             StartsWith.plan(bind_context.get_table_list(), vec![left, right])?;
                         Expression::ScalarFunction(ScalarFunctionExpr { function: planned })
                     }
+                    ast::BinaryOperator::JsonExtract => {
+                        let [left, right] =
+                            self.apply_cast_for_operator(bind_context, JsonExtract, [left, right])?;
+                        let planned =
+                            JsonExtract.plan(bind_context.get_table_list(), vec![left, right])?;
+                        Expression::ScalarFunction(ScalarFunctionExpr { function: planned })
+                    }
+                    ast::BinaryOperator::JsonExtractText => {
+                        let [left, right] = self.apply_cast_for_operator(
+                            bind_context,
+                            JsonExtractString,
+                            [left, right],
+                        )?;
+                        let planned = JsonExtractString
+                            .plan(bind_context.get_table_list(), vec![left, right])?;
+                        Expression::ScalarFunction(ScalarFunctionExpr { function: planned })
+                    }
+                    ast::BinaryOperator::Match => {
+                        let [left, right] =
+                            self.apply_cast_for_operator(bind_context, Matches, [left, right])?;
+                        let planned =
+                            Matches.plan(bind_context.get_table_list(), vec![left, right])?;
+                        Expression::ScalarFunction(ScalarFunctionExpr { function: planned })
+                    }
                     other => not_implemented!("binary operator {other:?}"),
                 })
             }
@@ -422,7 +506,20 @@ impl<'a> BaseExpressionBinder<'a> {
                 self.bind_subquery(bind_context, subquery, SubqueryType::Scalar)
             }
             ast::Expr::Tuple(_) => not_implemented!("tuple expressions"),
-            ast::Expr::Collate { .. } => not_implemented!("COLLATE"),
+            ast::Expr::Collate { expr, collation } => {
+                let expr = self.bind_expression(
+                    bind_context,
+                    expr,
+                    column_binder,
+                    RecursionContext {
+                        is_root: false,
+                        ..recur
+                    },
+                )?;
+                let collation = Collation::from_name(&collation.base()?.as_normalized_string())?;
+
+                self.collate_expression(bind_context, expr, collation)
+            }
             ast::Expr::Exists {
                 subquery,
                 not_exists,
@@ -464,6 +561,7 @@ impl<'a> BaseExpressionBinder<'a> {
                         let typ = SubqueryType::Any {
                             expr: Box::new(bound_expr),
                             op,
+                            cast_output: None,
                         };
                         self.bind_subquery(bind_context, right, typ)
                     }
@@ -473,6 +571,7 @@ impl<'a> BaseExpressionBinder<'a> {
                         let typ = SubqueryType::Any {
                             expr: Box::new(bound_expr),
                             op: op.negate(),
+                            cast_output: None,
                         };
                         let subquery = self.bind_subquery(bind_context, right, typ)?;
 
@@ -505,6 +604,7 @@ impl<'a> BaseExpressionBinder<'a> {
                     SubqueryType::Any {
                         expr: Box::new(bound_expr),
                         op: ComparisonOperator::Eq,
+                        cast_output: None,
                     },
                 )?;
 
@@ -542,6 +642,47 @@ impl<'a> BaseExpressionBinder<'a> {
                     },
                 )?;
 
+                // Cast the needle and all list values to a common type up front
+                // instead of pairwise, so every comparison sees the same
+                // needle.
+                let mut needle = needle;
+                let mut collated_list = Vec::with_capacity(list.len());
+                for expr in list {
+                    let [collated_needle, expr] =
+                        self.apply_collation(bind_context, [needle, expr])?;
+                    needle = collated_needle;
+                    collated_list.push(expr);
+                }
+
+                let table_list = bind_context.get_table_list();
+                let mut exprs = Vec::with_capacity(collated_list.len() + 1);
+                exprs.push(needle);
+                exprs.extend(collated_list);
+                let datatypes = exprs
+                    .iter()
+                    .map(|expr| expr.datatype(table_list))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let common = Self::common_datatype(&datatypes)?.ok_or_else(|| {
+                    RayexecError::new(format!(
+                        "Cannot find a common type for IN list: {}",
+                        datatypes.display_with_brackets()
+                    ))
+                })?;
+
+                let mut exprs = exprs.into_iter().zip(datatypes).map(|(expr, typ)| {
+                    if typ == common {
+                        expr
+                    } else {
+                        Expression::Cast(CastExpr {
+                            to: common.clone(),
+                            expr: Box::new(expr),
+                        })
+                    }
+                });
+                let needle = exprs.next().expect("needle to exist");
+                let list: Vec<_> = exprs.collect();
+
                 // 'IN (..)' => '(needle = a OR needle = b ...))'
                 // 'NOT IN (..)' => '(needle <> a AND needle <> b ...))'
                 let (conj_op, cmp_op) = if !negated {
@@ -785,6 +926,9 @@ impl<'a> BaseExpressionBinder<'a> {
                 let low = bind(low)?;
                 let high = bind(high)?;
 
+                let [expr, low] = self.apply_collation(bind_context, [expr, low])?;
+                let [expr, high] = self.apply_collation(bind_context, [expr, high])?;
+
                 // c1 BETWEEN a AND b
                 // c1 >= a AND c1 <= b
                 //
@@ -970,18 +1114,12 @@ impl<'a> BaseExpressionBinder<'a> {
 
                 Ok(Expression::ScalarFunction(ScalarFunctionExpr { function }))
             }
-            ast::Expr::Columns(_) => {
-                // TODO: This doens't need to be the case, but there's going to
-                // be slightly different handling if this is a top-level select
-                // expression, and argument to a function, or used elsewhere in
-                // the query.
-                //
-                // Currently we're just going to support top-level select
-                // expressions.
-                Err(RayexecError::new(
-                    "COLUMNS expression should have been handle in select list expander",
-                ))
-            }
+            ast::Expr::Columns(columns) => match column_binder.bind_from_columns(columns)? {
+                Some(expr) => Ok(expr),
+                None => Err(RayexecError::new(
+                    "COLUMNS expression can only be used in the SELECT list",
+                )),
+            },
         }
     }
 
@@ -1019,20 +1157,36 @@ impl<'a> BaseExpressionBinder<'a> {
             )));
         }
 
-        // Apply cast to expression to try to match the output of the subquery
-        // if needed.
+        // Cast the expression and the subquery output to a common type so the
+        // comparison is valid.
         let subquery_type = match subquery_type {
-            SubqueryType::Any { expr, op } => {
-                if expr.datatype(bind_context.get_table_list())? != return_type {
-                    SubqueryType::Any {
-                        expr: Box::new(Expression::Cast(CastExpr {
-                            to: query_return_type,
-                            expr,
-                        })),
-                        op,
-                    }
+            SubqueryType::Any { expr, op, .. } => {
+                let table_list = bind_context.get_table_list();
+                let expr_type = expr.datatype(table_list)?;
+                let datatypes = [expr_type.clone(), query_return_type.clone()];
+                let common = Self::common_datatype(&datatypes)?.ok_or_else(|| {
+                    RayexecError::new(format!(
+                        "Cannot compare {expr_type} with subquery returning {query_return_type}"
+                    ))
+                })?;
+
+                let expr = if expr_type == common {
+                    expr
                 } else {
-                    SubqueryType::Any { expr, op }
+                    Box::new(Expression::Cast(CastExpr {
+                        to: common.clone(),
+                        expr,
+                    }))
+                };
+
+                // Make sure the values can actually be compared.
+                let compare_inputs = vec![expr.as_ref().clone(), expr.as_ref().clone()];
+                let _ = op.as_scalar_function().plan(table_list, compare_inputs)?;
+
+                SubqueryType::Any {
+                    expr,
+                    op,
+                    cast_output: (query_return_type != common).then_some(common),
                 }
             }
             other => other,
@@ -1110,6 +1264,19 @@ impl<'a> BaseExpressionBinder<'a> {
             .functions
             .try_get_bound(func.reference)?;
 
+        if !reference.0.is_aggregate() {
+            if func.within_group.is_some() {
+                return Err(RayexecError::new(
+                    "WITHIN GROUP only supported for aggregate functions",
+                ));
+            }
+            if !func.order_by.is_empty() {
+                return Err(RayexecError::new(
+                    "ORDER BY in function arguments only supported for aggregate functions",
+                ));
+            }
+        }
+
         let recur = if reference.0.is_aggregate() {
             RecursionContext {
                 allow_windows: false,
@@ -1237,6 +1404,25 @@ impl<'a> BaseExpressionBinder<'a> {
 
                         Ok(Expression::GroupingSet(GroupingSetExpr { inputs }))
                     }
+                    SpecialBuiltinFunction::Coalesce
+                    | SpecialBuiltinFunction::NullIf
+                    | SpecialBuiltinFunction::Greatest
+                    | SpecialBuiltinFunction::Least => {
+                        let op = match special {
+                            SpecialBuiltinFunction::Coalesce => ConditionalOperator::Coalesce,
+                            SpecialBuiltinFunction::NullIf => ConditionalOperator::NullIf,
+                            SpecialBuiltinFunction::Greatest => ConditionalOperator::Greatest,
+                            _ => ConditionalOperator::Least,
+                        };
+
+                        if func.distinct || func.filter.is_some() || func.over.is_some() {
+                            return Err(RayexecError::new(format!(
+                                "{op} does not support DISTINCT, FILTER, or OVER"
+                            )));
+                        }
+
+                        self.bind_conditional(bind_context, op, inputs)
+                    }
                 }
             }
             (ResolvedFunction::Scalar(scalar), _) => {
@@ -1259,6 +1445,96 @@ impl<'a> BaseExpressionBinder<'a> {
                 Ok(Expression::ScalarFunction(ScalarFunctionExpr { function }))
             }
             (ResolvedFunction::Aggregate(agg), _) => {
+                // Ordered-set aggregates receive the WITHIN GROUP expressions
+                // as their leading inputs, followed by the direct arguments.
+                //
+                // E.g. `percentile_cont(0.5) WITHIN GROUP (ORDER BY a)` has
+                // inputs `[a, 0.5]`.
+                let (inputs, order_by) = match &func.within_group {
+                    Some(within_group) => {
+                        if !agg.is_ordered_set() {
+                            return Err(RayexecError::new(format!(
+                                "WITHIN GROUP is not supported for '{}'",
+                                agg.name()
+                            )));
+                        }
+                        if func.distinct {
+                            return Err(RayexecError::new(
+                                "DISTINCT cannot be used with WITHIN GROUP",
+                            ));
+                        }
+                        if func.over.is_some() {
+                            not_implemented!("OVER for ordered-set aggregates")
+                        }
+
+                        if !func.order_by.is_empty() {
+                            return Err(RayexecError::new(
+                                "ORDER BY in arguments cannot be used with WITHIN GROUP",
+                            ));
+                        }
+
+                        let order_by = self.bind_aggregate_order_by(
+                            bind_context,
+                            within_group,
+                            column_binder,
+                            recur,
+                        )?;
+
+                        let inputs = order_by
+                            .iter()
+                            .map(|order_by| order_by.expr.clone())
+                            .chain(inputs)
+                            .collect::<Vec<_>>();
+
+                        (inputs, order_by)
+                    }
+                    None => {
+                        if agg.is_ordered_set() {
+                            return Err(RayexecError::new(format!(
+                                "WITHIN GROUP is required for ordered-set aggregate '{}'",
+                                agg.name()
+                            )));
+                        }
+
+                        // Inputs to order-sensitive aggregates (e.g. `first(a
+                        // ORDER BY b)`) are provided in the given order.
+                        let order_by = self.bind_aggregate_order_by(
+                            bind_context,
+                            &func.order_by,
+                            column_binder,
+                            recur,
+                        )?;
+                        if !order_by.is_empty() {
+                            if func.distinct {
+                                not_implemented!("DISTINCT with ORDER BY for aggregates")
+                            }
+                            if func.over.is_some() {
+                                not_implemented!("ORDER BY in arguments for window aggregates")
+                            }
+                        }
+
+                        (inputs, order_by)
+                    }
+                };
+
+                let mut all_inputs = inputs
+                    .iter()
+                    .chain(order_by.iter().map(|order_by| &order_by.expr));
+
+                // Window functions may take aggregates as input, but nothing
+                // may take a window function as input.
+                if all_inputs.clone().any(|input| input.contains_window()) {
+                    return Err(RayexecError::new(match func.over {
+                        Some(_) => "Window function calls cannot be nested",
+                        None => "Aggregate function calls cannot contain window function calls",
+                    }));
+                }
+                if func.over.is_none() && all_inputs.any(|input| input.contains_aggregate()) {
+                    return Err(RayexecError::new(
+                        "Aggregate function calls cannot be nested",
+                    ));
+                }
+
                 let inputs =
                     self.apply_casts_for_aggregate_function(bind_context, agg.as_ref(), inputs)?;
 
@@ -1268,6 +1544,10 @@ impl<'a> BaseExpressionBinder<'a> {
                     Some(over) => {
                         // Window
 
+                        if func.distinct {
+                            not_implemented!("DISTINCT for window aggregates")
+                        }
+
                         match over {
                             ast::WindowSpec::Named(_) => {
                                 not_implemented!("named window spec")
@@ -1351,6 +1631,7 @@ impl<'a> BaseExpressionBinder<'a> {
                             agg,
                             distinct: func.distinct,
                             filter: None,
+                            order_by,
                         }))
                     }
                 }
@@ -1358,12 +1639,119 @@ impl<'a> BaseExpressionBinder<'a> {
         }
     }
 
+    /// Bind the ordering for an aggregate's inputs.
+    ///
+    /// Handled like a statement level ORDER BY, except that it can't bind to an
+    /// output column.
+    fn bind_aggregate_order_by(
+        &self,
+        bind_context: &mut BindContext,
+        nodes: &[ast::OrderByNode<ResolvedMeta>],
+        column_binder: &mut impl ExpressionColumnBinder,
+        recur: RecursionContext,
+    ) -> Result<Vec<BoundOrderByExpr>> {
+        nodes
+            .iter()
+            .map(|order_by| {
+                let expr = self.bind_expression(
+                    bind_context,
+                    &order_by.expr,
+                    column_binder,
+                    RecursionContext {
+                        is_root: false,
+                        ..recur
+                    },
+                )?;
+                let desc = matches!(
+                    order_by.typ.unwrap_or(ast::OrderByType::Asc),
+                    ast::OrderByType::Desc
+                );
+                let nulls_first = match order_by.nulls {
+                    Some(nulls) => matches!(nulls, ast::OrderByNulls::First),
+                    None => desc,
+                };
+                Ok(BoundOrderByExpr {
+                    expr,
+                    desc,
+                    nulls_first,
+                })
+            })
+            .collect()
+    }
+
+    /// Bind a conditional expression, casting all inputs to a common type.
+    fn bind_conditional(
+        &self,
+        bind_context: &BindContext,
+        op: ConditionalOperator,
+        inputs: Vec<Expression>,
+    ) -> Result<Expression> {
+        if op == ConditionalOperator::NullIf && inputs.len() != 2 {
+            return Err(RayexecError::new("NULLIF requires exactly two arguments"));
+        }
+        if inputs.is_empty() {
+            return Err(RayexecError::new(format!(
+                "{op} requires at least one argument"
+            )));
+        }
+
+        let table_list = bind_context.get_table_list();
+        let datatypes = inputs
+            .iter()
+            .map(|input| input.datatype(table_list))
+            .collect::<Result<Vec<_>>>()?;
+
+        let output = Self::common_datatype(&datatypes)?.ok_or_else(|| {
+            RayexecError::new(format!(
+                "Cannot find a common type for {op} arguments: {}",
+                datatypes.display_with_brackets()
+            ))
+        })?;
+
+        let inputs: Vec<_> = inputs
+            .into_iter()
+            .zip(datatypes)
+            .map(|(input, typ)| {
+                if typ == output {
+                    input
+                } else {
+                    Expression::Cast(CastExpr {
+                        to: output.clone(),
+                        expr: Box::new(input),
+                    })
+                }
+            })
+            .collect();
+
+        // Make sure the inputs can actually be compared.
+        let compare_op = match op {
+            ConditionalOperator::Coalesce => None,
+            ConditionalOperator::NullIf => Some(ComparisonOperator::Eq),
+            ConditionalOperator::Greatest => Some(ComparisonOperator::Gt),
+            ConditionalOperator::Least => Some(ComparisonOperator::Lt),
+        };
+        if let Some(compare_op) = compare_op {
+            if output != DataType::Null {
+                let _ = compare_op
+                    .as_scalar_function()
+                    .plan(table_list, vec![inputs[0].clone(), inputs[0].clone()])?;
+            }
+        }
+
+        Ok(Expression::Conditional(ConditionalExpr { op, inputs }))
+    }
+
     pub(crate) fn apply_cast_for_operator<const N: usize>(
         &self,
         bind_context: &BindContext,
         operator: impl AsScalarFunction,
         inputs: [Expression; N],
     ) -> Result<[Expression; N]> {
+        let original_types = inputs
+            .iter()
+            .map(|input| input.datatype(bind_context.get_table_list()))
+            .collect::<Result<Vec<_>>>()?;
+
         let mut inputs = self.apply_casts_for_scalar_function(
             bind_context,
             operator.as_scalar_function(),
@@ -1373,36 +1761,48 @@ impl<'a> BaseExpressionBinder<'a> {
         // Further refine the types. When we're applying casts for an operator,
         // we know there's some relationship between the inputs.
         //
+        // Casts were planned using default types for the target type id, so
+        // we replace those with the full type (e.g. decimal precision/scale,
+        // timestamp unit) of an uncasted input of the same type.
+        //
+        // Inputs that the user explicitly cast are left alone, only casts
+        // that were just added change the input's type.
+        //
         // TODO: This may be useful for all functions, might pull this out.
-        let mut decimal64_meta = None;
-        let mut decimal128_meta = None;
+        let is_implicit_cast = |input: &Expression, original: &DataType| match input {
+            Expression::Cast(cast) => cast.to.datatype_id() != original.datatype_id(),
+            _ => false,
+        };
 
-        for input in &inputs {
-            if matches!(input, Expression::Cast(_)) {
-                continue;
-            }
+        let uncasted_types: Vec<_> = inputs
+            .iter()
+            .zip(&original_types)
+            .filter(|(input, original)| !is_implicit_cast(input, original))
+            .map(|(_, original)| original.clone())
+            .collect();
 
-            match input.datatype(bind_context.get_table_list())? {
-                DataType::Decimal64(m) => decimal64_meta = Some(m),
-                DataType::Decimal128(m) => decimal128_meta = Some(m),
-                _ => (),
+        for (input, original) in inputs.iter_mut().zip(&original_types) {
+            if !is_implicit_cast(input, original) {
+                continue;
             }
-        }
-
-        for input in &mut inputs {
             if let Expression::Cast(cast) = input {
-                match &mut cast.to {
-                    DataType::Decimal64(curr) => {
-                        if let Some(m) = decimal64_meta {
-                            *curr = m;
-                        }
-                    }
-                    DataType::Decimal128(curr) => {
-                        if let Some(m) = decimal128_meta {
-                            *curr = m;
-                        }
-                    }
-                    _ => (),
+                if let Some(typ) = uncasted_types
+                    .iter()
+                    .find(|typ| typ.datatype_id() == cast.to.datatype_id())
+                {
+                    cast.to = typ.clone();
+                    continue;
+                }
+
+                let source = cast.expr.datatype(bind_context.get_table_list())?;
+                if source.is_decimal() && cast.to.is_decimal() {
+                    // Widening a decimal (e.g. Decimal64 -> Decimal128), keep
+                    // the original precision and scale.
+                    let meta = source.try_get_decimal_type_meta()?;
+                    cast.to = match cast.to {
+                        DataType::Decimal64(_) => DataType::Decimal64(meta),
+                        _ => DataType::Decimal128(meta),
+                    };
                 }
             }
         }
@@ -1412,6 +1812,106 @@ impl<'a> BaseExpressionBinder<'a> {
             .map_err(|_| RayexecError::new("Number of casted inputs incorrect"))
     }
 
+    /// Wrap an expression in the function producing its collation key.
+    fn collate_expression(
+        &self,
+        bind_context: &BindContext,
+        expr: Expression,
+        collation: Collation,
+    ) -> Result<Expression> {
+        let collation = Expression::Literal(LiteralExpr {
+            literal: OwnedScalarValue::Utf8(collation.name().into()),
+        });
+        let function = Collate.plan(bind_context.get_table_list(), vec![expr, collation])?;
+
+        Ok(Expression::ScalarFunction(ScalarFunctionExpr { function }))
+    }
+
+    /// Apply an explicit collation on one side of a comparison to the other
+    /// side.
+    ///
+    /// Errors if both sides have different explicit collations.
+    fn apply_collation(
+        &self,
+        bind_context: &BindContext,
+        [left, right]: [Expression; 2],
+    ) -> Result<[Expression; 2]> {
+        let table_list = bind_context.get_table_list();
+        match (
+            explicit_collation(table_list, &left)?,
+            explicit_collation(table_list, &right)?,
+        ) {
+            (Some(c1), Some(c2)) if c1 != c2 => Err(RayexecError::new(format!(
+                "Cannot compare values with different collations: {} and {}",
+                c1.name(),
+                c2.name()
+            ))),
+            (Some(collation), None) => Ok([
+                left,
+                self.collate_expression(bind_context, right, collation)?,
+            ]),
+            (None, Some(collation)) => Ok([
+                self.collate_expression(bind_context, left, collation)?,
+                right,
+            ]),
+            _ => Ok([left, right]),
+        }
+    }
+
+    /// Find the type that all `datatypes` can be implicitly cast to.
+    ///
+    /// Untyped NULLs can be cast to anything, so they don't take part in
+    /// finding the common type. Returns None if there's no common type.
+    fn common_datatype(datatypes: &[DataType]) -> Result<Option<DataType>> {
+        let typed: Vec<_> = datatypes
+            .iter()
+            .filter(|typ| **typ != DataType::Null)
+            .collect();
+
+        let first = match typed.first() {
+            None => return Ok(Some(DataType::Null)),
+            Some(first) => *first,
+        };
+        if typed.iter().all(|typ| *typ == first) {
+            return Ok(Some(first.clone()));
+        }
+
+        let common = match common_supertype(&typed) {
+            Some(common) => common,
+            None => return Ok(None),
+        };
+
+        let output = match common {
+            DataTypeId::Decimal64 | DataTypeId::Decimal128 => {
+                // Widen so that every decimal and integer input fits.
+                let (mut int_digits, mut scale) = (0, 0);
+                for typ in &typed {
+                    if let Ok(meta) = typ.try_get_decimal_type_meta() {
+                        int_digits = int_digits.max(meta.precision as i8 - meta.scale);
+                        scale = scale.max(meta.scale);
+                    } else if let Some(digits) = integer_digits(typ) {
+                        int_digits = int_digits.max(digits);
+                    }
+                }
+                if common == DataTypeId::Decimal64 {
+                    let precision = ((int_digits + scale) as u8).min(Decimal64Type::MAX_PRECISION);
+                    DataType::Decimal64(DecimalTypeMeta::new(precision, scale))
+                } else {
+                    let precision = ((int_digits + scale) as u8).min(Decimal128Type::MAX_PRECISION);
+                    DataType::Decimal128(DecimalTypeMeta::new(precision, scale))
+                }
+            }
+            // Prefer keeping the full type (e.g. timestamp unit) of one of the
+            // inputs.
+            _ => match typed.iter().find(|typ| typ.datatype_id() == common) {
+                Some(typ) => (*typ).clone(),
+                None => DataType::try_default_datatype(common)?,
+            },
+        };
+
+        Ok(Some(output))
+    }
+
     /// Applies casts to an input expression based on the signatures for a
     /// scalar function.
     fn apply_casts_for_scalar_function(
@@ -1435,13 +1935,7 @@ impl<'a> BaseExpressionBinder<'a> {
             if candidates.is_empty() {
                 // TODO: Do we want to fall through? Is it possible for a
                 // scalar and aggregate function to have the same name?
-
-                // TODO: Better error.
-                return Err(RayexecError::new(format!(
-                    "Invalid inputs to '{}': {}",
-                    scalar.name(),
-                    input_datatypes.display_with_brackets(),
-                )));
+                return Err(no_matching_signature_error(scalar, &input_datatypes));
             }
 
             // TODO: Maybe more sophisticated candidate selection.
@@ -1490,11 +1984,7 @@ impl<'a> BaseExpressionBinder<'a> {
             let mut candidates = agg.candidate(&input_datatypes);
 
             if candidates.is_empty() {
-                return Err(RayexecError::new(format!(
-                    "Invalid inputs to '{}': {}",
-                    agg.name(),
-                    input_datatypes.display_with_brackets(),
-                )));
+                return Err(no_matching_signature_error(agg, &input_datatypes));
             }
 
             // TODO: Maybe more sophisticated candidate selection.
@@ -1542,12 +2032,7 @@ impl<'a> BaseExpressionBinder<'a> {
             let mut candidates = table.candidate(&input_datatypes);
 
             if candidates.is_empty() {
-                // TODO: Better error.
-                return Err(RayexecError::new(format!(
-                    "Invalid inputs to '{}': {}",
-                    table.name(),
-                    input_datatypes.display_with_brackets(),
-                )));
+                return Err(no_matching_signature_error(table, &input_datatypes));
             }
 
             // TODO: Maybe more sophisticated candidate selection.
@@ -1572,3 +2057,46 @@ impl<'a> BaseExpressionBinder<'a> {
         }
     }
 }
+
+/// Verify that an expression bound for `clause` (e.g. "WHERE") doesn't contain
+/// any aggregates or window functions.
+pub fn verify_no_aggregates_or_windows(expr: &Expression, clause: &str) -> Result<()> {
+    if expr.contains_aggregate() {
+        return Err(RayexecError::new(format!(
+            "Aggregate functions are not allowed in {clause}"
+        )));
+    }
+    if expr.contains_window() {
+        return Err(RayexecError::new(format!(
+            "Window functions are not allowed in {clause}"
+        )));
+    }
+    Ok(())
+}
+
+/// Number of decimal digits needed to represent every value of an integer
+/// type.
+const fn integer_digits(datatype: &DataType) -> Option<i8> {
+    Some(match datatype {
+        DataType::Int8 | DataType::UInt8 => 3,
+        DataType::Int16 | DataType::UInt16 => 5,
+        DataType::Int32 | DataType::UInt32 => 10,
+        DataType::Int64 => 19,
+        DataType::UInt64 => 20,
+        DataType::Int128 | DataType::UInt128 => 39,
+        _ => return None,
+    })
+}
+
+/// Get the collation of an expression with an explicit COLLATE.
+fn explicit_collation(table_list: &TableList, expr: &Expression) -> Result<Option<Collation>> {
+    match expr {
+        Expression::ScalarFunction(scalar) if scalar.function.function.name() == Collate.name() => {
+            let collation = ConstFold::rewrite(table_list, scalar.function.inputs[1].clone())?
+                .try_into_scalar()?
+                .try_into_string()?;
+            Ok(Some(Collation::from_name(&collation)?))
+        }
+        _ => Ok(None),
+    }
+}
@@ -1,6 +1,10 @@
+pub mod bind_alter_table;
 pub mod bind_attach;
+pub mod bind_check;
 pub mod bind_context;
 pub mod bind_copy;
+pub mod bind_create_function;
+pub mod bind_create_macro;
 pub mod bind_create_schema;
 pub mod bind_create_table;
 pub mod bind_create_view;
@@ -8,9 +12,12 @@ pub mod bind_describe;
 pub mod bind_drop;
 pub mod bind_explain;
 pub mod bind_insert;
+pub mod bind_policy;
 pub mod bind_query;
+pub mod bind_secret;
 pub mod bind_set;
 pub mod bind_statement;
+pub mod bind_summarize;
 pub mod column_binder;
 pub mod constant_binder;
 pub mod expr_binder;
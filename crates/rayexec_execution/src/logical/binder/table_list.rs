@@ -94,6 +94,11 @@ impl TableList {
         TableList { tables: Vec::new() }
     }
 
+    /// Iterate all tables, ordered by table ref.
+    pub fn iter(&self) -> impl Iterator<Item = &Table> {
+        self.tables.iter()
+    }
+
     /// Get a table by table ref.
     pub fn get(&self, table_ref: TableRef) -> Result<&Table> {
         self.tables
@@ -0,0 +1,42 @@
+use rayexec_error::Result;
+
+use super::binder::bind_context::BindContext;
+use super::binder::table_list::TableRef;
+use super::operator::{LogicalNode, Node};
+use crate::database::alter::AlterTableInfo;
+use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::expr::Expression;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogicalAlterTable {
+    pub catalog: String,
+    pub schema: String,
+    pub if_exists: bool,
+    pub info: AlterTableInfo,
+}
+
+impl Explainable for LogicalAlterTable {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("AlterTable").with_value("name", &self.info.name)
+    }
+}
+
+impl LogicalNode for Node<LogicalAlterTable> {
+    fn get_output_table_refs(&self, _bind_context: &BindContext) -> Vec<TableRef> {
+        Vec::new()
+    }
+
+    fn for_each_expr<F>(&self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+
+    fn for_each_expr_mut<F>(&mut self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&mut Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+}
@@ -4,9 +4,11 @@ use super::binder::bind_context::BindContext;
 use super::binder::table_list::TableRef;
 use super::operator::{LogicalNode, Node};
 use crate::arrays::field::Field;
-use crate::database::create::OnConflict;
+use crate::database::catalog_entry::CheckConstraint;
+use crate::database::create::{CreateMacroInfo, OnConflict};
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 use crate::expr::Expression;
+use crate::functions::scalar::ScalarFunction;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct LogicalCreateSchema {
@@ -47,6 +49,9 @@ pub struct LogicalCreateTable {
     pub schema: String,
     pub name: String,
     pub columns: Vec<Field>,
+    pub checks: Vec<CheckConstraint>,
+    /// Indices of the columns making up the primary key.
+    pub primary_key: Vec<usize>,
     pub on_conflict: OnConflict,
 }
 
@@ -111,3 +116,72 @@ impl LogicalNode for Node<LogicalCreateView> {
         Ok(())
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogicalCreateFunction {
+    pub catalog: String,
+    pub schema: String,
+    pub function: Box<dyn ScalarFunction>,
+    pub on_conflict: OnConflict,
+}
+
+impl Explainable for LogicalCreateFunction {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("CreateFunction").with_value("name", self.function.name())
+    }
+}
+
+impl LogicalNode for Node<LogicalCreateFunction> {
+    fn get_output_table_refs(&self, _bind_context: &BindContext) -> Vec<TableRef> {
+        Vec::new()
+    }
+
+    fn for_each_expr<F>(&self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+
+    fn for_each_expr_mut<F>(&mut self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&mut Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogicalCreateMacro {
+    pub catalog: String,
+    pub schema: String,
+    pub info: CreateMacroInfo,
+    /// If this is a table macro, otherwise a scalar macro.
+    pub table_macro: bool,
+}
+
+impl Explainable for LogicalCreateMacro {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("CreateMacro").with_value("name", &self.info.name)
+    }
+}
+
+impl LogicalNode for Node<LogicalCreateMacro> {
+    fn get_output_table_refs(&self, _bind_context: &BindContext) -> Vec<TableRef> {
+        Vec::new()
+    }
+
+    fn for_each_expr<F>(&self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+
+    fn for_each_expr_mut<F>(&mut self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&mut Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+}
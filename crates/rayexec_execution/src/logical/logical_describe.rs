@@ -10,6 +10,10 @@ use crate::expr::Expression;
 #[derive(Debug, Clone, PartialEq)]
 pub struct LogicalDescribe {
     pub schema: Schema,
+    /// Constraints for each column in the schema, e.g. 'NOT NULL'.
+    ///
+    /// Only populated when describing a table.
+    pub constraints: Vec<Vec<String>>,
     pub table_ref: TableRef,
 }
 
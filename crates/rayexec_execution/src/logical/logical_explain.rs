@@ -17,8 +17,17 @@ pub struct LogicalExplain {
     pub analyze: bool,
     pub verbose: bool,
     pub format: ExplainFormat,
+    /// Resolved database objects for the query if the bind context should be
+    /// included in the output.
+    pub bindings: Option<Vec<ExplainEntry>>,
     pub logical_unoptimized: Box<LogicalOperator>,
     pub logical_optimized: Option<Box<LogicalOperator>>,
+    /// Plans after each optimizer rule was applied, along with the name of the
+    /// rule.
+    ///
+    /// Initialized to an empty vec during planning if steps were requested,
+    /// and filled in once the plan is optimized.
+    pub optimized_steps: Option<Vec<(&'static str, LogicalOperator)>>,
 }
 
 impl Explainable for LogicalExplain {
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use rayexec_error::Result;
 
+use super::binder::bind_check::BoundCheck;
 use super::binder::bind_context::BindContext;
 use super::binder::table_list::TableRef;
 use super::operator::{LogicalNode, Node};
@@ -14,6 +15,8 @@ pub struct LogicalInsert {
     pub catalog: String,
     pub schema: String,
     pub table: Arc<CatalogEntry>,
+    /// CHECK constraints to validate inserted rows against.
+    pub checks: Vec<BoundCheck>,
 }
 
 impl Explainable for LogicalInsert {
@@ -27,17 +30,23 @@ impl LogicalNode for Node<LogicalInsert> {
         Vec::new()
     }
 
-    fn for_each_expr<F>(&self, _func: &mut F) -> Result<()>
+    fn for_each_expr<F>(&self, func: &mut F) -> Result<()>
     where
         F: FnMut(&Expression) -> Result<()>,
     {
+        for check in &self.node.checks {
+            func(&check.expr)?;
+        }
         Ok(())
     }
 
-    fn for_each_expr_mut<F>(&mut self, _func: &mut F) -> Result<()>
+    fn for_each_expr_mut<F>(&mut self, func: &mut F) -> Result<()>
     where
         F: FnMut(&mut Expression) -> Result<()>,
     {
+        for check in &mut self.node.checks {
+            func(&mut check.expr)?;
+        }
         Ok(())
     }
 }
@@ -1,6 +1,7 @@
 use std::fmt;
 
 use rayexec_error::{RayexecError, Result};
+use rayexec_proto::ProtoConv;
 
 use super::binder::bind_context::{BindContext, MaterializationRef};
 use super::binder::table_list::TableRef;
@@ -70,6 +71,46 @@ impl fmt::Display for JoinType {
     }
 }
 
+impl ProtoConv for JoinType {
+    type ProtoType = rayexec_proto::generated::execution::PhysicalJoinType;
+
+    fn to_proto(&self) -> Result<Self::ProtoType> {
+        use rayexec_proto::generated::execution::JoinType as ProtoJoinType;
+
+        let (join_type, mark_table_ref) = match self {
+            Self::Left => (ProtoJoinType::Left, 0),
+            Self::Right => (ProtoJoinType::Right, 0),
+            Self::Inner => (ProtoJoinType::Inner, 0),
+            Self::Full => (ProtoJoinType::Full, 0),
+            Self::Semi => (ProtoJoinType::Semi, 0),
+            Self::Anti => (ProtoJoinType::Anti, 0),
+            Self::LeftMark { table_ref } => (ProtoJoinType::LeftMark, table_ref.table_idx as u32),
+        };
+
+        Ok(Self::ProtoType {
+            join_type: join_type as i32,
+            mark_table_ref,
+        })
+    }
+
+    fn from_proto(proto: Self::ProtoType) -> Result<Self> {
+        use rayexec_proto::generated::execution::JoinType as ProtoJoinType;
+
+        Ok(match proto.join_type() {
+            ProtoJoinType::InvalidJoinType => return Err(RayexecError::new("invalid")),
+            ProtoJoinType::Left => Self::Left,
+            ProtoJoinType::Right => Self::Right,
+            ProtoJoinType::Inner => Self::Inner,
+            ProtoJoinType::Full => Self::Full,
+            ProtoJoinType::Semi => Self::Semi,
+            ProtoJoinType::Anti => Self::Anti,
+            ProtoJoinType::LeftMark => Self::LeftMark {
+                table_ref: TableRef::from(proto.mark_table_ref as usize),
+            },
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ComparisonCondition {
     /// Expression containing column references from the left side.
@@ -133,17 +174,73 @@ impl ContextDisplay for ComparisonCondition {
     }
 }
 
+/// An equality condition that both inputs to a join are sorted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeJoinKey {
+    /// Index of the equality in the join's conditions.
+    pub condition: usize,
+    /// If both inputs are sorted in descending order.
+    pub desc: bool,
+    /// If both inputs have nulls ordered first.
+    pub nulls_first: bool,
+}
+
+impl fmt::Display for MergeJoinKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {} {}",
+            self.condition,
+            if self.desc { "desc" } else { "asc" },
+            if self.nulls_first {
+                "nulls first"
+            } else {
+                "nulls last"
+            }
+        )
+    }
+}
+
+impl ProtoConv for MergeJoinKey {
+    type ProtoType = rayexec_proto::generated::execution::MergeJoinKey;
+
+    fn to_proto(&self) -> Result<Self::ProtoType> {
+        Ok(Self::ProtoType {
+            condition: self.condition as u32,
+            desc: self.desc,
+            nulls_first: self.nulls_first,
+        })
+    }
+
+    fn from_proto(proto: Self::ProtoType) -> Result<Self> {
+        Ok(Self {
+            condition: proto.condition as usize,
+            desc: proto.desc,
+            nulls_first: proto.nulls_first,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LogicalComparisonJoin {
     pub join_type: JoinType,
     pub conditions: Vec<ComparisonCondition>,
+    /// Equalities to merge the inputs on, in the order the inputs are sorted.
+    ///
+    /// Set by the optimizer when both inputs are already sorted on the join
+    /// keys. If empty, the join is executed as a hash join.
+    pub merge_keys: Vec<MergeJoinKey>,
 }
 
 impl Explainable for LogicalComparisonJoin {
     fn explain_entry(&self, conf: ExplainConfig) -> ExplainEntry {
-        ExplainEntry::new("ComparisonJoin")
+        let mut ent = ExplainEntry::new("ComparisonJoin")
             .with_values_context("conditions", conf, &self.conditions)
-            .with_value("join_type", self.join_type)
+            .with_value("join_type", self.join_type);
+        if !self.merge_keys.is_empty() {
+            ent = ent.with_values("merge_keys", &self.merge_keys);
+        }
+        ent
     }
 }
 
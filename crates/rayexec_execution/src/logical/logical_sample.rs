@@ -0,0 +1,52 @@
+use rayexec_error::Result;
+
+use super::binder::bind_context::BindContext;
+use super::binder::table_list::TableRef;
+use super::operator::{LogicalNode, Node};
+use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::expr::Expression;
+use crate::storage::table_storage::TableSample;
+
+/// Sample rows from the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogicalSample {
+    /// Sample a percentage of rows from the input.
+    ///
+    /// Used when a TABLESAMPLE can't be pushed down into a scan (e.g. when
+    /// sampling a subquery).
+    Table(TableSample),
+    /// Uniformly sample a fixed number of rows from the input, returning them
+    /// in random order.
+    Reservoir { rows: usize },
+}
+
+impl Explainable for LogicalSample {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        match self {
+            Self::Table(sample) => ExplainEntry::new("Sample").with_value("sample", sample),
+            Self::Reservoir { rows } => {
+                ExplainEntry::new("ReservoirSample").with_value("rows", rows)
+            }
+        }
+    }
+}
+
+impl LogicalNode for Node<LogicalSample> {
+    fn get_output_table_refs(&self, bind_context: &BindContext) -> Vec<TableRef> {
+        self.get_children_table_refs(bind_context)
+    }
+
+    fn for_each_expr<F>(&self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+
+    fn for_each_expr_mut<F>(&mut self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&mut Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+}
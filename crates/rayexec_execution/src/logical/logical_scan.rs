@@ -12,6 +12,8 @@ use crate::database::catalog_entry::CatalogEntry;
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 use crate::expr::Expression;
 use crate::functions::table::PlannedTableFunction;
+use crate::optimizer::query_pushdown::ShippedRows;
+use crate::storage::table_storage::{IndexScan, RemoteQuery, TableAggregate, TableSample};
 
 // TODO: Probably remove view from this.
 // Maybe just split it all up.
@@ -33,6 +35,15 @@ pub enum ScanSource {
         schema: String,
         source: Arc<CatalogEntry>,
     },
+    /// A query executed entirely by the table storage for a catalog.
+    Query {
+        catalog: String,
+        query: RemoteQuery,
+        /// Rows sent along with the query.
+        ///
+        /// When set, the scan has a single child producing the rows.
+        shipped: Option<ShippedRows>,
+    },
 }
 
 impl ScanSource {
@@ -42,6 +53,7 @@ impl ScanSource {
             Self::TableFunction { function } => function.cardinality,
             Self::ExpressionList { rows } => StatisticsValue::Exact(rows.len()),
             Self::View { .. } => StatisticsValue::Unknown,
+            Self::Query { .. } => StatisticsValue::Unknown,
         }
     }
 }
@@ -73,6 +85,28 @@ pub struct LogicalScan {
     /// place directly above the scan with expressions representing the same
     /// filters applied here.
     pub scan_filters: Vec<ScanFilter>,
+    /// Sample pushed down into the scan.
+    ///
+    /// The data table is responsible for applying the sample, either by
+    /// skipping blocks entirely or by sampling batches after reading.
+    pub sample: Option<TableSample>,
+    /// Maximum number of rows the scan needs to produce.
+    ///
+    /// Set when a limit has been pushed down into the scan. This is only a
+    /// hint, the limit operator remains in place above the scan.
+    pub limit: Option<usize>,
+    /// Aggregate computed entirely by the source.
+    ///
+    /// When set, the scan outputs a single row of aggregate values instead of
+    /// the table's columns, and `types` and `names` describe the aggregate
+    /// outputs.
+    pub aggregate: Option<TableAggregate>,
+    /// Index to read rows through instead of scanning the whole table.
+    ///
+    /// The filter (or order and limit) the index scan was derived from
+    /// remains in place above the scan. Boxed to keep the size of the scan
+    /// node down.
+    pub index_scan: Option<Box<IndexScan>>,
     /// Source of the scan.
     pub source: ScanSource,
 }
@@ -100,6 +134,41 @@ impl Explainable for LogicalScan {
             ScanSource::ExpressionList { rows } => {
                 ent = ent.with_value("num_rows", rows.len());
             }
+            ScanSource::Query {
+                catalog,
+                query,
+                shipped,
+            } => {
+                ent = ent
+                    .with_value("catalog", catalog)
+                    .with_value("query", &query.sql);
+                if let Some(shipped) = shipped {
+                    ent = ent.with_values("shipped_columns", &shipped.columns);
+                }
+            }
+        }
+
+        if !self.scan_filters.is_empty() {
+            ent = ent.with_values("scan_filters", &self.scan_filters);
+        }
+
+        if let Some(sample) = &self.sample {
+            ent = ent.with_value("sample", sample);
+        }
+
+        if let Some(limit) = self.limit {
+            ent = ent.with_value("limit", limit);
+        }
+
+        if let Some(index_scan) = &self.index_scan {
+            ent = ent.with_value("index_scan", index_scan);
+        }
+
+        if let Some(aggregate) = &self.aggregate {
+            ent = ent.with_values("pushed_aggregates", &aggregate.aggregates);
+            if !aggregate.filters.is_empty() {
+                ent = ent.with_values("pushed_filters", &aggregate.filters);
+            }
         }
 
         if conf.verbose {
@@ -0,0 +1,77 @@
+use rayexec_error::Result;
+
+use super::binder::bind_context::BindContext;
+use super::binder::table_list::TableRef;
+use super::operator::{LogicalNode, Node};
+use crate::database::create::OnConflict;
+use crate::database::secrets::Secret;
+use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::expr::Expression;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogicalCreateSecret {
+    pub name: String,
+    pub secret: Secret,
+    pub on_conflict: OnConflict,
+}
+
+impl Explainable for LogicalCreateSecret {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        // Secret values intentionally omitted.
+        ExplainEntry::new("CreateSecret")
+            .with_value("name", &self.name)
+            .with_value("type", self.secret.secret_type)
+    }
+}
+
+impl LogicalNode for Node<LogicalCreateSecret> {
+    fn get_output_table_refs(&self, _bind_context: &BindContext) -> Vec<TableRef> {
+        Vec::new()
+    }
+
+    fn for_each_expr<F>(&self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+
+    fn for_each_expr_mut<F>(&mut self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&mut Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogicalDropSecret {
+    pub name: String,
+    pub if_exists: bool,
+}
+
+impl Explainable for LogicalDropSecret {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        ExplainEntry::new("DropSecret").with_value("name", &self.name)
+    }
+}
+
+impl LogicalNode for Node<LogicalDropSecret> {
+    fn get_output_table_refs(&self, _bind_context: &BindContext) -> Vec<TableRef> {
+        Vec::new()
+    }
+
+    fn for_each_expr<F>(&self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+
+    fn for_each_expr_mut<F>(&mut self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&mut Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use super::binder::bind_context::BindContext;
 use super::binder::table_list::TableRef;
 use super::operator::{LogicalNode, Node};
 use crate::arrays::scalar::OwnedScalarValue;
+use crate::config::session::SettingValue;
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 use crate::expr::Expression;
 
@@ -11,6 +12,8 @@ use crate::expr::Expression;
 pub struct LogicalSetVar {
     pub name: String,
     pub value: OwnedScalarValue,
+    /// If this setting should only apply to the current transaction.
+    pub local: bool,
 }
 
 impl Explainable for LogicalSetVar {
@@ -77,9 +80,14 @@ impl LogicalNode for Node<LogicalResetVar> {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct LogicalShowVar {
-    pub name: String,
-    pub value: OwnedScalarValue,
+pub enum LogicalShowVar {
+    /// Show a single variable.
+    Variable {
+        name: String,
+        value: OwnedScalarValue,
+    },
+    /// Show all variables along with their descriptions.
+    All { settings: Vec<SettingValue> },
 }
 
 impl Explainable for LogicalShowVar {
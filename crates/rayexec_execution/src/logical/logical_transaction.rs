@@ -0,0 +1,47 @@
+use rayexec_error::Result;
+
+use super::binder::bind_context::BindContext;
+use super::binder::table_list::TableRef;
+use super::operator::{LogicalNode, Node};
+use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
+use crate::expr::Expression;
+
+/// Transaction control (BEGIN, COMMIT, ROLLBACK).
+///
+/// Handled in the session, never planned into pipelines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicalTransaction {
+    Begin,
+    Commit,
+    Rollback,
+}
+
+impl Explainable for LogicalTransaction {
+    fn explain_entry(&self, _conf: ExplainConfig) -> ExplainEntry {
+        match self {
+            Self::Begin => ExplainEntry::new("Begin"),
+            Self::Commit => ExplainEntry::new("Commit"),
+            Self::Rollback => ExplainEntry::new("Rollback"),
+        }
+    }
+}
+
+impl LogicalNode for Node<LogicalTransaction> {
+    fn get_output_table_refs(&self, _bind_context: &BindContext) -> Vec<TableRef> {
+        Vec::new()
+    }
+
+    fn for_each_expr<F>(&self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+
+    fn for_each_expr_mut<F>(&mut self, _func: &mut F) -> Result<()>
+    where
+        F: FnMut(&mut Expression) -> Result<()>,
+    {
+        Ok(())
+    }
+}
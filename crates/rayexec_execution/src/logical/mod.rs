@@ -39,12 +39,14 @@
 pub mod operator;
 pub mod scan_filter;
 pub mod statistics;
+pub mod walk;
 
 pub mod binder;
 pub mod planner;
 pub mod resolver;
 
 pub mod logical_aggregate;
+pub mod logical_alter;
 pub mod logical_attach;
 pub mod logical_copy;
 pub mod logical_create;
@@ -61,8 +63,11 @@ pub mod logical_limit;
 pub mod logical_materialization;
 pub mod logical_order;
 pub mod logical_project;
+pub mod logical_sample;
 pub mod logical_scan;
+pub mod logical_secret;
 pub mod logical_set;
 pub mod logical_setop;
+pub mod logical_transaction;
 pub mod logical_unnest;
 pub mod logical_window;
@@ -6,9 +6,16 @@ use rayexec_proto::ProtoConv;
 use super::binder::bind_context::BindContext;
 use super::binder::table_list::TableRef;
 use super::logical_aggregate::LogicalAggregate;
+use super::logical_alter::LogicalAlterTable;
 use super::logical_attach::{LogicalAttachDatabase, LogicalDetachDatabase};
 use super::logical_copy::LogicalCopyTo;
-use super::logical_create::{LogicalCreateSchema, LogicalCreateTable, LogicalCreateView};
+use super::logical_create::{
+    LogicalCreateFunction,
+    LogicalCreateMacro,
+    LogicalCreateSchema,
+    LogicalCreateTable,
+    LogicalCreateView,
+};
 use super::logical_describe::LogicalDescribe;
 use super::logical_distinct::LogicalDistinct;
 use super::logical_drop::LogicalDrop;
@@ -27,12 +34,16 @@ use super::logical_limit::LogicalLimit;
 use super::logical_materialization::{LogicalMagicMaterializationScan, LogicalMaterializationScan};
 use super::logical_order::LogicalOrder;
 use super::logical_project::LogicalProject;
+use super::logical_sample::LogicalSample;
 use super::logical_scan::LogicalScan;
+use super::logical_secret::{LogicalCreateSecret, LogicalDropSecret};
 use super::logical_set::{LogicalResetVar, LogicalSetVar, LogicalShowVar};
 use super::logical_setop::LogicalSetop;
+use super::logical_transaction::LogicalTransaction;
 use super::logical_unnest::LogicalUnnest;
 use super::logical_window::LogicalWindow;
 use super::statistics::StatisticsValue;
+use super::walk::Recursion;
 use crate::explain::explainable::{ExplainConfig, ExplainEntry, Explainable};
 use crate::expr::Expression;
 
@@ -265,6 +276,7 @@ pub enum LogicalOperator {
     Project(Node<LogicalProject>),
     Filter(Node<LogicalFilter>),
     Limit(Node<LogicalLimit>),
+    Sample(Node<LogicalSample>),
     Order(Node<LogicalOrder>),
     Distinct(Node<LogicalDistinct>),
     Aggregate(Node<LogicalAggregate>),
@@ -275,14 +287,20 @@ pub enum LogicalOperator {
     Empty(Node<LogicalEmpty>),
     SetVar(Node<LogicalSetVar>),
     ResetVar(Node<LogicalResetVar>),
+    Transaction(Node<LogicalTransaction>),
     ShowVar(Node<LogicalShowVar>),
     AttachDatabase(Node<LogicalAttachDatabase>),
     DetachDatabase(Node<LogicalDetachDatabase>),
+    CreateSecret(Node<LogicalCreateSecret>),
+    DropSecret(Node<LogicalDropSecret>),
     Drop(Node<LogicalDrop>),
+    AlterTable(Node<LogicalAlterTable>),
     Insert(Node<LogicalInsert>),
     CreateSchema(Node<LogicalCreateSchema>),
     CreateTable(Node<LogicalCreateTable>),
     CreateView(Node<LogicalCreateView>),
+    CreateFunction(Node<LogicalCreateFunction>),
+    CreateMacro(Node<LogicalCreateMacro>),
     Describe(Node<LogicalDescribe>),
     Explain(Node<LogicalExplain>),
     CopyTo(Node<LogicalCopyTo>),
@@ -304,11 +322,91 @@ impl LogicalOperator {
     });
 
     pub fn location(&self) -> &LocationRequirement {
-        unimplemented!()
+        match self {
+            Self::Invalid => panic!("attempting to get location for invalid operator"),
+            Self::Project(n) => &n.location,
+            Self::Filter(n) => &n.location,
+            Self::Limit(n) => &n.location,
+            Self::Sample(n) => &n.location,
+            Self::Order(n) => &n.location,
+            Self::Distinct(n) => &n.location,
+            Self::Aggregate(n) => &n.location,
+            Self::SetOp(n) => &n.location,
+            Self::Scan(n) => &n.location,
+            Self::MaterializationScan(n) => &n.location,
+            Self::MagicMaterializationScan(n) => &n.location,
+            Self::Empty(n) => &n.location,
+            Self::SetVar(n) => &n.location,
+            Self::ResetVar(n) => &n.location,
+            Self::Transaction(n) => &n.location,
+            Self::ShowVar(n) => &n.location,
+            Self::AttachDatabase(n) => &n.location,
+            Self::DetachDatabase(n) => &n.location,
+            Self::CreateSecret(n) => &n.location,
+            Self::DropSecret(n) => &n.location,
+            Self::Drop(n) => &n.location,
+            Self::AlterTable(n) => &n.location,
+            Self::Insert(n) => &n.location,
+            Self::CreateSchema(n) => &n.location,
+            Self::CreateTable(n) => &n.location,
+            Self::CreateView(n) => &n.location,
+            Self::CreateFunction(n) => &n.location,
+            Self::CreateMacro(n) => &n.location,
+            Self::Describe(n) => &n.location,
+            Self::Explain(n) => &n.location,
+            Self::CopyTo(n) => &n.location,
+            Self::CrossJoin(n) => &n.location,
+            Self::ComparisonJoin(n) => &n.location,
+            Self::ArbitraryJoin(n) => &n.location,
+            Self::MagicJoin(n) => &n.location,
+            Self::Unnest(n) => &n.location,
+            Self::Window(n) => &n.location,
+            Self::InOut(n) => &n.location,
+        }
     }
 
     pub fn location_mut(&mut self) -> &mut LocationRequirement {
-        unimplemented!()
+        match self {
+            Self::Invalid => panic!("attempting to get location for invalid operator"),
+            Self::Project(n) => &mut n.location,
+            Self::Filter(n) => &mut n.location,
+            Self::Limit(n) => &mut n.location,
+            Self::Sample(n) => &mut n.location,
+            Self::Order(n) => &mut n.location,
+            Self::Distinct(n) => &mut n.location,
+            Self::Aggregate(n) => &mut n.location,
+            Self::SetOp(n) => &mut n.location,
+            Self::Scan(n) => &mut n.location,
+            Self::MaterializationScan(n) => &mut n.location,
+            Self::MagicMaterializationScan(n) => &mut n.location,
+            Self::Empty(n) => &mut n.location,
+            Self::SetVar(n) => &mut n.location,
+            Self::ResetVar(n) => &mut n.location,
+            Self::Transaction(n) => &mut n.location,
+            Self::ShowVar(n) => &mut n.location,
+            Self::AttachDatabase(n) => &mut n.location,
+            Self::DetachDatabase(n) => &mut n.location,
+            Self::CreateSecret(n) => &mut n.location,
+            Self::DropSecret(n) => &mut n.location,
+            Self::Drop(n) => &mut n.location,
+            Self::AlterTable(n) => &mut n.location,
+            Self::Insert(n) => &mut n.location,
+            Self::CreateSchema(n) => &mut n.location,
+            Self::CreateTable(n) => &mut n.location,
+            Self::CreateView(n) => &mut n.location,
+            Self::CreateFunction(n) => &mut n.location,
+            Self::CreateMacro(n) => &mut n.location,
+            Self::Describe(n) => &mut n.location,
+            Self::Explain(n) => &mut n.location,
+            Self::CopyTo(n) => &mut n.location,
+            Self::CrossJoin(n) => &mut n.location,
+            Self::ComparisonJoin(n) => &mut n.location,
+            Self::ArbitraryJoin(n) => &mut n.location,
+            Self::MagicJoin(n) => &mut n.location,
+            Self::Unnest(n) => &mut n.location,
+            Self::Window(n) => &mut n.location,
+            Self::InOut(n) => &mut n.location,
+        }
     }
 
     pub fn take(&mut self) -> Self {
@@ -319,23 +417,96 @@ impl LogicalOperator {
         std::mem::replace(self, Box::new(Self::EMPTY))
     }
 
-    pub fn for_each_child_mut<F>(&mut self, _f: &mut F) -> Result<()>
+    pub fn for_each_child<F>(&self, f: &mut F) -> Result<()>
+    where
+        F: FnMut(&LogicalOperator) -> Result<()>,
+    {
+        for child in self.children() {
+            f(child)?;
+        }
+        Ok(())
+    }
+
+    pub fn for_each_child_mut<F>(&mut self, f: &mut F) -> Result<()>
     where
         F: FnMut(&mut LogicalOperator) -> Result<()>,
     {
-        unimplemented!()
+        for child in self.children_mut() {
+            f(child)?;
+        }
+        Ok(())
     }
 
     /// Walk the plan depth first.
     ///
+    /// `pre` is called on each operator on the way down, and `post` on the way
+    /// up. See [`Recursion`] for how `pre` controls the walk.
+    ///
+    /// Expressions within each operator can be walked from the hooks with
+    /// `for_each_expr` and `Expression::walk`.
+    pub fn walk<F1, F2>(&self, pre: &mut F1, post: &mut F2) -> Result<()>
+    where
+        F1: FnMut(&LogicalOperator) -> Result<Recursion>,
+        F2: FnMut(&LogicalOperator) -> Result<()>,
+    {
+        self.walk_inner(pre, post)?;
+        Ok(())
+    }
+
+    fn walk_inner<F1, F2>(&self, pre: &mut F1, post: &mut F2) -> Result<Recursion>
+    where
+        F1: FnMut(&LogicalOperator) -> Result<Recursion>,
+        F2: FnMut(&LogicalOperator) -> Result<()>,
+    {
+        match pre(self)? {
+            Recursion::Stop => return Ok(Recursion::Stop),
+            Recursion::SkipChildren => (),
+            Recursion::Continue => {
+                for child in self.children() {
+                    if child.walk_inner(pre, post)? == Recursion::Stop {
+                        return Ok(Recursion::Stop);
+                    }
+                }
+            }
+        }
+
+        post(self)?;
+        Ok(Recursion::Continue)
+    }
+
+    /// Walk the plan depth first, allowing operators to be rewritten in place.
+    ///
     /// `pre` provides access to children on the way down, and `post` on the way
-    /// up.
-    pub fn walk_mut<F1, F2>(&mut self, _pre: &mut F1, _post: &mut F2) -> Result<()>
+    /// up. If `pre` replaces an operator, the walk continues with the children
+    /// of the replacement.
+    pub fn walk_mut<F1, F2>(&mut self, pre: &mut F1, post: &mut F2) -> Result<()>
     where
-        F1: FnMut(&mut LogicalOperator) -> Result<()>,
+        F1: FnMut(&mut LogicalOperator) -> Result<Recursion>,
         F2: FnMut(&mut LogicalOperator) -> Result<()>,
     {
-        unimplemented!()
+        self.walk_mut_inner(pre, post)?;
+        Ok(())
+    }
+
+    fn walk_mut_inner<F1, F2>(&mut self, pre: &mut F1, post: &mut F2) -> Result<Recursion>
+    where
+        F1: FnMut(&mut LogicalOperator) -> Result<Recursion>,
+        F2: FnMut(&mut LogicalOperator) -> Result<()>,
+    {
+        match pre(self)? {
+            Recursion::Stop => return Ok(Recursion::Stop),
+            Recursion::SkipChildren => (),
+            Recursion::Continue => {
+                for child in self.children_mut() {
+                    if child.walk_mut_inner(pre, post)? == Recursion::Stop {
+                        return Ok(Recursion::Stop);
+                    }
+                }
+            }
+        }
+
+        post(self)?;
+        Ok(Recursion::Continue)
     }
 
     /// Replaces the children in the operator by running them through `modify`.
@@ -370,17 +541,24 @@ impl LogicalOperator {
             Self::SetOp(n) => &n.children,
             Self::Empty(n) => &n.children,
             Self::Limit(n) => &n.children,
+            Self::Sample(n) => &n.children,
             Self::Order(n) => &n.children,
             Self::SetVar(n) => &n.children,
             Self::ResetVar(n) => &n.children,
+            Self::Transaction(n) => &n.children,
             Self::ShowVar(n) => &n.children,
             Self::AttachDatabase(n) => &n.children,
             Self::DetachDatabase(n) => &n.children,
+            Self::CreateSecret(n) => &n.children,
+            Self::DropSecret(n) => &n.children,
             Self::Drop(n) => &n.children,
+            Self::AlterTable(n) => &n.children,
             Self::Insert(n) => &n.children,
             Self::CreateSchema(n) => &n.children,
             Self::CreateTable(n) => &n.children,
             Self::CreateView(n) => &n.children,
+            Self::CreateFunction(n) => &n.children,
+            Self::CreateMacro(n) => &n.children,
             Self::Describe(n) => &n.children,
             Self::Explain(n) => &n.children,
             Self::CopyTo(n) => &n.children,
@@ -407,17 +585,24 @@ impl LogicalOperator {
             Self::SetOp(n) => &mut n.children,
             Self::Empty(n) => &mut n.children,
             Self::Limit(n) => &mut n.children,
+            Self::Sample(n) => &mut n.children,
             Self::Order(n) => &mut n.children,
             Self::SetVar(n) => &mut n.children,
             Self::ResetVar(n) => &mut n.children,
+            Self::Transaction(n) => &mut n.children,
             Self::ShowVar(n) => &mut n.children,
             Self::AttachDatabase(n) => &mut n.children,
             Self::DetachDatabase(n) => &mut n.children,
+            Self::CreateSecret(n) => &mut n.children,
+            Self::DropSecret(n) => &mut n.children,
             Self::Drop(n) => &mut n.children,
+            Self::AlterTable(n) => &mut n.children,
             Self::Insert(n) => &mut n.children,
             Self::CreateSchema(n) => &mut n.children,
             Self::CreateTable(n) => &mut n.children,
             Self::CreateView(n) => &mut n.children,
+            Self::CreateFunction(n) => &mut n.children,
+            Self::CreateMacro(n) => &mut n.children,
             Self::Describe(n) => &mut n.children,
             Self::Explain(n) => &mut n.children,
             Self::CopyTo(n) => &mut n.children,
@@ -448,17 +633,24 @@ impl LogicalOperator {
             LogicalOperator::SetOp(n) => n.estimated_cardinality,
             LogicalOperator::Empty(n) => n.estimated_cardinality,
             LogicalOperator::Limit(n) => n.estimated_cardinality,
+            LogicalOperator::Sample(n) => n.estimated_cardinality,
             LogicalOperator::Order(n) => n.estimated_cardinality,
             LogicalOperator::SetVar(n) => n.estimated_cardinality,
             LogicalOperator::ResetVar(n) => n.estimated_cardinality,
+            LogicalOperator::Transaction(n) => n.estimated_cardinality,
             LogicalOperator::ShowVar(n) => n.estimated_cardinality,
             LogicalOperator::AttachDatabase(n) => n.estimated_cardinality,
             LogicalOperator::DetachDatabase(n) => n.estimated_cardinality,
+            LogicalOperator::CreateSecret(n) => n.estimated_cardinality,
+            LogicalOperator::DropSecret(n) => n.estimated_cardinality,
             LogicalOperator::Drop(n) => n.estimated_cardinality,
+            LogicalOperator::AlterTable(n) => n.estimated_cardinality,
             LogicalOperator::Insert(n) => n.estimated_cardinality,
             LogicalOperator::CreateSchema(n) => n.estimated_cardinality,
             LogicalOperator::CreateTable(n) => n.estimated_cardinality,
             LogicalOperator::CreateView(n) => n.estimated_cardinality,
+            LogicalOperator::CreateFunction(n) => n.estimated_cardinality,
+            LogicalOperator::CreateMacro(n) => n.estimated_cardinality,
             LogicalOperator::Describe(n) => n.estimated_cardinality,
             LogicalOperator::Explain(n) => n.estimated_cardinality,
             LogicalOperator::CopyTo(n) => n.estimated_cardinality,
@@ -487,17 +679,24 @@ impl LogicalNode for LogicalOperator {
             LogicalOperator::SetOp(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::Empty(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::Limit(n) => n.get_output_table_refs(bind_context),
+            LogicalOperator::Sample(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::Order(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::SetVar(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::ResetVar(n) => n.get_output_table_refs(bind_context),
+            LogicalOperator::Transaction(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::ShowVar(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::AttachDatabase(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::DetachDatabase(n) => n.get_output_table_refs(bind_context),
+            LogicalOperator::CreateSecret(n) => n.get_output_table_refs(bind_context),
+            LogicalOperator::DropSecret(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::Drop(n) => n.get_output_table_refs(bind_context),
+            LogicalOperator::AlterTable(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::Insert(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::CreateSchema(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::CreateTable(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::CreateView(n) => n.get_output_table_refs(bind_context),
+            LogicalOperator::CreateFunction(n) => n.get_output_table_refs(bind_context),
+            LogicalOperator::CreateMacro(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::Describe(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::Explain(n) => n.get_output_table_refs(bind_context),
             LogicalOperator::CopyTo(n) => n.get_output_table_refs(bind_context),
@@ -527,17 +726,24 @@ impl LogicalNode for LogicalOperator {
             LogicalOperator::SetOp(n) => n.for_each_expr(func),
             LogicalOperator::Empty(n) => n.for_each_expr(func),
             LogicalOperator::Limit(n) => n.for_each_expr(func),
+            LogicalOperator::Sample(n) => n.for_each_expr(func),
             LogicalOperator::Order(n) => n.for_each_expr(func),
             LogicalOperator::SetVar(n) => n.for_each_expr(func),
             LogicalOperator::ResetVar(n) => n.for_each_expr(func),
+            LogicalOperator::Transaction(n) => n.for_each_expr(func),
             LogicalOperator::ShowVar(n) => n.for_each_expr(func),
             LogicalOperator::AttachDatabase(n) => n.for_each_expr(func),
             LogicalOperator::DetachDatabase(n) => n.for_each_expr(func),
+            LogicalOperator::CreateSecret(n) => n.for_each_expr(func),
+            LogicalOperator::DropSecret(n) => n.for_each_expr(func),
             LogicalOperator::Drop(n) => n.for_each_expr(func),
+            LogicalOperator::AlterTable(n) => n.for_each_expr(func),
             LogicalOperator::Insert(n) => n.for_each_expr(func),
             LogicalOperator::CreateSchema(n) => n.for_each_expr(func),
             LogicalOperator::CreateTable(n) => n.for_each_expr(func),
             LogicalOperator::CreateView(n) => n.for_each_expr(func),
+            LogicalOperator::CreateFunction(n) => n.for_each_expr(func),
+            LogicalOperator::CreateMacro(n) => n.for_each_expr(func),
             LogicalOperator::Describe(n) => n.for_each_expr(func),
             LogicalOperator::Explain(n) => n.for_each_expr(func),
             LogicalOperator::CopyTo(n) => n.for_each_expr(func),
@@ -567,17 +773,24 @@ impl LogicalNode for LogicalOperator {
             LogicalOperator::SetOp(n) => n.for_each_expr_mut(func),
             LogicalOperator::Empty(n) => n.for_each_expr_mut(func),
             LogicalOperator::Limit(n) => n.for_each_expr_mut(func),
+            LogicalOperator::Sample(n) => n.for_each_expr_mut(func),
             LogicalOperator::Order(n) => n.for_each_expr_mut(func),
             LogicalOperator::SetVar(n) => n.for_each_expr_mut(func),
             LogicalOperator::ResetVar(n) => n.for_each_expr_mut(func),
+            LogicalOperator::Transaction(n) => n.for_each_expr_mut(func),
             LogicalOperator::ShowVar(n) => n.for_each_expr_mut(func),
             LogicalOperator::AttachDatabase(n) => n.for_each_expr_mut(func),
             LogicalOperator::DetachDatabase(n) => n.for_each_expr_mut(func),
+            LogicalOperator::CreateSecret(n) => n.for_each_expr_mut(func),
+            LogicalOperator::DropSecret(n) => n.for_each_expr_mut(func),
             LogicalOperator::Drop(n) => n.for_each_expr_mut(func),
+            LogicalOperator::AlterTable(n) => n.for_each_expr_mut(func),
             LogicalOperator::Insert(n) => n.for_each_expr_mut(func),
             LogicalOperator::CreateSchema(n) => n.for_each_expr_mut(func),
             LogicalOperator::CreateTable(n) => n.for_each_expr_mut(func),
             LogicalOperator::CreateView(n) => n.for_each_expr_mut(func),
+            LogicalOperator::CreateFunction(n) => n.for_each_expr_mut(func),
+            LogicalOperator::CreateMacro(n) => n.for_each_expr_mut(func),
             LogicalOperator::Describe(n) => n.for_each_expr_mut(func),
             LogicalOperator::Explain(n) => n.for_each_expr_mut(func),
             LogicalOperator::CopyTo(n) => n.for_each_expr_mut(func),
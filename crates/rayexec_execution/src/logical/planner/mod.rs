@@ -39,4 +39,5 @@ mod plan_query;
 mod plan_select;
 mod plan_setop;
 mod plan_subquery;
+mod plan_summarize;
 mod plan_unnest;
@@ -28,6 +28,8 @@ impl CreateTablePlanner {
                 schema: create.schema,
                 name: create.name,
                 columns: create.columns,
+                checks: create.checks,
+                primary_key: create.primary_key,
                 on_conflict: create.on_conflict,
             },
             location: LocationRequirement::ClientLocal,
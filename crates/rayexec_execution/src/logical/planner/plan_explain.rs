@@ -23,8 +23,10 @@ impl ExplainPlanner {
                 analyze: explain.analyze,
                 verbose: explain.verbose,
                 format: explain.format,
+                bindings: explain.bindings,
                 logical_unoptimized: Box::new(plan.clone()),
                 logical_optimized: None,
+                optimized_steps: explain.optimized_steps.then(Vec::new),
             },
             location: LocationRequirement::Any,
             children: vec![plan],
@@ -22,6 +22,7 @@ use crate::logical::logical_join::{
 };
 use crate::logical::logical_materialization::LogicalMaterializationScan;
 use crate::logical::logical_project::LogicalProject;
+use crate::logical::logical_sample::LogicalSample;
 use crate::logical::logical_scan::{LogicalScan, ScanSource};
 use crate::logical::operator::{LocationRequirement, LogicalNode, LogicalOperator, Node};
 use crate::logical::statistics::StatisticsValue;
@@ -50,7 +51,7 @@ impl FromPlanner {
                 };
                 let estimated_cardinality = source.cardinality();
 
-                Ok(LogicalOperator::Scan(Node {
+                let scan = LogicalOperator::Scan(Node {
                     node: LogicalScan {
                         table_ref: table.table_ref,
                         types,
@@ -58,12 +59,26 @@ impl FromPlanner {
                         projection,
                         did_prune_columns: false,
                         scan_filters: Vec::new(),
+                        sample: None,
+                        limit: None,
+                        aggregate: None,
+                        index_scan: None,
                         source,
                     },
                     location: table.location,
                     children: Vec::new(),
                     estimated_cardinality,
-                }))
+                });
+
+                match table.policy {
+                    Some(policy) => Ok(LogicalOperator::Filter(Node {
+                        node: LogicalFilter { filter: policy },
+                        location: LocationRequirement::Any,
+                        children: vec![scan],
+                        estimated_cardinality: StatisticsValue::Unknown,
+                    })),
+                    None => Ok(scan),
+                }
             }
             BoundFromItem::Join(join) => self.plan_join(bind_context, join),
             BoundFromItem::TableFunction(func) => {
@@ -91,6 +106,10 @@ impl FromPlanner {
                                 projection,
                                 did_prune_columns: false,
                                 scan_filters: Vec::new(),
+                                sample: None,
+                                limit: None,
+                                aggregate: None,
+                                index_scan: None,
                                 source,
                             },
                             location: func.location,
@@ -204,6 +223,30 @@ impl FromPlanner {
                     estimated_cardinality: StatisticsValue::Unknown,
                 }))
             }
+            BoundFromItem::Sample(sample) => {
+                let mut plan = self.plan(bind_context, *sample.from)?;
+
+                // Push the sample into table scans, the data table can then
+                // decide how to best sample itself.
+                if let LogicalOperator::Scan(scan) = &mut plan {
+                    let is_table = matches!(
+                        scan.node.source,
+                        ScanSource::Table { .. } | ScanSource::TableFunction { .. }
+                    );
+                    if is_table && scan.node.sample.is_none() {
+                        scan.node.sample = Some(sample.sample);
+                        return Ok(plan);
+                    }
+                }
+
+                // Otherwise sample the output of the plan.
+                Ok(LogicalOperator::Sample(Node {
+                    node: LogicalSample::Table(sample.sample),
+                    location: LocationRequirement::Any,
+                    children: vec![plan],
+                    estimated_cardinality: StatisticsValue::Unknown,
+                }))
+            }
             BoundFromItem::Empty => Ok(LogicalOperator::Empty(Node {
                 node: LogicalEmpty,
                 location: LocationRequirement::Any,
@@ -347,6 +390,7 @@ impl FromPlanner {
             node: LogicalComparisonJoin {
                 join_type,
                 conditions: comparisons,
+                merge_keys: Vec::new(),
             },
             location: LocationRequirement::Any,
             children: vec![left, right],
@@ -36,6 +36,7 @@ impl InsertPlanner {
                 catalog: insert.table.catalog,
                 schema: insert.table.schema,
                 table: insert.table.entry,
+                checks: insert.checks,
             },
             location: insert.table_location,
             children: vec![source],
@@ -31,6 +31,10 @@ impl QueryPlanner {
                         projection: (0..table.num_columns()).collect(),
                         did_prune_columns: false,
                         scan_filters: Vec::new(),
+                        sample: None,
+                        limit: None,
+                        aggregate: None,
+                        index_scan: None,
                         source: ScanSource::ExpressionList { rows: values.rows },
                     },
                     location: LocationRequirement::Any,
@@ -38,7 +38,10 @@ impl SelectPlanner {
         }
 
         // Handle GROUP BY/aggregates
-        if !select.select_list.aggregates.is_empty() || select.group_by.is_some() {
+        if !select.select_list.aggregates.is_empty()
+            || select.group_by.is_some()
+            || select.having.is_some()
+        {
             let (mut group_exprs, group_table, grouping_sets) = match select.group_by {
                 Some(group_by) => (
                     group_by.expressions,
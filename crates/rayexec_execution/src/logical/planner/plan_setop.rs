@@ -1,7 +1,9 @@
 use rayexec_error::{RayexecError, Result};
 
+use crate::arrays::scalar::ScalarValue;
 use crate::expr::cast_expr::CastExpr;
 use crate::expr::column_expr::ColumnExpr;
+use crate::expr::literal_expr::LiteralExpr;
 use crate::expr::Expression;
 use crate::logical::binder::bind_context::{BindContext, BindScopeRef};
 use crate::logical::binder::bind_query::bind_setop::{BoundSetOp, SetOpCastRequirement};
@@ -26,19 +28,51 @@ impl SetOpPlanner {
         let mut left = QueryPlanner.plan(bind_context, *setop.left)?;
         let mut right = QueryPlanner.plan(bind_context, *setop.right)?;
 
+        let (left_mapping, right_mapping) = match &setop.column_mapping {
+            Some(mapping) => (
+                Some(mapping.left.as_slice()),
+                Some(mapping.right.as_slice()),
+            ),
+            None => (None, None),
+        };
+
         match setop.cast_req {
             SetOpCastRequirement::LeftNeedsCast(left_cast_ref) => {
-                left = self.wrap_cast(bind_context, left, setop.left_scope, left_cast_ref)?;
+                left = self.wrap_cast(
+                    bind_context,
+                    left,
+                    setop.left_scope,
+                    left_cast_ref,
+                    left_mapping,
+                )?;
             }
             SetOpCastRequirement::RightNeedsCast(right_cast_ref) => {
-                right = self.wrap_cast(bind_context, right, setop.right_scope, right_cast_ref)?;
+                right = self.wrap_cast(
+                    bind_context,
+                    right,
+                    setop.right_scope,
+                    right_cast_ref,
+                    right_mapping,
+                )?;
             }
             SetOpCastRequirement::BothNeedsCast {
                 left_cast_ref,
                 right_cast_ref,
             } => {
-                left = self.wrap_cast(bind_context, left, setop.left_scope, left_cast_ref)?;
-                right = self.wrap_cast(bind_context, right, setop.right_scope, right_cast_ref)?;
+                left = self.wrap_cast(
+                    bind_context,
+                    left,
+                    setop.left_scope,
+                    left_cast_ref,
+                    left_mapping,
+                )?;
+                right = self.wrap_cast(
+                    bind_context,
+                    right,
+                    setop.right_scope,
+                    right_cast_ref,
+                    right_mapping,
+                )?;
             }
             SetOpCastRequirement::None => (),
         }
@@ -88,6 +122,7 @@ impl SetOpPlanner {
         orig_plan: LogicalOperator,
         orig_scope: BindScopeRef,
         cast_table_ref: TableRef,
+        mapping: Option<&[Option<usize>]>,
     ) -> Result<LogicalOperator> {
         let orig_table = self.get_original_table(bind_context, orig_scope)?;
 
@@ -97,6 +132,7 @@ impl SetOpPlanner {
                     bind_context,
                     orig_table,
                     cast_table_ref,
+                    mapping,
                 )?,
                 projection_table: cast_table_ref,
             },
@@ -125,25 +161,48 @@ impl SetOpPlanner {
         Ok(table)
     }
 
+    /// Generate the casting projections for one side of the set operation.
+    ///
+    /// If a mapping is provided, output columns are pulled from the original
+    /// table according to the mapping, with unmapped columns producing NULLs.
     fn generate_cast_expressions(
         &self,
         bind_context: &BindContext,
         orig_table: &Table,
         cast_table_ref: TableRef,
+        mapping: Option<&[Option<usize>]>,
     ) -> Result<Vec<Expression>> {
         let cast_table = bind_context.get_table(cast_table_ref)?;
 
-        let mut cast_exprs = Vec::with_capacity(orig_table.column_types.len());
+        let mut cast_exprs = Vec::with_capacity(cast_table.column_types.len());
+
+        for (out_idx, need_type) in cast_table.column_types.iter().enumerate() {
+            let orig_idx = match mapping {
+                Some(mapping) => mapping[out_idx],
+                None => Some(out_idx),
+            };
+
+            let orig_idx = match orig_idx {
+                Some(idx) => idx,
+                None => {
+                    // Column doesn't exist on this side, fill with NULLs.
+                    cast_exprs.push(Expression::Cast(CastExpr {
+                        to: need_type.clone(),
+                        expr: Box::new(Expression::Literal(LiteralExpr {
+                            literal: ScalarValue::Null,
+                        })),
+                    }));
+                    continue;
+                }
+            };
+
+            let orig_type = orig_table.column_types.get(orig_idx).ok_or_else(|| {
+                RayexecError::new(format!("Missing column {orig_idx} in set operation input"))
+            })?;
 
-        for (idx, (orig_type, need_type)) in orig_table
-            .column_types
-            .iter()
-            .zip(&cast_table.column_types)
-            .enumerate()
-        {
             let col_expr = Expression::Column(ColumnExpr {
                 table_scope: orig_table.reference,
-                column: idx,
+                column: orig_idx,
             });
 
             if orig_type == need_type {
@@ -5,6 +5,7 @@ use super::plan_create_table::CreateTablePlanner;
 use super::plan_explain::ExplainPlanner;
 use super::plan_insert::InsertPlanner;
 use super::plan_query::QueryPlanner;
+use super::plan_summarize::SummarizePlanner;
 use crate::logical::binder::bind_attach::{BoundAttach, BoundDetach};
 use crate::logical::binder::bind_context::BindContext;
 use crate::logical::binder::bind_statement::BoundStatement;
@@ -24,6 +25,7 @@ impl StatementPlanner {
             BoundStatement::SetVar(plan) => Ok(LogicalOperator::SetVar(plan)),
             BoundStatement::ShowVar(plan) => Ok(LogicalOperator::ShowVar(plan)),
             BoundStatement::ResetVar(plan) => Ok(LogicalOperator::ResetVar(plan)),
+            BoundStatement::Transaction(plan) => Ok(LogicalOperator::Transaction(plan)),
             BoundStatement::Attach(BoundAttach::Database(plan)) => {
                 Ok(LogicalOperator::AttachDatabase(plan))
             }
@@ -31,11 +33,17 @@ impl StatementPlanner {
                 Ok(LogicalOperator::DetachDatabase(plan))
             }
             BoundStatement::Drop(plan) => Ok(LogicalOperator::Drop(plan)),
+            BoundStatement::CreateSecret(plan) => Ok(LogicalOperator::CreateSecret(plan)),
+            BoundStatement::DropSecret(plan) => Ok(LogicalOperator::DropSecret(plan)),
+            BoundStatement::AlterTable(plan) => Ok(LogicalOperator::AlterTable(plan)),
             BoundStatement::Insert(insert) => InsertPlanner.plan(bind_context, insert),
             BoundStatement::CreateSchema(plan) => Ok(LogicalOperator::CreateSchema(plan)),
             BoundStatement::CreateTable(create) => CreateTablePlanner.plan(bind_context, create),
             BoundStatement::CreateView(create) => Ok(LogicalOperator::CreateView(create)),
+            BoundStatement::CreateFunction(create) => Ok(LogicalOperator::CreateFunction(create)),
+            BoundStatement::CreateMacro(create) => Ok(LogicalOperator::CreateMacro(create)),
             BoundStatement::Describe(plan) => Ok(LogicalOperator::Describe(plan)),
+            BoundStatement::Summarize(summarize) => SummarizePlanner.plan(bind_context, summarize),
             BoundStatement::Explain(explain) => ExplainPlanner.plan(bind_context, explain),
             BoundStatement::CopyTo(copy_to) => CopyPlanner.plan(bind_context, copy_to),
         }
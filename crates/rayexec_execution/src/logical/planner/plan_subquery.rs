@@ -5,6 +5,7 @@ use rayexec_error::{not_implemented, RayexecError, Result};
 use crate::arrays::datatype::DataType;
 use crate::arrays::scalar::ScalarValue;
 use crate::expr::aggregate_expr::AggregateExpr;
+use crate::expr::cast_expr::CastExpr;
 use crate::expr::column_expr::ColumnExpr;
 use crate::expr::comparison_expr::{ComparisonExpr, ComparisonOperator};
 use crate::expr::literal_expr::LiteralExpr;
@@ -210,14 +211,24 @@ impl SubqueryPlanner {
 
                 Ok(visited_expr)
             }
-            SubqueryType::Any { expr, op } => {
+            SubqueryType::Any {
+                expr,
+                op,
+                cast_output,
+            } => {
                 // Similar to EXISTS, just with an extra join condition
                 // representing the ANY condition.
 
-                let right_out = Expression::Column(ColumnExpr {
+                let mut right_out = Expression::Column(ColumnExpr {
                     table_scope: right.get_output_table_refs(bind_context)[0],
                     column: 0,
                 });
+                if let Some(to) = cast_output {
+                    right_out = Expression::Cast(CastExpr {
+                        to: to.clone(),
+                        expr: Box::new(right_out),
+                    });
+                }
 
                 let mark_table = bind_context.new_ephemeral_table()?;
                 bind_context.push_column_for_table(
@@ -438,6 +449,7 @@ impl SubqueryPlanner {
                                 )?,
                                 distinct: false,
                                 filter: None,
+                                order_by: Vec::new(),
                             })],
                             group_table: None,
                             group_exprs: Vec::new(),
@@ -474,7 +486,11 @@ impl SubqueryPlanner {
                     column: 0,
                 }))
             }
-            SubqueryType::Any { expr, op } => {
+            SubqueryType::Any {
+                expr,
+                op,
+                cast_output,
+            } => {
                 // Any subquery.
                 //
                 // Join original plan (left) with subquery (right) with
@@ -491,14 +507,20 @@ impl SubqueryPlanner {
                 )?;
 
                 let subquery_table = subquery_plan.get_output_table_refs(bind_context)[0];
-                let column = ColumnExpr {
+                let mut right = Expression::Column(ColumnExpr {
                     table_scope: subquery_table,
                     column: 0,
-                };
+                });
+                if let Some(to) = cast_output {
+                    right = Expression::Cast(CastExpr {
+                        to: to.clone(),
+                        expr: Box::new(right),
+                    });
+                }
 
                 let condition = ComparisonCondition {
                     left: expr.as_ref().clone(),
-                    right: Expression::Column(column),
+                    right,
                     op: *op,
                 };
 
@@ -509,6 +531,7 @@ impl SubqueryPlanner {
                             table_ref: mark_table,
                         },
                         conditions: vec![condition],
+                        merge_keys: Vec::new(),
                     },
                     location: LocationRequirement::Any,
                     children: vec![orig, subquery_plan],
@@ -921,6 +944,7 @@ impl DependentJoinPushdown {
                     ScanSource::Table { .. }
                         | ScanSource::View { .. }
                         | ScanSource::TableFunction { .. }
+                        | ScanSource::Query { .. }
                 ) {
                     return Err(RayexecError::new(
                         "Unexpectedly reached scan node when pushing down dependent join",
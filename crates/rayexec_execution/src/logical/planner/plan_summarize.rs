@@ -0,0 +1,54 @@
+use rayexec_error::Result;
+
+use super::plan_unnest::UnnestPlanner;
+use crate::logical::binder::bind_context::BindContext;
+use crate::logical::binder::bind_summarize::{BoundSummarize, BoundSummarizeSource};
+use crate::logical::logical_aggregate::LogicalAggregate;
+use crate::logical::logical_project::LogicalProject;
+use crate::logical::operator::{LocationRequirement, LogicalOperator, Node};
+use crate::logical::planner::plan_from::FromPlanner;
+use crate::logical::planner::plan_query::QueryPlanner;
+use crate::logical::statistics::StatisticsValue;
+
+#[derive(Debug)]
+pub struct SummarizePlanner;
+
+impl SummarizePlanner {
+    pub fn plan(
+        &self,
+        bind_context: &mut BindContext,
+        summarize: BoundSummarize,
+    ) -> Result<LogicalOperator> {
+        let source = match summarize.source {
+            BoundSummarizeSource::Query(query) => QueryPlanner.plan(bind_context, *query)?,
+            BoundSummarizeSource::Table(table) => FromPlanner.plan(bind_context, *table)?,
+        };
+
+        let agg = LogicalOperator::Aggregate(Node {
+            node: LogicalAggregate {
+                aggregates_table: summarize.aggregates_table,
+                aggregates: summarize.aggregates,
+                group_table: None,
+                group_exprs: Vec::new(),
+                grouping_sets: None,
+                grouping_functions_table: None,
+                grouping_functions: Vec::new(),
+            },
+            location: LocationRequirement::Any,
+            children: vec![source],
+            estimated_cardinality: StatisticsValue::Unknown,
+        });
+
+        let project = LogicalOperator::Project(Node {
+            node: LogicalProject {
+                projections: summarize.projections,
+                projection_table: summarize.projection_table,
+            },
+            location: LocationRequirement::Any,
+            children: vec![agg],
+            estimated_cardinality: StatisticsValue::Unknown,
+        });
+
+        UnnestPlanner.plan_unnests(bind_context, project)
+    }
+}
@@ -1,14 +1,15 @@
 use std::collections::HashMap;
 
-use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_error::{not_implemented, ErrorKind, RayexecError, Result};
 use rayexec_parser::ast::{self, FunctionArg, ReplaceColumn};
 use rayexec_parser::meta::Raw;
+use rayexec_parser::parser;
 
 use super::resolve_normal::create_user_facing_resolve_err;
 use super::resolved_function::{ResolvedFunction, SpecialBuiltinFunction};
 use super::resolved_table_function::ConstantFunctionArgs;
 use super::{ResolveContext, ResolvedMeta, Resolver};
-use crate::database::catalog_entry::CatalogEntryType;
+use crate::database::catalog_entry::{CatalogEntry, CatalogEntryType};
 use crate::logical::binder::expr_binder::BaseExpressionBinder;
 use crate::logical::operator::LocationRequirement;
 
@@ -209,7 +210,14 @@ impl<'a> ExpressionResolver<'a> {
         // check_stack_redline("resolve expression")?;
 
         match expr {
-            ast::Expr::Ident(ident) => Ok(ast::Expr::Ident(ident)),
+            ast::Expr::Ident(ident) => {
+                // Parameters of a macro being expanded take precedence over
+                // columns.
+                match resolve_context.find_macro_arg(&ident.as_normalized_string()) {
+                    Some(arg) => Ok(arg.clone()),
+                    None => Ok(ast::Expr::Ident(ident)),
+                }
+            }
             ast::Expr::CompoundIdent(idents) => Ok(ast::Expr::CompoundIdent(idents)),
             ast::Expr::Literal(lit) => Ok(ast::Expr::Literal(match lit {
                 ast::Literal::Number(s) => ast::Literal::Number(s),
@@ -266,6 +274,13 @@ impl<'a> ExpressionResolver<'a> {
                 let expr = Box::pin(self.resolve_expression(*expr, resolve_context)).await?;
                 Ok(ast::Expr::Nested(Box::new(expr)))
             }
+            ast::Expr::Collate { expr, collation } => {
+                let expr = Box::pin(self.resolve_expression(*expr, resolve_context)).await?;
+                Ok(ast::Expr::Collate {
+                    expr: Box::new(expr),
+                    collation,
+                })
+            }
             ast::Expr::Interval(ast::Interval {
                 value,
                 leading,
@@ -646,13 +661,45 @@ impl<'a> ExpressionResolver<'a> {
             Some(over) => Some(self.resolve_window_spec(over, resolve_context).await?),
             None => None,
         };
+
+        let mut order_by = Vec::with_capacity(func.order_by.len());
+        for node in func.order_by {
+            order_by.push(ast::OrderByNode {
+                typ: node.typ,
+                nulls: node.nulls,
+                expr: Box::pin(self.resolve_expression(node.expr, resolve_context)).await?,
+            });
+        }
+
+        let within_group = match func.within_group {
+            Some(nodes) => {
+                let mut within_group = Vec::with_capacity(nodes.len());
+                for node in nodes {
+                    within_group.push(ast::OrderByNode {
+                        typ: node.typ,
+                        nulls: node.nulls,
+                        expr: Box::pin(self.resolve_expression(node.expr, resolve_context)).await?,
+                    });
+                }
+                Some(within_group)
+            }
+            None => None,
+        };
+        let has_modifiers = func.distinct
+            || !order_by.is_empty()
+            || within_group.is_some()
+            || filter.is_some()
+            || over.is_some();
         let args = Box::pin(self.resolve_function_args(func.args, resolve_context)).await?;
 
         let schema_ent = context
             .get_database(&catalog)?
             .catalog
             .get_schema(self.resolver.tx, &schema)?
-            .ok_or_else(|| RayexecError::new(format!("Missing schema: {schema}")))?;
+            .ok_or_else(|| {
+                RayexecError::new(format!("Missing schema: {schema}"))
+                    .with_kind(ErrorKind::SchemaNotFound)
+            })?;
 
         // Check if this is a special function.
         if let Some(special) = SpecialBuiltinFunction::try_from_name(&func_name) {
@@ -664,6 +711,8 @@ impl<'a> ExpressionResolver<'a> {
                 reference: resolve_idx,
                 distinct: func.distinct,
                 args,
+                order_by,
+                within_group,
                 filter,
                 over,
             })));
@@ -682,6 +731,8 @@ impl<'a> ExpressionResolver<'a> {
                 reference: resolve_idx,
                 distinct: func.distinct,
                 args,
+                order_by,
+                within_group,
                 filter,
                 over,
             })));
@@ -703,11 +754,55 @@ impl<'a> ExpressionResolver<'a> {
                 reference: resolve_idx,
                 distinct: func.distinct,
                 args,
+                order_by,
+                within_group,
                 filter,
                 over,
             })));
         }
 
+        if let Some(ent) = schema_ent.get_scalar_macro(self.resolver.tx, &func_name)? {
+            return self
+                .expand_scalar_macro(&ent, has_modifiers, args, resolve_context)
+                .await;
+        }
+
+        // Unqualified functions not in the system catalog may have been
+        // registered on the session (e.g. UDFs or macros). Check the temp
+        // schema for those.
+        if func.reference.0.len() == 1 {
+            let temp_ent = context
+                .get_database("temp")?
+                .catalog
+                .get_schema(self.resolver.tx, "temp")?;
+
+            if let Some(temp_ent) = temp_ent {
+                if let Some(scalar) = temp_ent.get_scalar_function(self.resolver.tx, &func_name)? {
+                    let resolve_idx = resolve_context.functions.push_resolved(
+                        ResolvedFunction::Scalar(
+                            scalar.try_as_scalar_function_entry()?.function.clone(),
+                        ),
+                        LocationRequirement::ClientLocal,
+                    );
+                    return Ok(ast::Expr::Function(Box::new(ast::Function {
+                        reference: resolve_idx,
+                        distinct: func.distinct,
+                        args,
+                        order_by,
+                        within_group,
+                        filter,
+                        over,
+                    })));
+                }
+
+                if let Some(ent) = temp_ent.get_scalar_macro(self.resolver.tx, &func_name)? {
+                    return self
+                        .expand_scalar_macro(&ent, has_modifiers, args, resolve_context)
+                        .await;
+                }
+            }
+        }
+
         Err(create_user_facing_resolve_err(
             self.resolver.tx,
             Some(&schema_ent),
@@ -719,6 +814,36 @@ impl<'a> ExpressionResolver<'a> {
         ))
     }
 
+    /// Expand a call to a scalar macro by resolving the macro body with the
+    /// already resolved arguments substituted for the parameters.
+    ///
+    /// `has_modifiers` indicates the call used DISTINCT, WITHIN GROUP, FILTER,
+    /// or OVER, none of which apply to macros.
+    async fn expand_scalar_macro(
+        &self,
+        ent: &CatalogEntry,
+        has_modifiers: bool,
+        args: Vec<ast::FunctionArg<ResolvedMeta>>,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<ast::Expr<ResolvedMeta>> {
+        if has_modifiers {
+            return Err(RayexecError::new(format!(
+                "DISTINCT, ORDER BY, WITHIN GROUP, FILTER, and OVER cannot be used with macro '{}'",
+                ent.name
+            )));
+        }
+
+        let macro_ent = ent.try_as_macro_entry()?;
+        let args = macro_args(&ent.name, &macro_ent.parameters, args)?;
+        let body = parser::parse_expr(&macro_ent.body_sql)?;
+
+        resolve_context.push_macro_args(args)?;
+        let expr = Box::pin(self.resolve_expression(body, resolve_context)).await;
+        resolve_context.pop_macro_args();
+
+        expr
+    }
+
     pub(crate) async fn resolve_function_args(
         &self,
         args: Vec<ast::FunctionArg<Raw>>,
@@ -843,3 +968,31 @@ impl<'a> ExpressionResolver<'a> {
         }
     }
 }
+
+/// Match up arguments to a macro call with the macro's parameters.
+pub(crate) fn macro_args(
+    name: &str,
+    parameters: &[String],
+    args: Vec<ast::FunctionArg<ResolvedMeta>>,
+) -> Result<HashMap<String, ast::Expr<ResolvedMeta>>> {
+    if args.len() != parameters.len() {
+        return Err(RayexecError::new(format!(
+            "Macro '{name}' expects {} arguments, got {}",
+            parameters.len(),
+            args.len()
+        )));
+    }
+
+    parameters
+        .iter()
+        .zip(args)
+        .map(|(param, arg)| match arg {
+            ast::FunctionArg::Unnamed {
+                arg: ast::FunctionArgExpr::Expr(expr),
+            } => Ok((param.clone(), expr)),
+            _ => Err(RayexecError::new(format!(
+                "Macro '{name}' only accepts positional arguments"
+            ))),
+        })
+        .collect()
+}
@@ -10,7 +10,7 @@ pub mod resolved_table_function;
 
 use std::collections::HashMap;
 
-use expr_resolver::ExpressionResolver;
+use expr_resolver::{macro_args, ExpressionResolver};
 use rayexec_error::{OptionExt, RayexecError, Result};
 use rayexec_io::location::FileLocation;
 use rayexec_parser::ast::{self, ColumnDef, ObjectReference};
@@ -28,7 +28,13 @@ use serde::{Deserialize, Serialize};
 use super::binder::constant_binder::ConstantBinder;
 use super::binder::expr_binder::BaseExpressionBinder;
 use super::binder::table_list::TableAlias;
-use crate::arrays::datatype::{DataType, DecimalTypeMeta, TimeUnit, TimestampTypeMeta};
+use crate::arrays::datatype::{
+    DataType,
+    DecimalTypeMeta,
+    ListTypeMeta,
+    TimeUnit,
+    TimestampTypeMeta,
+};
 use crate::arrays::scalar::decimal::{Decimal128Type, Decimal64Type, DecimalType};
 use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
 use crate::database::builtin_views::{
@@ -38,13 +44,15 @@ use crate::database::builtin_views::{
     SHOW_TABLES_VIEW,
 };
 use crate::database::catalog::CatalogTx;
-use crate::database::catalog_entry::{CatalogEntryInner, CatalogEntryType};
+use crate::database::catalog_entry::{CatalogEntry, CatalogEntryInner, CatalogEntryType};
 use crate::database::DatabaseContext;
 use crate::datasource::FileHandlers;
+use crate::engine::PERSISTENT_CATALOG;
 use crate::functions::copy::CopyToArgs;
 use crate::functions::proto::FUNCTION_LOOKUP_CATALOG;
-use crate::functions::table::TableFunctionPlanner;
+use crate::functions::table::{TableFunction, TableFunctionPlanner};
 use crate::logical::operator::LocationRequirement;
+use crate::storage::disk::DEFAULT_DISK_SCHEMA;
 
 /// An AST statement with references bound to data inside of the `resolve_context`.
 pub type ResolvedStatement = Statement<ResolvedMeta>;
@@ -68,8 +76,8 @@ impl AstMeta for ResolvedMeta {
     type CopyToOptions = CopyToArgs;
     /// SHOW statements will be converted to views if need during the resolve
     /// step (e.g. for SHOW DATABASES). If we produce a resolved SHOW, it will
-    /// always be pointing to a variable.
-    type ShowReference = ItemReference;
+    /// always be pointing to a variable, or all variables.
+    type ShowReference = ast::VariableOrAll<ResolvedMeta>;
 }
 
 /// Options for a resolved subquery.
@@ -115,6 +123,12 @@ impl ResolveMode {
 #[derive(Debug)]
 pub struct ResolveConfig {
     pub enable_function_chaining: bool,
+    /// Role of the session, used to pick which row-level security policies
+    /// apply.
+    pub role: String,
+    /// If passthrough table functions are allowed, see
+    /// `AllowedStatements::allows_passthrough`.
+    pub allow_passthrough: bool,
 }
 
 /// Resolves references in a raw SQL AST with entries in the catalog.
@@ -144,6 +158,21 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    /// Error if the function is a passthrough function and those aren't
+    /// allowed.
+    ///
+    /// Checked before planning since planning may already connect to the
+    /// external system.
+    fn check_passthrough(&self, function: &dyn TableFunction) -> Result<()> {
+        if function.is_passthrough() && !self.config.allow_passthrough {
+            return Err(RayexecError::new(format!(
+                "Table function '{}' is not allowed in this session",
+                function.name()
+            )));
+        }
+        Ok(())
+    }
+
     pub async fn resolve_statement(
         self,
         stmt: RawStatement,
@@ -159,6 +188,8 @@ impl<'a> Resolver<'a> {
                 Statement::Explain(ast::ExplainNode {
                     analyze: explain.analyze,
                     verbose: explain.verbose,
+                    bindings: explain.bindings,
+                    optimized_steps: explain.optimized_steps,
                     body,
                     output: explain.output,
                 })
@@ -174,6 +205,14 @@ impl<'a> Resolver<'a> {
                     self.resolve_from(from, &mut resolve_context).await?,
                 )),
             },
+            Statement::Summarize(summarize) => match summarize {
+                ast::Summarize::Query(query) => Statement::Summarize(ast::Summarize::Query(
+                    self.resolve_query(query, &mut resolve_context).await?,
+                )),
+                ast::Summarize::FromNode(from) => Statement::Summarize(ast::Summarize::FromNode(
+                    self.resolve_from(from, &mut resolve_context).await?,
+                )),
+            },
             Statement::Query(query) => {
                 Statement::Query(self.resolve_query(query, &mut resolve_context).await?)
             }
@@ -192,11 +231,37 @@ impl<'a> Resolver<'a> {
                 Statement::CreateSchema(self.resolve_create_schema(create).await?)
             }
             Statement::Drop(drop) => Statement::Drop(self.resolve_drop(drop).await?),
+            Statement::AlterTable(alter) => Statement::AlterTable(
+                self.resolve_alter_table(alter, &mut resolve_context)
+                    .await?,
+            ),
+            Statement::CreateSecret(create) => Statement::CreateSecret(
+                self.resolve_create_secret(create, &mut resolve_context)
+                    .await?,
+            ),
+            Statement::CreateFunction(create) => {
+                Statement::CreateFunction(self.resolve_create_function(create)?)
+            }
+            Statement::CreateMacro(create) => {
+                Statement::CreateMacro(self.resolve_create_macro(create)?)
+            }
+            Statement::CreateIndex(create) => Statement::CreateIndex(ast::CreateIndex {
+                if_not_exists: create.if_not_exists,
+                name: create.name,
+                table: self.resolve_alter_target(create.table)?,
+                using: create.using,
+                columns: create.columns,
+            }),
+            Statement::CreatePolicy(create) => Statement::CreatePolicy(
+                self.resolve_create_policy(create, &mut resolve_context)
+                    .await?,
+            ),
             Statement::SetVariable(set) => Statement::SetVariable(ast::SetVariable {
                 reference: Self::reference_to_strings(set.reference).into(),
                 value: ExpressionResolver::new(&self)
                     .resolve_expression(set.value, &mut resolve_context)
                     .await?,
+                local: set.local,
             }),
             Statement::Show(show) => self.resolve_show(show, &mut resolve_context).await?,
             Statement::ResetVariable(reset) => Statement::ResetVariable(ast::ResetVariable {
@@ -211,6 +276,7 @@ impl<'a> Resolver<'a> {
                 Statement::Attach(self.resolve_attach(attach, &mut resolve_context).await?)
             }
             Statement::Detach(detach) => Statement::Detach(self.resolve_detach(detach).await?),
+            Statement::Transaction(tx) => Statement::Transaction(tx),
         };
 
         Ok((bound, resolve_context))
@@ -246,7 +312,10 @@ impl<'a> Resolver<'a> {
 
         match show.reference {
             ast::ShowReference::Variable(var) => Ok(Statement::Show(ast::Show {
-                reference: Self::reference_to_strings(var).into(),
+                reference: ast::VariableOrAll::Variable(Self::reference_to_strings(var).into()),
+            })),
+            ast::ShowReference::All => Ok(Statement::Show(ast::Show {
+                reference: ast::VariableOrAll::All,
             })),
             ast::ShowReference::Databases => {
                 let query = get_view_query(SHOW_DATABASES_VIEW)?;
@@ -326,16 +395,15 @@ impl<'a> Resolver<'a> {
                                 MaybeResolved::Unresolved(unbound)
                             }
                             MaybeResolvedTable::Unresolved => {
-                                return Err(RayexecError::new(format!(
-                                    "Missing table or view for reference '{}'",
-                                    reference
-                                )))
+                                return Err(NormalResolver::new(self.tx, self.context)
+                                    .missing_table_err(&reference, resolve_context))
                             }
                         }
                     }
                 };
 
                 let idx = resolve_context.tables.push_maybe_resolved(table);
+                self.resolve_table_policies(idx, resolve_context).await?;
                 ast::CopyToSource::Table(idx)
             }
         };
@@ -365,6 +433,7 @@ impl<'a> Resolver<'a> {
             options.insert(key, val);
         }
 
+        self.context.secrets().apply_secret(&mut options)?;
         let mut options = CopyToArgs { named: options };
 
         let target = match copy_to.target {
@@ -431,9 +500,16 @@ impl<'a> Resolver<'a> {
         match drop.drop_type {
             ast::DropType::Schema => {
                 if name.0.len() == 1 {
-                    name.0.insert(0, "temp".to_string()); // Catalog
+                    if self.context.database_exists(PERSISTENT_CATALOG) {
+                        name.0.insert(0, PERSISTENT_CATALOG.to_string()); // Catalog
+                    } else {
+                        name.0.insert(0, "temp".to_string()); // Catalog
+                    }
                 }
             }
+            ast::DropType::Secret => {
+                // Secrets aren't catalog items.
+            }
             _ => {
                 if name.0.len() == 1 {
                     name.0.insert(0, "temp".to_string()); // Schema
@@ -453,6 +529,173 @@ impl<'a> Resolver<'a> {
         })
     }
 
+    /// Resolve the name of a table being altered to a fully qualified
+    /// reference.
+    fn resolve_alter_target(&self, name: ast::ObjectReference) -> Result<ItemReference> {
+        // TODO: Search path.
+        let mut name: ItemReference = Self::reference_to_strings(name).into();
+        if name.0.len() <= 2 {
+            // Alter the persistent table if there's no temp table shadowing
+            // it.
+            let (schema, table) = match name.0.as_slice() {
+                [table] => (None, table.as_str()),
+                [schema, table] => (Some(schema.as_str()), table.as_str()),
+                _ => return Err(RayexecError::new("Empty table name")),
+            };
+            let persistent = NormalResolver::new(self.tx, self.context)
+                .resolve_persistent_table(schema, table)?;
+
+            match persistent {
+                Some(persistent) => {
+                    name = ItemReference(vec![
+                        persistent.catalog,
+                        persistent.schema,
+                        table.to_string(),
+                    ]);
+                }
+                None => {
+                    if name.0.len() == 1 {
+                        name.0.insert(0, "temp".to_string()); // Schema
+                    }
+                    name.0.insert(0, "temp".to_string()); // Catalog
+                }
+            }
+        }
+
+        Ok(name)
+    }
+
+    async fn resolve_alter_table(
+        &self,
+        alter: ast::AlterTable<Raw>,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<ast::AlterTable<ResolvedMeta>> {
+        let name = self.resolve_alter_target(alter.name)?;
+
+        let operation = match alter.operation {
+            ast::AlterTableOperation::AddColumn {
+                if_not_exists,
+                column,
+                default,
+            } => {
+                let default = match default {
+                    Some(default) => Some(
+                        ExpressionResolver::new(self)
+                            .resolve_expression(default, resolve_context)
+                            .await?,
+                    ),
+                    None => None,
+                };
+
+                ast::AlterTableOperation::AddColumn {
+                    if_not_exists,
+                    column: self.resolve_column_def(column, resolve_context).await?,
+                    default,
+                }
+            }
+            ast::AlterTableOperation::DropColumn { if_exists, name } => {
+                ast::AlterTableOperation::DropColumn { if_exists, name }
+            }
+            ast::AlterTableOperation::RenameColumn { from, to } => {
+                ast::AlterTableOperation::RenameColumn { from, to }
+            }
+        };
+
+        Ok(ast::AlterTable {
+            if_exists: alter.if_exists,
+            name,
+            operation,
+        })
+    }
+
+    async fn resolve_create_secret(
+        &self,
+        create: ast::CreateSecret<Raw>,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<ast::CreateSecret<ResolvedMeta>> {
+        let mut options = HashMap::new();
+        for (k, v) in create.options {
+            let v = ExpressionResolver::new(self)
+                .resolve_expression(v, resolve_context)
+                .await?;
+            options.insert(k, v);
+        }
+
+        Ok(ast::CreateSecret {
+            or_replace: create.or_replace,
+            if_not_exists: create.if_not_exists,
+            persistent: create.persistent,
+            name: create.name,
+            secret_type: create.secret_type,
+            options,
+        })
+    }
+
+    fn resolve_create_function(
+        &self,
+        create: ast::CreateFunction<Raw>,
+    ) -> Result<ast::CreateFunction<ResolvedMeta>> {
+        // Functions are always created in the session's temp schema.
+        let mut name: ItemReference = Self::reference_to_strings(create.name).into();
+        if name.0.len() == 1 {
+            name.0.insert(0, "temp".to_string()); // Schema
+            name.0.insert(0, "temp".to_string()); // Catalog
+        }
+        if name.0.len() == 2 {
+            name.0.insert(0, "temp".to_string()); // Catalog
+        }
+
+        let args = create
+            .args
+            .into_iter()
+            .map(|arg| {
+                Ok(ast::FunctionArgDef::<ResolvedMeta> {
+                    name: arg.name,
+                    datatype: Self::ast_datatype_to_exec_datatype(arg.datatype)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let return_type = create
+            .return_type
+            .map(Self::ast_datatype_to_exec_datatype)
+            .transpose()?;
+
+        Ok(ast::CreateFunction {
+            or_replace: create.or_replace,
+            temp: create.temp,
+            name,
+            args,
+            return_type,
+            language: create.language,
+            body: create.body,
+        })
+    }
+
+    fn resolve_create_macro(
+        &self,
+        create: ast::CreateMacro<Raw>,
+    ) -> Result<ast::CreateMacro<ResolvedMeta>> {
+        // Macros are always created in the session's temp schema, the body
+        // is only resolved when the macro is called.
+        let mut name: ItemReference = Self::reference_to_strings(create.name).into();
+        if name.0.len() == 1 {
+            name.0.insert(0, "temp".to_string()); // Schema
+            name.0.insert(0, "temp".to_string()); // Catalog
+        }
+        if name.0.len() == 2 {
+            name.0.insert(0, "temp".to_string()); // Catalog
+        }
+
+        Ok(ast::CreateMacro {
+            or_replace: create.or_replace,
+            temp: create.temp,
+            name,
+            params: create.params,
+            body: create.body,
+        })
+    }
+
     async fn resolve_create_schema(
         &self,
         create: ast::CreateSchema<Raw>,
@@ -460,7 +703,11 @@ impl<'a> Resolver<'a> {
         // TODO: Search path.
         let mut name: ItemReference = Self::reference_to_strings(create.name).into();
         if name.0.len() == 1 {
-            name.0.insert(0, "temp".to_string()); // Catalog
+            if self.context.database_exists(PERSISTENT_CATALOG) {
+                name.0.insert(0, PERSISTENT_CATALOG.to_string()); // Catalog
+            } else {
+                name.0.insert(0, "temp".to_string()); // Catalog
+            }
         }
 
         Ok(ast::CreateSchema {
@@ -484,23 +731,24 @@ impl<'a> Resolver<'a> {
             if name.0.len() == 2 {
                 name.0.insert(0, "temp".to_string()); // Catalog
             }
+        } else if self.context.database_exists(PERSISTENT_CATALOG) {
+            if name.0.len() == 1 {
+                name.0.insert(0, DEFAULT_DISK_SCHEMA.to_string()); // Schema
+                name.0.insert(0, PERSISTENT_CATALOG.to_string()); // Catalog
+            }
+            if name.0.len() == 2 {
+                name.0.insert(0, PERSISTENT_CATALOG.to_string()); // Catalog
+            }
         } else {
             return Err(RayexecError::new(
                 "Persistent tables not yet supported, use CREATE TEMP TABLE",
             ));
         }
 
-        let columns = create
-            .columns
-            .into_iter()
-            .map(|col| {
-                Ok(ColumnDef::<ResolvedMeta> {
-                    name: col.name,
-                    datatype: Self::ast_datatype_to_exec_datatype(col.datatype)?,
-                    opts: col.opts,
-                })
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let mut columns = Vec::with_capacity(create.columns.len());
+        for col in create.columns {
+            columns.push(self.resolve_column_def(col, resolve_context).await?);
+        }
 
         let source = match create.source {
             Some(source) => Some(self.resolve_query(source, resolve_context).await?),
@@ -518,6 +766,33 @@ impl<'a> Resolver<'a> {
         })
     }
 
+    async fn resolve_column_def(
+        &self,
+        column: ColumnDef<Raw>,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<ColumnDef<ResolvedMeta>> {
+        let mut opts = Vec::with_capacity(column.opts.len());
+        for opt in column.opts {
+            opts.push(match opt {
+                ast::ColumnOption::Null => ast::ColumnOption::Null,
+                ast::ColumnOption::NotNull => ast::ColumnOption::NotNull,
+                ast::ColumnOption::PrimaryKey => ast::ColumnOption::PrimaryKey,
+                ast::ColumnOption::Check { expr, sql } => ast::ColumnOption::Check {
+                    expr: ExpressionResolver::new(self)
+                        .resolve_expression(expr, resolve_context)
+                        .await?,
+                    sql,
+                },
+            });
+        }
+
+        Ok(ColumnDef {
+            name: column.name,
+            datatype: Self::ast_datatype_to_exec_datatype(column.datatype)?,
+            opts,
+        })
+    }
+
     async fn resolve_create_view(
         &self,
         create: ast::CreateView<Raw>,
@@ -576,15 +851,24 @@ impl<'a> Resolver<'a> {
                         MaybeResolved::Unresolved(unbound)
                     }
                     MaybeResolvedTable::Unresolved => {
-                        return Err(RayexecError::new(format!(
-                            "Missing table or view for reference '{}'",
-                            insert.table
-                        )))
+                        return Err(NormalResolver::new(self.tx, self.context)
+                            .missing_table_err(&insert.table, resolve_context))
                     }
                 }
             }
         };
 
+        if let MaybeResolved::Resolved(ResolvedTableOrCteReference::Table(table), _) = &table {
+            if let CatalogEntryInner::Table(ent) = &table.entry.entry {
+                for check in &ent.checks {
+                    let expr = ExpressionResolver::new(self)
+                        .resolve_expression(parser::parse_expr(&check.expression)?, resolve_context)
+                        .await?;
+                    resolve_context.insert_checks.push(expr);
+                }
+            }
+        }
+
         let source = self.resolve_query(insert.source, resolve_context).await?;
 
         let idx = resolve_context.tables.push_maybe_resolved(table);
@@ -596,6 +880,77 @@ impl<'a> Resolver<'a> {
         })
     }
 
+    /// Resolve the row-level security policies on a table being read.
+    ///
+    /// Policies that apply to the session's role are OR'ed together into a
+    /// single predicate. If the table has policies but none of them apply,
+    /// no rows are visible.
+    async fn resolve_table_policies(
+        &self,
+        idx: ResolveListIdx,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<()> {
+        let entry = match resolve_context.tables.try_get_bound(idx) {
+            Ok((ResolvedTableOrCteReference::Table(table), _)) => table.entry.clone(),
+            // CTEs don't have policies, and unresolved tables are checked
+            // when they're resolved remotely.
+            _ => return Ok(()),
+        };
+        let policies = match &entry.entry {
+            CatalogEntryInner::Table(ent) if !ent.policies.is_empty() => &ent.policies,
+            _ => return Ok(()),
+        };
+
+        let mut predicate = None;
+        for policy in policies
+            .iter()
+            .filter(|policy| policy.applies_to(&self.config.role))
+        {
+            let expr = ExpressionResolver::new(self)
+                .resolve_expression(parser::parse_expr(&policy.expression)?, resolve_context)
+                .await?;
+            predicate = Some(match predicate {
+                Some(left) => ast::Expr::BinaryExpr {
+                    left: Box::new(left),
+                    op: ast::BinaryOperator::Or,
+                    right: Box::new(expr),
+                },
+                None => expr,
+            });
+        }
+
+        let predicate = predicate.unwrap_or(ast::Expr::Literal(ast::Literal::Boolean(false)));
+        resolve_context.table_policies.push((idx, predicate));
+
+        Ok(())
+    }
+
+    async fn resolve_create_policy(
+        &self,
+        create: ast::CreatePolicy<Raw>,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<ast::CreatePolicy<ResolvedMeta>> {
+        // Policies can only be created on local tables.
+        let table = NormalResolver::new(self.tx, self.context)
+            .require_resolve_table_or_cte(&create.table, resolve_context)
+            .await?;
+        let table = resolve_context
+            .tables
+            .push_resolved(table, LocationRequirement::ClientLocal);
+
+        let using = ExpressionResolver::new(self)
+            .resolve_expression(create.using, resolve_context)
+            .await?;
+
+        Ok(ast::CreatePolicy {
+            name: create.name,
+            table,
+            roles: create.roles,
+            using,
+            sql: create.sql,
+        })
+    }
+
     async fn resolve_query(
         &self,
         query: ast::QueryNode<Raw>,
@@ -686,6 +1041,7 @@ impl<'a> Resolver<'a> {
                 right,
                 operation,
                 all,
+                by_name,
             }) => {
                 let left = Box::pin(self.resolve_query_node_body(*left, resolve_context)).await?;
                 let right = Box::pin(self.resolve_query_node_body(*right, resolve_context)).await?;
@@ -694,6 +1050,7 @@ impl<'a> Resolver<'a> {
                     right: Box::new(right),
                     operation,
                     all,
+                    by_name,
                 })
             }
         })
@@ -872,10 +1229,8 @@ impl<'a> Resolver<'a> {
                                 MaybeResolved::Unresolved(unbound)
                             }
                             MaybeResolvedTable::Unresolved => {
-                                return Err(RayexecError::new(format!(
-                                    "Missing table or view for reference '{}'",
-                                    reference
-                                )))
+                                return Err(NormalResolver::new(self.tx, self.context)
+                                    .missing_table_err(&reference, resolve_context))
                             }
                         }
                     }
@@ -930,6 +1285,7 @@ impl<'a> Resolver<'a> {
                     _ => {
                         // Normal case, just a table or CTE
                         let idx = resolve_context.tables.push_maybe_resolved(table);
+                        Box::pin(self.resolve_table_policies(idx, resolve_context)).await?;
                         ast::FromNodeBody::BaseTable(ast::FromBaseTable { reference: idx })
                     }
                 }
@@ -1000,6 +1356,33 @@ impl<'a> Resolver<'a> {
                 )
                 .await?;
 
+                // Table macros are expanded into a subquery, treated the same
+                // as a view.
+                let normal = NormalResolver::new(self.tx, self.context);
+                if normal.resolve_table_function(&reference)?.is_none() {
+                    if let Some(ent) = normal.resolve_table_macro(&reference)? {
+                        let query =
+                            Box::pin(self.expand_table_macro(&ent, args, resolve_context)).await?;
+
+                        return Ok(ast::FromNode {
+                            alias: from.alias,
+                            body: ast::FromNodeBody::Subquery(ast::FromSubquery {
+                                lateral,
+                                options: ResolvedSubqueryOptions::View {
+                                    table_alias: TableAlias {
+                                        database: None,
+                                        schema: None,
+                                        table: ent.name.clone(),
+                                    },
+                                    column_aliases: Vec::new(),
+                                },
+                                query,
+                            }),
+                            sample: from.sample,
+                        });
+                    }
+                }
+
                 let function = match self.resolve_mode {
                     ResolveMode::Normal => {
                         let function = NormalResolver::new(self.tx, self.context)
@@ -1010,9 +1393,15 @@ impl<'a> Resolver<'a> {
                                 ResolvedTableFunctionReference::InOut(function)
                             }
                             TableFunctionPlanner::Scan(planner) => {
+                                self.check_passthrough(function.as_ref())?;
+
                                 // Requires constants.
                                 let binder = ConstantBinder::new(resolve_context);
-                                let constant_args = binder.bind_constant_function_args(&args)?;
+                                let mut constant_args =
+                                    binder.bind_constant_function_args(&args)?;
+                                self.context
+                                    .secrets()
+                                    .apply_secret(&mut constant_args.named)?;
 
                                 let planned = planner
                                     .plan(
@@ -1039,9 +1428,13 @@ impl<'a> Resolver<'a> {
                                         ResolvedTableFunctionReference::InOut(function)
                                     }
                                     TableFunctionPlanner::Scan(planner) => {
+                                        self.check_passthrough(function.as_ref())?;
                                         let binder = ConstantBinder::new(resolve_context);
-                                        let constant_args =
+                                        let mut constant_args =
                                             binder.bind_constant_function_args(&args)?;
+                                        self.context
+                                            .secrets()
+                                            .apply_secret(&mut constant_args.named)?;
 
                                         let planned = planner
                                             .plan(
@@ -1059,7 +1452,11 @@ impl<'a> Resolver<'a> {
                             }
                             None => {
                                 let binder = ConstantBinder::new(resolve_context);
-                                let constant_args = binder.bind_constant_function_args(&args)?;
+                                let mut constant_args =
+                                    binder.bind_constant_function_args(&args)?;
+                                self.context
+                                    .secrets()
+                                    .apply_secret(&mut constant_args.named)?;
 
                                 MaybeResolved::Unresolved(UnresolvedTableFunctionReference {
                                     reference,
@@ -1112,9 +1509,44 @@ impl<'a> Resolver<'a> {
         Ok(ast::FromNode {
             alias: from.alias,
             body,
+            sample: from.sample,
         })
     }
 
+    /// Expand a call to a table macro into a resolved query.
+    async fn expand_table_macro(
+        &self,
+        ent: &CatalogEntry,
+        args: Vec<ast::FunctionArg<ResolvedMeta>>,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<ast::QueryNode<ResolvedMeta>> {
+        let macro_ent = ent.try_as_macro_entry()?;
+        let args = macro_args(&ent.name, &macro_ent.parameters, args)?;
+
+        let mut statements = parser::parse(&macro_ent.body_sql)?;
+        let query = match statements.len() {
+            1 => match statements.pop().unwrap() {
+                Statement::Query(query) => query,
+                other => {
+                    return Err(RayexecError::new(format!(
+                        "Unexpected statement type for table macro: {other:?}"
+                    )))
+                }
+            },
+            other => {
+                return Err(RayexecError::new(format!(
+                    "Expected 1 statement inside table macro body, got {other}"
+                )))
+            }
+        };
+
+        resolve_context.push_macro_args(args)?;
+        let query = Box::pin(self.resolve_query(query, resolve_context)).await;
+        resolve_context.pop_macro_args();
+
+        query
+    }
+
     fn reference_to_strings(reference: ObjectReference) -> Vec<String> {
         reference
             .0
@@ -1182,6 +1614,22 @@ impl<'a> Resolver<'a> {
                 DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond))
             }
             ast::DataType::Interval => DataType::Interval,
+            ast::DataType::Json => DataType::Json,
+            ast::DataType::Geometry => DataType::Geometry,
+            ast::DataType::Array(datatype, size) => {
+                let datatype = Self::ast_datatype_to_exec_datatype(*datatype)?;
+                match size {
+                    Some(size) if size <= 0 => {
+                        return Err(RayexecError::new(format!(
+                            "Array size must be positive, got {size}"
+                        )))
+                    }
+                    Some(size) => {
+                        DataType::List(ListTypeMeta::new_fixed_size(datatype, size as usize))
+                    }
+                    None => DataType::List(ListTypeMeta::new(datatype)),
+                }
+            }
         })
     }
 }
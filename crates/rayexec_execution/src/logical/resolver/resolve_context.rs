@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt;
 
 use rayexec_error::{not_implemented, OptionExt, RayexecError, Result};
@@ -13,10 +14,17 @@ use super::resolved_table_function::{
     ResolvedTableFunctionReference,
     UnresolvedTableFunctionReference,
 };
+use super::ResolvedMeta;
 use crate::database::DatabaseContext;
 use crate::logical::operator::LocationRequirement;
 use crate::proto::DatabaseProtoConv;
 
+/// Maximum number of nested macro expansions.
+///
+/// Kept low since each expansion recurses through the resolver, and we need to
+/// error out before overflowing the stack of a worker thread.
+const MAX_MACRO_DEPTH: usize = 6;
+
 /// Context containing resolved database objects.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct ResolveContext {
@@ -54,6 +62,27 @@ pub struct ResolveContext {
     /// When search for a CTE, the vec should be iterated from right to left to
     /// try to get the "closest" CTE to the reference.
     pub ctes: Vec<ResolvedCte>,
+
+    /// Arguments for macros currently being expanded, keyed by parameter
+    /// name.
+    ///
+    /// Only the innermost macro's arguments are visible. A macro body can't
+    /// reference parameters of the macro it was called from.
+    pub macro_args: Vec<HashMap<String, ast::Expr<ResolvedMeta>>>,
+
+    /// CHECK constraints for the table being inserted into, in the same order
+    /// as the constraints in the table entry.
+    ///
+    /// Constraints are stored as SQL in the entry, and get resolved alongside
+    /// the insert.
+    pub insert_checks: Vec<ast::Expr<ResolvedMeta>>,
+
+    /// Row-level security predicates for tables being read, paired with the
+    /// table's index in `tables`.
+    ///
+    /// Only contains tables that have policies. The predicate already
+    /// accounts for the session's role.
+    pub table_policies: Vec<(ResolveListIdx, ast::Expr<ResolvedMeta>)>,
 }
 
 impl ResolveContext {
@@ -65,6 +94,9 @@ impl ResolveContext {
             copy_to: None,
             current_depth: 0,
             ctes: Vec::new(),
+            macro_args: Vec::new(),
+            insert_checks: Vec::new(),
+            table_policies: Vec::new(),
         }
     }
 
@@ -109,6 +141,23 @@ impl ResolveContext {
         None
     }
 
+    /// Iterate the names of CTEs that can be referenced at the current depth.
+    pub fn iter_cte_names(&self) -> impl Iterator<Item = &str> {
+        let mut search_depth = self.current_depth;
+
+        self.ctes
+            .iter()
+            .rev()
+            .take_while(move |cte| {
+                if cte.depth > search_depth {
+                    return false;
+                }
+                search_depth = cte.depth;
+                true
+            })
+            .map(|cte| cte.name.as_str())
+    }
+
     pub fn inc_depth(&mut self) {
         self.current_depth += 1
     }
@@ -117,6 +166,39 @@ impl ResolveContext {
         self.current_depth -= 1;
     }
 
+    /// Push arguments for a macro that's about to be expanded.
+    ///
+    /// Errors if macros are nested too deeply, e.g. a macro calling itself.
+    pub fn push_macro_args(
+        &mut self,
+        args: HashMap<String, ast::Expr<ResolvedMeta>>,
+    ) -> Result<()> {
+        if self.macro_args.len() >= MAX_MACRO_DEPTH {
+            return Err(RayexecError::new(format!(
+                "Macro expansion exceeded maximum depth of {MAX_MACRO_DEPTH}"
+            )));
+        }
+        self.macro_args.push(args);
+        Ok(())
+    }
+
+    pub fn pop_macro_args(&mut self) {
+        self.macro_args.pop();
+    }
+
+    /// Get the argument for a parameter of the macro currently being expanded.
+    pub fn find_macro_arg(&self, name: &str) -> Option<&ast::Expr<ResolvedMeta>> {
+        self.macro_args.last().and_then(|args| args.get(name))
+    }
+
+    /// Get the row-level security predicate for a table, if it has one.
+    pub fn find_table_policy(&self, table: ResolveListIdx) -> Option<&ast::Expr<ResolvedMeta>> {
+        self.table_policies
+            .iter()
+            .find(|(idx, _)| *idx == table)
+            .map(|(_, expr)| expr)
+    }
+
     /// Push a CTE into bind data, returning a CTE reference.
     pub fn push_cte(&mut self, cte: ResolvedCte) {
         self.ctes.push(cte);
@@ -131,6 +213,12 @@ impl DatabaseProtoConv for ResolveContext {
             // More ast work needed.
             not_implemented!("encode ctes in resolve context")
         }
+        if !self.insert_checks.is_empty() {
+            not_implemented!("encode check constraints in resolve context")
+        }
+        if !self.table_policies.is_empty() {
+            not_implemented!("encode table policies in resolve context")
+        }
 
         Ok(Self::ProtoType {
             tables: Some(self.tables.to_proto_ctx(context)?),
@@ -162,6 +250,9 @@ impl DatabaseProtoConv for ResolveContext {
                 .transpose()?,
             current_depth: proto.current_depth as usize,
             ctes: Vec::new(),
+            macro_args: Vec::new(),
+            insert_checks: Vec::new(),
+            table_policies: Vec::new(),
         })
     }
 }
@@ -1,13 +1,17 @@
 use std::sync::Arc;
 
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{not_implemented, RayexecError, Result};
 use tracing::debug;
+use uuid::Uuid;
 
 use super::resolve_context::MaybeResolved;
 use super::resolve_normal::NormalResolver;
+use super::resolved_table::ResolvedTableOrCteReference;
 use super::resolved_table_function::ResolvedTableFunctionReference;
 use super::{ResolveContext, Resolver};
+use crate::config::statements::AllowedStatements;
 use crate::database::catalog::CatalogTx;
+use crate::database::catalog_entry::CatalogEntryInner;
 use crate::database::memory_catalog::MemoryCatalog;
 use crate::database::{Database, DatabaseContext};
 use crate::datasource::{DataSourceRegistry, FileHandlers};
@@ -68,6 +72,7 @@ impl<'a> HybridContextExtender<'a> {
                         }
 
                         let database = Database {
+                            id: Uuid::new_v4(),
                             catalog,
                             catalog_storage: connection.catalog_storage,
                             table_storage: Some(connection.table_storage),
@@ -122,7 +127,11 @@ pub struct HybridResolver<'a> {
 }
 
 impl<'a> HybridResolver<'a> {
-    pub fn new(tx: &'a CatalogTx, context: &'a DatabaseContext) -> Self {
+    pub fn new(
+        tx: &'a CatalogTx,
+        context: &'a DatabaseContext,
+        allowed_statements: &AllowedStatements,
+    ) -> Self {
         // Currently just use an empty file handler, all files should have been
         // resolved appropriately on the "local" side.
         //
@@ -146,6 +155,8 @@ impl<'a> HybridResolver<'a> {
                 EMPTY_FILE_HANDLER_REF,
                 ResolveConfig {
                     enable_function_chaining: true, // TODO: We'll need to get this from the client.
+                    role: String::new(),
+                    allow_passthrough: allowed_statements.allows_passthrough(),
                 },
             ),
         }
@@ -182,6 +193,19 @@ impl<'a> HybridResolver<'a> {
 
                 debug!(%unresolved.reference, "(hybrid) resolved unbound table");
 
+                // The client's role isn't sent, so we can't apply policies
+                // for it.
+                if let ResolvedTableOrCteReference::Table(table) = &table {
+                    if let CatalogEntryInner::Table(ent) = &table.entry.entry {
+                        if !ent.policies.is_empty() {
+                            not_implemented!(
+                                "Reading table '{}' with row-level security policies from a hybrid query",
+                                table.entry.name
+                            );
+                        }
+                    }
+                }
+
                 *item = MaybeResolved::Resolved(table, LocationRequirement::Remote)
             }
         }
@@ -203,6 +227,7 @@ impl<'a> HybridResolver<'a> {
                         ResolvedTableFunctionReference::InOut(function)
                     }
                     TableFunctionPlanner::Scan(planner) => {
+                        self.resolver.check_passthrough(function.as_ref())?;
                         let planned = planner
                             .plan(
                                 self.resolver.context,
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{ErrorKind, RayexecError, Result};
 use rayexec_parser::ast;
 use tracing::error;
 
@@ -11,11 +11,14 @@ use super::resolved_table::{
 };
 use super::ResolveContext;
 use crate::database::catalog::CatalogTx;
-use crate::database::catalog_entry::{CatalogEntry, CatalogEntryType};
+use crate::database::catalog_entry::{CatalogEntry, CatalogEntryInner, CatalogEntryType};
 use crate::database::create::{CreateSchemaInfo, CreateTableInfo, OnConflict};
 use crate::database::memory_catalog::MemorySchema;
+use crate::database::suggest::{similar_names, with_suggestions};
 use crate::database::{Database, DatabaseContext};
+use crate::engine::PERSISTENT_CATALOG;
 use crate::functions::table::TableFunction;
+use crate::storage::disk::DEFAULT_DISK_SCHEMA;
 
 pub fn create_user_facing_resolve_err(
     tx: &CatalogTx,
@@ -23,17 +26,17 @@ pub fn create_user_facing_resolve_err(
     object_types: &[CatalogEntryType],
     name: &str,
 ) -> RayexecError {
-    // Find similar function to include in error message.
+    // Find similar entries to include in error message.
     let similar = match schema_ent {
-        Some(schema_ent) => match schema_ent.find_similar_entry(tx, object_types, name) {
-            Ok(maybe_similar) => maybe_similar,
+        Some(schema_ent) => match schema_ent.find_similar_entries(tx, object_types, name) {
+            Ok(similar) => similar,
             Err(e) => {
                 // Error shouldn't happen, but if it does, it shouldn't be user-facing.
-                error!(%e, %name, "failed to find similar entry to include in error message");
-                None
+                error!(%e, %name, "failed to find similar entries to include in error message");
+                Vec::new()
             }
         },
-        None => None,
+        None => Vec::new(),
     };
 
     // "table"
@@ -44,16 +47,23 @@ pub fn create_user_facing_resolve_err(
         .collect::<Vec<_>>()
         .join(" or ");
 
-    match similar {
-        Some(similar) => RayexecError::new(format!(
-            "Cannot resolve {} with name '{}', did you mean '{}'?",
-            formatted_object_types, name, similar.entry.name,
-        )),
-        None => RayexecError::new(format!(
-            "Cannot resolve {} with name '{}'",
-            formatted_object_types, name
-        )),
-    }
+    let kind = if object_types
+        .iter()
+        .any(|t| matches!(t, CatalogEntryType::Table | CatalogEntryType::View))
+    {
+        ErrorKind::TableNotFound
+    } else if object_types.contains(&CatalogEntryType::Schema) {
+        ErrorKind::SchemaNotFound
+    } else {
+        ErrorKind::FunctionNotFound
+    };
+
+    let msg = format!(
+        "Cannot resolve {} with name '{}'",
+        formatted_object_types, name
+    );
+
+    RayexecError::new(with_suggestions(msg, &similar)).with_kind(kind)
 }
 
 #[derive(Debug)]
@@ -84,30 +94,7 @@ impl<'a> NormalResolver<'a> {
         &self,
         reference: &ast::ObjectReference,
     ) -> Result<Option<Box<dyn TableFunction>>> {
-        // TODO: Search path.
-        let [catalog, schema, name] = match reference.0.len() {
-            1 => [
-                "system".to_string(),
-                "glare_catalog".to_string(),
-                reference.0[0].as_normalized_string(),
-            ],
-            2 => {
-                let name = reference.0[1].as_normalized_string();
-                let schema = reference.0[0].as_normalized_string();
-                ["system".to_string(), schema, name]
-            }
-            3 => {
-                let name = reference.0[2].as_normalized_string();
-                let schema = reference.0[1].as_normalized_string();
-                let catalog = reference.0[0].as_normalized_string();
-                [catalog, schema, name]
-            }
-            _ => {
-                return Err(RayexecError::new(
-                    "Unexpected number of identifiers in table function reference",
-                ))
-            }
-        };
+        let [catalog, schema, name] = table_function_path(reference)?;
 
         let schema_ent = match self
             .context
@@ -119,10 +106,41 @@ impl<'a> NormalResolver<'a> {
             None => return Ok(None),
         };
 
-        if let Some(entry) = schema_ent.get_table_function(self.tx, &name)? {
-            Ok(Some(entry.try_as_table_function_entry()?.function.clone()))
-        } else {
-            Ok(None)
+        match schema_ent.get_table_function(self.tx, &name)? {
+            Some(entry) => match &entry.entry {
+                CatalogEntryInner::TableFunction(ent) => Ok(Some(ent.function.clone())),
+                // Table macros share the namespace, and are expanded
+                // separately.
+                _ => Ok(None),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Resolve a table macro.
+    ///
+    /// Unqualified references are looked up in the session's temp schema.
+    pub fn resolve_table_macro(
+        &self,
+        reference: &ast::ObjectReference,
+    ) -> Result<Option<Arc<CatalogEntry>>> {
+        let [catalog, schema, name] = match reference.0.len() {
+            1 => [
+                "temp".to_string(),
+                "temp".to_string(),
+                reference.0[0].as_normalized_string(),
+            ],
+            _ => table_function_path(reference)?,
+        };
+
+        match self
+            .context
+            .get_database(&catalog)?
+            .catalog
+            .get_schema(self.tx, &schema)?
+        {
+            Some(schema_ent) => schema_ent.get_table_macro(self.tx, &name),
+            None => Ok(None),
         }
     }
 
@@ -130,12 +148,99 @@ impl<'a> NormalResolver<'a> {
         &self,
         reference: &ast::ObjectReference,
     ) -> Result<Box<dyn TableFunction>> {
-        self.resolve_table_function(reference)?.ok_or_else(|| {
-            RayexecError::new(format!(
-                "Missing table function for reference '{}'",
-                reference
-            ))
-        })
+        match self.resolve_table_function(reference)? {
+            Some(function) => Ok(function),
+            None => Err(self.missing_table_function_err(reference)),
+        }
+    }
+
+    /// Create an error for a table function that couldn't be resolved,
+    /// including similarly named table functions in the error message.
+    pub fn missing_table_function_err(&self, reference: &ast::ObjectReference) -> RayexecError {
+        let similar = match table_function_path(reference) {
+            Ok([catalog, schema, name]) => self.find_similar_entries(
+                &catalog,
+                &schema,
+                &[CatalogEntryType::TableFunction],
+                &name,
+            ),
+            Err(_) => Vec::new(),
+        };
+
+        let msg = format!("Missing table function for reference '{}'", reference);
+
+        RayexecError::new(with_suggestions(msg, &similar))
+            .with_kind(ErrorKind::FunctionNotFound)
+            .with_span(reference.span())
+    }
+
+    /// Create an error for a table or view that couldn't be resolved,
+    /// including similarly named tables, views, and CTEs in the error message.
+    pub fn missing_table_err(
+        &self,
+        reference: &ast::ObjectReference,
+        resolve_context: &ResolveContext,
+    ) -> RayexecError {
+        let similar = match table_path(reference) {
+            Ok([catalog, schema, name]) => {
+                let mut similar = self.find_similar_entries(
+                    &catalog,
+                    &schema,
+                    &[CatalogEntryType::Table, CatalogEntryType::View],
+                    &name,
+                );
+
+                if reference.0.len() == 1 {
+                    similar.extend(
+                        similar_names(&name, resolve_context.iter_cte_names())
+                            .into_iter()
+                            .map(|name| name.to_string()),
+                    );
+                }
+
+                // Re-rank across catalog entries and CTEs.
+                similar_names(&name, similar.iter().map(|name| name.as_str()))
+                    .into_iter()
+                    .map(|name| name.to_string())
+                    .collect()
+            }
+            Err(_) => Vec::new(),
+        };
+
+        let msg = format!("Missing table or view for reference '{}'", reference);
+
+        RayexecError::new(with_suggestions(msg, &similar))
+            .with_kind(ErrorKind::TableNotFound)
+            .with_span(reference.span())
+    }
+
+    /// Find names of entries in a schema similar to `name`.
+    ///
+    /// Errors are logged and treated as there being no similar entries since
+    /// this is only used for error messages.
+    fn find_similar_entries(
+        &self,
+        catalog: &str,
+        schema: &str,
+        typs: &[CatalogEntryType],
+        name: &str,
+    ) -> Vec<String> {
+        let similar = self
+            .context
+            .get_database(catalog)
+            .and_then(|database| database.catalog.get_schema(self.tx, schema))
+            .and_then(|schema_ent| match schema_ent {
+                Some(schema_ent) => schema_ent.find_similar_entries(self.tx, typs, name),
+                None => Ok(Vec::new()),
+            });
+
+        match similar {
+            Ok(similar) => similar,
+            Err(e) => {
+                error!(%e, %name, "failed to find similar entries to include in error message");
+                Vec::new()
+            }
+        }
     }
 
     /// Resolve a table or cte.
@@ -144,40 +249,48 @@ impl<'a> NormalResolver<'a> {
         reference: &ast::ObjectReference,
         resolve_context: &ResolveContext,
     ) -> Result<MaybeResolvedTable> {
-        // TODO: Seach path.
-        let [catalog, schema, table] = match reference.0.len() {
-            1 => {
-                let name = reference.0[0].as_normalized_string();
+        let [catalog, schema, table] = table_path(reference)?;
 
-                // Check bind data for cte that would satisfy this reference.
-                if let Some(cte) = resolve_context.find_cte(&name) {
+        if reference.0.len() == 1 {
+            // Check bind data for cte that would satisfy this reference.
+            if let Some(cte) = resolve_context.find_cte(&table) {
+                return Ok(MaybeResolvedTable::Resolved(
+                    ResolvedTableOrCteReference::Cte(cte.name.clone()),
+                ));
+            }
+        }
+
+        // Otherwise continue with trying to resolve from the catalogs.
+
+        let database = self.context.get_database(&catalog)?;
+
+        if reference.0.len() < 3 {
+            // Catalog wasn't specified. Fall back to the persistent catalog,
+            // then the system catalog if the temp catalog doesn't have it
+            // (e.g. for 'information_schema.tables').
+            if self
+                .resolve_from_memory_catalog(database, &schema, &table)?
+                .is_none()
+            {
+                let persistent_schema = (reference.0.len() == 2).then_some(schema.as_str());
+                if let Some(table) = self.resolve_persistent_table(persistent_schema, &table)? {
                     return Ok(MaybeResolvedTable::Resolved(
-                        ResolvedTableOrCteReference::Cte(cte.name.clone()),
+                        ResolvedTableOrCteReference::Table(table),
                     ));
                 }
 
-                // Otherwise continue with trying to resolve from the catalogs.
-                ["temp".to_string(), "temp".to_string(), name]
-            }
-            2 => {
-                let table = reference.0[1].as_normalized_string();
-                let schema = reference.0[0].as_normalized_string();
-                ["temp".to_string(), schema, table]
-            }
-            3 => {
-                let table = reference.0[2].as_normalized_string();
-                let schema = reference.0[1].as_normalized_string();
-                let catalog = reference.0[0].as_normalized_string();
-                [catalog, schema, table]
-            }
-            _ => {
-                return Err(RayexecError::new(
-                    "Unexpected number of identifiers in table reference",
-                ))
+                let system = self.context.get_database("system")?;
+                if let Some(entry) = self.resolve_from_memory_catalog(system, &schema, &table)? {
+                    return Ok(MaybeResolvedTable::Resolved(
+                        ResolvedTableOrCteReference::Table(ResolvedTableReference {
+                            catalog: "system".to_string(),
+                            schema,
+                            entry,
+                        }),
+                    ));
+                }
             }
-        };
-
-        let database = self.context.get_database(&catalog)?;
+        }
 
         // Try reading from in-memory catalog first.
         if let Some(entry) = self.resolve_from_memory_catalog(database, &schema, &table)? {
@@ -224,6 +337,8 @@ impl<'a> NormalResolver<'a> {
                     &CreateTableInfo {
                         name: table.clone(),
                         columns: ent.columns,
+                        checks: Vec::new(),
+                        primary_key: Vec::new(),
                         on_conflict: OnConflict::Error,
                     },
                 )?;
@@ -262,6 +377,41 @@ impl<'a> NormalResolver<'a> {
         }
     }
 
+    /// Resolve a table or view without a catalog specified from the
+    /// persistent catalog, using the default schema if one isn't provided.
+    ///
+    /// Temp tables shadow persistent tables, so this returns None if the temp
+    /// catalog contains the table. Also returns None if there's no persistent
+    /// catalog attached, or if it doesn't contain the table.
+    pub fn resolve_persistent_table(
+        &self,
+        schema: Option<&str>,
+        table: &str,
+    ) -> Result<Option<ResolvedTableReference>> {
+        if !self.context.database_exists(PERSISTENT_CATALOG) {
+            return Ok(None);
+        }
+
+        let temp = self.context.get_database("temp")?;
+        if self
+            .resolve_from_memory_catalog(temp, schema.unwrap_or("temp"), table)?
+            .is_some()
+        {
+            return Ok(None);
+        }
+
+        let schema = schema.unwrap_or(DEFAULT_DISK_SCHEMA);
+        let database = self.context.get_database(PERSISTENT_CATALOG)?;
+
+        Ok(self
+            .resolve_from_memory_catalog(database, schema, table)?
+            .map(|entry| ResolvedTableReference {
+                catalog: PERSISTENT_CATALOG.to_string(),
+                schema: schema.to_string(),
+                entry,
+            }))
+    }
+
     fn resolve_from_memory_catalog(
         &self,
         database: &Database,
@@ -286,10 +436,62 @@ impl<'a> NormalResolver<'a> {
             .await?
         {
             MaybeResolvedTable::Resolved(table) => Ok(table),
-            _ => Err(RayexecError::new(format!(
-                "Missing table or view for reference '{}'",
-                reference
-            ))),
+            _ => Err(self.missing_table_err(reference, resolve_context)),
         }
     }
 }
+
+/// Get the catalog, schema, and name for a table reference.
+fn table_path(reference: &ast::ObjectReference) -> Result<[String; 3]> {
+    // TODO: Seach path.
+    Ok(match reference.0.len() {
+        1 => {
+            let name = reference.0[0].as_normalized_string();
+            ["temp".to_string(), "temp".to_string(), name]
+        }
+        2 => {
+            let table = reference.0[1].as_normalized_string();
+            let schema = reference.0[0].as_normalized_string();
+            ["temp".to_string(), schema, table]
+        }
+        3 => {
+            let table = reference.0[2].as_normalized_string();
+            let schema = reference.0[1].as_normalized_string();
+            let catalog = reference.0[0].as_normalized_string();
+            [catalog, schema, table]
+        }
+        _ => {
+            return Err(RayexecError::new(
+                "Unexpected number of identifiers in table reference",
+            ))
+        }
+    })
+}
+
+/// Get the catalog, schema, and name for a table function reference.
+fn table_function_path(reference: &ast::ObjectReference) -> Result<[String; 3]> {
+    // TODO: Search path.
+    Ok(match reference.0.len() {
+        1 => [
+            "system".to_string(),
+            "glare_catalog".to_string(),
+            reference.0[0].as_normalized_string(),
+        ],
+        2 => {
+            let name = reference.0[1].as_normalized_string();
+            let schema = reference.0[0].as_normalized_string();
+            ["system".to_string(), schema, name]
+        }
+        3 => {
+            let name = reference.0[2].as_normalized_string();
+            let schema = reference.0[1].as_normalized_string();
+            let catalog = reference.0[0].as_normalized_string();
+            [catalog, schema, name]
+        }
+        _ => {
+            return Err(RayexecError::new(
+                "Unexpected number of identifiers in table function reference",
+            ))
+        }
+    })
+}
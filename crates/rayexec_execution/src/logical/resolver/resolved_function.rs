@@ -13,6 +13,14 @@ pub enum SpecialBuiltinFunction {
     /// GROUPING function for reporting the group of an expression in a grouping
     /// set.
     Grouping,
+    /// COALESCE, only evaluates arguments until a non-null value is found.
+    Coalesce,
+    /// NULLIF for nulling out a value equal to some other value.
+    NullIf,
+    /// GREATEST, largest of the non-null arguments.
+    Greatest,
+    /// LEAST, smallest of the non-null arguments.
+    Least,
 }
 
 impl SpecialBuiltinFunction {
@@ -20,6 +28,10 @@ impl SpecialBuiltinFunction {
         match self {
             Self::Unnest => "unnest",
             Self::Grouping => "grouping",
+            Self::Coalesce => "coalesce",
+            Self::NullIf => "nullif",
+            Self::Greatest => "greatest",
+            Self::Least => "least",
         }
     }
 
@@ -27,6 +39,10 @@ impl SpecialBuiltinFunction {
         match func_name {
             "unnest" => Some(Self::Unnest),
             "grouping" => Some(Self::Grouping),
+            "coalesce" => Some(Self::Coalesce),
+            "nullif" => Some(Self::NullIf),
+            "greatest" => Some(Self::Greatest),
+            "least" => Some(Self::Least),
             _ => None,
         }
     }
@@ -1,5 +1,9 @@
-use crate::arrays::scalar::OwnedScalarValue;
+use std::fmt;
+
+use super::logical_scan::LogicalScan;
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
 use crate::expr::comparison_expr::ComparisonOperator;
+use crate::expr::Expression;
 
 /// A simplified filter that can be pushed into a scan.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,6 +16,37 @@ pub struct ScanFilter {
     pub filter: ScanFilterType,
 }
 
+impl ScanFilter {
+    /// Try to convert an expression into a filter for a scan.
+    ///
+    /// Only comparisons between a column from the scan and a non-null constant
+    /// can be converted. Returns None for any other expression.
+    pub fn try_from_expr(scan: &LogicalScan, expr: &Expression) -> Option<Self> {
+        let cmp = match expr {
+            Expression::Comparison(cmp) => cmp,
+            _ => return None,
+        };
+
+        let (column, op, constant) = match (cmp.left.as_ref(), cmp.right.as_ref()) {
+            (Expression::Column(col), Expression::Literal(lit)) => (col, cmp.op, lit),
+            (Expression::Literal(lit), Expression::Column(col)) => (col, cmp.op.flip(), lit),
+            _ => return None,
+        };
+
+        if column.table_scope != scan.table_ref || constant.literal == ScalarValue::Null {
+            return None;
+        }
+
+        Some(ScanFilter {
+            column: *scan.projection.get(column.column)?,
+            filter: ScanFilterType::ConstComparison {
+                op,
+                constant: constant.literal.clone(),
+            },
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ScanFilterType {
     ConstComparison {
@@ -24,3 +59,13 @@ pub enum ScanFilterType {
         constant: OwnedScalarValue,
     },
 }
+
+impl fmt::Display for ScanFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.filter {
+            ScanFilterType::ConstComparison { op, constant } => {
+                write!(f, "#{} {op} {constant}", self.column)
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Generic depth first walks over logical plans and expressions.
+//!
+//! Both `LogicalOperator` and `Expression` expose `walk` for read-only visits
+//! and `walk_mut` for rewrites. Each accepts a `pre` hook that's called on the
+//! way down, and a `post` hook that's called on the way up. Rewrites happen by
+//! replacing the node passed to a hook in place.
+//!
+//! The `pre` hook decides if and how the walk continues. Skipping children is
+//! useful when a rewrite replaces a subtree that shouldn't be looked at again.
+
+/// Determines how a walk proceeds after calling the `pre` hook on a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recursion {
+    /// Walk the node's children, then call `post` on the node.
+    Continue,
+    /// Skip the node's children, but still call `post` on the node.
+    SkipChildren,
+    /// Stop walking entirely. No more hooks will be called, including `post`
+    /// hooks for nodes already visited.
+    Stop,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::arrays::scalar::ScalarValue;
+    use crate::expr::{self, Expression};
+    use crate::logical::logical_empty::LogicalEmpty;
+    use crate::logical::logical_limit::LogicalLimit;
+    use crate::logical::operator::{LocationRequirement, LogicalOperator, Node};
+    use crate::logical::statistics::StatisticsValue;
+
+    fn limit(n: usize, child: LogicalOperator) -> LogicalOperator {
+        LogicalOperator::Limit(Node {
+            node: LogicalLimit {
+                limit: n,
+                offset: None,
+            },
+            location: LocationRequirement::Any,
+            children: vec![child],
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+
+    fn empty() -> LogicalOperator {
+        LogicalOperator::Empty(Node {
+            node: LogicalEmpty,
+            location: LocationRequirement::Any,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+
+    #[test]
+    fn walk_plan_pre_and_post_order() {
+        let plan = limit(1, limit(2, empty()));
+
+        let order = RefCell::new(Vec::new());
+        plan.walk(
+            &mut |op| {
+                if let LogicalOperator::Limit(limit) = op {
+                    order.borrow_mut().push(format!("pre {}", limit.node.limit));
+                }
+                Ok(Recursion::Continue)
+            },
+            &mut |op| {
+                if let LogicalOperator::Limit(limit) = op {
+                    order
+                        .borrow_mut()
+                        .push(format!("post {}", limit.node.limit));
+                }
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec!["pre 1", "pre 2", "post 2", "post 1"],
+            order.into_inner()
+        );
+    }
+
+    #[test]
+    fn walk_mut_plan_skip_children() {
+        let mut plan = limit(1, limit(2, limit(3, empty())));
+
+        plan.walk_mut(
+            &mut |op| match op {
+                LogicalOperator::Limit(limit) if limit.node.limit == 2 => {
+                    limit.node.limit = 20;
+                    Ok(Recursion::SkipChildren)
+                }
+                LogicalOperator::Limit(limit) => {
+                    limit.node.limit *= 10;
+                    Ok(Recursion::Continue)
+                }
+                _ => Ok(Recursion::Continue),
+            },
+            &mut |_| Ok(()),
+        )
+        .unwrap();
+
+        assert_eq!(limit(10, limit(20, limit(3, empty()))), plan);
+    }
+
+    #[test]
+    fn walk_expr_stop() {
+        // '1 + (2 + 3)'
+        let expr = expr::add(expr::lit(1), expr::add(expr::lit(2), expr::lit(3)));
+
+        let mut visited = Vec::new();
+        let mut post_visited = Vec::new();
+        expr.walk(
+            &mut |expr| {
+                if let Expression::Literal(lit) = expr {
+                    visited.push(lit.literal.clone());
+                    if visited.len() == 2 {
+                        return Ok(Recursion::Stop);
+                    }
+                }
+                Ok(Recursion::Continue)
+            },
+            &mut |expr| {
+                post_visited.push(expr.clone());
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(vec![ScalarValue::Int32(1), ScalarValue::Int32(2)], visited);
+        assert_eq!(vec![expr::lit(1)], post_visited);
+    }
+}
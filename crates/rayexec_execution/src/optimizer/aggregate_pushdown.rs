@@ -0,0 +1,252 @@
+use rayexec_error::Result;
+
+use super::OptimizeRule;
+use crate::arrays::scalar::ScalarValue;
+use crate::database::DatabaseContext;
+use crate::expr::conjunction_expr::ConjunctionOperator;
+use crate::expr::Expression;
+use crate::functions::table::TableFunctionImpl;
+use crate::logical::binder::bind_context::BindContext;
+use crate::logical::logical_aggregate::LogicalAggregate;
+use crate::logical::logical_scan::{LogicalScan, ScanSource};
+use crate::logical::operator::{LogicalOperator, Node};
+use crate::logical::scan_filter::ScanFilter;
+use crate::logical::statistics::StatisticsValue;
+use crate::logical::walk::Recursion;
+use crate::storage::table_storage::{TableAggregate, TableAggregateExpr, TableAggregateFunction};
+
+/// Push ungrouped aggregates over a single table into the table itself.
+///
+/// Tables that are able to compute aggregates on their own (e.g. remote
+/// databases) can then return a single row instead of every row in the table.
+///
+/// Checking if a table supports the aggregate requires the database context, so
+/// this is applied outside of the normal optimizer passes.
+#[derive(Debug)]
+pub struct AggregatePushdown<'a> {
+    context: &'a DatabaseContext,
+}
+
+impl<'a> AggregatePushdown<'a> {
+    pub fn new(context: &'a DatabaseContext) -> Self {
+        AggregatePushdown { context }
+    }
+
+    /// Try to replace the aggregate with a scan that has the aggregate pushed
+    /// into it.
+    ///
+    /// Returns None if the aggregate can't be pushed down.
+    fn try_push_aggregate(
+        &self,
+        agg: &Node<LogicalAggregate>,
+    ) -> Result<Option<Node<LogicalScan>>> {
+        if agg.node.group_table.is_some()
+            || !agg.node.group_exprs.is_empty()
+            || agg.node.grouping_functions_table.is_some()
+        {
+            return Ok(None);
+        }
+
+        let (filter, scan) = match agg.children.as_slice() {
+            [LogicalOperator::Scan(scan)] => (None, scan),
+            [LogicalOperator::Filter(filter)] => match filter.children.as_slice() {
+                [LogicalOperator::Scan(scan)] => (Some(&filter.node.filter), scan),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        // Scan filters are always implied by the filter above the scan, so
+        // they don't need to be checked.
+        if scan.node.sample.is_some() || scan.node.limit.is_some() || scan.node.aggregate.is_some()
+        {
+            return Ok(None);
+        }
+
+        let mut filters = Vec::new();
+        if let Some(filter) = filter {
+            if !collect_filters(scan, filter, &mut filters) {
+                return Ok(None);
+            }
+        }
+
+        let mut aggregates = Vec::with_capacity(agg.node.aggregates.len());
+        let mut names = Vec::with_capacity(agg.node.aggregates.len());
+        for expr in &agg.node.aggregates {
+            let (aggregate, name) = match table_aggregate_expr(scan, expr) {
+                Some(aggregate) => aggregate,
+                None => return Ok(None),
+            };
+            aggregates.push(aggregate);
+            names.push(name);
+        }
+
+        let aggregate = TableAggregate {
+            aggregates,
+            filters,
+        };
+
+        let supported = match &scan.node.source {
+            ScanSource::Table {
+                catalog,
+                schema,
+                source,
+            } => {
+                let database = self.context.get_database(catalog)?;
+                match &database.table_storage {
+                    Some(storage) => storage
+                        .data_table(self.context.transaction(), schema, source)?
+                        .supports_aggregate(&aggregate),
+                    None => false,
+                }
+            }
+            ScanSource::TableFunction { function } => match &function.function_impl {
+                TableFunctionImpl::Scan(data_table) => data_table.supports_aggregate(&aggregate),
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if !supported {
+            return Ok(None);
+        }
+
+        let types = aggregate
+            .aggregates
+            .iter()
+            .map(|agg| agg.output_type.clone())
+            .collect();
+
+        Ok(Some(Node {
+            node: LogicalScan {
+                table_ref: agg.node.aggregates_table,
+                types,
+                projection: (0..names.len()).collect(),
+                names,
+                did_prune_columns: false,
+                scan_filters: Vec::new(),
+                sample: None,
+                limit: None,
+                aggregate: Some(aggregate),
+                index_scan: None,
+                source: scan.node.source.clone(),
+            },
+            location: scan.location,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Exact(1),
+        }))
+    }
+}
+
+impl OptimizeRule for AggregatePushdown<'_> {
+    fn optimize(
+        &mut self,
+        _bind_context: &mut BindContext,
+        mut plan: LogicalOperator,
+    ) -> Result<LogicalOperator> {
+        plan.walk_mut(
+            &mut |op| {
+                if let LogicalOperator::Aggregate(agg) = op {
+                    if let Some(scan) = self.try_push_aggregate(agg)? {
+                        *op = LogicalOperator::Scan(scan);
+                        return Ok(Recursion::SkipChildren);
+                    }
+                }
+                Ok(Recursion::Continue)
+            },
+            &mut |_| Ok(()),
+        )?;
+
+        Ok(plan)
+    }
+}
+
+/// Get the column in the scan output that an expression is referencing.
+///
+/// Returns None if the expression isn't a column from the scan.
+fn scan_column(scan: &Node<LogicalScan>, expr: &Expression) -> Option<usize> {
+    match expr {
+        Expression::Column(col) if col.table_scope == scan.node.table_ref => Some(col.column),
+        _ => None,
+    }
+}
+
+/// Convert the filter into simple scan filters, appending them to `filters`.
+///
+/// Returns false if any part of the filter can't be converted.
+fn collect_filters(
+    scan: &Node<LogicalScan>,
+    expr: &Expression,
+    filters: &mut Vec<ScanFilter>,
+) -> bool {
+    match expr {
+        Expression::Conjunction(conj) if conj.op == ConjunctionOperator::And => conj
+            .expressions
+            .iter()
+            .all(|expr| collect_filters(scan, expr, filters)),
+        expr => match ScanFilter::try_from_expr(&scan.node, expr) {
+            Some(filter) => {
+                filters.push(filter);
+                true
+            }
+            None => false,
+        },
+    }
+}
+
+/// Try to convert an aggregate expression into an aggregate to push down to
+/// the table, along with a display name for the output.
+fn table_aggregate_expr(
+    scan: &Node<LogicalScan>,
+    expr: &Expression,
+) -> Option<(TableAggregateExpr, String)> {
+    let agg = match expr {
+        Expression::Aggregate(agg)
+            if agg.filter.is_none() && !agg.distinct && agg.order_by.is_empty() =>
+        {
+            agg
+        }
+        _ => return None,
+    };
+
+    let function = match agg.agg.function.name() {
+        "count" => TableAggregateFunction::Count,
+        "sum" => TableAggregateFunction::Sum,
+        "avg" => TableAggregateFunction::Avg,
+        "min" => TableAggregateFunction::Min,
+        "max" => TableAggregateFunction::Max,
+        _ => return None,
+    };
+
+    // Names and types are for the scan output, which may have been pruned.
+    let input = match agg.agg.inputs.as_slice() {
+        // `count(*)` is planned as a count on a constant.
+        [Expression::Literal(lit)]
+            if function == TableAggregateFunction::Count && lit.literal != ScalarValue::Null =>
+        {
+            None
+        }
+        [input] => Some(scan_column(scan, input)?),
+        _ => return None,
+    };
+
+    let name = match input {
+        Some(input) => format!("{function}({})", scan.node.names.get(input)?),
+        None => format!("{function}(*)"),
+    };
+
+    let aggregate = TableAggregateExpr {
+        function,
+        column: match input {
+            Some(input) => Some(*scan.node.projection.get(input)?),
+            None => None,
+        },
+        input_type: match input {
+            Some(input) => Some(scan.node.types.get(input)?.clone()),
+            None => None,
+        },
+        output_type: agg.agg.return_type.clone(),
+    };
+
+    Some((aggregate, name))
+}
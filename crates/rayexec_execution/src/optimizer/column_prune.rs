@@ -401,9 +401,19 @@ impl PruneState {
                         new_proj_mapping.push((old_column, projection.clone()));
                     }
 
-                    // Generate the new table ref.
-                    let table_ref =
-                        bind_context.clone_to_new_ephemeral_table(project.node.projection_table)?;
+                    // Generate the new table ref containing only the
+                    // columns we're keeping.
+                    let orig = bind_context.get_table(project.node.projection_table)?;
+                    let (names, types) = new_proj_mapping
+                        .iter()
+                        .map(|(old_column, _)| {
+                            (
+                                orig.column_names[old_column.column].clone(),
+                                orig.column_types[old_column.column].clone(),
+                            )
+                        })
+                        .unzip();
+                    let table_ref = bind_context.new_ephemeral_table_with_columns(types, names)?;
 
                     // Generate the new projection, inserting updated
                     // expressions into the state.
@@ -538,6 +548,13 @@ impl PruneState {
                 }
                 self.apply_updated_expressions(plan)?;
             }
+            LogicalOperator::Sample(_) => {
+                // Can push through sample.
+                for child in plan.children_mut() {
+                    self.walk_plan(bind_context, child)?;
+                }
+                self.apply_updated_expressions(plan)?;
+            }
             LogicalOperator::CrossJoin(_)
             | LogicalOperator::ComparisonJoin(_)
             | LogicalOperator::ArbitraryJoin(_) => {
@@ -6,6 +6,7 @@ use crate::expr::literal_expr::LiteralExpr;
 use crate::expr::physical::planner::PhysicalExpressionPlanner;
 use crate::expr::Expression;
 use crate::logical::binder::table_list::TableList;
+use crate::logical::walk::Recursion;
 
 /// Pre-compute constant expressions.
 #[derive(Debug)]
@@ -19,48 +20,64 @@ impl ExpressionRewriteRule for ConstFold {
 }
 
 fn maybe_fold(table_list: &TableList, expr: &mut Expression) -> Result<()> {
-    if matches!(expr, Expression::Literal(_)) {
-        return Ok(());
-    }
-
-    if expr.is_const_foldable() {
-        let planner = PhysicalExpressionPlanner::new(table_list);
-        let phys_expr = planner.plan_scalar(&[], expr)?;
-        let dummy = Batch::empty_with_num_rows(1);
-        let val = phys_expr.eval(&dummy)?;
-
-        if val.logical_len() != 1 {
-            return Err(RayexecError::new(format!(
-                "Expected 1 value from const eval, got {}",
-                val.logical_len()
-            )));
-        }
-
-        let val = val
-            .logical_value(0) // Len checked above.
-            .map_err(|_| {
-                RayexecError::new(format!(
-                    "Failed to get folded scalar value from expression: {expr}"
-                ))
-            })?;
-
-        // Our brand new expression.
-        *expr = Expression::Literal(LiteralExpr {
-            literal: val.into_owned(),
-        });
-
-        return Ok(());
-    }
-
-    // Otherwise try the children.
-    expr.for_each_child_mut(&mut |child| maybe_fold(table_list, child))
+    expr.walk_mut(
+        &mut |expr| {
+            if matches!(expr, Expression::Literal(_)) {
+                return Ok(Recursion::SkipChildren);
+            }
+
+            if !expr.is_const_foldable() {
+                // Otherwise try the children.
+                return Ok(Recursion::Continue);
+            }
+
+            let planner = PhysicalExpressionPlanner::new(table_list);
+            let phys_expr = planner.plan_scalar(&[], expr)?;
+            let dummy = Batch::empty_with_num_rows(1);
+            let val = phys_expr.eval(&dummy)?;
+
+            if val.logical_len() != 1 {
+                return Err(RayexecError::new(format!(
+                    "Expected 1 value from const eval, got {}",
+                    val.logical_len()
+                )));
+            }
+
+            let val = val
+                .logical_value(0) // Len checked above.
+                .map_err(|_| {
+                    RayexecError::new(format!(
+                        "Failed to get folded scalar value from expression: {expr}"
+                    ))
+                })?;
+
+            // Our brand new expression.
+            *expr = Expression::Literal(LiteralExpr {
+                literal: val.into_owned(),
+            });
+
+            Ok(Recursion::SkipChildren)
+        },
+        &mut |_| Ok(()),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::arrays::datatype::DataType;
-    use crate::expr::{add, and, cast, col_ref, lit};
+    use crate::expr::scalar_function_expr::ScalarFunctionExpr;
+    use crate::expr::unnest_expr::UnnestExpr;
+    use crate::expr::{add, and, cast, col_ref, eq, lit};
+    use crate::functions::scalar::builtin::datetime::{CurrentDate, Now};
+    use crate::functions::scalar::builtin::random::Random;
+    use crate::functions::scalar::builtin::string::Upper;
+    use crate::functions::scalar::ScalarFunction;
+
+    fn call(function: impl ScalarFunction, inputs: Vec<Expression>) -> Expression {
+        let function = function.plan(&TableList::empty(), inputs).unwrap();
+        Expression::ScalarFunction(ScalarFunctionExpr { function })
+    }
 
     #[test]
     fn no_fold_literal() {
@@ -140,4 +157,59 @@ mod tests {
         let got = ConstFold::rewrite(&table_list, expr).unwrap();
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn fold_immutable_function() {
+        let expr = call(Upper, vec![lit("abc")]);
+
+        let expected = lit("ABC");
+
+        let table_list = TableList::empty();
+        let got = ConstFold::rewrite(&table_list, expr).unwrap();
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn no_fold_volatile_function() {
+        let expr = add(call(Random, Vec::new()), lit(1.0_f64));
+
+        // No change
+        let expected = expr.clone();
+
+        let table_list = TableList::empty();
+        let got = ConstFold::rewrite(&table_list, expr).unwrap();
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn no_fold_stable_function() {
+        let expr = and([
+            eq(call(CurrentDate, Vec::new()), call(CurrentDate, Vec::new())),
+            eq(call(Now, Vec::new()), call(Now, Vec::new())),
+        ])
+        .unwrap();
+
+        // No change
+        let expected = expr.clone();
+
+        let table_list = TableList::empty();
+        let got = ConstFold::rewrite(&table_list, expr).unwrap();
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn no_fold_unnest() {
+        let expr = Expression::Unnest(UnnestExpr {
+            expr: Box::new(cast(lit("3.1"), DataType::Float64)),
+        });
+
+        // Only the input gets folded.
+        let expected = Expression::Unnest(UnnestExpr {
+            expr: Box::new(lit(3.1_f64)),
+        });
+
+        let table_list = TableList::empty();
+        let got = ConstFold::rewrite(&table_list, expr).unwrap();
+        assert_eq!(expected, got);
+    }
 }
@@ -5,6 +5,7 @@ use super::ExpressionRewriteRule;
 use crate::expr::conjunction_expr::{ConjunctionExpr, ConjunctionOperator};
 use crate::expr::Expression;
 use crate::logical::binder::table_list::TableList;
+use crate::logical::walk::Recursion;
 
 /// Tries to lift up AND expressions through OR expressions
 ///
@@ -14,23 +15,28 @@ pub struct DistributiveOrRewrite;
 
 impl ExpressionRewriteRule for DistributiveOrRewrite {
     fn rewrite(_table_list: &TableList, mut expression: Expression) -> Result<Expression> {
-        fn inner(expr: &mut Expression) -> Result<()> {
-            match expr {
-                Expression::Conjunction(conj) if conj.op == ConjunctionOperator::Or => {
-                    maybe_rewrite_or(conj)?;
-
-                    // Go down through children too.
-                    for child in &mut conj.expressions {
-                        inner(child)?;
+        expression.walk_mut(
+            &mut |expr| {
+                if let Expression::Conjunction(conj) = expr {
+                    if conj.op == ConjunctionOperator::Or {
+                        maybe_rewrite_or(conj)?;
                     }
-
-                    Ok(())
                 }
-                other => other.for_each_child_mut(&mut inner),
-            }
-        }
-
-        inner(&mut expression)?;
+                // Go down through children too.
+                Ok(Recursion::Continue)
+            },
+            &mut |expr| {
+                // Rewriting may leave an AND with a single child, which is
+                // just the child itself.
+                if let Expression::Conjunction(conj) = expr {
+                    if conj.expressions.len() == 1 {
+                        let child = conj.expressions.pop().unwrap();
+                        *expr = child;
+                    }
+                }
+                Ok(())
+            },
+        )?;
 
         Ok(expression)
     }
@@ -69,6 +75,10 @@ fn maybe_rewrite_or(orig_expr: &mut ConjunctionExpr) -> Result<()> {
     let common_exprs: IndexSet<_> = common_exprs.into_iter().cloned().collect();
 
     let mut new_or_children = Vec::with_capacity(orig_expr.expressions.len());
+    // Set if any child of the OR consists only of common expressions, e.g. the
+    // 'a' in 'a OR (a AND b)'. The remaining OR is then always true once the
+    // common expressions hold.
+    let mut any_child_eliminated = false;
 
     // Update original child expressions in the OR to no longer contain the
     // common expressions.
@@ -87,6 +97,7 @@ fn maybe_rewrite_or(orig_expr: &mut ConjunctionExpr) -> Result<()> {
                 match new_and_children.len() {
                     0 => {
                         // All AND expressions were pulled out.
+                        any_child_eliminated = true;
                     }
                     1 => {
                         // We have single AND child remaining, just use that
@@ -103,13 +114,20 @@ fn maybe_rewrite_or(orig_expr: &mut ConjunctionExpr) -> Result<()> {
                 }
             }
             other => {
-                if !common_exprs.contains(&other) {
+                if common_exprs.contains(&other) {
+                    any_child_eliminated = true;
+                } else {
                     new_or_children.push(other);
                 }
             }
         }
     }
 
+    if any_child_eliminated {
+        // 'a OR (a AND b)' is just 'a'.
+        new_or_children.clear();
+    }
+
     // OR expression now becomes an AND expression.
     *orig_expr = ConjunctionExpr {
         op: ConjunctionOperator::And,
@@ -130,8 +148,6 @@ fn maybe_rewrite_or(orig_expr: &mut ConjunctionExpr) -> Result<()> {
             //
             // This isn't useful on its own, so just append directly to the AND
             // expression.
-            //
-            // Would happen in a case like 'a OR (a AND b)'.
             orig_expr.expressions.append(&mut new_or_children);
         }
         _ => {
@@ -205,10 +221,10 @@ mod tests {
 
     #[test]
     fn distribute_eliminate_or_with_single_remaining() {
-        // '(0) OR (0 AND 1)' => '(0 AND 1)'
+        // '(0) OR (0 AND 1)' => '(0)'
         let expr = or([lit(0), and([lit(0), lit(1)]).unwrap()]).unwrap();
 
-        let expected = and([lit(0), lit(1)]).unwrap();
+        let expected = lit(0);
 
         let table_list = TableList::empty();
         let got = DistributiveOrRewrite::rewrite(&table_list, expr).unwrap();
@@ -6,6 +6,7 @@ use super::ExpressionRewriteRule;
 use crate::expr::conjunction_expr::{ConjunctionExpr, ConjunctionOperator};
 use crate::expr::Expression;
 use crate::logical::binder::table_list::{TableList, TableRef};
+use crate::logical::walk::Recursion;
 
 /// Rewrites join filter expressions containing ORs that reference both sides of
 /// a join to an AND expression with the OR distributed.
@@ -21,18 +22,17 @@ pub struct JoinFilterOrRewrite;
 
 impl ExpressionRewriteRule for JoinFilterOrRewrite {
     fn rewrite(_table_list: &TableList, mut expression: Expression) -> Result<Expression> {
-        fn inner(expr: &mut Expression) -> Result<()> {
-            match expr {
+        expression.walk_mut(
+            &mut |expr| match expr {
                 Expression::Conjunction(conj) if conj.op == ConjunctionOperator::Or => {
                     maybe_rewrite_or(conj)?;
                     // Don't recurse here.
-                    Ok(())
+                    Ok(Recursion::SkipChildren)
                 }
-                other => other.for_each_child_mut(&mut inner),
-            }
-        }
-
-        inner(&mut expression)?;
+                _ => Ok(Recursion::Continue),
+            },
+            &mut |_| Ok(()),
+        )?;
 
         Ok(expression)
     }
@@ -8,6 +8,7 @@ use crate::functions::scalar::builtin::string::{Contains, EndsWith, Like, Starts
 use crate::functions::scalar::ScalarFunction;
 use crate::functions::FunctionInfo;
 use crate::logical::binder::table_list::TableList;
+use crate::logical::walk::Recursion;
 use crate::optimizer::expr_rewrite::const_fold::ConstFold;
 
 /// Rewrite LIKE expressions into equivalent prefix/suffix/contains calls if
@@ -17,14 +18,14 @@ pub struct LikeRewrite;
 
 impl ExpressionRewriteRule for LikeRewrite {
     fn rewrite(table_list: &TableList, mut expression: Expression) -> Result<Expression> {
-        fn inner(table_list: &TableList, expr: &mut Expression) -> Result<()> {
-            match expr {
+        expression.walk_mut(
+            &mut |expr| match expr {
                 Expression::ScalarFunction(scalar)
                     if scalar.function.function.name() == Like.name() =>
                 {
                     let pattern = &scalar.function.inputs[1];
                     if !pattern.is_const_foldable() {
-                        return Ok(());
+                        return Ok(Recursion::SkipChildren);
                     }
 
                     let pattern = ConstFold::rewrite(table_list, pattern.clone())?
@@ -38,7 +39,7 @@ impl ExpressionRewriteRule for LikeRewrite {
                             op: ComparisonOperator::Eq,
                         });
 
-                        Ok(())
+                        Ok(Recursion::SkipChildren)
                     } else if is_prefix_pattern(&pattern) {
                         // LIKE -> STARTS_WITH
 
@@ -49,7 +50,7 @@ impl ExpressionRewriteRule for LikeRewrite {
 
                         *expr = Expression::ScalarFunction(ScalarFunctionExpr { function });
 
-                        Ok(())
+                        Ok(Recursion::SkipChildren)
                     } else if is_suffix_pattern(&pattern) {
                         // LIKE -> ENDS_WITH
 
@@ -60,7 +61,7 @@ impl ExpressionRewriteRule for LikeRewrite {
 
                         *expr = Expression::ScalarFunction(ScalarFunctionExpr { function });
 
-                        Ok(())
+                        Ok(Recursion::SkipChildren)
                     } else if is_contains_pattern(&pattern) {
                         // LIKE -> CONTAINS
 
@@ -71,17 +72,16 @@ impl ExpressionRewriteRule for LikeRewrite {
 
                         *expr = Expression::ScalarFunction(ScalarFunctionExpr { function });
 
-                        Ok(())
+                        Ok(Recursion::SkipChildren)
                     } else {
                         // Leave unchanged.
-                        Ok(())
+                        Ok(Recursion::SkipChildren)
                     }
                 }
-                other => other.for_each_child_mut(&mut |child| inner(table_list, child)),
-            }
-        }
-
-        inner(table_list, &mut expression)?;
+                _ => Ok(Recursion::Continue),
+            },
+            &mut |_| Ok(()),
+        )?;
 
         Ok(expression)
     }
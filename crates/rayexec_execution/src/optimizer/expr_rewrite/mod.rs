@@ -16,6 +16,7 @@ use crate::expr::{self, Expression};
 use crate::logical::binder::bind_context::BindContext;
 use crate::logical::binder::table_list::TableList;
 use crate::logical::operator::{LogicalNode, LogicalOperator};
+use crate::logical::walk::Recursion;
 
 pub trait ExpressionRewriteRule {
     /// Rewrite a single expression.
@@ -33,39 +34,44 @@ impl OptimizeRule for ExpressionRewriter {
     fn optimize(
         &mut self,
         bind_context: &mut BindContext,
-        plan: LogicalOperator,
+        mut plan: LogicalOperator,
     ) -> Result<LogicalOperator> {
         let table_list = bind_context.get_table_list();
 
-        let mut plan = match plan {
-            LogicalOperator::Project(mut project) => {
-                project.node.projections =
-                    Self::apply_rewrites_all(table_list, project.node.projections)?;
-                LogicalOperator::Project(project)
-            }
-            LogicalOperator::Filter(mut filter) => {
-                filter.node.filter = Self::apply_rewrites(table_list, filter.node.filter)?;
-                filter.node.filter = JoinFilterOrRewrite::rewrite(table_list, filter.node.filter)?; // Special rewrite for join filter condition.
-                LogicalOperator::Filter(filter)
-            }
-            LogicalOperator::ArbitraryJoin(mut join) => {
-                join.node.condition = Self::apply_rewrites(table_list, join.node.condition)?;
-                join.node.condition =
-                    JoinFilterOrRewrite::rewrite(table_list, join.node.condition)?; // Special rewrite for join filter condition.
-                LogicalOperator::ArbitraryJoin(join)
-            }
-            mut other => {
-                other.for_each_expr_mut(&mut |expr| {
-                    let mut orig = std::mem::replace(expr, expr::lit(83));
-                    orig = Self::apply_rewrites(table_list, orig)?;
-                    *expr = orig;
-                    Ok(())
-                })?;
-                other
-            }
-        };
-
-        plan.modify_replace_children(&mut |child| self.optimize(bind_context, child))?;
+        plan.walk_mut(
+            &mut |op| {
+                match op {
+                    LogicalOperator::Project(project) => {
+                        project.node.projections = Self::apply_rewrites_all(
+                            table_list,
+                            std::mem::take(&mut project.node.projections),
+                        )?;
+                    }
+                    LogicalOperator::Filter(filter) => {
+                        let orig = std::mem::replace(&mut filter.node.filter, expr::lit(83));
+                        let rewritten = Self::apply_rewrites(table_list, orig)?;
+                        filter.node.filter = JoinFilterOrRewrite::rewrite(table_list, rewritten)?;
+                        // Special rewrite for join filter condition.
+                    }
+                    LogicalOperator::ArbitraryJoin(join) => {
+                        let orig = std::mem::replace(&mut join.node.condition, expr::lit(83));
+                        let rewritten = Self::apply_rewrites(table_list, orig)?;
+                        join.node.condition = JoinFilterOrRewrite::rewrite(table_list, rewritten)?;
+                        // Special rewrite for join filter condition.
+                    }
+                    other => {
+                        other.for_each_expr_mut(&mut |expr| {
+                            let mut orig = std::mem::replace(expr, expr::lit(83));
+                            orig = Self::apply_rewrites(table_list, orig)?;
+                            *expr = orig;
+                            Ok(())
+                        })?;
+                    }
+                }
+                Ok(Recursion::Continue)
+            },
+            &mut |_| Ok(()),
+        )?;
 
         Ok(plan)
     }
@@ -4,6 +4,7 @@ use super::ExpressionRewriteRule;
 use crate::expr::conjunction_expr::{ConjunctionExpr, ConjunctionOperator};
 use crate::expr::Expression;
 use crate::logical::binder::table_list::TableList;
+use crate::logical::walk::Recursion;
 
 /// Unnest nested AND or OR expressions.
 ///
@@ -13,37 +14,20 @@ pub struct UnnestConjunctionRewrite;
 
 impl ExpressionRewriteRule for UnnestConjunctionRewrite {
     fn rewrite(_table_list: &TableList, mut expression: Expression) -> Result<Expression> {
-        fn inner(expression: &mut Expression) {
-            match expression {
-                Expression::Conjunction(ConjunctionExpr { op, expressions }) => {
+        expression.walk_mut(
+            &mut |expr| {
+                if let Expression::Conjunction(ConjunctionExpr { op, expressions }) = expr {
                     let mut new_expressions = Vec::with_capacity(expressions.len());
                     for expr in expressions.drain(..) {
                         unnest_op(expr, *op, &mut new_expressions);
                     }
-
-                    *expression = Expression::Conjunction(ConjunctionExpr {
-                        op: *op,
-                        expressions: new_expressions,
-                    });
-
-                    // Recurse into the children too.
-                    expression
-                        .for_each_child_mut(&mut |child| {
-                            inner(child);
-                            Ok(())
-                        })
-                        .expect("unnest to not fail")
+                    *expressions = new_expressions;
                 }
-                other => other
-                    .for_each_child_mut(&mut |child| {
-                        inner(child);
-                        Ok(())
-                    })
-                    .expect("unnest to not fail"),
-            }
-        }
-
-        inner(&mut expression);
+                // Recurse into the children too.
+                Ok(Recursion::Continue)
+            },
+            &mut |_| Ok(()),
+        )?;
 
         Ok(expression)
     }
@@ -224,7 +224,11 @@ where
                                 continue;
                             }
 
-                            // TODO: Do we need to push to arbitrary here?
+                            // Can't be used as a join condition, but still
+                            // needs to filter the join output.
+                            extracted
+                                .arbitrary
+                                .push(Expression::Comparison(ComparisonExpr { left, right, op }));
                         }
                         other => {
                             extracted.arbitrary.push(other);
@@ -283,3 +287,29 @@ where
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn comparison_referencing_both_sides_kept_as_filter() {
+        let left = vec![TableRef::from(0)];
+        let right = vec![TableRef::from(1)];
+
+        // Left operand references both sides, can't be a join condition.
+        let filter = expr::eq(
+            expr::add(expr::col_ref(0, 0), expr::col_ref(1, 0)),
+            expr::lit(4),
+        );
+        let condition = expr::lt_eq(expr::col_ref(0, 0), expr::col_ref(1, 0));
+
+        let extracted = JoinConditionExtractor::new(&left, &right, JoinType::Inner)
+            .extract(vec![filter.clone(), condition])
+            .unwrap();
+
+        assert_eq!(1, extracted.comparisons.len());
+        assert_eq!(vec![filter], extracted.arbitrary);
+    }
+}
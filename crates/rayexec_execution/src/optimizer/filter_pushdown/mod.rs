@@ -26,8 +26,11 @@ use crate::logical::logical_join::{
 use crate::logical::logical_materialization::LogicalMaterializationScan;
 use crate::logical::logical_order::LogicalOrder;
 use crate::logical::logical_project::LogicalProject;
+use crate::logical::logical_scan::{LogicalScan, ScanSource};
+use crate::logical::logical_setop::LogicalSetop;
 use crate::logical::operator::{LocationRequirement, LogicalNode, LogicalOperator, Node};
 use crate::logical::planner::plan_from::FromPlanner;
+use crate::logical::scan_filter::ScanFilter;
 use crate::logical::statistics::StatisticsValue;
 
 // TODO: ExtractedFilter seems to not be entirely worth it here. There's
@@ -61,6 +64,8 @@ impl OptimizeRule for FilterPushdown {
             LogicalOperator::MaterializationScan(mat) => {
                 self.pushdown_materialized_scan(bind_context, mat)
             }
+            LogicalOperator::Scan(scan) => self.pushdown_scan(bind_context, scan),
+            LogicalOperator::SetOp(setop) => self.pushdown_setop(bind_context, setop),
             other => self.stop_pushdown(bind_context, other),
         }
     }
@@ -118,6 +123,37 @@ impl FilterPushdown {
         }))
     }
 
+    /// Stop the pushdown at a table or table function scan, also handing
+    /// simple filters to the scan.
+    ///
+    /// The scan may use the filters to skip reading data. The filters remain
+    /// in place above the scan.
+    fn pushdown_scan(
+        &mut self,
+        bind_context: &mut BindContext,
+        plan: Node<LogicalScan>,
+    ) -> Result<LogicalOperator> {
+        let mut plan = self.stop_pushdown(bind_context, LogicalOperator::Scan(plan))?;
+
+        if let LogicalOperator::Filter(filter) = &mut plan {
+            if let [LogicalOperator::Scan(scan)] = filter.children.as_mut_slice() {
+                if matches!(
+                    scan.node.source,
+                    ScanSource::Table { .. } | ScanSource::TableFunction { .. }
+                ) {
+                    let mut exprs = Vec::new();
+                    split_conjunction(filter.node.filter.clone(), &mut exprs);
+                    scan.node.scan_filters = exprs
+                        .iter()
+                        .filter_map(|expr| ScanFilter::try_from_expr(&scan.node, expr))
+                        .collect();
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
     fn pushdown_materialized_scan(
         &mut self,
         bind_context: &mut BindContext,
@@ -203,6 +239,14 @@ impl FilterPushdown {
                 continue;
             }
 
+            // Or GROUPING output.
+            if let Some(grouping_functions_table) = plan.node.grouping_functions_table {
+                if filter.table_refs.contains(&grouping_functions_table) {
+                    remaining_filters.push(filter);
+                    continue;
+                }
+            }
+
             let grouping_sets = match &plan.node.grouping_sets {
                 Some(sets) => sets,
                 None => {
@@ -267,6 +311,39 @@ impl FilterPushdown {
         Ok(LogicalOperator::Distinct(plan))
     }
 
+    /// Push down through a set operation.
+    ///
+    /// Filters on the output of a set operation can be applied to each input
+    /// instead, with column references updated to point to the columns of
+    /// that input.
+    fn pushdown_setop(
+        &mut self,
+        bind_context: &mut BindContext,
+        mut plan: Node<LogicalSetop>,
+    ) -> Result<LogicalOperator> {
+        let table_ref = plan.node.table_ref;
+        let filters: Vec<_> = self.drain_filters().map(|f| f.filter).collect();
+
+        plan.modify_replace_children(&mut |child| {
+            let mut columns = Vec::new();
+            for child_ref in child.get_output_table_refs(bind_context) {
+                let num_columns = bind_context.get_table(child_ref)?.num_columns();
+                columns.extend((0..num_columns).map(|col| expr::col_ref(child_ref, col)));
+            }
+
+            let mut child_pushdown = Self::default();
+            for filter in &filters {
+                let mut expr = filter.clone();
+                replace_references(&columns, table_ref, &mut expr)?;
+                child_pushdown.add_filters([expr]);
+            }
+
+            child_pushdown.optimize(bind_context, child)
+        })?;
+
+        Ok(LogicalOperator::SetOp(plan))
+    }
+
     /// Push down through an ORDER BY.
     ///
     /// No changes needed for the order by node.
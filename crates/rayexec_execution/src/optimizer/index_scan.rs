@@ -0,0 +1,530 @@
+use std::sync::Arc;
+
+use rayexec_error::Result;
+
+use super::OptimizeRule;
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::database::catalog_entry::{CatalogEntry, IndexMethod, TableEntry, TableIndex};
+use crate::database::DatabaseContext;
+use crate::expr::comparison_expr::ComparisonOperator;
+use crate::expr::conjunction_expr::ConjunctionOperator;
+use crate::expr::Expression;
+use crate::functions::scalar::builtin::similarity::VectorMetric;
+use crate::functions::scalar::builtin::text_search::{Matches, TextSearchConfig};
+use crate::functions::FunctionInfo;
+use crate::logical::binder::bind_context::BindContext;
+use crate::logical::logical_filter::LogicalFilter;
+use crate::logical::logical_limit::LogicalLimit;
+use crate::logical::logical_scan::{LogicalScan, ScanSource};
+use crate::logical::operator::{LogicalOperator, Node};
+use crate::logical::walk::Recursion;
+use crate::storage::table_storage::{IndexBound, IndexScan, NearestNeighbors};
+
+/// Read rows through a table index when a filter directly above a table scan
+/// constrains the index's columns.
+///
+/// Only comparisons between a column and a constant are used. An index is
+/// usable if some prefix of its columns is compared for equality, optionally
+/// followed by a range on the next column. Full-text indexes are usable for
+/// `matches` (MATCH) predicates on the indexed column with a constant query.
+/// The filter is left in place, so index scans only need to produce a
+/// superset of the matching rows.
+///
+/// HNSW indexes are used for `ORDER BY <distance>(column, constant) LIMIT k`
+/// directly over an unfiltered table scan, where the distance function
+/// matches the index's metric. The order and limit are left in place, and
+/// pick the final rows from the approximate nearest neighbors found through
+/// the index.
+///
+/// Like aggregate pushdown, this needs the database context to check what the
+/// table supports.
+#[derive(Debug)]
+pub struct IndexScanRule<'a> {
+    context: &'a DatabaseContext,
+}
+
+impl<'a> IndexScanRule<'a> {
+    pub fn new(context: &'a DatabaseContext) -> Self {
+        IndexScanRule { context }
+    }
+
+    /// Pick an index scan for the scan below the filter.
+    ///
+    /// Returns None if no index can be used.
+    fn try_index_scan(&self, filter: &Node<LogicalFilter>) -> Result<Option<IndexScan>> {
+        let scan = match filter.children.as_slice() {
+            [LogicalOperator::Scan(scan)] => scan,
+            _ => return Ok(None),
+        };
+
+        if scan.node.sample.is_some()
+            || scan.node.limit.is_some()
+            || scan.node.aggregate.is_some()
+            || scan.node.index_scan.is_some()
+        {
+            return Ok(None);
+        }
+
+        let (catalog, schema, source) = match &scan.node.source {
+            ScanSource::Table {
+                catalog,
+                schema,
+                source,
+            } => (catalog, schema, source),
+            _ => return Ok(None),
+        };
+
+        let table = source.try_as_table_entry()?;
+        if table.indexes.is_empty() {
+            return Ok(None);
+        }
+
+        let mut comparisons = Vec::new();
+        let mut matches = Vec::new();
+        collect_comparisons(scan, &filter.node.filter, &mut comparisons, &mut matches);
+        if comparisons.is_empty() && matches.is_empty() {
+            return Ok(None);
+        }
+
+        let mut best: Option<(usize, IndexScan)> = None;
+        for index in &table.indexes {
+            let planned = match index.method {
+                IndexMethod::BTree => plan_index_scan(table, index, &comparisons),
+                IndexMethod::FullText => plan_full_text_scan(table, index, &matches),
+                // Vector indexes are used for ordering, not filtering.
+                IndexMethod::Hnsw(_) => None,
+            };
+            if let Some((score, index_scan)) = planned {
+                if best.as_ref().map(|(best, _)| score > *best).unwrap_or(true) {
+                    best = Some((score, index_scan));
+                }
+            }
+        }
+
+        let index_scan = match best {
+            Some((_, index_scan)) => index_scan,
+            None => return Ok(None),
+        };
+
+        let supported = self.supports_index_scan(catalog, schema, source, &index_scan)?;
+
+        Ok(supported.then_some(index_scan))
+    }
+
+    /// Pick a nearest neighbor index scan for the scan below the limit and
+    /// order.
+    ///
+    /// Returns None if no vector index can be used.
+    fn try_nearest_scan(&self, limit: &Node<LogicalLimit>) -> Result<Option<IndexScan>> {
+        let order = match limit.children.as_slice() {
+            [LogicalOperator::Order(order)] => order,
+            _ => return Ok(None),
+        };
+        let (project, scan) = match order.children.as_slice() {
+            [LogicalOperator::Scan(scan)] => (None, scan),
+            [LogicalOperator::Project(project)] => match project.children.as_slice() {
+                [LogicalOperator::Scan(scan)] => (Some(project), scan),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        let order_expr = match order.node.exprs.as_slice() {
+            [order_expr] => order_expr,
+            _ => return Ok(None),
+        };
+        // Order by expressions are usually column references into the child
+        // projection.
+        let expr = match (&order_expr.expr, project) {
+            (Expression::Column(col), Some(project))
+                if col.table_scope == project.node.projection_table =>
+            {
+                match project.node.projections.get(col.column) {
+                    Some(expr) => expr,
+                    None => return Ok(None),
+                }
+            }
+            (expr, _) => expr,
+        };
+
+        let (metric, column, query) = match vector_distance(scan, expr) {
+            Some(distance) => distance,
+            None => return Ok(None),
+        };
+        if order_expr.desc != metric.nearest_is_largest() {
+            return Ok(None);
+        }
+
+        if scan.node.sample.is_some()
+            || scan.node.limit.is_some()
+            || scan.node.aggregate.is_some()
+            || scan.node.index_scan.is_some()
+            || !scan.node.scan_filters.is_empty()
+        {
+            return Ok(None);
+        }
+
+        let (catalog, schema, source) = match &scan.node.source {
+            ScanSource::Table {
+                catalog,
+                schema,
+                source,
+            } => (catalog, schema, source),
+            _ => return Ok(None),
+        };
+
+        let table = source.try_as_table_entry()?;
+        let index = table.indexes.iter().find(|index| {
+            index.method == IndexMethod::Hnsw(metric)
+                && index
+                    .column_ids
+                    .first()
+                    .and_then(|id| table.column_ids.iter().position(|col| col == id))
+                    == Some(column)
+        });
+        let index = match index {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        let index_scan = IndexScan {
+            index: index.name.clone(),
+            column_names: vec![table.columns[column].name.clone()],
+            equal: Vec::new(),
+            lower: None,
+            upper: None,
+            terms: Vec::new(),
+            nearest: Some(NearestNeighbors {
+                query,
+                k: limit.node.limit + limit.node.offset.unwrap_or(0),
+            }),
+        };
+
+        let supported = self.supports_index_scan(catalog, schema, source, &index_scan)?;
+
+        Ok(supported.then_some(index_scan))
+    }
+
+    fn supports_index_scan(
+        &self,
+        catalog: &str,
+        schema: &str,
+        source: &Arc<CatalogEntry>,
+        index_scan: &IndexScan,
+    ) -> Result<bool> {
+        let database = self.context.get_database(catalog)?;
+        Ok(match &database.table_storage {
+            Some(storage) => storage
+                .data_table(self.context.transaction(), schema, source)?
+                .supports_index_scan(index_scan),
+            None => false,
+        })
+    }
+}
+
+impl OptimizeRule for IndexScanRule<'_> {
+    fn optimize(
+        &mut self,
+        _bind_context: &mut BindContext,
+        mut plan: LogicalOperator,
+    ) -> Result<LogicalOperator> {
+        plan.walk_mut(
+            &mut |op| {
+                if let LogicalOperator::Filter(filter) = op {
+                    if let Some(index_scan) = self.try_index_scan(filter)? {
+                        if let [LogicalOperator::Scan(scan)] = filter.children.as_mut_slice() {
+                            scan.node.index_scan = Some(Box::new(index_scan));
+                        }
+                        return Ok(Recursion::SkipChildren);
+                    }
+                }
+
+                if let LogicalOperator::Limit(limit) = op {
+                    if let Some(index_scan) = self.try_nearest_scan(limit)? {
+                        if let Some(scan) = scan_below_order(limit) {
+                            scan.node.index_scan = Some(Box::new(index_scan));
+                        }
+                        return Ok(Recursion::SkipChildren);
+                    }
+                }
+
+                Ok(Recursion::Continue)
+            },
+            &mut |_| Ok(()),
+        )?;
+
+        Ok(plan)
+    }
+}
+
+/// Get the scan below a limit and order, possibly with a projection in
+/// between.
+fn scan_below_order(limit: &mut Node<LogicalLimit>) -> Option<&mut Node<LogicalScan>> {
+    let order = match limit.children.as_mut_slice() {
+        [LogicalOperator::Order(order)] => order,
+        _ => return None,
+    };
+    match order.children.as_mut_slice() {
+        [LogicalOperator::Scan(scan)] => Some(scan),
+        [LogicalOperator::Project(project)] => match project.children.as_mut_slice() {
+            [LogicalOperator::Scan(scan)] => Some(scan),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Get the metric, table column position, and query vector for a distance
+/// function between a scanned column and a constant vector.
+fn vector_distance(
+    scan: &Node<LogicalScan>,
+    expr: &Expression,
+) -> Option<(VectorMetric, usize, OwnedScalarValue)> {
+    let func = match expr {
+        Expression::ScalarFunction(func) => func,
+        _ => return None,
+    };
+    let metric = VectorMetric::from_function_name(func.function.function.name())?;
+
+    let (column, query) = match func.function.inputs.as_slice() {
+        [column, Expression::Literal(query)] | [Expression::Literal(query), column] => {
+            (column, query)
+        }
+        _ => return None,
+    };
+    if !matches!(query.literal, ScalarValue::List(_)) {
+        return None;
+    }
+
+    // The column may be cast to a wider float type to match the query, which
+    // doesn't change which rows are nearest.
+    let column = match column {
+        Expression::Cast(cast) => cast.expr.as_ref(),
+        column => column,
+    };
+    let column = match column {
+        Expression::Column(col) if col.table_scope == scan.node.table_ref => col,
+        _ => return None,
+    };
+    let column = *scan.node.projection.get(column.column)?;
+
+    Some((metric, column, query.literal.clone()))
+}
+
+/// A comparison between a table column and a non-null constant.
+#[derive(Debug)]
+struct ColumnComparison {
+    /// Position of the column in the table.
+    column: usize,
+    op: ComparisonOperator,
+    constant: OwnedScalarValue,
+}
+
+/// A full-text match between a table column and a constant query.
+#[derive(Debug)]
+struct ColumnMatch {
+    /// Position of the column in the table.
+    column: usize,
+    /// Terms in the query.
+    terms: Vec<String>,
+}
+
+/// Collect comparisons and full-text matches from the ANDed parts of a
+/// filter.
+///
+/// Parts that aren't simple comparisons are skipped, they don't prevent using
+/// an index for the rest of the filter.
+fn collect_comparisons(
+    scan: &Node<LogicalScan>,
+    expr: &Expression,
+    comparisons: &mut Vec<ColumnComparison>,
+    matches: &mut Vec<ColumnMatch>,
+) {
+    match expr {
+        Expression::Conjunction(conj) if conj.op == ConjunctionOperator::And => {
+            for expr in &conj.expressions {
+                collect_comparisons(scan, expr, comparisons, matches);
+            }
+        }
+        Expression::ScalarFunction(func) if func.function.function.name() == Matches.name() => {
+            let (column, query) = match func.function.inputs.as_slice() {
+                [Expression::Column(col), Expression::Literal(query), ..] => (col, query),
+                _ => return,
+            };
+
+            // Full-text indexes are built using the 'simple' config.
+            let config = match func.function.inputs.get(2) {
+                Some(Expression::Literal(lit)) => match &lit.literal {
+                    ScalarValue::Utf8(name) => TextSearchConfig::from_name(name).ok(),
+                    _ => None,
+                },
+                Some(_) => None,
+                None => Some(TextSearchConfig::Simple),
+            };
+            if column.table_scope != scan.node.table_ref {
+                return;
+            }
+            if config != Some(TextSearchConfig::Simple) {
+                return;
+            }
+
+            let terms = match &query.literal {
+                ScalarValue::Utf8(query) => TextSearchConfig::Simple.term_set(query),
+                _ => return,
+            };
+            // Queries without terms match nothing, leave that to the filter.
+            if terms.is_empty() {
+                return;
+            }
+
+            if let Some(column) = scan.node.projection.get(column.column) {
+                matches.push(ColumnMatch {
+                    column: *column,
+                    terms,
+                });
+            }
+        }
+        Expression::Comparison(cmp) => {
+            let (column, op, constant) = match (cmp.left.as_ref(), cmp.right.as_ref()) {
+                (Expression::Column(col), Expression::Literal(lit)) => (col, cmp.op, lit),
+                (Expression::Literal(lit), Expression::Column(col)) => (col, cmp.op.flip(), lit),
+                _ => return,
+            };
+
+            if column.table_scope != scan.node.table_ref
+                || op == ComparisonOperator::NotEq
+                || constant.literal == ScalarValue::Null
+            {
+                return;
+            }
+
+            // Index keys are compared using the column's type, constants of
+            // other types would need to be cast first.
+            match scan.node.types.get(column.column) {
+                Some(datatype) if *datatype == constant.literal.datatype() => (),
+                _ => return,
+            }
+
+            if let Some(column) = scan.node.projection.get(column.column) {
+                comparisons.push(ColumnComparison {
+                    column: *column,
+                    op,
+                    constant: constant.literal.clone(),
+                });
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Build an index scan for a single index from the filter's comparisons.
+///
+/// Returns the scan along with a score for how selective it is expected to be.
+/// Returns None if the index's leading column isn't constrained.
+fn plan_index_scan(
+    table: &TableEntry,
+    index: &TableIndex,
+    comparisons: &[ColumnComparison],
+) -> Option<(usize, IndexScan)> {
+    let mut column_names = Vec::new();
+    let mut equal = Vec::new();
+    let mut lower: Option<IndexBound> = None;
+    let mut upper: Option<IndexBound> = None;
+
+    for id in &index.column_ids {
+        let column = table.column_ids.iter().position(|col| col == id)?;
+
+        let eq = comparisons
+            .iter()
+            .find(|cmp| cmp.column == column && cmp.op == ComparisonOperator::Eq);
+        if let Some(eq) = eq {
+            column_names.push(table.columns[column].name.clone());
+            equal.push(eq.constant.clone());
+            continue;
+        }
+
+        // Range on the column following the equality prefix. Only the first
+        // bound in each direction is used, the filter handles the rest.
+        for cmp in comparisons.iter().filter(|cmp| cmp.column == column) {
+            match cmp.op {
+                ComparisonOperator::Gt | ComparisonOperator::GtEq if lower.is_none() => {
+                    lower = Some(IndexBound {
+                        value: cmp.constant.clone(),
+                        inclusive: cmp.op == ComparisonOperator::GtEq,
+                    })
+                }
+                ComparisonOperator::Lt | ComparisonOperator::LtEq if upper.is_none() => {
+                    upper = Some(IndexBound {
+                        value: cmp.constant.clone(),
+                        inclusive: cmp.op == ComparisonOperator::LtEq,
+                    })
+                }
+                _ => (),
+            }
+        }
+        if lower.is_some() || upper.is_some() {
+            column_names.push(table.columns[column].name.clone());
+        }
+        break;
+    }
+
+    let range = usize::from(lower.is_some()) + usize::from(upper.is_some());
+    let score = equal.len() * 3 + range;
+    if score == 0 {
+        return None;
+    }
+
+    Some((
+        score,
+        IndexScan {
+            index: index.name.clone(),
+            column_names,
+            equal,
+            lower,
+            upper,
+            terms: Vec::new(),
+            nearest: None,
+        },
+    ))
+}
+
+/// Build an index scan for a full-text index from the filter's matches.
+///
+/// Terms from all matches on the indexed column are combined since rows must
+/// contain all of them.
+fn plan_full_text_scan(
+    table: &TableEntry,
+    index: &TableIndex,
+    matches: &[ColumnMatch],
+) -> Option<(usize, IndexScan)> {
+    let id = index.column_ids.first()?;
+    let column = table.column_ids.iter().position(|col| col == id)?;
+
+    let mut terms: Vec<String> = matches
+        .iter()
+        .filter(|m| m.column == column)
+        .flat_map(|m| m.terms.iter().cloned())
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    terms.sort_unstable();
+    terms.dedup();
+
+    // Score like a single equality, with each additional term narrowing the
+    // rows further.
+    let score = 2 + terms.len();
+
+    Some((
+        score,
+        IndexScan {
+            index: index.name.clone(),
+            column_names: vec![table.columns[column].name.clone()],
+            equal: Vec::new(),
+            lower: None,
+            upper: None,
+            terms,
+            nearest: None,
+        },
+    ))
+}
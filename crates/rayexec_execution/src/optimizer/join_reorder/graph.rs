@@ -868,6 +868,7 @@ impl Graph {
                 node: LogicalComparisonJoin {
                     join_type,
                     conditions,
+                    merge_keys: Vec::new(),
                 },
                 location: LocationRequirement::Any,
                 children: vec![left, right],
@@ -2,9 +2,15 @@ use rayexec_error::Result;
 
 use super::OptimizeRule;
 use crate::logical::binder::bind_context::BindContext;
+use crate::logical::logical_scan::ScanSource;
 use crate::logical::operator::LogicalOperator;
+use crate::logical::walk::Recursion;
 
-/// Push down a limit below a project.
+/// Push down a limit below a project, and into scans.
+///
+/// Limits pushed into scans are only hints for the data source to avoid
+/// reading more than it needs to. The limit operator remains in place above the
+/// scan.
 #[derive(Debug)]
 pub struct LimitPushdown;
 
@@ -14,21 +20,49 @@ impl OptimizeRule for LimitPushdown {
         _bind_context: &mut BindContext,
         mut plan: LogicalOperator,
     ) -> Result<LogicalOperator> {
-        if let LogicalOperator::Limit(mut limit) = plan {
-            if limit.children.len() == 1
-                && matches!(&limit.children[0], LogicalOperator::Project(_))
-            {
-                let mut project = limit.children.pop().unwrap();
-                limit.children = std::mem::take(project.children_mut());
-                *project.children_mut() = vec![LogicalOperator::Limit(limit)];
-
-                plan = project;
-            } else {
-                plan = LogicalOperator::Limit(limit);
-            }
-        }
-
-        plan.modify_replace_children(&mut |child| self.optimize(_bind_context, child))?;
+        plan.walk_mut(
+            &mut |op| {
+                let LogicalOperator::Limit(limit) = op else {
+                    return Ok(Recursion::Continue);
+                };
+
+                if limit.children.len() == 1
+                    && matches!(&limit.children[0], LogicalOperator::Project(_))
+                {
+                    // Swap the limit and the projection. The walk continues
+                    // into the projection's children, allowing the limit to be
+                    // pushed further.
+                    let mut project = limit.children.pop().unwrap();
+                    limit.children = std::mem::take(project.children_mut());
+                    let limit = std::mem::replace(op, project);
+                    *op.children_mut() = vec![limit];
+
+                    return Ok(Recursion::Continue);
+                }
+
+                if let [LogicalOperator::Scan(scan)] = limit.children.as_mut_slice() {
+                    let can_push = matches!(
+                        scan.node.source,
+                        ScanSource::Table { .. } | ScanSource::TableFunction { .. }
+                    );
+
+                    // Sampling needs to see the full table, don't push the
+                    // limit past it.
+                    if can_push && scan.node.sample.is_none() {
+                        // Offset is applied by the limit operator, the scan
+                        // needs to produce enough rows to cover it.
+                        let rows = limit.node.limit + limit.node.offset.unwrap_or(0);
+                        scan.node.limit = Some(match scan.node.limit {
+                            Some(existing) => existing.min(rows),
+                            None => rows,
+                        });
+                    }
+                }
+
+                Ok(Recursion::Continue)
+            },
+            &mut |_| Ok(()),
+        )?;
 
         Ok(plan)
     }
@@ -3,6 +3,7 @@ use rayexec_error::Result;
 use super::OptimizeRule;
 use crate::logical::binder::bind_context::BindContext;
 use crate::logical::operator::{LocationRequirement, LogicalOperator};
+use crate::logical::walk::Recursion;
 
 /// Rule for pushing down and pulling up location requirements for operators.
 ///
@@ -24,7 +25,7 @@ impl OptimizeRule for LocationRule {
         plan.walk_mut(
             &mut |op| {
                 if op.location() == &LocationRequirement::Any {
-                    return Ok(());
+                    return Ok(Recursion::Continue);
                 }
 
                 // Push this operator's location down.
@@ -34,7 +35,9 @@ impl OptimizeRule for LocationRule {
                         *child.location_mut() = loc;
                     }
                     Ok(())
-                })
+                })?;
+
+                Ok(Recursion::Continue)
             },
             &mut |op| {
                 if op.location() != &LocationRequirement::Any {
@@ -0,0 +1,313 @@
+use rayexec_error::Result;
+
+use super::OptimizeRule;
+use crate::arrays::datatype::DataType;
+use crate::expr::column_expr::ColumnExpr;
+use crate::expr::comparison_expr::ComparisonOperator;
+use crate::expr::Expression;
+use crate::logical::binder::bind_context::BindContext;
+use crate::logical::logical_join::{JoinType, LogicalComparisonJoin, MergeJoinKey};
+use crate::logical::operator::{LocationRequirement, LogicalOperator, Node};
+use crate::logical::walk::Recursion;
+
+/// Column an operator's output is sorted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderedColumn {
+    pub column: ColumnExpr,
+    pub desc: bool,
+    pub nulls_first: bool,
+}
+
+/// Get the columns the output of an operator is sorted on, most significant
+/// first.
+///
+/// This is the "interesting order" the operator provides to its parent. Only
+/// sorts produce an order, and the order is kept through operators that
+/// don't reorder or repartition their input (filters, projections, and
+/// limits). Projections keep the order for columns they pass through
+/// unchanged.
+///
+/// Every operator from the sort up needs to execute in `location` (or not
+/// have a location requirement), otherwise rows may be sent between
+/// pipelines in a different order.
+pub fn provided_order(plan: &LogicalOperator, location: LocationRequirement) -> Vec<OrderedColumn> {
+    if plan.location() != &location && plan.location() != &LocationRequirement::Any {
+        return Vec::new();
+    }
+
+    match plan {
+        LogicalOperator::Order(order) => {
+            let mut ordered = Vec::with_capacity(order.node.exprs.len());
+            for order_expr in &order.node.exprs {
+                match &order_expr.expr {
+                    Expression::Column(column) => ordered.push(OrderedColumn {
+                        column: *column,
+                        desc: order_expr.desc,
+                        nulls_first: order_expr.nulls_first,
+                    }),
+                    // Rows are still ordered by the columns before this.
+                    _ => break,
+                }
+            }
+            ordered
+        }
+        LogicalOperator::Filter(filter) => match filter.children.as_slice() {
+            [child] => provided_order(child, location),
+            _ => Vec::new(),
+        },
+        LogicalOperator::Limit(limit) => match limit.children.as_slice() {
+            [child] => provided_order(child, location),
+            _ => Vec::new(),
+        },
+        LogicalOperator::Project(project) => {
+            let child = match project.children.as_slice() {
+                [child] => child,
+                _ => return Vec::new(),
+            };
+
+            let mut ordered = Vec::new();
+            for child_ordered in provided_order(child, location) {
+                let idx = project
+                    .node
+                    .projections
+                    .iter()
+                    .position(|expr| expr == &Expression::Column(child_ordered.column));
+                match idx {
+                    Some(idx) => ordered.push(OrderedColumn {
+                        column: ColumnExpr {
+                            table_scope: project.node.projection_table,
+                            column: idx,
+                        },
+                        ..child_ordered
+                    }),
+                    // Column not part of the output, later columns aren't
+                    // ordered relative to the output anymore.
+                    None => break,
+                }
+            }
+            ordered
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Use a merge join for comparison joins where both inputs are already sorted
+/// on the join's equalities.
+///
+/// A merge join streams the sorted inputs against each other instead of
+/// building a hash table over the left side.
+///
+/// Joins are only converted if the leading sort columns of both inputs are
+/// compared by equalities in the join condition, with both sides sorted the
+/// same way and having the same type. The matched prefix becomes the merge
+/// keys, remaining conditions are checked for rows with equal keys.
+///
+/// This should run after all rules that may replace the inputs of a join.
+#[derive(Debug)]
+pub struct MergeJoinRule;
+
+impl OptimizeRule for MergeJoinRule {
+    fn optimize(
+        &mut self,
+        bind_context: &mut BindContext,
+        mut plan: LogicalOperator,
+    ) -> Result<LogicalOperator> {
+        plan.walk_mut(
+            &mut |op| {
+                if let LogicalOperator::ComparisonJoin(join) = op {
+                    join.node.merge_keys = merge_keys(bind_context, join)?;
+                }
+                Ok(Recursion::Continue)
+            },
+            &mut |_| Ok(()),
+        )?;
+
+        Ok(plan)
+    }
+}
+
+/// Find the equalities both sides of the join are sorted on.
+///
+/// Returns an empty vec if the join can't be executed as a merge join.
+fn merge_keys(
+    bind_context: &BindContext,
+    join: &Node<LogicalComparisonJoin>,
+) -> Result<Vec<MergeJoinKey>> {
+    // Semi, anti, and mark joins deduplicate left rows through the hash join
+    // visit bitmaps.
+    if !matches!(
+        join.node.join_type,
+        JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full
+    ) {
+        return Ok(Vec::new());
+    }
+
+    let (left, right) = match join.children.as_slice() {
+        [left, right] => (left, right),
+        _ => return Ok(Vec::new()),
+    };
+
+    let left_order = provided_order(left, join.location);
+    let right_order = provided_order(right, join.location);
+
+    let table_list = bind_context.get_table_list();
+    let mut keys = Vec::new();
+
+    for (left_col, right_col) in left_order.iter().zip(&right_order) {
+        if left_col.desc != right_col.desc || left_col.nulls_first != right_col.nulls_first {
+            break;
+        }
+
+        let condition = join.node.conditions.iter().position(|cond| {
+            cond.op == ComparisonOperator::Eq
+                && cond.left == Expression::Column(left_col.column)
+                && cond.right == Expression::Column(right_col.column)
+        });
+        let condition = match condition {
+            Some(condition) => condition,
+            None => break,
+        };
+
+        // Keys from both sides are compared using their sort encoding, which
+        // requires the same type on both sides.
+        let cond = &join.node.conditions[condition];
+        let datatype = cond.left.datatype(table_list)?;
+        if datatype != cond.right.datatype(table_list)? || !encoding_matches_equality(&datatype) {
+            break;
+        }
+
+        keys.push(MergeJoinKey {
+            condition,
+            desc: left_col.desc,
+            nulls_first: left_col.nulls_first,
+        });
+    }
+
+    Ok(keys)
+}
+
+/// If keys with equal sort encodings are exactly the keys that compare equal.
+///
+/// Floats are sorted by their total order, which puts -0.0 before 0.0 even
+/// though they're equal. Nested types can't be encoded.
+fn encoding_matches_equality(datatype: &DataType) -> bool {
+    !matches!(
+        datatype,
+        DataType::Float16
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Struct(_)
+            | DataType::List(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logical::binder::bind_query::bind_modifier::BoundOrderByExpr;
+    use crate::logical::logical_filter::LogicalFilter;
+    use crate::logical::logical_order::LogicalOrder;
+    use crate::logical::logical_project::LogicalProject;
+    use crate::logical::statistics::StatisticsValue;
+
+    fn node<N>(node: N, children: Vec<LogicalOperator>) -> Node<N> {
+        Node {
+            node,
+            location: LocationRequirement::Any,
+            children,
+            estimated_cardinality: StatisticsValue::Unknown,
+        }
+    }
+
+    fn order_by(exprs: Vec<(Expression, bool)>, child: LogicalOperator) -> LogicalOperator {
+        LogicalOperator::Order(node(
+            LogicalOrder {
+                exprs: exprs
+                    .into_iter()
+                    .map(|(expr, desc)| BoundOrderByExpr {
+                        expr,
+                        desc,
+                        nulls_first: desc,
+                    })
+                    .collect(),
+            },
+            vec![child],
+        ))
+    }
+
+    fn ordered(table: usize, column: usize, desc: bool) -> OrderedColumn {
+        OrderedColumn {
+            column: ColumnExpr::new(table, column),
+            desc,
+            nulls_first: desc,
+        }
+    }
+
+    #[test]
+    fn order_provides_column_prefix() {
+        let plan = order_by(
+            vec![
+                (Expression::Column(ColumnExpr::new(0, 1)), false),
+                (Expression::Column(ColumnExpr::new(0, 0)), true),
+            ],
+            LogicalOperator::EMPTY,
+        );
+        assert_eq!(
+            vec![ordered(0, 1, false), ordered(0, 0, true)],
+            provided_order(&plan, LocationRequirement::Any)
+        );
+
+        // Not a column, nothing after it can be used.
+        let plan = order_by(
+            vec![
+                (Expression::Column(ColumnExpr::new(0, 1)), false),
+                (crate::expr::lit(1), false),
+                (Expression::Column(ColumnExpr::new(0, 0)), false),
+            ],
+            LogicalOperator::EMPTY,
+        );
+        assert_eq!(
+            vec![ordered(0, 1, false)],
+            provided_order(&plan, LocationRequirement::Any)
+        );
+    }
+
+    #[test]
+    fn order_through_filter_and_project() {
+        let sorted = order_by(
+            vec![
+                (Expression::Column(ColumnExpr::new(0, 0)), false),
+                (Expression::Column(ColumnExpr::new(0, 1)), false),
+            ],
+            LogicalOperator::EMPTY,
+        );
+        let filtered = LogicalOperator::Filter(node(
+            LogicalFilter {
+                filter: crate::expr::lit(true),
+            },
+            vec![sorted],
+        ));
+
+        // Projects the first sort column, but not the second.
+        let plan = LogicalOperator::Project(node(
+            LogicalProject {
+                projections: vec![
+                    Expression::Column(ColumnExpr::new(0, 2)),
+                    Expression::Column(ColumnExpr::new(0, 0)),
+                ],
+                projection_table: 1.into(),
+            },
+            vec![filtered],
+        ));
+
+        assert_eq!(
+            vec![ordered(1, 1, false)],
+            provided_order(&plan, LocationRequirement::Any)
+        );
+    }
+
+    #[test]
+    fn no_order_from_other_operators() {
+        assert!(provided_order(&LogicalOperator::EMPTY, LocationRequirement::Any).is_empty());
+    }
+}
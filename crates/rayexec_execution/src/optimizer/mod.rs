@@ -1,13 +1,20 @@
+pub mod aggregate_pushdown;
 pub mod column_prune;
 pub mod expr_rewrite;
 pub mod filter_pushdown;
+pub mod index_scan;
 pub mod join_reorder;
 pub mod limit_pushdown;
 pub mod location;
+pub mod merge_join;
+pub mod preview;
+pub mod query_pushdown;
+pub mod random_order;
 
 #[allow(dead_code)] // Until it's more robust
 pub mod redundant_groups;
 
+use std::collections::HashSet;
 use std::time::Duration;
 
 use column_prune::ColumnPrune;
@@ -15,6 +22,7 @@ use expr_rewrite::ExpressionRewriter;
 use filter_pushdown::FilterPushdown;
 use join_reorder::JoinReorder;
 use limit_pushdown::LimitPushdown;
+use random_order::RandomOrderSample;
 use rayexec_error::Result;
 use tracing::debug;
 
@@ -22,6 +30,21 @@ use crate::logical::binder::bind_context::BindContext;
 use crate::logical::operator::LogicalOperator;
 use crate::runtime::time::{RuntimeInstant, Timer};
 
+/// Names of rules that can be disabled with the `disable_optimizer_rules`
+/// setting.
+pub const OPTIMIZER_RULE_NAMES: &[&str] = &[
+    "expression_rewrite",
+    "filter_pushdown",
+    "limit_pushdown",
+    "random_order_sample",
+    "column_pruning",
+    "join_reorder",
+    "query_pushdown",
+    "aggregate_pushdown",
+    "index_scan",
+    "merge_join",
+];
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct OptimizerProfileData {
     pub total: Duration,
@@ -31,6 +54,12 @@ pub struct OptimizerProfileData {
 #[derive(Debug)]
 pub struct Optimizer {
     pub profile_data: OptimizerProfileData,
+    /// Rules that will be skipped.
+    pub disabled_rules: HashSet<String>,
+    /// Plans after each applied rule.
+    ///
+    /// Only captured if set, used for debugging the optimizer through EXPLAIN.
+    pub steps: Option<Vec<(&'static str, LogicalOperator)>>,
 }
 
 impl Default for Optimizer {
@@ -43,9 +72,29 @@ impl Optimizer {
     pub fn new() -> Self {
         Optimizer {
             profile_data: OptimizerProfileData::default(),
+            disabled_rules: HashSet::new(),
+            steps: None,
         }
     }
 
+    /// Skip the given rules when optimizing.
+    ///
+    /// Names should be from `OPTIMIZER_RULE_NAMES`.
+    pub fn with_disabled_rules<S>(mut self, rules: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.disabled_rules
+            .extend(rules.into_iter().map(|rule| rule.into()));
+        self
+    }
+
+    /// Capture the plan after each rule is applied.
+    pub fn with_captured_steps(mut self) -> Self {
+        self.steps = Some(Vec::new());
+        self
+    }
+
     /// Run a logical plan through the optimizer.
     pub fn optimize<I>(
         &mut self,
@@ -59,53 +108,40 @@ impl Optimizer {
 
         // Rewrite expressions first, makes it more likely the later
         // optimizations rules will be applied.
-        let timer = Timer::<I>::start();
-        let mut rule = ExpressionRewriter;
-        let plan = rule.optimize(bind_context, plan)?;
-        self.profile_data
-            .timings
-            .push(("expression_rewrite", timer.stop()));
+        let plan =
+            self.apply_rule::<I>("expression_rewrite", ExpressionRewriter, bind_context, plan)?;
 
         // First filter pushdown.
-        let timer = Timer::<I>::start();
-        let mut rule = FilterPushdown::default();
-        let plan = rule.optimize(bind_context, plan)?;
-        self.profile_data
-            .timings
-            .push(("filter_pushdown_1", timer.stop()));
+        let plan = self.apply_rule::<I>(
+            "filter_pushdown",
+            FilterPushdown::default(),
+            bind_context,
+            plan,
+        )?;
 
         // Limit pushdown.
-        let timer = Timer::<I>::start();
-        let mut rule = LimitPushdown;
-        let plan = rule.optimize(bind_context, plan)?;
-        self.profile_data
-            .timings
-            .push(("limit_pushdown", timer.stop()));
+        let plan = self.apply_rule::<I>("limit_pushdown", LimitPushdown, bind_context, plan)?;
+
+        // Replace ORDER BY random() LIMIT with a sample.
+        let plan =
+            self.apply_rule::<I>("random_order_sample", RandomOrderSample, bind_context, plan)?;
 
         // Column pruning.
-        let timer = Timer::<I>::start();
-        let mut rule = ColumnPrune::default();
-        let plan = rule.optimize(bind_context, plan)?;
-        self.profile_data
-            .timings
-            .push(("column_pruning", timer.stop()));
+        let plan =
+            self.apply_rule::<I>("column_pruning", ColumnPrune::default(), bind_context, plan)?;
 
         // TODO: Re-enable this when it works better with duplicated expressions
         // across grouping sets.
-        // let timer = Timer::<I>::start();
-        // let mut rule = RemoveRedundantGroups::default();
-        // let plan = rule.optimize(bind_context, plan)?;
-        // self.profile_data
-        //     .timings
-        //     .push(("remove_redundant_groups", timer.stop()));
-
-        // // Join reordering.
-        let timer = Timer::<I>::start();
-        let mut rule = JoinReorder::default();
-        let plan = rule.optimize(bind_context, plan)?;
-        self.profile_data
-            .timings
-            .push(("join_reorder", timer.stop()));
+        // let plan = self.apply_rule::<I>(
+        //     "remove_redundant_groups",
+        //     RemoveRedundantGroups::default(),
+        //     bind_context,
+        //     plan,
+        // )?;
+
+        // Join reordering.
+        let plan =
+            self.apply_rule::<I>("join_reorder", JoinReorder::default(), bind_context, plan)?;
 
         // DO THE OTHER RULES
 
@@ -115,12 +151,12 @@ impl Optimizer {
         // followed by a filter with the comparison not being an equality.
         // Pushing down again gives us the best chance to get equalities into
         // the condition (for now, we can probably work on the join order more).
-        // let timer = Timer::<I>::start();
-        // let mut rule = FilterPushdown::default();
-        // let plan = rule.optimize(bind_context, plan)?;
-        // self.profile_data
-        //     .timings
-        //     .push(("filter_pushdown_2", timer.stop()));
+        // let plan = self.apply_rule::<I>(
+        //     "filter_pushdown",
+        //     FilterPushdown::default(),
+        //     bind_context,
+        //     plan,
+        // )?;
 
         // TODO: Location clustering once the rest is done.
         // let rule = LocationRule {};
@@ -132,6 +168,34 @@ impl Optimizer {
 
         Ok(plan)
     }
+
+    /// Apply a single rule to the plan, recording how long it took.
+    ///
+    /// The plan is returned unchanged if the rule is disabled.
+    pub fn apply_rule<I>(
+        &mut self,
+        name: &'static str,
+        mut rule: impl OptimizeRule,
+        bind_context: &mut BindContext,
+        plan: LogicalOperator,
+    ) -> Result<LogicalOperator>
+    where
+        I: RuntimeInstant,
+    {
+        if self.disabled_rules.contains(name) {
+            return Ok(plan);
+        }
+
+        let timer = Timer::<I>::start();
+        let plan = rule.optimize(bind_context, plan)?;
+        self.profile_data.timings.push((name, timer.stop()));
+
+        if let Some(steps) = &mut self.steps {
+            steps.push((name, plan.clone()));
+        }
+
+        Ok(plan)
+    }
 }
 
 pub trait OptimizeRule {
@@ -0,0 +1,136 @@
+use rayexec_error::Result;
+
+use super::OptimizeRule;
+use crate::logical::binder::bind_context::BindContext;
+use crate::logical::logical_limit::LogicalLimit;
+use crate::logical::logical_scan::ScanSource;
+use crate::logical::operator::{LocationRequirement, LogicalOperator, Node};
+use crate::logical::statistics::StatisticsValue;
+
+/// Bound every source scan in a plan to some number of rows.
+///
+/// Used for preview execution (`SET preview_rows = ...`) where we want
+/// approximate results quickly instead of scanning entire (possibly huge)
+/// sources. Unlike the other rules, this changes the results of the query, and
+/// should only be applied when preview mode is enabled.
+///
+/// Plans that write data (inserts, COPY TO, CTAS) are left untouched since we
+/// don't want to persist partial results.
+#[derive(Debug)]
+pub struct PreviewSample {
+    /// Max number of rows to read from each source.
+    pub rows: usize,
+    /// Set if we limited any scans, indicating the results are partial.
+    pub applied: bool,
+}
+
+impl PreviewSample {
+    pub fn new(rows: usize) -> Self {
+        PreviewSample {
+            rows,
+            applied: false,
+        }
+    }
+
+    fn limit_scans(&mut self, mut plan: LogicalOperator) -> Result<LogicalOperator> {
+        match plan {
+            LogicalOperator::Scan(scan)
+                if !matches!(scan.node.source, ScanSource::ExpressionList { .. }) =>
+            {
+                let location = scan.location;
+                Ok(self.limit(LogicalOperator::Scan(scan), location))
+            }
+            LogicalOperator::InOut(_) => {
+                // Table functions like `generate_series` produce rows from
+                // their inputs, bound both.
+                plan.modify_replace_children(&mut |child| self.limit_scans(child))?;
+                let location = *plan.location();
+                Ok(self.limit(plan, location))
+            }
+            _ => {
+                plan.modify_replace_children(&mut |child| self.limit_scans(child))?;
+                Ok(plan)
+            }
+        }
+    }
+
+    fn limit(&mut self, plan: LogicalOperator, location: LocationRequirement) -> LogicalOperator {
+        self.applied = true;
+        LogicalOperator::Limit(Node {
+            node: LogicalLimit {
+                offset: None,
+                limit: self.rows,
+            },
+            location,
+            children: vec![plan],
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+}
+
+impl OptimizeRule for PreviewSample {
+    fn optimize(
+        &mut self,
+        _bind_context: &mut BindContext,
+        plan: LogicalOperator,
+    ) -> Result<LogicalOperator> {
+        match plan {
+            LogicalOperator::Insert(_)
+            | LogicalOperator::CopyTo(_)
+            | LogicalOperator::CreateTable(_)
+            | LogicalOperator::CreateView(_) => Ok(plan),
+            plan => self.limit_scans(plan),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logical::logical_project::LogicalProject;
+    use crate::logical::logical_scan::LogicalScan;
+
+    fn scan_with_source(source: ScanSource) -> LogicalOperator {
+        LogicalOperator::Scan(Node {
+            node: LogicalScan {
+                table_ref: 0.into(),
+                types: Vec::new(),
+                names: Vec::new(),
+                projection: Vec::new(),
+                did_prune_columns: false,
+                scan_filters: Vec::new(),
+                sample: None,
+                limit: None,
+                aggregate: None,
+                index_scan: None,
+                source,
+            },
+            location: LocationRequirement::Any,
+            children: Vec::new(),
+            estimated_cardinality: StatisticsValue::Unknown,
+        })
+    }
+
+    #[test]
+    fn expression_list_not_limited() {
+        let plan = LogicalOperator::Project(Node {
+            node: LogicalProject {
+                projections: Vec::new(),
+                projection_table: 1.into(),
+            },
+            location: LocationRequirement::Any,
+            children: vec![scan_with_source(ScanSource::ExpressionList {
+                rows: Vec::new(),
+            })],
+            estimated_cardinality: StatisticsValue::Unknown,
+        });
+
+        let mut rule = PreviewSample::new(10);
+        let out = rule
+            .optimize(&mut BindContext::new(), plan.clone())
+            .unwrap();
+
+        assert!(!rule.applied);
+        assert_eq!(plan, out);
+    }
+}
@@ -0,0 +1,1001 @@
+use rayexec_error::{RayexecError, Result};
+
+use super::OptimizeRule;
+use crate::arrays::batch::Batch;
+use crate::arrays::datatype::DataType;
+use crate::arrays::scalar::ScalarValue;
+use crate::database::DatabaseContext;
+use crate::expr::arith_expr::ArithOperator;
+use crate::expr::comparison_expr::ComparisonOperator;
+use crate::expr::conditional_expr::ConditionalOperator;
+use crate::expr::conjunction_expr::ConjunctionOperator;
+use crate::expr::negate_expr::NegateOperator;
+use crate::expr::Expression;
+use crate::logical::binder::bind_context::BindContext;
+use crate::logical::binder::table_list::TableRef;
+use crate::logical::logical_join::JoinType;
+use crate::logical::logical_scan::{LogicalScan, ScanSource};
+use crate::logical::operator::{LocationRequirement, LogicalNode, LogicalOperator, Node};
+use crate::logical::walk::Recursion;
+use crate::storage::table_storage::RemoteQuery;
+
+/// Push entire query subtrees that only read from a single catalog into that
+/// catalog's table storage.
+///
+/// Subtrees are rendered back to SQL and replaced with a scan of the query's
+/// output. The largest subtree that can be rendered is pushed down, so a query
+/// that only references tables from a single external database will be
+/// executed entirely by that database. Queries reading from multiple catalogs
+/// keep the parts combining the sources local.
+///
+/// Only operators and expressions with the same semantics in most SQL
+/// dialects are rendered. Anything else (e.g. string ordering that depends on
+/// collation) prevents pushing down the subtree containing it.
+///
+/// When a join combines a catalog's tables with a small input from somewhere
+/// else, the small side can be shipped to the catalog instead. The small side
+/// is executed locally, and its rows are sent along with the query as a
+/// VALUES list so that the join (and everything above it) runs remotely.
+/// Shipping is only considered for inputs with an estimated cardinality at or
+/// below the configured threshold.
+///
+/// Checking if a storage supports the query requires the database context, so
+/// this is applied outside of the normal optimizer passes.
+#[derive(Debug)]
+pub struct QueryPushdown<'a> {
+    context: &'a DatabaseContext,
+    /// Max estimated rows for a join input to be shipped to a remote catalog.
+    ///
+    /// Zero disables shipping.
+    ship_threshold: usize,
+}
+
+impl<'a> QueryPushdown<'a> {
+    pub fn new(context: &'a DatabaseContext) -> Self {
+        QueryPushdown {
+            context,
+            ship_threshold: 0,
+        }
+    }
+
+    pub fn with_ship_threshold(mut self, rows: usize) -> Self {
+        self.ship_threshold = rows;
+        self
+    }
+
+    /// Try to replace the plan with a scan of the plan rendered as a query.
+    ///
+    /// If `shipped` is provided, it must be a node within `plan`. It's
+    /// rendered as a reference to the shipped rows, and becomes the child of
+    /// the returned scan.
+    ///
+    /// Returns None if the plan can't be pushed down.
+    fn try_push_query(
+        &self,
+        bind_context: &BindContext,
+        plan: &LogicalOperator,
+        shipped: Option<&LogicalOperator>,
+    ) -> Result<Option<Node<LogicalScan>>> {
+        // Nothing gained from replacing a scan with a query of the same table.
+        if matches!(plan, LogicalOperator::Scan(_)) {
+            return Ok(None);
+        }
+
+        // The replacement scan can only output a single table.
+        let table_ref = match plan.get_output_table_refs(bind_context).as_slice() {
+            [table_ref] => *table_ref,
+            _ => return Ok(None),
+        };
+
+        let shipped = match shipped {
+            Some(shipped) => Some((shipped, ShippedRows::try_new(bind_context, shipped)?)),
+            None => None,
+        };
+
+        let mut renderer = SqlRenderer {
+            bind_context,
+            catalog: None,
+            location: LocationRequirement::Any,
+            alias_idx: 0,
+            shipped: shipped.as_ref().map(|(node, rows)| (*node, rows)),
+        };
+        let sql = match renderer.render_operator(plan) {
+            Some(sql) => sql,
+            None => return Ok(None),
+        };
+        let catalog = match renderer.catalog {
+            Some(catalog) => catalog,
+            None => return Ok(None),
+        };
+        let location = renderer.location;
+
+        let table = bind_context.get_table(table_ref)?;
+        let query = RemoteQuery {
+            sql,
+            columns: (0..table.num_columns())
+                .map(|idx| column_alias(table_ref, idx))
+                .collect(),
+            types: table.column_types.clone(),
+        };
+
+        let supported = match &self.context.get_database(&catalog)?.table_storage {
+            Some(storage) => storage.supports_query(&query),
+            None => false,
+        };
+        if !supported {
+            return Ok(None);
+        }
+
+        let (children, shipped) = match shipped {
+            Some((node, rows)) => (vec![node.clone()], Some(rows)),
+            None => (Vec::new(), None),
+        };
+
+        Ok(Some(Node {
+            node: LogicalScan {
+                table_ref,
+                types: table.column_types.clone(),
+                names: table.column_names.clone(),
+                projection: (0..table.num_columns()).collect(),
+                did_prune_columns: false,
+                scan_filters: Vec::new(),
+                sample: None,
+                limit: None,
+                aggregate: None,
+                index_scan: None,
+                source: ScanSource::Query {
+                    catalog,
+                    query,
+                    shipped,
+                },
+            },
+            location,
+            children,
+            estimated_cardinality: plan.estimated_cardinality(),
+        }))
+    }
+
+    /// Try to push down the plan by shipping the small side of a join within
+    /// it to the catalog the rest of the plan reads from.
+    fn try_ship_join(
+        &self,
+        bind_context: &BindContext,
+        plan: &LogicalOperator,
+    ) -> Result<Option<Node<LogicalScan>>> {
+        let mut candidates = Vec::new();
+        self.collect_ship_candidates(bind_context, plan, &mut candidates);
+
+        for candidate in candidates {
+            let scan = match self.try_push_query(bind_context, plan, Some(candidate))? {
+                Some(scan) => scan,
+                None => continue,
+            };
+
+            // Don't ship rows to a catalog that the input could have been
+            // read from directly.
+            let mut renderer = SqlRenderer {
+                bind_context,
+                catalog: None,
+                location: LocationRequirement::Any,
+                alias_idx: 0,
+                shipped: None,
+            };
+            if renderer.render_operator(candidate).is_some() {
+                let remote = match &scan.node.source {
+                    ScanSource::Query { catalog, .. } => Some(catalog),
+                    _ => None,
+                };
+                if renderer.catalog.as_ref() == remote {
+                    continue;
+                }
+            }
+
+            return Ok(Some(scan));
+        }
+
+        Ok(None)
+    }
+
+    /// Collect join inputs small enough to ship, smallest input of each join
+    /// first.
+    fn collect_ship_candidates<'b>(
+        &self,
+        bind_context: &BindContext,
+        plan: &'b LogicalOperator,
+        candidates: &mut Vec<&'b LogicalOperator>,
+    ) {
+        if matches!(
+            plan,
+            LogicalOperator::CrossJoin(_)
+                | LogicalOperator::ComparisonJoin(_)
+                | LogicalOperator::ArbitraryJoin(_)
+        ) {
+            let mut inputs: Vec<_> = plan
+                .children()
+                .iter()
+                .filter(|child| match child.estimated_cardinality().value() {
+                    Some(&rows) => {
+                        rows <= self.ship_threshold
+                            && ShippedRows::try_new(bind_context, child).is_ok()
+                    }
+                    None => false,
+                })
+                .collect();
+            inputs.sort_by_key(|child| child.estimated_cardinality());
+            candidates.extend(inputs);
+        }
+
+        for child in plan.children() {
+            self.collect_ship_candidates(bind_context, child, candidates);
+        }
+    }
+}
+
+impl OptimizeRule for QueryPushdown<'_> {
+    fn optimize(
+        &mut self,
+        bind_context: &mut BindContext,
+        mut plan: LogicalOperator,
+    ) -> Result<LogicalOperator> {
+        plan.walk_mut(
+            &mut |op| {
+                if let Some(scan) = self.try_push_query(bind_context, op, None)? {
+                    *op = LogicalOperator::Scan(scan);
+                    return Ok(Recursion::SkipChildren);
+                }
+
+                if self.ship_threshold > 0 {
+                    if let Some(scan) = self.try_ship_join(bind_context, op)? {
+                        // The shipped input is still executed locally, and may
+                        // be able to push down to its own catalog, so keep
+                        // walking into the scan's children.
+                        *op = LogicalOperator::Scan(scan);
+                    }
+                }
+
+                Ok(Recursion::Continue)
+            },
+            &mut |_| Ok(()),
+        )?;
+
+        Ok(plan)
+    }
+}
+
+/// Name of a column in the rendered query.
+///
+/// Every operator renders to a SELECT producing columns named after their
+/// table refs. Column names are unique across the entire query, so columns
+/// never need to be qualified.
+fn column_alias(table_ref: TableRef, column: usize) -> String {
+    format!("t{}_{column}", table_ref.table_idx)
+}
+
+/// Quote an identifier, escaping any embedded quotes.
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// SQL type name to use when casting to a data type.
+fn sql_type_name(datatype: &DataType) -> Option<String> {
+    Some(match datatype {
+        DataType::Boolean => "BOOLEAN".to_string(),
+        DataType::Int16 => "SMALLINT".to_string(),
+        DataType::Int32 => "INTEGER".to_string(),
+        DataType::Int64 => "BIGINT".to_string(),
+        DataType::Float32 => "REAL".to_string(),
+        DataType::Float64 => "DOUBLE PRECISION".to_string(),
+        DataType::Decimal64(m) | DataType::Decimal128(m) => {
+            format!("NUMERIC({}, {})", m.precision, m.scale)
+        }
+        DataType::Utf8 => "TEXT".to_string(),
+        _ => return None,
+    })
+}
+
+fn is_integer(datatype: &DataType) -> bool {
+    matches!(
+        datatype,
+        DataType::Int16 | DataType::Int32 | DataType::Int64
+    )
+}
+
+/// Check if a cast produces the same results when executed remotely.
+fn cast_is_portable(from: &DataType, to: &DataType) -> bool {
+    match (from, to) {
+        (from, to) if from == to => true,
+        (DataType::Float32, DataType::Float64) => true,
+        (from, DataType::Int16 | DataType::Int32 | DataType::Int64) => is_integer(from),
+        (from, DataType::Float32 | DataType::Float64 | DataType::Utf8) => is_integer(from),
+        (from, DataType::Decimal64(to) | DataType::Decimal128(to)) => match from {
+            DataType::Decimal64(from) | DataType::Decimal128(from) => from.scale <= to.scale,
+            from => is_integer(from),
+        },
+        _ => false,
+    }
+}
+
+/// Name of the CTE holding shipped rows in a remote query.
+const SHIPPED_CTE: &str = "shipped";
+
+/// Rows produced locally that are sent to the remote catalog along with a
+/// query.
+///
+/// The query reads the rows from a CTE, which is prepended to the query once
+/// the rows are available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShippedRows {
+    /// Names of the columns in the CTE.
+    pub columns: Vec<String>,
+    /// Types of the columns in the CTE.
+    pub types: Vec<DataType>,
+}
+
+impl ShippedRows {
+    /// Create the shipped rows description for the output of a plan.
+    ///
+    /// Errors if the output contains types that can't be rendered as
+    /// literals.
+    fn try_new(bind_context: &BindContext, plan: &LogicalOperator) -> Result<Self> {
+        let mut columns = Vec::new();
+        let mut types = Vec::new();
+
+        for table_ref in plan.get_output_table_refs(bind_context) {
+            let table = bind_context.get_table(table_ref)?;
+            for (idx, datatype) in table.column_types.iter().enumerate() {
+                // Floats are excluded since NaN and infinity don't have
+                // portable literals.
+                if datatype.is_float() || sql_type_name(datatype).is_none() {
+                    return Err(RayexecError::new(format!(
+                        "Cannot ship values of type {datatype}"
+                    )));
+                }
+                columns.push(column_alias(table_ref, idx));
+                types.push(datatype.clone());
+            }
+        }
+
+        Ok(ShippedRows { columns, types })
+    }
+
+    /// Render a SELECT reading all shipped columns.
+    fn render_select(&self) -> String {
+        let columns: Vec<_> = self.columns.iter().map(|c| quote_ident(c)).collect();
+        format!(
+            "SELECT {} FROM {}",
+            columns.join(", "),
+            quote_ident(SHIPPED_CTE)
+        )
+    }
+
+    /// Create the query to execute remotely with the shipped rows included.
+    pub fn query_with_rows(&self, query: &RemoteQuery, batches: &[Batch]) -> Result<RemoteQuery> {
+        let type_names = self
+            .types
+            .iter()
+            .map(|datatype| {
+                sql_type_name(datatype).ok_or_else(|| {
+                    RayexecError::new(format!("Cannot ship values of type {datatype}"))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut rows = Vec::new();
+        for batch in batches {
+            for row in 0..batch.num_rows() {
+                let values = batch
+                    .columns()
+                    .iter()
+                    .zip(&type_names)
+                    .map(|(array, type_name)| {
+                        let value = array.logical_value(row)?;
+                        // Non-NULL literals are already rendered with their
+                        // type.
+                        if value == ScalarValue::Null {
+                            return Ok(format!("CAST(NULL AS {type_name})"));
+                        }
+                        render_literal(&value)
+                            .ok_or_else(|| RayexecError::new(format!("Cannot ship value: {value}")))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                rows.push(format!("({})", values.join(", ")));
+            }
+        }
+
+        // VALUES can't be empty, select typed NULLs that are filtered out
+        // instead.
+        let body = if rows.is_empty() {
+            let nulls: Vec<_> = type_names
+                .iter()
+                .map(|type_name| format!("CAST(NULL AS {type_name})"))
+                .collect();
+            format!("SELECT {} WHERE 1 = 0", nulls.join(", "))
+        } else {
+            format!("VALUES {}", rows.join(", "))
+        };
+
+        let columns: Vec<_> = self.columns.iter().map(|c| quote_ident(c)).collect();
+        Ok(RemoteQuery {
+            sql: format!(
+                "WITH {}({}) AS ({body}) {}",
+                quote_ident(SHIPPED_CTE),
+                columns.join(", "),
+                query.sql
+            ),
+            columns: query.columns.clone(),
+            types: query.types.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct SqlRenderer<'a> {
+    bind_context: &'a BindContext,
+    /// Catalog for all tables read in the query.
+    catalog: Option<String>,
+    /// Location requirement of the scans in the query.
+    location: LocationRequirement,
+    /// Counter for generating subquery aliases.
+    alias_idx: usize,
+    /// Node in the plan to render as a read of shipped rows.
+    shipped: Option<(&'a LogicalOperator, &'a ShippedRows)>,
+}
+
+impl SqlRenderer<'_> {
+    fn next_alias(&mut self) -> String {
+        self.alias_idx += 1;
+        format!("s{}", self.alias_idx)
+    }
+
+    /// Wrap a rendered child so that it can be used in a FROM clause.
+    fn subquery(&mut self, child: String) -> String {
+        format!("({child}) AS {}", self.next_alias())
+    }
+
+    /// Render an operator as a SELECT that produces all columns from the
+    /// operator's output table refs.
+    fn render_operator(&mut self, plan: &LogicalOperator) -> Option<String> {
+        // Compared by address, the shipped node is a specific node in the
+        // plan being rendered.
+        if let Some((node, rows)) = self.shipped {
+            if std::ptr::eq(node, plan) {
+                return Some(rows.render_select());
+            }
+        }
+
+        match plan {
+            LogicalOperator::Scan(scan) => self.render_scan(scan),
+            LogicalOperator::Filter(filter) => {
+                let child = self.render_child(plan)?;
+                let predicate = self.render_expr(&filter.node.filter)?;
+                Some(format!(
+                    "SELECT * FROM {} WHERE {predicate}",
+                    self.subquery(child)
+                ))
+            }
+            LogicalOperator::Project(project) => {
+                let child = self.render_child(plan)?;
+                let table = self
+                    .bind_context
+                    .get_table(project.node.projection_table)
+                    .ok()?;
+                let columns = project
+                    .node
+                    .projections
+                    .iter()
+                    .zip(&table.column_types)
+                    .enumerate()
+                    .map(|(idx, (expr, datatype))| {
+                        Some(format!(
+                            "CAST({} AS {}) AS {}",
+                            self.render_expr(expr)?,
+                            sql_type_name(datatype)?,
+                            quote_ident(&column_alias(project.node.projection_table, idx))
+                        ))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!(
+                    "SELECT {} FROM {}",
+                    columns.join(", "),
+                    self.subquery(child)
+                ))
+            }
+            LogicalOperator::Aggregate(agg) => {
+                if agg.node.grouping_functions_table.is_some() {
+                    return None;
+                }
+                // Only a single grouping set containing all GROUP BY
+                // expressions is supported.
+                if let Some(sets) = &agg.node.grouping_sets {
+                    match sets.as_slice() {
+                        [set] if set.len() == agg.node.group_exprs.len() => (),
+                        _ => return None,
+                    }
+                }
+
+                let child = self.render_child(plan)?;
+
+                let mut columns = Vec::new();
+                let mut group_by = Vec::new();
+                if let Some(group_table) = agg.node.group_table {
+                    let table = self.bind_context.get_table(group_table).ok()?;
+                    for (idx, (expr, datatype)) in agg
+                        .node
+                        .group_exprs
+                        .iter()
+                        .zip(&table.column_types)
+                        .enumerate()
+                    {
+                        let expr = self.render_expr(expr)?;
+                        columns.push(format!(
+                            "CAST({expr} AS {}) AS {}",
+                            sql_type_name(datatype)?,
+                            quote_ident(&column_alias(group_table, idx))
+                        ));
+                        group_by.push(expr);
+                    }
+                }
+
+                let table = self
+                    .bind_context
+                    .get_table(agg.node.aggregates_table)
+                    .ok()?;
+                for (idx, (expr, datatype)) in agg
+                    .node
+                    .aggregates
+                    .iter()
+                    .zip(&table.column_types)
+                    .enumerate()
+                {
+                    columns.push(format!(
+                        "CAST({} AS {}) AS {}",
+                        self.render_aggregate(expr)?,
+                        sql_type_name(datatype)?,
+                        quote_ident(&column_alias(agg.node.aggregates_table, idx))
+                    ));
+                }
+
+                let mut sql = format!(
+                    "SELECT {} FROM {}",
+                    columns.join(", "),
+                    self.subquery(child)
+                );
+                if !group_by.is_empty() {
+                    sql.push_str(&format!(" GROUP BY {}", group_by.join(", ")));
+                }
+                Some(sql)
+            }
+            LogicalOperator::Order(_) => {
+                let (child, order_by) = self.render_ordered_child(plan)?;
+                Some(format!("SELECT * FROM {}{order_by}", self.subquery(child)))
+            }
+            LogicalOperator::Limit(limit) => {
+                // Render ORDER BY and LIMIT in the same SELECT to avoid relying
+                // on a subquery preserving order.
+                let (child, order_by) = match plan.children() {
+                    [order @ LogicalOperator::Order(_)] => self.render_ordered_child(order)?,
+                    _ => (self.render_child(plan)?, String::new()),
+                };
+                let mut sql = format!(
+                    "SELECT * FROM {}{order_by} LIMIT {}",
+                    self.subquery(child),
+                    limit.node.limit
+                );
+                if let Some(offset) = limit.node.offset {
+                    sql.push_str(&format!(" OFFSET {offset}"));
+                }
+                Some(sql)
+            }
+            LogicalOperator::Distinct(distinct) => {
+                if !distinct.node.on.is_empty() {
+                    return None;
+                }
+                let child = self.render_child(plan)?;
+                Some(format!("SELECT DISTINCT * FROM {}", self.subquery(child)))
+            }
+            LogicalOperator::CrossJoin(_) => {
+                let (left, right) = self.render_join_children(plan)?;
+                Some(format!("SELECT * FROM {left} CROSS JOIN {right}"))
+            }
+            LogicalOperator::ComparisonJoin(join) => {
+                let join_type = join_type_sql(join.node.join_type)?;
+                let (left, right) = self.render_join_children(plan)?;
+                let conditions = join
+                    .node
+                    .conditions
+                    .iter()
+                    .map(|cond| self.render_comparison(&cond.left, cond.op, &cond.right))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!(
+                    "SELECT * FROM {left} {join_type} JOIN {right} ON {}",
+                    conditions.join(" AND ")
+                ))
+            }
+            LogicalOperator::ArbitraryJoin(join) => {
+                let join_type = join_type_sql(join.node.join_type)?;
+                let (left, right) = self.render_join_children(plan)?;
+                let condition = self.render_expr(&join.node.condition)?;
+                Some(format!(
+                    "SELECT * FROM {left} {join_type} JOIN {right} ON {condition}"
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn render_scan(&mut self, scan: &Node<LogicalScan>) -> Option<String> {
+        let (catalog, schema, source) = match &scan.node.source {
+            ScanSource::Table {
+                catalog,
+                schema,
+                source,
+            } => (catalog, schema, source),
+            _ => return None,
+        };
+
+        // Pushed down limits are only hints, and are rendered from the limit
+        // operator instead.
+        if scan.node.sample.is_some() || scan.node.aggregate.is_some() {
+            return None;
+        }
+
+        match &self.catalog {
+            Some(existing) if existing != catalog => return None,
+            Some(_) => (),
+            None => self.catalog = Some(catalog.clone()),
+        }
+        if scan.location != LocationRequirement::Any {
+            self.location = scan.location;
+        }
+
+        let table = source.try_as_table_entry().ok()?;
+        let columns = scan
+            .node
+            .projection
+            .iter()
+            .enumerate()
+            .map(|(idx, &col)| {
+                let column = table.columns.get(col)?;
+                Some(format!(
+                    "{} AS {}",
+                    quote_ident(&column.name),
+                    quote_ident(&column_alias(scan.node.table_ref, idx))
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(format!(
+            "SELECT {} FROM {}.{}",
+            columns.join(", "),
+            quote_ident(schema),
+            quote_ident(&source.name)
+        ))
+    }
+
+    fn render_child(&mut self, plan: &LogicalOperator) -> Option<String> {
+        match plan.children() {
+            [child] => self.render_operator(child),
+            _ => None,
+        }
+    }
+
+    fn render_join_children(&mut self, plan: &LogicalOperator) -> Option<(String, String)> {
+        match plan.children() {
+            [left, right] => {
+                let left = self.render_operator(left)?;
+                let right = self.render_operator(right)?;
+                Some((self.subquery(left), self.subquery(right)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Render the child of an ORDER BY along with the ORDER BY clause.
+    fn render_ordered_child(&mut self, order: &LogicalOperator) -> Option<(String, String)> {
+        let exprs = match order {
+            LogicalOperator::Order(order) => &order.node.exprs,
+            _ => return None,
+        };
+
+        let child = self.render_child(order)?;
+        let table_list = self.bind_context.get_table_list();
+        let order_by = exprs
+            .iter()
+            .map(|expr| {
+                // String ordering depends on collation.
+                if expr.expr.datatype(table_list).ok()? == DataType::Utf8 {
+                    return None;
+                }
+                Some(format!(
+                    "{} {} NULLS {}",
+                    self.render_expr(&expr.expr)?,
+                    if expr.desc { "DESC" } else { "ASC" },
+                    if expr.nulls_first { "FIRST" } else { "LAST" }
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some((child, format!(" ORDER BY {}", order_by.join(", "))))
+    }
+
+    fn render_aggregate(&self, expr: &Expression) -> Option<String> {
+        let agg = match expr {
+            Expression::Aggregate(agg) => agg,
+            _ => return None,
+        };
+
+        let name = agg.agg.function.name();
+        if !matches!(name, "count" | "sum" | "avg" | "min" | "max") {
+            return None;
+        }
+
+        let input = match agg.agg.inputs.as_slice() {
+            // `count(*)` is planned as a count on a constant.
+            [Expression::Literal(lit)]
+                if name == "count" && !agg.distinct && lit.literal != ScalarValue::Null =>
+            {
+                "*".to_string()
+            }
+            [input] => {
+                // String ordering depends on collation.
+                if matches!(name, "min" | "max")
+                    && input.datatype(self.bind_context.get_table_list()).ok()? == DataType::Utf8
+                {
+                    return None;
+                }
+                let input = self.render_expr(input)?;
+                if agg.distinct {
+                    format!("DISTINCT {input}")
+                } else {
+                    input
+                }
+            }
+            _ => return None,
+        };
+
+        let mut sql = format!("{name}({input})");
+        if let Some(filter) = &agg.filter {
+            sql.push_str(&format!(" FILTER (WHERE {})", self.render_expr(filter)?));
+        }
+
+        Some(sql)
+    }
+
+    fn render_comparison(
+        &self,
+        left: &Expression,
+        op: ComparisonOperator,
+        right: &Expression,
+    ) -> Option<String> {
+        let table_list = self.bind_context.get_table_list();
+        // String ordering depends on collation, only equality is guaranteed to
+        // behave the same.
+        if (left.datatype(table_list).ok()? == DataType::Utf8
+            || right.datatype(table_list).ok()? == DataType::Utf8)
+            && !matches!(op, ComparisonOperator::Eq | ComparisonOperator::NotEq)
+        {
+            return None;
+        }
+
+        let op = match op {
+            ComparisonOperator::Eq => "=",
+            ComparisonOperator::NotEq => "<>",
+            ComparisonOperator::Lt => "<",
+            ComparisonOperator::LtEq => "<=",
+            ComparisonOperator::Gt => ">",
+            ComparisonOperator::GtEq => ">=",
+        };
+
+        Some(format!(
+            "({} {op} {})",
+            self.render_expr(left)?,
+            self.render_expr(right)?
+        ))
+    }
+
+    fn render_expr(&self, expr: &Expression) -> Option<String> {
+        let table_list = self.bind_context.get_table_list();
+
+        match expr {
+            Expression::Column(col) => {
+                Some(quote_ident(&column_alias(col.table_scope, col.column)))
+            }
+            Expression::Literal(lit) => render_literal(&lit.literal),
+            Expression::Cast(cast) => {
+                let from = cast.expr.datatype(table_list).ok()?;
+                if !cast_is_portable(&from, &cast.to) {
+                    return None;
+                }
+                Some(format!(
+                    "CAST({} AS {})",
+                    self.render_expr(&cast.expr)?,
+                    sql_type_name(&cast.to)?
+                ))
+            }
+            Expression::Arith(arith) => {
+                let datatype = arith.datatype(table_list).ok()?;
+                let portable = match arith.op {
+                    ArithOperator::Add | ArithOperator::Sub | ArithOperator::Mul => {
+                        is_integer(&datatype) || datatype.is_float() || datatype.is_decimal()
+                    }
+                    // Integer division differs between systems.
+                    ArithOperator::Div => datatype.is_float(),
+                    ArithOperator::Mod => is_integer(&datatype),
+                };
+                if !portable {
+                    return None;
+                }
+                Some(format!(
+                    "({} {} {})",
+                    self.render_expr(&arith.left)?,
+                    arith.op,
+                    self.render_expr(&arith.right)?
+                ))
+            }
+            Expression::Comparison(cmp) => self.render_comparison(&cmp.left, cmp.op, &cmp.right),
+            Expression::Conditional(cond) => {
+                // GREATEST and LEAST disagree on NULL handling across systems.
+                if !matches!(
+                    cond.op,
+                    ConditionalOperator::Coalesce | ConditionalOperator::NullIf
+                ) {
+                    return None;
+                }
+                let inputs = cond
+                    .inputs
+                    .iter()
+                    .map(|expr| self.render_expr(expr))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!("{}({})", cond.op, inputs.join(", ")))
+            }
+            Expression::Conjunction(conj) => {
+                let op = match conj.op {
+                    ConjunctionOperator::And => " AND ",
+                    ConjunctionOperator::Or => " OR ",
+                };
+                let exprs = conj
+                    .expressions
+                    .iter()
+                    .map(|expr| self.render_expr(expr))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!("({})", exprs.join(op)))
+            }
+            Expression::Negate(negate) => {
+                let op = match negate.op {
+                    NegateOperator::Not => "NOT ",
+                    NegateOperator::Negate => "-",
+                };
+                Some(format!("({op}{})", self.render_expr(&negate.expr)?))
+            }
+            Expression::Is(is) => Some(format!("({} {})", self.render_expr(&is.input)?, is.op)),
+            Expression::Between(between) => {
+                if between.input.datatype(table_list).ok()? == DataType::Utf8 {
+                    return None;
+                }
+                Some(format!(
+                    "({} BETWEEN {} AND {})",
+                    self.render_expr(&between.input)?,
+                    self.render_expr(&between.lower)?,
+                    self.render_expr(&between.upper)?
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn join_type_sql(join_type: JoinType) -> Option<&'static str> {
+    Some(match join_type {
+        JoinType::Inner => "INNER",
+        JoinType::Left => "LEFT",
+        JoinType::Right => "RIGHT",
+        JoinType::Full => "FULL",
+        _ => return None,
+    })
+}
+
+/// Render a literal, casting it to make sure it has the same type remotely.
+fn render_literal(literal: &ScalarValue) -> Option<String> {
+    let value = match literal {
+        ScalarValue::Null => return Some("NULL".to_string()),
+        ScalarValue::Boolean(v) => return Some(v.to_string().to_uppercase()),
+        ScalarValue::Utf8(v) => return Some(format!("'{}'", v.replace('\'', "''"))),
+        ScalarValue::Int16(v) => v.to_string(),
+        ScalarValue::Int32(v) => v.to_string(),
+        ScalarValue::Int64(v) => v.to_string(),
+        ScalarValue::Float32(v) if v.is_finite() => v.to_string(),
+        ScalarValue::Float64(v) if v.is_finite() => v.to_string(),
+        ScalarValue::Decimal64(_) | ScalarValue::Decimal128(_) => literal.to_string(),
+        _ => return None,
+    };
+
+    Some(format!(
+        "CAST({value} AS {})",
+        sql_type_name(&literal.datatype())?
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::array::Array;
+    use crate::arrays::datatype::DecimalTypeMeta;
+
+    #[test]
+    fn literals() {
+        assert_eq!(
+            Some("CAST(-5 AS BIGINT)".to_string()),
+            render_literal(&ScalarValue::Int64(-5))
+        );
+        assert_eq!(
+            Some("'it''s'".to_string()),
+            render_literal(&ScalarValue::Utf8("it's".into()))
+        );
+        assert_eq!(
+            Some("TRUE".to_string()),
+            render_literal(&ScalarValue::Boolean(true))
+        );
+        assert_eq!(None, render_literal(&ScalarValue::Float64(f64::NAN)));
+        assert_eq!(None, render_literal(&ScalarValue::UInt64(4)));
+    }
+
+    #[test]
+    fn portable_casts() {
+        let dec = |precision, scale| DataType::Decimal64(DecimalTypeMeta::new(precision, scale));
+
+        assert!(cast_is_portable(&DataType::Int32, &DataType::Int64));
+        assert!(cast_is_portable(&DataType::Int32, &DataType::Float64));
+        assert!(cast_is_portable(&DataType::Int32, &dec(18, 2)));
+        assert!(cast_is_portable(&dec(10, 2), &dec(18, 4)));
+
+        assert!(!cast_is_portable(&dec(18, 4), &dec(18, 2)));
+        assert!(!cast_is_portable(&DataType::Float64, &DataType::Int64));
+        assert!(!cast_is_portable(&DataType::Float64, &DataType::Utf8));
+        assert!(!cast_is_portable(&DataType::Utf8, &DataType::Int64));
+    }
+
+    fn shipped_query() -> (ShippedRows, RemoteQuery) {
+        let shipped = ShippedRows {
+            columns: vec!["t1_0".to_string(), "t1_1".to_string()],
+            types: vec![DataType::Int32, DataType::Utf8],
+        };
+        let query = RemoteQuery {
+            sql: "SELECT * FROM \"shipped\"".to_string(),
+            columns: vec!["t1_0".to_string(), "t1_1".to_string()],
+            types: vec![DataType::Int32, DataType::Utf8],
+        };
+        (shipped, query)
+    }
+
+    #[test]
+    fn query_with_shipped_rows() {
+        let (shipped, query) = shipped_query();
+        let batch = Batch::try_new([
+            Array::from_iter([Some(1), None]),
+            Array::from_iter([Some("a'b"), Some("c")]),
+        ])
+        .unwrap();
+
+        let out = shipped.query_with_rows(&query, &[batch]).unwrap();
+        assert_eq!(
+            "WITH \"shipped\"(\"t1_0\", \"t1_1\") AS (VALUES \
+             (CAST(1 AS INTEGER), 'a''b'), (CAST(NULL AS INTEGER), 'c')) \
+             SELECT * FROM \"shipped\"",
+            out.sql
+        );
+        assert_eq!(query.columns, out.columns);
+    }
+
+    #[test]
+    fn query_with_no_shipped_rows() {
+        let (shipped, query) = shipped_query();
+
+        let out = shipped.query_with_rows(&query, &[]).unwrap();
+        assert_eq!(
+            "WITH \"shipped\"(\"t1_0\", \"t1_1\") AS \
+             (SELECT CAST(NULL AS INTEGER), CAST(NULL AS TEXT) WHERE 1 = 0) \
+             SELECT * FROM \"shipped\"",
+            out.sql
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use rayexec_error::Result;
+
+use super::OptimizeRule;
+use crate::expr::Expression;
+use crate::logical::binder::bind_context::BindContext;
+use crate::logical::logical_order::LogicalOrder;
+use crate::logical::logical_sample::LogicalSample;
+use crate::logical::operator::{LogicalOperator, Node};
+use crate::logical::statistics::StatisticsValue;
+use crate::logical::walk::Recursion;
+
+/// Replace `ORDER BY random() LIMIT n` with a reservoir sample of n rows.
+///
+/// Sorting on a random value is just a roundabout way of getting a random
+/// sample. A reservoir sample avoids materializing and sorting the entire
+/// input, only needing to hold onto n rows at a time.
+#[derive(Debug)]
+pub struct RandomOrderSample;
+
+impl OptimizeRule for RandomOrderSample {
+    fn optimize(
+        &mut self,
+        _bind_context: &mut BindContext,
+        mut plan: LogicalOperator,
+    ) -> Result<LogicalOperator> {
+        plan.walk_mut(
+            &mut |op| {
+                if let LogicalOperator::Limit(limit) = op {
+                    if let [LogicalOperator::Order(order)] = limit.children.as_mut_slice() {
+                        if order_is_random(order) {
+                            let rows = limit.node.limit + limit.node.offset.unwrap_or(0);
+                            let children = std::mem::take(&mut order.children);
+
+                            limit.children = vec![LogicalOperator::Sample(Node {
+                                node: LogicalSample::Reservoir { rows },
+                                location: order.location,
+                                children,
+                                estimated_cardinality: StatisticsValue::Unknown,
+                            })];
+                        }
+                    }
+                }
+                Ok(Recursion::Continue)
+            },
+            &mut |_| Ok(()),
+        )?;
+
+        Ok(plan)
+    }
+}
+
+/// Check if every expression in the order by is a call to `random()`.
+///
+/// Order by expressions are usually column references into the child
+/// projection, so those are resolved before checking.
+fn order_is_random(order: &Node<LogicalOrder>) -> bool {
+    if order.node.exprs.is_empty() {
+        return false;
+    }
+
+    let project = match order.children.as_slice() {
+        [LogicalOperator::Project(project)] => Some(project),
+        _ => None,
+    };
+
+    order.node.exprs.iter().all(|order_expr| {
+        let expr = match (&order_expr.expr, project) {
+            (Expression::Column(col), Some(project))
+                if col.table_scope == project.node.projection_table =>
+            {
+                match project.node.projections.get(col.column) {
+                    Some(expr) => expr,
+                    None => return false,
+                }
+            }
+            (expr, _) => expr,
+        };
+
+        match expr {
+            Expression::ScalarFunction(func) => {
+                func.function.function.name() == "random" && func.function.inputs.is_empty()
+            }
+            _ => false,
+        }
+    })
+}
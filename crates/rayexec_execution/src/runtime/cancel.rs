@@ -0,0 +1,78 @@
+//! Query cancellation visible to long running function calls.
+//!
+//! Executors only check for cancellation between polls of a pipeline, which
+//! isn't enough for functions that may run for a long time within a single
+//! poll (e.g. WASM UDFs). Executors enter the query's flag while executing a
+//! partition pipeline so that those functions can check it without it being
+//! threaded through every operator and expression.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+thread_local! {
+    /// Flag for the query currently executing on this thread.
+    static CURRENT: RefCell<Option<CancelFlag>> = const { RefCell::new(None) };
+}
+
+/// Flag that's set once a query has been canceled.
+#[derive(Debug, Clone, Default)]
+pub struct CancelFlag {
+    canceled: Arc<AtomicBool>,
+}
+
+impl CancelFlag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.canceled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.canceled.load(Ordering::Relaxed)
+    }
+
+    /// Run `f` with this as the current thread's flag.
+    pub fn enter<T>(&self, f: impl FnOnce() -> T) -> T {
+        let prev = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let out = f();
+        CURRENT.with(|current| *current.borrow_mut() = prev);
+        out
+    }
+
+    /// Check if the query executing on the current thread has been canceled.
+    ///
+    /// Always false outside of `enter`.
+    pub fn current_is_canceled() -> bool {
+        CURRENT.with(|current| {
+            current
+                .borrow()
+                .as_ref()
+                .is_some_and(|flag| flag.is_canceled())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current_flag() {
+        let flag = CancelFlag::new();
+        flag.cancel();
+
+        assert!(!CancelFlag::current_is_canceled());
+        assert!(flag.enter(CancelFlag::current_is_canceled));
+        assert!(!CancelFlag::current_is_canceled());
+
+        // Nested flags restore the outer flag.
+        let other = CancelFlag::new();
+        flag.enter(|| {
+            assert!(!other.enter(CancelFlag::current_is_canceled));
+            assert!(CancelFlag::current_is_canceled());
+        });
+    }
+}
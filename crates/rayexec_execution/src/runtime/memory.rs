@@ -0,0 +1,259 @@
+//! Memory accounting for query execution.
+//!
+//! Operators that buffer their input (hash join build sides, sorts) reserve
+//! memory from a tracker before holding on to batches. Trackers are shared by
+//! every query in a resource group, so the limit applies to the group as a
+//! whole. Sessions with a `memory_limit` give each query its own tracker with
+//! the group's tracker as the parent, so reservations count against both.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use rayexec_error::{ErrorKind, RayexecError, Result};
+
+use crate::arrays::batch::Batch;
+
+/// Tracks memory reserved by executing queries against an optional limit.
+#[derive(Debug)]
+pub struct MemoryTracker {
+    /// Name used in error messages.
+    name: String,
+    /// Max number of bytes that can be reserved. Zero indicates no limit.
+    limit: AtomicUsize,
+    /// Number of bytes currently reserved.
+    reserved: AtomicUsize,
+    /// Tracker that reservations are also made against.
+    parent: Option<Arc<MemoryTracker>>,
+}
+
+impl MemoryTracker {
+    pub fn new(name: impl Into<String>, limit: Option<usize>) -> Self {
+        MemoryTracker {
+            name: name.into(),
+            limit: AtomicUsize::new(limit.unwrap_or(0)),
+            reserved: AtomicUsize::new(0),
+            parent: None,
+        }
+    }
+
+    /// Create a tracker where every reservation is also made against
+    /// `parent`.
+    pub fn new_with_parent(
+        name: impl Into<String>,
+        limit: Option<usize>,
+        parent: Arc<MemoryTracker>,
+    ) -> Self {
+        MemoryTracker {
+            parent: Some(parent),
+            ..Self::new(name, limit)
+        }
+    }
+
+    /// Get the current limit, None if there's no limit.
+    pub fn limit(&self) -> Option<usize> {
+        match self.limit.load(Ordering::Relaxed) {
+            0 => None,
+            limit => Some(limit),
+        }
+    }
+
+    /// Set the limit.
+    ///
+    /// Existing reservations are kept even if they exceed the new limit, only
+    /// future reservations are checked against it.
+    pub fn set_limit(&self, limit: Option<usize>) {
+        self.limit.store(limit.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// Number of bytes currently reserved.
+    pub fn reserved(&self) -> usize {
+        self.reserved.load(Ordering::Relaxed)
+    }
+
+    /// Create an empty reservation against this tracker.
+    pub fn new_reservation(self: &Arc<Self>) -> MemoryReservation {
+        MemoryReservation {
+            tracker: self.clone(),
+            bytes: 0,
+        }
+    }
+
+    fn try_reserve(&self, bytes: usize) -> Result<()> {
+        let limit = self.limit();
+        self.reserved
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |reserved| {
+                let new = reserved.checked_add(bytes)?;
+                match limit {
+                    Some(limit) if new > limit => None,
+                    _ => Some(new),
+                }
+            })
+            .map_err(|reserved| {
+                RayexecError::new(format!(
+                    "Memory limit exceeded for {}: requested {bytes} bytes with {reserved} of {} bytes reserved",
+                    self.name,
+                    limit.unwrap_or(usize::MAX),
+                ))
+                .with_kind(ErrorKind::ResourceExhausted)
+            })?;
+
+        if let Some(parent) = &self.parent {
+            if let Err(e) = parent.try_reserve(bytes) {
+                self.reserved.fetch_sub(bytes, Ordering::Relaxed);
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn release(&self, bytes: usize) {
+        self.reserved.fetch_sub(bytes, Ordering::Relaxed);
+        if let Some(parent) = &self.parent {
+            parent.release(bytes);
+        }
+    }
+}
+
+/// Memory reserved by a single operator.
+///
+/// The reserved bytes are returned to the tracker on drop, so the reservation
+/// should live as long as the memory it accounts for.
+#[derive(Debug)]
+pub struct MemoryReservation {
+    tracker: Arc<MemoryTracker>,
+    bytes: usize,
+}
+
+impl MemoryReservation {
+    /// Number of bytes held by this reservation.
+    pub fn size(&self) -> usize {
+        self.bytes
+    }
+
+    /// Try to grow the reservation by some number of bytes.
+    ///
+    /// Errors if this would put the tracker over its limit, leaving the
+    /// reservation unchanged.
+    pub fn try_grow(&mut self, bytes: usize) -> Result<()> {
+        self.tracker.try_reserve(bytes)?;
+        self.bytes += bytes;
+        Ok(())
+    }
+
+    /// Try to grow the reservation to account for a batch that will be
+    /// buffered.
+    pub fn try_grow_for_batch(&mut self, batch: &Batch) -> Result<()> {
+        self.try_grow(batch.data_size_bytes())
+    }
+
+    /// Move all bytes out of this reservation into a new one.
+    ///
+    /// Useful for handing off accounting when buffered data moves to some
+    /// other state that outlives this reservation.
+    pub fn take(&mut self) -> MemoryReservation {
+        MemoryReservation {
+            tracker: self.tracker.clone(),
+            bytes: std::mem::take(&mut self.bytes),
+        }
+    }
+
+    /// Merge another reservation into this one.
+    ///
+    /// Both reservations must be for the same tracker.
+    pub fn merge(&mut self, mut other: MemoryReservation) {
+        debug_assert!(Arc::ptr_eq(&self.tracker, &other.tracker));
+        self.bytes += std::mem::take(&mut other.bytes);
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.tracker.release(self.bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve_within_limit() {
+        let tracker = Arc::new(MemoryTracker::new("test", Some(100)));
+
+        let mut r1 = tracker.new_reservation();
+        r1.try_grow(60).unwrap();
+        let mut r2 = tracker.new_reservation();
+        r2.try_grow(40).unwrap();
+        assert_eq!(100, tracker.reserved());
+
+        let err = r2.try_grow(1).unwrap_err();
+        assert_eq!(ErrorKind::ResourceExhausted, err.kind());
+        assert_eq!(40, r2.size());
+
+        std::mem::drop(r1);
+        assert_eq!(40, tracker.reserved());
+        r2.try_grow(50).unwrap();
+    }
+
+    #[test]
+    fn unlimited() {
+        let tracker = Arc::new(MemoryTracker::new("test", None));
+        let mut r = tracker.new_reservation();
+        r.try_grow(usize::MAX / 2).unwrap();
+        std::mem::drop(r);
+        assert_eq!(0, tracker.reserved());
+    }
+
+    #[test]
+    fn reserve_against_parent() {
+        let parent = Arc::new(MemoryTracker::new("parent", Some(100)));
+        let child = Arc::new(MemoryTracker::new_with_parent(
+            "child",
+            Some(50),
+            parent.clone(),
+        ));
+
+        let mut r1 = child.new_reservation();
+        r1.try_grow(40).unwrap();
+        assert_eq!(40, parent.reserved());
+
+        // Child limit.
+        r1.try_grow(20).unwrap_err();
+        assert_eq!(40, child.reserved());
+        assert_eq!(40, parent.reserved());
+
+        // Parent limit, child reservation is rolled back.
+        let mut r2 = parent.new_reservation();
+        r2.try_grow(55).unwrap();
+        r1.try_grow(10).unwrap_err();
+        assert_eq!(40, child.reserved());
+        assert_eq!(95, parent.reserved());
+
+        std::mem::drop(r1);
+        assert_eq!(0, child.reserved());
+        assert_eq!(55, parent.reserved());
+    }
+
+    #[test]
+    fn merge_and_take_reservations() {
+        let tracker = Arc::new(MemoryTracker::new("test", Some(100)));
+
+        let mut r1 = tracker.new_reservation();
+        r1.try_grow(30).unwrap();
+        let mut r2 = tracker.new_reservation();
+        r2.try_grow(20).unwrap();
+
+        r1.merge(r2);
+        assert_eq!(50, r1.size());
+        assert_eq!(50, tracker.reserved());
+
+        let r3 = r1.take();
+        assert_eq!(0, r1.size());
+        assert_eq!(50, r3.size());
+        std::mem::drop(r3);
+        assert_eq!(0, tracker.reserved());
+
+        std::mem::drop(r1);
+        assert_eq!(0, tracker.reserved());
+    }
+}
@@ -1,4 +1,8 @@
+pub mod cancel;
 pub mod handle;
+pub mod memory;
+pub mod resource_group;
+pub mod stall;
 pub mod time;
 
 use std::fmt::Debug;
@@ -8,6 +12,8 @@ use handle::QueryHandle;
 use rayexec_error::{RayexecError, Result};
 use rayexec_io::http::HttpClient;
 use rayexec_io::FileProvider;
+use resource_group::ResourceGroup;
+use stall::ScanStall;
 use time::RuntimeInstant;
 
 use crate::execution::executable::pipeline::ExecutablePipeline;
@@ -33,11 +39,16 @@ pub trait PipelineExecutor: Debug + Sync + Send + Clone {
     /// written to the provided error sink. Recoverable errors should be handled
     /// internally.
     ///
+    /// Pipelines are scheduled as part of the given resource group. Executors
+    /// that multiplex queries on shared threads should divide time between
+    /// groups according to the group's CPU shares.
+    ///
     /// This must not block.
     fn spawn_pipelines(
         &self,
         pipelines: Vec<ExecutablePipeline>,
         errors: Arc<dyn ErrorSink>,
+        group: Arc<ResourceGroup>,
     ) -> Box<dyn QueryHandle>;
 }
 
@@ -46,7 +57,8 @@ pub trait Runtime: Debug + Sync + Send + Clone + 'static {
     type HttpClient: HttpClient;
     type FileProvider: FileProvider;
     type TokioHandle: TokioHandlerProvider;
-    type Instant: RuntimeInstant; // TODO: Should this be on the runtime?
+    // TODO: Should this be on the runtime?
+    type Instant: RuntimeInstant + Clone + Sync + Send + 'static;
 
     /// Returns a file provider.
     fn file_provider(&self) -> Arc<Self::FileProvider>;
@@ -64,6 +76,21 @@ pub trait Runtime: Debug + Sync + Send + Clone + 'static {
     /// Data sources should error if they require tokio and if this returns
     /// None.
     fn tokio_handle(&self) -> &Self::TokioHandle;
+
+    /// Stall handling for remote scans, e.g. the max time a scan can go
+    /// without receiving any data before the scan errors.
+    fn scan_stall(&self) -> ScanStall {
+        ScanStall::default()
+    }
+
+    /// Returns a copy of this runtime using the given stall handling for
+    /// remote scans.
+    ///
+    /// Used to apply session settings to scans planned by a session. Runtimes
+    /// that can't time out reads return an unchanged copy.
+    fn with_scan_stall(&self, _stall: ScanStall) -> Self {
+        self.clone()
+    }
 }
 
 pub trait TokioHandlerProvider {
@@ -0,0 +1,190 @@
+//! Resource groups for limiting CPU and memory used by sets of sessions.
+//!
+//! Every session belongs to exactly one resource group. Queries executing in
+//! a group share the group's memory limit, and the scheduler divides CPU time
+//! between groups proportionally to their CPU shares. Embedders can create a
+//! group per tenant (or per session) to keep one tenant from starving others.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use rayexec_error::{RayexecError, Result};
+
+use super::memory::MemoryTracker;
+
+/// Name of the group sessions belong to by default.
+pub const DEFAULT_RESOURCE_GROUP: &str = "default";
+
+/// CPU shares for groups that don't specify any.
+pub const DEFAULT_CPU_SHARES: usize = 100;
+
+/// Id for the next group created.
+///
+/// Ids are unique across engines since an executor may be shared between
+/// multiple engines.
+static NEXT_GROUP_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Configuration for a resource group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceGroupConfig {
+    /// Relative share of CPU time the group gets when the scheduler is busy.
+    ///
+    /// A group with 200 shares gets twice as much time as a group with 100
+    /// shares. Unused time isn't reserved, a group can use all threads when no
+    /// other group has work to do.
+    pub cpu_shares: usize,
+    /// Max bytes that can be reserved by all queries in the group at once. No
+    /// limit if None.
+    pub memory_limit: Option<usize>,
+}
+
+impl Default for ResourceGroupConfig {
+    fn default() -> Self {
+        ResourceGroupConfig {
+            cpu_shares: DEFAULT_CPU_SHARES,
+            memory_limit: None,
+        }
+    }
+}
+
+/// A named set of sessions sharing CPU and memory quotas.
+#[derive(Debug)]
+pub struct ResourceGroup {
+    /// Unique id for the group, used by schedulers to key per-group queues.
+    id: usize,
+    name: String,
+    cpu_shares: AtomicUsize,
+    memory: Arc<MemoryTracker>,
+}
+
+impl ResourceGroup {
+    fn new(name: String, config: ResourceGroupConfig) -> Self {
+        let id = NEXT_GROUP_ID.fetch_add(1, Ordering::Relaxed);
+        let memory = Arc::new(MemoryTracker::new(
+            format!("resource group '{name}'"),
+            config.memory_limit,
+        ));
+        ResourceGroup {
+            id,
+            name,
+            cpu_shares: AtomicUsize::new(config.cpu_shares.max(1)),
+            memory,
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cpu_shares(&self) -> usize {
+        self.cpu_shares.load(Ordering::Relaxed)
+    }
+
+    /// Tracker for memory reserved by queries in this group.
+    pub fn memory(&self) -> &Arc<MemoryTracker> {
+        &self.memory
+    }
+
+    /// Update the group's configuration.
+    ///
+    /// Takes effect for tasks scheduled and memory reserved after the update.
+    pub fn reconfigure(&self, config: ResourceGroupConfig) {
+        self.cpu_shares
+            .store(config.cpu_shares.max(1), Ordering::Relaxed);
+        self.memory.set_limit(config.memory_limit);
+    }
+}
+
+/// Resource groups registered with an engine.
+#[derive(Debug)]
+pub struct ResourceGroups {
+    groups: RwLock<HashMap<String, Arc<ResourceGroup>>>,
+}
+
+impl Default for ResourceGroups {
+    fn default() -> Self {
+        let groups = ResourceGroups {
+            groups: RwLock::new(HashMap::new()),
+        };
+        groups.create_or_update(DEFAULT_RESOURCE_GROUP, ResourceGroupConfig::default());
+        groups
+    }
+}
+
+impl ResourceGroups {
+    /// Create a group, or update the configuration of an existing group with
+    /// the same name.
+    pub fn create_or_update(
+        &self,
+        name: impl Into<String>,
+        config: ResourceGroupConfig,
+    ) -> Arc<ResourceGroup> {
+        let name = name.into();
+        let mut groups = self.groups.write();
+        match groups.get(&name) {
+            Some(group) => {
+                group.reconfigure(config);
+                group.clone()
+            }
+            None => {
+                let group = Arc::new(ResourceGroup::new(name.clone(), config));
+                groups.insert(name, group.clone());
+                group
+            }
+        }
+    }
+
+    /// Get a group by name.
+    pub fn get(&self, name: &str) -> Result<Arc<ResourceGroup>> {
+        self.groups
+            .read()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| RayexecError::new(format!("Missing resource group '{name}'")))
+    }
+
+    /// Get the default group.
+    pub fn default_group(&self) -> Arc<ResourceGroup> {
+        self.get(DEFAULT_RESOURCE_GROUP)
+            .expect("default resource group to exist")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_and_update() {
+        let groups = ResourceGroups::default();
+        assert_eq!(DEFAULT_CPU_SHARES, groups.default_group().cpu_shares());
+
+        let group = groups.create_or_update(
+            "tenant",
+            ResourceGroupConfig {
+                cpu_shares: 50,
+                memory_limit: Some(1024),
+            },
+        );
+        assert_eq!(Some(1024), group.memory().limit());
+
+        let updated = groups.create_or_update(
+            "tenant",
+            ResourceGroupConfig {
+                cpu_shares: 200,
+                memory_limit: None,
+            },
+        );
+        assert_eq!(group.id(), updated.id());
+        assert_eq!(200, group.cpu_shares());
+        assert_eq!(None, group.memory().limit());
+
+        assert_ne!(group.id(), groups.default_group().id());
+        groups.get("missing").unwrap_err();
+    }
+}
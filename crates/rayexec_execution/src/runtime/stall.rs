@@ -0,0 +1,207 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use rayexec_error::{ErrorKind, RayexecError, Result};
+
+/// What a scan should do when a read from a remote source stalls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StallAction {
+    /// Fail the scan, and the query.
+    #[default]
+    Fail,
+    /// Retry the stalled read, resuming the partition from where it stopped.
+    ///
+    /// Sources that can't resume a partition fail the scan instead.
+    Retry,
+}
+
+/// Stall handling for scans reading from remote sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScanStall {
+    /// Max time to wait for a response or for more data. None indicates no
+    /// timeout.
+    pub timeout: Option<Duration>,
+    /// What to do once the timeout is hit.
+    pub action: StallAction,
+}
+
+impl ScanStall {
+    /// If stalled reads should be retried.
+    pub fn retry(&self) -> bool {
+        self.action == StallAction::Retry
+    }
+}
+
+/// Create the error returned when a remote read stalls.
+pub fn stall_error(timeout: Duration) -> RayexecError {
+    RayexecError::new(format!(
+        "Remote read stalled, no data received for {} seconds",
+        timeout.as_secs_f64()
+    ))
+    .with_kind(ErrorKind::Timeout)
+}
+
+/// Wraps a stream from a remote source, erroring if no items are received for
+/// the configured timeout.
+///
+/// This fails a read from a wedged connection (e.g. an object store connection
+/// that stops sending data, or a hung COPY stream) instead of letting it wait
+/// forever. The timer resets every time an item is received.
+///
+/// Only waits on the wrapped stream are covered, sources should separately
+/// time out sending the request. The stream ends after the error, sources
+/// that retry stalled reads need to open a new stream.
+///
+/// The timer is driven by the provided tokio runtime handle.
+pub struct StallTimeoutStream<T> {
+    inner: BoxStream<'static, Result<T>>,
+    timeout: Duration,
+    handle: tokio::runtime::Handle,
+    /// Sleep future for the current wait. Created lazily when the inner stream
+    /// returns pending.
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    /// If we've already timed out. We'll return None after the error.
+    timed_out: bool,
+}
+
+impl<T> StallTimeoutStream<T> {
+    pub fn new(
+        inner: BoxStream<'static, Result<T>>,
+        timeout: Duration,
+        handle: tokio::runtime::Handle,
+    ) -> Self {
+        StallTimeoutStream {
+            inner,
+            timeout,
+            handle,
+            sleep: None,
+            timed_out: false,
+        }
+    }
+}
+
+impl<T> Stream for StallTimeoutStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.timed_out {
+            return Poll::Ready(None);
+        }
+
+        match self.inner.poll_next_unpin(cx) {
+            Poll::Ready(item) => {
+                self.sleep = None;
+                Poll::Ready(item)
+            }
+            Poll::Pending => {
+                let timeout = self.timeout;
+                let handle = self.handle.clone();
+                let sleep = self.sleep.get_or_insert_with(|| {
+                    // Sleep needs to be created in the context of the runtime
+                    // so it can register with its timer.
+                    let _guard = handle.enter();
+                    Box::pin(tokio::time::sleep(timeout))
+                });
+
+                match sleep.as_mut().poll(cx) {
+                    Poll::Ready(_) => {
+                        self.sleep = None;
+                        self.timed_out = true;
+                        Poll::Ready(Some(Err(stall_error(timeout))))
+                    }
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for StallTimeoutStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StallTimeoutStream")
+            .field("timeout", &self.timeout)
+            .field("timed_out", &self.timed_out)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Wrap a stream with a stall timeout if a timeout is provided.
+pub fn maybe_with_stall_timeout<T: 'static>(
+    stream: BoxStream<'static, Result<T>>,
+    timeout: Option<Duration>,
+    handle: Option<tokio::runtime::Handle>,
+) -> BoxStream<'static, Result<T>> {
+    match (timeout, handle) {
+        (Some(timeout), Some(handle)) => StallTimeoutStream::new(stream, timeout, handle).boxed(),
+        _ => stream,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+
+    fn test_runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn stalled_stream_errors() {
+        let rt = test_runtime();
+        let inner = stream::once(async { Ok(1) })
+            .chain(stream::pending())
+            .boxed();
+        let mut stream =
+            StallTimeoutStream::new(inner, Duration::from_millis(50), rt.handle().clone());
+
+        rt.block_on(async {
+            assert_eq!(1, stream.next().await.unwrap().unwrap());
+
+            let err = stream.next().await.unwrap().unwrap_err();
+            assert_eq!(ErrorKind::Timeout, err.kind());
+
+            // Not retried, stream ends after the error.
+            assert!(stream.next().await.is_none());
+        });
+    }
+
+    #[test]
+    fn timer_resets_on_item() {
+        let rt = test_runtime();
+        // Takes longer than the timeout in total, but each item arrives before
+        // the timeout.
+        let inner = stream::unfold(0, |count| async move {
+            if count == 5 {
+                return None;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Some((Ok(count), count + 1))
+        })
+        .boxed();
+        let stream = StallTimeoutStream::new(inner, Duration::from_millis(60), rt.handle().clone());
+
+        let items: Vec<_> = rt.block_on(stream.collect::<Vec<_>>());
+        let items: Vec<_> = items.into_iter().map(|item| item.unwrap()).collect();
+        assert_eq!(vec![0, 1, 2, 3, 4], items);
+    }
+
+    #[test]
+    fn no_timeout_returns_inner() {
+        let rt = test_runtime();
+        let inner = stream::iter([Ok(1), Ok(2)]).boxed();
+        let stream = maybe_with_stall_timeout(inner, None, Some(rt.handle().clone()));
+
+        let items: Vec<_> = rt.block_on(stream.collect::<Vec<_>>());
+        assert_eq!(2, items.len());
+    }
+}
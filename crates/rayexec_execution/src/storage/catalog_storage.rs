@@ -19,4 +19,12 @@ pub trait CatalogStorage: Debug + Sync + Send {
     }
 
     fn load_table(&self, schema: &str, name: &str) -> BoxFuture<'_, Result<Option<TableEntry>>>;
+
+    /// List (schema, table) names for all tables in the catalog.
+    ///
+    /// Used for introspecting the catalog (e.g. information_schema). Tables
+    /// returned here will be loaded with `load_table`.
+    fn list_tables(&self) -> BoxFuture<'_, Result<Vec<(String, String)>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
 }
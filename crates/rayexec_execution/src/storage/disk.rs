@@ -0,0 +1,2016 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use half::f16;
+use parking_lot::Mutex;
+use rayexec_error::{OptionExt, RayexecError, Result, ResultExt};
+use rayexec_proto::generated::storage::wal_record::Record;
+use rayexec_proto::generated::storage::{
+    ColumnBlock,
+    ColumnEncoding,
+    DataFile,
+    DatabaseManifest,
+    ManifestTable,
+    TableBlock,
+    WalAppend,
+    WalCompact,
+    WalDropTable,
+    WalRecord,
+};
+use rayexec_proto::prost::Message;
+use rayexec_proto::ProtoConv;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::memory::{next_data_version, PrimaryKeyIndex, StoredBatch};
+use super::table_storage::{
+    DataTable,
+    DataTableScan,
+    DataVersion,
+    Projections,
+    TableStatistics,
+    TableStorage,
+};
+use crate::arrays::array::{Array, ArrayData, BinaryData};
+use crate::arrays::batch::Batch;
+use crate::arrays::bitmap::Bitmap;
+use crate::arrays::datatype::DataType;
+use crate::arrays::executor::physical_type::PhysicalType;
+use crate::arrays::executor::scalar::concat;
+use crate::arrays::scalar::interval::Interval;
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::arrays::storage::{BooleanStorage, GermanVarlenStorage, PrimitiveStorage};
+use crate::database::catalog::CatalogTx;
+use crate::database::catalog_entry::{CatalogEntry, CatalogEntryInner, TableEntry};
+use crate::database::create::{CreateSchemaInfo, OnConflict};
+use crate::database::memory_catalog::MemoryCatalog;
+use crate::database::Database;
+use crate::execution::operators::sink::PartitionSink;
+use crate::logical::statistics::StatisticsValue;
+
+/// Name of the schema that's always present in a database stored on disk.
+pub const DEFAULT_DISK_SCHEMA: &str = "public";
+
+const MANIFEST_FILE: &str = "manifest.pb";
+const WAL_FILE: &str = "wal.log";
+const DATA_DIR: &str = "data";
+
+/// Number of records written to the log before checkpointing.
+const CHECKPOINT_THRESHOLD: usize = 128;
+
+/// Size of the header preceding each record in the log (length + checksum).
+const WAL_HEADER_SIZE: usize = 8;
+
+/// Target number of rows in a data file.
+///
+/// Inserts start a new file once this many rows have been buffered, and
+/// compaction merges smaller files into files of about this size.
+const TARGET_FILE_ROWS: usize = 64 * 1024;
+
+/// Number of files smaller than the target size a table can have before
+/// they're compacted after an insert.
+const COMPACTION_THRESHOLD: usize = 8;
+
+/// Table storage persisting tables to a directory on disk.
+///
+/// Table data is only read from disk when it's scanned, one data file at a
+/// time. Inserted rows are written to new data files before they become
+/// visible, and a manifest tracks the schemas, tables, and the data files for
+/// each table.
+///
+/// ```text
+/// <root>/manifest.pb
+/// <root>/wal.log
+/// <root>/data/<file_id>.pb
+/// ```
+///
+/// Data files store columns as buffers of values rather than individual
+/// scalars. Files are written with at most `TARGET_FILE_ROWS` rows, and small
+/// files left behind by small inserts are compacted into larger files once a
+/// table has enough of them.
+///
+/// Changes to the manifest are first appended to a write-ahead log and synced
+/// before being applied. The manifest itself is only rewritten on checkpoint,
+/// after which the log is cleared. Opening the database replays the log on
+/// top of the last checkpointed manifest, so a crash in the middle of a
+/// change either loses the change entirely or recovers it fully.
+#[derive(Debug)]
+pub struct DiskTableStorage {
+    state: Arc<DiskState>,
+}
+
+#[derive(Debug)]
+struct DiskState {
+    root: PathBuf,
+    inner: Mutex<DiskStateInner>,
+}
+
+#[derive(Debug)]
+struct DiskStateInner {
+    /// Manifest with all logged changes applied.
+    manifest: DatabaseManifest,
+    /// Write-ahead log, opened for appending.
+    wal: File,
+    /// Length of the log after the last successfully written record.
+    wal_len: u64,
+    /// Number of records in the log since the last checkpoint.
+    wal_records: usize,
+    /// Sequence number of the last logged record.
+    lsn: u64,
+    /// Data files for each table in the manifest.
+    tables: HashMap<TableKey, TableFiles>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TableKey {
+    schema: String,
+    name: String,
+}
+
+impl TableKey {
+    fn new(schema: &str, name: &str) -> Self {
+        TableKey {
+            schema: schema.to_string(),
+            name: name.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TableKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.schema, self.name)
+    }
+}
+
+/// Data files for a table, along with state that's only kept in memory.
+#[derive(Debug)]
+struct TableFiles {
+    files: Vec<TableFile>,
+    /// Index over the primary key, if the table has one.
+    ///
+    /// Built from the key columns of every file when the database is opened.
+    primary_key: Option<PrimaryKeyIndex>,
+    /// Version of the data, updated on every append.
+    version: u64,
+    /// Timestamp of the most recent commit that appended to this table.
+    last_commit: u64,
+    /// If the table's files are currently being compacted.
+    compacting: bool,
+}
+
+/// A data file belonging to a table.
+#[derive(Debug, Clone)]
+struct TableFile {
+    handle: Arc<DataFileHandle>,
+    num_rows: usize,
+    /// Timestamp of the commit that added the file. Zero for files that
+    /// existed when the database was opened.
+    commit_ts: u64,
+}
+
+/// Handle to a data file, shared between a table and its scans.
+///
+/// Files removed from a table are only deleted once the last handle is
+/// dropped, so scans that started before a table was dropped or compacted can
+/// still read them.
+#[derive(Debug)]
+struct DataFileHandle {
+    id: u64,
+    path: PathBuf,
+    removed: AtomicBool,
+}
+
+impl Drop for DataFileHandle {
+    fn drop(&mut self) {
+        if self.removed.load(Ordering::Acquire) {
+            // Failing to remove the file just leaves garbage around until the
+            // database is next opened.
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+impl TableFile {
+    fn new(root: &Path, file: &DataFile, commit_ts: u64) -> Self {
+        TableFile {
+            handle: Arc::new(DataFileHandle {
+                id: file.id,
+                path: root.join(data_file(file.id)),
+                removed: AtomicBool::new(false),
+            }),
+            num_rows: file.num_rows as usize,
+            commit_ts,
+        }
+    }
+}
+
+impl TableFiles {
+    /// Create the files for a new, empty table.
+    fn new(table: &TableEntry) -> Self {
+        TableFiles {
+            files: Vec::new(),
+            primary_key: (!table.primary_key.is_empty())
+                .then(|| PrimaryKeyIndex::new(table.primary_key.clone())),
+            version: next_data_version(),
+            last_commit: 0,
+            compacting: false,
+        }
+    }
+
+    /// Load the files for a table in the manifest, reading the primary key
+    /// columns of every file if the table has a primary key.
+    fn open(root: &Path, table: &ManifestTable) -> Result<Self> {
+        let files: Vec<_> = table
+            .data_files
+            .iter()
+            .map(|file| TableFile::new(root, file, 0))
+            .collect();
+
+        let entry = match &table.table {
+            Some(entry) => TableEntry::from_proto(entry.clone())?,
+            None => TableEntry::new(Vec::new()),
+        };
+        let mut loaded = TableFiles {
+            files,
+            ..TableFiles::new(&entry)
+        };
+
+        if let Some(index) = &mut loaded.primary_key {
+            let name = TableKey::new(&table.schema, &table.name).to_string();
+            let columns = primary_key_columns(&entry)?;
+            for file in &loaded.files {
+                let stored = StoredBatch {
+                    column_ids: entry.primary_key.clone().into(),
+                    batch: read_file(&file.handle.path, &entry, &columns)?,
+                };
+                let keys = index.new_keys(&name, None, &[stored])?;
+                index.committed.extend(keys);
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    /// Mark all files as removed, deleting them once they're no longer being
+    /// scanned.
+    fn remove(self) {
+        for file in self.files {
+            file.handle.removed.store(true, Ordering::Release);
+        }
+    }
+}
+
+impl DiskTableStorage {
+    /// Open the database in the given directory, creating the directory if it
+    /// doesn't exist.
+    ///
+    /// The returned database contains all previously persisted schemas and
+    /// tables. Table data stays on disk until it's scanned.
+    pub fn open_database(root: impl Into<PathBuf>) -> Result<Database> {
+        let state = DiskState::open(root.into())?;
+        let manifest = state.inner.lock().manifest.clone();
+
+        let tx = CatalogTx::new();
+        let catalog = MemoryCatalog::default();
+
+        let schemas = std::iter::once(DEFAULT_DISK_SCHEMA).chain(
+            manifest
+                .schemas
+                .iter()
+                .map(|s| s.as_str())
+                .filter(|s| *s != DEFAULT_DISK_SCHEMA),
+        );
+        for schema in schemas {
+            catalog.create_schema(
+                &tx,
+                &CreateSchemaInfo {
+                    name: schema.to_string(),
+                    on_conflict: OnConflict::Error,
+                },
+            )?;
+        }
+
+        for table in manifest.tables {
+            let ent = TableEntry::from_proto(table.table.required("table")?)?;
+            let schema = catalog.get_schema(&tx, &table.schema)?.ok_or_else(|| {
+                RayexecError::new(format!(
+                    "Missing schema '{}' for table '{}' in manifest",
+                    table.schema, table.name
+                ))
+            })?;
+            schema.create_table_entry(&tx, &table.name, ent)?;
+        }
+
+        let storage = DiskTableStorage {
+            state: Arc::new(state),
+        };
+
+        Ok(Database {
+            id: Uuid::new_v4(),
+            catalog: Arc::new(catalog),
+            catalog_storage: None,
+            table_storage: Some(Arc::new(storage)),
+            attach_info: None,
+        })
+    }
+
+    /// Write the manifest with all changes so far and clear the write-ahead
+    /// log.
+    ///
+    /// This happens automatically after enough changes have been logged.
+    pub fn checkpoint(&self) -> Result<()> {
+        let mut inner = self.state.inner.lock();
+        self.state.checkpoint(&mut inner)
+    }
+
+    /// Merge the small data files of every table.
+    ///
+    /// Tables are compacted automatically after an insert once they have
+    /// enough small files, this compacts tables with as few as two.
+    pub fn compact(&self) -> Result<()> {
+        let keys: Vec<_> = self.state.inner.lock().tables.keys().cloned().collect();
+        for key in keys {
+            self.state.compact_table(&key, 2)?;
+        }
+        Ok(())
+    }
+}
+
+impl TableStorage for DiskTableStorage {
+    fn data_table(
+        &self,
+        tx: &CatalogTx,
+        schema: &str,
+        ent: &CatalogEntry,
+    ) -> Result<Box<dyn DataTable>> {
+        let key = TableKey::new(schema, &ent.name);
+        if !self.state.inner.lock().tables.contains_key(&key) {
+            return Err(RayexecError::new(format!(
+                "Missing physical disk table for entry: {ent:?}. Cannot get data table",
+            )));
+        }
+
+        // Reads and writes use the columns from the entry the query was bound
+        // with, even if the table has since been altered.
+        Ok(Box::new(DiskDataTable {
+            state: self.state.clone(),
+            key,
+            tx: tx.clone(),
+            layout: Arc::new(table_entry(ent)?.clone()),
+        }))
+    }
+
+    fn create_physical_table(
+        &self,
+        schema: &str,
+        ent: &CatalogEntry,
+    ) -> BoxFuture<'_, Result<Box<dyn DataTable>>> {
+        let key = TableKey::new(schema, &ent.name);
+        let table = table_entry(ent).cloned();
+
+        Box::pin(async move {
+            let table = table?;
+            let mut inner = self.state.inner.lock();
+            self.state.log_locked(
+                &mut inner,
+                Record::CreateTable(ManifestTable {
+                    schema: key.schema.clone(),
+                    name: key.name.clone(),
+                    table: Some(table.to_proto()?),
+                    files: Vec::new(),
+                    data_files: Vec::new(),
+                }),
+            )?;
+            inner.tables.insert(key.clone(), TableFiles::new(&table));
+
+            Ok(Box::new(DiskDataTable {
+                state: self.state.clone(),
+                key,
+                tx: CatalogTx::new(),
+                layout: Arc::new(table),
+            }) as _)
+        })
+    }
+
+    fn drop_physical_table(&self, schema: &str, ent: &CatalogEntry) -> BoxFuture<'_, Result<()>> {
+        let key = TableKey::new(schema, &ent.name);
+
+        Box::pin(async move {
+            let mut inner = self.state.inner.lock();
+            self.state.log_locked(
+                &mut inner,
+                Record::DropTable(WalDropTable {
+                    schema: key.schema.clone(),
+                    name: key.name.clone(),
+                }),
+            )?;
+            if let Some(files) = inner.tables.remove(&key) {
+                files.remove();
+            }
+            Ok(())
+        })
+    }
+
+    fn alter_physical_table(&self, schema: &str, ent: &CatalogEntry) -> BoxFuture<'_, Result<()>> {
+        let key = TableKey::new(schema, &ent.name);
+        let table = table_entry(ent).and_then(|table| table.to_proto());
+
+        Box::pin(async move {
+            let table = table?;
+            let mut inner = self.state.inner.lock();
+            // Data files are kept as is, they're read using the column ids
+            // stored with them.
+            self.state.log_locked(
+                &mut inner,
+                Record::AlterTable(ManifestTable {
+                    schema: key.schema.clone(),
+                    name: key.name.clone(),
+                    table: Some(table),
+                    files: Vec::new(),
+                    data_files: Vec::new(),
+                }),
+            )?;
+            if let Some(files) = inner.tables.get_mut(&key) {
+                // Cached results for the table no longer match its columns.
+                files.version = next_data_version();
+            }
+            Ok(())
+        })
+    }
+
+    fn create_physical_schema(&self, schema: &str) -> BoxFuture<'_, Result<()>> {
+        let schema = schema.to_string();
+        Box::pin(async move { self.state.log(Record::CreateSchema(schema)) })
+    }
+
+    fn drop_physical_schema(&self, schema: &str) -> BoxFuture<'_, Result<()>> {
+        let schema = schema.to_string();
+        Box::pin(async move {
+            let mut inner = self.state.inner.lock();
+            self.state
+                .log_locked(&mut inner, Record::DropSchema(schema.clone()))?;
+            let dropped: Vec<_> = inner
+                .tables
+                .keys()
+                .filter(|key| key.schema == schema)
+                .cloned()
+                .collect();
+            for key in dropped {
+                if let Some(files) = inner.tables.remove(&key) {
+                    files.remove();
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+impl DiskState {
+    /// Open the database directory, recovering any changes in the
+    /// write-ahead log.
+    fn open(root: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(root.join(DATA_DIR))
+            .context_fn(|| format!("Failed to create database directory: {}", root.display()))?;
+
+        let manifest_path = root.join(MANIFEST_FILE);
+        let mut manifest = if manifest_path.exists() {
+            let buf = std::fs::read(&manifest_path).context("Failed to read manifest")?;
+            DatabaseManifest::decode(buf.as_slice()).context("Failed to decode manifest")?
+        } else {
+            DatabaseManifest::default()
+        };
+
+        let wal_path = root.join(WAL_FILE);
+        let records = if wal_path.exists() {
+            read_wal(&std::fs::read(&wal_path).context("Failed to read write-ahead log")?)
+        } else {
+            Vec::new()
+        };
+
+        // Records up to the checkpoint are already reflected in the manifest
+        // if we crashed after writing the manifest but before clearing the
+        // log.
+        let mut lsn = manifest.checkpoint_lsn;
+        for record in records {
+            if record.lsn <= lsn {
+                continue;
+            }
+            apply_record(&mut manifest, &record)?;
+            lsn = record.lsn;
+        }
+
+        upgrade_legacy_files(&root, &mut manifest)?;
+
+        let tables = manifest
+            .tables
+            .iter()
+            .map(|table| {
+                Ok((
+                    TableKey::new(&table.schema, &table.name),
+                    TableFiles::open(&root, table)?,
+                ))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        let wal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&wal_path)
+            .context("Failed to open write-ahead log")?;
+
+        let state = DiskState {
+            root,
+            inner: Mutex::new(DiskStateInner {
+                manifest,
+                wal,
+                wal_len: 0,
+                wal_records: 0,
+                lsn,
+                tables,
+            }),
+        };
+
+        // Fold the recovered changes into the manifest. This also discards a
+        // partially written record at the end of the log.
+        {
+            let mut inner = state.inner.lock();
+            state.checkpoint(&mut inner)?;
+        }
+        state.remove_unreferenced_files()?;
+
+        Ok(state)
+    }
+
+    /// Log a change to the manifest, applying it once it's durable.
+    ///
+    /// The in-memory manifest is left unchanged if the change is invalid or
+    /// can't be logged.
+    fn log(&self, record: Record) -> Result<()> {
+        let mut inner = self.inner.lock();
+        self.log_locked(&mut inner, record)
+    }
+
+    /// Same as `log`, for callers that need to update the table files along
+    /// with the manifest.
+    fn log_locked(&self, inner: &mut DiskStateInner, record: Record) -> Result<()> {
+        let record = WalRecord {
+            lsn: inner.lsn + 1,
+            record: Some(record),
+        };
+        let mut manifest = inner.manifest.clone();
+        apply_record(&mut manifest, &record)?;
+
+        let payload = record.encode_to_vec();
+        let mut frame = Vec::with_capacity(WAL_HEADER_SIZE + payload.len());
+        frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+        frame.extend_from_slice(&payload);
+
+        let result = inner
+            .wal
+            .write_all(&frame)
+            .and_then(|_| inner.wal.sync_data());
+        if let Err(e) = result {
+            // Don't leave a partial record around, later records would be
+            // ignored on replay.
+            let wal_len = inner.wal_len;
+            let _ = inner.wal.set_len(wal_len);
+            return Err(RayexecError::with_source(
+                "Failed to write to write-ahead log",
+                Box::new(e),
+            ));
+        }
+
+        inner.manifest = manifest;
+        inner.wal_len += frame.len() as u64;
+        inner.wal_records += 1;
+        inner.lsn = record.lsn;
+
+        if inner.wal_records >= CHECKPOINT_THRESHOLD {
+            // The change is durable in the log, a failed checkpoint just means
+            // a longer log to replay.
+            if let Err(e) = self.checkpoint(inner) {
+                warn!(%e, "failed to checkpoint database");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the manifest to disk and clear the log.
+    fn checkpoint(&self, inner: &mut DiskStateInner) -> Result<()> {
+        inner.manifest.checkpoint_lsn = inner.lsn;
+
+        // Write then rename so a partially written manifest is never read.
+        let tmp = self.root.join(format!("{MANIFEST_FILE}.tmp"));
+        write_synced(&tmp, &inner.manifest.encode_to_vec()).context("Failed to write manifest")?;
+        std::fs::rename(&tmp, self.root.join(MANIFEST_FILE))
+            .context("Failed to replace manifest")?;
+        sync_dir(&self.root)?;
+
+        // Only cleared once the new manifest is durable. Crashing before this
+        // skips the records already in the manifest on replay.
+        inner
+            .wal
+            .set_len(0)
+            .and_then(|_| inner.wal.sync_all())
+            .context("Failed to clear write-ahead log")?;
+        inner.wal_len = 0;
+        inner.wal_records = 0;
+
+        Ok(())
+    }
+
+    /// Write a new data file without adding it to the manifest.
+    ///
+    /// A crash before the file is logged leaves an unreferenced file, removed
+    /// the next time the database is opened.
+    fn write_file(&self, column_ids: &[usize], batch: &Batch) -> Result<DataFile> {
+        let id = {
+            let mut inner = self.inner.lock();
+            let id = inner.manifest.next_file_id;
+            inner.manifest.next_file_id += 1;
+            id
+        };
+
+        write_synced(
+            &self.root.join(data_file(id)),
+            &encode_block(batch, column_ids)?.encode_to_vec(),
+        )
+        .context("Failed to write data file")?;
+        sync_dir(&self.root.join(DATA_DIR))?;
+
+        Ok(DataFile {
+            id,
+            num_rows: batch.num_rows() as u64,
+        })
+    }
+
+    /// Remove written files that were never logged.
+    fn remove_unlogged(&self, files: &[DataFile]) {
+        for file in files {
+            let _ = std::fs::remove_file(self.root.join(data_file(file.id)));
+        }
+    }
+
+    /// Add files written by an insert to a table, making them visible.
+    ///
+    /// Errors if the inserted keys violate the table's primary key, in which
+    /// case the files are left unreferenced.
+    fn append(&self, key: &TableKey, files: &[DataFile], keys: &[StoredBatch]) -> Result<()> {
+        CatalogTx::auto_commit(|commit_ts| {
+            let mut inner = self.inner.lock();
+            let table = inner
+                .tables
+                .get(key)
+                .ok_or_else(|| RayexecError::new(format!("Table '{key}' missing from manifest")))?;
+            let new_keys = match &table.primary_key {
+                Some(index) => Some(index.new_keys(&key.to_string(), None, keys)?),
+                None => None,
+            };
+
+            self.log_locked(
+                &mut inner,
+                Record::Append(WalAppend {
+                    schema: key.schema.clone(),
+                    name: key.name.clone(),
+                    file_id: 0,
+                    files: files.to_vec(),
+                }),
+            )?;
+
+            let table = inner.tables.get_mut(key).required("table files")?;
+            if let (Some(index), Some(keys)) = (&mut table.primary_key, new_keys) {
+                index.committed.extend(keys);
+            }
+            table.files.extend(
+                files
+                    .iter()
+                    .map(|file| TableFile::new(&self.root, file, commit_ts)),
+            );
+            table.last_commit = commit_ts;
+            table.version = next_data_version();
+
+            Ok(())
+        })
+    }
+
+    /// Merge the files of a table smaller than `TARGET_FILE_ROWS`, if there's
+    /// at least `min_files` of them.
+    fn compact_table(&self, key: &TableKey, min_files: usize) -> Result<()> {
+        self.compact_table_at(key, min_files, &CatalogTx::active_snapshots())
+    }
+
+    /// Merge small files of a table, keeping the rows visible to each of the
+    /// given snapshots the same.
+    ///
+    /// Files are only merged with files that every snapshot either sees or
+    /// doesn't see, the merged files are then made visible to the same
+    /// snapshots.
+    fn compact_table_at(&self, key: &TableKey, min_files: usize, snapshots: &[u64]) -> Result<()> {
+        let (inputs, layout) = {
+            let mut inner = self.inner.lock();
+            let layout = match inner
+                .manifest
+                .tables
+                .iter()
+                .find(|table| table.schema == key.schema && table.name == key.name)
+                .and_then(|table| table.table.clone())
+            {
+                Some(layout) => TableEntry::from_proto(layout)?,
+                None => return Ok(()),
+            };
+            let table = match inner.tables.get_mut(key) {
+                Some(table) if !table.compacting => table,
+                _ => return Ok(()),
+            };
+
+            // Group files by the number of snapshots they're hidden from.
+            let mut groups: HashMap<usize, Vec<TableFile>> = HashMap::new();
+            for file in &table.files {
+                if file.num_rows < TARGET_FILE_ROWS {
+                    let hidden_from = snapshots.partition_point(|&s| s < file.commit_ts);
+                    groups.entry(hidden_from).or_default().push(file.clone());
+                }
+            }
+            let inputs = match groups.into_values().max_by_key(|group| group.len()) {
+                Some(inputs) if inputs.len() >= min_files.max(2) => inputs,
+                _ => return Ok(()),
+            };
+            table.compacting = true;
+
+            (inputs, layout)
+        };
+
+        let mut added = Vec::new();
+        let result = self.write_compacted(&inputs, &layout, &mut added);
+
+        let mut inner = self.inner.lock();
+        // The table may have been dropped (and recreated) in the meantime.
+        let still_present = inner.tables.get(key).is_some_and(|table| {
+            inputs.iter().all(|input| {
+                table
+                    .files
+                    .iter()
+                    .any(|file| Arc::ptr_eq(&file.handle, &input.handle))
+            })
+        });
+        if !still_present {
+            self.remove_unlogged(&added);
+            return result;
+        }
+        let logged = result.and_then(|_| {
+            self.log_locked(
+                &mut inner,
+                Record::Compact(WalCompact {
+                    schema: key.schema.clone(),
+                    name: key.name.clone(),
+                    removed: inputs.iter().map(|input| input.handle.id).collect(),
+                    added: added.clone(),
+                }),
+            )
+        });
+
+        let table = inner.tables.get_mut(key).required("table files")?;
+        table.compacting = false;
+        if let Err(e) = logged {
+            self.remove_unlogged(&added);
+            return Err(e);
+        }
+
+        table.files.retain(|file| {
+            let removed = inputs
+                .iter()
+                .any(|input| Arc::ptr_eq(&file.handle, &input.handle));
+            if removed {
+                file.handle.removed.store(true, Ordering::Release);
+            }
+            !removed
+        });
+        let commit_ts = inputs
+            .iter()
+            .map(|input| input.commit_ts)
+            .max()
+            .unwrap_or(0);
+        table.files.extend(
+            added
+                .iter()
+                .map(|file| TableFile::new(&self.root, file, commit_ts)),
+        );
+
+        Ok(())
+    }
+
+    /// Write the rows from `inputs` to new files, pushing each file to
+    /// `added` once it's been written.
+    fn write_compacted(
+        &self,
+        inputs: &[TableFile],
+        layout: &TableEntry,
+        added: &mut Vec<DataFile>,
+    ) -> Result<()> {
+        let columns: Vec<_> = (0..layout.columns.len()).collect();
+        let mut buffered = Vec::new();
+        let mut buffered_rows = 0;
+
+        for (idx, input) in inputs.iter().enumerate() {
+            let batch = read_file(&input.handle.path, layout, &columns)?;
+            buffered_rows += batch.num_rows();
+            buffered.push(batch);
+
+            if buffered_rows >= TARGET_FILE_ROWS || (idx == inputs.len() - 1 && buffered_rows > 0) {
+                let batch = Batch::concat(&buffered)?;
+                added.push(self.write_file(&layout.column_ids, &batch)?);
+                buffered.clear();
+                buffered_rows = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove data files that aren't referenced by the manifest, e.g. files
+    /// written for an append that was never logged.
+    ///
+    /// Only called when opening the database, otherwise this would race with
+    /// data files being written.
+    fn remove_unreferenced_files(&self) -> Result<()> {
+        let referenced: HashSet<_> = self
+            .inner
+            .lock()
+            .manifest
+            .tables
+            .iter()
+            .flat_map(|table| table.data_files.iter().map(|file| data_file(file.id)))
+            .collect();
+
+        let entries =
+            std::fs::read_dir(self.root.join(DATA_DIR)).context("Failed to read data directory")?;
+        for entry in entries {
+            let entry = entry.context("Failed to read data directory")?;
+            let file = format!("{DATA_DIR}/{}", entry.file_name().to_string_lossy());
+            if !referenced.contains(&file) {
+                // Failing to remove it just leaves garbage around until the
+                // database is next opened.
+                let _ = std::fs::remove_file(self.root.join(file));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Get the table entry for a physical table.
+fn table_entry(ent: &CatalogEntry) -> Result<&TableEntry> {
+    match &ent.entry {
+        CatalogEntryInner::Table(table) => Ok(table),
+        other => Err(RayexecError::new(format!(
+            "Unexpected catalog entry for physical table: {other:?}"
+        ))),
+    }
+}
+
+/// Get the positions of the primary key columns in a table.
+fn primary_key_columns(table: &TableEntry) -> Result<Vec<usize>> {
+    table
+        .primary_key
+        .iter()
+        .map(|id| {
+            table
+                .column_ids
+                .iter()
+                .position(|col| col == id)
+                .ok_or_else(|| {
+                    RayexecError::new(format!("Missing primary key column with id {id}"))
+                })
+        })
+        .collect()
+}
+
+/// Path of a data file relative to the database directory.
+fn data_file(file_id: u64) -> String {
+    format!("{DATA_DIR}/{file_id}.pb")
+}
+
+/// Move data files recorded without row counts to `data_files`, counting
+/// their rows.
+///
+/// Files are read one at a time, this only happens the first time a database
+/// written before row counts were recorded is opened.
+fn upgrade_legacy_files(root: &Path, manifest: &mut DatabaseManifest) -> Result<()> {
+    for table in &mut manifest.tables {
+        for path in std::mem::take(&mut table.files) {
+            let id = path
+                .strip_prefix(&format!("{DATA_DIR}/"))
+                .and_then(|file| file.strip_suffix(".pb"))
+                .and_then(|id| id.parse().ok())
+                .ok_or_else(|| RayexecError::new(format!("Invalid data file path: {path}")))?;
+            let block = read_block(&root.join(&path))?;
+
+            table.data_files.push(DataFile {
+                id,
+                num_rows: block_num_rows(&block) as u64,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply a logged change to the manifest.
+///
+/// Errors if the change is inconsistent with the manifest.
+fn apply_record(manifest: &mut DatabaseManifest, record: &WalRecord) -> Result<()> {
+    let record = record
+        .record
+        .as_ref()
+        .required("record")
+        .context("Invalid write-ahead log record")?;
+
+    let find_table = |manifest: &mut DatabaseManifest, schema: &str, name: &str| {
+        manifest
+            .tables
+            .iter_mut()
+            .position(|table| table.schema == schema && table.name == name)
+            .ok_or_else(|| {
+                RayexecError::new(format!("Table '{schema}.{name}' missing from manifest"))
+            })
+    };
+
+    match record {
+        Record::CreateSchema(schema) => {
+            if !manifest.schemas.contains(schema) {
+                manifest.schemas.push(schema.clone());
+            }
+        }
+        Record::DropSchema(schema) => {
+            manifest.schemas.retain(|s| s != schema);
+            manifest.tables.retain(|table| &table.schema != schema);
+        }
+        Record::CreateTable(table) => {
+            if manifest
+                .tables
+                .iter()
+                .any(|t| t.schema == table.schema && t.name == table.name)
+            {
+                return Err(RayexecError::new(format!(
+                    "Table '{}.{}' already exists in manifest",
+                    table.schema, table.name
+                )));
+            }
+            if !manifest.schemas.contains(&table.schema) {
+                manifest.schemas.push(table.schema.clone());
+            }
+            manifest.tables.push(table.clone());
+        }
+        Record::DropTable(drop) => {
+            manifest
+                .tables
+                .retain(|table| table.schema != drop.schema || table.name != drop.name);
+        }
+        Record::AlterTable(alter) => {
+            let idx = find_table(manifest, &alter.schema, &alter.name)?;
+            manifest.tables[idx].table = alter.table.clone();
+        }
+        Record::Append(append) => {
+            let idx = find_table(manifest, &append.schema, &append.name)?;
+            let table = &mut manifest.tables[idx];
+            if append.files.is_empty() {
+                // Logged before appends recorded row counts.
+                table.files.push(data_file(append.file_id));
+                manifest.next_file_id = manifest.next_file_id.max(append.file_id + 1);
+            }
+            for file in &append.files {
+                table.data_files.push(*file);
+                manifest.next_file_id = manifest.next_file_id.max(file.id + 1);
+            }
+        }
+        Record::Compact(compact) => {
+            let idx = find_table(manifest, &compact.schema, &compact.name)?;
+            let table = &mut manifest.tables[idx];
+            for id in &compact.removed {
+                if !table.data_files.iter().any(|file| file.id == *id) {
+                    return Err(RayexecError::new(format!(
+                        "Compacted file {id} missing from table '{}.{}'",
+                        compact.schema, compact.name
+                    )));
+                }
+            }
+            table
+                .data_files
+                .retain(|file| !compact.removed.contains(&file.id));
+            for file in &compact.added {
+                table.data_files.push(*file);
+                manifest.next_file_id = manifest.next_file_id.max(file.id + 1);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read all complete records from the write-ahead log.
+///
+/// Reading stops at the first record that's incomplete or fails its checksum.
+/// Records are synced as they're written, so this can only be a record that
+/// was being written during a crash.
+fn read_wal(mut buf: &[u8]) -> Vec<WalRecord> {
+    let mut records = Vec::new();
+
+    while !buf.is_empty() {
+        if buf.len() < WAL_HEADER_SIZE {
+            warn!("ignoring partial write-ahead log record");
+            break;
+        }
+        let len = u32::from_le_bytes(buf[0..4].try_into().unwrap()) as usize;
+        let checksum = u32::from_le_bytes(buf[4..8].try_into().unwrap());
+
+        let payload = match buf.get(WAL_HEADER_SIZE..WAL_HEADER_SIZE + len) {
+            Some(payload) if crc32fast::hash(payload) == checksum => payload,
+            _ => {
+                warn!("ignoring partial write-ahead log record");
+                break;
+            }
+        };
+        match WalRecord::decode(payload) {
+            Ok(record) => records.push(record),
+            Err(e) => {
+                warn!(%e, "ignoring invalid write-ahead log record");
+                break;
+            }
+        }
+
+        buf = &buf[WAL_HEADER_SIZE + len..];
+    }
+
+    records
+}
+
+/// Write a file, syncing it to disk before returning.
+fn write_synced(path: &Path, buf: &[u8]) -> std::io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(buf)?;
+    file.sync_all()
+}
+
+/// Sync a directory so that entries created or renamed in it are durable.
+fn sync_dir(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    File::open(path)
+        .and_then(|dir| dir.sync_all())
+        .context_fn(|| format!("Failed to sync directory: {}", path.display()))?;
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
+
+/// A table stored on disk.
+#[derive(Debug, Clone)]
+struct DiskDataTable {
+    state: Arc<DiskState>,
+    key: TableKey,
+    /// Transaction the table is being accessed in.
+    tx: CatalogTx,
+    /// Columns the table is being accessed with.
+    layout: Arc<TableEntry>,
+}
+
+impl DiskDataTable {
+    /// Get the files visible to the transaction.
+    fn visible_files(&self) -> Result<Vec<TableFile>> {
+        let inner = self.state.inner.lock();
+        let table = inner.tables.get(&self.key).ok_or_else(|| {
+            RayexecError::new(format!("Table '{}' missing from manifest", self.key))
+        })?;
+
+        Ok(table
+            .files
+            .iter()
+            .filter(|file| self.tx.is_visible(file.commit_ts))
+            .cloned()
+            .collect())
+    }
+}
+
+impl DataTable for DiskDataTable {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let columns = projections
+            .column_indices
+            .unwrap_or_else(|| (0..self.layout.columns.len()).collect());
+
+        let mut scans: Vec<_> = (0..num_partitions)
+            .map(|_| DiskDataTableScan {
+                files: VecDeque::new(),
+                layout: self.layout.clone(),
+                columns: columns.clone(),
+                batch_size: batch_size.max(1),
+                current: None,
+            })
+            .collect();
+
+        for (idx, file) in self.visible_files()?.into_iter().enumerate() {
+            scans[idx % num_partitions].files.push_back(file.handle);
+        }
+
+        Ok(scans.into_iter().map(|scan| Box::new(scan) as _).collect())
+    }
+
+    fn insert(&self, input_partitions: usize) -> Result<Vec<Box<dyn PartitionSink>>> {
+        if self.tx.is_explicit() {
+            // Data files would need to be written as part of the commit.
+            return Err(RayexecError::new(
+                "Inserting into tables stored on disk is not yet supported in explicit transactions",
+            ));
+        }
+
+        let key_columns = primary_key_columns(&self.layout)?;
+
+        Ok((0..input_partitions)
+            .map(|_| {
+                Box::new(DiskDataTableInsert {
+                    table: self.clone(),
+                    key_columns: key_columns.clone(),
+                    buffered: Vec::new(),
+                    buffered_rows: 0,
+                    written: Vec::new(),
+                    keys: Vec::new(),
+                }) as _
+            })
+            .collect())
+    }
+
+    fn data_version(&self) -> DataVersion {
+        let inner = self.state.inner.lock();
+        match inner.tables.get(&self.key) {
+            // Reading from a snapshot older than the latest data.
+            Some(table) if self.tx.is_visible(table.last_commit) => {
+                DataVersion::Version(table.version)
+            }
+            _ => DataVersion::Volatile,
+        }
+    }
+
+    fn statistics(&self) -> BoxFuture<'_, Result<TableStatistics>> {
+        let num_rows = self
+            .visible_files()
+            .map(|files| files.iter().map(|file| file.num_rows).sum());
+        Box::pin(async move {
+            Ok(TableStatistics {
+                num_rows: StatisticsValue::Exact(num_rows?),
+                ..Default::default()
+            })
+        })
+    }
+}
+
+/// Scan reading a table's data files one at a time.
+#[derive(Debug)]
+struct DiskDataTableScan {
+    files: VecDeque<Arc<DataFileHandle>>,
+    layout: Arc<TableEntry>,
+    /// Positions of the columns to read in the layout.
+    columns: Vec<usize>,
+    batch_size: usize,
+    /// Rows read from the current file, and the offset of the next row to
+    /// return.
+    current: Option<(Batch, usize)>,
+}
+
+impl DiskDataTableScan {
+    fn pull_inner(&mut self) -> Result<Option<Batch>> {
+        loop {
+            if let Some((batch, offset)) = &mut self.current {
+                if *offset < batch.num_rows() {
+                    let count = usize::min(self.batch_size, batch.num_rows() - *offset);
+                    let out = batch.slice(*offset, count);
+                    *offset += count;
+                    return Ok(Some(out));
+                }
+                self.current = None;
+            }
+
+            let file = match self.files.pop_front() {
+                Some(file) => file,
+                None => return Ok(None),
+            };
+            let batch = read_file(&file.path, &self.layout, &self.columns)?;
+            self.current = Some((batch, 0));
+        }
+    }
+}
+
+impl DataTableScan for DiskDataTableScan {
+    fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
+        Box::pin(async { self.pull_inner() })
+    }
+}
+
+/// Insert writing batches to new data files.
+///
+/// Files are written as rows are pushed, and only added to the table once the
+/// insert finishes.
+#[derive(Debug)]
+struct DiskDataTableInsert {
+    table: DiskDataTable,
+    /// Positions of the primary key columns in the table's layout.
+    key_columns: Vec<usize>,
+    /// Batches not yet written to a file.
+    buffered: Vec<Batch>,
+    buffered_rows: usize,
+    /// Files written by this insert so far.
+    written: Vec<DataFile>,
+    /// Primary key columns for all inserted rows.
+    keys: Vec<StoredBatch>,
+}
+
+impl DiskDataTableInsert {
+    /// Write buffered batches to a new file.
+    fn flush(&mut self) -> Result<()> {
+        if self.buffered_rows == 0 {
+            return Ok(());
+        }
+        let batch = Batch::concat(&self.buffered)?;
+        let file = self
+            .table
+            .state
+            .write_file(&self.table.layout.column_ids, &batch)?;
+        self.written.push(file);
+        self.buffered.clear();
+        self.buffered_rows = 0;
+        Ok(())
+    }
+}
+
+impl PartitionSink for DiskDataTableInsert {
+    fn push(&mut self, batch: Batch) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            if batch.num_rows() == 0 {
+                return Ok(());
+            }
+            if !self.key_columns.is_empty() {
+                self.keys.push(StoredBatch {
+                    column_ids: self.table.layout.primary_key.clone().into(),
+                    batch: batch.project(&self.key_columns),
+                });
+            }
+
+            self.buffered_rows += batch.num_rows();
+            self.buffered.push(batch);
+            if self.buffered_rows >= TARGET_FILE_ROWS {
+                self.flush()?;
+            }
+            Ok(())
+        })
+    }
+
+    fn finalize(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async {
+            self.flush()?;
+            if self.written.is_empty() {
+                return Ok(());
+            }
+
+            let state = &self.table.state;
+            state.append(&self.table.key, &self.written, &self.keys)?;
+            self.written.clear();
+
+            // The insert has already been committed, failing to compact just
+            // leaves more files to read.
+            if let Err(e) = state.compact_table(&self.table.key, COMPACTION_THRESHOLD) {
+                warn!(%e, table = %self.table.key, "failed to compact table");
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl Drop for DiskDataTableInsert {
+    fn drop(&mut self) {
+        // Files from an insert that failed or was canceled.
+        self.table.state.remove_unlogged(&self.written);
+    }
+}
+
+/// Encode a batch as a block of columns.
+fn encode_block(batch: &Batch, column_ids: &[usize]) -> Result<TableBlock> {
+    let columns = batch
+        .columns()
+        .iter()
+        .map(encode_column)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(TableBlock {
+        columns,
+        column_ids: column_ids.iter().map(|&id| id as u64).collect(),
+    })
+}
+
+/// Encode a column, storing values in buffers if there's a buffer layout for
+/// the column's physical type.
+///
+/// Buffers are written in native byte order, all supported targets are little
+/// endian.
+pub(crate) fn encode_column(array: &Array) -> Result<ColumnBlock> {
+    let array = array.unselect()?;
+    let len = array.logical_len();
+
+    let buffers = match array.array_data() {
+        ArrayData::UntypedNull(_) => Vec::new(),
+        ArrayData::Boolean(d) => vec![d.0.as_bytes().to_vec()],
+        ArrayData::Int8(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::Int16(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::Int32(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::Int64(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::Int128(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::UInt8(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::UInt16(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::UInt32(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::UInt64(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::UInt128(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::Float16(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::Float32(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::Float64(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::Interval(d) => vec![d.as_bytes().to_vec()],
+        ArrayData::Binary(d) => match d {
+            BinaryData::Binary(d) => encode_varlen(d.iter()),
+            BinaryData::LargeBinary(d) => encode_varlen(d.iter()),
+            BinaryData::German(d) => encode_varlen(d.iter()),
+        },
+        ArrayData::List(_) => {
+            // No buffer layout for nested values yet.
+            let values = (0..len)
+                .map(|idx| array.logical_value(idx)?.into_owned().to_proto())
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(ColumnBlock {
+                values,
+                ..Default::default()
+            });
+        }
+    };
+
+    let validity = match array.validity() {
+        Some(validity) if !validity.is_all_true() => validity.as_bytes().to_vec(),
+        _ => Vec::new(),
+    };
+
+    Ok(ColumnBlock {
+        values: Vec::new(),
+        encoding: ColumnEncoding::Buffers as i32,
+        len: len as u64,
+        validity,
+        buffers,
+    })
+}
+
+/// Encode variable length values as an offsets buffer and a data buffer.
+fn encode_varlen<'a>(values: impl Iterator<Item = &'a [u8]>) -> Vec<Vec<u8>> {
+    let mut offsets = 0_u64.to_le_bytes().to_vec();
+    let mut data = Vec::new();
+    for value in values {
+        data.extend_from_slice(value);
+        offsets.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    }
+    vec![offsets, data]
+}
+
+/// Read and decode a data file.
+fn read_block(path: &Path) -> Result<TableBlock> {
+    let buf = std::fs::read(path)
+        .context_fn(|| format!("Failed to read data file: {}", path.display()))?;
+    TableBlock::decode(buf.as_slice()).context("Failed to decode data file")
+}
+
+/// Get the number of rows in a block.
+fn block_num_rows(block: &TableBlock) -> usize {
+    match block.columns.first() {
+        Some(column) if column.encoding() == ColumnEncoding::Buffers => column.len as usize,
+        Some(column) => column.values.len(),
+        None => 0,
+    }
+}
+
+/// Read columns from a data file using a table's current columns.
+///
+/// `columns` are the positions of the columns to read in `table`. Columns
+/// added after the file was written are filled in with their default values.
+/// An empty list of columns produces a batch with just the number of rows.
+fn read_file(path: &Path, table: &TableEntry, columns: &[usize]) -> Result<Batch> {
+    let block = read_block(path)?;
+    let num_rows = block_num_rows(&block);
+
+    // Files written before columns could be altered don't store ids.
+    let block_ids: Vec<usize> = if block.column_ids.is_empty() {
+        (0..block.columns.len()).collect()
+    } else {
+        block.column_ids.iter().map(|&id| id as usize).collect()
+    };
+    if block_ids.len() != block.columns.len() {
+        return Err(RayexecError::new(format!(
+            "Data file '{}' has {} columns, but {} column ids",
+            path.display(),
+            block.columns.len(),
+            block_ids.len()
+        )));
+    }
+
+    let mut block_columns: Vec<_> = block.columns.into_iter().map(Some).collect();
+    let arrays = columns
+        .iter()
+        .map(|&idx| {
+            let (id, field, default) = match (
+                table.column_ids.get(idx),
+                table.columns.get(idx),
+                table.column_defaults.get(idx),
+            ) {
+                (Some(id), Some(field), Some(default)) => (id, field, default),
+                _ => return Err(RayexecError::new(format!("Missing column {idx} in table"))),
+            };
+
+            let column = block_ids
+                .iter()
+                .position(|block_id| block_id == id)
+                .and_then(|pos| block_columns[pos].take());
+            let array = match column {
+                Some(column) => decode_column(column, &field.datatype)?,
+                None => match default {
+                    ScalarValue::Null => {
+                        Array::new_typed_null_array(field.datatype.clone(), num_rows)?
+                    }
+                    value => value.as_array(num_rows)?,
+                },
+            };
+
+            if array.logical_len() != num_rows {
+                return Err(RayexecError::new(format!(
+                    "Data file '{}' has columns with differing lengths",
+                    path.display()
+                )));
+            }
+            Ok(array)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if arrays.is_empty() {
+        return Ok(Batch::empty_with_num_rows(num_rows));
+    }
+    Batch::try_new(arrays)
+}
+
+/// Decode a column from a data file.
+pub(crate) fn decode_column(column: ColumnBlock, datatype: &DataType) -> Result<Array> {
+    if column.encoding() == ColumnEncoding::Scalar {
+        return decode_scalar_column(column, datatype);
+    }
+
+    let len = column.len as usize;
+    let mut buffers = column.buffers.into_iter();
+    let mut next_buffer = || buffers.next().required("column buffer");
+
+    let data: ArrayData = match datatype.physical_type()? {
+        PhysicalType::UntypedNull => return Ok(Array::new_untyped_null_array(len)),
+        PhysicalType::Boolean => BooleanStorage::from(decode_bitmap(next_buffer()?, len)?).into(),
+        PhysicalType::Int8 => decode_primitive::<i8>(&next_buffer()?, len)?,
+        PhysicalType::Int16 => decode_primitive::<i16>(&next_buffer()?, len)?,
+        PhysicalType::Int32 => decode_primitive::<i32>(&next_buffer()?, len)?,
+        PhysicalType::Int64 => decode_primitive::<i64>(&next_buffer()?, len)?,
+        PhysicalType::Int128 => decode_primitive::<i128>(&next_buffer()?, len)?,
+        PhysicalType::UInt8 => decode_primitive::<u8>(&next_buffer()?, len)?,
+        PhysicalType::UInt16 => decode_primitive::<u16>(&next_buffer()?, len)?,
+        PhysicalType::UInt32 => decode_primitive::<u32>(&next_buffer()?, len)?,
+        PhysicalType::UInt64 => decode_primitive::<u64>(&next_buffer()?, len)?,
+        PhysicalType::UInt128 => decode_primitive::<u128>(&next_buffer()?, len)?,
+        PhysicalType::Float16 => decode_primitive::<f16>(&next_buffer()?, len)?,
+        PhysicalType::Float32 => decode_primitive::<f32>(&next_buffer()?, len)?,
+        PhysicalType::Float64 => decode_primitive::<f64>(&next_buffer()?, len)?,
+        PhysicalType::Interval => decode_primitive::<Interval>(&next_buffer()?, len)?,
+        PhysicalType::Binary | PhysicalType::Utf8 => {
+            let offsets = next_buffer()?;
+            let data = next_buffer()?;
+            decode_varlen(&offsets, &data, len)?
+        }
+        PhysicalType::List => {
+            return Err(RayexecError::new(
+                "List columns are expected to be stored as scalars",
+            ))
+        }
+    };
+
+    if column.validity.is_empty() {
+        Ok(Array::new_with_array_data(datatype.clone(), data))
+    } else {
+        let validity = decode_bitmap(column.validity, len)?;
+        Ok(Array::new_with_validity_and_array_data(
+            datatype.clone(),
+            validity,
+            data,
+        ))
+    }
+}
+
+/// Decode a column stored as individual scalar values.
+fn decode_scalar_column(column: ColumnBlock, datatype: &DataType) -> Result<Array> {
+    let values = column
+        .values
+        .into_iter()
+        .map(|value| match OwnedScalarValue::from_proto(value)? {
+            ScalarValue::Null => Array::new_typed_null_array(datatype.clone(), 1),
+            value => value.as_array(1),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if values.is_empty() {
+        return Array::new_typed_null_array(datatype.clone(), 0);
+    }
+    let refs: Vec<_> = values.iter().collect();
+    concat(&refs)
+}
+
+fn decode_bitmap(buf: Vec<u8>, len: usize) -> Result<Bitmap> {
+    if buf.len() < len.div_ceil(8) {
+        return Err(RayexecError::new(format!(
+            "Bitmap with {} bytes too short for {len} values",
+            buf.len()
+        )));
+    }
+    Bitmap::try_new(buf, len)
+}
+
+fn decode_primitive<T>(buf: &[u8], len: usize) -> Result<ArrayData>
+where
+    T: Copy + Default,
+    PrimitiveStorage<T>: Into<ArrayData>,
+{
+    let storage = PrimitiveStorage::<T>::copy_from_bytes(buf)?;
+    if storage.len() != len {
+        return Err(RayexecError::new(format!(
+            "Expected {len} values in column buffer, got {}",
+            storage.len()
+        )));
+    }
+    Ok(storage.into())
+}
+
+fn decode_varlen(offsets: &[u8], data: &[u8], len: usize) -> Result<ArrayData> {
+    if offsets.len() != (len + 1) * 8 {
+        return Err(RayexecError::new(format!(
+            "Expected {} offsets in column buffer, got {} bytes",
+            len + 1,
+            offsets.len()
+        )));
+    }
+    let offsets: Vec<_> = offsets
+        .chunks_exact(8)
+        .map(|offset| u64::from_le_bytes(offset.try_into().unwrap()) as usize)
+        .collect();
+
+    let mut storage = GermanVarlenStorage::with_metadata_capacity(len);
+    for window in offsets.windows(2) {
+        let value = data
+            .get(window[0]..window[1])
+            .ok_or_else(|| RayexecError::new("Column offsets out of bounds"))?;
+        storage.try_push(value)?;
+    }
+
+    Ok(storage.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::arrays::field::Field;
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("rayexec_{prefix}_{}", Uuid::new_v4()))
+    }
+
+    fn write_block(dir: &Path, block: &TableBlock) -> PathBuf {
+        std::fs::create_dir_all(dir).unwrap();
+        let path = dir.join("block.pb");
+        std::fs::write(&path, block.encode_to_vec()).unwrap();
+        path
+    }
+
+    fn assert_batches_eq(expected: &Batch, got: &Batch) {
+        assert_eq!(expected.num_rows(), got.num_rows());
+        assert_eq!(expected.columns().len(), got.columns().len());
+        for (expected, got) in expected.columns().iter().zip(got.columns()) {
+            for row in 0..expected.logical_len() {
+                assert_eq!(
+                    expected.logical_value(row).unwrap(),
+                    got.logical_value(row).unwrap(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn block_roundtrip() {
+        let batch = Batch::try_new([
+            Array::from_iter([Some(1), None, Some(3)]),
+            Array::from_iter(["a", "", "a much longer string value"]),
+            Array::from_iter([true, false, true]),
+            Array::from_iter([Some(1.5_f64), Some(2.5), None]),
+            Array::new_untyped_null_array(3),
+            OwnedScalarValue::List(vec![OwnedScalarValue::Int64(4)])
+                .as_array(3)
+                .unwrap(),
+        ])
+        .unwrap()
+        // Selections are materialized when encoding.
+        .slice(1, 2);
+        let table = TableEntry::new(
+            batch
+                .columns()
+                .iter()
+                .enumerate()
+                .map(|(idx, array)| Field::new(format!("c{idx}"), array.datatype().clone(), true))
+                .collect(),
+        );
+
+        let block = encode_block(&batch, &table.column_ids).unwrap();
+        for column in &block.columns[..5] {
+            assert_eq!(ColumnEncoding::Buffers, column.encoding());
+            assert!(column.values.is_empty());
+        }
+        assert_eq!(ColumnEncoding::Scalar, block.columns[5].encoding());
+
+        let dir = temp_dir("block");
+        let path = write_block(&dir, &block);
+
+        let got = read_file(&path, &table, &[0, 1, 2, 3, 4, 5]).unwrap();
+        assert_batches_eq(&batch, &got);
+
+        // Only the requested columns are read.
+        let got = read_file(&path, &table, &[1]).unwrap();
+        assert_batches_eq(&batch.project(&[1]), &got);
+        let got = read_file(&path, &table, &[]).unwrap();
+        assert_eq!(2, got.num_rows());
+        assert!(got.columns().is_empty());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn read_scalar_block() {
+        // Blocks written before columns were stored as buffers.
+        let block = TableBlock {
+            columns: vec![ColumnBlock {
+                values: vec![
+                    OwnedScalarValue::Int32(1).to_proto().unwrap(),
+                    OwnedScalarValue::Null.to_proto().unwrap(),
+                ],
+                ..Default::default()
+            }],
+            column_ids: Vec::new(),
+        };
+        let table = TableEntry::new(vec![Field::new("a", DataType::Int32, true)]);
+
+        let dir = temp_dir("block");
+        let path = write_block(&dir, &block);
+
+        let got = read_file(&path, &table, &[0]).unwrap();
+        let expected = Batch::try_new([Array::from_iter([Some(1), None])]).unwrap();
+        assert_batches_eq(&expected, &got);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn block_read_after_alter() {
+        let batch =
+            Batch::try_new([Array::from_iter([1, 2]), Array::from_iter(["a", "b"])]).unwrap();
+        let table = TableEntry::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+
+        let dir = temp_dir("block");
+        let path = write_block(&dir, &encode_block(&batch, &table.column_ids).unwrap());
+
+        // Column 'a' dropped, 'c' added.
+        let altered = TableEntry {
+            columns: vec![
+                Field::new("b", DataType::Utf8, true),
+                Field::new("c", DataType::Int64, true),
+            ],
+            column_ids: vec![1, 2],
+            column_defaults: vec![OwnedScalarValue::Null, OwnedScalarValue::Int64(3)],
+            next_column_id: 3,
+            checks: Vec::new(),
+            primary_key: Vec::new(),
+            indexes: Vec::new(),
+            policies: Vec::new(),
+        };
+        let got = read_file(&path, &altered, &[0, 1]).unwrap();
+        let expected =
+            Batch::try_new([Array::from_iter(["a", "b"]), Array::from_iter([3_i64, 3])]).unwrap();
+        assert_batches_eq(&expected, &got);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn create_table_record(name: &str) -> Record {
+        Record::CreateTable(ManifestTable {
+            schema: DEFAULT_DISK_SCHEMA.to_string(),
+            name: name.to_string(),
+            table: None,
+            files: Vec::new(),
+            data_files: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn wal_recovery() {
+        let dir = temp_dir("wal");
+
+        let state = DiskState::open(dir.clone()).unwrap();
+        state.log(create_table_record("t1")).unwrap();
+        state
+            .log(Record::Append(WalAppend {
+                schema: DEFAULT_DISK_SCHEMA.to_string(),
+                name: "t1".to_string(),
+                file_id: 0,
+                files: vec![DataFile { id: 3, num_rows: 0 }],
+            }))
+            .unwrap();
+        // Logging an invalid change doesn't apply it.
+        state.log(create_table_record("t1")).unwrap_err();
+        std::mem::drop(state);
+
+        // Simulate crashing while writing a record, and while writing a data
+        // file.
+        let mut wal = OpenOptions::new()
+            .append(true)
+            .open(dir.join(WAL_FILE))
+            .unwrap();
+        wal.write_all(&[64, 0, 0, 0, 1, 2, 3]).unwrap();
+        std::fs::write(dir.join(data_file(3)), []).unwrap();
+        std::fs::write(dir.join(data_file(4)), []).unwrap();
+
+        let state = DiskState::open(dir.clone()).unwrap();
+        let manifest = state.inner.lock().manifest.clone();
+        assert_eq!(1, manifest.tables.len());
+        assert_eq!(
+            vec![DataFile { id: 3, num_rows: 0 }],
+            manifest.tables[0].data_files
+        );
+        assert_eq!(4, manifest.next_file_id);
+        assert_eq!(0, std::fs::metadata(dir.join(WAL_FILE)).unwrap().len());
+        assert!(dir.join(data_file(3)).exists());
+        assert!(!dir.join(data_file(4)).exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn wal_recovers_legacy_appends() {
+        let dir = temp_dir("wal");
+
+        let state = DiskState::open(dir.clone()).unwrap();
+        state.log(create_table_record("t1")).unwrap();
+        state
+            .log(Record::Append(WalAppend {
+                schema: DEFAULT_DISK_SCHEMA.to_string(),
+                name: "t1".to_string(),
+                file_id: 0,
+                files: Vec::new(),
+            }))
+            .unwrap();
+        std::mem::drop(state);
+
+        let batch = Batch::try_new([Array::from_iter([1, 2, 3])]).unwrap();
+        std::fs::write(
+            dir.join(data_file(0)),
+            encode_block(&batch, &[0]).unwrap().encode_to_vec(),
+        )
+        .unwrap();
+
+        // Row counts are filled in when opening.
+        let state = DiskState::open(dir.clone()).unwrap();
+        let manifest = state.inner.lock().manifest.clone();
+        assert!(manifest.tables[0].files.is_empty());
+        assert_eq!(
+            vec![DataFile { id: 0, num_rows: 3 }],
+            manifest.tables[0].data_files
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn wal_skips_checkpointed_records() {
+        let dir = temp_dir("wal");
+
+        let state = DiskState::open(dir.clone()).unwrap();
+        state.log(create_table_record("t1")).unwrap();
+        let wal = std::fs::read(dir.join(WAL_FILE)).unwrap();
+        {
+            let mut inner = state.inner.lock();
+            state.checkpoint(&mut inner).unwrap();
+        }
+        std::mem::drop(state);
+
+        // Simulate crashing after writing the manifest, but before clearing
+        // the log.
+        std::fs::write(dir.join(WAL_FILE), wal).unwrap();
+
+        let state = DiskState::open(dir.clone()).unwrap();
+        assert_eq!(1, state.inner.lock().manifest.tables.len());
+        state.log(create_table_record("t2")).unwrap();
+        assert_eq!(2, state.inner.lock().manifest.tables.len());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn table_ent(table: TableEntry) -> CatalogEntry {
+        CatalogEntry {
+            oid: 0,
+            name: "t".to_string(),
+            entry: CatalogEntryInner::Table(table),
+            child: None,
+        }
+    }
+
+    fn insert(storage: &dyn TableStorage, ent: &CatalogEntry, batch: Batch) -> Result<()> {
+        let table = storage.data_table(&CatalogTx::new(), DEFAULT_DISK_SCHEMA, ent)?;
+        let mut sinks = table.insert(1)?;
+        block_on(sinks[0].push(batch))?;
+        block_on(sinks[0].finalize())
+    }
+
+    fn scan(storage: &dyn TableStorage, ent: &CatalogEntry) -> Vec<i32> {
+        let table = storage
+            .data_table(&CatalogTx::new(), DEFAULT_DISK_SCHEMA, ent)
+            .unwrap();
+        let mut values = Vec::new();
+        for mut scan in table.scan(Projections::all(), 2, 1024).unwrap() {
+            while let Some(batch) = block_on(scan.pull()).unwrap() {
+                for row in 0..batch.num_rows() {
+                    match batch.column(0).unwrap().logical_value(row).unwrap() {
+                        ScalarValue::Int32(v) => values.push(v),
+                        other => panic!("unexpected value: {other}"),
+                    }
+                }
+            }
+        }
+        values.sort();
+        values
+    }
+
+    fn open_storage(dir: &Path) -> DiskTableStorage {
+        DiskTableStorage {
+            state: Arc::new(DiskState::open(dir.to_path_buf()).unwrap()),
+        }
+    }
+
+    fn table_files(storage: &DiskTableStorage) -> Vec<TableFile> {
+        storage.state.inner.lock().tables[&TableKey::new(DEFAULT_DISK_SCHEMA, "t")]
+            .files
+            .clone()
+    }
+
+    fn num_data_files(dir: &Path) -> usize {
+        std::fs::read_dir(dir.join(DATA_DIR)).unwrap().count()
+    }
+
+    fn int_batch(values: impl IntoIterator<Item = i32>) -> Batch {
+        Batch::try_new([Array::from_iter(values)]).unwrap()
+    }
+
+    #[test]
+    fn scan_after_reopen() {
+        let dir = temp_dir("table");
+        let mut table = TableEntry::new(vec![Field::new("a", DataType::Int32, true)]);
+        table.primary_key = vec![0];
+        let ent = table_ent(table);
+
+        let storage = open_storage(&dir);
+        block_on(storage.create_physical_table(DEFAULT_DISK_SCHEMA, &ent)).unwrap();
+        insert(&storage, &ent, int_batch([1, 2, 3])).unwrap();
+        std::mem::drop(storage);
+
+        let db = DiskTableStorage::open_database(&dir).unwrap();
+        let storage = db.table_storage.unwrap();
+        assert_eq!(vec![1, 2, 3], scan(storage.as_ref(), &ent));
+
+        // Primary key is rebuilt from the files, and a failed insert leaves
+        // no files behind.
+        let err = insert(storage.as_ref(), &ent, int_batch([4, 2])).unwrap_err();
+        assert!(err.to_string().contains("Duplicate key"), "{err}");
+        assert_eq!(1, num_data_files(&dir));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn compaction_merges_small_files() {
+        let dir = temp_dir("table");
+        let key = TableKey::new(DEFAULT_DISK_SCHEMA, "t");
+        let ent = table_ent(TableEntry::new(vec![Field::new(
+            "a",
+            DataType::Int32,
+            true,
+        )]));
+
+        let storage = open_storage(&dir);
+        block_on(storage.create_physical_table(DEFAULT_DISK_SCHEMA, &ent)).unwrap();
+        for v in 0..(COMPACTION_THRESHOLD as i32 - 1) {
+            insert(&storage, &ent, int_batch([v])).unwrap();
+        }
+        assert_eq!(COMPACTION_THRESHOLD - 1, table_files(&storage).len());
+
+        // Scans started before compaction keep reading the old files.
+        let table = storage
+            .data_table(&CatalogTx::new(), DEFAULT_DISK_SCHEMA, &ent)
+            .unwrap();
+        let mut old_scan = table.scan(Projections::all(), 1, 1024).unwrap().remove(0);
+
+        // Reaching the threshold compacts the table after the insert, unless
+        // another test happens to be holding a snapshot in between our
+        // inserts. Compact again without snapshots to be sure.
+        insert(&storage, &ent, int_batch([100])).unwrap();
+        storage.state.compact_table_at(&key, 2, &[]).unwrap();
+        assert_eq!(1, table_files(&storage).len());
+        let expected: Vec<_> = (0..(COMPACTION_THRESHOLD as i32 - 1))
+            .chain([100])
+            .collect();
+        assert_eq!(expected, scan(&storage, &ent));
+
+        let mut old_rows = 0;
+        while let Some(batch) = block_on(old_scan.pull()).unwrap() {
+            old_rows += batch.num_rows();
+        }
+        assert_eq!(COMPACTION_THRESHOLD - 1, old_rows);
+        std::mem::drop(old_scan);
+        std::mem::drop(table);
+        assert_eq!(1, num_data_files(&dir));
+
+        // Compactions are recovered from the log.
+        insert(&storage, &ent, int_batch([200])).unwrap();
+        storage.state.compact_table_at(&key, 2, &[]).unwrap();
+        assert_eq!(1, table_files(&storage).len());
+        std::mem::drop(storage);
+
+        let storage = open_storage(&dir);
+        let expected: Vec<_> = expected.into_iter().chain([200]).collect();
+        assert_eq!(expected, scan(&storage, &ent));
+        assert_eq!(1, num_data_files(&dir));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn compaction_keeps_snapshot_visibility() {
+        let dir = temp_dir("table");
+        let key = TableKey::new(DEFAULT_DISK_SCHEMA, "t");
+        let ent = table_ent(TableEntry::new(vec![Field::new(
+            "a",
+            DataType::Int32,
+            true,
+        )]));
+
+        let storage = open_storage(&dir);
+        block_on(storage.create_physical_table(DEFAULT_DISK_SCHEMA, &ent)).unwrap();
+        insert(&storage, &ent, int_batch([1])).unwrap();
+        let tx = CatalogTx::begin();
+        insert(&storage, &ent, int_batch([2])).unwrap();
+        insert(&storage, &ent, int_batch([3])).unwrap();
+
+        let table = storage.data_table(&tx, DEFAULT_DISK_SCHEMA, &ent).unwrap();
+        let stats = block_on(table.statistics()).unwrap();
+        assert_eq!(StatisticsValue::Exact(1), stats.num_rows);
+        assert_eq!(DataVersion::Volatile, table.data_version());
+
+        // The first file is visible to the snapshot, the others aren't, so
+        // only the others can be merged.
+        let files = table_files(&storage);
+        let snapshot = files[0].commit_ts;
+        storage
+            .state
+            .compact_table_at(&key, 2, &[snapshot])
+            .unwrap();
+
+        let compacted = table_files(&storage);
+        assert_eq!(2, compacted.len());
+        assert!(compacted[0].commit_ts <= snapshot);
+        assert_eq!(2, compacted[1].num_rows);
+        assert!(compacted[1].commit_ts > snapshot);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,331 @@
+//! Hierarchical navigable small world (HNSW) graphs for approximate nearest
+//! neighbor search over fixed-size vectors.
+//!
+//! Every vector is a node in the bottom layer of the graph, with each layer
+//! above containing an exponentially decreasing subset of the nodes. Searches
+//! greedily walk the sparse upper layers to find a good starting point for a
+//! wider search of the bottom layer.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayexec_error::{RayexecError, Result};
+
+use crate::functions::scalar::builtin::similarity::VectorMetric;
+
+/// Max number of neighbors for a node in the upper layers.
+const MAX_NEIGHBORS: usize = 16;
+
+/// Max number of neighbors for a node in the bottom layer.
+const MAX_BASE_NEIGHBORS: usize = MAX_NEIGHBORS * 2;
+
+/// Number of candidates considered when finding neighbors for a new node.
+const EF_CONSTRUCTION: usize = 64;
+
+/// Upper bound on the number of layers.
+const MAX_LEVEL: usize = 16;
+
+#[derive(Debug)]
+pub struct Hnsw {
+    metric: VectorMetric,
+    dimensions: usize,
+    /// Vectors for all nodes, concatenated.
+    vectors: Vec<f32>,
+    /// Neighbors of each node, for each layer the node is part of.
+    neighbors: Vec<Vec<Vec<usize>>>,
+    /// Node in the top layer that searches start from.
+    entry_point: Option<usize>,
+    /// Used to pick node levels. Seeded so that graphs are reproducible.
+    rng: StdRng,
+}
+
+/// A node along with its distance to the vector being searched for.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    distance: f32,
+    node: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+impl Hnsw {
+    pub fn new(metric: VectorMetric, dimensions: usize) -> Self {
+        Hnsw {
+            metric,
+            dimensions,
+            vectors: Vec::new(),
+            neighbors: Vec::new(),
+            entry_point: None,
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.neighbors.is_empty()
+    }
+
+    /// Insert a vector into the graph, returning its node id.
+    ///
+    /// Node ids are assigned sequentially starting from zero.
+    pub fn insert(&mut self, vector: &[f32]) -> Result<usize> {
+        self.check_dimensions(vector)?;
+
+        let node = self.len();
+        let level = self.random_level();
+        self.vectors.extend_from_slice(vector);
+        self.neighbors.push(vec![Vec::new(); level + 1]);
+
+        let mut entry = match self.entry_point {
+            Some(entry) => entry,
+            None => {
+                self.entry_point = Some(node);
+                return Ok(node);
+            }
+        };
+        let top = self.neighbors[entry].len() - 1;
+
+        for layer in (level + 1..=top).rev() {
+            entry = self.search_layer(vector, entry, 1, layer)[0].node;
+        }
+
+        for layer in (0..=level.min(top)).rev() {
+            let candidates = self.search_layer(vector, entry, EF_CONSTRUCTION, layer);
+            let max_neighbors = Self::max_neighbors(layer);
+
+            let neighbors: Vec<_> = candidates
+                .iter()
+                .take(max_neighbors)
+                .map(|c| c.node)
+                .collect();
+            for &neighbor in &neighbors {
+                self.neighbors[neighbor][layer].push(node);
+                if self.neighbors[neighbor][layer].len() > max_neighbors {
+                    self.prune(neighbor, layer, max_neighbors);
+                }
+            }
+            self.neighbors[node][layer] = neighbors;
+
+            entry = candidates[0].node;
+        }
+
+        if level > top {
+            self.entry_point = Some(node);
+        }
+
+        Ok(node)
+    }
+
+    /// Search for the (approximately) `k` nearest nodes to a vector, ordered
+    /// from nearest to farthest.
+    ///
+    /// `ef` controls the number of candidates kept during the search, larger
+    /// values trade speed for better recall.
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Result<Vec<usize>> {
+        self.check_dimensions(query)?;
+
+        let mut entry = match self.entry_point {
+            Some(entry) => entry,
+            None => return Ok(Vec::new()),
+        };
+
+        for layer in (1..self.neighbors[entry].len()).rev() {
+            entry = self.search_layer(query, entry, 1, layer)[0].node;
+        }
+
+        Ok(self
+            .search_layer(query, entry, ef.max(k), 0)
+            .into_iter()
+            .take(k)
+            .map(|c| c.node)
+            .collect())
+    }
+
+    fn check_dimensions(&self, vector: &[f32]) -> Result<()> {
+        if vector.len() != self.dimensions {
+            return Err(RayexecError::new(format!(
+                "Expected vector with {} dimensions, got {}",
+                self.dimensions,
+                vector.len()
+            )));
+        }
+        Ok(())
+    }
+
+    fn vector(&self, node: usize) -> &[f32] {
+        &self.vectors[node * self.dimensions..(node + 1) * self.dimensions]
+    }
+
+    fn distance(&self, query: &[f32], node: usize) -> f32 {
+        self.metric.distance(query, self.vector(node))
+    }
+
+    /// Find the `ef` nearest nodes to the query in a single layer, starting
+    /// from `entry`. Returned candidates are ordered nearest first.
+    fn search_layer(&self, query: &[f32], entry: usize, ef: usize, layer: usize) -> Vec<Candidate> {
+        let start = Candidate {
+            distance: self.distance(query, entry),
+            node: entry,
+        };
+
+        let mut visited = HashSet::from([entry]);
+        // Nodes left to expand, nearest first.
+        let mut candidates = BinaryHeap::from([Reverse(start)]);
+        // Nearest nodes found so far, farthest first.
+        let mut nearest = BinaryHeap::from([start]);
+
+        while let Some(Reverse(candidate)) = candidates.pop() {
+            let farthest = nearest.peek().expect("at least one node");
+            if candidate.distance > farthest.distance && nearest.len() >= ef {
+                break;
+            }
+
+            for &neighbor in &self.neighbors[candidate.node][layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let next = Candidate {
+                    distance: self.distance(query, neighbor),
+                    node: neighbor,
+                };
+                let farthest = nearest.peek().expect("at least one node");
+                if nearest.len() < ef || next < *farthest {
+                    candidates.push(Reverse(next));
+                    nearest.push(next);
+                    if nearest.len() > ef {
+                        nearest.pop();
+                    }
+                }
+            }
+        }
+
+        nearest.into_sorted_vec()
+    }
+
+    /// Keep only the nearest neighbors of a node in a layer.
+    fn prune(&mut self, node: usize, layer: usize, max_neighbors: usize) {
+        let vector = self.vector(node);
+        let mut neighbors: Vec<_> = self.neighbors[node][layer]
+            .iter()
+            .map(|&neighbor| Candidate {
+                distance: self.metric.distance(vector, self.vector(neighbor)),
+                node: neighbor,
+            })
+            .collect();
+        neighbors.sort_unstable();
+
+        self.neighbors[node][layer] = neighbors
+            .into_iter()
+            .take(max_neighbors)
+            .map(|c| c.node)
+            .collect();
+    }
+
+    const fn max_neighbors(layer: usize) -> usize {
+        if layer == 0 {
+            MAX_BASE_NEIGHBORS
+        } else {
+            MAX_NEIGHBORS
+        }
+    }
+
+    /// Pick the top layer for a new node, with each layer being
+    /// `MAX_NEIGHBORS` times less likely than the one below it.
+    fn random_level(&mut self) -> usize {
+        let mut level = 0;
+        while level < MAX_LEVEL && self.rng.gen_ratio(1, MAX_NEIGHBORS as u32) {
+            level += 1;
+        }
+        level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_vectors(rng: &mut StdRng, count: usize, dimensions: usize) -> Vec<Vec<f32>> {
+        (0..count)
+            .map(|_| (0..dimensions).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn search_recall_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let vectors = random_vectors(&mut rng, 1000, 8);
+        let queries = random_vectors(&mut rng, 20, 8);
+
+        for metric in [
+            VectorMetric::L2,
+            VectorMetric::Cosine,
+            VectorMetric::InnerProduct,
+        ] {
+            let mut graph = Hnsw::new(metric, 8);
+            for (idx, vector) in vectors.iter().enumerate() {
+                assert_eq!(idx, graph.insert(vector).unwrap());
+            }
+
+            let mut found = 0;
+            for query in &queries {
+                let mut expected: Vec<_> = (0..vectors.len()).collect();
+                expected.sort_by(|a, b| {
+                    let a = metric.distance(query, &vectors[*a]);
+                    let b = metric.distance(query, &vectors[*b]);
+                    a.total_cmp(&b)
+                });
+                expected.truncate(10);
+
+                let got = graph.search(query, 10, 64).unwrap();
+                assert_eq!(10, got.len());
+                found += got.iter().filter(|node| expected.contains(node)).count();
+            }
+
+            let recall = found as f64 / (queries.len() * 10) as f64;
+            assert!(recall > 0.9, "recall for {metric:?}: {recall}");
+        }
+    }
+
+    #[test]
+    fn search_empty() {
+        let graph = Hnsw::new(VectorMetric::L2, 2);
+        assert!(graph.search(&[1.0, 2.0], 5, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn dimension_mismatch() {
+        let mut graph = Hnsw::new(VectorMetric::L2, 2);
+        graph.insert(&[1.0, 2.0]).unwrap();
+        graph.insert(&[1.0, 2.0, 3.0]).unwrap_err();
+        graph.search(&[1.0], 1, 1).unwrap_err();
+    }
+}
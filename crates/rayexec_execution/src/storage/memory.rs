@@ -1,15 +1,50 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use futures::future::BoxFuture;
 use parking_lot::Mutex;
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{ErrorKind, RayexecError, Result};
+use tracing::warn;
 
-use super::table_storage::{DataTable, DataTableScan, ProjectedScan, Projections, TableStorage};
+use super::hnsw::Hnsw;
+use super::table_storage::{
+    DataTable,
+    DataTableScan,
+    DataVersion,
+    IndexScan,
+    NearestNeighbors,
+    ProjectedScan,
+    Projections,
+    TableStatistics,
+    TableStorage,
+};
+use crate::arrays::array::{Array, ArrayData};
 use crate::arrays::batch::Batch;
-use crate::database::catalog_entry::CatalogEntry;
+use crate::arrays::compute::cast::array::cast_array;
+use crate::arrays::compute::cast::behavior::CastFailBehavior;
+use crate::arrays::datatype::{DataType, ListTypeMeta};
+use crate::arrays::executor::physical_type::{PhysicalF32, PhysicalUtf8};
+use crate::arrays::executor::scalar::UnaryExecutor;
+use crate::arrays::row::encoding::{ComparableColumn, ComparableRowEncoder};
+use crate::arrays::row::{OwnedScalarRow, ScalarRow};
+use crate::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use crate::arrays::selection::SelectionVector;
+use crate::database::catalog::{CatalogTx, TransactionParticipant};
+use crate::database::catalog_entry::{
+    CatalogEntry,
+    CatalogEntryInner,
+    IndexMethod,
+    TableEntry,
+    TableIndex,
+};
 use crate::execution::computed_batch::ComputedBatches;
 use crate::execution::operators::sink::PartitionSink;
 use crate::execution::operators::util::resizer::{BatchResizer, DEFAULT_TARGET_BATCH_SIZE};
+use crate::expr::comparison_expr::ComparisonOperator;
+use crate::functions::scalar::builtin::text_search::TextSearchConfig;
+use crate::logical::scan_filter::{ScanFilter, ScanFilterType};
+use crate::logical::statistics::StatisticsValue;
 
 #[derive(Debug, Default)]
 pub struct MemoryTableStorage {
@@ -24,8 +59,40 @@ struct TableKey {
     name: String,
 }
 
-impl TableStorage for MemoryTableStorage {
-    fn data_table(&self, schema: &str, ent: &CatalogEntry) -> Result<Box<dyn DataTable>> {
+impl MemoryTableStorage {
+    /// Create a new empty table.
+    pub(crate) fn create_table(
+        &self,
+        schema: &str,
+        name: &str,
+        layout: TableEntry,
+    ) -> Result<MemoryDataTable> {
+        let key = TableKey {
+            schema: schema.to_string(),
+            name: name.to_string(),
+        };
+
+        match self.tables.entry(key) {
+            scc::hash_index::Entry::Occupied(ent) => Err(RayexecError::new(format!(
+                "Duplicate physical table for entry: {:?}",
+                ent.key(),
+            ))),
+            scc::hash_index::Entry::Vacant(hash_ent) => {
+                let table = MemoryDataTable::new(format!("{schema}.{name}"), layout);
+                hash_ent.insert_entry(table.clone());
+                Ok(table)
+            }
+        }
+    }
+
+    /// Get the memory table for a table entry, accessed in the given
+    /// transaction.
+    pub(crate) fn memory_data_table(
+        &self,
+        tx: &CatalogTx,
+        schema: &str,
+        ent: &CatalogEntry,
+    ) -> Result<MemoryDataTable> {
         let key = TableKey {
             schema: schema.to_string(),
             name: ent.name.clone(),
@@ -37,7 +104,24 @@ impl TableStorage for MemoryTableStorage {
             ))
         })?;
 
-        Ok(Box::new(table.get().clone()))
+        // Reads and writes use the columns from the entry the query was bound
+        // with, even if the table has since been altered.
+        Ok(MemoryDataTable {
+            tx: tx.clone(),
+            layout: Arc::new(table_layout(ent)?),
+            ..table.get().clone()
+        })
+    }
+}
+
+impl TableStorage for MemoryTableStorage {
+    fn data_table(
+        &self,
+        tx: &CatalogTx,
+        schema: &str,
+        ent: &CatalogEntry,
+    ) -> Result<Box<dyn DataTable>> {
+        Ok(Box::new(self.memory_data_table(tx, schema, ent)?))
     }
 
     fn create_physical_table(
@@ -45,51 +129,962 @@ impl TableStorage for MemoryTableStorage {
         schema: &str,
         ent: &CatalogEntry,
     ) -> BoxFuture<'_, Result<Box<dyn DataTable>>> {
+        let result =
+            table_layout(ent).and_then(|layout| self.create_table(schema, &ent.name, layout));
+        Box::pin(async { Ok(Box::new(result?) as _) })
+    }
+
+    fn drop_physical_table(&self, schema: &str, ent: &CatalogEntry) -> BoxFuture<'_, Result<()>> {
         let key = TableKey {
             schema: schema.to_string(),
             name: ent.name.clone(),
         };
 
-        Box::pin(async {
-            match self.tables.entry(key) {
-                scc::hash_index::Entry::Occupied(ent) => Err(RayexecError::new(format!(
-                    "Duplicate physical table for entry: {:?}",
-                    ent.key(),
-                ))),
-                scc::hash_index::Entry::Vacant(hash_ent) => {
-                    let table = MemoryDataTable::default();
-                    hash_ent.insert_entry(table.clone());
-                    Ok(Box::new(table) as _)
-                }
+        Box::pin(async move {
+            let table = self.tables.get(&key).map(|ent| ent.get().clone());
+            if !self.tables.remove(&key) {
+                return Err(RayexecError::new(format!(
+                    "Missing physical memory table for entry: {key:?}. Cannot drop table.",
+                )));
+            }
+            if let Some(table) = table {
+                // Transactions with writes to this table should fail to
+                // commit.
+                table.data.lock().dropped = true;
             }
+            Ok(())
         })
     }
 
-    fn drop_physical_table(&self, schema: &str, ent: &CatalogEntry) -> BoxFuture<'_, Result<()>> {
+    fn alter_physical_table(&self, schema: &str, ent: &CatalogEntry) -> BoxFuture<'_, Result<()>> {
         let key = TableKey {
             schema: schema.to_string(),
             name: ent.name.clone(),
         };
 
+        let layout = table_layout(ent);
+
         Box::pin(async move {
-            if !self.tables.remove(&key) {
-                return Err(RayexecError::new(format!(
-                    "Missing physical memory table for entry: {key:?}. Cannot drop table.",
-                )));
-            }
+            let layout = layout?;
+            // Batches are stored with their column ids, so existing data
+            // doesn't need to be rewritten.
+            let table = self.tables.get(&key).ok_or_else(|| {
+                RayexecError::new(format!(
+                    "Missing physical memory table for entry: {key:?}. Cannot alter table.",
+                ))
+            })?;
+            table.get().data.lock().sync_indexes(&layout)?;
+            // Cached results for the table no longer match its columns.
+            table
+                .get()
+                .version
+                .store(next_data_version(), Ordering::Relaxed);
             Ok(())
         })
     }
 }
 
-#[derive(Debug, Clone, Default)]
+/// Get the column layout from a table entry.
+fn table_layout(ent: &CatalogEntry) -> Result<TableEntry> {
+    match &ent.entry {
+        CatalogEntryInner::Table(table) => Ok(table.clone()),
+        other => Err(RayexecError::new(format!(
+            "Unexpected catalog entry for physical table: {other:?}"
+        ))),
+    }
+}
+
+/// Source for data versions of tables in memory and on disk.
+///
+/// Shared across all tables so that a dropped and recreated table never reuses
+/// a version.
+static NEXT_DATA_VERSION: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn next_data_version() -> u64 {
+    NEXT_DATA_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A batch stored in a memory table.
+#[derive(Debug, Clone)]
+pub(crate) struct StoredBatch {
+    /// Ids of the columns in the batch, from the table entry the batch was
+    /// written with.
+    pub column_ids: Arc<[usize]>,
+    pub batch: Batch,
+}
+
+/// Data for a memory table.
+#[derive(Debug, Default)]
+struct TableData {
+    /// Index over the primary key, if the table has one.
+    primary_key: Option<PrimaryKeyIndex>,
+    /// Secondary indexes, keyed by index name.
+    indexes: HashMap<String, SecondaryIndex>,
+    /// Committed batches along with the timestamp they were committed at.
+    ///
+    /// Ordered by commit timestamp.
+    committed: Vec<(u64, StoredBatch)>,
+    /// Zone maps for each committed batch, in the same order as the batches.
+    zone_maps: Vec<ZoneMap>,
+    /// Timestamp of the most recent commit that wrote to this table.
+    last_commit: u64,
+    /// Batches inserted by explicit transactions that haven't been committed
+    /// yet, keyed by transaction id.
+    pending: HashMap<u64, Vec<StoredBatch>>,
+    /// If the table has been dropped.
+    dropped: bool,
+}
+
+impl TableData {
+    /// Get all batches visible to the transaction, including the
+    /// transaction's own uncommitted batches.
+    fn visible_batches(&self, tx: &CatalogTx) -> Vec<StoredBatch> {
+        self.visible_batches_matching(tx, &[])
+    }
+
+    /// Get batches visible to the transaction, skipping committed batches
+    /// whose zone maps show they can't match the predicates.
+    ///
+    /// The transaction's own uncommitted batches are always included.
+    fn visible_batches_matching(
+        &self,
+        tx: &CatalogTx,
+        predicates: &[ZonePredicate],
+    ) -> Vec<StoredBatch> {
+        let mut batches: Vec<_> = self
+            .committed
+            .iter()
+            .zip(&self.zone_maps)
+            .filter(|((ts, _), zone_map)| tx.is_visible(*ts) && zone_map.may_match(predicates))
+            .map(|((_, batch), _)| batch.clone())
+            .collect();
+
+        if let Some(pending) = tx.id().and_then(|id| self.pending.get(&id)) {
+            batches.extend(pending.iter().cloned());
+        }
+
+        batches
+    }
+
+    /// Add newly committed batches, updating zone maps and secondary indexes.
+    ///
+    /// An index that fails to update is removed. Scans that would have used
+    /// it read the whole table instead.
+    fn push_committed(&mut self, commit_ts: u64, batches: Vec<StoredBatch>) {
+        for batch in batches {
+            let batch_idx = self.committed.len();
+            self.indexes
+                .retain(|name, index| match index.insert(batch_idx, &batch) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        warn!(%e, %name, "failed to update index, removing it");
+                        false
+                    }
+                });
+            let zone_map = ZoneMap::new(&batch).unwrap_or_else(|e| {
+                warn!(%e, "failed to compute zone map for batch");
+                ZoneMap::default()
+            });
+            self.zone_maps.push(zone_map);
+            self.committed.push((commit_ts, batch));
+        }
+    }
+
+    /// Build indexes that have been added to the table's entry, and remove
+    /// indexes that are no longer part of it.
+    fn sync_indexes(&mut self, layout: &TableEntry) -> Result<()> {
+        self.indexes.retain(|name, index| {
+            layout
+                .indexes
+                .iter()
+                .any(|i| &i.name == name && i.column_ids == index.column_ids())
+        });
+
+        for index in &layout.indexes {
+            if self.indexes.contains_key(&index.name) {
+                continue;
+            }
+            let mut built = SecondaryIndex::new(layout, index);
+            for (batch_idx, (_, stored)) in self.committed.iter().enumerate() {
+                built.insert(batch_idx, stored)?;
+            }
+            self.indexes.insert(index.name.clone(), built);
+        }
+
+        Ok(())
+    }
+
+    /// If the data visible to the transaction may differ from the latest
+    /// committed data.
+    fn differs_from_latest(&self, tx: &CatalogTx) -> bool {
+        match tx.id() {
+            Some(id) => !tx.is_visible(self.last_commit) || self.pending.contains_key(&id),
+            None => false,
+        }
+    }
+}
+
+/// Hash index over the primary key of a table, used to reject duplicate keys.
+#[derive(Debug)]
+pub(crate) struct PrimaryKeyIndex {
+    /// Ids of the key columns.
+    pub column_ids: Vec<usize>,
+    /// Keys for all committed rows.
+    pub committed: HashSet<OwnedScalarRow>,
+    /// Keys for rows inserted by explicit transactions that haven't been
+    /// committed yet, keyed by transaction id.
+    pending: HashMap<u64, HashSet<OwnedScalarRow>>,
+}
+
+impl PrimaryKeyIndex {
+    pub fn new(column_ids: Vec<usize>) -> Self {
+        PrimaryKeyIndex {
+            column_ids,
+            committed: HashSet::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Get the keys for new rows, erroring if a key already exists or is
+    /// repeated in the new rows.
+    ///
+    /// Keys from other transactions' uncommitted writes aren't checked, those
+    /// transactions will fail to commit instead.
+    pub fn new_keys(
+        &self,
+        table: &str,
+        tx_id: Option<u64>,
+        batches: &[StoredBatch],
+    ) -> Result<HashSet<OwnedScalarRow>> {
+        let pending = tx_id.and_then(|id| self.pending.get(&id));
+        let mut keys = HashSet::new();
+
+        for stored in batches {
+            let arrays = self
+                .column_ids
+                .iter()
+                .map(|id| {
+                    let idx = stored
+                        .column_ids
+                        .iter()
+                        .position(|stored| stored == id)
+                        .ok_or_else(|| {
+                            RayexecError::new(format!(
+                                "Missing primary key column with id {id} for table '{table}'"
+                            ))
+                        })?;
+                    Ok(&stored.batch.columns()[idx])
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            for row in 0..stored.batch.num_rows() {
+                let key = ScalarRow::try_new_from_arrays(&arrays, row)?.into_owned();
+                if key.iter().any(|v| v == &ScalarValue::Null) {
+                    return Err(RayexecError::new(format!(
+                        "NULL value in primary key for table '{table}'"
+                    ))
+                    .with_kind(ErrorKind::NotNullViolation));
+                }
+
+                let exists = self.committed.contains(&key)
+                    || pending.is_some_and(|pending| pending.contains(&key));
+                if exists || keys.contains(&key) {
+                    let key = key
+                        .iter()
+                        .map(|v| v.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    return Err(RayexecError::new(format!(
+                        "Duplicate key ({key}) violates primary key for table '{table}'"
+                    ))
+                    .with_kind(ErrorKind::UniqueViolation));
+                }
+
+                keys.insert(key);
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+/// Key in a secondary index.
+///
+/// Each column is encoded on its own so that keys compare column by column.
+type IndexKey = Vec<Vec<u8>>;
+
+/// Secondary index over the columns of a memory table.
+///
+/// Only committed rows are indexed, index scans read a transaction's own
+/// uncommitted batches in full.
+///
+/// Indexes store row locations as the position of the batch in the table's
+/// committed batches and the row within that batch.
+#[derive(Debug)]
+enum SecondaryIndex {
+    Ordered(OrderedIndex),
+    FullText(FullTextIndex),
+    /// Boxed since the graph is much larger than the other indexes.
+    Vector(Box<VectorIndex>),
+}
+
+impl SecondaryIndex {
+    fn new(layout: &TableEntry, index: &TableIndex) -> Self {
+        let defaults = index
+            .column_ids
+            .iter()
+            .map(|id| {
+                layout
+                    .column_ids
+                    .iter()
+                    .position(|col| col == id)
+                    .map(|idx| layout.column_defaults[idx].clone())
+                    .unwrap_or(ScalarValue::Null)
+            })
+            .collect();
+
+        match index.method {
+            IndexMethod::BTree => SecondaryIndex::Ordered(OrderedIndex {
+                column_ids: index.column_ids.clone(),
+                defaults,
+                entries: BTreeMap::new(),
+            }),
+            IndexMethod::FullText => SecondaryIndex::FullText(FullTextIndex {
+                column_ids: index.column_ids.clone(),
+                defaults,
+                entries: HashMap::new(),
+            }),
+            IndexMethod::Hnsw(metric) => {
+                // Dimensions are checked when creating the index, a mismatch
+                // here causes inserts to fail and the index to be removed.
+                let dimensions = layout
+                    .column_ids
+                    .iter()
+                    .position(|col| Some(col) == index.column_ids.first())
+                    .and_then(|idx| match &layout.columns[idx].datatype {
+                        DataType::List(meta) => meta.size,
+                        _ => None,
+                    })
+                    .unwrap_or(0);
+                SecondaryIndex::Vector(Box::new(VectorIndex {
+                    column_ids: index.column_ids.clone(),
+                    defaults,
+                    graph: Hnsw::new(metric, dimensions),
+                    locations: Vec::new(),
+                    nulls: Vec::new(),
+                }))
+            }
+        }
+    }
+
+    fn column_ids(&self) -> &[usize] {
+        match self {
+            Self::Ordered(index) => &index.column_ids,
+            Self::FullText(index) => &index.column_ids,
+            Self::Vector(index) => &index.column_ids,
+        }
+    }
+
+    /// Add the rows from a committed batch to the index.
+    fn insert(&mut self, batch_idx: usize, stored: &StoredBatch) -> Result<()> {
+        match self {
+            Self::Ordered(index) => index.insert(batch_idx, stored),
+            Self::FullText(index) => index.insert(batch_idx, stored),
+            Self::Vector(index) => index.insert(batch_idx, stored),
+        }
+    }
+
+    /// Find the locations of rows matching an index scan, ordered by
+    /// location.
+    fn find(&self, scan: &IndexScan) -> Result<Vec<(usize, usize)>> {
+        match self {
+            Self::Ordered(index) => index.find(scan),
+            Self::FullText(index) => Ok(index.find(&scan.terms)),
+            Self::Vector(index) => match &scan.nearest {
+                Some(nearest) => index.find(nearest),
+                None => Err(RayexecError::new(
+                    "Vector index scan requires a query vector",
+                )),
+            },
+        }
+    }
+}
+
+/// Get an indexed column from a stored batch, using the column's default if
+/// the batch was written before the column was added.
+fn indexed_column(stored: &StoredBatch, id: usize, default: &OwnedScalarValue) -> Result<Array> {
+    match stored.column_ids.iter().position(|stored| *stored == id) {
+        Some(idx) => Ok(stored.batch.columns()[idx].clone()),
+        None => default.as_array(stored.batch.num_rows()),
+    }
+}
+
+/// Ordered index over one or more columns.
+#[derive(Debug)]
+struct OrderedIndex {
+    /// Ids of the indexed columns, in key order.
+    column_ids: Vec<usize>,
+    /// Values of the indexed columns for batches written before the columns
+    /// were added.
+    defaults: Vec<OwnedScalarValue>,
+    /// Locations of rows for each key.
+    entries: BTreeMap<IndexKey, Vec<(usize, usize)>>,
+}
+
+impl OrderedIndex {
+    fn insert(&mut self, batch_idx: usize, stored: &StoredBatch) -> Result<()> {
+        let num_rows = stored.batch.num_rows();
+        let mut keys: Vec<IndexKey> = vec![Vec::with_capacity(self.column_ids.len()); num_rows];
+
+        for (id, default) in self.column_ids.iter().zip(&self.defaults) {
+            let array = indexed_column(stored, *id, default)?;
+            let rows = index_key_encoder().encode(&[&array])?;
+            for (key, row) in keys.iter_mut().zip(rows.iter()) {
+                key.push(row.data().to_vec());
+            }
+        }
+
+        for (row, key) in keys.into_iter().enumerate() {
+            self.entries.entry(key).or_default().push((batch_idx, row));
+        }
+
+        Ok(())
+    }
+
+    fn find(&self, scan: &IndexScan) -> Result<Vec<(usize, usize)>> {
+        let equal = scan
+            .equal
+            .iter()
+            .map(encode_index_value)
+            .collect::<Result<Vec<_>>>()?;
+        let lower = match &scan.lower {
+            Some(bound) => Some((encode_index_value(&bound.value)?, bound.inclusive)),
+            None => None,
+        };
+        let upper = match &scan.upper {
+            Some(bound) => Some((encode_index_value(&bound.value)?, bound.inclusive)),
+            None => None,
+        };
+        let null = encode_index_value(&ScalarValue::Null)?;
+
+        let mut start = equal.clone();
+        if let Some((lower, _)) = &lower {
+            start.push(lower.clone());
+        }
+
+        let mut locations = Vec::new();
+        for (key, rows) in self.entries.range(start..) {
+            if key[..equal.len()] != equal[..] {
+                break;
+            }
+            if let Some(value) = key.get(equal.len()) {
+                if lower.is_some() || upper.is_some() {
+                    // NULLs are ordered after all other values, and never
+                    // match a bound.
+                    if value == &null {
+                        break;
+                    }
+                }
+                if let Some((lower, false)) = &lower {
+                    if value == lower {
+                        continue;
+                    }
+                }
+                if let Some((upper, inclusive)) = &upper {
+                    match value.cmp(upper) {
+                        std::cmp::Ordering::Greater => break,
+                        std::cmp::Ordering::Equal if !inclusive => break,
+                        _ => (),
+                    }
+                }
+            }
+            locations.extend_from_slice(rows);
+        }
+
+        locations.sort_unstable();
+        Ok(locations)
+    }
+}
+
+/// Inverted index from terms to the rows containing them.
+///
+/// Text is analyzed using the 'simple' text search config.
+#[derive(Debug)]
+struct FullTextIndex {
+    /// Id of the indexed column. Always a single column.
+    column_ids: Vec<usize>,
+    /// Value of the indexed column for batches written before the column was
+    /// added.
+    defaults: Vec<OwnedScalarValue>,
+    /// Locations of rows containing each term, in location order.
+    entries: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl FullTextIndex {
+    fn insert(&mut self, batch_idx: usize, stored: &StoredBatch) -> Result<()> {
+        let array = indexed_column(stored, self.column_ids[0], &self.defaults[0])?;
+        let config = TextSearchConfig::Simple;
+
+        // Batches are inserted in order, keeping each term's locations
+        // sorted.
+        UnaryExecutor::for_each::<PhysicalUtf8, _>(&array, |row, text| {
+            if let Some(text) = text {
+                for term in config.term_set(text) {
+                    self.entries.entry(term).or_default().push((batch_idx, row));
+                }
+            }
+        })
+    }
+
+    /// Find the locations of rows containing all terms.
+    fn find(&self, terms: &[String]) -> Vec<(usize, usize)> {
+        let mut lists = Vec::with_capacity(terms.len());
+        for term in terms {
+            match self.entries.get(term) {
+                Some(locations) => lists.push(locations),
+                None => return Vec::new(),
+            }
+        }
+
+        // Intersect starting from the term with the fewest rows.
+        lists.sort_by_key(|locations| locations.len());
+        let (first, rest) = match lists.split_first() {
+            Some(split) => split,
+            None => return Vec::new(),
+        };
+
+        first
+            .iter()
+            .filter(|loc| rest.iter().all(|other| other.binary_search(loc).is_ok()))
+            .copied()
+            .collect()
+    }
+}
+
+/// Minimum number of candidates kept when searching a vector index.
+///
+/// Searching wider than the number of rows needed improves recall, and leaves
+/// rows to spare for ones not visible to the transaction.
+const MIN_VECTOR_SEARCH_EF: usize = 40;
+
+/// HNSW graph over a fixed-size list column for nearest neighbor searches.
+///
+/// Rows with NULL vectors, or vectors containing NULLs, aren't part of the
+/// graph and are returned by every search. This leaves handling them to the
+/// order above the scan.
+#[derive(Debug)]
+struct VectorIndex {
+    /// Id of the indexed column. Always a single column.
+    column_ids: Vec<usize>,
+    /// Value of the indexed column for batches written before the column was
+    /// added.
+    defaults: Vec<OwnedScalarValue>,
+    graph: Hnsw,
+    /// Location of the row for each node in the graph.
+    locations: Vec<(usize, usize)>,
+    /// Locations of rows not in the graph.
+    nulls: Vec<(usize, usize)>,
+}
+
+impl VectorIndex {
+    fn insert(&mut self, batch_idx: usize, stored: &StoredBatch) -> Result<()> {
+        let array = indexed_column(stored, self.column_ids[0], &self.defaults[0])?;
+        let vectors = list_vectors(&array, self.graph.dimensions())?;
+
+        for (row, vector) in vectors.into_iter().enumerate() {
+            match vector {
+                Some(vector) => {
+                    self.graph.insert(&vector)?;
+                    self.locations.push((batch_idx, row));
+                }
+                None => self.nulls.push((batch_idx, row)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the locations of rows approximately nearest to the query, along
+    /// with all rows having NULL vectors.
+    fn find(&self, nearest: &NearestNeighbors) -> Result<Vec<(usize, usize)>> {
+        let query = query_vector(&nearest.query, self.graph.dimensions())?;
+        let ef = nearest.k.max(MIN_VECTOR_SEARCH_EF);
+
+        let mut locations: Vec<_> = self
+            .graph
+            .search(&query, ef, ef)?
+            .into_iter()
+            .map(|node| self.locations[node])
+            .collect();
+        locations.extend_from_slice(&self.nulls);
+
+        locations.sort_unstable();
+        Ok(locations)
+    }
+}
+
+/// Read a list array as vectors of floats with the given number of
+/// dimensions.
+///
+/// NULL lists and lists containing NULLs produce None.
+fn list_vectors(array: &Array, dimensions: usize) -> Result<Vec<Option<Vec<f32>>>> {
+    let datatype = DataType::List(ListTypeMeta::new_fixed_size(DataType::Float32, dimensions));
+    let array = cast_array(array, datatype, CastFailBehavior::Error)?.unselect()?;
+    let list = match array.array_data() {
+        ArrayData::List(list) => list,
+        _ => return Err(RayexecError::new("Expected list array data")),
+    };
+
+    // Arrays of only NULL lists may have an untyped, empty child array.
+    let mut elements = vec![None; list.array.logical_len()];
+    if !elements.is_empty() {
+        UnaryExecutor::for_each::<PhysicalF32, _>(&list.array, |idx, v| elements[idx] = v)?;
+    }
+
+    let vectors = list
+        .metadata
+        .as_slice()
+        .iter()
+        .enumerate()
+        .map(|(row, meta)| {
+            if array.is_valid(row) != Some(true) {
+                return None;
+            }
+            let start = meta.offset as usize;
+            elements[start..start + meta.len as usize]
+                .iter()
+                .copied()
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect();
+
+    Ok(vectors)
+}
+
+/// Get the query vector for a nearest neighbor search, erroring if it's NULL
+/// or doesn't have the right number of dimensions.
+fn query_vector(query: &OwnedScalarValue, dimensions: usize) -> Result<Vec<f32>> {
+    list_vectors(&query.as_array(1)?, dimensions)?
+        .pop()
+        .flatten()
+        .ok_or_else(|| RayexecError::new("Query vector for nearest neighbor search is NULL"))
+}
+
+/// Min/max statistics for the columns of a committed batch.
+///
+/// Used to skip batches during scans when a filter can't match any of the
+/// batch's rows.
+#[derive(Debug, Default)]
+struct ZoneMap {
+    num_rows: usize,
+    /// Statistics keyed by column id.
+    ///
+    /// Columns that were added after the batch was written, or with types that
+    /// can't be compared using their encoded keys, are omitted.
+    columns: HashMap<usize, ColumnZone>,
+}
+
+/// Statistics for a single column in a batch.
+#[derive(Debug)]
+struct ColumnZone {
+    /// Smallest non-null value, encoded as an index key.
+    ///
+    /// Empty if all values are NULL.
+    min: Vec<u8>,
+    /// Largest non-null value, encoded as an index key.
+    max: Vec<u8>,
+    null_count: usize,
+}
+
+/// A comparison between a column and a constant encoded as an index key.
+#[derive(Debug)]
+struct ZonePredicate {
+    column_id: usize,
+    op: ComparisonOperator,
+    value: Vec<u8>,
+}
+
+impl ZoneMap {
+    fn new(stored: &StoredBatch) -> Result<Self> {
+        let null = encode_index_value(&ScalarValue::Null)?;
+        let mut columns = HashMap::new();
+
+        for (id, array) in stored.column_ids.iter().zip(stored.batch.columns()) {
+            if !has_ordered_keys(array.datatype()) {
+                continue;
+            }
+
+            let rows = index_key_encoder().encode(&[array])?;
+            let mut min_max: Option<(&[u8], &[u8])> = None;
+            let mut null_count = 0;
+            for row in rows.iter() {
+                let value = row.data();
+                if value == null.as_slice() {
+                    null_count += 1;
+                    continue;
+                }
+                min_max = match min_max {
+                    Some((min, max)) => Some((min.min(value), max.max(value))),
+                    None => Some((value, value)),
+                };
+            }
+
+            let (min, max) = min_max.unwrap_or_default();
+            columns.insert(
+                *id,
+                ColumnZone {
+                    min: min.to_vec(),
+                    max: max.to_vec(),
+                    null_count,
+                },
+            );
+        }
+
+        Ok(ZoneMap {
+            num_rows: stored.batch.num_rows(),
+            columns,
+        })
+    }
+
+    /// Check if any row in the batch may match all predicates.
+    fn may_match(&self, predicates: &[ZonePredicate]) -> bool {
+        predicates.iter().all(|pred| {
+            let zone = match self.columns.get(&pred.column_id) {
+                Some(zone) => zone,
+                None => return true,
+            };
+
+            // Comparisons with NULL never match.
+            if zone.null_count == self.num_rows {
+                return false;
+            }
+
+            let (min, max, value) = (&zone.min, &zone.max, &pred.value);
+            match pred.op {
+                ComparisonOperator::Eq => min <= value && value <= max,
+                ComparisonOperator::NotEq => !(min == value && max == value),
+                ComparisonOperator::Lt => min < value,
+                ComparisonOperator::LtEq => min <= value,
+                ComparisonOperator::Gt => max > value,
+                ComparisonOperator::GtEq => max >= value,
+            }
+        })
+    }
+}
+
+/// If comparing encoded index keys gives the same result as comparing the
+/// values themselves.
+///
+/// Floats and intervals have equal values with different encodings, and
+/// booleans are encoded in reverse order.
+fn has_ordered_keys(datatype: &DataType) -> bool {
+    matches!(
+        datatype,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Int128
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::UInt128
+            | DataType::Decimal64(_)
+            | DataType::Decimal128(_)
+            | DataType::Timestamp(_)
+            | DataType::Date32
+            | DataType::Date64
+            | DataType::Utf8
+            | DataType::Binary
+    )
+}
+
+fn index_key_encoder() -> ComparableRowEncoder {
+    ComparableRowEncoder {
+        columns: vec![ComparableColumn {
+            desc: false,
+            nulls_first: false,
+        }],
+    }
+}
+
+/// Encode a single value as part of an index key.
+fn encode_index_value(value: &OwnedScalarValue) -> Result<Vec<u8>> {
+    let array = value.as_array(1)?;
+    let rows = index_key_encoder().encode(&[&array])?;
+    let row = rows
+        .first()
+        .ok_or_else(|| RayexecError::new("Missing encoded index value"))?;
+    Ok(row.data().to_vec())
+}
+
+#[derive(Debug, Clone)]
 pub struct MemoryDataTable {
-    data: Arc<Mutex<Vec<Batch>>>,
+    /// Name of the table, used in error messages.
+    name: Arc<str>,
+    data: Arc<Mutex<TableData>>,
+    /// Version of the data, updated on every commit.
+    version: Arc<AtomicU64>,
+    /// Transaction the table is being accessed in.
+    tx: CatalogTx,
+    /// Columns the table is being accessed with.
+    layout: Arc<TableEntry>,
 }
 
-impl DataTable for MemoryDataTable {
-    fn scan(
+impl MemoryDataTable {
+    fn new(name: impl Into<Arc<str>>, layout: TableEntry) -> Self {
+        let data = TableData {
+            primary_key: (!layout.primary_key.is_empty())
+                .then(|| PrimaryKeyIndex::new(layout.primary_key.clone())),
+            indexes: layout
+                .indexes
+                .iter()
+                .map(|index| (index.name.clone(), SecondaryIndex::new(&layout, index)))
+                .collect(),
+            ..Default::default()
+        };
+
+        MemoryDataTable {
+            name: name.into(),
+            data: Arc::new(Mutex::new(data)),
+            version: Arc::new(AtomicU64::new(next_data_version())),
+            tx: CatalogTx::new(),
+            layout: Arc::new(layout),
+        }
+    }
+
+    /// Append batches, checking them against the primary key.
+    ///
+    /// Batches are buffered until commit if the table is being accessed in an
+    /// explicit transaction.
+    fn append(&self, batches: Vec<StoredBatch>) -> Result<()> {
+        let tx_id = match self.tx.id() {
+            Some(tx_id) => tx_id,
+            None => {
+                CatalogTx::auto_commit(|commit_ts| {
+                    let mut data = self.data.lock();
+                    let keys = match &data.primary_key {
+                        Some(index) => Some(index.new_keys(&self.name, None, &batches)?),
+                        None => None,
+                    };
+                    if let (Some(index), Some(keys)) = (&mut data.primary_key, keys) {
+                        index.committed.extend(keys);
+                    }
+                    data.push_committed(commit_ts, batches);
+                    data.last_commit = commit_ts;
+                    Ok::<_, RayexecError>(())
+                })?;
+                // Bumped after the new data is visible. Results cached for the
+                // old version may include the new data, but they'll never be
+                // used again.
+                self.version.store(next_data_version(), Ordering::Relaxed);
+                return Ok(());
+            }
+        };
+
+        // Explicit transaction, buffer until commit.
+        let mut data = self.data.lock();
+        let keys = match &data.primary_key {
+            Some(index) => Some(index.new_keys(&self.name, Some(tx_id), &batches)?),
+            None => None,
+        };
+        if !data.pending.contains_key(&tx_id) {
+            // Errors if the transaction has already finished, which avoids
+            // leaving behind writes that will never be committed.
+            self.tx.register(Arc::new(MemoryTableWrites {
+                tx_id,
+                // Not bound to the transaction, the transaction holds on to
+                // the participant.
+                table: self.with_tx(CatalogTx::new()),
+            }))?;
+        }
+        if let (Some(index), Some(keys)) = (&mut data.primary_key, keys) {
+            index.pending.entry(tx_id).or_default().extend(keys);
+        }
+        data.pending.entry(tx_id).or_default().extend(batches);
+
+        Ok(())
+    }
+
+    /// Get a handle to this table for use in the given transaction.
+    fn with_tx(&self, tx: CatalogTx) -> Self {
+        MemoryDataTable { tx, ..self.clone() }
+    }
+
+    /// Read a stored batch using the table's current columns.
+    ///
+    /// Columns added after the batch was written are filled in with their
+    /// default values, and dropped columns are skipped.
+    fn read_batch(&self, stored: StoredBatch) -> Result<Batch> {
+        if *stored.column_ids == self.layout.column_ids {
+            return Ok(stored.batch);
+        }
+
+        let num_rows = stored.batch.num_rows();
+        let arrays = self
+            .layout
+            .column_ids
+            .iter()
+            .zip(&self.layout.columns)
+            .zip(&self.layout.column_defaults)
+            .map(|((id, field), default)| {
+                match stored.column_ids.iter().position(|stored| stored == id) {
+                    Some(idx) => Ok(stored.batch.columns()[idx].clone()),
+                    None => match default {
+                        ScalarValue::Null => {
+                            Array::new_typed_null_array(field.datatype.clone(), num_rows)
+                        }
+                        value => value.as_array(num_rows),
+                    },
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Batch::try_new(arrays)
+    }
+
+    /// Type of the first column of an index.
+    fn index_column_type(&self, index: &TableIndex) -> Option<&DataType> {
+        let id = index.column_ids.first()?;
+        let idx = self.layout.column_ids.iter().position(|col| col == id)?;
+        Some(&self.layout.columns[idx].datatype)
+    }
+
+    /// Convert scan filters into predicates that can be checked against zone
+    /// maps.
+    ///
+    /// Filters that can't be checked using zone maps are skipped.
+    fn zone_predicates(&self, filters: &[ScanFilter]) -> Result<Vec<ZonePredicate>> {
+        let mut predicates = Vec::new();
+        for filter in filters {
+            let ScanFilterType::ConstComparison { op, constant } = &filter.filter;
+            let (column_id, field) = match (
+                self.layout.column_ids.get(filter.column),
+                self.layout.columns.get(filter.column),
+            ) {
+                (Some(id), Some(field)) => (*id, field),
+                _ => continue,
+            };
+
+            if !has_ordered_keys(&field.datatype) || constant.datatype() != field.datatype {
+                continue;
+            }
+
+            predicates.push(ZonePredicate {
+                column_id,
+                op: *op,
+                value: encode_index_value(constant)?,
+            });
+        }
+
+        Ok(predicates)
+    }
+
+    /// Create scans producing the given batches, spread across partitions.
+    fn scans_for_batches(
         &self,
+        batches: Vec<StoredBatch>,
         projections: Projections,
         num_partitions: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>> {
@@ -97,13 +1092,10 @@ impl DataTable for MemoryDataTable {
             .map(|_| MemoryDataTableScan { data: Vec::new() })
             .collect();
 
-        let data = {
-            let data = self.data.lock();
-            data.clone()
-        };
-
-        for (idx, batch) in data.into_iter().enumerate() {
-            scans[idx % num_partitions].data.push(batch);
+        for (idx, batch) in batches.into_iter().enumerate() {
+            scans[idx % num_partitions]
+                .data
+                .push(self.read_batch(batch)?);
         }
 
         Ok(scans
@@ -111,19 +1103,207 @@ impl DataTable for MemoryDataTable {
             .map(|scan| Box::new(ProjectedScan::new(scan, projections.clone())) as Box<_>)
             .collect())
     }
+}
+
+impl DataTable for MemoryDataTable {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        _batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let batches = {
+            let data = self.data.lock();
+            data.visible_batches(&self.tx)
+        };
+
+        self.scans_for_batches(batches, projections, num_partitions)
+    }
+
+    fn scan_filtered(
+        &self,
+        projections: Projections,
+        filters: &[ScanFilter],
+        num_partitions: usize,
+        _batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let predicates = self.zone_predicates(filters)?;
+        let batches = {
+            let data = self.data.lock();
+            data.visible_batches_matching(&self.tx, &predicates)
+        };
+
+        self.scans_for_batches(batches, projections, num_partitions)
+    }
+
+    fn supports_index_scan(&self, scan: &IndexScan) -> bool {
+        let index = match self.layout.indexes.iter().find(|i| i.name == scan.index) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        let usable = match index.method {
+            IndexMethod::BTree => {
+                // Index scans find rows by comparing encoded keys.
+                let num_conditions =
+                    scan.equal.len() + usize::from(scan.lower.is_some() || scan.upper.is_some());
+                scan.terms.is_empty()
+                    && index.column_ids.iter().take(num_conditions).all(|id| {
+                        self.layout
+                            .column_ids
+                            .iter()
+                            .position(|col| col == id)
+                            .map(|idx| has_ordered_keys(&self.layout.columns[idx].datatype))
+                            .unwrap_or(false)
+                    })
+            }
+            IndexMethod::FullText => !scan.terms.is_empty(),
+            // Queries that can't be searched for fall back to a full scan,
+            // producing the same errors as without the index.
+            IndexMethod::Hnsw(_) => match (&scan.nearest, self.index_column_type(index)) {
+                (Some(nearest), Some(DataType::List(meta))) => meta
+                    .size
+                    .map(|size| query_vector(&nearest.query, size).is_ok())
+                    .unwrap_or(false),
+                _ => false,
+            },
+        };
+
+        usable && self.data.lock().indexes.contains_key(&scan.index)
+    }
+
+    fn scan_index(
+        &self,
+        projections: Projections,
+        scan: &IndexScan,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let batches = {
+            let data = self.data.lock();
+            let index = match data.indexes.get(&scan.index) {
+                Some(index) => index,
+                None => {
+                    // Index was removed after planning, rows get filtered
+                    // above the scan anyways.
+                    drop(data);
+                    return self.scan(projections, num_partitions, batch_size);
+                }
+            };
+
+            let locations = index.find(scan)?;
+            let mut batches = Vec::new();
+            for rows in locations.chunk_by(|a, b| a.0 == b.0) {
+                let (commit_ts, stored) = &data.committed[rows[0].0];
+                if !self.tx.is_visible(*commit_ts) {
+                    continue;
+                }
+                let selection: SelectionVector = rows.iter().map(|(_, row)| *row).collect();
+                batches.push(StoredBatch {
+                    column_ids: stored.column_ids.clone(),
+                    batch: stored.batch.select(Arc::new(selection)),
+                });
+            }
+
+            // The transaction's own writes aren't indexed until commit.
+            if let Some(pending) = self.tx.id().and_then(|id| data.pending.get(&id)) {
+                batches.extend(pending.iter().cloned());
+            }
+
+            batches
+        };
+
+        self.scans_for_batches(batches, projections, num_partitions)
+    }
 
     fn insert(&self, input_partitions: usize) -> Result<Vec<Box<dyn PartitionSink>>> {
-        let inserts: Vec<_> = (0..input_partitions)
+        Ok((0..input_partitions)
             .map(|_| {
                 Box::new(MemoryDataTableInsert {
                     resizer: BatchResizer::new(DEFAULT_TARGET_BATCH_SIZE),
                     collected: Vec::new(),
-                    data: self.data.clone(),
+                    table: self.clone(),
                 }) as _
             })
-            .collect();
+            .collect())
+    }
 
-        Ok(inserts)
+    fn data_version(&self) -> DataVersion {
+        if self.data.lock().differs_from_latest(&self.tx) {
+            // Reading from an older snapshot, or reading uncommitted data.
+            // Either way, results shouldn't be shared with other
+            // transactions.
+            return DataVersion::Volatile;
+        }
+        DataVersion::Version(self.version.load(Ordering::Relaxed))
+    }
+
+    fn statistics(&self) -> BoxFuture<'_, Result<TableStatistics>> {
+        let num_rows = self
+            .data
+            .lock()
+            .visible_batches(&self.tx)
+            .iter()
+            .map(|stored| stored.batch.num_rows())
+            .sum();
+        Box::pin(async move {
+            Ok(TableStatistics {
+                num_rows: StatisticsValue::Exact(num_rows),
+                ..Default::default()
+            })
+        })
+    }
+}
+
+/// Writes to a memory table buffered in an explicit transaction.
+#[derive(Debug)]
+struct MemoryTableWrites {
+    tx_id: u64,
+    table: MemoryDataTable,
+}
+
+impl TransactionParticipant for MemoryTableWrites {
+    fn check_commit(&self, snapshot: u64) -> Result<()> {
+        let data = self.table.data.lock();
+        if data.dropped {
+            return Err(RayexecError::new(format!(
+                "Table '{}' was dropped while the transaction was writing to it",
+                self.table.name
+            ))
+            .with_kind(ErrorKind::SerializationFailure));
+        }
+        if data.last_commit > snapshot {
+            return Err(RayexecError::new(format!(
+                "Could not serialize access due to concurrent writes to table '{}'",
+                self.table.name
+            ))
+            .with_kind(ErrorKind::SerializationFailure));
+        }
+        Ok(())
+    }
+
+    fn commit(&self, commit_ts: u64) {
+        let mut data = self.table.data.lock();
+        if let Some(index) = &mut data.primary_key {
+            // No other commits happened since the transaction's snapshot, so
+            // these keys can't conflict with committed keys.
+            let keys = index.pending.remove(&self.tx_id).unwrap_or_default();
+            index.committed.extend(keys);
+        }
+        let batches = data.pending.remove(&self.tx_id).unwrap_or_default();
+        data.push_committed(commit_ts, batches);
+        data.last_commit = commit_ts;
+        self.table
+            .version
+            .store(next_data_version(), Ordering::Relaxed);
+    }
+
+    fn rollback(&self) {
+        let mut data = self.table.data.lock();
+        if let Some(index) = &mut data.primary_key {
+            index.pending.remove(&self.tx_id);
+        }
+        data.pending.remove(&self.tx_id);
     }
 }
 
@@ -142,7 +1322,7 @@ impl DataTableScan for MemoryDataTableScan {
 pub struct MemoryDataTableInsert {
     resizer: BatchResizer, // TODO: Need to replace.
     collected: Vec<ComputedBatches>,
-    data: Arc<Mutex<Vec<Batch>>>,
+    table: MemoryDataTable,
 }
 
 impl PartitionSink for MemoryDataTableInsert {
@@ -162,14 +1342,420 @@ impl PartitionSink for MemoryDataTableInsert {
             let batches = self.resizer.flush_remaining()?;
             self.collected.push(batches);
 
-            let mut data = self.data.lock();
+            let column_ids: Arc<[usize]> = self.table.layout.column_ids.clone().into();
+            let mut new_batches = Vec::new();
             for mut computed in self.collected.drain(..) {
                 while let Some(batch) = computed.try_pop_front()? {
-                    data.push(batch);
+                    new_batches.push(StoredBatch {
+                        column_ids: column_ids.clone(),
+                        batch,
+                    });
                 }
             }
 
+            self.table.append(new_batches)?;
+
             Ok(())
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::arrays::datatype::DataType;
+    use crate::arrays::field::Field;
+    use crate::arrays::scalar::OwnedScalarValue;
+    use crate::database::alter::AlterTableOperation;
+    use crate::functions::scalar::builtin::similarity::VectorMetric;
+    use crate::storage::table_storage::IndexBound;
+
+    fn new_table() -> MemoryDataTable {
+        MemoryDataTable::new(
+            "t",
+            TableEntry::new(vec![Field::new("a", DataType::Int32, true)]),
+        )
+    }
+
+    fn insert(table: &MemoryDataTable, tx: &CatalogTx, vals: Vec<i32>) -> Result<()> {
+        let mut sinks = table.with_tx(tx.clone()).insert(1)?;
+        block_on(sinks[0].push(Batch::try_new(vec![Array::from_iter(vals)])?))?;
+        block_on(sinks[0].finalize())
+    }
+
+    fn num_rows(table: &MemoryDataTable, tx: &CatalogTx) -> StatisticsValue<usize> {
+        block_on(table.with_tx(tx.clone()).statistics())
+            .unwrap()
+            .num_rows
+    }
+
+    #[test]
+    fn explicit_tx_snapshot_reads() {
+        let table = new_table();
+        let auto = CatalogTx::new();
+        insert(&table, &auto, vec![1, 2]).unwrap();
+
+        let tx1 = CatalogTx::begin();
+        let tx2 = CatalogTx::begin();
+        insert(&table, &tx1, vec![3]).unwrap();
+
+        // Only tx1 sees its uncommitted write.
+        assert_eq!(StatisticsValue::Exact(3), num_rows(&table, &tx1));
+        assert_eq!(StatisticsValue::Exact(2), num_rows(&table, &tx2));
+        assert_eq!(StatisticsValue::Exact(2), num_rows(&table, &auto));
+        assert_eq!(
+            DataVersion::Volatile,
+            table.with_tx(tx1.clone()).data_version()
+        );
+
+        tx1.commit().unwrap();
+
+        // tx2 still reads from its snapshot.
+        assert_eq!(StatisticsValue::Exact(2), num_rows(&table, &tx2));
+        assert_eq!(StatisticsValue::Exact(3), num_rows(&table, &auto));
+        assert_eq!(DataVersion::Volatile, table.with_tx(tx2).data_version());
+    }
+
+    #[test]
+    fn rollback_discards_writes() {
+        let table = new_table();
+        let tx = CatalogTx::begin();
+        insert(&table, &tx, vec![1, 2]).unwrap();
+        tx.rollback().unwrap();
+
+        assert_eq!(
+            StatisticsValue::Exact(0),
+            num_rows(&table, &CatalogTx::new())
+        );
+        assert!(table.data.lock().pending.is_empty());
+
+        // Can't write to a finished transaction.
+        insert(&table, &tx, vec![3]).unwrap_err();
+        assert!(table.data.lock().pending.is_empty());
+    }
+
+    #[test]
+    fn concurrent_writes_conflict() {
+        let table = new_table();
+        let tx1 = CatalogTx::begin();
+        let tx2 = CatalogTx::begin();
+        insert(&table, &tx1, vec![1]).unwrap();
+        insert(&table, &tx2, vec![2]).unwrap();
+
+        tx1.commit().unwrap();
+        let err = tx2.commit().unwrap_err();
+        assert_eq!(ErrorKind::SerializationFailure, err.kind());
+
+        assert_eq!(
+            StatisticsValue::Exact(1),
+            num_rows(&table, &CatalogTx::new())
+        );
+        assert!(table.data.lock().pending.is_empty());
+    }
+
+    #[test]
+    fn auto_commit_write_conflicts_with_explicit_tx() {
+        let table = new_table();
+        let tx = CatalogTx::begin();
+        insert(&table, &tx, vec![1]).unwrap();
+        insert(&table, &CatalogTx::new(), vec![2]).unwrap();
+
+        tx.commit().unwrap_err();
+        assert_eq!(
+            StatisticsValue::Exact(1),
+            num_rows(&table, &CatalogTx::new())
+        );
+    }
+
+    #[test]
+    fn primary_key_rejects_duplicates() {
+        let table = MemoryDataTable::new(
+            "t",
+            TableEntry {
+                primary_key: vec![0],
+                ..TableEntry::new(vec![Field::new("a", DataType::Int32, false)])
+            },
+        );
+        let auto = CatalogTx::new();
+        insert(&table, &auto, vec![1, 2]).unwrap();
+
+        let err = insert(&table, &auto, vec![2]).unwrap_err();
+        assert_eq!(ErrorKind::UniqueViolation, err.kind());
+        let err = insert(&table, &auto, vec![3, 3]).unwrap_err();
+        assert_eq!(ErrorKind::UniqueViolation, err.kind());
+
+        // Keys from a rolled back transaction can be reused.
+        let tx = CatalogTx::begin();
+        insert(&table, &tx, vec![3]).unwrap();
+        insert(&table, &tx, vec![3]).unwrap_err();
+        tx.rollback().unwrap();
+        let data = table.data.lock();
+        assert!(data.primary_key.as_ref().unwrap().pending.is_empty());
+        drop(data);
+
+        insert(&table, &auto, vec![3]).unwrap();
+        assert_eq!(StatisticsValue::Exact(3), num_rows(&table, &auto));
+    }
+
+    #[test]
+    fn index_scan_reads_matching_rows() {
+        let table = new_table();
+        let auto = CatalogTx::new();
+        insert(&table, &auto, vec![1, 2, 3]).unwrap();
+
+        let create = AlterTableOperation::CreateIndex {
+            name: "idx".to_string(),
+            columns: vec!["a".to_string()],
+            method: IndexMethod::BTree,
+            if_not_exists: false,
+        };
+        let layout = create.apply(&table.layout).unwrap().unwrap();
+        table.data.lock().sync_indexes(&layout).unwrap();
+        let table = MemoryDataTable {
+            layout: Arc::new(layout),
+            ..table
+        };
+        // Rows inserted after creating the index get indexed too.
+        insert(&table, &auto, vec![2, 4]).unwrap();
+
+        let read = |table: &MemoryDataTable, scan: &IndexScan| {
+            assert!(table.supports_index_scan(scan));
+            let mut scans = table.scan_index(Projections::all(), scan, 1, 1024).unwrap();
+            let mut vals = Vec::new();
+            while let Some(batch) = block_on(scans[0].pull()).unwrap() {
+                for row in 0..batch.num_rows() {
+                    match batch.column(0).unwrap().logical_value(row).unwrap() {
+                        ScalarValue::Int32(v) => vals.push(v),
+                        other => panic!("unexpected value: {other}"),
+                    }
+                }
+            }
+            vals.sort_unstable();
+            vals
+        };
+        let bound = |v: i32, inclusive: bool| IndexBound {
+            value: OwnedScalarValue::Int32(v),
+            inclusive,
+        };
+
+        let eq = IndexScan {
+            index: "idx".to_string(),
+            column_names: vec!["a".to_string()],
+            equal: vec![OwnedScalarValue::Int32(2)],
+            lower: None,
+            upper: None,
+            terms: Vec::new(),
+            nearest: None,
+        };
+        assert_eq!(vec![2, 2], read(&table, &eq));
+
+        let range = IndexScan {
+            equal: Vec::new(),
+            lower: Some(bound(1, false)),
+            upper: Some(bound(3, true)),
+            ..eq.clone()
+        };
+        assert_eq!(vec![2, 2, 3], read(&table, &range));
+
+        // Uncommitted rows are only visible to the inserting transaction.
+        let tx = CatalogTx::begin();
+        insert(&table, &tx, vec![2]).unwrap();
+        assert_eq!(3, read(&table.with_tx(tx), &eq).len());
+        assert_eq!(2, read(&table, &eq).len());
+    }
+
+    #[test]
+    fn full_text_index_finds_rows_with_all_terms() {
+        let layout = TableEntry::new(vec![Field::new("body", DataType::Utf8, true)]);
+        let mut index = SecondaryIndex::new(
+            &layout,
+            &TableIndex {
+                name: "fts".to_string(),
+                column_ids: vec![0],
+                method: IndexMethod::FullText,
+            },
+        );
+
+        let batches = [
+            vec![Some("The quick brown fox"), None, Some("lazy dog")],
+            vec![Some("quick dog"), Some("FOX, quick!")],
+        ];
+        for (batch_idx, vals) in batches.into_iter().enumerate() {
+            let stored = StoredBatch {
+                column_ids: Arc::new([0]),
+                batch: Batch::try_new(vec![Array::from_iter(vals)]).unwrap(),
+            };
+            index.insert(batch_idx, &stored).unwrap();
+        }
+
+        let find = |terms: &[&str]| {
+            let scan = IndexScan {
+                index: "fts".to_string(),
+                column_names: vec!["body".to_string()],
+                equal: Vec::new(),
+                lower: None,
+                upper: None,
+                terms: terms.iter().map(|t| t.to_string()).collect(),
+                nearest: None,
+            };
+            index.find(&scan).unwrap()
+        };
+
+        assert_eq!(vec![(0, 0), (1, 0), (1, 1)], find(&["quick"]));
+        assert_eq!(vec![(0, 0), (1, 1)], find(&["fox", "quick"]));
+        assert_eq!(Vec::<(usize, usize)>::new(), find(&["fox", "cat"]));
+    }
+
+    #[test]
+    fn vector_index_finds_nearest_rows() {
+        let datatype = DataType::List(ListTypeMeta::new_fixed_size(DataType::Float32, 2));
+        let layout = TableEntry::new(vec![Field::new("v", datatype, true)]);
+        let mut index = SecondaryIndex::new(
+            &layout,
+            &TableIndex {
+                name: "hnsw".to_string(),
+                column_ids: vec![0],
+                method: IndexMethod::Hnsw(VectorMetric::L2),
+            },
+        );
+
+        let vector = |x: f32, y: f32| {
+            ScalarValue::List(vec![ScalarValue::Float32(x), ScalarValue::Float32(y)])
+        };
+        // One row per batch, with a NULL vector in the last batch.
+        let values: Vec<_> = (0..100)
+            .map(|i| vector(i as f32, 0.0))
+            .chain([ScalarValue::Null])
+            .collect();
+        for (batch_idx, value) in values.iter().enumerate() {
+            let stored = StoredBatch {
+                column_ids: Arc::new([0]),
+                batch: Batch::try_new(vec![value.as_array(1).unwrap()]).unwrap(),
+            };
+            index.insert(batch_idx, &stored).unwrap();
+        }
+
+        let scan = IndexScan {
+            index: "hnsw".to_string(),
+            column_names: vec!["v".to_string()],
+            equal: Vec::new(),
+            lower: None,
+            upper: None,
+            terms: Vec::new(),
+            nearest: Some(NearestNeighbors {
+                query: vector(10.2, 1.0),
+                k: 3,
+            }),
+        };
+        let found = index.find(&scan).unwrap();
+
+        // Searches find more rows than needed, but not the whole table. Rows
+        // with NULL vectors are always included.
+        for batch_idx in [9, 10, 11, 100] {
+            assert!(found.contains(&(batch_idx, 0)), "missing {batch_idx}");
+        }
+        assert!(found.len() < values.len());
+
+        let wrong_dimensions = NearestNeighbors {
+            query: ScalarValue::List(vec![ScalarValue::Float32(1.0)]),
+            k: 3,
+        };
+        index
+            .find(&IndexScan {
+                nearest: Some(wrong_dimensions),
+                ..scan
+            })
+            .unwrap_err();
+    }
+
+    #[test]
+    fn filtered_scan_skips_batches() {
+        let table = new_table();
+        let auto = CatalogTx::new();
+        insert(&table, &auto, vec![1, 2, 3]).unwrap();
+        insert(&table, &auto, vec![10, 11]).unwrap();
+
+        let num_rows = |table: &MemoryDataTable, op, constant| {
+            let filters = [ScanFilter {
+                column: 0,
+                filter: ScanFilterType::ConstComparison {
+                    op,
+                    constant: OwnedScalarValue::Int32(constant),
+                },
+            }];
+            let mut scans = table
+                .scan_filtered(Projections::all(), &filters, 1, 1024)
+                .unwrap();
+            let mut num_rows = 0;
+            while let Some(batch) = block_on(scans[0].pull()).unwrap() {
+                num_rows += batch.num_rows();
+            }
+            num_rows
+        };
+
+        assert_eq!(2, num_rows(&table, ComparisonOperator::Gt, 5));
+        assert_eq!(3, num_rows(&table, ComparisonOperator::LtEq, 3));
+        assert_eq!(0, num_rows(&table, ComparisonOperator::Eq, 7));
+        assert_eq!(5, num_rows(&table, ComparisonOperator::NotEq, 7));
+
+        // Uncommitted batches don't have zone maps and are always read.
+        let tx = CatalogTx::begin();
+        insert(&table, &tx, vec![20]).unwrap();
+        assert_eq!(3, num_rows(&table.with_tx(tx), ComparisonOperator::Gt, 5));
+    }
+
+    #[test]
+    fn read_with_altered_layout() {
+        let table = new_table();
+        insert(&table, &CatalogTx::new(), vec![1, 2]).unwrap();
+
+        let add = AlterTableOperation::AddColumn {
+            field: Field::new("b", DataType::Int32, true),
+            default: OwnedScalarValue::Int32(8),
+            if_not_exists: false,
+        };
+        let added = add.apply(&table.layout).unwrap().unwrap();
+        let drop = AlterTableOperation::DropColumn {
+            name: "a".to_string(),
+            if_exists: false,
+        };
+        let dropped = drop.apply(&added).unwrap().unwrap();
+
+        let read = |layout: &TableEntry| {
+            let table = MemoryDataTable {
+                layout: Arc::new(layout.clone()),
+                ..table.clone()
+            };
+            let mut scans = table.scan(Projections::all(), 1, 1024).unwrap();
+            block_on(scans[0].pull()).unwrap().unwrap()
+        };
+
+        let batch = read(&added);
+        assert_eq!(2, batch.columns().len());
+        assert_eq!(
+            ScalarValue::Int32(2),
+            batch.column(0).unwrap().logical_value(1).unwrap()
+        );
+        assert_eq!(
+            ScalarValue::Int32(8),
+            batch.column(1).unwrap().logical_value(1).unwrap()
+        );
+
+        let batch = read(&dropped);
+        assert_eq!(1, batch.columns().len());
+        assert_eq!(
+            ScalarValue::Int32(8),
+            batch.column(0).unwrap().logical_value(0).unwrap()
+        );
+
+        // Readers holding the original entry still see the original columns.
+        let batch = read(&table.layout);
+        assert_eq!(1, batch.columns().len());
+        assert_eq!(
+            ScalarValue::Int32(1),
+            batch.column(0).unwrap().logical_value(0).unwrap()
+        );
+    }
+}
@@ -1,3 +1,5 @@
 pub mod catalog_storage;
+pub mod disk;
+pub mod hnsw;
 pub mod memory;
 pub mod table_storage;
@@ -1,12 +1,24 @@
-use std::fmt::Debug;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 use futures::future::BoxFuture;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rayexec_error::{RayexecError, Result};
 use rayexec_proto::ProtoConv;
 
 use crate::arrays::batch::Batch;
+use crate::arrays::datatype::DataType;
+use crate::arrays::scalar::OwnedScalarValue;
+use crate::arrays::selection::SelectionVector;
+use crate::database::catalog::CatalogTx;
 use crate::database::catalog_entry::CatalogEntry;
+use crate::execution::operators::runtime_filter::ScanRuntimeFilter;
 use crate::execution::operators::sink::PartitionSink;
+use crate::logical::scan_filter::ScanFilter;
+use crate::logical::statistics::StatisticsValue;
 
 /// Scan projections.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -57,8 +69,265 @@ impl ProtoConv for Projections {
     }
 }
 
+/// Method to use when sampling a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleMethod {
+    /// Sample blocks of rows (row groups, pages, batches).
+    ///
+    /// Cheaper than bernoulli sampling since blocks can be skipped entirely,
+    /// but rows within a block are correlated.
+    System,
+    /// Sample individual rows.
+    Bernoulli,
+}
+
+impl fmt::Display for SampleMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::System => write!(f, "SYSTEM"),
+            Self::Bernoulli => write!(f, "BERNOULLI"),
+        }
+    }
+}
+
+/// Sampling to apply to a table scan.
+///
+/// Produced from a `TABLESAMPLE` clause.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSample {
+    pub method: SampleMethod,
+    /// Percentage of the table to return, between 0 and 100.
+    pub percentage: f64,
+    /// Seed to use for repeatable sampling.
+    pub seed: Option<u64>,
+}
+
+// Percentage is checked during binding, and will never be NaN.
+impl Eq for TableSample {}
+
+impl TableSample {
+    /// Fraction of the input to keep, between 0 and 1.
+    pub fn fraction(&self) -> f64 {
+        self.percentage / 100.0
+    }
+
+    /// Create the random number generator to use for sampling a single
+    /// partition.
+    ///
+    /// Partitions get distinct generators so that they don't all make the same
+    /// choices when a seed is provided.
+    pub fn rng(&self, partition_idx: usize) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(partition_idx as u64)),
+            None => StdRng::from_entropy(),
+        }
+    }
+
+    /// Select which of `num_blocks` blocks to read for this sample.
+    pub fn sample_blocks(&self, rng: &mut StdRng, num_blocks: usize) -> Vec<usize> {
+        let fraction = self.fraction();
+        (0..num_blocks)
+            .filter(|_| rng.gen::<f64>() < fraction)
+            .collect()
+    }
+
+    /// Sample rows from an already read batch.
+    ///
+    /// For system sampling, the batch itself is treated as the block being
+    /// sampled.
+    pub fn sample_batch(&self, rng: &mut StdRng, batch: Batch) -> Batch {
+        let fraction = self.fraction();
+        match self.method {
+            SampleMethod::System => {
+                if rng.gen::<f64>() < fraction {
+                    batch
+                } else {
+                    batch.slice(0, 0)
+                }
+            }
+            SampleMethod::Bernoulli => {
+                let selection: SelectionVector = (0..batch.num_rows())
+                    .filter(|_| rng.gen::<f64>() < fraction)
+                    .collect();
+                batch.select(Arc::new(selection))
+            }
+        }
+    }
+}
+
+impl fmt::Display for TableSample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}%)", self.method, self.percentage)?;
+        if let Some(seed) = self.seed {
+            write!(f, " REPEATABLE ({seed})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Aggregate function that a data table may be able to compute itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableAggregateFunction {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl fmt::Display for TableAggregateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Count => write!(f, "count"),
+            Self::Sum => write!(f, "sum"),
+            Self::Avg => write!(f, "avg"),
+            Self::Min => write!(f, "min"),
+            Self::Max => write!(f, "max"),
+        }
+    }
+}
+
+/// A single aggregate to compute as part of a `TableAggregate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableAggregateExpr {
+    pub function: TableAggregateFunction,
+    /// Column index the aggregate is computed over.
+    ///
+    /// This is referencing a column prior to any projections being performed.
+    /// None for `count(*)`.
+    pub column: Option<usize>,
+    /// Type of the input column, None for `count(*)`.
+    pub input_type: Option<DataType>,
+    /// Type the table must produce for this aggregate.
+    pub output_type: DataType,
+}
+
+impl fmt::Display for TableAggregateExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "{}(#{column})", self.function),
+            None => write!(f, "{}(*)", self.function),
+        }
+    }
+}
+
+/// An ungrouped aggregate to be computed entirely by a data table.
+///
+/// Scans for the aggregate produce a single row containing a value for each
+/// aggregate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableAggregate {
+    /// Aggregates to compute, in output order.
+    pub aggregates: Vec<TableAggregateExpr>,
+    /// Filters to apply before aggregating, logically ANDed together.
+    ///
+    /// Unlike filters pushed into normal scans, these must be applied by the
+    /// table.
+    pub filters: Vec<ScanFilter>,
+}
+
+/// Bound on a column for an index scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexBound {
+    pub value: OwnedScalarValue,
+    /// If rows equal to the value are included.
+    pub inclusive: bool,
+}
+
+/// Nearest neighbor search through a vector index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearestNeighbors {
+    /// Vector to find the neighbors of.
+    pub query: OwnedScalarValue,
+    /// Number of neighbors needed.
+    pub k: usize,
+}
+
+/// A scan of a table that reads rows through one of its indexes.
+///
+/// Rows are selected by a prefix of the index's columns being equal to
+/// constants, optionally followed by a range on the next column. Full-text
+/// indexes instead select rows containing all of the scan's terms, and vector
+/// indexes select the rows nearest to a query vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexScan {
+    /// Name of the index to scan.
+    pub index: String,
+    /// Names of the index columns with conditions, used for display.
+    pub column_names: Vec<String>,
+    /// Values for the leading index columns.
+    pub equal: Vec<OwnedScalarValue>,
+    /// Lower bound for the index column following the equality columns.
+    pub lower: Option<IndexBound>,
+    /// Upper bound for the index column following the equality columns.
+    pub upper: Option<IndexBound>,
+    /// Terms the indexed column must contain, as produced by the 'simple'
+    /// text search config. Only set for full-text indexes.
+    pub terms: Vec<String>,
+    /// Nearest neighbors to find. Only set for vector indexes.
+    pub nearest: Option<NearestNeighbors>,
+}
+
+impl fmt::Display for IndexScan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (", self.index)?;
+        let mut conditions = Vec::new();
+        for (name, value) in self.column_names.iter().zip(&self.equal) {
+            conditions.push(format!("{name} = {value}"));
+        }
+        if let Some(name) = self.column_names.get(self.equal.len()) {
+            if let Some(lower) = &self.lower {
+                let op = if lower.inclusive { ">=" } else { ">" };
+                conditions.push(format!("{name} {op} {}", lower.value));
+            }
+            if let Some(upper) = &self.upper {
+                let op = if upper.inclusive { "<=" } else { "<" };
+                conditions.push(format!("{name} {op} {}", upper.value));
+            }
+        }
+        if let Some(name) = self.column_names.first() {
+            if !self.terms.is_empty() {
+                conditions.push(format!("{name} MATCH '{}'", self.terms.join(" ")));
+            }
+            if let Some(nearest) = &self.nearest {
+                conditions.push(format!(
+                    "{name} NEAREST {} LIMIT {}",
+                    nearest.query, nearest.k
+                ));
+            }
+        }
+        write!(f, "{})", conditions.join(" AND "))
+    }
+}
+
+/// A query rendered back to SQL to be executed entirely by the storage.
+///
+/// All tables referenced in the query belong to the same storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteQuery {
+    /// The SQL query.
+    ///
+    /// Identifiers are double quoted, and the query only uses syntax common
+    /// to most SQL dialects.
+    pub sql: String,
+    /// Names of the output columns in the query, in the order they should be
+    /// returned.
+    pub columns: Vec<String>,
+    /// Types the storage must produce for each output column.
+    pub types: Vec<DataType>,
+}
+
 pub trait TableStorage: Debug + Sync + Send {
-    fn data_table(&self, schema: &str, ent: &CatalogEntry) -> Result<Box<dyn DataTable>>;
+    /// Get the data table for a table entry.
+    ///
+    /// Scans and inserts on the returned table happen within the provided
+    /// transaction.
+    fn data_table(
+        &self,
+        tx: &CatalogTx,
+        schema: &str,
+        ent: &CatalogEntry,
+    ) -> Result<Box<dyn DataTable>>;
 
     fn create_physical_table(
         &self,
@@ -67,6 +336,114 @@ pub trait TableStorage: Debug + Sync + Send {
     ) -> BoxFuture<'_, Result<Box<dyn DataTable>>>;
 
     fn drop_physical_table(&self, schema: &str, ent: &CatalogEntry) -> BoxFuture<'_, Result<()>>;
+
+    /// Update a physical table after its entry has been altered.
+    ///
+    /// Existing data should be readable using the new entry, with added
+    /// columns taking their default values.
+    fn alter_physical_table(&self, _schema: &str, ent: &CatalogEntry) -> BoxFuture<'_, Result<()>> {
+        let err = RayexecError::new(format!(
+            "Table storage does not support altering table '{}'",
+            ent.name
+        ));
+        Box::pin(async { Err(err) })
+    }
+
+    /// Persist the creation of a schema.
+    ///
+    /// Storage that only persists tables doesn't need to do anything here.
+    fn create_physical_schema(&self, _schema: &str) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Persist dropping a schema along with all tables in it.
+    fn drop_physical_schema(&self, _schema: &str) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Check if the storage is able to execute the query itself.
+    ///
+    /// Storage backed by an external database can return true here to have
+    /// whole queries (or parts of queries) that only reference its tables
+    /// executed remotely.
+    fn supports_query(&self, _query: &RemoteQuery) -> bool {
+        false
+    }
+
+    /// Return scanners for the output of a query.
+    ///
+    /// Only called if `supports_query` returned true for the query.
+    fn scan_query(
+        &self,
+        _query: &RemoteQuery,
+        _num_partitions: usize,
+        _batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        Err(RayexecError::new(
+            "Table storage does not support query pushdown",
+        ))
+    }
+}
+
+/// Version of the data backing a table.
+///
+/// Used by the result cache to determine if cached results for a query
+/// scanning the table are still valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataVersion {
+    /// The data can't change (e.g. values computed only from the query
+    /// itself).
+    ///
+    /// Cached results remain valid until they're evicted.
+    Untracked,
+    /// Data is versioned, cached results are valid as long as the version
+    /// doesn't change.
+    Version(u64),
+    /// Data may change between every scan, results should never be cached.
+    Volatile,
+}
+
+impl DataVersion {
+    /// Create a data version from an opaque version string, like a file's
+    /// etag or last modified time.
+    ///
+    /// Data without a version is volatile since we have no way of knowing if
+    /// it's changed.
+    pub fn from_opaque(version: Option<&str>) -> Self {
+        match version {
+            Some(version) => {
+                let mut hasher = DefaultHasher::new();
+                version.hash(&mut hasher);
+                DataVersion::Version(hasher.finish())
+            }
+            None => DataVersion::Volatile,
+        }
+    }
+}
+
+/// Statistics for a table that can be obtained without scanning it.
+///
+/// Statistics describe the table as a whole, independent of how the table is
+/// split up into partitions when scanned.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableStatistics {
+    /// Number of rows in the table.
+    pub num_rows: StatisticsValue<usize>,
+    /// Size of the table's data in bytes.
+    pub num_bytes: StatisticsValue<usize>,
+    /// Statistics for each column in the table, in table column order.
+    ///
+    /// Empty if the table doesn't provide column statistics.
+    pub columns: Vec<TableColumnStatistics>,
+}
+
+/// Statistics for a single column in a table.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableColumnStatistics {
+    /// Minimum non-null value in the column.
+    pub min: StatisticsValue<OwnedScalarValue>,
+    /// Maximum non-null value in the column.
+    pub max: StatisticsValue<OwnedScalarValue>,
 }
 
 pub trait DataTable: Debug + Sync + Send {
@@ -76,12 +453,133 @@ pub trait DataTable: Debug + Sync + Send {
     /// partitions in the table output. However, the table may return a
     /// different number of partitions if it's unable to use the provided
     /// number.
+    ///
+    /// `batch_size` is the target number of rows for each batch produced by
+    /// the scans.
     fn scan(
         &self,
         projections: Projections,
         num_partitions: usize,
+        batch_size: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>>;
 
+    /// Return table scanners that may skip data that can't match the filters.
+    ///
+    /// Filters are ANDed together and reference columns by their position in
+    /// the table. Scanners may still produce rows that don't match, a filter
+    /// remains in place above the scan. The default implementation ignores
+    /// the filters.
+    fn scan_filtered(
+        &self,
+        projections: Projections,
+        _filters: &[ScanFilter],
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        self.scan(projections, num_partitions, batch_size)
+    }
+
+    /// Return table scanners that may skip data that can't match filters
+    /// produced by joins while the query executes.
+    ///
+    /// Runtime filters are completed once the build side of a join has been
+    /// read. Scanners may wait on the filters before reading, or check if
+    /// they're complete as they go. Rows not matching the filters are removed
+    /// after scanning, the default implementation ignores the runtime filters.
+    fn scan_runtime_filtered(
+        &self,
+        projections: Projections,
+        filters: &[ScanFilter],
+        _runtime_filters: &[ScanRuntimeFilter],
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        self.scan_filtered(projections, filters, num_partitions, batch_size)
+    }
+
+    /// Return table scanners that produce a sample of the table.
+    ///
+    /// The default implementation samples batches after they've been read
+    /// from the normal scans. Tables that are able to skip reading data that
+    /// isn't part of the sample should override this.
+    fn scan_sample(
+        &self,
+        projections: Projections,
+        sample: &TableSample,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let scans = self.scan(projections, num_partitions, batch_size)?;
+        Ok(SampledScan::wrap_scans(scans, sample))
+    }
+
+    /// Return table scanners that only need to produce up to `limit` rows
+    /// each.
+    ///
+    /// The limit applies per scan, and scans are free to return more rows
+    /// than the limit. The default implementation stops pulling from the
+    /// normal scans once enough rows have been produced. Tables that are able
+    /// to avoid reading data entirely should override this.
+    fn scan_limit(
+        &self,
+        projections: Projections,
+        limit: usize,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let scans = self.scan(projections, num_partitions, batch_size)?;
+        Ok(LimitedScan::wrap_scans(scans, limit))
+    }
+
+    /// Check if this table is able to compute the aggregate itself.
+    ///
+    /// Checked during planning, returning true means that `scan_aggregate`
+    /// will be called instead of scanning the table.
+    fn supports_aggregate(&self, _aggregate: &TableAggregate) -> bool {
+        false
+    }
+
+    /// Return table scanners that produce the output of an aggregate.
+    ///
+    /// Only called if `supports_aggregate` returned true for the aggregate.
+    fn scan_aggregate(
+        &self,
+        _aggregate: &TableAggregate,
+        _num_partitions: usize,
+        _batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        Err(RayexecError::new(
+            "Data table does not support aggregate pushdown",
+        ))
+    }
+
+    /// Check if this table is able to scan using an index.
+    ///
+    /// Checked during planning, returning true means that `scan_index` will
+    /// be called instead of scanning the table.
+    fn supports_index_scan(&self, _scan: &IndexScan) -> bool {
+        false
+    }
+
+    /// Return table scanners that produce rows found through an index.
+    ///
+    /// Only called if `supports_index_scan` returned true for the scan.
+    /// Scanners may produce rows outside of the scan's bounds, a filter
+    /// remains in place above the scan.
+    ///
+    /// Nearest neighbor scans are approximate, the rows produced should
+    /// include most of the `k` nearest rows, and the ordering and limit above
+    /// the scan pick the final rows.
+    fn scan_index(
+        &self,
+        _projections: Projections,
+        _scan: &IndexScan,
+        _num_partitions: usize,
+        _batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        Err(RayexecError::new("Data table does not support index scans"))
+    }
+
     fn insert(&self, _input_partitions: usize) -> Result<Vec<Box<dyn PartitionSink>>> {
         Err(RayexecError::new("Data table does not support inserts"))
     }
@@ -93,6 +591,22 @@ pub trait DataTable: Debug + Sync + Send {
     fn delete(&self, _input_partitions: usize) -> Result<Vec<Box<dyn DataTableDelete>>> {
         Err(RayexecError::new("Data table does not support updates"))
     }
+
+    /// Get the current version of the data in this table.
+    ///
+    /// Tables are assumed to be volatile unless they can report a version.
+    fn data_version(&self) -> DataVersion {
+        DataVersion::Volatile
+    }
+
+    /// Get statistics for the table.
+    ///
+    /// This should only return statistics that are cheap to obtain, e.g.
+    /// from file metadata or the system catalog of a remote database. The
+    /// default implementation returns unknown statistics.
+    fn statistics(&self) -> BoxFuture<'_, Result<TableStatistics>> {
+        Box::pin(async { Ok(TableStatistics::default()) })
+    }
 }
 
 pub trait DataTableScan: Debug + Send {
@@ -140,6 +654,114 @@ impl<S: DataTableScan> DataTableScan for ProjectedScan<S> {
     }
 }
 
+/// Helper for sampling the output of a scan that can't sample on its own.
+///
+/// This still requires reading all the data from the underlying scan.
+#[derive(Debug)]
+pub struct SampledScan {
+    pub sample: TableSample,
+    pub rng: StdRng,
+    pub scan: Box<dyn DataTableScan>,
+}
+
+impl SampledScan {
+    pub fn new(scan: Box<dyn DataTableScan>, sample: TableSample, partition_idx: usize) -> Self {
+        SampledScan {
+            rng: sample.rng(partition_idx),
+            sample,
+            scan,
+        }
+    }
+
+    /// Wrap each scan with sampling.
+    pub fn wrap_scans(
+        scans: Vec<Box<dyn DataTableScan>>,
+        sample: &TableSample,
+    ) -> Vec<Box<dyn DataTableScan>> {
+        scans
+            .into_iter()
+            .enumerate()
+            .map(|(idx, scan)| {
+                Box::new(SampledScan::new(scan, sample.clone(), idx)) as Box<dyn DataTableScan>
+            })
+            .collect()
+    }
+
+    async fn pull_inner(&mut self) -> Result<Option<Batch>> {
+        loop {
+            let batch = match self.scan.pull().await? {
+                Some(batch) => batch,
+                None => return Ok(None),
+            };
+
+            let batch = self.sample.sample_batch(&mut self.rng, batch);
+            if batch.num_rows() > 0 {
+                return Ok(Some(batch));
+            }
+        }
+    }
+}
+
+impl DataTableScan for SampledScan {
+    fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
+        Box::pin(async { self.pull_inner().await })
+    }
+}
+
+/// Helper for stopping a scan once it's produced enough rows.
+#[derive(Debug)]
+pub struct LimitedScan {
+    /// Number of rows remaining before the scan is considered exhausted.
+    pub remaining: usize,
+    pub scan: Box<dyn DataTableScan>,
+}
+
+impl LimitedScan {
+    pub fn new(scan: Box<dyn DataTableScan>, limit: usize) -> Self {
+        LimitedScan {
+            remaining: limit,
+            scan,
+        }
+    }
+
+    /// Wrap each scan with a limit.
+    pub fn wrap_scans(
+        scans: Vec<Box<dyn DataTableScan>>,
+        limit: usize,
+    ) -> Vec<Box<dyn DataTableScan>> {
+        scans
+            .into_iter()
+            .map(|scan| Box::new(LimitedScan::new(scan, limit)) as Box<dyn DataTableScan>)
+            .collect()
+    }
+
+    async fn pull_inner(&mut self) -> Result<Option<Batch>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        let batch = match self.scan.pull().await? {
+            Some(batch) => batch,
+            None => return Ok(None),
+        };
+
+        let batch = if batch.num_rows() > self.remaining {
+            batch.slice(0, self.remaining)
+        } else {
+            batch
+        };
+        self.remaining -= batch.num_rows();
+
+        Ok(Some(batch))
+    }
+}
+
+impl DataTableScan for LimitedScan {
+    fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
+        Box::pin(async { self.pull_inner().await })
+    }
+}
+
 /// Implementation of `DataTableScan` that immediately returns exhausted.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EmptyTableScan;
@@ -0,0 +1,382 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::schema::{Column, ColumnType};
+use crate::value::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    And,
+    Or,
+    Concat,
+}
+
+impl BinaryOp {
+    const COMPARISONS: [BinaryOp; 6] = [
+        BinaryOp::Eq,
+        BinaryOp::NotEq,
+        BinaryOp::Lt,
+        BinaryOp::LtEq,
+        BinaryOp::Gt,
+        BinaryOp::GtEq,
+    ];
+
+    fn sql(&self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Eq => "=",
+            BinaryOp::NotEq => "<>",
+            BinaryOp::Lt => "<",
+            BinaryOp::LtEq => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::GtEq => ">=",
+            BinaryOp::And => "AND",
+            BinaryOp::Or => "OR",
+            BinaryOp::Concat => "||",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Lower,
+    Upper,
+    Length,
+}
+
+impl Function {
+    fn sql(&self) -> &'static str {
+        match self {
+            Function::Lower => "lower",
+            Function::Upper => "upper",
+            Function::Length => "length",
+        }
+    }
+}
+
+/// A typed expression that can be both rendered as SQL and evaluated directly
+/// against a row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Reference to a column, `idx` is the position of the column in the rows
+    /// the expression is evaluated against.
+    Column {
+        idx: usize,
+        name: String,
+    },
+    Literal {
+        value: Value,
+        datatype: ColumnType,
+    },
+    Binary {
+        op: BinaryOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Not(Box<Expr>),
+    IsNull {
+        input: Box<Expr>,
+        negated: bool,
+    },
+    Case {
+        when: Box<Expr>,
+        then: Box<Expr>,
+        else_expr: Box<Expr>,
+    },
+    Coalesce(Box<Expr>, Box<Expr>),
+    Function {
+        function: Function,
+        input: Box<Expr>,
+    },
+}
+
+impl Expr {
+    pub fn to_sql(&self) -> String {
+        match self {
+            Expr::Column { name, .. } => name.clone(),
+            Expr::Literal { value, datatype } => {
+                if value.is_null() {
+                    // Typed NULLs to avoid depending on how the engine
+                    // resolves functions for NULL inputs.
+                    format!("CAST(NULL AS {})", datatype.sql_name())
+                } else {
+                    value.to_sql()
+                }
+            }
+            Expr::Binary { op, left, right } => {
+                format!("({} {} {})", left.to_sql(), op.sql(), right.to_sql())
+            }
+            Expr::Not(input) => format!("(NOT {})", input.to_sql()),
+            Expr::IsNull { input, negated } => {
+                if *negated {
+                    format!("({} IS NOT NULL)", input.to_sql())
+                } else {
+                    format!("({} IS NULL)", input.to_sql())
+                }
+            }
+            Expr::Case {
+                when,
+                then,
+                else_expr,
+            } => format!(
+                "(CASE WHEN {} THEN {} ELSE {} END)",
+                when.to_sql(),
+                then.to_sql(),
+                else_expr.to_sql()
+            ),
+            Expr::Coalesce(a, b) => format!("COALESCE({}, {})", a.to_sql(), b.to_sql()),
+            Expr::Function { function, input } => {
+                format!("{}({})", function.sql(), input.to_sql())
+            }
+        }
+    }
+
+    /// Evaluate the expression for a single row.
+    pub fn eval(&self, row: &[Value]) -> Value {
+        match self {
+            Expr::Column { idx, .. } => row[*idx].clone(),
+            Expr::Literal { value, .. } => value.clone(),
+            Expr::Binary { op, left, right } => eval_binary(*op, left.eval(row), right.eval(row)),
+            Expr::Not(input) => match input.eval(row) {
+                Value::Bool(v) => Value::Bool(!v),
+                _ => Value::Null,
+            },
+            Expr::IsNull { input, negated } => Value::Bool(input.eval(row).is_null() != *negated),
+            Expr::Case {
+                when,
+                then,
+                else_expr,
+            } => match when.eval(row) {
+                Value::Bool(true) => then.eval(row),
+                _ => else_expr.eval(row),
+            },
+            Expr::Coalesce(a, b) => match a.eval(row) {
+                Value::Null => b.eval(row),
+                v => v,
+            },
+            Expr::Function { function, input } => match (function, input.eval(row)) {
+                (Function::Lower, Value::Text(s)) => Value::Text(s.to_lowercase()),
+                (Function::Upper, Value::Text(s)) => Value::Text(s.to_uppercase()),
+                (Function::Length, Value::Text(s)) => Value::Int(s.chars().count() as i64),
+                _ => Value::Null,
+            },
+        }
+    }
+}
+
+fn eval_binary(op: BinaryOp, left: Value, right: Value) -> Value {
+    // AND and OR don't propagate NULLs when the other side determines the
+    // result.
+    match (op, &left, &right) {
+        (BinaryOp::And, Value::Bool(false), _) | (BinaryOp::And, _, Value::Bool(false)) => {
+            return Value::Bool(false)
+        }
+        (BinaryOp::Or, Value::Bool(true), _) | (BinaryOp::Or, _, Value::Bool(true)) => {
+            return Value::Bool(true)
+        }
+        _ => (),
+    }
+
+    if left.is_null() || right.is_null() {
+        return Value::Null;
+    }
+
+    match op {
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul => match (left, right) {
+            (Value::Int(a), Value::Int(b)) => Value::Int(match op {
+                BinaryOp::Add => a + b,
+                BinaryOp::Sub => a - b,
+                _ => a * b,
+            }),
+            _ => unreachable!("arithmetic on non-integers"),
+        },
+        BinaryOp::Eq => Value::Bool(left.sort_cmp(&right).is_eq()),
+        BinaryOp::NotEq => Value::Bool(left.sort_cmp(&right).is_ne()),
+        BinaryOp::Lt => Value::Bool(left.sort_cmp(&right).is_lt()),
+        BinaryOp::LtEq => Value::Bool(left.sort_cmp(&right).is_le()),
+        BinaryOp::Gt => Value::Bool(left.sort_cmp(&right).is_gt()),
+        BinaryOp::GtEq => Value::Bool(left.sort_cmp(&right).is_ge()),
+        // Both sides are non-NULL and neither short circuited.
+        BinaryOp::And => Value::Bool(true),
+        BinaryOp::Or => Value::Bool(false),
+        BinaryOp::Concat => match (left, right) {
+            (Value::Text(a), Value::Text(b)) => Value::Text(a + &b),
+            _ => unreachable!("concat on non-text"),
+        },
+    }
+}
+
+/// Generates random expressions over a set of columns.
+#[derive(Debug)]
+pub struct ExprGenerator<'a> {
+    /// Columns that can be referenced, in the same order as the rows the
+    /// expressions will be evaluated against.
+    pub columns: &'a [Column],
+    /// Max nesting depth of generated expressions.
+    pub max_depth: usize,
+}
+
+impl ExprGenerator<'_> {
+    pub fn generate(&self, rng: &mut StdRng, datatype: ColumnType) -> Expr {
+        self.generate_inner(rng, datatype, 0)
+    }
+
+    /// Generate a reference to a random column of the given type, falling
+    /// back to a literal if there's no such column.
+    pub fn column_or_literal(&self, rng: &mut StdRng, datatype: ColumnType) -> Expr {
+        let candidates: Vec<_> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, col)| col.datatype == datatype)
+            .collect();
+
+        if candidates.is_empty() || rng.gen_bool(0.2) {
+            let value = if rng.gen_bool(0.1) {
+                Value::Null
+            } else {
+                datatype.random_value(rng)
+            };
+            return Expr::Literal { value, datatype };
+        }
+
+        let (idx, col) = candidates[rng.gen_range(0..candidates.len())];
+        Expr::Column {
+            idx,
+            name: col.name.clone(),
+        }
+    }
+
+    fn generate_inner(&self, rng: &mut StdRng, datatype: ColumnType, depth: usize) -> Expr {
+        if depth >= self.max_depth || rng.gen_bool(0.35) {
+            return self.column_or_literal(rng, datatype);
+        }
+
+        let depth = depth + 1;
+        let child =
+            |rng: &mut StdRng, datatype| Box::new(self.generate_inner(rng, datatype, depth));
+
+        match (datatype, rng.gen_range(0..4)) {
+            (ColumnType::Int, 0) => Expr::Binary {
+                op: [BinaryOp::Add, BinaryOp::Sub, BinaryOp::Mul][rng.gen_range(0..3)],
+                left: child(rng, ColumnType::Int),
+                right: child(rng, ColumnType::Int),
+            },
+            (ColumnType::Int, 1) => Expr::Function {
+                function: Function::Length,
+                input: child(rng, ColumnType::Text),
+            },
+            (ColumnType::Text, 0) => Expr::Binary {
+                op: BinaryOp::Concat,
+                left: child(rng, ColumnType::Text),
+                right: child(rng, ColumnType::Text),
+            },
+            (ColumnType::Text, 1) => Expr::Function {
+                function: if rng.gen() {
+                    Function::Lower
+                } else {
+                    Function::Upper
+                },
+                input: child(rng, ColumnType::Text),
+            },
+            (ColumnType::Bool, 0) => {
+                let (op, input_type) = if rng.gen_bool(0.2) {
+                    (BinaryOp::Eq, ColumnType::Bool)
+                } else {
+                    (
+                        BinaryOp::COMPARISONS[rng.gen_range(0..BinaryOp::COMPARISONS.len())],
+                        [ColumnType::Int, ColumnType::Text][rng.gen_range(0..2)],
+                    )
+                };
+                Expr::Binary {
+                    op,
+                    left: child(rng, input_type),
+                    right: child(rng, input_type),
+                }
+            }
+            (ColumnType::Bool, 1) => Expr::Binary {
+                op: if rng.gen() {
+                    BinaryOp::And
+                } else {
+                    BinaryOp::Or
+                },
+                left: child(rng, ColumnType::Bool),
+                right: child(rng, ColumnType::Bool),
+            },
+            (ColumnType::Bool, 2) => {
+                if rng.gen() {
+                    Expr::Not(child(rng, ColumnType::Bool))
+                } else {
+                    let input_type = ColumnType::ALL[rng.gen_range(0..ColumnType::ALL.len())];
+                    Expr::IsNull {
+                        input: child(rng, input_type),
+                        negated: rng.gen(),
+                    }
+                }
+            }
+            (_, 2) => Expr::Case {
+                when: child(rng, ColumnType::Bool),
+                then: child(rng, datatype),
+                else_expr: child(rng, datatype),
+            },
+            _ => Expr::Coalesce(child(rng, datatype), child(rng, datatype)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lit(value: Value, datatype: ColumnType) -> Box<Expr> {
+        Box::new(Expr::Literal { value, datatype })
+    }
+
+    #[test]
+    fn three_valued_logic() {
+        let and = Expr::Binary {
+            op: BinaryOp::And,
+            left: lit(Value::Null, ColumnType::Bool),
+            right: lit(Value::Bool(false), ColumnType::Bool),
+        };
+        assert_eq!(Value::Bool(false), and.eval(&[]));
+
+        let or = Expr::Binary {
+            op: BinaryOp::Or,
+            left: lit(Value::Null, ColumnType::Bool),
+            right: lit(Value::Bool(false), ColumnType::Bool),
+        };
+        assert_eq!(Value::Null, or.eval(&[]));
+    }
+
+    #[test]
+    fn render_sql() {
+        let expr = Expr::Case {
+            when: Box::new(Expr::IsNull {
+                input: Box::new(Expr::Column {
+                    idx: 0,
+                    name: "t0.c0".to_string(),
+                }),
+                negated: false,
+            }),
+            then: lit(Value::Int(-1), ColumnType::Int),
+            else_expr: lit(Value::Null, ColumnType::Int),
+        };
+        assert_eq!(
+            "(CASE WHEN (t0.c0 IS NULL) THEN (-1) ELSE CAST(NULL AS BIGINT) END)",
+            expr.to_sql()
+        );
+        assert_eq!(Value::Int(-1), expr.eval(&[Value::Null]));
+    }
+}
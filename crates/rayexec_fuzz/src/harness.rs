@@ -0,0 +1,149 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayexec_error::{RayexecError, Result, ResultExt};
+use rayexec_execution::datasource::{DataSourceRegistry, MemoryDataSource};
+use rayexec_execution::runtime::{Runtime, TokioHandlerProvider};
+use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
+use rayexec_shell::session::SingleUserEngine;
+
+use crate::query::Query;
+use crate::schema::TableDef;
+use crate::value::{sort_rows, Value};
+
+/// Runs randomly generated queries against the engine and compares the
+/// results against the naive interpreter.
+#[derive(Debug)]
+pub struct Fuzzer {
+    pub engine: SingleUserEngine<ThreadedNativeExecutor, NativeRuntime>,
+}
+
+impl Fuzzer {
+    pub fn try_new() -> Result<Self> {
+        let registry =
+            DataSourceRegistry::default().with_datasource("memory", Box::new(MemoryDataSource))?;
+        let engine = SingleUserEngine::try_new(
+            ThreadedNativeExecutor::try_new()?,
+            NativeRuntime::with_default_tokio()?,
+            registry,
+        )?;
+
+        Ok(Fuzzer { engine })
+    }
+
+    /// Generate tables for a seed and run `num_queries` random queries over
+    /// them.
+    ///
+    /// The same seed always produces the same tables and queries. Errors
+    /// include the seed, the SQL needed to reproduce the failure, and the
+    /// expected and actual results.
+    pub fn run_seed(&self, seed: u64, num_queries: usize) -> Result<()> {
+        let handle = self.engine.runtime.tokio_handle().handle()?;
+        let result: Result<_> = handle.block_on(async move {
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            let num_tables = rng.gen_range(1..=3);
+            let tables: Vec<_> = (0..num_tables)
+                .map(|idx| TableDef::random(&mut rng, format!("s{seed}_t{idx}"), format!("t{idx}")))
+                .collect();
+
+            let mut setup = Vec::new();
+            for table in &tables {
+                setup.push(table.create_sql());
+                setup.extend(table.insert_sql());
+            }
+            for sql in &setup {
+                self.execute(sql)
+                    .await
+                    .context_fn(|| format!("Failed to set up tables for seed {seed}"))?;
+            }
+
+            // Tables aren't dropped (DROP TABLE isn't implemented yet), table
+            // names include the seed to avoid conflicts between runs.
+            self.run_queries(&mut rng, &tables, num_queries)
+                .await
+                .map_err(|e| {
+                    e.with_field("seed", seed)
+                        .with_field("setup", setup.join(";\n"))
+                })
+        });
+
+        result
+    }
+
+    async fn run_queries(
+        &self,
+        rng: &mut StdRng,
+        tables: &[TableDef],
+        num_queries: usize,
+    ) -> Result<()> {
+        for _ in 0..num_queries {
+            let query = Query::random(rng, tables);
+            let sql = query.to_sql(tables);
+
+            let mut expected = query.interpret(tables);
+            let mut got = self
+                .query(&sql)
+                .await
+                .map_err(|e| e.with_field("query", sql.clone()))?;
+
+            // Only results with a limit have a defined order.
+            if query.limit.is_none() {
+                sort_rows(&mut expected);
+                sort_rows(&mut got);
+            }
+
+            let matches = expected.len() == got.len()
+                && expected.iter().zip(&got).all(|(a, b)| {
+                    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.approx_eq(b))
+                });
+
+            if !matches {
+                return Err(RayexecError::new("Query results differ from interpreter")
+                    .with_field("query", sql)
+                    .with_field("expected", format_rows(&expected))
+                    .with_field("got", format_rows(&got)));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn execute(&self, sql: &str) -> Result<()> {
+        let _ = self.engine.session().query(sql).await?.collect().await?;
+        Ok(())
+    }
+
+    async fn query(&self, sql: &str) -> Result<Vec<Vec<Value>>> {
+        let table = self.engine.session().query(sql).await?.collect().await?;
+        table
+            .iter_rows()
+            .map(|row| row.columns.iter().map(Value::from_scalar).collect())
+            .collect()
+    }
+}
+
+fn format_rows(rows: &[Vec<Value>]) -> String {
+    let rows: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            let row: Vec<_> = row.iter().map(|v| v.to_string()).collect();
+            format!("[{}]", row.join(", "))
+        })
+        .collect();
+    format!("\n{}", rows.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_seeds() {
+        let fuzzer = Fuzzer::try_new().unwrap();
+        for seed in 0..20 {
+            if let Err(e) = fuzzer.run_seed(seed, 20) {
+                panic!("{e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Deterministic SQL fuzzing.
+//!
+//! Random tables and queries are generated from a seed, then the results
+//! from the engine are compared against a naive row at a time interpreter.
+//! Since generation is deterministic, a failing seed can always be replayed.
+
+pub mod expr;
+pub mod harness;
+pub mod query;
+pub mod schema;
+pub mod value;
@@ -0,0 +1,36 @@
+use clap::Parser;
+use rayexec_error::Result;
+use rayexec_fuzz::harness::Fuzzer;
+
+#[derive(Parser)]
+#[clap(name = "rayexec_fuzz")]
+struct Arguments {
+    /// Seed to start at.
+    #[clap(long, default_value = "0")]
+    seed: u64,
+    /// Number of seeds to run.
+    #[clap(long, short, default_value = "100")]
+    count: u64,
+    /// Number of queries to generate per seed.
+    #[clap(long, short, default_value = "50")]
+    queries: usize,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    if let Err(e) = run(&args) {
+        println!("ERROR: {e}");
+        std::process::exit(1);
+    }
+
+    println!("Ran {} seeds starting at {}", args.count, args.seed);
+}
+
+fn run(args: &Arguments) -> Result<()> {
+    let fuzzer = Fuzzer::try_new()?;
+    for seed in args.seed..args.seed + args.count {
+        fuzzer.run_seed(seed, args.queries)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,410 @@
+use std::fmt::Write as _;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::expr::{Expr, ExprGenerator};
+use crate::schema::{Column, ColumnType, TableDef};
+use crate::value::{cmp_rows, Value};
+
+/// Max nesting depth for generated expressions.
+const MAX_EXPR_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinType {
+    Inner,
+    Left,
+}
+
+/// Equality join against a second table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JoinClause {
+    pub table: usize,
+    pub join_type: JoinType,
+    /// Column from the left table.
+    pub left_key: Expr,
+    /// Column from the right table.
+    pub right_key: Expr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunction {
+    CountStar,
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregate {
+    pub function: AggregateFunction,
+    /// Input to the aggregate, None for COUNT(*).
+    pub input: Option<Expr>,
+}
+
+impl Aggregate {
+    fn to_sql(&self) -> String {
+        let name = match self.function {
+            AggregateFunction::CountStar => return "count(*)".to_string(),
+            AggregateFunction::Count => "count",
+            AggregateFunction::Sum => "sum",
+            AggregateFunction::Min => "min",
+            AggregateFunction::Max => "max",
+            AggregateFunction::Avg => "avg",
+        };
+        let input = self.input.as_ref().expect("input for aggregate");
+        format!("{name}({})", input.to_sql())
+    }
+
+    fn compute(&self, rows: &[&Vec<Value>]) -> Value {
+        let input = match &self.input {
+            Some(input) => input,
+            None => return Value::Int(rows.len() as i64),
+        };
+
+        let values: Vec<_> = rows
+            .iter()
+            .map(|row| input.eval(row))
+            .filter(|v| !v.is_null())
+            .collect();
+
+        match self.function {
+            AggregateFunction::CountStar => unreachable!("count star has no input"),
+            AggregateFunction::Count => Value::Int(values.len() as i64),
+            AggregateFunction::Sum | AggregateFunction::Avg => {
+                if values.is_empty() {
+                    return Value::Null;
+                }
+                let sum: i64 = values
+                    .iter()
+                    .map(|v| match v {
+                        Value::Int(v) => *v,
+                        other => unreachable!("sum on non-integer: {other}"),
+                    })
+                    .sum();
+                if self.function == AggregateFunction::Sum {
+                    Value::Int(sum)
+                } else {
+                    Value::Float(sum as f64 / values.len() as f64)
+                }
+            }
+            AggregateFunction::Min => values
+                .into_iter()
+                .min_by(|a, b| a.sort_cmp(b))
+                .unwrap_or(Value::Null),
+            AggregateFunction::Max => values
+                .into_iter()
+                .max_by(|a, b| a.sort_cmp(b))
+                .unwrap_or(Value::Null),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectList {
+    Projections(Vec<Expr>),
+    Aggregates {
+        group_by: Vec<Expr>,
+        aggregates: Vec<Aggregate>,
+    },
+}
+
+/// A randomly generated query over a set of tables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub from: usize,
+    pub join: Option<JoinClause>,
+    pub filter: Option<Expr>,
+    pub select: SelectList,
+    /// Order by every output column and limit to this many rows.
+    pub limit: Option<usize>,
+}
+
+impl Query {
+    /// Generate a random query over the given tables.
+    pub fn random(rng: &mut StdRng, tables: &[TableDef]) -> Self {
+        let from = rng.gen_range(0..tables.len());
+        let mut columns = tables[from].columns();
+
+        let join = if tables.len() > 1 && rng.gen_bool(0.3) {
+            let table = loop {
+                let idx = rng.gen_range(0..tables.len());
+                if idx != from {
+                    break idx;
+                }
+            };
+            let right_columns = tables[table].columns();
+            let left_key = random_int_column(rng, &columns, 0);
+            let right_key = random_int_column(rng, &right_columns, columns.len());
+            columns.extend(right_columns);
+
+            Some(JoinClause {
+                table,
+                join_type: if rng.gen() {
+                    JoinType::Inner
+                } else {
+                    JoinType::Left
+                },
+                left_key,
+                right_key,
+            })
+        } else {
+            None
+        };
+
+        let generator = ExprGenerator {
+            columns: &columns,
+            max_depth: MAX_EXPR_DEPTH,
+        };
+
+        let filter = if rng.gen_bool(0.5) {
+            Some(generator.generate(rng, ColumnType::Bool))
+        } else {
+            None
+        };
+
+        let mut limit = None;
+        let select = if rng.gen_bool(0.6) {
+            let num_exprs = rng.gen_range(1..=3);
+            let exprs = (0..num_exprs)
+                .map(|_| {
+                    let datatype = random_type(rng);
+                    generator.generate(rng, datatype)
+                })
+                .collect();
+            if rng.gen_bool(0.3) {
+                limit = Some(rng.gen_range(0..=10));
+            }
+            SelectList::Projections(exprs)
+        } else {
+            let num_groups = rng.gen_range(0..=2);
+            let mut group_by = Vec::with_capacity(num_groups);
+            for _ in 0..num_groups {
+                let datatype = random_type(rng);
+                let col = generator.column_or_literal(rng, datatype);
+                if matches!(col, Expr::Column { .. }) && !group_by.contains(&col) {
+                    group_by.push(col);
+                }
+            }
+
+            let num_aggs = rng.gen_range(1..=3);
+            let aggregates = (0..num_aggs)
+                .map(|_| random_aggregate(rng, &generator))
+                .collect();
+
+            SelectList::Aggregates {
+                group_by,
+                aggregates,
+            }
+        };
+
+        Query {
+            from,
+            join,
+            filter,
+            select,
+            limit,
+        }
+    }
+
+    pub fn to_sql(&self, tables: &[TableDef]) -> String {
+        let from = &tables[self.from];
+
+        let select_list: Vec<_> = match &self.select {
+            SelectList::Projections(exprs) => exprs.iter().map(|expr| expr.to_sql()).collect(),
+            SelectList::Aggregates {
+                group_by,
+                aggregates,
+            } => group_by
+                .iter()
+                .map(|expr| expr.to_sql())
+                .chain(aggregates.iter().map(|agg| agg.to_sql()))
+                .collect(),
+        };
+        let num_outputs = select_list.len();
+        let select_list = select_list
+            .into_iter()
+            .enumerate()
+            .map(|(idx, item)| format!("{item} AS o{idx}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut sql = format!("SELECT {select_list} FROM {} AS {}", from.name, from.alias);
+
+        if let Some(join) = &self.join {
+            let right = &tables[join.table];
+            let join_type = match join.join_type {
+                JoinType::Inner => "INNER",
+                JoinType::Left => "LEFT",
+            };
+            write!(
+                sql,
+                " {join_type} JOIN {} AS {} ON {} = {}",
+                right.name,
+                right.alias,
+                join.left_key.to_sql(),
+                join.right_key.to_sql()
+            )
+            .expect("write to string");
+        }
+
+        if let Some(filter) = &self.filter {
+            write!(sql, " WHERE {}", filter.to_sql()).expect("write to string");
+        }
+
+        if let SelectList::Aggregates { group_by, .. } = &self.select {
+            if !group_by.is_empty() {
+                let group_by = group_by
+                    .iter()
+                    .map(|expr| expr.to_sql())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(sql, " GROUP BY {group_by}").expect("write to string");
+            }
+        }
+
+        if let Some(limit) = self.limit {
+            let order_by = (1..=num_outputs)
+                .map(|ordinal| format!("{ordinal} ASC NULLS FIRST"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(sql, " ORDER BY {order_by} LIMIT {limit}").expect("write to string");
+        }
+
+        sql
+    }
+
+    /// Compute the query results with a naive row at a time interpreter.
+    ///
+    /// Rows are only in a meaningful order if the query has a limit.
+    pub fn interpret(&self, tables: &[TableDef]) -> Vec<Vec<Value>> {
+        let left = &tables[self.from];
+
+        let input: Vec<Vec<Value>> = match &self.join {
+            Some(join) => {
+                let right = &tables[join.table];
+                let mut rows = Vec::new();
+                for left_row in &left.rows {
+                    let mut matched = false;
+                    for right_row in &right.rows {
+                        let row: Vec<_> = left_row.iter().chain(right_row).cloned().collect();
+                        let left_key = join.left_key.eval(&row);
+                        if !left_key.is_null() && left_key == join.right_key.eval(&row) {
+                            matched = true;
+                            rows.push(row);
+                        }
+                    }
+                    if !matched && join.join_type == JoinType::Left {
+                        let nulls = std::iter::repeat_n(Value::Null, right.column_types.len());
+                        rows.push(left_row.iter().cloned().chain(nulls).collect());
+                    }
+                }
+                rows
+            }
+            None => left.rows.clone(),
+        };
+
+        let filtered: Vec<_> = input
+            .iter()
+            .filter(|row| match &self.filter {
+                Some(filter) => filter.eval(row) == Value::Bool(true),
+                None => true,
+            })
+            .collect();
+
+        let mut output: Vec<Vec<Value>> = match &self.select {
+            SelectList::Projections(exprs) => filtered
+                .iter()
+                .map(|row| exprs.iter().map(|expr| expr.eval(row)).collect())
+                .collect(),
+            SelectList::Aggregates {
+                group_by,
+                aggregates,
+            } => {
+                let mut groups: Vec<(Vec<Value>, Vec<&Vec<Value>>)> = Vec::new();
+                for row in filtered {
+                    let key: Vec<_> = group_by.iter().map(|expr| expr.eval(row)).collect();
+                    match groups.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, rows)) => rows.push(row),
+                        None => groups.push((key, vec![row])),
+                    }
+                }
+
+                // Ungrouped aggregates always produce a single row.
+                if group_by.is_empty() && groups.is_empty() {
+                    groups.push((Vec::new(), Vec::new()));
+                }
+
+                groups
+                    .into_iter()
+                    .map(|(key, rows)| {
+                        key.into_iter()
+                            .chain(aggregates.iter().map(|agg| agg.compute(&rows)))
+                            .collect()
+                    })
+                    .collect()
+            }
+        };
+
+        if let Some(limit) = self.limit {
+            output.sort_by(|a, b| cmp_rows(a, b));
+            output.truncate(limit);
+        }
+
+        output
+    }
+}
+
+fn random_type(rng: &mut StdRng) -> ColumnType {
+    ColumnType::ALL[rng.gen_range(0..ColumnType::ALL.len())]
+}
+
+/// Pick a random integer column, `offset` is added to the column index to
+/// account for columns from tables earlier in the row.
+fn random_int_column(rng: &mut StdRng, columns: &[Column], offset: usize) -> Expr {
+    let candidates: Vec<_> = columns
+        .iter()
+        .enumerate()
+        .filter(|(_, col)| col.datatype == ColumnType::Int)
+        .collect();
+    // Every table starts with an integer column.
+    let (idx, col) = candidates[rng.gen_range(0..candidates.len())];
+    Expr::Column {
+        idx: idx + offset,
+        name: col.name.clone(),
+    }
+}
+
+fn random_aggregate(rng: &mut StdRng, generator: &ExprGenerator) -> Aggregate {
+    let function = [
+        AggregateFunction::CountStar,
+        AggregateFunction::Count,
+        AggregateFunction::Sum,
+        AggregateFunction::Min,
+        AggregateFunction::Max,
+        AggregateFunction::Avg,
+    ][rng.gen_range(0..6)];
+
+    let input = match function {
+        AggregateFunction::CountStar => None,
+        AggregateFunction::Count => {
+            let datatype = random_type(rng);
+            Some(generator.generate(rng, datatype))
+        }
+        AggregateFunction::Sum | AggregateFunction::Avg => {
+            Some(generator.generate(rng, ColumnType::Int))
+        }
+        AggregateFunction::Min | AggregateFunction::Max => {
+            let datatype = if rng.gen() {
+                ColumnType::Int
+            } else {
+                ColumnType::Text
+            };
+            Some(generator.generate(rng, datatype))
+        }
+    };
+
+    Aggregate { function, input }
+}
@@ -0,0 +1,160 @@
+use std::fmt::Write as _;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::value::Value;
+
+/// Characters used for generating text values.
+///
+/// Kept small so that equality comparisons and group by keys have a
+/// reasonable chance of matching.
+const TEXT_ALPHABET: &[char] = &['a', 'b', 'c', 'A'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Int,
+    Text,
+    Bool,
+}
+
+impl ColumnType {
+    pub const ALL: [ColumnType; 3] = [ColumnType::Int, ColumnType::Text, ColumnType::Bool];
+
+    pub fn sql_name(&self) -> &'static str {
+        match self {
+            ColumnType::Int => "BIGINT",
+            ColumnType::Text => "TEXT",
+            ColumnType::Bool => "BOOLEAN",
+        }
+    }
+
+    /// Generate a random non-NULL value of this type.
+    pub fn random_value(&self, rng: &mut StdRng) -> Value {
+        match self {
+            ColumnType::Int => Value::Int(rng.gen_range(-5..=5)),
+            ColumnType::Text => {
+                let len = rng.gen_range(0..=3);
+                Value::Text(
+                    (0..len)
+                        .map(|_| TEXT_ALPHABET[rng.gen_range(0..TEXT_ALPHABET.len())])
+                        .collect(),
+                )
+            }
+            ColumnType::Bool => Value::Bool(rng.gen()),
+        }
+    }
+}
+
+/// A column that can be referenced in a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    /// Fully qualified name of the column, e.g. 't0.c1'.
+    pub name: String,
+    pub datatype: ColumnType,
+}
+
+/// A randomly generated table along with its data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableDef {
+    /// Name of the table in the catalog.
+    pub name: String,
+    /// Alias used for the table in queries.
+    pub alias: String,
+    pub column_types: Vec<ColumnType>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl TableDef {
+    /// Generate a table with random columns and rows.
+    ///
+    /// The first column is always an integer so that every pair of tables has
+    /// something to join on.
+    pub fn random(rng: &mut StdRng, name: String, alias: String) -> Self {
+        let num_columns = rng.gen_range(1..=4);
+        let column_types: Vec<_> = (0..num_columns)
+            .map(|idx| {
+                if idx == 0 {
+                    ColumnType::Int
+                } else {
+                    ColumnType::ALL[rng.gen_range(0..ColumnType::ALL.len())]
+                }
+            })
+            .collect();
+
+        let num_rows = rng.gen_range(0..=24);
+        let rows = (0..num_rows)
+            .map(|_| {
+                column_types
+                    .iter()
+                    .map(|datatype| {
+                        if rng.gen_bool(0.15) {
+                            Value::Null
+                        } else {
+                            datatype.random_value(rng)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        TableDef {
+            name,
+            alias,
+            column_types,
+            rows,
+        }
+    }
+
+    /// Columns of this table, qualified by the table alias.
+    pub fn columns(&self) -> Vec<Column> {
+        self.column_types
+            .iter()
+            .enumerate()
+            .map(|(idx, datatype)| Column {
+                name: format!("{}.c{idx}", self.alias),
+                datatype: *datatype,
+            })
+            .collect()
+    }
+
+    /// SQL for creating the table.
+    pub fn create_sql(&self) -> String {
+        let columns = self
+            .column_types
+            .iter()
+            .enumerate()
+            .map(|(idx, datatype)| format!("c{idx} {}", datatype.sql_name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("CREATE TEMP TABLE {} ({columns})", self.name)
+    }
+
+    /// SQL for inserting the table's rows, None if the table is empty.
+    pub fn insert_sql(&self) -> Option<String> {
+        if self.rows.is_empty() {
+            return None;
+        }
+
+        let mut sql = format!("INSERT INTO {} VALUES ", self.name);
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            if row_idx > 0 {
+                sql.push_str(", ");
+            }
+            sql.push('(');
+            for (col_idx, (value, datatype)) in row.iter().zip(&self.column_types).enumerate() {
+                if col_idx > 0 {
+                    sql.push_str(", ");
+                }
+                // Cast everything so the VALUES types don't depend on which
+                // row happens to come first.
+                write!(sql, "CAST({} AS {})", value.to_sql(), datatype.sql_name())
+                    .expect("write to string");
+            }
+            sql.push(')');
+        }
+
+        Some(sql)
+    }
+}
@@ -0,0 +1,165 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::scalar::ScalarValue;
+
+/// Max relative difference allowed when comparing floats.
+const FLOAT_TOLERANCE: f64 = 1e-9;
+
+/// A single value produced by either the interpreter or the engine.
+///
+/// Integers of all widths are widened to i64 since the interpreter doesn't
+/// track the exact integer type the engine picks for literals and arithmetic.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl Value {
+    pub fn from_scalar(scalar: &ScalarValue) -> Result<Self> {
+        Ok(match scalar {
+            ScalarValue::Null => Value::Null,
+            ScalarValue::Boolean(v) => Value::Bool(*v),
+            ScalarValue::Int8(v) => Value::Int(*v as i64),
+            ScalarValue::Int16(v) => Value::Int(*v as i64),
+            ScalarValue::Int32(v) => Value::Int(*v as i64),
+            ScalarValue::Int64(v) => Value::Int(*v),
+            ScalarValue::UInt8(v) => Value::Int(*v as i64),
+            ScalarValue::UInt16(v) => Value::Int(*v as i64),
+            ScalarValue::UInt32(v) => Value::Int(*v as i64),
+            ScalarValue::Float32(v) => Value::Float(*v as f64),
+            ScalarValue::Float64(v) => Value::Float(*v),
+            ScalarValue::Utf8(v) => Value::Text(v.to_string()),
+            other => {
+                return Err(RayexecError::new(format!(
+                    "Unexpected value in query output: {other}"
+                )))
+            }
+        })
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns the value as a SQL literal.
+    pub fn to_sql(&self) -> String {
+        match self {
+            Value::Null => "NULL".to_string(),
+            Value::Bool(v) => v.to_string(),
+            // Parenthesized so that negative numbers can't combine with a
+            // preceding minus into a comment.
+            Value::Int(v) if *v < 0 => format!("({v})"),
+            Value::Int(v) => v.to_string(),
+            Value::Float(v) => format!("{v:?}"),
+            Value::Text(v) => format!("'{}'", v.replace('\'', "''")),
+        }
+    }
+
+    /// Compare two values for sorting.
+    ///
+    /// NULLs sort first. Values of different types are ordered by type, which
+    /// only matters for producing a canonical order of result rows.
+    pub fn sort_cmp(&self, other: &Value) -> Ordering {
+        fn rank(v: &Value) -> u8 {
+            match v {
+                Value::Null => 0,
+                Value::Bool(_) => 1,
+                Value::Int(_) | Value::Float(_) => 2,
+                Value::Text(_) => 3,
+            }
+        }
+
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::Text(a), Value::Text(b)) => a.cmp(b),
+            (a, b) => match (a.as_f64(), b.as_f64()) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                _ => rank(a).cmp(&rank(b)),
+            },
+        }
+    }
+
+    /// Check if two values are equal, allowing for small differences between
+    /// floats.
+    pub fn approx_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Float(_), _) | (_, Value::Float(_)) => match (self.as_f64(), other.as_f64()) {
+                (Some(a), Some(b)) => {
+                    a == b || (a - b).abs() <= FLOAT_TOLERANCE * a.abs().max(b.abs())
+                }
+                _ => false,
+            },
+            _ => self == other,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(v) => Some(*v as f64),
+            Value::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "NULL"),
+            Value::Bool(v) => write!(f, "{v}"),
+            Value::Int(v) => write!(f, "{v}"),
+            Value::Float(v) => write!(f, "{v}"),
+            Value::Text(v) => write!(f, "{v:?}"),
+        }
+    }
+}
+
+/// Sort rows into a canonical order.
+pub fn sort_rows(rows: &mut [Vec<Value>]) {
+    rows.sort_by(|a, b| cmp_rows(a, b));
+}
+
+/// Compare two rows column by column.
+pub fn cmp_rows(a: &[Value], b: &[Value]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| a.sort_cmp(b))
+        .find(|ord| ord.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_nulls_first() {
+        let mut rows = vec![vec![Value::Int(2)], vec![Value::Null], vec![Value::Int(-1)]];
+        sort_rows(&mut rows);
+        assert_eq!(
+            vec![vec![Value::Null], vec![Value::Int(-1)], vec![Value::Int(2)]],
+            rows
+        );
+    }
+
+    #[test]
+    fn approx_eq_floats() {
+        assert!(Value::Float(0.1 + 0.2).approx_eq(&Value::Float(0.3)));
+        assert!(Value::Float(2.0).approx_eq(&Value::Int(2)));
+        assert!(!Value::Float(2.5).approx_eq(&Value::Int(2)));
+        assert!(!Value::Float(2.0).approx_eq(&Value::Null));
+    }
+
+    #[test]
+    fn negative_literal_is_parenthesized() {
+        assert_eq!("(-4)", Value::Int(-4).to_sql());
+        assert_eq!("'it''s'", Value::Text("it's".to_string()).to_sql());
+    }
+}
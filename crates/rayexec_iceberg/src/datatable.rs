@@ -3,7 +3,12 @@ use std::sync::Arc;
 use futures::future::BoxFuture;
 use rayexec_error::Result;
 use rayexec_execution::arrays::batch::Batch;
-use rayexec_execution::storage::table_storage::{DataTable, DataTableScan, Projections};
+use rayexec_execution::storage::table_storage::{
+    DataTable,
+    DataTableScan,
+    DataVersion,
+    Projections,
+};
 
 use crate::table::{Table, TableScan};
 
@@ -17,8 +22,9 @@ impl DataTable for IcebergDataTable {
         &self,
         projections: Projections,
         num_partitions: usize,
+        batch_size: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>> {
-        let scans = self.table.scan(projections, num_partitions)?;
+        let scans = self.table.scan(projections, num_partitions, batch_size)?;
         let scans: Vec<_> = scans
             .into_iter()
             .map(|scan| Box::new(IcebergTableScan { scan }) as _)
@@ -26,6 +32,13 @@ impl DataTable for IcebergDataTable {
 
         Ok(scans)
     }
+
+    fn data_version(&self) -> DataVersion {
+        match self.table.current_snapshot_id() {
+            Some(id) => DataVersion::Version(id as u64),
+            None => DataVersion::Volatile,
+        }
+    }
 }
 
 #[derive(Debug)]
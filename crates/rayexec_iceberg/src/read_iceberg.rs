@@ -67,13 +67,14 @@ impl<R: Runtime> ScanPlanner for ReadIceberg<R> {
 impl<R: Runtime> ReadIceberg<R> {
     async fn plan_inner(
         self,
-        _context: &DatabaseContext,
+        context: &DatabaseContext,
         positional_inputs: Vec<OwnedScalarValue>,
         named_inputs: HashMap<String, OwnedScalarValue>,
     ) -> Result<PlannedTableFunction> {
         let (location, conf) =
             try_location_and_access_config_from_args(&self, &positional_inputs, &named_inputs)?;
-        let provider = self.runtime.file_provider();
+        let runtime = context.scan_runtime(&self.runtime);
+        let provider = runtime.file_provider();
 
         // TODO: Fetch stats, use during planning.
         let table = Table::load(location.clone(), provider, conf.clone()).await?;
@@ -11,6 +11,7 @@ use rayexec_execution::storage::table_storage::Projections;
 use rayexec_io::location::{AccessConfig, FileLocation};
 use rayexec_io::{FileProvider, FileSource, FileSourceExt};
 use rayexec_parquet::metadata::Metadata;
+use rayexec_parquet::metadata_cache::prefetch_metadata;
 use rayexec_parquet::reader::AsyncBatchReader;
 
 use crate::spec::{
@@ -126,7 +127,17 @@ impl Table {
         Ok(table)
     }
 
-    pub fn scan(&self, projections: Projections, num_partitions: usize) -> Result<Vec<TableScan>> {
+    /// Id of the snapshot the table was loaded at, if the table has one.
+    pub fn current_snapshot_id(&self) -> Option<i64> {
+        self.metadata.current_snapshot_id
+    }
+
+    pub fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<TableScan>> {
         // Find all data files in the manifests. We'll distribute these evenly
         // over however many partitions we need.
         let data_files_iter = self
@@ -171,6 +182,8 @@ impl Table {
                 files,
                 provider: self.provider.clone(),
                 conf: self.conf.clone(),
+                batch_size,
+                metadata: None,
                 current: None,
             })
             .collect();
@@ -269,6 +282,13 @@ pub struct TableScan {
     /// File provider for getting the actual file sources.
     provider: Arc<dyn FileProvider>,
     conf: AccessConfig,
+    /// Target number of rows per batch read from the data files.
+    batch_size: usize,
+    /// Metadata for each remaining file.
+    ///
+    /// Prefetched for all files on the first read so that footers are read
+    /// concurrently instead of one file at a time.
+    metadata: Option<VecDeque<Arc<Metadata>>>,
     /// Current reader, initially empty and populated on first stream.
     ///
     /// Once a reader runs out, the next file is loaded, and gets placed here.
@@ -277,30 +297,39 @@ pub struct TableScan {
 
 impl TableScan {
     pub async fn read_next(&mut self) -> Result<Option<Batch>> {
+        if self.metadata.is_none() {
+            let locations = self
+                .files
+                .iter()
+                .map(|file| self.file_location(file))
+                .collect::<Result<Vec<_>>>()?;
+            let metadata = prefetch_metadata(self.provider.as_ref(), locations, &self.conf).await?;
+            self.metadata = Some(metadata.into());
+        }
+
         loop {
             if self.current.is_none() {
                 let file = match self.files.pop_front() {
                     Some(file) => file,
                     None => return Ok(None), // We're done
                 };
-
-                // Get the path of the file relative to the path in the tabl's
-                // metadata. This let's us do the path join below without any
-                // issue as it'll already have the root in it.
-                let path = self.resolver.relative_path(&file.file_path);
-
-                let location = self.root.join(path.split('/'))?;
-
-                self.current = Some(
-                    Self::load_reader(
-                        location,
-                        &self.conf,
-                        self.provider.as_ref(),
-                        &self.schema,
-                        self.projections.clone(),
-                    )
-                    .await?,
-                )
+                let metadata = self
+                    .metadata
+                    .as_mut()
+                    .and_then(|metadata| metadata.pop_front())
+                    .ok_or_else(|| RayexecError::new("Missing prefetched metadata for file"))?;
+
+                let location = self.file_location(&file)?;
+
+                self.current = Some(Self::load_reader(
+                    location,
+                    metadata,
+                    &self.conf,
+                    self.provider.as_ref(),
+                    &self.schema,
+                    self.projections.clone(),
+                    self.batch_size,
+                )?)
             }
 
             match self.current.as_mut().unwrap().read_next().await {
@@ -315,26 +344,34 @@ impl TableScan {
         }
     }
 
-    async fn load_reader(
+    /// Get the location of a data file.
+    fn file_location(&self, file: &DataFile) -> Result<FileLocation> {
+        // Get the path of the file relative to the path in the tabl's
+        // metadata. This let's us do the path join below without any
+        // issue as it'll already have the root in it.
+        let path = self.resolver.relative_path(&file.file_path);
+        self.root.join(path.split('/'))
+    }
+
+    fn load_reader(
         location: FileLocation,
+        metadata: Arc<Metadata>,
         conf: &AccessConfig,
         provider: &dyn FileProvider,
         schema: &Schema,
         projections: Projections,
+        batch_size: usize,
     ) -> Result<AsyncBatchReader<Box<dyn FileSource>>> {
-        let mut source = provider.file_source(location, conf)?;
+        let source = provider.file_source(location, conf)?;
 
-        let size = source.size().await?;
-        let metadata = Arc::new(Metadata::new_from_source(source.as_mut(), size).await?);
         let row_groups: VecDeque<_> = (0..metadata.decoded_metadata.row_groups().len()).collect();
 
-        const BATCH_SIZE: usize = 4096; // TODO
         let reader = AsyncBatchReader::try_new(
             source,
             row_groups,
             metadata,
             schema,
-            BATCH_SIZE,
+            batch_size,
             projections,
         )?;
 
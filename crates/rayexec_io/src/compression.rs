@@ -0,0 +1,550 @@
+//! Transparent compression for streamed file reads and writes.
+//!
+//! File sources are wrapped with `DecompressingFileSource`, which decompresses
+//! the byte stream if the file is compressed. The compression type is taken
+//! from the file extension (e.g. 'data.csv.gz') when there is one, otherwise
+//! it's detected from the magic bytes at the start of the stream.
+//!
+//! Only streams are decompressed. Range reads are passed through as-is since
+//! we can't seek into a compressed stream, so formats that rely on range reads
+//! (parquet) should handle compression themselves.
+//!
+//! File sinks for paths with a compression extension are wrapped with
+//! `CompressedFileSink`, compressing everything written to it.
+//!
+//! Gzip is always available. Zstd and bzip2 require the `zstd` and `bzip2`
+//! features respectively as they need to compile C libraries.
+use std::fmt;
+use std::io::Write;
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, StreamExt};
+use parking_lot::Mutex;
+use rayexec_error::{RayexecError, Result, ResultExt};
+
+use crate::location::FileLocation;
+use crate::{FileSink, FileSource};
+
+/// Number of bytes needed to detect compression from magic bytes.
+const MAGIC_LEN: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionType {
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl CompressionType {
+    /// Get the compression type from a path's extension.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let path = path.to_ascii_lowercase();
+        if path.ends_with(".gz") || path.ends_with(".gzip") {
+            Some(CompressionType::Gzip)
+        } else if path.ends_with(".zst") || path.ends_with(".zstd") {
+            Some(CompressionType::Zstd)
+        } else if path.ends_with(".bz2") {
+            Some(CompressionType::Bzip2)
+        } else {
+            None
+        }
+    }
+
+    /// Get the compression type from a file location's extension.
+    ///
+    /// Query parameters for urls are ignored.
+    pub fn from_location(location: &FileLocation) -> Option<Self> {
+        match location {
+            FileLocation::Url(url) => Self::from_path(url.path()),
+            FileLocation::Path(path) => Self::from_path(&path.to_string_lossy()),
+        }
+    }
+
+    /// Detect the compression type from the magic bytes at the start of a
+    /// file.
+    pub fn from_magic_bytes(buf: &[u8]) -> Option<Self> {
+        const BZIP2_BLOCK_MAGIC: [u8; 6] = [0x31, 0x41, 0x59, 0x26, 0x53, 0x59];
+        const BZIP2_EOS_MAGIC: [u8; 6] = [0x17, 0x72, 0x45, 0x38, 0x50, 0x90];
+
+        match buf {
+            [0x1f, 0x8b, ..] => Some(CompressionType::Gzip),
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(CompressionType::Zstd),
+            // "BZh", block size, then either the start of a block or the end
+            // of the stream (for empty input). The header alone is plain text
+            // so we check for the block magic too.
+            [b'B', b'Z', b'h', b'1'..=b'9', rest @ ..]
+                if rest.starts_with(&BZIP2_BLOCK_MAGIC) || rest.starts_with(&BZIP2_EOS_MAGIC) =>
+            {
+                Some(CompressionType::Bzip2)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CompressionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gzip => write!(f, "gzip"),
+            Self::Zstd => write!(f, "zstd"),
+            Self::Bzip2 => write!(f, "bzip2"),
+        }
+    }
+}
+
+/// A file source that decompresses streams from an underlying source.
+#[derive(Debug)]
+pub struct DecompressingFileSource<S: FileSource> {
+    inner: S,
+    /// Compression type if known ahead of time (from the file extension).
+    ///
+    /// If None, the compression type is detected from the stream.
+    compression: Option<CompressionType>,
+}
+
+impl<S: FileSource> DecompressingFileSource<S> {
+    pub fn new(inner: S, compression: Option<CompressionType>) -> Self {
+        DecompressingFileSource { inner, compression }
+    }
+}
+
+impl<S: FileSource> FileSource for DecompressingFileSource<S> {
+    fn read_range(&mut self, start: usize, len: usize) -> BoxFuture<Result<Bytes>> {
+        self.inner.read_range(start, len)
+    }
+
+    fn read_stream(&mut self) -> BoxStream<'static, Result<Bytes>> {
+        let stream = self.inner.read_stream();
+        match self.compression {
+            Some(compression) => decompress_stream(stream, compression),
+            None => detect_and_decompress_stream(stream),
+        }
+    }
+
+    fn size(&mut self) -> BoxFuture<Result<usize>> {
+        // Size of the compressed file, we don't know the decompressed size
+        // without reading everything.
+        self.inner.size()
+    }
+
+    fn version(&self) -> Option<String> {
+        self.inner.version()
+    }
+}
+
+/// Buffer the start of the stream to detect compression from magic bytes,
+/// decompressing the stream if needed.
+fn detect_and_decompress_stream(
+    mut stream: BoxStream<'static, Result<Bytes>>,
+) -> BoxStream<'static, Result<Bytes>> {
+    async move {
+        let mut head = Vec::new();
+        let mut head_len = 0;
+        while head_len < MAGIC_LEN {
+            match stream.next().await {
+                Some(Ok(bs)) => {
+                    head_len += bs.len();
+                    head.push(bs);
+                }
+                Some(Err(e)) => return stream::once(async move { Err(e) }).boxed(),
+                None => break,
+            }
+        }
+
+        let magic: Vec<u8> = head
+            .iter()
+            .flat_map(|bs| bs.iter().copied())
+            .take(MAGIC_LEN)
+            .collect();
+        let compression = CompressionType::from_magic_bytes(&magic);
+
+        // Put back what we've read.
+        let stream = stream::iter(head.into_iter().map(Ok)).chain(stream).boxed();
+
+        match compression {
+            Some(compression) => decompress_stream(stream, compression),
+            None => stream,
+        }
+    }
+    .flatten_stream()
+    .boxed()
+}
+
+fn decompress_stream(
+    stream: BoxStream<'static, Result<Bytes>>,
+    compression: CompressionType,
+) -> BoxStream<'static, Result<Bytes>> {
+    let decoder = match Decoder::try_new(compression) {
+        Ok(decoder) => decoder,
+        Err(e) => return stream::once(async move { Err(e) }).boxed(),
+    };
+
+    // State is None once the stream completes or errors.
+    stream::unfold(Some((stream, decoder)), |state| async move {
+        let (mut stream, mut decoder) = state?;
+        loop {
+            match stream.next().await {
+                Some(Ok(bs)) => match decoder.decode(&bs) {
+                    // Decoder may need more input before producing anything.
+                    Ok(out) if out.is_empty() => continue,
+                    Ok(out) => return Some((Ok(out), Some((stream, decoder)))),
+                    Err(e) => return Some((Err(e), None)),
+                },
+                Some(Err(e)) => return Some((Err(e), None)),
+                None => {
+                    return match decoder.finish() {
+                        Ok(out) if out.is_empty() => None,
+                        Ok(out) => Some((Ok(out), None)),
+                        Err(e) => Some((Err(e), None)),
+                    }
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+/// A file sink that compresses everything written to it before writing to the
+/// underlying sink.
+#[derive(Debug)]
+pub struct CompressedFileSink<S: FileSink> {
+    inner: S,
+    /// Encoder behind a mutex since some encoders aren't Sync. Only accessed
+    /// through `get_mut`.
+    encoder: Mutex<Encoder>,
+}
+
+impl<S: FileSink> CompressedFileSink<S> {
+    pub fn try_new(inner: S, compression: CompressionType) -> Result<Self> {
+        Ok(CompressedFileSink {
+            inner,
+            encoder: Mutex::new(Encoder::try_new(compression)?),
+        })
+    }
+}
+
+impl<S: FileSink> FileSink for CompressedFileSink<S> {
+    fn write_all(&mut self, buf: Bytes) -> BoxFuture<'static, Result<()>> {
+        match self.encoder.get_mut().encode(&buf) {
+            Ok(out) if out.is_empty() => async { Ok(()) }.boxed(),
+            Ok(out) => self.inner.write_all(out),
+            Err(e) => async move { Err(e) }.boxed(),
+        }
+    }
+
+    fn finish(&mut self) -> BoxFuture<'static, Result<()>> {
+        let out = match self.encoder.get_mut().finish() {
+            Ok(out) => out,
+            Err(e) => return async move { Err(e) }.boxed(),
+        };
+
+        // Both futures are created up front, and rely on the inner sink
+        // beginning the write when `write_all` is called.
+        let write = self.inner.write_all(out);
+        let finish = self.inner.finish();
+
+        async move {
+            write.await?;
+            finish.await
+        }
+        .boxed()
+    }
+}
+
+/// Take the bytes written to an encoder or decoder's output buffer.
+fn take_output(buf: &mut Vec<u8>) -> Bytes {
+    Bytes::from(std::mem::take(buf))
+}
+
+fn feature_not_enabled(compression: CompressionType) -> RayexecError {
+    RayexecError::new(format!("Support for {compression} compression not enabled"))
+}
+
+enum Decoder {
+    Gzip(flate2::write::MultiGzDecoder<Vec<u8>>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::write::BzDecoder<Vec<u8>>),
+}
+
+impl Decoder {
+    fn try_new(compression: CompressionType) -> Result<Self> {
+        Ok(match compression {
+            CompressionType::Gzip => Decoder::Gzip(flate2::write::MultiGzDecoder::new(Vec::new())),
+            #[cfg(feature = "zstd")]
+            CompressionType::Zstd => Decoder::Zstd(
+                zstd::stream::write::Decoder::new(Vec::new())
+                    .context("Failed to create zstd decoder")?,
+            ),
+            #[cfg(feature = "bzip2")]
+            CompressionType::Bzip2 => Decoder::Bzip2(bzip2::write::BzDecoder::new(Vec::new())),
+            #[allow(unreachable_patterns)]
+            other => return Err(feature_not_enabled(other)),
+        })
+    }
+
+    /// Decode a chunk of the compressed input, returning decompressed bytes.
+    ///
+    /// The returned bytes may be empty.
+    fn decode(&mut self, buf: &[u8]) -> Result<Bytes> {
+        match self {
+            Self::Gzip(dec) => {
+                dec.write_all(buf).context("Failed to decompress gzip")?;
+                Ok(take_output(dec.get_mut()))
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd(dec) => {
+                dec.write_all(buf).context("Failed to decompress zstd")?;
+                Ok(take_output(dec.get_mut()))
+            }
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(dec) => {
+                dec.write_all(buf).context("Failed to decompress bzip2")?;
+                Ok(take_output(dec.get_mut()))
+            }
+        }
+    }
+
+    /// Finish decoding, returning any remaining decompressed bytes.
+    fn finish(&mut self) -> Result<Bytes> {
+        match self {
+            Self::Gzip(dec) => {
+                dec.try_finish().context("Failed to decompress gzip")?;
+                Ok(take_output(dec.get_mut()))
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd(dec) => {
+                dec.flush().context("Failed to decompress zstd")?;
+                Ok(take_output(dec.get_mut()))
+            }
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(dec) => {
+                dec.try_finish().context("Failed to decompress bzip2")?;
+                Ok(take_output(dec.get_mut()))
+            }
+        }
+    }
+}
+
+enum Encoder {
+    Gzip(flate2::write::GzEncoder<Vec<u8>>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::write::BzEncoder<Vec<u8>>),
+}
+
+impl Encoder {
+    fn try_new(compression: CompressionType) -> Result<Self> {
+        Ok(match compression {
+            CompressionType::Gzip => Encoder::Gzip(flate2::write::GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::default(),
+            )),
+            #[cfg(feature = "zstd")]
+            CompressionType::Zstd => Encoder::Zstd(
+                zstd::stream::write::Encoder::new(Vec::new(), zstd::DEFAULT_COMPRESSION_LEVEL)
+                    .context("Failed to create zstd encoder")?,
+            ),
+            #[cfg(feature = "bzip2")]
+            CompressionType::Bzip2 => Encoder::Bzip2(bzip2::write::BzEncoder::new(
+                Vec::new(),
+                bzip2::Compression::default(),
+            )),
+            #[allow(unreachable_patterns)]
+            other => return Err(feature_not_enabled(other)),
+        })
+    }
+
+    /// Compress some bytes, returning compressed bytes ready to be written.
+    ///
+    /// The returned bytes may be empty.
+    fn encode(&mut self, buf: &[u8]) -> Result<Bytes> {
+        match self {
+            Self::Gzip(enc) => {
+                enc.write_all(buf).context("Failed to compress gzip")?;
+                Ok(take_output(enc.get_mut()))
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd(enc) => {
+                enc.write_all(buf).context("Failed to compress zstd")?;
+                Ok(take_output(enc.get_mut()))
+            }
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(enc) => {
+                enc.write_all(buf).context("Failed to compress bzip2")?;
+                Ok(take_output(enc.get_mut()))
+            }
+        }
+    }
+
+    /// Finish compressing, returning the remaining compressed bytes.
+    fn finish(&mut self) -> Result<Bytes> {
+        match self {
+            Self::Gzip(enc) => {
+                enc.try_finish().context("Failed to compress gzip")?;
+                Ok(take_output(enc.get_mut()))
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd(enc) => {
+                enc.do_finish().context("Failed to compress zstd")?;
+                Ok(take_output(enc.get_mut()))
+            }
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(enc) => {
+                enc.try_finish().context("Failed to compress bzip2")?;
+                Ok(take_output(enc.get_mut()))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decoder").finish_non_exhaustive()
+    }
+}
+
+impl fmt::Debug for Encoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Encoder").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::memory::MemoryFileSystem;
+    use crate::FileSourceExt;
+
+    /// Write content through a compressed sink, returning the file system
+    /// containing the compressed file.
+    fn write_compressed(
+        name: &str,
+        content: &[u8],
+        compression: CompressionType,
+    ) -> MemoryFileSystem {
+        let fs = MemoryFileSystem::default();
+        let mut sink =
+            CompressedFileSink::try_new(fs.file_sink(name.as_ref()).unwrap(), compression).unwrap();
+        // Multiple writes to make sure the encoder state carries across.
+        for chunk in content.chunks(7) {
+            block_on(sink.write_all(Bytes::copy_from_slice(chunk))).unwrap();
+        }
+        block_on(sink.finish()).unwrap();
+
+        fs
+    }
+
+    fn read_decompressed(
+        fs: &MemoryFileSystem,
+        name: &str,
+        hint: Option<CompressionType>,
+    ) -> Bytes {
+        let mut source = DecompressingFileSource::new(fs.file_source(name.as_ref()).unwrap(), hint);
+        block_on(source.read_stream_all()).unwrap()
+    }
+
+    #[test]
+    fn compression_from_path() {
+        assert_eq!(
+            Some(CompressionType::Gzip),
+            CompressionType::from_path("data.csv.gz")
+        );
+        assert_eq!(
+            Some(CompressionType::Gzip),
+            CompressionType::from_path("DATA.CSV.GZ")
+        );
+        assert_eq!(
+            Some(CompressionType::Zstd),
+            CompressionType::from_path("data.csv.zst")
+        );
+        assert_eq!(
+            Some(CompressionType::Bzip2),
+            CompressionType::from_path("data.csv.bz2")
+        );
+        assert_eq!(None, CompressionType::from_path("data.csv"));
+
+        let location = FileLocation::parse("https://example.com/data.csv.gz?version=2");
+        assert_eq!(
+            Some(CompressionType::Gzip),
+            CompressionType::from_location(&location)
+        );
+    }
+
+    #[test]
+    fn magic_bytes_not_plain_text() {
+        assert_eq!(None, CompressionType::from_magic_bytes(b"a,b,c\n1,2,3\n"));
+        assert_eq!(
+            None,
+            CompressionType::from_magic_bytes(b"BZh1,2,3\n4,5,6\n")
+        );
+        assert_eq!(None, CompressionType::from_magic_bytes(b""));
+    }
+
+    #[test]
+    fn gzip_roundtrip() {
+        let content = b"a,b\n1,hello\n2,world\n".repeat(20);
+        let fs = write_compressed("data.csv.gz", &content, CompressionType::Gzip);
+
+        let out = read_decompressed(&fs, "data.csv.gz", Some(CompressionType::Gzip));
+        assert_eq!(content.as_slice(), out.as_ref());
+    }
+
+    #[test]
+    fn detect_from_magic_bytes() {
+        let content = b"a,b\n1,hello\n2,world\n".repeat(20);
+        let fs = write_compressed("data", &content, CompressionType::Gzip);
+
+        let out = read_decompressed(&fs, "data", None);
+        assert_eq!(content.as_slice(), out.as_ref());
+    }
+
+    #[test]
+    fn uncompressed_passthrough() {
+        let fs = MemoryFileSystem::default();
+        fs.register_file("data.csv".as_ref(), Bytes::from_static(b"a,b\n1,2\n"))
+            .unwrap();
+
+        let out = read_decompressed(&fs, "data.csv", None);
+        assert_eq!(b"a,b\n1,2\n".as_slice(), out.as_ref());
+    }
+
+    #[test]
+    fn invalid_compressed_data() {
+        let fs = MemoryFileSystem::default();
+        fs.register_file("data.csv.gz".as_ref(), Bytes::from_static(b"a,b\n1,2\n"))
+            .unwrap();
+
+        let mut source = DecompressingFileSource::new(
+            fs.file_source("data.csv.gz".as_ref()).unwrap(),
+            Some(CompressionType::Gzip),
+        );
+        block_on(source.read_stream_all()).unwrap_err();
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_roundtrip() {
+        let content = b"a,b\n1,hello\n2,world\n".repeat(20);
+        let fs = write_compressed("data.csv.zst", &content, CompressionType::Zstd);
+
+        let out = read_decompressed(&fs, "data.csv.zst", None);
+        assert_eq!(content.as_slice(), out.as_ref());
+    }
+
+    #[cfg(feature = "bzip2")]
+    #[test]
+    fn bzip2_roundtrip() {
+        let content = b"a,b\n1,hello\n2,world\n".repeat(20);
+        let fs = write_compressed("data.csv.bz2", &content, CompressionType::Bzip2);
+
+        let out = read_decompressed(&fs, "data.csv.bz2", None);
+        assert_eq!(content.as_slice(), out.as_ref());
+    }
+}
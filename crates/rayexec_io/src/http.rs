@@ -3,10 +3,10 @@ use std::fmt::Debug;
 use bytes::Bytes;
 use futures::future::BoxFuture;
 use futures::stream::{self, BoxStream};
-use futures::{Future, Stream, StreamExt, TryStreamExt};
-use rayexec_error::{RayexecError, Result, ResultExt};
+use futures::{Future, Stream, StreamExt};
+use rayexec_error::{ErrorKind, RayexecError, Result, ResultExt};
 pub use reqwest;
-use reqwest::header::{HeaderMap, CONTENT_LENGTH, RANGE};
+use reqwest::header::{HeaderMap, CONTENT_LENGTH, ETAG, LAST_MODIFIED, RANGE};
 use reqwest::{Method, Request, StatusCode};
 use serde::de::DeserializeOwned;
 use tracing::debug;
@@ -14,11 +14,23 @@ use url::Url;
 
 use crate::FileSource;
 
+/// Max number of times a request is retried after a transient failure.
+///
+/// Streams reset this count whenever they make progress.
+pub const MAX_REQUEST_RETRIES: usize = 3;
+
 pub trait HttpClient: Sync + Send + Debug + Clone {
     type Response: HttpResponse + Send;
     type RequestFuture: Future<Output = Result<Self::Response>> + Send;
 
     fn do_request(&self, request: Request) -> Self::RequestFuture;
+
+    /// If requests or response bodies that timed out should be retried.
+    ///
+    /// Other failures that may succeed when tried again are always retried.
+    fn retry_timeouts(&self) -> bool {
+        true
+    }
 }
 
 pub trait HttpResponse {
@@ -49,11 +61,17 @@ pub async fn read_json<T: DeserializeOwned>(resp: impl HttpResponse) -> Result<T
 pub struct HttpClientReader<C: HttpClient> {
     client: C,
     url: Url,
+    /// Version of the file from the response when getting the size.
+    version: Option<String>,
 }
 
 impl<C: HttpClient> HttpClientReader<C> {
     pub fn new(client: C, url: Url) -> Self {
-        HttpClientReader { client, url }
+        HttpClientReader {
+            client,
+            url,
+            version: None,
+        }
     }
 }
 
@@ -61,40 +79,19 @@ impl<C: HttpClient + 'static> FileSource for HttpClientReader<C> {
     fn read_range(&mut self, start: usize, len: usize) -> BoxFuture<Result<Bytes>> {
         debug!(url = %self.url, %start, %len, "http reading range");
 
-        let range = format_range_header(start, start + len - 1);
-
-        let mut request = Request::new(Method::GET, self.url.clone());
-        request
-            .headers_mut()
-            .insert(RANGE, range.try_into().unwrap());
-
-        let fut = self.client.do_request(request);
-
-        Box::pin(async move {
-            let resp = fut.await?;
-
-            if resp.status() != StatusCode::PARTIAL_CONTENT {
-                return Err(RayexecError::new("Server does not support range requests"));
-            }
-
-            resp.bytes().await.context("failed to get response body")
-        })
+        Box::pin(read_range_with_retries(
+            self.client.clone(),
+            self.url.clone(),
+            start,
+            len,
+            Ok,
+        ))
     }
 
     fn read_stream(&mut self) -> BoxStream<'static, Result<Bytes>> {
         debug!(url = %self.url, "http reading stream");
 
-        let client = self.client.clone();
-        let req = Request::new(Method::GET, self.url.clone());
-
-        let stream = stream::once(async move {
-            let resp = client.do_request(req).await?;
-
-            Ok::<_, RayexecError>(resp.bytes_stream())
-        })
-        .try_flatten();
-
-        stream.boxed()
+        resumable_stream(self.client.clone(), self.url.clone(), Ok)
     }
 
     fn size(&mut self) -> BoxFuture<Result<usize>> {
@@ -103,6 +100,7 @@ impl<C: HttpClient + 'static> FileSource for HttpClientReader<C> {
         let fut = self
             .client
             .do_request(Request::new(Method::GET, self.url.clone()));
+        let version_slot = &mut self.version;
 
         Box::pin(async move {
             let resp = fut.await?;
@@ -111,6 +109,8 @@ impl<C: HttpClient + 'static> FileSource for HttpClientReader<C> {
                 return Err(RayexecError::new("Failed to get content-length"));
             }
 
+            *version_slot = version_from_headers(resp.headers());
+
             let len = match resp.headers().get(CONTENT_LENGTH) {
                 Some(header) => header
                     .to_str()
@@ -123,8 +123,444 @@ impl<C: HttpClient + 'static> FileSource for HttpClientReader<C> {
             Ok(len)
         })
     }
+
+    fn version(&self) -> Option<String> {
+        self.version.clone()
+    }
+}
+
+/// Get the version of an object from response headers, preferring the etag
+/// over the last modified time.
+pub(crate) fn version_from_headers(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(ETAG)
+        .or_else(|| headers.get(LAST_MODIFIED))
+        .and_then(|header| header.to_str().ok())
+        .map(|version| version.to_string())
 }
 
 pub(crate) fn format_range_header(start: usize, end: usize) -> String {
     format!("bytes={start}-{end}")
 }
+
+/// Error from a single request attempt.
+#[derive(Debug)]
+enum AttemptError {
+    /// The request may succeed if tried again (dropped connection, server
+    /// error, throttling).
+    Transient(RayexecError),
+    /// Retrying won't help.
+    Fatal(RayexecError),
+}
+
+impl AttemptError {
+    /// Error from sending a request or reading a response body.
+    ///
+    /// These are transient unless the client timed out and isn't configured
+    /// to retry timeouts.
+    fn from_client<C: HttpClient>(client: &C, e: RayexecError) -> Self {
+        if e.kind() == ErrorKind::Timeout && !client.retry_timeouts() {
+            AttemptError::Fatal(e)
+        } else {
+            AttemptError::Transient(e)
+        }
+    }
+}
+
+/// Check if a response status indicates the request may succeed if retried.
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Send a GET request for the given url, optionally starting at some byte
+/// offset, and check the response status.
+///
+/// `prepare` is called on every request before it's sent (e.g. for signing).
+async fn send_get<C, F>(
+    client: &C,
+    url: &Url,
+    range: Option<(usize, Option<usize>)>,
+    prepare: &F,
+) -> Result<C::Response, AttemptError>
+where
+    C: HttpClient,
+    F: Fn(Request) -> Result<Request>,
+{
+    let mut request = Request::new(Method::GET, url.clone());
+    if let Some((start, end)) = range {
+        let header = match end {
+            Some(end) => format_range_header(start, end),
+            None => format!("bytes={start}-"),
+        };
+        request
+            .headers_mut()
+            .insert(RANGE, header.try_into().unwrap());
+    }
+    let request = prepare(request).map_err(AttemptError::Fatal)?;
+
+    let resp = client
+        .do_request(request)
+        .await
+        .map_err(|e| AttemptError::from_client(client, e))?;
+
+    let status = resp.status();
+    let expected = if range.is_some() {
+        status == StatusCode::PARTIAL_CONTENT
+    } else {
+        status.is_success()
+    };
+    if expected {
+        return Ok(resp);
+    }
+
+    let text = read_text(resp).await.unwrap_or_default();
+    if is_retryable_status(status) {
+        Err(AttemptError::Transient(RayexecError::new(format!(
+            "Request failed with status {status}: {text}"
+        ))))
+    } else if range.is_some() && status.is_success() {
+        Err(AttemptError::Fatal(RayexecError::new(
+            "Server does not support range requests",
+        )))
+    } else {
+        Err(AttemptError::Fatal(RayexecError::new(format!(
+            "Request failed with status {status}: {text}"
+        ))))
+    }
+}
+
+/// Read a range of bytes, retrying transient failures.
+pub(crate) async fn read_range_with_retries<C, F>(
+    client: C,
+    url: Url,
+    start: usize,
+    len: usize,
+    prepare: F,
+) -> Result<Bytes>
+where
+    C: HttpClient,
+    F: Fn(Request) -> Result<Request>,
+{
+    let mut retries = 0;
+    loop {
+        let attempt = async {
+            let resp = send_get(
+                &client,
+                &url,
+                Some((start, Some(start + len - 1))),
+                &prepare,
+            )
+            .await?;
+            resp.bytes()
+                .await
+                .map_err(|e| AttemptError::from_client(&client, e))
+        };
+
+        match attempt.await {
+            Ok(bytes) => return Ok(bytes),
+            Err(AttemptError::Transient(e)) if retries < MAX_REQUEST_RETRIES => {
+                retries += 1;
+                debug!(%url, %start, %len, %retries, %e, "retrying range read");
+            }
+            Err(AttemptError::Transient(e)) | Err(AttemptError::Fatal(e)) => return Err(e),
+        }
+    }
+}
+
+/// State for a stream that resumes from the last received byte when the
+/// response body fails.
+struct ResumableStreamState<C: HttpClient, F> {
+    client: C,
+    url: Url,
+    prepare: F,
+    /// Number of bytes received so far.
+    offset: usize,
+    /// Retries since the last received chunk.
+    retries: usize,
+    body: Option<BoxStream<'static, Result<Bytes>>>,
+    finished: bool,
+}
+
+impl<C, F> ResumableStreamState<C, F>
+where
+    C: HttpClient + 'static,
+    F: Fn(Request) -> Result<Request>,
+{
+    async fn next_chunk(&mut self) -> Option<Result<Bytes>> {
+        loop {
+            if self.finished {
+                return None;
+            }
+
+            let err = match self.body.as_mut() {
+                Some(body) => match body.next().await {
+                    Some(Ok(bytes)) => {
+                        if !bytes.is_empty() {
+                            self.offset += bytes.len();
+                            self.retries = 0;
+                        }
+                        return Some(Ok(bytes));
+                    }
+                    Some(Err(e)) => AttemptError::from_client(&self.client, e),
+                    None => {
+                        self.finished = true;
+                        return None;
+                    }
+                },
+                None => {
+                    // Continue from where the previous body stopped.
+                    let range = (self.offset > 0).then_some((self.offset, None));
+                    match send_get(&self.client, &self.url, range, &self.prepare).await {
+                        Ok(resp) => {
+                            self.body = Some(resp.bytes_stream().boxed());
+                            continue;
+                        }
+                        Err(e) => e,
+                    }
+                }
+            };
+
+            match err {
+                AttemptError::Transient(e) if self.retries < MAX_REQUEST_RETRIES => {
+                    self.retries += 1;
+                    self.body = None;
+                    debug!(url = %self.url, offset = %self.offset, retries = %self.retries, %e, "resuming stream");
+                }
+                AttemptError::Transient(e) | AttemptError::Fatal(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// Stream the body for a url, resuming with a range request starting after
+/// the last received byte if the body fails mid-stream.
+pub(crate) fn resumable_stream<C, F>(
+    client: C,
+    url: Url,
+    prepare: F,
+) -> BoxStream<'static, Result<Bytes>>
+where
+    C: HttpClient + 'static,
+    F: Fn(Request) -> Result<Request> + Send + Sync + 'static,
+{
+    let state = ResumableStreamState {
+        client,
+        url,
+        prepare,
+        offset: 0,
+        retries: 0,
+        body: None,
+        finished: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        let chunk = state.next_chunk().await?;
+        Some((chunk, state))
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::executor::block_on;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::FileSourceExt;
+
+    /// Serves a fixed body, with the first `failures` responses dropping the
+    /// body after `fail_after` bytes.
+    #[derive(Debug, Clone)]
+    struct FlakyClient {
+        data: Bytes,
+        fail_after: usize,
+        failures: Arc<Mutex<usize>>,
+        /// Range headers for each request received.
+        ranges: Arc<Mutex<Vec<Option<String>>>>,
+        /// If bodies fail by timing out instead of dropping the connection.
+        stalls: bool,
+        retry_timeouts: bool,
+    }
+
+    impl FlakyClient {
+        fn new(data: &'static [u8], fail_after: usize, failures: usize) -> Self {
+            FlakyClient {
+                data: Bytes::from_static(data),
+                fail_after,
+                failures: Arc::new(Mutex::new(failures)),
+                ranges: Arc::new(Mutex::new(Vec::new())),
+                stalls: false,
+                retry_timeouts: true,
+            }
+        }
+
+        fn with_stalls(mut self, retry_timeouts: bool) -> Self {
+            self.stalls = true;
+            self.retry_timeouts = retry_timeouts;
+            self
+        }
+    }
+
+    struct FlakyResponse {
+        status: StatusCode,
+        headers: HeaderMap,
+        chunks: Vec<Result<Bytes>>,
+    }
+
+    impl HttpClient for FlakyClient {
+        type Response = FlakyResponse;
+        type RequestFuture = BoxFuture<'static, Result<FlakyResponse>>;
+
+        fn do_request(&self, request: Request) -> Self::RequestFuture {
+            let range = request
+                .headers()
+                .get(RANGE)
+                .map(|v| v.to_str().unwrap().to_string());
+            self.ranges.lock().push(range.clone());
+
+            let (status, start, end) = match range {
+                Some(range) => {
+                    let (start, end) = range
+                        .strip_prefix("bytes=")
+                        .unwrap()
+                        .split_once('-')
+                        .unwrap();
+                    let end = match end {
+                        "" => self.data.len(),
+                        end => end.parse::<usize>().unwrap() + 1,
+                    };
+                    (StatusCode::PARTIAL_CONTENT, start.parse().unwrap(), end)
+                }
+                None => (StatusCode::OK, 0, self.data.len()),
+            };
+            let body = self.data.slice(start..end);
+
+            let mut failures = self.failures.lock();
+            let chunks = if *failures > 0 && body.len() > self.fail_after {
+                *failures -= 1;
+                let err = if self.stalls {
+                    RayexecError::new("stalled").with_kind(ErrorKind::Timeout)
+                } else {
+                    RayexecError::new("connection reset")
+                };
+                vec![Ok(body.slice(..self.fail_after)), Err(err)]
+            } else {
+                vec![Ok(body)]
+            };
+
+            let resp = FlakyResponse {
+                status,
+                headers: HeaderMap::new(),
+                chunks,
+            };
+            Box::pin(async move { Ok(resp) })
+        }
+
+        fn retry_timeouts(&self) -> bool {
+            self.retry_timeouts
+        }
+    }
+
+    impl HttpResponse for FlakyResponse {
+        type BytesFuture = BoxFuture<'static, Result<Bytes>>;
+        type BytesStream = BoxStream<'static, Result<Bytes>>;
+
+        fn status(&self) -> StatusCode {
+            self.status
+        }
+
+        fn headers(&self) -> &HeaderMap {
+            &self.headers
+        }
+
+        fn bytes(self) -> Self::BytesFuture {
+            Box::pin(async move {
+                let mut buf = Vec::new();
+                for chunk in self.chunks {
+                    buf.extend_from_slice(&chunk?);
+                }
+                Ok(buf.into())
+            })
+        }
+
+        fn bytes_stream(self) -> Self::BytesStream {
+            stream::iter(self.chunks).boxed()
+        }
+    }
+
+    fn test_url() -> Url {
+        Url::parse("http://localhost/file").unwrap()
+    }
+
+    #[test]
+    fn stream_resumes_after_failure() {
+        let client = FlakyClient::new(b"hello world", 4, 2);
+        let mut reader = HttpClientReader::new(client.clone(), test_url());
+
+        let out = block_on(reader.read_stream_all()).unwrap();
+        assert_eq!(b"hello world".as_slice(), out.as_ref());
+
+        let expected = vec![
+            None,
+            Some("bytes=4-".to_string()),
+            Some("bytes=8-".to_string()),
+        ];
+        assert_eq!(expected, *client.ranges.lock());
+    }
+
+    #[test]
+    fn stream_gives_up_after_max_retries() {
+        let client = FlakyClient::new(b"hello world", 1, MAX_REQUEST_RETRIES + 1);
+        let mut reader = HttpClientReader::new(client, test_url());
+
+        // Every failure makes progress, so the retries are reset.
+        let out = block_on(reader.read_stream_all()).unwrap();
+        assert_eq!(b"hello world".as_slice(), out.as_ref());
+
+        let client = FlakyClient::new(b"hello world", 0, MAX_REQUEST_RETRIES + 1);
+        let mut reader = HttpClientReader::new(client, test_url());
+        block_on(reader.read_stream_all()).unwrap_err();
+    }
+
+    #[test]
+    fn stalled_stream_retried_if_configured() {
+        let client = FlakyClient::new(b"hello world", 4, 1).with_stalls(true);
+        let mut reader = HttpClientReader::new(client.clone(), test_url());
+        let out = block_on(reader.read_stream_all()).unwrap();
+        assert_eq!(b"hello world".as_slice(), out.as_ref());
+        assert_eq!(2, client.ranges.lock().len());
+
+        let client = FlakyClient::new(b"hello world", 4, 1).with_stalls(false);
+        let mut reader = HttpClientReader::new(client.clone(), test_url());
+        let err = block_on(reader.read_stream_all()).unwrap_err();
+        assert_eq!(ErrorKind::Timeout, err.kind());
+        assert_eq!(1, client.ranges.lock().len());
+    }
+
+    #[test]
+    fn stalled_range_read_fails_if_not_retrying() {
+        let client = FlakyClient::new(b"hello world", 2, 1).with_stalls(false);
+        let mut reader = HttpClientReader::new(client.clone(), test_url());
+
+        let err = block_on(reader.read_range(6, 5)).unwrap_err();
+        assert_eq!(ErrorKind::Timeout, err.kind());
+        assert_eq!(1, client.ranges.lock().len());
+    }
+
+    #[test]
+    fn range_read_retries() {
+        let client = FlakyClient::new(b"hello world", 2, 1);
+        let mut reader = HttpClientReader::new(client.clone(), test_url());
+
+        let out = block_on(reader.read_range(6, 5)).unwrap();
+        assert_eq!(b"world".as_slice(), out.as_ref());
+
+        let expected = vec![Some("bytes=6-10".to_string()); 2];
+        assert_eq!(expected, *client.ranges.lock());
+    }
+}
@@ -1,6 +1,9 @@
+pub mod compression;
 pub mod http;
+pub mod limit;
 pub mod location;
 pub mod memory;
+pub mod read_cache;
 pub mod s3;
 
 mod util;
@@ -68,6 +71,16 @@ pub trait FileSource: Sync + Send + Debug {
     /// For other data sources like json and csv, this can be skipped and the
     /// content can just be streamed.
     fn size(&mut self) -> BoxFuture<Result<usize>>;
+
+    /// Get an opaque version for the file's contents, like an etag or the
+    /// last modified time.
+    ///
+    /// This is used to check if anything cached for a file is still valid, and
+    /// is only available after `size` has been called. Returns None if the
+    /// source can't provide a version.
+    fn version(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Extension traits that provide convenience utilities.
@@ -108,6 +121,10 @@ impl FileSource for Box<dyn FileSource + '_> {
     fn size(&mut self) -> BoxFuture<Result<usize>> {
         self.as_mut().size()
     }
+
+    fn version(&self) -> Option<String> {
+        self.as_ref().version()
+    }
 }
 
 /// Asynchronous writes to some file source.
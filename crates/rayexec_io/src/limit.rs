@@ -0,0 +1,398 @@
+//! Limits on the number and rate of requests made to an object store.
+//!
+//! Scanning many small files can otherwise fire off more requests than the
+//! store allows, resulting in throttling errors. A single limiter is meant to
+//! be shared by every client for a source so that limits apply across files
+//! and queries.
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use rayexec_error::{RayexecError, Result};
+use reqwest::header::HeaderMap;
+use reqwest::{Request, StatusCode};
+
+use crate::http::{HttpClient, HttpResponse};
+
+/// Limits for requests to a single source.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequestLimits {
+    /// Max number of requests in flight at once. A request is in flight until
+    /// its response body has been read or dropped.
+    pub max_concurrent_requests: Option<usize>,
+    /// Max number of requests to send per second on average.
+    ///
+    /// Up to this many requests may be sent at once before being limited.
+    pub max_requests_per_second: Option<f64>,
+}
+
+impl RequestLimits {
+    pub const fn is_unlimited(&self) -> bool {
+        self.max_concurrent_requests.is_none() && self.max_requests_per_second.is_none()
+    }
+}
+
+/// Time source used for rate limiting.
+pub trait LimiterClock: Sync + Send + Debug {
+    /// Time elapsed since some fixed point.
+    fn elapsed(&self) -> Duration;
+
+    /// Wait for the given duration.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// Limits requests according to some `RequestLimits`.
+///
+/// Cloning is cheap, and clones share the same limits.
+#[derive(Debug, Clone)]
+pub struct RequestLimiter {
+    concurrency: Option<Arc<Semaphore>>,
+    rate: Option<Arc<TokenBucket>>,
+    clock: Arc<dyn LimiterClock>,
+}
+
+impl RequestLimiter {
+    pub fn try_new(limits: RequestLimits, clock: Arc<dyn LimiterClock>) -> Result<Self> {
+        let concurrency = match limits.max_concurrent_requests {
+            Some(0) => {
+                return Err(RayexecError::new(
+                    "Max concurrent requests must be greater than zero",
+                ))
+            }
+            Some(n) => Some(Arc::new(Semaphore::new(n))),
+            None => None,
+        };
+
+        let rate = match limits.max_requests_per_second {
+            Some(rate) if !(rate > 0.0 && rate.is_finite()) => {
+                return Err(RayexecError::new(format!(
+                    "Max requests per second must be a positive number, got {rate}"
+                )))
+            }
+            Some(rate) => Some(Arc::new(TokenBucket::new(rate, clock.elapsed()))),
+            None => None,
+        };
+
+        Ok(RequestLimiter {
+            concurrency,
+            rate,
+            clock,
+        })
+    }
+
+    /// Wait until a request is allowed to be sent.
+    ///
+    /// The returned permit should be held until the request completes.
+    pub async fn acquire(&self) -> RequestPermit {
+        let permit = match &self.concurrency {
+            Some(semaphore) => Some(semaphore.acquire().await),
+            None => None,
+        };
+
+        if let Some(bucket) = &self.rate {
+            let wait = bucket.take(self.clock.elapsed());
+            if !wait.is_zero() {
+                self.clock.sleep(wait).await;
+            }
+        }
+
+        RequestPermit { _permit: permit }
+    }
+}
+
+/// Permit for an in-flight request.
+#[derive(Debug)]
+pub struct RequestPermit {
+    _permit: Option<SemaphorePermit>,
+}
+
+/// Async semaphore for limiting concurrent requests.
+#[derive(Debug)]
+struct Semaphore {
+    state: Mutex<SemaphoreState>,
+}
+
+#[derive(Debug)]
+struct SemaphoreState {
+    available: usize,
+    waiters: Vec<Waker>,
+}
+
+impl Semaphore {
+    fn new(permits: usize) -> Self {
+        Semaphore {
+            state: Mutex::new(SemaphoreState {
+                available: permits,
+                waiters: Vec::new(),
+            }),
+        }
+    }
+
+    fn acquire(self: &Arc<Self>) -> Acquire {
+        Acquire {
+            semaphore: self.clone(),
+        }
+    }
+}
+
+struct Acquire {
+    semaphore: Arc<Semaphore>,
+}
+
+impl Future for Acquire {
+    type Output = SemaphorePermit;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.semaphore.state.lock();
+        if state.available > 0 {
+            state.available -= 1;
+            return Poll::Ready(SemaphorePermit {
+                semaphore: self.semaphore.clone(),
+            });
+        }
+        state.waiters.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[derive(Debug)]
+struct SemaphorePermit {
+    semaphore: Arc<Semaphore>,
+}
+
+impl Drop for SemaphorePermit {
+    fn drop(&mut self) {
+        let waiters = {
+            let mut state = self.semaphore.state.lock();
+            state.available += 1;
+            std::mem::take(&mut state.waiters)
+        };
+
+        // Wake everyone since a woken waiter may have been dropped before
+        // polling again.
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+}
+
+/// Token bucket refilling at a fixed rate.
+///
+/// Tokens are reserved up front, going negative if the bucket is empty, so
+/// that waiting requests are spaced out in the order they arrived.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Tokens added per second.
+    rate: f64,
+    /// Max tokens the bucket can hold.
+    capacity: f64,
+    state: Mutex<TokenBucketState>,
+}
+
+#[derive(Debug)]
+struct TokenBucketState {
+    tokens: f64,
+    last_refill: Duration,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Duration) -> Self {
+        let capacity = rate.max(1.0);
+        TokenBucket {
+            rate,
+            capacity,
+            state: Mutex::new(TokenBucketState {
+                tokens: capacity,
+                last_refill: now,
+            }),
+        }
+    }
+
+    /// Take a token, returning how long to wait before it's available.
+    fn take(&self, now: Duration) -> Duration {
+        let mut state = self.state.lock();
+
+        let elapsed = now.saturating_sub(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.rate).min(self.capacity);
+        state.last_refill = now;
+
+        state.tokens -= 1.0;
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / self.rate)
+        }
+    }
+}
+
+/// Http client that limits requests made through it.
+#[derive(Debug, Clone)]
+pub struct LimitedHttpClient<C: HttpClient> {
+    client: C,
+    limiter: RequestLimiter,
+}
+
+impl<C: HttpClient> LimitedHttpClient<C> {
+    pub fn new(client: C, limiter: RequestLimiter) -> Self {
+        LimitedHttpClient { client, limiter }
+    }
+}
+
+impl<C> HttpClient for LimitedHttpClient<C>
+where
+    C: HttpClient + 'static,
+    C::Response: 'static,
+{
+    type Response = LimitedResponse<C::Response>;
+    type RequestFuture = BoxFuture<'static, Result<Self::Response>>;
+
+    fn do_request(&self, request: Request) -> Self::RequestFuture {
+        let client = self.client.clone();
+        let limiter = self.limiter.clone();
+
+        Box::pin(async move {
+            let permit = limiter.acquire().await;
+            let response = client.do_request(request).await?;
+            Ok(LimitedResponse { response, permit })
+        })
+    }
+
+    fn retry_timeouts(&self) -> bool {
+        self.client.retry_timeouts()
+    }
+}
+
+/// Response that holds onto its request permit until the body is read.
+#[derive(Debug)]
+pub struct LimitedResponse<R> {
+    response: R,
+    permit: RequestPermit,
+}
+
+impl<R> HttpResponse for LimitedResponse<R>
+where
+    R: HttpResponse,
+    R::BytesFuture: 'static,
+    R::BytesStream: 'static,
+{
+    type BytesFuture = BoxFuture<'static, Result<Bytes>>;
+    type BytesStream = BoxStream<'static, Result<Bytes>>;
+
+    fn status(&self) -> StatusCode {
+        self.response.status()
+    }
+
+    fn headers(&self) -> &HeaderMap {
+        self.response.headers()
+    }
+
+    fn bytes(self) -> Self::BytesFuture {
+        let fut = self.response.bytes();
+        let permit = self.permit;
+        Box::pin(async move {
+            let result = fut.await;
+            std::mem::drop(permit);
+            result
+        })
+    }
+
+    fn bytes_stream(self) -> Self::BytesStream {
+        let permit = self.permit;
+        self.response
+            .bytes_stream()
+            .map(move |result| {
+                // Permit released once the stream is dropped.
+                let _ = &permit;
+                result
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+
+    /// Clock that only advances when sleeping.
+    #[derive(Debug, Default)]
+    struct ManualClock {
+        now: Mutex<Duration>,
+    }
+
+    impl LimiterClock for ManualClock {
+        fn elapsed(&self) -> Duration {
+            *self.now.lock()
+        }
+
+        fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+            *self.now.lock() += duration;
+            Box::pin(async {})
+        }
+    }
+
+    #[test]
+    fn concurrency_limited() {
+        let limits = RequestLimits {
+            max_concurrent_requests: Some(2),
+            max_requests_per_second: None,
+        };
+        let limiter = RequestLimiter::try_new(limits, Arc::new(ManualClock::default())).unwrap();
+
+        let p1 = limiter.acquire().now_or_never().unwrap();
+        let _p2 = limiter.acquire().now_or_never().unwrap();
+
+        let mut third = Box::pin(limiter.acquire());
+        assert!((&mut third).now_or_never().is_none());
+
+        std::mem::drop(p1);
+        assert!(third.now_or_never().is_some());
+    }
+
+    #[test]
+    fn rate_limited() {
+        let clock = Arc::new(ManualClock::default());
+        let limits = RequestLimits {
+            max_concurrent_requests: None,
+            max_requests_per_second: Some(2.0),
+        };
+        let limiter = RequestLimiter::try_new(limits, clock.clone()).unwrap();
+
+        // Burst up to the rate without waiting.
+        limiter.acquire().now_or_never().unwrap();
+        limiter.acquire().now_or_never().unwrap();
+        assert_eq!(Duration::ZERO, clock.elapsed());
+
+        // Then spaced out.
+        limiter.acquire().now_or_never().unwrap();
+        assert_eq!(Duration::from_millis(500), clock.elapsed());
+        limiter.acquire().now_or_never().unwrap();
+        assert_eq!(Duration::from_millis(1000), clock.elapsed());
+    }
+
+    #[test]
+    fn invalid_limits() {
+        let clock = Arc::new(ManualClock::default());
+
+        let limits = RequestLimits {
+            max_concurrent_requests: Some(0),
+            max_requests_per_second: None,
+        };
+        RequestLimiter::try_new(limits, clock.clone()).unwrap_err();
+
+        let limits = RequestLimits {
+            max_concurrent_requests: None,
+            max_requests_per_second: Some(-1.0),
+        };
+        RequestLimiter::try_new(limits, clock).unwrap_err();
+    }
+}
@@ -11,7 +11,7 @@ use crate::s3::credentials::AwsCredentials;
 /// Configuration for accessing various object stores.
 ///
 /// The variant used determines how we should interpret the file location.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AccessConfig {
     S3 {
         credentials: AwsCredentials,
@@ -65,7 +65,7 @@ impl ProtoConv for AccessConfig {
 
 /// Location for a file.
 // TODO: Glob/hive
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FileLocation {
     Url(Url),
     Path(PathBuf),
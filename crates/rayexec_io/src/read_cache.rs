@@ -0,0 +1,414 @@
+//! Process-wide cache for byte ranges read from remote files.
+//!
+//! Readers for remote files (http, s3) are wrapped with `CachedFileSource`,
+//! which serves repeated reads of the same byte range from the cache instead of
+//! making another request. This mostly helps with parquet, where every query
+//! reads the footer, and often the same hot row groups.
+//!
+//! Ranges are keyed by a file key (the url, plus anything that affects access
+//! like the credentials used), and by the file's size and version (etag or
+//! last modified time) as returned when fetching the size. Replaced objects get
+//! a new version and won't be served from the cache. Sources that don't provide
+//! a version bypass the cache since there's no way to tell if cached ranges are
+//! stale.
+//!
+//! The least recently used ranges are evicted once the cache grows past its
+//! max size. Setting the max size to zero disables caching.
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use parking_lot::Mutex;
+use rayexec_error::Result;
+
+use crate::FileSource;
+
+/// Default max size of the cache in bytes.
+pub const DEFAULT_READ_CACHE_BYTES: usize = 64 * 1024 * 1024;
+
+static READ_CACHE: LazyLock<ReadCache> = LazyLock::new(|| ReadCache::new(DEFAULT_READ_CACHE_BYTES));
+
+/// Statistics for the read cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReadCacheStats {
+    /// Number of cached ranges.
+    pub entries: usize,
+    /// Bytes currently held by the cache.
+    pub bytes: usize,
+    /// Number of reads served from the cache.
+    pub hits: usize,
+    /// Number of reads that needed to go to the underlying source.
+    pub misses: usize,
+}
+
+/// Set the max size of the cache in bytes, evicting ranges if needed.
+pub fn set_max_bytes(max_bytes: usize) {
+    READ_CACHE.set_max_bytes(max_bytes)
+}
+
+/// Get the max size of the cache in bytes.
+pub fn max_bytes() -> usize {
+    READ_CACHE.state.lock().max_bytes
+}
+
+/// Get the current stats for the cache.
+pub fn stats() -> ReadCacheStats {
+    READ_CACHE.stats()
+}
+
+/// A file source that caches byte ranges read from an underlying source.
+///
+/// Only `read_range` is cached, and only once `size` has been called and the
+/// underlying source reported a version. Streams are passed through as they're
+/// used for reading whole files once (e.g. csv).
+#[derive(Debug)]
+pub struct CachedFileSource<S: FileSource> {
+    inner: S,
+    /// Key identifying the file in the cache.
+    file_key: String,
+    /// Size of the file if we've fetched it.
+    size: Option<usize>,
+}
+
+impl<S: FileSource> CachedFileSource<S> {
+    /// Wrap a file source.
+    ///
+    /// The file key should uniquely identify the file, along with anything
+    /// that may affect access to it.
+    pub fn new(inner: S, file_key: impl Into<String>) -> Self {
+        CachedFileSource {
+            inner,
+            file_key: file_key.into(),
+            size: None,
+        }
+    }
+}
+
+impl<S: FileSource> FileSource for CachedFileSource<S> {
+    fn read_range(&mut self, start: usize, len: usize) -> BoxFuture<Result<Bytes>> {
+        let file_version = match self.inner.version() {
+            Some(version) => version,
+            None => return self.inner.read_range(start, len),
+        };
+
+        let key = RangeKey {
+            file_key: self.file_key.clone(),
+            file_version,
+            file_size: self.size,
+            start,
+            len,
+        };
+
+        if let Some(bs) = READ_CACHE.get(&key) {
+            return Box::pin(async move { Ok(bs) });
+        }
+
+        let fut = self.inner.read_range(start, len);
+        Box::pin(async move {
+            let bs = fut.await?;
+            READ_CACHE.insert(key, bs.clone());
+            Ok(bs)
+        })
+    }
+
+    fn read_stream(&mut self) -> BoxStream<'static, Result<Bytes>> {
+        self.inner.read_stream()
+    }
+
+    fn size(&mut self) -> BoxFuture<Result<usize>> {
+        // Size is always fetched from the source so that changes to the file
+        // size or version result in cache misses.
+        let size_slot = &mut self.size;
+        let fut = self.inner.size();
+        Box::pin(async move {
+            let size = fut.await?;
+            *size_slot = Some(size);
+            Ok(size)
+        })
+    }
+
+    fn version(&self) -> Option<String> {
+        self.inner.version()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RangeKey {
+    file_key: String,
+    file_version: String,
+    file_size: Option<usize>,
+    start: usize,
+    len: usize,
+}
+
+#[derive(Debug)]
+struct CachedRange {
+    bytes: Bytes,
+    /// Tick when this range was last used.
+    last_used: u64,
+}
+
+#[derive(Debug)]
+struct CacheState {
+    ranges: HashMap<RangeKey, CachedRange>,
+    /// Keys ordered by when they were last used.
+    lru: BTreeMap<u64, RangeKey>,
+    /// Incremented on every access.
+    tick: u64,
+    bytes: usize,
+    max_bytes: usize,
+    hits: usize,
+    misses: usize,
+}
+
+impl CacheState {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Evict the least recently used ranges until we're under the max size.
+    fn evict(&mut self) {
+        while self.bytes > self.max_bytes {
+            let key = match self.lru.pop_first() {
+                Some((_, key)) => key,
+                None => break,
+            };
+            if let Some(range) = self.ranges.remove(&key) {
+                self.bytes -= range.bytes.len();
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ReadCache {
+    state: Mutex<CacheState>,
+}
+
+impl ReadCache {
+    fn new(max_bytes: usize) -> Self {
+        ReadCache {
+            state: Mutex::new(CacheState {
+                ranges: HashMap::new(),
+                lru: BTreeMap::new(),
+                tick: 0,
+                bytes: 0,
+                max_bytes,
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    fn set_max_bytes(&self, max_bytes: usize) {
+        let mut state = self.state.lock();
+        state.max_bytes = max_bytes;
+        state.evict();
+    }
+
+    fn stats(&self) -> ReadCacheStats {
+        let state = self.state.lock();
+        ReadCacheStats {
+            entries: state.ranges.len(),
+            bytes: state.bytes,
+            hits: state.hits,
+            misses: state.misses,
+        }
+    }
+
+    fn get(&self, key: &RangeKey) -> Option<Bytes> {
+        let mut state = self.state.lock();
+        let tick = state.next_tick();
+
+        let (prev_tick, bytes) = match state.ranges.get_mut(key) {
+            Some(range) => {
+                let prev_tick = range.last_used;
+                range.last_used = tick;
+                (prev_tick, range.bytes.clone())
+            }
+            None => {
+                state.misses += 1;
+                return None;
+            }
+        };
+
+        state.hits += 1;
+        state.lru.remove(&prev_tick);
+        state.lru.insert(tick, key.clone());
+
+        Some(bytes)
+    }
+
+    fn insert(&self, key: RangeKey, bytes: Bytes) {
+        let mut state = self.state.lock();
+        if bytes.len() > state.max_bytes {
+            return;
+        }
+
+        let tick = state.next_tick();
+        state.bytes += bytes.len();
+        state.lru.insert(tick, key.clone());
+
+        let prev = state.ranges.insert(
+            key,
+            CachedRange {
+                bytes,
+                last_used: tick,
+            },
+        );
+
+        // Concurrent reads of the same range may both end up inserting.
+        if let Some(prev) = prev {
+            state.bytes -= prev.bytes.len();
+            state.lru.remove(&prev.last_used);
+        }
+
+        state.evict();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::memory::MemoryFileSystem;
+
+    /// Source that counts the number of range reads.
+    #[derive(Debug)]
+    struct CountingSource {
+        inner: Box<dyn FileSource>,
+        version: Option<String>,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl FileSource for CountingSource {
+        fn read_range(&mut self, start: usize, len: usize) -> BoxFuture<Result<Bytes>> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.inner.read_range(start, len)
+        }
+
+        fn read_stream(&mut self) -> BoxStream<'static, Result<Bytes>> {
+            self.inner.read_stream()
+        }
+
+        fn size(&mut self) -> BoxFuture<Result<usize>> {
+            self.inner.size()
+        }
+
+        fn version(&self) -> Option<String> {
+            self.version.clone()
+        }
+    }
+
+    fn cached_source(
+        file_key: &str,
+        content: &'static [u8],
+    ) -> (CachedFileSource<CountingSource>, Arc<AtomicUsize>) {
+        versioned_source(file_key, content, Some("v1"))
+    }
+
+    fn versioned_source(
+        file_key: &str,
+        content: &'static [u8],
+        version: Option<&str>,
+    ) -> (CachedFileSource<CountingSource>, Arc<AtomicUsize>) {
+        let fs = MemoryFileSystem::default();
+        fs.register_file("file".as_ref(), Bytes::from_static(content))
+            .unwrap();
+
+        let reads = Arc::new(AtomicUsize::new(0));
+        let source = CountingSource {
+            inner: fs.file_source("file".as_ref()).unwrap(),
+            version: version.map(|v| v.to_string()),
+            reads: reads.clone(),
+        };
+
+        (CachedFileSource::new(source, file_key), reads)
+    }
+
+    #[test]
+    fn repeated_reads_hit_cache() {
+        let (mut source, reads) = cached_source("test://repeated_reads", b"hello world");
+
+        let bs = block_on(source.read_range(6, 5)).unwrap();
+        assert_eq!(b"world".as_slice(), bs.as_ref());
+        let bs = block_on(source.read_range(6, 5)).unwrap();
+        assert_eq!(b"world".as_slice(), bs.as_ref());
+        assert_eq!(1, reads.load(Ordering::Relaxed));
+
+        // Different range needs to be read.
+        block_on(source.read_range(0, 5)).unwrap();
+        assert_eq!(2, reads.load(Ordering::Relaxed));
+
+        // Shared across sources for the same file.
+        let (mut other, other_reads) = cached_source("test://repeated_reads", b"hello world");
+        block_on(other.read_range(6, 5)).unwrap();
+        assert_eq!(0, other_reads.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn file_size_is_part_of_key() {
+        let (mut source, reads) = cached_source("test://file_size", b"hello world");
+        block_on(source.read_range(0, 5)).unwrap();
+
+        // Object replaced with a larger one.
+        let (mut source, reads2) = cached_source("test://file_size", b"hello world!");
+        assert_eq!(12, block_on(source.size()).unwrap());
+        block_on(source.read_range(0, 5)).unwrap();
+
+        assert_eq!(1, reads.load(Ordering::Relaxed));
+        assert_eq!(1, reads2.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn version_is_part_of_key() {
+        let (mut source, reads) = versioned_source("test://version", b"hello world", Some("v1"));
+        block_on(source.read_range(0, 5)).unwrap();
+
+        // Object replaced with one of the same size.
+        let (mut source, reads2) = versioned_source("test://version", b"jello world", Some("v2"));
+        let bs = block_on(source.read_range(0, 5)).unwrap();
+        assert_eq!(b"jello".as_slice(), bs.as_ref());
+
+        assert_eq!(1, reads.load(Ordering::Relaxed));
+        assert_eq!(1, reads2.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn unversioned_source_bypasses_cache() {
+        let (mut source, reads) = versioned_source("test://unversioned", b"hello world", None);
+        block_on(source.read_range(0, 5)).unwrap();
+        block_on(source.read_range(0, 5)).unwrap();
+
+        assert_eq!(2, reads.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = ReadCache::new(10);
+        let key = |start| RangeKey {
+            file_key: "file".to_string(),
+            file_version: "v1".to_string(),
+            file_size: None,
+            start,
+            len: 4,
+        };
+
+        cache.insert(key(0), Bytes::from_static(b"aaaa"));
+        cache.insert(key(4), Bytes::from_static(b"bbbb"));
+        cache.get(&key(0)).unwrap();
+        cache.insert(key(8), Bytes::from_static(b"cccc"));
+
+        assert!(cache.get(&key(0)).is_some());
+        assert!(cache.get(&key(4)).is_none());
+        assert!(cache.get(&key(8)).is_some());
+        assert_eq!(8, cache.stats().bytes);
+    }
+}
@@ -5,16 +5,24 @@ use bytes::{Buf, Bytes};
 use chrono::Utc;
 use credentials::{AwsCredentials, AwsRequestAuthorizer};
 use futures::future::BoxFuture;
-use futures::stream::{self, BoxStream};
-use futures::{Stream, StreamExt, TryStreamExt};
+use futures::stream::BoxStream;
+use futures::Stream;
 use list::{S3ListContents, S3ListResponse};
 use rayexec_error::{not_implemented, RayexecError, Result, ResultExt};
-use reqwest::header::{CONTENT_LENGTH, RANGE};
+use reqwest::header::CONTENT_LENGTH;
 use reqwest::{Method, Request, StatusCode};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::http::{format_range_header, read_text, HttpClient, HttpResponse};
+use crate::http::{
+    read_range_with_retries,
+    read_text,
+    resumable_stream,
+    version_from_headers,
+    HttpClient,
+    HttpResponse,
+};
+use crate::read_cache::CachedFileSource;
 use crate::FileSource;
 
 // TODO: Lots of cloning...
@@ -85,12 +93,17 @@ impl<C: HttpClient + 'static> S3Client<C> {
     }
 
     pub fn file_source(&self, location: S3Location, region: &str) -> Result<Box<dyn FileSource>> {
-        Ok(Box::new(S3Reader::new(
+        // Include the key id so that cached reads are only shared between
+        // readers using the same credentials.
+        let file_key = format!("{}#{}", location.url, self.credentials.key_id);
+        let reader = S3Reader::new(
             self.client.clone(),
             location,
             self.credentials.clone(),
             region.to_string(),
-        )))
+        );
+
+        Ok(Box::new(CachedFileSource::new(reader, file_key)))
     }
 
     pub fn list_prefix(
@@ -186,6 +199,8 @@ pub struct S3Reader<C: HttpClient> {
     location: S3Location,
     credentials: AwsCredentials,
     region: String,
+    /// Version of the object from the response when getting the size.
+    version: Option<String>,
 }
 
 impl<C: HttpClient + 'static> S3Reader<C> {
@@ -200,6 +215,7 @@ impl<C: HttpClient + 'static> S3Reader<C> {
             location,
             credentials,
             region,
+            version: None,
         }
     }
 
@@ -212,56 +228,49 @@ impl<C: HttpClient + 'static> S3Reader<C> {
 
         authorizer.authorize(request)
     }
+
+    /// Create a function for authorizing requests that can outlive the reader.
+    ///
+    /// Retried requests are authorized again so that they're signed with the
+    /// current time.
+    fn request_authorizer(&self) -> impl Fn(Request) -> Result<Request> + Send + Sync + 'static {
+        let credentials = self.credentials.clone();
+        let region = self.region.clone();
+
+        move |request| {
+            let authorizer = AwsRequestAuthorizer {
+                date: Utc::now(),
+                credentials: &credentials,
+                region: &region,
+            };
+            authorizer.authorize(request)
+        }
+    }
 }
 
 impl<C: HttpClient + 'static> FileSource for S3Reader<C> {
     fn read_range(&mut self, start: usize, len: usize) -> BoxFuture<Result<Bytes>> {
-        let range = format_range_header(start, start + len - 1);
-
-        let mut request = Request::new(Method::GET, self.location.url.clone());
-        request
-            .headers_mut()
-            .insert(RANGE, range.try_into().unwrap());
-
-        let client = self.client.clone();
-        let request = self.authorize_request(request);
-
-        Box::pin(async move {
-            let request = request?;
-            let resp = client.do_request(request).await?;
-
-            if resp.status() != StatusCode::PARTIAL_CONTENT {
-                return Err(RayexecError::new("Server does not support range requests"));
-            }
-
-            resp.bytes().await.context("failed to get response body")
-        })
+        Box::pin(read_range_with_retries(
+            self.client.clone(),
+            self.location.url.clone(),
+            start,
+            len,
+            self.request_authorizer(),
+        ))
     }
 
     fn read_stream(&mut self) -> BoxStream<'static, Result<Bytes>> {
-        let client = self.client.clone();
-        let request = Request::new(Method::GET, self.location.url.clone());
-        let request = self.authorize_request(request);
-
-        let stream = stream::once(async move {
-            let request = request?;
-            let resp = client.do_request(request).await?;
-
-            if resp.status() != StatusCode::OK {
-                let text = read_text(resp).await?;
-                return Err(RayexecError::new(format!("Stream results: {text}")));
-            }
-
-            Ok::<_, RayexecError>(resp.bytes_stream())
-        })
-        .try_flatten();
-
-        stream.boxed()
+        resumable_stream(
+            self.client.clone(),
+            self.location.url.clone(),
+            self.request_authorizer(),
+        )
     }
 
     fn size(&mut self) -> BoxFuture<Result<usize>> {
         let client = self.client.clone();
         let request = self.authorize_request(Request::new(Method::GET, self.location.url.clone()));
+        let version_slot = &mut self.version;
 
         Box::pin(async move {
             let request = request?;
@@ -274,6 +283,8 @@ impl<C: HttpClient + 'static> FileSource for S3Reader<C> {
                 )));
             }
 
+            *version_slot = version_from_headers(resp.headers());
+
             let len = match resp.headers().get(CONTENT_LENGTH) {
                 Some(header) => header
                     .to_str()
@@ -286,6 +297,10 @@ impl<C: HttpClient + 'static> FileSource for S3Reader<C> {
             Ok(len)
         })
     }
+
+    fn version(&self) -> Option<String> {
+        self.version.clone()
+    }
 }
 
 #[cfg(test)]
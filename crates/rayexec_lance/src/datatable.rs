@@ -0,0 +1,281 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::logical::statistics::StatisticsValue;
+use rayexec_execution::runtime::Runtime;
+use rayexec_execution::storage::table_storage::{
+    DataTable,
+    DataTableScan,
+    LimitedScan,
+    Projections,
+    TableStatistics,
+};
+use rayexec_io::location::AccessConfig;
+use rayexec_io::{FileProvider, FileSource};
+
+use crate::metadata::LanceFileMetadata;
+use crate::reader::AsyncFileReader;
+use crate::table::{Fragment, LanceTable};
+
+/// Data table implementation which parallelizes on fragments. During scanning,
+/// each returned scan object is responsible for distinct fragments to read.
+#[derive(Debug)]
+pub struct FragmentPartitionedDataTable<R: Runtime> {
+    pub table: Arc<LanceTable>,
+    pub conf: AccessConfig,
+    pub runtime: R,
+}
+
+impl<R: Runtime> FragmentPartitionedDataTable<R> {
+    /// Create scans for reading the provided fragments.
+    fn scan_fragments(
+        &self,
+        fragments: impl IntoIterator<Item = usize>,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let num_fields = self.table.schema.schema.fields.len();
+        let columns = match projections.column_indices {
+            Some(indices) => {
+                if let Some(idx) = indices.iter().find(|&&idx| idx >= num_fields) {
+                    return Err(RayexecError::new(format!(
+                        "Projected column {idx} out of range for {num_fields} columns"
+                    )));
+                }
+                indices
+            }
+            None => (0..num_fields).collect(),
+        };
+
+        let mut partitioned_fragments = vec![VecDeque::new(); num_partitions];
+
+        // Split fragments into individual partitions.
+        for (idx, fragment) in fragments.into_iter().enumerate() {
+            let partition = idx % num_partitions;
+            partitioned_fragments[partition].push_back(fragment);
+        }
+
+        Ok(partitioned_fragments
+            .into_iter()
+            .map(|fragments| {
+                Box::new(FragmentsScan {
+                    table: self.table.clone(),
+                    provider: self.runtime.file_provider(),
+                    conf: self.conf.clone(),
+                    columns: columns.clone(),
+                    fragments,
+                    batch_size,
+                    current: None,
+                }) as _
+            })
+            .collect())
+    }
+}
+
+impl<R: Runtime> DataTable for FragmentPartitionedDataTable<R> {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let num_fragments = self.table.fragments.len();
+        self.scan_fragments(0..num_fragments, projections, num_partitions, batch_size)
+    }
+
+    fn scan_limit(
+        &self,
+        projections: Projections,
+        limit: usize,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        // Only read enough fragments to cover the limit. Fragments with an
+        // unknown number of rows are assumed to be empty.
+        let mut num_rows = 0;
+        let num_fragments = self
+            .table
+            .fragments
+            .iter()
+            .take_while(|fragment| {
+                let needs_more = num_rows < limit;
+                num_rows += fragment.num_rows.unwrap_or(0);
+                needs_more
+            })
+            .count();
+
+        let scans =
+            self.scan_fragments(0..num_fragments, projections, num_partitions, batch_size)?;
+        Ok(LimitedScan::wrap_scans(scans, limit))
+    }
+
+    fn statistics(&self) -> BoxFuture<'_, Result<TableStatistics>> {
+        let statistics = TableStatistics {
+            num_rows: match self.table.num_rows() {
+                Some(num_rows) => StatisticsValue::Exact(num_rows),
+                None => StatisticsValue::Unknown,
+            },
+            ..Default::default()
+        };
+        Box::pin(async move { Ok(statistics) })
+    }
+}
+
+struct FragmentsScan {
+    table: Arc<LanceTable>,
+    provider: Arc<dyn FileProvider>,
+    conf: AccessConfig,
+    /// Schema fields to read.
+    columns: Vec<usize>,
+    /// Indices of fragments to read, in order.
+    fragments: VecDeque<usize>,
+    batch_size: usize,
+    /// Reader for the current fragment.
+    current: Option<FragmentReader>,
+}
+
+impl FragmentsScan {
+    async fn read_next(&mut self) -> Result<Option<Batch>> {
+        loop {
+            if let Some(reader) = &mut self.current {
+                match reader.read_next().await? {
+                    Some(batch) => return Ok(Some(batch)),
+                    None => self.current = None,
+                }
+            }
+
+            let fragment = match self.fragments.pop_front() {
+                Some(idx) => &self.table.fragments[idx],
+                None => return Ok(None),
+            };
+
+            self.current = Some(
+                FragmentReader::open(
+                    fragment,
+                    &self.table,
+                    &self.columns,
+                    self.provider.as_ref(),
+                    &self.conf,
+                    self.batch_size,
+                )
+                .await?,
+            );
+        }
+    }
+}
+
+impl DataTableScan for FragmentsScan {
+    fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
+        Box::pin(async { self.read_next().await })
+    }
+}
+
+impl fmt::Debug for FragmentsScan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FragmentsScan").finish_non_exhaustive()
+    }
+}
+
+/// Reads a fragment, combining columns read from each of its data files.
+/// A file reader along with the output position of each column it reads.
+type PositionedReader = (AsyncFileReader<Box<dyn FileSource>>, Vec<usize>);
+
+#[derive(Debug)]
+struct FragmentReader {
+    readers: Vec<PositionedReader>,
+    num_columns: usize,
+}
+
+impl FragmentReader {
+    async fn open(
+        fragment: &Fragment,
+        table: &LanceTable,
+        columns: &[usize],
+        provider: &dyn FileProvider,
+        conf: &AccessConfig,
+        batch_size: usize,
+    ) -> Result<Self> {
+        // Group columns by the file they're read from.
+        let mut file_columns: Vec<Vec<(usize, DataType)>> = vec![Vec::new(); fragment.files.len()];
+        let mut file_outputs: Vec<Vec<usize>> = vec![Vec::new(); fragment.files.len()];
+        for (output, &field_idx) in columns.iter().enumerate() {
+            let (file_idx, column) = fragment
+                .files
+                .iter()
+                .enumerate()
+                .find_map(|(file_idx, file)| {
+                    file.columns
+                        .iter()
+                        .find(|(idx, _)| *idx == field_idx)
+                        .map(|(_, column)| (file_idx, *column))
+                })
+                .ok_or_else(|| {
+                    RayexecError::new(format!(
+                        "Missing data file for column '{}'",
+                        table.schema.schema.fields[field_idx].name
+                    ))
+                })?;
+
+            let datatype = table.schema.schema.fields[field_idx].datatype.clone();
+            file_columns[file_idx].push((column, datatype));
+            file_outputs[file_idx].push(output);
+        }
+
+        let mut readers = Vec::new();
+        for (idx, (file, columns)) in fragment.files.iter().zip(file_columns).enumerate() {
+            // Still need one file for the row count if no columns are
+            // projected.
+            if columns.is_empty() && !(readers.is_empty() && idx == fragment.files.len() - 1) {
+                continue;
+            }
+
+            let mut source = provider.file_source(file.location.clone(), conf)?;
+            let size = source.size().await?;
+            let metadata = LanceFileMetadata::new_from_source(source.as_mut(), size).await?;
+            let reader = AsyncFileReader::try_new(source, Arc::new(metadata), columns, batch_size)?;
+
+            readers.push((reader, std::mem::take(&mut file_outputs[idx])));
+        }
+
+        Ok(FragmentReader {
+            readers,
+            num_columns: columns.len(),
+        })
+    }
+
+    async fn read_next(&mut self) -> Result<Option<Batch>> {
+        if self.readers.is_empty() {
+            return Ok(None);
+        }
+
+        // Fast path, single file producing columns in order.
+        if let [(reader, _)] = self.readers.as_mut_slice() {
+            return reader.read_next().await;
+        }
+
+        let mut arrays = vec![None; self.num_columns];
+        let mut num_rows = None;
+        for (reader, outputs) in &mut self.readers {
+            let batch = match reader.read_next().await? {
+                Some(batch) => batch,
+                None => return Ok(None),
+            };
+            if *num_rows.get_or_insert(batch.num_rows()) != batch.num_rows() {
+                return Err(RayexecError::new(
+                    "Data files in fragment have different row counts",
+                ));
+            }
+            for (array, &output) in batch.into_arrays().into_iter().zip(outputs.iter()) {
+                arrays[output] = Some(array);
+            }
+        }
+
+        Ok(Some(Batch::try_new(arrays.into_iter().flatten())?))
+    }
+}
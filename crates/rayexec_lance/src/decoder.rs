@@ -0,0 +1,454 @@
+use bytes::Bytes;
+use prost::Message;
+use rayexec_error::{not_implemented, RayexecError, Result, ResultExt};
+use rayexec_execution::arrays::array::{Array, ArrayData};
+use rayexec_execution::arrays::bitmap::Bitmap;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::storage::{
+    BooleanStorage,
+    GermanVarlenStorage,
+    ListItemMetadata,
+    ListStorage,
+    PrimitiveStorage,
+};
+
+use crate::proto::array_encoding::ArrayEncoding as Encoding;
+use crate::proto::buffer::BufferType;
+use crate::proto::encoding::Location;
+use crate::proto::nullable::Nullability;
+use crate::proto::{AnyMessage, ArrayEncoding, Binary, FixedSizeList, Flat};
+
+/// Get the array encoding for a page.
+pub fn page_encoding(encoding: Option<&crate::proto::Encoding>) -> Result<ArrayEncoding> {
+    match encoding.and_then(|e| e.location.as_ref()) {
+        Some(Location::Direct(direct)) => {
+            let any = AnyMessage::decode(direct.encoding.as_slice())
+                .context("failed to decode page encoding")?;
+            ArrayEncoding::decode(any.value.as_slice()).context("failed to decode array encoding")
+        }
+        None => Err(RayexecError::new(
+            "Missing or unsupported Lance page encoding",
+        )),
+    }
+}
+
+/// Decode a page containing `num_rows` values.
+///
+/// `buffers` holds the page's buffers in the order they're listed in the page
+/// metadata.
+pub fn decode_page(
+    encoding: &ArrayEncoding,
+    buffers: &[Bytes],
+    datatype: &DataType,
+    num_rows: usize,
+) -> Result<Array> {
+    let (data, validity) = decode_array_data(encoding, buffers, datatype, num_rows)?;
+    Ok(match validity {
+        Some(validity) => Array::new_with_validity_and_array_data(datatype.clone(), validity, data),
+        None => Array::new_with_array_data(datatype.clone(), data),
+    })
+}
+
+fn decode_array_data(
+    encoding: &ArrayEncoding,
+    buffers: &[Bytes],
+    datatype: &DataType,
+    num_rows: usize,
+) -> Result<(ArrayData, Option<Bitmap>)> {
+    match encoding.array_encoding.as_ref() {
+        Some(Encoding::Nullable(nullable)) => match nullable.nullability.as_ref() {
+            Some(Nullability::NoNulls(no_nulls)) => {
+                let values = required(no_nulls.values.as_ref())?;
+                decode_array_data(values, buffers, datatype, num_rows)
+            }
+            Some(Nullability::AllNulls(_)) => {
+                let data = datatype.physical_type()?.zeroed_array_data(num_rows);
+                Ok((data, Some(Bitmap::new_with_all_false(num_rows))))
+            }
+            Some(Nullability::SomeNulls(some_nulls)) => {
+                let validity =
+                    decode_bitmap(required(some_nulls.validity.as_ref())?, buffers, num_rows)?;
+                let (data, _) = decode_array_data(
+                    required(some_nulls.values.as_ref())?,
+                    buffers,
+                    datatype,
+                    num_rows,
+                )?;
+                Ok((data, Some(validity)))
+            }
+            None => Err(RayexecError::new(
+                "Missing nullability for nullable encoding",
+            )),
+        },
+        Some(Encoding::Flat(flat)) => Ok((decode_flat(flat, buffers, datatype, num_rows)?, None)),
+        Some(Encoding::FixedSizeList(list)) => Ok((
+            decode_fixed_size_list(list, buffers, datatype, num_rows)?,
+            None,
+        )),
+        Some(Encoding::Binary(binary)) => decode_binary(binary, buffers, datatype, num_rows),
+        Some(Encoding::List(_)) => not_implemented!("Lance list encoding"),
+        Some(Encoding::Struct(_)) => not_implemented!("Lance struct encoding"),
+        Some(Encoding::Dictionary(_)) => not_implemented!("Lance dictionary encoding"),
+        None => not_implemented!("Unknown Lance array encoding"),
+    }
+}
+
+fn required<T>(encoding: Option<&T>) -> Result<&T> {
+    encoding.ok_or_else(|| RayexecError::new("Missing nested Lance encoding"))
+}
+
+/// Get the buffer for a flat encoding, checking that it holds at least
+/// `num_values` values.
+fn flat_buffer<'a>(flat: &Flat, buffers: &'a [Bytes], num_values: usize) -> Result<&'a [u8]> {
+    if let Some(compression) = &flat.compression {
+        if !compression.scheme.is_empty() {
+            not_implemented!("Lance '{}' compression", compression.scheme);
+        }
+    }
+
+    let buffer = flat
+        .buffer
+        .as_ref()
+        .ok_or_else(|| RayexecError::new("Missing buffer for flat encoding"))?;
+    if buffer.buffer_type() != BufferType::Page {
+        not_implemented!("Lance {:?} buffers", buffer.buffer_type());
+    }
+    let buf = buffers
+        .get(buffer.buffer_index as usize)
+        .ok_or_else(|| RayexecError::new(format!("Missing page buffer {}", buffer.buffer_index)))?;
+
+    let num_bytes = (num_values * flat.bits_per_value as usize).div_ceil(8);
+    buf.get(..num_bytes).ok_or_else(|| {
+        RayexecError::new(format!(
+            "Page buffer too small, expected at least {num_bytes} bytes, got {}",
+            buf.len()
+        ))
+    })
+}
+
+fn decode_bitmap(encoding: &ArrayEncoding, buffers: &[Bytes], num_rows: usize) -> Result<Bitmap> {
+    match encoding.array_encoding.as_ref() {
+        Some(Encoding::Flat(flat)) if flat.bits_per_value == 1 => {
+            let buf = flat_buffer(flat, buffers, num_rows)?;
+            Bitmap::try_new(buf.to_vec(), num_rows)
+        }
+        _ => Err(RayexecError::new(
+            "Expected flat encoding with one bit per value for bitmap",
+        )),
+    }
+}
+
+fn decode_flat(
+    flat: &Flat,
+    buffers: &[Bytes],
+    datatype: &DataType,
+    num_rows: usize,
+) -> Result<ArrayData> {
+    let expected_bits = match datatype {
+        DataType::Boolean => 1,
+        DataType::Int8 | DataType::UInt8 => 8,
+        DataType::Int16 | DataType::UInt16 => 16,
+        DataType::Int32 | DataType::UInt32 | DataType::Float32 | DataType::Date32 => 32,
+        DataType::Int64
+        | DataType::UInt64
+        | DataType::Float64
+        | DataType::Date64
+        | DataType::Timestamp(_) => 64,
+        DataType::Decimal128(_) => 128,
+        other => {
+            return Err(RayexecError::new(format!(
+                "Unexpected flat encoding for {other}"
+            )))
+        }
+    };
+    if flat.bits_per_value != expected_bits {
+        return Err(RayexecError::new(format!(
+            "Expected {expected_bits} bits per value for {datatype}, got {}",
+            flat.bits_per_value
+        )));
+    }
+
+    let buf = flat_buffer(flat, buffers, num_rows)?;
+
+    Ok(match datatype {
+        DataType::Boolean => BooleanStorage::from(Bitmap::try_new(buf.to_vec(), num_rows)?).into(),
+        DataType::Int8 => primitive(buf, i8::from_le_bytes),
+        DataType::UInt8 => primitive(buf, u8::from_le_bytes),
+        DataType::Int16 => primitive(buf, i16::from_le_bytes),
+        DataType::UInt16 => primitive(buf, u16::from_le_bytes),
+        DataType::Int32 | DataType::Date32 => primitive(buf, i32::from_le_bytes),
+        DataType::UInt32 => primitive(buf, u32::from_le_bytes),
+        DataType::Float32 => primitive(buf, f32::from_le_bytes),
+        DataType::Int64 | DataType::Date64 | DataType::Timestamp(_) => {
+            primitive(buf, i64::from_le_bytes)
+        }
+        DataType::UInt64 => primitive(buf, u64::from_le_bytes),
+        DataType::Float64 => primitive(buf, f64::from_le_bytes),
+        _ => primitive(buf, i128::from_le_bytes),
+    })
+}
+
+/// Convert little endian bytes to primitive storage.
+///
+/// This is the hot path for vector columns, so it's kept to a single pass over
+/// the buffer.
+fn primitive<T, const N: usize>(buf: &[u8], from_le_bytes: fn([u8; N]) -> T) -> ArrayData
+where
+    PrimitiveStorage<T>: Into<ArrayData>,
+{
+    let values: Vec<T> = buf
+        .chunks_exact(N)
+        .map(|chunk| from_le_bytes(chunk.try_into().unwrap()))
+        .collect();
+    PrimitiveStorage::from(values).into()
+}
+
+fn decode_fixed_size_list(
+    list: &FixedSizeList,
+    buffers: &[Bytes],
+    datatype: &DataType,
+    num_rows: usize,
+) -> Result<ArrayData> {
+    let (element_type, size) = match datatype {
+        DataType::List(m) => (m.datatype.as_ref(), m.size),
+        other => {
+            return Err(RayexecError::new(format!(
+                "Unexpected fixed size list encoding for {other}"
+            )))
+        }
+    };
+    let dimension = list.dimension as usize;
+    if size != Some(dimension) {
+        return Err(RayexecError::new(format!(
+            "Fixed size list dimension {dimension} doesn't match type {datatype}"
+        )));
+    }
+
+    // Items are stored contiguously, so every list can point into a single
+    // child array.
+    let items = decode_page(
+        required(list.items.as_deref())?,
+        buffers,
+        element_type,
+        num_rows * dimension,
+    )?;
+    let metadata: Vec<_> = (0..num_rows)
+        .map(|idx| ListItemMetadata {
+            offset: (idx * dimension) as i32,
+            len: dimension as i32,
+        })
+        .collect();
+
+    Ok(ListStorage::try_new(metadata, items)?.into())
+}
+
+fn decode_binary(
+    binary: &Binary,
+    buffers: &[Bytes],
+    datatype: &DataType,
+    num_rows: usize,
+) -> Result<(ArrayData, Option<Bitmap>)> {
+    if !matches!(datatype, DataType::Utf8 | DataType::Binary) {
+        return Err(RayexecError::new(format!(
+            "Unexpected binary encoding for {datatype}"
+        )));
+    }
+
+    let offsets = decode_offsets(required(binary.indices.as_deref())?, buffers, num_rows)?;
+    let bytes = match required(binary.bytes.as_deref())?.array_encoding.as_ref() {
+        Some(Encoding::Flat(flat)) if flat.bits_per_value == 8 => {
+            let num_bytes = offsets
+                .iter()
+                .map(|&offset| remove_null_adjustment(offset, binary.null_adjustment).0)
+                .max()
+                .unwrap_or(0);
+            flat_buffer(flat, buffers, num_bytes as usize)?
+        }
+        _ => return Err(RayexecError::new("Expected flat encoding for binary bytes")),
+    };
+
+    let mut storage = GermanVarlenStorage::with_metadata_capacity(num_rows);
+    let mut validity = Bitmap::new_with_all_true(num_rows);
+
+    let mut start = 0;
+    for (idx, &offset) in offsets.iter().enumerate() {
+        let (end, valid) = remove_null_adjustment(offset, binary.null_adjustment);
+        if !valid {
+            validity.set_unchecked(idx, false);
+        }
+        let end = end as usize;
+
+        let value = bytes
+            .get(start..end)
+            .ok_or_else(|| RayexecError::new(format!("Invalid binary offsets {start}..{end}")))?;
+        storage.try_push(value)?;
+        start = end;
+    }
+
+    let validity = if validity.is_all_true() {
+        None
+    } else {
+        Some(validity)
+    };
+
+    Ok((storage.into(), validity))
+}
+
+/// Get the real end offset for a value, and if the value is valid.
+///
+/// Null values have their end offset shifted by the null adjustment.
+fn remove_null_adjustment(offset: u64, null_adjustment: u64) -> (u64, bool) {
+    if null_adjustment != 0 && offset >= null_adjustment {
+        (offset - null_adjustment, false)
+    } else {
+        (offset, true)
+    }
+}
+
+/// Decode unsigned offsets stored as 32 or 64 bit values.
+fn decode_offsets(
+    encoding: &ArrayEncoding,
+    buffers: &[Bytes],
+    num_rows: usize,
+) -> Result<Vec<u64>> {
+    match encoding.array_encoding.as_ref() {
+        Some(Encoding::Nullable(nullable)) => match nullable.nullability.as_ref() {
+            Some(Nullability::NoNulls(no_nulls)) => {
+                decode_offsets(required(no_nulls.values.as_ref())?, buffers, num_rows)
+            }
+            _ => Err(RayexecError::new("Binary offsets cannot contain nulls")),
+        },
+        Some(Encoding::Flat(flat)) => {
+            let buf = flat_buffer(flat, buffers, num_rows)?;
+            match flat.bits_per_value {
+                32 => Ok(buf
+                    .chunks_exact(4)
+                    .map(|c| u32::from_le_bytes(c.try_into().unwrap()) as u64)
+                    .collect()),
+                64 => Ok(buf
+                    .chunks_exact(8)
+                    .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+                    .collect()),
+                other => not_implemented!("Lance binary offsets with {other} bits per value"),
+            }
+        }
+        _ => not_implemented!("Lance binary offset encoding"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayexec_execution::arrays::datatype::ListTypeMeta;
+    use rayexec_execution::arrays::scalar::ScalarValue;
+    use rayexec_execution::arrays::testutil::assert_arrays_eq;
+
+    use super::*;
+    use crate::proto::nullable::SomeNull;
+    use crate::proto::{Buffer, Nullable};
+
+    fn flat(bits_per_value: u64, buffer_index: u32) -> ArrayEncoding {
+        ArrayEncoding {
+            array_encoding: Some(Encoding::Flat(Flat {
+                bits_per_value,
+                buffer: Some(Buffer {
+                    buffer_index,
+                    buffer_type: BufferType::Page as i32,
+                }),
+                compression: None,
+            })),
+        }
+    }
+
+    #[test]
+    fn flat_ints() {
+        let buf: Vec<u8> = [1_i32, -2, 3]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let out = decode_page(&flat(32, 0), &[buf.into()], &DataType::Int32, 3).unwrap();
+        assert_arrays_eq(&Array::from_iter([1_i32, -2, 3]), &out);
+    }
+
+    #[test]
+    fn flat_wrong_width() {
+        let buf = Bytes::from(vec![0; 8]);
+        assert!(decode_page(&flat(32, 0), &[buf], &DataType::Int64, 1).is_err());
+    }
+
+    #[test]
+    fn flat_buffer_too_small() {
+        let buf = Bytes::from(vec![0; 7]);
+        assert!(decode_page(&flat(64, 0), &[buf], &DataType::Int64, 1).is_err());
+    }
+
+    #[test]
+    fn nullable_some_nulls() {
+        let encoding = ArrayEncoding {
+            array_encoding: Some(Encoding::Nullable(Box::new(Nullable {
+                nullability: Some(Nullability::SomeNulls(Box::new(SomeNull {
+                    validity: Some(Box::new(flat(1, 0))),
+                    values: Some(Box::new(flat(64, 1))),
+                }))),
+            }))),
+        };
+        let validity = Bytes::from(vec![0b101]);
+        let values: Vec<u8> = [1.5_f64, 0.0, 3.5]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+
+        let out =
+            decode_page(&encoding, &[validity, values.into()], &DataType::Float64, 3).unwrap();
+        assert_arrays_eq(&Array::from_iter([Some(1.5_f64), None, Some(3.5)]), &out);
+    }
+
+    #[test]
+    fn fixed_size_list_vectors() {
+        let encoding = ArrayEncoding {
+            array_encoding: Some(Encoding::FixedSizeList(Box::new(FixedSizeList {
+                dimension: 2,
+                items: Some(Box::new(flat(32, 0))),
+            }))),
+        };
+        let values: Vec<u8> = [1.0_f32, 2.0, 3.0, 4.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let datatype = DataType::List(ListTypeMeta::new_fixed_size(DataType::Float32, 2));
+
+        let out = decode_page(&encoding, &[values.into()], &datatype, 2).unwrap();
+        assert_eq!(2, out.logical_len());
+        assert_eq!(
+            ScalarValue::List(vec![ScalarValue::Float32(3.0), ScalarValue::Float32(4.0)]),
+            out.logical_value(1).unwrap()
+        );
+
+        // Dimension mismatch.
+        let datatype = DataType::List(ListTypeMeta::new_fixed_size(DataType::Float32, 4));
+        let values = Bytes::from(vec![0; 16]);
+        assert!(decode_page(&encoding, &[values], &datatype, 1).is_err());
+    }
+
+    #[test]
+    fn binary_with_null_adjustment() {
+        let encoding = ArrayEncoding {
+            array_encoding: Some(Encoding::Binary(Box::new(Binary {
+                indices: Some(Box::new(flat(64, 0))),
+                bytes: Some(Box::new(flat(8, 1))),
+                null_adjustment: 100,
+            }))),
+        };
+        // "a", NULL, "a longer string value"
+        let offsets: Vec<u8> = [1_u64, 101, 22]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let bytes = Bytes::from_static(b"aa longer string value");
+
+        let out = decode_page(&encoding, &[offsets.into(), bytes], &DataType::Utf8, 3).unwrap();
+        assert_arrays_eq(
+            &Array::from_iter([Some("a"), None, Some("a longer string value")]),
+            &out,
+        );
+    }
+}
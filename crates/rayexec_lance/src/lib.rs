@@ -0,0 +1,77 @@
+pub mod decoder;
+pub mod metadata;
+pub mod reader;
+pub mod schema;
+pub mod table;
+
+mod datatable;
+mod read_lance;
+
+#[cfg(test)]
+mod testutil;
+
+#[allow(clippy::all)]
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/lance.rs"));
+}
+
+use rayexec_execution::datasource::{DataSource, DataSourceBuilder, FileHandler};
+use rayexec_execution::functions::table::TableFunction;
+use rayexec_execution::runtime::Runtime;
+use read_lance::ReadLance;
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanceDataSource<R> {
+    runtime: R,
+}
+
+impl<R: Runtime> DataSourceBuilder<R> for LanceDataSource<R> {
+    fn initialize(runtime: R) -> Box<dyn DataSource> {
+        Box::new(Self { runtime })
+    }
+}
+
+impl<R> LanceDataSource<R> {
+    fn file_regex() -> Regex {
+        RegexBuilder::new(r"^.*\.(lance)$")
+            .case_insensitive(true)
+            .build()
+            .expect("regex to build")
+    }
+}
+
+impl<R: Runtime> DataSource for LanceDataSource<R> {
+    fn initialize_table_functions(&self) -> Vec<Box<dyn TableFunction>> {
+        vec![Box::new(ReadLance {
+            runtime: self.runtime.clone(),
+        })]
+    }
+
+    fn file_handlers(&self) -> Vec<FileHandler> {
+        vec![FileHandler {
+            regex: Self::file_regex(),
+            table_func: Box::new(ReadLance {
+                runtime: self.runtime.clone(),
+            }),
+            copy_to: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_regex() {
+        let regex = LanceDataSource::<()>::file_regex();
+
+        assert!(regex.is_match("file.lance"));
+        assert!(regex.is_match("dataset.LANCE"));
+        assert!(regex.is_match("dir/dataset.lance"));
+
+        assert!(!regex.is_match("file.parquet"));
+        assert!(!regex.is_match("dataset.lance/_versions"));
+    }
+}
@@ -0,0 +1,184 @@
+use bytes::Bytes;
+use prost::Message;
+use rayexec_error::{not_implemented, RayexecError, Result, ResultExt};
+use rayexec_io::FileSource;
+use tracing::trace;
+
+use crate::proto::{ColumnMetadata, FileDescriptor};
+use crate::schema::LanceSchema;
+
+pub const MAGIC: &[u8; 4] = b"LANC";
+
+/// Size of the fixed footer at the end of every file.
+pub const FOOTER_SIZE: usize = 40;
+
+/// Number of bytes to read from the end of the file when fetching the footer.
+/// Typically large enough to include the column metadata too.
+const TAIL_READ_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct LanceFileMetadata {
+    /// Major and minor version of the file format.
+    pub version: (u16, u16),
+    pub num_rows: usize,
+    pub schema: LanceSchema,
+    /// Metadata for each column in the file.
+    pub columns: Vec<ColumnMetadata>,
+}
+
+/// The fixed size footer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Footer {
+    column_meta_start: usize,
+    column_meta_offsets_start: usize,
+    global_buffer_offsets_start: usize,
+    num_global_buffers: usize,
+    num_columns: usize,
+    major_version: u16,
+    minor_version: u16,
+}
+
+impl Footer {
+    fn decode(buf: &[u8]) -> Result<Self> {
+        if buf.len() != FOOTER_SIZE || &buf[36..] != MAGIC {
+            return Err(RayexecError::new("Missing Lance magic in footer"));
+        }
+
+        Ok(Footer {
+            column_meta_start: read_u64(buf, 0),
+            column_meta_offsets_start: read_u64(buf, 8),
+            global_buffer_offsets_start: read_u64(buf, 16),
+            num_global_buffers: u32::from_le_bytes(buf[24..28].try_into().unwrap()) as usize,
+            num_columns: u32::from_le_bytes(buf[28..32].try_into().unwrap()) as usize,
+            major_version: u16::from_le_bytes(buf[32..34].try_into().unwrap()),
+            minor_version: u16::from_le_bytes(buf[34..36].try_into().unwrap()),
+        })
+    }
+}
+
+impl LanceFileMetadata {
+    /// Loads Lance file metadata from an async source.
+    pub async fn new_from_source(reader: &mut dyn FileSource, size: usize) -> Result<Self> {
+        if size < FOOTER_SIZE {
+            return Err(RayexecError::new("File size is too small"));
+        }
+
+        let tail_len = size.min(TAIL_READ_SIZE);
+        let mut tail_start = size - tail_len;
+        let mut tail = reader.read_range(tail_start, tail_len).await?;
+        trace!("read lance tail bytes");
+
+        let footer = Footer::decode(&tail[tail_len - FOOTER_SIZE..])?;
+        match (footer.major_version, footer.minor_version) {
+            // 2.0 was originally written as 0.3.
+            (0, 3) | (2, 0) => (),
+            (0, _) => not_implemented!("Reading legacy Lance files"),
+            (major, minor) => not_implemented!("Reading Lance file format version {major}.{minor}"),
+        }
+
+        // Everything from the start of the column metadata to the end of the
+        // file.
+        if footer.column_meta_start > size - FOOTER_SIZE {
+            return Err(RayexecError::new("Column metadata start exceeds file size"));
+        }
+        if footer.column_meta_start < tail_start {
+            tail_start = footer.column_meta_start;
+            tail = reader.read_range(tail_start, size - tail_start).await?;
+        }
+
+        let slice_tail = |offset: usize, len: usize| -> Result<Bytes> {
+            let start = offset.checked_sub(tail_start);
+            match start {
+                Some(start) if start + len <= tail.len() => Ok(tail.slice(start..start + len)),
+                _ => Err(RayexecError::new(format!(
+                    "Metadata range {offset}..{} out of bounds",
+                    offset + len
+                ))),
+            }
+        };
+
+        let column_offsets = slice_tail(footer.column_meta_offsets_start, footer.num_columns * 16)?;
+        let columns = (0..footer.num_columns)
+            .map(|idx| {
+                let position = read_u64(&column_offsets, idx * 16);
+                let len = read_u64(&column_offsets, idx * 16 + 8);
+                ColumnMetadata::decode(slice_tail(position, len)?)
+                    .context("failed to decode column metadata")
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if footer.num_global_buffers == 0 {
+            return Err(RayexecError::new("Lance file missing schema global buffer"));
+        }
+        let buffer_offsets = slice_tail(
+            footer.global_buffer_offsets_start,
+            footer.num_global_buffers * 16,
+        )?;
+        // The first global buffer holds the schema. It's written before the
+        // column metadata so likely isn't part of what we've read so far.
+        let position = read_u64(&buffer_offsets, 0);
+        let len = read_u64(&buffer_offsets, 8);
+        let descriptor_buf = match slice_tail(position, len) {
+            Ok(buf) => buf,
+            Err(_) => {
+                if position + len > size {
+                    return Err(RayexecError::new("Schema buffer exceeds file size"));
+                }
+                reader.read_range(position, len).await?
+            }
+        };
+        let descriptor =
+            FileDescriptor::decode(descriptor_buf).context("failed to decode file descriptor")?;
+
+        let schema = LanceSchema::try_from_fields(
+            &descriptor
+                .schema
+                .ok_or_else(|| RayexecError::new("Lance file missing schema"))?
+                .fields,
+        )?;
+
+        Ok(LanceFileMetadata {
+            version: (footer.major_version, footer.minor_version),
+            num_rows: descriptor.length as usize,
+            schema,
+            columns,
+        })
+    }
+}
+
+fn read_u64(buf: &[u8], offset: usize) -> usize {
+    u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap()) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_footer() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&100_u64.to_le_bytes());
+        buf.extend_from_slice(&200_u64.to_le_bytes());
+        buf.extend_from_slice(&232_u64.to_le_bytes());
+        buf.extend_from_slice(&1_u32.to_le_bytes());
+        buf.extend_from_slice(&2_u32.to_le_bytes());
+        buf.extend_from_slice(&0_u16.to_le_bytes());
+        buf.extend_from_slice(&3_u16.to_le_bytes());
+        buf.extend_from_slice(MAGIC);
+
+        let footer = Footer::decode(&buf).unwrap();
+        let expected = Footer {
+            column_meta_start: 100,
+            column_meta_offsets_start: 200,
+            global_buffer_offsets_start: 232,
+            num_global_buffers: 1,
+            num_columns: 2,
+            major_version: 0,
+            minor_version: 3,
+        };
+        assert_eq!(expected, footer);
+
+        buf[39] = b'X';
+        assert!(Footer::decode(&buf).is_err());
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::executor::scalar::concat;
+use rayexec_io::FileSource;
+use tracing::trace;
+
+use crate::decoder::{decode_page, page_encoding};
+use crate::metadata::LanceFileMetadata;
+
+/// Reads batches from a single Lance file.
+///
+/// Pages for each column are decoded independently since page boundaries don't
+/// line up across columns. Decoded pages are buffered until enough rows are
+/// available for a batch.
+#[derive(Debug)]
+pub struct AsyncFileReader<R: FileSource> {
+    reader: R,
+    metadata: Arc<LanceFileMetadata>,
+    columns: Vec<ColumnReader>,
+    batch_size: usize,
+    /// Number of rows left to produce.
+    remaining: usize,
+}
+
+#[derive(Debug)]
+struct ColumnReader {
+    /// Index of the column in the file.
+    column: usize,
+    datatype: DataType,
+    /// Index of the next page to read.
+    next_page: usize,
+    /// Decoded pages that haven't been fully returned yet.
+    decoded: VecDeque<Array>,
+    /// Total number of rows in `decoded`.
+    decoded_rows: usize,
+}
+
+impl<R: FileSource> AsyncFileReader<R> {
+    /// Create a new reader for the given file columns and their types.
+    pub fn try_new(
+        reader: R,
+        metadata: Arc<LanceFileMetadata>,
+        columns: Vec<(usize, DataType)>,
+        batch_size: usize,
+    ) -> Result<Self> {
+        let num_columns = metadata.columns.len();
+        let columns = columns
+            .into_iter()
+            .map(|(column, datatype)| {
+                if column >= num_columns {
+                    return Err(RayexecError::new(format!(
+                        "Column {column} out of range for file with {num_columns} columns"
+                    )));
+                }
+                Ok(ColumnReader {
+                    column,
+                    datatype,
+                    next_page: 0,
+                    decoded: VecDeque::new(),
+                    decoded_rows: 0,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(AsyncFileReader {
+            reader,
+            remaining: metadata.num_rows,
+            metadata,
+            columns,
+            batch_size,
+        })
+    }
+
+    pub async fn read_next(&mut self) -> Result<Option<Batch>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        let num_rows = self.batch_size.min(self.remaining);
+        self.remaining -= num_rows;
+
+        if self.columns.is_empty() {
+            return Ok(Some(Batch::empty_with_num_rows(num_rows)));
+        }
+
+        let mut arrays = Vec::with_capacity(self.columns.len());
+        for idx in 0..self.columns.len() {
+            while self.columns[idx].decoded_rows < num_rows {
+                self.read_page(idx).await?;
+            }
+            arrays.push(self.columns[idx].take(num_rows)?);
+        }
+
+        Ok(Some(Batch::try_new(arrays)?))
+    }
+
+    /// Read and decode the next page for a column.
+    async fn read_page(&mut self, idx: usize) -> Result<()> {
+        let column = &self.columns[idx];
+        let page = self.metadata.columns[column.column]
+            .pages
+            .get(column.next_page)
+            .ok_or_else(|| {
+                RayexecError::new(format!(
+                    "Column {} has fewer rows than the file",
+                    column.column
+                ))
+            })?;
+
+        if page.buffer_offsets.len() != page.buffer_sizes.len() {
+            return Err(RayexecError::new(
+                "Mismatched page buffer offsets and sizes",
+            ));
+        }
+
+        // Buffers for a page are typically contiguous, fetch them all with a
+        // single read.
+        let ranges: Vec<_> = page
+            .buffer_offsets
+            .iter()
+            .zip(&page.buffer_sizes)
+            .map(|(&offset, &size)| (offset as usize, (offset + size) as usize))
+            .collect();
+        let start = ranges.iter().map(|r| r.0).min().unwrap_or(0);
+        let end = ranges.iter().map(|r| r.1).max().unwrap_or(0);
+
+        let buf = self.reader.read_range(start, end - start).await?;
+        let buffers: Vec<_> = ranges
+            .iter()
+            .map(|(offset, end)| buf.slice(offset - start..end - start))
+            .collect();
+        trace!(column = %column.column, page = %column.next_page, "read lance page");
+
+        let encoding = page_encoding(page.encoding.as_ref())?;
+        let num_rows = page.length as usize;
+        let array = decode_page(&encoding, &buffers, &column.datatype, num_rows)?;
+
+        let column = &mut self.columns[idx];
+        column.next_page += 1;
+        column.decoded_rows += num_rows;
+        column.decoded.push_back(array);
+
+        Ok(())
+    }
+}
+
+impl ColumnReader {
+    /// Take `count` rows from the decoded pages.
+    fn take(&mut self, count: usize) -> Result<Array> {
+        let mut arrays = Vec::new();
+        let mut needed = count;
+
+        while needed > 0 {
+            let array = self
+                .decoded
+                .pop_front()
+                .ok_or_else(|| RayexecError::new("Not enough decoded rows"))?;
+            let len = array.logical_len();
+            if len <= needed {
+                needed -= len;
+                arrays.push(array);
+            } else {
+                arrays.push(array.slice(0, needed));
+                self.decoded.push_front(array.slice(needed, len - needed));
+                needed = 0;
+            }
+        }
+        self.decoded_rows -= count;
+
+        if arrays.len() == 1 {
+            Ok(arrays.pop().unwrap())
+        } else {
+            concat(&arrays.iter().collect::<Vec<_>>())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use futures::executor::block_on;
+    use rayexec_execution::arrays::datatype::ListTypeMeta;
+    use rayexec_execution::arrays::scalar::ScalarValue;
+    use rayexec_execution::arrays::testutil::assert_batches_eq;
+    use rayexec_io::memory::MemoryFileSystem;
+
+    use super::*;
+    use crate::testutil::{write_lance, TestColumn};
+
+    fn test_columns() -> Vec<TestColumn> {
+        vec![
+            TestColumn::Int64(vec![Some(1), Some(2), None, Some(4), Some(5)]),
+            TestColumn::Utf8(vec![
+                Some("a"),
+                None,
+                Some("a longer string value"),
+                Some("d"),
+                Some("e"),
+            ]),
+            TestColumn::Vector(2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]),
+        ]
+    }
+
+    fn open(buf: Vec<u8>) -> (Box<dyn FileSource>, LanceFileMetadata) {
+        let fs = MemoryFileSystem::default();
+        fs.register_file(Path::new("test.lance"), buf.into())
+            .unwrap();
+
+        let mut source = fs.file_source(Path::new("test.lance")).unwrap();
+        let size = block_on(source.size()).unwrap();
+        let metadata = block_on(LanceFileMetadata::new_from_source(source.as_mut(), size)).unwrap();
+
+        (source, metadata)
+    }
+
+    fn read_all(columns: &[usize], page_size: usize, batch_size: usize) -> Vec<Batch> {
+        let buf = write_lance(&["id", "name", "vector"], &test_columns(), page_size);
+        let (source, metadata) = open(buf);
+
+        let columns = columns
+            .iter()
+            .map(|&idx| (idx, metadata.schema.schema.fields[idx].datatype.clone()))
+            .collect();
+        let mut reader =
+            AsyncFileReader::try_new(source, Arc::new(metadata), columns, batch_size).unwrap();
+
+        let mut batches = Vec::new();
+        while let Some(batch) = block_on(reader.read_next()).unwrap() {
+            batches.push(batch);
+        }
+        batches
+    }
+
+    fn vector(values: &[f32]) -> ScalarValue<'static> {
+        ScalarValue::List(values.iter().map(|&v| ScalarValue::Float32(v)).collect())
+    }
+
+    #[test]
+    fn read_metadata() {
+        let buf = write_lance(&["id", "name", "vector"], &test_columns(), 2);
+        let (_, metadata) = open(buf);
+
+        assert_eq!(5, metadata.num_rows);
+        assert_eq!(3, metadata.columns.len());
+        assert_eq!(3, metadata.columns[0].pages.len());
+        assert_eq!(
+            DataType::List(ListTypeMeta::new_fixed_size(DataType::Float32, 2)),
+            metadata.schema.schema.fields[2].datatype
+        );
+    }
+
+    #[test]
+    fn read_single_batch() {
+        let batches = read_all(&[0, 1], 2, 1024);
+        assert_eq!(1, batches.len());
+
+        let expected = Batch::try_new([
+            Array::from_iter([Some(1_i64), Some(2), None, Some(4), Some(5)]),
+            Array::from_iter([
+                Some("a"),
+                None,
+                Some("a longer string value"),
+                Some("d"),
+                Some("e"),
+            ]),
+        ])
+        .unwrap();
+        assert_batches_eq(&expected, &batches[0]);
+    }
+
+    #[test]
+    fn read_batches_across_pages() {
+        // Pages of 2 rows, batches of 3 rows.
+        let batches = read_all(&[2, 0], 2, 3);
+
+        let num_rows: Vec<_> = batches.iter().map(|b| b.num_rows()).collect();
+        assert_eq!(vec![3, 2], num_rows);
+
+        let vectors = batches[0].column(0).unwrap();
+        assert_eq!(vector(&[1.0, 2.0]), vectors.logical_value(0).unwrap());
+        assert_eq!(vector(&[5.0, 6.0]), vectors.logical_value(2).unwrap());
+
+        let vectors = batches[1].column(0).unwrap();
+        assert_eq!(vector(&[9.0, 10.0]), vectors.logical_value(1).unwrap());
+
+        let ids = batches[1].column(1).unwrap();
+        assert_eq!(ScalarValue::Int64(4), ids.logical_value(0).unwrap());
+    }
+
+    #[test]
+    fn read_no_columns() {
+        let batches = read_all(&[], 2, 4);
+
+        let num_rows: Vec<_> = batches.iter().map(|b| b.num_rows()).collect();
+        assert_eq!(vec![4, 1], num_rows);
+    }
+}
@@ -0,0 +1,165 @@
+use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_execution::arrays::datatype::{
+    DataType,
+    DecimalTypeMeta,
+    ListTypeMeta,
+    TimeUnit,
+    TimestampTypeMeta,
+};
+use rayexec_execution::arrays::field::{Field, Schema};
+
+use crate::proto;
+
+/// Schema of a Lance file or dataset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanceSchema {
+    pub schema: Schema,
+    /// Lance field ids for each field in the schema.
+    pub field_ids: Vec<i32>,
+}
+
+impl LanceSchema {
+    /// Create a schema from a flattened list of Lance fields.
+    ///
+    /// Only top-level fields are included. Children of fixed size lists are
+    /// described by the list's logical type.
+    pub fn try_from_fields(fields: &[proto::Field]) -> Result<Self> {
+        let mut schema_fields = Vec::new();
+        let mut field_ids = Vec::new();
+
+        for field in fields.iter().filter(|f| f.parent_id == -1) {
+            let datatype = parse_logical_type(&field.logical_type)?;
+            schema_fields.push(Field::new(&field.name, datatype, field.nullable));
+            field_ids.push(field.id);
+        }
+
+        Ok(LanceSchema {
+            schema: Schema::new(schema_fields),
+            field_ids,
+        })
+    }
+}
+
+/// Parse a Lance logical type string.
+pub fn parse_logical_type(logical_type: &str) -> Result<DataType> {
+    Ok(match logical_type {
+        "null" => DataType::Null,
+        "bool" => DataType::Boolean,
+        "int8" => DataType::Int8,
+        "int16" => DataType::Int16,
+        "int32" => DataType::Int32,
+        "int64" => DataType::Int64,
+        "uint8" => DataType::UInt8,
+        "uint16" => DataType::UInt16,
+        "uint32" => DataType::UInt32,
+        "uint64" => DataType::UInt64,
+        "float" => DataType::Float32,
+        "double" => DataType::Float64,
+        "string" | "large_string" => DataType::Utf8,
+        "binary" | "large_binary" => DataType::Binary,
+        "date32:day" => DataType::Date32,
+        "date64:ms" => DataType::Date64,
+        other => {
+            if let Some(rest) = other.strip_prefix("timestamp:") {
+                // Timezone follows the unit, which we currently ignore.
+                let unit = match rest.split(':').next() {
+                    Some("s") => TimeUnit::Second,
+                    Some("ms") => TimeUnit::Millisecond,
+                    Some("us") => TimeUnit::Microsecond,
+                    Some("ns") => TimeUnit::Nanosecond,
+                    _ => return Err(invalid_type(other)),
+                };
+                return Ok(DataType::Timestamp(TimestampTypeMeta::new(unit)));
+            }
+
+            if let Some(rest) = other.strip_prefix("decimal:128:") {
+                let (precision, scale) = rest.split_once(':').ok_or_else(|| invalid_type(other))?;
+                let precision = precision.parse().map_err(|_| invalid_type(other))?;
+                let scale = scale.parse().map_err(|_| invalid_type(other))?;
+                return Ok(DataType::Decimal128(DecimalTypeMeta::new(precision, scale)));
+            }
+
+            if let Some(rest) = other.strip_prefix("fixed_size_list:") {
+                // The child type may contain ':' itself, the size is always
+                // last.
+                let (child, size) = rest.rsplit_once(':').ok_or_else(|| invalid_type(other))?;
+                let size = size.parse().map_err(|_| invalid_type(other))?;
+                let child = parse_logical_type(child)?;
+                if matches!(child, DataType::List(_)) {
+                    not_implemented!("Reading nested Lance lists");
+                }
+                return Ok(DataType::List(ListTypeMeta::new_fixed_size(child, size)));
+            }
+
+            not_implemented!("Reading Lance columns with type '{other}'")
+        }
+    })
+}
+
+fn invalid_type(logical_type: &str) -> RayexecError {
+    RayexecError::new(format!("Invalid Lance logical type: '{logical_type}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logical_types() {
+        assert_eq!(DataType::Int64, parse_logical_type("int64").unwrap());
+        assert_eq!(DataType::Utf8, parse_logical_type("large_string").unwrap());
+        assert_eq!(
+            DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond)),
+            parse_logical_type("timestamp:us:UTC").unwrap()
+        );
+        assert_eq!(
+            DataType::Decimal128(DecimalTypeMeta::new(10, 2)),
+            parse_logical_type("decimal:128:10:2").unwrap()
+        );
+        assert_eq!(
+            DataType::List(ListTypeMeta::new_fixed_size(DataType::Float32, 128)),
+            parse_logical_type("fixed_size_list:float:128").unwrap()
+        );
+        assert_eq!(
+            DataType::List(ListTypeMeta::new_fixed_size(
+                DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Second)),
+                2
+            )),
+            parse_logical_type("fixed_size_list:timestamp:s:-:2").unwrap()
+        );
+
+        assert!(parse_logical_type("struct").is_err());
+        assert!(parse_logical_type("fixed_size_list:float").is_err());
+    }
+
+    #[test]
+    fn top_level_fields() {
+        let field = |id, parent_id, name: &str, logical_type: &str| proto::Field {
+            id,
+            parent_id,
+            name: name.to_string(),
+            logical_type: logical_type.to_string(),
+            nullable: true,
+            ..Default::default()
+        };
+        let fields = [
+            field(0, -1, "id", "int64"),
+            field(1, -1, "vector", "fixed_size_list:float:3"),
+            field(2, 1, "item", "float"),
+            field(3, -1, "text", "string"),
+        ];
+
+        let schema = LanceSchema::try_from_fields(&fields).unwrap();
+        let expected = Schema::new([
+            Field::new("id", DataType::Int64, true),
+            Field::new(
+                "vector",
+                DataType::List(ListTypeMeta::new_fixed_size(DataType::Float32, 3)),
+                true,
+            ),
+            Field::new("text", DataType::Utf8, true),
+        ]);
+        assert_eq!(expected, schema.schema);
+        assert_eq!(vec![0, 1, 3], schema.field_ids);
+    }
+}
@@ -0,0 +1,276 @@
+use futures::TryStreamExt;
+use prost::Message;
+use rayexec_error::{not_implemented, RayexecError, Result, ResultExt};
+use rayexec_io::location::{AccessConfig, FileLocation};
+use rayexec_io::{FileProvider, FileSourceExt};
+use tracing::trace;
+
+use crate::metadata::{LanceFileMetadata, MAGIC};
+use crate::proto::Manifest;
+use crate::schema::LanceSchema;
+
+/// Relative path to manifest files in a dataset.
+const VERSIONS_PATH: &str = "_versions";
+
+/// Relative path to data files in a dataset.
+const DATA_PATH: &str = "data";
+
+/// A Lance dataset or a single Lance file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanceTable {
+    pub schema: LanceSchema,
+    pub fragments: Vec<Fragment>,
+}
+
+/// Rows stored across one or more data files. Every file in a fragment has the
+/// same number of rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fragment {
+    /// Number of rows in the fragment, if known.
+    pub num_rows: Option<usize>,
+    pub files: Vec<FragmentFile>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FragmentFile {
+    pub location: FileLocation,
+    /// Pairs of (schema field index, file column index) for the fields stored
+    /// in this file.
+    pub columns: Vec<(usize, usize)>,
+}
+
+impl LanceTable {
+    /// Load a table from either a dataset directory or a single data file.
+    ///
+    /// The location is treated as a dataset if it contains any manifests.
+    pub async fn load(
+        location: FileLocation,
+        provider: &dyn FileProvider,
+        conf: &AccessConfig,
+    ) -> Result<Self> {
+        let versions = location.join([VERSIONS_PATH])?;
+        let paths: Vec<String> = provider
+            .list_prefix(versions.clone(), conf)
+            .try_concat()
+            .await?;
+
+        match latest_manifest(&paths) {
+            Some(path) => {
+                trace!(%path, "reading lance manifest");
+                let buf = provider
+                    .file_source(versions.join([path])?, conf)?
+                    .read_stream_all()
+                    .await?;
+                let manifest = decode_manifest(&buf)?;
+                Self::try_from_manifest(&location, manifest)
+            }
+            None => {
+                let mut source = provider.file_source(location.clone(), conf)?;
+                let size = source.size().await?;
+                let metadata = LanceFileMetadata::new_from_source(source.as_mut(), size).await?;
+                Ok(Self::from_file(location, &metadata))
+            }
+        }
+    }
+
+    /// Create a table for a single file.
+    pub fn from_file(location: FileLocation, metadata: &LanceFileMetadata) -> Self {
+        // Every top-level field we can read is stored in a single column.
+        let num_fields = metadata.schema.schema.fields.len();
+        LanceTable {
+            schema: metadata.schema.clone(),
+            fragments: vec![Fragment {
+                num_rows: Some(metadata.num_rows),
+                files: vec![FragmentFile {
+                    location,
+                    columns: (0..num_fields).map(|idx| (idx, idx)).collect(),
+                }],
+            }],
+        }
+    }
+
+    /// Create a table from a dataset's manifest.
+    pub fn try_from_manifest(root: &FileLocation, manifest: Manifest) -> Result<Self> {
+        let schema = LanceSchema::try_from_fields(&manifest.fields)?;
+
+        let fragments = manifest
+            .fragments
+            .into_iter()
+            .map(|fragment| {
+                if fragment.deletion_file.is_some() {
+                    not_implemented!("Reading Lance fragments with deleted rows");
+                }
+
+                let files = fragment
+                    .files
+                    .into_iter()
+                    .map(|file| {
+                        let mut columns = Vec::new();
+                        for (pos, field_id) in file.fields.iter().enumerate() {
+                            // Nested fields aren't part of our schema.
+                            let field_idx =
+                                match schema.field_ids.iter().position(|id| id == field_id) {
+                                    Some(idx) => idx,
+                                    None => continue,
+                                };
+                            let column = match file.column_indices.get(pos) {
+                                Some(&column) if column >= 0 => column as usize,
+                                Some(_) => continue,
+                                None => {
+                                    return Err(RayexecError::new(format!(
+                                        "Missing column index for field {field_id} in '{}'",
+                                        file.path
+                                    )))
+                                }
+                            };
+                            columns.push((field_idx, column));
+                        }
+
+                        Ok(FragmentFile {
+                            location: root.join([DATA_PATH, &file.path])?,
+                            columns,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(Fragment {
+                    num_rows: match fragment.physical_rows {
+                        0 => None,
+                        n => Some(n as usize),
+                    },
+                    files,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(LanceTable { schema, fragments })
+    }
+
+    /// Total number of rows in the table, if known.
+    pub fn num_rows(&self) -> Option<usize> {
+        self.fragments.iter().map(|f| f.num_rows).sum()
+    }
+}
+
+/// Get the path to the manifest for the latest version.
+///
+/// Manifests are either named with the version number directly, or with
+/// `u64::MAX - version` zero padded to 20 digits so that the latest version is
+/// listed first.
+fn latest_manifest(paths: &[String]) -> Option<&str> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let stem = path.strip_suffix(".manifest")?;
+            let num: u64 = stem.parse().ok()?;
+            let version = if stem.len() == 20 {
+                u64::MAX - num
+            } else {
+                num
+            };
+            Some((version, path.as_str()))
+        })
+        .max_by_key(|(version, _)| *version)
+        .map(|(_, path)| path)
+}
+
+/// Decode a manifest file.
+///
+/// The file ends with the position of the length-prefixed manifest message,
+/// followed by the format version and magic.
+fn decode_manifest(buf: &[u8]) -> Result<Manifest> {
+    if buf.len() < 16 || &buf[buf.len() - 4..] != MAGIC {
+        return Err(RayexecError::new("Missing Lance magic in manifest"));
+    }
+    let tail = &buf[buf.len() - 16..];
+    let position = u64::from_le_bytes(tail[..8].try_into().unwrap()) as usize;
+
+    let len_buf = buf
+        .get(position..position + 4)
+        .ok_or_else(|| RayexecError::new("Manifest position out of bounds"))?;
+    let len = u32::from_le_bytes(len_buf.try_into().unwrap()) as usize;
+    let message = buf
+        .get(position + 4..position + 4 + len)
+        .ok_or_else(|| RayexecError::new("Manifest length out of bounds"))?;
+
+    Manifest::decode(message).context("failed to decode manifest")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::DeletionFile;
+    use crate::testutil::{lance_fields, manifest, write_manifest, TestColumn};
+
+    #[test]
+    fn latest_manifest_versions() {
+        let paths = [
+            "1.manifest",
+            "10.manifest",
+            "2.manifest",
+            "_latest.manifest",
+        ]
+        .map(String::from);
+        assert_eq!(Some("10.manifest"), latest_manifest(&paths));
+
+        let paths = [
+            format!("{:020}.manifest", u64::MAX - 7),
+            format!("{:020}.manifest", u64::MAX - 3),
+        ];
+        assert_eq!(Some(paths[0].as_str()), latest_manifest(&paths));
+
+        assert_eq!(None, latest_manifest(&["3.txn".to_string()]));
+    }
+
+    #[test]
+    fn manifest_roundtrip() {
+        let columns = [
+            TestColumn::Int64(vec![Some(1)]),
+            TestColumn::Vector(2, vec![1.0, 2.0]),
+        ];
+        let manifest = manifest(lance_fields(&["id", "vector"], &columns), &[("a.lance", 1)]);
+
+        let out = decode_manifest(&write_manifest(&manifest)).unwrap();
+        assert_eq!(manifest, out);
+
+        assert!(decode_manifest(b"not a manifest").is_err());
+    }
+
+    #[test]
+    fn fragments_from_manifest() {
+        let columns = [
+            TestColumn::Vector(2, vec![1.0, 2.0]),
+            TestColumn::Int64(vec![Some(1)]),
+        ];
+        let manifest = manifest(
+            lance_fields(&["vector", "id"], &columns),
+            &[("a.lance", 10), ("b.lance", 20)],
+        );
+
+        let root = FileLocation::parse("dataset.lance");
+        let table = LanceTable::try_from_manifest(&root, manifest).unwrap();
+
+        assert_eq!(2, table.schema.schema.fields.len());
+        assert_eq!(Some(30), table.num_rows());
+        assert_eq!(
+            Fragment {
+                num_rows: Some(20),
+                files: vec![FragmentFile {
+                    location: root.join(["data", "b.lance"]).unwrap(),
+                    columns: vec![(0, 0), (1, 1)],
+                }],
+            },
+            table.fragments[1]
+        );
+    }
+
+    #[test]
+    fn deleted_rows_not_supported() {
+        let columns = [TestColumn::Int64(vec![Some(1)])];
+        let mut manifest = manifest(lance_fields(&["id"], &columns), &[("a.lance", 1)]);
+        manifest.fragments[0].deletion_file = Some(DeletionFile::default());
+
+        let root = FileLocation::parse("dataset.lance");
+        assert!(LanceTable::try_from_manifest(&root, manifest).is_err());
+    }
+}
@@ -0,0 +1,319 @@
+//! Minimal Lance writer for producing test files.
+//!
+//! Files are written using the 2.0 file format, with uncompressed flat
+//! encodings for all values.
+use prost::Message;
+
+use crate::metadata::MAGIC;
+use crate::proto::array_encoding::ArrayEncoding as Encoding;
+use crate::proto::buffer::BufferType;
+use crate::proto::column_metadata::Page;
+use crate::proto::encoding::Location;
+use crate::proto::nullable::{NoNull, Nullability, SomeNull};
+use crate::proto::{
+    AnyMessage,
+    ArrayEncoding,
+    Binary,
+    Buffer,
+    ColumnMetadata,
+    DataFile,
+    DataFragment,
+    DirectEncoding,
+    Field,
+    FileDescriptor,
+    FixedSizeList,
+    Flat,
+    Manifest,
+    Nullable,
+    Schema,
+};
+
+#[derive(Debug, Clone)]
+pub enum TestColumn {
+    Int64(Vec<Option<i64>>),
+    Utf8(Vec<Option<&'static str>>),
+    /// Vectors with the given dimension, stored flattened.
+    Vector(usize, Vec<f32>),
+}
+
+impl TestColumn {
+    fn len(&self) -> usize {
+        match self {
+            Self::Int64(v) => v.len(),
+            Self::Utf8(v) => v.len(),
+            Self::Vector(dim, v) => v.len() / dim,
+        }
+    }
+
+    /// Encode rows `start..end` as a page, returning the encoding and buffers.
+    fn encode_page(&self, start: usize, end: usize) -> (ArrayEncoding, Vec<Vec<u8>>) {
+        match self {
+            Self::Int64(values) => {
+                let values = &values[start..end];
+                let data = values
+                    .iter()
+                    .flat_map(|v| v.unwrap_or_default().to_le_bytes())
+                    .collect();
+                if values.iter().all(|v| v.is_some()) {
+                    let nullability = Nullability::NoNulls(Box::new(NoNull {
+                        values: Some(Box::new(flat(64, 0))),
+                    }));
+                    (nullable(nullability), vec![data])
+                } else {
+                    let validity = encode_bitmap(values.iter().map(|v| v.is_some()));
+                    let nullability = Nullability::SomeNulls(Box::new(SomeNull {
+                        validity: Some(Box::new(flat(1, 0))),
+                        values: Some(Box::new(flat(64, 1))),
+                    }));
+                    (nullable(nullability), vec![validity, data])
+                }
+            }
+            Self::Utf8(values) => {
+                let values = &values[start..end];
+                let bytes: Vec<u8> = values.iter().flatten().flat_map(|v| v.bytes()).collect();
+                let null_adjustment = if values.iter().all(|v| v.is_some()) {
+                    0
+                } else {
+                    bytes.len() as u64 + 1
+                };
+
+                let mut offset = 0;
+                let mut offsets = Vec::new();
+                for value in values {
+                    match value {
+                        Some(v) => {
+                            offset += v.len() as u64;
+                            offsets.extend_from_slice(&offset.to_le_bytes());
+                        }
+                        None => {
+                            offsets.extend_from_slice(&(offset + null_adjustment).to_le_bytes())
+                        }
+                    }
+                }
+
+                let encoding = ArrayEncoding {
+                    array_encoding: Some(Encoding::Binary(Box::new(Binary {
+                        indices: Some(Box::new(flat(64, 0))),
+                        bytes: Some(Box::new(flat(8, 1))),
+                        null_adjustment,
+                    }))),
+                };
+                (encoding, vec![offsets, bytes])
+            }
+            Self::Vector(dim, values) => {
+                let data = values[start * dim..end * dim]
+                    .iter()
+                    .flat_map(|v| v.to_le_bytes())
+                    .collect();
+                let encoding = ArrayEncoding {
+                    array_encoding: Some(Encoding::FixedSizeList(Box::new(FixedSizeList {
+                        dimension: *dim as u32,
+                        items: Some(Box::new(flat(32, 0))),
+                    }))),
+                };
+                (encoding, vec![data])
+            }
+        }
+    }
+}
+
+fn flat(bits_per_value: u64, buffer_index: u32) -> ArrayEncoding {
+    ArrayEncoding {
+        array_encoding: Some(Encoding::Flat(Flat {
+            bits_per_value,
+            buffer: Some(Buffer {
+                buffer_index,
+                buffer_type: BufferType::Page as i32,
+            }),
+            compression: None,
+        })),
+    }
+}
+
+fn nullable(nullability: Nullability) -> ArrayEncoding {
+    ArrayEncoding {
+        array_encoding: Some(Encoding::Nullable(Box::new(Nullable {
+            nullability: Some(nullability),
+        }))),
+    }
+}
+
+fn encode_bitmap(values: impl Iterator<Item = bool>) -> Vec<u8> {
+    let values: Vec<_> = values.collect();
+    values
+        .chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (idx, v)| acc | ((*v as u8) << idx))
+        })
+        .collect()
+}
+
+/// Create the flattened Lance fields for the columns.
+///
+/// Field ids are assigned in order, with children of vector columns getting
+/// their own id.
+pub fn lance_fields(names: &[&str], columns: &[TestColumn]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    for (name, column) in names.iter().zip(columns) {
+        let id = fields.len() as i32;
+        let logical_type = match column {
+            TestColumn::Int64(_) => "int64".to_string(),
+            TestColumn::Utf8(_) => "string".to_string(),
+            TestColumn::Vector(dim, _) => format!("fixed_size_list:float:{dim}"),
+        };
+        fields.push(Field {
+            name: name.to_string(),
+            id,
+            parent_id: -1,
+            logical_type,
+            nullable: true,
+            ..Default::default()
+        });
+        if matches!(column, TestColumn::Vector(..)) {
+            fields.push(Field {
+                name: "item".to_string(),
+                id: id + 1,
+                parent_id: id,
+                logical_type: "float".to_string(),
+                nullable: true,
+                ..Default::default()
+            });
+        }
+    }
+    fields
+}
+
+/// Write a Lance file containing the given columns, with at most `page_size`
+/// rows per page.
+pub fn write_lance(names: &[&str], columns: &[TestColumn], page_size: usize) -> Vec<u8> {
+    let num_rows = columns[0].len();
+    let mut file = Vec::new();
+
+    let mut column_metadatas = Vec::new();
+    for column in columns {
+        let mut pages = Vec::new();
+        for start in (0..num_rows).step_by(page_size) {
+            let end = (start + page_size).min(num_rows);
+            let (encoding, buffers) = column.encode_page(start, end);
+
+            let mut page = Page {
+                length: (end - start) as u64,
+                encoding: Some(crate::proto::Encoding {
+                    location: Some(Location::Direct(DirectEncoding {
+                        encoding: AnyMessage {
+                            type_url: "/lance.encodings.ArrayEncoding".to_string(),
+                            value: encoding.encode_to_vec(),
+                        }
+                        .encode_to_vec(),
+                    })),
+                }),
+                ..Default::default()
+            };
+            for buffer in buffers {
+                page.buffer_offsets.push(file.len() as u64);
+                page.buffer_sizes.push(buffer.len() as u64);
+                file.extend_from_slice(&buffer);
+            }
+            pages.push(page);
+        }
+        column_metadatas.push(ColumnMetadata { pages });
+    }
+
+    let descriptor = FileDescriptor {
+        schema: Some(Schema {
+            fields: lance_fields(names, columns),
+        }),
+        length: num_rows as u64,
+    }
+    .encode_to_vec();
+    let descriptor_position = file.len();
+    file.extend_from_slice(&descriptor);
+
+    let column_meta_start = file.len();
+    let mut column_offsets = Vec::new();
+    for metadata in column_metadatas {
+        let buf = metadata.encode_to_vec();
+        column_offsets.push((file.len(), buf.len()));
+        file.extend_from_slice(&buf);
+    }
+
+    let column_meta_offsets_start = file.len();
+    for (position, size) in column_offsets {
+        file.extend_from_slice(&(position as u64).to_le_bytes());
+        file.extend_from_slice(&(size as u64).to_le_bytes());
+    }
+
+    let global_buffer_offsets_start = file.len();
+    file.extend_from_slice(&(descriptor_position as u64).to_le_bytes());
+    file.extend_from_slice(&(descriptor.len() as u64).to_le_bytes());
+
+    file.extend_from_slice(&(column_meta_start as u64).to_le_bytes());
+    file.extend_from_slice(&(column_meta_offsets_start as u64).to_le_bytes());
+    file.extend_from_slice(&(global_buffer_offsets_start as u64).to_le_bytes());
+    file.extend_from_slice(&1_u32.to_le_bytes());
+    file.extend_from_slice(&(columns.len() as u32).to_le_bytes());
+    file.extend_from_slice(&0_u16.to_le_bytes());
+    file.extend_from_slice(&3_u16.to_le_bytes());
+    file.extend_from_slice(MAGIC);
+
+    file
+}
+
+/// Create a manifest where every fragment is stored in a single data file.
+pub fn manifest(fields: Vec<Field>, fragments: &[(&str, usize)]) -> Manifest {
+    let field_ids: Vec<_> = fields.iter().map(|f| f.id).collect();
+    let column_indices: Vec<i32> = {
+        let mut column = 0;
+        fields
+            .iter()
+            .map(|f| {
+                if f.parent_id == -1 {
+                    column += 1;
+                    column - 1
+                } else {
+                    -1
+                }
+            })
+            .collect()
+    };
+
+    Manifest {
+        fields,
+        fragments: fragments
+            .iter()
+            .enumerate()
+            .map(|(idx, (path, num_rows))| DataFragment {
+                id: idx as u64,
+                files: vec![DataFile {
+                    path: path.to_string(),
+                    fields: field_ids.clone(),
+                    column_indices: column_indices.clone(),
+                    file_major_version: 2,
+                    file_minor_version: 0,
+                }],
+                deletion_file: None,
+                physical_rows: *num_rows as u64,
+            })
+            .collect(),
+        version: 1,
+    }
+}
+
+/// Write a manifest file.
+pub fn write_manifest(manifest: &Manifest) -> Vec<u8> {
+    let buf = manifest.encode_to_vec();
+
+    let mut file = Vec::new();
+    file.extend_from_slice(&(buf.len() as u32).to_le_bytes());
+    file.extend_from_slice(&buf);
+
+    file.extend_from_slice(&0_u64.to_le_bytes());
+    file.extend_from_slice(&0_u16.to_le_bytes());
+    file.extend_from_slice(&1_u16.to_le_bytes());
+    file.extend_from_slice(MAGIC);
+
+    file
+}
@@ -0,0 +1,503 @@
+use chrono::{DateTime, NaiveDate};
+use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_execution::arrays::array::{Array, ArrayData};
+use rayexec_execution::arrays::bitmap::Bitmap;
+use rayexec_execution::arrays::compute::cast::parse::{Decimal128Parser, Parser};
+use rayexec_execution::arrays::datatype::{DataType, DecimalTypeMeta, TimeUnit, TimestampTypeMeta};
+use rayexec_execution::arrays::field::Field;
+use rayexec_execution::arrays::storage::{BooleanStorage, GermanVarlenStorage, PrimitiveStorage};
+
+use crate::ffi::{self, SqlDate, SqlLen, SqlPointer, SqlSmallInt, SqlTimestamp};
+
+/// Max number of bytes bound per value for variable length columns.
+///
+/// Columns with no declared size, or a size larger than this, use this as the
+/// buffer width.
+pub const MAX_VARLEN_WIDTH: usize = 8192;
+
+/// Max bytes a single character may take up once converted to UTF-8.
+const MAX_BYTES_PER_CHAR: usize = 4;
+
+/// Description of a single column in a result set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDescription {
+    pub name: String,
+    pub sql_type: SqlSmallInt,
+    /// Declared size of the column. Precision for numeric types, and number of
+    /// characters or bytes for string types.
+    pub column_size: usize,
+    pub decimal_digits: SqlSmallInt,
+    pub nullable: bool,
+}
+
+impl ColumnDescription {
+    /// Get the field for this column.
+    pub fn field(&self) -> Result<Field> {
+        let datatype = match self.sql_type {
+            ffi::SQL_BIT => DataType::Boolean,
+            ffi::SQL_TINYINT => DataType::Int8,
+            ffi::SQL_SMALLINT => DataType::Int16,
+            ffi::SQL_INTEGER => DataType::Int32,
+            ffi::SQL_BIGINT => DataType::Int64,
+            ffi::SQL_REAL => DataType::Float32,
+            // SQL_FLOAT defaults to double precision.
+            ffi::SQL_FLOAT | ffi::SQL_DOUBLE => DataType::Float64,
+            ffi::SQL_NUMERIC | ffi::SQL_DECIMAL => {
+                let precision = self.column_size.clamp(1, 38) as u8;
+                let scale = (self.decimal_digits.max(0) as u8).min(precision);
+                DataType::Decimal128(DecimalTypeMeta::new(precision, scale as i8))
+            }
+            ffi::SQL_CHAR
+            | ffi::SQL_VARCHAR
+            | ffi::SQL_LONGVARCHAR
+            | ffi::SQL_WCHAR
+            | ffi::SQL_WVARCHAR
+            | ffi::SQL_WLONGVARCHAR
+            | ffi::SQL_GUID => DataType::Utf8,
+            ffi::SQL_BINARY | ffi::SQL_VARBINARY | ffi::SQL_LONGVARBINARY => DataType::Binary,
+            ffi::SQL_TYPE_DATE => DataType::Date32,
+            ffi::SQL_TYPE_TIMESTAMP => {
+                DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond))
+            }
+            other => not_implemented!("ODBC column '{}' with SQL type {other}", self.name),
+        };
+
+        Ok(Field::new(&self.name, datatype, self.nullable))
+    }
+}
+
+/// Buffer that a column is bound to for bulk fetches.
+///
+/// Holds space for up to `capacity` values, along with a length/indicator per
+/// value.
+#[derive(Debug)]
+pub struct ColumnBuffer {
+    datatype: DataType,
+    values: BufferValues,
+    indicators: Vec<SqlLen>,
+}
+
+#[derive(Debug)]
+enum BufferValues {
+    Boolean(Vec<u8>),
+    Int8(Vec<i8>),
+    Int16(Vec<i16>),
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Float32(Vec<f32>),
+    Float64(Vec<f64>),
+    Date(Vec<SqlDate>),
+    Timestamp(Vec<SqlTimestamp>),
+    /// Fixed width slots for variable length values. Used for strings,
+    /// binary, and decimals (fetched as strings).
+    Varlen {
+        data: Vec<u8>,
+        width: usize,
+    },
+}
+
+impl ColumnBuffer {
+    /// Create a buffer for a column with the given output type.
+    ///
+    /// `column_size` is the column size reported by the driver, and is used to
+    /// size buffers for variable length values.
+    pub fn try_new(datatype: &DataType, column_size: usize, capacity: usize) -> Result<Self> {
+        let values = match datatype {
+            DataType::Boolean => BufferValues::Boolean(vec![0; capacity]),
+            DataType::Int8 => BufferValues::Int8(vec![0; capacity]),
+            DataType::Int16 => BufferValues::Int16(vec![0; capacity]),
+            DataType::Int32 => BufferValues::Int32(vec![0; capacity]),
+            DataType::Int64 => BufferValues::Int64(vec![0; capacity]),
+            DataType::Float32 => BufferValues::Float32(vec![0.0; capacity]),
+            DataType::Float64 => BufferValues::Float64(vec![0.0; capacity]),
+            DataType::Date32 => BufferValues::Date(vec![SqlDate::default(); capacity]),
+            DataType::Timestamp(_) => {
+                BufferValues::Timestamp(vec![SqlTimestamp::default(); capacity])
+            }
+            DataType::Decimal128(m) => {
+                // Sign, decimal point, and null terminator.
+                let width = m.precision as usize + 3;
+                BufferValues::Varlen {
+                    data: vec![0; width * capacity],
+                    width,
+                }
+            }
+            DataType::Utf8 => {
+                let max_bytes = column_size.saturating_mul(MAX_BYTES_PER_CHAR);
+                // Include space for the null terminator.
+                let width = varlen_width(max_bytes) + 1;
+                BufferValues::Varlen {
+                    data: vec![0; width * capacity],
+                    width,
+                }
+            }
+            DataType::Binary => {
+                let width = varlen_width(column_size);
+                BufferValues::Varlen {
+                    data: vec![0; width * capacity],
+                    width,
+                }
+            }
+            other => not_implemented!("ODBC buffer for {other}"),
+        };
+
+        Ok(ColumnBuffer {
+            datatype: datatype.clone(),
+            values,
+            indicators: vec![0; capacity],
+        })
+    }
+
+    /// The C type the column should be bound as.
+    pub fn c_type(&self) -> SqlSmallInt {
+        match &self.values {
+            BufferValues::Boolean(_) => ffi::SQL_C_BIT,
+            BufferValues::Int8(_) => ffi::SQL_C_STINYINT,
+            BufferValues::Int16(_) => ffi::SQL_C_SSHORT,
+            BufferValues::Int32(_) => ffi::SQL_C_SLONG,
+            BufferValues::Int64(_) => ffi::SQL_C_SBIGINT,
+            BufferValues::Float32(_) => ffi::SQL_C_FLOAT,
+            BufferValues::Float64(_) => ffi::SQL_C_DOUBLE,
+            BufferValues::Date(_) => ffi::SQL_C_TYPE_DATE,
+            BufferValues::Timestamp(_) => ffi::SQL_C_TYPE_TIMESTAMP,
+            BufferValues::Varlen { .. } => match self.datatype {
+                DataType::Binary => ffi::SQL_C_BINARY,
+                _ => ffi::SQL_C_CHAR,
+            },
+        }
+    }
+
+    /// Get the pointers to bind the column to.
+    ///
+    /// Returns the value buffer, the width of a single value in bytes, and the
+    /// indicator buffer. Pointers stay valid as long as the buffer isn't
+    /// dropped.
+    pub fn binding(&mut self) -> (SqlPointer, SqlLen, *mut SqlLen) {
+        fn ptr<T>(values: &mut [T]) -> (SqlPointer, SqlLen) {
+            (
+                values.as_mut_ptr().cast(),
+                std::mem::size_of::<T>() as SqlLen,
+            )
+        }
+
+        let (values, width) = match &mut self.values {
+            BufferValues::Boolean(v) => ptr(v),
+            BufferValues::Int8(v) => ptr(v),
+            BufferValues::Int16(v) => ptr(v),
+            BufferValues::Int32(v) => ptr(v),
+            BufferValues::Int64(v) => ptr(v),
+            BufferValues::Float32(v) => ptr(v),
+            BufferValues::Float64(v) => ptr(v),
+            BufferValues::Date(v) => ptr(v),
+            BufferValues::Timestamp(v) => ptr(v),
+            BufferValues::Varlen { data, width } => (data.as_mut_ptr().cast(), *width as SqlLen),
+        };
+
+        (values, width, self.indicators.as_mut_ptr())
+    }
+
+    /// Convert the first `num_rows` values in the buffer to an array.
+    pub fn to_array(&self, num_rows: usize) -> Result<Array> {
+        let mut validity = Bitmap::new_with_all_true(num_rows);
+        for (idx, &indicator) in self.indicators[..num_rows].iter().enumerate() {
+            if indicator == ffi::SQL_NULL_DATA {
+                validity.set_unchecked(idx, false);
+            }
+        }
+
+        let data: ArrayData = match &self.values {
+            BufferValues::Boolean(v) => {
+                BooleanStorage::from(v[..num_rows].iter().map(|&b| b != 0).collect::<Bitmap>())
+                    .into()
+            }
+            BufferValues::Int8(v) => primitive(&v[..num_rows]),
+            BufferValues::Int16(v) => primitive(&v[..num_rows]),
+            BufferValues::Int32(v) => primitive(&v[..num_rows]),
+            BufferValues::Int64(v) => primitive(&v[..num_rows]),
+            BufferValues::Float32(v) => primitive(&v[..num_rows]),
+            BufferValues::Float64(v) => primitive(&v[..num_rows]),
+            BufferValues::Date(v) => {
+                let days = v[..num_rows]
+                    .iter()
+                    .zip(validity.iter())
+                    .map(|(date, valid)| if valid { date_to_days(date) } else { Ok(0) })
+                    .collect::<Result<Vec<_>>>()?;
+                PrimitiveStorage::from(days).into()
+            }
+            BufferValues::Timestamp(v) => {
+                let micros = v[..num_rows]
+                    .iter()
+                    .zip(validity.iter())
+                    .map(|(ts, valid)| {
+                        if valid {
+                            timestamp_to_micros(ts)
+                        } else {
+                            Ok(0)
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                PrimitiveStorage::from(micros).into()
+            }
+            BufferValues::Varlen { data, width } => {
+                let values = (0..num_rows).map(|idx| {
+                    if !validity.value(idx) {
+                        return Ok(&[] as &[u8]);
+                    }
+                    varlen_value(data, *width, idx, self.indicators[idx], &self.datatype)
+                });
+
+                match &self.datatype {
+                    DataType::Decimal128(m) => {
+                        let mut parser = Decimal128Parser::new(m.precision, m.scale);
+                        let decimals = values
+                            .map(|value| {
+                                let value = value?;
+                                if value.is_empty() {
+                                    return Ok(0);
+                                }
+                                let s = std::str::from_utf8(value).map_err(|_| {
+                                    RayexecError::new("ODBC decimal value is not valid UTF-8")
+                                })?;
+                                parser.parse(s).ok_or_else(|| {
+                                    RayexecError::new(format!(
+                                        "Failed to parse '{s}' as {}",
+                                        self.datatype
+                                    ))
+                                })
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        PrimitiveStorage::from(decimals).into()
+                    }
+                    _ => {
+                        let mut storage = GermanVarlenStorage::with_metadata_capacity(num_rows);
+                        for value in values {
+                            let value = value?;
+                            if self.datatype == DataType::Utf8
+                                && std::str::from_utf8(value).is_err()
+                            {
+                                return Err(RayexecError::new(
+                                    "ODBC string value is not valid UTF-8",
+                                ));
+                            }
+                            storage.try_push(value)?;
+                        }
+                        storage.into()
+                    }
+                }
+            }
+        };
+
+        Ok(if validity.is_all_true() {
+            Array::new_with_array_data(self.datatype.clone(), data)
+        } else {
+            Array::new_with_validity_and_array_data(self.datatype.clone(), validity, data)
+        })
+    }
+}
+
+fn varlen_width(max_bytes: usize) -> usize {
+    if max_bytes == 0 {
+        MAX_VARLEN_WIDTH
+    } else {
+        max_bytes.min(MAX_VARLEN_WIDTH)
+    }
+}
+
+fn primitive<T: Copy>(values: &[T]) -> ArrayData
+where
+    PrimitiveStorage<T>: Into<ArrayData>,
+{
+    PrimitiveStorage::from(values.to_vec()).into()
+}
+
+/// Get the bytes for a single variable length value.
+fn varlen_value<'a>(
+    data: &'a [u8],
+    width: usize,
+    idx: usize,
+    indicator: SqlLen,
+    datatype: &DataType,
+) -> Result<&'a [u8]> {
+    // Strings are null terminated, so can hold one less byte.
+    let capacity = match datatype {
+        DataType::Binary => width,
+        _ => width - 1,
+    };
+
+    if indicator == ffi::SQL_NO_TOTAL || indicator as usize > capacity {
+        return Err(RayexecError::new(format!(
+            "ODBC value exceeds max supported length of {capacity} bytes"
+        )));
+    }
+    let len = indicator.max(0) as usize;
+
+    Ok(&data[idx * width..idx * width + len])
+}
+
+fn date_to_days(date: &SqlDate) -> Result<i32> {
+    let date = NaiveDate::from_ymd_opt(date.year as i32, date.month as u32, date.day as u32)
+        .ok_or_else(|| RayexecError::new(format!("Invalid ODBC date: {date:?}")))?;
+    Ok(date
+        .signed_duration_since(DateTime::UNIX_EPOCH.date_naive())
+        .num_days() as i32)
+}
+
+fn timestamp_to_micros(ts: &SqlTimestamp) -> Result<i64> {
+    NaiveDate::from_ymd_opt(ts.year as i32, ts.month as u32, ts.day as u32)
+        .and_then(|date| {
+            date.and_hms_nano_opt(
+                ts.hour as u32,
+                ts.minute as u32,
+                ts.second as u32,
+                ts.fraction,
+            )
+        })
+        .map(|datetime| datetime.and_utc().timestamp_micros())
+        .ok_or_else(|| RayexecError::new(format!("Invalid ODBC timestamp: {ts:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use rayexec_execution::arrays::scalar::decimal::Decimal128Scalar;
+    use rayexec_execution::arrays::scalar::timestamp::TimestampScalar;
+    use rayexec_execution::arrays::scalar::ScalarValue;
+
+    use super::*;
+
+    fn column(name: &str, sql_type: SqlSmallInt, column_size: usize) -> ColumnDescription {
+        ColumnDescription {
+            name: name.to_string(),
+            sql_type,
+            column_size,
+            decimal_digits: 2,
+            nullable: true,
+        }
+    }
+
+    #[test]
+    fn fields_from_sql_types() {
+        assert_eq!(
+            Field::new("a", DataType::Int64, true),
+            column("a", ffi::SQL_BIGINT, 19).field().unwrap()
+        );
+        assert_eq!(
+            Field::new("b", DataType::Decimal128(DecimalTypeMeta::new(10, 2)), true),
+            column("b", ffi::SQL_DECIMAL, 10).field().unwrap()
+        );
+        assert_eq!(
+            Field::new("c", DataType::Utf8, true),
+            column("c", ffi::SQL_WVARCHAR, 255).field().unwrap()
+        );
+        assert!(column("d", 2004, 0).field().is_err());
+    }
+
+    #[test]
+    fn buffer_widths() {
+        let buf = ColumnBuffer::try_new(&DataType::Utf8, 10, 4).unwrap();
+        match buf.values {
+            BufferValues::Varlen { width, data } => {
+                assert_eq!(41, width);
+                assert_eq!(164, data.len());
+            }
+            other => panic!("unexpected values: {other:?}"),
+        }
+
+        // No declared size.
+        let buf = ColumnBuffer::try_new(&DataType::Binary, 0, 4).unwrap();
+        match buf.values {
+            BufferValues::Varlen { width, .. } => assert_eq!(MAX_VARLEN_WIDTH, width),
+            other => panic!("unexpected values: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn int_array_with_nulls() {
+        let mut buf = ColumnBuffer::try_new(&DataType::Int32, 10, 4).unwrap();
+        buf.values = BufferValues::Int32(vec![1, 0, 3, 99]);
+        buf.indicators = vec![4, ffi::SQL_NULL_DATA, 4, 4];
+
+        let arr = buf.to_array(3).unwrap();
+        assert_eq!(Array::from_iter([Some(1), None, Some(3)]), arr);
+    }
+
+    #[test]
+    fn strings_from_slots() {
+        let mut buf = ColumnBuffer::try_new(&DataType::Utf8, 1, 3).unwrap();
+        let width = 5;
+        let mut data = vec![0; width * 3];
+        data[..2].copy_from_slice(b"ab");
+        data[2 * width..2 * width + 4].copy_from_slice(b"cdef");
+        buf.values = BufferValues::Varlen { data, width };
+        buf.indicators = vec![2, ffi::SQL_NULL_DATA, 4];
+
+        let arr = buf.to_array(3).unwrap();
+        assert_eq!(Array::from_iter([Some("ab"), None, Some("cdef")]), arr);
+
+        // Truncated value.
+        buf.indicators[2] = 5;
+        assert!(buf.to_array(3).is_err());
+    }
+
+    #[test]
+    fn decimals_from_strings() {
+        let datatype = DataType::Decimal128(DecimalTypeMeta::new(5, 2));
+        let mut buf = ColumnBuffer::try_new(&datatype, 5, 2).unwrap();
+        let width = 8;
+        let mut data = vec![0; width * 2];
+        data[..6].copy_from_slice(b"-12.50");
+        buf.values = BufferValues::Varlen { data, width };
+        buf.indicators = vec![6, ffi::SQL_NULL_DATA];
+
+        let arr = buf.to_array(2).unwrap();
+        assert_eq!(&datatype, arr.datatype());
+        assert_eq!(
+            ScalarValue::Decimal128(Decimal128Scalar {
+                precision: 5,
+                scale: 2,
+                value: -1250,
+            }),
+            arr.logical_value(0).unwrap()
+        );
+        assert_eq!(ScalarValue::Null, arr.logical_value(1).unwrap());
+    }
+
+    #[test]
+    fn dates_and_timestamps() {
+        let mut buf = ColumnBuffer::try_new(&DataType::Date32, 10, 2).unwrap();
+        buf.values = BufferValues::Date(vec![
+            SqlDate {
+                year: 1970,
+                month: 1,
+                day: 2,
+            },
+            // Garbage for the null value shouldn't error.
+            SqlDate::default(),
+        ]);
+        buf.indicators = vec![6, ffi::SQL_NULL_DATA];
+        let arr = buf.to_array(2).unwrap();
+        assert_eq!(ScalarValue::Date32(1), arr.logical_value(0).unwrap());
+        assert_eq!(ScalarValue::Null, arr.logical_value(1).unwrap());
+
+        let datatype = DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Microsecond));
+        let mut buf = ColumnBuffer::try_new(&datatype, 26, 1).unwrap();
+        buf.values = BufferValues::Timestamp(vec![SqlTimestamp {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 1,
+            fraction: 500_000_000,
+        }]);
+        buf.indicators = vec![16];
+        let arr = buf.to_array(1).unwrap();
+        assert_eq!(&datatype, arr.datatype());
+        assert_eq!(
+            ScalarValue::Timestamp(TimestampScalar {
+                unit: TimeUnit::Microsecond,
+                value: 1_500_000,
+            }),
+            arr.logical_value(0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use std::ptr;
+
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::datatype::DataType;
+use tracing::trace;
+
+use crate::buffer::{ColumnBuffer, ColumnDescription};
+use crate::ffi::{self, driver_manager, DriverManager, SqlHandle, SqlSmallInt, SqlULen};
+
+/// A connection to a data source through the ODBC driver manager.
+///
+/// All calls are blocking.
+#[derive(Debug)]
+pub struct OdbcConnection {
+    dm: &'static DriverManager,
+    env: SqlHandle,
+    dbc: SqlHandle,
+}
+
+impl OdbcConnection {
+    /// Connect using a connection string, e.g. `DSN=mydb;UID=user;PWD=pass`.
+    pub fn connect(connection_string: &str) -> Result<Self> {
+        let dm = driver_manager()?;
+
+        let mut env = ptr::null_mut();
+        // SAFETY: Allocating a new environment with no parent.
+        let ret = unsafe { (dm.alloc_handle)(ffi::SQL_HANDLE_ENV, ptr::null_mut(), &mut env) };
+        if !ffi::succeeded(ret) {
+            return Err(RayexecError::new("Failed to allocate ODBC environment"));
+        }
+
+        // Construct early so the environment is freed on error.
+        let mut conn = OdbcConnection {
+            dm,
+            env,
+            dbc: ptr::null_mut(),
+        };
+
+        // SAFETY: The version attribute takes an integer value passed as a
+        // pointer.
+        let ret = unsafe {
+            (dm.set_env_attr)(env, ffi::SQL_ATTR_ODBC_VERSION, ffi::SQL_OV_ODBC3 as _, 0)
+        };
+        dm.check(ret, ffi::SQL_HANDLE_ENV, env, "Failed to set ODBC version")?;
+
+        let mut dbc = ptr::null_mut();
+        // SAFETY: Environment handle is valid.
+        let ret = unsafe { (dm.alloc_handle)(ffi::SQL_HANDLE_DBC, env, &mut dbc) };
+        dm.check(
+            ret,
+            ffi::SQL_HANDLE_ENV,
+            env,
+            "Failed to allocate ODBC connection",
+        )?;
+
+        let len = SqlSmallInt::try_from(connection_string.len())
+            .map_err(|_| RayexecError::new("ODBC connection string too long"))?;
+        // SAFETY: Connection string is passed with an explicit length, and no
+        // output buffer is provided.
+        let ret = unsafe {
+            (dm.driver_connect)(
+                dbc,
+                ptr::null_mut(),
+                connection_string.as_ptr(),
+                len,
+                ptr::null_mut(),
+                0,
+                ptr::null_mut(),
+                ffi::SQL_DRIVER_NOPROMPT,
+            )
+        };
+        if !ffi::succeeded(ret) {
+            let err = RayexecError::new(format!(
+                "Failed to connect to ODBC data source: {}",
+                dm.diagnostics(ffi::SQL_HANDLE_DBC, dbc)
+            ));
+            // SAFETY: Connection handle was allocated above and isn't
+            // connected.
+            unsafe { (dm.free_handle)(ffi::SQL_HANDLE_DBC, dbc) };
+            return Err(err);
+        }
+        conn.dbc = dbc;
+
+        Ok(conn)
+    }
+
+    /// Execute a query.
+    ///
+    /// `max_rows` hints to the driver that only that many rows are needed.
+    /// Drivers may ignore this.
+    pub fn execute(&self, query: &str, max_rows: Option<usize>) -> Result<OdbcStatement<'_>> {
+        let dm = self.dm;
+
+        let mut handle = ptr::null_mut();
+        // SAFETY: Connection handle is valid.
+        let ret = unsafe { (dm.alloc_handle)(ffi::SQL_HANDLE_STMT, self.dbc, &mut handle) };
+        dm.check(
+            ret,
+            ffi::SQL_HANDLE_DBC,
+            self.dbc,
+            "Failed to allocate ODBC statement",
+        )?;
+        let stmt = OdbcStatement { conn: self, handle };
+
+        if let Some(max_rows) = max_rows {
+            stmt.set_attr(ffi::SQL_ATTR_MAX_ROWS, max_rows)?;
+        }
+
+        trace!(%query, "executing odbc query");
+        let len =
+            i32::try_from(query.len()).map_err(|_| RayexecError::new("ODBC query too long"))?;
+        // SAFETY: Query is passed with an explicit length.
+        let ret = unsafe { (dm.exec_direct)(handle, query.as_ptr(), len) };
+        // Queries returning no rows may return SQL_NO_DATA.
+        if ret != ffi::SQL_NO_DATA {
+            stmt.check(ret, "Failed to execute ODBC query")?;
+        }
+
+        Ok(stmt)
+    }
+}
+
+impl Drop for OdbcConnection {
+    fn drop(&mut self) {
+        // SAFETY: Handles were allocated in `connect` and are no longer used.
+        unsafe {
+            if !self.dbc.is_null() {
+                (self.dm.disconnect)(self.dbc);
+                (self.dm.free_handle)(ffi::SQL_HANDLE_DBC, self.dbc);
+            }
+            (self.dm.free_handle)(ffi::SQL_HANDLE_ENV, self.env);
+        }
+    }
+}
+
+/// An executed statement.
+#[derive(Debug)]
+pub struct OdbcStatement<'a> {
+    conn: &'a OdbcConnection,
+    handle: SqlHandle,
+}
+
+impl OdbcStatement<'_> {
+    /// Describe the columns in the result set.
+    pub fn describe(&self) -> Result<Vec<ColumnDescription>> {
+        let dm = self.conn.dm;
+
+        let mut num_cols = 0;
+        // SAFETY: Statement handle is valid.
+        let ret = unsafe { (dm.num_result_cols)(self.handle, &mut num_cols) };
+        self.check(ret, "Failed to get number of ODBC result columns")?;
+
+        (1..=num_cols.max(0) as u16)
+            .map(|col| {
+                let mut name = [0_u8; 256];
+                let mut name_len = 0;
+                let mut sql_type = 0;
+                let mut column_size = 0;
+                let mut decimal_digits = 0;
+                let mut nullable = 0;
+
+                // SAFETY: Output pointers outlive the call, name buffer length
+                // is passed through.
+                let ret = unsafe {
+                    (dm.describe_col)(
+                        self.handle,
+                        col,
+                        name.as_mut_ptr(),
+                        name.len() as SqlSmallInt,
+                        &mut name_len,
+                        &mut sql_type,
+                        &mut column_size,
+                        &mut decimal_digits,
+                        &mut nullable,
+                    )
+                };
+                self.check(ret, "Failed to describe ODBC result column")?;
+
+                let name_len = (name_len.max(0) as usize).min(name.len() - 1);
+                Ok(ColumnDescription {
+                    name: String::from_utf8_lossy(&name[..name_len]).into_owned(),
+                    sql_type,
+                    column_size,
+                    decimal_digits,
+                    // SQL_NO_NULLS is 0, treat unknown as nullable.
+                    nullable: nullable != 0,
+                })
+            })
+            .collect()
+    }
+
+    fn set_attr(&self, attr: i32, value: usize) -> Result<()> {
+        // SAFETY: Integer attributes are passed as pointers.
+        let ret = unsafe { (self.conn.dm.set_stmt_attr)(self.handle, attr, value as _, 0) };
+        self.check(ret, "Failed to set ODBC statement attribute")
+    }
+
+    fn check(&self, ret: ffi::SqlReturn, context: &str) -> Result<()> {
+        self.conn
+            .dm
+            .check(ret, ffi::SQL_HANDLE_STMT, self.handle, context)
+    }
+}
+
+impl Drop for OdbcStatement<'_> {
+    fn drop(&mut self) {
+        // SAFETY: Handle was allocated in `execute`, and any bound buffers are
+        // unbound when the handle is freed.
+        unsafe { (self.conn.dm.free_handle)(ffi::SQL_HANDLE_STMT, self.handle) };
+    }
+}
+
+/// Fetches batches from a statement using column-wise bulk fetches.
+#[derive(Debug)]
+pub struct BatchCursor<'a> {
+    // Dropped before the buffers, since dropping the statement unbinds them.
+    stmt: OdbcStatement<'a>,
+    buffers: Vec<ColumnBuffer>,
+    /// Written to by the driver on each fetch.
+    rows_fetched: Box<SqlULen>,
+    finished: bool,
+}
+
+impl<'a> BatchCursor<'a> {
+    /// Create a cursor reading the statement's columns as the given types,
+    /// fetching up to `batch_size` rows at a time.
+    pub fn try_new(
+        stmt: OdbcStatement<'a>,
+        datatypes: &[DataType],
+        batch_size: usize,
+    ) -> Result<Self> {
+        let columns = stmt.describe()?;
+        if columns.len() != datatypes.len() {
+            return Err(RayexecError::new(format!(
+                "ODBC result has {} columns, expected {}",
+                columns.len(),
+                datatypes.len()
+            )));
+        }
+
+        let batch_size = batch_size.max(1);
+        let mut buffers = columns
+            .iter()
+            .zip(datatypes)
+            .map(|(col, datatype)| ColumnBuffer::try_new(datatype, col.column_size, batch_size))
+            .collect::<Result<Vec<_>>>()?;
+        let mut rows_fetched = Box::new(0);
+
+        stmt.set_attr(ffi::SQL_ATTR_ROW_BIND_TYPE, ffi::SQL_BIND_BY_COLUMN)?;
+        stmt.set_attr(ffi::SQL_ATTR_ROW_ARRAY_SIZE, batch_size)?;
+        // SAFETY: Boxed so the pointer stays valid when the cursor is moved.
+        let ret = unsafe {
+            (stmt.conn.dm.set_stmt_attr)(
+                stmt.handle,
+                ffi::SQL_ATTR_ROWS_FETCHED_PTR,
+                (rows_fetched.as_mut() as *mut SqlULen).cast(),
+                0,
+            )
+        };
+        stmt.check(ret, "Failed to set ODBC rows fetched pointer")?;
+
+        for (idx, buffer) in buffers.iter_mut().enumerate() {
+            let c_type = buffer.c_type();
+            let (values, width, indicators) = buffer.binding();
+            // SAFETY: Buffers hold `batch_size` values of `width` bytes, and
+            // are heap allocated so stay valid when the cursor is moved.
+            let ret = unsafe {
+                (stmt.conn.dm.bind_col)(
+                    stmt.handle,
+                    (idx + 1) as u16,
+                    c_type,
+                    values,
+                    width,
+                    indicators,
+                )
+            };
+            stmt.check(ret, "Failed to bind ODBC column")?;
+        }
+
+        Ok(BatchCursor {
+            stmt,
+            buffers,
+            rows_fetched,
+            finished: false,
+        })
+    }
+
+    /// Fetch the next batch, returning None once the result set is exhausted.
+    pub fn next_batch(&mut self) -> Result<Option<Batch>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        // SAFETY: Bound buffers are valid for the lifetime of the cursor.
+        let ret = unsafe { (self.stmt.conn.dm.fetch)(self.stmt.handle) };
+        if ret == ffi::SQL_NO_DATA {
+            self.finished = true;
+            return Ok(None);
+        }
+        self.stmt.check(ret, "Failed to fetch ODBC rows")?;
+
+        let num_rows = *self.rows_fetched;
+        trace!(%num_rows, "fetched odbc rows");
+
+        if self.buffers.is_empty() {
+            return Ok(Some(Batch::empty_with_num_rows(num_rows)));
+        }
+
+        let arrays = self
+            .buffers
+            .iter()
+            .map(|buffer| buffer.to_array(num_rows))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(Batch::try_new(arrays)?))
+    }
+}
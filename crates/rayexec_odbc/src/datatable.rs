@@ -0,0 +1,160 @@
+use std::fmt;
+use std::time::Duration;
+
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::{SinkExt, StreamExt};
+use rayexec_error::{Result, ResultExt};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::field::Field;
+use rayexec_execution::runtime::stall::maybe_with_stall_timeout;
+use rayexec_execution::storage::table_storage::{
+    DataTable,
+    DataTableScan,
+    EmptyTableScan,
+    LimitedScan,
+    ProjectedScan,
+    Projections,
+};
+use tokio::runtime::Handle;
+
+use crate::client::{BatchCursor, OdbcConnection};
+
+/// A table read through an ODBC connection.
+///
+/// The full table is read by a single partition, with projections applied
+/// after reading.
+#[derive(Debug)]
+pub struct OdbcDataTable {
+    pub(crate) connection_string: String,
+    pub(crate) table: String,
+    pub(crate) fields: Vec<Field>,
+    pub(crate) handle: Handle,
+    pub(crate) stall_timeout: Option<Duration>,
+}
+
+impl OdbcDataTable {
+    fn scan_inner(
+        &self,
+        projections: Projections,
+        max_rows: Option<usize>,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Vec<Box<dyn DataTableScan>> {
+        let query = format!("SELECT * FROM {}", self.table);
+        let datatypes = self.fields.iter().map(|f| f.datatype.clone()).collect();
+
+        let stream = query_stream(
+            &self.handle,
+            self.connection_string.clone(),
+            query,
+            datatypes,
+            max_rows,
+            batch_size,
+        );
+
+        let mut scans = vec![Box::new(ProjectedScan::new(
+            OdbcTableScan {
+                stream: maybe_with_stall_timeout(
+                    stream,
+                    self.stall_timeout,
+                    Some(self.handle.clone()),
+                ),
+            },
+            projections,
+        )) as _];
+        (1..num_partitions).for_each(|_| scans.push(Box::new(EmptyTableScan) as _));
+
+        scans
+    }
+}
+
+impl DataTable for OdbcDataTable {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        Ok(self.scan_inner(projections, None, num_partitions, batch_size))
+    }
+
+    fn scan_limit(
+        &self,
+        projections: Projections,
+        limit: usize,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        // Drivers aren't required to respect max rows, so still limit on our
+        // end.
+        let scans = self.scan_inner(projections, Some(limit), num_partitions, batch_size);
+        Ok(LimitedScan::wrap_scans(scans, limit))
+    }
+}
+
+/// Run a blocking ODBC call on tokio's blocking thread pool.
+pub(crate) async fn run_blocking<T, F>(handle: &Handle, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    handle.spawn_blocking(f).await.context("ODBC task failed")?
+}
+
+/// Create a stream of batches for a query.
+///
+/// The query is executed and fetched on a blocking thread, sending batches
+/// back through a bounded channel. The thread stops fetching once the stream
+/// is dropped.
+fn query_stream(
+    handle: &Handle,
+    connection_string: String,
+    query: String,
+    datatypes: Vec<DataType>,
+    max_rows: Option<usize>,
+    batch_size: usize,
+) -> BoxStream<'static, Result<Batch>> {
+    let (mut tx, rx) = mpsc::channel(1);
+
+    handle.spawn_blocking(move || {
+        let fetch = |tx: &mut mpsc::Sender<Result<Batch>>| -> Result<()> {
+            let conn = OdbcConnection::connect(&connection_string)?;
+            let stmt = conn.execute(&query, max_rows)?;
+            let mut cursor = BatchCursor::try_new(stmt, &datatypes, batch_size)?;
+
+            while let Some(batch) = cursor.next_batch()? {
+                if block_on(tx.send(Ok(batch))).is_err() {
+                    // Receiver dropped, nothing else to do.
+                    return Ok(());
+                }
+            }
+            Ok(())
+        };
+
+        if let Err(e) = fetch(&mut tx) {
+            let _ = block_on(tx.send(Err(e)));
+        }
+    });
+
+    rx.boxed()
+}
+
+pub struct OdbcTableScan {
+    stream: BoxStream<'static, Result<Batch>>,
+}
+
+impl DataTableScan for OdbcTableScan {
+    fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
+        Box::pin(async { self.stream.next().await.transpose() })
+    }
+}
+
+impl fmt::Debug for OdbcTableScan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OdbcTableScan").finish()
+    }
+}
@@ -0,0 +1,297 @@
+//! Bindings to the ODBC driver manager.
+//!
+//! The driver manager is loaded at runtime so that builds don't require ODBC
+//! headers or libraries to be installed. Only the subset of the API needed
+//! for reading result sets is bound.
+use std::ffi::c_void;
+use std::fmt;
+use std::sync::OnceLock;
+
+use libloading::Library;
+use rayexec_error::{RayexecError, Result};
+
+pub type SqlHandle = *mut c_void;
+pub type SqlPointer = *mut c_void;
+pub type SqlReturn = i16;
+pub type SqlSmallInt = i16;
+pub type SqlUSmallInt = u16;
+pub type SqlInteger = i32;
+pub type SqlLen = isize;
+pub type SqlULen = usize;
+
+pub const SQL_HANDLE_ENV: SqlSmallInt = 1;
+pub const SQL_HANDLE_DBC: SqlSmallInt = 2;
+pub const SQL_HANDLE_STMT: SqlSmallInt = 3;
+
+pub const SQL_SUCCESS: SqlReturn = 0;
+pub const SQL_SUCCESS_WITH_INFO: SqlReturn = 1;
+pub const SQL_NO_DATA: SqlReturn = 100;
+
+pub const SQL_ATTR_ODBC_VERSION: SqlInteger = 200;
+pub const SQL_OV_ODBC3: usize = 3;
+
+pub const SQL_ATTR_MAX_ROWS: SqlInteger = 1;
+pub const SQL_ATTR_ROW_BIND_TYPE: SqlInteger = 5;
+pub const SQL_ATTR_ROWS_FETCHED_PTR: SqlInteger = 26;
+pub const SQL_ATTR_ROW_ARRAY_SIZE: SqlInteger = 27;
+pub const SQL_BIND_BY_COLUMN: usize = 0;
+
+pub const SQL_DRIVER_NOPROMPT: SqlUSmallInt = 0;
+pub const SQL_NULL_DATA: SqlLen = -1;
+pub const SQL_NO_TOTAL: SqlLen = -4;
+
+// SQL data types.
+pub const SQL_CHAR: SqlSmallInt = 1;
+pub const SQL_NUMERIC: SqlSmallInt = 2;
+pub const SQL_DECIMAL: SqlSmallInt = 3;
+pub const SQL_INTEGER: SqlSmallInt = 4;
+pub const SQL_SMALLINT: SqlSmallInt = 5;
+pub const SQL_FLOAT: SqlSmallInt = 6;
+pub const SQL_REAL: SqlSmallInt = 7;
+pub const SQL_DOUBLE: SqlSmallInt = 8;
+pub const SQL_VARCHAR: SqlSmallInt = 12;
+pub const SQL_TYPE_DATE: SqlSmallInt = 91;
+pub const SQL_TYPE_TIMESTAMP: SqlSmallInt = 93;
+pub const SQL_LONGVARCHAR: SqlSmallInt = -1;
+pub const SQL_BINARY: SqlSmallInt = -2;
+pub const SQL_VARBINARY: SqlSmallInt = -3;
+pub const SQL_LONGVARBINARY: SqlSmallInt = -4;
+pub const SQL_BIGINT: SqlSmallInt = -5;
+pub const SQL_TINYINT: SqlSmallInt = -6;
+pub const SQL_BIT: SqlSmallInt = -7;
+pub const SQL_WCHAR: SqlSmallInt = -8;
+pub const SQL_WVARCHAR: SqlSmallInt = -9;
+pub const SQL_WLONGVARCHAR: SqlSmallInt = -10;
+pub const SQL_GUID: SqlSmallInt = -11;
+
+// C data types used when binding columns.
+pub const SQL_C_CHAR: SqlSmallInt = 1;
+pub const SQL_C_FLOAT: SqlSmallInt = 7;
+pub const SQL_C_DOUBLE: SqlSmallInt = 8;
+pub const SQL_C_BIT: SqlSmallInt = -7;
+pub const SQL_C_BINARY: SqlSmallInt = -2;
+pub const SQL_C_SSHORT: SqlSmallInt = -15;
+pub const SQL_C_SLONG: SqlSmallInt = -16;
+pub const SQL_C_SBIGINT: SqlSmallInt = -25;
+pub const SQL_C_STINYINT: SqlSmallInt = -26;
+pub const SQL_C_TYPE_DATE: SqlSmallInt = 91;
+pub const SQL_C_TYPE_TIMESTAMP: SqlSmallInt = 93;
+
+/// SQL_DATE_STRUCT
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SqlDate {
+    pub year: i16,
+    pub month: u16,
+    pub day: u16,
+}
+
+/// SQL_TIMESTAMP_STRUCT
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SqlTimestamp {
+    pub year: i16,
+    pub month: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16,
+    /// Fractional seconds in nanoseconds.
+    pub fraction: u32,
+}
+
+/// Names to try when loading the driver manager.
+#[cfg(target_os = "windows")]
+const LIBRARY_NAMES: &[&str] = &["odbc32.dll"];
+#[cfg(target_os = "macos")]
+const LIBRARY_NAMES: &[&str] = &["libodbc.2.dylib", "libiodbc.2.dylib"];
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const LIBRARY_NAMES: &[&str] = &["libodbc.so.2", "libodbc.so", "libiodbc.so.2"];
+
+static DRIVER_MANAGER: OnceLock<Result<DriverManager, String>> = OnceLock::new();
+
+/// Get the driver manager, loading it on first use.
+pub fn driver_manager() -> Result<&'static DriverManager> {
+    DRIVER_MANAGER
+        .get_or_init(DriverManager::load)
+        .as_ref()
+        .map_err(|e| RayexecError::new(e.clone()))
+}
+
+/// Function pointers into the loaded driver manager.
+pub struct DriverManager {
+    pub alloc_handle:
+        unsafe extern "system" fn(SqlSmallInt, SqlHandle, *mut SqlHandle) -> SqlReturn,
+    pub free_handle: unsafe extern "system" fn(SqlSmallInt, SqlHandle) -> SqlReturn,
+    pub set_env_attr:
+        unsafe extern "system" fn(SqlHandle, SqlInteger, SqlPointer, SqlInteger) -> SqlReturn,
+    pub driver_connect: unsafe extern "system" fn(
+        SqlHandle,
+        SqlPointer,
+        *const u8,
+        SqlSmallInt,
+        *mut u8,
+        SqlSmallInt,
+        *mut SqlSmallInt,
+        SqlUSmallInt,
+    ) -> SqlReturn,
+    pub disconnect: unsafe extern "system" fn(SqlHandle) -> SqlReturn,
+    pub exec_direct: unsafe extern "system" fn(SqlHandle, *const u8, SqlInteger) -> SqlReturn,
+    pub num_result_cols: unsafe extern "system" fn(SqlHandle, *mut SqlSmallInt) -> SqlReturn,
+    pub describe_col: unsafe extern "system" fn(
+        SqlHandle,
+        SqlUSmallInt,
+        *mut u8,
+        SqlSmallInt,
+        *mut SqlSmallInt,
+        *mut SqlSmallInt,
+        *mut SqlULen,
+        *mut SqlSmallInt,
+        *mut SqlSmallInt,
+    ) -> SqlReturn,
+    pub set_stmt_attr:
+        unsafe extern "system" fn(SqlHandle, SqlInteger, SqlPointer, SqlInteger) -> SqlReturn,
+    pub bind_col: unsafe extern "system" fn(
+        SqlHandle,
+        SqlUSmallInt,
+        SqlSmallInt,
+        SqlPointer,
+        SqlLen,
+        *mut SqlLen,
+    ) -> SqlReturn,
+    pub fetch: unsafe extern "system" fn(SqlHandle) -> SqlReturn,
+    pub get_diag_rec: unsafe extern "system" fn(
+        SqlSmallInt,
+        SqlHandle,
+        SqlSmallInt,
+        *mut u8,
+        *mut SqlInteger,
+        *mut u8,
+        SqlSmallInt,
+        *mut SqlSmallInt,
+    ) -> SqlReturn,
+    /// Keeps the function pointers above valid.
+    _library: Library,
+}
+
+impl DriverManager {
+    fn load() -> Result<Self, String> {
+        let mut errors = Vec::new();
+        for name in LIBRARY_NAMES {
+            // SAFETY: Loading the driver manager runs its initialization
+            // routines, which we trust.
+            match unsafe { Library::new(name) } {
+                Ok(library) => return Self::from_library(library),
+                Err(e) => errors.push(format!("{name}: {e}")),
+            }
+        }
+
+        Err(format!(
+            "Failed to load ODBC driver manager, is unixODBC installed? ({})",
+            errors.join(", ")
+        ))
+    }
+
+    fn from_library(library: Library) -> Result<Self, String> {
+        /// Get a function pointer from the library.
+        ///
+        /// SAFETY: The caller must ensure the function type matches the
+        /// symbol's signature.
+        unsafe fn symbol<T: Copy>(library: &Library, name: &str) -> Result<T, String> {
+            library
+                .get::<T>(name.as_bytes())
+                .map(|sym| *sym)
+                .map_err(|e| format!("Missing ODBC function {name}: {e}"))
+        }
+
+        // SAFETY: Signatures match the ODBC 3 ANSI API.
+        unsafe {
+            Ok(DriverManager {
+                alloc_handle: symbol(&library, "SQLAllocHandle")?,
+                free_handle: symbol(&library, "SQLFreeHandle")?,
+                set_env_attr: symbol(&library, "SQLSetEnvAttr")?,
+                driver_connect: symbol(&library, "SQLDriverConnect")?,
+                disconnect: symbol(&library, "SQLDisconnect")?,
+                exec_direct: symbol(&library, "SQLExecDirect")?,
+                num_result_cols: symbol(&library, "SQLNumResultCols")?,
+                describe_col: symbol(&library, "SQLDescribeCol")?,
+                set_stmt_attr: symbol(&library, "SQLSetStmtAttr")?,
+                bind_col: symbol(&library, "SQLBindCol")?,
+                fetch: symbol(&library, "SQLFetch")?,
+                get_diag_rec: symbol(&library, "SQLGetDiagRec")?,
+                _library: library,
+            })
+        }
+    }
+
+    /// Collect diagnostic records for a handle into a single message.
+    pub fn diagnostics(&self, handle_type: SqlSmallInt, handle: SqlHandle) -> String {
+        let mut messages = Vec::new();
+        for rec in 1.. {
+            let mut state = [0_u8; 6];
+            let mut native = 0;
+            let mut message = [0_u8; 1024];
+            let mut message_len = 0;
+
+            // SAFETY: Buffers outlive the call and their lengths are passed
+            // through.
+            let ret = unsafe {
+                (self.get_diag_rec)(
+                    handle_type,
+                    handle,
+                    rec,
+                    state.as_mut_ptr(),
+                    &mut native,
+                    message.as_mut_ptr(),
+                    message.len() as SqlSmallInt,
+                    &mut message_len,
+                )
+            };
+            if !succeeded(ret) {
+                break;
+            }
+
+            let message_len = (message_len.max(0) as usize).min(message.len() - 1);
+            messages.push(format!(
+                "[{}] {}",
+                String::from_utf8_lossy(&state[..5]),
+                String::from_utf8_lossy(&message[..message_len])
+            ));
+        }
+
+        if messages.is_empty() {
+            "no diagnostics available".to_string()
+        } else {
+            messages.join("; ")
+        }
+    }
+
+    /// Check the return code from an ODBC call, producing an error from the
+    /// handle's diagnostics if the call failed.
+    pub fn check(
+        &self,
+        ret: SqlReturn,
+        handle_type: SqlSmallInt,
+        handle: SqlHandle,
+        context: &str,
+    ) -> Result<()> {
+        if succeeded(ret) {
+            Ok(())
+        } else {
+            Err(RayexecError::new(format!(
+                "{context}: {}",
+                self.diagnostics(handle_type, handle)
+            )))
+        }
+    }
+}
+
+impl fmt::Debug for DriverManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DriverManager").finish_non_exhaustive()
+    }
+}
+
+pub fn succeeded(ret: SqlReturn) -> bool {
+    ret == SQL_SUCCESS || ret == SQL_SUCCESS_WITH_INFO
+}
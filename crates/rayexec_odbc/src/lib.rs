@@ -0,0 +1,35 @@
+pub mod buffer;
+pub mod client;
+pub mod datatable;
+pub mod read_odbc;
+
+mod ffi;
+
+use rayexec_execution::datasource::{DataSource, DataSourceBuilder};
+use rayexec_execution::functions::table::TableFunction;
+use rayexec_execution::runtime::Runtime;
+use read_odbc::ReadOdbc;
+
+/// Data source for reading from any system with an ODBC driver.
+///
+/// The ODBC driver manager (unixODBC on Linux and macOS) is loaded when first
+/// connecting, and needs to be installed along with drivers for the systems
+/// being read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OdbcDataSource<R: Runtime> {
+    runtime: R,
+}
+
+impl<R: Runtime> DataSourceBuilder<R> for OdbcDataSource<R> {
+    fn initialize(runtime: R) -> Box<dyn DataSource> {
+        Box::new(OdbcDataSource { runtime })
+    }
+}
+
+impl<R: Runtime> DataSource for OdbcDataSource<R> {
+    fn initialize_table_functions(&self) -> Vec<Box<dyn TableFunction>> {
+        vec![Box::new(ReadOdbc {
+            runtime: self.runtime.clone(),
+        })]
+    }
+}
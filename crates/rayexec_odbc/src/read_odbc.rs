@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::datatype::DataTypeId;
+use rayexec_execution::arrays::field::Schema;
+use rayexec_execution::arrays::scalar::OwnedScalarValue;
+use rayexec_execution::database::DatabaseContext;
+use rayexec_execution::expr;
+use rayexec_execution::functions::documentation::{Category, Documentation};
+use rayexec_execution::functions::table::{
+    PlannedTableFunction,
+    ScanPlanner,
+    TableFunction,
+    TableFunctionImpl,
+    TableFunctionPlanner,
+};
+use rayexec_execution::functions::{FunctionInfo, Signature};
+use rayexec_execution::logical::statistics::StatisticsValue;
+use rayexec_execution::runtime::{Runtime, TokioHandlerProvider};
+
+use crate::client::OdbcConnection;
+use crate::datatable::{run_blocking, OdbcDataTable};
+
+/// Read a table through an ODBC connection string.
+///
+/// The table name is inserted into the query as is, and should be quoted as
+/// required by the remote system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadOdbc<R: Runtime> {
+    pub(crate) runtime: R,
+}
+
+impl<R: Runtime> FunctionInfo for ReadOdbc<R> {
+    fn name(&self) -> &'static str {
+        "read_odbc"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        const DOC: &Documentation = &Documentation {
+            category: Category::Table,
+            description: "Read a table from any ODBC data source using a connection string.",
+            arguments: &["connection_string", "table"],
+            example: None,
+        };
+
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(DOC),
+        }]
+    }
+}
+
+impl<R: Runtime> TableFunction for ReadOdbc<R> {
+    fn planner(&self) -> TableFunctionPlanner {
+        TableFunctionPlanner::Scan(self)
+    }
+}
+
+impl<R: Runtime> ScanPlanner for ReadOdbc<R> {
+    fn plan<'a>(
+        &self,
+        context: &'a DatabaseContext,
+        positional_inputs: Vec<OwnedScalarValue>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> BoxFuture<'a, Result<PlannedTableFunction>> {
+        Self::plan_inner(self.clone(), context, positional_inputs, named_inputs).boxed()
+    }
+}
+
+impl<R: Runtime> ReadOdbc<R> {
+    async fn plan_inner(
+        self,
+        context: &DatabaseContext,
+        positional_inputs: Vec<OwnedScalarValue>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> Result<PlannedTableFunction> {
+        if !named_inputs.is_empty() {
+            return Err(RayexecError::new(
+                "read_odbc does not accept named arguments",
+            ));
+        }
+        if positional_inputs.len() != 2 {
+            return Err(RayexecError::new("read_odbc requires 2 arguments"));
+        }
+
+        let connection_string = positional_inputs.first().unwrap().try_as_str()?.to_string();
+        let table = positional_inputs.get(1).unwrap().try_as_str()?.to_string();
+
+        let handle = self.runtime.tokio_handle().handle()?;
+
+        // Get the columns from an empty result.
+        let fields = {
+            let connection_string = connection_string.clone();
+            let query = format!("SELECT * FROM {table} WHERE 1 = 0");
+            run_blocking(&handle, move || {
+                let conn = OdbcConnection::connect(&connection_string)?;
+                let stmt = conn.execute(&query, None)?;
+                stmt.describe()?
+                    .iter()
+                    .map(|col| col.field())
+                    .collect::<Result<Vec<_>>>()
+            })
+            .await?
+        };
+
+        let datatable = OdbcDataTable {
+            connection_string,
+            table,
+            fields: fields.clone(),
+            handle,
+            // ODBC scans can't resume a partition, stalls always fail.
+            stall_timeout: context.scan_runtime(&self.runtime).scan_stall().timeout,
+        };
+
+        Ok(PlannedTableFunction {
+            function: Box::new(self),
+            positional_inputs: positional_inputs.into_iter().map(expr::lit).collect(),
+            named_inputs,
+            function_impl: TableFunctionImpl::Scan(Arc::new(datatable)),
+            cardinality: StatisticsValue::Unknown,
+            schema: Schema::new(fields),
+        })
+    }
+}
@@ -0,0 +1,9 @@
+fn main() {
+    if let Err(e) = prost_build::compile_protos(&["proto/orc_proto.proto"], &["proto"]) {
+        // Printing out the error here instead of returning it so that we print
+        // out the Display impl of the error which is easier to read (properly
+        // formatted newlines).
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
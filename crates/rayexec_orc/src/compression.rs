@@ -0,0 +1,170 @@
+//! Decompression of ORC streams.
+//!
+//! Compressed streams are split into chunks, each prefixed with a 3 byte
+//! header containing the chunk length and if the chunk is stored without
+//! compression.
+use std::io::Read;
+
+use flate2::read::DeflateDecoder;
+use rayexec_error::{not_implemented, RayexecError, Result, ResultExt};
+
+use crate::proto::CompressionKind;
+
+/// Compression used for a file's footer, metadata, and streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Compression {
+    pub kind: CompressionKind,
+    /// Max size of a decompressed chunk.
+    pub block_size: usize,
+}
+
+impl Compression {
+    pub const NONE: Self = Compression {
+        kind: CompressionKind::None,
+        block_size: 0,
+    };
+
+    /// Decompress a complete stream.
+    pub fn decompress(&self, buf: &[u8]) -> Result<Vec<u8>> {
+        if self.kind == CompressionKind::None {
+            return Ok(buf.to_vec());
+        }
+
+        let mut out = Vec::with_capacity(buf.len());
+        let mut remaining = buf;
+
+        while !remaining.is_empty() {
+            if remaining.len() < 3 {
+                return Err(RayexecError::new("Truncated compression chunk header"));
+            }
+
+            let header = remaining[0] as usize
+                | (remaining[1] as usize) << 8
+                | (remaining[2] as usize) << 16;
+            let is_original = header & 1 == 1;
+            let len = header >> 1;
+
+            let chunk = remaining
+                .get(3..3 + len)
+                .ok_or_else(|| RayexecError::new("Truncated compression chunk"))?;
+            remaining = &remaining[3 + len..];
+
+            if is_original {
+                out.extend_from_slice(chunk);
+            } else {
+                self.decompress_chunk(chunk, &mut out)?;
+            }
+        }
+
+        Ok(out)
+    }
+
+    fn decompress_chunk(&self, chunk: &[u8], out: &mut Vec<u8>) -> Result<()> {
+        match self.kind {
+            CompressionKind::None => out.extend_from_slice(chunk),
+            CompressionKind::Zlib => {
+                DeflateDecoder::new(chunk)
+                    .read_to_end(out)
+                    .context("Failed to decompress zlib chunk")?;
+            }
+            CompressionKind::Snappy => {
+                let decompressed = snap::raw::Decoder::new()
+                    .decompress_vec(chunk)
+                    .context("Failed to decompress snappy chunk")?;
+                out.extend_from_slice(&decompressed);
+            }
+            CompressionKind::Lz4 => {
+                let decompressed = lz4_flex::block::decompress(chunk, self.block_size)
+                    .context("Failed to decompress lz4 chunk")?;
+                out.extend_from_slice(&decompressed);
+            }
+            CompressionKind::Zstd => {
+                #[cfg(feature = "zstd")]
+                {
+                    let decompressed = zstd::bulk::decompress(chunk, self.block_size)
+                        .context("Failed to decompress zstd chunk")?;
+                    out.extend_from_slice(&decompressed);
+                }
+                #[cfg(not(feature = "zstd"))]
+                {
+                    return Err(RayexecError::new(
+                        "Reading zstd compressed ORC files requires the 'zstd' feature",
+                    ));
+                }
+            }
+            CompressionKind::Lzo => not_implemented!("LZO compressed ORC files"),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::write::DeflateEncoder;
+
+    use super::*;
+
+    fn chunk_header(len: usize, is_original: bool) -> [u8; 3] {
+        let header = (len << 1) | is_original as usize;
+        [header as u8, (header >> 8) as u8, (header >> 16) as u8]
+    }
+
+    #[test]
+    fn uncompressed_passthrough() {
+        let out = Compression::NONE.decompress(&[1, 2, 3]).unwrap();
+        assert_eq!(vec![1, 2, 3], out);
+    }
+
+    #[test]
+    fn zlib_chunks() {
+        let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"hello ").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        // A compressed chunk followed by an original chunk.
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&chunk_header(compressed.len(), false));
+        buf.extend_from_slice(&compressed);
+        buf.extend_from_slice(&chunk_header(5, true));
+        buf.extend_from_slice(b"world");
+
+        let compression = Compression {
+            kind: CompressionKind::Zlib,
+            block_size: 256 * 1024,
+        };
+        let out = compression.decompress(&buf).unwrap();
+        assert_eq!(b"hello world".as_slice(), out.as_slice());
+    }
+
+    #[test]
+    fn snappy_chunk() {
+        let compressed = snap::raw::Encoder::new()
+            .compress_vec(b"snappy snappy snappy")
+            .unwrap();
+
+        let mut buf = chunk_header(compressed.len(), false).to_vec();
+        buf.extend_from_slice(&compressed);
+
+        let compression = Compression {
+            kind: CompressionKind::Snappy,
+            block_size: 256 * 1024,
+        };
+        let out = compression.decompress(&buf).unwrap();
+        assert_eq!(b"snappy snappy snappy".as_slice(), out.as_slice());
+    }
+
+    #[test]
+    fn truncated_chunk() {
+        let mut buf = chunk_header(10, true).to_vec();
+        buf.extend_from_slice(&[1, 2, 3]);
+
+        let compression = Compression {
+            kind: CompressionKind::Zlib,
+            block_size: 256 * 1024,
+        };
+        compression.decompress(&buf).unwrap_err();
+    }
+}
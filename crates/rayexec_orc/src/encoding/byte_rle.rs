@@ -0,0 +1,76 @@
+use rayexec_error::Result;
+
+use super::ByteReader;
+
+/// Decode `count` bytes from a byte run length encoded stream.
+///
+/// Each run starts with a header byte. Non-negative headers are followed by a
+/// single byte repeated `header + 3` times, negative headers are followed by
+/// `-header` literal bytes.
+pub fn decode_byte_rle(buf: &[u8], count: usize) -> Result<Vec<u8>> {
+    let mut reader = ByteReader::new(buf);
+    let mut out = Vec::with_capacity(count);
+
+    while out.len() < count {
+        let header = reader.read_u8()? as i8;
+        if header >= 0 {
+            let len = header as usize + 3;
+            let value = reader.read_u8()?;
+            out.extend(std::iter::repeat_n(value, len));
+        } else {
+            let len = -(header as isize) as usize;
+            out.extend_from_slice(reader.read_bytes(len)?);
+        }
+    }
+
+    // Runs may extend past the values we need.
+    out.truncate(count);
+
+    Ok(out)
+}
+
+/// Decode `count` booleans from a boolean run length encoded stream.
+///
+/// Booleans are packed into bytes, most significant bit first, and the bytes
+/// are byte run length encoded.
+pub fn decode_boolean_rle(buf: &[u8], count: usize) -> Result<Vec<bool>> {
+    let bytes = decode_byte_rle(buf, count.div_ceil(8))?;
+    Ok(bytes
+        .iter()
+        .flat_map(|b| (0..8).map(move |bit| b & (0x80 >> bit) != 0))
+        .take(count)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_runs_and_literals() {
+        // Examples from the spec.
+        assert_eq!(vec![0; 100], decode_byte_rle(&[0x61, 0x00], 100).unwrap());
+        assert_eq!(
+            vec![0x44, 0x45],
+            decode_byte_rle(&[0xfe, 0x44, 0x45], 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn byte_rle_truncated() {
+        decode_byte_rle(&[0xfe, 0x44], 2).unwrap_err();
+    }
+
+    #[test]
+    fn booleans() {
+        // A true followed by 15 falses.
+        let out = decode_boolean_rle(&[0xfe, 0x80, 0x00], 16).unwrap();
+        let mut expected = vec![false; 16];
+        expected[0] = true;
+        assert_eq!(expected, out);
+
+        // Trailing bits of the last byte are ignored.
+        let out = decode_boolean_rle(&[0xff, 0b1010_1111], 4).unwrap();
+        assert_eq!(vec![true, false, true, false], out);
+    }
+}
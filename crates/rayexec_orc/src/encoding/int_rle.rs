@@ -0,0 +1,371 @@
+use rayexec_error::{RayexecError, Result};
+
+use super::{zigzag_decode, ByteReader};
+
+/// Version of the integer run length encoding used by a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RleVersion {
+    V1,
+    V2,
+}
+
+/// Decode `count` integers from an integer run length encoded stream.
+///
+/// Unsigned values are returned reinterpreted as i64.
+pub fn decode_int_rle(
+    buf: &[u8],
+    count: usize,
+    signed: bool,
+    version: RleVersion,
+) -> Result<Vec<i64>> {
+    let mut reader = ByteReader::new(buf);
+    let mut out = Vec::with_capacity(count);
+
+    while out.len() < count {
+        match version {
+            RleVersion::V1 => decode_v1_run(&mut reader, signed, &mut out)?,
+            RleVersion::V2 => decode_v2_run(&mut reader, signed, &mut out)?,
+        }
+    }
+
+    out.truncate(count);
+
+    Ok(out)
+}
+
+fn read_varint(reader: &mut ByteReader, signed: bool) -> Result<i64> {
+    if signed {
+        reader.read_svarint()
+    } else {
+        Ok(reader.read_uvarint()? as i64)
+    }
+}
+
+/// Decode a single run using the version 1 encoding.
+///
+/// Non-negative headers are followed by a delta byte and a base value, and
+/// produce `header + 3` values. Negative headers are followed by `-header`
+/// literal varints.
+fn decode_v1_run(reader: &mut ByteReader, signed: bool, out: &mut Vec<i64>) -> Result<()> {
+    let header = reader.read_u8()? as i8;
+    if header >= 0 {
+        let len = header as i64 + 3;
+        let delta = reader.read_u8()? as i8 as i64;
+        let base = read_varint(reader, signed)?;
+        out.extend((0..len).map(|idx| base.wrapping_add(idx * delta)));
+    } else {
+        for _ in 0..-(header as i32) {
+            out.push(read_varint(reader, signed)?);
+        }
+    }
+    Ok(())
+}
+
+/// Decode a single run using the version 2 encoding.
+///
+/// The top two bits of the first byte select between short repeat, direct,
+/// patched base, and delta sub-encodings.
+fn decode_v2_run(reader: &mut ByteReader, signed: bool, out: &mut Vec<i64>) -> Result<()> {
+    let first = reader.read_u8()?;
+    match first >> 6 {
+        0 => {
+            // Short repeat.
+            let width = ((first >> 3) & 0x07) as usize + 1;
+            let len = (first & 0x07) as usize + 3;
+            let value = read_be(reader, width)?;
+            let value = if signed {
+                zigzag_decode(value)
+            } else {
+                value as i64
+            };
+            out.extend(std::iter::repeat_n(value, len));
+        }
+        1 => {
+            // Direct.
+            let width = decode_width((first >> 1) & 0x1f);
+            let len = read_v2_len(reader, first)?;
+            let start = out.len();
+            unpack(reader, width, len, out)?;
+            if signed {
+                for v in &mut out[start..] {
+                    *v = zigzag_decode(*v as u64);
+                }
+            }
+        }
+        2 => decode_patched_base(reader, first, out)?,
+        _ => decode_delta(reader, first, signed, out)?,
+    }
+    Ok(())
+}
+
+/// Read the 9 bit run length stored in the first two bytes of direct,
+/// patched base, and delta runs.
+fn read_v2_len(reader: &mut ByteReader, first: u8) -> Result<usize> {
+    let second = reader.read_u8()?;
+    Ok((((first & 0x01) as usize) << 8 | second as usize) + 1)
+}
+
+fn decode_patched_base(reader: &mut ByteReader, first: u8, out: &mut Vec<i64>) -> Result<()> {
+    let width = decode_width((first >> 1) & 0x1f);
+    let len = read_v2_len(reader, first)?;
+
+    let third = reader.read_u8()?;
+    let base_width = ((third >> 5) & 0x07) as usize + 1;
+    let patch_width = decode_width(third & 0x1f);
+
+    let fourth = reader.read_u8()?;
+    let patch_gap_width = ((fourth >> 5) & 0x07) as usize + 1;
+    let patch_list_len = (fourth & 0x1f) as usize;
+
+    // Base is stored big endian with the most significant bit as the sign.
+    let base = read_be(reader, base_width)?;
+    let sign_mask = 1_u64 << (base_width * 8 - 1);
+    let base = if base & sign_mask != 0 {
+        -((base & !sign_mask) as i64)
+    } else {
+        base as i64
+    };
+
+    let start = out.len();
+    unpack(reader, width, len, out)?;
+
+    let mut patches = Vec::with_capacity(patch_list_len);
+    unpack(
+        reader,
+        closest_fixed_bits(patch_gap_width + patch_width),
+        patch_list_len,
+        &mut patches,
+    )?;
+
+    let patch_mask = (1_u64 << patch_width) - 1;
+    let mut idx = start;
+    for patch in patches {
+        let patch = patch as u64;
+        let gap = (patch >> patch_width) as usize;
+        let value = patch & patch_mask;
+        idx += gap;
+        if value == 0 {
+            // Gaps larger than can be stored are split with empty patches.
+            continue;
+        }
+        let target = out
+            .get_mut(idx)
+            .ok_or_else(|| RayexecError::new("Patch index out of range"))?;
+        *target = ((*target as u64) | (value << width)) as i64;
+    }
+
+    for v in &mut out[start..] {
+        *v = base.wrapping_add(*v);
+    }
+
+    Ok(())
+}
+
+fn decode_delta(
+    reader: &mut ByteReader,
+    first: u8,
+    signed: bool,
+    out: &mut Vec<i64>,
+) -> Result<()> {
+    let code = (first >> 1) & 0x1f;
+    let width = if code == 0 { 0 } else { decode_width(code) };
+    let len = read_v2_len(reader, first)?;
+
+    let base = read_varint(reader, signed)?;
+    let delta_base = reader.read_svarint()?;
+
+    out.push(base);
+    if len == 1 {
+        return Ok(());
+    }
+
+    if width == 0 {
+        // Fixed delta.
+        let mut prev = base;
+        for _ in 1..len {
+            prev = prev.wrapping_add(delta_base);
+            out.push(prev);
+        }
+        return Ok(());
+    }
+
+    let mut prev = base.wrapping_add(delta_base);
+    out.push(prev);
+
+    // Remaining deltas all have the same sign as the delta base.
+    let mut deltas = Vec::with_capacity(len.saturating_sub(2));
+    unpack(reader, width, len.saturating_sub(2), &mut deltas)?;
+    for delta in deltas {
+        prev = if delta_base < 0 {
+            prev.wrapping_sub(delta)
+        } else {
+            prev.wrapping_add(delta)
+        };
+        out.push(prev);
+    }
+
+    Ok(())
+}
+
+/// Read an unsigned big endian value of `width` bytes.
+fn read_be(reader: &mut ByteReader, width: usize) -> Result<u64> {
+    let bytes = reader.read_bytes(width)?;
+    Ok(bytes.iter().fold(0_u64, |acc, &b| (acc << 8) | b as u64))
+}
+
+/// Unpack `count` big endian bit packed values of `width` bits.
+///
+/// Packed values always start on a byte boundary, any unused bits in the
+/// last byte are skipped.
+fn unpack(reader: &mut ByteReader, width: usize, count: usize, out: &mut Vec<i64>) -> Result<()> {
+    let bytes = reader.read_bytes((width * count).div_ceil(8))?;
+
+    let mut bit_pos = 0;
+    for _ in 0..count {
+        let mut value = 0_u64;
+        let mut remaining = width;
+        while remaining > 0 {
+            let byte = bytes[bit_pos / 8];
+            let offset = bit_pos % 8;
+            let take = remaining.min(8 - offset);
+            let bits = (byte >> (8 - offset - take)) & ((1_u16 << take) - 1) as u8;
+            value = (value << take) | bits as u64;
+            remaining -= take;
+            bit_pos += take;
+        }
+        out.push(value as i64);
+    }
+
+    Ok(())
+}
+
+/// Decode the 5 bit width code used by direct, patched base, and delta runs.
+const fn decode_width(code: u8) -> usize {
+    match code {
+        0..=23 => code as usize + 1,
+        24 => 26,
+        25 => 28,
+        26 => 30,
+        27 => 32,
+        28 => 40,
+        29 => 48,
+        30 => 56,
+        _ => 64,
+    }
+}
+
+/// Round a bit width up to one of the widths that can be encoded.
+const fn closest_fixed_bits(width: usize) -> usize {
+    match width {
+        0 => 1,
+        1..=24 => width,
+        25..=26 => 26,
+        27..=28 => 28,
+        29..=30 => 30,
+        31..=32 => 32,
+        33..=40 => 40,
+        41..=48 => 48,
+        49..=56 => 56,
+        _ => 64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v1_run_and_literals() {
+        // Examples from the spec.
+        let out = decode_int_rle(&[0x61, 0x00, 0x07], 100, false, RleVersion::V1).unwrap();
+        assert_eq!(vec![7; 100], out);
+
+        let out = decode_int_rle(&[0x61, 0xff, 0x64], 100, false, RleVersion::V1).unwrap();
+        assert_eq!((1..=100).rev().collect::<Vec<i64>>(), out);
+
+        let out = decode_int_rle(
+            &[0xfb, 0x02, 0x03, 0x06, 0x07, 0x0b],
+            5,
+            false,
+            RleVersion::V1,
+        )
+        .unwrap();
+        assert_eq!(vec![2, 3, 6, 7, 11], out);
+    }
+
+    #[test]
+    fn v1_signed() {
+        // Run of 3 starting at -1 with a delta of -2.
+        let out = decode_int_rle(&[0x00, 0xfe, 0x01], 3, true, RleVersion::V1).unwrap();
+        assert_eq!(vec![-1, -3, -5], out);
+    }
+
+    #[test]
+    fn v2_short_repeat() {
+        let out = decode_int_rle(&[0x0a, 0x27, 0x10], 5, false, RleVersion::V2).unwrap();
+        assert_eq!(vec![10000; 5], out);
+    }
+
+    #[test]
+    fn v2_direct() {
+        let out = decode_int_rle(
+            &[0x5e, 0x03, 0x5c, 0xa1, 0xab, 0x1e, 0xde, 0xad, 0xbe, 0xef],
+            4,
+            false,
+            RleVersion::V2,
+        )
+        .unwrap();
+        assert_eq!(vec![23713, 43806, 57005, 48879], out);
+    }
+
+    #[test]
+    fn v2_patched_base() {
+        let buf = [
+            0x8e, 0x13, 0x2b, 0x21, 0x07, 0xd0, 0x1e, 0x00, 0x14, 0x70, 0x28, 0x32, 0x3c, 0x46,
+            0x50, 0x5a, 0x64, 0x6e, 0x78, 0x82, 0x8c, 0x96, 0xa0, 0xaa, 0xb4, 0xbe, 0xfc, 0xe8,
+        ];
+        let out = decode_int_rle(&buf, 20, false, RleVersion::V2).unwrap();
+        assert_eq!(
+            vec![
+                2030, 2000, 2020, 1000000, 2040, 2050, 2060, 2070, 2080, 2090, 2100, 2110, 2120,
+                2130, 2140, 2150, 2160, 2170, 2180, 2190
+            ],
+            out
+        );
+    }
+
+    #[test]
+    fn v2_delta() {
+        let out = decode_int_rle(
+            &[0xc6, 0x09, 0x02, 0x02, 0x22, 0x42, 0x42, 0x46],
+            10,
+            false,
+            RleVersion::V2,
+        )
+        .unwrap();
+        assert_eq!(vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29], out);
+    }
+
+    #[test]
+    fn v2_fixed_delta_signed() {
+        // Width 0, 4 values, base 10 (zigzag 20), delta -3 (zigzag 5).
+        let out = decode_int_rle(&[0xc0, 0x03, 0x14, 0x05], 4, true, RleVersion::V2).unwrap();
+        assert_eq!(vec![10, 7, 4, 1], out);
+    }
+
+    #[test]
+    fn v2_multiple_runs() {
+        let mut buf = vec![0x0a, 0x27, 0x10];
+        buf.extend_from_slice(&[0xc6, 0x09, 0x02, 0x02, 0x22, 0x42, 0x42, 0x46]);
+
+        let out = decode_int_rle(&buf, 15, false, RleVersion::V2).unwrap();
+        assert_eq!(10000, out[4]);
+        assert_eq!(2, out[5]);
+        assert_eq!(29, out[14]);
+    }
+
+    #[test]
+    fn truncated() {
+        decode_int_rle(&[0x5e, 0x03, 0x5c], 4, false, RleVersion::V2).unwrap_err();
+    }
+}
@@ -0,0 +1,103 @@
+//! Decoders for the run length encodings used in ORC streams.
+//!
+//! See <https://orc.apache.org/specification/ORCv1/>
+pub mod byte_rle;
+pub mod int_rle;
+
+use rayexec_error::{RayexecError, Result};
+
+/// Reads values from a decompressed stream.
+#[derive(Debug)]
+pub struct ByteReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        ByteReader { buf, pos: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.buf.len()
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8> {
+        let b = *self
+            .buf
+            .get(self.pos)
+            .ok_or_else(|| RayexecError::new("Unexpected end of ORC stream"))?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    pub fn read_bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        let bs = self
+            .buf
+            .get(self.pos..self.pos + n)
+            .ok_or_else(|| RayexecError::new("Unexpected end of ORC stream"))?;
+        self.pos += n;
+        Ok(bs)
+    }
+
+    /// Read a base 128 varint.
+    pub fn read_uvarint(&mut self) -> Result<u64> {
+        let mut value = 0_u64;
+        let mut shift = 0;
+        loop {
+            let b = self.read_u8()?;
+            if shift >= 64 {
+                return Err(RayexecError::new("Varint too long"));
+            }
+            value |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    /// Read a zigzag encoded base 128 varint.
+    pub fn read_svarint(&mut self) -> Result<i64> {
+        Ok(zigzag_decode(self.read_uvarint()?))
+    }
+
+    /// Read a zigzag encoded base 128 varint of up to 128 bits, used for
+    /// decimals.
+    pub fn read_svarint128(&mut self) -> Result<i128> {
+        let mut value = 0_u128;
+        let mut shift = 0;
+        loop {
+            let b = self.read_u8()?;
+            if shift >= 128 {
+                return Err(RayexecError::new("Decimal varint too long"));
+            }
+            value |= ((b & 0x7f) as u128) << shift;
+            if b & 0x80 == 0 {
+                return Ok((value >> 1) as i128 ^ -((value & 1) as i128));
+            }
+            shift += 7;
+        }
+    }
+}
+
+pub const fn zigzag_decode(v: u64) -> i64 {
+    (v >> 1) as i64 ^ -((v & 1) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn varints() {
+        let mut reader = ByteReader::new(&[0x96, 0x01, 0x03, 0x04, 0x81, 0x01]);
+        assert_eq!(150, reader.read_uvarint().unwrap());
+        assert_eq!(-2, reader.read_svarint().unwrap());
+        assert_eq!(2, reader.read_svarint().unwrap());
+        assert_eq!(-65, reader.read_svarint128().unwrap());
+        assert!(reader.is_empty());
+
+        reader.read_uvarint().unwrap_err();
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use rayexec_error::Result;
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::logical::scan_filter::ScanFilter;
+use rayexec_execution::runtime::Runtime;
+use rayexec_execution::storage::table_storage::{
+    DataTable,
+    DataTableScan,
+    DataVersion,
+    LimitedScan,
+    Projections,
+    SampleMethod,
+    SampledScan,
+    TableSample,
+    TableStatistics,
+};
+use rayexec_io::location::{AccessConfig, FileLocation};
+use rayexec_io::{FileProvider, FileSource};
+
+use crate::metadata::OrcMetadata;
+use crate::reader::AsyncStripeReader;
+use crate::statistics::{stripe_may_match, table_statistics};
+
+/// Data table implementation which parallelizes on stripes. During scanning,
+/// each returned scan object is responsible for distinct stripes to read.
+#[derive(Debug)]
+pub struct StripePartitionedDataTable<R: Runtime> {
+    pub metadata: Arc<OrcMetadata>,
+    /// Version of the file when it was planned.
+    pub version: Option<String>,
+    pub location: FileLocation,
+    pub conf: AccessConfig,
+    pub runtime: R,
+}
+
+impl<R: Runtime> StripePartitionedDataTable<R> {
+    /// Create scans for reading the provided stripes.
+    fn scan_stripes(
+        &self,
+        stripes: impl IntoIterator<Item = usize>,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let file_provider = self.runtime.file_provider();
+
+        let mut partitioned_stripes = vec![VecDeque::new(); num_partitions];
+
+        // Split stripes into individual partitions.
+        for (idx, stripe) in stripes.into_iter().enumerate() {
+            let partition = idx % num_partitions;
+            partitioned_stripes[partition].push_back(stripe);
+        }
+
+        partitioned_stripes
+            .into_iter()
+            .map(|stripes| {
+                let reader = file_provider.file_source(self.location.clone(), &self.conf)?;
+                let reader = AsyncStripeReader::try_new(
+                    reader,
+                    stripes,
+                    self.metadata.clone(),
+                    batch_size,
+                    projections.clone(),
+                )?;
+                Ok(Box::new(StripesScan { reader }) as _)
+            })
+            .collect()
+    }
+}
+
+impl<R: Runtime> DataTable for StripePartitionedDataTable<R> {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let num_stripes = self.metadata.stripes().len();
+        self.scan_stripes(0..num_stripes, projections, num_partitions, batch_size)
+    }
+
+    fn scan_filtered(
+        &self,
+        projections: Projections,
+        filters: &[ScanFilter],
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        // Skip stripes whose statistics show they can't match.
+        let num_stripes = self.metadata.stripes().len();
+        let stripes =
+            (0..num_stripes).filter(|&stripe| stripe_may_match(&self.metadata, stripe, filters));
+        self.scan_stripes(stripes, projections, num_partitions, batch_size)
+    }
+
+    fn scan_sample(
+        &self,
+        projections: Projections,
+        sample: &TableSample,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        match sample.method {
+            SampleMethod::System => {
+                // Stripes are our blocks, skip reading the stripes that aren't
+                // part of the sample.
+                let num_stripes = self.metadata.stripes().len();
+                let stripes = sample.sample_blocks(&mut sample.rng(0), num_stripes);
+                self.scan_stripes(stripes, projections, num_partitions, batch_size)
+            }
+            SampleMethod::Bernoulli => {
+                let scans = self.scan(projections, num_partitions, batch_size)?;
+                Ok(SampledScan::wrap_scans(scans, sample))
+            }
+        }
+    }
+
+    fn scan_limit(
+        &self,
+        projections: Projections,
+        limit: usize,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        // Only read enough stripes to cover the limit.
+        let mut num_rows = 0;
+        let num_stripes = self
+            .metadata
+            .stripes()
+            .iter()
+            .take_while(|stripe| {
+                let needs_more = num_rows < limit;
+                num_rows += stripe.number_of_rows() as usize;
+                needs_more
+            })
+            .count();
+
+        let scans = self.scan_stripes(0..num_stripes, projections, num_partitions, batch_size)?;
+        Ok(LimitedScan::wrap_scans(scans, limit))
+    }
+
+    fn data_version(&self) -> DataVersion {
+        DataVersion::from_opaque(self.version.as_deref())
+    }
+
+    fn statistics(&self) -> BoxFuture<'_, Result<TableStatistics>> {
+        let statistics = table_statistics(&self.metadata);
+        Box::pin(async move { Ok(statistics) })
+    }
+}
+
+struct StripesScan {
+    reader: AsyncStripeReader<Box<dyn FileSource>>,
+}
+
+impl DataTableScan for StripesScan {
+    fn pull(&mut self) -> BoxFuture<'_, Result<Option<Batch>>> {
+        Box::pin(async { self.reader.read_next().await })
+    }
+}
+
+impl fmt::Debug for StripesScan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StripesScan").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod read_orc;
+
+mod datatable;
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use rayexec_error::Result;
+use rayexec_execution::arrays::datatype::DataTypeId;
+use rayexec_execution::arrays::scalar::OwnedScalarValue;
+use rayexec_execution::database::DatabaseContext;
+use rayexec_execution::expr;
+use rayexec_execution::functions::documentation::{Category, Documentation};
+use rayexec_execution::functions::table::{
+    try_location_and_access_config_from_args,
+    PlannedTableFunction,
+    ScanPlanner,
+    TableFunction,
+    TableFunctionImpl,
+    TableFunctionPlanner,
+};
+use rayexec_execution::functions::{FunctionInfo, Signature};
+use rayexec_execution::runtime::Runtime;
+use rayexec_execution::storage::table_storage::DataTable;
+use rayexec_io::FileProvider;
+
+use super::datatable::StripePartitionedDataTable;
+use crate::metadata::OrcMetadata;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadOrc<R: Runtime> {
+    pub(crate) runtime: R,
+}
+
+impl<R: Runtime> FunctionInfo for ReadOrc<R> {
+    fn name(&self) -> &'static str {
+        "read_orc"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["orc_scan"]
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        const DOC: &Documentation = &Documentation {
+            category: Category::Table,
+            description: "Read an ORC file.",
+            arguments: &["path"],
+            example: None,
+        };
+
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(DOC),
+        }]
+    }
+}
+
+impl<R: Runtime> TableFunction for ReadOrc<R> {
+    fn planner(&self) -> TableFunctionPlanner {
+        TableFunctionPlanner::Scan(self)
+    }
+}
+
+impl<R: Runtime> ScanPlanner for ReadOrc<R> {
+    fn plan<'a>(
+        &self,
+        context: &'a DatabaseContext,
+        positional_inputs: Vec<OwnedScalarValue>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> BoxFuture<'a, Result<PlannedTableFunction>> {
+        Self::plan_inner(self.clone(), context, positional_inputs, named_inputs).boxed()
+    }
+}
+
+impl<R: Runtime> ReadOrc<R> {
+    async fn plan_inner(
+        self,
+        context: &DatabaseContext,
+        positional_inputs: Vec<OwnedScalarValue>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> Result<PlannedTableFunction> {
+        let (location, conf) =
+            try_location_and_access_config_from_args(&self, &positional_inputs, &named_inputs)?;
+        let runtime = context.scan_runtime(&self.runtime);
+
+        let mut source = runtime
+            .file_provider()
+            .file_source(location.clone(), &conf)?;
+
+        let size = source.size().await?;
+
+        let metadata = OrcMetadata::new_from_source(source.as_mut(), size).await?;
+        let schema = metadata.schema.schema.clone();
+
+        let datatable = StripePartitionedDataTable {
+            metadata: Arc::new(metadata),
+            version: source.version(),
+            location,
+            conf,
+            runtime,
+        };
+
+        let statistics = datatable.statistics().await?;
+
+        Ok(PlannedTableFunction {
+            function: Box::new(self),
+            positional_inputs: positional_inputs.into_iter().map(expr::lit).collect(),
+            named_inputs,
+            function_impl: TableFunctionImpl::Scan(Arc::new(datatable)),
+            cardinality: statistics.num_rows,
+            schema,
+        })
+    }
+}
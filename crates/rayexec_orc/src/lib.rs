@@ -0,0 +1,77 @@
+pub mod compression;
+pub mod encoding;
+pub mod functions;
+pub mod metadata;
+pub mod reader;
+pub mod schema;
+
+mod statistics;
+
+#[cfg(test)]
+mod testutil;
+
+#[allow(clippy::all)]
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/orc.proto.rs"));
+}
+
+use functions::read_orc::ReadOrc;
+use rayexec_execution::datasource::{DataSource, DataSourceBuilder, FileHandler};
+use rayexec_execution::functions::table::TableFunction;
+use rayexec_execution::runtime::Runtime;
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrcDataSource<R> {
+    runtime: R,
+}
+
+impl<R: Runtime> DataSourceBuilder<R> for OrcDataSource<R> {
+    fn initialize(runtime: R) -> Box<dyn DataSource> {
+        Box::new(Self { runtime })
+    }
+}
+
+impl<R> OrcDataSource<R> {
+    fn file_regex() -> Regex {
+        RegexBuilder::new(r"^.*\.(orc)$")
+            .case_insensitive(true)
+            .build()
+            .expect("regex to build")
+    }
+}
+
+impl<R: Runtime> DataSource for OrcDataSource<R> {
+    fn initialize_table_functions(&self) -> Vec<Box<dyn TableFunction>> {
+        vec![Box::new(ReadOrc {
+            runtime: self.runtime.clone(),
+        })]
+    }
+
+    fn file_handlers(&self) -> Vec<FileHandler> {
+        vec![FileHandler {
+            regex: Self::file_regex(),
+            table_func: Box::new(ReadOrc {
+                runtime: self.runtime.clone(),
+            }),
+            copy_to: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_regex() {
+        let regex = OrcDataSource::<()>::file_regex();
+
+        assert!(regex.is_match("file.orc"));
+        assert!(regex.is_match("file.ORC"));
+        assert!(regex.is_match("dir/*.orc"));
+
+        assert!(!regex.is_match("file.parquet"));
+        assert!(!regex.is_match("file.orc.bak"));
+    }
+}
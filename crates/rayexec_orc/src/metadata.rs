@@ -0,0 +1,125 @@
+use prost::Message;
+use rayexec_error::{RayexecError, Result, ResultExt};
+use rayexec_io::FileSource;
+use tracing::trace;
+
+use crate::compression::Compression;
+use crate::proto::{ColumnStatistics, Footer, Metadata, PostScript, StripeInformation};
+use crate::schema::OrcSchema;
+
+const MAGIC: &str = "ORC";
+
+/// Number of bytes to read from the end of the file when fetching the
+/// postscript. Typically large enough to include the footer and metadata too.
+const TAIL_READ_SIZE: usize = 16 * 1024;
+
+/// Default compression block size if not set in the postscript.
+const DEFAULT_BLOCK_SIZE: usize = 256 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct OrcMetadata {
+    pub postscript: PostScript,
+    pub footer: Footer,
+    /// Statistics for each stripe.
+    ///
+    /// Empty if the file doesn't contain stripe statistics.
+    pub stripe_statistics: Vec<Vec<ColumnStatistics>>,
+    pub compression: Compression,
+    pub schema: OrcSchema,
+}
+
+impl OrcMetadata {
+    /// Loads ORC metadata from an async source.
+    pub async fn new_from_source(reader: &mut dyn FileSource, size: usize) -> Result<Self> {
+        if size < MAGIC.len() + 1 {
+            return Err(RayexecError::new("File size is too small"));
+        }
+
+        let tail_len = size.min(TAIL_READ_SIZE);
+        let mut tail = reader.read_range(size - tail_len, tail_len).await?;
+        trace!("read orc tail bytes");
+
+        let ps_len = *tail.last().unwrap() as usize;
+        if tail_len < ps_len + 1 {
+            return Err(RayexecError::new("Postscript length exceeds file size"));
+        }
+        let postscript = PostScript::decode(&tail[tail_len - 1 - ps_len..tail_len - 1])
+            .context("failed to decode postscript")?;
+        if postscript.magic() != MAGIC {
+            return Err(RayexecError::new("Missing ORC magic in postscript"));
+        }
+
+        let footer_len = postscript.footer_length() as usize;
+        let metadata_len = postscript.metadata_length() as usize;
+
+        // Read more if the tail didn't include the footer and metadata.
+        let needed = footer_len + metadata_len + ps_len + 1;
+        if needed > size {
+            return Err(RayexecError::new(format!(
+                "File size of {size} is less than metadata + footer + postscript {needed}"
+            )));
+        }
+        if needed > tail_len {
+            tail = reader.read_range(size - needed, needed).await?;
+        }
+
+        let compression = Compression {
+            kind: postscript.compression(),
+            block_size: match postscript.compression_block_size {
+                Some(block_size) => block_size as usize,
+                None => DEFAULT_BLOCK_SIZE,
+            },
+        };
+
+        let footer_end = tail.len() - 1 - ps_len;
+        let footer_start = footer_end - footer_len;
+        let metadata_start = footer_start - metadata_len;
+
+        let footer = Footer::decode(
+            compression
+                .decompress(&tail[footer_start..footer_end])?
+                .as_slice(),
+        )
+        .context("failed to decode footer")?;
+
+        let stripe_statistics = if metadata_len > 0 {
+            let metadata = Metadata::decode(
+                compression
+                    .decompress(&tail[metadata_start..footer_start])?
+                    .as_slice(),
+            )
+            .context("failed to decode metadata")?;
+            metadata
+                .stripe_stats
+                .into_iter()
+                .map(|stats| stats.col_stats)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let schema = OrcSchema::try_from_types(&footer.types)?;
+
+        Ok(OrcMetadata {
+            postscript,
+            footer,
+            stripe_statistics,
+            compression,
+            schema,
+        })
+    }
+
+    pub fn stripes(&self) -> &[StripeInformation] {
+        &self.footer.stripes
+    }
+
+    /// Get column statistics for a stripe, if the file has them.
+    pub fn stripe_statistics(&self, stripe: usize) -> Option<&[ColumnStatistics]> {
+        // Writers may omit statistics, only use them if they line up with the
+        // stripes.
+        if self.stripe_statistics.len() != self.footer.stripes.len() {
+            return None;
+        }
+        self.stripe_statistics.get(stripe).map(|s| s.as_slice())
+    }
+}
@@ -0,0 +1,305 @@
+use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_execution::arrays::array::{Array, ArrayData};
+use rayexec_execution::arrays::bitmap::Bitmap;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::storage::{BooleanStorage, GermanVarlenStorage, PrimitiveStorage};
+
+use crate::encoding::byte_rle::{decode_boolean_rle, decode_byte_rle};
+use crate::encoding::int_rle::{decode_int_rle, RleVersion};
+use crate::encoding::ByteReader;
+use crate::proto::column_encoding::Kind as EncodingKind;
+use crate::proto::r#type::Kind;
+use crate::schema::OrcColumn;
+
+/// Seconds between the unix epoch and the ORC timestamp epoch of
+/// 2015-01-01 00:00:00.
+const ORC_EPOCH_SECONDS: i64 = 1_420_070_400;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// Decompressed streams for a single column in a stripe.
+#[derive(Debug, Default)]
+pub struct ColumnStreams {
+    pub present: Option<Vec<u8>>,
+    pub data: Option<Vec<u8>>,
+    pub length: Option<Vec<u8>>,
+    pub dictionary_data: Option<Vec<u8>>,
+    pub secondary: Option<Vec<u8>>,
+}
+
+impl ColumnStreams {
+    fn data(&self) -> &[u8] {
+        self.data.as_deref().unwrap_or_default()
+    }
+
+    fn length(&self) -> &[u8] {
+        self.length.as_deref().unwrap_or_default()
+    }
+
+    fn secondary(&self) -> &[u8] {
+        self.secondary.as_deref().unwrap_or_default()
+    }
+}
+
+/// Decode all values for a column in a stripe.
+pub fn decode_column(
+    column: &OrcColumn,
+    encoding: EncodingKind,
+    dictionary_size: usize,
+    streams: &ColumnStreams,
+    num_rows: usize,
+) -> Result<Array> {
+    let validity = match &streams.present {
+        Some(present) => Some(decode_boolean_rle(present, num_rows)?),
+        None => None,
+    };
+    let num_values = match &validity {
+        Some(validity) => validity.iter().filter(|v| **v).count(),
+        None => num_rows,
+    };
+
+    let version = match encoding {
+        EncodingKind::Direct | EncodingKind::Dictionary => RleVersion::V1,
+        EncodingKind::DirectV2 | EncodingKind::DictionaryV2 => RleVersion::V2,
+    };
+    let ints = |buf: &[u8], signed: bool| decode_int_rle(buf, num_values, signed, version);
+
+    let data: ArrayData = match column.kind {
+        Kind::Boolean => {
+            let values = decode_boolean_rle(streams.data(), num_values)?;
+            let values = scatter(values, validity.as_deref());
+            BooleanStorage::from(Bitmap::from_iter(values)).into()
+        }
+        Kind::Byte => {
+            let values = decode_byte_rle(streams.data(), num_values)?;
+            primitive(values.into_iter().map(|v| v as i8), validity.as_deref())
+        }
+        Kind::Short => primitive(
+            ints(streams.data(), true)?.into_iter().map(|v| v as i16),
+            validity.as_deref(),
+        ),
+        Kind::Int => primitive(
+            ints(streams.data(), true)?.into_iter().map(|v| v as i32),
+            validity.as_deref(),
+        ),
+        Kind::Long => primitive(ints(streams.data(), true)?, validity.as_deref()),
+        Kind::Date => primitive(
+            ints(streams.data(), true)?.into_iter().map(|v| v as i32),
+            validity.as_deref(),
+        ),
+        Kind::Float => {
+            let values = read_fixed::<4>(streams.data(), num_values)?;
+            primitive(values.map(f32::from_le_bytes), validity.as_deref())
+        }
+        Kind::Double => {
+            let values = read_fixed::<8>(streams.data(), num_values)?;
+            primitive(values.map(f64::from_le_bytes), validity.as_deref())
+        }
+        Kind::String | Kind::Varchar | Kind::Char | Kind::Binary => {
+            let values = match encoding {
+                EncodingKind::Direct | EncodingKind::DirectV2 => {
+                    let lengths = ints(streams.length(), false)?;
+                    split_lengths(streams.data(), &lengths)?
+                }
+                EncodingKind::Dictionary | EncodingKind::DictionaryV2 => {
+                    let lengths =
+                        decode_int_rle(streams.length(), dictionary_size, false, version)?;
+                    let dictionary = split_lengths(
+                        streams.dictionary_data.as_deref().unwrap_or_default(),
+                        &lengths,
+                    )?;
+                    ints(streams.data(), false)?
+                        .into_iter()
+                        .map(|idx| {
+                            dictionary.get(idx as usize).copied().ok_or_else(|| {
+                                RayexecError::new(format!(
+                                    "Dictionary index {idx} out of range for dictionary of size {}",
+                                    dictionary.len()
+                                ))
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()?
+                }
+            };
+            varlen(values, validity.as_deref())?
+        }
+        Kind::Decimal => {
+            let mut reader = ByteReader::new(streams.data());
+            let unscaled = (0..num_values)
+                .map(|_| reader.read_svarint128())
+                .collect::<Result<Vec<_>>>()?;
+            let scales = ints(streams.secondary(), true)?;
+
+            let target_scale = match &column.datatype {
+                DataType::Decimal64(m) | DataType::Decimal128(m) => m.scale as i64,
+                other => {
+                    return Err(RayexecError::new(format!(
+                        "Unexpected type for decimal column: {other}"
+                    )))
+                }
+            };
+            let values = unscaled
+                .into_iter()
+                .zip(scales)
+                .map(|(v, scale)| rescale(v, scale, target_scale))
+                .collect::<Result<Vec<_>>>()?;
+
+            match column.datatype {
+                DataType::Decimal64(_) => {
+                    primitive(values.into_iter().map(|v| v as i64), validity.as_deref())
+                }
+                _ => primitive(values, validity.as_deref()),
+            }
+        }
+        Kind::Timestamp | Kind::TimestampInstant => {
+            let seconds = ints(streams.data(), true)?;
+            let nanos = ints(streams.secondary(), false)?;
+            let values = seconds
+                .into_iter()
+                .zip(nanos)
+                .map(|(seconds, nanos)| timestamp_nanos(seconds, nanos as u64));
+            primitive(values, validity.as_deref())
+        }
+        other => not_implemented!("Reading ORC {other:?} columns"),
+    };
+
+    Ok(match validity {
+        Some(validity) => Array::new_with_validity_and_array_data(
+            column.datatype.clone(),
+            Bitmap::from_iter(validity),
+            data,
+        ),
+        None => Array::new_with_array_data(column.datatype.clone(), data),
+    })
+}
+
+/// Spread non-null values out to their row positions, filling null rows with
+/// the default value.
+fn scatter<T: Default>(values: impl IntoIterator<Item = T>, validity: Option<&[bool]>) -> Vec<T> {
+    match validity {
+        Some(validity) => {
+            let mut values = values.into_iter();
+            validity
+                .iter()
+                .map(|valid| {
+                    if *valid {
+                        values.next().unwrap_or_default()
+                    } else {
+                        T::default()
+                    }
+                })
+                .collect()
+        }
+        None => values.into_iter().collect(),
+    }
+}
+
+fn primitive<T: Default>(
+    values: impl IntoIterator<Item = T>,
+    validity: Option<&[bool]>,
+) -> ArrayData
+where
+    PrimitiveStorage<T>: Into<ArrayData>,
+{
+    PrimitiveStorage::from(scatter(values, validity)).into()
+}
+
+fn varlen(values: Vec<&[u8]>, validity: Option<&[bool]>) -> Result<ArrayData> {
+    let values = scatter(values, validity);
+    let mut storage = GermanVarlenStorage::with_metadata_capacity(values.len());
+    for value in values {
+        storage.try_push(value)?;
+    }
+    Ok(storage.into())
+}
+
+/// Read `count` fixed size little endian values.
+fn read_fixed<const N: usize>(
+    buf: &[u8],
+    count: usize,
+) -> Result<impl Iterator<Item = [u8; N]> + '_> {
+    let buf = buf
+        .get(..count * N)
+        .ok_or_else(|| RayexecError::new("Unexpected end of ORC stream"))?;
+    Ok(buf.chunks_exact(N).map(|chunk| chunk.try_into().unwrap()))
+}
+
+/// Split concatenated values using their lengths.
+fn split_lengths<'a>(buf: &'a [u8], lengths: &[i64]) -> Result<Vec<&'a [u8]>> {
+    let mut reader = ByteReader::new(buf);
+    lengths
+        .iter()
+        .map(|&len| reader.read_bytes(len as usize))
+        .collect()
+}
+
+/// Adjust a decimal value's scale to match the column's scale.
+fn rescale(value: i128, scale: i64, target_scale: i64) -> Result<i128> {
+    let diff = target_scale - scale;
+    let factor = 10_i128
+        .checked_pow(diff.unsigned_abs() as u32)
+        .ok_or_else(|| RayexecError::new("Decimal scale out of range"))?;
+    if diff >= 0 {
+        value
+            .checked_mul(factor)
+            .ok_or_else(|| RayexecError::new("Decimal value out of range"))
+    } else {
+        Ok(value / factor)
+    }
+}
+
+/// Convert ORC seconds and encoded nanoseconds to nanoseconds since the unix
+/// epoch.
+///
+/// The low 3 bits of the encoded nanoseconds are the number of trailing
+/// decimal zeros that were removed, minus one.
+fn timestamp_nanos(seconds: i64, encoded_nanos: u64) -> i64 {
+    let zeros = encoded_nanos & 0x07;
+    let mut nanos = (encoded_nanos >> 3) as i64;
+    if zeros != 0 {
+        nanos *= 10_i64.pow(zeros as u32 + 1);
+    }
+
+    let mut seconds = seconds + ORC_EPOCH_SECONDS;
+    // Writers truncate negative seconds towards zero, while nanos are always
+    // positive.
+    if seconds < 0 && nanos > 999_999 {
+        seconds -= 1;
+    }
+
+    seconds * NANOS_PER_SECOND + nanos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scatter_nulls() {
+        let out = scatter(vec![1, 2], Some(&[false, true, false, true]));
+        assert_eq!(vec![0, 1, 0, 2], out);
+    }
+
+    #[test]
+    fn decimal_rescale() {
+        assert_eq!(12300, rescale(123, 1, 3).unwrap());
+        assert_eq!(12, rescale(1234, 4, 2).unwrap());
+        assert_eq!(-5, rescale(-5, 2, 2).unwrap());
+    }
+
+    #[test]
+    fn timestamps() {
+        // 2015-01-01 00:00:00.
+        assert_eq!(ORC_EPOCH_SECONDS * NANOS_PER_SECOND, timestamp_nanos(0, 0));
+        // 1 second and 500ms after, 5 with 8 zeros removed (encoded as 7).
+        assert_eq!(
+            (ORC_EPOCH_SECONDS + 1) * NANOS_PER_SECOND + 500_000_000,
+            timestamp_nanos(1, (5 << 3) | 7)
+        );
+        // No zeros removed.
+        assert_eq!(
+            ORC_EPOCH_SECONDS * NANOS_PER_SECOND + 123,
+            timestamp_nanos(0, 123 << 3)
+        );
+    }
+}
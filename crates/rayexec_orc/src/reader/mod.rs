@@ -0,0 +1,297 @@
+mod column;
+
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use column::{decode_column, ColumnStreams};
+use prost::Message;
+use rayexec_error::{RayexecError, Result, ResultExt};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::storage::table_storage::Projections;
+use rayexec_io::FileSource;
+use tracing::trace;
+
+use crate::metadata::OrcMetadata;
+use crate::proto::stream::Kind as StreamKind;
+use crate::proto::StripeFooter;
+
+/// Reads batches from a set of stripes.
+#[derive(Debug)]
+pub struct AsyncStripeReader<R: FileSource> {
+    reader: R,
+    /// Stripes to read, in order.
+    stripes: VecDeque<usize>,
+    metadata: Arc<OrcMetadata>,
+    /// Indices of the schema columns to read.
+    columns: Vec<usize>,
+    batch_size: usize,
+    /// Decoded stripe we're currently producing batches from, along with the
+    /// offset of the next row to produce.
+    current: Option<(Batch, usize)>,
+}
+
+impl<R: FileSource> AsyncStripeReader<R> {
+    pub fn try_new(
+        reader: R,
+        stripes: VecDeque<usize>,
+        metadata: Arc<OrcMetadata>,
+        batch_size: usize,
+        projections: Projections,
+    ) -> Result<Self> {
+        let num_columns = metadata.schema.columns.len();
+        let columns = match projections.column_indices {
+            Some(indices) => {
+                if let Some(idx) = indices.iter().find(|&&idx| idx >= num_columns) {
+                    return Err(RayexecError::new(format!(
+                        "Projected column {idx} out of range for {num_columns} columns"
+                    )));
+                }
+                indices
+            }
+            None => (0..num_columns).collect(),
+        };
+
+        Ok(AsyncStripeReader {
+            reader,
+            stripes,
+            metadata,
+            columns,
+            batch_size,
+            current: None,
+        })
+    }
+
+    pub async fn read_next(&mut self) -> Result<Option<Batch>> {
+        loop {
+            if let Some((batch, offset)) = &mut self.current {
+                if *offset < batch.num_rows() {
+                    let len = self.batch_size.min(batch.num_rows() - *offset);
+                    let out = batch.slice(*offset, len);
+                    *offset += len;
+                    return Ok(Some(out));
+                }
+            }
+
+            let stripe = match self.stripes.pop_front() {
+                Some(stripe) => stripe,
+                None => return Ok(None),
+            };
+
+            let batch = self.read_stripe(stripe).await?;
+            self.current = Some((batch, 0));
+        }
+    }
+
+    /// Read and decode the projected columns for a stripe.
+    async fn read_stripe(&mut self, stripe: usize) -> Result<Batch> {
+        let info = self.metadata.stripes()[stripe];
+        let num_rows = info.number_of_rows() as usize;
+        let compression = self.metadata.compression;
+
+        let footer_offset = (info.offset() + info.index_length() + info.data_length()) as usize;
+        let footer_buf = self
+            .reader
+            .read_range(footer_offset, info.footer_length() as usize)
+            .await?;
+        let footer = StripeFooter::decode(compression.decompress(&footer_buf)?.as_slice())
+            .context("failed to decode stripe footer")?;
+        trace!(%stripe, %num_rows, "read orc stripe footer");
+
+        if self.columns.is_empty() {
+            return Ok(Batch::empty_with_num_rows(num_rows));
+        }
+
+        // Streams are stored contiguously in the order they're listed in the
+        // footer, starting with the index streams.
+        let mut stream_offset = info.offset() as usize;
+        let mut stream_locations = Vec::with_capacity(footer.streams.len());
+        for stream in &footer.streams {
+            stream_locations.push((stream.kind(), stream.column() as usize, stream_offset));
+            stream_offset += stream.length() as usize;
+        }
+
+        let mut arrays = Vec::with_capacity(self.columns.len());
+        for &col_idx in &self.columns {
+            let column = &self.metadata.schema.columns[col_idx];
+
+            let mut streams = ColumnStreams::default();
+            for (stream, &(kind, column_id, offset)) in footer.streams.iter().zip(&stream_locations)
+            {
+                if column_id != column.column_id {
+                    continue;
+                }
+
+                let target = match kind {
+                    StreamKind::Present => &mut streams.present,
+                    StreamKind::Data => &mut streams.data,
+                    StreamKind::Length => &mut streams.length,
+                    StreamKind::DictionaryData => &mut streams.dictionary_data,
+                    StreamKind::Secondary => &mut streams.secondary,
+                    _ => continue,
+                };
+
+                let buf = self
+                    .reader
+                    .read_range(offset, stream.length() as usize)
+                    .await?;
+                *target = Some(compression.decompress(&buf)?);
+            }
+
+            let encoding = footer.columns.get(column.column_id).ok_or_else(|| {
+                RayexecError::new(format!(
+                    "Missing encoding for column {} in stripe footer",
+                    column.column_id
+                ))
+            })?;
+
+            arrays.push(decode_column(
+                column,
+                encoding.kind(),
+                encoding.dictionary_size() as usize,
+                &streams,
+                num_rows,
+            )?);
+        }
+
+        Batch::try_new(arrays)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use futures::executor::block_on;
+    use rayexec_execution::arrays::array::Array;
+    use rayexec_execution::arrays::testutil::assert_batches_eq;
+    use rayexec_io::memory::MemoryFileSystem;
+
+    use super::*;
+    use crate::testutil::{write_orc, TestColumn, TestCompression};
+
+    fn test_stripes() -> Vec<Vec<TestColumn>> {
+        vec![
+            vec![
+                TestColumn::Long(vec![Some(1), Some(2), None]),
+                TestColumn::Utf8(vec![Some("a"), None, Some("a longer string value")]),
+                TestColumn::Double(vec![Some(1.5), Some(2.5), Some(3.5)]),
+            ],
+            vec![
+                TestColumn::Long(vec![Some(4), Some(5)]),
+                TestColumn::Utf8(vec![Some("d"), Some("e")]),
+                TestColumn::Double(vec![None, Some(-1.0)]),
+            ],
+        ]
+    }
+
+    fn open(buf: Vec<u8>) -> (Box<dyn FileSource>, OrcMetadata) {
+        let fs = MemoryFileSystem::default();
+        fs.register_file(Path::new("test.orc"), buf.into()).unwrap();
+
+        let mut source = fs.file_source(Path::new("test.orc")).unwrap();
+        let size = block_on(source.size()).unwrap();
+        let metadata = block_on(OrcMetadata::new_from_source(source.as_mut(), size)).unwrap();
+
+        (source, metadata)
+    }
+
+    fn read_all(
+        compression: TestCompression,
+        stripes: VecDeque<usize>,
+        projections: Projections,
+        batch_size: usize,
+    ) -> Vec<Batch> {
+        let buf = write_orc(&["id", "name", "score"], &test_stripes(), compression);
+        let (source, metadata) = open(buf);
+
+        let mut reader = AsyncStripeReader::try_new(
+            source,
+            stripes,
+            Arc::new(metadata),
+            batch_size,
+            projections,
+        )
+        .unwrap();
+
+        let mut batches = Vec::new();
+        while let Some(batch) = block_on(reader.read_next()).unwrap() {
+            batches.push(batch);
+        }
+        batches
+    }
+
+    #[test]
+    fn read_metadata() {
+        let buf = write_orc(
+            &["id", "name", "score"],
+            &test_stripes(),
+            TestCompression::None,
+        );
+        let (_, metadata) = open(buf);
+
+        assert_eq!(2, metadata.stripes().len());
+        assert_eq!(5, metadata.footer.number_of_rows());
+        assert_eq!(3, metadata.schema.schema.fields.len());
+        assert!(metadata.stripe_statistics(1).is_some());
+    }
+
+    #[test]
+    fn read_all_columns() {
+        for compression in [TestCompression::None, TestCompression::Zlib] {
+            let batches = read_all(compression, [0, 1].into(), Projections::all(), 1024);
+            assert_eq!(2, batches.len());
+
+            let expected = Batch::try_new([
+                Array::from_iter([Some(1_i64), Some(2), None]),
+                Array::from_iter([Some("a"), None, Some("a longer string value")]),
+                Array::from_iter([1.5_f64, 2.5, 3.5]),
+            ])
+            .unwrap();
+            assert_batches_eq(&expected, &batches[0]);
+
+            let expected = Batch::try_new([
+                Array::from_iter([4_i64, 5]),
+                Array::from_iter(["d", "e"]),
+                Array::from_iter([None, Some(-1.0_f64)]),
+            ])
+            .unwrap();
+            assert_batches_eq(&expected, &batches[1]);
+        }
+    }
+
+    #[test]
+    fn read_projected_stripe() {
+        let projections = Projections {
+            column_indices: Some(vec![2, 0]),
+        };
+        let batches = read_all(TestCompression::Zlib, [1].into(), projections, 1024);
+
+        assert_eq!(1, batches.len());
+        let expected = Batch::try_new([
+            Array::from_iter([None, Some(-1.0_f64)]),
+            Array::from_iter([4_i64, 5]),
+        ])
+        .unwrap();
+        assert_batches_eq(&expected, &batches[0]);
+    }
+
+    #[test]
+    fn read_no_columns() {
+        let projections = Projections {
+            column_indices: Some(Vec::new()),
+        };
+        let batches = read_all(TestCompression::None, [0, 1].into(), projections, 1024);
+
+        let num_rows: Vec<_> = batches.iter().map(|b| b.num_rows()).collect();
+        assert_eq!(vec![3, 2], num_rows);
+    }
+
+    #[test]
+    fn read_small_batches() {
+        let batches = read_all(TestCompression::None, [0, 1].into(), Projections::all(), 2);
+
+        let num_rows: Vec<_> = batches.iter().map(|b| b.num_rows()).collect();
+        assert_eq!(vec![2, 1, 2], num_rows);
+    }
+}
@@ -0,0 +1,186 @@
+use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_execution::arrays::datatype::{DataType, DecimalTypeMeta, TimeUnit, TimestampTypeMeta};
+use rayexec_execution::arrays::field::{Field, Schema};
+use rayexec_execution::arrays::scalar::decimal::{Decimal128Type, Decimal64Type, DecimalType};
+
+use crate::proto::r#type::Kind;
+use crate::proto::Type;
+
+/// Precision and scale of decimals written by Hive 0.11, which didn't store
+/// them in the file.
+const HIVE_11_DECIMAL: (u8, i8) = (38, 10);
+
+/// A top level column in an ORC file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrcColumn {
+    /// Id of the column in the file.
+    ///
+    /// Columns are numbered by a pre-order traversal of the type tree, with
+    /// the root struct being column 0.
+    pub column_id: usize,
+    pub kind: Kind,
+    pub datatype: DataType,
+}
+
+/// Schema of an ORC file along with the file's column ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrcSchema {
+    pub schema: Schema,
+    /// Columns in the same order as the schema's fields.
+    pub columns: Vec<OrcColumn>,
+}
+
+impl OrcSchema {
+    /// Create the schema from the types in an ORC footer.
+    pub fn try_from_types(types: &[Type]) -> Result<Self> {
+        let root = types
+            .first()
+            .ok_or_else(|| RayexecError::new("ORC file has no types"))?;
+        if root.kind() != Kind::Struct {
+            return Err(RayexecError::new(format!(
+                "Expected root ORC type to be a struct, got {:?}",
+                root.kind()
+            )));
+        }
+        if root.subtypes.len() != root.field_names.len() {
+            return Err(RayexecError::new(
+                "Root ORC struct has mismatched field names and types",
+            ));
+        }
+
+        let mut fields = Vec::with_capacity(root.subtypes.len());
+        let mut columns = Vec::with_capacity(root.subtypes.len());
+
+        for (&column_id, name) in root.subtypes.iter().zip(&root.field_names) {
+            let column_id = column_id as usize;
+            let typ = types.get(column_id).ok_or_else(|| {
+                RayexecError::new(format!("Missing ORC type for column {column_id}"))
+            })?;
+
+            let datatype = convert_type(typ, name)?;
+            fields.push(Field::new(name, datatype.clone(), true));
+            columns.push(OrcColumn {
+                column_id,
+                kind: typ.kind(),
+                datatype,
+            });
+        }
+
+        Ok(OrcSchema {
+            schema: Schema::new(fields),
+            columns,
+        })
+    }
+}
+
+fn convert_type(typ: &Type, name: &str) -> Result<DataType> {
+    Ok(match typ.kind() {
+        Kind::Boolean => DataType::Boolean,
+        Kind::Byte => DataType::Int8,
+        Kind::Short => DataType::Int16,
+        Kind::Int => DataType::Int32,
+        Kind::Long => DataType::Int64,
+        Kind::Float => DataType::Float32,
+        Kind::Double => DataType::Float64,
+        Kind::String | Kind::Varchar | Kind::Char => DataType::Utf8,
+        Kind::Binary => DataType::Binary,
+        Kind::Date => DataType::Date32,
+        // Timestamps are read as UTC, the writer's timezone isn't applied.
+        Kind::Timestamp | Kind::TimestampInstant => {
+            DataType::Timestamp(TimestampTypeMeta::new(TimeUnit::Nanosecond))
+        }
+        Kind::Decimal => {
+            let (precision, scale) = match (typ.precision, typ.scale) {
+                (Some(precision), scale) if precision > 0 => {
+                    (precision as u8, scale.unwrap_or(0) as i8)
+                }
+                _ => HIVE_11_DECIMAL,
+            };
+
+            if precision <= Decimal64Type::MAX_PRECISION {
+                DataType::Decimal64(DecimalTypeMeta::new(precision, scale))
+            } else if precision <= Decimal128Type::MAX_PRECISION {
+                DataType::Decimal128(DecimalTypeMeta::new(precision, scale))
+            } else {
+                return Err(RayexecError::new(format!(
+                    "Decimal precision of {precision} is to high for column '{name}'"
+                )));
+            }
+        }
+        Kind::List | Kind::Map | Kind::Struct | Kind::Union => {
+            not_implemented!("ORC {:?} column '{name}'", typ.kind())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn primitive(kind: Kind) -> Type {
+        Type {
+            kind: Some(kind as i32),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flat_schema() {
+        let types = vec![
+            Type {
+                kind: Some(Kind::Struct as i32),
+                subtypes: vec![1, 2, 3],
+                field_names: vec!["id".to_string(), "name".to_string(), "price".to_string()],
+                ..Default::default()
+            },
+            primitive(Kind::Long),
+            primitive(Kind::Varchar),
+            Type {
+                kind: Some(Kind::Decimal as i32),
+                precision: Some(10),
+                scale: Some(2),
+                ..Default::default()
+            },
+        ];
+
+        let schema = OrcSchema::try_from_types(&types).unwrap();
+        let expected = Schema::new([
+            Field::new("id", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new(
+                "price",
+                DataType::Decimal64(DecimalTypeMeta::new(10, 2)),
+                true,
+            ),
+        ]);
+        assert_eq!(expected, schema.schema);
+        assert_eq!(
+            vec![1, 2, 3],
+            schema
+                .columns
+                .iter()
+                .map(|c| c.column_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn nested_not_supported() {
+        let types = vec![
+            Type {
+                kind: Some(Kind::Struct as i32),
+                subtypes: vec![1],
+                field_names: vec!["tags".to_string()],
+                ..Default::default()
+            },
+            Type {
+                kind: Some(Kind::List as i32),
+                subtypes: vec![2],
+                ..Default::default()
+            },
+            primitive(Kind::String),
+        ];
+
+        OrcSchema::try_from_types(&types).unwrap_err();
+    }
+}
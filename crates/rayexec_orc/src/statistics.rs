@@ -0,0 +1,291 @@
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use rayexec_execution::expr::comparison_expr::ComparisonOperator;
+use rayexec_execution::logical::scan_filter::{ScanFilter, ScanFilterType};
+use rayexec_execution::logical::statistics::StatisticsValue;
+use rayexec_execution::storage::table_storage::{TableColumnStatistics, TableStatistics};
+
+use crate::metadata::OrcMetadata;
+use crate::proto::ColumnStatistics;
+
+/// Compute table statistics from ORC metadata.
+///
+/// Row counts and sizes are exact. Column min/max values come from the file
+/// level statistics.
+pub fn table_statistics(metadata: &OrcMetadata) -> TableStatistics {
+    let stripes = metadata.stripes();
+
+    let num_rows = metadata.footer.number_of_rows() as usize;
+    let num_bytes = stripes
+        .iter()
+        .map(|s| s.index_length() + s.data_length() + s.footer_length())
+        .sum::<u64>() as usize;
+
+    let columns = metadata
+        .schema
+        .columns
+        .iter()
+        .map(|column| {
+            let stats = metadata.footer.statistics.get(column.column_id);
+            match stats.and_then(|stats| scalar_min_max(stats, &column.datatype)) {
+                Some((min, max)) => TableColumnStatistics {
+                    min: StatisticsValue::Exact(min),
+                    max: StatisticsValue::Exact(max),
+                },
+                None => TableColumnStatistics::default(),
+            }
+        })
+        .collect();
+
+    TableStatistics {
+        num_rows: StatisticsValue::Exact(num_rows),
+        num_bytes: StatisticsValue::Exact(num_bytes),
+        columns,
+    }
+}
+
+/// Get the min and max values from column statistics.
+///
+/// Only types where the ORC sort order matches ours are handled.
+fn scalar_min_max(
+    stats: &ColumnStatistics,
+    datatype: &DataType,
+) -> Option<(OwnedScalarValue, OwnedScalarValue)> {
+    match datatype {
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            let int_stats = stats.int_statistics.as_ref()?;
+            let (min, max) = (int_stats.minimum?, int_stats.maximum?);
+            let cast = |v: i64| match datatype {
+                DataType::Int8 => ScalarValue::Int8(v as i8),
+                DataType::Int16 => ScalarValue::Int16(v as i16),
+                DataType::Int32 => ScalarValue::Int32(v as i32),
+                _ => ScalarValue::Int64(v),
+            };
+            Some((cast(min), cast(max)))
+        }
+        DataType::Float32 | DataType::Float64 => {
+            let double_stats = stats.double_statistics.as_ref()?;
+            let (min, max) = (double_stats.minimum?, double_stats.maximum?);
+            // NaN can't be compared.
+            min.partial_cmp(&max)?;
+            Some(match datatype {
+                DataType::Float32 => (
+                    ScalarValue::Float32(min as f32),
+                    ScalarValue::Float32(max as f32),
+                ),
+                _ => (ScalarValue::Float64(min), ScalarValue::Float64(max)),
+            })
+        }
+        DataType::Utf8 => {
+            let string_stats = stats.string_statistics.as_ref()?;
+            let (min, max) = (string_stats.minimum.clone()?, string_stats.maximum.clone()?);
+            Some((min.into(), max.into()))
+        }
+        DataType::Date32 => {
+            let date_stats = stats.date_statistics.as_ref()?;
+            Some((
+                ScalarValue::Date32(date_stats.minimum?),
+                ScalarValue::Date32(date_stats.maximum?),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Check if any row in a stripe may match all filters using the stripe's
+/// statistics.
+///
+/// Filters on columns without usable statistics are assumed to match.
+pub fn stripe_may_match(metadata: &OrcMetadata, stripe: usize, filters: &[ScanFilter]) -> bool {
+    let stats = match metadata.stripe_statistics(stripe) {
+        Some(stats) => stats,
+        None => return true,
+    };
+    let num_rows = metadata.stripes()[stripe].number_of_rows();
+
+    filters.iter().all(|filter| {
+        let ScanFilterType::ConstComparison { op, constant } = &filter.filter;
+        let column_stats = metadata
+            .schema
+            .columns
+            .get(filter.column)
+            .and_then(|column| Some((column, stats.get(column.column_id)?)));
+        let (column, column_stats) = match column_stats {
+            Some(v) => v,
+            None => return true,
+        };
+
+        // Comparisons with NULL never match.
+        if num_rows > 0 && column_stats.number_of_values == Some(0) {
+            return false;
+        }
+
+        column_may_match(column_stats, &column.datatype, *op, constant)
+    })
+}
+
+fn column_may_match(
+    stats: &ColumnStatistics,
+    datatype: &DataType,
+    op: ComparisonOperator,
+    constant: &OwnedScalarValue,
+) -> bool {
+    match datatype {
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            match (stats.int_statistics.as_ref(), constant.try_as_i64()) {
+                (Some(s), Ok(v)) => match (s.minimum, s.maximum) {
+                    (Some(min), Some(max)) => range_may_match(&min, &max, op, &v),
+                    _ => true,
+                },
+                _ => true,
+            }
+        }
+        DataType::Float32 | DataType::Float64 => {
+            let v = match constant {
+                ScalarValue::Float32(v) => *v as f64,
+                ScalarValue::Float64(v) => *v,
+                _ => return true,
+            };
+            match stats.double_statistics.as_ref() {
+                Some(s) => match (s.minimum, s.maximum) {
+                    (Some(min), Some(max)) if !min.is_nan() && !max.is_nan() && !v.is_nan() => {
+                        range_may_match(&min, &max, op, &v)
+                    }
+                    _ => true,
+                },
+                None => true,
+            }
+        }
+        DataType::Utf8 => {
+            let (s, v) = match (stats.string_statistics.as_ref(), constant.try_as_str()) {
+                (Some(s), Ok(v)) => (s, v),
+                _ => return true,
+            };
+            // Bounds are written instead of the min and max when the values
+            // are too long.
+            let min = s.minimum.as_deref().or(s.lower_bound.as_deref());
+            let max = s.maximum.as_deref().or(s.upper_bound.as_deref());
+            match (min, max) {
+                (Some(min), Some(max)) => range_may_match(&min, &max, op, &v),
+                _ => true,
+            }
+        }
+        DataType::Date32 => match (stats.date_statistics.as_ref(), constant) {
+            (Some(s), ScalarValue::Date32(v)) => match (s.minimum, s.maximum) {
+                (Some(min), Some(max)) => range_may_match(&min, &max, op, v),
+                _ => true,
+            },
+            _ => true,
+        },
+        _ => true,
+    }
+}
+
+/// Check if a value in the range `[min, max]` may satisfy a comparison with
+/// `value`.
+fn range_may_match<T: PartialOrd + ?Sized>(
+    min: &T,
+    max: &T,
+    op: ComparisonOperator,
+    value: &T,
+) -> bool {
+    match op {
+        ComparisonOperator::Eq => min <= value && value <= max,
+        ComparisonOperator::NotEq => !(min == value && max == value),
+        ComparisonOperator::Lt => min < value,
+        ComparisonOperator::LtEq => min <= value,
+        ComparisonOperator::Gt => max > value,
+        ComparisonOperator::GtEq => max >= value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{IntegerStatistics, StringStatistics};
+
+    fn int_stats(min: i64, max: i64) -> ColumnStatistics {
+        ColumnStatistics {
+            number_of_values: Some(10),
+            int_statistics: Some(IntegerStatistics {
+                minimum: Some(min),
+                maximum: Some(max),
+                sum: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn int_ranges() {
+        let stats = int_stats(10, 20);
+        let may_match =
+            |op, v: i32| column_may_match(&stats, &DataType::Int32, op, &ScalarValue::Int32(v));
+
+        assert!(may_match(ComparisonOperator::Eq, 15));
+        assert!(!may_match(ComparisonOperator::Eq, 21));
+        assert!(!may_match(ComparisonOperator::Lt, 10));
+        assert!(may_match(ComparisonOperator::LtEq, 10));
+        assert!(!may_match(ComparisonOperator::Gt, 20));
+        assert!(may_match(ComparisonOperator::GtEq, 20));
+        assert!(may_match(ComparisonOperator::NotEq, 10));
+        assert!(!column_may_match(
+            &int_stats(5, 5),
+            &DataType::Int32,
+            ComparisonOperator::NotEq,
+            &ScalarValue::Int32(5)
+        ));
+    }
+
+    #[test]
+    fn string_bounds() {
+        let stats = ColumnStatistics {
+            number_of_values: Some(10),
+            string_statistics: Some(StringStatistics {
+                lower_bound: Some("b".to_string()),
+                upper_bound: Some("d".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let may_match = |v: &str| {
+            column_may_match(
+                &stats,
+                &DataType::Utf8,
+                ComparisonOperator::Eq,
+                &v.to_string().into(),
+            )
+        };
+        assert!(may_match("c"));
+        assert!(!may_match("a"));
+        assert!(!may_match("e"));
+    }
+
+    #[test]
+    fn missing_statistics_match() {
+        let stats = ColumnStatistics::default();
+        assert!(column_may_match(
+            &stats,
+            &DataType::Int64,
+            ComparisonOperator::Eq,
+            &ScalarValue::Int64(1)
+        ));
+
+        // Types that aren't handled always match.
+        assert!(column_may_match(
+            &int_stats(0, 1),
+            &DataType::Boolean,
+            ComparisonOperator::Eq,
+            &ScalarValue::Boolean(true)
+        ));
+    }
+
+    #[test]
+    fn min_max_from_statistics() {
+        let out = scalar_min_max(&int_stats(-2, 10), &DataType::Int16).unwrap();
+        assert_eq!((ScalarValue::Int16(-2), ScalarValue::Int16(10)), out);
+
+        assert_eq!(None, scalar_min_max(&int_stats(-2, 10), &DataType::Utf8));
+    }
+}
@@ -0,0 +1,315 @@
+//! Minimal ORC writer for producing test files.
+//!
+//! Columns are written with the version 1 direct encodings, with each stream
+//! stored as a single compression chunk.
+use std::io::Write;
+
+use flate2::write::DeflateEncoder;
+use prost::Message;
+
+use crate::proto::column_encoding::Kind as EncodingKind;
+use crate::proto::r#type::Kind;
+use crate::proto::stream::Kind as StreamKind;
+use crate::proto::{
+    ColumnEncoding,
+    ColumnStatistics,
+    CompressionKind,
+    DoubleStatistics,
+    Footer,
+    IntegerStatistics,
+    Metadata,
+    PostScript,
+    Stream,
+    StringStatistics,
+    StripeFooter,
+    StripeInformation,
+    StripeStatistics,
+    Type,
+};
+
+/// Compression kinds supported by the test writer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestCompression {
+    None,
+    Zlib,
+}
+
+impl TestCompression {
+    fn kind(self) -> CompressionKind {
+        match self {
+            Self::None => CompressionKind::None,
+            Self::Zlib => CompressionKind::Zlib,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum TestColumn {
+    Long(Vec<Option<i64>>),
+    Double(Vec<Option<f64>>),
+    Utf8(Vec<Option<&'static str>>),
+}
+
+impl TestColumn {
+    fn kind(&self) -> Kind {
+        match self {
+            Self::Long(_) => Kind::Long,
+            Self::Double(_) => Kind::Double,
+            Self::Utf8(_) => Kind::String,
+        }
+    }
+
+    fn validity(&self) -> Vec<bool> {
+        match self {
+            Self::Long(v) => v.iter().map(|v| v.is_some()).collect(),
+            Self::Double(v) => v.iter().map(|v| v.is_some()).collect(),
+            Self::Utf8(v) => v.iter().map(|v| v.is_some()).collect(),
+        }
+    }
+
+    /// Encode the data streams for the column.
+    fn streams(&self) -> Vec<(StreamKind, Vec<u8>)> {
+        match self {
+            Self::Long(values) => {
+                let values: Vec<_> = values.iter().flatten().copied().collect();
+                vec![(StreamKind::Data, encode_int_literals(&values, true))]
+            }
+            Self::Double(values) => {
+                let data = values
+                    .iter()
+                    .flatten()
+                    .flat_map(|v| v.to_le_bytes())
+                    .collect();
+                vec![(StreamKind::Data, data)]
+            }
+            Self::Utf8(values) => {
+                let values: Vec<_> = values.iter().flatten().collect();
+                let data = values.iter().flat_map(|v| v.bytes()).collect();
+                let lengths: Vec<_> = values.iter().map(|v| v.len() as i64).collect();
+                vec![
+                    (StreamKind::Data, data),
+                    (StreamKind::Length, encode_int_literals(&lengths, false)),
+                ]
+            }
+        }
+    }
+
+    fn statistics(&self) -> ColumnStatistics {
+        let validity = self.validity();
+        let mut stats = ColumnStatistics {
+            number_of_values: Some(validity.iter().filter(|v| **v).count() as u64),
+            has_null: Some(validity.iter().any(|v| !v)),
+            ..Default::default()
+        };
+
+        match self {
+            Self::Long(values) => {
+                let values = values.iter().flatten();
+                stats.int_statistics = Some(IntegerStatistics {
+                    minimum: values.clone().min().copied(),
+                    maximum: values.max().copied(),
+                    sum: None,
+                });
+            }
+            Self::Double(values) => {
+                let values = values.iter().flatten();
+                stats.double_statistics = Some(DoubleStatistics {
+                    minimum: values.clone().copied().reduce(f64::min),
+                    maximum: values.copied().reduce(f64::max),
+                    sum: None,
+                });
+            }
+            Self::Utf8(values) => {
+                let values = values.iter().flatten();
+                stats.string_statistics = Some(StringStatistics {
+                    minimum: values.clone().min().map(|s| s.to_string()),
+                    maximum: values.max().map(|s| s.to_string()),
+                    ..Default::default()
+                });
+            }
+        }
+
+        stats
+    }
+}
+
+/// Write an ORC file containing the given stripes.
+///
+/// Every stripe must have the same columns.
+pub fn write_orc(
+    names: &[&str],
+    stripes: &[Vec<TestColumn>],
+    compression: TestCompression,
+) -> Vec<u8> {
+    let compress = |buf: &[u8]| compress_stream(buf, compression);
+
+    let mut file = b"ORC".to_vec();
+    let mut stripe_infos = Vec::new();
+    let mut stripe_stats = Vec::new();
+    let mut num_rows = 0;
+
+    for columns in stripes {
+        let stripe_rows = columns[0].validity().len();
+        let offset = file.len();
+
+        let mut streams = Vec::new();
+        for (idx, column) in columns.iter().enumerate() {
+            let column_id = idx as u32 + 1;
+            let validity = column.validity();
+
+            let mut column_streams = Vec::new();
+            if validity.iter().any(|v| !v) {
+                column_streams.push((StreamKind::Present, encode_booleans(&validity)));
+            }
+            column_streams.extend(column.streams());
+
+            for (kind, data) in column_streams {
+                let data = compress(&data);
+                streams.push(Stream {
+                    kind: Some(kind as i32),
+                    column: Some(column_id),
+                    length: Some(data.len() as u64),
+                });
+                file.extend_from_slice(&data);
+            }
+        }
+        let data_length = file.len() - offset;
+
+        let footer = StripeFooter {
+            streams,
+            columns: vec![
+                ColumnEncoding {
+                    kind: Some(EncodingKind::Direct as i32),
+                    ..Default::default()
+                };
+                columns.len() + 1
+            ],
+            writer_timezone: None,
+        };
+        let footer = compress(&footer.encode_to_vec());
+        file.extend_from_slice(&footer);
+
+        stripe_infos.push(StripeInformation {
+            offset: Some(offset as u64),
+            index_length: Some(0),
+            data_length: Some(data_length as u64),
+            footer_length: Some(footer.len() as u64),
+            number_of_rows: Some(stripe_rows as u64),
+        });
+
+        let mut col_stats = vec![ColumnStatistics {
+            number_of_values: Some(stripe_rows as u64),
+            ..Default::default()
+        }];
+        col_stats.extend(columns.iter().map(|c| c.statistics()));
+        stripe_stats.push(StripeStatistics { col_stats });
+
+        num_rows += stripe_rows;
+    }
+
+    let mut types = vec![Type {
+        kind: Some(Kind::Struct as i32),
+        subtypes: (1..=names.len() as u32).collect(),
+        field_names: names.iter().map(|n| n.to_string()).collect(),
+        ..Default::default()
+    }];
+    types.extend(stripes[0].iter().map(|c| Type {
+        kind: Some(c.kind() as i32),
+        ..Default::default()
+    }));
+
+    let metadata = compress(
+        &Metadata {
+            stripe_stats: stripe_stats.clone(),
+        }
+        .encode_to_vec(),
+    );
+    file.extend_from_slice(&metadata);
+
+    let footer = Footer {
+        header_length: Some(3),
+        content_length: Some(file.len() as u64 - 3 - metadata.len() as u64),
+        stripes: stripe_infos,
+        types,
+        number_of_rows: Some(num_rows as u64),
+        row_index_stride: Some(0),
+        ..Default::default()
+    };
+    let footer = compress(&footer.encode_to_vec());
+    file.extend_from_slice(&footer);
+
+    let postscript = PostScript {
+        footer_length: Some(footer.len() as u64),
+        compression: Some(compression.kind() as i32),
+        compression_block_size: Some(256 * 1024),
+        version: vec![0, 12],
+        metadata_length: Some(metadata.len() as u64),
+        magic: Some("ORC".to_string()),
+        ..Default::default()
+    }
+    .encode_to_vec();
+    file.extend_from_slice(&postscript);
+    file.push(postscript.len() as u8);
+
+    file
+}
+
+fn compress_stream(buf: &[u8], compression: TestCompression) -> Vec<u8> {
+    let chunk = match compression {
+        TestCompression::None => return buf.to_vec(),
+        TestCompression::Zlib => {
+            let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(buf).unwrap();
+            encoder.finish().unwrap()
+        }
+    };
+
+    let header = chunk.len() << 1;
+    let mut out = vec![header as u8, (header >> 8) as u8, (header >> 16) as u8];
+    out.extend_from_slice(&chunk);
+    out
+}
+
+/// Encode integers as version 1 literal runs.
+fn encode_int_literals(values: &[i64], signed: bool) -> Vec<u8> {
+    let mut out = Vec::new();
+    for chunk in values.chunks(128) {
+        out.push((-(chunk.len() as i32)) as u8);
+        for &v in chunk {
+            let mut v = if signed {
+                ((v << 1) ^ (v >> 63)) as u64
+            } else {
+                v as u64
+            };
+            loop {
+                if v < 0x80 {
+                    out.push(v as u8);
+                    break;
+                }
+                out.push((v as u8 & 0x7f) | 0x80);
+                v >>= 7;
+            }
+        }
+    }
+    out
+}
+
+/// Encode booleans as byte literal runs.
+fn encode_booleans(values: &[bool]) -> Vec<u8> {
+    let bytes: Vec<u8> = values
+        .chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (idx, v)| acc | ((*v as u8) << (7 - idx)))
+        })
+        .collect();
+
+    let mut out = Vec::new();
+    for chunk in bytes.chunks(128) {
+        out.push((-(chunk.len() as i32)) as u8);
+        out.extend_from_slice(chunk);
+    }
+    out
+}
@@ -7,12 +7,23 @@ use rayexec_error::Result;
 use rayexec_execution::arrays::batch::Batch;
 use rayexec_execution::arrays::field::Schema;
 use rayexec_execution::runtime::Runtime;
-use rayexec_execution::storage::table_storage::{DataTable, DataTableScan, Projections};
+use rayexec_execution::storage::table_storage::{
+    DataTable,
+    DataTableScan,
+    DataVersion,
+    LimitedScan,
+    Projections,
+    SampleMethod,
+    SampledScan,
+    TableSample,
+    TableStatistics,
+};
 use rayexec_io::location::{AccessConfig, FileLocation};
 use rayexec_io::{FileProvider, FileSource};
 
 use crate::metadata::Metadata;
 use crate::reader::AsyncBatchReader;
+use crate::statistics::table_statistics;
 
 /// Data table implementation which parallelizes on row groups. During scanning,
 /// each returned scan object is responsible for distinct row groups to read.
@@ -20,24 +31,29 @@ use crate::reader::AsyncBatchReader;
 pub struct RowGroupPartitionedDataTable<R: Runtime> {
     pub metadata: Arc<Metadata>,
     pub schema: Schema,
+    /// Version of the file when it was planned.
+    pub version: Option<String>,
     pub location: FileLocation,
     pub conf: AccessConfig,
     pub runtime: R,
 }
 
-impl<R: Runtime> DataTable for RowGroupPartitionedDataTable<R> {
-    fn scan(
+impl<R: Runtime> RowGroupPartitionedDataTable<R> {
+    /// Create scans for reading the provided row groups.
+    fn scan_row_groups(
         &self,
+        row_groups: impl IntoIterator<Item = usize>,
         projections: Projections,
         num_partitions: usize,
+        batch_size: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>> {
         let file_provider = self.runtime.file_provider();
 
         let mut partitioned_row_groups = vec![VecDeque::new(); num_partitions];
 
         // Split row groups into individual partitions.
-        for row_group in 0..self.metadata.decoded_metadata.row_groups().len() {
-            let partition = row_group % num_partitions;
+        for (idx, row_group) in row_groups.into_iter().enumerate() {
+            let partition = idx % num_partitions;
             partitioned_row_groups[partition].push_back(row_group);
         }
 
@@ -45,13 +61,12 @@ impl<R: Runtime> DataTable for RowGroupPartitionedDataTable<R> {
             .into_iter()
             .map(|row_groups| {
                 let reader = file_provider.file_source(self.location.clone(), &self.conf)?;
-                const BATCH_SIZE: usize = 4096; // TODO
                 AsyncBatchReader::try_new(
                     reader,
                     row_groups,
                     self.metadata.clone(),
                     &self.schema,
-                    BATCH_SIZE,
+                    batch_size,
                     projections.clone(),
                 )
             })
@@ -66,6 +81,75 @@ impl<R: Runtime> DataTable for RowGroupPartitionedDataTable<R> {
     }
 }
 
+impl<R: Runtime> DataTable for RowGroupPartitionedDataTable<R> {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let num_row_groups = self.metadata.decoded_metadata.row_groups().len();
+        self.scan_row_groups(0..num_row_groups, projections, num_partitions, batch_size)
+    }
+
+    fn scan_sample(
+        &self,
+        projections: Projections,
+        sample: &TableSample,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        match sample.method {
+            SampleMethod::System => {
+                // Row groups are our blocks, skip reading the row groups that
+                // aren't part of the sample.
+                let num_row_groups = self.metadata.decoded_metadata.row_groups().len();
+                let row_groups = sample.sample_blocks(&mut sample.rng(0), num_row_groups);
+                self.scan_row_groups(row_groups, projections, num_partitions, batch_size)
+            }
+            SampleMethod::Bernoulli => {
+                let scans = self.scan(projections, num_partitions, batch_size)?;
+                Ok(SampledScan::wrap_scans(scans, sample))
+            }
+        }
+    }
+
+    fn scan_limit(
+        &self,
+        projections: Projections,
+        limit: usize,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        // Only read enough row groups to cover the limit.
+        let mut num_rows = 0;
+        let num_row_groups = self
+            .metadata
+            .decoded_metadata
+            .row_groups()
+            .iter()
+            .take_while(|row_group| {
+                let needs_more = num_rows < limit;
+                num_rows += row_group.num_rows() as usize;
+                needs_more
+            })
+            .count();
+
+        let scans =
+            self.scan_row_groups(0..num_row_groups, projections, num_partitions, batch_size)?;
+        Ok(LimitedScan::wrap_scans(scans, limit))
+    }
+
+    fn data_version(&self) -> DataVersion {
+        DataVersion::from_opaque(self.version.as_deref())
+    }
+
+    fn statistics(&self) -> BoxFuture<'_, Result<TableStatistics>> {
+        let statistics = table_statistics(&self.metadata, &self.schema);
+        Box::pin(async move { Ok(statistics) })
+    }
+}
+
 struct RowGroupsScan {
     reader: AsyncBatchReader<Box<dyn FileSource>>,
 }
@@ -17,12 +17,12 @@ use rayexec_execution::functions::table::{
     TableFunctionPlanner,
 };
 use rayexec_execution::functions::{FunctionInfo, Signature};
-use rayexec_execution::logical::statistics::StatisticsValue;
 use rayexec_execution::runtime::Runtime;
+use rayexec_execution::storage::table_storage::DataTable;
 use rayexec_io::FileProvider;
 
 use super::datatable::RowGroupPartitionedDataTable;
-use crate::metadata::Metadata;
+use crate::metadata_cache::load_metadata;
 use crate::schema::from_parquet_schema;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -69,44 +69,38 @@ impl<R: Runtime> ScanPlanner for ReadParquet<R> {
 impl<R: Runtime> ReadParquet<R> {
     async fn plan_inner(
         self,
-        _context: &DatabaseContext,
+        context: &DatabaseContext,
         positional_inputs: Vec<OwnedScalarValue>,
         named_inputs: HashMap<String, OwnedScalarValue>,
     ) -> Result<PlannedTableFunction> {
         let (location, conf) =
             try_location_and_access_config_from_args(&self, &positional_inputs, &named_inputs)?;
+        let runtime = context.scan_runtime(&self.runtime);
 
-        let mut source = self
-            .runtime
+        let mut source = runtime
             .file_provider()
             .file_source(location.clone(), &conf)?;
 
-        let size = source.size().await?;
-
-        let metadata = Metadata::new_from_source(source.as_mut(), size).await?;
+        let metadata = load_metadata(source.as_mut(), &location, &conf).await?;
         let schema = from_parquet_schema(metadata.decoded_metadata.file_metadata().schema_descr())?;
 
-        let num_rows = metadata
-            .decoded_metadata
-            .row_groups()
-            .iter()
-            .map(|g| g.num_rows())
-            .sum::<i64>() as usize;
-
         let datatable = RowGroupPartitionedDataTable {
-            metadata: Arc::new(metadata),
+            metadata,
             schema: schema.clone(),
+            version: source.version(),
             location,
             conf,
-            runtime: self.runtime.clone(),
+            runtime,
         };
 
+        let statistics = datatable.statistics().await?;
+
         Ok(PlannedTableFunction {
             function: Box::new(self),
             positional_inputs: positional_inputs.into_iter().map(expr::lit).collect(),
             named_inputs,
             function_impl: TableFunctionImpl::Scan(Arc::new(datatable)),
-            cardinality: StatisticsValue::Exact(num_rows),
+            cardinality: statistics.num_rows,
             schema,
         })
     }
@@ -1,10 +1,12 @@
 pub mod copy_to;
 pub mod functions;
 pub mod metadata;
+pub mod metadata_cache;
 pub mod reader;
 pub mod writer;
 
 mod schema;
+mod statistics;
 
 use copy_to::ParquetCopyToFunction;
 use functions::read_parquet::ReadParquet;
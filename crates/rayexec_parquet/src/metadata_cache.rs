@@ -0,0 +1,366 @@
+//! Process-wide cache for parsed parquet metadata.
+//!
+//! Scanning a table made up of many parquet files requires reading the footer
+//! of every file before any data can be read. Parsed metadata is cached across
+//! queries so that repeated scans of the same files skip decoding, and only
+//! need a single request per file to validate the cached entry.
+//!
+//! Entries are keyed by the file's location, the access config used to read it,
+//! and the file's size and version (etag or last modified time). Files from
+//! sources that can't provide a version are never cached, since there'd be no
+//! way to tell if the file was replaced.
+//!
+//! The least recently used entries are evicted once the serialized size of all
+//! cached metadata grows past the max size. Setting the max size to zero
+//! disables caching.
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, LazyLock};
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
+use rayexec_error::Result;
+use rayexec_io::location::{AccessConfig, FileLocation};
+use rayexec_io::{FileProvider, FileSource};
+
+use crate::metadata::Metadata;
+
+/// Default max size of the cache in bytes.
+pub const DEFAULT_METADATA_CACHE_BYTES: usize = 128 * 1024 * 1024;
+
+/// Number of footers to read at once when prefetching metadata for many
+/// files.
+pub const PREFETCH_CONCURRENCY: usize = 32;
+
+static METADATA_CACHE: LazyLock<MetadataCache> =
+    LazyLock::new(|| MetadataCache::new(DEFAULT_METADATA_CACHE_BYTES));
+
+/// Statistics for the metadata cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MetadataCacheStats {
+    /// Number of cached files.
+    pub entries: usize,
+    /// Serialized size of all cached metadata.
+    pub bytes: usize,
+    /// Number of loads served from the cache.
+    pub hits: usize,
+    /// Number of loads that needed to read the footer from the file.
+    pub misses: usize,
+}
+
+/// Set the max size of the cache in bytes, evicting entries if needed.
+pub fn set_max_bytes(max_bytes: usize) {
+    METADATA_CACHE.set_max_bytes(max_bytes)
+}
+
+/// Get the max size of the cache in bytes.
+pub fn max_bytes() -> usize {
+    METADATA_CACHE.state.lock().max_bytes
+}
+
+/// Get the current stats for the cache.
+pub fn stats() -> MetadataCacheStats {
+    METADATA_CACHE.stats()
+}
+
+/// Load metadata for a file, using the cached metadata if the file hasn't
+/// changed.
+pub async fn load_metadata(
+    source: &mut dyn FileSource,
+    location: &FileLocation,
+    conf: &AccessConfig,
+) -> Result<Arc<Metadata>> {
+    load_metadata_with_cache(&METADATA_CACHE, source, location, conf).await
+}
+
+/// Load metadata for many files, reading up to `PREFETCH_CONCURRENCY` footers
+/// at once.
+///
+/// Metadata is returned in the same order as the provided locations.
+pub async fn prefetch_metadata(
+    provider: &dyn FileProvider,
+    locations: impl IntoIterator<Item = FileLocation>,
+    conf: &AccessConfig,
+) -> Result<Vec<Arc<Metadata>>> {
+    stream::iter(locations)
+        .map(|location| async move {
+            let mut source = provider.file_source(location.clone(), conf)?;
+            load_metadata(source.as_mut(), &location, conf).await
+        })
+        .buffered(PREFETCH_CONCURRENCY)
+        .try_collect()
+        .await
+}
+
+async fn load_metadata_with_cache(
+    cache: &MetadataCache,
+    source: &mut dyn FileSource,
+    location: &FileLocation,
+    conf: &AccessConfig,
+) -> Result<Arc<Metadata>> {
+    let size = source.size().await?;
+
+    let key = source.version().map(|version| MetadataKey {
+        location: location.clone(),
+        conf: conf.clone(),
+        size,
+        version,
+    });
+
+    if let Some(key) = &key {
+        if let Some(metadata) = cache.get(key) {
+            return Ok(metadata);
+        }
+    }
+
+    let metadata = Arc::new(Metadata::new_from_source(source, size).await?);
+    if let Some(key) = key {
+        cache.insert(key, metadata.clone());
+    }
+
+    Ok(metadata)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MetadataKey {
+    location: FileLocation,
+    conf: AccessConfig,
+    size: usize,
+    version: String,
+}
+
+#[derive(Debug)]
+struct CachedMetadata {
+    metadata: Arc<Metadata>,
+    /// Tick when this entry was last used.
+    last_used: u64,
+}
+
+#[derive(Debug)]
+struct CacheState {
+    entries: HashMap<MetadataKey, CachedMetadata>,
+    /// Keys ordered by when they were last used.
+    lru: BTreeMap<u64, MetadataKey>,
+    /// Incremented on every access.
+    tick: u64,
+    bytes: usize,
+    max_bytes: usize,
+    hits: usize,
+    misses: usize,
+}
+
+impl CacheState {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Evict the least recently used entries until we're under the max size.
+    fn evict(&mut self) {
+        while self.bytes > self.max_bytes {
+            let key = match self.lru.pop_first() {
+                Some((_, key)) => key,
+                None => break,
+            };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.bytes -= entry.metadata.metadata_buffer.len();
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct MetadataCache {
+    state: Mutex<CacheState>,
+}
+
+impl MetadataCache {
+    fn new(max_bytes: usize) -> Self {
+        MetadataCache {
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                lru: BTreeMap::new(),
+                tick: 0,
+                bytes: 0,
+                max_bytes,
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    fn set_max_bytes(&self, max_bytes: usize) {
+        let mut state = self.state.lock();
+        state.max_bytes = max_bytes;
+        state.evict();
+    }
+
+    fn stats(&self) -> MetadataCacheStats {
+        let state = self.state.lock();
+        MetadataCacheStats {
+            entries: state.entries.len(),
+            bytes: state.bytes,
+            hits: state.hits,
+            misses: state.misses,
+        }
+    }
+
+    fn get(&self, key: &MetadataKey) -> Option<Arc<Metadata>> {
+        let mut state = self.state.lock();
+        let tick = state.next_tick();
+
+        let (prev_tick, metadata) = match state.entries.get_mut(key) {
+            Some(entry) => {
+                let prev_tick = entry.last_used;
+                entry.last_used = tick;
+                (prev_tick, entry.metadata.clone())
+            }
+            None => {
+                state.misses += 1;
+                return None;
+            }
+        };
+
+        state.hits += 1;
+        state.lru.remove(&prev_tick);
+        state.lru.insert(tick, key.clone());
+
+        Some(metadata)
+    }
+
+    fn insert(&self, key: MetadataKey, metadata: Arc<Metadata>) {
+        let mut state = self.state.lock();
+        let size = metadata.metadata_buffer.len();
+        if size > state.max_bytes {
+            return;
+        }
+
+        let tick = state.next_tick();
+        state.bytes += size;
+        state.lru.insert(tick, key.clone());
+
+        let prev = state.entries.insert(
+            key,
+            CachedMetadata {
+                metadata,
+                last_used: tick,
+            },
+        );
+
+        // Concurrent loads of the same file may both end up inserting.
+        if let Some(prev) = prev {
+            state.bytes -= prev.metadata.metadata_buffer.len();
+            state.lru.remove(&prev.last_used);
+        }
+
+        state.evict();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use bytes::Bytes;
+    use futures::executor::block_on;
+    use futures::future::BoxFuture;
+    use futures::stream::BoxStream;
+
+    use super::*;
+
+    const SMALL_PARQUET: &[u8] = include_bytes!("../../../testdata/parquet/small.parquet");
+
+    /// In-memory source with a fixed version that counts range reads.
+    #[derive(Debug)]
+    struct VersionedSource {
+        content: Bytes,
+        version: Option<String>,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl FileSource for VersionedSource {
+        fn read_range(&mut self, start: usize, len: usize) -> BoxFuture<Result<Bytes>> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            let bs = self.content.slice(start..start + len);
+            Box::pin(async move { Ok(bs) })
+        }
+
+        fn read_stream(&mut self) -> BoxStream<'static, Result<Bytes>> {
+            let bs = self.content.clone();
+            stream::once(async move { Ok(bs) }).boxed()
+        }
+
+        fn size(&mut self) -> BoxFuture<Result<usize>> {
+            let size = self.content.len();
+            Box::pin(async move { Ok(size) })
+        }
+
+        fn version(&self) -> Option<String> {
+            self.version.clone()
+        }
+    }
+
+    /// Load metadata for a source with the given version, returning the number
+    /// of range reads made.
+    fn load(cache: &MetadataCache, version: Option<&str>) -> usize {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let mut source = VersionedSource {
+            content: Bytes::from_static(SMALL_PARQUET),
+            version: version.map(|v| v.to_string()),
+            reads: reads.clone(),
+        };
+        let location = FileLocation::parse("s3://bucket/small.parquet");
+
+        block_on(load_metadata_with_cache(
+            cache,
+            &mut source,
+            &location,
+            &AccessConfig::None,
+        ))
+        .unwrap();
+
+        reads.load(Ordering::Relaxed)
+    }
+
+    #[test]
+    fn cached_until_version_changes() {
+        let cache = MetadataCache::new(DEFAULT_METADATA_CACHE_BYTES);
+
+        assert_eq!(2, load(&cache, Some("v1")));
+        assert_eq!(0, load(&cache, Some("v1")));
+
+        // File replaced.
+        assert_eq!(2, load(&cache, Some("v2")));
+        assert_eq!(0, load(&cache, Some("v2")));
+
+        assert_eq!(2, cache.stats().hits);
+        assert_eq!(2, cache.stats().entries);
+    }
+
+    #[test]
+    fn unversioned_not_cached() {
+        let cache = MetadataCache::new(DEFAULT_METADATA_CACHE_BYTES);
+
+        assert_eq!(2, load(&cache, None));
+        assert_eq!(2, load(&cache, None));
+        assert_eq!(0, cache.stats().entries);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let metadata_size = {
+            let cache = MetadataCache::new(DEFAULT_METADATA_CACHE_BYTES);
+            load(&cache, Some("v1"));
+            cache.stats().bytes
+        };
+
+        // Room for two entries.
+        let cache = MetadataCache::new(metadata_size * 2);
+        load(&cache, Some("v1"));
+        load(&cache, Some("v2"));
+        assert_eq!(0, load(&cache, Some("v1")));
+        load(&cache, Some("v3"));
+
+        assert_eq!(0, load(&cache, Some("v1")));
+        assert_eq!(2, load(&cache, Some("v2")));
+    }
+}
@@ -22,7 +22,7 @@ use rayexec_error::{RayexecError, Result, ResultExt};
 use rayexec_execution::arrays::array::{Array, ArrayData};
 use rayexec_execution::arrays::batch::Batch;
 use rayexec_execution::arrays::bitmap::Bitmap;
-use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::datatype::{DataType, TimeUnit};
 use rayexec_execution::arrays::field::Schema;
 use rayexec_execution::storage::table_storage::Projections;
 use rayexec_io::FileSource;
@@ -116,6 +116,17 @@ where
 /// Trait for converting a buffer of values into array data.
 pub trait IntoArrayData {
     fn into_array_data(self) -> ArrayData;
+
+    /// Convert into array data for timestamps with the given unit.
+    ///
+    /// Only needed for values that don't already have a unit (INT96), other
+    /// values are converted as is.
+    fn into_timestamp_array_data(self, _unit: TimeUnit) -> ArrayData
+    where
+        Self: Sized,
+    {
+        self.into_array_data()
+    }
 }
 
 pub fn def_levels_into_bitmap(def_levels: Vec<i16>) -> Bitmap {
@@ -8,7 +8,7 @@ use rayexec_execution::arrays::array::{Array, ArrayData};
 use rayexec_execution::arrays::bitmap::Bitmap;
 use rayexec_execution::arrays::compute::cast::array::cast_array;
 use rayexec_execution::arrays::compute::cast::behavior::CastFailBehavior;
-use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::datatype::{DataType, TimeUnit, TimestampTypeMeta};
 use rayexec_execution::arrays::storage::{BooleanStorage, PrimitiveStorage};
 
 use super::{
@@ -18,6 +18,7 @@ use super::{
     IntoArrayData,
     ValuesReader,
 };
+use crate::schema::int64_timestamp_unit;
 
 pub struct PrimitiveArrayReader<T: ParquetDataType, P: PageReader> {
     batch_size: usize,
@@ -77,8 +78,16 @@ where
             (PhysicalType::INT64, DataType::Int64) => (data.into_array_data(), self.datatype.clone()),
             (PhysicalType::INT64, DataType::Decimal64(_)) => (data.into_array_data(), self.datatype.clone()),
             (PhysicalType::INT64, DataType::Decimal128(_)) => (data.into_array_data(), DataType::Int64), // TODO
-            (PhysicalType::INT64, DataType::Timestamp(_)) => (data.into_array_data(), self.datatype.clone()),
-            (PhysicalType::INT96, DataType::Timestamp(_)) => (data.into_array_data(), self.datatype.clone()),
+            (PhysicalType::INT64, DataType::Timestamp(_)) => {
+                // Values are in the file's unit, cast to the desired unit if
+                // they differ.
+                let build_type = match int64_timestamp_unit(self.values_reader.description.self_type().get_basic_info()) {
+                    Some(unit) => DataType::Timestamp(TimestampTypeMeta::new(unit)),
+                    None => self.datatype.clone(),
+                };
+                (data.into_array_data(), build_type)
+            }
+            (PhysicalType::INT96, DataType::Timestamp(meta)) => (data.into_timestamp_array_data(meta.unit), self.datatype.clone()),
             (PhysicalType::FLOAT, DataType::Float32) => (data.into_array_data(), self.datatype.clone()),
             (PhysicalType::DOUBLE, DataType::Float64) => (data.into_array_data(), self.datatype.clone()),
             (p_other, d_other) => return Err(RayexecError::new(format!("Unknown conversion from parquet to bullet type in primitive reader; parquet: {p_other}, bullet: {d_other}")))
@@ -152,7 +161,54 @@ impl_into_array_primitive!(f64);
 
 impl IntoArrayData for Vec<Int96> {
     fn into_array_data(self) -> ArrayData {
-        let values: Vec<_> = self.into_iter().map(|v| v.to_nanos()).collect();
+        self.into_timestamp_array_data(TimeUnit::Nanosecond)
+    }
+
+    fn into_timestamp_array_data(self, unit: TimeUnit) -> ArrayData {
+        // Convert directly to the desired unit instead of going through
+        // nanoseconds to avoid overflowing for timestamps outside of the
+        // nanosecond range (years 1677 to 2262).
+        let values: Vec<_> = match unit {
+            TimeUnit::Second => self
+                .into_iter()
+                .map(|v| v.to_seconds_and_nanos().0)
+                .collect(),
+            TimeUnit::Millisecond => self.into_iter().map(|v| v.to_i64()).collect(),
+            TimeUnit::Microsecond => self.into_iter().map(|v| v.to_micros()).collect(),
+            TimeUnit::Nanosecond => self.into_iter().map(|v| v.to_nanos()).collect(),
+        };
         PrimitiveStorage::from(values).into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rayexec_execution::arrays::array::Array;
+    use rayexec_execution::arrays::scalar::timestamp::TimestampScalar;
+    use rayexec_execution::arrays::scalar::ScalarValue;
+
+    use super::*;
+
+    #[test]
+    fn int96_to_timestamp_units() {
+        // 1 second and 500 micros after midnight on 1600-01-01, before the
+        // range representable with nanoseconds.
+        let mut v = Int96::new();
+        let nanos: i64 = 1_000_500_000;
+        v.set_data(nanos as u32, (nanos >> 32) as u32, 2_305_448);
+
+        let timestamp = |unit| DataType::Timestamp(TimestampTypeMeta::new(unit));
+        let array = Array::new_with_array_data(
+            timestamp(TimeUnit::Microsecond),
+            vec![v].into_timestamp_array_data(TimeUnit::Microsecond),
+        );
+
+        assert_eq!(
+            ScalarValue::Timestamp(TimestampScalar {
+                unit: TimeUnit::Microsecond,
+                value: -11_676_096_000_000_000 + 1_000_500,
+            }),
+            array.logical_value(0).unwrap()
+        );
+    }
+}
@@ -2,12 +2,14 @@ use parquet::basic::Type as PhysicalType;
 use parquet::column::page::PageReader;
 use parquet::column::reader::view::ViewColumnValueDecoder;
 use parquet::data_type::{ByteArray, DataType as ParquetDataType};
-use parquet::decoding::view::ViewBuffer;
+use parquet::decoding::view::{ViewBuffer, ViewValues};
 use parquet::schema::types::ColumnDescPtr;
 use rayexec_error::{RayexecError, Result};
 use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::bitmap::Bitmap;
 use rayexec_execution::arrays::datatype::DataType;
 use rayexec_execution::arrays::executor::builder::ArrayDataBuffer;
+use rayexec_execution::arrays::selection::SelectionVector;
 
 use super::{def_levels_into_bitmap, insert_null_values, ArrayBuilder, ValuesReader};
 
@@ -42,14 +44,40 @@ where
 
         let arr = match (ByteArray::get_physical_type(), &self.datatype) {
             (PhysicalType::BYTE_ARRAY, _) => {
-                match def_levels {
-                    Some(levels) => {
+                match (view_buffer.into_values(), def_levels) {
+                    (ViewValues::Dictionary { dict, mut keys }, def_levels) => {
+                        // All values read from the same dictionary, select
+                        // from the dictionary instead of copying values.
+                        let data = dict.array_data();
+                        match def_levels {
+                            Some(levels) => {
+                                let bitmap = def_levels_into_bitmap(levels);
+                                insert_null_values(&mut keys, &bitmap);
+                                for (idx, valid) in bitmap.iter().enumerate() {
+                                    if !valid {
+                                        keys[idx] = dict.null_key();
+                                    }
+                                }
+
+                                // Validity is for the dictionary values, only
+                                // the extra null value is invalid.
+                                let mut validity = Bitmap::new_with_all_true(dict.len() + 1);
+                                validity.set_unchecked(dict.null_key(), false);
+
+                                Array::new_with_validity_selection_and_array_data(self.datatype.clone(), validity, SelectionVector::from(keys), data)
+                            }
+                            None => {
+                                let mut arr = Array::new_with_array_data(self.datatype.clone(), data);
+                                arr.put_selection(SelectionVector::from(keys));
+                                arr
+                            }
+                        }
+                    }
+                    (ViewValues::Buffer(mut buffer), Some(levels)) => {
                         // Logical validities, used to insert null values into
                         // the metadata vec.
                         let bitmap = def_levels_into_bitmap(levels);
 
-                        let mut buffer = view_buffer.into_buffer();
-
                         // Insert nulls into the correct location.
                         //
                         // The "null" values will just be zeroed metadata fields.
@@ -57,8 +85,8 @@ where
 
                         Array::new_with_validity_and_array_data(self.datatype.clone(), bitmap, buffer.into_data())
                     }
-                    None => {
-                        Array::new_with_array_data(self.datatype.clone(), view_buffer.into_buffer().into_data())
+                    (ViewValues::Buffer(buffer), None) => {
+                        Array::new_with_array_data(self.datatype.clone(), buffer.into_data())
                     }
                 }
             }
@@ -350,6 +350,21 @@ fn from_int32(info: &BasicTypeInfo, _scale: i32, _precision: i32) -> Result<Data
     }
 }
 
+/// Get the unit for an INT64 timestamp column, returns None if the column isn't
+/// a timestamp.
+pub(crate) fn int64_timestamp_unit(info: &BasicTypeInfo) -> Option<TimeUnit> {
+    match (info.logical_type(), info.converted_type()) {
+        (Some(LogicalType::Timestamp { unit, .. }), _) => Some(match unit {
+            ParquetTimeUnit::MILLIS(_) => TimeUnit::Millisecond,
+            ParquetTimeUnit::MICROS(_) => TimeUnit::Microsecond,
+            ParquetTimeUnit::NANOS(_) => TimeUnit::Nanosecond,
+        }),
+        (None, ConvertedType::TIMESTAMP_MILLIS) => Some(TimeUnit::Millisecond),
+        (None, ConvertedType::TIMESTAMP_MICROS) => Some(TimeUnit::Microsecond),
+        _ => None,
+    }
+}
+
 fn from_int64(info: &BasicTypeInfo, _scale: i32, _precision: i32) -> Result<DataType> {
     match (info.logical_type(), info.converted_type()) {
         (None, ConvertedType::NONE) => Ok(DataType::Int64),
@@ -370,28 +385,16 @@ fn from_int64(info: &BasicTypeInfo, _scale: i32, _precision: i32) -> Result<Data
             ParquetTimeUnit::MICROS(_) => unimplemented!(),
             ParquetTimeUnit::NANOS(_) => unimplemented!(),
         },
-        (
-            Some(LogicalType::Timestamp {
-                is_adjusted_to_u_t_c,
-                unit,
-            }),
-            _,
-        ) => {
-            let unit = match unit {
-                ParquetTimeUnit::MILLIS(_) => TimeUnit::Millisecond,
-                ParquetTimeUnit::MICROS(_) => TimeUnit::Microsecond,
-                ParquetTimeUnit::NANOS(_) => TimeUnit::Nanosecond,
-            };
-            if is_adjusted_to_u_t_c {
-                not_implemented!("parquet timestamp adjusted to utc");
-            }
+        (Some(LogicalType::Timestamp { .. }), _)
+        | (None, ConvertedType::TIMESTAMP_MILLIS | ConvertedType::TIMESTAMP_MICROS) => {
+            // TODO: Timestamps adjusted to UTC should have a UTC timezone once
+            // we support timezones. Values are UTC either way.
+            let unit = int64_timestamp_unit(info).expect("timestamp to have a unit");
             Ok(DataType::Timestamp(TimestampTypeMeta::new(unit)))
         }
         (None, ConvertedType::INT_64) => Ok(DataType::Int64),
         (None, ConvertedType::UINT_64) => Ok(DataType::UInt64),
         (None, ConvertedType::TIME_MICROS) => unimplemented!(),
-        (None, ConvertedType::TIMESTAMP_MILLIS) => unimplemented!(),
-        (None, ConvertedType::TIMESTAMP_MICROS) => unimplemented!(),
         (Some(LogicalType::Decimal { scale, precision }), _) => decimal_type(precision, scale),
         (None, ConvertedType::DECIMAL) => unimplemented!(),
         (logical, converted) => Err(RayexecError::new(format!(
@@ -426,3 +429,36 @@ fn from_byte_array(info: &BasicTypeInfo, _precision: i32, _scale: i32) -> Result
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use parquet::schema::parser::parse_message_type;
+
+    use super::*;
+
+    #[test]
+    fn timestamp_units() {
+        let message = "
+            message schema {
+                REQUIRED INT64 millis (TIMESTAMP_MILLIS);
+                REQUIRED INT64 micros (TIMESTAMP_MICROS);
+                REQUIRED INT64 nanos_utc (TIMESTAMP(NANOS, true));
+                REQUIRED INT96 int96;
+            }
+        ";
+        let parquet_schema = SchemaDescriptor::new(Arc::new(parse_message_type(message).unwrap()));
+        let schema = from_parquet_schema(&parquet_schema).unwrap();
+
+        let timestamp = |unit| DataType::Timestamp(TimestampTypeMeta::new(unit));
+        let got: Vec<_> = schema.fields.iter().map(|f| f.datatype.clone()).collect();
+        assert_eq!(
+            vec![
+                timestamp(TimeUnit::Millisecond),
+                timestamp(TimeUnit::Microsecond),
+                timestamp(TimeUnit::Nanosecond),
+                timestamp(TimeUnit::Nanosecond),
+            ],
+            got
+        );
+    }
+}
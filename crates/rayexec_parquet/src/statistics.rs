@@ -0,0 +1,155 @@
+use parquet::file::metadata::RowGroupMetaData;
+use parquet::file::statistics::Statistics;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::field::Schema;
+use rayexec_execution::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use rayexec_execution::logical::statistics::StatisticsValue;
+use rayexec_execution::storage::table_storage::{TableColumnStatistics, TableStatistics};
+
+use crate::metadata::Metadata;
+
+/// Compute table statistics from parquet metadata.
+///
+/// Row counts and sizes are exact. Column min/max values are only provided
+/// for flat schemas where every row group has statistics for the column.
+pub fn table_statistics(metadata: &Metadata, schema: &Schema) -> TableStatistics {
+    let row_groups = metadata.decoded_metadata.row_groups();
+
+    let num_rows = row_groups.iter().map(|g| g.num_rows()).sum::<i64>() as usize;
+    let num_bytes = row_groups.iter().map(|g| g.total_byte_size()).sum::<i64>() as usize;
+
+    // Leaf columns only line up with fields if there's no nesting.
+    let num_leaves = metadata
+        .decoded_metadata
+        .file_metadata()
+        .schema_descr()
+        .num_columns();
+    let columns = if num_leaves == schema.fields.len() {
+        schema
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| column_statistics(row_groups, idx, &field.datatype))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    TableStatistics {
+        num_rows: StatisticsValue::Exact(num_rows),
+        num_bytes: StatisticsValue::Exact(num_bytes),
+        columns,
+    }
+}
+
+fn column_statistics(
+    row_groups: &[RowGroupMetaData],
+    column: usize,
+    datatype: &DataType,
+) -> TableColumnStatistics {
+    let stats = row_groups
+        .iter()
+        .map(|g| g.column(column).statistics())
+        .collect::<Option<Vec<_>>>();
+
+    let min_max = stats.and_then(|stats| scalar_min_max(&stats, datatype));
+
+    match min_max {
+        Some((min, max)) => TableColumnStatistics {
+            min: StatisticsValue::Exact(min),
+            max: StatisticsValue::Exact(max),
+        },
+        None => TableColumnStatistics::default(),
+    }
+}
+
+/// Get the min and max values across all row group statistics for a column.
+///
+/// Only types where the parquet sort order matches ours are handled.
+fn scalar_min_max(
+    stats: &[&Statistics],
+    datatype: &DataType,
+) -> Option<(OwnedScalarValue, OwnedScalarValue)> {
+    macro_rules! min_max {
+        ($stat:ident, $scalar:ident, $cast:ty) => {
+            min_max(stats, |s| match s {
+                Statistics::$stat(v) if v.has_min_max_set() => Some((*v.min(), *v.max())),
+                _ => None,
+            })
+            .map(|(min, max)| {
+                (
+                    ScalarValue::$scalar(min as $cast),
+                    ScalarValue::$scalar(max as $cast),
+                )
+            })
+        };
+    }
+
+    match datatype {
+        DataType::Boolean => min_max!(Boolean, Boolean, bool),
+        DataType::Int8 => min_max!(Int32, Int8, i8),
+        DataType::Int16 => min_max!(Int32, Int16, i16),
+        DataType::Int32 => min_max!(Int32, Int32, i32),
+        DataType::Int64 => min_max!(Int64, Int64, i64),
+        DataType::Float32 => min_max!(Float, Float32, f32),
+        DataType::Float64 => min_max!(Double, Float64, f64),
+        _ => None,
+    }
+}
+
+/// Fold min/max values from multiple statistics.
+///
+/// `typed` extracts the min/max values from statistics of the expected
+/// physical type.
+///
+/// Returns None if any statistics are missing min/max values, or if the
+/// values can't be compared (NaN).
+fn min_max<T: PartialOrd + Copy>(
+    stats: &[&Statistics],
+    typed: impl Fn(&Statistics) -> Option<(T, T)>,
+) -> Option<(T, T)> {
+    let mut acc: Option<(T, T)> = None;
+
+    for stats in stats {
+        let (min, max) = typed(stats)?;
+        // NaN can't be compared.
+        min.partial_cmp(&max)?;
+
+        acc = Some(match acc {
+            Some((acc_min, acc_max)) => (
+                if min < acc_min { min } else { acc_min },
+                if max > acc_max { max } else { acc_max },
+            ),
+            None => (min, max),
+        });
+    }
+
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_max_across_row_groups() {
+        let a = Statistics::int32(Some(4), Some(10), None, 0, false);
+        let b = Statistics::int32(Some(-2), Some(8), None, 1, false);
+
+        let out = scalar_min_max(&[&a, &b], &DataType::Int64);
+        assert_eq!(None, out, "physical type doesn't match");
+
+        let out = scalar_min_max(&[&a, &b], &DataType::Int16).unwrap();
+        assert_eq!((ScalarValue::Int16(-2), ScalarValue::Int16(10)), out);
+    }
+
+    #[test]
+    fn min_max_missing_values() {
+        let a = Statistics::double(Some(1.5), Some(2.5), None, 0, false);
+        let b = Statistics::double(None, None, None, 4, false);
+        assert_eq!(None, scalar_min_max(&[&a, &b], &DataType::Float64));
+
+        let nan = Statistics::double(Some(f64::NAN), Some(2.5), None, 0, false);
+        assert_eq!(None, scalar_min_max(&[&a, &nan], &DataType::Float64));
+    }
+}
@@ -0,0 +1,166 @@
+use rayexec_error::{RayexecError, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{AstParseable, ColumnDef, Expr, Ident, ObjectReference};
+use crate::keywords::Keyword;
+use crate::meta::{AstMeta, Raw};
+use crate::parser::Parser;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlterTable<T: AstMeta> {
+    pub if_exists: bool,
+    pub name: T::ItemReference,
+    pub operation: AlterTableOperation<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AlterTableOperation<T: AstMeta> {
+    /// ADD [COLUMN] [IF NOT EXISTS] <column> [DEFAULT <expr>]
+    AddColumn {
+        if_not_exists: bool,
+        column: ColumnDef<T>,
+        default: Option<Expr<T>>,
+    },
+    /// DROP [COLUMN] [IF EXISTS] <column>
+    DropColumn { if_exists: bool, name: Ident },
+    /// RENAME [COLUMN] <column> TO <new_name>
+    RenameColumn { from: Ident, to: Ident },
+}
+
+impl AstParseable for AlterTable<Raw> {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.expect_keyword(Keyword::ALTER)?;
+        parser.expect_keyword(Keyword::TABLE)?;
+
+        let if_exists = parser.parse_keyword_sequence(&[Keyword::IF, Keyword::EXISTS]);
+        let name = ObjectReference::parse(parser)?;
+
+        let operation = match parser.next_keyword()? {
+            Keyword::ADD => {
+                parser.parse_keyword(Keyword::COLUMN);
+                let if_not_exists =
+                    parser.parse_keyword_sequence(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+                let column = ColumnDef::parse(parser)?;
+                let default = if parser.parse_keyword(Keyword::DEFAULT) {
+                    Some(Expr::parse(parser)?)
+                } else {
+                    None
+                };
+
+                AlterTableOperation::AddColumn {
+                    if_not_exists,
+                    column,
+                    default,
+                }
+            }
+            Keyword::DROP => {
+                parser.parse_keyword(Keyword::COLUMN);
+                let if_exists = parser.parse_keyword_sequence(&[Keyword::IF, Keyword::EXISTS]);
+                let name = Ident::parse(parser)?;
+
+                AlterTableOperation::DropColumn { if_exists, name }
+            }
+            Keyword::RENAME => {
+                parser.parse_keyword(Keyword::COLUMN);
+                let from = Ident::parse(parser)?;
+                parser.expect_keyword(Keyword::TO)?;
+                let to = Ident::parse(parser)?;
+
+                AlterTableOperation::RenameColumn { from, to }
+            }
+            other => {
+                return Err(RayexecError::new(format!(
+                    "Got unexpected keyword for alter table: {other}"
+                )))
+            }
+        };
+
+        Ok(AlterTable {
+            if_exists,
+            name,
+            operation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::testutil::parse_ast;
+    use crate::ast::{ColumnOption, DataType, Literal};
+
+    #[test]
+    fn add_column() {
+        let got = parse_ast::<AlterTable<_>>("alter table t1 add column c int").unwrap();
+        let expected = AlterTable {
+            if_exists: false,
+            name: ObjectReference::from_strings(["t1"]),
+            operation: AlterTableOperation::AddColumn {
+                if_not_exists: false,
+                column: ColumnDef {
+                    name: Ident::new_unquoted("c"),
+                    datatype: DataType::Integer,
+                    opts: Vec::new(),
+                },
+                default: None,
+            },
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn add_column_default() {
+        let got = parse_ast::<AlterTable<_>>(
+            "alter table if exists s.t1 add if not exists c int not null default 4",
+        )
+        .unwrap();
+        let expected = AlterTable {
+            if_exists: true,
+            name: ObjectReference::from_strings(["s", "t1"]),
+            operation: AlterTableOperation::AddColumn {
+                if_not_exists: true,
+                column: ColumnDef {
+                    name: Ident::new_unquoted("c"),
+                    datatype: DataType::Integer,
+                    opts: vec![ColumnOption::NotNull],
+                },
+                default: Some(Expr::Literal(Literal::Number("4".to_string()))),
+            },
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn drop_column() {
+        for sql in [
+            "alter table t1 drop column c",
+            "alter table t1 drop c",
+            "ALTER TABLE t1 DROP COLUMN IF EXISTS c",
+        ] {
+            let got = parse_ast::<AlterTable<_>>(sql).unwrap();
+            assert!(
+                matches!(&got.operation, AlterTableOperation::DropColumn { name, .. } if name == &Ident::new_unquoted("c")),
+                "sql: {sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn rename_column() {
+        let got = parse_ast::<AlterTable<_>>("alter table t1 rename column a to b").unwrap();
+        let expected = AlterTable {
+            if_exists: false,
+            name: ObjectReference::from_strings(["t1"]),
+            operation: AlterTableOperation::RenameColumn {
+                from: Ident::new_unquoted("a"),
+                to: Ident::new_unquoted("b"),
+            },
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn unknown_operation() {
+        parse_ast::<AlterTable<_>>("alter table t1 truncate").unwrap_err();
+    }
+}
@@ -0,0 +1,150 @@
+use rayexec_error::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{AstParseable, DataType, Expr, Ident, ObjectReference};
+use crate::keywords::Keyword;
+use crate::meta::{AstMeta, Raw};
+use crate::parser::Parser;
+
+/// CREATE FUNCTION
+///
+/// ```text
+/// CREATE [OR REPLACE] [TEMP] FUNCTION <name>([<arg_name>] <type>, ...)
+///     RETURNS <type>
+///     LANGUAGE <language>
+///     AS '<body>'
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateFunction<T: AstMeta> {
+    pub or_replace: bool,
+    pub temp: bool,
+    pub name: T::ItemReference,
+    pub args: Vec<FunctionArgDef<T>>,
+    pub return_type: Option<T::DataType>,
+    pub language: Option<Ident>,
+    /// Function body, interpreted according to the language.
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionArgDef<T: AstMeta> {
+    pub name: Option<Ident>,
+    pub datatype: T::DataType,
+}
+
+impl AstParseable for CreateFunction<Raw> {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.expect_keyword(Keyword::CREATE)?;
+
+        let or_replace = parser.parse_keyword_sequence(&[Keyword::OR, Keyword::REPLACE]);
+        let temp = parser
+            .parse_one_of_keywords(&[Keyword::TEMP, Keyword::TEMPORARY])
+            .is_some();
+
+        parser.expect_keyword(Keyword::FUNCTION)?;
+
+        let name = ObjectReference::parse(parser)?;
+        let args = parser.parse_parenthesized_comma_separated(FunctionArgDef::parse)?;
+
+        // RETURNS and LANGUAGE may be provided in any order.
+        let mut return_type = None;
+        let mut language = None;
+        loop {
+            if return_type.is_none() && parser.parse_keyword(Keyword::RETURNS) {
+                return_type = Some(DataType::parse(parser)?);
+            } else if language.is_none() && parser.parse_keyword(Keyword::LANGUAGE) {
+                language = Some(Ident::parse(parser)?);
+            } else {
+                break;
+            }
+        }
+
+        parser.expect_keyword(Keyword::AS)?;
+        let body = Expr::parse_string_literal(parser)?;
+
+        Ok(CreateFunction {
+            or_replace,
+            temp,
+            name,
+            args,
+            return_type,
+            language,
+            body,
+        })
+    }
+}
+
+impl AstParseable for FunctionArgDef<Raw> {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        // Try with an argument name first, falling back to just the type.
+        let named = parser.maybe_parse(|parser| {
+            let name = Ident::parse(parser)?;
+            let datatype = DataType::parse(parser)?;
+            Ok((name, datatype))
+        });
+
+        Ok(match named {
+            Some((name, datatype)) => FunctionArgDef {
+                name: Some(name),
+                datatype,
+            },
+            None => FunctionArgDef {
+                name: None,
+                datatype: DataType::parse(parser)?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::ast::testutil::parse_ast;
+
+    #[test]
+    fn wasm_function() {
+        let got = parse_ast::<CreateFunction<_>>(
+            "CREATE FUNCTION add_one(x INT, DOUBLE) RETURNS INT LANGUAGE wasm AS '0061736d'",
+        )
+        .unwrap();
+        let expected = CreateFunction {
+            or_replace: false,
+            temp: false,
+            name: ObjectReference::from_strings(["add_one"]),
+            args: vec![
+                FunctionArgDef {
+                    name: Some(Ident::new_unquoted("x")),
+                    datatype: DataType::Integer,
+                },
+                FunctionArgDef {
+                    name: None,
+                    datatype: DataType::Double,
+                },
+            ],
+            return_type: Some(DataType::Integer),
+            language: Some(Ident::new_unquoted("wasm")),
+            body: "0061736d".to_string(),
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn language_before_returns() {
+        let got = parse_ast::<CreateFunction<_>>(
+            "CREATE OR REPLACE TEMP FUNCTION f() LANGUAGE wasm RETURNS BIGINT AS ''",
+        )
+        .unwrap();
+        assert!(got.or_replace);
+        assert!(got.temp);
+        assert!(got.args.is_empty());
+        assert_eq!(Some(DataType::BigInt), got.return_type);
+    }
+
+    #[test]
+    fn missing_body() {
+        parse_ast::<CreateFunction<_>>("CREATE FUNCTION f(INT) RETURNS INT LANGUAGE wasm")
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,89 @@
+use rayexec_error::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{AstParseable, Ident, ObjectReference};
+use crate::keywords::Keyword;
+use crate::meta::{AstMeta, Raw};
+use crate::parser::Parser;
+
+/// CREATE INDEX [IF NOT EXISTS] <name> ON <table> [USING <method>] (<column>, ...)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateIndex<T: AstMeta> {
+    pub if_not_exists: bool,
+    pub name: Ident,
+    pub table: T::ItemReference,
+    /// Index method, if one was provided.
+    pub using: Option<Ident>,
+    pub columns: Vec<Ident>,
+}
+
+impl AstParseable for CreateIndex<Raw> {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.expect_keyword(Keyword::CREATE)?;
+        parser.expect_keyword(Keyword::INDEX)?;
+
+        let if_not_exists =
+            parser.parse_keyword_sequence(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = Ident::parse(parser)?;
+
+        parser.expect_keyword(Keyword::ON)?;
+        let table = ObjectReference::parse(parser)?;
+
+        let using = if parser.parse_keyword(Keyword::USING) {
+            Some(Ident::parse(parser)?)
+        } else {
+            None
+        };
+
+        let columns = parser.parse_parenthesized_comma_separated(Ident::parse)?;
+
+        Ok(CreateIndex {
+            if_not_exists,
+            name,
+            table,
+            using,
+            columns,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::testutil::parse_ast;
+
+    #[test]
+    fn basic() {
+        let got = parse_ast::<CreateIndex<_>>("create index idx on t1 (a)").unwrap();
+        let expected = CreateIndex {
+            if_not_exists: false,
+            name: Ident::new_unquoted("idx"),
+            table: ObjectReference::from_strings(["t1"]),
+            using: None,
+            columns: vec![Ident::new_unquoted("a")],
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn using_multiple_columns() {
+        let got = parse_ast::<CreateIndex<_>>(
+            "CREATE INDEX IF NOT EXISTS idx ON s.t1 USING btree (a, b)",
+        )
+        .unwrap();
+        let expected = CreateIndex {
+            if_not_exists: true,
+            name: Ident::new_unquoted("idx"),
+            table: ObjectReference::from_strings(["s", "t1"]),
+            using: Some(Ident::new_unquoted("btree")),
+            columns: vec![Ident::new_unquoted("a"), Ident::new_unquoted("b")],
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn missing_columns() {
+        parse_ast::<CreateIndex<_>>("create index idx on t1").unwrap_err();
+        parse_ast::<CreateIndex<_>>("create index on t1 (a)").unwrap_err();
+    }
+}
@@ -0,0 +1,134 @@
+use rayexec_error::{RayexecError, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{AstParseable, Expr, Ident, ObjectReference, QueryNode};
+use crate::keywords::Keyword;
+use crate::meta::{AstMeta, Raw};
+use crate::parser::Parser;
+
+/// CREATE MACRO
+///
+/// ```text
+/// CREATE [OR REPLACE] [TEMP] MACRO <name>(<param>, ...) AS <expr>
+/// CREATE [OR REPLACE] [TEMP] MACRO <name>(<param>, ...) AS TABLE <query>
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateMacro<T: AstMeta> {
+    pub or_replace: bool,
+    pub temp: bool,
+    pub name: T::ItemReference,
+    pub params: Vec<Ident>,
+    pub body: MacroBody,
+}
+
+/// Body of a macro.
+///
+/// Only the sql string is kept since parameters can only be substituted once
+/// the macro is called.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MacroBody {
+    /// A scalar expression.
+    Expr(String),
+    /// A query producing a table.
+    Table(String),
+}
+
+impl AstParseable for CreateMacro<Raw> {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.expect_keyword(Keyword::CREATE)?;
+
+        let or_replace = parser.parse_keyword_sequence(&[Keyword::OR, Keyword::REPLACE]);
+        let temp = parser
+            .parse_one_of_keywords(&[Keyword::TEMP, Keyword::TEMPORARY])
+            .is_some();
+
+        parser.expect_keyword(Keyword::MACRO)?;
+
+        let name = ObjectReference::parse(parser)?;
+        let params = parser.parse_parenthesized_comma_separated(Ident::parse)?;
+
+        parser.expect_keyword(Keyword::AS)?;
+        let is_table = parser.parse_keyword(Keyword::TABLE);
+
+        let body_tok = match parser.peek() {
+            Some(tok) => tok.clone(),
+            None => {
+                return Err(RayexecError::new(
+                    "Unexpected end of statement, expect macro body",
+                ))
+            }
+        };
+
+        // Parse the body to catch syntax errors early, even though we only
+        // keep the string around.
+        if is_table {
+            let _ = QueryNode::parse(parser)?;
+        } else {
+            let _ = Expr::parse(parser)?;
+        }
+
+        let body_sql = parser.sql_slice_starting_at(&body_tok)?.trim().to_string();
+        let body = if is_table {
+            MacroBody::Table(body_sql)
+        } else {
+            MacroBody::Expr(body_sql)
+        };
+
+        Ok(CreateMacro {
+            or_replace,
+            temp,
+            name,
+            params,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::ast::testutil::parse_ast;
+
+    #[test]
+    fn scalar_macro() {
+        let got = parse_ast::<CreateMacro<_>>("CREATE MACRO add(a, b) AS a + b").unwrap();
+        let expected = CreateMacro {
+            or_replace: false,
+            temp: false,
+            name: ObjectReference::from_strings(["add"]),
+            params: vec![Ident::new_unquoted("a"), Ident::new_unquoted("b")],
+            body: MacroBody::Expr("a + b".to_string()),
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn table_macro() {
+        let got = parse_ast::<CreateMacro<_>>(
+            "CREATE OR REPLACE TEMP MACRO gen(n) AS TABLE SELECT * FROM generate_series(1, n) ",
+        )
+        .unwrap();
+        let expected = CreateMacro {
+            or_replace: true,
+            temp: true,
+            name: ObjectReference::from_strings(["gen"]),
+            params: vec![Ident::new_unquoted("n")],
+            body: MacroBody::Table("SELECT * FROM generate_series(1, n)".to_string()),
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn no_params() {
+        let got = parse_ast::<CreateMacro<_>>("CREATE MACRO one() AS 1").unwrap();
+        assert!(got.params.is_empty());
+        assert_eq!(MacroBody::Expr("1".to_string()), got.body);
+    }
+
+    #[test]
+    fn invalid_body() {
+        parse_ast::<CreateMacro<_>>("CREATE MACRO m(a) AS TABLE a +").unwrap_err();
+    }
+}
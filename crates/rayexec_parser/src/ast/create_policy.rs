@@ -0,0 +1,107 @@
+use rayexec_error::{RayexecError, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{AstParseable, Expr, Ident, ObjectReference};
+use crate::keywords::Keyword;
+use crate::meta::{AstMeta, Raw};
+use crate::parser::Parser;
+use crate::tokens::Token;
+
+/// CREATE POLICY <name> ON <table> [TO <role>, ...] USING (<expr>)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreatePolicy<T: AstMeta> {
+    pub name: Ident,
+    pub table: T::TableReference,
+    /// Roles the policy applies to. Empty if no roles were provided, meaning
+    /// the policy applies to everyone.
+    pub roles: Vec<Ident>,
+    /// Predicate rows must satisfy to be visible.
+    pub using: Expr<T>,
+    /// The original sql for the predicate, persisted with the table.
+    pub sql: String,
+}
+
+impl AstParseable for CreatePolicy<Raw> {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.expect_keyword(Keyword::CREATE)?;
+        parser.expect_keyword(Keyword::POLICY)?;
+
+        let name = Ident::parse(parser)?;
+
+        parser.expect_keyword(Keyword::ON)?;
+        let table = ObjectReference::parse(parser)?;
+
+        let roles = if parser.parse_keyword(Keyword::TO) {
+            parser.parse_comma_separated(Ident::parse)?
+        } else {
+            Vec::new()
+        };
+
+        parser.expect_keyword(Keyword::USING)?;
+        parser.expect_token(&Token::LeftParen)?;
+        let expr_tok = match parser.peek() {
+            Some(tok) => tok.clone(),
+            None => {
+                return Err(RayexecError::new(
+                    "Unexpected end of statement, expected policy expression",
+                ))
+            }
+        };
+        let using = Expr::parse(parser)?;
+        let sql = parser.sql_slice_starting_at(&expr_tok)?.trim().to_string();
+        parser.expect_token(&Token::RightParen)?;
+
+        Ok(CreatePolicy {
+            name,
+            table,
+            roles,
+            using,
+            sql,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::testutil::parse_ast;
+    use crate::ast::{BinaryOperator, Literal};
+
+    #[test]
+    fn basic() {
+        let got = parse_ast::<CreatePolicy<_>>("create policy p1 on t1 using (a > 4)").unwrap();
+        let expected = CreatePolicy {
+            name: Ident::new_unquoted("p1"),
+            table: ObjectReference::from_strings(["t1"]),
+            roles: Vec::new(),
+            using: Expr::BinaryExpr {
+                left: Box::new(Expr::Ident(Ident::new_unquoted("a"))),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::Literal(Literal::Number("4".to_string()))),
+            },
+            sql: "a > 4".to_string(),
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn with_roles() {
+        let got = parse_ast::<CreatePolicy<_>>(
+            "CREATE POLICY p1 ON s.t1 TO analyst, admin USING (owner = 'bob')",
+        )
+        .unwrap();
+        assert_eq!(ObjectReference::from_strings(["s", "t1"]), got.table);
+        assert_eq!(
+            vec![Ident::new_unquoted("analyst"), Ident::new_unquoted("admin")],
+            got.roles
+        );
+        assert_eq!("owner = 'bob'", got.sql);
+    }
+
+    #[test]
+    fn missing_using() {
+        parse_ast::<CreatePolicy<_>>("create policy p1 on t1").unwrap_err();
+        parse_ast::<CreatePolicy<_>>("create policy p1 on t1 to analyst").unwrap_err();
+        parse_ast::<CreatePolicy<_>>("create policy p1 on t1 using a > 4").unwrap_err();
+    }
+}
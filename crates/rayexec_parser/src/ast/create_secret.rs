@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use rayexec_error::{RayexecError, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{AstParseable, Expr, Ident};
+use crate::keywords::Keyword;
+use crate::meta::{AstMeta, Raw};
+use crate::parser::Parser;
+use crate::tokens::Token;
+
+/// CREATE SECRET
+///
+/// ```text
+/// CREATE [OR REPLACE] [TEMP | PERSISTENT] SECRET [IF NOT EXISTS] <name> (
+///     TYPE <type>,
+///     <key> <value>,
+///     ...
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateSecret<T: AstMeta> {
+    pub or_replace: bool,
+    pub if_not_exists: bool,
+    pub persistent: bool,
+    pub name: Ident,
+    /// Type of the secret (e.g. `s3`).
+    pub secret_type: Ident,
+    pub options: HashMap<Ident, Expr<T>>,
+}
+
+impl AstParseable for CreateSecret<Raw> {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.expect_keyword(Keyword::CREATE)?;
+
+        let or_replace = parser.parse_keyword_sequence(&[Keyword::OR, Keyword::REPLACE]);
+        let persistent = matches!(
+            parser.parse_one_of_keywords(&[Keyword::TEMP, Keyword::TEMPORARY, Keyword::PERSISTENT]),
+            Some(Keyword::PERSISTENT)
+        );
+
+        parser.expect_keyword(Keyword::SECRET)?;
+
+        let if_not_exists =
+            parser.parse_keyword_sequence(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+
+        if or_replace && if_not_exists {
+            return Err(RayexecError::new(
+                "Cannot specify both OR REPLACE and IF NOT EXISTS",
+            ));
+        }
+
+        let name = Ident::parse(parser)?;
+
+        let mut secret_type = None;
+        let mut options = HashMap::new();
+
+        parser.expect_token(&Token::LeftParen)?;
+        loop {
+            let key = match Ident::parse(parser) {
+                Ok(ident) => ident,
+                Err(_) => return Err(RayexecError::new("Expected identifier for option key")),
+            };
+
+            if key.as_normalized_string() == "type" {
+                // Type is an identifier, not an expression.
+                secret_type = Some(Ident::parse(parser)?);
+            } else {
+                let val = Expr::parse(parser)?;
+                options.insert(key, val);
+            }
+
+            if parser.consume_token(&Token::RightParen) {
+                break;
+            }
+
+            parser.expect_token(&Token::Comma)?;
+        }
+
+        let secret_type =
+            secret_type.ok_or_else(|| RayexecError::new("Missing TYPE for secret"))?;
+
+        Ok(CreateSecret {
+            or_replace,
+            if_not_exists,
+            persistent,
+            name,
+            secret_type,
+            options,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::ast::testutil::parse_ast;
+    use crate::ast::Literal;
+
+    #[test]
+    fn basic() {
+        let got = parse_ast::<CreateSecret<_>>(
+            "CREATE SECRET my_s3 (TYPE s3, KEY_ID 'key', SECRET 'secret', REGION 'us-east-1')",
+        )
+        .unwrap();
+        let expected = CreateSecret {
+            or_replace: false,
+            if_not_exists: false,
+            persistent: false,
+            name: Ident::new_unquoted("my_s3"),
+            secret_type: Ident::new_unquoted("s3"),
+            options: [
+                (
+                    Ident::new_unquoted("KEY_ID"),
+                    Expr::Literal(Literal::SingleQuotedString("key".to_string())),
+                ),
+                (
+                    Ident::new_unquoted("SECRET"),
+                    Expr::Literal(Literal::SingleQuotedString("secret".to_string())),
+                ),
+                (
+                    Ident::new_unquoted("REGION"),
+                    Expr::Literal(Literal::SingleQuotedString("us-east-1".to_string())),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn or_replace_persistent() {
+        let got = parse_ast::<CreateSecret<_>>(
+            "CREATE OR REPLACE PERSISTENT SECRET pg (TYPE postgres, CONNECTION_STRING 'postgres://localhost')",
+        )
+        .unwrap();
+        assert!(got.or_replace);
+        assert!(got.persistent);
+        assert_eq!(Ident::new_unquoted("postgres"), got.secret_type);
+    }
+
+    #[test]
+    fn missing_type() {
+        parse_ast::<CreateSecret<_>>("CREATE SECRET my_s3 (KEY_ID 'key')").unwrap_err();
+    }
+}
@@ -1,7 +1,7 @@
-use rayexec_error::Result;
+use rayexec_error::{RayexecError, Result};
 use serde::{Deserialize, Serialize};
 
-use super::{AstParseable, DataType, Ident, ObjectReference, QueryNode};
+use super::{AstParseable, DataType, Expr, Ident, ObjectReference, QueryNode};
 use crate::keywords::Keyword;
 use crate::meta::{AstMeta, Raw};
 use crate::parser::Parser;
@@ -65,13 +65,20 @@ impl AstParseable for CreateTable<Raw> {
 pub struct ColumnDef<T: AstMeta> {
     pub name: Ident,
     pub datatype: T::DataType,
-    pub opts: Vec<ColumnOption>,
+    pub opts: Vec<ColumnOption<T>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum ColumnOption {
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ColumnOption<T: AstMeta> {
     Null,
     NotNull,
+    PrimaryKey,
+    /// CHECK (<expr>)
+    Check {
+        expr: Expr<T>,
+        /// The original sql for the expression, persisted with the table.
+        sql: String,
+    },
 }
 
 impl AstParseable for ColumnDef<Raw> {
@@ -81,11 +88,31 @@ impl AstParseable for ColumnDef<Raw> {
 
         let mut opts = Vec::new();
 
-        if parser.parse_keyword_sequence(&[Keyword::NOT, Keyword::NULL]) {
-            opts.push(ColumnOption::NotNull)
-        }
-        if parser.parse_keyword(Keyword::NULL) {
-            opts.push(ColumnOption::Null)
+        loop {
+            if parser.parse_keyword_sequence(&[Keyword::NOT, Keyword::NULL]) {
+                opts.push(ColumnOption::NotNull)
+            } else if parser.parse_keyword(Keyword::NULL) {
+                opts.push(ColumnOption::Null)
+            } else if parser.parse_keyword_sequence(&[Keyword::PRIMARY, Keyword::KEY]) {
+                opts.push(ColumnOption::PrimaryKey)
+            } else if parser.parse_keyword(Keyword::CHECK) {
+                parser.expect_token(&Token::LeftParen)?;
+                let expr_tok = match parser.peek() {
+                    Some(tok) => tok.clone(),
+                    None => {
+                        return Err(RayexecError::new(
+                            "Unexpected end of statement, expect check expression",
+                        ))
+                    }
+                };
+                let expr = Expr::parse(parser)?;
+                let sql = parser.sql_slice_starting_at(&expr_tok)?.trim().to_string();
+                parser.expect_token(&Token::RightParen)?;
+
+                opts.push(ColumnOption::Check { expr, sql })
+            } else {
+                break;
+            }
         }
 
         Ok(ColumnDef {
@@ -100,7 +127,7 @@ impl AstParseable for ColumnDef<Raw> {
 mod tests {
     use super::*;
     use crate::ast::testutil::parse_ast;
-    use crate::ast::{Expr, LimitModifier, Literal, QueryNodeBody, Values};
+    use crate::ast::{BinaryOperator, LimitModifier, Literal, QueryNodeBody, Values};
 
     /// Query node for 'values (1)'
     fn query_node_values_1() -> QueryNode<Raw> {
@@ -241,4 +268,41 @@ mod tests {
         };
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn column_constraints() {
+        let got = parse_ast::<CreateTable<_>>(
+            "create table hello (a int primary key, b int not null check ( b > 0 ) null)",
+        )
+        .unwrap();
+        let expected = vec![
+            ColumnDef {
+                name: Ident::new_unquoted("a"),
+                datatype: DataType::Integer,
+                opts: vec![ColumnOption::PrimaryKey],
+            },
+            ColumnDef {
+                name: Ident::new_unquoted("b"),
+                datatype: DataType::Integer,
+                opts: vec![
+                    ColumnOption::NotNull,
+                    ColumnOption::Check {
+                        expr: Expr::BinaryExpr {
+                            left: Box::new(Expr::Ident(Ident::new_unquoted("b"))),
+                            op: BinaryOperator::Gt,
+                            right: Box::new(Expr::Literal(Literal::Number("0".to_string()))),
+                        },
+                        sql: "b > 0".to_string(),
+                    },
+                    ColumnOption::Null,
+                ],
+            },
+        ];
+        assert_eq!(expected, got.columns);
+    }
+
+    #[test]
+    fn check_requires_parens() {
+        parse_ast::<CreateTable<_>>("create table hello (a int check a > 0)").unwrap_err();
+    }
 }
@@ -22,7 +22,7 @@ pub enum DataType {
     Half,
     /// REAL, FLOAT, FLOAT4
     Real,
-    /// DOUBLE, FLOAT8
+    /// DOUBLE, DOUBLE PRECISION, FLOAT8
     Double,
     /// DECIMAL, DECIMAL(<prec>, <scale>), NUMERIC
     Decimal(Option<i64>, Option<i64>),
@@ -34,6 +34,12 @@ pub enum DataType {
     Timestamp,
     /// INTERVAL
     Interval,
+    /// JSON, JSONB
+    Json,
+    /// GEOMETRY
+    Geometry,
+    /// <type>[], <type>[<size>]
+    Array(Box<DataType>, Option<i64>),
 }
 
 impl AstParseable for DataType {
@@ -46,7 +52,7 @@ impl AstParseable for DataType {
             None => return Err(RayexecError::new("Unexpected end of query")),
         };
 
-        Ok(match kw {
+        let mut datatype = match kw {
             Keyword::VARCHAR => DataType::Varchar(None), // TODO: With length.
             Keyword::TEXT | Keyword::STRING => DataType::Varchar(None),
             Keyword::TINYINT | Keyword::INT1 => DataType::TinyInt,
@@ -55,7 +61,12 @@ impl AstParseable for DataType {
             Keyword::BIGINT | Keyword::INT8 => DataType::BigInt,
             Keyword::HALF | Keyword::FLOAT2 => DataType::Half,
             Keyword::REAL | Keyword::FLOAT | Keyword::FLOAT4 => DataType::Real,
-            Keyword::DOUBLE | Keyword::FLOAT8 => DataType::Double,
+            Keyword::DOUBLE => {
+                // Optional 'PRECISION' following 'DOUBLE' (SQL standard).
+                let _ = parser.parse_keyword(Keyword::PRECISION);
+                DataType::Double
+            }
+            Keyword::FLOAT8 => DataType::Double,
             Keyword::DECIMAL | Keyword::NUMERIC => {
                 let (prec, scale) = Self::parse_precision_scale(parser)?;
                 DataType::Decimal(prec, scale)
@@ -64,12 +75,27 @@ impl AstParseable for DataType {
             Keyword::DATE => DataType::Date,
             Keyword::TIMESTAMP => DataType::Timestamp,
             Keyword::INTERVAL => DataType::Interval,
+            Keyword::JSON | Keyword::JSONB => DataType::Json,
+            Keyword::GEOMETRY => DataType::Geometry,
             other => {
                 return Err(RayexecError::new(format!(
                     "Unexpected keyword for data type: {other:?}",
                 )))
             }
-        })
+        };
+
+        while parser.consume_token(&Token::LeftBracket) {
+            let size = if parser.consume_token(&Token::RightBracket) {
+                None
+            } else {
+                let size = Expr::parse_i64_literal(parser)?;
+                parser.expect_token(&Token::RightBracket)?;
+                Some(size)
+            };
+            datatype = DataType::Array(Box::new(datatype), size);
+        }
+
+        Ok(datatype)
     }
 }
 
@@ -123,6 +149,7 @@ mod tests {
         assert_ast_eq(DataType::Real, "float4");
 
         assert_ast_eq(DataType::Double, "double");
+        assert_ast_eq(DataType::Double, "double precision");
         assert_ast_eq(DataType::Double, "float8");
 
         assert_ast_eq(DataType::Bool, "bool");
@@ -133,6 +160,11 @@ mod tests {
         assert_ast_eq(DataType::Timestamp, "TIMESTAMP");
 
         assert_ast_eq(DataType::Interval, "INTERVAL");
+
+        assert_ast_eq(DataType::Json, "json");
+        assert_ast_eq(DataType::Json, "jsonb");
+
+        assert_ast_eq(DataType::Geometry, "geometry");
     }
 
     #[test]
@@ -144,4 +176,22 @@ mod tests {
 
         assert_ast_eq(DataType::Decimal(Some(4), Some(1)), "numeric(4, 1)");
     }
+
+    #[test]
+    fn array() {
+        assert_ast_eq(DataType::Array(Box::new(DataType::Integer), None), "int[]");
+        assert_ast_eq(
+            DataType::Array(Box::new(DataType::Real), Some(3)),
+            "float[3]",
+        );
+        assert_ast_eq(
+            DataType::Array(
+                Box::new(DataType::Array(Box::new(DataType::Double), Some(2))),
+                None,
+            ),
+            "double[2][]",
+        );
+
+        parse_ast::<DataType>("int[3").unwrap_err();
+    }
 }
@@ -13,6 +13,7 @@ pub enum DropType {
     Table,
     View,
     Schema,
+    Secret,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -40,6 +41,7 @@ impl AstParseable for DropStatement<Raw> {
             Keyword::FUNCTION => DropType::Function,
             Keyword::SCHEMA => DropType::Schema,
             Keyword::VIEW => DropType::View,
+            Keyword::SECRET => DropType::Secret,
             other => {
                 return Err(RayexecError::new(format!(
                     "Got unexpected keyword for drop type: {other}"
@@ -95,4 +97,16 @@ mod tests {
         };
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn drop_secret_if_exists() {
+        let got = parse_ast::<DropStatement<_>>("drop secret if exists my_secret").unwrap();
+        let expected = DropStatement {
+            drop_type: DropType::Secret,
+            if_exists: true,
+            name: ObjectReference::from_strings(["my_secret"]),
+            deps: None,
+        };
+        assert_eq!(expected, got);
+    }
 }
@@ -18,6 +18,10 @@ pub enum ExplainOutput {
 pub struct ExplainNode<T: AstMeta> {
     pub analyze: bool,
     pub verbose: bool,
+    /// Include the bind context in the output.
+    pub bindings: bool,
+    /// Include the plan after each optimizer rule in the output.
+    pub optimized_steps: bool,
     pub body: ExplainBody<T>,
     pub output: Option<ExplainOutput>,
 }
@@ -31,24 +35,39 @@ impl AstParseable for ExplainNode<Raw> {
     fn parse(parser: &mut Parser) -> Result<Self> {
         parser.expect_keyword(Keyword::EXPLAIN)?;
 
-        let analyze = parser.parse_keyword(Keyword::ANALYZE);
-        let verbose = parser.parse_keyword(Keyword::VERBOSE);
-
-        let output = if parser.consume_token(&Token::LeftParen) {
-            // Just FORMAT for now.
-            parser.expect_keyword(Keyword::FORMAT)?;
-            let format = if parser.parse_keyword(Keyword::JSON) {
-                ExplainOutput::Json
-            } else if parser.parse_keyword(Keyword::TEXT) {
-                ExplainOutput::Text
-            } else {
-                return Err(RayexecError::new("Expect JSON or TEXT for explain format"));
-            };
+        let mut analyze = parser.parse_keyword(Keyword::ANALYZE);
+        let mut verbose = parser.parse_keyword(Keyword::VERBOSE);
+        let mut bindings = false;
+        let mut optimized_steps = false;
+        let mut output = None;
+
+        if parser.consume_token(&Token::LeftParen) {
+            parser.parse_comma_separated(|parser| {
+                if parser.parse_keyword(Keyword::ANALYZE) {
+                    analyze = true;
+                } else if parser.parse_keyword(Keyword::VERBOSE) {
+                    verbose = true;
+                } else if parser.parse_keyword(Keyword::BINDINGS) {
+                    bindings = true;
+                } else if parser.parse_keyword(Keyword::OPTIMIZED_STEPS) {
+                    optimized_steps = true;
+                } else if parser.parse_keyword(Keyword::FORMAT) {
+                    output = if parser.parse_keyword(Keyword::JSON) {
+                        Some(ExplainOutput::Json)
+                    } else if parser.parse_keyword(Keyword::TEXT) {
+                        Some(ExplainOutput::Text)
+                    } else {
+                        return Err(RayexecError::new("Expect JSON or TEXT for explain format"));
+                    };
+                } else {
+                    return Err(RayexecError::new(
+                        "Expected ANALYZE, VERBOSE, BINDINGS, OPTIMIZED_STEPS, or FORMAT for explain option",
+                    ));
+                }
+                Ok(())
+            })?;
             parser.expect_token(&Token::RightParen)?;
-            Some(format)
-        } else {
-            None
-        };
+        }
 
         let body = match parser.parse_statement()? {
             Statement::Query(query) => ExplainBody::Query(query),
@@ -62,6 +81,8 @@ impl AstParseable for ExplainNode<Raw> {
         Ok(ExplainNode {
             analyze,
             verbose,
+            bindings,
+            optimized_steps,
             body,
             output,
         })
@@ -100,6 +121,8 @@ mod tests {
         let expected = ExplainNode {
             analyze: false,
             verbose: false,
+            bindings: false,
+            optimized_steps: false,
             body: ExplainBody::Query(query_node_select_1()),
             output: None,
         };
@@ -112,6 +135,8 @@ mod tests {
         let expected = ExplainNode {
             analyze: false,
             verbose: false,
+            bindings: false,
+            optimized_steps: false,
             body: ExplainBody::Query(query_node_select_1()),
             output: Some(ExplainOutput::Json),
         };
@@ -124,6 +149,8 @@ mod tests {
         let expected = ExplainNode {
             analyze: false,
             verbose: false,
+            bindings: false,
+            optimized_steps: false,
             body: ExplainBody::Query(query_node_select_1()),
             output: Some(ExplainOutput::Text),
         };
@@ -141,6 +168,8 @@ mod tests {
         let expected = ExplainNode {
             analyze: true,
             verbose: false,
+            bindings: false,
+            optimized_steps: false,
             body: ExplainBody::Query(query_node_select_1()),
             output: None,
         };
@@ -153,6 +182,8 @@ mod tests {
         let expected = ExplainNode {
             analyze: false,
             verbose: true,
+            bindings: false,
+            optimized_steps: false,
             body: ExplainBody::Query(query_node_select_1()),
             output: None,
         };
@@ -165,6 +196,8 @@ mod tests {
         let expected = ExplainNode {
             analyze: true,
             verbose: true,
+            bindings: false,
+            optimized_steps: false,
             body: ExplainBody::Query(query_node_select_1()),
             output: None,
         };
@@ -175,4 +208,53 @@ mod tests {
     fn verbose_analyze() {
         let _ = parse_ast::<ExplainNode<_>>("explain verbose analyze select 1").unwrap_err();
     }
+
+    #[test]
+    fn options_list() {
+        let explain: ExplainNode<_> =
+            parse_ast("explain (verbose, bindings, format json) select 1").unwrap();
+        let expected = ExplainNode {
+            analyze: false,
+            verbose: true,
+            bindings: true,
+            optimized_steps: false,
+            body: ExplainBody::Query(query_node_select_1()),
+            output: Some(ExplainOutput::Json),
+        };
+        assert_eq!(expected, explain)
+    }
+
+    #[test]
+    fn bindings_only() {
+        let explain: ExplainNode<_> = parse_ast("explain (bindings) select 1").unwrap();
+        let expected = ExplainNode {
+            analyze: false,
+            verbose: false,
+            bindings: true,
+            optimized_steps: false,
+            body: ExplainBody::Query(query_node_select_1()),
+            output: None,
+        };
+        assert_eq!(expected, explain)
+    }
+
+    #[test]
+    fn optimized_steps() {
+        let explain: ExplainNode<_> =
+            parse_ast("explain (optimized_steps, verbose) select 1").unwrap();
+        let expected = ExplainNode {
+            analyze: false,
+            verbose: true,
+            bindings: false,
+            optimized_steps: true,
+            body: ExplainBody::Query(query_node_select_1()),
+            output: None,
+        };
+        assert_eq!(expected, explain)
+    }
+
+    #[test]
+    fn option_unknown() {
+        let _ = parse_ast::<ExplainNode<_>>("explain (bindings, costs) select 1").unwrap_err();
+    }
 }
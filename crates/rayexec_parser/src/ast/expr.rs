@@ -1,7 +1,7 @@
 use std::ops::Neg;
 use std::str::FromStr;
 
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{RayexecError, Result, Span};
 use serde::{Deserialize, Serialize};
 
 use super::{
@@ -9,6 +9,7 @@ use super::{
     DataType,
     Ident,
     ObjectReference,
+    OrderByNode,
     QueryNode,
     WindowDefinition,
     WindowSpec,
@@ -46,6 +47,12 @@ pub enum BinaryOperator {
     StringConcat,
     /// String starts with operator, e.g. `a ^@ b`
     StringStartsWith,
+    /// JSON extract operator, e.g. `a -> b`
+    JsonExtract,
+    /// JSON extract as text operator, e.g. `a ->> b`
+    JsonExtractText,
+    /// Full-text match, e.g. `a MATCH b`
+    Match,
     /// Greater than, e.g. `a > b`
     Gt,
     /// Less than, e.g. `a < b`
@@ -102,6 +109,14 @@ pub struct Function<T: AstMeta> {
     pub distinct: bool,
     /// Arguments to the function.
     pub args: Vec<FunctionArg<T>>,
+    /// Order in which arguments should be provided to an aggregate.
+    ///
+    /// E.g. `first(col1 ORDER BY col2)`
+    pub order_by: Vec<OrderByNode<T>>,
+    /// Ordering for ordered-set aggregates.
+    ///
+    /// E.g. `percentile_cont(0.5) WITHIN GROUP (ORDER BY col)`
+    pub within_group: Option<Vec<OrderByNode<T>>>,
     /// Filter part of `COUNT(col) FILTER (WHERE col > 5)`
     pub filter: Option<Box<Expr<T>>>,
     /// Option OVER clause indicating this is a window function.
@@ -336,7 +351,7 @@ impl Expr<Raw> {
     const _PREC_EXPONENTIATION: u8 = 100;
     const PREC_UNARY_MINUS: u8 = 105;
     const _PREC_AT: u8 = 110; // AT TIME ZONE
-    const _PREC_COLLATE: u8 = 120;
+    const PREC_COLLATE: u8 = 120;
     const PREC_ARRAY_ELEM: u8 = 130; // []
     const PREC_CAST: u8 = 140; // ::
 
@@ -384,6 +399,7 @@ impl Expr<Raw> {
                 ))
             }
         };
+        let span = tok.span();
 
         let expr = match &tok.token {
             Token::Word(w) => match w.keyword {
@@ -508,18 +524,29 @@ impl Expr<Raw> {
                         // assume ident otherwise?
                         parser.expect_token(&Token::LeftParen)?;
 
-                        // TODO: Other COLUMNS exprs
-                        let pattern = Expr::parse_string_literal(parser)?;
-                        let columns_expr = ColumnsExpr::Pattern(pattern);
+                        let columns_expr = if parser.consume_token(&Token::Mul) {
+                            let exclude_cols = if parser
+                                .parse_one_of_keywords(&[Keyword::EXCEPT, Keyword::EXCLUDE])
+                                .is_some()
+                            {
+                                parser.parse_parenthesized_comma_separated(Ident::parse)?
+                            } else {
+                                Vec::new()
+                            };
+                            ColumnsExpr::Wildcard { exclude_cols }
+                        } else {
+                            let pattern = Expr::parse_string_literal(parser)?;
+                            ColumnsExpr::Pattern(pattern)
+                        };
 
                         parser.expect_token(&Token::RightParen)?;
 
                         Expr::Columns(columns_expr)
                     }
 
-                    _ => Self::parse_ident_expr(w.clone(), parser)?,
+                    _ => Self::parse_ident_expr(w.clone(), span, parser)?,
                 },
-                None => Self::parse_ident_expr(w.clone(), parser)?,
+                None => Self::parse_ident_expr(w.clone(), span, parser)?,
             },
             Token::LeftBracket => {
                 if parser.consume_token(&Token::RightBracket) {
@@ -591,9 +618,12 @@ impl Expr<Raw> {
             Token::Mod => Some(BinaryOperator::Modulo),
             Token::Concat => Some(BinaryOperator::StringConcat),
             Token::CaretAt => Some(BinaryOperator::StringStartsWith),
+            Token::Arrow => Some(BinaryOperator::JsonExtract),
+            Token::LongArrow => Some(BinaryOperator::JsonExtractText),
             Token::Word(w) => match w.keyword {
                 Some(Keyword::AND) => Some(BinaryOperator::And),
                 Some(Keyword::OR) => Some(BinaryOperator::Or),
+                Some(Keyword::MATCH) => Some(BinaryOperator::Match),
                 _ => None,
             },
             _ => None,
@@ -756,6 +786,10 @@ impl Expr<Raw> {
                         high: Box::new(high),
                     })
                 }
+                Keyword::COLLATE => Ok(Expr::Collate {
+                    expr: Box::new(prefix),
+                    collation: ObjectReference::parse(parser)?,
+                }),
                 other => {
                     return Err(RayexecError::new(format!(
                         "Unexpected keyword in infix expression: {other}"
@@ -842,6 +876,7 @@ impl Expr<Raw> {
             Token::Word(w) if w.keyword == Some(Keyword::RLIKE) => Ok(Self::PREC_CONTAINMENT),
             Token::Word(w) if w.keyword == Some(Keyword::REGEXP) => Ok(Self::PREC_CONTAINMENT),
             Token::Word(w) if w.keyword == Some(Keyword::SIMILAR) => Ok(Self::PREC_CONTAINMENT),
+            Token::Word(w) if w.keyword == Some(Keyword::MATCH) => Ok(Self::PREC_CONTAINMENT),
 
             // Equalities
             Token::Eq
@@ -859,12 +894,18 @@ impl Expr<Raw> {
             // Cast
             Token::DoubleColon => Ok(Self::PREC_CAST),
 
+            // Collation
+            Token::Word(w) if w.keyword == Some(Keyword::COLLATE) => Ok(Self::PREC_COLLATE),
+
             // Concat
             Token::Concat => Ok(Self::PREC_EVERYTHING_ELSE),
 
             // Starts with
             Token::CaretAt => Ok(Self::PREC_EVERYTHING_ELSE),
 
+            // JSON extract
+            Token::Arrow | Token::LongArrow => Ok(Self::PREC_EVERYTHING_ELSE),
+
             // Array, struct literals
             Token::LeftBrace | Token::LeftBracket => Ok(Self::PREC_ARRAY_ELEM),
 
@@ -874,15 +915,15 @@ impl Expr<Raw> {
 
     /// Handle parsing expressions containing identifiers, starting with a word
     /// that is known to already be part of an identifier.
-    fn parse_ident_expr(w: Word, parser: &mut Parser) -> Result<Expr<Raw>> {
+    fn parse_ident_expr(w: Word, span: Span, parser: &mut Parser) -> Result<Expr<Raw>> {
         let mut wildcard = false;
-        let mut idents = vec![Ident::from(w)];
+        let mut idents = vec![Ident::from(w).with_span(span)];
 
         // Possibly compound identifier.
         while parser.consume_token(&Token::Period) {
             match parser.next() {
                 Some(tok) => match &tok.token {
-                    Token::Word(w) => idents.push(w.clone().into()),
+                    Token::Word(w) => idents.push(Ident::from(w.clone()).with_span(tok.span())),
                     Token::Mul => wildcard = true,
                     other => {
                         return Err(RayexecError::new(format!(
@@ -904,12 +945,31 @@ impl Expr<Raw> {
                 return Err(RayexecError::new("Cannot have wildcard function call"));
             }
 
-            let args = if parser.consume_token(&Token::RightParen) {
-                Vec::new()
+            let (args, order_by) = if parser.consume_token(&Token::RightParen) {
+                (Vec::new(), Vec::new())
             } else {
                 let args = parser.parse_comma_separated(FunctionArg::parse)?;
+                // <args> ORDER BY <expr>, ...
+                let order_by = if parser.parse_keyword_sequence(&[Keyword::ORDER, Keyword::BY]) {
+                    parser.parse_comma_separated(OrderByNode::parse)?
+                } else {
+                    Vec::new()
+                };
                 parser.expect_token(&Token::RightParen)?;
-                args
+                (args, order_by)
+            };
+
+            // WITHIN GROUP (ORDER BY <expr>, ...)
+            let within_group = if parser.parse_keyword_sequence(&[Keyword::WITHIN, Keyword::GROUP])
+            {
+                parser.expect_token(&Token::LeftParen)?;
+                parser.expect_keyword(Keyword::ORDER)?;
+                parser.expect_keyword(Keyword::BY)?;
+                let order_by = parser.parse_comma_separated(OrderByNode::parse)?;
+                parser.expect_token(&Token::RightParen)?;
+                Some(order_by)
+            } else {
+                None
             };
 
             // FILTER (WHERE <expr>)
@@ -945,6 +1005,8 @@ impl Expr<Raw> {
                 reference: ObjectReference(idents),
                 distinct,
                 args,
+                order_by,
+                within_group,
                 filter,
                 over,
             })))
@@ -1014,6 +1076,10 @@ impl Expr<Raw> {
 pub enum ColumnsExpr {
     /// `COLUMNS('regex_pattern')`
     Pattern(String),
+    /// `COLUMNS(*)`
+    ///
+    /// `COLUMNS(* EXCLUDE (col1, col2, ...))`
+    Wildcard { exclude_cols: Vec<Ident> },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -1337,6 +1403,8 @@ mod tests {
             args: vec![FunctionArg::Unnamed {
                 arg: FunctionArgExpr::Expr(Expr::Ident(Ident::new_unquoted("my_col"))),
             }],
+            order_by: Vec::new(),
+            within_group: None,
             filter: None,
             over: None,
         }));
@@ -1350,6 +1418,8 @@ mod tests {
             reference: ObjectReference(vec![Ident::new_unquoted("random")]),
             distinct: false,
             args: Vec::new(),
+            order_by: Vec::new(),
+            within_group: None,
             filter: None,
             over: None,
         }));
@@ -1365,6 +1435,8 @@ mod tests {
             args: vec![FunctionArg::Unnamed {
                 arg: FunctionArgExpr::Expr(Expr::Ident(Ident::new_unquoted("x"))),
             }],
+            order_by: Vec::new(),
+            within_group: None,
             filter: Some(Box::new(Expr::BinaryExpr {
                 left: Box::new(Expr::Ident(Ident::new_unquoted("x"))),
                 op: BinaryOperator::Gt,
@@ -1384,6 +1456,51 @@ mod tests {
             args: vec![FunctionArg::Unnamed {
                 arg: FunctionArgExpr::Expr(Expr::Ident(Ident::new_unquoted("x"))),
             }],
+            order_by: Vec::new(),
+            within_group: None,
+            filter: None,
+            over: None,
+        }));
+        assert_eq!(expected, expr);
+    }
+
+    #[test]
+    fn function_call_order_by() {
+        let expr: Expr<_> = parse_ast("first(x order by y)").unwrap();
+        let expected = Expr::Function(Box::new(Function {
+            reference: ObjectReference(vec![Ident::new_unquoted("first")]),
+            distinct: false,
+            args: vec![FunctionArg::Unnamed {
+                arg: FunctionArgExpr::Expr(Expr::Ident(Ident::new_unquoted("x"))),
+            }],
+            order_by: vec![OrderByNode {
+                typ: None,
+                nulls: None,
+                expr: Expr::Ident(Ident::new_unquoted("y")),
+            }],
+            within_group: None,
+            filter: None,
+            over: None,
+        }));
+        assert_eq!(expected, expr);
+    }
+
+    #[test]
+    fn function_call_within_group() {
+        let expr: Expr<_> =
+            parse_ast("percentile_cont(0.5) within group (order by x desc)").unwrap();
+        let expected = Expr::Function(Box::new(Function {
+            reference: ObjectReference(vec![Ident::new_unquoted("percentile_cont")]),
+            distinct: false,
+            args: vec![FunctionArg::Unnamed {
+                arg: FunctionArgExpr::Expr(Expr::Literal(Literal::Number("0.5".to_string()))),
+            }],
+            order_by: Vec::new(),
+            within_group: Some(vec![OrderByNode {
+                typ: Some(OrderByType::Desc),
+                nulls: None,
+                expr: Expr::Ident(Ident::new_unquoted("x")),
+            }]),
             filter: None,
             over: None,
         }));
@@ -1398,6 +1515,8 @@ mod tests {
             reference: ObjectReference(vec![Ident::new_unquoted("rank")]),
             distinct: false,
             args: Vec::new(),
+            order_by: Vec::new(),
+            within_group: None,
             filter: None,
             over: Some(WindowSpec::Definition(WindowDefinition {
                 existing: None,
@@ -1427,6 +1546,8 @@ mod tests {
             reference: ObjectReference(vec![Ident::new_unquoted("rank")]),
             distinct: false,
             args: Vec::new(),
+            order_by: Vec::new(),
+            within_group: None,
             filter: None,
             // Note that this should be Some but everything empty. We need to
             // differentiate between and empty OVER and missing OVER.
@@ -1460,6 +1581,8 @@ mod tests {
             args: vec![FunctionArg::Unnamed {
                 arg: FunctionArgExpr::Wildcard,
             }],
+            order_by: Vec::new(),
+            within_group: None,
             filter: None,
             over: None,
         }));
@@ -1478,6 +1601,8 @@ mod tests {
                 args: vec![FunctionArg::Unnamed {
                     arg: FunctionArgExpr::Wildcard,
                 }],
+                order_by: Vec::new(),
+                within_group: None,
                 filter: None,
                 over: None,
             }))),
@@ -1495,6 +1620,8 @@ mod tests {
                 args: vec![FunctionArg::Unnamed {
                     arg: FunctionArgExpr::Wildcard,
                 }],
+                order_by: Vec::new(),
+                within_group: None,
                 filter: None,
                 over: None,
             }))),
@@ -1524,6 +1651,20 @@ mod tests {
         assert_eq!(expected, expr);
     }
 
+    #[test]
+    fn collate() {
+        let expr: Expr<_> = parse_ast("a || b COLLATE nocase").unwrap();
+        let expected = Expr::BinaryExpr {
+            left: Box::new(Expr::Ident(Ident::new_unquoted("a"))),
+            op: BinaryOperator::StringConcat,
+            right: Box::new(Expr::Collate {
+                expr: Box::new(Expr::Ident(Ident::new_unquoted("b"))),
+                collation: ObjectReference::from_strings(["nocase"]),
+            }),
+        };
+        assert_eq!(expected, expr);
+    }
+
     #[test]
     fn cast_function() {
         let expr: Expr<_> = parse_ast("CAST('4.0' AS REAL)").unwrap();
@@ -1700,6 +1841,42 @@ mod tests {
         assert_eq!(expected, expr);
     }
 
+    #[test]
+    fn match_operator() {
+        let expr: Expr<_> = parse_ast("body MATCH 'fox' AND id > 1").unwrap();
+        let expected = Expr::BinaryExpr {
+            left: Box::new(Expr::BinaryExpr {
+                left: Box::new(Expr::Ident(Ident::new_unquoted("body"))),
+                op: BinaryOperator::Match,
+                right: Box::new(Expr::Literal(Literal::SingleQuotedString(
+                    "fox".to_string(),
+                ))),
+            }),
+            op: BinaryOperator::And,
+            right: Box::new(Expr::BinaryExpr {
+                left: Box::new(Expr::Ident(Ident::new_unquoted("id"))),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::Literal(Literal::Number("1".to_string()))),
+            }),
+        };
+        assert_eq!(expected, expr);
+    }
+
+    #[test]
+    fn json_extract_operators() {
+        let expr: Expr<_> = parse_ast("j -> 'a' ->> 0").unwrap();
+        let expected = Expr::BinaryExpr {
+            left: Box::new(Expr::BinaryExpr {
+                left: Box::new(Expr::Ident(Ident::new_unquoted("j"))),
+                op: BinaryOperator::JsonExtract,
+                right: Box::new(Expr::Literal(Literal::SingleQuotedString("a".to_string()))),
+            }),
+            op: BinaryOperator::JsonExtractText,
+            right: Box::new(Expr::Literal(Literal::Number("0".to_string()))),
+        };
+        assert_eq!(expected, expr);
+    }
+
     #[test]
     fn between() {
         let expr: Expr<_> = parse_ast("col BETWEEN a AND b").unwrap();
@@ -1782,4 +1959,39 @@ mod tests {
         };
         assert_eq!(expected, expr);
     }
+
+    #[test]
+    fn columns_pattern() {
+        let expr: Expr<_> = parse_ast("COLUMNS('col_.*')").unwrap();
+        let expected = Expr::Columns(ColumnsExpr::Pattern("col_.*".to_string()));
+        assert_eq!(expected, expr);
+    }
+
+    #[test]
+    fn columns_wildcard_exclude() {
+        let expr: Expr<_> = parse_ast("COLUMNS(* EXCLUDE (a, b))").unwrap();
+        let expected = Expr::Columns(ColumnsExpr::Wildcard {
+            exclude_cols: vec![Ident::new_unquoted("a"), Ident::new_unquoted("b")],
+        });
+        assert_eq!(expected, expr);
+    }
+
+    #[test]
+    fn columns_in_function() {
+        let expr: Expr<_> = parse_ast("min(COLUMNS(*))").unwrap();
+        let expected = Expr::Function(Box::new(Function {
+            reference: ObjectReference(vec![Ident::new_unquoted("min")]),
+            distinct: false,
+            args: vec![FunctionArg::Unnamed {
+                arg: FunctionArgExpr::Expr(Expr::Columns(ColumnsExpr::Wildcard {
+                    exclude_cols: Vec::new(),
+                })),
+            }],
+            order_by: Vec::new(),
+            within_group: None,
+            filter: None,
+            over: None,
+        }));
+        assert_eq!(expected, expr);
+    }
 }
@@ -21,6 +21,7 @@ use crate::tokens::{Token, TokenWithLocation};
 pub struct FromNode<T: AstMeta> {
     pub alias: Option<FromAlias>,
     pub body: FromNodeBody<T>,
+    pub sample: Option<TableSample>,
 }
 
 impl AstParseable for FromNode<Raw> {
@@ -45,6 +46,7 @@ impl AstParseable for FromNode<Raw> {
                         join_type: JoinType::Inner,
                         join_condition: JoinCondition::None,
                     }),
+                    sample: None,
                 }
             } else if parser.consume_token(&Token::Comma) {
                 // <left>, <right>
@@ -58,6 +60,7 @@ impl AstParseable for FromNode<Raw> {
                         join_type: JoinType::Inner,
                         join_condition: JoinCondition::None,
                     }),
+                    sample: None,
                 }
             } else {
                 // Optional NATURAL prefixing the join type.
@@ -186,6 +189,7 @@ impl AstParseable for FromNode<Raw> {
                         join_type,
                         join_condition,
                     }),
+                    sample: None,
                 };
             }
         }
@@ -205,6 +209,8 @@ impl FromNode<Raw> {
             let subquery = QueryNode::parse(parser)?;
             parser.expect_token(&Token::RightParen)?;
             let alias = Self::maybe_parse_alias(parser)?;
+            let sample = Self::maybe_parse_sample(parser)?;
+
             Ok(FromNode {
                 alias,
                 body: FromNodeBody::Subquery(FromSubquery {
@@ -212,12 +218,14 @@ impl FromNode<Raw> {
                     options: (),
                     query: subquery,
                 }),
+                sample,
             })
         } else if parser.parse_keyword(Keyword::VALUES) {
             // Allow `SELECT * FROM VALUES ...` as a convenience (don't require
             // parenthesis).
             let values = Values::parse(parser)?;
             let alias = Self::maybe_parse_alias(parser)?;
+            let sample = Self::maybe_parse_sample(parser)?;
 
             Ok(FromNode {
                 alias,
@@ -234,6 +242,7 @@ impl FromNode<Raw> {
                         },
                     },
                 }),
+                sample,
             })
         } else {
             if let Some(tok) = parser.peek().cloned() {
@@ -242,11 +251,14 @@ impl FromNode<Raw> {
                     let _ = parser.next();
 
                     let alias = Self::maybe_parse_alias(parser)?;
+                    let sample = Self::maybe_parse_sample(parser)?;
+
                     return Ok(FromNode {
                         alias,
                         body: FromNodeBody::File(FromFilePath {
                             path: s.to_string(),
                         }),
+                        sample,
                     });
                 }
             }
@@ -273,7 +285,13 @@ impl FromNode<Raw> {
             };
 
             let alias = Self::maybe_parse_alias(parser)?;
-            Ok(FromNode { alias, body })
+            let sample = Self::maybe_parse_sample(parser)?;
+
+            Ok(FromNode {
+                alias,
+                body,
+                sample,
+            })
         }
     }
 
@@ -292,6 +310,77 @@ impl FromNode<Raw> {
 
         Ok(Some(FromAlias { alias, columns }))
     }
+
+    /// Parse an optional sampling clause following the table alias.
+    ///
+    /// `TABLESAMPLE SYSTEM|BERNOULLI (<n> [PERCENT|%]) [REPEATABLE (<seed>)]`
+    fn maybe_parse_sample(parser: &mut Parser) -> Result<Option<TableSample>> {
+        if !parser.parse_keyword(Keyword::TABLESAMPLE) {
+            return Ok(None);
+        }
+
+        let method = match parser.parse_one_of_keywords(&[Keyword::SYSTEM, Keyword::BERNOULLI]) {
+            Some(Keyword::SYSTEM) => TableSampleMethod::System,
+            Some(Keyword::BERNOULLI) => TableSampleMethod::Bernoulli,
+            _ => {
+                return Err(RayexecError::new(
+                    "Expected SYSTEM or BERNOULLI for TABLESAMPLE method",
+                ))
+            }
+        };
+
+        parser.expect_token(&Token::LeftParen)?;
+        let percentage = match parser.next() {
+            Some(TokenWithLocation {
+                token: Token::Number(s),
+                ..
+            }) => s.parse::<f64>().map_err(|_| {
+                RayexecError::new(format!("Unable to parse '{s}' as a sample percentage"))
+            })?,
+            other => {
+                return Err(RayexecError::new(format!(
+                    "Expected sample percentage, got {:?}",
+                    other.map(|t| &t.token)
+                )))
+            }
+        };
+        if !parser.consume_token(&Token::Mod) {
+            parser.parse_keyword(Keyword::PERCENT); // Optional PERCENT
+        }
+        parser.expect_token(&Token::RightParen)?;
+
+        let seed = if parser.parse_keyword(Keyword::REPEATABLE) {
+            parser.expect_token(&Token::LeftParen)?;
+            let seed = Expr::parse_i64_literal(parser)?;
+            parser.expect_token(&Token::RightParen)?;
+            Some(seed as u64)
+        } else {
+            None
+        };
+
+        Ok(Some(TableSample {
+            method,
+            percentage,
+            seed,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableSampleMethod {
+    /// Sample blocks of rows (e.g. row groups or batches).
+    System,
+    /// Sample individual rows.
+    Bernoulli,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableSample {
+    pub method: TableSampleMethod,
+    /// Percentage of the input to keep, 0 to 100.
+    pub percentage: f64,
+    /// Optional seed for REPEATABLE sampling.
+    pub seed: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -378,8 +467,10 @@ mod tests {
                 reference: ObjectReference(vec![Ident {
                     value: "my_table".into(),
                     quoted: false,
+                    span: None,
                 }]),
             }),
+            sample: None,
         };
         assert_eq!(expected, node)
     }
@@ -394,8 +485,10 @@ mod tests {
                 reference: ObjectReference(vec![Ident {
                     value: "my_table".into(),
                     quoted: false,
+                    span: None,
                 }]),
             }),
+            sample: None,
         };
         assert_eq!(expected, node)
     }
@@ -413,8 +506,10 @@ mod tests {
                 reference: ObjectReference(vec![Ident {
                     value: "my_table".into(),
                     quoted: false,
+                    span: None,
                 }]),
             }),
+            sample: None,
         };
         assert_eq!(expected, node)
     }
@@ -427,6 +522,7 @@ mod tests {
                 alias: Ident {
                     value: "t1".into(),
                     quoted: false,
+                    span: None,
                 },
                 columns: None,
             }),
@@ -434,8 +530,10 @@ mod tests {
                 reference: ObjectReference(vec![Ident {
                     value: "my_table".into(),
                     quoted: false,
+                    span: None,
                 }]),
             }),
+            sample: None,
         };
         assert_eq!(expected, node)
     }
@@ -448,12 +546,14 @@ mod tests {
                 alias: Ident {
                     value: "t1".into(),
                     quoted: false,
+                    span: None,
                 },
                 columns: None,
             }),
             body: FromNodeBody::File(FromFilePath {
                 path: "dir/file.parquet".to_string(),
             }),
+            sample: None,
         };
         assert_eq!(expected, node)
     }
@@ -466,19 +566,23 @@ mod tests {
                 alias: Ident {
                     value: "t1".into(),
                     quoted: false,
+                    span: None,
                 },
                 columns: Some(vec![
                     Ident {
                         value: "c1".into(),
                         quoted: false,
+                        span: None,
                     },
                     Ident {
                         value: "c2".into(),
                         quoted: false,
+                        span: None,
                     },
                     Ident {
                         value: "c3".into(),
                         quoted: false,
+                        span: None,
                     },
                 ]),
             }),
@@ -486,8 +590,10 @@ mod tests {
                 reference: ObjectReference(vec![Ident {
                     value: "my_table".into(),
                     quoted: false,
+                    span: None,
                 }]),
             }),
+            sample: None,
         };
         assert_eq!(expected, node)
     }
@@ -502,9 +608,11 @@ mod tests {
                 reference: ObjectReference(vec![Ident {
                     value: "my_table_func".into(),
                     quoted: false,
+                    span: None,
                 }]),
                 args: Vec::new(),
             }),
+            sample: None,
         };
         assert_eq!(expected, node)
     }
@@ -519,6 +627,7 @@ mod tests {
                 reference: ObjectReference(vec![Ident {
                     value: "my_table_func".into(),
                     quoted: false,
+                    span: None,
                 }]),
                 args: vec![
                     FunctionArg::Unnamed {
@@ -530,6 +639,7 @@ mod tests {
                         name: Ident {
                             value: "kw".into(),
                             quoted: false,
+                            span: None,
                         },
                         arg: FunctionArgExpr::Expr(Expr::Literal(Literal::SingleQuotedString(
                             "arg2".to_string(),
@@ -537,6 +647,7 @@ mod tests {
                     },
                 ],
             }),
+            sample: None,
         };
         assert_eq!(expected, node)
     }
@@ -552,12 +663,14 @@ mod tests {
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["table1"]),
                     }),
+                    sample: None,
                 }),
                 right: Box::new(FromNode {
                     alias: None,
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["table2"]),
                     }),
+                    sample: None,
                 }),
                 join_type: JoinType::Inner,
                 join_condition: JoinCondition::On(Expr::BinaryExpr {
@@ -566,6 +679,7 @@ mod tests {
                     right: Box::new(Expr::Ident(Ident::new_unquoted("c2"))),
                 }),
             }),
+            sample: None,
         };
         assert_eq!(expected, node);
     }
@@ -581,12 +695,14 @@ mod tests {
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["table1"]),
                     }),
+                    sample: None,
                 }),
                 right: Box::new(FromNode {
                     alias: None,
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["table2"]),
                     }),
+                    sample: None,
                 }),
                 join_type: JoinType::Inner,
                 join_condition: JoinCondition::On(Expr::BinaryExpr {
@@ -595,6 +711,7 @@ mod tests {
                     right: Box::new(Expr::Ident(Ident::new_unquoted("c2"))),
                 }),
             }),
+            sample: None,
         };
         assert_eq!(expected, node);
     }
@@ -610,12 +727,14 @@ mod tests {
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["table1"]),
                     }),
+                    sample: None,
                 }),
                 right: Box::new(FromNode {
                     alias: None,
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["table2"]),
                     }),
+                    sample: None,
                 }),
                 join_type: JoinType::Inner,
                 join_condition: JoinCondition::Using(vec![
@@ -624,6 +743,7 @@ mod tests {
                     Ident::new_unquoted("c3"),
                 ]),
             }),
+            sample: None,
         };
         assert_eq!(expected, node);
     }
@@ -639,6 +759,7 @@ mod tests {
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["t1"]),
                     }),
+                    sample: None,
                 }),
                 right: Box::new(FromNode {
                     alias: None,
@@ -648,20 +769,24 @@ mod tests {
                             body: FromNodeBody::BaseTable(FromBaseTable {
                                 reference: ObjectReference::from_strings(["t2"]),
                             }),
+                            sample: None,
                         }),
                         right: Box::new(FromNode {
                             alias: None,
                             body: FromNodeBody::BaseTable(FromBaseTable {
                                 reference: ObjectReference::from_strings(["t3"]),
                             }),
+                            sample: None,
                         }),
                         join_type: JoinType::Right,
                         join_condition: JoinCondition::None,
                     }),
+                    sample: None,
                 }),
                 join_type: JoinType::Left,
                 join_condition: JoinCondition::None,
             }),
+            sample: None,
         };
         assert_eq!(expected, node, "left:\n{expected:#?}\nright:\n{node:#?}");
     }
@@ -677,6 +802,7 @@ mod tests {
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["t1"]),
                     }),
+                    sample: None,
                 }),
                 right: Box::new(FromNode {
                     alias: None,
@@ -690,10 +816,12 @@ mod tests {
                             ])),
                         }],
                     }),
+                    sample: None,
                 }),
                 join_type: JoinType::Left,
                 join_condition: JoinCondition::None,
             }),
+            sample: None,
         };
         assert_eq!(expected, node, "left:\n{expected:#?}\nright:\n{node:#?}");
     }
@@ -709,17 +837,63 @@ mod tests {
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["t1"]),
                     }),
+                    sample: None,
                 }),
                 right: Box::new(FromNode {
                     alias: None,
                     body: FromNodeBody::BaseTable(FromBaseTable {
                         reference: ObjectReference::from_strings(["t2"]),
                     }),
+                    sample: None,
                 }),
                 join_type: JoinType::Inner,
                 join_condition: JoinCondition::Natural,
             }),
+            sample: None,
+        };
+        assert_eq!(expected, node, "left:\n{expected:#?}\nright:\n{node:#?}");
+    }
+
+    #[test]
+    fn table_sample_system() {
+        let node: FromNode<_> = parse_ast("my_table t TABLESAMPLE SYSTEM (10 PERCENT)").unwrap();
+        let expected = FromNode {
+            alias: Some(FromAlias {
+                alias: Ident::new_unquoted("t"),
+                columns: None,
+            }),
+            body: FromNodeBody::BaseTable(FromBaseTable {
+                reference: ObjectReference::from_strings(["my_table"]),
+            }),
+            sample: Some(TableSample {
+                method: TableSampleMethod::System,
+                percentage: 10.0,
+                seed: None,
+            }),
         };
         assert_eq!(expected, node, "left:\n{expected:#?}\nright:\n{node:#?}");
     }
+
+    #[test]
+    fn table_sample_bernoulli_repeatable() {
+        let node: FromNode<_> =
+            parse_ast("my_table TABLESAMPLE BERNOULLI (2.5%) REPEATABLE (42)").unwrap();
+        let expected = FromNode {
+            alias: None,
+            body: FromNodeBody::BaseTable(FromBaseTable {
+                reference: ObjectReference::from_strings(["my_table"]),
+            }),
+            sample: Some(TableSample {
+                method: TableSampleMethod::Bernoulli,
+                percentage: 2.5,
+                seed: Some(42),
+            }),
+        };
+        assert_eq!(expected, node, "left:\n{expected:#?}\nright:\n{node:#?}");
+    }
+
+    #[test]
+    fn table_sample_missing_method() {
+        parse_ast::<FromNode<_>>("my_table TABLESAMPLE (10)").unwrap_err();
+    }
 }
@@ -4,12 +4,24 @@ pub use copy::*;
 pub use show::*;
 pub mod describe;
 pub use describe::*;
+pub mod summarize;
+pub use summarize::*;
 pub mod create_table;
 pub use create_table::*;
 pub mod create_schema;
 pub use create_schema::*;
 pub mod create_view;
 pub use create_view::*;
+pub mod create_index;
+pub use create_index::*;
+pub mod create_policy;
+pub use create_policy::*;
+pub mod create_secret;
+pub use create_secret::*;
+pub mod create_function;
+pub use create_function::*;
+pub mod create_macro;
+pub use create_macro::*;
 pub mod datatype;
 pub use datatype::*;
 pub mod expr;
@@ -33,13 +45,18 @@ pub mod cte;
 pub use cte::*;
 pub mod drop;
 pub use drop::*;
+pub mod transaction;
+pub use transaction::*;
+pub mod alter_table;
+pub use alter_table::*;
 pub mod attach;
 pub mod window;
+use std::cmp::Ordering;
 use std::fmt;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 
 pub use attach::*;
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{RayexecError, Result, Span};
 use serde::{Deserialize, Serialize};
 pub use window::*;
 
@@ -67,10 +84,17 @@ mod testutil {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+/// An identifier.
+///
+/// The span is only used for error reporting, and is ignored when comparing
+/// identifiers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ident {
     pub(crate) value: String,
     pub(crate) quoted: bool,
+    /// Location of the identifier in the sql string, if parsed from one.
+    #[serde(skip)]
+    pub(crate) span: Option<Span>,
 }
 
 impl Ident {
@@ -79,9 +103,21 @@ impl Ident {
         Ident {
             value: s.into(),
             quoted: false,
+            span: None,
         }
     }
 
+    /// Set the location of the identifier in the sql string.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Get the location of the identifier in the sql string.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
     /// Returns the string representation of this ident, taking into account if
     /// it's quoted.
     ///
@@ -100,10 +136,37 @@ impl Ident {
     }
 }
 
+impl PartialEq for Ident {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.quoted == other.quoted
+    }
+}
+
+impl Eq for Ident {}
+
+impl PartialOrd for Ident {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ident {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.value, self.quoted).cmp(&(&other.value, other.quoted))
+    }
+}
+
+impl Hash for Ident {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.quoted.hash(state);
+    }
+}
+
 impl AstParseable for Ident {
     fn parse(parser: &mut Parser) -> Result<Self> {
-        let tok = match parser.next() {
-            Some(tok) => &tok.token,
+        let (tok, span) = match parser.next() {
+            Some(tok) => (&tok.token, tok.span()),
             None => {
                 return Err(RayexecError::new(
                     "Expected identifier, found end of statement",
@@ -112,10 +175,11 @@ impl AstParseable for Ident {
         };
 
         match tok {
-            Token::Word(w) => Ok(w.clone().into()),
+            Token::Word(w) => Ok(Ident::from(w.clone()).with_span(span)),
             other => Err(RayexecError::new(format!(
                 "Unexpected token: {other:?}. Expected an identifier.",
-            ))),
+            ))
+            .with_span(Some(span))),
         }
     }
 }
@@ -125,6 +189,7 @@ impl From<Word> for Ident {
         Ident {
             value: w.value,
             quoted: w.quote == Some('"'),
+            span: None,
         }
     }
 }
@@ -143,6 +208,7 @@ impl ProtoConv for Ident {
         Ok(Self {
             value: proto.value,
             quoted: proto.quoted,
+            span: None,
         })
     }
 }
@@ -166,14 +232,18 @@ impl ObjectReference {
     {
         let mut idents = Vec::new();
         for s in strings {
-            idents.push(Ident {
-                value: s.into(),
-                quoted: false,
-            })
+            idents.push(Ident::new_unquoted(s))
         }
         ObjectReference(idents)
     }
 
+    /// Get the location of the reference in the sql string.
+    pub fn span(&self) -> Option<Span> {
+        let first = self.0.first()?.span()?;
+        let last = self.0.last()?.span()?;
+        Some(first.merge(last))
+    }
+
     pub fn base(&self) -> Result<Ident> {
         match self.0.last() {
             Some(ident) => Ok(ident.clone()),
@@ -187,11 +257,12 @@ impl AstParseable for ObjectReference {
         let mut idents = Vec::new();
         while let Some(tok) = parser.next() {
             let ident = match &tok.token {
-                Token::Word(w) => w.clone().into(),
+                Token::Word(w) => Ident::from(w.clone()).with_span(tok.span()),
                 other => {
                     return Err(RayexecError::new(format!(
                         "Unexpected token: {other:?}. Expected an object reference.",
-                    )))
+                    ))
+                    .with_span(Some(tok.span())))
                 }
             };
             idents.push(ident);
@@ -113,12 +113,14 @@ impl QueryNodeBody<Raw> {
 
             let _ = parser.next();
             let all = parser.parse_keyword(Keyword::ALL);
+            let by_name = parser.parse_keyword_sequence(&[Keyword::BY, Keyword::NAME]);
 
             body = QueryNodeBody::Set(SetOp {
                 left: Box::new(body),
                 right: Box::new(Self::parse_inner(parser, next_precedence)?),
                 operation: op,
                 all,
+                by_name,
             });
         }
 
@@ -132,6 +134,8 @@ pub struct SetOp<T: AstMeta> {
     pub right: Box<QueryNodeBody<T>>,
     pub operation: SetOperation,
     pub all: bool,
+    /// Align columns by name instead of position (`UNION BY NAME`).
+    pub by_name: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -200,4 +204,17 @@ mod tests {
         };
         assert_eq!(expected, values);
     }
+
+    #[test]
+    fn union_all_by_name() {
+        let node: QueryNode<_> = parse_ast("SELECT 1 UNION ALL BY NAME SELECT 2").unwrap();
+        match node.body {
+            QueryNodeBody::Set(setop) => {
+                assert_eq!(SetOperation::Union, setop.operation);
+                assert!(setop.all);
+                assert!(setop.by_name);
+            }
+            other => panic!("unexpected body: {other:?}"),
+        }
+    }
 }
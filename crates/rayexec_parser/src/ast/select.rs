@@ -167,8 +167,8 @@ impl AstParseable for WildcardExpr<Raw> {
     fn parse(parser: &mut Parser) -> Result<Self> {
         let idx = parser.idx; // Needed for resetting the position if this is just an expression.
 
-        let tok = match parser.next() {
-            Some(tok) => &tok.token,
+        let (tok, span) = match parser.next() {
+            Some(tok) => (&tok.token, tok.span()),
             None => {
                 return Err(RayexecError::new(
                     "Expected wild card expression, found end of statement",
@@ -186,32 +186,38 @@ impl AstParseable for WildcardExpr<Raw> {
         // `table.*` or `'table'.*`
         if matches!(tok, Token::Word(_) | Token::SingleQuotedString(_)) {
             let ident = match tok {
-                Token::Word(w) => w.clone().into(),
+                Token::Word(w) => Ident::from(w.clone()),
                 Token::SingleQuotedString(s) => Ident {
                     value: s.clone(),
                     quoted: false,
+                    span: None,
                 },
                 _ => unreachable!("token variants previously matched on"),
-            };
+            }
+            .with_span(span);
 
             if parser.peek().is_some_and(|tok| tok.token == Token::Period) {
                 let mut idents = vec![ident];
 
                 while parser.consume_token(&Token::Period) {
-                    let next =
+                    let (next, span) =
                         match parser.next() {
-                            Some(tok) => &tok.token,
+                            Some(tok) => (&tok.token, tok.span()),
                             None => return Err(RayexecError::new(
                                 "Expected an identifier or '*' after '.', found end of statement",
                             )),
                         };
 
                     match next {
-                        Token::Word(w) => idents.push(w.clone().into()),
-                        Token::SingleQuotedString(s) => idents.push(Ident {
-                            value: s.clone(),
-                            quoted: false,
-                        }),
+                        Token::Word(w) => idents.push(Ident::from(w.clone()).with_span(span)),
+                        Token::SingleQuotedString(s) => idents.push(
+                            Ident {
+                                value: s.clone(),
+                                quoted: false,
+                                span: None,
+                            }
+                            .with_span(span),
+                        ),
                         Token::Mul => {
                             return Ok(WildcardExpr::QualifiedWildcard(ObjectReference(idents)))
                         }
@@ -14,6 +14,8 @@ pub struct Show<T: AstMeta> {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ShowReference {
     Variable(ObjectReference),
+    /// SHOW ALL
+    All,
     /// SHOW DATABASES or SHOW CATALOGS.
     Databases,
     /// SHOW SCHEMAS
@@ -35,6 +37,8 @@ impl AstParseable for Show<Raw> {
             ShowReference::Schemas
         } else if parser.parse_keyword(Keyword::TABLES) {
             ShowReference::Tables
+        } else if parser.parse_keyword(Keyword::ALL) {
+            ShowReference::All
         } else {
             let name = ObjectReference::parse(parser)?;
             ShowReference::Variable(name)
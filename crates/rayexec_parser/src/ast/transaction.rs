@@ -0,0 +1,104 @@
+use rayexec_error::{RayexecError, Result};
+use serde::{Deserialize, Serialize};
+
+use super::AstParseable;
+use crate::keywords::Keyword;
+use crate::parser::Parser;
+
+/// Transaction control statements.
+///
+/// ```text
+/// BEGIN [TRANSACTION | WORK]
+/// START TRANSACTION
+/// COMMIT [TRANSACTION | WORK]
+/// END [TRANSACTION | WORK]
+/// ROLLBACK [TRANSACTION | WORK]
+/// ABORT [TRANSACTION | WORK]
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionStatement {
+    Begin,
+    Commit,
+    Rollback,
+}
+
+impl AstParseable for TransactionStatement {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        let keyword = match parser.parse_one_of_keywords(&[
+            Keyword::BEGIN,
+            Keyword::START,
+            Keyword::COMMIT,
+            Keyword::END,
+            Keyword::ROLLBACK,
+            Keyword::ABORT,
+        ]) {
+            Some(keyword) => keyword,
+            None => {
+                return Err(RayexecError::new(
+                    "Expected BEGIN, START, COMMIT, END, ROLLBACK, or ABORT",
+                ))
+            }
+        };
+
+        let stmt = match keyword {
+            Keyword::BEGIN => {
+                let _ = parser.parse_one_of_keywords(&[Keyword::TRANSACTION, Keyword::WORK]);
+                TransactionStatement::Begin
+            }
+            Keyword::START => {
+                parser.expect_keyword(Keyword::TRANSACTION)?;
+                TransactionStatement::Begin
+            }
+            Keyword::COMMIT | Keyword::END => {
+                let _ = parser.parse_one_of_keywords(&[Keyword::TRANSACTION, Keyword::WORK]);
+                TransactionStatement::Commit
+            }
+            _ => {
+                let _ = parser.parse_one_of_keywords(&[Keyword::TRANSACTION, Keyword::WORK]);
+                TransactionStatement::Rollback
+            }
+        };
+
+        Ok(stmt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::testutil::parse_ast;
+
+    #[test]
+    fn begin() {
+        for sql in [
+            "BEGIN",
+            "BEGIN TRANSACTION",
+            "begin work",
+            "START TRANSACTION",
+        ] {
+            let got: TransactionStatement = parse_ast(sql).unwrap();
+            assert_eq!(TransactionStatement::Begin, got, "sql: {sql}");
+        }
+    }
+
+    #[test]
+    fn commit() {
+        for sql in ["COMMIT", "COMMIT TRANSACTION", "END", "END WORK"] {
+            let got: TransactionStatement = parse_ast(sql).unwrap();
+            assert_eq!(TransactionStatement::Commit, got, "sql: {sql}");
+        }
+    }
+
+    #[test]
+    fn rollback() {
+        for sql in ["ROLLBACK", "ROLLBACK WORK", "ABORT", "ABORT TRANSACTION"] {
+            let got: TransactionStatement = parse_ast(sql).unwrap();
+            assert_eq!(TransactionStatement::Rollback, got, "sql: {sql}");
+        }
+    }
+
+    #[test]
+    fn start_requires_transaction() {
+        parse_ast::<TransactionStatement>("START").unwrap_err();
+    }
+}
@@ -11,18 +11,27 @@ use crate::tokens::Token;
 pub struct SetVariable<T: AstMeta> {
     pub reference: T::ItemReference,
     pub value: Expr<T>,
+    /// If this is `SET LOCAL`, only applying to the current transaction.
+    pub local: bool,
 }
 
 impl AstParseable for SetVariable<Raw> {
     fn parse(parser: &mut Parser) -> Result<Self> {
         parser.expect_keyword(Keyword::SET)?;
 
+        // SET SESSION is the same as a plain SET.
+        let local = matches!(
+            parser.parse_one_of_keywords(&[Keyword::LOCAL, Keyword::SESSION]),
+            Some(Keyword::LOCAL)
+        );
+
         let name = ObjectReference::parse(parser)?;
         if parser.parse_keyword(Keyword::TO) || parser.consume_token(&Token::Eq) {
             let expr = Expr::parse(parser)?;
             return Ok(SetVariable {
                 reference: name,
                 value: expr,
+                local,
             });
         }
 
@@ -54,3 +63,32 @@ impl AstParseable for ResetVariable<Raw> {
         Ok(ResetVariable { var })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::ast::testutil::parse_ast;
+    use crate::ast::Literal;
+
+    #[test]
+    fn set_variable() {
+        let set: SetVariable<_> = parse_ast("SET batch_size = 1024").unwrap();
+        let expected = SetVariable {
+            reference: ObjectReference::from_strings(["batch_size"]),
+            value: Expr::Literal(Literal::Number("1024".to_string())),
+            local: false,
+        };
+        assert_eq!(expected, set);
+    }
+
+    #[test]
+    fn set_local_variable() {
+        let set: SetVariable<_> = parse_ast("SET LOCAL batch_size TO 1024").unwrap();
+        assert!(set.local);
+
+        let set: SetVariable<_> = parse_ast("SET SESSION batch_size TO 1024").unwrap();
+        assert!(!set.local);
+    }
+}
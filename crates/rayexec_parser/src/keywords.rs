@@ -45,7 +45,10 @@ macro_rules! define_keywords {
 // Keep keywords sorted to allow for binary search.
 #[rustfmt::skip]
 define_keywords!(
+    ABORT,
+    ADD,
     ALL,
+    ALTER,
     ANALYZE,
     AND,
     ANTI,
@@ -54,11 +57,13 @@ define_keywords!(
     ASC,
     ATTACH,
     BEGIN,
+    BERNOULLI,
     BETWEEN,
     BIGDECIMAL,
     BIGINT,
     BIGNUMERIC,
     BINARY,
+    BINDINGS,
     BOOL,
     BOOLEAN,
     BY,
@@ -68,8 +73,12 @@ define_keywords!(
     CATALOGS,
     CENTURIES,
     CENTURY,
+    CHECK,
     CLUSTER,
+    COLLATE,
+    COLUMN,
     COLUMNS,
+    COMMIT,
     COPY,
     CREATE,
     CROSS,
@@ -83,6 +92,7 @@ define_keywords!(
     DECADE,
     DECADES,
     DECIMAL,
+    DEFAULT,
     DESC,
     DESCRIBE,
     DETACH,
@@ -115,6 +125,7 @@ define_keywords!(
     FROM,
     FULL,
     FUNCTION,
+    GEOMETRY,
     GROUP,
     GROUPING,
     GROUPS,
@@ -142,12 +153,18 @@ define_keywords!(
     ISOYEAR,
     JOIN,
     JSON,
+    JSONB,
     JULIAN,
+    KEY,
+    LANGUAGE,
     LAST,
     LATERAL,
     LEFT,
     LIKE,
     LIMIT,
+    LOCAL,
+    MACRO,
+    MATCH,
     MATERIALIZED,
     MICROSECOND,
     MICROSECONDS,
@@ -159,6 +176,7 @@ define_keywords!(
     MINUTES,
     MONTH,
     MONTHS,
+    NAME,
     NANOSECOND,
     NANOSECONDS,
     NATURAL,
@@ -169,14 +187,19 @@ define_keywords!(
     NUMERIC,
     OFFSET,
     ON,
+    OPTIMIZED_STEPS,
     OR,
     ORDER,
     OTHERS,
     OUTER,
     OVER,
     PARTITION,
+    PERCENT,
+    PERSISTENT,
     PIVOT,
+    POLICY,
     PRECEDING,
+    PRECISION,
     PRIMARY,
     QUALIFY,
     QUARTER,
@@ -184,9 +207,12 @@ define_keywords!(
     REAL,
     RECURSIVE,
     REGEXP,
+    RENAME,
+    REPEATABLE,
     REPLACE,
     RESET,
     RESTRICT,
+    RETURNS,
     RIGHT,
     RLIKE,
     ROLLBACK,
@@ -197,8 +223,10 @@ define_keywords!(
     SCHEMAS,
     SECOND,
     SECONDS,
+    SECRET,
     SELECT,
     SEMI,
+    SESSION,
     SET,
     SETS,
     SHOW,
@@ -206,10 +234,14 @@ define_keywords!(
     SMALLINT,
     SOME,
     SORT,
+    START,
     STRING,
     SUBSTRING,
+    SUMMARIZE,
+    SYSTEM,
     TABLE,
     TABLES,
+    TABLESAMPLE,
     TEMP,
     TEMPORARY,
     TEXT,
@@ -223,6 +255,7 @@ define_keywords!(
     TINYINT,
     TO,
     TOP,
+    TRANSACTION,
     TRUE,
     UNBOUNDED,
     UNION,
@@ -238,6 +271,8 @@ define_keywords!(
     WHERE,
     WINDOW,
     WITH,
+    WITHIN,
+    WORK,
     YEAR,
     YEARS,
 );
@@ -267,6 +302,7 @@ pub const RESERVED_FOR_TABLE_ALIAS: &[Keyword] = &[
     Keyword::EXCEPT,
     Keyword::INTERSECT,
     // Reserved only as a table alias in the `FROM`/`JOIN` clauses:
+    Keyword::TABLESAMPLE,
     Keyword::ON,
     Keyword::JOIN,
     Keyword::INNER,
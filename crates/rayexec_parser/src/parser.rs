@@ -1,23 +1,32 @@
-use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_error::{not_implemented, ErrorKind, RayexecError, Result, Span};
 use tracing::trace;
 
 use crate::ast::{
+    AlterTable,
     AstParseable,
     Attach,
     CopyTo,
+    CreateFunction,
+    CreateIndex,
+    CreateMacro,
+    CreatePolicy,
     CreateSchema,
+    CreateSecret,
     CreateTable,
     CreateView,
     Describe,
     Detach,
     DropStatement,
     ExplainNode,
+    Expr,
     Ident,
     Insert,
     QueryNode,
     ResetVariable,
     SetVariable,
     Show,
+    Summarize,
+    TransactionStatement,
 };
 use crate::keywords::{Keyword, RESERVED_FOR_COLUMN_ALIAS};
 use crate::meta::Raw;
@@ -25,10 +34,57 @@ use crate::statement::{RawStatement, Statement};
 use crate::tokens::{Token, TokenWithLocation, Tokenizer};
 
 /// Parse a sql query into statements.
+///
+/// Errors without a more specific kind are marked as syntax errors. Errors
+/// without a span point at the last token the parser looked at.
 pub fn parse(sql: &str) -> Result<Vec<Statement<Raw>>> {
+    Ok(parse_with_sql(sql)?
+        .into_iter()
+        .map(|(stmt, _)| stmt)
+        .collect())
+}
+
+/// Parse a sql query into statements, along with the sql text of each
+/// statement.
+///
+/// The text for a statement excludes the semicolon delimiting it from the
+/// next statement.
+pub fn parse_with_sql(sql: &str) -> Result<Vec<(Statement<Raw>, &str)>> {
     trace!(%sql, "parsing sql statement");
+    let syntax_error = |e: RayexecError| match e.kind() {
+        ErrorKind::Other => e.with_kind(ErrorKind::SyntaxError),
+        _ => e,
+    };
+
+    let toks = Tokenizer::new(sql).tokenize().map_err(syntax_error)?;
+    let mut parser = Parser::with_tokens(toks, sql);
+    parser.parse_statements_with_sql().map_err(|e| {
+        let e = syntax_error(e);
+        match e.span() {
+            Some(_) => e,
+            None => {
+                let span = parser.fallback_error_span();
+                e.with_span(Some(span))
+            }
+        }
+    })
+}
+
+/// Parse a sql string containing a single expression.
+pub fn parse_expr(sql: &str) -> Result<Expr<Raw>> {
     let toks = Tokenizer::new(sql).tokenize()?;
-    Parser::with_tokens(toks, sql).parse_statements()
+    let mut parser = Parser::with_tokens(toks, sql);
+    let expr = Expr::parse(&mut parser)?;
+
+    if let Some(tok) = parser.peek() {
+        return Err(RayexecError::new(format!(
+            "Unexpected token after expression: {:?}",
+            tok.token
+        ))
+        .with_kind(ErrorKind::SyntaxError));
+    }
+
+    Ok(expr)
 }
 
 #[derive(Debug)]
@@ -49,6 +105,16 @@ impl<'a> Parser<'a> {
     ///
     /// Statements are expected to be delineated with a semicolon.
     pub fn parse_statements(&mut self) -> Result<Vec<RawStatement>> {
+        Ok(self
+            .parse_statements_with_sql()?
+            .into_iter()
+            .map(|(stmt, _)| stmt)
+            .collect())
+    }
+
+    /// Parse any number of statements, returning each statement along with
+    /// the slice of the sql string it was parsed from.
+    fn parse_statements_with_sql(&mut self) -> Result<Vec<(RawStatement, &'a str)>> {
         let mut stmts = Vec::new();
         let mut expect_delimiter = false;
 
@@ -57,25 +123,27 @@ impl<'a> Parser<'a> {
                 expect_delimiter = false;
             }
 
-            if self.peek().is_none() {
-                // We're done.
-                break;
-            }
+            let start = match self.peek() {
+                Some(tok) => tok.clone(),
+                None => {
+                    // We're done.
+                    break;
+                }
+            };
 
             if expect_delimiter {
-                let unparsed = match self.toks.get(self.idx) {
-                    Some(tok) => self.sql.get(tok.start_idx..).unwrap_or_default(),
-                    None => "",
-                };
+                let unparsed = self.sql.get(start.start_idx..).unwrap_or_default();
 
                 return Err(RayexecError::new(format!(
                     "Expected semicolon between statements. Unparsed SQL: '{}'",
                     unparsed,
-                )));
+                ))
+                .with_span(Some(self.current_span())));
             }
 
             let stmt = self.parse_statement()?;
-            stmts.push(stmt);
+            let sql = self.sql_slice_starting_at(&start)?.trim_end();
+            stmts.push((stmt, sql));
 
             expect_delimiter = true;
         }
@@ -98,7 +166,8 @@ impl<'a> Parser<'a> {
                         return Err(RayexecError::new(format!(
                             "Expected a keyword, got {}",
                             word.value,
-                        )))
+                        ))
+                        .with_span(Some(tok.span())))
                     }
                 };
 
@@ -108,21 +177,33 @@ impl<'a> Parser<'a> {
                     Keyword::COPY => Ok(RawStatement::CopyTo(CopyTo::parse(self)?)),
                     Keyword::CREATE => self.parse_create(),
                     Keyword::DROP => Ok(RawStatement::Drop(DropStatement::parse(self)?)),
+                    Keyword::ALTER => Ok(RawStatement::AlterTable(AlterTable::parse(self)?)),
                     Keyword::SET => Ok(RawStatement::SetVariable(SetVariable::parse(self)?)),
                     Keyword::RESET => Ok(RawStatement::ResetVariable(ResetVariable::parse(self)?)),
                     Keyword::SHOW => Ok(RawStatement::Show(Show::parse(self)?)),
                     Keyword::DESCRIBE => Ok(RawStatement::Describe(Describe::parse(self)?)),
+                    Keyword::SUMMARIZE => Ok(RawStatement::Summarize(Summarize::parse(self)?)),
                     Keyword::SELECT | Keyword::WITH | Keyword::VALUES => {
                         Ok(RawStatement::Query(QueryNode::parse(self)?))
                     }
                     Keyword::INSERT => Ok(RawStatement::Insert(Insert::parse(self)?)),
                     Keyword::EXPLAIN => Ok(RawStatement::Explain(ExplainNode::parse(self)?)),
-                    other => Err(RayexecError::new(format!("Unexpected keyword: {other:?}",))),
+                    Keyword::BEGIN
+                    | Keyword::START
+                    | Keyword::COMMIT
+                    | Keyword::END
+                    | Keyword::ROLLBACK
+                    | Keyword::ABORT => Ok(RawStatement::Transaction(TransactionStatement::parse(
+                        self,
+                    )?)),
+                    other => Err(RayexecError::new(format!("Unexpected keyword: {other:?}",))
+                        .with_span(Some(tok.span()))),
                 }
             }
-            other => Err(RayexecError::new(format!(
-                "Expected a SQL statement, got {other:?}"
-            ))),
+            other => Err(
+                RayexecError::new(format!("Expected a SQL statement, got {other:?}"))
+                    .with_span(Some(tok.span())),
+            ),
         }
     }
 
@@ -139,7 +220,7 @@ impl<'a> Parser<'a> {
         // again.
         let _or_replace = self.parse_keyword_sequence(&[Keyword::OR, Keyword::REPLACE]);
         let _temp = self
-            .parse_one_of_keywords(&[Keyword::TEMP, Keyword::TEMPORARY])
+            .parse_one_of_keywords(&[Keyword::TEMP, Keyword::TEMPORARY, Keyword::PERSISTENT])
             .is_some();
 
         if self.parse_keyword(Keyword::TABLE) {
@@ -151,6 +232,21 @@ impl<'a> Parser<'a> {
         } else if self.parse_keyword(Keyword::VIEW) {
             self.idx = start;
             Ok(RawStatement::CreateView(CreateView::parse(self)?))
+        } else if self.parse_keyword(Keyword::SECRET) {
+            self.idx = start;
+            Ok(RawStatement::CreateSecret(CreateSecret::parse(self)?))
+        } else if self.parse_keyword(Keyword::FUNCTION) {
+            self.idx = start;
+            Ok(RawStatement::CreateFunction(CreateFunction::parse(self)?))
+        } else if self.parse_keyword(Keyword::MACRO) {
+            self.idx = start;
+            Ok(RawStatement::CreateMacro(CreateMacro::parse(self)?))
+        } else if self.parse_keyword(Keyword::INDEX) {
+            self.idx = start;
+            Ok(RawStatement::CreateIndex(CreateIndex::parse(self)?))
+        } else if self.parse_keyword(Keyword::POLICY) {
+            self.idx = start;
+            Ok(RawStatement::CreatePolicy(CreatePolicy::parse(self)?))
         } else {
             not_implemented!("CREATE: {}", self.sql);
         }
@@ -159,8 +255,8 @@ impl<'a> Parser<'a> {
     /// Parse an optional alias.
     pub(crate) fn parse_alias(&mut self, reserved: &[Keyword]) -> Result<Option<Ident>> {
         let has_as = self.parse_keyword(Keyword::AS);
-        let tok = match self.peek() {
-            Some(tok) => &tok.token,
+        let (tok, span) = match self.peek() {
+            Some(tok) => (&tok.token, tok.span()),
             None => return Ok(None),
         };
 
@@ -180,6 +276,7 @@ impl<'a> Parser<'a> {
             Token::SingleQuotedString(s) => Some(Ident {
                 value: s.clone(),
                 quoted: false,
+                span: None,
             }),
 
             _ => {
@@ -195,7 +292,7 @@ impl<'a> Parser<'a> {
             self.next();
         }
 
-        Ok(ident)
+        Ok(ident.map(|ident| ident.with_span(span)))
     }
 
     /// Parse a comma-separated list of one or more items.
@@ -325,10 +422,10 @@ impl<'a> Parser<'a> {
     /// error.
     pub(crate) fn expect_token(&mut self, expected: &Token) -> Result<()> {
         if !self.consume_token(expected) {
-            return Err(RayexecError::new(format!(
-                "Expected {expected:?}, got {:?}",
-                self.peek()
-            )));
+            return Err(
+                RayexecError::new(format!("Expected {expected:?}, got {:?}", self.peek()))
+                    .with_span(Some(self.current_span())),
+            );
         }
         Ok(())
     }
@@ -342,17 +439,18 @@ impl<'a> Parser<'a> {
         Err(RayexecError::new(format!(
             "Expected one of {expected:?}, got {:?}",
             self.peek()
-        )))
+        ))
+        .with_span(Some(self.current_span())))
     }
 
     /// Consume the current keyword if it matches expected, otherwise return an
     /// error.
     pub(crate) fn expect_keyword(&mut self, expected: Keyword) -> Result<()> {
         if !self.parse_keyword(expected) {
-            return Err(RayexecError::new(format!(
-                "Expected {expected:?}, got {:?}",
-                self.peek()
-            )));
+            return Err(
+                RayexecError::new(format!("Expected {expected:?}, got {:?}", self.peek()))
+                    .with_span(Some(self.current_span())),
+            );
         }
         Ok(())
     }
@@ -380,7 +478,10 @@ impl<'a> Parser<'a> {
     pub(crate) fn next_keyword(&mut self) -> Result<Keyword> {
         let tok = match self.peek() {
             Some(tok) => tok,
-            None => return Err(RayexecError::new("Expected keyword, got end of statement")),
+            None => {
+                return Err(RayexecError::new("Expected keyword, got end of statement")
+                    .with_span(Some(self.current_span())))
+            }
         };
 
         match &tok.token {
@@ -391,7 +492,8 @@ impl<'a> Parser<'a> {
                         return Err(RayexecError::new(format!(
                             "Expected a keyword, got {}",
                             word.value,
-                        )))
+                        ))
+                        .with_span(Some(tok.span())))
                     }
                 };
 
@@ -399,9 +501,10 @@ impl<'a> Parser<'a> {
 
                 Ok(keyword)
             }
-            other => Err(RayexecError::new(format!(
-                "Expected a keyword: got {other:?}"
-            ))),
+            other => Err(
+                RayexecError::new(format!("Expected a keyword: got {other:?}"))
+                    .with_span(Some(tok.span())),
+            ),
         }
     }
 
@@ -464,9 +567,41 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Get the span of the next token, or an empty span at the end of the sql
+    /// string if there are no more tokens.
+    pub(crate) fn current_span(&self) -> Span {
+        match self.peek() {
+            Some(tok) => tok.span(),
+            None => Span {
+                start: self.sql.len(),
+                end: self.sql.len(),
+            },
+        }
+    }
+
+    /// Get the span to use for an error that doesn't have one.
+    ///
+    /// Points at the end of the sql string if all tokens have been consumed,
+    /// otherwise at the last token consumed.
+    fn fallback_error_span(&self) -> Span {
+        if self.peek().is_none() {
+            return self.current_span();
+        }
+
+        let prev = self.toks[..self.idx.min(self.toks.len())]
+            .iter()
+            .rev()
+            .find(|tok| !matches!(tok.token, Token::Whitespace));
+
+        match prev {
+            Some(tok) => tok.span(),
+            None => self.current_span(),
+        }
+    }
+
     /// Returns a slice of the original sql string starting at some token to the
     /// current position of the parser.
-    pub(crate) fn sql_slice_starting_at(&self, start: &TokenWithLocation) -> Result<&str> {
+    pub(crate) fn sql_slice_starting_at(&self, start: &TokenWithLocation) -> Result<&'a str> {
         match self.peek() {
             Some(end) => self.sql.get(start.start_idx..end.start_idx).ok_or_else(|| {
                 RayexecError::new("Unable to get string slice for original sql string")
@@ -477,3 +612,43 @@ impl<'a> Parser<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_span_unexpected_token() {
+        let err = parse("SELECT 1 FROM t1 WHERE a = 1 ORDER a").unwrap_err();
+        assert_eq!(ErrorKind::SyntaxError, err.kind());
+        // ORDER
+        assert_eq!(Some(Span { start: 29, end: 34 }), err.span());
+    }
+
+    #[test]
+    fn error_span_end_of_input() {
+        let sql = "SELECT * FROM t1 ORDER BY";
+        let err = parse(sql).unwrap_err();
+        assert_eq!(
+            Some(Span {
+                start: sql.len(),
+                end: sql.len()
+            }),
+            err.span()
+        );
+    }
+
+    #[test]
+    fn parse_expr_trailing_tokens() {
+        parse_expr("a + 1").unwrap();
+        let err = parse_expr("a + 1 b").unwrap_err();
+        assert_eq!(ErrorKind::SyntaxError, err.kind());
+    }
+
+    #[test]
+    fn parse_with_sql_per_statement() {
+        let stmts = parse_with_sql("SELECT 1;  select 2 ;\nSELECT 3").unwrap();
+        let sql: Vec<_> = stmts.iter().map(|(_, sql)| *sql).collect();
+        assert_eq!(vec!["SELECT 1", "select 2", "SELECT 3"], sql);
+    }
+}
@@ -1,9 +1,15 @@
 use serde::{Deserialize, Serialize};
 
 use crate::ast::{
+    AlterTable,
     Attach,
     CopyTo,
+    CreateFunction,
+    CreateIndex,
+    CreateMacro,
+    CreatePolicy,
     CreateSchema,
+    CreateSecret,
     CreateTable,
     CreateView,
     Describe,
@@ -15,6 +21,8 @@ use crate::ast::{
     ResetVariable,
     SetVariable,
     Show,
+    Summarize,
+    TransactionStatement,
 };
 use crate::meta::{AstMeta, Raw};
 
@@ -34,6 +42,10 @@ pub enum Statement<T: AstMeta> {
     /// DESCRIBE <query>
     Describe(Describe<T>),
 
+    /// SUMMARIZE <table>
+    /// SUMMARIZE <query>
+    Summarize(Summarize<T>),
+
     /// SELECT/VALUES
     Query(QueryNode<T>),
 
@@ -46,9 +58,27 @@ pub enum Statement<T: AstMeta> {
     /// CREATE VIEW ...
     CreateView(CreateView<T>),
 
+    /// CREATE SECRET ...
+    CreateSecret(CreateSecret<T>),
+
+    /// CREATE FUNCTION ...
+    CreateFunction(CreateFunction<T>),
+
+    /// CREATE MACRO ...
+    CreateMacro(CreateMacro<T>),
+
+    /// CREATE INDEX ...
+    CreateIndex(CreateIndex<T>),
+
+    /// CREATE POLICY ...
+    CreatePolicy(CreatePolicy<T>),
+
     /// DROP ...
     Drop(DropStatement<T>),
 
+    /// ALTER TABLE ...
+    AlterTable(AlterTable<T>),
+
     /// INSERT INTO ...
     Insert(Insert<T>),
 
@@ -62,4 +92,7 @@ pub enum Statement<T: AstMeta> {
 
     /// RESET <variable>
     ResetVariable(ResetVariable<T>),
+
+    /// BEGIN/COMMIT/ROLLBACK
+    Transaction(TransactionStatement),
 }
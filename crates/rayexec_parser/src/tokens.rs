@@ -1,6 +1,6 @@
 use std::fmt;
 
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{RayexecError, Result, Span};
 
 use crate::keywords::{keyword_from_str, Keyword};
 
@@ -70,6 +70,10 @@ pub enum Token {
     Caret,
     /// '^@'
     CaretAt,
+    /// '->'
+    Arrow,
+    /// '->>'
+    LongArrow,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -77,6 +81,8 @@ pub struct TokenWithLocation {
     pub token: Token,
     /// Starting index of the token within the sql string.
     pub start_idx: usize,
+    /// Index immediately after the end of the token within the sql string.
+    pub end_idx: usize,
     /// Line number for the token.
     pub line: usize,
     /// Column number for where the token starts.
@@ -84,6 +90,14 @@ pub struct TokenWithLocation {
 }
 
 impl TokenWithLocation {
+    /// Get the span of the token within the sql string.
+    pub fn span(&self) -> Span {
+        Span {
+            start: self.start_idx,
+            end: self.end_idx,
+        }
+    }
+
     pub fn is_keyword(&self, other: Keyword) -> bool {
         let word = match &self.token {
             Token::Word(w) => w,
@@ -230,10 +244,22 @@ impl<'a> Tokenizer<'a> {
     pub fn tokenize(&mut self) -> Result<Vec<TokenWithLocation>> {
         let mut tokens = Vec::new();
         let mut start_idx = self.state.idx;
-        while let Some(token) = self.next_token()? {
+        loop {
+            let token = self.next_token().map_err(|e| {
+                e.with_span(Some(Span {
+                    start: start_idx,
+                    end: self.state.idx,
+                }))
+            })?;
+            let token = match token {
+                Some(token) => token,
+                None => break,
+            };
+
             tokens.push(TokenWithLocation {
                 token,
                 start_idx,
+                end_idx: self.state.idx,
                 line: self.state.line,
                 col: self.state.col,
             });
@@ -289,7 +315,19 @@ impl<'a> Tokenizer<'a> {
             }
             '-' => {
                 self.state.next();
-                Token::Minus
+                match self.state.peek() {
+                    Some('>') => {
+                        self.state.next();
+                        match self.state.peek() {
+                            Some('>') => {
+                                self.state.next();
+                                Token::LongArrow
+                            }
+                            _ => Token::Arrow,
+                        }
+                    }
+                    _ => Token::Minus,
+                }
             }
             '/' => {
                 self.state.next();
@@ -505,4 +543,20 @@ mod tests {
         // hi
         assert_eq!(toks[6].start_idx, 14);
     }
+
+    #[test]
+    fn token_spans() {
+        let toks = Tokenizer::new("SELECT 'abc', my_table.a")
+            .tokenize()
+            .unwrap();
+
+        // SELECT
+        assert_eq!(Span { start: 0, end: 6 }, toks[0].span());
+
+        // 'abc'
+        assert_eq!(Span { start: 7, end: 12 }, toks[2].span());
+
+        // my_table
+        assert_eq!(Span { start: 14, end: 22 }, toks[5].span());
+    }
 }
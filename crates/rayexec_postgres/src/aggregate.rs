@@ -0,0 +1,267 @@
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::field::Field;
+use rayexec_execution::arrays::scalar::{OwnedScalarValue, ScalarValue};
+use rayexec_execution::expr::comparison_expr::ComparisonOperator;
+use rayexec_execution::logical::scan_filter::{ScanFilter, ScanFilterType};
+use rayexec_execution::storage::table_storage::{
+    TableAggregate,
+    TableAggregateExpr,
+    TableAggregateFunction,
+};
+use tokio_postgres::types::Type as PostgresType;
+
+/// Check if we're able to have postgres compute the aggregate.
+pub fn supports_aggregate(aggregate: &TableAggregate) -> bool {
+    aggregate.aggregates.iter().all(supports_aggregate_expr)
+        && aggregate
+            .filters
+            .iter()
+            .all(|filter| filter_sql(filter, "").is_some())
+}
+
+fn supports_aggregate_expr(expr: &TableAggregateExpr) -> bool {
+    if output_type(&expr.output_type).is_none() {
+        return false;
+    }
+
+    match (expr.function, &expr.input_type) {
+        // Postgres orders strings by collation, which may not match our byte
+        // ordering.
+        (TableAggregateFunction::Min | TableAggregateFunction::Max, Some(DataType::Utf8)) => false,
+        _ => true,
+    }
+}
+
+/// Generate the query for computing the aggregate, along with the postgres
+/// types of the output columns.
+///
+/// Fields are the columns of the table being aggregated.
+pub fn aggregate_query(
+    aggregate: &TableAggregate,
+    fields: &[Field],
+    schema: &str,
+    table: &str,
+) -> Result<(String, Vec<PostgresType>)> {
+    let column_name = |idx: usize| -> Result<String> {
+        let field = fields
+            .get(idx)
+            .ok_or_else(|| RayexecError::new(format!("Missing column at index {idx}")))?;
+        Ok(quote_ident(&field.name))
+    };
+
+    let mut select_list = Vec::with_capacity(aggregate.aggregates.len());
+    let mut typs = Vec::with_capacity(aggregate.aggregates.len());
+    for expr in &aggregate.aggregates {
+        let (typ, type_name) = output_type(&expr.output_type).ok_or_else(|| {
+            RayexecError::new(format!(
+                "Unsupported aggregate output type: {}",
+                expr.output_type
+            ))
+        })?;
+
+        let input = match expr.column {
+            Some(column) => column_name(column)?,
+            None => "*".to_string(),
+        };
+
+        select_list.push(format!("{}({input})::{type_name}", expr.function));
+        typs.push(typ);
+    }
+
+    let mut query = format!("SELECT {} FROM {schema}.{table}", select_list.join(", "));
+
+    if !aggregate.filters.is_empty() {
+        let filters = aggregate
+            .filters
+            .iter()
+            .map(|filter| {
+                let column = column_name(filter.column)?;
+                filter_sql(filter, &column).ok_or_else(|| {
+                    RayexecError::new(format!("Unsupported filter for postgres: {filter}"))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        query.push_str(" WHERE ");
+        query.push_str(&filters.join(" AND "));
+    }
+
+    Ok((query, typs))
+}
+
+/// Postgres type to cast an aggregate output to so that we can decode it as
+/// the expected type.
+pub(crate) fn output_type(datatype: &DataType) -> Option<(PostgresType, String)> {
+    Some(match datatype {
+        DataType::Boolean => (PostgresType::BOOL, "bool".to_string()),
+        DataType::Int16 => (PostgresType::INT2, "int2".to_string()),
+        DataType::Int32 => (PostgresType::INT4, "int4".to_string()),
+        DataType::Int64 => (PostgresType::INT8, "int8".to_string()),
+        DataType::Float32 => (PostgresType::FLOAT4, "float4".to_string()),
+        DataType::Float64 => (PostgresType::FLOAT8, "float8".to_string()),
+        DataType::Decimal128(m) => (
+            PostgresType::NUMERIC,
+            format!("numeric({},{})", m.precision, m.scale),
+        ),
+        DataType::Utf8 => (PostgresType::TEXT, "text".to_string()),
+        _ => return None,
+    })
+}
+
+/// Generate the SQL for a filter on a column.
+///
+/// Returns None if the filter can't be expressed in postgres with the same
+/// semantics.
+fn filter_sql(filter: &ScanFilter, column: &str) -> Option<String> {
+    match &filter.filter {
+        ScanFilterType::ConstComparison { op, constant } => {
+            // String comparisons depend on collation, only equality is
+            // guaranteed to behave the same.
+            if matches!(constant, ScalarValue::Utf8(_))
+                && !matches!(op, ComparisonOperator::Eq | ComparisonOperator::NotEq)
+            {
+                return None;
+            }
+
+            let op = match op {
+                ComparisonOperator::Eq => "=",
+                ComparisonOperator::NotEq => "<>",
+                ComparisonOperator::Lt => "<",
+                ComparisonOperator::LtEq => "<=",
+                ComparisonOperator::Gt => ">",
+                ComparisonOperator::GtEq => ">=",
+            };
+
+            Some(format!("{column} {op} {}", literal(constant)?))
+        }
+    }
+}
+
+/// Generate a postgres literal for a scalar value.
+pub fn literal(value: &OwnedScalarValue) -> Option<String> {
+    Some(match value {
+        ScalarValue::Boolean(v) => v.to_string(),
+        ScalarValue::Int8(v) => v.to_string(),
+        ScalarValue::Int16(v) => v.to_string(),
+        ScalarValue::Int32(v) => v.to_string(),
+        ScalarValue::Int64(v) => v.to_string(),
+        ScalarValue::UInt8(v) => v.to_string(),
+        ScalarValue::UInt16(v) => v.to_string(),
+        ScalarValue::UInt32(v) => v.to_string(),
+        ScalarValue::UInt64(v) => v.to_string(),
+        ScalarValue::Float32(v) if v.is_finite() => v.to_string(),
+        ScalarValue::Float64(v) if v.is_finite() => v.to_string(),
+        ScalarValue::Decimal64(_) | ScalarValue::Decimal128(_) => value.to_string(),
+        ScalarValue::Utf8(v) => format!("'{}'", v.replace('\'', "''")),
+        _ => return None,
+    })
+}
+
+/// Quote an identifier, escaping any embedded quotes.
+pub fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> Vec<Field> {
+        vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int64, true),
+            Field::new("c", DataType::Utf8, true),
+        ]
+    }
+
+    fn agg_expr(
+        function: TableAggregateFunction,
+        column: Option<usize>,
+        output_type: DataType,
+    ) -> TableAggregateExpr {
+        TableAggregateExpr {
+            function,
+            column,
+            input_type: column.map(|idx| fields()[idx].datatype.clone()),
+            output_type,
+        }
+    }
+
+    #[test]
+    fn count_and_sum_with_filters() {
+        let aggregate = TableAggregate {
+            aggregates: vec![
+                agg_expr(TableAggregateFunction::Count, None, DataType::Int64),
+                agg_expr(TableAggregateFunction::Sum, Some(1), DataType::Int64),
+            ],
+            filters: vec![
+                ScanFilter {
+                    column: 0,
+                    filter: ScanFilterType::ConstComparison {
+                        op: ComparisonOperator::Gt,
+                        constant: ScalarValue::Int32(5),
+                    },
+                },
+                ScanFilter {
+                    column: 2,
+                    filter: ScanFilterType::ConstComparison {
+                        op: ComparisonOperator::Eq,
+                        constant: ScalarValue::Utf8("it's".into()),
+                    },
+                },
+            ],
+        };
+
+        assert!(supports_aggregate(&aggregate));
+
+        let (query, typs) = aggregate_query(&aggregate, &fields(), "public", "t1").unwrap();
+        assert_eq!(
+            "SELECT count(*)::int8, sum(\"b\")::int8 FROM public.t1 WHERE \"a\" > 5 AND \"c\" = 'it''s'",
+            query
+        );
+        assert_eq!(vec![PostgresType::INT8, PostgresType::INT8], typs);
+    }
+
+    #[test]
+    fn unsupported_string_ordering() {
+        let aggregate = TableAggregate {
+            aggregates: vec![agg_expr(
+                TableAggregateFunction::Max,
+                Some(2),
+                DataType::Utf8,
+            )],
+            filters: Vec::new(),
+        };
+        assert!(!supports_aggregate(&aggregate));
+
+        let aggregate = TableAggregate {
+            aggregates: vec![agg_expr(
+                TableAggregateFunction::Count,
+                None,
+                DataType::Int64,
+            )],
+            filters: vec![ScanFilter {
+                column: 2,
+                filter: ScanFilterType::ConstComparison {
+                    op: ComparisonOperator::Lt,
+                    constant: ScalarValue::Utf8("m".into()),
+                },
+            }],
+        };
+        assert!(!supports_aggregate(&aggregate));
+    }
+
+    #[test]
+    fn unsupported_output_type() {
+        let aggregate = TableAggregate {
+            aggregates: vec![agg_expr(
+                TableAggregateFunction::Min,
+                Some(0),
+                DataType::Date32,
+            )],
+            filters: Vec::new(),
+        };
+        assert!(!supports_aggregate(&aggregate));
+    }
+}
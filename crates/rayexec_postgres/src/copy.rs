@@ -0,0 +1,272 @@
+use std::error::Error as _;
+use std::io;
+use std::sync::Arc;
+
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::runtime::stall::{maybe_with_stall_timeout, stall_error};
+use tokio_postgres::binary_copy::{BinaryCopyOutRow, BinaryCopyOutStream};
+use tokio_postgres::types::Type as PostgresType;
+use tracing::debug;
+
+use crate::PostgresClient;
+
+/// Error while reading from a COPY.
+enum CopyError {
+    Postgres(tokio_postgres::Error),
+    /// Postgres stopped responding or sending rows.
+    Stalled(RayexecError),
+    Other(RayexecError),
+}
+
+impl From<tokio_postgres::Error> for CopyError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        CopyError::Postgres(e)
+    }
+}
+
+/// Builds a ctid ordered query, reading rows after the provided ctid.
+pub(crate) type CtidQueryBuilder = Box<dyn Fn(Option<&str>) -> String + Send>;
+
+/// Query for a COPY, and how to resume it after reconnecting.
+pub(crate) enum CopyQuery {
+    /// Query that may return rows in a different order every time it's
+    /// executed. Only retried if no rows have been read yet.
+    Unordered(String),
+    /// Query returning rows ordered by ctid, with each row's ctid as text in
+    /// the last column.
+    ///
+    /// Called with the ctid of the last row read to build a query for the
+    /// remaining rows when resuming, and None for the initial query.
+    CtidOrdered(CtidQueryBuilder),
+}
+
+/// Max number of times a COPY is retried after the connection drops or the
+/// COPY stalls.
+///
+/// Reset whenever a batch is successfully read.
+const MAX_COPY_RETRIES: usize = 3;
+
+/// Reads batches from a binary COPY, reconnecting and resuming if the
+/// connection drops.
+///
+/// Stalls are only timed out here if the client is configured to retry them,
+/// otherwise the stall timeout is applied to the stream of batches as a whole.
+pub(crate) struct CopyOutState {
+    client: PostgresClient,
+    /// Connection the COPY is currently running on.
+    ///
+    /// Starts as the client's shared connection, replaced with a new
+    /// connection when retrying.
+    conn: Option<Arc<tokio_postgres::Client>>,
+    query: CopyQuery,
+    typs: Vec<PostgresType>,
+    data_types: Vec<DataType>,
+    batch_size: usize,
+    /// Number of rows returned in complete batches.
+    rows_read: usize,
+    /// Ctid of the last row returned in a complete batch, only set for ctid
+    /// ordered queries.
+    last_ctid: Option<String>,
+    /// Retries since the last complete batch.
+    retries: usize,
+    stream: Option<BoxStream<'static, Result<BinaryCopyOutRow, CopyError>>>,
+    finished: bool,
+}
+
+impl CopyOutState {
+    pub(crate) fn new(
+        client: PostgresClient,
+        query: CopyQuery,
+        typs: Vec<PostgresType>,
+        data_types: Vec<DataType>,
+        batch_size: usize,
+    ) -> Self {
+        CopyOutState {
+            conn: Some(client.client.clone()),
+            client,
+            query,
+            typs,
+            data_types,
+            batch_size,
+            rows_read: 0,
+            last_ctid: None,
+            retries: 0,
+            stream: None,
+            finished: false,
+        }
+    }
+
+    pub(crate) fn into_stream(self) -> BoxStream<'static, Result<Batch>> {
+        stream::unfold(self, |mut state| async move {
+            if state.finished {
+                return None;
+            }
+            match state.next_batch().await {
+                Ok(Some(batch)) => Some((Ok(batch), state)),
+                Ok(None) => None,
+                Err(e) => {
+                    state.finished = true;
+                    Some((Err(e), state))
+                }
+            }
+        })
+        .boxed()
+    }
+
+    async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        loop {
+            let (err, retryable) = match self.try_next_batch().await {
+                Ok(batch) => {
+                    if batch.is_some() {
+                        self.retries = 0;
+                    }
+                    return Ok(batch);
+                }
+                Err(CopyError::Postgres(e)) => {
+                    let retryable = is_connection_error(&e);
+                    (
+                        RayexecError::with_source("Failed to read rows from postgres", Box::new(e)),
+                        retryable,
+                    )
+                }
+                Err(CopyError::Stalled(e)) => (e, true),
+                Err(CopyError::Other(e)) => return Err(e),
+            };
+
+            let can_resume = matches!(self.query, CopyQuery::CtidOrdered(_)) || self.rows_read == 0;
+            if !retryable || !can_resume || self.retries >= MAX_COPY_RETRIES {
+                return Err(err);
+            }
+
+            self.retries += 1;
+            debug!(%err, rows_read = %self.rows_read, retries = %self.retries, "retrying postgres copy");
+
+            // Shared connection is likely dead, continue on a new one.
+            self.stream = None;
+            self.conn = None;
+        }
+    }
+
+    /// Read the next batch, starting the COPY if needed.
+    ///
+    /// Rows from partial batches are dropped on error, and read again when
+    /// resuming.
+    async fn try_next_batch(&mut self) -> Result<Option<Batch>, CopyError> {
+        if self.stream.is_none() {
+            self.start_copy().await?;
+        }
+        let stream = self.stream.as_mut().expect("stream to be set");
+
+        let mut rows = Vec::with_capacity(self.batch_size);
+        while rows.len() < self.batch_size {
+            match stream.next().await {
+                Some(row) => rows.push(row?),
+                None => {
+                    // Polling the COPY again after it's complete errors as
+                    // if the connection was closed.
+                    self.finished = true;
+                    break;
+                }
+            }
+        }
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let last_ctid = match &self.query {
+            CopyQuery::CtidOrdered(_) => {
+                let row = rows.last().expect("at least one row");
+                let ctid: &str = row.try_get(self.typs.len() - 1)?;
+                Some(ctid.to_string())
+            }
+            CopyQuery::Unordered(_) => None,
+        };
+
+        let num_rows = rows.len();
+        // Trailing ctid column is ignored since there's no data type for it.
+        let batch = PostgresClient::binary_rows_to_batch(&self.data_types, rows)
+            .map_err(CopyError::Other)?;
+        self.rows_read += num_rows;
+        if last_ctid.is_some() {
+            self.last_ctid = last_ctid;
+        }
+
+        Ok(Some(batch))
+    }
+
+    /// Start the COPY, only reading rows after the last ctid read if
+    /// resuming.
+    async fn start_copy(&mut self) -> Result<(), CopyError> {
+        let conn = match &self.conn {
+            Some(conn) => conn.clone(),
+            None => {
+                let conn = PostgresClient::connect_client(
+                    &self.client.handle,
+                    self.client.conn_str.clone(),
+                )
+                .await
+                .map_err(CopyError::Other)?;
+                let conn = Arc::new(conn);
+                self.conn = Some(conn.clone());
+                conn
+            }
+        };
+
+        let timeout = if self.client.stall.retry() {
+            self.client.stall.timeout
+        } else {
+            None
+        };
+
+        let query = match &self.query {
+            CopyQuery::Unordered(query) => query.clone(),
+            CopyQuery::CtidOrdered(build) => build(self.last_ctid.as_deref()),
+        };
+
+        let copy = match timeout {
+            Some(timeout) => {
+                let copy = {
+                    // Timer needs to be created in the context of the runtime.
+                    let _guard = self.client.handle.enter();
+                    tokio::time::timeout(timeout, conn.copy_out(&query))
+                };
+                copy.await
+                    .map_err(|_| CopyError::Stalled(stall_error(timeout)))??
+            }
+            None => conn.copy_out(&query).await?,
+        };
+
+        let rows = BinaryCopyOutStream::new(copy, &self.typs)
+            .map(|row| Ok(row.map_err(CopyError::Postgres)))
+            .boxed();
+        let stream = maybe_with_stall_timeout(rows, timeout, Some(self.client.handle.clone()))
+            .map(|row| match row {
+                Ok(row) => row,
+                Err(e) => Err(CopyError::Stalled(e)),
+            })
+            .boxed();
+
+        self.stream = Some(stream);
+        Ok(())
+    }
+}
+
+/// Check if an error is from the connection dropping, meaning the COPY may
+/// succeed on a new connection.
+fn is_connection_error(err: &tokio_postgres::Error) -> bool {
+    if err.is_closed() {
+        return true;
+    }
+    if let Some(code) = err.code() {
+        // Class 08 is connection exceptions, 57P01-57P03 are the server
+        // shutting down or not accepting connections.
+        let code = code.code();
+        return code.starts_with("08") || matches!(code, "57P01" | "57P02" | "57P03");
+    }
+    err.source().is_some_and(|source| source.is::<io::Error>())
+}
@@ -0,0 +1,37 @@
+use rayexec_error::RayexecError;
+use tokio_postgres::types::{FromSql, Type};
+
+/// Version byte prefixing the binary representation of JSONB values.
+const JSONB_VERSION: u8 = 1;
+
+/// JSON or JSONB value decoded as its text representation.
+#[derive(Debug)]
+pub struct PostgresJson<'a>(pub &'a str);
+
+impl<'a> FromSql<'a> for PostgresJson<'a> {
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        // JSONB is sent as a version byte followed by the JSON text.
+        let raw = if *ty == Type::JSONB {
+            match raw.split_first() {
+                Some((&JSONB_VERSION, rest)) => rest,
+                Some((version, _)) => {
+                    return Err(Box::new(RayexecError::new(format!(
+                        "Unsupported JSONB version: {version}"
+                    ))))
+                }
+                None => return Err(Box::new(RayexecError::new("Empty JSONB buffer"))),
+            }
+        } else {
+            raw
+        };
+
+        Ok(PostgresJson(std::str::from_utf8(raw)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::JSON | Type::JSONB)
+    }
+}
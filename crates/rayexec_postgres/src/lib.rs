@@ -1,21 +1,32 @@
+pub mod pg_execute;
 pub mod read_postgres;
 
+mod aggregate;
+mod copy;
 mod decimal;
+mod json;
+mod query;
+mod runtime_filter;
 
 use std::collections::HashMap;
 use std::fmt;
+use std::future::Future;
 use std::sync::Arc;
 
+use copy::{CopyOutState, CopyQuery};
 use decimal::PostgresDecimal;
 use futures::future::BoxFuture;
 use futures::stream::BoxStream;
-use futures::{StreamExt, TryFutureExt};
+use futures::{FutureExt, StreamExt, TryFutureExt};
+use json::PostgresJson;
+use pg_execute::PgExecute;
 use rayexec_error::{RayexecError, Result, ResultExt};
 use rayexec_execution::arrays::array::Array;
 use rayexec_execution::arrays::batch::Batch;
 use rayexec_execution::arrays::datatype::{DataType, DecimalTypeMeta};
 use rayexec_execution::arrays::field::Field;
 use rayexec_execution::arrays::scalar::OwnedScalarValue;
+use rayexec_execution::database::catalog::CatalogTx;
 use rayexec_execution::database::catalog_entry::{CatalogEntry, TableEntry};
 use rayexec_execution::database::memory_catalog::MemoryCatalog;
 use rayexec_execution::datasource::{
@@ -25,7 +36,11 @@ use rayexec_execution::datasource::{
     DataSourceBuilder,
     DataSourceConnection,
 };
+use rayexec_execution::execution::operators::runtime_filter::ScanRuntimeFilter;
 use rayexec_execution::functions::table::TableFunction;
+use rayexec_execution::logical::scan_filter::ScanFilter;
+use rayexec_execution::logical::statistics::StatisticsValue;
+use rayexec_execution::runtime::stall::{maybe_with_stall_timeout, ScanStall};
 use rayexec_execution::runtime::{Runtime, TokioHandlerProvider};
 use rayexec_execution::storage::catalog_storage::CatalogStorage;
 use rayexec_execution::storage::table_storage::{
@@ -34,10 +49,14 @@ use rayexec_execution::storage::table_storage::{
     EmptyTableScan,
     ProjectedScan,
     Projections,
+    RemoteQuery,
+    TableAggregate,
+    TableSample,
+    TableStatistics,
     TableStorage,
 };
 use read_postgres::ReadPostgres;
-use tokio_postgres::binary_copy::{BinaryCopyOutRow, BinaryCopyOutStream};
+use tokio_postgres::binary_copy::BinaryCopyOutRow;
 use tokio_postgres::types::{FromSql, Type as PostgresType};
 use tokio_postgres::NoTls;
 use tracing::debug;
@@ -70,9 +89,14 @@ impl<R: Runtime> DataSource for PostgresDataSource<R> {
     }
 
     fn initialize_table_functions(&self) -> Vec<Box<dyn TableFunction>> {
-        vec![Box::new(ReadPostgres {
-            runtime: self.runtime.clone(),
-        })]
+        vec![
+            Box::new(ReadPostgres {
+                runtime: self.runtime.clone(),
+            }),
+            Box::new(PgExecute {
+                runtime: self.runtime.clone(),
+            }),
+        ]
     }
 }
 
@@ -119,17 +143,26 @@ impl<R: Runtime> CatalogStorage for PostgresConnection<R> {
 
         Box::pin(async move {
             let fields = match self.client.get_fields_and_types(&schema, &name).await? {
-                Some((fields, _)) => fields,
+                Some(columns) => columns.fields,
                 None => return Ok(None),
             };
 
-            Ok(Some(TableEntry { columns: fields }))
+            Ok(Some(TableEntry::new(fields)))
         })
     }
+
+    fn list_tables(&self) -> BoxFuture<'_, Result<Vec<(String, String)>>> {
+        Box::pin(async move { self.client.list_tables().await })
+    }
 }
 
 impl<R: Runtime> TableStorage for PostgresConnection<R> {
-    fn data_table(&self, schema: &str, ent: &CatalogEntry) -> Result<Box<dyn DataTable>> {
+    fn data_table(
+        &self,
+        _tx: &CatalogTx,
+        schema: &str,
+        ent: &CatalogEntry,
+    ) -> Result<Box<dyn DataTable>> {
         Ok(Box::new(PostgresDataTable {
             client: self.client.clone(),
             schema: schema.to_string(),
@@ -156,6 +189,30 @@ impl<R: Runtime> TableStorage for PostgresConnection<R> {
             ))
         })
     }
+
+    fn supports_query(&self, query: &RemoteQuery) -> bool {
+        query::supports_query(query)
+    }
+
+    fn scan_query(
+        &self,
+        query: &RemoteQuery,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let (sql, typs) = query::copy_query(query)?;
+        let data_types = query.types.clone();
+
+        // Row order isn't deterministic for arbitrary queries.
+        let stream = self.client.copy_out_stream(batch_size, async move {
+            Ok((CopyQuery::Unordered(sql), typs, data_types))
+        });
+
+        let mut scans = vec![Box::new(PostgresDataTableScan { stream }) as _];
+        (1..num_partitions).for_each(|_| scans.push(Box::new(EmptyTableScan) as _));
+
+        Ok(scans)
+    }
 }
 
 #[derive(Debug)]
@@ -165,23 +222,51 @@ pub struct PostgresDataTable {
     pub(crate) table: String,
 }
 
-impl DataTable for PostgresDataTable {
-    fn scan(
+impl PostgresDataTable {
+    /// Scan the table, optionally having postgres sample, limit, or apply
+    /// runtime filters to the table for us.
+    fn scan_remote(
         &self,
         projections: Projections,
+        sample: Option<&TableSample>,
+        limit: Option<usize>,
+        runtime_filters: Vec<ScanRuntimeFilter>,
         num_partitions: usize,
+        batch_size: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>> {
         let schema = self.schema.clone();
         let table = self.table.clone();
 
-        let client = self.client.clone();
+        // Postgres supports both SYSTEM and BERNOULLI sampling with the same
+        // syntax we do.
+        let sample_string = match sample {
+            Some(sample) => {
+                let mut s = format!(" TABLESAMPLE {} ({})", sample.method, sample.percentage);
+                if let Some(seed) = sample.seed {
+                    s.push_str(&format!(" REPEATABLE ({seed})"));
+                }
+                s
+            }
+            None => String::new(),
+        };
 
-        let binary_copy_open = async move {
-            // TODO: Remove this, we should already have the types.
-            let (fields, typs) = match client.get_fields_and_types(&schema, &table).await? {
-                Some((fields, typs)) => (fields, typs),
-                None => return Err(RayexecError::new("Missing table")),
-            };
+        let limit_string = match limit {
+            Some(limit) => format!(" LIMIT {limit}"),
+            None => String::new(),
+        };
+
+        // Sampling picks different rows each time unless it's seeded, and
+        // limits may return any rows since there's no order, so resuming
+        // these could return different rows than the ones already read.
+        let resumable = sample.is_none() && limit.is_none();
+
+        let wait_filters = runtime_filters.clone();
+        let binary_copy_stream = self.copy_out_stream(batch_size, wait_filters, move |columns| {
+            let TableColumns {
+                fields,
+                mut typs,
+                has_ctid,
+            } = columns;
 
             let projection_string = fields
                 .iter()
@@ -189,37 +274,52 @@ impl DataTable for PostgresDataTable {
                 .collect::<Vec<_>>()
                 .join(", ");
 
+            // Filters have all been completed at this point, predicates that
+            // can't be rendered are skipped and applied after reading.
+            let predicates: Vec<_> = runtime_filters
+                .iter()
+                .filter_map(|filter| {
+                    let values = filter.filter.get()?;
+                    let field = fields.get(filter.column)?;
+                    runtime_filter::runtime_filter_sql(&values, &field.name)
+                })
+                .collect();
+
             let data_types: Vec<_> = fields.into_iter().map(|field| field.datatype).collect();
 
-            let query = format!(
-                "COPY (SELECT {} FROM {}.{}) TO STDOUT (FORMAT binary)",
-                projection_string, // SELECT <str>
-                schema,            // FROM <schema>
-                table,             // .<table>
-            );
+            if !resumable || !has_ctid {
+                let query = format!(
+                    "COPY (SELECT {} FROM {}.{}{}{}{}) TO STDOUT (FORMAT binary)",
+                    projection_string,         // SELECT <str>
+                    schema,                    // FROM <schema>
+                    table,                     // .<table>
+                    sample_string,             // TABLESAMPLE ...
+                    where_clause(&predicates), // WHERE ...
+                    limit_string,              // LIMIT ...
+                );
+                return Ok((CopyQuery::Unordered(query), typs, data_types));
+            }
 
-            let copy_stream = client
-                .client
-                .copy_out(&query)
-                .await
-                .context("Failed to create copy out stream")?;
-            let copy_stream = BinaryCopyOutStream::new(copy_stream, &typs);
-            // let copy_stream = BinaryCopyOutStream::new(copy_stream,)
-            let chunked = copy_stream.chunks(1024).boxed(); // TODO: Batch size
-
-            let batch_stream = chunked.map(move |rows| {
-                let rows = rows
-                    .into_iter()
-                    .collect::<Result<Vec<_>, _>>()
-                    .context("Failed to collect binary rows")?;
-                let batch = PostgresClient::binary_rows_to_batch(&data_types, rows)?;
-                Ok(batch)
-            });
-
-            Ok(batch_stream)
-        };
+            // Read rows in ctid order so a resumed COPY can pick up after the
+            // last row read, even if postgres would return rows in a
+            // different order the second time around.
+            typs.push(PostgresType::TEXT);
+            let build_query = move |last_ctid: Option<&str>| {
+                let mut predicates = predicates.clone();
+                if let Some(ctid) = last_ctid {
+                    predicates.push(format!("ctid > '{}'", ctid.replace('\'', "''")));
+                }
+                format!(
+                    "COPY (SELECT {}, ctid::text FROM {}.{}{} ORDER BY ctid) TO STDOUT (FORMAT binary)",
+                    projection_string,         // SELECT <str>
+                    schema,                    // FROM <schema>
+                    table,                     // .<table>
+                    where_clause(&predicates), // WHERE ...
+                )
+            };
 
-        let binary_copy_stream = binary_copy_open.try_flatten_stream().boxed();
+            Ok((CopyQuery::CtidOrdered(Box::new(build_query)), typs, data_types))
+        });
 
         let mut scans = vec![Box::new(ProjectedScan::new(
             PostgresDataTableScan {
@@ -233,6 +333,169 @@ impl DataTable for PostgresDataTable {
 
         Ok(scans)
     }
+
+    /// Create a stream of batches from a binary COPY out of postgres.
+    ///
+    /// `build_query` is provided the columns for the table, and returns the
+    /// COPY query along with the postgres types and our data types for the
+    /// query output.
+    ///
+    /// The query isn't built until all `runtime_filters` have been completed.
+    fn copy_out_stream<F>(
+        &self,
+        batch_size: usize,
+        runtime_filters: Vec<ScanRuntimeFilter>,
+        build_query: F,
+    ) -> BoxStream<'static, Result<Batch>>
+    where
+        F: FnOnce(TableColumns) -> Result<(CopyQuery, Vec<PostgresType>, Vec<DataType>)>
+            + Send
+            + 'static,
+    {
+        let schema = self.schema.clone();
+        let table = self.table.clone();
+        let client = self.client.clone();
+
+        let stream = self.client.copy_out_stream(batch_size, async move {
+            // TODO: Remove this, we should already have the types.
+            let columns = match client.get_fields_and_types(&schema, &table).await? {
+                Some(columns) => columns,
+                None => return Err(RayexecError::new("Missing table")),
+            };
+
+            build_query(columns)
+        });
+
+        if runtime_filters.is_empty() {
+            return stream;
+        }
+
+        // Wait outside of the stream so the time spent waiting on the filters
+        // doesn't count towards the stall timeout.
+        async move {
+            for filter in &runtime_filters {
+                filter.filter.wait().await;
+            }
+            stream
+        }
+        .flatten_stream()
+        .boxed()
+    }
+}
+impl DataTable for PostgresDataTable {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        self.scan_remote(
+            projections,
+            None,
+            None,
+            Vec::new(),
+            num_partitions,
+            batch_size,
+        )
+    }
+
+    fn scan_runtime_filtered(
+        &self,
+        projections: Projections,
+        _filters: &[ScanFilter],
+        runtime_filters: &[ScanRuntimeFilter],
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        self.scan_remote(
+            projections,
+            None,
+            None,
+            runtime_filters.to_vec(),
+            num_partitions,
+            batch_size,
+        )
+    }
+
+    fn scan_sample(
+        &self,
+        projections: Projections,
+        sample: &TableSample,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        self.scan_remote(
+            projections,
+            Some(sample),
+            None,
+            Vec::new(),
+            num_partitions,
+            batch_size,
+        )
+    }
+
+    fn scan_limit(
+        &self,
+        projections: Projections,
+        limit: usize,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        self.scan_remote(
+            projections,
+            None,
+            Some(limit),
+            Vec::new(),
+            num_partitions,
+            batch_size,
+        )
+    }
+
+    fn supports_aggregate(&self, aggregate: &TableAggregate) -> bool {
+        aggregate::supports_aggregate(aggregate)
+    }
+
+    fn scan_aggregate(
+        &self,
+        table_aggregate: &TableAggregate,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let schema = self.schema.clone();
+        let table = self.table.clone();
+        let table_aggregate = table_aggregate.clone();
+
+        let stream = self.copy_out_stream(batch_size, Vec::new(), move |columns| {
+            let (query, typs) =
+                aggregate::aggregate_query(&table_aggregate, &columns.fields, &schema, &table)?;
+            let query = format!("COPY ({query}) TO STDOUT (FORMAT binary)");
+            let data_types = table_aggregate
+                .aggregates
+                .iter()
+                .map(|agg| agg.output_type.clone())
+                .collect();
+
+            Ok((CopyQuery::Unordered(query), typs, data_types))
+        });
+
+        let mut scans = vec![Box::new(PostgresDataTableScan { stream }) as _];
+        (1..num_partitions).for_each(|_| scans.push(Box::new(EmptyTableScan) as _));
+
+        Ok(scans)
+    }
+
+    fn statistics(&self) -> BoxFuture<'_, Result<TableStatistics>> {
+        Box::pin(async {
+            match self
+                .client
+                .get_statistics(&self.schema, &self.table)
+                .await?
+            {
+                Some(statistics) => Ok(statistics),
+                None => Err(RayexecError::new("Missing table")),
+            }
+        })
+    }
 }
 
 pub struct PostgresDataTableScan {
@@ -251,18 +514,101 @@ impl fmt::Debug for PostgresDataTableScan {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Columns of a postgres table.
+#[derive(Debug)]
+struct TableColumns {
+    fields: Vec<Field>,
+    typs: Vec<PostgresType>,
+    /// If rows in the table have a ctid that can be used to resume scans.
+    ///
+    /// Views don't have ctids, and ctids are only unique within a single
+    /// partition of a partitioned table.
+    has_ctid: bool,
+}
+
+/// Render predicates as a WHERE clause, empty if there are no predicates.
+fn where_clause(predicates: &[String]) -> String {
+    if predicates.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", predicates.join(" AND "))
+    }
+}
+
+#[derive(Clone)]
 struct PostgresClient {
     client: Arc<tokio_postgres::Client>,
-    // TODO: Runtime spawn handle
+    /// Connection string used to reconnect when resuming a COPY stream.
+    conn_str: Arc<str>,
+    handle: tokio::runtime::Handle,
+    /// Timeout for COPY streams that stop sending data, and if stalled COPYs
+    /// should be retried.
+    stall: ScanStall,
+}
+
+impl fmt::Debug for PostgresClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Connection string omitted, it may contain a password.
+        f.debug_struct("PostgresClient")
+            .field("client", &self.client)
+            .field("stall", &self.stall)
+            .finish_non_exhaustive()
+    }
 }
 
 impl PostgresClient {
+    /// Create a stream of batches from a binary COPY out of postgres.
+    ///
+    /// `open` resolves to the COPY query along with the postgres types and our
+    /// data types for the query output.
+    ///
+    /// If the connection drops, the COPY is retried on a new connection.
+    /// Queries ordered by ctid resume after the last row returned, other
+    /// queries are only retried if no rows have been returned yet.
+    ///
+    /// A COPY that stalls is retried the same way if the stall action is set
+    /// to retry, otherwise the stall fails the stream.
+    fn copy_out_stream<F>(&self, batch_size: usize, open: F) -> BoxStream<'static, Result<Batch>>
+    where
+        F: Future<Output = Result<(CopyQuery, Vec<PostgresType>, Vec<DataType>)>> + Send + 'static,
+    {
+        let client = self.clone();
+
+        let binary_copy_open = async move {
+            let (query, typs, data_types) = open.await?;
+            let state = CopyOutState::new(client, query, typs, data_types, batch_size);
+            Ok::<_, RayexecError>(state.into_stream())
+        };
+
+        let stream = binary_copy_open.try_flatten_stream().boxed();
+        if self.stall.retry() {
+            // Timeouts are applied to each attempt by the COPY state.
+            return stream;
+        }
+
+        maybe_with_stall_timeout(stream, self.stall.timeout, Some(self.handle.clone()))
+    }
+
     async fn connect<R: Runtime>(conn_str: impl Into<String>, runtime: &R) -> Result<Self> {
         let tokio_handle = runtime.tokio_handle().handle()?;
 
-        let conn_str = conn_str.into();
-        let (client, connection) = tokio_handle
+        let conn_str: Arc<str> = conn_str.into().into();
+        let client = Self::connect_client(&tokio_handle, conn_str.clone()).await?;
+
+        Ok(PostgresClient {
+            client: Arc::new(client),
+            conn_str,
+            handle: tokio_handle,
+            stall: runtime.scan_stall(),
+        })
+    }
+
+    /// Open a new connection, driving the connection on the tokio runtime.
+    async fn connect_client(
+        handle: &tokio::runtime::Handle,
+        conn_str: Arc<str>,
+    ) -> Result<tokio_postgres::Client> {
+        let (client, connection) = handle
             .spawn(async move {
                 let (client, connection) = tokio_postgres::connect(&conn_str, NoTls).await?;
                 Ok::<_, tokio_postgres::Error>((client, connection))
@@ -272,30 +618,26 @@ impl PostgresClient {
             .context("Failed to connect to postgres instance")?;
 
         // TODO: Doesn't need to be on tokio.
-        tokio_handle.spawn(async move {
+        handle.spawn(async move {
             if let Err(e) = connection.await {
                 debug!(%e, "postgres connection errored");
             }
         });
 
-        Ok(PostgresClient {
-            client: Arc::new(client),
-        })
+        Ok(client)
     }
 
-    async fn get_fields_and_types(
-        &self,
-        schema: &str,
-        name: &str,
-    ) -> Result<Option<(Vec<Field>, Vec<PostgresType>)>> {
-        // Get oid of table, and approx number of pages for the relation.
+    async fn get_fields_and_types(&self, schema: &str, name: &str) -> Result<Option<TableColumns>> {
+        // Get oid of table, approx number of pages for the relation, and if
+        // the relation has ctids (plain tables and materialized views).
         let mut rows = self
             .client
             .query(
                 "
                 SELECT
                     pg_class.oid,
-                    GREATEST(relpages, 1)
+                    GREATEST(relpages, 1),
+                    relkind IN ('r', 'm')
                 FROM pg_class INNER JOIN pg_namespace ON relnamespace = pg_namespace.oid
                 WHERE nspname=$1 AND relname=$2;
                 ",
@@ -310,6 +652,7 @@ impl PostgresClient {
             None => return Ok(None),
         };
         let oid: u32 = row.try_get(0).context("Missing OID for table")?;
+        let has_ctid: bool = row.try_get(2).context("Missing relation kind for table")?;
 
         // TODO: Get approx pages to allow us to calculate number of pages to
         // scan per thread once we do parallel scanning.
@@ -350,7 +693,100 @@ impl PostgresClient {
 
         let fields = Self::fields_from_columns(names, &pg_types)?;
 
-        Ok(Some((fields, pg_types)))
+        Ok(Some(TableColumns {
+            fields,
+            typs: pg_types,
+            has_ctid,
+        }))
+    }
+
+    /// Get the output fields and postgres types for an arbitrary query without
+    /// executing it.
+    async fn describe_query(&self, query: &str) -> Result<(Vec<Field>, Vec<PostgresType>)> {
+        let statement = self
+            .client
+            .prepare(query)
+            .await
+            .context("Failed to prepare query")?;
+
+        let columns = statement.columns();
+        if columns.is_empty() {
+            return Err(RayexecError::new("Query does not return any columns"));
+        }
+
+        let names = columns.iter().map(|col| col.name().to_string()).collect();
+        let typs: Vec<_> = columns.iter().map(|col| col.type_().clone()).collect();
+        let fields = Self::fields_from_columns(names, &typs)?;
+
+        Ok((fields, typs))
+    }
+
+    /// Get table statistics from the system catalog.
+    ///
+    /// Values are only as up to date as the last VACUUM or ANALYZE on the
+    /// table.
+    async fn get_statistics(&self, schema: &str, name: &str) -> Result<Option<TableStatistics>> {
+        let mut rows = self
+            .client
+            .query(
+                "
+                SELECT
+                    reltuples::int8,
+                    pg_table_size(pg_class.oid)
+                FROM pg_class INNER JOIN pg_namespace ON relnamespace = pg_namespace.oid
+                WHERE nspname=$1 AND relname=$2;
+                ",
+                &[&schema, &name],
+            )
+            .await
+            .context("Failed to get table statistics")?;
+        let row = match rows.pop() {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        // Tables that have never been analyzed report -1 tuples.
+        let num_rows: i64 = row.try_get(0).context("Missing tuple count")?;
+        let num_bytes: Option<i64> = row.try_get(1).context("Missing table size")?;
+
+        let estimated = |v: i64| {
+            if v >= 0 {
+                StatisticsValue::Estimated(v as usize)
+            } else {
+                StatisticsValue::Unknown
+            }
+        };
+
+        Ok(Some(TableStatistics {
+            num_rows: estimated(num_rows),
+            num_bytes: num_bytes.map(estimated).unwrap_or_default(),
+            columns: Vec::new(),
+        }))
+    }
+
+    /// List all user tables and views as (schema, name) pairs.
+    async fn list_tables(&self) -> Result<Vec<(String, String)>> {
+        let rows = self
+            .client
+            .query(
+                "
+                SELECT table_schema, table_name
+                FROM information_schema.tables
+                WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+                ORDER BY table_schema, table_name;
+                ",
+                &[],
+            )
+            .await
+            .context("Failed to list tables")?;
+
+        rows.into_iter()
+            .map(|row| {
+                let schema: String = row.try_get(0).context("Missing table schema")?;
+                let name: String = row.try_get(1).context("Missing table name")?;
+                Ok((schema, name))
+            })
+            .collect()
     }
 
     fn fields_from_columns(names: Vec<String>, typs: &[PostgresType]) -> Result<Vec<Field>> {
@@ -368,9 +804,8 @@ impl PostgresClient {
                 | &PostgresType::BPCHAR
                 | &PostgresType::VARCHAR
                 | &PostgresType::TEXT
-                | &PostgresType::JSONB
-                | &PostgresType::JSON
                 | &PostgresType::UUID => DataType::Utf8,
+                &PostgresType::JSON | &PostgresType::JSONB => DataType::Json,
                 &PostgresType::BYTEA => DataType::Binary,
                 // While postgres numerics are "unconstrained" by default, we need
                 // to specify the precision and scale for the column. Setting these
@@ -406,6 +841,8 @@ impl PostgresClient {
                 DataType::Int16 => Array::from_iter(row_iter::<i16>(&rows, idx)),
                 DataType::Int32 => Array::from_iter(row_iter::<i32>(&rows, idx)),
                 DataType::Int64 => Array::from_iter(row_iter::<i64>(&rows, idx)),
+                DataType::Float32 => Array::from_iter(row_iter::<f32>(&rows, idx)),
+                DataType::Float64 => Array::from_iter(row_iter::<f64>(&rows, idx)),
                 DataType::Decimal128(m) => {
                     let primitives = Array::from_iter(rows.iter().map(|row| {
                         let decimal = row.try_get::<PostgresDecimal>(idx).ok();
@@ -430,6 +867,11 @@ impl PostgresClient {
                     rows.iter()
                         .map(|row| -> Option<&str> { row.try_get(idx).ok() }),
                 ),
+                DataType::Json => Array::from_iter(
+                    rows.iter()
+                        .map(|row| row.try_get::<PostgresJson>(idx).ok().map(|json| json.0)),
+                )
+                .try_with_datatype(DataType::Json)?,
                 other => {
                     return Err(RayexecError::new(format!(
                         "Unimplemented data type conversion: {other:?} (postgres)"
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::datatype::{DataType, DataTypeId};
+use rayexec_execution::arrays::field::Schema;
+use rayexec_execution::arrays::scalar::OwnedScalarValue;
+use rayexec_execution::database::DatabaseContext;
+use rayexec_execution::expr;
+use rayexec_execution::functions::documentation::{Category, Documentation, Example};
+use rayexec_execution::functions::table::{
+    PlannedTableFunction,
+    ScanPlanner,
+    TableFunction,
+    TableFunctionImpl,
+    TableFunctionPlanner,
+};
+use rayexec_execution::functions::{FunctionInfo, Signature};
+use rayexec_execution::logical::statistics::StatisticsValue;
+use rayexec_execution::runtime::Runtime;
+use rayexec_execution::storage::table_storage::{
+    DataTable,
+    DataTableScan,
+    DataVersion,
+    EmptyTableScan,
+    ProjectedScan,
+    Projections,
+};
+use tokio_postgres::types::Type as PostgresType;
+
+use crate::copy::CopyQuery;
+use crate::{PostgresClient, PostgresDataTableScan};
+
+/// Execute a raw query against an attached postgres catalog, returning the
+/// results as a table.
+///
+/// The query is sent to postgres as-is, so it can use anything postgres
+/// supports (including things we'd never push down).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgExecute<R: Runtime> {
+    pub(crate) runtime: R,
+}
+
+impl<R: Runtime> FunctionInfo for PgExecute<R> {
+    fn name(&self) -> &'static str {
+        "pg_execute"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        const DOC: &Documentation = &Documentation {
+            category: Category::Table,
+            description: "Execute a query in an attached Postgres database, returning the results as a table.",
+            arguments: &["catalog", "query"],
+            example: Some(Example {
+                example: "pg_execute('my_pg', 'SELECT 1 AS a')",
+                output: "1",
+            }),
+        };
+
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8, DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Any,
+            doc: Some(DOC),
+        }]
+    }
+}
+
+impl<R: Runtime> TableFunction for PgExecute<R> {
+    fn planner(&self) -> TableFunctionPlanner {
+        TableFunctionPlanner::Scan(self)
+    }
+
+    fn is_passthrough(&self) -> bool {
+        true
+    }
+}
+
+impl<R: Runtime> ScanPlanner for PgExecute<R> {
+    fn plan<'a>(
+        &self,
+        context: &'a DatabaseContext,
+        positional_inputs: Vec<OwnedScalarValue>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> BoxFuture<'a, Result<PlannedTableFunction>> {
+        Self::plan_inner(self.clone(), context, positional_inputs, named_inputs).boxed()
+    }
+}
+
+impl<R: Runtime> PgExecute<R> {
+    async fn plan_inner(
+        self,
+        context: &DatabaseContext,
+        positional_inputs: Vec<OwnedScalarValue>,
+        named_inputs: HashMap<String, OwnedScalarValue>,
+    ) -> Result<PlannedTableFunction> {
+        if !named_inputs.is_empty() {
+            return Err(RayexecError::new(
+                "pg_execute does not accept named arguments",
+            ));
+        }
+        if positional_inputs.len() != 2 {
+            return Err(RayexecError::new("pg_execute requires 2 arguments"));
+        }
+
+        let catalog = positional_inputs.first().unwrap().try_as_str()?;
+        let query = positional_inputs.get(1).unwrap().try_as_str()?;
+
+        let conn_str = connection_string(context, catalog)?;
+        let client =
+            PostgresClient::connect(conn_str, &context.scan_runtime(&self.runtime)).await?;
+
+        let query = query.trim().trim_end_matches(';').trim_end().to_string();
+        let (fields, typs) = client.describe_query(&query).await?;
+
+        let schema = Schema::new(fields.clone());
+        let datatable = PostgresQueryTable {
+            client,
+            query,
+            data_types: fields.into_iter().map(|field| field.datatype).collect(),
+            typs,
+        };
+
+        Ok(PlannedTableFunction {
+            function: Box::new(self),
+            positional_inputs: positional_inputs.into_iter().map(expr::lit).collect(),
+            named_inputs,
+            function_impl: TableFunctionImpl::Scan(Arc::new(datatable)),
+            cardinality: StatisticsValue::Unknown,
+            schema,
+        })
+    }
+}
+
+/// Get the connection string for an attached postgres catalog.
+fn connection_string<'a>(context: &'a DatabaseContext, catalog: &str) -> Result<&'a str> {
+    let database = context.get_database(catalog)?;
+    let attach_info = match &database.attach_info {
+        Some(info) if info.datasource == "postgres" => info,
+        _ => {
+            return Err(RayexecError::new(format!(
+                "Catalog '{catalog}' is not an attached Postgres database"
+            )))
+        }
+    };
+
+    match attach_info.options.get("connection_string") {
+        Some(conn_str) => conn_str.try_as_str(),
+        None => Err(RayexecError::new(format!(
+            "Missing connection string for catalog '{catalog}'"
+        ))),
+    }
+}
+
+/// Results of a query executed by postgres.
+#[derive(Debug)]
+struct PostgresQueryTable {
+    client: PostgresClient,
+    query: String,
+    /// Our data types for the query output.
+    data_types: Vec<DataType>,
+    /// Postgres types for the query output.
+    typs: Vec<PostgresType>,
+}
+
+impl DataTable for PostgresQueryTable {
+    fn scan(
+        &self,
+        projections: Projections,
+        num_partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn DataTableScan>>> {
+        let sql = format!("COPY ({}) TO STDOUT (FORMAT binary)", self.query);
+        let typs = self.typs.clone();
+        let data_types = self.data_types.clone();
+
+        // Row order isn't deterministic for arbitrary queries.
+        let stream = self.client.copy_out_stream(batch_size, async move {
+            Ok((CopyQuery::Unordered(sql), typs, data_types))
+        });
+
+        let mut scans = vec![Box::new(ProjectedScan::new(
+            PostgresDataTableScan { stream },
+            projections,
+        )) as _];
+        (1..num_partitions).for_each(|_| scans.push(Box::new(EmptyTableScan) as _));
+
+        Ok(scans)
+    }
+
+    fn data_version(&self) -> DataVersion {
+        // The query may read anything (or not read a table at all), we can't
+        // know when its results change.
+        DataVersion::Volatile
+    }
+}
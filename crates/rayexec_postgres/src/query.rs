@@ -0,0 +1,73 @@
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::storage::table_storage::RemoteQuery;
+use tokio_postgres::types::Type as PostgresType;
+
+use crate::aggregate::output_type;
+
+/// Check if we're able to have postgres execute the query.
+///
+/// The query itself only uses syntax postgres supports, we just need to be
+/// able to decode the output.
+pub fn supports_query(query: &RemoteQuery) -> bool {
+    query.types.iter().all(|typ| output_type(typ).is_some())
+}
+
+/// Generate the COPY query for streaming out the query results, along with
+/// the postgres types of the output columns.
+pub fn copy_query(query: &RemoteQuery) -> Result<(String, Vec<PostgresType>)> {
+    let mut select_list = Vec::with_capacity(query.columns.len());
+    let mut typs = Vec::with_capacity(query.columns.len());
+
+    for (column, datatype) in query.columns.iter().zip(&query.types) {
+        let (typ, type_name) = output_type(datatype).ok_or_else(|| {
+            RayexecError::new(format!("Unsupported query output type: {datatype}"))
+        })?;
+
+        select_list.push(format!("\"{}\"::{type_name}", column.replace('"', "\"\"")));
+        typs.push(typ);
+    }
+
+    let sql = format!(
+        "COPY (SELECT {} FROM ({}) AS q) TO STDOUT (FORMAT binary)",
+        select_list.join(", "),
+        query.sql
+    );
+
+    Ok((sql, typs))
+}
+
+#[cfg(test)]
+mod tests {
+    use rayexec_execution::arrays::datatype::DataType;
+
+    use super::*;
+
+    #[test]
+    fn copy_query_casts_output() {
+        let query = RemoteQuery {
+            sql: "SELECT \"a\" AS \"t0_0\", \"c\" AS \"t0_1\" FROM \"public\".\"t1\"".to_string(),
+            columns: vec!["t0_0".to_string(), "t0_1".to_string()],
+            types: vec![DataType::Int32, DataType::Utf8],
+        };
+
+        assert!(supports_query(&query));
+
+        let (sql, typs) = copy_query(&query).unwrap();
+        assert_eq!(
+            "COPY (SELECT \"t0_0\"::int4, \"t0_1\"::text FROM (SELECT \"a\" AS \"t0_0\", \"c\" AS \"t0_1\" FROM \"public\".\"t1\") AS q) TO STDOUT (FORMAT binary)",
+            sql
+        );
+        assert_eq!(vec![PostgresType::INT4, PostgresType::TEXT], typs);
+    }
+
+    #[test]
+    fn unsupported_output_type() {
+        let query = RemoteQuery {
+            sql: "SELECT \"d\" AS \"t0_0\" FROM \"public\".\"t1\"".to_string(),
+            columns: vec!["t0_0".to_string()],
+            types: vec![DataType::Date32],
+        };
+
+        assert!(!supports_query(&query));
+    }
+}
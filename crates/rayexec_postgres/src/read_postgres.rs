@@ -17,8 +17,8 @@ use rayexec_execution::functions::table::{
     TableFunctionPlanner,
 };
 use rayexec_execution::functions::{FunctionInfo, Signature};
-use rayexec_execution::logical::statistics::StatisticsValue;
 use rayexec_execution::runtime::Runtime;
+use rayexec_execution::storage::table_storage::DataTable;
 
 use crate::{PostgresClient, PostgresDataTable};
 
@@ -62,7 +62,7 @@ impl<R: Runtime> ScanPlanner for ReadPostgres<R> {
 impl<R: Runtime> ReadPostgres<R> {
     async fn plan_inner(
         self,
-        _context: &DatabaseContext,
+        context: &DatabaseContext,
         positional_inputs: Vec<OwnedScalarValue>,
         named_inputs: HashMap<String, OwnedScalarValue>,
     ) -> Result<PlannedTableFunction> {
@@ -79,10 +79,11 @@ impl<R: Runtime> ReadPostgres<R> {
         let schema = positional_inputs.get(1).unwrap().try_as_str()?;
         let table = positional_inputs.get(2).unwrap().try_as_str()?;
 
-        let client = PostgresClient::connect(conn_str, &self.runtime).await?;
+        let client =
+            PostgresClient::connect(conn_str, &context.scan_runtime(&self.runtime)).await?;
 
         let fields = match client.get_fields_and_types(schema, table).await? {
-            Some((fields, _)) => fields,
+            Some(columns) => columns.fields,
             None => return Err(RayexecError::new("Table not found")),
         };
 
@@ -94,12 +95,14 @@ impl<R: Runtime> ReadPostgres<R> {
             table: table.to_string(),
         };
 
+        let statistics = datatable.statistics().await?;
+
         Ok(PlannedTableFunction {
             function: Box::new(self),
             positional_inputs: positional_inputs.into_iter().map(expr::lit).collect(),
             named_inputs,
             function_impl: TableFunctionImpl::Scan(Arc::new(datatable)),
-            cardinality: StatisticsValue::Unknown,
+            cardinality: statistics.num_rows,
             schema: table_schema,
         })
     }
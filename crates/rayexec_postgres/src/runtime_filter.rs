@@ -0,0 +1,86 @@
+use rayexec_execution::execution::operators::runtime_filter::RuntimeFilterValues;
+
+use crate::aggregate::{literal, quote_ident};
+
+/// Generate a predicate for a completed runtime filter on a column.
+///
+/// Keys are rendered as an IN list if there's only a few distinct keys,
+/// otherwise as a BETWEEN on the key range. Returns None if the filter can't
+/// be rendered, in which case rows are only filtered after being read.
+pub fn runtime_filter_sql(values: &RuntimeFilterValues, column: &str) -> Option<String> {
+    let column = quote_ident(column);
+
+    // Nothing on the build side, nothing can match.
+    if values.is_empty() {
+        return Some("false".to_string());
+    }
+
+    if let Some(distinct) = &values.distinct {
+        let literals: Option<Vec<_>> = distinct.iter().map(literal).collect();
+        if let Some(literals) = literals {
+            return Some(format!("{column} IN ({})", literals.join(", ")));
+        }
+    }
+
+    let (min, max) = values.range?;
+    Some(format!("{column} BETWEEN {min} AND {max}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use rayexec_execution::arrays::array::Array;
+    use rayexec_execution::execution::operators::runtime_filter::{
+        RuntimeFilterBuilder,
+        MAX_RUNTIME_FILTER_DISTINCT,
+    };
+
+    use super::*;
+
+    fn build(array: Array) -> RuntimeFilterValues {
+        let mut builder = RuntimeFilterBuilder::default();
+        builder.insert(&array).unwrap();
+        builder.finish()
+    }
+
+    #[test]
+    fn in_list() {
+        let values = build(Array::from_iter([Some(3), None, Some(3)]));
+        assert_eq!(
+            Some("\"a\" IN (3)".to_string()),
+            runtime_filter_sql(&values, "a")
+        );
+
+        let values = build(Array::from_iter(["it's"]));
+        assert_eq!(
+            Some("\"b\" IN ('it''s')".to_string()),
+            runtime_filter_sql(&values, "b")
+        );
+    }
+
+    #[test]
+    fn between() {
+        let values = build(Array::from_iter(-5..(MAX_RUNTIME_FILTER_DISTINCT as i64)));
+        assert_eq!(
+            Some(format!(
+                "\"a\" BETWEEN -5 AND {}",
+                MAX_RUNTIME_FILTER_DISTINCT - 1
+            )),
+            runtime_filter_sql(&values, "a")
+        );
+    }
+
+    #[test]
+    fn empty_build() {
+        let values = build(Array::from_iter([None as Option<i32>]));
+        assert_eq!(Some("false".to_string()), runtime_filter_sql(&values, "a"));
+    }
+
+    #[test]
+    fn unsupported() {
+        let strings: Vec<_> = (0..=MAX_RUNTIME_FILTER_DISTINCT)
+            .map(|i| i.to_string())
+            .collect();
+        let values = build(Array::from_iter(strings));
+        assert_eq!(None, runtime_filter_sql(&values, "a"));
+    }
+}
@@ -15,6 +15,7 @@ fn main() {
             "proto/foreign.proto",
             "proto/hybrid.proto",
             "proto/catalog.proto",
+            "proto/storage.proto",
         ],
         &["proto"],
     ) {
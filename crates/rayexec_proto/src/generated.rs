@@ -32,6 +32,8 @@ pub mod access {
     include!(concat!(env!("OUT_DIR"), "/rayexec.access.rs"));
 }
 
+// Resolved table references embed the full catalog entry.
+#[allow(clippy::large_enum_variant)]
 pub mod resolver {
     include!(concat!(env!("OUT_DIR"), "/rayexec.resolver.rs"));
 }
@@ -57,3 +59,7 @@ pub mod hybrid {
 pub mod catalog {
     include!(concat!(env!("OUT_DIR"), "/rayexec.catalog.rs"));
 }
+
+pub mod storage {
+    include!(concat!(env!("OUT_DIR"), "/rayexec.storage.rs"));
+}
@@ -0,0 +1,861 @@
+//! Conversion to and from the Arrow C Data Interface.
+//!
+//! This lets us hand off results to pyarrow (and everything built on top of
+//! it, like pandas and polars) without going through IPC, and lets us read
+//! data frames from those libraries.
+//!
+//! See: <https://arrow.apache.org/docs/format/CDataInterface.html>
+use std::ffi::{c_char, c_void, CStr, CString};
+
+use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_execution::arrays::array::{Array, ArrayData, BinaryData};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::bitmap::Bitmap;
+use rayexec_execution::arrays::datatype::{DataType, DecimalTypeMeta, TimeUnit, TimestampTypeMeta};
+use rayexec_execution::arrays::field::Field;
+use rayexec_execution::arrays::scalar::interval::Interval;
+use rayexec_execution::arrays::storage::{BooleanStorage, GermanVarlenStorage, PrimitiveStorage};
+
+/// `ArrowSchema` from the C Data Interface.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowSchema {
+    format: *const c_char,
+    name: *const c_char,
+    metadata: *const c_char,
+    flags: i64,
+    n_children: i64,
+    children: *mut *mut ArrowSchema,
+    dictionary: *mut ArrowSchema,
+    release: Option<unsafe extern "C" fn(schema: *mut ArrowSchema)>,
+    private_data: *mut c_void,
+}
+
+/// `ArrowArray` from the C Data Interface.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowArray {
+    length: i64,
+    null_count: i64,
+    offset: i64,
+    n_buffers: i64,
+    n_children: i64,
+    buffers: *mut *const c_void,
+    children: *mut *mut ArrowArray,
+    dictionary: *mut ArrowArray,
+    release: Option<unsafe extern "C" fn(array: *mut ArrowArray)>,
+    private_data: *mut c_void,
+}
+
+/// Flag indicating the field is nullable.
+const ARROW_FLAG_NULLABLE: i64 = 2;
+
+impl ArrowSchema {
+    /// Create an empty, released schema.
+    ///
+    /// Used as the destination when importing from another library.
+    pub const fn empty() -> Self {
+        ArrowSchema {
+            format: std::ptr::null(),
+            name: std::ptr::null(),
+            metadata: std::ptr::null(),
+            flags: 0,
+            n_children: 0,
+            children: std::ptr::null_mut(),
+            dictionary: std::ptr::null_mut(),
+            release: None,
+            private_data: std::ptr::null_mut(),
+        }
+    }
+
+    fn format(&self) -> Result<&str> {
+        if self.format.is_null() {
+            return Err(RayexecError::new("Arrow schema missing format"));
+        }
+        unsafe { CStr::from_ptr(self.format) }
+            .to_str()
+            .map_err(|_| RayexecError::new("Arrow schema format not valid utf8"))
+    }
+
+    fn name(&self) -> Result<String> {
+        if self.name.is_null() {
+            return Ok(String::new());
+        }
+        let name = unsafe { CStr::from_ptr(self.name) }
+            .to_str()
+            .map_err(|_| RayexecError::new("Arrow schema name not valid utf8"))?;
+        Ok(name.to_string())
+    }
+
+    fn child(&self, idx: usize) -> Result<&ArrowSchema> {
+        if idx >= self.n_children as usize {
+            return Err(RayexecError::new(format!(
+                "Arrow schema child out of bounds: {idx}"
+            )));
+        }
+        Ok(unsafe { &**self.children.add(idx) })
+    }
+}
+
+impl Drop for ArrowSchema {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self) }
+        }
+    }
+}
+
+impl ArrowArray {
+    /// Create an empty, released array.
+    ///
+    /// Used as the destination when importing from another library.
+    pub const fn empty() -> Self {
+        ArrowArray {
+            length: 0,
+            null_count: 0,
+            offset: 0,
+            n_buffers: 0,
+            n_children: 0,
+            buffers: std::ptr::null_mut(),
+            children: std::ptr::null_mut(),
+            dictionary: std::ptr::null_mut(),
+            release: None,
+            private_data: std::ptr::null_mut(),
+        }
+    }
+
+    fn buffer(&self, idx: usize) -> Result<*const u8> {
+        if idx >= self.n_buffers as usize {
+            return Err(RayexecError::new(format!(
+                "Arrow array buffer out of bounds: {idx}"
+            )));
+        }
+        Ok(unsafe { *self.buffers.add(idx) } as *const u8)
+    }
+
+    fn child(&self, idx: usize) -> Result<&ArrowArray> {
+        if idx >= self.n_children as usize {
+            return Err(RayexecError::new(format!(
+                "Arrow array child out of bounds: {idx}"
+            )));
+        }
+        Ok(unsafe { &**self.children.add(idx) })
+    }
+}
+
+impl Drop for ArrowArray {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self) }
+        }
+    }
+}
+
+/// Private data for exported schemas.
+struct SchemaPrivateData {
+    _format: CString,
+    _name: CString,
+    children: Vec<*mut ArrowSchema>,
+}
+
+unsafe extern "C" fn release_schema(schema: *mut ArrowSchema) {
+    if schema.is_null() {
+        return;
+    }
+    let schema = &mut *schema;
+    let private = Box::from_raw(schema.private_data as *mut SchemaPrivateData);
+    for child in private.children.iter() {
+        // Dropping calls the child's release callback.
+        drop(Box::from_raw(*child));
+    }
+    schema.release = None;
+}
+
+/// Byte buffer with 8-byte alignment.
+///
+/// Arrow recommends (and some consumers expect) aligned buffers.
+struct AlignedBuffer {
+    data: Vec<u64>,
+}
+
+impl AlignedBuffer {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let mut data = vec![0_u64; bytes.len().div_ceil(8)];
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), data.as_mut_ptr().cast(), bytes.len())
+        };
+        AlignedBuffer { data }
+    }
+
+    fn as_ptr(&self) -> *const c_void {
+        self.data.as_ptr().cast()
+    }
+}
+
+/// Private data for exported arrays.
+struct ArrayPrivateData {
+    _buffers: Vec<Option<AlignedBuffer>>,
+    buffer_ptrs: Vec<*const c_void>,
+    children: Vec<*mut ArrowArray>,
+}
+
+unsafe extern "C" fn release_array(array: *mut ArrowArray) {
+    if array.is_null() {
+        return;
+    }
+    let array = &mut *array;
+    let private = Box::from_raw(array.private_data as *mut ArrayPrivateData);
+    for child in private.children.iter() {
+        drop(Box::from_raw(*child));
+    }
+    array.release = None;
+}
+
+fn new_exported_schema(format: &str, name: &str, children: Vec<ArrowSchema>) -> ArrowSchema {
+    let format = CString::new(format).expect("format to not contain null bytes");
+    let name = CString::new(name.replace('\0', "")).expect("null bytes to be removed");
+
+    let mut private = Box::new(SchemaPrivateData {
+        _format: format,
+        _name: name,
+        children: children
+            .into_iter()
+            .map(|c| Box::into_raw(Box::new(c)))
+            .collect(),
+    });
+
+    ArrowSchema {
+        format: private._format.as_ptr(),
+        name: private._name.as_ptr(),
+        metadata: std::ptr::null(),
+        flags: ARROW_FLAG_NULLABLE,
+        n_children: private.children.len() as i64,
+        children: private.children.as_mut_ptr(),
+        dictionary: std::ptr::null_mut(),
+        release: Some(release_schema),
+        private_data: Box::into_raw(private) as *mut c_void,
+    }
+}
+
+fn new_exported_array(
+    length: usize,
+    null_count: usize,
+    buffers: Vec<Option<AlignedBuffer>>,
+    children: Vec<ArrowArray>,
+) -> ArrowArray {
+    let buffer_ptrs = buffers
+        .iter()
+        .map(|b| match b {
+            Some(b) => b.as_ptr(),
+            None => std::ptr::null(),
+        })
+        .collect();
+
+    let mut private = Box::new(ArrayPrivateData {
+        _buffers: buffers,
+        buffer_ptrs,
+        children: children
+            .into_iter()
+            .map(|c| Box::into_raw(Box::new(c)))
+            .collect(),
+    });
+
+    ArrowArray {
+        length: length as i64,
+        null_count: null_count as i64,
+        offset: 0,
+        n_buffers: private.buffer_ptrs.len() as i64,
+        n_children: private.children.len() as i64,
+        buffers: private.buffer_ptrs.as_mut_ptr(),
+        children: private.children.as_mut_ptr(),
+        dictionary: std::ptr::null_mut(),
+        release: Some(release_array),
+        private_data: Box::into_raw(private) as *mut c_void,
+    }
+}
+
+/// Get the arrow format string for a data type.
+fn format_for_datatype(datatype: &DataType) -> Result<String> {
+    Ok(match datatype {
+        DataType::Null => "n".to_string(),
+        DataType::Boolean => "b".to_string(),
+        DataType::Int8 => "c".to_string(),
+        DataType::Int16 => "s".to_string(),
+        DataType::Int32 => "i".to_string(),
+        DataType::Int64 => "l".to_string(),
+        DataType::UInt8 => "C".to_string(),
+        DataType::UInt16 => "S".to_string(),
+        DataType::UInt32 => "I".to_string(),
+        DataType::UInt64 => "L".to_string(),
+        DataType::Float16 => "e".to_string(),
+        DataType::Float32 => "f".to_string(),
+        DataType::Float64 => "g".to_string(),
+        // Decimal64 values are widened to 128 bits on export.
+        DataType::Decimal64(m) | DataType::Decimal128(m) => {
+            format!("d:{},{}", m.precision, m.scale)
+        }
+        DataType::Timestamp(m) => match m.unit {
+            TimeUnit::Second => "tss:".to_string(),
+            TimeUnit::Millisecond => "tsm:".to_string(),
+            TimeUnit::Microsecond => "tsu:".to_string(),
+            TimeUnit::Nanosecond => "tsn:".to_string(),
+        },
+        DataType::Date32 => "tdD".to_string(),
+        DataType::Date64 => "tdm".to_string(),
+        DataType::Interval => "tin".to_string(),
+        // Always exported with 64 bit offsets, a single batch can hold more
+        // than 2 GiB of string data.
+        DataType::Utf8 | DataType::Json => "U".to_string(),
+        DataType::Binary | DataType::Geometry => "Z".to_string(),
+        other => not_implemented!("Export {other} to arrow"),
+    })
+}
+
+/// Get the data type for an arrow format string.
+fn datatype_for_format(format: &str) -> Result<DataType> {
+    Ok(match format {
+        "n" => DataType::Null,
+        "b" => DataType::Boolean,
+        "c" => DataType::Int8,
+        "s" => DataType::Int16,
+        "i" => DataType::Int32,
+        "l" => DataType::Int64,
+        "C" => DataType::UInt8,
+        "S" => DataType::UInt16,
+        "I" => DataType::UInt32,
+        "L" => DataType::UInt64,
+        "e" => DataType::Float16,
+        "f" => DataType::Float32,
+        "g" => DataType::Float64,
+        "tdD" => DataType::Date32,
+        "tdm" => DataType::Date64,
+        "tin" => DataType::Interval,
+        "u" | "U" | "vu" => DataType::Utf8,
+        "z" | "Z" | "vz" => DataType::Binary,
+        other if other.starts_with("ts") => {
+            // Timezone (if any) is ignored.
+            let unit = match other.as_bytes().get(2) {
+                Some(b's') => TimeUnit::Second,
+                Some(b'm') => TimeUnit::Millisecond,
+                Some(b'u') => TimeUnit::Microsecond,
+                Some(b'n') => TimeUnit::Nanosecond,
+                _ => {
+                    return Err(RayexecError::new(format!(
+                        "Invalid timestamp format: {other}"
+                    )))
+                }
+            };
+            DataType::Timestamp(TimestampTypeMeta::new(unit))
+        }
+        other if other.starts_with("d:") => {
+            let parts: Vec<_> = other[2..].split(',').collect();
+            if parts.len() == 3 && parts[2] != "128" {
+                not_implemented!("Import decimal with bit width {}", parts[2]);
+            }
+            let (precision, scale) = match (parts.first(), parts.get(1)) {
+                (Some(p), Some(s)) => (
+                    p.parse::<u8>()
+                        .map_err(|_| RayexecError::new(format!("Invalid decimal: {other}")))?,
+                    s.parse::<i8>()
+                        .map_err(|_| RayexecError::new(format!("Invalid decimal: {other}")))?,
+                ),
+                _ => return Err(RayexecError::new(format!("Invalid decimal: {other}"))),
+            };
+            DataType::Decimal128(DecimalTypeMeta::new(precision, scale))
+        }
+        other => not_implemented!("Import arrow format '{other}'"),
+    })
+}
+
+/// Export the schema of a set of fields as a struct schema.
+pub fn export_schema(fields: &[Field]) -> Result<ArrowSchema> {
+    let children = fields
+        .iter()
+        .map(|f| {
+            let format = format_for_datatype(&f.datatype)?;
+            Ok(new_exported_schema(&format, &f.name, Vec::new()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(new_exported_schema("+s", "", children))
+}
+
+/// Export a batch as a struct array.
+pub fn export_batch(batch: &Batch) -> Result<ArrowArray> {
+    let children = batch
+        .columns()
+        .iter()
+        .map(export_array)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(new_exported_array(
+        batch.num_rows(),
+        0,
+        vec![None],
+        children,
+    ))
+}
+
+fn export_array(array: &Array) -> Result<ArrowArray> {
+    // Arrow has no concept of selection vectors.
+    let array = array.unselect()?;
+    let len = array.logical_len();
+
+    let (validity, null_count) = match array.validity() {
+        Some(validity) => (
+            Some(AlignedBuffer::from_bytes(validity.as_bytes())),
+            validity.count_falses(),
+        ),
+        None => (None, 0),
+    };
+
+    let mut buffers = vec![validity];
+
+    match array.array_data() {
+        ArrayData::UntypedNull(_) => {
+            return Ok(new_exported_array(len, len, Vec::new(), Vec::new()))
+        }
+        ArrayData::Boolean(s) => {
+            let bits: &Bitmap = s.as_ref().as_ref();
+            buffers.push(Some(AlignedBuffer::from_bytes(bits.as_bytes())))
+        }
+        ArrayData::Int8(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::Int16(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::Int32(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::Int64(s) => match array.datatype() {
+            DataType::Decimal64(_) => {
+                let widened: PrimitiveStorage<i128> = s
+                    .as_slice()
+                    .iter()
+                    .map(|&v| v as i128)
+                    .collect::<Vec<_>>()
+                    .into();
+                buffers.push(Some(AlignedBuffer::from_bytes(widened.as_bytes())))
+            }
+            _ => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        },
+        ArrayData::Int128(s) => match array.datatype() {
+            DataType::Decimal128(_) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+            other => not_implemented!("Export {other} to arrow"),
+        },
+        ArrayData::UInt8(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::UInt16(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::UInt32(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::UInt64(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::Float16(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::Float32(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::Float64(s) => buffers.push(Some(AlignedBuffer::from_bytes(s.as_bytes()))),
+        ArrayData::Interval(s) => {
+            // Arrow's month_day_nano layout, our struct isn't repr(C) so write
+            // it out explicitly.
+            let mut bytes = Vec::with_capacity(s.len() * 16);
+            for interval in s.as_slice() {
+                bytes.extend_from_slice(&interval.months.to_ne_bytes());
+                bytes.extend_from_slice(&interval.days.to_ne_bytes());
+                bytes.extend_from_slice(&interval.nanos.to_ne_bytes());
+            }
+            buffers.push(Some(AlignedBuffer::from_bytes(&bytes)))
+        }
+        ArrayData::Binary(b) => {
+            let (offsets, data) = match b {
+                BinaryData::Binary(s) => contiguous_offsets_and_data(s.iter(), len),
+                BinaryData::LargeBinary(s) => contiguous_offsets_and_data(s.iter(), len),
+                BinaryData::German(s) => contiguous_offsets_and_data(s.iter(), len),
+            };
+            buffers.push(Some(AlignedBuffer::from_bytes(offsets.as_bytes())));
+            buffers.push(Some(AlignedBuffer::from_bytes(&data)));
+        }
+        ArrayData::UInt128(_) | ArrayData::List(_) => {
+            not_implemented!("Export {} to arrow", array.datatype())
+        }
+    }
+
+    Ok(new_exported_array(len, null_count, buffers, Vec::new()))
+}
+
+/// Build i64 offsets and a contiguous data buffer for varlen values.
+fn contiguous_offsets_and_data<'a>(
+    values: impl Iterator<Item = &'a [u8]>,
+    len: usize,
+) -> (PrimitiveStorage<i64>, Vec<u8>) {
+    let mut offsets = Vec::with_capacity(len + 1);
+    let mut data = Vec::new();
+    offsets.push(0);
+
+    for value in values {
+        data.extend_from_slice(value);
+        offsets.push(data.len() as i64);
+    }
+
+    (offsets.into(), data)
+}
+
+/// Import fields from a struct schema.
+///
+/// # Safety
+///
+/// The schema must have been populated by a conforming producer.
+pub unsafe fn import_schema(schema: &ArrowSchema) -> Result<Vec<Field>> {
+    if schema.format()? != "+s" {
+        return Err(RayexecError::new(format!(
+            "Expected struct schema, got format '{}'",
+            schema.format()?
+        )));
+    }
+
+    (0..(schema.n_children as usize))
+        .map(|idx| {
+            let child = schema.child(idx)?;
+            let datatype = datatype_for_format(child.format()?)?;
+            Ok(Field::new(child.name()?, datatype, true))
+        })
+        .collect()
+}
+
+/// Import a batch from a struct array and its schema.
+///
+/// # Safety
+///
+/// The array and schema must have been populated by a conforming producer.
+pub unsafe fn import_batch(
+    array: &ArrowArray,
+    schema: &ArrowSchema,
+) -> Result<(Vec<Field>, Batch)> {
+    let fields = import_schema(schema)?;
+
+    let columns = fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let format = schema.child(idx)?.format()?;
+            import_array(
+                array.child(idx)?,
+                format,
+                &field.datatype,
+                array.offset as usize,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let batch = if columns.is_empty() {
+        Batch::empty_with_num_rows(array.length as usize)
+    } else {
+        Batch::try_new(columns)?
+    };
+
+    Ok((fields, batch))
+}
+
+/// Get a byte slice for a buffer holding `count` values of `T`, starting at
+/// `offset` values.
+unsafe fn buffer_bytes<T>(
+    array: &ArrowArray,
+    idx: usize,
+    offset: usize,
+    count: usize,
+) -> Result<&[u8]> {
+    let ptr = array.buffer(idx)?;
+    if ptr.is_null() {
+        if count == 0 {
+            return Ok(&[]);
+        }
+        return Err(RayexecError::new(format!("Arrow buffer {idx} is null")));
+    }
+    let size = std::mem::size_of::<T>();
+    Ok(std::slice::from_raw_parts(
+        ptr.add(offset * size),
+        count * size,
+    ))
+}
+
+/// Read `len` bits starting at bit `offset` from a buffer.
+unsafe fn read_bitmap(array: &ArrowArray, idx: usize, offset: usize, len: usize) -> Result<Bitmap> {
+    let ptr = array.buffer(idx)?;
+    if ptr.is_null() {
+        // Producers may omit buffers for empty arrays.
+        if len == 0 {
+            return Ok(Bitmap::default());
+        }
+        return Err(RayexecError::new(format!("Arrow buffer {idx} is null")));
+    }
+    let bytes = std::slice::from_raw_parts(ptr, (offset + len).div_ceil(8));
+    Ok(Bitmap::from_iter(
+        (offset..(offset + len)).map(|i| (bytes[i >> 3] >> (i & 7)) & 1 != 0),
+    ))
+}
+
+unsafe fn import_primitive<T>(
+    array: &ArrowArray,
+    offset: usize,
+    len: usize,
+) -> Result<PrimitiveStorage<T>>
+where
+    T: Default + Copy,
+{
+    PrimitiveStorage::<T>::copy_from_bytes(buffer_bytes::<T>(array, 1, offset, len)?)
+}
+
+unsafe fn import_array(
+    array: &ArrowArray,
+    format: &str,
+    datatype: &DataType,
+    parent_offset: usize,
+) -> Result<Array> {
+    let len = array.length as usize;
+    let offset = array.offset as usize + parent_offset;
+
+    if datatype == &DataType::Null {
+        return Ok(Array::new_untyped_null_array(len));
+    }
+
+    let validity = if array.null_count != 0 && !array.buffer(0)?.is_null() {
+        Some(read_bitmap(array, 0, offset, len)?)
+    } else {
+        None
+    };
+
+    let data: ArrayData = match format {
+        "b" => BooleanStorage::from(read_bitmap(array, 1, offset, len)?).into(),
+        "c" => import_primitive::<i8>(array, offset, len)?.into(),
+        "s" => import_primitive::<i16>(array, offset, len)?.into(),
+        "i" | "tdD" => import_primitive::<i32>(array, offset, len)?.into(),
+        "l" | "tdm" => import_primitive::<i64>(array, offset, len)?.into(),
+        "C" => import_primitive::<u8>(array, offset, len)?.into(),
+        "S" => import_primitive::<u16>(array, offset, len)?.into(),
+        "I" => import_primitive::<u32>(array, offset, len)?.into(),
+        "L" => import_primitive::<u64>(array, offset, len)?.into(),
+        "e" => import_primitive::<half::f16>(array, offset, len)?.into(),
+        "f" => import_primitive::<f32>(array, offset, len)?.into(),
+        "g" => import_primitive::<f64>(array, offset, len)?.into(),
+        "tin" => import_intervals(array, offset, len)?.into(),
+        "u" | "z" => {
+            let offsets = import_offsets::<i32>(array, offset, len)?;
+            import_varlen(array, &offsets, len)?.into()
+        }
+        "U" | "Z" => {
+            let offsets = import_offsets::<i64>(array, offset, len)?;
+            import_varlen(array, &offsets, len)?.into()
+        }
+        "vu" | "vz" => import_views(array, offset, len)?.into(),
+        other if other.starts_with("ts") => import_primitive::<i64>(array, offset, len)?.into(),
+        other if other.starts_with("d:") => import_primitive::<i128>(array, offset, len)?.into(),
+        other => not_implemented!("Import arrow format '{other}'"),
+    };
+
+    Ok(match validity {
+        Some(validity) => Array::new_with_validity_and_array_data(datatype.clone(), validity, data),
+        None => Array::new_with_array_data(datatype.clone(), data),
+    })
+}
+
+unsafe fn import_intervals(
+    array: &ArrowArray,
+    offset: usize,
+    len: usize,
+) -> Result<PrimitiveStorage<Interval>> {
+    let bytes = buffer_bytes::<u128>(array, 1, offset, len)?;
+    let intervals: Vec<_> = bytes
+        .chunks_exact(16)
+        .map(|b| Interval {
+            months: i32::from_ne_bytes(b[0..4].try_into().unwrap()),
+            days: i32::from_ne_bytes(b[4..8].try_into().unwrap()),
+            nanos: i64::from_ne_bytes(b[8..16].try_into().unwrap()),
+        })
+        .collect();
+    Ok(intervals.into())
+}
+
+unsafe fn import_offsets<O>(array: &ArrowArray, offset: usize, len: usize) -> Result<Vec<usize>>
+where
+    O: Default + Copy + TryInto<usize>,
+{
+    let offsets =
+        PrimitiveStorage::<O>::copy_from_bytes(buffer_bytes::<O>(array, 1, offset, len + 1)?)?;
+    offsets
+        .as_slice()
+        .iter()
+        .map(|&o| {
+            o.try_into()
+                .map_err(|_| RayexecError::new("Invalid arrow offset"))
+        })
+        .collect()
+}
+
+unsafe fn import_varlen(
+    array: &ArrowArray,
+    offsets: &[usize],
+    len: usize,
+) -> Result<GermanVarlenStorage> {
+    let data_len = offsets.last().copied().unwrap_or(0);
+    let data = buffer_bytes::<u8>(array, 2, 0, data_len)?;
+
+    let mut storage = GermanVarlenStorage::with_metadata_capacity(len);
+    for window in offsets.windows(2) {
+        storage.try_push(&data[window[0]..window[1]])?;
+    }
+
+    Ok(storage)
+}
+
+/// Import a string or binary view array.
+///
+/// Arrow's view layout is the same as our german layout, but data may be spread
+/// across multiple variadic buffers.
+unsafe fn import_views(
+    array: &ArrowArray,
+    offset: usize,
+    len: usize,
+) -> Result<GermanVarlenStorage> {
+    let views = buffer_bytes::<u128>(array, 1, offset, len)?;
+
+    let mut storage = GermanVarlenStorage::with_metadata_capacity(len);
+    for view in views.chunks_exact(16) {
+        let view_len = i32::from_le_bytes(view[0..4].try_into().unwrap()) as usize;
+        if view_len <= 12 {
+            storage.try_push(&view[4..(4 + view_len)])?;
+        } else {
+            let buffer_idx = i32::from_le_bytes(view[8..12].try_into().unwrap()) as usize;
+            let buf_offset = i32::from_le_bytes(view[12..16].try_into().unwrap()) as usize;
+            // Variadic data buffers start after validity and views.
+            let ptr = array.buffer(2 + buffer_idx)?;
+            let value = std::slice::from_raw_parts(ptr.add(buf_offset), view_len);
+            storage.try_push(value)?;
+        }
+    }
+
+    Ok(storage)
+}
+
+/// Wrapper for holding exported arrays that get moved into python.
+#[derive(Debug)]
+pub struct ExportedBatch {
+    pub array: Box<ArrowArray>,
+    pub schema: Box<ArrowSchema>,
+}
+
+impl ExportedBatch {
+    pub fn try_new(fields: &[Field], batch: &Batch) -> Result<Self> {
+        Ok(ExportedBatch {
+            array: Box::new(export_batch(batch)?),
+            schema: Box::new(export_schema(fields)?),
+        })
+    }
+
+    pub fn array_addr(&mut self) -> usize {
+        self.array.as_mut() as *mut ArrowArray as usize
+    }
+
+    pub fn schema_addr(&mut self) -> usize {
+        self.schema.as_mut() as *mut ArrowSchema as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayexec_execution::arrays::scalar::ScalarValue;
+
+    use super::*;
+
+    fn test_fields() -> Vec<Field> {
+        vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Boolean, true),
+        ]
+    }
+
+    fn test_batch() -> Batch {
+        Batch::try_new([
+            Array::from_iter([Some(1_i64), None, Some(3)]),
+            Array::from_iter([Some("hello"), Some("a string longer than 12 bytes"), None]),
+            Array::from_iter([true, false, true]),
+        ])
+        .unwrap()
+    }
+
+    /// Get the child array at `idx` of an exported struct array.
+    fn child_mut(array: &mut ArrowArray, idx: usize) -> &mut ArrowArray {
+        unsafe { &mut **array.children.add(idx) }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let fields = test_fields();
+        let batch = test_batch();
+
+        let array = export_batch(&batch).unwrap();
+        let schema = export_schema(&fields).unwrap();
+        let (got_fields, got) = unsafe { import_batch(&array, &schema).unwrap() };
+
+        assert_eq!(fields, got_fields);
+        assert_eq!(3, got.num_rows());
+        for col in 0..3 {
+            for row in 0..3 {
+                assert_eq!(
+                    batch.column(col).unwrap().logical_value(row).unwrap(),
+                    got.column(col).unwrap().logical_value(row).unwrap(),
+                    "col: {col}, row: {row}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn strings_exported_with_large_offsets() {
+        let schema = export_schema(&test_fields()).unwrap();
+        assert_eq!("U", schema.child(1).unwrap().format().unwrap());
+
+        let array = export_batch(&test_batch()).unwrap();
+        let strings = array.child(1).unwrap();
+        let offsets = unsafe { buffer_bytes::<i64>(strings, 1, 0, 4).unwrap() };
+        let offsets: Vec<_> = offsets
+            .chunks_exact(8)
+            .map(|b| i64::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(vec![0, 5, 34, 34], offsets);
+    }
+
+    #[test]
+    fn import_missing_validity_with_unknown_null_count() {
+        let fields = vec![Field::new("a", DataType::Int64, true)];
+        let batch = Batch::try_new([Array::from_iter([1_i64, 2, 3])]).unwrap();
+
+        let mut array = export_batch(&batch).unwrap();
+        let schema = export_schema(&fields).unwrap();
+        // Null count unknown, but no validity buffer means everything's valid.
+        child_mut(&mut array, 0).null_count = -1;
+
+        let (_, got) = unsafe { import_batch(&array, &schema).unwrap() };
+        assert_eq!(
+            ScalarValue::Int64(2),
+            got.column(0).unwrap().logical_value(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn import_empty_bool_with_null_buffers() {
+        let fields = vec![Field::new("a", DataType::Boolean, true)];
+        let batch = Batch::try_new([Array::from_iter(Vec::<bool>::new())]).unwrap();
+
+        let mut array = export_batch(&batch).unwrap();
+        let schema = export_schema(&fields).unwrap();
+        let child = child_mut(&mut array, 0);
+        unsafe { *child.buffers.add(1) = std::ptr::null() };
+
+        let (_, got) = unsafe { import_batch(&array, &schema).unwrap() };
+        assert_eq!(0, got.num_rows());
+    }
+
+    #[test]
+    fn import_null_values_buffer_errors() {
+        let fields = vec![Field::new("a", DataType::Boolean, true)];
+        let batch = Batch::try_new([Array::from_iter([true, false])]).unwrap();
+
+        let mut array = export_batch(&batch).unwrap();
+        let schema = export_schema(&fields).unwrap();
+        let child = child_mut(&mut array, 0);
+        unsafe { *child.buffers.add(1) = std::ptr::null() };
+
+        unsafe { import_batch(&array, &schema).unwrap_err() };
+    }
+}
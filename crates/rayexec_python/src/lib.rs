@@ -1,8 +1,10 @@
+mod arrow;
 mod errors;
 mod event_loop;
 mod print;
 mod session;
 mod table;
+mod udf;
 
 use pyo3::types::{PyModule, PyModuleMethods};
 use pyo3::{pymodule, wrap_pyfunction, Bound, PyResult};
@@ -1,15 +1,21 @@
-use pyo3::{pyclass, pyfunction, pymethods, Python};
+use pyo3::types::{PyAnyMethods, PyModule};
+use pyo3::{pyclass, pyfunction, pymethods, Bound, Py, PyAny, Python};
 use rayexec_csv::CsvDataSource;
 use rayexec_delta::DeltaDataSource;
 use rayexec_error::RayexecError;
 use rayexec_execution::datasource::{DataSourceBuilder, DataSourceRegistry, MemoryDataSource};
+use rayexec_lance::LanceDataSource;
+use rayexec_orc::OrcDataSource;
 use rayexec_parquet::ParquetDataSource;
 use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
 use rayexec_shell::session::SingleUserEngine;
+use rayexec_spatial::SpatialDataSource;
 
+use crate::arrow::{import_batch, import_schema, ArrowArray, ArrowSchema};
 use crate::errors::Result;
 use crate::event_loop::run_until_complete;
 use crate::table::PythonMaterializedResultTable;
+use crate::udf::{parse_datatype, PythonScalarFunction};
 
 #[pyfunction]
 pub fn connect() -> Result<PythonSession> {
@@ -18,8 +24,11 @@ pub fn connect() -> Result<PythonSession> {
     let registry = DataSourceRegistry::default()
         .with_datasource("memory", Box::new(MemoryDataSource))?
         .with_datasource("parquet", ParquetDataSource::initialize(runtime.clone()))?
+        .with_datasource("orc", OrcDataSource::initialize(runtime.clone()))?
+        .with_datasource("lance", LanceDataSource::initialize(runtime.clone()))?
         .with_datasource("csv", CsvDataSource::initialize(runtime.clone()))?
-        .with_datasource("delta", DeltaDataSource::initialize(runtime.clone()))?;
+        .with_datasource("delta", DeltaDataSource::initialize(runtime.clone()))?
+        .with_datasource("spatial", SpatialDataSource::initialize(runtime.clone()))?;
 
     let executor = ThreadedNativeExecutor::try_new()?;
     let engine = SingleUserEngine::try_new(executor, runtime.clone(), registry)?;
@@ -65,6 +74,105 @@ impl PythonSession {
         Ok(table)
     }
 
+    /// Runs a single query, returning the results.
+    ///
+    /// Alias for `query`.
+    #[pyo3(signature = (sql, collect_profile_data=false, /))]
+    fn sql(
+        &mut self,
+        py: Python,
+        sql: String,
+        collect_profile_data: bool,
+    ) -> Result<PythonMaterializedResultTable> {
+        self.query(py, sql, collect_profile_data)
+    }
+
+    /// Register a python callable as a scalar function.
+    ///
+    /// The callable is called once per batch of rows with a list of values
+    /// for each argument, and must return a list with a value for each row.
+    /// Argument and return types are provided as type names (e.g. "int64",
+    /// "utf8").
+    #[pyo3(signature = (name, func, arg_types, return_type))]
+    fn register_udf(
+        &mut self,
+        py: Python,
+        name: String,
+        func: Py<PyAny>,
+        arg_types: Vec<String>,
+        return_type: String,
+    ) -> Result<()> {
+        if !func.bind(py).is_callable() {
+            return Err(RayexecError::new(format!("Function for '{name}' is not callable")).into());
+        }
+
+        let arg_types = arg_types
+            .iter()
+            .map(|t| parse_datatype(t))
+            .collect::<rayexec_error::Result<Vec<_>>>()?;
+        let return_type = parse_datatype(&return_type)?;
+
+        let function = PythonScalarFunction::new(name, func, arg_types, return_type);
+        let session = self.try_get_engine()?.session().clone();
+        run_until_complete(py, async move {
+            session.register_scalar_function(Box::new(function)).await?;
+            Ok(())
+        })
+    }
+
+    /// Register a data frame as a temporary table.
+    ///
+    /// Accepts pyarrow tables, polars data frames, and pandas data frames.
+    fn register_dataframe(&mut self, py: Python, name: String, df: Bound<'_, PyAny>) -> Result<()> {
+        let pa = PyModule::import(py, "pyarrow")?;
+
+        let table = if df.is_instance(&pa.getattr("Table")?)? {
+            df
+        } else if df.hasattr("to_arrow")? {
+            // Polars
+            df.call_method0("to_arrow")?
+        } else {
+            // Assume pandas
+            pa.getattr("Table")?.call_method1("from_pandas", (df,))?
+        };
+
+        let mut fields = Vec::new();
+        let mut batches = Vec::new();
+
+        for pa_batch in table.call_method0("to_batches")?.try_iter()? {
+            let mut array = Box::new(ArrowArray::empty());
+            let mut schema = Box::new(ArrowSchema::empty());
+            pa_batch?.call_method1(
+                "_export_to_c",
+                (
+                    array.as_mut() as *mut _ as usize,
+                    schema.as_mut() as *mut _ as usize,
+                ),
+            )?;
+
+            // SAFETY: Populated by pyarrow. Both get released on drop.
+            let (batch_fields, batch) = unsafe { import_batch(&array, &schema)? };
+            fields = batch_fields;
+            batches.push(batch);
+        }
+
+        if batches.is_empty() {
+            let mut schema = Box::new(ArrowSchema::empty());
+            table
+                .getattr("schema")?
+                .call_method1("_export_to_c", (schema.as_mut() as *mut _ as usize,))?;
+            // SAFETY: Populated by pyarrow, released on drop.
+            let schema_fields = unsafe { import_schema(&schema)? };
+            fields = schema_fields;
+        }
+
+        let session = self.try_get_engine()?.session().clone();
+        run_until_complete(py, async move {
+            session.register_table(&name, fields, batches).await?;
+            Ok(())
+        })
+    }
+
     fn close(&mut self, _py: Python) -> Result<()> {
         match self.engine.take() {
             Some(_) => {
@@ -1,6 +1,8 @@
-use pyo3::{pyclass, pymethods, Python};
+use pyo3::types::{PyAnyMethods, PyModule};
+use pyo3::{pyclass, pymethods, PyObject, Python};
 use rayexec_shell::result_table::MaterializedResultTable;
 
+use crate::arrow::{export_schema, ExportedBatch};
 use crate::errors::Result;
 use crate::print::pyprint;
 
@@ -25,6 +27,50 @@ impl PythonMaterializedResultTable {
         pyprint(pretty, py)
     }
 
+    /// Convert the result to a pyarrow table.
+    ///
+    /// Data is handed off through the Arrow C Data Interface.
+    fn to_arrow(&self, py: Python) -> Result<PyObject> {
+        let pa = PyModule::import(py, "pyarrow")?;
+        let fields = &self.table.schema().fields;
+
+        let mut schema = Box::new(export_schema(fields)?);
+        let pa_schema = pa
+            .getattr("Schema")?
+            .call_method1("_import_from_c", (schema.as_mut() as *mut _ as usize,))?;
+
+        let mut batches = Vec::new();
+        for batch in self.table.iter_batches() {
+            let mut exported = ExportedBatch::try_new(fields, batch)?;
+            // Importing moves the arrays into pyarrow. Anything not moved gets
+            // released when `exported` is dropped.
+            let pa_batch = pa.getattr("RecordBatch")?.call_method1(
+                "_import_from_c",
+                (exported.array_addr(), exported.schema_addr()),
+            )?;
+            batches.push(pa_batch);
+        }
+
+        let table = pa
+            .getattr("Table")?
+            .call_method1("from_batches", (batches, pa_schema))?;
+
+        Ok(table.unbind())
+    }
+
+    /// Convert the result to a pandas data frame.
+    fn to_pandas(&self, py: Python) -> Result<PyObject> {
+        let table = self.to_arrow(py)?;
+        Ok(table.call_method0(py, "to_pandas")?)
+    }
+
+    /// Convert the result to a polars data frame.
+    fn to_polars(&self, py: Python) -> Result<PyObject> {
+        let table = self.to_arrow(py)?;
+        let pl = PyModule::import(py, "polars")?;
+        Ok(pl.call_method1("from_arrow", (table,))?.unbind())
+    }
+
     /// Prints out the profiling data for the query.
     ///
     /// Currently just used for debugging/perf testings.
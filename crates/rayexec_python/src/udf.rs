@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use pyo3::types::{PyAnyMethods, PyList, PyTuple};
+use pyo3::{IntoPyObjectExt, Py, PyAny, PyObject, Python};
+use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::scalar::ScalarValue;
+use rayexec_execution::expr::Expression;
+use rayexec_execution::functions::scalar::{
+    PlannedScalarFunction,
+    ScalarFunction,
+    ScalarFunctionImpl,
+};
+use rayexec_execution::functions::{
+    intern_positional_args,
+    plan_check_num_args,
+    FunctionInfo,
+    FunctionVolatility,
+    Signature,
+};
+use rayexec_execution::logical::binder::table_list::TableList;
+
+/// Parse a user provided type name into a data type.
+///
+/// Only types that can be easily round-tripped through python objects are
+/// supported.
+pub fn parse_datatype(name: &str) -> Result<DataType> {
+    Ok(match name.to_lowercase().as_str() {
+        "bool" | "boolean" => DataType::Boolean,
+        "int" | "int64" | "bigint" => DataType::Int64,
+        "float" | "float64" | "double" => DataType::Float64,
+        "str" | "utf8" | "text" | "varchar" => DataType::Utf8,
+        other => {
+            return Err(RayexecError::new(format!(
+                "Unsupported type for python function: {other}"
+            )))
+        }
+    })
+}
+
+/// A scalar function backed by a python callable.
+///
+/// The callable is invoked once per array with a list of python objects for
+/// each argument, and should return a list of the same length.
+#[derive(Debug, Clone)]
+pub struct PythonScalarFunction {
+    name: String,
+    signatures: Vec<Signature>,
+    arg_types: Vec<DataType>,
+    return_type: DataType,
+    callable: Arc<Py<PyAny>>,
+}
+
+impl PythonScalarFunction {
+    pub fn new(
+        name: String,
+        callable: Py<PyAny>,
+        arg_types: Vec<DataType>,
+        return_type: DataType,
+    ) -> Self {
+        let positional_args = intern_positional_args(
+            &arg_types
+                .iter()
+                .map(|t| t.datatype_id())
+                .collect::<Vec<_>>(),
+        );
+
+        PythonScalarFunction {
+            name,
+            signatures: vec![Signature {
+                positional_args,
+                variadic_arg: None,
+                return_type: return_type.datatype_id(),
+                doc: None,
+            }],
+            arg_types,
+            return_type,
+            callable: Arc::new(callable),
+        }
+    }
+}
+
+impl FunctionInfo for PythonScalarFunction {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &self.signatures
+    }
+
+    fn volatility(&self) -> FunctionVolatility {
+        // We have no idea what the python function is doing.
+        FunctionVolatility::Volatile
+    }
+}
+
+impl ScalarFunction for PythonScalarFunction {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, self.arg_types.len())?;
+
+        for (input, expected) in inputs.iter().zip(&self.arg_types) {
+            let datatype = input.datatype(table_list)?;
+            if &datatype != expected {
+                return Err(RayexecError::new(format!(
+                    "Expected {expected} for argument to '{}', got {datatype}",
+                    self.name
+                )));
+            }
+        }
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(self.clone()),
+            return_type: self.return_type.clone(),
+            inputs,
+            function_impl: Box::new(PythonScalarFunctionImpl {
+                return_type: self.return_type.clone(),
+                callable: self.callable.clone(),
+            }),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PythonScalarFunctionImpl {
+    return_type: DataType,
+    callable: Arc<Py<PyAny>>,
+}
+
+impl ScalarFunctionImpl for PythonScalarFunctionImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let len = match inputs.first() {
+            Some(input) => input.logical_len(),
+            None => 1,
+        };
+
+        Python::with_gil(|py| {
+            let args = inputs
+                .iter()
+                .map(|input| array_to_py(py, input))
+                .collect::<Result<Vec<_>>>()?;
+            let args = PyTuple::new(py, args).map_err(py_err)?;
+            let out = self.callable.call1(py, args).map_err(py_err)?;
+
+            let outputs = out
+                .bind(py)
+                .try_iter()
+                .map_err(py_err)?
+                .map(|v| v.map(|v| v.unbind()).map_err(py_err))
+                .collect::<Result<Vec<_>>>()?;
+            if outputs.len() != len {
+                return Err(RayexecError::new(format!(
+                    "Python function returned {} values, expected {len}",
+                    outputs.len()
+                )));
+            }
+
+            py_to_array(py, &self.return_type, outputs)
+        })
+    }
+}
+
+fn py_err(err: pyo3::PyErr) -> RayexecError {
+    RayexecError::with_source("Python function errored", Box::new(err))
+}
+
+/// Convert an array to a python list.
+fn array_to_py(py: Python, array: &Array) -> Result<PyObject> {
+    let values = (0..array.logical_len())
+        .map(|row| scalar_to_py(py, array.logical_value(row)?))
+        .collect::<Result<Vec<_>>>()?;
+    let list = PyList::new(py, values).map_err(py_err)?;
+    Ok(list.into_any().unbind())
+}
+
+fn scalar_to_py(py: Python, value: ScalarValue) -> Result<PyObject> {
+    let obj = match value {
+        ScalarValue::Null => Ok(py.None()),
+        ScalarValue::Boolean(v) => v.into_py_any(py),
+        ScalarValue::Int8(v) => v.into_py_any(py),
+        ScalarValue::Int16(v) => v.into_py_any(py),
+        ScalarValue::Int32(v) => v.into_py_any(py),
+        ScalarValue::Int64(v) => v.into_py_any(py),
+        ScalarValue::UInt8(v) => v.into_py_any(py),
+        ScalarValue::UInt16(v) => v.into_py_any(py),
+        ScalarValue::UInt32(v) => v.into_py_any(py),
+        ScalarValue::UInt64(v) => v.into_py_any(py),
+        ScalarValue::Float32(v) => v.into_py_any(py),
+        ScalarValue::Float64(v) => v.into_py_any(py),
+        ScalarValue::Utf8(v) => v.as_ref().into_py_any(py),
+        ScalarValue::Binary(v) => v.as_ref().into_py_any(py),
+        other => not_implemented!("Convert {other} to python object"),
+    }
+    .map_err(py_err)?;
+
+    Ok(obj)
+}
+
+fn py_to_array(py: Python, datatype: &DataType, values: Vec<PyObject>) -> Result<Array> {
+    fn extract<'py, T>(py: Python<'py>, values: &[PyObject]) -> Result<Vec<Option<T>>>
+    where
+        T: pyo3::FromPyObject<'py>,
+    {
+        values
+            .iter()
+            .map(|v| v.bind(py).extract::<Option<T>>().map_err(py_err))
+            .collect()
+    }
+
+    Ok(match datatype {
+        DataType::Boolean => Array::from_iter(extract::<bool>(py, &values)?),
+        DataType::Int64 => Array::from_iter(extract::<i64>(py, &values)?),
+        DataType::Float64 => Array::from_iter(extract::<f64>(py, &values)?),
+        DataType::Utf8 => Array::from_iter(extract::<String>(py, &values)?),
+        other => not_implemented!("Convert python objects to {other}"),
+    })
+}
@@ -3,6 +3,7 @@ use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use bytes::Bytes;
 use futures::future::{self, BoxFuture, FutureExt};
@@ -27,9 +28,15 @@ impl LocalFileSystemProvider {
             )
         })?;
 
-        let len = file.metadata()?.len() as usize;
+        let metadata = file.metadata()?;
+        let len = metadata.len() as usize;
+        let modified = metadata.modified().ok();
 
-        Ok(Box::new(LocalFile { len, file }))
+        Ok(Box::new(LocalFile {
+            len,
+            modified,
+            file,
+        }))
     }
 
     pub fn file_sink(&self, path: &Path) -> Result<Box<dyn FileSink>> {
@@ -88,6 +95,8 @@ impl LocalFileSystemProvider {
 #[derive(Debug)]
 pub struct LocalFile {
     len: usize,
+    /// Last modified time, if supported by the platform.
+    modified: Option<SystemTime>,
     file: File,
 }
 
@@ -121,6 +130,11 @@ impl FileSource for LocalFile {
     fn size(&mut self) -> BoxFuture<Result<usize>> {
         async move { Ok(self.len) }.boxed()
     }
+
+    fn version(&self) -> Option<String> {
+        let since_epoch = self.modified?.duration_since(UNIX_EPOCH).ok()?;
+        Some(since_epoch.as_nanos().to_string())
+    }
 }
 
 #[derive(Debug)]
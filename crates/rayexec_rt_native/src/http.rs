@@ -1,15 +1,18 @@
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use bytes::Bytes;
 use futures::future::{BoxFuture, FutureExt};
 use futures::stream::{BoxStream, StreamExt};
 use futures::Future;
 use rayexec_error::{RayexecError, Result, ResultExt};
+use rayexec_execution::runtime::stall::{maybe_with_stall_timeout, stall_error, ScanStall};
 use rayexec_io::http::{HttpClient, HttpResponse};
 use reqwest::header::HeaderMap;
 use reqwest::{Request, StatusCode};
 use tokio::task::JoinHandle;
+use tracing::debug;
 
 /// Wrapper around a reqwest client that ensures are request are done in a tokio
 /// context.
@@ -17,11 +20,25 @@ use tokio::task::JoinHandle;
 pub struct TokioWrappedHttpClient {
     client: reqwest::Client,
     handle: tokio::runtime::Handle,
+    /// Timeout for requests that get no response, or response bodies that
+    /// stop producing data, and if those should be retried.
+    stall: ScanStall,
 }
 
 impl TokioWrappedHttpClient {
     pub fn new(client: reqwest::Client, handle: tokio::runtime::Handle) -> Self {
-        TokioWrappedHttpClient { client, handle }
+        TokioWrappedHttpClient {
+            client,
+            handle,
+            stall: ScanStall::default(),
+        }
+    }
+
+    /// Error requests if no response is received, or reading response bodies
+    /// if no bytes are received, within the stall timeout.
+    pub fn with_stall(mut self, stall: ScanStall) -> Self {
+        self.stall = stall;
+        self
     }
 }
 
@@ -31,50 +48,89 @@ impl HttpClient for TokioWrappedHttpClient {
 
     fn do_request(&self, request: Request) -> Self::RequestFuture {
         let fut = self.client.execute(request);
+        let handle = self.handle.clone();
+        let stall_timeout = self.stall.timeout;
         let join_handle = self.handle.spawn(async move {
-            let result = fut.await;
-
-            if result.is_err() {
-                println!("ERROR: {result:?}");
+            let result = match stall_timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, fut).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        debug!(?timeout, "http request stalled");
+                        return Err(stall_error(timeout));
+                    }
+                },
+                None => fut.await,
+            };
+
+            if let Err(e) = &result {
+                debug!(%e, "http request failed");
             }
 
             let resp = result.context("Failed to send request")?;
 
-            Ok(BoxingResponse(resp))
+            Ok(BoxingResponse {
+                response: resp,
+                handle,
+                stall_timeout,
+            })
         });
 
         ResponseJoinHandle { join_handle }
     }
+
+    fn retry_timeouts(&self) -> bool {
+        self.stall.retry()
+    }
 }
 
 /// Wrapper around a reqwest response that boxes the futures and streams.
 #[derive(Debug)]
-pub struct BoxingResponse(pub reqwest::Response);
+pub struct BoxingResponse {
+    pub response: reqwest::Response,
+    handle: tokio::runtime::Handle,
+    stall_timeout: Option<Duration>,
+}
 
 impl HttpResponse for BoxingResponse {
     type BytesFuture = BoxFuture<'static, Result<Bytes>>;
     type BytesStream = BoxStream<'static, Result<Bytes>>;
 
     fn status(&self) -> StatusCode {
-        self.0.status()
+        self.response.status()
     }
 
     fn headers(&self) -> &HeaderMap {
-        self.0.headers()
+        self.response.headers()
     }
 
     fn bytes(self) -> Self::BytesFuture {
-        self.0
-            .bytes()
-            .map(|r| r.context("failed to get byte response"))
-            .boxed()
+        if self.stall_timeout.is_none() {
+            return self
+                .response
+                .bytes()
+                .map(|r| r.context("failed to get byte response"))
+                .boxed();
+        }
+
+        // Read through the stream so that the stall timeout applies.
+        let mut stream = self.bytes_stream();
+        Box::pin(async move {
+            let mut buf = Vec::new();
+            while let Some(result) = stream.next().await {
+                buf.extend_from_slice(result?.as_ref());
+            }
+            Ok(buf.into())
+        })
     }
 
     fn bytes_stream(self) -> Self::BytesStream {
-        self.0
+        let stream = self
+            .response
             .bytes_stream()
             .map(|r| r.context("failed to get byte stream"))
-            .boxed()
+            .boxed();
+
+        maybe_with_stall_timeout(stream, self.stall_timeout, Some(self.handle))
     }
 }
 
@@ -95,3 +151,47 @@ impl Future for ResponseJoinHandle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rayexec_error::ErrorKind;
+    use rayexec_execution::runtime::stall::StallAction;
+    use reqwest::Method;
+
+    use super::*;
+
+    #[test]
+    fn request_without_response_times_out() {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_io()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        // Accepts connections but never responds.
+        let listener = rt
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        rt.spawn(async move {
+            let mut conns = Vec::new();
+            while let Ok((conn, _)) = listener.accept().await {
+                conns.push(conn);
+            }
+        });
+
+        let client = TokioWrappedHttpClient::new(reqwest::Client::new(), rt.handle().clone())
+            .with_stall(ScanStall {
+                timeout: Some(Duration::from_millis(50)),
+                action: StallAction::Fail,
+            });
+        assert!(!client.retry_timeouts());
+
+        let url = format!("http://{addr}/file").parse().unwrap();
+        let err = rt
+            .block_on(client.do_request(Request::new(Method::GET, url)))
+            .unwrap_err();
+        assert_eq!(ErrorKind::Timeout, err.kind());
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::stream::{self, BoxStream};
 use futures::StreamExt;
@@ -9,6 +10,8 @@ use rayexec_execution::execution::executable::pipeline::{
     ExecutablePipeline,
 };
 use rayexec_execution::runtime::handle::QueryHandle;
+use rayexec_execution::runtime::resource_group::ResourceGroup;
+use rayexec_execution::runtime::stall::ScanStall;
 use rayexec_execution::runtime::{
     ErrorSink,
     OptionalTokioRuntime,
@@ -16,15 +19,18 @@ use rayexec_execution::runtime::{
     Runtime,
     TokioHandlerProvider,
 };
+use rayexec_io::compression::{CompressedFileSink, CompressionType, DecompressingFileSource};
 use rayexec_io::http::HttpClientReader;
+use rayexec_io::limit::{LimitedHttpClient, RequestLimiter, RequestLimits};
 use rayexec_io::location::{AccessConfig, FileLocation};
+use rayexec_io::read_cache::CachedFileSource;
 use rayexec_io::s3::{S3Client, S3Location};
 use rayexec_io::{FileProvider, FileSink, FileSource};
 
 use crate::filesystem::LocalFileSystemProvider;
 use crate::http::TokioWrappedHttpClient;
 use crate::threaded::ThreadedScheduler;
-use crate::time::NativeInstant;
+use crate::time::{NativeInstant, TokioLimiterClock};
 
 /// Inner behavior of the execution runtime.
 // TODO: Single-threaded scheduler to run our SLTs on to ensure no operators
@@ -36,7 +42,12 @@ pub trait Scheduler: Sync + Send + Debug + Sized + Clone {
 
     fn num_threads(&self) -> usize;
 
-    fn spawn_pipelines<P>(&self, pipelines: P, errors: Arc<dyn ErrorSink>) -> Self::Handle
+    fn spawn_pipelines<P>(
+        &self,
+        pipelines: P,
+        errors: Arc<dyn ErrorSink>,
+        group: Arc<ResourceGroup>,
+    ) -> Self::Handle
     where
         P: IntoIterator<Item = ExecutablePartitionPipeline>;
 }
@@ -64,12 +75,14 @@ impl<S: Scheduler + 'static> PipelineExecutor for NativeExecutor<S> {
         &self,
         pipelines: Vec<ExecutablePipeline>,
         errors: Arc<dyn ErrorSink>,
+        group: Arc<ResourceGroup>,
     ) -> Box<dyn QueryHandle> {
         let handle = self.0.spawn_pipelines(
             pipelines
                 .into_iter()
                 .flat_map(|pipeline| pipeline.into_partition_pipeline_iter()),
             errors,
+            group,
         );
         Box::new(handle)
     }
@@ -80,6 +93,12 @@ pub type ThreadedNativeExecutor = NativeExecutor<ThreadedScheduler>;
 #[derive(Debug, Clone)]
 pub struct NativeRuntime {
     tokio: Arc<OptionalTokioRuntime>,
+    /// Stall handling for remote scans that stop receiving data.
+    scan_stall: ScanStall,
+    /// Limiter shared by all requests to S3.
+    s3_limiter: RequestLimiter,
+    /// Limiter shared by all requests to plain http file sources.
+    http_limiter: RequestLimiter,
 }
 
 impl NativeRuntime {
@@ -95,10 +114,41 @@ impl NativeRuntime {
             .build()
             .context("Failed to build tokio runtime")?;
 
+        let clock = Arc::new(TokioLimiterClock::new(tokio.handle().clone()));
+        let unlimited = RequestLimiter::try_new(RequestLimits::default(), clock)?;
+
         Ok(NativeRuntime {
             tokio: Arc::new(OptionalTokioRuntime::new(Some(tokio))),
+            scan_stall: ScanStall::default(),
+            s3_limiter: unlimited.clone(),
+            http_limiter: unlimited,
         })
     }
+
+    /// Fail remote scans that go longer than `timeout` without receiving any
+    /// data.
+    pub fn with_scan_stall_timeout(mut self, timeout: Duration) -> Self {
+        self.scan_stall.timeout = Some(timeout);
+        self
+    }
+
+    /// Limit requests to S3 across all queries using this runtime.
+    pub fn with_s3_request_limits(mut self, limits: RequestLimits) -> Result<Self> {
+        self.s3_limiter = self.request_limiter(limits)?;
+        Ok(self)
+    }
+
+    /// Limit requests to http file sources across all queries using this
+    /// runtime.
+    pub fn with_http_request_limits(mut self, limits: RequestLimits) -> Result<Self> {
+        self.http_limiter = self.request_limiter(limits)?;
+        Ok(self)
+    }
+
+    fn request_limiter(&self, limits: RequestLimits) -> Result<RequestLimiter> {
+        let clock = Arc::new(TokioLimiterClock::new(self.tokio.handle()?));
+        RequestLimiter::try_new(limits, clock)
+    }
 }
 
 impl Runtime for NativeRuntime {
@@ -110,6 +160,9 @@ impl Runtime for NativeRuntime {
     fn file_provider(&self) -> Arc<Self::FileProvider> {
         Arc::new(NativeFileProvider {
             handle: self.tokio.handle_opt(),
+            stall: self.scan_stall,
+            s3_limiter: self.s3_limiter.clone(),
+            http_limiter: self.http_limiter.clone(),
         })
     }
 
@@ -117,11 +170,23 @@ impl Runtime for NativeRuntime {
         // TODO: Currently not possible to construct a native runtime without
         // tokio, but it is optional...
         TokioWrappedHttpClient::new(reqwest::Client::default(), self.tokio.handle().unwrap())
+            .with_stall(self.scan_stall)
     }
 
     fn tokio_handle(&self) -> &Self::TokioHandle {
         self.tokio.as_ref()
     }
+
+    fn scan_stall(&self) -> ScanStall {
+        self.scan_stall
+    }
+
+    fn with_scan_stall(&self, stall: ScanStall) -> Self {
+        NativeRuntime {
+            scan_stall: stall,
+            ..self.clone()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -131,6 +196,22 @@ pub struct NativeFileProvider {
     /// If we don't have it, we return an error when attempting to access an
     /// http file.
     handle: Option<tokio::runtime::Handle>,
+    /// Stall handling to apply to http clients.
+    stall: ScanStall,
+    s3_limiter: RequestLimiter,
+    http_limiter: RequestLimiter,
+}
+
+impl NativeFileProvider {
+    fn http_client(
+        &self,
+        handle: &tokio::runtime::Handle,
+        limiter: &RequestLimiter,
+    ) -> LimitedHttpClient<TokioWrappedHttpClient> {
+        let client = TokioWrappedHttpClient::new(reqwest::Client::default(), handle.clone())
+            .with_stall(self.stall);
+        LimitedHttpClient::new(client, limiter.clone())
+    }
 }
 
 impl FileProvider for NativeFileProvider {
@@ -139,11 +220,16 @@ impl FileProvider for NativeFileProvider {
         location: FileLocation,
         config: &AccessConfig,
     ) -> Result<Box<dyn FileSource>> {
-        match (location, config, self.handle.as_ref()) {
+        let compression = CompressionType::from_location(&location);
+
+        let source: Box<dyn FileSource> = match (location, config, self.handle.as_ref()) {
             (FileLocation::Url(url), AccessConfig::None, Some(handle)) => {
-                let client =
-                    TokioWrappedHttpClient::new(reqwest::Client::default(), handle.clone());
-                Ok(Box::new(HttpClientReader::new(client, url)))
+                let client = self.http_client(handle, &self.http_limiter);
+                let file_key = url.to_string();
+                Box::new(CachedFileSource::new(
+                    HttpClientReader::new(client, url),
+                    file_key,
+                ))
             }
             (
                 FileLocation::Url(url),
@@ -154,18 +240,21 @@ impl FileProvider for NativeFileProvider {
                 Some(handle),
             ) => {
                 let client = S3Client::new(
-                    TokioWrappedHttpClient::new(reqwest::Client::default(), handle.clone()),
+                    self.http_client(handle, &self.s3_limiter),
                     credentials.clone(),
                 );
                 let location = S3Location::from_url(url, region)?;
-                let reader = client.file_source(location, region)?;
-                Ok(reader)
+                client.file_source(location, region)?
             }
-            (FileLocation::Url(_), _, None) => Err(RayexecError::new(
-                "Cannot create http client, missing tokio runtime",
-            )),
-            (FileLocation::Path(path), _, _) => LocalFileSystemProvider.file_source(&path),
-        }
+            (FileLocation::Url(_), _, None) => {
+                return Err(RayexecError::new(
+                    "Cannot create http client, missing tokio runtime",
+                ))
+            }
+            (FileLocation::Path(path), _, _) => LocalFileSystemProvider.file_source(&path)?,
+        };
+
+        Ok(Box::new(DecompressingFileSource::new(source, compression)))
     }
 
     fn file_sink(
@@ -173,9 +262,16 @@ impl FileProvider for NativeFileProvider {
         location: FileLocation,
         _config: &AccessConfig,
     ) -> Result<Box<dyn FileSink>> {
-        match (location, self.handle.as_ref()) {
+        let compression = CompressionType::from_location(&location);
+
+        let sink = match (location, self.handle.as_ref()) {
             (FileLocation::Url(_url), _) => not_implemented!("http sink native"),
-            (FileLocation::Path(path), _) => LocalFileSystemProvider.file_sink(&path),
+            (FileLocation::Path(path), _) => LocalFileSystemProvider.file_sink(&path)?,
+        };
+
+        match compression {
+            Some(compression) => Ok(Box::new(CompressedFileSink::try_new(sink, compression)?)),
+            None => Ok(sink),
         }
     }
 
@@ -194,7 +290,7 @@ impl FileProvider for NativeFileProvider {
                 Some(handle),
             ) => {
                 let client = S3Client::new(
-                    TokioWrappedHttpClient::new(reqwest::Client::default(), handle.clone()),
+                    self.http_client(handle, &self.s3_limiter),
                     credentials.clone(),
                 );
                 let location = S3Location::from_url(url, region).unwrap(); // TODO
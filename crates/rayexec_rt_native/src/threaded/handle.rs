@@ -4,6 +4,7 @@ use futures::future::BoxFuture;
 use parking_lot::Mutex;
 use rayexec_error::Result;
 use rayexec_execution::execution::executable::profiler::ExecutionProfileData;
+use rayexec_execution::runtime::cancel::CancelFlag;
 use rayexec_execution::runtime::handle::QueryHandle;
 
 use super::task::{PartitionPipelineTask, TaskState};
@@ -13,19 +14,20 @@ use super::task::{PartitionPipelineTask, TaskState};
 pub struct ThreadedQueryHandle {
     /// Registered task states for all pipelines in a query.
     pub(crate) states: Mutex<Vec<Arc<TaskState>>>,
+    /// Cancel flag shared with all task states.
+    pub(crate) canceled: CancelFlag,
 }
 
 impl QueryHandle for ThreadedQueryHandle {
     /// Cancel the query.
     fn cancel(&self) {
-        let states = self.states.lock();
+        // Set before re-executing so that pipelines currently executing can
+        // see it as well.
+        self.canceled.cancel();
 
+        let states = self.states.lock();
         for state in states.iter() {
-            let mut pipeline = state.pipeline.lock();
-            pipeline.query_canceled = true;
-            std::mem::drop(pipeline);
-
-            // Re-execute the pipeline so it picks up the set bool. This lets us
+            // Re-execute the pipeline so it picks up the cancel. This lets us
             // cancel the pipeline regardless of if it's pending.
             let task = PartitionPipelineTask::from_task_state(state.clone());
             task.execute()
@@ -39,7 +41,7 @@ impl QueryHandle for ThreadedQueryHandle {
 
             for state in states.iter() {
                 let pipeline = state.pipeline.lock();
-                data.add_partition_data(&pipeline.pipeline);
+                data.add_partition_data(&pipeline);
             }
 
             // TODO: Get remote pipeline data somehow.
@@ -8,23 +8,30 @@ use handle::ThreadedQueryHandle;
 use parking_lot::Mutex;
 use rayexec_error::{RayexecError, Result};
 use rayexec_execution::execution::executable::pipeline::ExecutablePartitionPipeline;
+use rayexec_execution::runtime::cancel::CancelFlag;
+use rayexec_execution::runtime::resource_group::ResourceGroup;
 use rayexec_execution::runtime::ErrorSink;
-use rayon::{ThreadPool, ThreadPoolBuilder};
-use task::{PartitionPipelineTask, PipelineState, TaskState};
+use rayon::ThreadPoolBuilder;
+use task::{PartitionPipelineTask, RunQueue, TaskState};
 use tracing::debug;
 
 use crate::runtime::Scheduler;
 
-/// Work-stealing scheduler for executing queries on a thread pool.
+/// Scheduler for executing queries on a thread pool.
+///
+/// Tasks yield after a fixed budget, so concurrently executing queries share
+/// the pool instead of the first query to start running to completion. Time is
+/// divided between resource groups according to their CPU shares, and tasks
+/// within a group execute in FIFO order.
 #[derive(Clone)]
 pub struct ThreadedScheduler {
-    pool: Arc<ThreadPool>,
+    queue: Arc<RunQueue>,
 }
 
 impl fmt::Debug for ThreadedScheduler {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Scheduler")
-            .field("num_threads", &self.pool.current_num_threads())
+            .field("num_threads", &self.queue.pool.current_num_threads())
             .finish_non_exhaustive()
     }
 }
@@ -40,45 +47,51 @@ impl Scheduler for ThreadedScheduler {
             .map_err(|e| RayexecError::with_source("Failed to build thread pool", Box::new(e)))?;
 
         Ok(ThreadedScheduler {
-            pool: Arc::new(thread_pool),
+            queue: Arc::new(RunQueue::new(thread_pool)),
         })
     }
 
     fn num_threads(&self) -> usize {
-        self.pool.current_num_threads()
+        self.queue.pool.current_num_threads()
     }
 
     /// Spawn execution of a query graph on the thread pool.
     ///
     /// Each partition pipeline in the query graph will be independently
     /// executed.
-    fn spawn_pipelines<P>(&self, pipelines: P, errors: Arc<dyn ErrorSink>) -> ThreadedQueryHandle
+    fn spawn_pipelines<P>(
+        &self,
+        pipelines: P,
+        errors: Arc<dyn ErrorSink>,
+        group: Arc<ResourceGroup>,
+    ) -> ThreadedQueryHandle
     where
         P: IntoIterator<Item = ExecutablePartitionPipeline>,
     {
         debug!("spawning execution of query graph");
 
+        let canceled = CancelFlag::new();
         let task_states: Vec<_> = pipelines
             .into_iter()
             .map(|pipeline| {
                 Arc::new(TaskState {
-                    pipeline: Mutex::new(PipelineState {
-                        pipeline,
-                        query_canceled: false,
-                    }),
+                    pipeline: Mutex::new(pipeline),
+                    canceled: canceled.clone(),
                     errors: errors.clone(),
-                    pool: self.pool.clone(),
+                    queue: self.queue.clone(),
+                    group: group.clone(),
                 })
             })
             .collect();
 
         let handle = ThreadedQueryHandle {
             states: Mutex::new(task_states.clone()),
+            canceled,
         };
 
         for state in task_states {
             let task = PartitionPipelineTask::from_task_state(state);
-            self.pool.spawn(|| task.execute());
+            self.queue.schedule(task);
         }
 
         handle
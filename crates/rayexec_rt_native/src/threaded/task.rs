@@ -1,35 +1,151 @@
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::task::{Context, Poll, Wake, Waker};
 
 use parking_lot::Mutex;
-use rayexec_error::RayexecError;
+use rayexec_error::{ErrorKind, RayexecError};
 use rayexec_execution::execution::executable::pipeline::ExecutablePartitionPipeline;
+use rayexec_execution::runtime::cancel::CancelFlag;
+use rayexec_execution::runtime::resource_group::ResourceGroup;
 use rayexec_execution::runtime::ErrorSink;
 use rayon::ThreadPool;
 
 use crate::time::NativeInstant;
 
+/// Max number of times a task will push batches through its pipeline before
+/// yielding the thread.
+///
+/// Once the budget is exhausted, the task goes to the back of the run queue so
+/// that pipelines from other queries get a turn. This keeps a single long
+/// running query from monopolizing the worker threads.
+pub(crate) const TASK_POLL_BUDGET: usize = 64;
+
+/// Virtual time charged to a group with a single CPU share each time one of
+/// its tasks is run. Groups with more shares are charged proportionally less.
+const STRIDE: u64 = 1 << 20;
+
+/// Queue of tasks ready to execute, shared by all queries running on the pool.
+///
+/// Rayon prioritizes jobs spawned from a worker thread over jobs spawned from
+/// outside the pool, so spawning tasks directly would let an already running
+/// query starve newly started ones. Instead tasks are pushed here, and each
+/// job spawned on the pool executes whichever task should go next.
+///
+/// Tasks are queued per resource group, and groups take turns according to
+/// their CPU shares (stride scheduling). Every time a group's task is run, the
+/// group's pass is advanced by `STRIDE / cpu_shares`, and the group with the
+/// lowest pass goes next. Within a group, tasks execute in FIFO order.
+#[derive(Debug)]
+pub(crate) struct RunQueue {
+    pub(crate) pool: ThreadPool,
+    state: Mutex<RunQueueState>,
+}
+
+#[derive(Debug, Default)]
+struct RunQueueState {
+    /// Queues keyed by resource group id.
+    ///
+    /// Groups may be kept around without any tasks so that a group can't
+    /// reset its pass by briefly having nothing to run.
+    groups: HashMap<usize, GroupQueue>,
+    /// Pass of the most recently run task. Groups that become ready start
+    /// from here so they can't bank time while idle.
+    vtime: u64,
+}
+
+#[derive(Debug)]
+struct GroupQueue {
+    pass: u64,
+    tasks: VecDeque<PartitionPipelineTask>,
+}
+
+impl RunQueue {
+    pub(crate) fn new(pool: ThreadPool) -> Self {
+        RunQueue {
+            pool,
+            state: Mutex::new(RunQueueState::default()),
+        }
+    }
+
+    /// Schedule a task to execute after all currently queued tasks in the
+    /// same resource group.
+    pub(crate) fn schedule(self: &Arc<Self>, task: PartitionPipelineTask) {
+        {
+            let mut state = self.state.lock();
+            let vtime = state.vtime;
+            let group = state
+                .groups
+                .entry(task.state.group.id())
+                .or_insert_with(|| GroupQueue {
+                    pass: vtime,
+                    tasks: VecDeque::new(),
+                });
+            group.pass = group.pass.max(vtime);
+            group.tasks.push_back(task);
+        }
+
+        // One job per queued task, so there's always a task to pop.
+        let queue = self.clone();
+        self.pool.spawn(move || {
+            if let Some(task) = queue.pop_next() {
+                task.execute();
+            }
+        });
+    }
+
+    /// Pop the next task from the group with the lowest pass.
+    fn pop_next(&self) -> Option<PartitionPipelineTask> {
+        let mut state = self.state.lock();
+
+        // Ties broken by group id to keep the order deterministic.
+        let (_, group) = state
+            .groups
+            .iter_mut()
+            .filter(|(_, group)| !group.tasks.is_empty())
+            .min_by_key(|(&id, group)| (group.pass, id))?;
+        let task = group.tasks.pop_front()?;
+
+        let pass = group.pass;
+        let shares = task.state.group.cpu_shares().max(1) as u64;
+        group.pass += (STRIDE / shares).max(1);
+
+        state.vtime = state.vtime.max(pass);
+
+        // Idle groups at or behind the current virtual time would be reset to
+        // it when they become ready anyways.
+        let vtime = state.vtime;
+        state
+            .groups
+            .retain(|_, group| !group.tasks.is_empty() || group.pass > vtime);
+
+        Some(task)
+    }
+}
+
 /// State shared by the partition pipeline task and the waker.
 #[derive(Debug)]
 pub(crate) struct TaskState {
-    /// The partition pipeline we're operating on alongside a boolean for if the
-    /// query's been canceled.
-    pub(crate) pipeline: Mutex<PipelineState>,
+    /// The partition pipeline we're operating on.
+    pub(crate) pipeline: Mutex<ExecutablePartitionPipeline>,
+
+    /// Set when the query's been canceled.
+    ///
+    /// Kept outside of the pipeline lock so that canceling doesn't have to
+    /// wait for the pipeline to finish executing.
+    pub(crate) canceled: CancelFlag,
 
     /// Error sink for any errors that occur during execution.
     pub(crate) errors: Arc<dyn ErrorSink>,
 
-    /// The threadpool to execute on.
-    pub(crate) pool: Arc<ThreadPool>,
-}
+    /// Queue for scheduling the task on the thread pool.
+    pub(crate) queue: Arc<RunQueue>,
 
-#[derive(Debug)]
-pub(crate) struct PipelineState {
-    pub(crate) pipeline: ExecutablePartitionPipeline,
-    pub(crate) query_canceled: bool,
+    /// Resource group the query is executing in.
+    pub(crate) group: Arc<ResourceGroup>,
 }
 
 /// Task for executing a partition pipeline.
+#[derive(Debug)]
 pub struct PartitionPipelineTask {
     state: Arc<TaskState>,
 }
@@ -40,12 +156,12 @@ impl PartitionPipelineTask {
     }
 
     pub(crate) fn execute(self) {
-        let mut pipeline_state = self.state.pipeline.lock();
+        let mut pipeline = self.state.pipeline.lock();
 
-        if pipeline_state.query_canceled {
+        if self.state.canceled.is_canceled() {
             self.state
                 .errors
-                .push_error(RayexecError::new("Query canceled"));
+                .push_error(RayexecError::new("Query canceled").with_kind(ErrorKind::Cancelled));
             return;
         }
 
@@ -55,11 +171,12 @@ impl PartitionPipelineTask {
         .into();
 
         let mut cx = Context::from_waker(&waker);
-        loop {
-            match pipeline_state
-                .pipeline
-                .poll_execute::<NativeInstant>(&mut cx)
-            {
+        for _ in 0..TASK_POLL_BUDGET {
+            let poll = self
+                .state
+                .canceled
+                .enter(|| pipeline.poll_execute::<NativeInstant>(&mut cx));
+            match poll {
                 Poll::Ready(Some(Ok(()))) => {
                     // Pushing through the pipeline was successful. Continue the
                     // loop to try to get as much work done as possible.
@@ -82,6 +199,12 @@ impl PartitionPipelineTask {
                 }
             }
         }
+
+        // Budget exhausted with work still remaining. Reschedule behind
+        // everything else that's ready to execute.
+        std::mem::drop(pipeline);
+        let queue = self.state.queue.clone();
+        queue.schedule(self);
     }
 }
 
@@ -96,10 +219,9 @@ impl Wake for PartitionPipelineWaker {
     }
 
     fn wake_by_ref(self: &Arc<Self>) {
-        let pool = self.state.pool.clone();
         let task = PartitionPipelineTask {
             state: self.state.clone(),
         };
-        pool.spawn(|| task.execute());
+        self.state.queue.schedule(task);
     }
 }
@@ -1,4 +1,8 @@
+use std::time::Duration;
+
+use futures::future::BoxFuture;
 use rayexec_execution::runtime::time::RuntimeInstant;
+use rayexec_io::limit::LimiterClock;
 
 /// Instant implementation that wraps std Instant.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -13,3 +17,32 @@ impl RuntimeInstant for NativeInstant {
         self.0.saturating_duration_since(earlier.0)
     }
 }
+
+/// Clock for request limiting using tokio's timer.
+#[derive(Debug)]
+pub struct TokioLimiterClock {
+    start: std::time::Instant,
+    handle: tokio::runtime::Handle,
+}
+
+impl TokioLimiterClock {
+    pub fn new(handle: tokio::runtime::Handle) -> Self {
+        TokioLimiterClock {
+            start: std::time::Instant::now(),
+            handle,
+        }
+    }
+}
+
+impl LimiterClock for TokioLimiterClock {
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        // Sleep needs to be created in the context of the runtime so it can
+        // register with its timer.
+        let _guard = self.handle.enter();
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
@@ -4,6 +4,7 @@ use std::fmt;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use rayexec_error::RayexecError;
+use rayexec_execution::hybrid::client::SQLSTATE_HEADER;
 
 pub type ServerResult<T, E = ServerError> = std::result::Result<T, E>;
 
@@ -15,7 +16,12 @@ pub struct ServerError {
 
 impl IntoResponse for ServerError {
     fn into_response(self) -> Response {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            [(SQLSTATE_HEADER, self.error.kind().sqlstate())],
+            self.to_string(),
+        )
+            .into_response()
     }
 }
 
@@ -99,13 +99,13 @@ pub async fn push_batch_rpc(
     let msg = HybridPushRequest::from_proto(
         Message::decode(body.encoded_msg.as_slice()).context("failed to decode message")?,
     )?;
-    if msg.partition != 0 {
-        return Err(RayexecError::new(format!("Invalid partition: {}", msg.partition)).into());
-    }
 
+    // Waits until the partition has room for the batch, providing backpressure
+    // to the sender.
     state
         .server_state
-        .push_batch_for_stream(msg.stream_id, msg.batch.0)?;
+        .push_batch_for_stream(msg.stream_id, msg.partition, msg.batch.0)
+        .await?;
 
     Ok(Json(ResponseEnvelope {
         encoded_msg: HybridPushResponse {}.to_proto()?.encode_to_vec(),
@@ -119,11 +119,10 @@ pub async fn finalize_rpc(
     let msg = HybridFinalizeRequest::from_proto(
         Message::decode(body.encoded_msg.as_slice()).context("failed to decode message")?,
     )?;
-    if msg.partition != 0 {
-        return Err(RayexecError::new(format!("Invalid partition: {}", msg.partition)).into());
-    }
 
-    state.server_state.finalize_stream(msg.stream_id)?;
+    state
+        .server_state
+        .finalize_stream(msg.stream_id, msg.partition)?;
 
     Ok(Json(ResponseEnvelope {
         encoded_msg: HybridFinalizeResponse {}.to_proto()?.encode_to_vec(),
@@ -1,14 +1,19 @@
 use clap::{Parser, ValueEnum};
+use rayexec_bigquery::BigQueryDataSource;
 use rayexec_csv::CsvDataSource;
 use rayexec_delta::DeltaDataSource;
 use rayexec_error::Result;
 use rayexec_execution::datasource::{DataSourceBuilder, DataSourceRegistry, MemoryDataSource};
+use rayexec_execution::engine::otlp::OtlpConfig;
 use rayexec_execution::engine::Engine;
 use rayexec_execution::runtime::{Runtime, TokioHandlerProvider};
+use rayexec_lance::LanceDataSource;
+use rayexec_orc::OrcDataSource;
 use rayexec_parquet::ParquetDataSource;
 use rayexec_postgres::PostgresDataSource;
 use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
 use rayexec_server::serve_with_engine;
+use rayexec_spatial::SpatialDataSource;
 use rayexec_unity_catalog::UnityCatalogDataSource;
 
 #[derive(Parser)]
@@ -21,6 +26,13 @@ struct Arguments {
     /// Log format.
     #[arg(value_enum, long, value_parser, default_value_t = LogFormat::Json)]
     log_format: LogFormat,
+
+    /// Endpoint of an OTLP collector to export spans to (e.g.
+    /// "http://localhost:4317").
+    ///
+    /// Spans are only exported if this is set.
+    #[clap(long)]
+    otlp_endpoint: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -50,14 +62,32 @@ fn main() -> Result<()> {
     let registry = DataSourceRegistry::default()
         .with_datasource("memory", Box::new(MemoryDataSource))?
         .with_datasource("postgres", PostgresDataSource::initialize(runtime.clone()))?
+        .with_datasource("bigquery", BigQueryDataSource::initialize(runtime.clone()))?
         .with_datasource("delta", DeltaDataSource::initialize(runtime.clone()))?
         .with_datasource(
             "unity_catalog",
             UnityCatalogDataSource::initialize(runtime.clone()),
         )?
         .with_datasource("parquet", ParquetDataSource::initialize(runtime.clone()))?
-        .with_datasource("csv", CsvDataSource::initialize(runtime.clone()))?;
-    let engine = Engine::new_with_registry(sched.clone(), runtime.clone(), registry)?;
+        .with_datasource("orc", OrcDataSource::initialize(runtime.clone()))?
+        .with_datasource("lance", LanceDataSource::initialize(runtime.clone()))?
+        .with_datasource("csv", CsvDataSource::initialize(runtime.clone()))?
+        .with_datasource("spatial", SpatialDataSource::initialize(runtime.clone()))?;
+    #[cfg(feature = "odbc")]
+    let registry = registry.with_datasource(
+        "odbc",
+        rayexec_odbc::OdbcDataSource::initialize(runtime.clone()),
+    )?;
+    let mut engine = Engine::new_with_registry(sched.clone(), runtime.clone(), registry)?;
+    if let Some(endpoint) = args.otlp_endpoint {
+        engine = engine.with_otlp_export(
+            &OtlpConfig {
+                endpoint,
+                service_name: "rayexec_server".to_string(),
+            },
+            &tokio_handle,
+        )?;
+    }
 
     tokio_handle.block_on(async move { serve_with_engine(engine, args.port).await })
 }
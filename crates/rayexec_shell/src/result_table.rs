@@ -29,6 +29,11 @@ impl StreamingTable {
         &self.result.handle
     }
 
+    /// If the results are partial due to preview mode.
+    pub fn is_partial(&self) -> bool {
+        self.result.partial
+    }
+
     pub async fn collect(self) -> Result<MaterializedResultTable> {
         let batches: Vec<_> = self.result.stream.try_collect::<Vec<_>>().await?;
 
@@ -37,6 +42,7 @@ impl StreamingTable {
             batches,
             planning_profile: Some(self.result.planning_profile),
             execution_profile: None,
+            partial: self.result.partial,
         })
     }
 
@@ -49,6 +55,7 @@ impl StreamingTable {
             batches,
             planning_profile: Some(self.result.planning_profile),
             execution_profile: Some(execution_profile),
+            partial: self.result.partial,
         })
     }
 
@@ -74,6 +81,7 @@ pub struct MaterializedResultTable {
     pub(crate) batches: Vec<Batch>,
     pub(crate) planning_profile: Option<PlanningProfileData>,
     pub(crate) execution_profile: Option<ExecutionProfileData>,
+    pub(crate) partial: bool,
 }
 
 impl MaterializedResultTable {
@@ -107,6 +115,7 @@ impl MaterializedResultTable {
             batches,
             planning_profile: None,
             execution_profile: None,
+            partial: false,
         })
     }
 
@@ -122,6 +131,11 @@ impl MaterializedResultTable {
         self.execution_profile.as_ref()
     }
 
+    /// If the results are partial due to preview mode.
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+
     pub fn pretty_table(&self, width: usize, max_rows: Option<usize>) -> Result<PrettyTable> {
         PrettyTable::try_new(&self.schema, &self.batches, width, max_rows)
     }
@@ -1,10 +1,14 @@
 use std::collections::VecDeque;
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use rayexec_error::{RayexecError, Result};
+use rayexec_error::{OptionExt, RayexecError, Result};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::field::Field;
 use rayexec_execution::datasource::DataSourceRegistry;
 use rayexec_execution::engine::session::Session;
 use rayexec_execution::engine::Engine;
+use rayexec_execution::functions::scalar::ScalarFunction;
 use rayexec_execution::hybrid::client::{HybridClient, HybridConnectConfig};
 use rayexec_execution::runtime::{PipelineExecutor, Runtime};
 use rayexec_parser::parser;
@@ -30,6 +34,24 @@ where
     /// Create a new single user engine using the provided runtime and registry.
     pub fn try_new(executor: P, runtime: R, registry: DataSourceRegistry) -> Result<Self> {
         let engine = Engine::new_with_registry(executor, runtime.clone(), registry)?;
+        Self::try_from_engine(engine, runtime)
+    }
+
+    /// Create a new single user engine that persists tables to a database in
+    /// the given directory.
+    pub fn try_new_with_database_path(
+        executor: P,
+        runtime: R,
+        registry: DataSourceRegistry,
+        path: impl Into<PathBuf>,
+    ) -> Result<Self> {
+        let engine = Engine::new_with_registry(executor, runtime.clone(), registry)?
+            .with_database_path(path)?;
+        Self::try_from_engine(engine, runtime)
+    }
+
+    /// Create a new single user engine from an already configured engine.
+    pub fn try_from_engine(engine: Engine<P, R>, runtime: R) -> Result<Self> {
         let session = SingleUserSession {
             session: Arc::new(Mutex::new(engine.new_session()?)),
         };
@@ -78,40 +100,71 @@ where
     P: PipelineExecutor,
     R: Runtime,
 {
-    /// Execute a single sql query.
+    /// Execute a sql query.
+    ///
+    /// The query may be a script containing multiple statements, in which
+    /// case the statements are executed in order and the result of the last
+    /// statement is returned.
     pub async fn query(&self, sql: &str) -> Result<StreamingTable> {
         let mut statements = parser::parse(sql)?;
         let statement = match statements.len() {
+            0 => return Err(RayexecError::new("Expected at least 1 statement, got 0")),
             1 => statements.pop().unwrap(),
-            other => {
-                return Err(RayexecError::new(format!(
-                    "Expected 1 statement, got {}",
-                    other
-                )))
+            _ => {
+                let mut results = self.session.lock().await.simple(sql).await?;
+                let result = results.pop().required("result for last statement")?;
+                return Ok(StreamingTable { result });
             }
         };
 
         PendingQuery {
             session: self.session.clone(),
             statement,
+            sql: sql.to_string(),
+            begins_implicit_tx: true,
+            ends_implicit_tx: true,
         }
         .execute()
         .await
     }
 
+    /// Register a scalar function with the session.
+    pub async fn register_scalar_function(&self, function: Box<dyn ScalarFunction>) -> Result<()> {
+        self.session.lock().await.register_scalar_function(function)
+    }
+
+    /// Register a set of batches as a temporary table.
+    pub async fn register_table(
+        &self,
+        name: &str,
+        columns: Vec<Field>,
+        batches: Vec<Batch>,
+    ) -> Result<()> {
+        self.session
+            .lock()
+            .await
+            .register_table(name, columns, batches)
+            .await
+    }
+
     /// Execute multiple queries.
     ///
     /// Pending queries must be executed and streamed to completion in order.
     pub fn query_many(&self, sql: &str) -> Result<VecDeque<PendingQuery<P, R>>> {
-        let statements = parser::parse(sql)?;
-
-        // TODO: Implicit tx
+        let statements = parser::parse_with_sql(sql)?;
+        let num_statements = statements.len();
 
+        // All statements are part of a single implicit transaction. The last
+        // query ends the transaction after it executes.
         Ok(statements
             .into_iter()
-            .map(|statement| PendingQuery {
+            .enumerate()
+            .map(|(idx, (statement, sql))| PendingQuery {
                 session: self.session.clone(),
                 statement,
+                sql: sql.to_string(),
+                begins_implicit_tx: idx == 0,
+                ends_implicit_tx: idx == num_statements - 1,
             })
             .collect())
     }
@@ -120,7 +173,14 @@ where
 #[derive(Debug)]
 pub struct PendingQuery<P: PipelineExecutor, R: Runtime> {
     pub(crate) statement: RawStatement,
+    /// Sql text of the statement.
+    pub(crate) sql: String,
     pub(crate) session: Arc<Mutex<Session<P, R>>>,
+    /// If this is the first query in the batch. Any state left over from a
+    /// previous batch (e.g. from a query that errored) will be cleared.
+    pub(crate) begins_implicit_tx: bool,
+    /// If this is the last query in the batch.
+    pub(crate) ends_implicit_tx: bool,
 }
 
 impl<P, R> PendingQuery<P, R>
@@ -133,11 +193,21 @@ where
 
         let mut session = self.session.lock().await;
 
-        session.prepare(UNNAMED, self.statement)?;
-        session.bind(UNNAMED, UNNAMED).await?;
+        if self.begins_implicit_tx {
+            session.begin_implicit_transaction()?;
+        }
 
-        let result = session.execute(UNNAMED).await?;
+        let result = async {
+            session.prepare_with_sql(UNNAMED, self.statement, Some(self.sql))?;
+            session.bind(UNNAMED, UNNAMED).await?;
+            session.execute(UNNAMED).await
+        }
+        .await;
+
+        if self.ends_implicit_tx {
+            session.end_implicit_transaction()?;
+        }
 
-        Ok(StreamingTable { result })
+        Ok(StreamingTable { result: result? })
     }
 }
@@ -127,7 +127,7 @@ where
                             let table = match pending.execute().await {
                                 Ok(table) => table,
                                 Err(e) => {
-                                    writeln!(writer, "{e}")?;
+                                    write_error(&mut writer, &e, &query)?;
                                     break;
                                 }
                             };
@@ -135,17 +135,23 @@ where
                             let table = match table.collect().await {
                                 Ok(table) => table,
                                 Err(e) => {
-                                    writeln!(writer, "{e}")?;
+                                    write_error(&mut writer, &e, &query)?;
                                     break;
                                 }
                             };
 
                             match table.pretty_table(width, None) {
-                                Ok(table) => {
-                                    writeln!(writer, "{table}")?;
+                                Ok(pretty) => {
+                                    writeln!(writer, "{pretty}")?;
+                                    if table.is_partial() {
+                                        writeln!(
+                                            writer,
+                                            "Preview results (sampled sources, may be incomplete)"
+                                        )?;
+                                    }
                                 }
                                 Err(e) => {
-                                    writeln!(writer, "{e}")?;
+                                    write_error(&mut writer, &e, &query)?;
                                     break;
                                 }
                             }
@@ -155,7 +161,7 @@ where
                         // We're not returning the error here since it's related
                         // to the user input. We want to show the error to the
                         // user.
-                        writeln!(writer, "{e}")?;
+                        write_error(&mut writer, &e, &query)?;
                     }
                 }
 
@@ -170,3 +176,13 @@ where
         }
     }
 }
+
+/// Write an error to the output, along with the part of the query the error
+/// points to if it has a span.
+fn write_error(writer: &mut impl io::Write, error: &RayexecError, query: &str) -> Result<()> {
+    writeln!(writer, "{error}")?;
+    if let Some(snippet) = error.span().and_then(|span| span.render_snippet(query)) {
+        writeln!(writer, "{snippet}")?;
+    }
+    Ok(())
+}
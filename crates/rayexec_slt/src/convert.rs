@@ -10,6 +10,7 @@ pub fn table_to_rows(table: MaterializedResultTable) -> Result<Vec<Vec<String>>>
     const OPTS: FormatOptions = FormatOptions {
         null: "NULL",
         empty_string: "(empty)",
+        ..FormatOptions::new()
     };
     let formatter = Formatter::new(OPTS);
 
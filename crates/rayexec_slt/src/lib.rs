@@ -4,6 +4,7 @@ use rayexec_shell::session::SingleUserEngine;
 pub use vars::*;
 
 mod convert;
+mod mock;
 
 use std::fs;
 use std::future::Future;
@@ -13,6 +14,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use convert::{schema_to_types, table_to_rows};
 use libtest_mimic::{Arguments, Trial};
+use mock::{MockDirective, MockPostgres, MockSources};
 use rayexec_error::{RayexecError, Result, ResultExt};
 use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
 use sqllogictest::DefaultColumnType;
@@ -169,6 +171,7 @@ where
         Ok(TestSession {
             debug_partitions_set: false,
             conf,
+            mocks: MockSources::default(),
         })
     });
     runner
@@ -185,6 +188,9 @@ struct TestSession {
     debug_partitions_set: bool,
 
     conf: RunConfig,
+
+    /// Mock external sources declared by the file.
+    mocks: MockSources,
 }
 
 impl TestSession {
@@ -271,6 +277,85 @@ impl TestSession {
         }
     }
 
+    async fn run_mock_directive(
+        &mut self,
+        directive: MockDirective,
+    ) -> Result<sqllogictest::DBOutput<DefaultColumnType>, RayexecError> {
+        match directive {
+            MockDirective::PostgresTable {
+                schema,
+                name,
+                query,
+            } => {
+                if self.mocks.postgres.is_none() {
+                    // Tables live in a separate session so they're not visible
+                    // to the session under test.
+                    let session = self.conf.engine.engine.new_session()?;
+                    let postgres = MockPostgres::start(session).await?;
+                    self.conf.vars.add_var(
+                        "MOCK_POSTGRES",
+                        VarValue::Plain(postgres.connection_string()),
+                    );
+                    self.mocks.postgres = Some(postgres);
+                }
+                let postgres = self
+                    .mocks
+                    .postgres
+                    .as_ref()
+                    .expect("mock postgres to be started");
+                postgres.create_table(&schema, &name, &query).await?;
+
+                Ok(sqllogictest::DBOutput::StatementComplete(0))
+            }
+            MockDirective::PostgresQueries => {
+                let postgres = self.mocks.postgres.as_ref().ok_or_else(|| {
+                    RayexecError::new("Mock postgres not started, no tables have been created")
+                })?;
+
+                Ok(sqllogictest::DBOutput::Rows {
+                    types: vec![DefaultColumnType::Text],
+                    rows: postgres
+                        .take_queries()
+                        .into_iter()
+                        .map(|query| vec![query])
+                        .collect(),
+                })
+            }
+            MockDirective::PostgresDisconnect { after } => {
+                let postgres = self.mocks.postgres.as_ref().ok_or_else(|| {
+                    RayexecError::new("Mock postgres not started, no tables have been created")
+                })?;
+                postgres.disconnect_after(after);
+
+                Ok(sqllogictest::DBOutput::StatementComplete(0))
+            }
+            MockDirective::Parquet { name, query } => {
+                let tmp = self
+                    .conf
+                    .vars
+                    .get("SLT_TMP")
+                    .map(|v| v.as_ref().to_string())
+                    .ok_or_else(|| RayexecError::new("Missing slt tmp dir"))?;
+                std::fs::create_dir_all(&tmp).context("failed to create slt tmp dir")?;
+
+                let path = format!("{tmp}/mock_{name}.parquet");
+                let _ = self
+                    .conf
+                    .engine
+                    .session()
+                    .query(&format!("COPY ({query}) TO '{path}'"))
+                    .await?
+                    .collect()
+                    .await?;
+                self.conf
+                    .vars
+                    .add_var(&format!("MOCK_PARQUET_{name}"), VarValue::Plain(path));
+
+                Ok(sqllogictest::DBOutput::StatementComplete(0))
+            }
+        }
+    }
+
     async fn run_inner(
         &mut self,
         sql: &str,
@@ -286,6 +371,10 @@ impl TestSession {
             sql_with_replacements = sql_with_replacements.replace(k, v.as_ref());
         }
 
+        if let Some(directive) = MockDirective::parse(&sql_with_replacements)? {
+            return self.run_mock_directive(directive).await;
+        }
+
         self.debug_explain(&sql_with_replacements).await;
         self.debug_set_partitions().await;
 
@@ -0,0 +1,190 @@
+//! Mock external sources that SLT files can declare inline.
+//!
+//! This allows testing data sources (including what gets pushed down to them)
+//! without needing any network access or external services.
+//!
+//! Mocks are declared with harness-only statements:
+//!
+//! - `MOCK POSTGRES TABLE <schema>.<name> AS <query>`: Start a local server
+//!   speaking the postgres protocol (if not already started), and create a
+//!   table in it containing the results of `<query>`. The connection string
+//!   for the server is available via the `__MOCK_POSTGRES__` variable.
+//!
+//! - `MOCK POSTGRES QUERIES`: Return the queries the mock postgres server has
+//!   executed for reading data since the last time this was called, one row per
+//!   query. Catalog queries are omitted.
+//!
+//! - `MOCK POSTGRES DISCONNECT AFTER <n> ROWS`: Drop the connection after
+//!   sending `<n>` rows for the next COPY the mock postgres server executes.
+//!
+//! - `MOCK PARQUET <name> AS <query>`: Write the results of `<query>` to a
+//!   parquet file in the slt tmp dir. The path to the file is available via
+//!   the `__MOCK_PARQUET_<NAME>__` variable.
+
+mod postgres;
+
+pub use postgres::MockPostgres;
+use rayexec_error::{RayexecError, Result};
+
+/// A harness-only statement for setting up mocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockDirective {
+    PostgresTable {
+        schema: String,
+        name: String,
+        query: String,
+    },
+    PostgresQueries,
+    PostgresDisconnect {
+        after: usize,
+    },
+    Parquet {
+        name: String,
+        query: String,
+    },
+}
+
+impl MockDirective {
+    /// Try to parse a mock directive from a statement.
+    ///
+    /// Returns Ok(None) if the statement isn't a mock directive, and an error
+    /// if it is one but is malformed.
+    pub fn parse(sql: &str) -> Result<Option<Self>> {
+        let trimmed = sql.trim().trim_end_matches(';').trim_end();
+        let rest = match strip_keyword(trimmed, "MOCK") {
+            Some(rest) => rest,
+            None => return Ok(None),
+        };
+        let malformed = || RayexecError::new(format!("Malformed mock directive: {sql}"));
+
+        if let Some(rest) = strip_keyword(rest, "POSTGRES") {
+            if let Some(rest) = strip_keyword(rest, "TABLE") {
+                let (table, query) = split_as(rest).ok_or_else(malformed)?;
+                let (schema, name) = table.split_once('.').ok_or_else(malformed)?;
+                return Ok(Some(MockDirective::PostgresTable {
+                    schema: schema.to_string(),
+                    name: name.to_string(),
+                    query: query.to_string(),
+                }));
+            }
+            if let Some(rest) = strip_keyword(rest, "QUERIES") {
+                if !rest.is_empty() {
+                    return Err(malformed());
+                }
+                return Ok(Some(MockDirective::PostgresQueries));
+            }
+            if let Some(rest) = strip_keyword(rest, "DISCONNECT") {
+                let rest = strip_keyword(rest, "AFTER").ok_or_else(malformed)?;
+                let (after, rest) = rest.split_once(char::is_whitespace).ok_or_else(malformed)?;
+                if !strip_keyword(rest.trim_start(), "ROWS").is_some_and(|rest| rest.is_empty()) {
+                    return Err(malformed());
+                }
+                let after = after.parse().map_err(|_| malformed())?;
+                return Ok(Some(MockDirective::PostgresDisconnect { after }));
+            }
+            return Err(malformed());
+        }
+
+        if let Some(rest) = strip_keyword(rest, "PARQUET") {
+            let (name, query) = split_as(rest).ok_or_else(malformed)?;
+            return Ok(Some(MockDirective::Parquet {
+                name: name.to_string(),
+                query: query.to_string(),
+            }));
+        }
+
+        Err(malformed())
+    }
+}
+
+/// Strip a leading keyword (case insensitive) followed by whitespace or the
+/// end of the input, returning the trimmed remainder.
+fn strip_keyword<'a>(s: &'a str, keyword: &str) -> Option<&'a str> {
+    let prefix = s.get(..keyword.len())?;
+    if !prefix.eq_ignore_ascii_case(keyword) {
+        return None;
+    }
+    let rest = &s[keyword.len()..];
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.trim_start())
+}
+
+/// Split `<ident> AS <query>` into the identifier and query.
+fn split_as(s: &str) -> Option<(&str, &str)> {
+    let (ident, rest) = s.split_once(char::is_whitespace)?;
+    let query = strip_keyword(rest.trim_start(), "AS")?;
+    if ident.is_empty() || query.is_empty() {
+        return None;
+    }
+    Some((ident, query))
+}
+
+/// Mocks created by a single SLT file.
+#[derive(Debug, Default)]
+pub struct MockSources {
+    /// Started on the first postgres directive.
+    pub postgres: Option<MockPostgres>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_not_a_directive() {
+        assert_eq!(None, MockDirective::parse("SELECT 1").unwrap());
+        assert_eq!(None, MockDirective::parse("MOCKING").unwrap());
+    }
+
+    #[test]
+    fn parse_postgres_table() {
+        let directive =
+            MockDirective::parse("mock postgres table public.t1 as select 1 as a").unwrap();
+        assert_eq!(
+            Some(MockDirective::PostgresTable {
+                schema: "public".to_string(),
+                name: "t1".to_string(),
+                query: "select 1 as a".to_string(),
+            }),
+            directive
+        );
+    }
+
+    #[test]
+    fn parse_parquet() {
+        let directive = MockDirective::parse("MOCK PARQUET f1 AS\nVALUES (1);").unwrap();
+        assert_eq!(
+            Some(MockDirective::Parquet {
+                name: "f1".to_string(),
+                query: "VALUES (1)".to_string(),
+            }),
+            directive
+        );
+    }
+
+    #[test]
+    fn parse_postgres_queries() {
+        let directive = MockDirective::parse("MOCK POSTGRES QUERIES;\n").unwrap();
+        assert_eq!(Some(MockDirective::PostgresQueries), directive);
+    }
+
+    #[test]
+    fn parse_postgres_disconnect() {
+        let directive = MockDirective::parse("MOCK POSTGRES DISCONNECT AFTER 2 ROWS;").unwrap();
+        assert_eq!(
+            Some(MockDirective::PostgresDisconnect { after: 2 }),
+            directive
+        );
+    }
+
+    #[test]
+    fn parse_malformed() {
+        MockDirective::parse("MOCK POSTGRES TABLE t1 AS SELECT 1").unwrap_err();
+        MockDirective::parse("MOCK POSTGRES QUERIES extra").unwrap_err();
+        MockDirective::parse("MOCK POSTGRES DISCONNECT AFTER two ROWS").unwrap_err();
+        MockDirective::parse("MOCK POSTGRES DISCONNECT AFTER 2").unwrap_err();
+        MockDirective::parse("MOCK MYSQL TABLE public.t1 AS SELECT 1").unwrap_err();
+    }
+}
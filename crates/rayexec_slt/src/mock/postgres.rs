@@ -0,0 +1,1003 @@
+//! Minimal in-process server speaking the postgres wire protocol.
+//!
+//! Only the parts of the protocol (and the catalog queries) used by the
+//! postgres data source are implemented. Tables are backed by temp tables in a
+//! separate session, and queries sent by the data source are executed against
+//! that session, so anything pushed down needs to be something we're able to
+//! execute ourselves.
+//!
+//! Mock tables don't have ctids. Scans ordered by ctid are executed without
+//! the ctid parts of the query, and rows are numbered in the order they're
+//! returned instead.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use futures::TryStreamExt;
+use rayexec_error::{RayexecError, Result, ResultExt};
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::field::Schema;
+use rayexec_execution::arrays::scalar::ScalarValue;
+use rayexec_execution::engine::session::Session;
+use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+const PROTOCOL_VERSION: i32 = 196608;
+const SSL_REQUEST_CODE: i32 = 80877103;
+const GSSENC_REQUEST_CODE: i32 = 80877104;
+
+/// First OID handed out to mock tables. Matches the first OID postgres uses
+/// for user objects.
+const FIRST_TABLE_OID: u32 = 16384;
+
+mod oid {
+    pub const BOOL: u32 = 16;
+    pub const BYTEA: u32 = 17;
+    pub const INT8: u32 = 20;
+    pub const INT2: u32 = 21;
+    pub const INT4: u32 = 23;
+    pub const TEXT: u32 = 25;
+    pub const OID: u32 = 26;
+    pub const JSON: u32 = 114;
+    pub const FLOAT4: u32 = 700;
+    pub const FLOAT8: u32 = 701;
+}
+
+/// Postgres server listening on a local port.
+///
+/// The server is stopped when this is dropped.
+pub struct MockPostgres {
+    addr: SocketAddr,
+    state: Arc<MockState>,
+    accept_handle: JoinHandle<()>,
+}
+
+impl MockPostgres {
+    /// Start listening on a random local port, backing tables with the
+    /// provided session.
+    pub async fn start(session: Session<ThreadedNativeExecutor, NativeRuntime>) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("Failed to bind mock postgres listener")?;
+        let addr = listener
+            .local_addr()
+            .context("Failed to get mock postgres address")?;
+
+        let state = Arc::new(MockState {
+            session: tokio::sync::Mutex::new(session),
+            tables: Mutex::new(Vec::new()),
+            queries: Mutex::new(Vec::new()),
+            disconnect_after: Mutex::new(None),
+        });
+
+        let accept_state = state.clone();
+        let accept_handle = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = accept_state.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = MockConnection::new(stream, state).run().await {
+                        debug!(%e, "mock postgres connection closed with error");
+                    }
+                });
+            }
+        });
+
+        Ok(MockPostgres {
+            addr,
+            state,
+            accept_handle,
+        })
+    }
+
+    /// Connection string for connecting to this server.
+    pub fn connection_string(&self) -> String {
+        format!(
+            "host={} port={} user=slt dbname=mock",
+            self.addr.ip(),
+            self.addr.port()
+        )
+    }
+
+    /// Create a table in the mock server from the results of a query.
+    ///
+    /// The query is executed by us, not the engine under test.
+    pub async fn create_table(&self, schema: &str, name: &str, query: &str) -> Result<()> {
+        let mut session = self.state.session.lock().await;
+
+        execute(
+            &mut session,
+            &format!("CREATE SCHEMA IF NOT EXISTS temp.{schema}"),
+        )
+        .await?;
+        execute(
+            &mut session,
+            &format!("CREATE TEMP TABLE {schema}.{name} AS {query}"),
+        )
+        .await?;
+        let (table_schema, batches) =
+            execute(&mut session, &format!("SELECT * FROM {schema}.{name}")).await?;
+
+        let columns = table_schema
+            .fields
+            .iter()
+            .map(|field| Ok((field.name.clone(), type_oid(&field.datatype)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let num_rows = batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+
+        let mut tables = self.state.tables.lock().expect("tables lock not poisoned");
+        let oid = FIRST_TABLE_OID + tables.len() as u32;
+        tables.push(MockTable {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            oid,
+            columns,
+            num_rows: num_rows as i64,
+        });
+
+        Ok(())
+    }
+
+    /// Take the queries the server has executed for reading data since the
+    /// last call.
+    ///
+    /// Catalog queries aren't included.
+    pub fn take_queries(&self) -> Vec<String> {
+        let mut queries = self
+            .state
+            .queries
+            .lock()
+            .expect("queries lock not poisoned");
+        std::mem::take(&mut *queries)
+    }
+
+    /// Close the connection after sending `num_rows` rows for the next COPY,
+    /// as if the connection dropped.
+    pub fn disconnect_after(&self, num_rows: usize) {
+        *self
+            .state
+            .disconnect_after
+            .lock()
+            .expect("disconnect lock not poisoned") = Some(num_rows);
+    }
+}
+
+impl Drop for MockPostgres {
+    fn drop(&mut self) {
+        self.accept_handle.abort();
+    }
+}
+
+impl fmt::Debug for MockPostgres {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockPostgres")
+            .field("addr", &self.addr)
+            .finish_non_exhaustive()
+    }
+}
+
+struct MockState {
+    /// Session holding the temp tables backing the mock tables.
+    session: tokio::sync::Mutex<Session<ThreadedNativeExecutor, NativeRuntime>>,
+    tables: Mutex<Vec<MockTable>>,
+    /// Data queries executed by the server.
+    queries: Mutex<Vec<String>>,
+    /// Close the connection after sending this many rows for the next COPY.
+    disconnect_after: Mutex<Option<usize>>,
+}
+
+impl MockState {
+    fn find_table<T>(
+        &self,
+        pred: impl Fn(&MockTable) -> bool,
+        f: impl Fn(&MockTable) -> T,
+    ) -> Option<T> {
+        let tables = self.tables.lock().expect("tables lock not poisoned");
+        tables.iter().find(|table| pred(table)).map(f)
+    }
+}
+
+#[derive(Debug)]
+struct MockTable {
+    schema: String,
+    name: String,
+    oid: u32,
+    /// Column names and type OIDs.
+    columns: Vec<(String, u32)>,
+    num_rows: i64,
+}
+
+/// Queries the server knows how to answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MockQuery {
+    /// Query used for checking the connection.
+    SelectOne,
+    /// Look up a table's OID by schema and name.
+    TableOid,
+    /// Look up column names and types by table OID.
+    TableColumns,
+    /// Row count and size for a table by schema and name.
+    TableStatistics,
+    /// List all (schema, name) pairs.
+    ListTables,
+    /// Binary COPY out of a query.
+    Copy { query: String },
+}
+
+impl MockQuery {
+    fn plan(sql: &str) -> Result<Self> {
+        let sql = sql.trim();
+
+        if let Some(query) = copy_inner_query(sql) {
+            return Ok(MockQuery::Copy {
+                query: query.to_string(),
+            });
+        }
+
+        Ok(if sql.eq_ignore_ascii_case("select 1") {
+            MockQuery::SelectOne
+        } else if sql.contains("pg_attribute") {
+            MockQuery::TableColumns
+        } else if sql.contains("reltuples") {
+            MockQuery::TableStatistics
+        } else if sql.contains("pg_class") {
+            MockQuery::TableOid
+        } else if sql.contains("information_schema.tables") {
+            MockQuery::ListTables
+        } else {
+            return Err(RayexecError::new(format!(
+                "Mock postgres doesn't support query: {sql}"
+            )));
+        })
+    }
+
+    fn param_types(&self) -> &'static [u32] {
+        match self {
+            MockQuery::TableOid | MockQuery::TableStatistics => &[oid::TEXT, oid::TEXT],
+            MockQuery::TableColumns => &[oid::OID],
+            _ => &[],
+        }
+    }
+
+    /// Names and type OIDs of the output columns, None if the query doesn't
+    /// return rows.
+    fn columns(&self) -> Option<&'static [(&'static str, u32)]> {
+        Some(match self {
+            MockQuery::SelectOne => &[("?column?", oid::INT4)],
+            MockQuery::TableOid => &[
+                ("oid", oid::OID),
+                ("greatest", oid::INT4),
+                ("?column?", oid::BOOL),
+            ],
+            MockQuery::TableColumns => &[("attname", oid::TEXT), ("oid", oid::OID)],
+            MockQuery::TableStatistics => &[("reltuples", oid::INT8), ("pg_table_size", oid::INT8)],
+            MockQuery::ListTables => &[("table_schema", oid::TEXT), ("table_name", oid::TEXT)],
+            MockQuery::Copy { .. } => return None,
+        })
+    }
+
+    async fn execute(&self, state: &MockState, params: &[Option<Vec<u8>>]) -> Result<QueryOutput> {
+        let text_param = |idx: usize| -> Result<String> {
+            match params.get(idx) {
+                Some(Some(bytes)) => String::from_utf8(bytes.clone())
+                    .context("Mock postgres received invalid utf8 param"),
+                _ => Err(RayexecError::new(format!("Missing param {idx}"))),
+            }
+        };
+        let table_by_name = |schema: &str, name: &str| {
+            let schema = schema.to_string();
+            let name = name.to_string();
+            move |table: &MockTable| table.schema == schema && table.name == name
+        };
+
+        let rows = match self {
+            MockQuery::SelectOne => vec![vec![Some(1_i32.to_be_bytes().to_vec())]],
+            MockQuery::TableOid => {
+                let pred = table_by_name(&text_param(0)?, &text_param(1)?);
+                state
+                    .find_table(pred, |table| {
+                        vec![
+                            Some(table.oid.to_be_bytes().to_vec()),
+                            Some(1_i32.to_be_bytes().to_vec()),
+                            // Mock tables are plain tables.
+                            Some(vec![1]),
+                        ]
+                    })
+                    .into_iter()
+                    .collect()
+            }
+            MockQuery::TableStatistics => {
+                let pred = table_by_name(&text_param(0)?, &text_param(1)?);
+                state
+                    .find_table(pred, |table| {
+                        vec![Some(table.num_rows.to_be_bytes().to_vec()), None]
+                    })
+                    .into_iter()
+                    .collect()
+            }
+            MockQuery::TableColumns => {
+                let oid = match params.first() {
+                    Some(Some(bytes)) if bytes.len() == 4 => {
+                        u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                    }
+                    _ => return Err(RayexecError::new("Missing OID param")),
+                };
+                state
+                    .find_table(
+                        |table| table.oid == oid,
+                        |table| {
+                            table
+                                .columns
+                                .iter()
+                                .map(|(name, typ)| {
+                                    vec![
+                                        Some(name.as_bytes().to_vec()),
+                                        Some(typ.to_be_bytes().to_vec()),
+                                    ]
+                                })
+                                .collect()
+                        },
+                    )
+                    .unwrap_or_default()
+            }
+            MockQuery::ListTables => {
+                let tables = state.tables.lock().expect("tables lock not poisoned");
+                let mut names: Vec<_> = tables
+                    .iter()
+                    .map(|table| (table.schema.clone(), table.name.clone()))
+                    .collect();
+                names.sort();
+                names
+                    .into_iter()
+                    .map(|(schema, name)| vec![Some(schema.into_bytes()), Some(name.into_bytes())])
+                    .collect()
+            }
+            MockQuery::Copy { query } => {
+                state
+                    .queries
+                    .lock()
+                    .expect("queries lock not poisoned")
+                    .push(query.clone());
+
+                let ctid_scan = CtidScan::parse(query)?;
+                let query = match &ctid_scan {
+                    Some(scan) => &scan.query,
+                    None => query,
+                };
+
+                let mut session = state.session.lock().await;
+                let (_, batches) = execute(&mut session, query).await?;
+                let mut rows = Vec::new();
+                for batch in &batches {
+                    for idx in 0..batch.num_rows() {
+                        let row = batch.row(idx).expect("row to exist");
+                        let row = row
+                            .columns
+                            .iter()
+                            .map(encode_value)
+                            .collect::<Result<Vec<_>>>()?;
+                        rows.push(row);
+                    }
+                }
+
+                if let Some(scan) = ctid_scan {
+                    rows = rows
+                        .into_iter()
+                        .enumerate()
+                        .skip(scan.after)
+                        .map(|(idx, mut row)| {
+                            row.push(Some(format!("(0,{})", idx + 1).into_bytes()));
+                            row
+                        })
+                        .collect();
+                }
+
+                return Ok(encode_binary_copy(rows));
+            }
+        };
+
+        Ok(QueryOutput::Rows(rows))
+    }
+}
+
+#[derive(Debug)]
+enum QueryOutput {
+    /// Rows with binary encoded values.
+    Rows(Vec<Vec<Option<Vec<u8>>>>),
+    /// Binary COPY data messages, along with the number of rows.
+    Copy {
+        messages: Vec<Vec<u8>>,
+        num_rows: usize,
+    },
+}
+
+/// A scan reading rows in ctid order.
+///
+/// `SELECT <cols>, ctid::text FROM <table> [WHERE ...] ORDER BY ctid`
+#[derive(Debug, PartialEq, Eq)]
+struct CtidScan {
+    /// Query with the ctid column, order, and predicate removed.
+    query: String,
+    /// Number of rows to skip, from a `ctid > '(0,<n>)'` predicate.
+    after: usize,
+}
+
+impl CtidScan {
+    /// Parse a ctid ordered scan, returning None if the query isn't one.
+    fn parse(query: &str) -> Result<Option<Self>> {
+        let query = match query.strip_suffix(" ORDER BY ctid") {
+            Some(query) => query,
+            None => return Ok(None),
+        };
+        let query = query.replacen(", ctid::text FROM ", " FROM ", 1);
+
+        let (query, after) = match query.split_once("ctid > '(0,") {
+            Some((before, rest)) => {
+                let (after, rest) = rest.split_once(")'").ok_or_else(|| {
+                    RayexecError::new(format!("Malformed ctid predicate: {query}"))
+                })?;
+                let after = after
+                    .parse()
+                    .context("Mock postgres ctid offset isn't a number")?;
+                // Drop the predicate, along with the WHERE or AND before it.
+                let before = before
+                    .strip_suffix(" WHERE ")
+                    .or_else(|| before.strip_suffix(" AND "))
+                    .ok_or_else(|| {
+                        RayexecError::new(format!("Unexpected ctid predicate: {query}"))
+                    })?;
+                (format!("{before}{rest}"), after)
+            }
+            None => (query, 0),
+        };
+
+        Ok(Some(CtidScan { query, after }))
+    }
+}
+
+/// Extract the query from `COPY (<query>) TO STDOUT (FORMAT binary)`.
+fn copy_inner_query(sql: &str) -> Option<&str> {
+    const PREFIX: &str = "COPY (";
+    const SUFFIX: &str = ") TO STDOUT (FORMAT binary)";
+
+    let prefix = sql.get(..PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(PREFIX) {
+        return None;
+    }
+    sql[PREFIX.len()..].strip_suffix(SUFFIX)
+}
+
+async fn execute(
+    session: &mut Session<ThreadedNativeExecutor, NativeRuntime>,
+    sql: &str,
+) -> Result<(Schema, Vec<Batch>)> {
+    let result = session.execute_streaming(sql).await?;
+    let schema = result.schema.clone();
+    let batches = result.try_collect().await?;
+    Ok((schema, batches))
+}
+
+fn type_oid(datatype: &DataType) -> Result<u32> {
+    Ok(match datatype {
+        DataType::Boolean => oid::BOOL,
+        DataType::Int16 => oid::INT2,
+        DataType::Int32 => oid::INT4,
+        DataType::Int64 => oid::INT8,
+        DataType::Float32 => oid::FLOAT4,
+        DataType::Float64 => oid::FLOAT8,
+        DataType::Utf8 => oid::TEXT,
+        DataType::Binary => oid::BYTEA,
+        DataType::Json => oid::JSON,
+        other => {
+            return Err(RayexecError::new(format!(
+                "Unsupported data type for mock postgres table: {other}"
+            )))
+        }
+    })
+}
+
+/// Encode a value using postgres' binary format, None for NULL.
+fn encode_value(value: &ScalarValue) -> Result<Option<Vec<u8>>> {
+    Ok(Some(match value {
+        ScalarValue::Null => return Ok(None),
+        ScalarValue::Boolean(v) => vec![*v as u8],
+        ScalarValue::Int16(v) => v.to_be_bytes().to_vec(),
+        ScalarValue::Int32(v) => v.to_be_bytes().to_vec(),
+        ScalarValue::Int64(v) => v.to_be_bytes().to_vec(),
+        ScalarValue::Float32(v) => v.to_be_bytes().to_vec(),
+        ScalarValue::Float64(v) => v.to_be_bytes().to_vec(),
+        ScalarValue::Utf8(v) | ScalarValue::Json(v) => v.as_bytes().to_vec(),
+        ScalarValue::Binary(v) => v.to_vec(),
+        other => {
+            return Err(RayexecError::new(format!(
+                "Mock postgres can't encode value: {other}"
+            )))
+        }
+    }))
+}
+
+/// Encode rows of binary encoded values using the binary COPY format.
+///
+/// Each row is sent in its own CopyData message (the header is sent with the
+/// first row, and the trailer on its own) since that's what clients expect.
+fn encode_binary_copy(rows: Vec<Vec<Option<Vec<u8>>>>) -> QueryOutput {
+    let mut messages = Vec::new();
+
+    let mut buf = Vec::new();
+    buf.extend_from_slice(b"PGCOPY\n\xff\r\n\0");
+    buf.extend_from_slice(&0_i32.to_be_bytes()); // Flags
+    buf.extend_from_slice(&0_i32.to_be_bytes()); // Header extension length
+
+    for row in rows {
+        buf.extend_from_slice(&(row.len() as i16).to_be_bytes());
+        for value in row {
+            write_value(&mut buf, value.as_deref());
+        }
+        messages.push(std::mem::take(&mut buf));
+    }
+
+    let num_rows = messages.len();
+    buf.extend_from_slice(&(-1_i16).to_be_bytes()); // Trailer
+    messages.push(buf);
+
+    QueryOutput::Copy { messages, num_rows }
+}
+
+#[derive(Debug)]
+struct Portal {
+    query: MockQuery,
+    params: Vec<Option<Vec<u8>>>,
+}
+
+/// A single client connection.
+struct MockConnection {
+    stream: TcpStream,
+    state: Arc<MockState>,
+    /// Buffered messages to send on the next flush.
+    out: Vec<u8>,
+    statements: HashMap<String, MockQuery>,
+    portals: HashMap<String, Portal>,
+    /// Set after an error, messages are ignored until the next Sync.
+    skip_until_sync: bool,
+    /// Set to close the connection without responding further.
+    disconnect: bool,
+}
+
+impl MockConnection {
+    fn new(stream: TcpStream, state: Arc<MockState>) -> Self {
+        MockConnection {
+            stream,
+            state,
+            out: Vec::new(),
+            statements: HashMap::new(),
+            portals: HashMap::new(),
+            skip_until_sync: false,
+            disconnect: false,
+        }
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.startup().await?;
+
+        loop {
+            let tag = match self.stream.read_u8().await {
+                Ok(tag) => tag,
+                // Client disconnected.
+                Err(_) => return Ok(()),
+            };
+            let body = self.read_body().await?;
+            let mut body = MessageReader::new(&body);
+
+            match tag {
+                b'S' => {
+                    self.skip_until_sync = false;
+                    self.ready_for_query();
+                    self.flush().await?;
+                }
+                b'H' => self.flush().await?,
+                b'X' => return Ok(()),
+                b'Q' => {
+                    // Only session configuration is sent using the simple
+                    // query protocol, accept and ignore it.
+                    let sql = body.read_cstr()?;
+                    if sql.trim_start().to_ascii_lowercase().starts_with("set ") {
+                        self.command_complete("SET");
+                    } else {
+                        self.error(
+                            "Mock postgres only supports SET with the simple query protocol",
+                        );
+                    }
+                    self.ready_for_query();
+                    self.flush().await?;
+                }
+                _ if self.skip_until_sync => (),
+                _ => {
+                    if let Err(e) = self.handle_extended(tag, &mut body).await {
+                        self.error(&e.to_string());
+                        self.skip_until_sync = true;
+                    }
+                    if self.disconnect {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handle a message that's part of the extended query protocol.
+    async fn handle_extended(&mut self, tag: u8, body: &mut MessageReader<'_>) -> Result<()> {
+        match tag {
+            b'P' => {
+                let name = body.read_cstr()?;
+                let sql = body.read_cstr()?;
+                self.statements.insert(name, MockQuery::plan(&sql)?);
+                self.message(b'1', &[]);
+            }
+            b'B' => {
+                let portal = body.read_cstr()?;
+                let statement = body.read_cstr()?;
+                let query = self.statement(&statement)?.clone();
+
+                let num_formats = body.read_i16()?;
+                for _ in 0..num_formats {
+                    if body.read_i16()? != 1 {
+                        return Err(RayexecError::new(
+                            "Mock postgres only supports binary params",
+                        ));
+                    }
+                }
+                let num_params = body.read_i16()?;
+                let mut params = Vec::with_capacity(num_params as usize);
+                for _ in 0..num_params {
+                    params.push(body.read_value()?);
+                }
+                // Result format codes are ignored, results are always binary.
+
+                self.portals.insert(portal, Portal { query, params });
+                self.message(b'2', &[]);
+            }
+            b'D' => {
+                let kind = body.read_u8()?;
+                let name = body.read_cstr()?;
+                let query = match kind {
+                    b'S' => {
+                        let query = self.statement(&name)?.clone();
+                        let param_types = query.param_types();
+                        let mut msg = (param_types.len() as i16).to_be_bytes().to_vec();
+                        for typ in param_types {
+                            msg.extend_from_slice(&typ.to_be_bytes());
+                        }
+                        self.message(b't', &msg);
+                        query
+                    }
+                    _ => self.portal(&name)?.query.clone(),
+                };
+                self.row_description(&query);
+            }
+            b'E' => {
+                let name = body.read_cstr()?;
+                let portal = self.portal(&name)?;
+                let output = portal.query.execute(&self.state, &portal.params).await?;
+                match output {
+                    QueryOutput::Rows(rows) => {
+                        let num_rows = rows.len();
+                        for row in rows {
+                            let mut msg = (row.len() as i16).to_be_bytes().to_vec();
+                            for value in row {
+                                write_value(&mut msg, value.as_deref());
+                            }
+                            self.message(b'D', &msg);
+                        }
+                        self.command_complete(&format!("SELECT {num_rows}"));
+                    }
+                    QueryOutput::Copy { messages, num_rows } => {
+                        // Overall binary format, zero columns since the
+                        // per-column formats aren't needed by the client.
+                        let mut msg = vec![1];
+                        msg.extend_from_slice(&0_i16.to_be_bytes());
+                        self.message(b'H', &msg);
+
+                        let disconnect_after = self
+                            .state
+                            .disconnect_after
+                            .lock()
+                            .expect("disconnect lock not poisoned")
+                            .take();
+                        if let Some(n) = disconnect_after {
+                            // Send the first n rows, then drop the connection
+                            // mid-COPY.
+                            for data in messages.into_iter().take(n) {
+                                self.message(b'd', &data);
+                            }
+                            self.flush().await?;
+                            self.disconnect = true;
+                            return Ok(());
+                        }
+
+                        for data in messages {
+                            self.message(b'd', &data);
+                        }
+                        self.message(b'c', &[]);
+                        self.command_complete(&format!("COPY {num_rows}"));
+                    }
+                }
+            }
+            b'C' => {
+                let kind = body.read_u8()?;
+                let name = body.read_cstr()?;
+                match kind {
+                    b'S' => self.statements.remove(&name),
+                    _ => self.portals.remove(&name).map(|portal| portal.query),
+                };
+                self.message(b'3', &[]);
+            }
+            other => {
+                return Err(RayexecError::new(format!(
+                    "Unexpected message from client: {}",
+                    other as char
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn startup(&mut self) -> Result<()> {
+        loop {
+            let body = self.read_body().await?;
+            let code = MessageReader::new(&body).read_i32()?;
+            match code {
+                SSL_REQUEST_CODE | GSSENC_REQUEST_CODE => {
+                    // Encryption not supported.
+                    self.stream
+                        .write_all(b"N")
+                        .await
+                        .context("Failed to write to client")?;
+                }
+                PROTOCOL_VERSION => break,
+                other => {
+                    return Err(RayexecError::new(format!(
+                        "Unsupported protocol version: {other}"
+                    )))
+                }
+            }
+        }
+
+        // AuthenticationOk
+        self.message(b'R', &0_i32.to_be_bytes());
+        for (key, value) in [
+            ("server_version", "16.0"),
+            ("server_encoding", "UTF8"),
+            ("client_encoding", "UTF8"),
+            ("DateStyle", "ISO, MDY"),
+            ("integer_datetimes", "on"),
+            ("standard_conforming_strings", "on"),
+        ] {
+            let mut msg = Vec::new();
+            write_cstr(&mut msg, key);
+            write_cstr(&mut msg, value);
+            self.message(b'S', &msg);
+        }
+        // BackendKeyData, cancellation isn't supported.
+        self.message(b'K', &[0; 8]);
+        self.ready_for_query();
+        self.flush().await
+    }
+
+    fn statement(&self, name: &str) -> Result<&MockQuery> {
+        self.statements
+            .get(name)
+            .ok_or_else(|| RayexecError::new(format!("Missing prepared statement: '{name}'")))
+    }
+
+    fn portal(&self, name: &str) -> Result<&Portal> {
+        self.portals
+            .get(name)
+            .ok_or_else(|| RayexecError::new(format!("Missing portal: '{name}'")))
+    }
+
+    /// Read the length prefixed body of a message.
+    async fn read_body(&mut self) -> Result<Vec<u8>> {
+        let len = self
+            .stream
+            .read_i32()
+            .await
+            .context("Failed to read message length")?;
+        let mut body = vec![0; (len as usize).saturating_sub(4)];
+        self.stream
+            .read_exact(&mut body)
+            .await
+            .context("Failed to read message body")?;
+        Ok(body)
+    }
+
+    fn message(&mut self, tag: u8, body: &[u8]) {
+        self.out.push(tag);
+        self.out
+            .extend_from_slice(&(body.len() as i32 + 4).to_be_bytes());
+        self.out.extend_from_slice(body);
+    }
+
+    fn row_description(&mut self, query: &MockQuery) {
+        let columns = match query.columns() {
+            Some(columns) => columns,
+            None => {
+                self.message(b'n', &[]);
+                return;
+            }
+        };
+
+        let mut msg = (columns.len() as i16).to_be_bytes().to_vec();
+        for (name, typ) in columns {
+            write_cstr(&mut msg, name);
+            msg.extend_from_slice(&0_i32.to_be_bytes()); // Table OID
+            msg.extend_from_slice(&0_i16.to_be_bytes()); // Column attribute number
+            msg.extend_from_slice(&typ.to_be_bytes());
+            msg.extend_from_slice(&(-1_i16).to_be_bytes()); // Type size
+            msg.extend_from_slice(&(-1_i32).to_be_bytes()); // Type modifier
+            msg.extend_from_slice(&1_i16.to_be_bytes()); // Binary format
+        }
+        self.message(b'T', &msg);
+    }
+
+    fn command_complete(&mut self, tag: &str) {
+        let mut msg = Vec::new();
+        write_cstr(&mut msg, tag);
+        self.message(b'C', &msg);
+    }
+
+    fn ready_for_query(&mut self) {
+        self.message(b'Z', b"I");
+    }
+
+    fn error(&mut self, message: &str) {
+        let mut msg = Vec::new();
+        for (field, value) in [
+            (b'S', "ERROR"),
+            (b'V', "ERROR"),
+            (b'C', "XX000"),
+            (b'M', message),
+        ] {
+            msg.push(field);
+            write_cstr(&mut msg, value);
+        }
+        msg.push(0);
+        self.message(b'E', &msg);
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.stream
+            .write_all(&self.out)
+            .await
+            .context("Failed to write to client")?;
+        self.out.clear();
+        Ok(())
+    }
+}
+
+fn write_cstr(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+}
+
+fn write_value(buf: &mut Vec<u8>, value: Option<&[u8]>) {
+    match value {
+        Some(bytes) => {
+            buf.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+            buf.extend_from_slice(bytes);
+        }
+        None => buf.extend_from_slice(&(-1_i32).to_be_bytes()),
+    }
+}
+
+/// Reads fields from the body of a client message.
+#[derive(Debug)]
+struct MessageReader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> MessageReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        MessageReader { buf }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.buf.len() < n {
+            return Err(RayexecError::new("Unexpected end of message"));
+        }
+        let (taken, rest) = self.buf.split_at(n);
+        self.buf = rest;
+        Ok(taken)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_i16(&mut self) -> Result<i16> {
+        let b = self.take(2)?;
+        Ok(i16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn read_i32(&mut self) -> Result<i32> {
+        let b = self.take(4)?;
+        Ok(i32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn read_cstr(&mut self) -> Result<String> {
+        let len = self
+            .buf
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| RayexecError::new("Missing string terminator"))?;
+        let s = self.take(len)?;
+        self.take(1)?;
+        String::from_utf8(s.to_vec()).context("Invalid utf8 in message")
+    }
+
+    /// Read a length prefixed value, None for NULL.
+    fn read_value(&mut self) -> Result<Option<Vec<u8>>> {
+        let len = self.read_i32()?;
+        if len < 0 {
+            return Ok(None);
+        }
+        Ok(Some(self.take(len as usize)?.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_copy_query() {
+        let query =
+            MockQuery::plan("COPY (SELECT a, b FROM public.t1 LIMIT 2) TO STDOUT (FORMAT binary)")
+                .unwrap();
+        assert_eq!(
+            MockQuery::Copy {
+                query: "SELECT a, b FROM public.t1 LIMIT 2".to_string()
+            },
+            query
+        );
+    }
+
+    #[test]
+    fn parse_ctid_scan() {
+        assert_eq!(None, CtidScan::parse("SELECT a FROM public.t1").unwrap());
+
+        let scan = CtidScan::parse("SELECT a, ctid::text FROM public.t1 ORDER BY ctid")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            CtidScan {
+                query: "SELECT a FROM public.t1".to_string(),
+                after: 0,
+            },
+            scan
+        );
+
+        let scan = CtidScan::parse(
+            "SELECT a, ctid::text FROM public.t1 WHERE a IN (1, 2) AND ctid > '(0,2)' ORDER BY ctid",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            CtidScan {
+                query: "SELECT a FROM public.t1 WHERE a IN (1, 2)".to_string(),
+                after: 2,
+            },
+            scan
+        );
+    }
+
+    #[test]
+    fn plan_unsupported_query() {
+        MockQuery::plan("DELETE FROM t1").unwrap_err();
+    }
+}
@@ -100,6 +100,11 @@ impl ReplacementVars {
         self.vars.insert(key, val);
     }
 
+    /// Get a variable by its name (without the surrounding underscores).
+    pub fn get(&self, key: &str) -> Option<&VarValue> {
+        self.vars.get(&format!("__{}__", key.to_uppercase()))
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&String, &VarValue)> {
         self.vars.iter()
     }
@@ -0,0 +1,197 @@
+use std::str::FromStr;
+
+use geo::{Geometry, Point};
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::datatype::{DataType, DataTypeId};
+use rayexec_execution::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use rayexec_execution::arrays::executor::physical_type::{PhysicalF64, PhysicalUtf8};
+use rayexec_execution::arrays::executor::scalar::{BinaryExecutor, UnaryExecutor};
+use rayexec_execution::expr::Expression;
+use rayexec_execution::functions::documentation::{Category, Documentation, Example};
+use rayexec_execution::functions::scalar::{
+    PlannedScalarFunction,
+    ScalarFunction,
+    ScalarFunctionImpl,
+};
+use rayexec_execution::functions::{
+    invalid_input_types_error,
+    plan_check_num_args,
+    FunctionInfo,
+    Signature,
+};
+use rayexec_execution::logical::binder::table_list::TableList;
+use wkt::Wkt;
+
+use crate::wkb;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StPoint;
+
+impl FunctionInfo for StPoint {
+    fn name(&self) -> &'static str {
+        "st_point"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Float64, DataTypeId::Float64],
+            variadic_arg: None,
+            return_type: DataTypeId::Geometry,
+            doc: Some(&Documentation {
+                category: Category::Spatial,
+                description: "Create a point geometry from x and y coordinates.",
+                arguments: &["x", "y"],
+                example: Some(Example {
+                    example: "st_point(1, 2)",
+                    output: "0101000000000000000000F03F0000000000000040",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for StPoint {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 2)?;
+        match (
+            inputs[0].datatype(table_list)?,
+            inputs[1].datatype(table_list)?,
+        ) {
+            (DataType::Float64, DataType::Float64) => (),
+            (a, b) => return Err(invalid_input_types_error(self, &[a, b])),
+        }
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Geometry,
+            inputs,
+            function_impl: Box::new(StPointImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StPointImpl;
+
+impl ScalarFunctionImpl for StPointImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Geometry,
+            buffer: GermanVarlenBuffer::<[u8]>::with_len(inputs[0].logical_len()),
+        };
+
+        BinaryExecutor::execute::<PhysicalF64, PhysicalF64, _, _>(
+            inputs[0],
+            inputs[1],
+            builder,
+            |x, y, buf| buf.put(&wkb::encode(&Geometry::Point(Point::new(x, y)))),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StGeomFromText;
+
+impl FunctionInfo for StGeomFromText {
+    fn name(&self) -> &'static str {
+        "st_geomfromtext"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Utf8],
+            variadic_arg: None,
+            return_type: DataTypeId::Geometry,
+            doc: Some(&Documentation {
+                category: Category::Spatial,
+                description: "Create a geometry from its well-known text (WKT) representation.",
+                arguments: &["text"],
+                example: Some(Example {
+                    example: "st_geomfromtext('POINT(1 2)')",
+                    output: "0101000000000000000000F03F0000000000000040",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for StGeomFromText {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_num_args(self, &inputs, 1)?;
+        match inputs[0].datatype(table_list)? {
+            DataType::Utf8 => (),
+            other => return Err(invalid_input_types_error(self, &[other])),
+        }
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Geometry,
+            inputs,
+            function_impl: Box::new(StGeomFromTextImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StGeomFromTextImpl;
+
+impl ScalarFunctionImpl for StGeomFromTextImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Geometry,
+            buffer: GermanVarlenBuffer::<[u8]>::with_len(inputs[0].logical_len()),
+        };
+
+        let mut error: Option<RayexecError> = None;
+        let out = UnaryExecutor::execute::<PhysicalUtf8, _, _>(inputs[0], builder, |text, buf| {
+            match parse_wkt(text) {
+                Ok(geometry) => buf.put(&wkb::encode(&geometry)),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        })?;
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(out),
+        }
+    }
+}
+
+/// Parse a geometry from well-known text.
+pub fn parse_wkt(text: &str) -> Result<Geometry> {
+    let wkt = Wkt::<f64>::from_str(text)
+        .map_err(|e| RayexecError::new(format!("Invalid WKT '{text}': {e}")))?;
+    Geometry::try_from(wkt).map_err(|e| RayexecError::new(format!("Unsupported WKT '{text}': {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::{point, polygon};
+
+    use super::*;
+
+    #[test]
+    fn parse_wkt_geometries() {
+        assert_eq!(
+            Geometry::Point(point!(x: 1.0, y: 2.0)),
+            parse_wkt("POINT(1 2)").unwrap()
+        );
+        assert_eq!(
+            Geometry::Polygon(polygon![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 0.0, y: 1.0)]),
+            parse_wkt("POLYGON((0 0, 1 0, 0 1, 0 0))").unwrap()
+        );
+        parse_wkt("POINT(1)").unwrap_err();
+        parse_wkt("CIRCLE(1 2)").unwrap_err();
+    }
+}
@@ -0,0 +1,134 @@
+use geo::{Area, Distance, Euclidean};
+use rayexec_error::Result;
+use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::datatype::{DataType, DataTypeId};
+use rayexec_execution::arrays::executor::builder::{ArrayBuilder, PrimitiveBuffer};
+use rayexec_execution::expr::Expression;
+use rayexec_execution::functions::documentation::{Category, Documentation, Example};
+use rayexec_execution::functions::scalar::{
+    PlannedScalarFunction,
+    ScalarFunction,
+    ScalarFunctionImpl,
+};
+use rayexec_execution::functions::{FunctionInfo, Signature};
+use rayexec_execution::logical::binder::table_list::TableList;
+
+use super::{execute_binary, execute_unary, plan_check_geometry_args};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StArea;
+
+impl FunctionInfo for StArea {
+    fn name(&self) -> &'static str {
+        "st_area"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Geometry],
+            variadic_arg: None,
+            return_type: DataTypeId::Float64,
+            doc: Some(&Documentation {
+                category: Category::Spatial,
+                description: "Compute the area of a geometry. Points and lines have an area of 0.",
+                arguments: &["geometry"],
+                example: Some(Example {
+                    example: "st_area(st_geomfromtext('POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))'))",
+                    output: "4",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for StArea {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_geometry_args(self, table_list, &inputs, 1)?;
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Float64,
+            inputs,
+            function_impl: Box::new(StAreaImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StAreaImpl;
+
+impl ScalarFunctionImpl for StAreaImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Float64,
+            buffer: PrimitiveBuffer::<f64>::with_len(inputs[0].logical_len()),
+        };
+
+        execute_unary(inputs[0], builder, |geometry, buf| {
+            buf.put(&geometry.unsigned_area())
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StDistance;
+
+impl FunctionInfo for StDistance {
+    fn name(&self) -> &'static str {
+        "st_distance"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Geometry, DataTypeId::Geometry],
+            variadic_arg: None,
+            return_type: DataTypeId::Float64,
+            doc: Some(&Documentation {
+                category: Category::Spatial,
+                description: "Compute the minimum euclidean distance between two geometries.",
+                arguments: &["a", "b"],
+                example: Some(Example {
+                    example: "st_distance(st_point(0, 0), st_point(3, 4))",
+                    output: "5",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for StDistance {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_geometry_args(self, table_list, &inputs, 2)?;
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Float64,
+            inputs,
+            function_impl: Box::new(StDistanceImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StDistanceImpl;
+
+impl ScalarFunctionImpl for StDistanceImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Float64,
+            buffer: PrimitiveBuffer::<f64>::with_len(inputs[0].logical_len()),
+        };
+
+        execute_binary(inputs[0], inputs[1], builder, |a, b, buf| {
+            buf.put(&Euclidean::distance(a, b))
+        })
+    }
+}
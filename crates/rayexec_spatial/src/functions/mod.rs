@@ -0,0 +1,119 @@
+//! Spatial scalar functions.
+
+mod constructors;
+pub use constructors::*;
+
+mod output;
+pub use output::*;
+
+mod predicates;
+pub use predicates::*;
+
+mod measurements;
+use geo::Geometry;
+pub use measurements::*;
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::executor::builder::{ArrayBuilder, ArrayDataBuffer, OutputBuffer};
+use rayexec_execution::arrays::executor::physical_type::PhysicalBinary;
+use rayexec_execution::arrays::executor::scalar::{BinaryExecutor, UnaryExecutor};
+use rayexec_execution::expr::Expression;
+use rayexec_execution::functions::scalar::ScalarFunction;
+use rayexec_execution::functions::{invalid_input_types_error, plan_check_num_args, FunctionInfo};
+use rayexec_execution::logical::binder::table_list::TableList;
+
+use crate::wkb;
+
+/// Get all spatial functions.
+pub fn spatial_functions() -> Vec<Box<dyn ScalarFunction>> {
+    vec![
+        Box::new(StPoint),
+        Box::new(StGeomFromText),
+        Box::new(StAsText),
+        Box::new(StContains),
+        Box::new(StIntersects),
+        Box::new(StArea),
+        Box::new(StDistance),
+    ]
+}
+
+/// Check that the function was called with `n` geometry arguments.
+fn plan_check_geometry_args<F>(
+    func: &F,
+    table_list: &TableList,
+    inputs: &[Expression],
+    n: usize,
+) -> Result<()>
+where
+    F: FunctionInfo,
+{
+    plan_check_num_args(func, inputs, n)?;
+    let datatypes = inputs
+        .iter()
+        .map(|expr| expr.datatype(table_list))
+        .collect::<Result<Vec<_>>>()?;
+    if datatypes.iter().any(|dt| dt != &DataType::Geometry) {
+        return Err(invalid_input_types_error(func, &datatypes));
+    }
+    Ok(())
+}
+
+/// Decode every geometry in the input and apply `op` to it.
+///
+/// Errors if any geometry isn't valid WKB.
+fn execute_unary<B, Op>(input: &Array, builder: ArrayBuilder<B>, mut op: Op) -> Result<Array>
+where
+    B: ArrayDataBuffer,
+    Op: FnMut(&Geometry, &mut OutputBuffer<B>),
+{
+    let mut error: Option<RayexecError> = None;
+    let out =
+        UnaryExecutor::execute::<PhysicalBinary, _, _>(
+            input,
+            builder,
+            |v, buf| match wkb::decode(v) {
+                Ok(geometry) => op(&geometry, buf),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            },
+        )?;
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(out),
+    }
+}
+
+/// Decode every pair of geometries in the inputs and apply `op` to them.
+///
+/// Errors if any geometry isn't valid WKB.
+fn execute_binary<B, Op>(
+    left: &Array,
+    right: &Array,
+    builder: ArrayBuilder<B>,
+    mut op: Op,
+) -> Result<Array>
+where
+    B: ArrayDataBuffer,
+    Op: FnMut(&Geometry, &Geometry, &mut OutputBuffer<B>),
+{
+    let mut error: Option<RayexecError> = None;
+    let out = BinaryExecutor::execute::<PhysicalBinary, PhysicalBinary, _, _>(
+        left,
+        right,
+        builder,
+        |a, b, buf| match (wkb::decode(a), wkb::decode(b)) {
+            (Ok(a), Ok(b)) => op(&a, &b, buf),
+            (Err(e), _) | (_, Err(e)) => {
+                error.get_or_insert(e);
+            }
+        },
+    )?;
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(out),
+    }
+}
@@ -0,0 +1,75 @@
+use rayexec_error::Result;
+use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::datatype::{DataType, DataTypeId};
+use rayexec_execution::arrays::executor::builder::{ArrayBuilder, GermanVarlenBuffer};
+use rayexec_execution::expr::Expression;
+use rayexec_execution::functions::documentation::{Category, Documentation, Example};
+use rayexec_execution::functions::scalar::{
+    PlannedScalarFunction,
+    ScalarFunction,
+    ScalarFunctionImpl,
+};
+use rayexec_execution::functions::{FunctionInfo, Signature};
+use rayexec_execution::logical::binder::table_list::TableList;
+use wkt::ToWkt;
+
+use super::{execute_unary, plan_check_geometry_args};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StAsText;
+
+impl FunctionInfo for StAsText {
+    fn name(&self) -> &'static str {
+        "st_astext"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Geometry],
+            variadic_arg: None,
+            return_type: DataTypeId::Utf8,
+            doc: Some(&Documentation {
+                category: Category::Spatial,
+                description: "Get the well-known text (WKT) representation of a geometry.",
+                arguments: &["geometry"],
+                example: Some(Example {
+                    example: "st_astext(st_point(1, 2))",
+                    output: "POINT(1 2)",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for StAsText {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_geometry_args(self, table_list, &inputs, 1)?;
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Utf8,
+            inputs,
+            function_impl: Box::new(StAsTextImpl),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StAsTextImpl;
+
+impl ScalarFunctionImpl for StAsTextImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Utf8,
+            buffer: GermanVarlenBuffer::<str>::with_len(inputs[0].logical_len()),
+        };
+
+        execute_unary(inputs[0], builder, |geometry, buf| {
+            buf.put(&geometry.wkt_string())
+        })
+    }
+}
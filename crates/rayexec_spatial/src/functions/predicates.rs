@@ -0,0 +1,176 @@
+use geo::{Contains, Geometry, Intersects};
+use rayexec_error::Result;
+use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::datatype::{DataType, DataTypeId};
+use rayexec_execution::arrays::executor::builder::{ArrayBuilder, BooleanBuffer};
+use rayexec_execution::expr::Expression;
+use rayexec_execution::functions::documentation::{Category, Documentation, Example};
+use rayexec_execution::functions::scalar::{
+    JoinIndex,
+    PlannedScalarFunction,
+    ScalarFunction,
+    ScalarFunctionImpl,
+};
+use rayexec_execution::functions::{FunctionInfo, Signature};
+use rayexec_execution::logical::binder::table_list::TableList;
+
+use super::{execute_binary, plan_check_geometry_args};
+use crate::join_index::SpatialJoinIndex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StContains;
+
+impl FunctionInfo for StContains {
+    fn name(&self) -> &'static str {
+        "st_contains"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Geometry, DataTypeId::Geometry],
+            variadic_arg: None,
+            return_type: DataTypeId::Boolean,
+            doc: Some(&Documentation {
+                category: Category::Spatial,
+                description: "Check if no points of 'b' lie outside of 'a', and at least one \
+                              point of the interior of 'b' lies in the interior of 'a'.",
+                arguments: &["a", "b"],
+                example: Some(Example {
+                    example:
+                        "st_contains(st_geomfromtext('POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))'), st_point(1, 1))",
+                    output: "true",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for StContains {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_geometry_args(self, table_list, &inputs, 2)?;
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Boolean,
+            inputs,
+            function_impl: Box::new(SpatialPredicateImpl {
+                predicate: SpatialPredicate::Contains,
+            }),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StIntersects;
+
+impl FunctionInfo for StIntersects {
+    fn name(&self) -> &'static str {
+        "st_intersects"
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        &[Signature {
+            positional_args: &[DataTypeId::Geometry, DataTypeId::Geometry],
+            variadic_arg: None,
+            return_type: DataTypeId::Boolean,
+            doc: Some(&Documentation {
+                category: Category::Spatial,
+                description: "Check if two geometries share any point.",
+                arguments: &["a", "b"],
+                example: Some(Example {
+                    example:
+                        "st_intersects(st_geomfromtext('LINESTRING(0 0, 2 2)'), st_point(1, 1))",
+                    output: "true",
+                }),
+            }),
+        }]
+    }
+}
+
+impl ScalarFunction for StIntersects {
+    fn plan(
+        &self,
+        table_list: &TableList,
+        inputs: Vec<Expression>,
+    ) -> Result<PlannedScalarFunction> {
+        plan_check_geometry_args(self, table_list, &inputs, 2)?;
+
+        Ok(PlannedScalarFunction {
+            function: Box::new(*self),
+            return_type: DataType::Boolean,
+            inputs,
+            function_impl: Box::new(SpatialPredicateImpl {
+                predicate: SpatialPredicate::Intersects,
+            }),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialPredicate {
+    Contains,
+    Intersects,
+}
+
+impl SpatialPredicate {
+    pub fn eval(&self, a: &Geometry, b: &Geometry) -> bool {
+        match self {
+            Self::Contains => a.contains(b),
+            Self::Intersects => a.intersects(b),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpatialPredicateImpl {
+    pub predicate: SpatialPredicate,
+}
+
+impl ScalarFunctionImpl for SpatialPredicateImpl {
+    fn execute(&self, inputs: &[&Array]) -> Result<Array> {
+        let builder = ArrayBuilder {
+            datatype: DataType::Boolean,
+            buffer: BooleanBuffer::with_len(inputs[0].logical_len()),
+        };
+
+        execute_binary(inputs[0], inputs[1], builder, |a, b, buf| {
+            buf.put(&self.predicate.eval(a, b))
+        })
+    }
+
+    fn build_join_index(
+        &self,
+        _arg: usize,
+        build_arrays: &[&Array],
+    ) -> Result<Option<Box<dyn JoinIndex>>> {
+        // Both predicates can only be true if the bounding boxes of the
+        // geometries intersect, so which side is being built doesn't matter.
+        Ok(Some(Box::new(SpatialJoinIndex::try_new(build_arrays)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::parse_wkt;
+
+    #[test]
+    fn predicates() {
+        let square = parse_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))").unwrap();
+        let inside = parse_wkt("POINT(1 1)").unwrap();
+        let edge = parse_wkt("POINT(2 1)").unwrap();
+        let outside = parse_wkt("POINT(3 3)").unwrap();
+
+        assert!(SpatialPredicate::Contains.eval(&square, &inside));
+        assert!(!SpatialPredicate::Contains.eval(&square, &edge));
+        assert!(!SpatialPredicate::Contains.eval(&square, &outside));
+
+        assert!(SpatialPredicate::Intersects.eval(&square, &inside));
+        assert!(SpatialPredicate::Intersects.eval(&square, &edge));
+        assert!(!SpatialPredicate::Intersects.eval(&square, &outside));
+    }
+}
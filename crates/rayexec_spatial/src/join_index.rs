@@ -0,0 +1,156 @@
+use geo::{BoundingRect, Geometry, Rect};
+use rayexec_error::{RayexecError, Result};
+use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::executor::physical_type::PhysicalBinary;
+use rayexec_execution::arrays::executor::scalar::UnaryExecutor;
+use rayexec_execution::functions::scalar::{JoinCandidate, JoinIndex};
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+
+use crate::wkb;
+
+/// Bounding box of a build side geometry, along with the (batch, row) it came
+/// from.
+type Entry = GeomWithData<Rectangle<[f64; 2]>, (usize, usize)>;
+
+/// R-tree over the bounding boxes of the geometries on the build side of a
+/// spatial join.
+///
+/// Probing returns build side rows whose bounding box intersects the bounding
+/// box of the probe geometry. Null and empty geometries never match.
+#[derive(Debug)]
+pub struct SpatialJoinIndex {
+    tree: RTree<Entry>,
+}
+
+impl SpatialJoinIndex {
+    pub fn try_new(build_arrays: &[&Array]) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (batch_idx, array) in build_arrays.iter().enumerate() {
+            for_each_bounding_box(array, |row, rect| {
+                let rect = Rectangle::from_corners(rect.min().into(), rect.max().into());
+                entries.push(GeomWithData::new(rect, (batch_idx, row)));
+            })?;
+        }
+
+        Ok(SpatialJoinIndex {
+            tree: RTree::bulk_load(entries),
+        })
+    }
+}
+
+impl JoinIndex for SpatialJoinIndex {
+    fn probe(&self, probe: &Array) -> Result<Vec<JoinCandidate>> {
+        let mut candidates = Vec::new();
+        for_each_bounding_box(probe, |probe_row, rect| {
+            let envelope = AABB::from_corners(rect.min().into(), rect.max().into());
+            for entry in self.tree.locate_in_envelope_intersecting(&envelope) {
+                let (build_batch, build_row) = entry.data;
+                candidates.push(JoinCandidate {
+                    probe_row,
+                    build_batch,
+                    build_row,
+                });
+            }
+        })?;
+
+        Ok(candidates)
+    }
+}
+
+/// Call `f` with the bounding box of every non-null, non-empty geometry in the
+/// array.
+fn for_each_bounding_box<F>(array: &Array, mut f: F) -> Result<()>
+where
+    F: FnMut(usize, Rect),
+{
+    let mut error: Option<RayexecError> = None;
+    UnaryExecutor::for_each::<PhysicalBinary, _>(array, |row, v| {
+        let v = match v {
+            Some(v) => v,
+            None => return,
+        };
+        match wkb::decode(v) {
+            Ok(geometry) => {
+                if let Some(rect) = bounding_box(&geometry) {
+                    f(row, rect)
+                }
+            }
+            Err(e) => {
+                error.get_or_insert(e);
+            }
+        }
+    })?;
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+fn bounding_box(geometry: &Geometry) -> Option<Rect> {
+    let rect = geometry.bounding_rect()?;
+    let finite = [rect.min(), rect.max()]
+        .iter()
+        .all(|coord| coord.x.is_finite() && coord.y.is_finite());
+    finite.then_some(rect)
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::Point;
+    use rayexec_execution::arrays::datatype::DataType;
+    use rayexec_execution::arrays::storage::GermanVarlenStorage;
+
+    use super::*;
+    use crate::functions::parse_wkt;
+
+    fn geometry_array(wkts: &[&str]) -> Array {
+        let mut storage = GermanVarlenStorage::with_metadata_capacity(wkts.len());
+        for wkt in wkts {
+            storage
+                .try_push(&wkb::encode(&parse_wkt(wkt).unwrap()))
+                .unwrap();
+        }
+        Array::new_with_array_data(DataType::Geometry, storage)
+    }
+
+    #[test]
+    fn probe_finds_overlapping_boxes() {
+        let build1 = geometry_array(&[
+            "POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))",
+            "POLYGON((10 10, 12 10, 12 12, 10 12, 10 10))",
+        ]);
+        let build2 = geometry_array(&["LINESTRING(1 5, 3 5)"]);
+        let index = SpatialJoinIndex::try_new(&[&build1, &build2]).unwrap();
+
+        let probe = geometry_array(&["POINT(1 1)", "POINT(50 50)", "POINT(11 11)", "POINT(2 5)"]);
+        let mut candidates = index.probe(&probe).unwrap();
+        candidates.sort_by_key(|c| (c.probe_row, c.build_batch, c.build_row));
+
+        let expected = vec![
+            JoinCandidate {
+                probe_row: 0,
+                build_batch: 0,
+                build_row: 0,
+            },
+            JoinCandidate {
+                probe_row: 2,
+                build_batch: 0,
+                build_row: 1,
+            },
+            JoinCandidate {
+                probe_row: 3,
+                build_batch: 1,
+                build_row: 0,
+            },
+        ];
+        assert_eq!(expected, candidates);
+    }
+
+    #[test]
+    fn empty_geometries_have_no_bounding_box() {
+        assert!(bounding_box(&parse_wkt("GEOMETRYCOLLECTION EMPTY").unwrap()).is_none());
+        assert!(bounding_box(&Geometry::Point(Point::new(f64::NAN, f64::NAN))).is_none());
+    }
+}
@@ -0,0 +1,28 @@
+//! Geometry functions backed by the well-known binary (WKB) format.
+//!
+//! Registering [`SpatialDataSource`] makes the spatial functions available in
+//! the system catalog. Joins on `st_contains` or `st_intersects` use an R-tree
+//! over the build side of the join to avoid checking every pair of rows.
+
+pub mod functions;
+pub mod join_index;
+pub mod wkb;
+
+use rayexec_execution::datasource::{DataSource, DataSourceBuilder};
+use rayexec_execution::functions::scalar::ScalarFunction;
+use rayexec_execution::runtime::Runtime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpatialDataSource;
+
+impl<R: Runtime> DataSourceBuilder<R> for SpatialDataSource {
+    fn initialize(_runtime: R) -> Box<dyn DataSource> {
+        Box::new(SpatialDataSource)
+    }
+}
+
+impl DataSource for SpatialDataSource {
+    fn initialize_scalar_functions(&self) -> Vec<Box<dyn ScalarFunction>> {
+        functions::spatial_functions()
+    }
+}
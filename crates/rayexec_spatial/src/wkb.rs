@@ -0,0 +1,319 @@
+//! Encoding and decoding of geometries in the well-known binary (WKB) format.
+//!
+//! Only two dimensional geometries are supported. Geometries are always
+//! encoded as little endian, but both byte orders can be decoded.
+
+use geo::{
+    Coord,
+    Geometry,
+    GeometryCollection,
+    LineString,
+    MultiLineString,
+    MultiPoint,
+    MultiPolygon,
+    Point,
+    Polygon,
+};
+use rayexec_error::{RayexecError, Result};
+
+const POINT: u32 = 1;
+const LINE_STRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTI_POINT: u32 = 4;
+const MULTI_LINE_STRING: u32 = 5;
+const MULTI_POLYGON: u32 = 6;
+const GEOMETRY_COLLECTION: u32 = 7;
+
+/// Byte order marker for little endian.
+const LITTLE_ENDIAN: u8 = 1;
+/// Byte order marker for big endian.
+const BIG_ENDIAN: u8 = 0;
+
+/// Encode a geometry as WKB.
+pub fn encode(geometry: &Geometry) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_geometry(&mut buf, geometry);
+    buf
+}
+
+/// Decode a geometry from WKB.
+pub fn decode(buf: &[u8]) -> Result<Geometry> {
+    let mut reader = WkbReader { buf, little: true };
+    let geometry = reader.read_geometry()?;
+    if !reader.buf.is_empty() {
+        return Err(RayexecError::new(format!(
+            "Invalid WKB, {} trailing bytes after geometry",
+            reader.buf.len()
+        )));
+    }
+    Ok(geometry)
+}
+
+fn write_geometry(buf: &mut Vec<u8>, geometry: &Geometry) {
+    buf.push(LITTLE_ENDIAN);
+    match geometry {
+        Geometry::Point(point) => {
+            write_u32(buf, POINT);
+            write_coord(buf, point.0);
+        }
+        Geometry::Line(line) => {
+            write_u32(buf, LINE_STRING);
+            write_u32(buf, 2);
+            write_coord(buf, line.start);
+            write_coord(buf, line.end);
+        }
+        Geometry::LineString(line) => {
+            write_u32(buf, LINE_STRING);
+            write_line_string(buf, line);
+        }
+        Geometry::Polygon(polygon) => {
+            write_u32(buf, POLYGON);
+            write_polygon(buf, polygon);
+        }
+        Geometry::Rect(rect) => {
+            write_u32(buf, POLYGON);
+            write_polygon(buf, &rect.to_polygon());
+        }
+        Geometry::Triangle(triangle) => {
+            write_u32(buf, POLYGON);
+            write_polygon(buf, &triangle.to_polygon());
+        }
+        Geometry::MultiPoint(points) => {
+            write_u32(buf, MULTI_POINT);
+            write_u32(buf, points.0.len() as u32);
+            for point in &points.0 {
+                write_geometry(buf, &Geometry::Point(*point));
+            }
+        }
+        Geometry::MultiLineString(lines) => {
+            write_u32(buf, MULTI_LINE_STRING);
+            write_u32(buf, lines.0.len() as u32);
+            for line in &lines.0 {
+                buf.push(LITTLE_ENDIAN);
+                write_u32(buf, LINE_STRING);
+                write_line_string(buf, line);
+            }
+        }
+        Geometry::MultiPolygon(polygons) => {
+            write_u32(buf, MULTI_POLYGON);
+            write_u32(buf, polygons.0.len() as u32);
+            for polygon in &polygons.0 {
+                buf.push(LITTLE_ENDIAN);
+                write_u32(buf, POLYGON);
+                write_polygon(buf, polygon);
+            }
+        }
+        Geometry::GeometryCollection(collection) => {
+            write_u32(buf, GEOMETRY_COLLECTION);
+            write_u32(buf, collection.0.len() as u32);
+            for geometry in &collection.0 {
+                write_geometry(buf, geometry);
+            }
+        }
+    }
+}
+
+fn write_u32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn write_coord(buf: &mut Vec<u8>, coord: Coord) {
+    buf.extend_from_slice(&coord.x.to_le_bytes());
+    buf.extend_from_slice(&coord.y.to_le_bytes());
+}
+
+fn write_line_string(buf: &mut Vec<u8>, line: &LineString) {
+    write_u32(buf, line.0.len() as u32);
+    for coord in &line.0 {
+        write_coord(buf, *coord);
+    }
+}
+
+fn write_polygon(buf: &mut Vec<u8>, polygon: &Polygon) {
+    if polygon.exterior().0.is_empty() {
+        write_u32(buf, 0);
+        return;
+    }
+    write_u32(buf, 1 + polygon.interiors().len() as u32);
+    write_line_string(buf, polygon.exterior());
+    for interior in polygon.interiors() {
+        write_line_string(buf, interior);
+    }
+}
+
+#[derive(Debug)]
+struct WkbReader<'a> {
+    buf: &'a [u8],
+    /// Byte order of the geometry currently being read.
+    little: bool,
+}
+
+impl WkbReader<'_> {
+    fn read_geometry(&mut self) -> Result<Geometry> {
+        self.little = match self.read_bytes::<1>()?[0] {
+            LITTLE_ENDIAN => true,
+            BIG_ENDIAN => false,
+            other => {
+                return Err(RayexecError::new(format!(
+                    "Invalid WKB byte order: {other}"
+                )))
+            }
+        };
+
+        Ok(match self.read_u32()? {
+            POINT => Geometry::Point(Point(self.read_coord()?)),
+            LINE_STRING => Geometry::LineString(self.read_line_string()?),
+            POLYGON => Geometry::Polygon(self.read_polygon()?),
+            MULTI_POINT => {
+                let points = self.read_children(|geometry| match geometry {
+                    Geometry::Point(point) => Some(point),
+                    _ => None,
+                })?;
+                Geometry::MultiPoint(MultiPoint(points))
+            }
+            MULTI_LINE_STRING => {
+                let lines = self.read_children(|geometry| match geometry {
+                    Geometry::LineString(line) => Some(line),
+                    _ => None,
+                })?;
+                Geometry::MultiLineString(MultiLineString(lines))
+            }
+            MULTI_POLYGON => {
+                let polygons = self.read_children(|geometry| match geometry {
+                    Geometry::Polygon(polygon) => Some(polygon),
+                    _ => None,
+                })?;
+                Geometry::MultiPolygon(MultiPolygon(polygons))
+            }
+            GEOMETRY_COLLECTION => {
+                let geometries = self.read_children(Some)?;
+                Geometry::GeometryCollection(GeometryCollection(geometries))
+            }
+            other => {
+                return Err(RayexecError::new(format!(
+                    "Unsupported WKB geometry type: {other}"
+                )))
+            }
+        })
+    }
+
+    /// Read the child geometries of a multi geometry or collection, erroring if
+    /// `extract` returns None for a child.
+    fn read_children<T>(&mut self, extract: impl Fn(Geometry) -> Option<T>) -> Result<Vec<T>> {
+        let count = self.read_u32()? as usize;
+        let mut children = Vec::with_capacity(count.min(self.buf.len()));
+        for _ in 0..count {
+            let child = self.read_geometry()?;
+            let child = extract(child)
+                .ok_or_else(|| RayexecError::new("Invalid WKB, unexpected child geometry type"))?;
+            children.push(child);
+        }
+        Ok(children)
+    }
+
+    fn read_polygon(&mut self) -> Result<Polygon> {
+        let num_rings = self.read_u32()? as usize;
+        if num_rings == 0 {
+            return Ok(Polygon::new(LineString::new(Vec::new()), Vec::new()));
+        }
+        let exterior = self.read_line_string()?;
+        let interiors = (1..num_rings)
+            .map(|_| self.read_line_string())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Polygon::new(exterior, interiors))
+    }
+
+    fn read_line_string(&mut self) -> Result<LineString> {
+        let num_points = self.read_u32()? as usize;
+        // Each coordinate is 16 bytes, avoid allocating huge vecs for
+        // malformed input.
+        let mut coords = Vec::with_capacity(num_points.min(self.buf.len() / 16));
+        for _ in 0..num_points {
+            coords.push(self.read_coord()?);
+        }
+        Ok(LineString::new(coords))
+    }
+
+    fn read_coord(&mut self) -> Result<Coord> {
+        let x = self.read_f64()?;
+        let y = self.read_f64()?;
+        Ok(Coord { x, y })
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes::<4>()?;
+        Ok(if self.little {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn read_f64(&mut self) -> Result<f64> {
+        let bytes = self.read_bytes::<8>()?;
+        Ok(if self.little {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        if self.buf.len() < N {
+            return Err(RayexecError::new("Invalid WKB, unexpected end of input"));
+        }
+        let (bytes, rest) = self.buf.split_at(N);
+        self.buf = rest;
+        Ok(bytes.try_into().expect("slice to be the right length"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::{line_string, point, polygon};
+
+    use super::*;
+
+    #[test]
+    fn point_roundtrip() {
+        let geometry = Geometry::Point(point!(x: 1.0, y: 2.0));
+        let buf = encode(&geometry);
+        assert_eq!(21, buf.len());
+        assert_eq!(geometry, decode(&buf).unwrap());
+    }
+
+    #[test]
+    fn nested_roundtrip() {
+        let polygon =
+            polygon![(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 0.0)];
+        let geometry = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::MultiPolygon(MultiPolygon(vec![polygon.clone(), polygon])),
+            Geometry::LineString(line_string![(x: 1.0, y: 1.0), (x: 2.0, y: 3.0)]),
+            Geometry::MultiPoint(MultiPoint(vec![point!(x: 5.0, y: 6.0)])),
+        ]));
+        assert_eq!(geometry, decode(&encode(&geometry)).unwrap());
+    }
+
+    #[test]
+    fn decode_big_endian_point() {
+        let mut buf = vec![BIG_ENDIAN];
+        buf.extend_from_slice(&POINT.to_be_bytes());
+        buf.extend_from_slice(&3.0_f64.to_be_bytes());
+        buf.extend_from_slice(&4.0_f64.to_be_bytes());
+        assert_eq!(
+            Geometry::Point(point!(x: 3.0, y: 4.0)),
+            decode(&buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        decode(&[]).unwrap_err();
+        decode(&[LITTLE_ENDIAN, 1, 0, 0, 0, 0]).unwrap_err();
+        decode(&[LITTLE_ENDIAN, 99, 0, 0, 0]).unwrap_err();
+
+        let mut buf = encode(&Geometry::Point(point!(x: 1.0, y: 2.0)));
+        buf.push(0);
+        decode(&buf).unwrap_err();
+    }
+}
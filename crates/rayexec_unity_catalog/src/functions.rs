@@ -333,6 +333,7 @@ impl<R: Runtime, O: UnityObjectsOperation<R>> DataTable for UnityObjectsDataTabl
         &self,
         projections: Projections,
         num_partitions: usize,
+        _batch_size: usize,
     ) -> Result<Vec<Box<dyn DataTableScan>>> {
         let stream = O::create_stream_state(&self.state)?;
 
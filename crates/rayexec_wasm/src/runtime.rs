@@ -5,17 +5,21 @@ use futures::future::BoxFuture;
 use futures::stream::{self, BoxStream};
 use futures::StreamExt;
 use parking_lot::Mutex;
-use rayexec_error::{not_implemented, RayexecError, Result};
+use rayexec_error::{not_implemented, ErrorKind, RayexecError, Result};
 use rayexec_execution::execution::executable::pipeline::{
     ExecutablePartitionPipeline,
     ExecutablePipeline,
 };
 use rayexec_execution::execution::executable::profiler::ExecutionProfileData;
+use rayexec_execution::runtime::cancel::CancelFlag;
 use rayexec_execution::runtime::handle::QueryHandle;
+use rayexec_execution::runtime::resource_group::ResourceGroup;
 use rayexec_execution::runtime::{ErrorSink, PipelineExecutor, Runtime, TokioHandlerProvider};
+use rayexec_io::compression::{CompressedFileSink, CompressionType, DecompressingFileSource};
 use rayexec_io::http::HttpClientReader;
 use rayexec_io::location::{AccessConfig, FileLocation};
 use rayexec_io::memory::MemoryFileSystem;
+use rayexec_io::read_cache::CachedFileSource;
 use rayexec_io::s3::{S3Client, S3Location};
 use rayexec_io::{FileProvider, FileSink, FileSource};
 use tracing::debug;
@@ -85,26 +89,33 @@ impl PipelineExecutor for WasmExecutor {
         &self,
         pipelines: Vec<ExecutablePipeline>,
         errors: Arc<dyn ErrorSink>,
+        _group: Arc<ResourceGroup>,
     ) -> Box<dyn QueryHandle> {
+        // Resource groups are ignored, there's only ever a single session
+        // executing in the browser.
         debug!("spawning query graph on wasm runtime");
 
+        let canceled = CancelFlag::new();
         let states: Vec<_> = pipelines
             .into_iter()
             .flat_map(|pipeline| pipeline.into_partition_pipeline_iter())
             .map(|pipeline| WasmTaskState {
                 errors: errors.clone(),
+                canceled: canceled.clone(),
                 pipeline: Arc::new(Mutex::new(pipeline)),
             })
             .collect();
 
-        // TODO: Put references into query handle to allow canceling.
-
         for state in &states {
             let state = state.clone();
             spawn_local(async move { state.execute() })
         }
 
-        Box::new(WasmQueryHandle { states })
+        Box::new(WasmQueryHandle {
+            states,
+            canceled,
+            errors,
+        })
     }
 }
 
@@ -119,10 +130,16 @@ impl FileProvider for WasmFileProvider {
         location: FileLocation,
         config: &AccessConfig,
     ) -> Result<Box<dyn FileSource>> {
-        match (location, config) {
+        let compression = CompressionType::from_location(&location);
+
+        let source: Box<dyn FileSource> = match (location, config) {
             (FileLocation::Url(url), AccessConfig::None) => {
                 let client = WasmHttpClient::new(reqwest::Client::default());
-                Ok(Box::new(HttpClientReader::new(client, url)))
+                let file_key = url.to_string();
+                Box::new(CachedFileSource::new(
+                    HttpClientReader::new(client, url),
+                    file_key,
+                ))
             }
             (
                 FileLocation::Url(url),
@@ -136,11 +153,12 @@ impl FileProvider for WasmFileProvider {
                     credentials.clone(),
                 );
                 let location = S3Location::from_url(url, region)?;
-                let reader = client.file_source(location, region)?;
-                Ok(reader)
+                client.file_source(location, region)?
             }
-            (FileLocation::Path(path), _) => self.fs.file_source(&path),
-        }
+            (FileLocation::Path(path), _) => self.fs.file_source(&path)?,
+        };
+
+        Ok(Box::new(DecompressingFileSource::new(source, compression)))
     }
 
     fn file_sink(
@@ -148,9 +166,16 @@ impl FileProvider for WasmFileProvider {
         location: FileLocation,
         _config: &AccessConfig,
     ) -> Result<Box<dyn FileSink>> {
-        match location {
+        let compression = CompressionType::from_location(&location);
+
+        let sink = match location {
             FileLocation::Url(_url) => not_implemented!("http sink wasm"),
-            FileLocation::Path(path) => self.fs.file_sink(&path),
+            FileLocation::Path(path) => self.fs.file_sink(&path)?,
+        };
+
+        match compression {
+            Some(compression) => Ok(Box::new(CompressedFileSink::try_new(sink, compression)?)),
+            None => Ok(sink),
         }
     }
 
@@ -188,18 +213,30 @@ impl FileProvider for WasmFileProvider {
 #[derive(Debug, Clone)]
 struct WasmTaskState {
     errors: Arc<dyn ErrorSink>,
+    /// Cancel flag shared with all task states for the query.
+    canceled: CancelFlag,
     pipeline: Arc<Mutex<ExecutablePartitionPipeline>>,
 }
 
 impl WasmTaskState {
     fn execute(&self) {
+        // The error for the cancel is pushed by the query handle, pipelines
+        // just stop executing.
+        if self.canceled.is_canceled() {
+            return;
+        }
+
+        let mut pipeline = self.pipeline.lock();
+
         let state = self.clone();
         let waker: Waker = Arc::new(WasmWaker { state }).into();
         let mut cx = Context::from_waker(&waker);
 
-        let mut pipeline = self.pipeline.lock();
         loop {
-            match pipeline.poll_execute::<PerformanceInstant>(&mut cx) {
+            match self
+                .canceled
+                .enter(|| pipeline.poll_execute::<PerformanceInstant>(&mut cx))
+            {
                 Poll::Ready(Some(Ok(()))) => {
                     continue;
                 }
@@ -221,11 +258,23 @@ impl WasmTaskState {
 #[derive(Debug)]
 pub struct WasmQueryHandle {
     states: Vec<WasmTaskState>,
+    /// Cancel flag shared with all task states.
+    canceled: CancelFlag,
+    errors: Arc<dyn ErrorSink>,
 }
 
 impl QueryHandle for WasmQueryHandle {
     fn cancel(&self) {
-        // TODO
+        if self.canceled.is_canceled() {
+            return;
+        }
+
+        // Pipelines check the flag before executing, including when woken
+        // after this, so the error only needs to be recorded once for the
+        // query.
+        self.canceled.cancel();
+        self.errors
+            .push_error(RayexecError::new("Query canceled").with_kind(ErrorKind::Cancelled));
     }
 
     fn generate_execution_profile_data(&self) -> BoxFuture<'_, Result<ExecutionProfileData>> {
@@ -258,3 +307,42 @@ impl Wake for WasmWaker {
         spawn_local(async move { self.state.execute() })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct TestErrorSink {
+        errors: Mutex<Vec<RayexecError>>,
+    }
+
+    impl ErrorSink for TestErrorSink {
+        fn push_error(&self, error: RayexecError) {
+            self.errors.lock().push(error);
+        }
+    }
+
+    fn test_handle() -> (WasmQueryHandle, Arc<TestErrorSink>) {
+        let errors = Arc::new(TestErrorSink::default());
+        let handle = WasmQueryHandle {
+            states: Vec::new(),
+            canceled: CancelFlag::new(),
+            errors: errors.clone(),
+        };
+        (handle, errors)
+    }
+
+    #[test]
+    fn cancel_records_single_error() {
+        let (handle, errors) = test_handle();
+
+        handle.cancel();
+        handle.cancel();
+
+        let errors = errors.errors.lock();
+        assert_eq!(1, errors.len());
+        assert_eq!(ErrorKind::Cancelled, errors[0].kind());
+        assert!(handle.canceled.is_canceled());
+    }
+}
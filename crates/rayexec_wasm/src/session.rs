@@ -6,9 +6,12 @@ use rayexec_delta::DeltaDataSource;
 use rayexec_execution::arrays::format::{FormatOptions, Formatter};
 use rayexec_execution::datasource::{DataSourceBuilder, DataSourceRegistry, MemoryDataSource};
 use rayexec_iceberg::IcebergDataSource;
+use rayexec_lance::LanceDataSource;
+use rayexec_orc::OrcDataSource;
 use rayexec_parquet::ParquetDataSource;
 use rayexec_shell::result_table::{MaterializedColumn, MaterializedResultTable};
 use rayexec_shell::session::SingleUserEngine;
+use rayexec_spatial::SpatialDataSource;
 use rayexec_unity_catalog::UnityCatalogDataSource;
 use tracing::trace;
 use wasm_bindgen::prelude::*;
@@ -30,10 +33,13 @@ impl WasmSession {
         let registry = DataSourceRegistry::default()
             .with_datasource("memory", Box::new(MemoryDataSource))?
             .with_datasource("parquet", ParquetDataSource::initialize(runtime.clone()))?
+            .with_datasource("orc", OrcDataSource::initialize(runtime.clone()))?
+            .with_datasource("lance", LanceDataSource::initialize(runtime.clone()))?
             .with_datasource("csv", CsvDataSource::initialize(runtime.clone()))?
             .with_datasource("delta", DeltaDataSource::initialize(runtime.clone()))?
             .with_datasource("unity", UnityCatalogDataSource::initialize(runtime.clone()))?
-            .with_datasource("iceberg", IcebergDataSource::initialize(runtime.clone()))?;
+            .with_datasource("iceberg", IcebergDataSource::initialize(runtime.clone()))?
+            .with_datasource("spatial", SpatialDataSource::initialize(runtime.clone()))?;
 
         let engine = SingleUserEngine::try_new(WasmExecutor, runtime.clone(), registry)?;
 
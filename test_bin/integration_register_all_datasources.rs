@@ -1,12 +1,16 @@
+use rayexec_bigquery::BigQueryDataSource;
 use rayexec_csv::CsvDataSource;
 use rayexec_delta::DeltaDataSource;
 use rayexec_error::{RayexecError, Result};
 use rayexec_execution::datasource::{DataSourceBuilder, DataSourceRegistry, MemoryDataSource};
 use rayexec_execution::engine::Engine;
 use rayexec_execution::runtime::{Runtime, TokioHandlerProvider};
+use rayexec_lance::LanceDataSource;
+use rayexec_orc::OrcDataSource;
 use rayexec_parquet::ParquetDataSource;
 use rayexec_postgres::PostgresDataSource;
 use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
+use rayexec_spatial::SpatialDataSource;
 
 fn main() -> Result<()> {
     let sched = ThreadedNativeExecutor::try_new().unwrap();
@@ -19,9 +23,13 @@ fn main() -> Result<()> {
     let registry = DataSourceRegistry::default()
         .with_datasource("memory", Box::new(MemoryDataSource))?
         .with_datasource("postgres", PostgresDataSource::initialize(runtime.clone()))?
+        .with_datasource("bigquery", BigQueryDataSource::initialize(runtime.clone()))?
         .with_datasource("csv", CsvDataSource::initialize(runtime.clone()))?
         .with_datasource("delta", DeltaDataSource::initialize(runtime.clone()))?
-        .with_datasource("parquet", ParquetDataSource::initialize(runtime.clone()))?;
+        .with_datasource("parquet", ParquetDataSource::initialize(runtime.clone()))?
+        .with_datasource("orc", OrcDataSource::initialize(runtime.clone()))?
+        .with_datasource("lance", LanceDataSource::initialize(runtime.clone()))?
+        .with_datasource("spatial", SpatialDataSource::initialize(runtime.clone()))?;
 
     let engine = Engine::new_with_registry(sched, runtime.clone(), registry)?;
     let mut session = engine.new_session()?;
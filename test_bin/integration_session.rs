@@ -0,0 +1,590 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::TryStreamExt;
+use rayexec_debug::table_storage::TablePreload;
+use rayexec_debug::{DebugDataSource, DebugDataSourceOptions};
+use rayexec_execution::arrays::array::Array;
+use rayexec_execution::arrays::batch::Batch;
+use rayexec_execution::arrays::datatype::DataType;
+use rayexec_execution::arrays::field::Field;
+use rayexec_execution::datasource::{DataSourceBuilder, DataSourceRegistry};
+use rayexec_execution::engine::session::Session;
+use rayexec_execution::engine::Engine;
+use rayexec_execution::runtime::{Runtime, TokioHandlerProvider};
+use rayexec_parquet::ParquetDataSource;
+use rayexec_parser::parser;
+use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{Layer, Registry};
+
+type TestEngine = Engine<ThreadedNativeExecutor, NativeRuntime>;
+type TestSession = Session<ThreadedNativeExecutor, NativeRuntime>;
+
+fn new_engine() -> (TestEngine, tokio::runtime::Handle) {
+    let sched = ThreadedNativeExecutor::try_new().unwrap();
+    let runtime = NativeRuntime::with_default_tokio().unwrap();
+    let handle = runtime.tokio_handle().handle().unwrap();
+    (Engine::new(sched, runtime).unwrap(), handle)
+}
+
+/// Run a single query, returning the number of rows it produced.
+fn row_count(session: &mut TestSession, handle: &tokio::runtime::Handle, sql: &str) -> usize {
+    handle.block_on(async {
+        let mut results = session.simple(sql).await.unwrap();
+        assert_eq!(1, results.len());
+        let batches: Vec<_> = results.remove(0).stream.try_collect().await.unwrap();
+        batches.iter().map(|batch| batch.num_rows()).sum()
+    })
+}
+
+/// Run a single statement through prepare, bind, and execute.
+fn execute_one(
+    session: &mut TestSession,
+    handle: &tokio::runtime::Handle,
+    sql: &str,
+) -> Vec<Batch> {
+    handle.block_on(async {
+        let stmt = parser::parse(sql).unwrap().pop().unwrap();
+        session.prepare("", stmt).unwrap();
+        session.bind("", "").await.unwrap();
+        let result = session.execute("").await.unwrap();
+        result.stream.try_collect().await.unwrap()
+    })
+}
+
+/// Get the current value of a setting using SHOW.
+fn show(session: &mut TestSession, handle: &tokio::runtime::Handle, name: &str) -> String {
+    handle.block_on(async {
+        let mut results = session.simple(&format!("SHOW {name}")).await.unwrap();
+        let batches: Vec<_> = results.remove(0).stream.try_collect().await.unwrap();
+        batches[0]
+            .column(0)
+            .unwrap()
+            .logical_value(0)
+            .unwrap()
+            .to_string()
+    })
+}
+
+/// Name of a span along with the name of its parent.
+type SpanWithParent = (String, Option<String>);
+
+/// Collects the names of created spans along with the name of their parent.
+#[derive(Debug, Clone, Default)]
+struct SpanCollector {
+    spans: Arc<Mutex<Vec<SpanWithParent>>>,
+}
+
+impl SpanCollector {
+    fn parent_of(&self, name: &str) -> Vec<Option<String>> {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(span, _)| span == name)
+            .map(|(_, parent)| parent.clone())
+            .collect()
+    }
+}
+
+impl<S> Layer<S> for SpanCollector
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let span = ctx.span(id).unwrap();
+        let parent = span.parent().map(|parent| parent.name().to_string());
+        self.spans
+            .lock()
+            .unwrap()
+            .push((span.name().to_string(), parent));
+    }
+}
+
+#[test]
+fn query_spans_created_with_engine_subscriber() {
+    let collector = SpanCollector::default();
+    let (engine, handle) = new_engine();
+    let engine = engine.with_span_subscriber(Registry::default().with(collector.clone()));
+    let mut session = engine.new_session().unwrap();
+
+    handle
+        .block_on(session.simple("SELECT * FROM generate_series(1, 10)"))
+        .unwrap();
+
+    assert_eq!(vec![None], collector.parent_of("parse"));
+    assert_eq!(vec![None], collector.parent_of("query"));
+    for name in [
+        "resolve",
+        "bind",
+        "plan_logical",
+        "optimize",
+        "plan_intermediate",
+    ] {
+        assert_eq!(
+            vec![Some("query".to_string())],
+            collector.parent_of(name),
+            "span: {name}"
+        );
+    }
+
+    let pipelines = collector.parent_of("execute_pipeline");
+    assert!(!pipelines.is_empty());
+    assert!(pipelines
+        .iter()
+        .all(|parent| parent.as_deref() == Some("query")));
+}
+
+#[test]
+fn query_spans_not_shared_across_engines() {
+    let collector = SpanCollector::default();
+    let (engine, _) = new_engine();
+    let _engine = engine.with_span_subscriber(Registry::default().with(collector.clone()));
+
+    // Different engine without a subscriber configured.
+    let (engine, handle) = new_engine();
+    let mut session = engine.new_session().unwrap();
+    handle.block_on(session.simple("SELECT 1")).unwrap();
+
+    assert!(collector.spans.lock().unwrap().is_empty());
+}
+
+#[test]
+fn policies_follow_embedder_set_role() {
+    let (engine, handle) = new_engine();
+    let mut session = engine.new_session().unwrap();
+
+    handle
+        .block_on(session.simple(
+            "CREATE TEMP TABLE accounts (id int, owner text, balance int);
+             INSERT INTO accounts VALUES (1, 'alice', 100), (2, 'bob', 200), (3, 'carol', 300);
+             CREATE POLICY alice_rows ON accounts TO alice USING (owner = 'alice');
+             CREATE POLICY big_balances ON accounts TO auditor USING (balance >= 200);
+             CREATE POLICY everything ON accounts TO admin, auditor USING (true);
+             CREATE TEMP TABLE notes (id int, public boolean);
+             INSERT INTO notes VALUES (1, true), (2, false);
+             CREATE POLICY public_notes ON notes TO public USING (public);",
+        ))
+        .unwrap();
+
+    // No role, and no policies on accounts apply to everyone.
+    assert_eq!(
+        0,
+        row_count(&mut session, &handle, "SELECT * FROM accounts")
+    );
+
+    session.set_role(Some("alice"));
+    assert_eq!(
+        1,
+        row_count(&mut session, &handle, "SELECT * FROM accounts")
+    );
+    assert_eq!(
+        1,
+        row_count(
+            &mut session,
+            &handle,
+            "SELECT * FROM accounts AS a(x, y, z) WHERE z > 0"
+        )
+    );
+    assert_eq!(
+        0,
+        row_count(
+            &mut session,
+            &handle,
+            "SELECT * FROM (SELECT * FROM accounts) s WHERE s.owner = 'bob'"
+        )
+    );
+    assert_eq!(1, row_count(&mut session, &handle, "SELECT id FROM notes"));
+    assert_eq!(
+        1,
+        row_count(
+            &mut session,
+            &handle,
+            "SELECT n.id, a.owner FROM notes n JOIN accounts a ON n.id = a.id"
+        )
+    );
+
+    // Sessions with a role can't change policies.
+    let err = handle
+        .block_on(session.simple("CREATE POLICY mine ON accounts TO alice USING (true)"))
+        .unwrap_err();
+    assert!(err.to_string().contains("Cannot create policies"), "{err}");
+
+    // Multiple applicable policies are OR'ed together.
+    session.set_role(Some("auditor"));
+    assert_eq!(
+        3,
+        row_count(&mut session, &handle, "SELECT * FROM accounts")
+    );
+
+    session.set_role(Some("nobody"));
+    assert_eq!(
+        0,
+        row_count(&mut session, &handle, "SELECT * FROM accounts")
+    );
+
+    session.set_role(None);
+    assert_eq!(
+        0,
+        row_count(&mut session, &handle, "SELECT * FROM accounts")
+    );
+}
+
+#[test]
+fn set_local_reverted_after_standalone_execute() {
+    let (engine, handle) = new_engine();
+    let mut session = engine.new_session().unwrap();
+    let original = show(&mut session, &handle, "batch_size");
+
+    execute_one(&mut session, &handle, "SET LOCAL batch_size = 17");
+    assert_eq!(original, show(&mut session, &handle, "batch_size"));
+
+    // Statements in the same batch see the local setting.
+    session.begin_implicit_transaction().unwrap();
+    execute_one(&mut session, &handle, "SET LOCAL batch_size = 17");
+    let batches = execute_one(&mut session, &handle, "SHOW batch_size");
+    let value = batches[0].column(0).unwrap().logical_value(0).unwrap();
+    assert_eq!("17", value.to_string());
+    session.end_implicit_transaction().unwrap();
+
+    assert_eq!(original, show(&mut session, &handle, "batch_size"));
+}
+
+/// Get the lines of the physical plan output by EXPLAIN.
+fn explain_physical(
+    session: &mut TestSession,
+    handle: &tokio::runtime::Handle,
+    sql: &str,
+) -> Vec<String> {
+    for batch in execute_one(session, handle, &format!("EXPLAIN {sql}")) {
+        for row in 0..batch.num_rows() {
+            let label = batch.column(0).unwrap().logical_value(row).unwrap();
+            if label.to_string() == "physical" {
+                let plan = batch.column(1).unwrap().logical_value(row).unwrap();
+                return plan
+                    .to_string()
+                    .lines()
+                    .map(|line| line.trim().to_string())
+                    .collect();
+            }
+        }
+    }
+    panic!("missing physical plan")
+}
+
+#[test]
+fn partial_aggregate_before_repartition() {
+    let (engine, handle) = new_engine();
+    let mut session = engine.new_session().unwrap();
+    execute_one(&mut session, &handle, "SET partitions = 4");
+
+    // LIMIT runs with a single partition, aggregate before repartitioning its
+    // output.
+    let plan = explain_physical(
+        &mut session,
+        &handle,
+        "SELECT a % 3, count(*) FROM (SELECT * FROM generate_series(1, 100) g(a) LIMIT 50) GROUP BY 1",
+    );
+    let aggregates: Vec<_> = plan
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.as_str() == "HashAggregate")
+        .map(|(idx, _)| idx)
+        .collect();
+    assert_eq!(2, aggregates.len(), "plan: {plan:#?}");
+
+    let requirement = |start: usize| {
+        plan[start..]
+            .iter()
+            .find(|line| line.contains("partitioning_requirement"))
+            .unwrap()
+            .clone()
+    };
+    assert!(
+        requirement(aggregates[0]).ends_with("Some(1)"),
+        "plan: {plan:#?}"
+    );
+    assert!(
+        requirement(aggregates[1]).ends_with("None"),
+        "plan: {plan:#?}"
+    );
+
+    // Input already runs with the target partitions, aggregate once.
+    let plan = explain_physical(
+        &mut session,
+        &handle,
+        "SELECT a % 3, count(*) FROM generate_series(1, 100) g(a) GROUP BY 1",
+    );
+    let count = plan
+        .iter()
+        .filter(|line| line.as_str() == "HashAggregate")
+        .count();
+    assert_eq!(1, count, "plan: {plan:#?}");
+}
+
+#[test]
+fn parallel_merge_sort() {
+    let (engine, handle) = new_engine();
+    let mut session = engine.new_session().unwrap();
+    execute_one(&mut session, &handle, "SET partitions = 4");
+
+    let plan = explain_physical(
+        &mut session,
+        &handle,
+        "SELECT a FROM generate_series(1, 1000) g(a) ORDER BY a DESC",
+    );
+
+    // Sorting and merging happen in the same pipeline, running with every
+    // partition.
+    let merge = plan
+        .iter()
+        .position(|line| line.as_str() == "ParallelMerge")
+        .unwrap_or_else(|| panic!("plan: {plan:#?}"));
+    let pipeline = plan[..merge]
+        .iter()
+        .rposition(|line| line.starts_with("IntermediatePipeline "))
+        .unwrap();
+    assert!(
+        plan[pipeline..merge]
+            .iter()
+            .any(|line| line == "ScatterSort"),
+        "plan: {plan:#?}"
+    );
+    assert!(
+        plan[pipeline + 1].starts_with("├ Parallelism: Full {estimated: 4, actual: 4"),
+        "plan: {plan:#?}"
+    );
+    assert!(
+        !plan.iter().any(|line| line == "GatherSort"),
+        "plan: {plan:#?}"
+    );
+
+    let batches = execute_one(
+        &mut session,
+        &handle,
+        "SELECT a FROM generate_series(1, 1000) g(a) ORDER BY a DESC",
+    );
+    let values: Vec<_> = batches
+        .iter()
+        .flat_map(|batch| {
+            (0..batch.num_rows()).map(|row| {
+                batch
+                    .column(0)
+                    .unwrap()
+                    .logical_value(row)
+                    .unwrap()
+                    .to_string()
+            })
+        })
+        .collect();
+    let expected: Vec<_> = (1..=1000).rev().map(|v| v.to_string()).collect();
+    assert_eq!(expected, values);
+}
+
+#[test]
+fn execute_streaming_produces_batches_incrementally() {
+    let (engine, handle) = new_engine();
+    let mut session = engine.new_session().unwrap();
+    execute_one(&mut session, &handle, "SET batch_size = 1024");
+
+    handle.block_on(async {
+        let mut stream = session
+            .execute_streaming("SELECT * FROM generate_series(1, 100000)")
+            .await
+            .unwrap();
+        assert_eq!(1, stream.schema.fields.len());
+
+        // First batch is available without the rest of the query's output
+        // being buffered.
+        let first = stream.try_next().await.unwrap().unwrap();
+        assert!(first.num_rows() > 0);
+        assert!(first.num_rows() < 100000);
+
+        let mut count = first.num_rows();
+        let mut batches = 1;
+        while let Some(batch) = stream.try_next().await.unwrap() {
+            count += batch.num_rows();
+            batches += 1;
+        }
+        assert_eq!(100000, count);
+        assert!(batches > 1);
+    });
+}
+
+#[test]
+fn execute_streaming_drop_cancels_query() {
+    let (engine, handle) = new_engine();
+    let mut session = engine.new_session().unwrap();
+    execute_one(&mut session, &handle, "SET batch_size = 1024");
+    let memory = session.resource_group().memory().clone();
+
+    handle.block_on(async {
+        // Join build side is held in memory while probing.
+        let mut stream = session
+            .execute_streaming(
+                "SELECT * FROM generate_series(1, 100000) t1(a) \
+                 JOIN generate_series(1, 100000) t2(b) ON a = b",
+            )
+            .await
+            .unwrap();
+        let first = stream.try_next().await.unwrap().unwrap();
+        assert!(first.num_rows() > 0);
+        assert!(memory.reserved() > 0);
+
+        std::mem::drop(stream);
+    });
+
+    // Canceled pipelines are dropped, releasing their memory.
+    let start = std::time::Instant::now();
+    while memory.reserved() > 0 {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "memory not released: {}",
+            memory.reserved()
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    let queries = execute_one(
+        &mut session,
+        &handle,
+        "SELECT state FROM list_queries() WHERE query LIKE '%ON a = b'",
+    );
+    let states: Vec<_> = queries
+        .iter()
+        .flat_map(|batch| {
+            (0..batch.num_rows()).map(|row| {
+                batch
+                    .column(0)
+                    .unwrap()
+                    .logical_value(row)
+                    .unwrap()
+                    .to_string()
+            })
+        })
+        .collect();
+    assert_eq!(vec!["canceled".to_string()], states);
+}
+
+#[test]
+fn simple_streams_last_statement() {
+    let (engine, handle) = new_engine();
+    let mut session = engine.new_session().unwrap();
+
+    const SORT: &str = "SELECT * FROM generate_series(1, 1000) ORDER BY 1 DESC";
+
+    handle.block_on(async {
+        // Last statement isn't executed to completion before returning, its
+        // error is only seen when reading the stream.
+        let mut results = session
+            .simple(&format!("SET memory_limit = 1; {SORT}"))
+            .await
+            .unwrap();
+        assert_eq!(2, results.len());
+        let err = results
+            .pop()
+            .unwrap()
+            .stream
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Memory limit exceeded"),
+            "unexpected error: {err}"
+        );
+
+        // Earlier statements are buffered, failing the script.
+        let err = session
+            .simple(&format!("{SORT}; SELECT 1"))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Memory limit exceeded"),
+            "unexpected error: {err}"
+        );
+    });
+}
+
+#[test]
+fn scan_stall_timeout_setting_applies_to_remote_scans() {
+    let sched = ThreadedNativeExecutor::try_new().unwrap();
+    let runtime = NativeRuntime::with_default_tokio().unwrap();
+    let handle = runtime.tokio_handle().handle().unwrap();
+    let registry = DataSourceRegistry::default()
+        .with_datasource("parquet", ParquetDataSource::initialize(runtime.clone()))
+        .unwrap();
+    let engine = Engine::new_with_registry(sched, runtime, registry).unwrap();
+    let mut session = engine.new_session().unwrap();
+
+    // Accepts connections but never responds.
+    let listener = handle
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+    handle.spawn(async move {
+        let mut conns = Vec::new();
+        while let Ok((conn, _)) = listener.accept().await {
+            conns.push(conn);
+        }
+    });
+
+    let start = std::time::Instant::now();
+    let err = handle
+        .block_on(session.simple(&format!(
+            "SET scan_stall_timeout = 1; SELECT * FROM read_parquet('http://{addr}/file.parquet')"
+        )))
+        .unwrap_err();
+    assert_eq!(rayexec_error::ErrorKind::Timeout, err.kind(), "{err}");
+    assert!(start.elapsed() < std::time::Duration::from_secs(30));
+}
+
+#[test]
+fn result_cache_not_shared_across_attached_sources() {
+    // The result cache is process-wide, so sessions from different engines
+    // still share it.
+    fn debug_engine(value: i64) -> (TestEngine, tokio::runtime::Handle) {
+        let sched = ThreadedNativeExecutor::try_new().unwrap();
+        let runtime = NativeRuntime::with_default_tokio().unwrap();
+        let handle = runtime.tokio_handle().handle().unwrap();
+        let source = DebugDataSource::new(DebugDataSourceOptions {
+            preloads: vec![TablePreload {
+                schema: "schema1".to_string(),
+                name: "table1".to_string(),
+                columns: vec![Field::new("c1", DataType::Int64, false)],
+                data: Batch::try_new([Array::from_iter([value])]).unwrap(),
+            }],
+            expected_options: HashMap::new(),
+            discard_format: "discard".to_string(),
+        });
+        let registry = DataSourceRegistry::default()
+            .with_datasource("debug", Box::new(source))
+            .unwrap();
+        let engine = Engine::new_with_registry(sched, runtime, registry).unwrap();
+        (engine, handle)
+    }
+
+    // Both sources report the same version for their table, only the attached
+    // database differs.
+    for value in [1, 2, 1] {
+        let (engine, handle) = debug_engine(value);
+        let mut session = engine.new_session().unwrap();
+        let batches = handle.block_on(async {
+            let mut results = session
+                .simple(
+                    "SET enable_result_cache = true;
+                     ATTACH debug DATABASE AS db;
+                     SELECT c1 FROM db.schema1.table1",
+                )
+                .await
+                .unwrap();
+            let batches: Vec<_> = results.pop().unwrap().stream.try_collect().await.unwrap();
+            batches
+        });
+        let got = batches[0].column(0).unwrap().logical_value(0).unwrap();
+        assert_eq!(value.to_string(), got.to_string());
+    }
+}
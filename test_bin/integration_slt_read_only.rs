@@ -0,0 +1,42 @@
+use std::path::Path;
+use std::time::Duration;
+
+use rayexec_error::Result;
+use rayexec_execution::config::statements::AllowedStatements;
+use rayexec_execution::datasource::{DataSourceBuilder, DataSourceRegistry, MemoryDataSource};
+use rayexec_execution::engine::Engine;
+use rayexec_postgres::PostgresDataSource;
+use rayexec_rt_native::runtime::{NativeRuntime, ThreadedNativeExecutor};
+use rayexec_shell::session::SingleUserEngine;
+use rayexec_slt::{ReplacementVars, RunConfig};
+
+/// SLTs running in sessions that only allow read-only statements.
+pub fn main() -> Result<()> {
+    let rt = NativeRuntime::with_default_tokio()?;
+    let executor = ThreadedNativeExecutor::try_new()?;
+    let paths = rayexec_slt::find_files(Path::new("../slt/read_only")).unwrap();
+
+    rayexec_slt::run(
+        paths,
+        move || {
+            let executor = executor.clone();
+            let rt = rt.clone();
+
+            async move {
+                let registry = DataSourceRegistry::default()
+                    .with_datasource("memory", Box::new(MemoryDataSource))?
+                    .with_datasource("postgres", PostgresDataSource::initialize(rt.clone()))?;
+                let engine = Engine::new_with_registry(executor.clone(), rt.clone(), registry)?
+                    .with_allowed_statements(AllowedStatements::read_only());
+
+                Ok(RunConfig {
+                    engine: SingleUserEngine::try_from_engine(engine, rt.clone())?,
+                    vars: ReplacementVars::default(),
+                    create_slt_tmp: false,
+                    query_timeout: Duration::from_secs(5),
+                })
+            }
+        },
+        "slt_read_only",
+    )
+}